@@ -36,6 +36,57 @@ timelapse-saving = Saving video...
 mode-virtual = Virtual
 # Preview only mode with no capture controls. Same carousel length constraint.
 mode-view = View
+# Mode that scans sheets of paper into a multi-page PDF. Same carousel length
+# constraint.
+mode-document = Document
+# Page counter in the Document mode status pill.
+document-pages = { $count ->
+    [one] 1 page
+   *[other] { $count } pages
+}
+# Status badge shown while the scanned pages are written to a PDF. Replaces
+# the page counter, so keep it short.
+document-saving = Saving PDF...
+# Toggle button in the Document mode pill: capture each page automatically
+# once it has been put down and held still.
+document-auto-capture = Auto
+# Button in the Document mode pill that saves the scanned pages as one PDF.
+document-save-pdf = Save PDF
+# Button in the Document mode pill that reads the text on the scanned pages
+# and copies it to the clipboard.
+document-copy-text = Copy Text
+# Status badge shown while the text on the scanned pages is being read.
+# Replaces the page counter, so keep it short.
+document-reading-text = Reading text...
+# Mode that snapshots a whiteboard, straightened and cleaned up. Same
+# carousel length constraint.
+mode-whiteboard = Board
+# Snapshot counter in the Whiteboard mode status pill.
+whiteboard-snapshots = { $count ->
+    [one] 1 snapshot
+   *[other] { $count } snapshots
+}
+# Button in the Whiteboard mode pill that keeps the detected board corners
+# for this camera, so a mounted camera keeps its keystone correction.
+whiteboard-lock = Lock area
+# Same button once the corners are locked; goes back to detecting the board.
+whiteboard-unlock = Unlock area
+# Mode that keeps decoding barcodes and QR codes into a list, for inventory.
+# Same carousel length constraint.
+mode-scan = Scan
+# Code counter in the Scan mode status pill.
+scan-codes = { $count ->
+    [one] 1 code
+   *[other] { $count } codes
+}
+# Status badge shown while scanning is paused. Replaces the code counter, so
+# keep it short.
+scan-paused = Paused
+# Status badge shown while the list is written to a CSV file. Replaces the
+# code counter, so keep it short.
+scan-exporting = Saving CSV...
+# Button in the Scan mode pill that saves the scanned codes as a CSV file.
+scan-export-csv = Save CSV
 
 ## Virtual camera, a device other applications can read this camera from.
 
@@ -44,9 +95,39 @@ mode-view = View
 virtual-camera-title = Virtual camera (experimental)
 # Description under the virtual camera toggle.
 virtual-camera-description = Stream your camera feed to other applications via a virtual camera device. Requires PipeWire.
+# Toggle in the virtual camera settings: pulsing dot where the camera lens is.
+settings-look-here = Look here marker
+# Description under the "Look here marker" toggle.
+settings-look-here-description = Show a small pulsing dot near the camera in Virtual mode, as a reminder to look at the lens on calls
+# Dropdown in the virtual camera settings: where the current camera sits relative to the screen.
+settings-camera-location = Camera location
+# Description under the "Camera location" dropdown.
+settings-camera-location-description = Where this camera sits relative to the screen, remembered for each camera
+# Camera location option.
+camera-location-top-left = Top left
+# Camera location option.
+camera-location-top-center = Top center
+# Camera location option.
+camera-location-top-right = Top right
+# Camera location option.
+camera-location-left = Left side
+# Camera location option.
+camera-location-right = Right side
+# Camera location option.
+camera-location-bottom-left = Bottom left
+# Camera location option.
+camera-location-bottom-center = Bottom center
+# Camera location option.
+camera-location-bottom-right = Bottom right
 # Badge shown next to a green dot while the virtual camera is streaming.
 # Very short, it sits in a small pill in the top bar. Uppercase in English.
 streaming-live = LIVE
+# Top-bar pill shown while recording/streaming continues on the standby camera
+# because the main camera stalled or was unplugged. Keep it short.
+failover-active = Backup camera
+# Top-bar pill shown while exposure and focus are locked by long-pressing the
+# preview ("AE" = auto exposure, "AF" = autofocus). Keep it short.
+ae-af-lock = AE/AF lock
 # Name of the file type filter in the file chooser used to pick media to stream
 # through the virtual camera.
 virtual-camera-file-filter-name = Images and Videos
@@ -88,6 +169,25 @@ filter-solarize = Solarize
 filter-chroma = Chroma
 # Imitates a pencil sketch.
 filter-pencil = Pencil
+# Film emulation: warm, soft colour negative film for portraits, with fine
+# grain. Named after the film's speed (ISO 400), like a film box label.
+filter-film-portrait = Portrait 400
+# Film emulation: saturated, high-contrast colour slide film (ISO 50).
+filter-film-slide = Slide 50
+# Film emulation: grainy black-and-white film (ISO 400).
+filter-film-pan = Pan 400
+# Film emulation: cool, grainy film for tungsten lighting (ISO 800), where
+# bright lights glow red at the edges.
+filter-film-tungsten = Tungsten 800
+# Imported 3D LUT filter. The swatch shows the active LUT's file name instead
+# when one is loaded.
+filter-lut = Custom LUT
+# Heading of the imported LUT list below the filter grid.
+filters-luts-title = LUTs
+# Button opening a file chooser to import a .cube LUT file.
+filters-import-lut = Import LUT…
+# Name of the file type filter in the LUT import file chooser.
+filters-lut-file-filter-name = 3D LUT files
 
 ## Settings.
 
@@ -119,6 +219,142 @@ overlay-effect-off = Off
 settings-default-mode = Default mode
 # Description under the default mode dropdown.
 settings-default-mode-description = Camera mode to use when the app launches
+# Toggle in the camera settings: restore exposure/color controls per camera.
+settings-persist-controls = Remember camera controls
+# Description under the "Remember camera controls" toggle.
+settings-persist-controls-description = Restore exposure and color adjustments when the camera reconnects or another app changes them
+# Toggle in the camera settings: meter auto exposure on a detected face.
+settings-face-exposure = Expose for faces
+# Description under the "Expose for faces" toggle.
+settings-face-exposure-description = Meter auto exposure on the face in view, so faces in front of a window or a bright sky aren't left in shadow
+# Toggle in the camera settings: show capture suggestions for the scene in view.
+settings-scene-suggestions = Scene suggestions
+# Description under the "Scene suggestions" toggle.
+settings-scene-suggestions-description = In Photo mode, offer a setting that suits what the camera sees, such as HDR+ in dim light or Document mode for a page
+# Suggestion chip above the shutter when the scene is dark. Tapping turns on HDR+.
+scene-low-light = Low light — try HDR+
+# Suggestion chip above the shutter when a sheet of paper is in view. Tapping switches to Document mode.
+scene-document = Page in view — try Document mode
+# Suggestion chip above the shutter when a QR code is in view. Tapping turns on QR scanning.
+scene-qr = QR code in view — turn on scanning
+# Suggestion chip above the shutter when the picture is blurry, as with a close-up. Tapping turns on autofocus.
+scene-macro = Out of focus — turn on autofocus
+# Suggestion chip above the shutter when a face fills the frame. Tapping turns on "Expose for faces".
+scene-portrait = Face in view — expose for faces
+# Toggle in the camera settings: show live exposure and focus values over the preview.
+settings-exposure-osd = Exposure display
+# Description under the "Exposure display" toggle.
+settings-exposure-osd-description = Show the exposure time, gain, white balance and focus the camera is using, including in automatic modes
+# Settings toggle for the false color exposure map on the preview.
+settings-false-color = False color
+# Description under the "False color" toggle.
+settings-false-color-description = Show the preview as an exposure map: clipped highlights in red, crushed shadows in purple, middle grey in green and skin tones in pink
+# Settings dropdown adapting the preview colors to color blindness.
+settings-color-vision = Color vision
+# Description under the "Color vision" dropdown.
+settings-color-vision-description = Boost the color differences a color-blind eye misses, or show how the scene looks with color blindness. Only the preview changes, not what is captured
+# Color vision option: preview colors unchanged.
+color-vision-off = Off
+# Color vision option: daltonize for red-blindness.
+color-vision-correct-protanopia = Enhance for protanopia
+# Color vision option: daltonize for green-blindness.
+color-vision-correct-deuteranopia = Enhance for deuteranopia
+# Color vision option: daltonize for blue-blindness.
+color-vision-correct-tritanopia = Enhance for tritanopia
+# Color vision option: show the preview as seen with red-blindness.
+color-vision-simulate-protanopia = Simulate protanopia
+# Color vision option: show the preview as seen with green-blindness.
+color-vision-simulate-deuteranopia = Simulate deuteranopia
+# Color vision option: show the preview as seen with blue-blindness.
+color-vision-simulate-tritanopia = Simulate tritanopia
+# Settings label for the filter the preview is scaled to the window with.
+settings-preview-scaling = Preview scaling
+# Description under the "Preview scaling" dropdown.
+settings-preview-scaling-description = How the camera image is resized to fit the window. Lanczos stops fine detail shimmering when the camera resolution is much higher than the window, at some GPU cost
+# Preview scaling option: blocky, every camera pixel kept as a square.
+preview-scaling-nearest = Nearest neighbor
+# Preview scaling option: smooth and cheap.
+preview-scaling-bilinear = Bilinear
+# Preview scaling option: sharp, high quality resampling. Keep the name as is.
+preview-scaling-lanczos = Lanczos
+# Settings label for running the preview at a lower resolution when it can.
+settings-adaptive-preview = Adaptive preview resolution
+# Description under the "Adaptive preview resolution" toggle.
+settings-adaptive-preview-description = Lowers the preview resolution while the window is small or frames are being dropped, to save CPU and GPU. Photos are still taken at full resolution
+# Settings toggle for low-latency handling of HDMI capture cards.
+settings-capture-card-mode = Capture card mode
+# Description under the "Capture card mode" toggle.
+settings-capture-card-mode-description = Run HDMI capture cards with minimal buffering, uncompressed frames where available and no preview filter
+# Settings label for how 10-bit camera and capture card signals are interpreted.
+settings-hdr-input = HDR input
+# Description under the "HDR input" dropdown.
+settings-hdr-input-description = How 10-bit signals are shown. HDR is tone-mapped for the preview and photos and kept in recordings when the encoder supports it
+# HDR input option: follow what the driver reports.
+hdr-input-auto = Automatic
+# HDR input option: treat the signal as standard dynamic range.
+hdr-input-sdr = SDR
+# HDR input option: HDR10 (PQ transfer). Keep "PQ" as is.
+hdr-input-pq = HDR10 (PQ)
+# HDR input option: hybrid log-gamma HDR. Keep "HLG" as is.
+hdr-input-hlg = HLG
+# Settings item for measuring the latency of the active capture card.
+settings-latency = Glass-to-glass latency
+# Description under "Glass-to-glass latency" before a measurement.
+settings-latency-description = Point the camera at this screen or feed the capture card from it, then measure. The window flashes black and white for a few seconds.
+# Shown under "Glass-to-glass latency" when the preview never saw the window flash.
+settings-latency-no-flash = The flash was not seen. Check that the camera sees this screen.
+# Button that starts a latency measurement.
+settings-latency-measure = Measure
+# Indicator above the preview while a capture card runs in capture card mode.
+capture-card = Capture card
+# Indicator above the preview with the measured latency of the capture card.
+capture-card-latency = Capture card · { $ms } ms
+# Chip above the shutter for a few seconds after a capture; opens the share targets.
+share = Share
+# Share target showing the desktop's application chooser.
+share-open-with = Open with…
+# Share target starting an email with the capture attached.
+share-email = Email
+# Dropdown in the camera settings: camera that takes over if the active one fails.
+settings-standby-camera = Standby camera
+# Description under the "Standby camera" dropdown.
+settings-standby-camera-description = Switch to this camera if the active camera stalls or is unplugged while recording or streaming
+# Standby camera dropdown option that disables failover.
+settings-standby-camera-none = None
+# Dropdown in the camera settings: second camera combined with the active one.
+settings-pip-camera = Second camera
+# Description under the "Second camera" dropdown.
+settings-pip-camera-description = Combine a second camera with the active one in the preview, photos, recordings and the virtual camera
+# Second camera dropdown option that turns the second camera off.
+settings-pip-camera-off = Off
+# Dropdown in the camera settings: how the second camera is combined with the active one.
+settings-dual-camera-layout = Dual camera layout
+# Description under the "Dual camera layout" dropdown.
+settings-dual-camera-layout-description = Side by side gives each camera half the frame, for lens comparisons and stereo rigs
+# Dual camera layout option: second camera as a small inset in a corner.
+dual-camera-layout-pip = Picture-in-picture
+# Dual camera layout option: each camera fills one half of the frame.
+dual-camera-layout-side-by-side = Side by side
+# Dropdown in the camera settings: capture resolution of the second camera.
+settings-secondary-resolution = Second camera resolution
+# Description under the "Second camera resolution" dropdown.
+settings-secondary-resolution-description = Set independently of the active camera's format
+# Second camera resolution option; 640 pixels wide.
+secondary-resolution-low = 640 px (inset)
+# Second camera resolution option; 1280 pixels wide.
+secondary-resolution-hd = 1280 px
+# Second camera resolution option; 1920 pixels wide.
+secondary-resolution-full-hd = 1920 px
+# Dropdown in the camera settings: corner the picture-in-picture inset sits in.
+settings-pip-corner = Inset corner
+# Picture-in-picture corner option.
+pip-corner-top-left = Top left
+# Picture-in-picture corner option.
+pip-corner-top-right = Top right
+# Picture-in-picture corner option.
+pip-corner-bottom-left = Bottom left
+# Picture-in-picture corner option.
+pip-corner-bottom-right = Bottom right
 # Settings row, page title and section title for video recording options.
 settings-video = Video
 # Label of the camera selection row. This row also holds an info button and the
@@ -128,6 +364,71 @@ settings-device = Device
 settings-microphone = Microphone
 # Toggle that records audio alongside video.
 settings-record-audio = Record audio
+# Toggle that records desktop sound (whatever the speakers play) as a second
+# audio track alongside the microphone.
+settings-record-system-audio = Record system audio
+# Description under the system-audio toggle.
+settings-record-system-audio-description = Desktop sound as a separate track
+# Toggle that saves a subtitle (.srt) file next to each recording, listing
+# detected events with their time in the video.
+settings-event-subtitles = Event subtitles
+# Description under the event subtitles toggle. $key is the marker shortcut.
+settings-event-subtitles-description = Log QR codes seen and markers added with { $key } as subtitles
+# Toggle in the video settings: save each frame's time of day next to recordings.
+settings-recording-timecode = Timecode
+# Description under the timecode toggle.
+settings-recording-timecode-description = Save the time each frame was captured, with its timecode, in a file next to the video
+# Toggle in the video settings: burn the date and time into recordings.
+settings-timestamp-overlay = Date and time stamp
+# Description under the date and time stamp toggle.
+settings-timestamp-overlay-description = Show the date and time in the corner of recordings, like a dashcam
+# Toggle that makes Record capture a screen or window, picked in the
+# desktop's screen sharing dialog, with the camera inset in a corner.
+settings-record-screen = Record screen
+# Description under the record screen toggle.
+settings-record-screen-description = Record a screen or window with the camera in the picture-in-picture corner
+# Subtitle shown in a recording when a QR code comes into view. $content is
+# the decoded text of the code.
+recording-event-qr = QR code: { $content }
+# Subtitle shown in a recording at a marker the user added. $number counts
+# the markers from 1.
+recording-event-marker = Marker { $number }
+# Dropdown label for video stabilization strength.
+settings-stabilization = Stabilization
+# Description under the stabilization dropdown when it is off.
+settings-stabilization-off-description = Steadies handheld recordings by cropping into the frame
+# Description under the stabilization dropdown. $percent is the width and
+# height of the frame that is kept, e.g. 84. The crop is also outlined on
+# the preview in Video mode.
+settings-stabilization-description = Records the middle { $percent }% of the frame, outlined on the preview
+# Stabilization dropdown options.
+stabilization-off = Off
+stabilization-low = Low
+stabilization-medium = Medium
+stabilization-high = High
+# Dropdown label for low-light noise reduction of recordings and the virtual camera.
+settings-denoise = Low-light denoise
+# Description under the denoise dropdown.
+settings-denoise-description = Smooths sensor grain in recordings and the virtual camera. Stronger settings soften fine detail.
+# Toggle under the denoise dropdown: only denoise when the picture is noisy.
+settings-denoise-auto = Only in low light
+# Description under the "Only in low light" toggle.
+settings-denoise-auto-description = Measure the noise and denoise only while the picture is grainy
+# Denoise dropdown options.
+denoise-off = Off
+denoise-low = Low
+denoise-medium = Medium
+denoise-high = High
+# Dropdown label for the anamorphic desqueeze factor in the video settings.
+settings-desqueeze = Anamorphic desqueeze
+# Description under the anamorphic desqueeze dropdown.
+settings-desqueeze-description = Stretch the preview to undo the squeeze of an anamorphic lens adapter
+# First option of the anamorphic desqueeze dropdown; the others are factors like 1.5×.
+desqueeze-off = Off
+# Toggle under the desqueeze dropdown, shown when a factor is selected.
+settings-desqueeze-recordings = Desqueeze recordings
+# Description under the "Desqueeze recordings" toggle.
+settings-desqueeze-recordings-description = Record the stretched picture instead of leaving it squeezed for your editor
 # Dropdown label for the audio codec used in recordings.
 settings-audio-encoder = Audio encoder
 # Label of the live microphone meter row. The row also holds the meter and a
@@ -135,10 +436,72 @@ settings-audio-encoder = Audio encoder
 settings-mic-level = Microphone level
 # Shown in place of the microphone meter until the first audio level arrives.
 settings-mic-level-initializing = Initializing…
+# Label of the live desktop-audio meter row. Keep it as short as the
+# microphone level label.
+settings-system-audio-level = System audio level
+# Slider label for the extra microphone gain applied to recordings.
+settings-mic-gain = Microphone gain
+# Readout next to the gain slider. $gain is a signed whole number of decibels, e.g. "+3" or "-6".
+settings-mic-gain-value = { $gain } dB
+# Toggle that filters background noise (fans, keyboard) out of recorded audio.
+settings-noise-suppression = Noise suppression
+# Description under the noise-suppression toggle when no suppressor plugin is installed.
+settings-noise-suppression-unavailable = Needs the RNNoise or WebRTC audio plugin
+# Slider label for the per-microphone audio delay that corrects A/V drift.
+settings-audio-sync = Audio sync offset
+# Description under the audio sync slider.
+settings-audio-sync-description = Delay or advance this microphone against the video
+# Value next to the audio sync slider. $offset is a signed number of
+# milliseconds, e.g. "+120" or "-40".
+settings-audio-sync-value = { $offset } ms
+# Settings item for the test that measures the audio sync offset.
+settings-av-sync-test = Sync test
+# Description under "Sync test" before it has run.
+settings-av-sync-test-description = Point the camera at this screen with the speakers on. The window flashes while a beep plays, and a short test recording measures the offset.
+# Description under "Sync test" while it runs.
+settings-av-sync-test-running = Measuring…
+# Description under "Sync test" with its result. $offset is a signed number
+# of milliseconds, e.g. "+120" or "-40".
+settings-av-sync-test-result = Measured correction: { $offset } ms
+# Button that adds the measured correction to the audio sync offset.
+settings-av-sync-test-apply = Apply
+# Button that runs the sync test.
+settings-av-sync-test-start = Run test
 # Dropdown label for the video codec used in recordings.
 settings-encoder = Encoder
+# Dropdown label for the video container (file format) used for recordings.
+settings-container = Container
+# Container dropdown option that uses the encoder's preferred container.
+settings-container-auto = Automatic
+# Description under the container dropdown when the chosen container can't hold the encoder's output. { $encoder } is the encoder name, { $container } e.g. "MP4".
+settings-container-incompatible = { $encoder } can't be stored in this container; { $container } will be used instead
+# Description under the container dropdown with the "Edit-friendly" quality preset. { $container } is e.g. "MKV".
+settings-container-edit-friendly = Edit-friendly recordings are saved as { $container } to keep uncompressed audio
 # Dropdown label for the recording bitrate preset.
 settings-quality = Quality
+# Description under the quality dropdown. { $size } is e.g. "61.2 MB".
+settings-quality-estimate = About { $size } per minute at the current resolution
+# Recording time that fits in the free space, e.g. "3 h 20 min".
+settings-quality-duration = { $hours ->
+    [0] { $minutes } min
+   *[other] { $hours } h { $minutes } min
+}
+# Shown below the size estimate. { $time } is a settings-quality-duration value.
+settings-quality-capacity = { $time } of recording fits on disk
+# Warning shown instead when little recording time is left on disk.
+settings-quality-low-space = Low disk space: recording will fill the disk after { $time }
+# Chip above the shutter when the disk captures are saved to is nearly full. $free is a size such as "640 MB".
+storage-low = Low storage: { $free } left
+# Chip above the shutter when the disk is too full to save anything new.
+storage-full = Storage full: { $free } left, captures paused
+# Notice above the shutter after a photo was put on the clipboard.
+photo-copied = Photo copied to clipboard
+# Section in the video settings about the disk captures are saved to.
+settings-storage = Storage
+# Dropdown for the free space below which the app warns.
+settings-low-space-warning = Low storage warning
+# Description under the "Low storage warning" dropdown.
+settings-low-space-warning-description = Warn when less space than this is left. Captures stop before the disk is completely full.
 # Toggle that flips the preview horizontally, like a mirror.
 settings-mirror-preview = Mirror preview
 # Description under the mirror preview toggle.
@@ -148,6 +511,31 @@ settings-mirror-preview-description = Flip the camera preview horizontally
 settings-mirror-captures = Mirror captures
 # Description under the mirror captures toggle.
 settings-mirror-captures-description = Apply the same horizontal flip to saved photos, videos, and timelapse output
+# Settings section with the current camera's rotation and flips, applied to
+# the preview, photos, recordings and the virtual camera.
+settings-camera-orientation = Orientation
+settings-camera-rotation = Rotation
+settings-camera-rotation-description = Turn this camera's picture clockwise, on top of the rotation it reports
+settings-camera-mirror = Flip horizontally
+settings-camera-mirror-description = For a camera filming through a mirror or a teleprompter
+settings-camera-flip = Flip vertically
+# Global: photos and videos taken with a phone or convertible on its side are
+# normally saved as the preview showed them, with a tag telling viewers how
+# to turn them upright
+settings-bake-rotation = Rotate saved pixels
+settings-bake-rotation-description = Turn photos and videos upright in the file itself instead of tagging their orientation, for apps that ignore the tag
+# Settings section for the app's sounds.
+settings-sounds = Sounds
+# Settings toggle that silences every sound the app makes.
+settings-silent-mode = Silent mode
+# Description under the "Silent mode" toggle.
+settings-silent-mode-description = Play no sounds, including capture sounds and the scan beep
+# Settings toggle for the shutter, countdown and recording sounds.
+settings-capture-sounds = Capture sounds
+# Description under the "Capture sounds" toggle.
+settings-capture-sounds-description = Shutter click, self-timer beeps and chimes when a recording starts and stops
+# Slider label for the volume of the capture sounds.
+settings-capture-sound-volume = Volume
 # Toggle for vibration feedback. Only shown on devices that support it.
 settings-haptic-feedback = Haptic feedback
 # Description under the haptic feedback toggle.
@@ -163,6 +551,84 @@ settings-report-bug = Report bug
 # above, so keep both short.
 settings-show-report = Show Report
 
+## Automation settings page. Rules are run by the headless daemon (`camera daemon`).
+
+# Settings row and page title for automation rules.
+settings-automation = Automation
+# Section title for the list of rules.
+settings-automation-rules = Rules
+# Explanation at the top of the automation page.
+settings-automation-description = Rules run while the camera daemon is active, for example to record a clip when motion is detected at night.
+# Shown when the rules file has no rules.
+settings-automation-empty = No rules yet
+# Button that opens the rules file in a text editor.
+settings-automation-edit = Edit rules
+# Section in the automation settings about sharing captures.
+settings-sharing = Sharing
+# Toggle for the "Share" chip shown briefly after each capture.
+settings-share-after-capture = Offer to share new captures
+# Description under the "Offer to share new captures" toggle.
+settings-share-after-capture-description = Show a Share button above the shutter for a few seconds after each photo or video. Right-click the gallery button to share the latest photo at any time.
+# Item listing the custom share targets; its description lists their names.
+settings-share-targets = Custom share targets
+# Description of the custom share targets item when none are set up.
+settings-share-targets-empty = Scripts or uploads, such as Nextcloud, listed next to "Open with" and "Email"
+# Button that opens the share targets file in a text editor.
+settings-share-targets-edit = Edit targets
+# Settings page for uploading captures to a server.
+settings-upload = Uploads
+# Toggle that uploads each new capture.
+settings-upload-enabled = Upload new captures
+# Description under the "Upload new captures" toggle.
+settings-upload-enabled-description = Send each photo and video to the remote below once it is saved. Fill in and save the remote first.
+# Section with the server details.
+settings-upload-remote = Remote
+# Dropdown choosing the kind of server.
+settings-upload-provider = Server type
+# Upload server types.
+upload-provider-webdav = WebDAV (Nextcloud, ownCloud)
+upload-provider-s3 = S3-compatible storage
+# Text fields of a WebDAV remote.
+settings-upload-folder = Folder URL
+settings-upload-username = User name
+settings-upload-password = Password
+# Text fields of an S3 remote.
+settings-upload-endpoint = Endpoint
+settings-upload-bucket = Bucket
+settings-upload-region = Region
+settings-upload-access-key = Access key ID
+settings-upload-secret-key = Secret access key
+# Description under the password field.
+settings-upload-secret-description = Kept in the system keyring. Leave empty to keep the saved one.
+# Button saving the remote.
+settings-upload-save = Save
+# Shown next to the save button once the remote is saved.
+settings-upload-saved = Saved
+# Section on the uploads since the app was started.
+settings-upload-session = This session
+# Upload counts since the app was started.
+settings-upload-session-counts = { $uploaded } uploaded, { $uploading } in progress, { $failed } failed
+# Button that uploads the failed captures again.
+settings-upload-retry = Retry failed uploads
+# Button that re-reads the rules file after editing it.
+settings-automation-reload = Reload
+# Rule trigger in the rule summary: fires when motion is detected.
+automation-trigger-motion = On motion
+# Rule trigger in the rule summary: fires periodically. { $seconds } is a number of seconds.
+automation-trigger-every = Every { $seconds } s
+# Rule trigger limited to a time window. { $trigger } is one of the two strings above, { $window } e.g. "22:00-06:00".
+automation-trigger-between = { $trigger }, { $window }
+# Rule action in the summary: save a photo.
+automation-action-photo = photo
+# Rule action in the summary: record a clip. { $seconds } is the clip length.
+automation-action-record = record { $seconds } s
+# Rule action in the summary: send a desktop notification.
+automation-action-notify = notify
+# Rule action in the summary: run an external program. { $command } is its path.
+automation-action-run = run { $command }
+# Description under each rule. { $trigger } is the trigger text, { $actions } a comma-separated list of actions.
+automation-rule-summary = { $trigger }: { $actions }
+
 ## Device information panel, expanded from the camera row in settings.
 ## These are labels in a two column list. Values are technical and untranslated.
 
@@ -200,12 +666,23 @@ initializing-camera = Initializing camera...
 
 ## Format picker, the resolution and frame rate chooser.
 
-# Label before the row of resolution buttons. Includes the colon. Fixed 80px
-# label column, so keep it short.
-format-resolution = Resolution:
-# Label before the row of frame rate buttons. Includes the colon. Same 80px
-# label column.
-format-framerate = Frame Rate:
+# Placeholder of the search field above the format list. Formats can be
+# found by size, name, aspect ratio, pixel format or frame rate.
+format-search = Search formats
+# Shown in place of the list when no format matches the search.
+format-no-match = No matching formats
+# Group header in the format list, frame rate of the formats below it.
+format-group-fps = { $fps } fps
+# Group header for formats whose frame rate the camera picks itself.
+format-group-auto = Auto frame rate
+# Tiny badge on formats the camera compresses in hardware. 2 to 4 characters.
+format-badge-hw = HW
+# Warning under the list. $encoder is the video encoder's name, $size the
+# largest frame it takes, e.g. 4096x2304.
+format-encoder-too-large = { $encoder } can't record frames larger than { $size }
+# Warning under the list. $encoder is the video encoder's name, $format the
+# selected format, e.g. 3840x2160 at 60 fps.
+format-encoder-too-fast = { $encoder } may not keep up with { $format }; recordings could drop frames
 
 ## Status indicators in the format button in the top bar.
 ## These are tiny badges, 2 to 4 characters. Abbreviate.
@@ -245,6 +722,62 @@ qr-add-contact = Add Contact
 # Saves a detected calendar entry.
 qr-add-event = Add Event
 
+## Progress of joining a WiFi network from a QR code, shown on the code's
+## button in place of "Connect to WiFi".
+
+qr-wifi-connecting = Connecting…
+# Joined; checking whether the internet can be reached.
+qr-wifi-checking = Checking Connection…
+qr-wifi-online = Connected
+# Joined, but the network has no internet access.
+qr-wifi-no-internet = Connected, No Internet
+# Joined a network (hotel, café) that asks for a sign-in first. Opens its sign-in page.
+qr-wifi-sign-in = Sign In to Network
+# Joining failed; pressing it tries again.
+qr-wifi-retry = Couldn't Connect – Retry
+
+## "Show QR" tool: makes a QR code for another device to scan off the screen.
+
+# Title of the QR code composer.
+qr-share-title = Show QR Code
+# Tab for a code made of free text or a web address.
+qr-share-text = Text or link
+# Tab for a code that shares a WiFi network.
+qr-share-wifi = WiFi
+qr-share-text-placeholder = Text or link
+# Placeholder of the WiFi network name field.
+qr-share-ssid = Network name
+qr-share-password = Password
+qr-share-hidden = Hidden network
+# Fills the WiFi fields in from the network this computer is connected to.
+qr-share-current-wifi = Use current network
+# Shown in place of the code while nothing is filled in.
+qr-share-empty = Type something to make a code
+# Shown in place of the code when the content doesn't fit in one.
+qr-share-too-long = Too long for a QR code
+# Shows the code alone on a white, full-screen window.
+qr-share-fullscreen = Full screen
+qr-share-close = Close
+
+## History of scanned QR codes, opened from a code's history button or from Settings.
+
+qr-history-title = Scanned QR Codes
+qr-history-keep = Keep history
+qr-history-keep-description = Remember scanned codes on this computer
+# Number of codes in the history.
+qr-history-count = { $count ->
+    [one] 1 code
+   *[other] { $count } codes
+}
+qr-history-clear = Clear history
+qr-history-search = Search
+qr-history-empty = No codes scanned yet
+qr-history-no-match = No codes match
+# What was done with a code, listed after when it was scanned.
+qr-history-opened = Opened
+qr-history-joined = Joined
+qr-history-copied = Copied
+
 ## Exposure picker.
 ## Row labels here sit in a fixed 70px column at text size 13. Long words are
 ## clipped, so abbreviate where needed.
@@ -273,6 +806,20 @@ exposure-auto-mode = Auto
 # Shown beside a control the connected camera does not offer. Lowercase in
 # English because it reads as a status, not a heading.
 exposure-not-supported = unsupported
+# Button in manual exposure mode that proposes exposure time and gain from the live histogram.
+exposure-suggest = Auto-suggest
+# Proposed manual exposure. { $time } is a shutter time such as "1/60s", { $gain } the sensor gain value.
+exposure-suggestion-gain = { $time } at gain { $gain }
+# Button that applies the proposed exposure time and gain.
+exposure-suggestion-apply = Apply
+# Toggle button that holds the current automatic exposure, so reframing or a
+# light in the shot doesn't change it ("AE-L" = auto exposure lock, a common
+# camera abbreviation). Also shown in the top-bar pill while held.
+exposure-hold = AE-L
+# Caption beside the AE-L button.
+exposure-hold-description = Keep the current exposure
+# Warning in the exposure and color pickers when another app keeps changing the controls.
+controls-overridden = Another app keeps changing these controls
 
 ## Focus controls, part of the exposure picker. Same 70px label column.
 
@@ -295,6 +842,11 @@ color-sharpness = Sharpness
 color-hue = Hue
 # Toggle for automatic white balance.
 color-white-balance = White Balance
+# Toggle button that holds the current automatic white balance ("AWB-L" =
+# auto white balance lock). Also shown in the top-bar pill while held.
+color-hold = AWB-L
+# Caption beside the AWB-L button.
+color-hold-description = Keep the current white balance
 # Slider label for the manual white balance temperature in Kelvin. Shown when
 # automatic white balance is off. Abbreviated to fit the 70px column.
 color-temperature = Temp
@@ -302,6 +854,18 @@ color-temperature = Temp
 color-auto = Auto
 # Status text beside those toggles when they are off.
 color-manual = Manual
+# Button that arms the white balance eyedropper; the next click on the preview
+# picks something that should be neutral.
+color-pick-white = Pick white
+# Hint shown over the preview while the eyedropper is armed.
+color-pick-white-hint = Click something white or gray
+# Hint shown over the preview while the focus loupe follows the pointer.
+loupe-hint = Click to pin the loupe · Esc to close
+# Hint shown over the preview while the focus loupe is pinned to a spot.
+loupe-pinned-hint = Click to move the loupe · Esc to close
+# Caption beside the eyedropper button when the camera has no white balance
+# control and the eyedropper's correction is applied in software.
+color-software-wb = Software white balance
 
 ## Tools grid, the row of buttons above the shutter.
 ## These are labels under 32px icons at text size 11. One short word each.
@@ -318,6 +882,18 @@ tools-color = Color
 tools-filter = Filter
 # Opens the pan and tilt controls. Only shown when the camera has a motor.
 tools-motor = Motor
+# Shows the camera full screen as a mirror, with almost no controls. Photo mode only.
+tools-mirror = Mirror
+# Toggles the flash, or the torch in video modes.
+tools-flash = Flash
+# Keeps the flash LED lit as a flashlight. Only shown on cameras with a flash LED.
+tools-torch = Torch
+# Opens the QR code composer, to show a link, text or WiFi network as a code.
+tools-show-qr = Show QR
+# Opens a magnifier showing the camera's pixels at full size, for checking focus.
+tools-loupe = Loupe
+# Toggles HDR+ multi-frame capture. Keep the brand name as is.
+tools-hdr = HDR+
 
 ## Pan and tilt controls for motorised cameras.
 
@@ -330,12 +906,40 @@ ptz-pan = Pan
 # column.
 ptz-tilt = Tilt
 
-## Privacy cover warning, a modal shown when the lens is physically covered.
-
-# Title of the warning. Large bold text, keep it to one line.
-privacy-cover-closed = Privacy cover is closed
-# Body of the warning, referring to the physical shutter on the device.
-privacy-cover-hint = Open the privacy cover to use the camera
+## Camera blocked screen, shown in place of the preview while the lens is
+## physically covered or the camera's privacy switch is engaged.
+
+# Title of the screen. Large bold text, keep it to one line.
+privacy-cover-closed = Camera blocked
+# Body of the screen, referring to the physical shutter or switch on the device.
+privacy-cover-hint = Open the privacy cover or release the privacy switch. The camera turns back on by itself.
+
+## Error notice, shown above the shutter when the camera, a photo or a
+## recording fails. A short headline, then on a second line what to do about
+## it where that is known. "Settings → Applications" names the system settings
+## page; keep the arrow.
+
+error-no-camera = No camera found
+error-camera-disconnected = The camera was disconnected
+error-camera-disconnected-remedy = Plug it back in or pick another camera.
+error-camera-busy = The camera is in use by another app
+error-camera-busy-remedy = Close the other app using it, then try again.
+error-camera-permission = No permission to use the camera
+error-camera-permission-remedy = Grant camera permission in Settings → Applications.
+error-camera-failed = The camera failed to start
+error-encoder = The video encoder failed
+error-encoder-remedy = Pick another encoder in the video settings, or install the GStreamer plugins it needs.
+error-recording-failed = Recording failed
+error-photo-failed = The photo couldn't be saved
+error-disk-full = The disk is full
+error-disk-full-remedy = Free up some space on the disk, then try again.
+error-folder-not-writable = The save folder can't be written to
+error-folder-permission-remedy = Grant access to the Pictures folder in Settings → Applications.
+error-folder-read-only-remedy = The Pictures folder is on a read-only disk. Move it to a writable one.
+error-save-failed = The file couldn't be saved
+error-portal-missing = A desktop service is missing
+error-portal-missing-remedy = Install xdg-desktop-portal and the portal for your desktop.
+error-generic = Something went wrong
 
 ## HDR+ burst capture, which merges several frames into one photo.
 
@@ -347,6 +951,9 @@ burst-mode-hold-steady = Hold steady...
 burst-mode-frames = { $captured }/{ $total } frames
 # Full screen status while the frames are merged. Same large text, keep short.
 burst-mode-processing = Processing...
+# Button under the progress bar that stops the merge. The first frame of the
+# burst is still saved.
+burst-mode-cancel = Cancel
 # Merge algorithm option: slower, better results. FFT is a technical term.
 burst-mode-quality = Quality (FFT)
 # Merge algorithm option: faster, lower quality.
@@ -367,6 +974,15 @@ hdr-plus-frames-8 = 8 frames
 # Fixed count of 50 frames.
 hdr-plus-frames-50 = 50 frames
 
+## Night mode options in settings.
+
+# Nothing happens when it gets dark.
+night-mode-off = Off
+# Offer HDR+ with a suggestion chip when it gets dark.
+night-mode-suggest = Suggest HDR+
+# Take photos with HDR+ when it gets dark, even with HDR+ off.
+night-mode-auto = Use HDR+ automatically
+
 ## Photo settings.
 
 # Settings row, page title and section title for photo options.
@@ -379,12 +995,161 @@ settings-photo-format-description = File format for saved photos. JPEG is compre
 # Dropdown label for the HDR+ frame count. HDR+ is a product name, keep it.
 settings-hdr-plus = HDR+ (experimental)
 # Description under the HDR+ dropdown.
-settings-hdr-plus-description = Multi-frame capture for improved low-light photos and dynamic range. Auto selects frame count based on scene brightness.
+settings-hdr-plus-description = Multi-frame capture for improved low-light photos and dynamic range. Auto selects frame count based on scene brightness and sensor noise.
+# Toggle for reconstructing burst photos at twice the resolution.
+settings-super-resolution = Super-resolution (experimental)
+# Description under the super-resolution toggle. HDR+ is a product name, keep
+# it.
+settings-super-resolution-description = Take a burst for every photo and combine the frames into one with twice the width and height, instead of merging them with HDR+. Hold the camera by hand: the slight shake is what adds the detail. Not available for raw-capture cameras.
+# Dropdown label for what happens when the preview turns dark.
+settings-night-mode = Night mode
+# Description under the night mode dropdown. HDR+ is a product name, keep it.
+settings-night-mode-description = Measures brightness and sensor noise in the preview. When it gets dark, offer HDR+ or take photos with it automatically, sizing the burst to the scene.
 # Toggle that also keeps every individual burst frame. Only shown when HDR+ is
 # enabled.
 settings-save-burst-raw = Save raw burst frames
 # Description under the toggle above.
 settings-save-burst-raw-description = Save individual burst frames as DNG files alongside HDR+ photos. Useful for debugging or reprocessing.
+# Section title for product photo settings on the Photo settings page.
+settings-product = Product photos
+# Toggle that turns the shutter into a product-photo capture.
+settings-product-mode = Product mode
+# Description under the product mode toggle.
+settings-product-mode-description = Average several frames, whiten the background and crop to the object. Works best with the object on a white surface.
+# Toggle for exporting product photos with a transparent background.
+settings-product-transparent = Transparent background
+# Description under the transparent background toggle.
+settings-product-transparent-description = Save product photos as PNG with the background removed.
+# Section title for focus stacking settings on the Photo settings page.
+settings-focus-stack = Focus stacking
+# Toggle that makes the shutter take a focus-stacked photo.
+settings-focus-stack-mode = Focus stacking
+# Description under the focus stacking toggle.
+settings-focus-stack-mode-description = The shutter sweeps the focus across its range and merges the frames into one photo that is sharp throughout. For close-ups; keep the camera and subject still. Needs a camera with manual focus.
+# Label for the dropdown choosing how many focus positions a sweep visits.
+settings-focus-stack-steps = Focus positions
+# Focus positions option. { $count } is the number of positions.
+focus-stack-step-count = { $count } positions
+# Section title for photo booth settings on the Photo settings page.
+settings-photo-booth = Photo booth
+# Toggle that turns the shutter into a photo booth run.
+settings-photo-booth-mode = Photo booth mode
+# Description under the photo booth toggle.
+settings-photo-booth-mode-description = The shutter takes a series of shots, with a countdown before each, and saves them as one collage.
+# Label for the photo booth collage layout dropdown.
+settings-photo-booth-layout = Layout
+# Photo booth layout: shots stacked in one column.
+photo-booth-layout-strip = Strip
+# Photo booth layout: shots in two columns.
+photo-booth-layout-grid = Grid
+# Label for the photo booth shot count dropdown.
+settings-photo-booth-shots = Shots
+# Photo booth shot count option. { $count } is the number of shots.
+photo-booth-shot-count = { $count } shots
+# Label for the countdown before each photo booth shot.
+settings-photo-booth-countdown = Countdown
+# Description under the photo booth countdown dropdown.
+settings-photo-booth-countdown-description = Time to get ready before each shot.
+# Photo booth countdown option. { $seconds } is a number of seconds.
+photo-booth-countdown-seconds = { $seconds } s
+# Toggle giving each photo booth shot a different filter.
+settings-photo-booth-filters = Filter per shot
+# Description under the filter per shot toggle.
+settings-photo-booth-filters-description = Cycle through Original, Mono, Sepia and Vivid instead of using the selected filter.
+# Caption under the photo booth countdown. { $shot } is the upcoming shot, { $total } the number of shots.
+photo-booth-progress = Shot { $shot } of { $total }
+# Slider label for the sharpening applied to saved photos.
+settings-sharpening = Sharpening
+# Description under the sharpening slider.
+settings-sharpening-description = Crispens fine detail in saved photos and HDR+ results. 0% turns it off.
+# Readout next to the sharpening slider. $amount is a whole number, e.g. "50".
+settings-sharpening-value = { $amount }%
+# Section title for hand-tuning how HDR+ bursts are merged.
+settings-burst-tuning = Advanced computational photography
+# Toggle that replaces the scene-adaptive HDR+ parameters with the sliders
+# below.
+settings-burst-tuning-custom = Tune HDR+ manually
+# Description under the manual tuning toggle.
+settings-burst-tuning-custom-description = Use the values below for every HDR+ photo instead of picking them from the scene's brightness and noise.
+# Slider label, number of frames in an HDR+ burst.
+settings-burst-tuning-frames = Frame count
+# Description under the frame count slider.
+settings-burst-tuning-frames-description = Frames merged in Auto and night mode bursts. More frames remove more noise but take longer.
+# Slider label, how strongly frames are merged.
+settings-burst-tuning-robustness = Merge strength
+# Description under the merge strength slider.
+settings-burst-tuning-robustness-description = Higher values denoise more but may ghost moving subjects.
+# Slider label, how much dark areas are brightened.
+settings-burst-tuning-shadows = Shadow boost
+# Description under the shadow boost slider.
+settings-burst-tuning-shadows-description = Lifts dark areas of the merged photo.
+# Slider label, local contrast enhancement.
+settings-burst-tuning-contrast = Local contrast
+# Description under the local contrast slider.
+settings-burst-tuning-contrast-description = Brings out texture and detail in the merged photo.
+# Readout next to the tuning sliders. $amount is a whole number, e.g. "20".
+settings-burst-tuning-percent = { $amount }%
+# Toggle that lets a raised open palm start the photo timer.
+settings-gesture-shutter = Gesture shutter
+# Description under the gesture shutter toggle.
+settings-gesture-shutter-description = Hold up an open hand for a second to start the timer countdown.
+# Photo setting: the shutter puts photos on the clipboard instead of saving them.
+settings-photo-to-clipboard = Copy to clipboard
+settings-photo-to-clipboard-description = The shutter copies each photo to the clipboard instead of saving a file, for pasting straight into a chat or document.
+# Section title for rapid burst settings on the Photo settings page.
+settings-rapid-burst = Rapid burst
+# Toggle that makes a long press take a burst of photos instead of recording.
+settings-rapid-burst-mode = Burst on long press
+# Description under the rapid burst toggle.
+settings-rapid-burst-mode-description = Holding the shutter takes photos in quick succession, then lets you pick the sharpest one.
+# Label for the most shots one burst may take.
+settings-rapid-burst-max-shots = Shot limit
+# Rapid burst shot limit option. { $count } is the number of shots.
+rapid-burst-shot-count = { $count } shots
+# Settings section for Document and Whiteboard mode PDFs.
+settings-documents = Documents
+settings-searchable-pdf = Searchable PDFs
+# { $language } is the Tesseract language code(s), e.g. "eng" or "eng+deu".
+settings-searchable-pdf-description = Recognize the text on each page (language: { $language }) so saved PDFs can be searched and copied from.
+# Title of the picker shown after a rapid burst. { $count } is the number of shots.
+burst-picker-title = { $count } burst shots
+# Badge on the sharpest shot in the burst picker.
+burst-picker-best = Sharpest
+# Button keeping the selected burst shot and deleting the rest.
+burst-picker-keep-one = Keep this one
+# Button closing the burst picker without deleting anything.
+burst-picker-keep-all = Keep all
+
+## Animated clips, short GIF/WebP captures made with the Video mode record button.
+
+# Section title for animated clip settings on the Video settings page.
+settings-animated-clip = Animated clips
+# Dropdown label choosing what the record button produces.
+settings-animated-clip-format = Record as
+# Description under the record-as dropdown.
+settings-animated-clip-format-description = Capture a short animation instead of a video. It stops by itself.
+# Record-as option: a regular video file.
+animated-clip-format-off = Video
+# Record-as option: an animated GIF.
+animated-clip-format-gif = Animated GIF
+# Record-as option: an animated WebP.
+animated-clip-format-webp = Animated WebP
+# Label for the clip length dropdown.
+settings-animated-clip-length = Length
+# Clip length option. { $seconds } is a number of seconds.
+animated-clip-seconds = { $seconds } s
+# Label for the clip frame rate dropdown.
+settings-animated-clip-fps = Frame rate
+# Clip frame rate option. { $fps } is frames per second.
+animated-clip-fps = { $fps } fps
+# Toggle making clips repeat forever.
+settings-animated-clip-loop = Loop
+# Description under the loop toggle.
+settings-animated-clip-loop-description = Play the clip on repeat instead of once.
+# Status badge while a clip is captured. { $format } is GIF or WebP, { $frame } and { $total } count frames. Keep it short.
+animated-clip-progress = { $format } { $frame }/{ $total }
+# Status badge while the clip file is written. { $format } is GIF or WebP.
+animated-clip-saving = Saving { $format }...
 
 ## Composition guides, optional lines drawn over the preview to help framing.
 
@@ -392,6 +1157,63 @@ settings-save-burst-raw-description = Save individual burst frames as DNG files
 settings-composition-guide = Composition guide
 # Description under the composition guide dropdown.
 settings-composition-guide-description = Overlay guide lines on the camera preview for framing
+
+# Dropdown label for the one-handed phone layout.
+settings-one-handed = One-handed layout
+# Description under the one-handed layout dropdown.
+settings-one-handed-description = On a phone held upright, move the shutter to a bottom corner and enlarge the buttons beside it
+# One-handed option: the shutter stays centred.
+one-handed-off = Off
+# One-handed option: shutter in the bottom-left corner.
+one-handed-left = Left hand
+# One-handed option: shutter in the bottom-right corner.
+one-handed-right = Right hand
+# Dropdown label for what the phone's hardware volume keys do in the app.
+settings-volume-keys = Volume keys
+# Description under the volume keys dropdown.
+settings-volume-keys-description = What the volume buttons do while the camera is open
+# Volume keys option: either key takes a photo or starts and stops recording.
+volume-key-capture = Shutter
+# Volume keys option: volume up zooms in, volume down zooms out.
+volume-key-zoom = Zoom
+# Volume keys option: the keys keep changing the system volume.
+volume-key-volume = System volume
+
+## Controls page: the per-mode control layout editor.
+
+# Settings row and page title for the control layout editor.
+settings-controls = Controls
+# Dropdown label for the camera mode whose layout is being edited.
+settings-controls-mode = Mode
+# Description under the mode dropdown.
+settings-controls-description = Choose where each button appears in this mode
+# Section title above the list of controls.
+settings-controls-layout = Layout
+# Button restoring the stock layout for the selected mode.
+settings-controls-reset = Reset to defaults
+# Toggle for touch gestures on the camera preview.
+settings-preview-gestures = Preview gestures
+# Description under the preview gestures toggle.
+settings-preview-gestures-description = Swipe sideways to change mode, swipe up or down or double-tap to switch camera, long-press to lock exposure and focus
+# Toggle for moving between the controls with Tab.
+settings-keyboard-navigation = Keyboard navigation
+# Description under the keyboard navigation toggle.
+settings-keyboard-navigation-description = Tab and Shift+Tab move between the controls, Enter or Space uses the focused one, and the arrow keys change mode on the mode switcher
+# Placement option: a button in the bar over the preview.
+control-placement-top-bar = Top bar
+# Placement option: a tile in the tools menu.
+control-placement-tools-menu = Tools menu
+# Placement option for controls with a fixed spot, e.g. the gallery button.
+control-placement-shown = Shown
+# Placement option: the control is not shown at all. Its keyboard shortcut
+# still works.
+control-placement-hidden = Hidden
+# Layout editor name of the gallery thumbnail button in the bottom bar.
+control-gallery = Gallery
+# Layout editor name of the switch-camera button in the bottom bar.
+control-camera-switcher = Camera switcher
+# Layout editor name of the fit/fill and zoom chips above the shutter.
+control-zoom = Fit and zoom
 # Guide option: no lines.
 guide-none = None
 # Guide option: a 3 by 3 grid.
@@ -436,8 +1258,10 @@ insights-stream-combined = Preview + Capture Stream
 
 # Row label, delay from sensor to screen, in milliseconds.
 insights-frame-latency = Frame Latency
-# Row label, count of frames that never arrived.
+# Row label, count of preview frames replaced by newer ones before they were shown.
 insights-dropped-frames = Dropped Frames
+# Row label, count of extra frame intervals a preview frame stayed on screen because the next one was late.
+insights-duplicated-frames = Duplicated Frames
 # Row label, size of one decoded frame in memory, in megabytes.
 insights-frame-size-decoded = Frame Size
 # Row label, time spent wrapping a frame for the renderer, in milliseconds.
@@ -484,6 +1308,41 @@ insights-sensor-model = Sensor
 # Row label, which component decodes MJPEG frames.
 insights-mjpeg-decoder = MJPEG Decoder
 
+## Insights, USB connection of the camera.
+
+# Section title for how the camera is connected over USB.
+insights-usb = USB Connection
+# Row label, the USB speed the camera is running at, for example USB 2.0.
+insights-usb-speed = Connected At
+# Row label, the fastest USB speed the camera supports.
+insights-usb-camera-max = Camera Supports
+# Row label, the fastest USB speed the computer's USB controller supports.
+insights-usb-controller-max = Computer Supports
+# Row label, number of USB hubs between the camera and the computer.
+insights-usb-hubs = Hubs In Between
+# Row label, number of other cameras sharing the same USB bus.
+insights-usb-shared = Other Cameras On Bus
+# Row label, estimated data rate of the current format against what the USB
+# connection carries, for example "37.3 / 24.6 MB/s".
+insights-usb-format-bandwidth = Format Bandwidth
+
+## USB bandwidth advice, shown under the frame rate buttons in the format
+## picker and in Insights. $format is a short format name such as
+## "4K30 MJPG"; $required, $connected and $controller are USB versions such
+## as "USB 3.0" and stay as they are.
+
+# The camera and the computer support a faster USB than the camera is running at.
+usb-advice-slow-port = This { $format } format needs { $required }, but the camera is connected at { $connected } speed. Plug it straight into a { $required } port, without a hub or extension cable.
+# The format needs a faster USB than the computer has.
+usb-advice-slow-controller = This { $format } format needs { $required }, but this computer's USB ports only go up to { $controller }. Pick MJPEG, a lower resolution or a lower frame rate.
+# The camera offers a format its own USB connection can't carry.
+usb-advice-beyond-camera = This { $format } format needs more than { $connected } can carry, so frames will be dropped. Pick MJPEG, a lower resolution or a lower frame rate.
+# Other cameras on the same USB bus share its bandwidth.
+usb-advice-shared-bus = { $cameras ->
+    [one] Another camera shares this USB connection.
+   *[other] { $cameras } other cameras share this USB connection.
+} If frames drop, plug one of them into a different port.
+
 ## Insights, stream layout.
 
 # Row label used when preview and capture share one stream. Its value is the
@@ -612,6 +1471,39 @@ settings-timelapse = Timelapse
 settings-timelapse-interval = Interval
 # Description under the interval dropdown.
 settings-timelapse-interval-description = Time between consecutive photo captures
+# Section title for saving stills from a recorded video.
+settings-still-extraction = Extract stills
+# Dropdown label choosing how often a still is saved.
+settings-still-extraction-interval = Save a still every
+# Description under the still extraction dropdown.
+settings-still-extraction-description = Stills go to the photo folder. Right-click a video's gallery thumbnail to extract from it directly
+# Button that opens a file chooser for the video to extract stills from.
+settings-still-extraction-start = Extract from video…
+# First entry of the still extraction dropdown: a still whenever the picture changes.
+still-extraction-scenes = Scene change
+# File chooser filter name for video files.
+still-extraction-file-filter-name = Videos
+# Progress of a running still extraction, in percent.
+still-extraction-progress = Extracting… { $percent }%
+# Result of the last still extraction.
+still-extraction-done = { $count ->
+    [one] Saved 1 still
+   *[other] Saved { $count } stills
+}
+# Button that stops a running still extraction.
+still-extraction-cancel = Cancel
+# Video settings row for stabilizing a video recorded without stabilization.
+settings-stabilize-video = Stabilize a recording
+# Description under the "Stabilize a recording" row.
+settings-stabilize-video-description = Saves a steadied copy next to the original. Middle-click a video's gallery thumbnail to stabilize it directly
+# Button that opens a file chooser for the video to stabilize.
+settings-stabilize-video-start = Stabilize video…
+# Progress of a running stabilization, in percent.
+post-stabilization-progress = Stabilizing… { $percent }%
+# Result of the last stabilization; $name is the saved copy's file name.
+post-stabilization-done = Saved { $name }
+# Button that stops a running stabilization.
+post-stabilization-cancel = Cancel
 
 ## Insights, V4L2 format list. Each row is one resolution the kernel driver
 ## reports, marked with whether libcamera also offers it.
@@ -655,6 +1547,11 @@ action-capture = Capture / Record / Play-Pause
 # Takes a still photo without interrupting an ongoing video recording. Only
 # works while recording.
 action-photo-snapshot = Photo during recording
+# Takes a photo and copies it to the clipboard instead of saving a file.
+action-copy-photo = Copy photo to clipboard
+# Adds a numbered marker to the event subtitles saved with the video being
+# recorded. Only works while recording.
+action-add-recording-marker = Add marker
 # Switches between the front and back camera.
 action-switch-camera = Switch camera
 # Turns autofocus on or off.
@@ -693,6 +1590,8 @@ action-reset-zoom = Reset zoom
 action-toggle-preview-fit = Toggle fit / fill
 # Steps through the available photo aspect ratios.
 action-cycle-photo-aspect-ratio = Cycle photo aspect ratio
+# Opens or closes the magnifier that shows the camera's pixels at full size.
+action-toggle-loupe = Toggle focus loupe
 # Opens saved photos and videos in the system gallery.
 action-open-gallery = Open gallery
 # Opens this keyboard shortcuts page.
@@ -760,7 +1659,7 @@ metainfo-feature-controls = Manual controls: exposure, ISO, shutter, focus and w
 metainfo-feature-qr = QR code scanner: open links, connect to WiFi, and more
 # Feature list item. The filter names are pulled straight from the filter
 # picker above, so translate them there and they follow here on their own.
-metainfo-feature-filters = 14 creative filters: { filter-mono }, { filter-sepia }, { filter-vivid }, { filter-noir }, { filter-pencil }, and more
+metainfo-feature-filters = 18 creative filters: { filter-mono }, { filter-sepia }, { filter-vivid }, { filter-noir }, { filter-pencil }, and more
 # Feature list item.
 metainfo-feature-virtual-camera = Virtual camera: use your filtered camera feed in video calls and other apps
 # Feature list item.
@@ -780,3 +1679,63 @@ metainfo-caption-recording = Video recording in progress
 metainfo-caption-qr = QR code detection
 # Caption for the screenshot of the settings panel.
 metainfo-caption-settings = Advanced settings
+
+# Title of the vendor extension controls page, and the settings row that opens it.
+extension-units-title = Extension Controls
+# Shown when the current camera exposes no UVC extension unit controls.
+extension-units-empty = This camera has no vendor extension controls.
+# Section title for one extension unit. { $id } is the unit number, { $guid } its identifier.
+extension-units-unit = Unit { $id } ({ $guid })
+# Name of a control that has no known preset. { $selector } is its number.
+extension-units-selector = Control { $selector }
+# Description under a control: its payload size.
+extension-units-bytes = { $count ->
+    [one] { $count } byte
+   *[other] { $count } bytes
+}
+# Button that writes the edited raw value to the camera.
+extension-units-apply = Apply
+# Shown instead of a value when the control cannot be read.
+extension-units-unreadable = Write-only
+
+## Accessible names of buttons that show only an icon, read out by screen
+## readers and shown as tooltips.
+
+# Minimizes the window.
+a11y-minimize = Minimize
+# Maximizes or restores the window.
+a11y-maximize = Maximize
+# Closes the window.
+a11y-close = Close
+# Closes a notice chip or menu over the preview.
+a11y-dismiss = Dismiss
+# Picks an image or video to stream as the virtual camera.
+a11y-open-file = Open file
+# Picks a folder whose images and videos play one after another as the virtual camera.
+a11y-open-folder = Open folder
+# Stops streaming the picked file and goes back to the camera.
+a11y-clear-file = Stop streaming file
+# Plays or pauses the file being streamed.
+a11y-play-pause = Play or pause
+# Throws away the pages, snapshots or codes collected so far.
+a11y-discard = Discard
+# Resets controls to their defaults.
+a11y-reset = Reset
+# Removes an item from a list.
+a11y-remove = Remove
+# Copies text to the clipboard.
+a11y-copy = Copy
+# Goes back to the previous settings page.
+a11y-back = Back
+# Shows details about the camera device.
+a11y-device-info = Device information
+# Turns the scan beep on or off.
+a11y-scan-beep = Scan beep
+# Opens the list of scanned codes.
+a11y-qr-history = Scanned codes
+# Turns the mirror-mode screen light on or off.
+a11y-mirror-light = Screen light
+# Leaves mirror mode.
+a11y-mirror-exit = Leave mirror mode
+# Leaves the full-screen QR code.
+a11y-qr-share-exit-fullscreen = Exit full screen
@@ -0,0 +1,101 @@
+#!/usr/bin/env python3
+"""Save what the camera app's debug frame tap streams.
+
+Run the app with `camera --frame-tap`, then this script. Each frame the app
+sends is written as a PPM image next to a JSON file holding the pixel format,
+stride and plane layout the camera delivered and the pipeline statistics at
+the time, which is usually enough to tell a colour conversion or stride bug
+apart from a driver one. The statistics are also printed as they arrive.
+
+    scripts/frame-tap.py                  # save frames to ./frame-tap
+    scripts/frame-tap.py -n 5 -o /tmp/tap # stop after 5 frames
+
+Only the standard library is needed, so it can be handed to users as is.
+"""
+
+from __future__ import annotations
+
+import argparse
+import json
+import os
+import socket
+import sys
+from pathlib import Path
+
+
+def socket_path() -> Path | None:
+    runtime_dir = os.environ.get("XDG_RUNTIME_DIR")
+    return Path(runtime_dir) / "camera-frame-tap.sock" if runtime_dir else None
+
+
+def read_exactly(stream, size: int) -> bytes:
+    data = stream.read(size)
+    if data is None or len(data) < size:
+        raise EOFError
+    return data
+
+
+def write_ppm(path: Path, width: int, height: int, rgba: bytes) -> None:
+    rgb = bytearray(width * height * 3)
+    rgb[0::3] = rgba[0::4]
+    rgb[1::3] = rgba[1::4]
+    rgb[2::3] = rgba[2::4]
+    with path.open("wb") as f:
+        f.write(b"P6\n%d %d\n255\n" % (width, height))
+        f.write(rgb)
+
+
+def summary(header: dict) -> str:
+    source = header["source"]
+    stats = header["stats"]
+    return (
+        f"{source['width']}x{source['height']} {source['pixel_format']}"
+        f" stride {source['stride']}, {source['data_len']} bytes"
+        f" | {stats.get('format') or '?'}"
+        f" | decode {stats.get('mjpeg_decode_us', 0)} us"
+        f", upload {stats.get('gpu_upload_us', 0)} us"
+        f", {source['age_ms']} ms old"
+    )
+
+
+def main() -> int:
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("-o", "--output", type=Path, default=Path("frame-tap"))
+    parser.add_argument("-n", "--count", type=int, default=0, help="frames to save (0 = until interrupted)")
+    parser.add_argument("--socket", type=Path, default=socket_path())
+    args = parser.parse_args()
+    if args.socket is None:
+        print("XDG_RUNTIME_DIR is not set; pass --socket", file=sys.stderr)
+        return 1
+
+    args.output.mkdir(parents=True, exist_ok=True)
+    client = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+    try:
+        client.connect(str(args.socket))
+    except OSError as e:
+        print(f"Can't connect to {args.socket}: {e}. Is the app running with --frame-tap?", file=sys.stderr)
+        return 1
+
+    stream = client.makefile("rb")
+    saved = 0
+    try:
+        while args.count == 0 or saved < args.count:
+            line = stream.readline()
+            if not line:
+                break
+            header = json.loads(line)
+            rgba = read_exactly(stream, header["bytes"])
+            name = args.output / f"frame-{saved:04d}"
+            write_ppm(name.with_suffix(".ppm"), header["width"], header["height"], rgba)
+            name.with_suffix(".json").write_text(json.dumps(header, indent=2) + "\n")
+            print(f"{name.name}: {summary(header)}")
+            saved += 1
+    except (EOFError, KeyboardInterrupt):
+        pass
+
+    print(f"Saved {saved} frame(s) to {args.output}")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Window-size driven layout variants.
+//!
+//! `view()` used to build one fixed layout and rely on the preview to absorb
+//! whatever space was left. On a phone held in one hand the centred shutter
+//! is a stretch for the thumb, and on a short landscape window (a tablet on
+//! its side, a docked phone) the stacked capture row and bottom bar eat a
+//! large share of the height. [`LayoutVariant::select`] picks a variant from
+//! the window size and the user's one-handed preference; the view and the
+//! preview geometry both read it through `AppModel::layout_variant` so the
+//! scrim heights stay in sync with what is drawn.
+
+use crate::config::OneHandedLayout;
+
+/// Widest window (logical px) treated as a phone held in portrait
+pub const PHONE_MAX_WIDTH: f32 = 600.0;
+
+/// Tallest landscape window (logical px) that gets the compact layout
+pub const COMPACT_LANDSCAPE_MAX_HEIGHT: f32 = 560.0;
+
+/// Side and gallery button size in the one-handed layout, up from the
+/// regular 44 px so they're easier to hit with a thumb
+pub const LARGE_TOUCH_TARGET: f32 = 56.0;
+
+/// Bottom corner the shutter sits in for one-handed use
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// Layout the view builds for the current window
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LayoutVariant {
+    /// Centred shutter above the gallery / mode carousel / switcher row
+    Regular,
+    /// Portrait phone: shutter in a bottom corner, larger touch targets
+    OneHanded(Side),
+    /// Short landscape window: the shutter moves to a rail on the right
+    /// edge so the capture row above the bottom bar collapses
+    CompactLandscape,
+}
+
+impl LayoutVariant {
+    /// Pick the variant for a `width` × `height` window. Sizes of 0 (before
+    /// the first resize event) give the regular layout.
+    pub fn select(width: f32, height: f32, one_handed: OneHandedLayout) -> Self {
+        if width <= 0.0 || height <= 0.0 {
+            return Self::Regular;
+        }
+        if height > width {
+            if width > PHONE_MAX_WIDTH {
+                return Self::Regular;
+            }
+            return match one_handed {
+                OneHandedLayout::Off => Self::Regular,
+                OneHandedLayout::Left => Self::OneHanded(Side::Left),
+                OneHandedLayout::Right => Self::OneHanded(Side::Right),
+            };
+        }
+        if height <= COMPACT_LANDSCAPE_MAX_HEIGHT {
+            Self::CompactLandscape
+        } else {
+            Self::Regular
+        }
+    }
+
+    /// Whether the shutter lives outside the capture row, leaving it empty
+    pub fn has_capture_rail(self) -> bool {
+        self == Self::CompactLandscape
+    }
+
+    /// Size of the gallery and camera-switch buttons
+    pub fn side_button_size(self) -> f32 {
+        match self {
+            Self::OneHanded(_) => LARGE_TOUCH_TARGET,
+            _ => crate::constants::ui::PLACEHOLDER_BUTTON_WIDTH,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_size_is_regular() {
+        assert_eq!(
+            LayoutVariant::select(0.0, 0.0, OneHandedLayout::Left),
+            LayoutVariant::Regular
+        );
+    }
+
+    #[test]
+    fn one_handed_only_applies_to_portrait_phones() {
+        assert_eq!(
+            LayoutVariant::select(400.0, 800.0, OneHandedLayout::Left),
+            LayoutVariant::OneHanded(Side::Left)
+        );
+        assert_eq!(
+            LayoutVariant::select(400.0, 800.0, OneHandedLayout::Off),
+            LayoutVariant::Regular
+        );
+        // Portrait tablet: too wide to reach across anyway
+        assert_eq!(
+            LayoutVariant::select(900.0, 1200.0, OneHandedLayout::Right),
+            LayoutVariant::Regular
+        );
+    }
+
+    #[test]
+    fn short_landscape_is_compact() {
+        assert_eq!(
+            LayoutVariant::select(800.0, 400.0, OneHandedLayout::Right),
+            LayoutVariant::CompactLandscape
+        );
+        assert_eq!(
+            LayoutVariant::select(1280.0, 800.0, OneHandedLayout::Off),
+            LayoutVariant::Regular
+        );
+    }
+
+    #[test]
+    fn only_one_handed_enlarges_side_buttons() {
+        assert_eq!(
+            LayoutVariant::OneHanded(Side::Right).side_button_size(),
+            LARGE_TOUCH_TARGET
+        );
+        assert!(LayoutVariant::Regular.side_button_size() < LARGE_TOUCH_TARGET);
+    }
+}
@@ -0,0 +1,384 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Adaptive preview resolution
+//!
+//! The preview rarely needs every pixel the camera can deliver: a small
+//! window shows a fraction of them, and on a slow machine converting and
+//! uploading them all costs frames. With `Config::adaptive_preview` on,
+//! [`AdaptivePreview`] runs the preview at a smaller format of the same
+//! aspect ratio, pixel format and frame rate, and goes back up when the
+//! window grows or the machine has caught up. Two signals drive it:
+//!
+//! - Window size: the smallest format that covers the preview with
+//!   [`WINDOW_MARGIN`] to spare. It only steps back up once the window is
+//!   larger than the format, so a resize within the margin changes nothing.
+//! - Load: frames delivered against the format's frame rate. A preview that
+//!   keeps falling short gets a ceiling one format lower. The ceiling lifts
+//!   after a stretch of full-rate frames, and waits twice as long each time
+//!   it has to come back down.
+//!
+//! Every change restarts the stream, so each has to hold for a while before
+//! it happens. Photos still come out at full resolution: the capture path
+//! brings the full format back first and shoots on its first frame, unless
+//! the camera delivers stills on a stream of its own.
+
+use crate::backends::camera::types::CameraFormat;
+use std::time::{Duration, Instant};
+
+/// How much larger than the window, per axis, a reduced format is picked
+const WINDOW_MARGIN: f32 = 1.25;
+
+/// Narrowest format the preview steps down to for load
+const MIN_WIDTH: u32 = 640;
+
+/// Frames are counted over windows this long
+const MEASURE_WINDOW: Duration = Duration::from_secs(1);
+
+/// How long a smaller format has to be wanted before the preview steps down
+const STEP_DOWN_AFTER: Duration = Duration::from_secs(3);
+
+/// How long a larger format has to be wanted before the preview steps up
+const STEP_UP_AFTER: Duration = Duration::from_secs(2);
+
+/// Share of the frame rate below which the machine counts as struggling
+const STRUGGLE_RATIO: f64 = 0.75;
+
+/// Share of the frame rate above which it counts as keeping up
+const KEEPING_UP_RATIO: f64 = 0.95;
+
+/// How long it has to keep up before a load ceiling first lifts
+const RELAX_AFTER: Duration = Duration::from_secs(10);
+
+/// Longest the ceiling waits, however often it came back down
+const MAX_RELAX_AFTER: Duration = Duration::from_secs(160);
+
+/// Longest a photo waits for the full format before it is taken from
+/// whatever frame there is
+pub const HOLD_TIMEOUT: Duration = Duration::from_secs(3);
+
+fn area(format: &CameraFormat) -> u64 {
+    u64::from(format.width) * u64::from(format.height)
+}
+
+/// Formats the preview can use instead of `base`, smallest first, ending
+/// with `base` itself
+fn ladder(base: &CameraFormat, formats: &[CameraFormat]) -> Vec<CameraFormat> {
+    let mut ladder: Vec<CameraFormat> = formats
+        .iter()
+        .filter(|f| {
+            f.pixel_format == base.pixel_format
+                && f.framerate == base.framerate
+                && u64::from(f.width) * u64::from(base.height)
+                    == u64::from(f.height) * u64::from(base.width)
+                && f.width >= MIN_WIDTH
+                && area(f) < area(base)
+        })
+        .cloned()
+        .collect();
+    ladder.sort_by_key(area);
+    ladder.dedup_by_key(|f| (f.width, f.height));
+    ladder.push(base.clone());
+    ladder
+}
+
+/// Smallest format of `ladder` covering `view` scaled by `margin`, or the
+/// largest if none does
+fn covering(ladder: &[CameraFormat], view: (u32, u32), margin: f32) -> &CameraFormat {
+    ladder
+        .iter()
+        .find(|f| {
+            f.width as f32 >= view.0 as f32 * margin && f.height as f32 >= view.1 as f32 * margin
+        })
+        .unwrap_or(&ladder[ladder.len() - 1])
+}
+
+/// Picks the preview's format
+#[derive(Debug, Clone, Default)]
+pub struct AdaptivePreview {
+    /// Format the preview was asked for, which the steps are taken from
+    base: Option<CameraFormat>,
+    /// Smaller format the preview runs at instead
+    reduced: Option<CameraFormat>,
+    /// Largest pixel count load allows
+    ceiling: Option<u64>,
+    /// How long full-rate frames must last before the ceiling lifts
+    relax_after: Duration,
+    /// Whether the ceiling has lifted since it was set
+    relaxed: bool,
+    window_start: Option<Instant>,
+    frames: u32,
+    /// Pixel count of the format the window wants, and since when
+    wanted: Option<(u64, Instant)>,
+    struggling_since: Option<Instant>,
+    keeping_up_since: Option<Instant>,
+    /// Since when the full format is held for a photo
+    held_since: Option<Instant>,
+    /// A photo is waiting for the full format to come back
+    shot_waiting: bool,
+}
+
+impl AdaptivePreview {
+    /// Format the preview runs at for `base`, if smaller than it
+    pub fn reduced_for(&self, base: &CameraFormat) -> Option<&CameraFormat> {
+        self.reduced
+            .as_ref()
+            .filter(|_| self.base.as_ref() == Some(base))
+    }
+
+    /// Since when the full format is held, if it is
+    pub fn held_since(&self) -> Option<Instant> {
+        self.held_since
+    }
+
+    /// Go back to the full format at `now`, and stay there until
+    /// [`Self::release`]
+    pub fn hold(&mut self, now: Instant) {
+        self.reduced = None;
+        self.held_since.get_or_insert(now);
+        self.restart_measuring();
+    }
+
+    /// Take a photo once the held full format is back
+    pub fn wait_for_shot(&mut self) {
+        self.shot_waiting = true;
+    }
+
+    /// The full format is back: go on measuring from here. Returns whether
+    /// a photo was waiting for it.
+    pub fn release(&mut self) -> bool {
+        self.held_since = None;
+        self.restart_measuring();
+        std::mem::take(&mut self.shot_waiting)
+    }
+
+    /// Start over at the full format
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    fn restart_measuring(&mut self) {
+        self.window_start = None;
+        self.frames = 0;
+        self.wanted = None;
+        self.struggling_since = None;
+        self.keeping_up_since = None;
+    }
+
+    /// Count a preview frame that arrived at `now`, with `base` asked for
+    /// and a preview of `view` physical px. Returns whether the preview
+    /// format changed.
+    pub fn on_frame(
+        &mut self,
+        now: Instant,
+        base: &CameraFormat,
+        formats: &[CameraFormat],
+        view: (u32, u32),
+    ) -> bool {
+        if self.base.as_ref() != Some(base) {
+            *self = Self {
+                base: Some(base.clone()),
+                relax_after: RELAX_AFTER,
+                ..Self::default()
+            };
+        }
+        if self.held_since.is_some() {
+            return false;
+        }
+
+        self.frames += 1;
+        let Some(start) = self.window_start else {
+            self.window_start = Some(now);
+            self.frames = 0;
+            return false;
+        };
+        let elapsed = now.duration_since(start);
+        if elapsed < MEASURE_WINDOW {
+            return false;
+        }
+        let rate = f64::from(self.frames) / elapsed.as_secs_f64();
+        self.window_start = Some(now);
+        self.frames = 0;
+
+        let ladder = ladder(base, formats);
+        let current = self.reduced.clone().unwrap_or_else(|| base.clone());
+        let ceiling_changed = self.update_ceiling(now, rate, base, &ladder, &current);
+
+        let needed = covering(&ladder, view, 1.0);
+        let comfortable = covering(&ladder, view, WINDOW_MARGIN);
+        let mut target = if area(&current) < area(needed) || area(&current) > area(comfortable) {
+            comfortable
+        } else {
+            &current
+        };
+        if let Some(ceiling) = self.ceiling
+            && area(target) > ceiling
+        {
+            target = ladder
+                .iter()
+                .rev()
+                .find(|f| area(f) <= ceiling)
+                .unwrap_or(&ladder[0]);
+        }
+
+        if area(target) == area(&current) {
+            self.wanted = None;
+            return false;
+        }
+        let since = match self.wanted {
+            Some((wanted, since)) if wanted == area(target) => since,
+            _ => {
+                self.wanted = Some((area(target), now));
+                now
+            }
+        };
+        let hold = if ceiling_changed {
+            Duration::ZERO
+        } else if area(target) > area(&current) {
+            STEP_UP_AFTER
+        } else {
+            STEP_DOWN_AFTER
+        };
+        if now.duration_since(since) < hold {
+            return false;
+        }
+
+        self.reduced = (target != base).then(|| target.clone());
+        self.restart_measuring();
+        true
+    }
+
+    /// Lower the load ceiling after a stretch of missed frames, or lift it
+    /// after a stretch of full-rate ones. Returns whether it changed.
+    fn update_ceiling(
+        &mut self,
+        now: Instant,
+        rate: f64,
+        base: &CameraFormat,
+        ladder: &[CameraFormat],
+        current: &CameraFormat,
+    ) -> bool {
+        // Without a fixed frame rate there is nothing to fall short of
+        let Some(expected) = base.framerate.map(|fps| fps.as_f64()) else {
+            return false;
+        };
+        let ratio = rate / expected;
+        if ratio < STRUGGLE_RATIO {
+            self.keeping_up_since = None;
+            let since = *self.struggling_since.get_or_insert(now);
+            let smaller = ladder.iter().rev().find(|f| area(f) < area(current));
+            if now.duration_since(since) >= STEP_DOWN_AFTER
+                && let Some(smaller) = smaller
+            {
+                if self.relaxed {
+                    self.relax_after = (self.relax_after * 2).min(MAX_RELAX_AFTER);
+                    self.relaxed = false;
+                }
+                self.ceiling = Some(area(smaller));
+                self.struggling_since = None;
+                return true;
+            }
+        } else if ratio >= KEEPING_UP_RATIO {
+            self.struggling_since = None;
+            let Some(ceiling) = self.ceiling else {
+                return false;
+            };
+            let since = *self.keeping_up_since.get_or_insert(now);
+            if now.duration_since(since) >= self.relax_after {
+                self.ceiling = ladder
+                    .iter()
+                    .find(|f| area(f) > ceiling)
+                    .filter(|f| *f != base)
+                    .map(area);
+                self.relaxed = true;
+                self.keeping_up_since = None;
+                return true;
+            }
+        } else {
+            self.struggling_since = None;
+            self.keeping_up_since = None;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::camera::types::Framerate;
+
+    fn format(width: u32, height: u32, pixel_format: &str) -> CameraFormat {
+        CameraFormat {
+            width,
+            height,
+            framerate: Some(Framerate::from_int(30)),
+            hardware_accelerated: false,
+            pixel_format: pixel_format.to_string(),
+        }
+    }
+
+    /// Frames at `fps` for `secs`; the preview's format after each change
+    fn run(
+        preview: &mut AdaptivePreview,
+        now: &mut Instant,
+        secs: u32,
+        fps: u32,
+        base: &CameraFormat,
+        formats: &[CameraFormat],
+        view: (u32, u32),
+    ) -> Vec<(u32, u32)> {
+        let mut changes = Vec::new();
+        for _ in 0..secs * fps {
+            *now += Duration::from_secs(1) / fps;
+            if preview.on_frame(*now, base, formats, view) {
+                let format = preview.reduced_for(base).unwrap_or(base);
+                changes.push((format.width, format.height));
+            }
+        }
+        changes
+    }
+
+    #[test]
+    fn steps_follow_the_window_and_the_load_without_flapping() {
+        let base = format(2592, 1944, "YUYV");
+        let formats = [
+            format(640, 480, "YUYV"),
+            format(1280, 960, "YUYV"),
+            format(1280, 960, "MJPG"),
+            format(1280, 720, "YUYV"),
+            format(1920, 1440, "YUYV"),
+            base.clone(),
+        ];
+        let mut preview = AdaptivePreview::default();
+        let mut now = Instant::now();
+        let mut run = |preview: &mut AdaptivePreview, secs, fps, view| {
+            run(preview, &mut now, secs, fps, &base, &formats, view)
+        };
+
+        // A small window: down to the smallest 4:3 YUYV format covering it
+        // with margin, once that has held for a while
+        assert_eq!(run(&mut preview, 3, 30, (800, 600)), vec![]);
+        assert_eq!(run(&mut preview, 3, 30, (800, 600)), vec![(1280, 960)]);
+
+        // Growing within the margin changes nothing; past it, back up
+        assert_eq!(run(&mut preview, 10, 30, (1200, 900)), vec![]);
+        assert_eq!(run(&mut preview, 5, 30, (1400, 1000)), vec![(1920, 1440)]);
+
+        // A full-size window at half rate: a ceiling one format down
+        assert_eq!(run(&mut preview, 5, 30, (2000, 1500)), vec![(2592, 1944)]);
+        assert_eq!(run(&mut preview, 6, 15, (2000, 1500)), vec![(1920, 1440)]);
+
+        // It stays until full-rate frames have lasted, then lifts
+        assert_eq!(run(&mut preview, 8, 30, (2000, 1500)), vec![]);
+        assert_eq!(run(&mut preview, 6, 30, (2000, 1500)), vec![(2592, 1944)]);
+
+        // A capture holds the full format until it's released
+        assert_eq!(run(&mut preview, 6, 30, (800, 600)), vec![(1280, 960)]);
+        preview.hold(Instant::now());
+        preview.wait_for_shot();
+        assert_eq!(preview.reduced_for(&base), None);
+        assert_eq!(run(&mut preview, 10, 30, (800, 600)), vec![]);
+        assert!(preview.release());
+        assert!(!preview.release());
+        assert_eq!(run(&mut preview, 6, 30, (800, 600)), vec![(1280, 960)]);
+    }
+}
@@ -3,7 +3,7 @@
 //! Camera switcher button widget implementation
 
 use crate::app::state::{AppModel, Message};
-use crate::constants::ui;
+use crate::fl;
 use cosmic::Element;
 use cosmic::iced::Length;
 use cosmic::widget;
@@ -19,7 +19,8 @@ impl AppModel {
     /// otherwise shows an invisible placeholder to maintain consistent layout.
     /// Disabled and grayed out during transitions and recording.
     /// Hidden during virtual camera streaming (camera cannot be switched while streaming).
-    pub fn build_camera_switcher(&self) -> Element<'_, Message> {
+    /// `size` is the square button size (larger in the one-handed layout).
+    pub fn build_camera_switcher(&self, size: f32) -> Element<'_, Message> {
         let is_disabled = self.transition_state.ui_disabled
             || self.recording.is_recording()
             || self.quick_record.is_recording()
@@ -28,7 +29,7 @@ impl AppModel {
         // Hide camera switcher during virtual camera streaming
         if self.virtual_camera.is_streaming() {
             return widget::Space::new()
-                .width(Length::Fixed(ui::PLACEHOLDER_BUTTON_WIDTH))
+                .width(Length::Fixed(size))
                 .height(Length::Shrink)
                 .into();
         }
@@ -47,9 +48,10 @@ impl AppModel {
                     }));
 
             let mut btn = widget::button::custom(icon_widget)
-                .padding(10)
-                .width(Length::Fixed(44.0))
-                .height(Length::Fixed(44.0))
+                .name(fl!("action-switch-camera"))
+                .padding((size - 24.0) / 2.0)
+                .width(Length::Fixed(size))
+                .height(Length::Fixed(size))
                 .class(cosmic::theme::Button::Standard);
 
             if !is_disabled {
@@ -59,7 +61,7 @@ impl AppModel {
             btn.into()
         } else {
             widget::Space::new()
-                .width(Length::Fixed(ui::PLACEHOLDER_BUTTON_WIDTH))
+                .width(Length::Fixed(size))
                 .height(Length::Shrink)
                 .into()
         }
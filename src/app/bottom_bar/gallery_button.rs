@@ -4,8 +4,10 @@
 
 use std::sync::Arc;
 
+use crate::app::controls::focus_ring::FocusRing;
 use crate::app::gallery_widget::gallery_widget;
 use crate::app::state::{AppModel, Message};
+use crate::upload::UploadStatus;
 use cosmic::Element;
 use cosmic::iced::Length;
 use cosmic::widget::{self, icon};
@@ -14,13 +16,14 @@ impl AppModel {
     /// Build the gallery button widget
     ///
     /// Shows a thumbnail if available, otherwise shows a folder icon.
-    /// Disabled and grayed out during transitions.
-    pub fn build_gallery_button(&self) -> Element<'_, Message> {
+    /// Disabled and grayed out during transitions. `size` is the square
+    /// button size (larger in the one-handed layout).
+    pub fn build_gallery_button(&self, size: f32) -> Element<'_, Message> {
         let is_disabled = self.transition_state.ui_disabled;
 
         // Get corner radius from theme — cap at half button size
         let theme = cosmic::theme::active();
-        let corner_radius = theme.cosmic().corner_radii.radius_xl[0].min(22.0);
+        let corner_radius = theme.cosmic().corner_radii.radius_xl[0].min(size / 2.0);
 
         // If we have both the thumbnail handle and RGBA data, use custom primitive
         let button_content = if let (Some(thumbnail), Some((rgba_data, width, height))) =
@@ -32,34 +35,83 @@ impl AppModel {
                 *width,
                 *height,
                 corner_radius,
+                size,
             )
         } else if let Some(thumbnail) = &self.gallery_thumbnail {
             let image = widget::image::Image::new(thumbnail.clone())
                 .content_fit(cosmic::iced::ContentFit::Cover)
-                .width(Length::Fixed(42.0))
-                .height(Length::Fixed(42.0));
+                .width(Length::Fixed(size - 2.0))
+                .height(Length::Fixed(size - 2.0));
 
             widget::container(image)
-                .width(Length::Fixed(44.0))
-                .height(Length::Fixed(44.0))
+                .width(Length::Fixed(size))
+                .height(Length::Fixed(size))
                 .into()
         } else {
             widget::container(icon::from_name("folder-pictures-symbolic").size(24))
-                .width(Length::Fixed(44.0))
-                .height(Length::Fixed(44.0))
-                .center(44.0)
+                .width(Length::Fixed(size))
+                .height(Length::Fixed(size))
+                .center(size)
                 .into()
         };
 
+        // Upload state of the capture shown, in the corner
+        let upload_badge = self
+            .gallery_thumbnail_path
+            .as_ref()
+            .and_then(|path| self.upload_status.get(path))
+            .map(|status| match status {
+                UploadStatus::Uploading => "emblem-synchronizing-symbolic",
+                UploadStatus::Uploaded => "emblem-ok-symbolic",
+                UploadStatus::Failed(_) => "dialog-error-symbolic",
+            });
+        let button_content = match upload_badge {
+            Some(icon_name) => cosmic::iced::widget::stack![
+                button_content,
+                widget::container(icon::from_name(icon_name).symbolic(true).size(14))
+                    .align_right(Length::Fixed(size))
+                    .align_bottom(Length::Fixed(size))
+                    .padding(4),
+            ]
+            .into(),
+            None => button_content,
+        };
+
+        // Right-clicking a video's thumbnail extracts stills from it, and
+        // middle-clicking writes a stabilized copy. A photo's thumbnail
+        // opens the share menu on right click.
+        let last_video = self
+            .last_media_path
+            .as_deref()
+            .map(std::path::Path::new)
+            .filter(|path| {
+                path.extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(crate::constants::file_formats::is_video_extension)
+            });
+
         // No button widget — the shader handles hover overlay directly.
         // Use mouse_area for click handling only.
         if is_disabled {
             button_content
         } else {
-            widget::mouse_area(button_content)
+            let mut area = widget::mouse_area(button_content)
                 .on_press(Message::OpenGallery)
-                .interaction(cosmic::iced::mouse::Interaction::Pointer)
-                .into()
+                .interaction(cosmic::iced::mouse::Interaction::Pointer);
+            if let Some(video) = last_video {
+                if !self.still_extraction.is_running() {
+                    area =
+                        area.on_right_press(Message::ExtractStillsFrom(Some(video.to_path_buf())));
+                }
+            } else if self.last_media_path.is_some() {
+                area = area.on_right_press(Message::ShowShareMenu(None));
+            }
+            if let Some(video) = last_video
+                && !self.post_stabilization.is_running()
+            {
+                area = area.on_middle_press(Message::StabilizeVideoFrom(Some(video.to_path_buf())));
+            }
+            FocusRing::new(area, Message::OpenGallery, corner_radius).into()
         }
     }
 }
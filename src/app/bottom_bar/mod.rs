@@ -16,6 +16,7 @@ pub mod slide_h;
 
 // Re-export for convenience
 
+use crate::app::control_layout::{Control, Placement};
 use crate::app::state::{AppModel, Message};
 use cosmic::Element;
 use cosmic::iced::{Alignment, Background, Color, Length};
@@ -97,12 +98,33 @@ impl AppModel {
         } else {
             let spacing = cosmic::theme::spacing();
             let slide = std::sync::Arc::clone(&self.carousel_button_slide);
+            // Larger side buttons in the one-handed layout
+            let size = self.layout_variant().side_button_size();
+            // A side button the user's layout hides leaves a same-width
+            // placeholder so the carousel stays centered.
+            let placeholder = || -> Element<'_, Message> {
+                widget::Space::new()
+                    .width(Length::Fixed(size))
+                    .height(Length::Shrink)
+                    .into()
+            };
+            let shown = |control| self.control_placement(control) != Placement::Hidden;
             // The carousel extends visually beyond its layout via render_bounds,
             // and SlideH slides the side buttons in sync with the expansion.
+            let gallery: Element<'_, Message> = if shown(Control::Gallery) {
+                SlideH::new(self.build_gallery_button(size), slide.clone(), 1.0).into()
+            } else {
+                placeholder()
+            };
+            let switcher: Element<'_, Message> = if shown(Control::CameraSwitcher) {
+                SlideH::new(self.build_camera_switcher(size), slide, -1.0).into()
+            } else {
+                placeholder()
+            };
             three_col_row(
-                SlideH::new(self.build_gallery_button(), slide.clone(), 1.0).into(),
+                gallery,
                 self.build_mode_switcher(),
-                SlideH::new(self.build_camera_switcher(), slide, -1.0).into(),
+                switcher,
                 [0, spacing.space_m],
             )
         };
@@ -5,14 +5,17 @@
 //! A custom iced Widget that handles touch and mouse events directly.
 //! Labels slide continuously with the finger during drag, then snap
 //! to the nearest mode center on release with an ease-out animation.
+//! It takes part in Tab focus traversal: while focused it draws a focus
+//! ring and the arrow keys step through the modes.
 
 use crate::app::state::{CameraMode, Message};
 use crate::fl;
 use cosmic::iced::advanced::widget::Tree;
-use cosmic::iced::advanced::widget::tree;
+use cosmic::iced::advanced::widget::operation::Focusable;
+use cosmic::iced::advanced::widget::{Operation, tree};
 use cosmic::iced::advanced::{Clipboard, Shell, Widget, layout, renderer};
 use cosmic::iced::{Background, Border, Color, Element, Event, Length, Pixels, Rectangle, Size};
-use cosmic::iced::{alignment, mouse, touch};
+use cosmic::iced::{alignment, keyboard, mouse, touch};
 use cosmic::{Renderer, Theme};
 use iced_core::text::{self as iced_text, Text as IcedText};
 use std::time::Instant;
@@ -118,6 +121,22 @@ struct CarouselState {
     cached_mode_count: usize,
     /// Nearest mode index during drag (for haptic feedback on boundary crossing)
     drag_nearest_idx: Option<usize>,
+    /// Whether the carousel has keyboard focus
+    focused: bool,
+}
+
+impl Focusable for CarouselState {
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
 }
 
 impl Default for CarouselState {
@@ -155,6 +174,7 @@ impl Default for CarouselState {
             label_strings: Vec::new(),
             cached_mode_count: 0,
             drag_nearest_idx: None,
+            focused: false,
         }
     }
 }
@@ -641,6 +661,25 @@ impl<'a> Widget<Message, Theme, Renderer> for ModeCarousel<'a> {
                 );
             });
         }
+
+        // Keyboard focus ring, on top of the fades
+        if state.focused && !self.disabled {
+            renderer.with_layer(render_bounds, |renderer| {
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: render_bounds,
+                        border: Border {
+                            color: accent.into(),
+                            width: 2.0,
+                            radius: bg_radius.into(),
+                        },
+                        shadow: Default::default(),
+                        snap: true,
+                    },
+                    Background::Color(Color::TRANSPARENT),
+                );
+            });
+        }
     }
 
     fn update(
@@ -888,10 +927,50 @@ impl<'a> Widget<Message, Theme, Renderer> for ModeCarousel<'a> {
                 shell.capture_event();
             }
 
+            // === Keyboard (while focused) ===
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(named),
+                ..
+            }) if state.focused => {
+                let idx = self.selected_index();
+                let target = match named {
+                    keyboard::key::Named::ArrowRight if idx + 1 < self.modes.len() => Some(idx + 1),
+                    keyboard::key::Named::ArrowLeft if idx > 0 => Some(idx - 1),
+                    keyboard::key::Named::Home => Some(0),
+                    keyboard::key::Named::End => Some(self.modes.len().saturating_sub(1)),
+                    _ => None,
+                };
+                if let Some(target) = target {
+                    if target != idx {
+                        shell.publish((self.on_select)(self.modes[target]));
+                    }
+                    shell.capture_event();
+                }
+            }
+
+            // Clicking anywhere else takes the focus away
+            Event::Mouse(mouse::Event::ButtonPressed(_)) if state.focused => {
+                state.focused = false;
+                shell.request_redraw();
+            }
+
             _ => {}
         }
     }
 
+    fn operate(
+        &mut self,
+        tree: &mut Tree,
+        layout: layout::Layout<'_>,
+        _renderer: &Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        let state = tree.state.downcast_mut::<CarouselState>();
+        if !self.disabled {
+            operation.focusable(None, layout.bounds(), state);
+        }
+    }
+
     fn mouse_interaction(
         &self,
         tree: &Tree,
@@ -931,6 +1010,9 @@ fn mode_label(mode: CameraMode) -> String {
         CameraMode::Photo => fl!("mode-photo"),
         CameraMode::Video => fl!("mode-video"),
         CameraMode::Timelapse => fl!("mode-timelapse"),
+        CameraMode::Document => fl!("mode-document"),
+        CameraMode::Whiteboard => fl!("mode-whiteboard"),
+        CameraMode::Scan => fl!("mode-scan"),
         CameraMode::Virtual => fl!("mode-virtual"),
         CameraMode::View => fl!("mode-view"),
     }
@@ -0,0 +1,199 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Best-shot picker for rapid bursts
+//!
+//! After a rapid burst is saved every shot is already on disk. The picker
+//! shows them sharpest first, with the sharpest preselected, so keeping one
+//! is a single click; the other files are then deleted. "Keep all" closes
+//! the picker and leaves the burst untouched.
+
+use crate::app::overlay_style::POPUP_PANEL;
+use crate::app::state::{AppModel, Message};
+use crate::fl;
+use crate::pipelines::photo::rapid_burst::{BurstShot, rank_by_sharpness};
+use cosmic::Element;
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget;
+use std::path::PathBuf;
+
+/// Thumbnails per picker row
+const THUMBNAILS_PER_ROW: usize = 5;
+
+/// Displayed thumbnail width
+const THUMBNAIL_DISPLAY_WIDTH: f32 = 120.0;
+
+/// One burst shot as the picker shows it
+#[derive(Debug, Clone)]
+pub struct PickerShot {
+    pub path: PathBuf,
+    pub thumbnail: widget::image::Handle,
+}
+
+/// Shots of the last rapid burst and which one is highlighted
+#[derive(Debug, Clone)]
+pub struct BurstPicker {
+    /// Shots in capture order
+    pub shots: Vec<PickerShot>,
+    /// Capture indices from sharpest to blurriest
+    pub ranking: Vec<usize>,
+    /// Capture index of the highlighted shot
+    pub selected: usize,
+}
+
+impl BurstPicker {
+    /// Build the picker with the sharpest shot selected
+    pub fn new(shots: Vec<BurstShot>) -> Self {
+        let sharpness: Vec<f32> = shots.iter().map(|shot| shot.sharpness).collect();
+        let ranking = rank_by_sharpness(&sharpness);
+        let selected = ranking.first().copied().unwrap_or(0);
+        let shots = shots
+            .into_iter()
+            .map(|shot| PickerShot {
+                thumbnail: widget::image::Handle::from_rgba(
+                    shot.thumbnail.width(),
+                    shot.thumbnail.height(),
+                    shot.thumbnail.into_raw(),
+                ),
+                path: shot.path,
+            })
+            .collect();
+        Self {
+            shots,
+            ranking,
+            selected,
+        }
+    }
+
+    /// Path of the highlighted shot
+    pub fn selected_path(&self) -> Option<&PathBuf> {
+        self.shots.get(self.selected).map(|shot| &shot.path)
+    }
+
+    /// Files to delete when keeping only the highlighted shot
+    pub fn discarded_paths(&self) -> Vec<PathBuf> {
+        self.shots
+            .iter()
+            .enumerate()
+            .filter(|&(index, _)| index != self.selected)
+            .map(|(_, shot)| shot.path.clone())
+            .collect()
+    }
+}
+
+impl AppModel {
+    /// Build the burst best-shot picker overlay
+    pub fn build_burst_picker(&self) -> Element<'_, Message> {
+        let Some(picker) = &self.burst_picker else {
+            return widget::Space::new()
+                .width(Length::Shrink)
+                .height(Length::Shrink)
+                .into();
+        };
+        let spacing = cosmic::theme::spacing();
+        let best = picker.ranking.first().copied();
+
+        let mut grid = widget::Column::new().spacing(spacing.space_xs);
+        for row_indices in picker.ranking.chunks(THUMBNAILS_PER_ROW) {
+            let mut row = widget::Row::new().spacing(spacing.space_xs);
+            for &index in row_indices {
+                let shot = &picker.shots[index];
+                let mut tile = widget::Column::new()
+                    .push(
+                        widget::image(shot.thumbnail.clone())
+                            .width(Length::Fixed(THUMBNAIL_DISPLAY_WIDTH))
+                            .content_fit(cosmic::iced::ContentFit::Contain),
+                    )
+                    .spacing(spacing.space_xxs)
+                    .align_x(Alignment::Center);
+                if Some(index) == best {
+                    tile = tile.push(widget::text::caption(fl!("burst-picker-best")));
+                }
+                let button = widget::button::custom(tile)
+                    .padding(spacing.space_xxs)
+                    .on_press(Message::SelectBurstShot(index))
+                    .class(if index == picker.selected {
+                        cosmic::theme::Button::Suggested
+                    } else {
+                        cosmic::theme::Button::Text
+                    });
+                row = row.push(button);
+            }
+            grid = grid.push(row);
+        }
+
+        let buttons = widget::Row::new()
+            .push(
+                widget::Space::new()
+                    .width(Length::Fill)
+                    .height(Length::Shrink),
+            )
+            .push(
+                widget::button::standard(fl!("burst-picker-keep-all"))
+                    .on_press(Message::KeepAllBurstShots),
+            )
+            .push(
+                widget::button::suggested(fl!("burst-picker-keep-one"))
+                    .on_press(Message::KeepBurstShot),
+            )
+            .spacing(spacing.space_s);
+
+        let content = widget::Column::new()
+            .push(widget::text::heading(fl!(
+                "burst-picker-title",
+                count = picker.shots.len()
+            )))
+            .push(widget::scrollable(grid).height(Length::Shrink))
+            .push(buttons)
+            .spacing(spacing.space_s)
+            .width(Length::Shrink);
+
+        let panel = widget::mouse_area(self.frosted_panel(
+            widget::container(content).padding(spacing.space_m).into(),
+            POPUP_PANEL,
+        ))
+        .on_press(Message::Noop);
+
+        // Clicking outside keeps everything, like closing any other picker
+        widget::mouse_area(
+            widget::container(panel)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .padding([self.top_ui_height(), 0.0, self.bottom_ui_height(), 0.0])
+                .align_x(cosmic::iced::alignment::Horizontal::Center)
+                .align_y(cosmic::iced::alignment::Vertical::Center),
+        )
+        .on_press(Message::KeepAllBurstShots)
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shot(name: &str, sharpness: f32) -> BurstShot {
+        BurstShot {
+            path: PathBuf::from(name),
+            sharpness,
+            thumbnail: image::RgbaImage::new(2, 2),
+        }
+    }
+
+    #[test]
+    fn sharpest_shot_is_preselected() {
+        let picker = BurstPicker::new(vec![shot("a", 0.1), shot("b", 0.7), shot("c", 0.4)]);
+        assert_eq!(picker.selected, 1);
+        assert_eq!(picker.ranking, vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn keeping_one_discards_the_others() {
+        let mut picker = BurstPicker::new(vec![shot("a", 0.1), shot("b", 0.7), shot("c", 0.4)]);
+        picker.selected = 2;
+        assert_eq!(
+            picker.discarded_paths(),
+            vec![PathBuf::from("a"), PathBuf::from("b")]
+        );
+        assert_eq!(picker.selected_path(), Some(&PathBuf::from("c")));
+    }
+}
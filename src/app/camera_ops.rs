@@ -21,24 +21,38 @@ fn framerate_matches_config(framerate: Option<&Framerate>, config_fps: Option<u3
 impl AppModel {
     /// Whether the format picker should be hidden for the current mode.
     /// Libcamera handles resolution automatically in Photo / Video /
-    /// Timelapse modes; View doesn't expose any resolution controls
+    /// Timelapse / Document / Whiteboard / Scan modes; View doesn't expose any resolution controls
     /// either (it's a passive viewer with no top-bar buttons).
     pub fn is_format_picker_hidden(&self) -> bool {
         matches!(
             self.mode,
-            CameraMode::Photo | CameraMode::Video | CameraMode::Timelapse | CameraMode::View
+            CameraMode::Photo
+                | CameraMode::Video
+                | CameraMode::Timelapse
+                | CameraMode::Document
+                | CameraMode::Whiteboard
+                | CameraMode::Scan
+                | CameraMode::View
         )
     }
 
+    /// Where the user's control layout puts `control` in the current mode.
+    pub fn control_placement(
+        &self,
+        control: crate::app::control_layout::Control,
+    ) -> crate::app::control_layout::Placement {
+        self.config.control_layout.placement(self.mode, control)
+    }
+
     /// Whether the HDR+/burst mode button should be visible.
     ///
-    /// - Off setting: hidden
+    /// - Off setting: hidden, unless night mode switched HDR+ on
     /// - Auto with 1 frame (bright scene) AND not overridden: hidden
     /// - Auto with >1 frames OR overridden: visible
     /// - Fixed frame counts: always visible
     pub fn should_show_burst_button(&self) -> bool {
         match self.config.burst_mode_setting {
-            BurstModeSetting::Off => false,
+            BurstModeSetting::Off => self.night_mode_engaged || self.hdr_override_disabled,
             BurstModeSetting::Auto => {
                 self.auto_detected_frame_count > 1 || self.hdr_override_disabled
             }
@@ -52,6 +66,9 @@ impl AppModel {
             CameraMode::Timelapse,
             CameraMode::Video,
             CameraMode::Photo,
+            CameraMode::Document,
+            CameraMode::Whiteboard,
+            CameraMode::Scan,
             CameraMode::View,
         ];
         if self.config.virtual_camera_enabled {
@@ -112,10 +129,16 @@ impl AppModel {
         };
 
         // Store in per-camera settings based on current mode.
-        // Virtual / Timelapse / View share Photo's per-camera format slot
+        // Virtual / Timelapse / Document / Whiteboard / Scan / View share Photo's per-camera format slot
         // (View is a passive viewer with no format choice of its own).
         let (mode_name, settings_key) = match self.mode {
-            CameraMode::Photo | CameraMode::Virtual | CameraMode::Timelapse | CameraMode::View => {
+            CameraMode::Photo
+            | CameraMode::Virtual
+            | CameraMode::Timelapse
+            | CameraMode::Document
+            | CameraMode::Whiteboard
+            | CameraMode::Scan
+            | CameraMode::View => {
                 self.config
                     .photo_settings
                     .insert(camera.path.clone(), format_settings);
@@ -123,6 +146,9 @@ impl AppModel {
                     CameraMode::Photo => "Photo",
                     CameraMode::Virtual => "Virtual",
                     CameraMode::Timelapse => "Timelapse",
+                    CameraMode::Document => "Document",
+                    CameraMode::Whiteboard => "Whiteboard",
+                    CameraMode::Scan => "Scan",
                     CameraMode::View => "View",
                     _ => unreachable!(),
                 };
@@ -232,9 +258,13 @@ impl AppModel {
             .unwrap_or_default();
 
         self.active_format = match mode {
-            CameraMode::Photo | CameraMode::Virtual | CameraMode::Timelapse | CameraMode::View => {
-                self.select_photo_format(&camera_path)
-            }
+            CameraMode::Photo
+            | CameraMode::Virtual
+            | CameraMode::Timelapse
+            | CameraMode::Document
+            | CameraMode::Whiteboard
+            | CameraMode::Scan
+            | CameraMode::View => self.select_photo_format(&camera_path),
             CameraMode::Video => self.select_video_format(&camera_path),
         };
 
@@ -259,11 +289,15 @@ impl AppModel {
         self.available_formats = backend.get_formats(camera, mode == CameraMode::Video);
 
         // Format selection logic: both modes use saved settings, current format, or defaults.
-        // Virtual / Timelapse / View use the same format selection as Photo.
+        // Virtual / Timelapse / Document / Whiteboard / Scan / View use the same format selection as Photo.
         self.active_format = match mode {
-            CameraMode::Photo | CameraMode::Virtual | CameraMode::Timelapse | CameraMode::View => {
-                self.select_photo_format(&camera_path)
-            }
+            CameraMode::Photo
+            | CameraMode::Virtual
+            | CameraMode::Timelapse
+            | CameraMode::Document
+            | CameraMode::Whiteboard
+            | CameraMode::Scan
+            | CameraMode::View => self.select_photo_format(&camera_path),
             CameraMode::Video => self.select_video_format(&camera_path),
         };
 
@@ -360,6 +394,61 @@ impl AppModel {
         }
     }
 
+    /// Whether the preview may run below the active format. Only Photo and
+    /// View modes qualify: recordings, the virtual camera and the analysis
+    /// of the other modes all take the preview stream as it comes.
+    fn adaptive_preview_allowed(&self) -> bool {
+        self.config.adaptive_preview
+            && matches!(self.mode, CameraMode::Photo | CameraMode::View)
+            && !self.recording.is_recording()
+            && !self.virtual_camera.is_streaming()
+            && !self.current_frame_is_file_source
+    }
+
+    /// Format the camera stream runs at: the active format, or a smaller one
+    /// while adaptive preview resolution has stepped down
+    pub fn preview_format(&self) -> Option<CameraFormat> {
+        let active = self.active_format.as_ref()?;
+        Some(
+            self.adaptive_preview
+                .reduced_for(active)
+                .filter(|_| self.adaptive_preview_allowed())
+                .unwrap_or(active)
+                .clone(),
+        )
+    }
+
+    /// Let adaptive preview resolution count a frame, stepping the preview
+    /// format when the window size or the frame rate call for it
+    pub fn track_adaptive_preview(&mut self) {
+        if !self.adaptive_preview_allowed() {
+            return;
+        }
+        let Some(active) = self.active_format.clone() else {
+            return;
+        };
+        // The window in physical px, in the frame's orientation
+        let scale = self.core.scale_factor();
+        let mut view = (
+            (self.screen_width * scale).round() as u32,
+            (self.screen_height * scale).round() as u32,
+        );
+        if self.current_frame_rotation.swaps_dimensions() {
+            view = (view.1, view.0);
+        }
+        let now = self.clock.now();
+        if self
+            .adaptive_preview
+            .on_frame(now, &active, &self.available_formats, view)
+        {
+            if let Some(format) = self.preview_format() {
+                info!(format = %format, "Adaptive preview switched format");
+            }
+            // The subscription restarts on the new format
+            self.start_blur_transition();
+        }
+    }
+
     /// Change resolution while trying to preserve pixel format and framerate
     pub fn change_resolution(&mut self, width: u32, height: u32) {
         let current_pixel_format = self.active_format.as_ref().map(|f| f.pixel_format.clone());
@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! White balance eyedropper layer
+//!
+//! While the eyedropper is active this invisible layer covers the preview,
+//! shows a crosshair cursor and turns the next click or tap into a point in
+//! normalized frame coordinates. The screen-to-frame mapping is the inverse
+//! of the one the QR overlay draws with, plus the digital zoom's centre crop.
+//! A click outside the frame cancels the pick.
+
+use crate::app::qr_overlay::calculate_video_bounds;
+use crate::app::state::Message;
+use cosmic::iced::advanced::widget::Tree;
+use cosmic::iced::advanced::{Clipboard, Layout, Shell, Widget, layout, mouse, renderer};
+use cosmic::iced::{Element, Event, Length, Point, Rectangle, Size, touch};
+use cosmic::{Renderer, Theme};
+
+/// Preview geometry the pick is mapped through
+#[derive(Debug, Clone, Copy)]
+pub struct PreviewMapping {
+    pub frame_width: u32,
+    pub frame_height: u32,
+    /// Animated Cover↔Contain blend (1.0 = Cover, 0.0 = Contain)
+    pub cover_blend: f32,
+    pub top_bar_h: f32,
+    pub bottom_bar_h: f32,
+    pub mirrored: bool,
+    pub zoom_level: f32,
+}
+
+/// Map a point on the preview (relative to its bounds) to normalized frame
+/// coordinates, or `None` when it misses the frame
+pub fn screen_to_frame(mapping: &PreviewMapping, size: Size, point: Point) -> Option<(f32, f32)> {
+    let (offset_x, offset_y, video_width, video_height) = calculate_video_bounds(
+        size.width,
+        size.height,
+        mapping.frame_width,
+        mapping.frame_height,
+        mapping.cover_blend,
+        mapping.top_bar_h,
+        mapping.bottom_bar_h,
+    );
+    if video_width <= 0.0 || video_height <= 0.0 {
+        return None;
+    }
+
+    let mut u = (point.x - offset_x) / video_width;
+    let v = (point.y - offset_y) / video_height;
+    if mapping.mirrored {
+        u = 1.0 - u;
+    }
+    if !(0.0..=1.0).contains(&u) || !(0.0..=1.0).contains(&v) {
+        return None;
+    }
+
+    // Digital zoom shows only the centre 1/zoom of the frame
+    let zoom = mapping.zoom_level.max(1.0);
+    Some((0.5 + (u - 0.5) / zoom, 0.5 + (v - 0.5) / zoom))
+}
+
+/// Full-screen layer that turns the next press into a gray-point pick
+pub struct EyedropperLayer {
+    mapping: PreviewMapping,
+}
+
+impl EyedropperLayer {
+    pub fn new(mapping: PreviewMapping) -> Self {
+        Self { mapping }
+    }
+
+    fn pick(&self, bounds: Rectangle, position: Point, shell: &mut Shell<'_, Message>) {
+        let point = Point::new(position.x - bounds.x, position.y - bounds.y);
+        shell.publish(match screen_to_frame(&self.mapping, bounds.size(), point) {
+            Some((x, y)) => Message::WhiteBalancePointPicked(x, y),
+            None => Message::CancelWhiteBalancePick,
+        });
+        shell.capture_event();
+    }
+}
+
+impl Widget<Message, Theme, Renderer> for EyedropperLayer {
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fill, Length::Fill)
+    }
+
+    fn layout(
+        &mut self,
+        _tree: &mut Tree,
+        _renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::Node::new(limits.max())
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        _renderer: &mut Renderer,
+        _theme: &Theme,
+        _style: &renderer::Style,
+        _layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        // Nothing to draw: the crosshair cursor and the hint chip say it all
+    }
+
+    fn update(
+        &mut self,
+        _tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                if let Some(position) = cursor.position_over(bounds) {
+                    self.pick(bounds, position, shell);
+                }
+            }
+            Event::Touch(touch::Event::FingerPressed { position, .. })
+                if bounds.contains(*position) =>
+            {
+                self.pick(bounds, *position, shell);
+            }
+            _ => {}
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if cursor.is_over(layout.bounds()) {
+            mouse::Interaction::Crosshair
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
+
+impl<'a> From<EyedropperLayer> for Element<'a, Message, Theme, Renderer> {
+    fn from(layer: EyedropperLayer) -> Self {
+        Element::new(layer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping() -> PreviewMapping {
+        PreviewMapping {
+            frame_width: 1920,
+            frame_height: 1080,
+            cover_blend: 1.0,
+            top_bar_h: 0.0,
+            bottom_bar_h: 0.0,
+            mirrored: false,
+            zoom_level: 1.0,
+        }
+    }
+
+    #[test]
+    fn centre_of_the_preview_is_the_centre_of_the_frame() {
+        let size = Size::new(1280.0, 720.0);
+        let (x, y) = screen_to_frame(&mapping(), size, Point::new(640.0, 360.0)).unwrap();
+        assert!((x - 0.5).abs() < 1e-6 && (y - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn mirror_and_zoom_are_undone() {
+        let size = Size::new(1280.0, 720.0);
+        let mirrored = PreviewMapping {
+            mirrored: true,
+            ..mapping()
+        };
+        let (x, _) = screen_to_frame(&mirrored, size, Point::new(320.0, 360.0)).unwrap();
+        assert!((x - 0.75).abs() < 1e-6);
+
+        let zoomed = PreviewMapping {
+            zoom_level: 2.0,
+            ..mapping()
+        };
+        let (x, _) = screen_to_frame(&zoomed, size, Point::new(0.0, 360.0)).unwrap();
+        assert!((x - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn letterbox_misses_the_frame() {
+        let contain = PreviewMapping {
+            cover_blend: 0.0,
+            ..mapping()
+        };
+        let size = Size::new(1280.0, 1280.0);
+        assert!(screen_to_frame(&contain, size, Point::new(640.0, 20.0)).is_none());
+    }
+}
@@ -0,0 +1,294 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Single-finger touch gestures on the camera preview.
+//!
+//! The preview widget feeds raw touch events into a [`GestureRecognizer`],
+//! which turns them into swipes, double-taps and long-presses. A second
+//! finger cancels the gesture in progress so it never fights pinch-to-zoom.
+//! Timestamps are passed in rather than read from the clock so the
+//! thresholds can be tested.
+
+use cosmic::iced::Point;
+use cosmic::iced::touch::Finger;
+use std::time::{Duration, Instant};
+
+/// Minimum travel (logical px) along the dominant axis for a swipe
+const SWIPE_MIN_DISTANCE: f32 = 60.0;
+/// A swipe must be this many times longer on its axis than across it
+const SWIPE_AXIS_RATIO: f32 = 1.5;
+/// Slower drags are treated as aborted gestures, not swipes
+const SWIPE_MAX_DURATION: Duration = Duration::from_millis(600);
+/// A finger that moves further than this is no longer a tap or long-press
+const TAP_SLOP: f32 = 12.0;
+/// Maximum gap between the two taps of a double-tap
+const DOUBLE_TAP_WINDOW: Duration = Duration::from_millis(300);
+/// Hold time before a press becomes a long-press
+pub const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+
+/// A recognised preview gesture
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Gesture {
+    /// Finger moved right-to-left
+    SwipeLeft,
+    /// Finger moved left-to-right
+    SwipeRight,
+    SwipeUp,
+    SwipeDown,
+    DoubleTap,
+    LongPress,
+}
+
+/// The finger currently being tracked
+#[derive(Clone, Copy, Debug)]
+struct Press {
+    finger: Finger,
+    start: Point,
+    started_at: Instant,
+    /// Left the tap slop, so it can only end as a swipe (or nothing)
+    moved: bool,
+    /// Long-press already reported; the lift is then ignored
+    long_pressed: bool,
+}
+
+/// Touch gesture state machine for one preview widget
+#[derive(Debug, Default)]
+pub struct GestureRecognizer {
+    press: Option<Press>,
+    /// Other fingers down; a multi-touch gesture (pinch) is in progress
+    extra_fingers: usize,
+    /// End of the last single tap, waiting for a second one
+    last_tap: Option<Instant>,
+}
+
+impl GestureRecognizer {
+    /// A finger touched the preview
+    pub fn finger_pressed(&mut self, finger: Finger, position: Point, now: Instant) {
+        if self.press.is_some() || self.extra_fingers > 0 {
+            // Second finger: hand over to pinch-to-zoom. The tracked finger
+            // now counts as one of the pinch fingers too.
+            let handed_over = usize::from(self.press.take().is_some());
+            self.last_tap = None;
+            self.extra_fingers += 1 + handed_over;
+            return;
+        }
+        self.press = Some(Press {
+            finger,
+            start: position,
+            started_at: now,
+            moved: false,
+            long_pressed: false,
+        });
+    }
+
+    /// A tracked finger moved
+    pub fn finger_moved(&mut self, finger: Finger, position: Point) {
+        if let Some(press) = self.press.as_mut()
+            && press.finger == finger
+            && distance(press.start, position) > TAP_SLOP
+        {
+            press.moved = true;
+        }
+    }
+
+    /// Whether a press is waiting to become a long-press. The widget keeps
+    /// requesting redraws while this is true so [`Self::poll`] gets called.
+    pub fn awaiting_long_press(&self) -> bool {
+        self.press.is_some_and(|p| !p.moved && !p.long_pressed)
+    }
+
+    /// Report a long-press once the finger has been held still long enough
+    pub fn poll(&mut self, now: Instant) -> Option<Gesture> {
+        let press = self.press.as_mut()?;
+        if press.moved || press.long_pressed {
+            return None;
+        }
+        if now.duration_since(press.started_at) >= LONG_PRESS_DURATION {
+            press.long_pressed = true;
+            self.last_tap = None;
+            return Some(Gesture::LongPress);
+        }
+        None
+    }
+
+    /// A finger was lifted (or lost); returns the gesture it completed
+    pub fn finger_lifted(
+        &mut self,
+        finger: Finger,
+        position: Point,
+        now: Instant,
+    ) -> Option<Gesture> {
+        let Some(press) = self.press.filter(|p| p.finger == finger) else {
+            self.extra_fingers = self.extra_fingers.saturating_sub(1);
+            return None;
+        };
+        self.press = None;
+
+        if press.long_pressed {
+            return None;
+        }
+
+        let elapsed = now.duration_since(press.started_at);
+        if press.moved || distance(press.start, position) > TAP_SLOP {
+            self.last_tap = None;
+            if elapsed > SWIPE_MAX_DURATION {
+                return None;
+            }
+            return swipe_direction(press.start, position);
+        }
+
+        // A tap: the second one within the window makes a double-tap
+        match self.last_tap.take() {
+            Some(previous) if now.duration_since(previous) <= DOUBLE_TAP_WINDOW => {
+                Some(Gesture::DoubleTap)
+            }
+            _ => {
+                self.last_tap = Some(now);
+                None
+            }
+        }
+    }
+
+    /// Drop all tracking (e.g. the widget stopped receiving events)
+    pub fn cancel(&mut self) {
+        *self = Self::default();
+    }
+}
+
+fn distance(a: Point, b: Point) -> f32 {
+    let dx = a.x - b.x;
+    let dy = a.y - b.y;
+    (dx * dx + dy * dy).sqrt()
+}
+
+/// Classify a drag from `start` to `end`, or `None` if it's too short or
+/// too diagonal to tell
+fn swipe_direction(start: Point, end: Point) -> Option<Gesture> {
+    let dx = end.x - start.x;
+    let dy = end.y - start.y;
+    if dx.abs() >= SWIPE_MIN_DISTANCE && dx.abs() >= dy.abs() * SWIPE_AXIS_RATIO {
+        Some(if dx < 0.0 {
+            Gesture::SwipeLeft
+        } else {
+            Gesture::SwipeRight
+        })
+    } else if dy.abs() >= SWIPE_MIN_DISTANCE && dy.abs() >= dx.abs() * SWIPE_AXIS_RATIO {
+        Some(if dy < 0.0 {
+            Gesture::SwipeUp
+        } else {
+            Gesture::SwipeDown
+        })
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: Finger = Finger(1);
+    const B: Finger = Finger(2);
+
+    fn at(base: Instant, ms: u64) -> Instant {
+        base + Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn quick_horizontal_drag_is_a_swipe() {
+        let t = Instant::now();
+        let mut g = GestureRecognizer::default();
+        g.finger_pressed(A, Point::new(300.0, 200.0), t);
+        g.finger_moved(A, Point::new(200.0, 205.0));
+        assert_eq!(
+            g.finger_lifted(A, Point::new(150.0, 210.0), at(t, 200)),
+            Some(Gesture::SwipeLeft)
+        );
+
+        g.finger_pressed(A, Point::new(100.0, 100.0), at(t, 500));
+        assert_eq!(
+            g.finger_lifted(A, Point::new(100.0, 250.0), at(t, 700)),
+            Some(Gesture::SwipeDown)
+        );
+    }
+
+    #[test]
+    fn slow_or_diagonal_drags_are_ignored() {
+        let t = Instant::now();
+        let mut g = GestureRecognizer::default();
+        g.finger_pressed(A, Point::new(0.0, 0.0), t);
+        assert_eq!(
+            g.finger_lifted(A, Point::new(200.0, 0.0), at(t, 1000)),
+            None
+        );
+
+        g.finger_pressed(A, Point::new(0.0, 0.0), at(t, 2000));
+        assert_eq!(
+            g.finger_lifted(A, Point::new(100.0, 90.0), at(t, 2100)),
+            None
+        );
+    }
+
+    #[test]
+    fn two_taps_in_the_window_are_a_double_tap() {
+        let t = Instant::now();
+        let mut g = GestureRecognizer::default();
+        let p = Point::new(50.0, 50.0);
+        g.finger_pressed(A, p, t);
+        assert_eq!(g.finger_lifted(A, p, at(t, 80)), None);
+        g.finger_pressed(A, p, at(t, 200));
+        assert_eq!(g.finger_lifted(A, p, at(t, 260)), Some(Gesture::DoubleTap));
+
+        // Too far apart: two single taps
+        g.finger_pressed(A, p, at(t, 1000));
+        assert_eq!(g.finger_lifted(A, p, at(t, 1050)), None);
+        g.finger_pressed(A, p, at(t, 1600));
+        assert_eq!(g.finger_lifted(A, p, at(t, 1650)), None);
+    }
+
+    #[test]
+    fn holding_still_is_a_long_press_once() {
+        let t = Instant::now();
+        let mut g = GestureRecognizer::default();
+        let p = Point::new(50.0, 50.0);
+        g.finger_pressed(A, p, t);
+        assert!(g.awaiting_long_press());
+        assert_eq!(g.poll(at(t, 300)), None);
+        assert_eq!(g.poll(at(t, 550)), Some(Gesture::LongPress));
+        assert_eq!(g.poll(at(t, 700)), None);
+        assert_eq!(g.finger_lifted(A, p, at(t, 800)), None);
+    }
+
+    #[test]
+    fn moving_cancels_the_long_press() {
+        let t = Instant::now();
+        let mut g = GestureRecognizer::default();
+        g.finger_pressed(A, Point::new(50.0, 50.0), t);
+        g.finger_moved(A, Point::new(90.0, 50.0));
+        assert!(!g.awaiting_long_press());
+        assert_eq!(g.poll(at(t, 900)), None);
+    }
+
+    #[test]
+    fn second_finger_hands_over_to_pinch() {
+        let t = Instant::now();
+        let mut g = GestureRecognizer::default();
+        g.finger_pressed(A, Point::new(100.0, 100.0), t);
+        g.finger_pressed(B, Point::new(200.0, 200.0), at(t, 20));
+        assert!(!g.awaiting_long_press());
+        assert_eq!(
+            g.finger_lifted(A, Point::new(20.0, 100.0), at(t, 200)),
+            None
+        );
+        assert_eq!(
+            g.finger_lifted(B, Point::new(300.0, 300.0), at(t, 210)),
+            None
+        );
+
+        // Both fingers up: single-finger gestures work again
+        g.finger_pressed(A, Point::new(300.0, 100.0), at(t, 500));
+        assert_eq!(
+            g.finger_lifted(A, Point::new(400.0, 100.0), at(t, 600)),
+            Some(Gesture::SwipeRight)
+        );
+    }
+}
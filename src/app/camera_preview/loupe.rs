@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Focus loupe
+//!
+//! A magnifier that shows the sensor's own pixels, one per physical screen
+//! pixel, around a point of the preview, so focus can be judged before
+//! capturing. It follows the pointer, and a click pins it to a spot (or
+//! lets it follow again).
+//!
+//! [`LoupeLayer`] sits over the preview, under the bars, and maps the
+//! pointer to sensor coordinates: the eyedropper's screen-to-frame mapping
+//! in display orientation, then the sensor rotation undone. The loupe
+//! itself is a second video widget on the preview's frame; the GPU crops it
+//! to 1:1 around that point (see `VideoPrimitive::loupe_center`).
+
+use super::eyedropper::{PreviewMapping, screen_to_frame};
+use crate::app::state::Message;
+use cosmic::iced::advanced::widget::Tree;
+use cosmic::iced::advanced::{Clipboard, Layout, Shell, Widget, layout, mouse, renderer};
+use cosmic::iced::{Element, Event, Length, Point, Rectangle, Size, touch};
+use cosmic::{Renderer, Theme};
+
+/// Side of the loupe, in logical px
+pub const LOUPE_SIZE: f32 = 220.0;
+
+/// Gap between the pointer and the loupe, so it never hides what it shows
+const LOUPE_GAP: f32 = 24.0;
+
+/// Where the loupe points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Loupe {
+    /// The point on the preview, relative to it, in logical px
+    pub anchor: Point,
+    /// The same point in sensor UV
+    pub center: (f32, f32),
+    /// Stays where it is instead of following the pointer
+    pub pinned: bool,
+}
+
+impl Loupe {
+    /// A loupe on the middle of a `size` preview
+    pub fn centered(size: Size) -> Self {
+        Self {
+            anchor: Point::new(size.width / 2.0, size.height / 2.0),
+            center: (0.5, 0.5),
+            pinned: false,
+        }
+    }
+}
+
+/// Sensor UV of a point in display-oriented frame UV, undoing the shader's
+/// rotation (`video_shader.wgsl`) for rotation code `rotation`
+pub fn display_to_sensor(rotation: u32, (u, v): (f32, f32)) -> (f32, f32) {
+    match rotation {
+        1 => (1.0 - v, u),
+        2 => (1.0 - u, 1.0 - v),
+        3 => (v, 1.0 - u),
+        _ => (u, v),
+    }
+}
+
+/// Top-left corner of the loupe for `anchor` in a `window`: above and to the
+/// right of it, flipped to the other side near an edge
+pub fn loupe_origin(anchor: Point, window: Size) -> Point {
+    let mut x = anchor.x + LOUPE_GAP;
+    if x + LOUPE_SIZE > window.width {
+        x = anchor.x - LOUPE_GAP - LOUPE_SIZE;
+    }
+    let mut y = anchor.y - LOUPE_GAP - LOUPE_SIZE;
+    if y < 0.0 {
+        y = anchor.y + LOUPE_GAP;
+    }
+    Point::new(
+        x.clamp(0.0, (window.width - LOUPE_SIZE).max(0.0)),
+        y.clamp(0.0, (window.height - LOUPE_SIZE).max(0.0)),
+    )
+}
+
+/// Layer over the preview that steers the loupe
+pub struct LoupeLayer {
+    /// Preview geometry with the frame's display-oriented size
+    mapping: PreviewMapping,
+    rotation: u32,
+    pinned: bool,
+}
+
+impl LoupeLayer {
+    pub fn new(mapping: PreviewMapping, rotation: u32, pinned: bool) -> Self {
+        Self {
+            mapping,
+            rotation,
+            pinned,
+        }
+    }
+
+    /// The point under `position` and its sensor UV, if it is on the frame
+    fn locate(&self, bounds: Rectangle, position: Point) -> Option<(Point, (f32, f32))> {
+        let point = Point::new(position.x - bounds.x, position.y - bounds.y);
+        let frame = screen_to_frame(&self.mapping, bounds.size(), point)?;
+        Some((point, display_to_sensor(self.rotation, frame)))
+    }
+}
+
+impl Widget<Message, Theme, Renderer> for LoupeLayer {
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fill, Length::Fill)
+    }
+
+    fn layout(
+        &mut self,
+        _tree: &mut Tree,
+        _renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::Node::new(limits.max())
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        _renderer: &mut Renderer,
+        _theme: &Theme,
+        _style: &renderer::Style,
+        _layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        // The loupe is drawn above the bars, by the view
+    }
+
+    fn update(
+        &mut self,
+        _tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        match event {
+            Event::Mouse(mouse::Event::CursorMoved { .. }) if !self.pinned => {
+                if let Some(position) = cursor.position_over(bounds)
+                    && let Some((point, center)) = self.locate(bounds, position)
+                {
+                    shell.publish(Message::LoupeMoved(point, center));
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                if let Some(position) = cursor.position_over(bounds)
+                    && let Some((point, center)) = self.locate(bounds, position)
+                {
+                    shell.publish(Message::ToggleLoupePin(point, center));
+                    shell.capture_event();
+                }
+            }
+            Event::Touch(touch::Event::FingerPressed { position, .. })
+                if bounds.contains(*position) =>
+            {
+                if let Some((point, center)) = self.locate(bounds, *position) {
+                    shell.publish(Message::ToggleLoupePin(point, center));
+                    shell.capture_event();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if cursor.is_over(layout.bounds()) {
+            mouse::Interaction::Crosshair
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
+
+impl<'a> From<LoupeLayer> for Element<'a, Message, Theme, Renderer> {
+    fn from(layer: LoupeLayer) -> Self {
+        Element::new(layer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_is_undone_like_the_shader_applies_it() {
+        // The display's top-left corner, for each sensor mounting
+        assert_eq!(display_to_sensor(0, (0.0, 0.0)), (0.0, 0.0));
+        assert_eq!(display_to_sensor(1, (0.0, 0.0)), (1.0, 0.0));
+        assert_eq!(display_to_sensor(2, (0.0, 0.0)), (1.0, 1.0));
+        assert_eq!(display_to_sensor(3, (0.0, 0.0)), (0.0, 1.0));
+        assert_eq!(display_to_sensor(1, (0.5, 0.5)), (0.5, 0.5));
+    }
+
+    #[test]
+    fn loupe_stays_in_the_window_and_off_the_pointer() {
+        let window = Size::new(1280.0, 720.0);
+        let origin = loupe_origin(Point::new(400.0, 400.0), window);
+        assert_eq!(origin, Point::new(424.0, 156.0));
+
+        // Top-right corner: flipped below and to the left
+        let origin = loupe_origin(Point::new(1270.0, 10.0), window);
+        assert_eq!(origin, Point::new(1026.0, 34.0));
+
+        // A window smaller than the loupe pins it to the corner
+        let origin = loupe_origin(Point::new(50.0, 50.0), Size::new(100.0, 100.0));
+        assert_eq!(origin, Point::ORIGIN);
+    }
+}
@@ -6,6 +6,9 @@
 //! The actual video rendering is delegated to the video_widget module
 //! which uses GPU-accelerated RGBA rendering with filter support.
 
+pub mod eyedropper;
+pub mod gestures;
+pub mod loupe;
 pub mod widget;
 
 // Re-export for convenience
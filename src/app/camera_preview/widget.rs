@@ -28,21 +28,36 @@ pub struct FrozenPreviewTransforms {
 }
 
 impl AppModel {
-    /// Whether the preview should be mirrored (front cameras only, not file sources)
-    pub(crate) fn should_mirror_preview(&self) -> bool {
+    /// Whether the selfie mirror is on (front cameras only, not file sources).
+    /// Mirror mode turns it on regardless of the setting.
+    fn selfie_mirror(&self) -> bool {
         let is_back = self
             .available_cameras
             .get(self.current_camera_index)
             .and_then(|c| c.camera_location.as_deref())
             == Some("back");
-        self.config.mirror_preview && !self.current_frame_is_file_source && !is_back
+        (self.config.mirror_preview || self.mirror_mode)
+            && !self.current_frame_is_file_source
+            && !is_back
+    }
+
+    /// Whether the camera's own mirror correction applies (not file sources)
+    fn camera_mirror(&self) -> bool {
+        !self.current_frame_is_file_source && self.camera_orientation().mirrored()
+    }
+
+    /// Whether the preview should be mirrored: the selfie mirror, undone or
+    /// added to by the camera's mirror correction
+    pub(crate) fn should_mirror_preview(&self) -> bool {
+        self.selfie_mirror() != self.camera_mirror()
     }
 
-    /// Whether captured media (photo / video / timelapse) should be mirrored
-    /// to match the preview. Only applies when the preview itself is mirrored
-    /// AND the user has opted in via the `mirror_captures` setting.
+    /// Whether captured media (photo / video / timelapse) should be mirrored.
+    /// The selfie mirror only carries over when the user has opted in via
+    /// the `mirror_captures` setting; the camera's mirror correction always
+    /// does.
     pub(crate) fn should_mirror_captures(&self) -> bool {
-        self.config.mirror_captures && self.should_mirror_preview()
+        (self.config.mirror_captures && self.selfie_mirror()) != self.camera_mirror()
     }
 
     /// Whether the preview is currently painting the *frozen* blur frame —
@@ -96,7 +111,12 @@ impl AppModel {
         let frame = self.current_frame.as_ref()?;
 
         let cover_blend = self.cover_blend();
-        let filter_mode = self.selected_filter;
+        // A capture card is monitored as it is, without the shader pass
+        let filter_mode = if self.capture_card_active() {
+            crate::app::state::FilterType::Standard
+        } else {
+            self.selected_filter
+        };
 
         let live = || FrozenPreviewTransforms {
             rotation: self.current_frame_rotation,
@@ -115,6 +135,7 @@ impl AppModel {
 
         let zoom_level = transforms.zoom;
         let scroll_zoom_enabled = self.mode.supports_fit_and_zoom();
+        let gestures_enabled = self.config.preview_gestures;
 
         let bg = cosmic::theme::active().cosmic().bg_color();
         let letterbox_color = [bg.red, bg.green, bg.blue, 1.0];
@@ -129,10 +150,16 @@ impl AppModel {
             crop_uv,
             zoom_level,
             scroll_zoom_enabled,
+            gestures_enabled,
             cover_blend: Some(cover_blend),
             bar_top_px: self.top_ui_height(),
             bar_bottom_px: self.bottom_ui_height(),
             letterbox_color,
+            false_color: self.config.false_color,
+            color_vision: self.config.color_vision_assist,
+            scaling: self.config.preview_scaling,
+            desqueeze: self.config.anamorphic_desqueeze.factor(),
+            loupe_center: None,
         })
     }
 
@@ -142,7 +169,12 @@ impl AppModel {
     /// Shows a loading indicator when cameras are initializing.
     /// Shows a black placeholder when no camera frame is available.
     /// Shows a blurred last frame during camera transitions.
+    /// Shows the blocked screen while the privacy cover is closed.
     pub fn build_camera_preview(&self) -> Element<'_, Message> {
+        if self.privacy_cover_closed {
+            return self.build_camera_blocked();
+        }
+
         // Show loading indicator if cameras aren't initialized yet
         if self.available_cameras.is_empty() {
             return widget::container(
@@ -233,6 +265,37 @@ impl AppModel {
         })
         .into()
     }
+
+    /// Screen in place of the preview while the camera's privacy cover or
+    /// switch blocks it
+    fn build_camera_blocked(&self) -> Element<'_, Message> {
+        widget::container(
+            widget::Column::new()
+                .push(
+                    widget::icon::from_name("camera-disabled-symbolic")
+                        .symbolic(true)
+                        .size(64),
+                )
+                .push(
+                    widget::text(fl!("privacy-cover-closed"))
+                        .size(20)
+                        .font(cosmic::font::bold()),
+                )
+                .push(widget::text(fl!("privacy-cover-hint")).size(14))
+                .spacing(cosmic::theme::spacing().space_s)
+                .align_x(cosmic::iced::alignment::Horizontal::Center),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .align_x(cosmic::iced::alignment::Horizontal::Center)
+        .align_y(cosmic::iced::alignment::Vertical::Center)
+        .style(|theme| widget::container::Style {
+            background: Some(Background::Color(theme.cosmic().bg_color().into())),
+            text_color: Some(theme.cosmic().on_bg_color().into()),
+            ..Default::default()
+        })
+        .into()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Injectable time source for the UI timers.
+//!
+//! The recording, streaming and timelapse timers and the countdown fade read
+//! the time through `AppModel::clock` instead of calling `Instant::now()`
+//! directly. The app normally runs on [`SystemClock`]; the preview harness
+//! and the overlay snapshot tests use a [`ManualClock`] so the rendered
+//! durations and fade levels don't depend on when the screenshot or test
+//! happened to run.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Source of the current time for UI state
+pub trait Clock: Send + Sync + std::fmt::Debug {
+    fn now(&self) -> Instant;
+}
+
+/// Clock handle shared by the app model and its handlers
+pub type SharedClock = Arc<dyn Clock>;
+
+/// The real monotonic clock
+#[derive(Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only moves when told to
+#[derive(Debug)]
+pub struct ManualClock {
+    now: Mutex<Instant>,
+}
+
+impl ManualClock {
+    /// Start the clock frozen at `start`
+    pub fn new(start: Instant) -> Self {
+        Self {
+            now: Mutex::new(start),
+        }
+    }
+
+    /// Move the clock forward by `by`
+    pub fn advance(&self, by: Duration) {
+        let mut now = self.now.lock().unwrap_or_else(|e| e.into_inner());
+        *now += by;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// The clock the app runs on outside of tests and the preview harness
+pub fn system() -> SharedClock {
+    Arc::new(SystemClock)
+}
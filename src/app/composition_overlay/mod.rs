@@ -3,12 +3,14 @@
 //! Composition guide overlay module
 //!
 //! Renders composition guide lines (Rule of Thirds, Phi Grid, etc.)
-//! on top of the camera preview using a canvas widget.
+//! on top of the camera preview using a canvas widget. In Video mode with
+//! stabilization on, it also outlines the part of the frame that will be
+//! recorded.
 
 mod widget;
 
 use crate::app::state::{AppModel, CameraMode, Message};
-use crate::config::CompositionGuide;
+use crate::config::{CompositionGuide, VideoStabilization};
 use cosmic::Element;
 use cosmic::iced::Length;
 
@@ -28,7 +30,11 @@ impl AppModel {
     /// ratio crop, and the bottom-bar scrim height (which differs by mode
     /// and animates across mode switches).
     pub fn build_composition_overlay(&self) -> Element<'_, Message> {
-        if self.config.composition_guide == CompositionGuide::None {
+        let stabilization_margin = (self.mode == CameraMode::Video
+            && self.config.video_stabilization != VideoStabilization::Off)
+            .then(|| self.config.video_stabilization.crop_margin());
+        if self.config.composition_guide == CompositionGuide::None && stabilization_margin.is_none()
+        {
             return empty_overlay();
         }
 
@@ -69,6 +75,7 @@ impl AppModel {
             // settle at 0 and the guide aligns with the full window.
             self.top_ui_height(),
             self.bottom_ui_height(),
+            stabilization_margin,
         )
     }
 }
@@ -10,6 +10,9 @@ use cosmic::widget::canvas;
 /// Semi-transparent white, 40% opacity
 const LINE_COLOR: Color = Color::from_rgba(1.0, 1.0, 1.0, 0.4);
 const LINE_WIDTH: f32 = 1.5;
+/// Outline of the stabilization crop, brighter than the guides so it reads
+/// as the edge of the recording
+const CROP_COLOR: Color = Color::from_rgba(1.0, 0.85, 0.3, 0.8);
 const PHI: f32 = 1.618_034;
 
 /// Inputs needed to compute the visible-video rectangle at draw time. The
@@ -31,6 +34,9 @@ struct GuideProgram {
     top_bar_h: f32,
     /// Bottom UI scrim height in pixels — likewise.
     bottom_bar_h: f32,
+    /// Fraction cropped from each edge by video stabilization, if on. The
+    /// crop is outlined and the guide drawn inside it.
+    stabilization_margin: Option<f32>,
 }
 
 impl GuideProgram {
@@ -163,6 +169,25 @@ impl canvas::Program<Message, cosmic::Theme> for GuideProgram {
             return vec![frame.into_geometry()];
         }
 
+        let vb = match self.stabilization_margin {
+            Some(margin) => {
+                let crop = Rectangle {
+                    x: vb.x + vb.width * margin,
+                    y: vb.y + vb.height * margin,
+                    width: vb.width * (1.0 - 2.0 * margin),
+                    height: vb.height * (1.0 - 2.0 * margin),
+                };
+                frame.stroke(
+                    &canvas::path::Path::rectangle(crop.position(), crop.size()),
+                    canvas::Stroke::default()
+                        .with_color(CROP_COLOR)
+                        .with_width(LINE_WIDTH),
+                );
+                crop
+            }
+            None => vb,
+        };
+
         frame.with_clip(vb, |frame| match self.guide {
             CompositionGuide::RuleOfThirds => {
                 draw_grid_lines(frame, vb, 1.0 / 3.0, 2.0 / 3.0, stroke);
@@ -361,6 +386,7 @@ pub fn composition_canvas<'a>(
     cover_blend: f32,
     top_bar_h: f32,
     bottom_bar_h: f32,
+    stabilization_margin: Option<f32>,
 ) -> cosmic::Element<'a, Message> {
     cosmic::widget::Canvas::new(GuideProgram {
         guide,
@@ -370,6 +396,7 @@ pub fn composition_canvas<'a>(
         cover_blend,
         top_bar_h,
         bottom_bar_h,
+        stabilization_margin,
     })
     .width(Length::Fill)
     .height(Length::Fill)
@@ -0,0 +1,248 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Per-mode placement of the optional overlay controls.
+//!
+//! Each [`Control`] can sit in the top bar, in the tools menu, or be hidden,
+//! independently per camera mode. Only user overrides are persisted (like key
+//! bindings); anything not overridden falls back to
+//! [`Control::default_placement`], so new controls get sane defaults without a
+//! config migration.
+
+use crate::app::CameraMode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// An overlay control whose placement the user can change.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum Control {
+    Flash,
+    /// Torch kept lit as a flashlight
+    Torch,
+    HdrPlus,
+    Timer,
+    AspectRatio,
+    Exposure,
+    Color,
+    Filter,
+    Motor,
+    /// Switch to mirror mode
+    Mirror,
+    /// Open the "Show QR" composer
+    ShowQr,
+    /// Open the 1:1 focus loupe
+    Loupe,
+    /// Gallery thumbnail at the left of the bottom bar
+    Gallery,
+    /// Camera switcher at the right of the bottom bar
+    CameraSwitcher,
+    /// Fit/fill and zoom chips above the capture button
+    Zoom,
+}
+
+/// Where a control is shown.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum Placement {
+    /// Its own button on the preview: top-bar chip for tools, the usual spot
+    /// for the bottom-bar and zoom controls
+    Bar,
+    /// Inside the tools menu grid
+    Tools,
+    Hidden,
+}
+
+/// Modes with a layout to edit. View mode has no optional controls.
+pub const EDITABLE_MODES: [CameraMode; 4] = [
+    CameraMode::Photo,
+    CameraMode::Video,
+    CameraMode::Timelapse,
+    CameraMode::Virtual,
+];
+
+impl Control {
+    /// All controls, in top bar / tools menu order
+    pub const ALL: [Control; 15] = [
+        Control::Flash,
+        Control::Torch,
+        Control::HdrPlus,
+        Control::Timer,
+        Control::AspectRatio,
+        Control::Exposure,
+        Control::Color,
+        Control::Filter,
+        Control::Motor,
+        Control::Mirror,
+        Control::ShowQr,
+        Control::Loupe,
+        Control::Gallery,
+        Control::CameraSwitcher,
+        Control::Zoom,
+    ];
+
+    /// Placement when the user hasn't chosen one
+    pub fn default_placement(self) -> Placement {
+        match self {
+            Control::Flash | Control::HdrPlus => Placement::Bar,
+            Control::Torch
+            | Control::Timer
+            | Control::AspectRatio
+            | Control::Exposure
+            | Control::Color
+            | Control::Filter
+            | Control::Motor
+            | Control::Mirror
+            | Control::ShowQr
+            | Control::Loupe => Placement::Tools,
+            Control::Gallery | Control::CameraSwitcher | Control::Zoom => Placement::Bar,
+        }
+    }
+
+    /// Placements the control supports. Bottom-bar and zoom controls have a
+    /// fixed spot and can only be shown or hidden.
+    pub fn placements(self) -> &'static [Placement] {
+        match self {
+            Control::Gallery | Control::CameraSwitcher | Control::Zoom => {
+                &[Placement::Bar, Placement::Hidden]
+            }
+            _ => &[Placement::Bar, Placement::Tools, Placement::Hidden],
+        }
+    }
+
+    /// Whether the control exists at all in `mode`. Hardware-dependent
+    /// controls (motor, flash in video) are still listed so the layout is
+    /// ready when such a camera is connected.
+    pub fn applies_to(self, mode: CameraMode) -> bool {
+        match self {
+            Control::Timer | Control::AspectRatio | Control::Mirror => mode == CameraMode::Photo,
+            Control::Flash | Control::Torch | Control::HdrPlus => matches!(
+                mode,
+                CameraMode::Photo | CameraMode::Video | CameraMode::Timelapse
+            ),
+            Control::Zoom => mode.supports_fit_and_zoom(),
+            _ => !mode.is_view_only(),
+        }
+    }
+}
+
+/// User overrides of the default control placements, keyed by mode and
+/// control.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ControlLayout {
+    overrides: HashMap<(CameraMode, Control), Placement>,
+}
+
+impl ControlLayout {
+    /// Effective placement of `control` in `mode`
+    pub fn placement(&self, mode: CameraMode, control: Control) -> Placement {
+        self.overrides
+            .get(&(mode, control))
+            .copied()
+            .filter(|p| control.placements().contains(p))
+            .unwrap_or_else(|| control.default_placement())
+    }
+
+    /// Place `control` in `mode`. Choosing the default drops the override.
+    pub fn set(&mut self, mode: CameraMode, control: Control, placement: Placement) {
+        if placement == control.default_placement() {
+            self.overrides.remove(&(mode, control));
+        } else {
+            self.overrides.insert((mode, control), placement);
+        }
+    }
+
+    /// Drop every override for `mode`
+    pub fn reset(&mut self, mode: CameraMode) {
+        self.overrides.retain(|(m, _), _| *m != mode);
+    }
+
+    /// Whether `mode` uses any non-default placement
+    pub fn is_customized(&self, mode: CameraMode) -> bool {
+        self.overrides.keys().any(|(m, _)| *m == mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_match_the_stock_layout() {
+        let layout = ControlLayout::default();
+        assert_eq!(
+            layout.placement(CameraMode::Photo, Control::Flash),
+            Placement::Bar
+        );
+        assert_eq!(
+            layout.placement(CameraMode::Video, Control::Filter),
+            Placement::Tools
+        );
+        assert_eq!(
+            layout.placement(CameraMode::Photo, Control::Gallery),
+            Placement::Bar
+        );
+    }
+
+    #[test]
+    fn overrides_are_per_mode() {
+        let mut layout = ControlLayout::default();
+        layout.set(CameraMode::Video, Control::Filter, Placement::Hidden);
+        layout.set(CameraMode::Photo, Control::Timer, Placement::Bar);
+
+        assert_eq!(
+            layout.placement(CameraMode::Video, Control::Filter),
+            Placement::Hidden
+        );
+        assert_eq!(
+            layout.placement(CameraMode::Photo, Control::Filter),
+            Placement::Tools
+        );
+        assert_eq!(
+            layout.placement(CameraMode::Photo, Control::Timer),
+            Placement::Bar
+        );
+        assert!(layout.is_customized(CameraMode::Video));
+        assert!(!layout.is_customized(CameraMode::Timelapse));
+    }
+
+    #[test]
+    fn choosing_the_default_drops_the_override() {
+        let mut layout = ControlLayout::default();
+        layout.set(CameraMode::Photo, Control::Exposure, Placement::Bar);
+        layout.set(CameraMode::Photo, Control::Exposure, Placement::Tools);
+        assert_eq!(layout, ControlLayout::default());
+    }
+
+    #[test]
+    fn reset_only_touches_one_mode() {
+        let mut layout = ControlLayout::default();
+        layout.set(CameraMode::Photo, Control::Flash, Placement::Hidden);
+        layout.set(CameraMode::Video, Control::Flash, Placement::Tools);
+        layout.reset(CameraMode::Photo);
+
+        assert!(!layout.is_customized(CameraMode::Photo));
+        assert_eq!(
+            layout.placement(CameraMode::Video, Control::Flash),
+            Placement::Tools
+        );
+    }
+
+    /// A stored placement the control can't take (e.g. a hand-edited config
+    /// putting the gallery in the tools menu) falls back to the default.
+    #[test]
+    fn unsupported_override_falls_back() {
+        let mut layout = ControlLayout::default();
+        layout
+            .overrides
+            .insert((CameraMode::Photo, Control::Gallery), Placement::Tools);
+        assert_eq!(
+            layout.placement(CameraMode::Photo, Control::Gallery),
+            Placement::Bar
+        );
+    }
+
+    #[test]
+    fn every_default_is_a_supported_placement() {
+        for control in Control::ALL {
+            assert!(control.placements().contains(&control.default_placement()));
+        }
+    }
+}
@@ -2,6 +2,7 @@
 
 //! Capture button widget implementation
 
+use super::focus_ring::FocusRing;
 use crate::app::state::{AppModel, CameraMode, Message, PhotoTimerSetting};
 use crate::constants::ui;
 use cosmic::Element;
@@ -189,6 +190,15 @@ impl AppModel {
                 }
                 CameraMode::Video => destructive,
                 CameraMode::Timelapse => destructive,
+                CameraMode::Document | CameraMode::Whiteboard => accent,
+                // Red while decoding, like a session that is running
+                CameraMode::Scan => {
+                    if self.scan.paused {
+                        accent
+                    } else {
+                        destructive
+                    }
+                }
                 // View hides the capture button entirely; this color is
                 // only used if the build path is reached, which it isn't.
                 CameraMode::View => accent,
@@ -236,18 +246,32 @@ impl AppModel {
                 CameraMode::Video => Message::ToggleRecording,
                 CameraMode::Virtual => Message::ToggleVirtualCamera,
                 CameraMode::Timelapse => Message::ToggleTimelapse,
+                CameraMode::Document => Message::CaptureDocumentPage,
+                CameraMode::Whiteboard => Message::CaptureWhiteboard,
+                CameraMode::Scan => Message::ToggleScanning,
                 // View mode hides the capture button entirely (see
                 // `view::capture_button_only`); this branch is unreachable
                 // in practice but needs to compile.
                 CameraMode::View => Message::Noop,
             };
+            // From the keyboard there is no hold to tell apart from a tap,
+            // so a photo is taken straight away, as with the Capture shortcut
+            let activate_message = if self.mode == CameraMode::Photo {
+                Message::Capture
+            } else {
+                press_message.clone()
+            };
             let mut area = widget::mouse_area(circle)
                 .on_press(press_message)
                 .interaction(cosmic::iced::mouse::Interaction::Pointer);
             if self.mode == CameraMode::Photo {
                 area = area.on_release(Message::CaptureButtonReleased);
             }
-            area.into()
+            let radius = theme_radius(
+                cosmic_theme.corner_radii.radius_xl[0],
+                CAPTURE_BUTTON_OUTER_SIZE,
+            );
+            FocusRing::new(area, activate_message, radius).into()
         }
     }
 
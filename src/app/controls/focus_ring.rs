@@ -0,0 +1,254 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Keyboard focus for controls built from `mouse_area`
+//!
+//! The capture button and the gallery thumbnail draw their own look and
+//! take clicks through a `mouse_area`, which iced's focus traversal skips.
+//! [`FocusRing`] wraps such a control so Tab reaches it: while focused it
+//! draws an accent ring around it, and Enter or Space sends the control's
+//! message as a click would.
+
+use crate::app::state::Message;
+use cosmic::iced::advanced::widget::operation::Focusable;
+use cosmic::iced::advanced::widget::{Operation, Tree, tree};
+use cosmic::iced::advanced::{Clipboard, Layout, Shell, Widget, layout, overlay, renderer};
+use cosmic::iced::keyboard::{self, key::Named};
+use cosmic::iced::mouse;
+use cosmic::iced::{Background, Border, Color, Element, Event, Length, Rectangle, Size, Vector};
+use cosmic::{Renderer, Theme};
+
+/// Gap between the control and its focus ring
+const RING_GAP: f32 = 3.0;
+
+/// Width of the focus ring
+const RING_WIDTH: f32 = 2.0;
+
+#[derive(Debug, Default)]
+struct FocusState {
+    focused: bool,
+}
+
+impl Focusable for FocusState {
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+/// A control that Tab can focus and Enter or Space can activate
+pub struct FocusRing<'a> {
+    content: Element<'a, Message, Theme, Renderer>,
+    on_activate: Message,
+    /// Corner radius of the control; the ring follows it
+    radius: f32,
+}
+
+impl<'a> FocusRing<'a> {
+    pub fn new(
+        content: impl Into<Element<'a, Message, Theme, Renderer>>,
+        on_activate: Message,
+        radius: f32,
+    ) -> Self {
+        Self {
+            content: content.into(),
+            on_activate,
+            radius,
+        }
+    }
+}
+
+impl<'a> Widget<Message, Theme, Renderer> for FocusRing<'a> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<FocusState>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(FocusState::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&mut self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_mut(&mut self.content));
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.content.as_widget().size_hint()
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let node = self
+            .content
+            .as_widget_mut()
+            .layout(&mut tree.children[0], renderer, limits);
+        layout::Node::with_children(node.size(), vec![node])
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let content_layout = layout.children().next().unwrap_or(layout);
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            content_layout,
+            cursor,
+            viewport,
+        );
+
+        if !tree.state.downcast_ref::<FocusState>().focused {
+            return;
+        }
+        use cosmic::iced::advanced::Renderer as _;
+        let bounds = content_layout.bounds().expand(RING_GAP + RING_WIDTH);
+        let accent: Color = theme.cosmic().accent_color().into();
+        renderer.with_layer(bounds, |renderer| {
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds,
+                    border: Border {
+                        color: accent,
+                        width: RING_WIDTH,
+                        radius: (self.radius + RING_GAP + RING_WIDTH).into(),
+                    },
+                    shadow: Default::default(),
+                    snap: true,
+                },
+                Background::Color(Color::TRANSPARENT),
+            );
+        });
+    }
+
+    fn operate(
+        &mut self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<()>,
+    ) {
+        let content_layout = layout.children().next().unwrap_or(layout);
+        operation.focusable(
+            None,
+            content_layout.bounds(),
+            tree.state.downcast_mut::<FocusState>(),
+        );
+        self.content.as_widget_mut().operate(
+            &mut tree.children[0],
+            content_layout,
+            renderer,
+            operation,
+        );
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_mut::<FocusState>();
+        match event {
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(Named::Enter | Named::Space),
+                ..
+            }) if state.focused => {
+                shell.publish(self.on_activate.clone());
+                shell.capture_event();
+                return;
+            }
+            // The ring is for keyboard users; a click hands control back to
+            // the pointer
+            Event::Mouse(mouse::Event::ButtonPressed(_)) | Event::Touch(_) if state.focused => {
+                state.focused = false;
+                shell.request_redraw();
+            }
+            _ => {}
+        }
+
+        let content_layout = layout.children().next().unwrap_or(layout);
+        self.content.as_widget_mut().update(
+            &mut tree.children[0],
+            event,
+            content_layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let content_layout = layout.children().next().unwrap_or(layout);
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            content_layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'b>,
+        renderer: &Renderer,
+        viewport: &Rectangle,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        let content_layout = layout.children().next().unwrap_or(layout);
+        self.content.as_widget_mut().overlay(
+            &mut tree.children[0],
+            content_layout,
+            renderer,
+            viewport,
+            translation,
+        )
+    }
+}
+
+impl<'a> From<FocusRing<'a>> for Element<'a, Message, Theme, Renderer> {
+    fn from(widget: FocusRing<'a>) -> Self {
+        Element::new(widget)
+    }
+}
@@ -8,6 +8,7 @@
 
 pub mod audio_meter;
 pub mod capture_button;
+pub mod focus_ring;
 pub mod recording_ui;
 
 // Re-export for convenience
@@ -2,6 +2,7 @@
 
 //! Recording and streaming UI components (indicator and timer)
 
+use crate::app::overlay_snapshot::{ClipIndicator, TimelapseIndicator};
 use crate::app::overlay_style::OVERLAY_CONTAINER;
 use crate::app::state::{AppModel, CameraMode, FileSource, Message};
 use crate::fl;
@@ -27,16 +28,6 @@ fn indicator_dot<'a>(color: Color) -> Element<'a, Message> {
     .into()
 }
 
-/// Format duration as MM:SS
-fn format_duration(seconds: u64) -> String {
-    format!("{:02}:{:02}", seconds / 60, seconds % 60)
-}
-
-/// Placeholder elapsed time shown by the recording indicator under
-/// `--preview-spoof-recording`, so the screenshot reads a believable duration
-/// (00:07) deterministically rather than whatever the live timer happened to be.
-const PREVIEW_SPOOF_RECORDING_SECS: u64 = 7;
-
 impl AppModel {
     /// Wrap a status-indicator `row` (dot + label) in the standard frosted
     /// overlay pill shared by the recording, streaming and timelapse indicators.
@@ -63,17 +54,9 @@ impl AppModel {
     /// Shows a red dot and elapsed time when recording is active.
     /// Returns None when not recording.
     pub fn build_recording_indicator<'a>(&self) -> Option<Element<'a, Message>> {
-        if !self.recording.is_recording() {
-            return None;
-        }
+        let duration_text = self.overlay_snapshot().recording?;
 
         let spacing = cosmic::theme::spacing();
-        let elapsed = if self.preview_spoof_recording {
-            PREVIEW_SPOOF_RECORDING_SECS
-        } else {
-            self.recording.elapsed_duration()
-        };
-        let duration_text = format_duration(elapsed);
 
         let row = widget::Row::new()
             .push(indicator_dot(Color::from_rgb(1.0, 0.0, 0.0)))
@@ -104,31 +87,352 @@ impl AppModel {
         Some(self.indicator_pill(row))
     }
 
+    /// Build the failover indicator widget
+    ///
+    /// Shows an amber dot and "Backup camera" while a recording or stream
+    /// continues on the standby camera after the primary camera failed.
+    /// Returns None otherwise.
+    pub fn build_failover_indicator<'a>(&self) -> Option<Element<'a, Message>> {
+        let session_active = self.recording.is_recording() || self.virtual_camera.is_streaming();
+        let on_standby = self
+            .available_cameras
+            .get(self.current_camera_index)
+            .is_some_and(|cam| self.failover_camera.as_ref() == Some(&cam.path));
+        if !session_active || !on_standby {
+            return None;
+        }
+
+        let spacing = cosmic::theme::spacing();
+
+        let row = widget::Row::new()
+            .push(indicator_dot(Color::from_rgb(1.0, 0.7, 0.0)))
+            .push(widget::text(fl!("failover-active")).size(14))
+            .align_y(Alignment::Center)
+            .spacing(spacing.space_xxs);
+
+        Some(self.indicator_pill(row))
+    }
+
+    /// Build the exposure/focus lock indicator widget
+    ///
+    /// Shows a yellow dot and "AE/AF lock" while a long-press lock is held,
+    /// and "AE-L" / "AWB-L" for the held automatic controls.
+    /// Returns None when nothing is locked.
+    pub fn build_lock_indicator<'a>(&self) -> Option<Element<'a, Message>> {
+        use crate::backends::camera::v4l2_controls::AutoControl;
+
+        let mut locks = Vec::new();
+        if self.ae_af_lock.is_some() {
+            locks.push(fl!("ae-af-lock"));
+        }
+        if self.auto_holds.contains_key(&AutoControl::Exposure) {
+            locks.push(fl!("exposure-hold"));
+        }
+        if self.auto_holds.contains_key(&AutoControl::WhiteBalance) {
+            locks.push(fl!("color-hold"));
+        }
+        if locks.is_empty() {
+            return None;
+        }
+
+        let spacing = cosmic::theme::spacing();
+
+        let row = widget::Row::new()
+            .push(indicator_dot(Color::from_rgb(1.0, 0.85, 0.0)))
+            .push(widget::text(locks.join(" · ")).size(14))
+            .align_y(Alignment::Center)
+            .spacing(spacing.space_xxs);
+
+        Some(self.indicator_pill(row))
+    }
+
+    /// Build the exposure/focus OSD widget
+    ///
+    /// Shows the live exposure time, gain or ISO, white balance temperature
+    /// and focus position when the OSD is on. Returns None when it is off or
+    /// the camera reports none of them.
+    pub fn build_exposure_osd_indicator<'a>(&self) -> Option<Element<'a, Message>> {
+        let labels = self.exposure_osd_values()?.labels();
+
+        let spacing = cosmic::theme::spacing();
+
+        let row = labels
+            .into_iter()
+            .fold(widget::Row::new(), |row, label| {
+                row.push(widget::text(label).size(14).font(cosmic::font::mono()))
+            })
+            .align_y(Alignment::Center)
+            .spacing(spacing.space_s);
+
+        Some(self.indicator_pill(row))
+    }
+
+    /// Build the capture card indicator: the mode, and the last measured
+    /// latency. Returns None unless a capture card runs in capture card mode.
+    pub fn build_capture_card_indicator<'a>(&self) -> Option<Element<'a, Message>> {
+        use crate::app::latency_probe::ProbeOutcome;
+
+        if !self.capture_card_active() {
+            return None;
+        }
+        let label = match self.measured_latency {
+            Some(ProbeOutcome::Measured(latency)) => {
+                fl!("capture-card-latency", ms = latency.as_millis() as u64)
+            }
+            _ => fl!("capture-card"),
+        };
+        let row = widget::Row::new()
+            .push(widget::text(label).size(14))
+            .align_y(Alignment::Center);
+        Some(self.indicator_pill(row))
+    }
+
     /// Build the timelapse indicator widget
     ///
     /// Shows an orange dot, shot count, and elapsed time when timelapse is active.
     /// Shows "Assembling..." when building the video.
     /// Returns None when timelapse is idle.
     pub fn build_timelapse_indicator<'a>(&self) -> Option<Element<'a, Message>> {
-        if !self.timelapse.is_active() {
+        let label = match self.overlay_snapshot().timelapse? {
+            TimelapseIndicator::Saving => fl!("timelapse-saving"),
+            TimelapseIndicator::Capturing { shots, elapsed } => {
+                format!("{shots} shots - {elapsed}")
+            }
+        };
+
+        let spacing = cosmic::theme::spacing();
+
+        let theme = cosmic::theme::active();
+        let destructive: Color = theme.cosmic().destructive_color().into();
+
+        let row = widget::Row::new()
+            .push(indicator_dot(destructive))
+            .push(widget::text(label).size(14))
+            .align_y(Alignment::Center)
+            .spacing(spacing.space_xxs);
+
+        Some(self.indicator_pill(row))
+    }
+
+    /// Build the document scan indicator widget
+    ///
+    /// In Document mode, shows the page count with the auto-capture switch
+    /// and the buttons that save the pages as a PDF or drop them.
+    /// Returns None in other modes.
+    pub fn build_document_indicator<'a>(&self) -> Option<Element<'a, Message>> {
+        if self.mode != CameraMode::Document {
             return None;
         }
 
         let spacing = cosmic::theme::spacing();
+        let theme = cosmic::theme::active();
+        let dot_color: Color = if self.config.document_auto_capture {
+            theme.cosmic().accent_color().into()
+        } else {
+            Color::from_rgba(0.5, 0.5, 0.5, 0.8)
+        };
 
-        let label = if self.timelapse.is_finalising() {
-            fl!("timelapse-saving")
+        let pages = self.document.pages.len();
+        let label = if self.document.saving {
+            fl!("document-saving")
+        } else if self.document.reading_text {
+            fl!("document-reading-text")
         } else {
-            let taken = self.timelapse.shots_taken();
-            let elapsed = format_duration(self.timelapse.elapsed_duration());
-            format!("{taken} shots - {elapsed}")
+            fl!("document-pages", count = pages)
         };
+        let idle = !self.document.is_busy();
+
+        let auto = widget::button::text(fl!("document-auto-capture"))
+            .class(if self.config.document_auto_capture {
+                cosmic::theme::Button::Suggested
+            } else {
+                cosmic::theme::Button::Text
+            })
+            .on_press(Message::ToggleDocumentAutoCapture);
+        let mut save = widget::button::text(fl!("document-save-pdf"));
+        let mut copy_text = widget::button::text(fl!("document-copy-text"));
+        let mut discard = widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+            .tooltip(fl!("a11y-discard"));
+        if idle && pages > 0 {
+            save = save.on_press(Message::SaveDocument);
+            copy_text = copy_text.on_press(Message::CopyDocumentText);
+            discard = discard.on_press(Message::DiscardDocument);
+        }
+
+        let mut row = widget::Row::new()
+            .push(indicator_dot(dot_color))
+            .push(widget::text(label).size(14))
+            .push(auto)
+            .push(save);
+        if crate::media::ocr::AVAILABLE {
+            row = row.push(copy_text);
+        }
+        let row = row
+            .push(discard)
+            .align_y(Alignment::Center)
+            .spacing(spacing.space_xxs);
 
+        Some(self.indicator_pill(row))
+    }
+
+    /// Build the whiteboard indicator widget
+    ///
+    /// In Whiteboard mode, shows the snapshot count with the keystone lock,
+    /// the auto-snapshot switch, and the buttons that save the snapshots as
+    /// a PDF or drop them. Returns None in other modes.
+    pub fn build_whiteboard_indicator<'a>(&self) -> Option<Element<'a, Message>> {
+        if self.mode != CameraMode::Whiteboard {
+            return None;
+        }
+
+        let spacing = cosmic::theme::spacing();
         let theme = cosmic::theme::active();
-        let destructive: Color = theme.cosmic().destructive_color().into();
+        let dot_color: Color = if self.config.whiteboard_auto_snapshot {
+            theme.cosmic().accent_color().into()
+        } else {
+            Color::from_rgba(0.5, 0.5, 0.5, 0.8)
+        };
+
+        let snapshots = self.whiteboard.pages.len();
+        let label = if self.whiteboard.saving {
+            fl!("document-saving")
+        } else {
+            fl!("whiteboard-snapshots", count = snapshots)
+        };
+        let idle = !self.whiteboard.is_busy();
+        let locked = self.whiteboard_keystone().is_some();
+
+        let mut lock = widget::button::text(if locked {
+            fl!("whiteboard-unlock")
+        } else {
+            fl!("whiteboard-lock")
+        })
+        .class(if locked {
+            cosmic::theme::Button::Suggested
+        } else {
+            cosmic::theme::Button::Text
+        });
+        if locked || self.whiteboard.detected.is_some() {
+            lock = lock.on_press(Message::ToggleWhiteboardLock);
+        }
+        let auto = widget::button::text(fl!("document-auto-capture"))
+            .class(if self.config.whiteboard_auto_snapshot {
+                cosmic::theme::Button::Suggested
+            } else {
+                cosmic::theme::Button::Text
+            })
+            .on_press(Message::ToggleWhiteboardAutoSnapshot);
+        let mut save = widget::button::text(fl!("document-save-pdf"));
+        let mut discard = widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+            .tooltip(fl!("a11y-discard"));
+        if idle && snapshots > 0 {
+            save = save.on_press(Message::SaveWhiteboardPdf);
+            discard = discard.on_press(Message::DiscardWhiteboard);
+        }
 
         let row = widget::Row::new()
-            .push(indicator_dot(destructive))
+            .push(indicator_dot(dot_color))
+            .push(widget::text(label).size(14))
+            .push(lock)
+            .push(auto)
+            .push(save)
+            .push(discard)
+            .align_y(Alignment::Center)
+            .spacing(spacing.space_xxs);
+
+        Some(self.indicator_pill(row))
+    }
+
+    /// Build the scan indicator widget
+    ///
+    /// In Scan mode, shows the code count and the newest code with the beep
+    /// switch and the buttons that save the list as a CSV or drop it.
+    /// Returns None in other modes.
+    pub fn build_scan_indicator<'a>(&self) -> Option<Element<'a, Message>> {
+        /// Characters of the newest code shown; the rest is elided
+        const LATEST_CHARS: usize = 24;
+
+        if self.mode != CameraMode::Scan {
+            return None;
+        }
+
+        let spacing = cosmic::theme::spacing();
+        let theme = cosmic::theme::active();
+        let dot_color: Color = if self.scan.paused {
+            Color::from_rgba(0.5, 0.5, 0.5, 0.8)
+        } else {
+            theme.cosmic().accent_color().into()
+        };
+
+        let codes = self.scan.list.codes.len();
+        let label = if self.scan.exporting {
+            fl!("scan-exporting")
+        } else if self.scan.paused {
+            fl!("scan-paused")
+        } else {
+            fl!("scan-codes", count = codes)
+        };
+        let idle = !self.scan.exporting;
+
+        let beep = widget::button::icon(widget::icon::from_name(if self.config.scan_beep {
+            "audio-volume-high-symbolic"
+        } else {
+            "audio-volume-muted-symbolic"
+        }))
+        .tooltip(fl!("a11y-scan-beep"))
+        .on_press(Message::ToggleScanBeep);
+        let mut export = widget::button::text(fl!("scan-export-csv"));
+        let mut discard = widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+            .tooltip(fl!("a11y-discard"));
+        if idle && codes > 0 {
+            export = export.on_press(Message::ExportScans);
+            discard = discard.on_press(Message::ClearScans);
+        }
+
+        let mut row = widget::Row::new()
+            .push(indicator_dot(dot_color))
+            .push(widget::text(label).size(14));
+        if let Some(latest) = self.scan.list.codes.last() {
+            let mut text: String = latest.content.chars().take(LATEST_CHARS).collect();
+            if text.len() < latest.content.len() {
+                text.push('…');
+            }
+            row = row.push(widget::text(text).size(14).font(cosmic::font::mono()));
+        }
+        let row = row
+            .push(beep)
+            .push(export)
+            .push(discard)
+            .align_y(Alignment::Center)
+            .spacing(spacing.space_xxs);
+
+        Some(self.indicator_pill(row))
+    }
+
+    /// Build the animated clip indicator widget
+    ///
+    /// Shows a red dot and frame progress while a GIF/WebP clip is captured,
+    /// then "Saving GIF..." while the file is written.
+    /// Returns None when no clip is in progress.
+    pub fn build_animated_clip_indicator<'a>(&self) -> Option<Element<'a, Message>> {
+        let label = match self.overlay_snapshot().clip? {
+            ClipIndicator::Capturing {
+                format,
+                frame,
+                total,
+            } => fl!(
+                "animated-clip-progress",
+                format = format.label(),
+                frame = frame,
+                total = total
+            ),
+            ClipIndicator::Saving(format) => fl!("animated-clip-saving", format = format.label()),
+        };
+
+        let spacing = cosmic::theme::spacing();
+
+        let row = widget::Row::new()
+            .push(indicator_dot(Color::from_rgb(1.0, 0.0, 0.0)))
             .push(widget::text(label).size(14))
             .align_y(Alignment::Center)
             .spacing(spacing.space_xxs);
@@ -190,6 +494,7 @@ impl AppModel {
         };
 
         let button = widget::button::icon(widget::icon::from_name(icon_name))
+            .tooltip(fl!("a11y-play-pause"))
             .on_press(Message::ToggleVideoPlayPause)
             .class(cosmic::theme::Button::Standard);
 
@@ -0,0 +1,276 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Developer scripting console (`dev-console` feature)
+//!
+//! Started with `camera --console`, the running app reads [Rhai] from
+//! stdin and exposes high-level commands to it, for trying things out,
+//! demos and reproducing bug conditions by hand:
+//!
+//! ```text
+//! camera> cameras()
+//! ["Integrated Camera", "USB Camera"]
+//! camera> switch_camera(1); mode("video")
+//! camera> set_control("gain", 40); wait(500); capture()
+//! camera> state().format
+//! "1920x1080 @ 30fps MJPG"
+//! ```
+//!
+//! Each line is evaluated on its own, keeping variables between lines. Piped
+//! input (`camera --console < repro.rhai`) is run as one script instead.
+//!
+//! Scripts run on their own thread. Commands go to the app as
+//! [`Message::DevConsole`] and block until the app has handled them, so a
+//! script sees the state its previous commands left behind (work the app
+//! finishes in the background, like opening a camera, can take longer; use
+//! `wait`).
+//!
+//! [Rhai]: https://rhai.rs
+
+use super::state::{AppModel, CameraMode, Message};
+use crate::app::keybind::Action;
+use cosmic::Task;
+use cosmic::iced::Subscription;
+use futures::{SinkExt, StreamExt};
+use rhai::{Dynamic, Engine, EvalAltResult, Scope};
+use std::io::{BufRead, IsTerminal, Write};
+use std::sync::mpsc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Longest a command waits for the app to handle it
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Prompt shown before each line read from a terminal
+const PROMPT: &str = "camera> ";
+
+/// Something a script asks of the app
+#[derive(Debug, Clone)]
+pub enum ConsoleCommand {
+    /// Snapshot of the app state
+    State,
+    /// Dispatch a message, as a click or key press would
+    Send(Box<Message>),
+}
+
+/// A command and where its result goes
+#[derive(Debug, Clone)]
+pub struct ConsoleRequest {
+    pub command: ConsoleCommand,
+    pub reply: mpsc::Sender<serde_json::Value>,
+}
+
+/// Subscription that runs the console on a thread of its own and forwards
+/// its commands to the app
+pub fn subscription() -> Subscription<Message> {
+    super::subscription_with_id(
+        "dev_console",
+        cosmic::iced::stream::channel(8, async move |mut output| {
+            let (sender, mut requests) = futures::channel::mpsc::unbounded();
+            std::thread::spawn(move || run(sender));
+            while let Some(request) = requests.next().await {
+                if output.send(Message::DevConsole(request)).await.is_err() {
+                    break;
+                }
+            }
+        }),
+    )
+}
+
+/// Read and evaluate stdin until it closes
+fn run(sender: futures::channel::mpsc::UnboundedSender<ConsoleRequest>) {
+    let engine = engine(sender);
+    let mut scope = Scope::new();
+    let stdin = std::io::stdin();
+
+    if !stdin.is_terminal() {
+        let mut script = String::new();
+        for line in stdin.lock().lines().map_while(Result::ok) {
+            script.push_str(&line);
+            script.push('\n');
+        }
+        report(engine.eval_with_scope::<Dynamic>(&mut scope, &script));
+        return;
+    }
+
+    info!("Developer console ready on stdin");
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("{PROMPT}");
+        let _ = std::io::stdout().flush();
+        let Some(Ok(line)) = lines.next() else {
+            break;
+        };
+        if !line.trim().is_empty() {
+            report(engine.eval_with_scope::<Dynamic>(&mut scope, &line));
+        }
+    }
+    info!("Developer console closed");
+}
+
+/// Print what a line evaluated to
+fn report(result: Result<Dynamic, Box<EvalAltResult>>) {
+    match result {
+        Ok(value) if value.is_unit() => {}
+        Ok(value) => println!("{value:?}"),
+        Err(err) => eprintln!("error: {err}"),
+    }
+}
+
+/// Script engine with the app's commands registered
+fn engine(sender: futures::channel::mpsc::UnboundedSender<ConsoleRequest>) -> Engine {
+    let mut engine = Engine::new();
+
+    // Send a command and wait until the app has handled it
+    let request = move |command: ConsoleCommand| -> Result<serde_json::Value, Box<EvalAltResult>> {
+        let (reply, result) = mpsc::channel();
+        sender
+            .unbounded_send(ConsoleRequest { command, reply })
+            .map_err(|_| "The app has quit")?;
+        result
+            .recv_timeout(REPLY_TIMEOUT)
+            .map_err(|_| "The app didn't answer".into())
+    };
+    let send = {
+        let request = request.clone();
+        move |message: Message| request(ConsoleCommand::Send(Box::new(message))).map(|_| ())
+    };
+    let state = move || request(ConsoleCommand::State);
+
+    let state_ = state.clone();
+    engine.register_fn("state", move || -> Result<Dynamic, Box<EvalAltResult>> {
+        rhai::serde::to_dynamic(state_()?)
+    });
+    engine.register_fn("cameras", move || -> Result<Dynamic, Box<EvalAltResult>> {
+        rhai::serde::to_dynamic(state()?["cameras"].take())
+    });
+
+    let send_ = send.clone();
+    engine.register_fn("capture", move || send_(Message::Capture));
+    let send_ = send.clone();
+    engine.register_fn("record", move || send_(Message::ToggleRecording));
+    let send_ = send.clone();
+    engine.register_fn("switch_camera", move || send_(Message::SwitchCamera));
+    let send_ = send.clone();
+    engine.register_fn(
+        "switch_camera",
+        move |index: i64| -> Result<(), Box<EvalAltResult>> {
+            let index = usize::try_from(index).map_err(|_| "Camera index can't be negative")?;
+            send_(Message::SelectCamera(index))
+        },
+    );
+    let send_ = send.clone();
+    engine.register_fn(
+        "mode",
+        move |name: &str| -> Result<(), Box<EvalAltResult>> {
+            send_(Message::SetMode(parse_mode(name)?))
+        },
+    );
+    let send_ = send.clone();
+    engine.register_fn(
+        "set_control",
+        move |name: &str, value: i64| -> Result<(), Box<EvalAltResult>> {
+            let value = i32::try_from(value).map_err(|_| format!("{value} is out of range"))?;
+            send_(control_message(name, value)?)
+        },
+    );
+    engine.register_fn(
+        "action",
+        move |name: &str| -> Result<(), Box<EvalAltResult>> {
+            let action: Action = serde_json::from_value(serde_json::Value::from(name))
+                .map_err(|_| format!("Unknown action '{name}'"))?;
+            send(action.message())
+        },
+    );
+    engine.register_fn("wait", |ms: i64| {
+        std::thread::sleep(Duration::from_millis(ms.max(0) as u64));
+    });
+
+    engine
+}
+
+/// Camera mode by its name, e.g. "photo" or "timelapse"
+fn parse_mode(name: &str) -> Result<CameraMode, String> {
+    CameraMode::ALL
+        .into_iter()
+        .find(|mode| format!("{mode:?}").eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("Unknown mode '{name}'"))
+}
+
+/// Message setting a camera control by its name
+fn control_message(name: &str, value: i32) -> Result<Message, String> {
+    Ok(match name {
+        "exposure_compensation" => Message::SetExposureCompensation(value),
+        "exposure_time" => Message::SetExposureTime(value),
+        "gain" => Message::SetGain(value),
+        "iso" => Message::SetIsoSensitivity(value),
+        "backlight_compensation" => Message::SetBacklightCompensation(value),
+        "focus" => Message::SetFocusAbsolute(value),
+        "contrast" => Message::SetContrast(value),
+        "saturation" => Message::SetSaturation(value),
+        "sharpness" => Message::SetSharpness(value),
+        "hue" => Message::SetHue(value),
+        "white_balance" => Message::SetWhiteBalanceTemperature(value),
+        "pan" => Message::SetPanAbsolute(value),
+        "tilt" => Message::SetTiltAbsolute(value),
+        "zoom" => Message::SetZoomAbsolute(value),
+        _ => return Err(format!("Unknown control '{name}'")),
+    })
+}
+
+impl AppModel {
+    pub(crate) fn handle_dev_console(
+        &mut self,
+        request: ConsoleRequest,
+    ) -> Task<cosmic::Action<Message>> {
+        let (reply, task) = match request.command {
+            ConsoleCommand::State => (self.console_state(), Task::none()),
+            ConsoleCommand::Send(message) => {
+                info!(?message, "Developer console command");
+                (serde_json::Value::Null, self.update(*message))
+            }
+        };
+        if request.reply.send(reply).is_err() {
+            warn!("Developer console went away before its reply");
+        }
+        task
+    }
+
+    /// What `state()` returns to scripts
+    fn console_state(&self) -> serde_json::Value {
+        serde_json::json!({
+            "mode": format!("{:?}", self.mode).to_lowercase(),
+            "camera": self.current_camera_index,
+            "cameras": self
+                .available_cameras
+                .iter()
+                .map(|camera| camera.name.clone())
+                .collect::<Vec<_>>(),
+            "format": self.active_format.as_ref().map(ToString::to_string),
+            "recording": self.recording.is_recording(),
+            "capturing": self.is_capturing,
+            "zoom": self.zoom_level,
+            "filter": format!("{:?}", self.selected_filter),
+            "exposure": self.exposure_settings,
+            "color": self.color_settings,
+            "hdr_plus": self.would_use_burst_mode(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_map_to_commands() {
+        assert_eq!(parse_mode("Video"), Ok(CameraMode::Video));
+        assert_eq!(parse_mode("timelapse"), Ok(CameraMode::Timelapse));
+        assert!(parse_mode("night").is_err());
+
+        assert!(matches!(
+            control_message("gain", 40),
+            Ok(Message::SetGain(40))
+        ));
+        assert!(control_message("shutter", 1).is_err());
+    }
+}
@@ -0,0 +1,161 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Exposure and focus on-screen display
+//!
+//! Shows what the camera is actually doing while it runs on automatic:
+//! exposure time, gain or ISO, white balance temperature and focus position.
+//! libcamera reports these with every frame; UVC cameras only expose them as
+//! V4L2 controls, which are read back every [`POLL_INTERVAL`]. Values a
+//! camera doesn't report are left out rather than shown as blanks.
+
+use crate::backends::camera::types::FrameMetadata;
+use crate::backends::camera::v4l2_controls;
+use std::time::Duration;
+
+/// Time between V4L2 control reads
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Sensor gain, in whatever units the camera reports it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OsdGain {
+    /// ISO sensitivity
+    Iso(u32),
+    /// Analogue gain multiplier (libcamera)
+    Multiplier(f32),
+    /// Raw V4L2 gain control value, in camera-specific units
+    Raw(i32),
+}
+
+/// Lens focus position, in whatever units the camera reports it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OsdFocus {
+    /// Lens position in dioptres (libcamera): 0 is infinity
+    Dioptres(f32),
+    /// Raw V4L2 absolute focus value, in camera-specific units
+    Raw(i32),
+}
+
+/// Values shown by the OSD; `None` where the camera doesn't report one
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OsdValues {
+    /// Exposure time in seconds
+    pub exposure_time: Option<f64>,
+    pub gain: Option<OsdGain>,
+    /// White balance temperature in Kelvin
+    pub white_balance: Option<u32>,
+    pub focus: Option<OsdFocus>,
+}
+
+impl OsdValues {
+    /// Values libcamera reported with a frame
+    pub fn from_metadata(metadata: &FrameMetadata) -> Self {
+        Self {
+            exposure_time: metadata.exposure_time.map(|us| us as f64 / 1_000_000.0),
+            gain: metadata.analogue_gain.map(OsdGain::Multiplier),
+            white_balance: metadata.colour_temperature,
+            focus: metadata.lens_position.map(OsdFocus::Dioptres),
+        }
+    }
+
+    /// Read the current values from a V4L2 device, and focus from its lens
+    /// actuator if it has a separate one. Blocking.
+    pub fn read_v4l2(device_path: &str, focus_path: Option<&str>) -> Self {
+        let exposure = v4l2_controls::read_exposure_metadata(device_path);
+        let gain = exposure
+            .iso
+            .map(OsdGain::Iso)
+            .or(exposure.gain.map(OsdGain::Raw));
+        let white_balance = v4l2_controls::get_control(
+            device_path,
+            v4l2_controls::V4L2_CID_WHITE_BALANCE_TEMPERATURE,
+        )
+        .and_then(|k| u32::try_from(k).ok())
+        .filter(|&k| k > 0);
+        let focus = v4l2_controls::get_control(
+            focus_path.unwrap_or(device_path),
+            v4l2_controls::V4L2_CID_FOCUS_ABSOLUTE,
+        )
+        .map(OsdFocus::Raw);
+        Self {
+            exposure_time: exposure.exposure_time.filter(|&t| t > 0.0),
+            gain,
+            white_balance,
+            focus,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The values as short labels, in display order
+    pub fn labels(&self) -> Vec<String> {
+        let mut labels = Vec::with_capacity(4);
+        if let Some(time) = self.exposure_time {
+            labels.push(format_exposure_time(time));
+        }
+        if let Some(gain) = self.gain {
+            labels.push(match gain {
+                OsdGain::Iso(iso) => format!("ISO {iso}"),
+                OsdGain::Multiplier(gain) => format!("×{gain:.1}"),
+                OsdGain::Raw(gain) => format!("G {gain}"),
+            });
+        }
+        if let Some(kelvin) = self.white_balance {
+            labels.push(format!("{kelvin}K"));
+        }
+        if let Some(focus) = self.focus {
+            labels.push(match focus {
+                OsdFocus::Dioptres(d) if d <= 0.01 => "F ∞".to_string(),
+                OsdFocus::Dioptres(d) => format!("F {:.2}m", 1.0 / d),
+                OsdFocus::Raw(position) => format!("F {position}"),
+            });
+        }
+        labels
+    }
+}
+
+/// Exposure time the way shutter speeds are written: a fraction of a second
+/// below half a second, decimal seconds above
+fn format_exposure_time(seconds: f64) -> String {
+    if seconds < 0.5 {
+        format!("1/{:.0}s", 1.0 / seconds)
+    } else {
+        format!("{seconds:.1}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_are_labelled_like_a_camera_display() {
+        let metadata = FrameMetadata {
+            exposure_time: Some(16_667),
+            analogue_gain: Some(2.4),
+            colour_temperature: Some(5200),
+            lens_position: Some(2.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            OsdValues::from_metadata(&metadata).labels(),
+            ["1/60s", "×2.4", "5200K", "F 0.50m"]
+        );
+
+        let v4l2 = OsdValues {
+            exposure_time: Some(1.5),
+            gain: Some(OsdGain::Iso(400)),
+            white_balance: None,
+            focus: Some(OsdFocus::Raw(120)),
+        };
+        assert_eq!(v4l2.labels(), ["1.5s", "ISO 400", "F 120"]);
+
+        let infinity = OsdValues {
+            focus: Some(OsdFocus::Dioptres(0.0)),
+            ..Default::default()
+        };
+        assert_eq!(infinity.labels(), ["F ∞"]);
+        assert!(OsdValues::default().is_empty());
+    }
+}
@@ -109,6 +109,16 @@ pub fn query_exposure_controls(
         debug!(device_path, modes = ?controls.metering_modes, "Metering modes available");
     }
 
+    // Query UVC region of interest (used to meter on a detected face)
+    if let Some(info) = v4l2_controls::query_control(
+        device_path,
+        v4l2_controls::V4L2_CID_UVC_REGION_OF_INTEREST_RECT,
+    ) && !info.is_disabled()
+    {
+        controls.has_region_of_interest = true;
+        debug!(device_path, "Region of interest available");
+    }
+
     // Query boolean controls
     controls.has_auto_priority =
         query_bool_control(device_path, v4l2_controls::V4L2_CID_EXPOSURE_AUTO_PRIORITY);
@@ -170,6 +180,7 @@ pub fn query_exposure_controls(
         has_autogain = controls.has_autogain,
         has_iso = controls.iso.available,
         has_metering = controls.has_metering,
+        has_roi = controls.has_region_of_interest,
         has_auto_priority = controls.has_auto_priority,
         has_backlight = controls.backlight_compensation.available,
         has_contrast = controls.contrast.available,
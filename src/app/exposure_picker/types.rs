@@ -214,6 +214,8 @@ pub struct AvailableExposureControls {
     pub has_metering: bool,
     /// Available metering modes
     pub metering_modes: Vec<MeteringMode>,
+    /// UVC region of interest, which auto exposure can meter on
+    pub has_region_of_interest: bool,
 
     // === Auto Priority ===
     pub has_auto_priority: bool,
@@ -8,6 +8,7 @@ use super::ControlRange;
 use crate::app::overlay_style::PICKER_PANEL;
 use crate::app::preview_geometry::TOP_BAR_HEIGHT;
 use crate::app::state::{AppModel, Message};
+use crate::backends::camera::v4l2_controls::AutoControl;
 use crate::fl;
 use cosmic::Element;
 use cosmic::iced::{Alignment, Length};
@@ -39,6 +40,20 @@ impl AppModel {
         // Mode toggle row (segmented button + reset icon)
         column = column.push(self.build_mode_toggle());
 
+        if self.control_overrides.is_conflicting() {
+            column = column.push(Self::build_control_conflict_row());
+        }
+
+        // AE-L while exposure is automatic, and to release it once held
+        // (an emulated hold shows as manual mode)
+        if !is_manual || self.auto_holds.contains_key(&AutoControl::Exposure) {
+            column = column.push(self.build_hold_row(
+                AutoControl::Exposure,
+                fl!("exposure-hold"),
+                fl!("exposure-hold-description"),
+            ));
+        }
+
         // Add controls based on mode
         if is_manual {
             column = self.add_manual_controls(column, settings_data);
@@ -85,6 +100,7 @@ impl AppModel {
             .on_activate(Message::ExposureModeSelected);
 
         let reset_btn = widget::button::icon(widget::icon::from_name("edit-undo-symbolic"))
+            .tooltip(fl!("a11y-reset"))
             .on_press(Message::ResetExposureSettings)
             .class(cosmic::theme::Button::Text)
             .padding(4);
@@ -97,6 +113,44 @@ impl AppModel {
             .into()
     }
 
+    /// AE-L / AWB-L toggle with its caption
+    fn build_hold_row(
+        &self,
+        control: AutoControl,
+        label: String,
+        description: String,
+    ) -> Element<'_, Message> {
+        let held = self.auto_holds.contains_key(&control);
+        widget::Row::new()
+            .push(
+                widget::button::text(label)
+                    .class(if held {
+                        cosmic::theme::Button::Suggested
+                    } else {
+                        cosmic::theme::Button::Standard
+                    })
+                    .on_press(Message::ToggleAutoHold(control)),
+            )
+            .push(widget::text::caption(description).width(Length::Fill))
+            .spacing(CONTROL_SPACING)
+            .align_y(Alignment::Center)
+            .into()
+    }
+
+    /// Warning shown while another app keeps overriding the stored controls
+    fn build_control_conflict_row() -> Element<'static, Message> {
+        widget::Row::new()
+            .push(
+                widget::icon::from_name("dialog-warning-symbolic")
+                    .symbolic(true)
+                    .size(16),
+            )
+            .push(widget::text::caption(fl!("controls-overridden")).width(Length::Fill))
+            .spacing(CONTROL_SPACING)
+            .align_y(Alignment::Center)
+            .into()
+    }
+
     /// Add auto mode controls to column
     fn add_auto_controls<'a>(
         &'a self,
@@ -147,6 +201,11 @@ impl AppModel {
             column = column.push(Self::build_unsupported_row(fl!("exposure-iso")));
         }
 
+        // Histogram-based suggestion for exposure time and gain
+        if controls.exposure_time.available {
+            column = column.push(self.build_exposure_suggestion_row());
+        }
+
         // EV Compensation
         if controls.exposure_bias.available {
             column = column.push(self.build_ev_row(settings_data));
@@ -329,6 +388,35 @@ impl AppModel {
         )
     }
 
+    /// Build the auto-suggest row: a button, or the proposal with Apply
+    fn build_exposure_suggestion_row(&self) -> Element<'_, Message> {
+        let Some(suggestion) = self.exposure_suggestion else {
+            return widget::button::standard(fl!("exposure-suggest"))
+                .on_press(Message::SuggestExposure)
+                .into();
+        };
+
+        let mut summary = format_exposure_time(suggestion.exposure_time);
+        if let Some(gain) = suggestion.gain {
+            summary = fl!(
+                "exposure-suggestion-gain",
+                time = summary,
+                gain = gain.to_string()
+            );
+        }
+        let summary = format!("{summary} ({:+.1} EV)", suggestion.ev_change);
+
+        widget::Row::new()
+            .push(widget::text::caption(summary).width(Length::Fill))
+            .push(
+                widget::button::suggested(fl!("exposure-suggestion-apply"))
+                    .on_press(Message::ApplyExposureSuggestion),
+            )
+            .spacing(CONTROL_SPACING)
+            .align_y(Alignment::Center)
+            .into()
+    }
+
     /// Build ISO row
     fn build_iso_row(
         &self,
@@ -497,6 +585,9 @@ impl AppModel {
             .width(Length::Shrink);
 
         column = column.push(self.build_color_header());
+        if self.control_overrides.is_conflicting() {
+            column = column.push(Self::build_control_conflict_row());
+        }
         column = self.add_image_controls(column, color_data);
         column = self.add_white_balance_controls(column, color_data);
 
@@ -532,6 +623,7 @@ impl AppModel {
     /// Build color picker header with title and reset button
     fn build_color_header(&self) -> Element<'_, Message> {
         let reset_btn = widget::button::icon(widget::icon::from_name("edit-undo-symbolic"))
+            .tooltip(fl!("a11y-reset"))
             .on_press(Message::ResetColorSettings)
             .class(cosmic::theme::Button::Text)
             .padding(4);
@@ -624,6 +716,18 @@ impl AppModel {
 
         if controls.has_white_balance_auto {
             column = column.push(self.build_auto_white_balance_row(settings_data));
+            // AWB-L while white balance is automatic, and to release it once
+            // held (an emulated hold shows as manual)
+            let is_auto = settings_data
+                .and_then(|s| s.white_balance_auto)
+                .unwrap_or(true);
+            if is_auto || self.auto_holds.contains_key(&AutoControl::WhiteBalance) {
+                column = column.push(self.build_hold_row(
+                    AutoControl::WhiteBalance,
+                    fl!("color-hold"),
+                    fl!("color-hold-description"),
+                ));
+            }
         }
 
         if controls.white_balance_temperature.available {
@@ -635,7 +739,28 @@ impl AppModel {
             }
         }
 
-        column
+        // The eyedropper works on every camera: without a temperature
+        // control it falls back to software gains
+        column.push(self.build_white_balance_pick_row())
+    }
+
+    /// Build the eyedropper row, noting when software white balance is active
+    fn build_white_balance_pick_row(&self) -> Element<'_, Message> {
+        let status = if self.software_white_balance.is_identity() {
+            String::new()
+        } else {
+            fl!("color-software-wb")
+        };
+
+        widget::Row::new()
+            .push(
+                widget::button::standard(fl!("color-pick-white"))
+                    .on_press(Message::StartWhiteBalancePick),
+            )
+            .push(widget::text::caption(status))
+            .spacing(CONTROL_SPACING)
+            .align_y(Alignment::Center)
+            .into()
     }
 
     /// Build auto white balance toggle row
@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! UVC extension unit controls sub-page within the Settings drawer.
+//!
+//! Lists every vendor-specific control the current camera advertises through
+//! its UVC extension units. Controls of known devices get named preset
+//! buttons; everything else can be read and written as raw hex bytes.
+
+use crate::app::state::{AppModel, ContextPage, Message};
+use crate::backends::camera::v4l2_controls::XuPreset;
+use crate::fl;
+use cosmic::app::context_drawer;
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget;
+
+pub fn view(app: &AppModel) -> context_drawer::ContextDrawer<'_, Message> {
+    let spacing = cosmic::theme::spacing();
+    let mut column = widget::column::with_capacity(4).spacing(spacing.space_m);
+
+    if app.xu_controls.is_empty() {
+        column = column.push(widget::text::body(fl!("extension-units-empty")));
+    }
+
+    // Controls arrive grouped by unit; start a new section whenever the unit changes
+    let mut current_unit: Option<u8> = None;
+    let mut section = widget::settings::section();
+    for (index, control) in app.xu_controls.iter().enumerate() {
+        if current_unit != Some(control.info.unit_id) {
+            if current_unit.is_some() {
+                column = column.push(section);
+            }
+            current_unit = Some(control.info.unit_id);
+            section = widget::settings::section().title(fl!(
+                "extension-units-unit",
+                id = control.info.unit_id,
+                guid = control.guid.clone()
+            ));
+        }
+
+        let preset = XuPreset::find(&control.guid, control.info.selector);
+        let title = preset
+            .map(|p| p.name.to_string())
+            .unwrap_or_else(|| fl!("extension-units-selector", selector = control.info.selector));
+
+        let mut description = fl!("extension-units-bytes", count = control.info.len);
+        if let Some(error) = &control.error {
+            description = format!("{description} — {error}");
+        }
+
+        let item = widget::settings::item::builder(title).description(description);
+        let controls: cosmic::Element<'_, Message> = match preset {
+            Some(preset) if control.info.can_set => {
+                let current = control
+                    .value
+                    .as_ref()
+                    .and_then(|v| v.get(preset.offset))
+                    .copied();
+                let mut row = widget::row::with_capacity(preset.options.len())
+                    .spacing(spacing.space_xxs)
+                    .align_y(Alignment::Center);
+                for &(label, value) in preset.options {
+                    let button = if current == Some(value) {
+                        widget::button::suggested(label)
+                    } else {
+                        widget::button::standard(label)
+                    };
+                    row = row.push(button.on_press(Message::XuApplyPreset(index, value)));
+                }
+                row.into()
+            }
+            _ if control.info.can_set => widget::row::with_capacity(2)
+                .spacing(spacing.space_xs)
+                .align_y(Alignment::Center)
+                .push(
+                    widget::text_input("00 00", &control.edit)
+                        .on_input(move |text| Message::XuEditValue(index, text))
+                        .on_submit(move |_| Message::XuApplyValue(index))
+                        .width(Length::Fixed(160.0)),
+                )
+                .push(
+                    widget::button::standard(fl!("extension-units-apply"))
+                        .on_press(Message::XuApplyValue(index)),
+                )
+                .into(),
+            _ => widget::text::body(
+                control
+                    .value
+                    .as_deref()
+                    .map(format_hex_bytes)
+                    .unwrap_or_else(|| fl!("extension-units-unreadable")),
+            )
+            .into(),
+        };
+
+        section = section.add(item.control(controls));
+    }
+    if current_unit.is_some() {
+        column = column.push(section);
+    }
+
+    context_drawer::context_drawer(
+        widget::scrollable(column).width(Length::Fill),
+        Message::ToggleContextPage(ContextPage::ExtensionUnits),
+    )
+    .title(fl!("extension-units-title"))
+    .actions(app.settings_back_button())
+}
+
+/// Format a payload as space-separated hex bytes
+pub(crate) fn format_hex_bytes(data: &[u8]) -> String {
+    data.iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parse space- or comma-separated hex bytes (with optional `0x` prefixes)
+pub(crate) fn parse_hex_bytes(text: &str) -> Option<Vec<u8>> {
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty())
+        .map(|t| {
+            let t = t.trim_start_matches("0x").trim_start_matches("0X");
+            u8::from_str_radix(t, 16).ok()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_round_trip() {
+        let data = [0x00, 0x01, 0xab, 0xff];
+        assert_eq!(format_hex_bytes(&data), "00 01 ab ff");
+        assert_eq!(parse_hex_bytes("00 01 ab ff").unwrap(), data);
+    }
+
+    #[test]
+    fn hex_accepts_prefixes_and_commas() {
+        assert_eq!(parse_hex_bytes("0x01, 0X2, ff").unwrap(), vec![1, 2, 0xff]);
+    }
+
+    #[test]
+    fn hex_rejects_invalid_bytes() {
+        assert!(parse_hex_bytes("01 zz").is_none());
+        assert!(parse_hex_bytes("100").is_none());
+    }
+}
@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Imported LUT library
+//!
+//! `.cube` files imported from the filter picker are validated and copied
+//! into the app's data directory, so a LUT keeps working after the original
+//! file is moved or deleted. Each LUT is named after its file stem.
+
+use crate::shaders::CubeLut;
+use std::path::{Path, PathBuf};
+
+const APP_ID: &str = <crate::app::AppModel as cosmic::Application>::APP_ID;
+
+/// Directory under the app's data directory holding imported LUTs
+const LUT_DIR_NAME: &str = "luts";
+
+/// File extension of imported LUTs
+pub const LUT_EXTENSION: &str = "cube";
+
+/// Directory imported LUTs are copied into
+fn library_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_ID).join(LUT_DIR_NAME))
+}
+
+/// Names of the imported LUTs, sorted case-insensitively
+pub fn list() -> Vec<String> {
+    let Some(dir) = library_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case(LUT_EXTENSION))
+        })
+        .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+    names
+}
+
+/// Validate a `.cube` file and copy it into the library
+///
+/// Returns the name it was stored under, which is the file stem with a
+/// numeric suffix if a LUT of that name already exists.
+pub fn import(source: &Path) -> Result<String, String> {
+    CubeLut::load(source)?;
+
+    let dir = library_dir().ok_or("No data directory available")?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+
+    let stem = source
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .filter(|s| !s.is_empty())
+        .unwrap_or("LUT");
+    let name = unique_name(stem, &list());
+    let destination = lut_path(&dir, &name);
+    std::fs::copy(source, &destination)
+        .map_err(|e| format!("Failed to copy {}: {e}", source.display()))?;
+    Ok(name)
+}
+
+/// Load an imported LUT by name
+pub fn load(name: &str) -> Result<CubeLut, String> {
+    let dir = library_dir().ok_or("No data directory available")?;
+    CubeLut::load(&lut_path(&dir, name))
+}
+
+/// Delete an imported LUT
+pub fn remove(name: &str) -> Result<(), String> {
+    let dir = library_dir().ok_or("No data directory available")?;
+    let path = lut_path(&dir, name);
+    std::fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {e}", path.display()))
+}
+
+fn lut_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{name}.{LUT_EXTENSION}"))
+}
+
+/// `stem`, or `stem (2)`, `stem (3)`, ... if it is already taken
+fn unique_name(stem: &str, existing: &[String]) -> String {
+    let taken = |name: &str| existing.iter().any(|e| e.eq_ignore_ascii_case(name));
+    if !taken(stem) {
+        return stem.to_string();
+    }
+    (2..)
+        .map(|n| format!("{stem} ({n})"))
+        .find(|name| !taken(name))
+        .unwrap_or_else(|| stem.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unique_name_appends_a_counter() {
+        let existing = vec!["Teal Orange".to_string(), "teal orange (2)".to_string()];
+        assert_eq!(unique_name("Kodak", &existing), "Kodak");
+        assert_eq!(unique_name("Teal Orange", &existing), "Teal Orange (3)");
+    }
+}
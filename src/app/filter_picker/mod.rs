@@ -3,7 +3,9 @@
 //! Filter picker module
 //!
 //! Grid-style filter selector shown in context drawer.
-//! Displays filter options in a responsive 3-column grid with live camera preview thumbnails.
+//! Displays filter options in a responsive 3-column grid with live camera preview thumbnails,
+//! followed by the imported `.cube` LUTs.
 
+pub(crate) mod lut_library;
 mod square_container;
 mod view;
@@ -47,7 +47,15 @@ impl AppModel {
             FilterType::Posterize,
             FilterType::Solarize,
             FilterType::Pencil,
+            FilterType::FilmPortrait,
+            FilterType::FilmSlide,
+            FilterType::FilmPan,
+            FilterType::FilmTungsten,
         ];
+        // The custom LUT swatch previews the imported LUT that is active
+        let filters = filters
+            .into_iter()
+            .chain(self.selected_lut.is_some().then_some(FilterType::Lut));
 
         // Build filter grid with responsive sizing
         let spacing = FILTER_GRID_SPACING as u16;
@@ -66,12 +74,8 @@ impl AppModel {
             let thumbnail: Element<'_, Message> = if let Some(frame) = &self.current_frame {
                 // Use video widget with the specific filter type
                 // The video widget fills its container and handles aspect ratio via Cover mode
-                // Get rotation from current camera
-                let rotation = self
-                    .available_cameras
-                    .get(self.current_camera_index)
-                    .map(|c| c.rotation.gpu_rotation_code())
-                    .unwrap_or(0);
+                // Rotate like the preview
+                let rotation = self.current_frame_rotation.gpu_rotation_code();
 
                 // `Arc::clone` of the preview's own `current_frame`, and that is
                 // load-bearing: the pipeline maps VIDEO_ID_FILTER_PREVIEW onto
@@ -81,7 +85,7 @@ impl AppModel {
                 video_widget::video_widget(
                     Arc::clone(frame),
                     video_widget::VideoWidgetConfig {
-                        // One id for all fifteen: the filter lives in the
+                        // One id for every swatch: the filter lives in the
                         // per-`(video_id, filter_mode)` binding, not the texture.
                         video_id: crate::app::video_primitive::VIDEO_ID_FILTER_PREVIEW,
                         content_fit: VideoContentFit::Cover,
@@ -92,12 +96,21 @@ impl AppModel {
                         crop_uv: None,   // No aspect ratio cropping in filter previews
                         zoom_level: 1.0, // No zoom for filter previews
                         scroll_zoom_enabled: false, // No scroll zoom for filter previews
+                        gestures_enabled: false,
                         cover_blend: None,
                         bar_top_px: 0.0,
                         bar_bottom_px: 0.0,
                         // Filter previews don't use blur, so this is only here
                         // to satisfy the struct — value is ignored downstream.
                         letterbox_color: [0.0, 0.0, 0.0, 1.0],
+                        // Swatches show what each filter looks like
+                        false_color: false,
+                        // Swatches are judged by the same eyes as the preview
+                        color_vision: self.config.color_vision_assist,
+                        // Thumbnails of a full-size frame shimmer as much as the preview
+                        scaling: self.config.preview_scaling,
+                        desqueeze: 1.0,
+                        loupe_center: None,
                     },
                 )
             } else {
@@ -133,7 +146,11 @@ impl AppModel {
                 .class(button::ButtonClass::Image);
 
             // Filter name label below thumbnail (outside button, no hover effect)
-            let name_label = widget::text(Self::filter_display_name(filter_type))
+            let name = match (filter_type, &self.selected_lut) {
+                (FilterType::Lut, Some(lut_name)) => lut_name.clone(),
+                _ => Self::filter_display_name(filter_type),
+            };
+            let name_label = widget::text(name)
                 .width(Length::Fill)
                 .align_x(cosmic::iced::alignment::Horizontal::Center);
 
@@ -171,8 +188,13 @@ impl AppModel {
             grid_column = grid_column.push(current_row);
         }
 
+        let content = widget::column()
+            .push(grid_column)
+            .push(self.build_lut_section())
+            .spacing(cosmic::theme::spacing().space_m);
+
         // Context drawer already provides scrollable behavior, so just wrap in a clipping container
-        let content: Element<'_, Message> = widget::container(grid_column)
+        let content: Element<'_, Message> = widget::container(content)
             .width(Length::Fill)
             .clip(true)
             .into();
@@ -181,6 +203,45 @@ impl AppModel {
             .title(fl!("filters-title"))
     }
 
+    /// Imported LUTs: one row per LUT (select / remove) and the import button
+    fn build_lut_section(&self) -> Element<'_, Message> {
+        let spacing = cosmic::theme::spacing();
+        let mut section = widget::column()
+            .push(widget::text::heading(fl!("filters-luts-title")))
+            .spacing(spacing.space_xxs);
+
+        for name in &self.imported_luts {
+            let is_selected = self.selected_filter == FilterType::Lut
+                && self.selected_lut.as_deref() == Some(name.as_str());
+            let select = button::text(name.clone())
+                .on_press(Message::SelectLut(name.clone()))
+                .class(if is_selected {
+                    cosmic::theme::Button::Suggested
+                } else {
+                    cosmic::theme::Button::Text
+                })
+                .width(Length::Fill);
+            let remove = button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                .tooltip(fl!("a11y-remove"))
+                .on_press(Message::RemoveLut(name.clone()));
+            section = section.push(
+                widget::row()
+                    .push(select)
+                    .push(remove)
+                    .spacing(spacing.space_xxs)
+                    .align_y(Alignment::Center),
+            );
+        }
+
+        if let Some(err) = &self.lut_error {
+            section = section.push(widget::text::caption(err.clone()));
+        }
+
+        section
+            .push(button::standard(fl!("filters-import-lut")).on_press(Message::ImportLut))
+            .into()
+    }
+
     /// Get placeholder color for a filter type
     fn filter_placeholder_color(filter_type: FilterType) -> Color {
         match filter_type {
@@ -199,6 +260,11 @@ impl AppModel {
             FilterType::Solarize => Color::from_rgb(0.5, 0.6, 0.35),
             FilterType::ChromaticAberration => Color::from_rgb(0.6, 0.4, 0.5),
             FilterType::Pencil => Color::from_rgb(0.9, 0.9, 0.85),
+            FilterType::FilmPortrait => Color::from_rgb(0.6, 0.5, 0.42),
+            FilterType::FilmSlide => Color::from_rgb(0.3, 0.45, 0.6),
+            FilterType::FilmPan => Color::from_rgb(0.4, 0.4, 0.4),
+            FilterType::FilmTungsten => Color::from_rgb(0.3, 0.35, 0.5),
+            FilterType::Lut => Color::from_rgb(0.45, 0.4, 0.5),
         }
     }
 
@@ -220,6 +286,11 @@ impl AppModel {
             FilterType::Solarize => fl!("filter-solarize"),
             FilterType::ChromaticAberration => fl!("filter-chroma"),
             FilterType::Pencil => fl!("filter-pencil"),
+            FilterType::FilmPortrait => fl!("filter-film-portrait"),
+            FilterType::FilmSlide => fl!("filter-film-slide"),
+            FilterType::FilmPan => fl!("filter-film-pan"),
+            FilterType::FilmTungsten => fl!("filter-film-tungsten"),
+            FilterType::Lut => fl!("filter-lut"),
         }
     }
 }
@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Grouped, searchable format list
+//!
+//! Some cameras list well over a hundred formats: every resolution, in
+//! several pixel formats, at several frame rates. The picker shows them
+//! grouped by aspect ratio and frame rate, largest first, and narrows them
+//! down with a free-text search such as "4k 60", "mjpg" or "16:9".
+
+use crate::backends::camera::types::{CameraFormat, Framerate};
+use crate::media::Codec;
+
+/// Named aspect ratios, as (width, height, label)
+const ASPECT_RATIOS: &[(u32, u32, &str)] = &[
+    (16, 9, "16:9"),
+    (4, 3, "4:3"),
+    (3, 2, "3:2"),
+    (16, 10, "16:10"),
+    (5, 4, "5:4"),
+    (1, 1, "1:1"),
+    (21, 9, "21:9"),
+    (11, 9, "11:9"),
+    (5, 3, "5:3"),
+];
+
+/// How far a frame may be off a named aspect ratio and still get its name
+const ASPECT_TOLERANCE: f64 = 0.015;
+
+/// Formats sharing an aspect ratio and frame rate
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatGroup<'a> {
+    /// Aspect ratio, e.g. "16:9"
+    pub aspect: String,
+    /// Frame rate (None = variable)
+    pub framerate: Option<Framerate>,
+    /// (index into the camera's formats, format), largest first
+    pub formats: Vec<(usize, &'a CameraFormat)>,
+}
+
+/// Aspect ratio name of a frame, e.g. "16:9" for 1920x1080
+pub fn aspect_label(width: u32, height: u32) -> String {
+    if height == 0 {
+        return String::from("?");
+    }
+    let ratio = width as f64 / height as f64;
+    ASPECT_RATIOS
+        .iter()
+        .find(|&&(w, h, _)| (ratio / (w as f64 / h as f64) - 1.0).abs() < ASPECT_TOLERANCE)
+        .map(|&(_, _, label)| label.to_string())
+        .unwrap_or_else(|| format!("{:.2}:1", ratio))
+}
+
+/// Words a format can be found by
+fn keywords(format: &CameraFormat) -> Vec<String> {
+    let mut words = vec![
+        format!("{}x{}", format.width, format.height),
+        format.width.to_string(),
+        format.height.to_string(),
+        format!("{}p", format.height),
+        aspect_label(format.width, format.height),
+        format.pixel_format.trim().to_lowercase(),
+    ];
+    words.extend(
+        Codec::from_fourcc(&format.pixel_format)
+            .short_description()
+            .to_lowercase()
+            .split(['/', ' '])
+            .map(str::to_string),
+    );
+    if let Some(label) = crate::constants::get_resolution_label(format.width) {
+        words.push(label.to_lowercase());
+    }
+    match format.framerate {
+        Some(fps) => {
+            words.push(fps.to_string());
+            words.push(format!("{}fps", fps));
+        }
+        None => words.push(String::from("auto")),
+    }
+    if format.hardware_accelerated {
+        words.push(String::from("hw"));
+    }
+    words
+}
+
+/// Whether a format matches a search query
+///
+/// Every word of the query must match: numbers exactly ("60" doesn't find
+/// 600 px tall formats), anything else as the start of a keyword ("mj"
+/// finds MJPG).
+pub fn matches_query(format: &CameraFormat, query: &str) -> bool {
+    let words = keywords(format);
+    query.split_whitespace().all(|token| {
+        let token = token.to_lowercase();
+        if token.chars().all(|c| c.is_ascii_digit()) {
+            words.iter().any(|word| *word == token)
+        } else {
+            words.iter().any(|word| word.starts_with(&token))
+        }
+    })
+}
+
+/// Group the formats matching `query`
+///
+/// Aspect ratios are ordered by their largest format, and within one the
+/// highest frame rate comes first. Formats of a group are ordered by size,
+/// then by the pixel format preference auto-selection uses.
+pub fn group_formats<'a>(formats: &'a [CameraFormat], query: &str) -> Vec<FormatGroup<'a>> {
+    let mut groups: Vec<FormatGroup<'a>> = Vec::new();
+    for (index, format) in formats.iter().enumerate() {
+        if !matches_query(format, query) {
+            continue;
+        }
+        let aspect = aspect_label(format.width, format.height);
+        match groups
+            .iter_mut()
+            .find(|group| group.aspect == aspect && group.framerate == format.framerate)
+        {
+            Some(group) => group.formats.push((index, format)),
+            None => groups.push(FormatGroup {
+                aspect,
+                framerate: format.framerate,
+                formats: vec![(index, format)],
+            }),
+        }
+    }
+
+    let pixels = |format: &CameraFormat| u64::from(format.width) * u64::from(format.height);
+    for group in &mut groups {
+        group.formats.sort_by_key(|&(_, format)| {
+            (
+                std::cmp::Reverse(pixels(format)),
+                Codec::from_fourcc(&format.pixel_format).preference_rank(),
+            )
+        });
+    }
+    let largest_of_aspect = |aspect: &str, groups: &[FormatGroup<'_>]| {
+        groups
+            .iter()
+            .filter(|group| group.aspect == aspect)
+            .flat_map(|group| group.formats.iter().map(|&(_, format)| pixels(format)))
+            .max()
+            .unwrap_or(0)
+    };
+    let aspect_sizes: Vec<u64> = groups
+        .iter()
+        .map(|group| largest_of_aspect(&group.aspect, &groups))
+        .collect();
+    let mut keyed: Vec<(u64, FormatGroup<'a>)> = aspect_sizes.into_iter().zip(groups).collect();
+    keyed.sort_by(|(size_a, a), (size_b, b)| {
+        size_b
+            .cmp(size_a)
+            .then_with(|| a.aspect.cmp(&b.aspect))
+            .then_with(|| {
+                let fps = |group: &FormatGroup<'_>| group.framerate.map(|f| f.as_f64());
+                fps(b)
+                    .partial_cmp(&fps(a))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+    });
+    keyed.into_iter().map(|(_, group)| group).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(width: u32, height: u32, fps: u32, pixel_format: &str) -> CameraFormat {
+        CameraFormat {
+            width,
+            height,
+            framerate: Some(Framerate::from_int(fps)),
+            hardware_accelerated: pixel_format == "MJPG",
+            pixel_format: pixel_format.to_string(),
+        }
+    }
+
+    #[test]
+    fn aspect_ratios_are_named() {
+        assert_eq!(aspect_label(1920, 1080), "16:9");
+        assert_eq!(aspect_label(640, 480), "4:3");
+        // 1366x768 is a hair off 16:9
+        assert_eq!(aspect_label(1366, 768), "16:9");
+        assert_eq!(aspect_label(1280, 800), "16:10");
+        assert_eq!(aspect_label(1000, 300), "3.33:1");
+    }
+
+    #[test]
+    fn search_narrows_the_list() {
+        let hd = format(1920, 1080, 60, "MJPG");
+        assert!(matches_query(&hd, ""));
+        assert!(matches_query(&hd, "1080p 60"));
+        assert!(matches_query(&hd, "HD mj"));
+        assert!(matches_query(&hd, "16:9 hw"));
+        assert!(!matches_query(&hd, "yuyv"));
+        // Numbers match whole
+        assert!(!matches_query(&hd, "6"));
+        assert!(!matches_query(&format(800, 600, 30, "YUYV"), "60"));
+    }
+
+    #[test]
+    fn groups_by_aspect_then_framerate() {
+        let formats = vec![
+            format(640, 480, 30, "YUYV"),
+            format(1280, 720, 30, "YUYV"),
+            format(1920, 1080, 30, "YUYV"),
+            format(1920, 1080, 30, "MJPG"),
+            format(1920, 1080, 60, "MJPG"),
+        ];
+        let groups = group_formats(&formats, "");
+        let summary: Vec<(&str, u32, Vec<usize>)> = groups
+            .iter()
+            .map(|group| {
+                (
+                    group.aspect.as_str(),
+                    group.framerate.unwrap().as_int(),
+                    group.formats.iter().map(|&(index, _)| index).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("16:9", 60, vec![4]),
+                // Raw before MJPG at the same size, as in auto-selection
+                ("16:9", 30, vec![2, 3, 1]),
+                ("4:3", 30, vec![0]),
+            ]
+        );
+
+        assert_eq!(group_formats(&formats, "4:3").len(), 1);
+        assert!(group_formats(&formats, "4k").is_empty());
+    }
+}
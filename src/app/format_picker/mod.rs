@@ -4,8 +4,10 @@
 //!
 //! This module handles resolution and framerate selection:
 //! - Preference logic for auto-selection
-//! - iOS-style picker UI overlay
+//! - Grouping and search of the format list
+//! - Picker UI overlay
 
+pub mod list;
 pub mod preferences;
 pub mod view;
 
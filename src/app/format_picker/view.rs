@@ -2,145 +2,240 @@
 
 //! Format picker UI view
 
-use crate::app::overlay_style::{OVERLAY_CONTAINER, PICKER_PANEL};
+use crate::app::overlay_style::PICKER_PANEL;
 use crate::app::preview_geometry::TOP_BAR_HEIGHT;
 use crate::app::state::{AppModel, Message};
-use crate::constants::{formats, ui};
+use crate::backends::camera::types::CameraFormat;
+use crate::backends::camera::usb_bandwidth::{self, BandwidthAdvice};
+use crate::constants::ui;
 use crate::fl;
+use crate::media::encoders::video::EncoderLimit;
 use cosmic::Element;
 use cosmic::iced::{Alignment, Length};
 use cosmic::widget;
 
-impl AppModel {
-    /// Build the iOS-style format picker overlay
-    ///
-    /// Shows resolution and framerate selection buttons in a semi-transparent overlay.
-    /// Click outside the picker to close.
-    pub fn build_format_picker(&self) -> Element<'_, Message> {
-        let spacing = cosmic::theme::spacing();
+/// Short name of a format for messages, e.g. "4K30 MJPG"
+fn format_name(format: &CameraFormat) -> String {
+    let resolution = crate::constants::get_resolution_label(format.width)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{}x{}", format.width, format.height));
+    let fps = format
+        .framerate
+        .map(|fps| fps.to_string())
+        .unwrap_or_default();
+    format!("{}{} {}", resolution, fps, format.pixel_format.trim())
+}
 
-        // Group formats by resolution label
-        let (unique_resolutions, resolution_groups) = self.group_formats_by_label();
+/// Explain USB bandwidth advice for `format` to the user
+pub(crate) fn usb_advice_text(advice: BandwidthAdvice, format: &CameraFormat) -> String {
+    let format = format_name(format);
+    match advice {
+        BandwidthAdvice::SlowPort {
+            required,
+            connected,
+        } => fl!(
+            "usb-advice-slow-port",
+            format = format.as_str(),
+            required = required.label(),
+            connected = connected.label()
+        ),
+        BandwidthAdvice::SlowController {
+            required,
+            controller,
+        } => fl!(
+            "usb-advice-slow-controller",
+            format = format.as_str(),
+            required = required.label(),
+            controller = controller.label()
+        ),
+        BandwidthAdvice::BeyondCamera { connected } => fl!(
+            "usb-advice-beyond-camera",
+            format = format.as_str(),
+            connected = connected.label()
+        ),
+        BandwidthAdvice::SharedBus { cameras } => fl!("usb-advice-shared-bus", cameras = cameras),
+    }
+}
 
-        // Determine currently selected resolution for picker
-        let selected_res = self
-            .picker_selected_resolution
-            .or_else(|| self.active_format.as_ref().map(|f| f.width))
-            .unwrap_or(formats::DEFAULT_PICKER_RESOLUTION);
+/// Explain why the selected video encoder may not record `format`
+fn encoder_limit_text(limit: EncoderLimit, encoder: &str, format: &CameraFormat) -> String {
+    match limit {
+        EncoderLimit::TooLarge {
+            max_width,
+            max_height,
+        } => fl!(
+            "format-encoder-too-large",
+            encoder = encoder,
+            size = format!("{}x{}", max_width, max_height)
+        ),
+        EncoderLimit::TooFast => fl!(
+            "format-encoder-too-fast",
+            encoder = encoder,
+            format = format_name(format)
+        ),
+    }
+}
 
-        const BUTTON_WIDTH: f32 = ui::PICKER_BUTTON_WIDTH;
+/// Data rate in megabytes per second, e.g. "9.3 MB/s"
+fn bandwidth_label(bytes_per_sec: u64) -> String {
+    format!("{:.1} MB/s", bytes_per_sec as f64 / 1_000_000.0)
+}
 
-        // Build resolution row
-        let mut res_row = widget::Row::new()
-            .spacing(spacing.space_xxs)
+/// Warning line shown under the list
+fn warning_row<'a>(text: String) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+    widget::Row::new()
+        .spacing(spacing.space_xxs)
+        .align_y(Alignment::Center)
+        .push(
+            widget::icon::from_name("dialog-warning-symbolic")
+                .symbolic(true)
+                .size(16),
+        )
+        .push(widget::text::caption(text).width(Length::Fill))
+        .into()
+}
+
+impl AppModel {
+    /// Why the selected video encoder may not record `format`
+    fn encoder_limit(&self, format: &CameraFormat) -> Option<EncoderLimit> {
+        self.available_video_encoders
+            .get(self.current_video_encoder_index)?
+            .check_format(
+                format.width,
+                format.height,
+                format.framerate.map(|fps| fps.as_f64()),
+            )
+    }
+
+    /// Build one selectable row of the format list
+    fn build_format_row<'a>(&self, index: usize, format: &'a CameraFormat) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+        let is_active = self.active_format.as_ref() == Some(format);
+
+        let mut row = widget::Row::new()
+            .spacing(spacing.space_xs)
             .align_y(Alignment::Center)
             .push(
-                widget::text(fl!("format-resolution"))
-                    .size(ui::PICKER_LABEL_TEXT_SIZE)
-                    .width(Length::Fixed(ui::PICKER_LABEL_WIDTH)),
+                widget::text::body(format!("{}x{}", format.width, format.height))
+                    .width(Length::Fill),
+            )
+            .push(widget::text::caption(
+                format.pixel_format.trim().to_string(),
+            ));
+
+        if format.hardware_accelerated {
+            row = row.push(
+                widget::text::caption(fl!("format-badge-hw"))
+                    .class(cosmic::theme::style::Text::Accent),
             );
+        }
+        if let Some(bytes_per_sec) = usb_bandwidth::format_bytes_per_sec(format) {
+            row = row.push(widget::text::caption(bandwidth_label(bytes_per_sec)));
+        }
+        let usb_warning = self
+            .usb_link
+            .as_ref()
+            .and_then(|link| usb_bandwidth::advise(format, link))
+            .is_some();
+        if usb_warning || self.encoder_limit(format).is_some() {
+            row = row.push(
+                widget::icon::from_name("dialog-warning-symbolic")
+                    .symbolic(true)
+                    .size(16),
+            );
+        }
 
-        for &(res_label, width) in &unique_resolutions {
-            let centered_text = widget::container(widget::text(res_label))
-                .width(Length::Fill)
-                .align_x(cosmic::iced::alignment::Horizontal::Center);
-
-            let is_selected = width == selected_res;
-
-            // Use Suggested for selected, Text for unselected - COSMIC's native button highlighting
-            let button = widget::button::custom(centered_text)
-                .on_press(Message::PickerSelectResolution(width))
-                .class(if is_selected {
-                    cosmic::theme::Button::Suggested
-                } else {
-                    cosmic::theme::Button::Text
-                })
-                .width(Length::Fill);
-
-            // Wrap in styled container with overlay background
-            let styled_button = widget::container(button)
-                .style(OVERLAY_CONTAINER.style())
-                .width(Length::Fixed(BUTTON_WIDTH));
-
-            res_row = res_row.push(styled_button);
+        widget::button::custom(row.padding([0, spacing.space_xxs]))
+            .on_press(Message::PickerSelectFormat(index))
+            .class(if is_active {
+                cosmic::theme::Button::Suggested
+            } else {
+                cosmic::theme::Button::Text
+            })
+            .width(Length::Fill)
+            .into()
+    }
+
+    /// Build the format picker overlay
+    ///
+    /// A search field over a scrolling list of the camera's formats,
+    /// grouped by aspect ratio and frame rate (see [`super::list`]). Each
+    /// row shows the pixel format, a badge for hardware-decoded formats,
+    /// the estimated bandwidth, and a warning icon if the USB connection or
+    /// the selected video encoder may not cope; why is spelled out below
+    /// the list for the active format. Click outside to close.
+    pub fn build_format_picker(&self) -> Element<'_, Message> {
+        let spacing = cosmic::theme::spacing();
+
+        let search = widget::search_input(fl!("format-search"), &self.format_picker_query)
+            .on_input(Message::FormatPickerSearch)
+            .on_clear(Message::FormatPickerSearch(String::new()));
+
+        let groups = super::list::group_formats(&self.available_formats, &self.format_picker_query);
+        let mut list = widget::Column::new().spacing(spacing.space_xxxs);
+        if groups.is_empty() {
+            list = list.push(
+                widget::container(widget::text::body(fl!("format-no-match")))
+                    .padding(spacing.space_s)
+                    .center_x(Length::Fill),
+            );
+        }
+        for group in groups {
+            let framerate = group
+                .framerate
+                .map(|fps| fl!("format-group-fps", fps = fps.to_string()))
+                .unwrap_or_else(|| fl!("format-group-auto"));
+            list = list.push(
+                widget::text::caption_heading(format!("{} · {}", group.aspect, framerate))
+                    .width(Length::Fill),
+            );
+            for (index, format) in group.formats {
+                list = list.push(self.build_format_row(index, format));
+            }
         }
 
-        // Build framerate row
-        let mut fps_row = widget::Row::new()
-            .spacing(spacing.space_xxs)
-            .align_y(Alignment::Center)
+        let mut picker_column = widget::Column::new()
+            .spacing(spacing.space_xs)
+            .width(Length::Fixed(ui::PICKER_LIST_WIDTH))
+            .push(search)
             .push(
-                widget::text(fl!("format-framerate"))
-                    .size(ui::PICKER_LABEL_TEXT_SIZE)
-                    .width(Length::Fixed(ui::PICKER_LABEL_WIDTH)),
+                widget::scrollable(list)
+                    .width(Length::Fill)
+                    .height(Length::Shrink),
             );
 
-        if let Some(formats) = resolution_groups.get(&selected_res) {
-            use crate::backends::camera::types::Framerate;
-            use std::collections::HashSet;
-            let mut seen_fps: HashSet<Framerate> = HashSet::new();
-            let mut fps_buttons: Vec<(Framerate, usize)> = Vec::new();
-
-            // Collect unique framerates
-            for &(idx, fmt) in formats {
-                if let Some(fps) = fmt.framerate
-                    && seen_fps.insert(fps)
-                {
-                    fps_buttons.push((fps, idx));
-                }
+        // Why the active format may not work: the camera's USB connection
+        // can't carry it, or the video encoder can't record it
+        if let Some(format) = &self.active_format {
+            if let Some(advice) = self.active_format_usb_advice() {
+                picker_column = picker_column.push(warning_row(usb_advice_text(advice, format)));
             }
-
-            fps_buttons.sort_by(|(a, _), (b, _)| {
-                a.as_f64()
-                    .partial_cmp(&b.as_f64())
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            });
-
-            // Create framerate buttons
-            for (fps, idx) in fps_buttons {
-                let centered_text = widget::container(widget::text(fps.to_string()))
-                    .width(Length::Fill)
-                    .align_x(cosmic::iced::alignment::Horizontal::Center);
-
-                // Check if this framerate matches the active format's framerate
-                // (not exact format match, since pixel format may differ)
-                let is_selected = self
-                    .active_format
-                    .as_ref()
-                    .and_then(|active| active.framerate)
-                    .map(|active_fps| active_fps == fps)
-                    .unwrap_or(false);
-
-                // Use Suggested for selected, Text for unselected - COSMIC's native button highlighting
-                let button = widget::button::custom(centered_text)
-                    .on_press(Message::PickerSelectFormat(idx))
-                    .class(if is_selected {
-                        cosmic::theme::Button::Suggested
-                    } else {
-                        cosmic::theme::Button::Text
-                    })
-                    .width(Length::Fill);
-
-                // Wrap in styled container with overlay background
-                let styled_button = widget::container(button)
-                    .style(OVERLAY_CONTAINER.style())
-                    .width(Length::Fixed(BUTTON_WIDTH));
-
-                fps_row = fps_row.push(styled_button);
+            if let Some(limit) = self.encoder_limit(format)
+                && let Some(encoder) = self
+                    .available_video_encoders
+                    .get(self.current_video_encoder_index)
+            {
+                picker_column = picker_column.push(warning_row(encoder_limit_text(
+                    limit,
+                    &encoder.display_name,
+                    format,
+                )));
             }
         }
 
         // Build picker panel with semi-transparent themed background
-        // Uses PICKER_PANEL which caps roundness at "slightly rounded"
-        let picker_panel = self.frosted_panel(
-            widget::Column::new()
-                .push(res_row)
-                .push(widget::space::vertical().height(spacing.space_s))
-                .push(fps_row)
-                .padding(spacing.space_xs)
-                .into(),
-            PICKER_PANEL,
-        );
+        // Uses PICKER_PANEL which caps roundness at "slightly rounded".
+        // Clicks on the panel itself (headings, gaps) must not close it.
+        let picker_panel = widget::mouse_area(
+            self.frosted_panel(
+                widget::container(picker_column.padding(spacing.space_xs))
+                    .max_height(ui::PICKER_LIST_MAX_HEIGHT)
+                    .into(),
+                PICKER_PANEL,
+            ),
+        )
+        .on_press(Message::Noop);
 
         // Position picker and add click-outside-to-close
         let picker_positioned = widget::Row::new()
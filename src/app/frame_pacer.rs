@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Preview frame pacing
+//!
+//! The camera subscription used to send every preview frame to the UI as a
+//! message. A busy UI thread let them queue up, then played the backlog back
+//! late or lost whole runs of it once the channel filled. Now three buffers
+//! sit between the camera and the screen: the frame the capture thread is
+//! filling, the newest finished one waiting in a [`FrameMailbox`], and the
+//! one on screen. Neither side ever waits for the other. A newer frame
+//! replaces the waiting one, and the UI is only woken when the mailbox goes
+//! from empty to full, so wake-ups can't pile up either.
+//!
+//! [`FramePacer`] decides when the UI takes the waiting frame: once the
+//! renderer has uploaded the previous one, which it does at most once per
+//! display refresh. Presentation follows the display that way, and a burst
+//! from the ISP can't put two frames into one refresh where the first would
+//! never be seen. Frames replaced in the mailbox meanwhile count as dropped.
+//! A frame that reaches the screen later than the camera's own frame
+//! interval leaves the previous one up for longer, which counts as a
+//! duplicated frame.
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// How often a held frame checks whether the renderer has caught up
+pub const POLL_INTERVAL: Duration = Duration::from_millis(4);
+
+/// Longest a frame is held for the renderer. Nothing is uploaded while the
+/// preview is hidden, and the app still wants its frames then.
+const MAX_HOLD: Duration = Duration::from_millis(50);
+
+/// The newest preview frame the UI hasn't taken yet
+#[derive(Debug, Default)]
+pub struct FrameMailbox<T> {
+    slot: Mutex<Option<T>>,
+    /// Frames that never reached the UI
+    dropped: AtomicU64,
+}
+
+impl<T> FrameMailbox<T> {
+    /// Leave `frame` for the UI, replacing any it hasn't taken. Returns
+    /// whether the mailbox was empty, in which case the UI needs waking.
+    pub fn post(&self, frame: T) -> bool {
+        let mut slot = self.slot.lock().unwrap_or_else(|e| e.into_inner());
+        let was_empty = slot.replace(frame).is_none();
+        if !was_empty {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        was_empty
+    }
+
+    /// Take the waiting frame
+    pub fn take(&self) -> Option<T> {
+        self.slot.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+
+    /// Count frames skipped before they were posted
+    pub fn count_dropped(&self, frames: u64) {
+        self.dropped.fetch_add(frames, Ordering::Relaxed);
+    }
+
+    /// Frames replaced or skipped before the UI took them
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// The last frame put on screen
+#[derive(Debug, Clone, Copy)]
+struct Presented {
+    /// Address of its pixel data, which the renderer reports on upload
+    key: usize,
+    at: Instant,
+    captured_at: Instant,
+    /// Whether the next frame waits for this one to be drawn. Not when the
+    /// one before wasn't drawn either: the preview is hidden, and holding
+    /// every frame for nothing would only cost wake-ups.
+    hold: bool,
+}
+
+/// Paces the preview to the renderer
+#[derive(Debug, Default)]
+pub struct FramePacer {
+    last: Option<Presented>,
+    /// A check for the renderer is scheduled
+    pub poll_scheduled: bool,
+    /// Frames that stayed up longer than the camera's frame interval, in
+    /// extra intervals
+    duplicated: u64,
+}
+
+impl FramePacer {
+    /// Whether the next frame may go on screen at `now`, with `uploaded` the
+    /// key of the frame the renderer took last
+    pub fn ready(&self, now: Instant, uploaded: usize) -> bool {
+        self.last.is_none_or(|last| {
+            !last.hold || last.key == uploaded || now.duration_since(last.at) >= MAX_HOLD
+        })
+    }
+
+    /// The frame keyed `key`, captured at `captured_at`, went on screen at
+    /// `now`; `uploaded` is as for [`Self::ready`]
+    pub fn on_present(&mut self, now: Instant, key: usize, captured_at: Instant, uploaded: usize) {
+        let drawn = self.last.is_none_or(|last| last.key == uploaded);
+        // Only a frame that was drawn can have been drawn for too long
+        if let Some(last) = self.last
+            && drawn
+        {
+            let camera = captured_at.saturating_duration_since(last.captured_at);
+            let shown = now.saturating_duration_since(last.at);
+            if !camera.is_zero() && shown > camera + camera / 2 {
+                self.duplicated += (shown.as_secs_f64() / camera.as_secs_f64()).round() as u64 - 1;
+            }
+        }
+        self.last = Some(Presented {
+            key,
+            at: now,
+            captured_at,
+            hold: drawn,
+        });
+    }
+
+    /// Forget the last frame, which didn't reach the screen after all
+    pub fn forget(&mut self) {
+        self.last = None;
+    }
+
+    /// Extra camera intervals frames stayed on screen for
+    pub fn duplicated(&self) -> u64 {
+        self.duplicated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mailbox_keeps_the_newest_frame_and_wakes_once() {
+        let mailbox = FrameMailbox::default();
+        assert!(mailbox.post(1));
+        assert!(!mailbox.post(2));
+        assert!(!mailbox.post(3));
+        mailbox.count_dropped(4);
+        assert_eq!(mailbox.take(), Some(3));
+        assert_eq!(mailbox.take(), None);
+        assert!(mailbox.post(5));
+        assert_eq!(mailbox.dropped(), 6);
+    }
+
+    #[test]
+    fn frames_wait_for_the_renderer_and_late_ones_count_as_duplicated() {
+        let ms = Duration::from_millis;
+        let start = Instant::now();
+        let mut pacer = FramePacer::default();
+        assert!(pacer.ready(start, 0));
+
+        // 30 fps, each drawn in time
+        pacer.on_present(start, 1, start, 0);
+        assert!(!pacer.ready(start + ms(10), 0));
+        assert!(pacer.ready(start + ms(10), 1));
+        pacer.on_present(start + ms(33), 2, start + ms(33), 1);
+        assert_eq!(pacer.duplicated(), 0);
+
+        // The next one reaches the screen two intervals late
+        pacer.on_present(start + ms(133), 3, start + ms(66), 2);
+        assert_eq!(pacer.duplicated(), 2);
+
+        // A hidden preview never uploads: a frame goes after a while anyway,
+        // and the ones after it right away
+        assert!(!pacer.ready(start + ms(150), 2));
+        assert!(pacer.ready(start + ms(183), 2));
+        pacer.on_present(start + ms(300), 4, start + ms(100), 2);
+        assert!(pacer.ready(start + ms(301), 2));
+        assert_eq!(pacer.duplicated(), 2);
+    }
+}
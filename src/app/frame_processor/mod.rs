@@ -3,11 +3,21 @@
 //! Frame processor module for async frame analysis
 //!
 //! This module provides a system for sampling camera frames at intervals
-//! and running async detection tasks. Currently implements QR code detection.
+//! and running async detection tasks. Currently implements QR code detection,
+//! the raised-palm gesture for the gesture shutter, histogram-based exposure
+//! suggestions for manual mode, the white balance eyedropper's gray point,
+//! page signatures for document auto-capture, board signatures for
+//! whiteboard mode, faces for face-priority exposure, the scene
+//! classification behind capture mode suggestions, and the barcodes of
+//! Scan mode.
 
 pub mod tasks;
 pub mod types;
 
 pub use tasks::qr_detector;
 pub(crate) use types::urlencoding_encode;
-pub use types::{FrameRegion, QrAction, QrDetection, WifiSecurity};
+pub use types::{
+    BarcodeDetection, BoardSignature, ExposureSuggestion, FaceDetection, FrameRegion,
+    GestureDetection, GrayPoint, PageSignature, QrAction, QrDetection, Scene, WifiSecurity,
+    wifi_payload,
+};
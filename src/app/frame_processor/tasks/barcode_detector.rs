@@ -0,0 +1,141 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Barcode detection for Scan mode
+//!
+//! Where the QR overlay only looks for QR codes once a second, Scan mode
+//! decodes every symbology rxing knows (EAN/UPC, Code 128, Code 39, ITF,
+//! Data Matrix, PDF417, QR, ...) several times a second, so items can be
+//! swept past the camera one after another.
+
+use crate::app::frame_processor::types::{BarcodeDetection, FrameRegion};
+use crate::backends::camera::types::CameraFrame;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{trace, warn};
+
+use super::qr_detector::{convert_to_gray, downscale_gray};
+
+/// Time between analysed frames in Scan mode
+pub const SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Longest side frames are scaled down to. Higher than the QR detector's:
+/// the narrow bars of a 1D code blur together sooner than QR modules.
+const MAX_DIMENSION: u32 = 1280;
+
+/// Barcode detector
+pub struct BarcodeDetector;
+
+impl Default for BarcodeDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BarcodeDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Decode every barcode in a camera frame
+    pub async fn detect(&self, frame: Arc<CameraFrame>) -> Vec<BarcodeDetection> {
+        tokio::task::spawn_blocking(move || detect_sync(&frame))
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "Barcode detection task panicked");
+                Vec::new()
+            })
+    }
+}
+
+fn detect_sync(frame: &CameraFrame) -> Vec<BarcodeDetection> {
+    let start = std::time::Instant::now();
+    let (gray, width, height) = convert_to_gray(frame);
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+
+    let scale = (width.max(height) as f32 / MAX_DIMENSION as f32).max(1.0);
+    let (gray, proc_width, proc_height) = if scale > 1.0 {
+        let proc_width = (width as f32 / scale) as u32;
+        let proc_height = (height as f32 / scale) as u32;
+        (
+            downscale_gray(&gray, width, height, proc_width, proc_height),
+            proc_width,
+            proc_height,
+        )
+    } else {
+        (gray, width, height)
+    };
+
+    // Nothing found is reported as an error
+    let results =
+        rxing::helpers::detect_multiple_in_luma(gray, proc_width, proc_height).unwrap_or_default();
+
+    let detections: Vec<BarcodeDetection> = results
+        .iter()
+        .filter(|result| !result.getText().is_empty())
+        .map(|result| BarcodeDetection {
+            format: result.getBarcodeFormat().to_string(),
+            content: result.getText().to_string(),
+            bounds: bounds_of(
+                result.getPoints().iter().map(|p| (p.x, p.y)),
+                (proc_width, proc_height),
+            ),
+        })
+        .collect();
+
+    trace!(
+        count = detections.len(),
+        elapsed_ms = start.elapsed().as_millis(),
+        "Barcode detection complete"
+    );
+    detections
+}
+
+/// Normalized bounding box of a code's points, in a `size` frame. 1D codes
+/// only report points along the scan line, so the box may have no height.
+fn bounds_of(points: impl Iterator<Item = (f32, f32)>, size: (u32, u32)) -> FrameRegion {
+    let (mut min_x, mut min_y) = (f32::MAX, f32::MAX);
+    let (mut max_x, mut max_y) = (f32::MIN, f32::MIN);
+    for (x, y) in points {
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x);
+        max_y = max_y.max(y);
+    }
+    if min_x > max_x {
+        return FrameRegion {
+            x: 0.0,
+            y: 0.0,
+            width: 0.0,
+            height: 0.0,
+        };
+    }
+    let (width, height) = (size.0 as f32, size.1 as f32);
+    FrameRegion {
+        x: (min_x / width).clamp(0.0, 1.0),
+        y: (min_y / height).clamp(0.0, 1.0),
+        width: ((max_x - min_x) / width).clamp(0.0, 1.0),
+        height: ((max_y - min_y) / height).clamp(0.0, 1.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_cover_the_points() {
+        let region = bounds_of([(100.0, 50.0), (300.0, 50.0)].into_iter(), (400, 200));
+        assert_eq!(
+            region,
+            FrameRegion {
+                x: 0.25,
+                y: 0.25,
+                width: 0.5,
+                height: 0.0,
+            }
+        );
+        assert_eq!(bounds_of(std::iter::empty(), (400, 200)).width, 0.0);
+    }
+}
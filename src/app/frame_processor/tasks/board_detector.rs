@@ -0,0 +1,313 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Board detection for whiteboard mode
+//!
+//! Each sampled frame is reduced to a [`BoardSignature`]: a luma grid fine
+//! enough to see a new line of writing, plus the corners of the bright
+//! region a whiteboard makes against the wall around it. The corners are
+//! offered as the keystone to lock for a mounted camera.
+//!
+//! [`BoardFeed`] takes a snapshot once the picture has held still for a
+//! while and the writing differs from the last snapshot, so a lecture or a
+//! meeting is kept one board state at a time without anyone pressing a
+//! button. Grid differences are measured after removing the overall
+//! brightness change, so auto exposure settling doesn't count as new
+//! writing.
+
+use crate::app::frame_processor::types::BoardSignature;
+use crate::backends::camera::types::CameraFrame;
+use crate::media::whiteboard::BoardQuad;
+use crate::terminal::sample_pixel_rgb;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{trace, warn};
+
+/// Time between analysed frames in whiteboard mode
+pub const SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long the picture must hold still before a snapshot is taken. Long
+/// enough that someone pausing mid-sentence in front of the board isn't
+/// taken for finished writing.
+pub const SETTLE_DURATION: Duration = Duration::from_secs(3);
+
+/// Columns and rows of the luma grid
+const GRID: (usize, usize) = (48, 36);
+
+/// Samples per cell along each axis, averaged into the cell's luma so a
+/// thin stroke between sample points still registers
+const CELL_SAMPLES: usize = 4;
+
+/// Luma change (0-1) of a cell that counts as the cell having changed
+const CELL_CHANGE_THRESHOLD: f32 = 0.04;
+
+/// Share of changed cells between consecutive samples that counts as motion
+/// (a couple of cells, so a marker stroke in progress keeps the timer back)
+const MOTION_FRACTION: f32 = 0.001;
+
+/// Share of changed cells since the last snapshot that counts as new
+/// writing (a few words)
+const CONTENT_CHANGE_FRACTION: f32 = 0.003;
+
+/// Smallest bright region, as a fraction of the frame, taken for a board
+const MIN_BOARD_FRACTION: f32 = 0.2;
+
+/// Board signature extraction
+pub struct BoardDetector;
+
+impl Default for BoardDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BoardDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Reduce a camera frame to its board signature
+    pub async fn detect(&self, frame: Arc<CameraFrame>) -> Option<BoardSignature> {
+        tokio::task::spawn_blocking(move || {
+            let start = std::time::Instant::now();
+            let signature = signature_from_frame(&frame);
+            trace!(
+                board = signature.quad.is_some(),
+                elapsed_ms = start.elapsed().as_millis(),
+                "Board detection complete"
+            );
+            signature
+        })
+        .await
+        .map_err(|e| warn!(error = %e, "Board detection task panicked"))
+        .ok()
+    }
+}
+
+fn signature_from_frame(frame: &CameraFrame) -> BoardSignature {
+    let (columns, rows) = GRID;
+    let (sample_columns, sample_rows) = (columns * CELL_SAMPLES, rows * CELL_SAMPLES);
+    let mut luma = vec![0.0f32; columns * rows];
+    for sample_row in 0..sample_rows {
+        let y = ((2 * sample_row + 1) as u64 * u64::from(frame.height) / (2 * sample_rows) as u64)
+            as u32;
+        for sample_column in 0..sample_columns {
+            let x = ((2 * sample_column + 1) as u64 * u64::from(frame.width)
+                / (2 * sample_columns) as u64) as u32;
+            let (r, g, b) = sample_pixel_rgb(frame, x, y);
+            let cell = (sample_row / CELL_SAMPLES) * columns + sample_column / CELL_SAMPLES;
+            luma[cell] += 0.299 * f32::from(r) + 0.587 * f32::from(g) + 0.114 * f32::from(b);
+        }
+    }
+    let scale = 1.0 / (255.0 * (CELL_SAMPLES * CELL_SAMPLES) as f32);
+    luma.iter_mut().for_each(|l| *l *= scale);
+
+    let quad = board_quad(&luma, columns, rows);
+    BoardSignature { luma, quad }
+}
+
+/// Corners of the largest bright region, if it covers enough of the frame
+/// to be a board
+///
+/// Cells are split at the midpoint between the darkest and brightest tenth
+/// like the document page outline. The corners are the region's extreme
+/// cells along the two diagonals, which follows a board seen at an angle.
+fn board_quad(luma: &[f32], columns: usize, rows: usize) -> Option<BoardQuad> {
+    let mut sorted = luma.to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let dark = sorted.get(sorted.len() / 10).copied()?;
+    let bright = sorted.get(sorted.len() * 9 / 10).copied()?;
+    if bright - dark < 0.1 {
+        return None;
+    }
+    let threshold = (dark + bright) / 2.0;
+    let is_bright: Vec<bool> = luma.iter().map(|&l| l > threshold).collect();
+
+    // Largest 4-connected bright region; writing splits the board into
+    // pieces only where it crosses the whole board, which it rarely does
+    let mut label = vec![usize::MAX; luma.len()];
+    let mut best: Vec<usize> = Vec::new();
+    for seed in 0..luma.len() {
+        if !is_bright[seed] || label[seed] != usize::MAX {
+            continue;
+        }
+        let mut region = vec![seed];
+        label[seed] = seed;
+        let mut next = 0;
+        while let Some(&cell) = region.get(next) {
+            next += 1;
+            let (x, y) = (cell % columns, cell / columns);
+            let neighbours = [
+                (x > 0).then(|| cell - 1),
+                (x + 1 < columns).then(|| cell + 1),
+                (y > 0).then(|| cell - columns),
+                (y + 1 < rows).then(|| cell + columns),
+            ];
+            for neighbour in neighbours.into_iter().flatten() {
+                if is_bright[neighbour] && label[neighbour] == usize::MAX {
+                    label[neighbour] = seed;
+                    region.push(neighbour);
+                }
+            }
+        }
+        if region.len() > best.len() {
+            best = region;
+        }
+    }
+    if (best.len() as f32) < (columns * rows) as f32 * MIN_BOARD_FRACTION {
+        return None;
+    }
+
+    let extreme = |key: fn(f32, f32) -> f32, corner: (f32, f32)| {
+        best.iter()
+            .map(|&cell| ((cell % columns) as f32, (cell / columns) as f32))
+            .max_by(|a, b| key(a.0, a.1).total_cmp(&key(b.0, b.1)))
+            // Out to the cell's outer corner
+            .map(|(x, y)| {
+                (
+                    (x + corner.0) / columns as f32,
+                    (y + corner.1) / rows as f32,
+                )
+            })
+    };
+    Some(BoardQuad {
+        corners: [
+            extreme(|x, y| -x - y, (0.0, 0.0))?,
+            extreme(|x, y| x - y, (1.0, 0.0))?,
+            extreme(|x, y| x + y, (1.0, 1.0))?,
+            extreme(|x, y| y - x, (0.0, 1.0))?,
+        ],
+    })
+}
+
+/// Share of cells that changed between two signatures, after taking out
+/// the change in overall brightness
+fn changed_fraction(a: &BoardSignature, b: &BoardSignature) -> f32 {
+    if a.luma.len() != b.luma.len() || a.luma.is_empty() {
+        return 1.0;
+    }
+    let len = a.luma.len() as f32;
+    let shift = (b.luma.iter().sum::<f32>() - a.luma.iter().sum::<f32>()) / len;
+    let changed = a
+        .luma
+        .iter()
+        .zip(&b.luma)
+        .filter(|(a, b)| (*b - *a - shift).abs() > CELL_CHANGE_THRESHOLD)
+        .count();
+    changed as f32 / len
+}
+
+/// Decides from successive signatures when the board should be snapshotted
+#[derive(Debug, Default)]
+pub struct BoardFeed {
+    /// Signature of the previous sample
+    previous: Option<BoardSignature>,
+    /// When the picture stopped moving
+    still_since: Option<Instant>,
+    /// Signature of the board at the last snapshot
+    last_snapshot: Option<BoardSignature>,
+}
+
+impl BoardFeed {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the signature sampled at `now`; returns true when the board
+    /// has new writing on it and has settled
+    pub fn observe(&mut self, signature: BoardSignature, now: Instant) -> bool {
+        let moving = self
+            .previous
+            .as_ref()
+            .is_none_or(|previous| changed_fraction(previous, &signature) > MOTION_FRACTION);
+        if moving {
+            self.still_since = None;
+        }
+        let still_since = *self.still_since.get_or_insert(now);
+
+        let ready = now.duration_since(still_since) >= SETTLE_DURATION
+            && self
+                .last_snapshot
+                .as_ref()
+                .is_none_or(|last| changed_fraction(last, &signature) >= CONTENT_CHANGE_FRACTION);
+        if ready {
+            self.last_snapshot = Some(signature.clone());
+        }
+        self.previous = Some(signature);
+        ready
+    }
+
+    /// A snapshot was taken by hand; don't take the same board again
+    pub fn snapshot_taken(&mut self) {
+        self.last_snapshot = self.previous.clone();
+    }
+
+    /// Forget the snapshots taken so far (new session)
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A bright board over the middle of the frame, `words` cells of it
+    /// written on, at overall `exposure`
+    fn board(words: usize, exposure: f32) -> BoardSignature {
+        let (width, height) = GRID;
+        let luma: Vec<f32> = (0..width * height)
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                let value = if (8..40).contains(&x) && (6..30).contains(&y) {
+                    if y == 10 && x - 8 < words { 0.6 } else { 0.85 }
+                } else {
+                    0.3
+                };
+                value * exposure
+            })
+            .collect();
+        let quad = board_quad(&luma, width, height);
+        BoardSignature { luma, quad }
+    }
+
+    #[test]
+    fn quad_follows_the_board() {
+        let quad = board(0, 1.0).quad.expect("board found");
+        let [tl, _, br, _] = quad.corners;
+        assert!((tl.0 - 8.0 / 48.0).abs() < 1e-4 && (tl.1 - 6.0 / 36.0).abs() < 1e-4);
+        assert!((br.0 - 40.0 / 48.0).abs() < 1e-4 && (br.1 - 30.0 / 36.0).abs() < 1e-4);
+
+        let wall = vec![0.3; GRID.0 * GRID.1];
+        assert!(board_quad(&wall, GRID.0, GRID.1).is_none());
+    }
+
+    #[test]
+    fn snapshots_follow_new_writing() {
+        let start = Instant::now();
+        let at = |s: u64| start + Duration::from_secs(s);
+        let mut feed = BoardFeed::new();
+
+        // The board as the session starts, once settled
+        assert!(!feed.observe(board(0, 1.0), at(0)));
+        assert!(feed.observe(board(0, 1.0), at(3)));
+        assert!(!feed.observe(board(0, 1.0), at(6)));
+
+        // Exposure drifting isn't new writing
+        assert!(!feed.observe(board(0, 0.95), at(7)));
+        assert!(!feed.observe(board(0, 0.95), at(11)));
+
+        // A few words written, then left alone
+        assert!(!feed.observe(board(8, 1.0), at(12)));
+        assert!(!feed.observe(board(8, 1.0), at(13)));
+        assert!(feed.observe(board(8, 1.0), at(15)));
+    }
+
+    #[test]
+    fn manual_snapshot_counts_as_taken() {
+        let start = Instant::now();
+        let mut feed = BoardFeed::new();
+        feed.observe(board(8, 1.0), start);
+        feed.snapshot_taken();
+        assert!(!feed.observe(board(8, 1.0), start + Duration::from_secs(5)));
+    }
+}
@@ -0,0 +1,322 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Histogram-based exposure suggestions for manual mode
+//!
+//! The frame is sampled into a luma histogram and the exposure change that
+//! brings its mean to mid-gray is worked out, capped so the brightest
+//! percentile doesn't clip. The change goes into exposure time first, up to
+//! a handheld limit, and only the rest into gain, since gain adds noise.
+//!
+//! [`face_exposure_bias`] does the same for face-priority auto exposure on
+//! cameras without a metering region: the exposure bias is nudged until the
+//! detected face, rather than the whole frame, sits at the target.
+
+use crate::app::exposure_picker::ControlRange;
+use crate::app::frame_processor::types::ExposureSuggestion;
+use crate::backends::camera::types::CameraFrame;
+use crate::terminal::sample_pixel_rgb;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Width of the grid frames are sampled on
+const GRID_WIDTH: u32 = 160;
+
+/// Mean luma the suggestion aims for (18% gray after sRGB encoding)
+const TARGET_MEAN: f32 = 118.0;
+
+/// Luma the bright percentile may reach before it counts as clipping
+const HIGHLIGHT_LIMIT: f32 = 245.0;
+
+/// Fraction of pixels allowed above [`HIGHLIGHT_LIMIT`]
+const HIGHLIGHT_PERCENTILE: f32 = 0.99;
+
+/// Largest correction suggested at once, in either direction
+const MAX_RATIO: f32 = 16.0;
+
+/// Longest exposure time suggested before gain is raised (1/30 s in 100µs units)
+const HANDHELD_LIMIT: i32 = 333;
+
+/// Amplification assumed at the top of the gain range
+///
+/// V4L2 doesn't report what gain values mean; most UVC sensors span about
+/// 18 dB (8x) linearly from the bottom of the range.
+const MAX_GAIN_FACTOR: f32 = 8.0;
+
+/// Face luma (0-1) face-priority exposure aims for: skin a little above
+/// mid-gray, where a portrait meter places it
+const FACE_TARGET_LUMA: f32 = 0.45;
+
+/// Share of the face's exposure error corrected per detection. Auto
+/// exposure takes a moment to follow a bias change, so stepping part of the
+/// way avoids overshooting on the next sample.
+const FACE_CORRECTION: f32 = 0.5;
+
+/// Bias change (0.001 EV) too small to be worth applying
+const FACE_DEADBAND: i32 = 200;
+
+/// Furthest (0.001 EV) the face may pull the bias from the user's setting
+const FACE_MAX_OFFSET: i32 = 2000;
+
+/// Exposure controls the suggestion works within
+#[derive(Debug, Clone)]
+pub struct ExposureInputs {
+    /// Current exposure time in 100µs units
+    pub exposure_time: i32,
+    pub exposure_range: ControlRange,
+    /// Current gain, `None` when the camera has no gain control
+    pub gain: Option<i32>,
+    pub gain_range: ControlRange,
+}
+
+/// Compute an exposure suggestion from a frame's luma histogram
+pub async fn suggest(
+    frame: Arc<CameraFrame>,
+    inputs: ExposureInputs,
+) -> Option<ExposureSuggestion> {
+    tokio::task::spawn_blocking(move || {
+        let histogram = LumaHistogram::from_frame(&frame, GRID_WIDTH);
+        let suggestion = suggest_from_histogram(&histogram, &inputs);
+        debug!(
+            mean = histogram.mean(),
+            ?suggestion,
+            "Exposure suggestion computed"
+        );
+        suggestion
+    })
+    .await
+    .unwrap_or_else(|e| {
+        warn!(error = %e, "Exposure analysis task panicked");
+        None
+    })
+}
+
+/// 256-bin luma histogram
+struct LumaHistogram {
+    bins: [u32; 256],
+    total: u32,
+}
+
+impl LumaHistogram {
+    fn from_frame(frame: &CameraFrame, grid_width: u32) -> Self {
+        let width = grid_width.min(frame.width).max(1);
+        let height = (u64::from(frame.height) * u64::from(width) / u64::from(frame.width.max(1)))
+            .max(1) as u32;
+
+        let mut histogram = Self {
+            bins: [0; 256],
+            total: 0,
+        };
+        for gy in 0..height {
+            let sy = gy * frame.height / height;
+            for gx in 0..width {
+                let sx = gx * frame.width / width;
+                let (r, g, b) = sample_pixel_rgb(frame, sx, sy);
+                let luma =
+                    (0.2126 * f32::from(r) + 0.7152 * f32::from(g) + 0.0722 * f32::from(b)) as u8;
+                histogram.add(luma);
+            }
+        }
+        histogram
+    }
+
+    fn add(&mut self, luma: u8) {
+        self.bins[luma as usize] += 1;
+        self.total += 1;
+    }
+
+    fn mean(&self) -> f32 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        let sum: u64 = self
+            .bins
+            .iter()
+            .enumerate()
+            .map(|(luma, &count)| luma as u64 * u64::from(count))
+            .sum();
+        sum as f32 / self.total as f32
+    }
+
+    /// Luma below which `fraction` of the pixels lie
+    fn percentile(&self, fraction: f32) -> f32 {
+        let target = (self.total as f32 * fraction).ceil() as u32;
+        let mut seen = 0;
+        for (luma, &count) in self.bins.iter().enumerate() {
+            seen += count;
+            if seen >= target {
+                return luma as f32;
+            }
+        }
+        255.0
+    }
+
+    /// Share of pixels at full scale, where the true brightness is unknown
+    fn clipped_fraction(&self) -> f32 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.bins[255] as f32 / self.total as f32
+    }
+}
+
+/// Exposure time and gain that bring the histogram toward mid-gray
+fn suggest_from_histogram(
+    histogram: &LumaHistogram,
+    inputs: &ExposureInputs,
+) -> Option<ExposureSuggestion> {
+    if histogram.total == 0 || !inputs.exposure_range.available {
+        return None;
+    }
+
+    let mean = histogram.mean().max(1.0);
+    let mut ratio = TARGET_MEAN / mean;
+    // Clipped highlights hide how much too bright the frame is: step down
+    // at least a stop so the next suggestion can see the real histogram
+    if histogram.clipped_fraction() > 1.0 - HIGHLIGHT_PERCENTILE {
+        ratio = ratio.min(0.5);
+    }
+    let highlight = histogram.percentile(HIGHLIGHT_PERCENTILE).max(1.0);
+    if ratio > 1.0 {
+        ratio = ratio.min((HIGHLIGHT_LIMIT / highlight).max(1.0));
+    }
+    let ratio = ratio.clamp(1.0 / MAX_RATIO, MAX_RATIO);
+
+    let gain_range = &inputs.gain_range;
+    let gain = inputs.gain.filter(|_| gain_range.available);
+    let current_gain_factor = gain.map_or(1.0, |g| gain_factor(g, gain_range));
+    let wanted = inputs.exposure_time.max(1) as f32 * current_gain_factor * ratio;
+
+    // Exposure time takes as much of the total as the handheld limit allows
+    let time_range = &inputs.exposure_range;
+    let time_limit = HANDHELD_LIMIT
+        .max(inputs.exposure_time)
+        .clamp(time_range.min, time_range.max);
+    let exposure_time = snap(wanted.round() as i32, time_range).min(time_limit);
+
+    let gain = gain.map(|_| {
+        let needed_factor = wanted / exposure_time.max(1) as f32;
+        snap(gain_from_factor(needed_factor, gain_range), gain_range)
+    });
+
+    Some(ExposureSuggestion {
+        exposure_time,
+        gain,
+        ev_change: ratio.log2(),
+    })
+}
+
+/// Exposure bias (0.001 EV) that moves a face of `face_luma` toward the
+/// target, or `None` when the current bias is close enough
+///
+/// The result stays within [`FACE_MAX_OFFSET`] of `user_bias`, so the face
+/// refines the user's compensation instead of replacing it.
+pub fn face_exposure_bias(
+    face_luma: f32,
+    user_bias: i32,
+    current_bias: i32,
+    range: &ControlRange,
+) -> Option<i32> {
+    if !range.available {
+        return None;
+    }
+    // Luma is gamma encoded; one stop moves it by about 2^(1/2.2)
+    let error_ev = 2.2 * (FACE_TARGET_LUMA / face_luma.max(0.02)).log2();
+    let wanted = current_bias + (error_ev * FACE_CORRECTION * 1000.0).round() as i32;
+    let bias = snap(
+        wanted.clamp(user_bias - FACE_MAX_OFFSET, user_bias + FACE_MAX_OFFSET),
+        range,
+    );
+    ((bias - current_bias).abs() >= FACE_DEADBAND).then_some(bias)
+}
+
+/// Linear amplification of a gain value, 1.0 at the bottom of the range
+fn gain_factor(gain: i32, range: &ControlRange) -> f32 {
+    let span = (range.max - range.min).max(1) as f32;
+    1.0 + (gain - range.min) as f32 / span * (MAX_GAIN_FACTOR - 1.0)
+}
+
+/// Gain value giving `factor` amplification
+fn gain_from_factor(factor: f32, range: &ControlRange) -> i32 {
+    let span = (range.max - range.min).max(1) as f32;
+    range.min + ((factor - 1.0).max(0.0) / (MAX_GAIN_FACTOR - 1.0) * span).round() as i32
+}
+
+/// Clamp a value into the control range and onto its step
+fn snap(value: i32, range: &ControlRange) -> i32 {
+    let value = value.clamp(range.min, range.max);
+    let step = range.step.max(1);
+    range.min + (value - range.min) / step * step
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat(luma: u8, count: u32) -> LumaHistogram {
+        let mut histogram = LumaHistogram {
+            bins: [0; 256],
+            total: 0,
+        };
+        for _ in 0..count {
+            histogram.add(luma);
+        }
+        histogram
+    }
+
+    fn inputs(exposure_time: i32, gain: Option<i32>) -> ExposureInputs {
+        ExposureInputs {
+            exposure_time,
+            exposure_range: ControlRange::new(1, 5000, 1, 156),
+            gain,
+            gain_range: ControlRange::new(0, 255, 1, 0),
+        }
+    }
+
+    #[test]
+    fn dark_frame_lengthens_exposure_first() {
+        let suggestion = suggest_from_histogram(&flat(59, 100), &inputs(100, Some(0))).unwrap();
+        assert_eq!(suggestion.exposure_time, 200);
+        assert_eq!(suggestion.gain, Some(0));
+        assert!((suggestion.ev_change - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn gain_covers_what_the_handheld_limit_cannot() {
+        let suggestion = suggest_from_histogram(&flat(15, 100), &inputs(200, Some(0))).unwrap();
+        assert_eq!(suggestion.exposure_time, HANDHELD_LIMIT);
+        assert!(suggestion.gain.unwrap() > 0);
+    }
+
+    #[test]
+    fn clipped_frame_steps_down_at_least_a_stop() {
+        let mut histogram = flat(255, 50);
+        for _ in 0..50 {
+            histogram.add(100);
+        }
+        let suggestion = suggest_from_histogram(&histogram, &inputs(400, None)).unwrap();
+        assert!(suggestion.exposure_time <= 200);
+        assert_eq!(suggestion.gain, None);
+    }
+
+    #[test]
+    fn highlights_limit_brightening() {
+        let mut histogram = flat(30, 98);
+        histogram.add(200);
+        histogram.add(200);
+        let suggestion = suggest_from_histogram(&histogram, &inputs(100, None)).unwrap();
+        assert!(suggestion.exposure_time <= 123);
+    }
+
+    #[test]
+    fn face_bias_brightens_backlit_faces_within_limits() {
+        let range = ControlRange::new(-3000, 3000, 1, 0);
+        // A face in silhouette is pulled up, but only so far from the user's bias
+        assert_eq!(face_exposure_bias(0.1, 0, 0, &range), Some(2000));
+        let bias = face_exposure_bias(0.1, 1500, 0, &range).unwrap();
+        assert!((2300..2500).contains(&bias), "{bias}");
+        // An overexposed face is brought down
+        assert_eq!(face_exposure_bias(0.9, 0, 0, &range), Some(-1100));
+        // Close enough: leave the bias alone
+        assert_eq!(face_exposure_bias(0.44, 0, 500, &range), None);
+    }
+}
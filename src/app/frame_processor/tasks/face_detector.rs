@@ -0,0 +1,212 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Face detection for face-priority auto exposure
+//!
+//! Works on the same skin map as the gesture detector. A face is the
+//! largest skin blob that is about as tall as it is wide (a little taller
+//! with the neck), mostly solid apart from the eyes and mouth, and doesn't
+//! show spread fingers. That is coarse, but exposure only needs to know
+//! roughly where the face is and how bright its skin came out; a backlit
+//! selfie or a video call in front of a window is exactly the case where
+//! the face is the one big skin-coloured thing in the picture.
+
+use super::gesture_detector::{Blob, SkinMask, label_blobs, shows_spread_fingers};
+use crate::app::frame_processor::types::{FaceDetection, FrameRegion};
+use crate::backends::camera::types::CameraFrame;
+use crate::terminal::sample_pixel_rgb;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{trace, warn};
+
+/// Time between analysed frames while face-priority exposure is on
+pub const SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long a face may go unseen before metering returns to the whole frame
+pub const LOST_GRACE: Duration = Duration::from_secs(2);
+
+/// Width of the skin map the detector works on
+pub(super) const GRID_WIDTH: u32 = 160;
+
+/// Smallest face blob, as a fraction of the skin map area
+const MIN_FACE_FRACTION: f32 = 0.015;
+
+/// Range of blob height over width taken for a face
+const ASPECT_RANGE: std::ops::RangeInclusive<f32> = 1.0..=1.8;
+
+/// Share of the bounding box a face blob must fill
+const MIN_FILL: f32 = 0.55;
+
+/// Share of the box trimmed off each side before measuring brightness
+const INNER_MARGIN: f32 = 0.2;
+
+/// Face detector
+pub struct FaceDetector {
+    /// Width of the skin map frames are sampled into
+    grid_width: u32,
+}
+
+impl Default for FaceDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FaceDetector {
+    /// Create a detector with the default skin map size
+    pub fn new() -> Self {
+        Self {
+            grid_width: GRID_WIDTH,
+        }
+    }
+
+    /// Look for the most prominent face in a camera frame
+    pub async fn detect(&self, frame: Arc<CameraFrame>) -> Option<FaceDetection> {
+        let grid_width = self.grid_width;
+
+        tokio::task::spawn_blocking(move || detect_sync(&frame, grid_width))
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "Face detection task panicked");
+                None
+            })
+    }
+}
+
+/// Synchronous detection (runs in blocking task)
+pub(super) fn detect_sync(frame: &CameraFrame, grid_width: u32) -> Option<FaceDetection> {
+    let start = std::time::Instant::now();
+    let mask = SkinMask::from_frame(frame, grid_width);
+    let (labels, blobs) = label_blobs(&mask);
+    let face = find_face(&mask, &labels, &blobs).map(|blob| {
+        let luma = face_luma(frame, &mask, &labels, blob);
+        FaceDetection {
+            bounds: FrameRegion::from_pixels(
+                blob.min_x as u32,
+                blob.min_y as u32,
+                (blob.max_x - blob.min_x + 1) as u32,
+                (blob.max_y - blob.min_y + 1) as u32,
+                mask.width as u32,
+                mask.height as u32,
+            ),
+            luma,
+        }
+    });
+    trace!(
+        found = face.is_some(),
+        luma = face.as_ref().map(|face| face.luma),
+        elapsed_ms = start.elapsed().as_millis(),
+        "Face detection complete"
+    );
+    face
+}
+
+/// Largest blob shaped like a face
+fn find_face<'a>(mask: &SkinMask, labels: &[u32], blobs: &'a [Blob]) -> Option<&'a Blob> {
+    let min_area = ((mask.width * mask.height) as f32 * MIN_FACE_FRACTION) as usize;
+
+    blobs
+        .iter()
+        .filter(|blob| blob.area >= min_area.max(1))
+        .filter(|blob| {
+            let width = (blob.max_x - blob.min_x + 1) as f32;
+            let height = (blob.max_y - blob.min_y + 1) as f32;
+            ASPECT_RANGE.contains(&(height / width))
+                && blob.area as f32 >= width * height * MIN_FILL
+        })
+        .filter(|blob| !shows_spread_fingers(labels, mask.width, blob))
+        .max_by_key(|blob| blob.area)
+}
+
+/// Mean luma (0-1) of the face's skin over the middle of its box
+fn face_luma(frame: &CameraFrame, mask: &SkinMask, labels: &[u32], blob: &Blob) -> f32 {
+    let inner = |min: usize, max: usize| {
+        let margin = ((max - min + 1) as f32 * INNER_MARGIN) as usize;
+        min + margin..=max - margin
+    };
+    let mut sum = 0.0;
+    let mut count = 0u32;
+    for gy in inner(blob.min_y, blob.max_y) {
+        for gx in inner(blob.min_x, blob.max_x) {
+            if labels[gy * mask.width + gx] != blob.label {
+                continue;
+            }
+            let sx = (gx as u64 * u64::from(frame.width) / mask.width as u64) as u32;
+            let sy = (gy as u64 * u64::from(frame.height) / mask.height as u64) as u32;
+            let (r, g, b) = sample_pixel_rgb(frame, sx, sy);
+            sum += 0.299 * f32::from(r) + 0.587 * f32::from(g) + 0.114 * f32::from(b);
+            count += 1;
+        }
+    }
+    if count == 0 {
+        return 0.0;
+    }
+    sum / (count as f32 * 255.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a mask from rows of `#` (skin) and `.` (background)
+    fn mask(rows: &[&str]) -> SkinMask {
+        SkinMask {
+            width: rows[0].len(),
+            height: rows.len(),
+            skin: rows
+                .iter()
+                .flat_map(|row| row.chars().map(|c| c == '#'))
+                .collect(),
+        }
+    }
+
+    fn find(mask: &SkinMask) -> Option<(usize, usize, usize, usize)> {
+        let (labels, blobs) = label_blobs(mask);
+        find_face(mask, &labels, &blobs).map(|b| (b.min_x, b.min_y, b.max_x, b.max_y))
+    }
+
+    #[test]
+    fn face_with_eyes_is_found() {
+        let face = mask(&[
+            "..............",
+            ".....####.....",
+            "....######....",
+            "...##.##.##...",
+            "...########...",
+            "...########...",
+            "...###..###...",
+            "....######....",
+            ".....####.....",
+            "......##......",
+            "..............",
+        ]);
+        assert_eq!(find(&face), Some((3, 1, 10, 9)));
+    }
+
+    #[test]
+    fn hands_and_arms_are_not_faces() {
+        let hand = mask(&[
+            "............",
+            ".#.#.#.#....",
+            ".#.#.#.#....",
+            ".#.#.#.#....",
+            ".#######....",
+            ".#######....",
+            ".#######....",
+            ".#######....",
+            "..#####.....",
+            "..#####.....",
+            "............",
+        ]);
+        assert_eq!(find(&hand), None);
+
+        let forearm = mask(&[
+            "............",
+            "............",
+            ".##########.",
+            ".##########.",
+            ".##########.",
+            "............",
+        ]);
+        assert_eq!(find(&forearm), None);
+    }
+}
@@ -0,0 +1,292 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Raised-palm gesture detection
+//!
+//! A deliberately small detector for the gesture shutter: the frame is
+//! sampled into a low-resolution skin map (YCbCr skin-tone box), skin
+//! pixels are grouped into connected blobs, and a blob counts as a raised
+//! open palm when it is upright and the rows across its top cross several
+//! separate fingers. A face is a single solid blob and never shows that
+//! pattern, so looking into the camera doesn't trigger the shutter.
+
+use crate::app::frame_processor::types::{FrameRegion, GestureDetection};
+use crate::backends::camera::types::CameraFrame;
+use crate::terminal::sample_pixel_rgb;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{trace, warn};
+
+/// How long the palm must stay up before the countdown starts
+pub const HOLD_DURATION: Duration = Duration::from_secs(1);
+
+/// Time between analysed frames while the gesture shutter is armed
+pub const SAMPLE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Width of the skin map the detector works on
+const GRID_WIDTH: u32 = 160;
+
+/// Smallest hand blob, as a fraction of the skin map area
+const MIN_BLOB_FRACTION: f32 = 0.01;
+
+/// Separate skin runs a row across spread fingers must show
+const MIN_FINGER_RUNS: usize = 4;
+
+/// Rows in the top third of a blob that must cross the spread fingers
+const MIN_FINGER_ROWS: usize = 2;
+
+/// Raised-palm detector
+pub struct GestureDetector {
+    /// Width of the skin map frames are sampled into
+    grid_width: u32,
+}
+
+impl Default for GestureDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GestureDetector {
+    /// Create a detector with the default skin map size
+    pub fn new() -> Self {
+        Self {
+            grid_width: GRID_WIDTH,
+        }
+    }
+
+    /// Look for a raised open palm in a camera frame
+    pub async fn detect(&self, frame: Arc<CameraFrame>) -> Option<GestureDetection> {
+        let grid_width = self.grid_width;
+
+        tokio::task::spawn_blocking(move || detect_sync(&frame, grid_width))
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "Gesture detection task panicked");
+                None
+            })
+    }
+}
+
+/// Synchronous detection (runs in blocking task)
+fn detect_sync(frame: &CameraFrame, grid_width: u32) -> Option<GestureDetection> {
+    let start = std::time::Instant::now();
+    let mask = SkinMask::from_frame(frame, grid_width);
+    let region = find_raised_palm(&mask);
+    trace!(
+        found = region.is_some(),
+        elapsed_ms = start.elapsed().as_millis(),
+        "Gesture detection complete"
+    );
+    region.map(|bounds| GestureDetection { bounds })
+}
+
+/// Low-resolution map of skin-coloured pixels (shared with the face
+/// detector)
+pub(super) struct SkinMask {
+    pub(super) width: usize,
+    pub(super) height: usize,
+    pub(super) skin: Vec<bool>,
+}
+
+impl SkinMask {
+    pub(super) fn from_frame(frame: &CameraFrame, grid_width: u32) -> Self {
+        let width = grid_width.min(frame.width).max(1);
+        let height = (u64::from(frame.height) * u64::from(width) / u64::from(frame.width.max(1)))
+            .max(1) as u32;
+
+        let mut skin = Vec::with_capacity((width * height) as usize);
+        for gy in 0..height {
+            let sy = gy * frame.height / height;
+            for gx in 0..width {
+                let sx = gx * frame.width / width;
+                let (r, g, b) = sample_pixel_rgb(frame, sx, sy);
+                skin.push(is_skin(r, g, b));
+            }
+        }
+        Self {
+            width: width as usize,
+            height: height as usize,
+            skin,
+        }
+    }
+}
+
+/// YCbCr skin-tone box (Chai & Ngan), which holds across skin tones
+/// because it ignores luma
+fn is_skin(r: u8, g: u8, b: u8) -> bool {
+    let (r, g, b) = (f32::from(r), f32::from(g), f32::from(b));
+    let y = 0.299 * r + 0.587 * g + 0.114 * b;
+    let cb = 128.0 - 0.168_736 * r - 0.331_264 * g + 0.5 * b;
+    let cr = 128.0 + 0.5 * r - 0.418_688 * g - 0.081_312 * b;
+    y > 40.0 && (77.0..=127.0).contains(&cb) && (133.0..=173.0).contains(&cr)
+}
+
+/// Bounding box and size of one connected skin blob
+pub(super) struct Blob {
+    pub(super) label: u32,
+    pub(super) area: usize,
+    pub(super) min_x: usize,
+    pub(super) max_x: usize,
+    pub(super) min_y: usize,
+    pub(super) max_y: usize,
+}
+
+/// Largest upright skin blob with spread fingers across its top, in
+/// normalized frame coordinates
+fn find_raised_palm(mask: &SkinMask) -> Option<FrameRegion> {
+    let (labels, blobs) = label_blobs(mask);
+    let min_area = ((mask.width * mask.height) as f32 * MIN_BLOB_FRACTION) as usize;
+
+    blobs
+        .iter()
+        .filter(|blob| blob.area >= min_area.max(1))
+        .filter(|blob| blob.max_y - blob.min_y >= blob.max_x - blob.min_x)
+        .filter(|blob| shows_spread_fingers(&labels, mask.width, blob))
+        .max_by_key(|blob| blob.area)
+        .map(|blob| {
+            FrameRegion::from_pixels(
+                blob.min_x as u32,
+                blob.min_y as u32,
+                (blob.max_x - blob.min_x + 1) as u32,
+                (blob.max_y - blob.min_y + 1) as u32,
+                mask.width as u32,
+                mask.height as u32,
+            )
+        })
+}
+
+/// Label 4-connected skin blobs. Label 0 is background.
+pub(super) fn label_blobs(mask: &SkinMask) -> (Vec<u32>, Vec<Blob>) {
+    let mut labels = vec![0u32; mask.skin.len()];
+    let mut blobs = Vec::new();
+    let mut stack = Vec::new();
+
+    for start in 0..mask.skin.len() {
+        if !mask.skin[start] || labels[start] != 0 {
+            continue;
+        }
+        let label = blobs.len() as u32 + 1;
+        let mut blob = Blob {
+            label,
+            area: 0,
+            min_x: usize::MAX,
+            max_x: 0,
+            min_y: usize::MAX,
+            max_y: 0,
+        };
+        labels[start] = label;
+        stack.push(start);
+        while let Some(index) = stack.pop() {
+            let (x, y) = (index % mask.width, index / mask.width);
+            blob.area += 1;
+            blob.min_x = blob.min_x.min(x);
+            blob.max_x = blob.max_x.max(x);
+            blob.min_y = blob.min_y.min(y);
+            blob.max_y = blob.max_y.max(y);
+
+            let neighbours = [
+                (x > 0, index.wrapping_sub(1)),
+                (x + 1 < mask.width, index + 1),
+                (y > 0, index.wrapping_sub(mask.width)),
+                (y + 1 < mask.height, index + mask.width),
+            ];
+            for (in_bounds, next) in neighbours {
+                if in_bounds && mask.skin[next] && labels[next] == 0 {
+                    labels[next] = label;
+                    stack.push(next);
+                }
+            }
+        }
+        blobs.push(blob);
+    }
+    (labels, blobs)
+}
+
+/// Whether rows across the top third of the blob cross enough fingers
+pub(super) fn shows_spread_fingers(labels: &[u32], width: usize, blob: &Blob) -> bool {
+    let top_rows = (blob.max_y - blob.min_y + 1).div_ceil(3);
+    let finger_rows = (blob.min_y..blob.min_y + top_rows)
+        .filter(|&y| {
+            let row = &labels[y * width + blob.min_x..=y * width + blob.max_x];
+            count_runs(row, blob.label) >= MIN_FINGER_RUNS
+        })
+        .count();
+    finger_rows >= MIN_FINGER_ROWS
+}
+
+/// Number of separate runs of `label` in a row
+fn count_runs(row: &[u32], label: u32) -> usize {
+    let mut runs = 0;
+    let mut inside = false;
+    for &value in row {
+        let is_label = value == label;
+        if is_label && !inside {
+            runs += 1;
+        }
+        inside = is_label;
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a mask from rows of `#` (skin) and `.` (background)
+    fn mask(rows: &[&str]) -> SkinMask {
+        SkinMask {
+            width: rows[0].len(),
+            height: rows.len(),
+            skin: rows
+                .iter()
+                .flat_map(|row| row.chars().map(|c| c == '#'))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn spread_hand_is_a_raised_palm() {
+        let hand = mask(&[
+            "............",
+            ".#.#.#.#....",
+            ".#.#.#.#....",
+            ".#.#.#.#..#.",
+            ".#######.##.",
+            ".##########.",
+            ".#########..",
+            ".#######....",
+            ".#######....",
+            "..#####.....",
+            "..#####.....",
+            "..#####.....",
+        ]);
+        let region = find_raised_palm(&hand).expect("palm not found");
+        assert!((region.x - 1.0 / 12.0).abs() < 1e-6);
+        assert!((region.y - 1.0 / 12.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn solid_face_is_not_a_palm() {
+        let face = mask(&[
+            "............",
+            "....####....",
+            "...######...",
+            "..##.##.##..",
+            "..########..",
+            "..########..",
+            "..###..###..",
+            "...######...",
+            "....####....",
+            "............",
+        ]);
+        assert!(find_raised_palm(&face).is_none());
+    }
+
+    #[test]
+    fn skin_tones_pass_the_colour_test() {
+        assert!(is_skin(224, 172, 140));
+        assert!(is_skin(141, 85, 36));
+        assert!(!is_skin(40, 90, 200));
+        assert!(!is_skin(120, 200, 80));
+    }
+}
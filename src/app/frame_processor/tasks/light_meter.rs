@@ -0,0 +1,275 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Live light metering for night mode
+//!
+//! Once a second in Photo mode a preview frame is measured on the GPU: the
+//! brightness histogram of a small copy and the noise of a full-resolution
+//! crop from the middle, with the same estimators HDR+ uses on its bursts.
+//! The reading sizes the next HDR+ burst (see
+//! [`calculate_noise_aware_params`](crate::pipelines::photo::burst_mode::burst::calculate_noise_aware_params)),
+//! and [`NightDetector`] decides from it whether the scene is dark enough
+//! to recommend or switch to HDR+.
+
+use super::qr_detector;
+use crate::backends::camera::types::CameraFrame;
+use crate::pipelines::photo::burst_mode::BurstModeGpuPipeline;
+use crate::pipelines::photo::burst_mode::burst::SceneBrightness;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{trace, warn};
+
+/// Time between measured frames
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Longest side of the copy the brightness histogram is taken of
+const HISTOGRAM_SIZE: u32 = 512;
+
+/// Side of the full-resolution centre crop the noise is measured on;
+/// scaling down would average the noise away
+const NOISE_CROP_SIZE: u32 = 512;
+
+/// Noise, in 8-bit levels, above which a scene counts as dark whatever its
+/// brightness (the camera already turned up the gain)
+const NOISY_ABOVE: f32 = 5.0;
+
+/// Noise below which a scene that isn't dim stops counting as dark
+const CLEAN_BELOW: f32 = 3.0;
+
+/// Readings in a row that must agree before the verdict changes
+const STREAK: u8 = 2;
+
+/// One measurement of the preview
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LightReading {
+    /// Median luma (0-1), or the mean when the GPU histogram is unavailable
+    pub luminance: f32,
+    /// Brightness class the luminance falls in
+    pub brightness: SceneBrightness,
+    /// Noise standard deviation in 8-bit levels, `None` if it couldn't be
+    /// measured
+    pub noise_sd: Option<f32>,
+}
+
+impl LightReading {
+    /// Whether the reading looks dark, given whether the scene was dark
+    /// before (leaving takes a clearly cleaner picture than entering)
+    pub fn is_dark(&self, was_dark: bool) -> bool {
+        let dim = matches!(
+            self.brightness,
+            SceneBrightness::Low | SceneBrightness::VeryDark
+        );
+        match self.noise_sd {
+            Some(noise_sd) if was_dark => dim || noise_sd >= CLEAN_BELOW,
+            Some(noise_sd) => dim || noise_sd > NOISY_ABOVE,
+            None => dim,
+        }
+    }
+}
+
+/// Debounces readings into whether the scene is dark
+#[derive(Debug, Default)]
+pub struct NightDetector {
+    dark: bool,
+    /// Readings in a row that disagreed with `dark`
+    streak: u8,
+}
+
+impl NightDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take in a reading, returning whether the scene is dark
+    pub fn observe(&mut self, reading: &LightReading) -> bool {
+        if reading.is_dark(self.dark) == self.dark {
+            self.streak = 0;
+        } else {
+            self.streak += 1;
+            if self.streak >= STREAK {
+                self.dark = !self.dark;
+                self.streak = 0;
+            }
+        }
+        self.dark
+    }
+
+    /// Whether the scene is dark
+    pub fn is_dark(&self) -> bool {
+        self.dark
+    }
+
+    /// Forget what was seen (mode or camera changed)
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Light meter
+pub struct LightMeter;
+
+impl LightMeter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Measure a camera frame, `None` if it couldn't be read (raw Bayer)
+    pub async fn measure(&self, frame: Arc<CameraFrame>) -> Option<LightReading> {
+        if frame.format.is_bayer() {
+            return None;
+        }
+
+        let images = tokio::task::spawn_blocking(move || prepare_images(&frame))
+            .await
+            .map_err(|e| warn!(error = %e, "Light meter task panicked"))
+            .ok()?;
+        let start = std::time::Instant::now();
+
+        let luminance = match crate::shaders::analyze_brightness_gpu(
+            &images.small.data,
+            images.small.width,
+            images.small.height,
+        )
+        .await
+        {
+            Some(metrics) => metrics.median_luminance,
+            None => images.mean_luma,
+        };
+
+        let noise_sd = match BurstModeGpuPipeline::shared().await {
+            Ok(estimator) => estimator
+                .estimate_noise_gpu(&images.crop.data, images.crop.width, images.crop.height)
+                .await
+                .map_err(|e| warn!(error = %e, "Live noise estimation failed"))
+                .ok(),
+            Err(e) => {
+                warn!(error = %e, "Noise estimation unavailable for the light meter");
+                None
+            }
+        };
+
+        let reading = LightReading {
+            luminance,
+            brightness: SceneBrightness::from_luminance(luminance),
+            noise_sd,
+        };
+        trace!(
+            ?reading,
+            elapsed_ms = start.elapsed().as_millis(),
+            "Light metering complete"
+        );
+        Some(reading)
+    }
+}
+
+impl Default for LightMeter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tightly packed RGBA image
+struct RgbaImage {
+    data: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+/// What the GPU measures, prepared from a frame (runs in blocking task)
+struct MeterImages {
+    /// Whole frame, scaled down, for the histogram
+    small: RgbaImage,
+    /// Middle of the frame at full resolution, for the noise
+    crop: RgbaImage,
+    /// Mean luma (0-1) of `small`, used without a GPU histogram
+    mean_luma: f32,
+}
+
+fn prepare_images(frame: &CameraFrame) -> MeterImages {
+    let (gray, width, height) = qr_detector::convert_to_gray(frame);
+    let scale = (width.max(height) as f32 / HISTOGRAM_SIZE as f32).max(1.0);
+    let (small_width, small_height) = (
+        ((width as f32 / scale) as u32).max(1),
+        ((height as f32 / scale) as u32).max(1),
+    );
+    let small = qr_detector::downscale_gray(&gray, width, height, small_width, small_height);
+    let mean_luma = small.iter().map(|&v| u64::from(v)).sum::<u64>() as f32
+        / (small.len().max(1) as f32 * 255.0);
+
+    let (crop, crop_width, crop_height) = center_crop_gray(&gray, width, height, NOISE_CROP_SIZE);
+    MeterImages {
+        small: gray_to_rgba(&small, small_width, small_height),
+        crop: gray_to_rgba(&crop, crop_width, crop_height),
+        mean_luma,
+    }
+}
+
+/// Middle `size`x`size` pixels of a grayscale image (all of it if smaller)
+fn center_crop_gray(gray: &[u8], width: u32, height: u32, size: u32) -> (Vec<u8>, u32, u32) {
+    let crop_width = width.min(size);
+    let crop_height = height.min(size);
+    let left = ((width - crop_width) / 2) as usize;
+    let top = ((height - crop_height) / 2) as usize;
+    let mut out = Vec::with_capacity(crop_width as usize * crop_height as usize);
+    for y in top..top + crop_height as usize {
+        let start = y * width as usize + left;
+        out.extend_from_slice(&gray[start..start + crop_width as usize]);
+    }
+    (out, crop_width, crop_height)
+}
+
+/// Grayscale as opaque gray RGBA, which the GPU estimators take
+fn gray_to_rgba(gray: &[u8], width: u32, height: u32) -> RgbaImage {
+    RgbaImage {
+        data: gray.iter().flat_map(|&v| [v, v, v, 255]).collect(),
+        width,
+        height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reading(luminance: f32, noise_sd: Option<f32>) -> LightReading {
+        LightReading {
+            luminance,
+            brightness: SceneBrightness::from_luminance(luminance),
+            noise_sd,
+        }
+    }
+
+    #[test]
+    fn dim_or_noisy_scenes_are_dark() {
+        assert!(!reading(0.4, Some(1.0)).is_dark(false));
+        assert!(reading(0.05, None).is_dark(false));
+        // Bright enough, but the gain is up
+        assert!(reading(0.2, Some(7.0)).is_dark(false));
+        // Between the noise thresholds it stays as it was
+        assert!(!reading(0.2, Some(4.0)).is_dark(false));
+        assert!(reading(0.2, Some(4.0)).is_dark(true));
+    }
+
+    #[test]
+    fn verdict_needs_a_streak() {
+        let mut detector = NightDetector::new();
+        assert!(!detector.observe(&reading(0.02, Some(8.0))));
+        assert!(detector.observe(&reading(0.02, Some(8.0))));
+        // One bright reading doesn't end the night
+        assert!(detector.observe(&reading(0.4, Some(1.0))));
+        assert!(detector.observe(&reading(0.02, Some(8.0))));
+        assert!(detector.observe(&reading(0.4, Some(1.0))));
+        assert!(!detector.observe(&reading(0.4, Some(1.0))));
+        detector.reset();
+        assert!(!detector.is_dark());
+    }
+
+    #[test]
+    fn noise_is_measured_on_the_middle() {
+        // 4x2 image holding the column index
+        let gray: Vec<u8> = (0..2).flat_map(|_| 0..4u8).collect();
+        let (crop, width, height) = center_crop_gray(&gray, 4, 2, 2);
+        assert_eq!((width, height), (2, 2));
+        assert_eq!(crop, [1, 2, 1, 2]);
+        assert_eq!(gray_to_rgba(&[7], 1, 1).data, [7, 7, 7, 255]);
+    }
+}
@@ -5,6 +5,22 @@
 //! This module contains the task abstraction and implementations for
 //! various frame analysis tasks.
 
+pub mod barcode_detector;
+pub mod board_detector;
+pub mod exposure_assist;
+pub mod face_detector;
+pub mod gesture_detector;
+pub mod light_meter;
+pub mod page_detector;
 pub mod qr_detector;
+pub mod scene_classifier;
+pub mod white_point;
 
+pub use barcode_detector::BarcodeDetector;
+pub use board_detector::{BoardDetector, BoardFeed};
+pub use face_detector::FaceDetector;
+pub use gesture_detector::GestureDetector;
+pub use light_meter::{LightMeter, NightDetector};
+pub use page_detector::{PageDetector, PageFeed};
 pub use qr_detector::QrDetector;
+pub use scene_classifier::{SceneClassifier, SceneFeed};
@@ -0,0 +1,286 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Page detection for document auto-capture
+//!
+//! Each sampled frame is reduced to a [`PageSignature`]: a coarse luma grid
+//! plus the outline of the bright region a sheet of paper makes against a
+//! darker desk. [`PageFeed`] watches the stream of signatures and reports
+//! when a page has been put down and held still, so a stack can be scanned
+//! by swapping sheets without touching the app. A page is only taken once:
+//! after a capture, the picture has to change (a hand, a new sheet) and
+//! settle again on something that differs from the last page.
+
+use crate::app::frame_processor::types::{FrameRegion, PageSignature};
+use crate::backends::camera::types::CameraFrame;
+use crate::terminal::sample_pixel_rgb;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{trace, warn};
+
+/// Time between analysed frames while document auto-capture is on
+pub const SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long the picture must hold still before a page is taken
+pub const SETTLE_DURATION: Duration = Duration::from_millis(750);
+
+/// Columns and rows of the luma grid
+const GRID: (usize, usize) = (32, 24);
+
+/// Mean luma change (0-1) between consecutive samples that counts as motion
+const MOTION_THRESHOLD: f32 = 0.015;
+
+/// Mean luma change (0-1) from the last page that counts as a new page
+const PAGE_CHANGE_THRESHOLD: f32 = 0.04;
+
+/// Movement of any outline edge, as a fraction of the frame, that counts
+/// as the page having moved or changed size
+const OUTLINE_TOLERANCE: f32 = 0.04;
+
+/// Smallest bright region, as a fraction of the frame, taken for a page
+const MIN_PAGE_FRACTION: f32 = 0.15;
+
+/// Page signature extraction
+pub struct PageDetector;
+
+impl Default for PageDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PageDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Reduce a camera frame to its page signature
+    pub async fn detect(&self, frame: Arc<CameraFrame>) -> Option<PageSignature> {
+        tokio::task::spawn_blocking(move || {
+            let start = std::time::Instant::now();
+            let signature = signature_from_frame(&frame);
+            trace!(
+                page = signature.outline.is_some(),
+                elapsed_ms = start.elapsed().as_millis(),
+                "Page detection complete"
+            );
+            signature
+        })
+        .await
+        .map_err(|e| warn!(error = %e, "Page detection task panicked"))
+        .ok()
+    }
+}
+
+pub(super) fn signature_from_frame(frame: &CameraFrame) -> PageSignature {
+    let (columns, rows) = GRID;
+    let mut luma = Vec::with_capacity(columns * rows);
+    for row in 0..rows {
+        // Sample the middle of each cell
+        let y = ((2 * row + 1) as u64 * u64::from(frame.height) / (2 * rows) as u64) as u32;
+        for column in 0..columns {
+            let x =
+                ((2 * column + 1) as u64 * u64::from(frame.width) / (2 * columns) as u64) as u32;
+            let (r, g, b) = sample_pixel_rgb(frame, x, y);
+            luma.push((0.299 * f32::from(r) + 0.587 * f32::from(g) + 0.114 * f32::from(b)) / 255.0);
+        }
+    }
+    let outline = page_outline(&luma, columns, rows);
+    PageSignature { luma, outline }
+}
+
+/// Bounding box of the bright cells, if they cover enough of the frame to
+/// be a sheet of paper
+///
+/// Cells are split at the midpoint between the darkest and brightest tenth,
+/// which adapts to the room's lighting without a fixed threshold.
+fn page_outline(luma: &[f32], columns: usize, rows: usize) -> Option<FrameRegion> {
+    let mut sorted = luma.to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let dark = sorted.get(sorted.len() / 10).copied()?;
+    let bright = sorted.get(sorted.len() * 9 / 10).copied()?;
+    // A uniform picture (a blank desk, the lens cap) has no page in it
+    if bright - dark < 0.1 {
+        return None;
+    }
+    let threshold = (dark + bright) / 2.0;
+
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (columns, rows, 0, 0);
+    let mut count = 0;
+    for (i, _) in luma.iter().enumerate().filter(|(_, l)| **l > threshold) {
+        let (x, y) = (i % columns, i / columns);
+        min_x = min_x.min(x);
+        max_x = max_x.max(x);
+        min_y = min_y.min(y);
+        max_y = max_y.max(y);
+        count += 1;
+    }
+    if (count as f32) < (columns * rows) as f32 * MIN_PAGE_FRACTION {
+        return None;
+    }
+    Some(FrameRegion::from_pixels(
+        min_x as u32,
+        min_y as u32,
+        (max_x - min_x + 1) as u32,
+        (max_y - min_y + 1) as u32,
+        columns as u32,
+        rows as u32,
+    ))
+}
+
+/// Mean absolute luma difference of two signatures
+fn luma_difference(a: &PageSignature, b: &PageSignature) -> f32 {
+    if a.luma.len() != b.luma.len() || a.luma.is_empty() {
+        return f32::MAX;
+    }
+    a.luma
+        .iter()
+        .zip(&b.luma)
+        .map(|(a, b)| (a - b).abs())
+        .sum::<f32>()
+        / a.luma.len() as f32
+}
+
+/// Whether the page outline appeared, disappeared or moved
+fn outline_changed(a: &PageSignature, b: &PageSignature) -> bool {
+    match (&a.outline, &b.outline) {
+        (Some(a), Some(b)) => {
+            (a.x - b.x).abs() > OUTLINE_TOLERANCE
+                || (a.y - b.y).abs() > OUTLINE_TOLERANCE
+                || (a.x + a.width - b.x - b.width).abs() > OUTLINE_TOLERANCE
+                || (a.y + a.height - b.y - b.height).abs() > OUTLINE_TOLERANCE
+        }
+        (None, None) => false,
+        _ => true,
+    }
+}
+
+/// Decides from successive signatures when a new page is ready
+#[derive(Debug, Default)]
+pub struct PageFeed {
+    /// Signature of the previous sample
+    previous: Option<PageSignature>,
+    /// When the picture stopped moving
+    still_since: Option<Instant>,
+    /// A page was taken during the current still period
+    taken_while_still: bool,
+    /// Signature of the last page taken
+    last_page: Option<PageSignature>,
+}
+
+impl PageFeed {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the signature sampled at `now`; returns true when the page in
+    /// view should be captured
+    pub fn observe(&mut self, signature: PageSignature, now: Instant) -> bool {
+        let moving = self.previous.as_ref().is_none_or(|previous| {
+            luma_difference(previous, &signature) > MOTION_THRESHOLD
+                || outline_changed(previous, &signature)
+        });
+        if moving {
+            self.still_since = None;
+            self.taken_while_still = false;
+        }
+        let still_since = *self.still_since.get_or_insert(now);
+
+        let ready = !self.taken_while_still
+            && signature.outline.is_some()
+            && now.duration_since(still_since) >= SETTLE_DURATION
+            && self.last_page.as_ref().is_none_or(|last| {
+                luma_difference(last, &signature) > PAGE_CHANGE_THRESHOLD
+                    || outline_changed(last, &signature)
+            });
+        if ready {
+            self.taken_while_still = true;
+            self.last_page = Some(signature.clone());
+        }
+        self.previous = Some(signature);
+        ready
+    }
+
+    /// A page was captured by hand; don't take the same page again
+    pub fn page_captured(&mut self) {
+        self.taken_while_still = true;
+        self.last_page = self.previous.clone();
+    }
+
+    /// Forget the pages taken so far (new document)
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A bright page over `columns` of the grid on a dark desk, with some
+    /// `ink` darkening part of it
+    fn page(columns: std::ops::Range<usize>, ink: f32) -> PageSignature {
+        let (width, height) = GRID;
+        let luma: Vec<f32> = (0..width * height)
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                if columns.contains(&x) && (4..20).contains(&y) {
+                    if y < 12 { 0.9 - ink } else { 0.9 }
+                } else {
+                    0.2
+                }
+            })
+            .collect();
+        let outline = page_outline(&luma, width, height);
+        PageSignature { luma, outline }
+    }
+
+    fn desk() -> PageSignature {
+        let (width, height) = GRID;
+        let luma = vec![0.2; width * height];
+        let outline = page_outline(&luma, width, height);
+        PageSignature { luma, outline }
+    }
+
+    #[test]
+    fn outline_covers_the_bright_region() {
+        let outline = page(8..24, 0.0).outline.expect("page found");
+        assert!((outline.x - 0.25).abs() < 0.01);
+        assert!((outline.width - 0.5).abs() < 0.01);
+        assert!(desk().outline.is_none());
+    }
+
+    #[test]
+    fn stack_of_pages_is_taken_once_each() {
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let mut feed = PageFeed::new();
+
+        // Empty desk: nothing to take
+        assert!(!feed.observe(desk(), at(0)));
+        assert!(!feed.observe(desk(), at(1000)));
+
+        // First sheet put down, taken once it has settled
+        assert!(!feed.observe(page(8..24, 0.3), at(1250)));
+        assert!(!feed.observe(page(8..24, 0.3), at(1500)));
+        assert!(feed.observe(page(8..24, 0.3), at(2000)));
+        assert!(!feed.observe(page(8..24, 0.3), at(3000)));
+
+        // A hand passes over the same sheet: it isn't taken again
+        assert!(!feed.observe(page(4..28, 0.0), at(3250)));
+        assert!(!feed.observe(page(8..24, 0.3), at(3500)));
+        assert!(!feed.observe(page(8..24, 0.3), at(4500)));
+
+        // Next sheet with different text on top
+        assert!(!feed.observe(page(8..24, 0.0), at(4750)));
+        assert!(feed.observe(page(8..24, 0.0), at(5500)));
+    }
+
+    #[test]
+    fn manual_capture_counts_as_taken() {
+        let start = Instant::now();
+        let mut feed = PageFeed::new();
+        feed.observe(page(8..24, 0.3), start);
+        feed.page_captured();
+        assert!(!feed.observe(page(8..24, 0.3), start + Duration::from_secs(2)));
+    }
+}
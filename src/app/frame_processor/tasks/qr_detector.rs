@@ -5,12 +5,21 @@
 //! This module implements QR code detection using the rqrr crate.
 //! It converts camera frames to grayscale and searches for QR codes,
 //! returning their positions and decoded content.
+//!
+//! With OCR fallback on, a code that is found but can't be decoded (damaged,
+//! glared, half covered) has the text around it read instead: codes are
+//! often printed next to what they encode, such as a URL or a Wi-Fi
+//! password, and that text is offered like a decoded code would be.
 
 use crate::app::frame_processor::types::{FrameRegion, QrDetection};
 use crate::backends::camera::types::{CameraFrame, PixelFormat};
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{debug, trace, warn};
 
+/// Time between scanned frames
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
 /// QR code detector
 ///
 /// Analyzes camera frames to detect and decode QR codes.
@@ -18,6 +27,8 @@ use tracing::{debug, trace, warn};
 pub struct QrDetector {
     /// Maximum dimension for processing (frames are downscaled to this)
     max_dimension: u32,
+    /// Tesseract language for reading the text around undecodable codes
+    ocr_language: Option<String>,
 }
 
 impl Default for QrDetector {
@@ -33,12 +44,22 @@ impl QrDetector {
             // Process at 1024px max for better performance while maintaining detection accuracy
             // Higher resolution improves detection of smaller or distant QR codes
             max_dimension: 1024,
+            ocr_language: None,
         }
     }
 
     /// Create a QR detector with custom max dimension
     pub fn with_max_dimension(max_dimension: u32) -> Self {
-        Self { max_dimension }
+        Self {
+            max_dimension,
+            ocr_language: None,
+        }
+    }
+
+    /// Read the text around codes that fail to decode, in `language`
+    pub fn with_ocr_fallback(mut self, language: String) -> Self {
+        self.ocr_language = Some(language);
+        self
     }
 
     /// Detect QR codes in a camera frame
@@ -48,9 +69,10 @@ impl QrDetector {
     /// faster processing.
     pub async fn detect(&self, frame: Arc<CameraFrame>) -> Vec<QrDetection> {
         let max_dim = self.max_dimension;
+        let ocr_language = self.ocr_language.clone();
 
         // Run detection in a blocking task to avoid blocking the async runtime
-        tokio::task::spawn_blocking(move || detect_sync(&frame, max_dim))
+        tokio::task::spawn_blocking(move || detect_sync(&frame, max_dim, ocr_language.as_deref()))
             .await
             .unwrap_or_else(|e| {
                 warn!(error = %e, "QR detection task panicked");
@@ -60,7 +82,11 @@ impl QrDetector {
 }
 
 /// Synchronous QR detection (runs in blocking task)
-fn detect_sync(frame: &CameraFrame, max_dimension: u32) -> Vec<QrDetection> {
+fn detect_sync(
+    frame: &CameraFrame,
+    max_dimension: u32,
+    ocr_language: Option<&str>,
+) -> Vec<QrDetection> {
     let start = std::time::Instant::now();
 
     // Convert frame to grayscale (handles all pixel formats)
@@ -116,17 +142,9 @@ fn detect_sync(frame: &CameraFrame, max_dimension: u32) -> Vec<QrDetection> {
 
     // Decode and convert to our format
     let mut detections = Vec::with_capacity(grids.len());
+    let mut undecoded = Vec::new();
 
     for grid in grids {
-        // Decode the QR content
-        let content = match grid.decode() {
-            Ok((_, content)) => content,
-            Err(e) => {
-                debug!(error = %e, "Failed to decode QR code");
-                continue;
-            }
-        };
-
         // Get bounding box from grid bounds
         let bounds = grid.bounds;
 
@@ -136,6 +154,16 @@ fn detect_sync(frame: &CameraFrame, max_dimension: u32) -> Vec<QrDetection> {
         let min_y = bounds.iter().map(|p| p.y).min().unwrap_or(0) as f32;
         let max_y = bounds.iter().map(|p| p.y).max().unwrap_or(0) as f32;
 
+        // Decode the QR content
+        let content = match grid.decode() {
+            Ok((_, content)) => content,
+            Err(e) => {
+                debug!(error = %e, "Failed to decode QR code");
+                undecoded.push((min_x, min_y, max_x - min_x, max_y - min_y));
+                continue;
+            }
+        };
+
         // Scale back to original frame coordinates
         let x = min_x * scale;
         let y = min_y * scale;
@@ -164,6 +192,22 @@ fn detect_sync(frame: &CameraFrame, max_dimension: u32) -> Vec<QrDetection> {
         detections.push(QrDetection::new(region, content));
     }
 
+    // Only when nothing decoded: one readable code is all the overlay needs
+    if detections.is_empty()
+        && let Some(language) = ocr_language
+        && let Some(&code) = undecoded.first()
+        && let Some(detection) = read_text_around(
+            &gray_data,
+            (proc_width, proc_height),
+            code,
+            scale,
+            (width, height),
+            language,
+        )
+    {
+        detections.push(detection);
+    }
+
     let total_time = start.elapsed();
     if !detections.is_empty() {
         debug!(
@@ -176,18 +220,85 @@ fn detect_sync(frame: &CameraFrame, max_dimension: u32) -> Vec<QrDetection> {
     detections
 }
 
+/// Fewest letters and digits OCR must find for the text to be offered
+const MIN_TEXT_CHARS: usize = 4;
+
+/// Region read around an undecodable code at `code` (x, y, width, height):
+/// the code's own size again on every side, where a caption would be
+fn text_region(code: (f32, f32, f32, f32), size: (u32, u32)) -> (u32, u32, u32, u32) {
+    let (x, y, w, h) = code;
+    let left = (x - w).max(0.0) as u32;
+    let top = (y - h).max(0.0) as u32;
+    let right = ((x + 2.0 * w) as u32).min(size.0);
+    let bottom = ((y + 2.0 * h) as u32).min(size.1);
+    (
+        left,
+        top,
+        right.saturating_sub(left),
+        bottom.saturating_sub(top),
+    )
+}
+
+/// Read the text around an undecodable code in the processed `gray` image
+/// and offer it as a detection covering the code
+fn read_text_around(
+    gray: &[u8],
+    gray_size: (u32, u32),
+    code: (f32, f32, f32, f32),
+    scale: f32,
+    frame_size: (u32, u32),
+    language: &str,
+) -> Option<QrDetection> {
+    let (left, top, width, height) = text_region(code, gray_size);
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let crop = image::GrayImage::from_fn(width, height, |x, y| {
+        image::Luma([gray[((top + y) * gray_size.0 + left + x) as usize]])
+    });
+    let mut png = Vec::new();
+    crop.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .ok()?;
+
+    let page = match crate::media::ocr::recognize(&png, language) {
+        Ok(page) => page,
+        Err(e) => {
+            debug!(error = %e, "OCR fallback failed");
+            return None;
+        }
+    };
+    let text = page.text();
+    if text.chars().filter(|c| c.is_alphanumeric()).count() < MIN_TEXT_CHARS {
+        return None;
+    }
+    debug!(text = %text, "Read text next to an undecodable QR code");
+
+    let (x, y, w, h) = code;
+    let region = FrameRegion::from_pixels(
+        (x * scale) as u32,
+        (y * scale) as u32,
+        (w * scale) as u32,
+        (h * scale) as u32,
+        frame_size.0,
+        frame_size.1,
+    );
+    let mut detection = QrDetection::new(region, text);
+    detection.confidence = Some(page.confidence() / 100.0);
+    Some(detection)
+}
+
 /// Convert frame to grayscale, handling all pixel formats
 ///
 /// For YUV formats (NV12, I420, YUYV, etc.), the Y plane IS the luminance,
 /// so we can extract it directly - this is more efficient than RGB conversion.
-fn convert_to_gray(frame: &CameraFrame) -> (Vec<u8>, u32, u32) {
+pub(super) fn convert_to_gray(frame: &CameraFrame) -> (Vec<u8>, u32, u32) {
     let width = frame.width as usize;
     let height = frame.height as usize;
     let stride = frame.stride as usize;
 
     match frame.format {
-        // RGBA: Convert RGB to grayscale
-        PixelFormat::RGBA => {
+        // RGBA/RGBx: Convert RGB to grayscale
+        PixelFormat::RGBA | PixelFormat::RGBx => {
             let mut gray = Vec::with_capacity(width * height);
             for y in 0..height {
                 let row_start = y * stride;
@@ -296,6 +407,40 @@ fn convert_to_gray(frame: &CameraFrame) -> (Vec<u8>, u32, u32) {
             (gray, frame.width, frame.height)
         }
 
+        // P010: 16-bit little-endian Y plane, keep the high byte of each sample
+        PixelFormat::P010 => {
+            let y_start = frame
+                .yuv_planes
+                .as_ref()
+                .map_or(0, |planes| planes.y_offset);
+            let mut gray = Vec::with_capacity(width * height);
+            for y in 0..height {
+                let row_start = y_start + y * stride;
+                for x in 0..width {
+                    let offset = row_start + x * 2 + 1;
+                    if offset < frame.data.len() {
+                        gray.push(frame.data[offset]);
+                    }
+                }
+            }
+            (gray, frame.width, frame.height)
+        }
+
+        // Y210: Y0 U Y1 V with 16-bit little-endian samples (Y high bytes at 1, 5)
+        PixelFormat::Y210 => {
+            let mut gray = Vec::with_capacity(width * height);
+            for y in 0..height {
+                let row_start = y * stride;
+                for x in 0..width {
+                    let offset = row_start + x * 4 + 1;
+                    if offset < frame.data.len() {
+                        gray.push(frame.data[offset]);
+                    }
+                }
+            }
+            (gray, frame.width, frame.height)
+        }
+
         // YUYV/UYVY/YVYU/VYUY: Extract Y values from packed format
         // YUYV: Y0 U Y1 V (Y at positions 0, 2)
         // UYVY: U Y0 V Y1 (Y at positions 1, 3)
@@ -352,8 +497,8 @@ fn convert_to_gray(frame: &CameraFrame) -> (Vec<u8>, u32, u32) {
             (gray, frame.width, frame.height)
         }
 
-        // BGRA: Memory layout [B][G][R][A], so R=offset+2, G=offset+1, B=offset+0
-        PixelFormat::BGRA => {
+        // BGRA/BGRx: Memory layout [B][G][R][A], so R=offset+2, G=offset+1, B=offset+0
+        PixelFormat::BGRA | PixelFormat::BGRx => {
             let mut gray = Vec::with_capacity(width * height);
             for y in 0..height {
                 let row_start = y * stride;
@@ -393,7 +538,7 @@ fn convert_to_gray(frame: &CameraFrame) -> (Vec<u8>, u32, u32) {
 }
 
 /// Downscale grayscale image using bilinear interpolation
-fn downscale_gray(
+pub(super) fn downscale_gray(
     data: &[u8],
     src_width: u32,
     src_height: u32,
@@ -479,6 +624,44 @@ mod tests {
         assert!(gray[3] > 250); // White -> ~255
     }
 
+    #[test]
+    fn ten_bit_formats_keep_the_high_byte_of_luma() {
+        let frame = |format, data: Vec<u8>, stride| CameraFrame {
+            width: 2,
+            height: 1,
+            data: FrameData::Copied(Arc::from(data.as_slice())),
+            format,
+            stride,
+            yuv_planes: None,
+            captured_at: std::time::Instant::now(),
+            sensor_timestamp_ns: None,
+            libcamera_metadata: None,
+        };
+
+        // P010: Y0 = 0x4000, Y1 = 0xC000
+        let (gray, _, _) = convert_to_gray(&frame(PixelFormat::P010, vec![0, 0x40, 0, 0xC0], 4));
+        assert_eq!(gray, vec![0x40, 0xC0]);
+
+        // Y210: Y0 = 0x4000, U = 0x8000, Y1 = 0xC000, V = 0x8000
+        let data = vec![0, 0x40, 0, 0x80, 0, 0xC0, 0, 0x80];
+        let (gray, _, _) = convert_to_gray(&frame(PixelFormat::Y210, data, 8));
+        assert_eq!(gray, vec![0x40, 0xC0]);
+    }
+
+    #[test]
+    fn text_region_surrounds_the_code_within_the_frame() {
+        // The code's size again on every side
+        assert_eq!(
+            text_region((300.0, 200.0, 100.0, 100.0), (1024, 768)),
+            (200, 100, 300, 300)
+        );
+        // Clipped at the frame edges
+        assert_eq!(
+            text_region((20.0, 700.0, 50.0, 50.0), (1024, 768)),
+            (0, 650, 120, 118)
+        );
+    }
+
     #[test]
     fn test_downscale() {
         // 4x2 uniform gradient
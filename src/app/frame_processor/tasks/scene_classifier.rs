@@ -0,0 +1,310 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Scene classification for capture mode suggestions
+//!
+//! Every couple of seconds in Photo mode a frame is reduced to a handful of
+//! cues, each from a detector the app already has: overall brightness and
+//! fine detail from a small grayscale copy, QR finder patterns from the QR
+//! detector, a sheet of paper from the page detector's luma grid, and a
+//! face from the face detector. [`pick_scene`] turns the cues into at most
+//! one [`Scene`], most specific first. [`SceneFeed`] only reports a scene
+//! once it has been seen in a row, so a passing glance at a bright window
+//! doesn't pop up a suggestion.
+
+use super::{face_detector, page_detector, qr_detector};
+use crate::app::frame_processor::types::{PageSignature, Scene};
+use crate::backends::camera::types::CameraFrame;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{trace, warn};
+
+/// Time between classified frames while suggestions are on
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Samples in a row a scene must be seen in before it is suggested, and
+/// missed in before the suggestion goes away
+const STREAK: u8 = 2;
+
+/// Longest side of the grayscale copy brightness and detail are measured on
+const GRAY_SIZE: u32 = 512;
+
+/// Mean luma (0-1) below which the scene counts as low light
+const LOW_LIGHT_LUMA: f32 = 0.15;
+
+/// Mean absolute Laplacian (0-1) below which the picture is out of focus
+const BLURRY_DETAIL: f32 = 0.008;
+
+/// Share of its bounding box the page's bright cells must fill
+const MIN_PAGE_FILL: f32 = 0.85;
+
+/// Largest share of the frame a page may cover; beyond this it is more
+/// likely a bright wall or window than a sheet on a desk
+const MAX_PAGE_FRACTION: f32 = 0.9;
+
+/// Smallest face, as a fraction of the frame, that makes a portrait
+const MIN_PORTRAIT_FRACTION: f32 = 0.06;
+
+/// What a frame was reduced to for classification
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SceneCues {
+    /// Mean luma (0-1)
+    pub luma: f32,
+    /// Mean absolute Laplacian of the luma (0-1)
+    pub detail: f32,
+    /// QR codes found (located, not necessarily decodable)
+    pub qr_codes: usize,
+    /// A sheet of paper fills a rectangle of the frame
+    pub page: bool,
+    /// Area of the face, as a fraction of the frame
+    pub face_fraction: Option<f32>,
+}
+
+/// The scene a frame's cues describe, if any stands out
+///
+/// Darkness wins, since every other cue is unreliable in the dark; a
+/// blurry picture is only called a close-up when nothing else explains it.
+pub fn pick_scene(cues: &SceneCues) -> Option<Scene> {
+    if cues.luma < LOW_LIGHT_LUMA {
+        return Some(Scene::LowLight);
+    }
+    if cues.qr_codes > 0 {
+        return Some(Scene::QrCodes);
+    }
+    if cues.page {
+        return Some(Scene::Document);
+    }
+    if cues
+        .face_fraction
+        .is_some_and(|f| f >= MIN_PORTRAIT_FRACTION)
+    {
+        return Some(Scene::Portrait);
+    }
+    if cues.detail < BLURRY_DETAIL {
+        return Some(Scene::Macro);
+    }
+    None
+}
+
+/// Scene classifier
+pub struct SceneClassifier {
+    /// Look for QR codes (skipped while QR detection runs anyway)
+    look_for_qr: bool,
+}
+
+impl SceneClassifier {
+    pub fn new(look_for_qr: bool) -> Self {
+        Self { look_for_qr }
+    }
+
+    /// Classify a camera frame
+    pub async fn classify(&self, frame: Arc<CameraFrame>) -> Option<Scene> {
+        let look_for_qr = self.look_for_qr;
+
+        tokio::task::spawn_blocking(move || {
+            let start = std::time::Instant::now();
+            let cues = measure_cues(&frame, look_for_qr);
+            let scene = pick_scene(&cues);
+            trace!(
+                ?cues,
+                ?scene,
+                elapsed_ms = start.elapsed().as_millis(),
+                "Scene classification complete"
+            );
+            scene
+        })
+        .await
+        .unwrap_or_else(|e| {
+            warn!(error = %e, "Scene classification task panicked");
+            None
+        })
+    }
+}
+
+/// Reduce a frame to its scene cues (runs in blocking task)
+fn measure_cues(frame: &CameraFrame, look_for_qr: bool) -> SceneCues {
+    let (gray, width, height) = qr_detector::convert_to_gray(frame);
+    let scale = (width.max(height) as f32 / GRAY_SIZE as f32).max(1.0);
+    let (small_width, small_height) = (
+        ((width as f32 / scale) as u32).max(1),
+        ((height as f32 / scale) as u32).max(1),
+    );
+    let small = qr_detector::downscale_gray(&gray, width, height, small_width, small_height);
+
+    let luma = small.iter().map(|&v| u64::from(v)).sum::<u64>() as f32
+        / (small.len().max(1) as f32 * 255.0);
+    let detail = laplacian_energy(&small, small_width as usize, small_height as usize);
+
+    let qr_codes = if look_for_qr {
+        rqrr::PreparedImage::prepare_from_greyscale(
+            small_width as usize,
+            small_height as usize,
+            |x, y| small[y * small_width as usize + x],
+        )
+        .detect_grids()
+        .len()
+    } else {
+        0
+    };
+
+    let page = is_page(&page_detector::signature_from_frame(frame));
+
+    let face_fraction = face_detector::detect_sync(frame, face_detector::GRID_WIDTH)
+        .map(|face| face.bounds.width * face.bounds.height);
+
+    SceneCues {
+        luma,
+        detail,
+        qr_codes,
+        page,
+        face_fraction,
+    }
+}
+
+/// Mean absolute 4-neighbour Laplacian of a grayscale image, 0-1
+fn laplacian_energy(gray: &[u8], width: usize, height: usize) -> f32 {
+    if width < 3 || height < 3 {
+        return 0.0;
+    }
+    let mut sum = 0u64;
+    for y in 1..height - 1 {
+        for x in 1..width - 1 {
+            let at = |x: usize, y: usize| i32::from(gray[y * width + x]);
+            let laplacian =
+                4 * at(x, y) - at(x - 1, y) - at(x + 1, y) - at(x, y - 1) - at(x, y + 1);
+            sum += u64::from(laplacian.unsigned_abs());
+        }
+    }
+    // A Laplacian spans up to four times the pixel range
+    sum as f32 / (((width - 2) * (height - 2)) as f32 * 4.0 * 255.0)
+}
+
+/// Whether a page signature shows a sheet of paper rather than any bright
+/// patch: the bright cells fill their box and don't cover the whole frame
+fn is_page(signature: &PageSignature) -> bool {
+    let Some(outline) = &signature.outline else {
+        return false;
+    };
+    let area = outline.width * outline.height;
+    if area > MAX_PAGE_FRACTION {
+        return false;
+    }
+    let threshold = {
+        let mut sorted = signature.luma.clone();
+        sorted.sort_by(f32::total_cmp);
+        let dark = sorted[sorted.len() / 10];
+        let bright = sorted[sorted.len() * 9 / 10];
+        (dark + bright) / 2.0
+    };
+    let bright = signature.luma.iter().filter(|&&l| l > threshold).count();
+    bright as f32 >= signature.luma.len() as f32 * area * MIN_PAGE_FILL
+}
+
+/// Debounces classified scenes into the suggestion to show
+#[derive(Debug, Default)]
+pub struct SceneFeed {
+    /// Scene of the latest samples and how many in a row showed it
+    candidate: Option<(Option<Scene>, u8)>,
+    /// Scene currently suggested
+    shown: Option<Scene>,
+}
+
+impl SceneFeed {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take in the latest sample's scene, returning the scene to suggest
+    pub fn observe(&mut self, scene: Option<Scene>) -> Option<Scene> {
+        let streak = match self.candidate {
+            Some((candidate, streak)) if candidate == scene => streak.saturating_add(1),
+            _ => 1,
+        };
+        self.candidate = Some((scene, streak));
+        if streak >= STREAK {
+            self.shown = scene;
+        }
+        self.shown
+    }
+
+    /// Forget what was seen (mode or camera changed)
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cues() -> SceneCues {
+        SceneCues {
+            luma: 0.45,
+            detail: 0.03,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn most_specific_scene_wins() {
+        assert_eq!(pick_scene(&cues()), None);
+
+        let dark_page = SceneCues {
+            luma: 0.08,
+            page: true,
+            ..cues()
+        };
+        assert_eq!(pick_scene(&dark_page), Some(Scene::LowLight));
+
+        let page_with_code = SceneCues {
+            qr_codes: 1,
+            page: true,
+            ..cues()
+        };
+        assert_eq!(pick_scene(&page_with_code), Some(Scene::QrCodes));
+
+        let small_face = SceneCues {
+            face_fraction: Some(0.02),
+            ..cues()
+        };
+        assert_eq!(pick_scene(&small_face), None);
+        let portrait = SceneCues {
+            face_fraction: Some(0.12),
+            detail: 0.001,
+            ..cues()
+        };
+        assert_eq!(pick_scene(&portrait), Some(Scene::Portrait));
+
+        let blurry = SceneCues {
+            detail: 0.002,
+            ..cues()
+        };
+        assert_eq!(pick_scene(&blurry), Some(Scene::Macro));
+    }
+
+    #[test]
+    fn suggestions_need_a_streak() {
+        let mut feed = SceneFeed::new();
+        assert_eq!(feed.observe(Some(Scene::Document)), None);
+        assert_eq!(feed.observe(Some(Scene::Document)), Some(Scene::Document));
+        // One odd sample doesn't take it away
+        assert_eq!(feed.observe(None), Some(Scene::Document));
+        assert_eq!(feed.observe(Some(Scene::Document)), Some(Scene::Document));
+        assert_eq!(feed.observe(None), Some(Scene::Document));
+        assert_eq!(feed.observe(None), None);
+
+        feed.observe(Some(Scene::LowLight));
+        feed.reset();
+        assert_eq!(feed.observe(Some(Scene::LowLight)), None);
+    }
+
+    #[test]
+    fn flat_pictures_have_no_detail() {
+        let flat = vec![128u8; 16 * 16];
+        assert_eq!(laplacian_energy(&flat, 16, 16), 0.0);
+
+        let checker: Vec<u8> = (0..16 * 16)
+            .map(|i| if (i % 16 + i / 16) % 2 == 0 { 0 } else { 255 })
+            .collect();
+        assert!(laplacian_energy(&checker, 16, 16) > 0.9);
+    }
+}
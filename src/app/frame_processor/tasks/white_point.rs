@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Gray-point white balance for the eyedropper
+//!
+//! A small patch around the picked point is averaged. A neutral surface
+//! should come out with equal channels, so the imbalance shows how far the
+//! current white balance is off. Cameras with a temperature control get a
+//! corrected temperature, shifted along the mired scale where equal steps
+//! look like equal colour changes; the rest get per-channel gains for the
+//! GPU filter pass.
+
+use crate::app::exposure_picker::ControlRange;
+use crate::app::frame_processor::types::GrayPoint;
+use crate::backends::camera::types::CameraFrame;
+use crate::shaders::WhiteBalanceGains;
+use crate::terminal::sample_pixel_rgb;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Half-size of the sampled patch, as a fraction of the frame's shorter side
+const PATCH_FRACTION: f32 = 0.02;
+
+/// Patches darker than this (mean 0-255) are mostly sensor noise
+const MIN_LEVEL: f32 = 16.0;
+
+/// Patches with a channel brighter than this are clipped, so their
+/// channel ratio is unreliable
+const MAX_LEVEL: f32 = 250.0;
+
+/// Mired shift per unit of ln(blue / red)
+///
+/// Going from a 6500K to a 3200K illuminant moves a camera's gray by about
+/// 160 mired and its blue/red ratio by about ln(2.2) ≈ 0.8.
+const MIREDS_PER_LOG_RATIO: f32 = 200.0;
+
+/// Largest correction a single channel gain may make
+const MAX_GAIN: f32 = 4.0;
+
+/// Average the patch around a point given in normalized frame coordinates.
+/// Returns `None` when the patch is too dark or clipped to judge.
+pub async fn sample(frame: Arc<CameraFrame>, x: f32, y: f32) -> Option<GrayPoint> {
+    tokio::task::spawn_blocking(move || {
+        let gray = average_patch(&frame, x, y);
+        debug!(x, y, ?gray, "Gray point sampled");
+        gray.filter(is_usable)
+    })
+    .await
+    .unwrap_or_else(|e| {
+        warn!(error = %e, "Gray point sampling task panicked");
+        None
+    })
+}
+
+fn average_patch(frame: &CameraFrame, x: f32, y: f32) -> Option<GrayPoint> {
+    if frame.width == 0 || frame.height == 0 {
+        return None;
+    }
+    let radius = (frame.width.min(frame.height) as f32 * PATCH_FRACTION).max(1.0) as i64;
+    let cx = (x.clamp(0.0, 1.0) * (frame.width - 1) as f32) as i64;
+    let cy = (y.clamp(0.0, 1.0) * (frame.height - 1) as f32) as i64;
+
+    let (mut red, mut green, mut blue, mut count) = (0u64, 0u64, 0u64, 0u64);
+    for sy in (cy - radius).max(0)..=(cy + radius).min(i64::from(frame.height) - 1) {
+        for sx in (cx - radius).max(0)..=(cx + radius).min(i64::from(frame.width) - 1) {
+            let (r, g, b) = sample_pixel_rgb(frame, sx as u32, sy as u32);
+            red += u64::from(r);
+            green += u64::from(g);
+            blue += u64::from(b);
+            count += 1;
+        }
+    }
+
+    (count > 0).then(|| GrayPoint {
+        red: red as f32 / count as f32,
+        green: green as f32 / count as f32,
+        blue: blue as f32 / count as f32,
+    })
+}
+
+fn is_usable(gray: &GrayPoint) -> bool {
+    let mean = (gray.red + gray.green + gray.blue) / 3.0;
+    let brightest = gray.red.max(gray.green).max(gray.blue);
+    mean >= MIN_LEVEL && brightest <= MAX_LEVEL
+}
+
+/// Temperature that neutralizes `gray`, seen under the `current` setting
+///
+/// A bluish gray means the camera assumed warmer light than there is, so the
+/// temperature goes up; a reddish gray brings it down.
+pub fn corrected_temperature(current: i32, gray: GrayPoint, range: &ControlRange) -> i32 {
+    let log_ratio = (gray.blue.max(1.0) / gray.red.max(1.0)).ln();
+    let mired = 1_000_000.0 / current.max(1) as f32 - MIREDS_PER_LOG_RATIO * log_ratio;
+    let kelvin = (1_000_000.0 / mired.max(1.0)).round() as i32;
+
+    let kelvin = kelvin.clamp(range.min, range.max);
+    let step = range.step.max(1);
+    range.min + (kelvin - range.min) / step * step
+}
+
+/// Channel gains that neutralize `gray`, keeping green (most of the
+/// brightness) unchanged
+pub fn correction_gains(gray: GrayPoint) -> WhiteBalanceGains {
+    let gain = |channel: f32| (gray.green / channel.max(1.0)).clamp(1.0 / MAX_GAIN, MAX_GAIN);
+    WhiteBalanceGains {
+        red: gain(gray.red),
+        green: 1.0,
+        blue: gain(gray.blue),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gray(red: f32, green: f32, blue: f32) -> GrayPoint {
+        GrayPoint { red, green, blue }
+    }
+
+    fn temperature_range() -> ControlRange {
+        ControlRange::new(2800, 6500, 10, 4600)
+    }
+
+    #[test]
+    fn neutral_gray_keeps_the_temperature() {
+        let range = temperature_range();
+        assert_eq!(
+            corrected_temperature(4600, gray(120.0, 120.0, 120.0), &range),
+            4600
+        );
+        assert_eq!(
+            correction_gains(gray(120.0, 120.0, 120.0)),
+            WhiteBalanceGains::IDENTITY
+        );
+    }
+
+    #[test]
+    fn bluish_gray_raises_the_temperature() {
+        let range = temperature_range();
+        let warmer = corrected_temperature(4000, gray(100.0, 120.0, 150.0), &range);
+        assert!(warmer > 4000);
+        let cooler = corrected_temperature(4000, gray(150.0, 120.0, 100.0), &range);
+        assert!(cooler < 4000);
+        assert_eq!(cooler % 10, 0);
+    }
+
+    #[test]
+    fn gains_neutralize_a_warm_cast() {
+        let cast = gray(160.0, 120.0, 80.0);
+        let gains = correction_gains(cast);
+        assert!((cast.red * gains.red - cast.green).abs() < 0.01);
+        assert!((cast.blue * gains.blue - cast.green).abs() < 0.01);
+    }
+
+    #[test]
+    fn dark_or_clipped_patches_are_rejected() {
+        assert!(!is_usable(&gray(8.0, 10.0, 9.0)));
+        assert!(!is_usable(&gray(255.0, 240.0, 230.0)));
+        assert!(is_usable(&gray(110.0, 120.0, 130.0)));
+    }
+}
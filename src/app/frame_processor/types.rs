@@ -81,6 +81,49 @@ impl WifiSecurity {
             Self::Wpa3 => "WPA3",
         }
     }
+
+    /// The `T:` value of a WiFi QR code for this security type
+    pub fn qr_type(&self) -> &'static str {
+        match self {
+            Self::None => "nopass",
+            Self::Wep => "WEP",
+            Self::Wpa => "WPA",
+            Self::Wpa2Enterprise => "WPA2-EAP",
+            Self::Wpa3 => "SAE",
+        }
+    }
+}
+
+/// WiFi QR code content (`WIFI:T:WPA;S:<ssid>;P:<password>;;`) for a
+/// network, in the form phones and [`QrAction::parse`] read
+pub fn wifi_payload(
+    ssid: &str,
+    password: Option<&str>,
+    security: &WifiSecurity,
+    hidden: bool,
+) -> String {
+    fn escape(value: &str) -> String {
+        let mut escaped = String::with_capacity(value.len());
+        for c in value.chars() {
+            if matches!(c, '\\' | ';' | ',' | ':') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    }
+
+    let mut payload = format!("WIFI:T:{};S:{};", security.qr_type(), escape(ssid));
+    if *security != WifiSecurity::None
+        && let Some(password) = password
+    {
+        payload.push_str(&format!("P:{};", escape(password)));
+    }
+    if hidden {
+        payload.push_str("H:true;");
+    }
+    payload.push(';');
+    payload
 }
 
 /// Action type derived from QR code content
@@ -409,6 +452,91 @@ impl QrDetection {
     }
 }
 
+/// A barcode decoded in Scan mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarcodeDetection {
+    /// Symbology, as rxing names it (`EAN_13`, `CODE_128`, `QR_CODE`, ...)
+    pub format: String,
+    /// Decoded content
+    pub content: String,
+    /// Bounding box of the code in normalized frame coordinates
+    pub bounds: FrameRegion,
+}
+
+/// A raised open palm held up to the camera
+#[derive(Debug, Clone, PartialEq)]
+pub struct GestureDetection {
+    /// Bounding box of the hand in normalized frame coordinates
+    pub bounds: FrameRegion,
+}
+
+/// The most prominent face in a frame, for face-priority auto exposure
+#[derive(Debug, Clone, PartialEq)]
+pub struct FaceDetection {
+    /// Bounding box of the face in normalized frame coordinates
+    pub bounds: FrameRegion,
+    /// Mean luma (0-1) over the middle of the face, away from hair and
+    /// background at the edges of the box
+    pub luma: f32,
+}
+
+/// Kind of scene the scene classifier recognised, each with a capture
+/// setting that suits it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scene {
+    /// Too dark for a clean single exposure
+    LowLight,
+    /// A sheet of paper on a surface
+    Document,
+    /// One or more QR codes in view
+    QrCodes,
+    /// Something too close to be in focus
+    Macro,
+    /// A face filling a good part of the frame
+    Portrait,
+}
+
+/// Coarse description of the framed page, compared between samples by
+/// document auto-capture
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageSignature {
+    /// Mean luma (0-1) of each grid cell, row by row
+    pub luma: Vec<f32>,
+    /// Bounding box of the page in normalized frame coordinates, `None`
+    /// when no page stands out from the background
+    pub outline: Option<FrameRegion>,
+}
+
+/// Coarse description of the framed whiteboard, compared between samples
+/// by whiteboard auto-snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardSignature {
+    /// Mean luma (0-1) of each grid cell, row by row
+    pub luma: Vec<f32>,
+    /// Corners of the board in normalized frame coordinates, `None` when
+    /// no board stands out from the wall
+    pub quad: Option<crate::media::whiteboard::BoardQuad>,
+}
+
+/// Manual exposure values proposed from the live histogram
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExposureSuggestion {
+    /// Exposure time in 100µs units
+    pub exposure_time: i32,
+    /// Gain, `None` when the camera has no gain control
+    pub gain: Option<i32>,
+    /// Brightness change the values aim for, in stops
+    pub ev_change: f32,
+}
+
+/// Average colour (0-255 per channel) of a patch picked as neutral
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GrayPoint {
+    pub red: f32,
+    pub green: f32,
+    pub blue: f32,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -455,6 +583,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_wifi_payload_round_trip() {
+        let payload = wifi_payload("Cafe, upstairs", Some("p:ss"), &WifiSecurity::Wpa3, true);
+        assert_eq!(payload, "WIFI:T:SAE;S:Cafe\\, upstairs;P:p\\:ss;H:true;;");
+        assert_eq!(
+            QrAction::parse(&payload),
+            QrAction::Wifi {
+                ssid: "Cafe, upstairs".to_string(),
+                password: Some("p:ss".to_string()),
+                security: WifiSecurity::Wpa3,
+                hidden: true,
+            }
+        );
+        assert_eq!(
+            wifi_payload("Guest", Some("unused"), &WifiSecurity::None, false),
+            "WIFI:T:nopass;S:Guest;;"
+        );
+    }
+
     #[test]
     fn test_parse_phone() {
         let action = QrAction::parse("tel:+1234567890");
@@ -0,0 +1,272 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Debug frame tap (`camera --frame-tap`)
+//!
+//! Listens on a UNIX socket in the user's runtime directory and streams
+//! small copies of the preview to whatever connects, a few times a second,
+//! together with the pipeline statistics the Insights drawer shows. A user
+//! with a miscoloured or sheared preview can run the app with the flag and
+//! `scripts/frame-tap.py`, and send back frames that show what the pipeline
+//! produced, without a debug build.
+//!
+//! Each frame is one line of JSON followed by its pixels:
+//!
+//! ```text
+//! {"width":480,"height":270,"bytes":518400,"source":{...},"stats":{...}}\n
+//! <518400 bytes of RGBA>
+//! ```
+//!
+//! Frames go through the same GPU conversion as photos and are scaled down
+//! by picking pixels rather than averaging them, so stride and chroma
+//! artefacts survive the trip.
+
+use super::state::AppModel;
+use crate::backends::camera::types::CameraFrame;
+use std::io::Write;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Time between frames sent
+pub const SEND_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Longest side of the frames sent
+const MAX_SIDE: u32 = 480;
+
+/// How long a slow reader may hold up a frame before it is dropped
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Where the socket is created. Only the user's runtime directory is
+/// private to them; anywhere shared would let other users watch the camera.
+pub fn socket_path() -> Result<PathBuf, String> {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| PathBuf::from(dir).join("camera-frame-tap.sock"))
+        .ok_or_else(|| "XDG_RUNTIME_DIR is not set".to_string())
+}
+
+/// Listening socket and the inspectors connected to it
+#[derive(Clone)]
+pub struct FrameTap {
+    clients: Arc<Mutex<Vec<UnixStream>>>,
+    _socket: Arc<SocketFile>,
+}
+
+/// Removes the socket file once the last [`FrameTap`] is gone
+struct SocketFile(PathBuf);
+
+impl Drop for SocketFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Bind `path` with the socket file created owner-only, rather than
+/// restricting it after other users had a chance to connect
+fn bind_private(path: &Path) -> std::io::Result<UnixListener> {
+    // SAFETY: umask only swaps the process file mode mask. Files other
+    // threads create meanwhile come out owner-only, never more open.
+    let previous = unsafe { libc::umask(0o177) };
+    let listener = UnixListener::bind(path);
+    // SAFETY: as above, putting back the mask that was in place
+    unsafe { libc::umask(previous) };
+    listener
+}
+
+impl FrameTap {
+    /// Bind the socket and accept inspectors on a thread of its own
+    pub fn start() -> Result<Self, String> {
+        let path = socket_path()?;
+        // A socket left behind by an earlier run would make bind fail
+        if UnixStream::connect(&path).is_err() {
+            let _ = std::fs::remove_file(&path);
+        }
+        let listener =
+            bind_private(&path).map_err(|e| format!("Failed to bind {}: {}", path.display(), e))?;
+        let socket = Arc::new(SocketFile(path));
+        info!(path = %socket.0.display(), "Frame tap listening");
+
+        let clients: Arc<Mutex<Vec<UnixStream>>> = Arc::default();
+        let accepted = Arc::clone(&clients);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let _ = stream.set_write_timeout(Some(WRITE_TIMEOUT));
+                        info!("Frame tap inspector connected");
+                        accepted.lock().unwrap().push(stream);
+                    }
+                    Err(e) => warn!(error = %e, "Frame tap failed to accept"),
+                }
+            }
+        });
+        Ok(Self {
+            clients,
+            _socket: socket,
+        })
+    }
+
+    /// Whether an inspector is connected
+    pub fn has_clients(&self) -> bool {
+        !self.clients.lock().unwrap().is_empty()
+    }
+
+    /// Convert, shrink and send a frame to every inspector, dropping those
+    /// that went away
+    pub async fn send(&self, frame: Arc<CameraFrame>, stats: serde_json::Value) {
+        let rgba = match crate::pipelines::video::recorder::convert_frame_to_rgba(&frame).await {
+            Ok(rgba) => rgba,
+            Err(e) => {
+                debug!(error = %e, "Frame tap couldn't convert a frame");
+                return;
+            }
+        };
+        let (width, height) = tap_size(frame.width, frame.height);
+        let pixels = pick_pixels(&rgba, frame.width, frame.height, width, height);
+
+        let header = serde_json::json!({
+            "width": width,
+            "height": height,
+            "bytes": pixels.len(),
+            "source": source_info(&frame),
+            "stats": stats,
+        });
+        let mut message = header.to_string().into_bytes();
+        message.push(b'\n');
+        message.extend_from_slice(&pixels);
+
+        let clients = Arc::clone(&self.clients);
+        let _ = tokio::task::spawn_blocking(move || {
+            clients.lock().unwrap().retain_mut(|client| {
+                let sent = client.write_all(&message).is_ok();
+                if !sent {
+                    info!("Frame tap inspector disconnected");
+                }
+                sent
+            });
+        })
+        .await;
+    }
+}
+
+/// Size of a tapped frame, keeping the aspect ratio
+fn tap_size(width: u32, height: u32) -> (u32, u32) {
+    let longest = width.max(height).max(1);
+    if longest <= MAX_SIDE {
+        return (width, height);
+    }
+    (
+        (u64::from(width) * u64::from(MAX_SIDE) / u64::from(longest)).max(1) as u32,
+        (u64::from(height) * u64::from(MAX_SIDE) / u64::from(longest)).max(1) as u32,
+    )
+}
+
+/// Nearest-neighbour downscale of tightly packed RGBA
+fn pick_pixels(rgba: &[u8], width: u32, height: u32, out_width: u32, out_height: u32) -> Vec<u8> {
+    let mut out = Vec::with_capacity(out_width as usize * out_height as usize * 4);
+    for y in 0..out_height {
+        let sy = (u64::from(y) * u64::from(height) / u64::from(out_height)) as usize;
+        for x in 0..out_width {
+            let sx = (u64::from(x) * u64::from(width) / u64::from(out_width)) as usize;
+            let at = (sy * width as usize + sx) * 4;
+            out.extend_from_slice(&rgba[at..at + 4]);
+        }
+    }
+    out
+}
+
+/// What the frame looked like before conversion
+fn source_info(frame: &CameraFrame) -> serde_json::Value {
+    serde_json::json!({
+        "width": frame.width,
+        "height": frame.height,
+        "pixel_format": format!("{:?}", frame.format),
+        "gst_format": frame.gst_format_string(),
+        "stride": frame.stride,
+        "data_len": frame.data.len(),
+        "planes": frame.yuv_planes.as_ref().map(|planes| serde_json::json!({
+            "y_offset": planes.y_offset,
+            "y_size": planes.y_size,
+            "uv_offset": planes.uv_offset,
+            "uv_size": planes.uv_size,
+            "uv_stride": planes.uv_stride,
+            "v_offset": planes.v_offset,
+            "v_size": planes.v_size,
+            "v_stride": planes.v_stride,
+            "uv_width": planes.uv_width,
+            "uv_height": planes.uv_height,
+        })),
+        "sensor_timestamp_ns": frame.sensor_timestamp_ns,
+        "age_ms": frame.captured_at.elapsed().as_millis() as u64,
+    })
+}
+
+impl AppModel {
+    /// Settings sent along with each frame. They only change on user
+    /// action, so the tap subscription is rebuilt when they do.
+    pub(crate) fn frame_tap_settings(&self) -> serde_json::Value {
+        serde_json::json!({
+            "camera": self
+                .available_cameras
+                .get(self.current_camera_index)
+                .map(|camera| camera.name.clone()),
+            "format": self.active_format.as_ref().map(ToString::to_string),
+            "mode": format!("{:?}", self.mode),
+            "filter": format!("{:?}", self.selected_filter),
+            "zoom": self.zoom_level,
+        })
+    }
+}
+
+/// Pipeline statistics sent along with a frame, together with the app's
+/// [`AppModel::frame_tap_settings`]
+pub(crate) fn frame_tap_stats(
+    frame: &CameraFrame,
+    settings: &serde_json::Value,
+) -> serde_json::Value {
+    use crate::backends::camera::libcamera::native::diagnostics as diag;
+
+    let mut stats = serde_json::json!({
+        "pipeline": diag::get_pipeline_string(),
+        "preview_stream": diag::get_preview_stream_info().map(
+            |(resolution, pixel_format, role, frame_count)| serde_json::json!({
+                "resolution": resolution,
+                "pixel_format": pixel_format,
+                "role": role,
+                "frame_count": frame_count,
+            })
+        ),
+        "mjpeg_decoder": diag::get_mjpeg_decoder(),
+        "mjpeg_decode_us": diag::get_mjpeg_decode_time_us(),
+        "gpu_upload_us": super::video_primitive::get_gpu_upload_time_us(),
+        "metadata": frame.libcamera_metadata.as_ref().map(|m| format!("{:?}", m)),
+    });
+    if let (Some(stats), Some(settings)) = (stats.as_object_mut(), settings.as_object()) {
+        stats.extend(settings.clone());
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_shrink_to_fit() {
+        assert_eq!(tap_size(320, 240), (320, 240));
+        assert_eq!(tap_size(1920, 1080), (480, 270));
+        assert_eq!(tap_size(1080, 1920), (270, 480));
+
+        // 4x2 image holding the column index, halved
+        let rgba: Vec<u8> = (0..2)
+            .flat_map(|_| (0..4u8).flat_map(|x| [x, x, x, 255]))
+            .collect();
+        let picked: Vec<u8> = pick_pixels(&rgba, 4, 2, 2, 1)
+            .chunks(4)
+            .map(|p| p[0])
+            .collect();
+        assert_eq!(picked, [0, 2]);
+    }
+}
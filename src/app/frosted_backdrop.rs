@@ -124,14 +124,24 @@ fn make_primitive(
     primitive.crop_uv = config.crop_uv;
     primitive.zoom_level = config.zoom_level;
     primitive.letterbox_color = config.letterbox_color;
+    primitive.false_color = config.false_color;
+    primitive.color_vision = config.color_vision;
+    primitive.scaling = config.scaling;
+    primitive.desqueeze = config.desqueeze;
 
     if frame.width > 0 && frame.height > 0 {
         let stride = if frame.stride > 0 {
             frame.stride
         } else {
             match frame.format {
-                PixelFormat::RGBA | PixelFormat::ABGR | PixelFormat::BGRA => frame.width * 4,
+                PixelFormat::RGBA
+                | PixelFormat::ABGR
+                | PixelFormat::BGRA
+                | PixelFormat::RGBx
+                | PixelFormat::BGRx
+                | PixelFormat::Y210 => frame.width * 4,
                 PixelFormat::RGB24 => frame.width * 3,
+                PixelFormat::P010 => frame.width * 2,
                 PixelFormat::YUYV | PixelFormat::UYVY | PixelFormat::YVYU | PixelFormat::VYUY => {
                     frame.width * 2
                 }
@@ -514,10 +524,16 @@ mod tests {
             crop_uv: Some((0.125, 0.0, 0.875, 1.0)),
             zoom_level: 2.5,
             scroll_zoom_enabled: true,
+            gestures_enabled: true,
             cover_blend: Some(0.5),
             bar_top_px: 47.0,
             bar_bottom_px: 174.0,
             letterbox_color: [0.1, 0.2, 0.3, 1.0],
+            false_color: true,
+            color_vision: crate::config::ColorVisionAssist::SimulateTritanopia,
+            scaling: crate::config::PreviewScaling::Lanczos,
+            desqueeze: 1.5,
+            loupe_center: None,
         }
     }
 
@@ -546,6 +562,10 @@ mod tests {
         // the preview was a pencil drawing and the scrim bars blurred the raw
         // colour scene right up against it.
         assert_eq!(p.filter_type, cfg.filter_type);
+        assert_eq!(p.false_color, cfg.false_color);
+        assert_eq!(p.color_vision, cfg.color_vision);
+        assert_eq!(p.scaling, cfg.scaling);
+        assert_eq!(p.desqueeze, cfg.desqueeze);
         // And the blur really is parameterized from the theme, not left at the
         // transition blur's default.
         assert_eq!(p.blur_params, frost_blur_params());
@@ -588,7 +608,11 @@ mod tests {
             (PixelFormat::RGBA, 64 * 4),
             (PixelFormat::ABGR, 64 * 4),
             (PixelFormat::BGRA, 64 * 4),
+            (PixelFormat::RGBx, 64 * 4),
+            (PixelFormat::BGRx, 64 * 4),
+            (PixelFormat::Y210, 64 * 4),
             (PixelFormat::RGB24, 64 * 3),
+            (PixelFormat::P010, 64 * 2),
             (PixelFormat::YUYV, 64 * 2),
             (PixelFormat::UYVY, 64 * 2),
             (PixelFormat::YVYU, 64 * 2),
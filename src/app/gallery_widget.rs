@@ -19,21 +19,22 @@ pub struct GalleryWidget {
 }
 
 impl GalleryWidget {
-    /// Create a new gallery widget from image data
+    /// Create a new gallery widget from image data, `size` px square
     pub fn new(
         image_handle: cosmic::widget::image::Handle,
         rgba_data: Arc<Vec<u8>>,
         width: u32,
         height: u32,
         corner_radius: f32,
+        size: f32,
     ) -> Self {
         let primitive =
             GalleryPrimitive::new(image_handle, rgba_data, width, height, corner_radius);
 
         Self {
             primitive,
-            width: Length::Fixed(44.0),
-            height: Length::Fixed(44.0),
+            width: Length::Fixed(size),
+            height: Length::Fixed(size),
         }
     }
 }
@@ -93,6 +94,7 @@ pub fn gallery_widget<'a>(
     width: u32,
     height: u32,
     corner_radius: f32,
+    size: f32,
 ) -> Element<'a, crate::app::Message, Theme, Renderer> {
     Element::new(GalleryWidget::new(
         image_handle,
@@ -100,5 +102,6 @@ pub fn gallery_widget<'a>(
         width,
         height,
         corner_radius,
+        size,
     ))
 }
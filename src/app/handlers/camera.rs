@@ -6,10 +6,12 @@
 //! hotplug events, and mirror/virtual camera settings.
 
 use crate::app::state::{AppModel, CameraMode, Message, RecordingState, VirtualCameraState};
+use crate::backends::camera::frame_hub::ConsumerKind;
 use crate::backends::camera::v4l2_controls;
+use crate::fl;
 use cosmic::Task;
 use std::sync::Arc;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 impl AppModel {
     /// Trigger haptic feedback if enabled and available.
@@ -19,6 +21,13 @@ impl AppModel {
         }
     }
 
+    /// Play a capture sound if capture sounds are on and silent mode is off.
+    pub(crate) fn play_capture_sound(&self, sound: crate::sound::CaptureSound) {
+        if self.config.capture_sounds && !self.config.silent_mode {
+            crate::sound::play(sound, f64::from(self.config.capture_sound_volume) / 100.0);
+        }
+    }
+
     /// Promote `pending_persist_camera` to `last_camera_path` if `frame` is
     /// new enough to prove the current camera is streaming. Also clears
     /// `pending_camera_path` and `failed_camera_paths` (a successful frame
@@ -95,7 +104,7 @@ impl AppModel {
         self.camera_cancel_flag = Arc::new(std::sync::atomic::AtomicBool::new(false));
         self.current_frame = None;
         self.available_cameras.clear();
-        self.camera_dropdown_options.clear();
+        self.refresh_camera_dropdowns();
 
         Task::perform(
             async move {
@@ -116,7 +125,25 @@ impl AppModel {
     ///
     /// If the target camera was added via hotplug and has no libcamera path yet,
     /// a full re-enumeration is performed first to discover the correct path.
+    ///
+    /// A long-press exposure/focus lock, AE-L / AWB-L holds and any
+    /// face-priority metering are released first, while the old camera is
+    /// still the current one.
     fn do_camera_switch(&mut self, new_index: usize) -> Task<cosmic::Action<Message>> {
+        let unlock = self.release_exposure_focus_lock();
+        let release_holds = self.release_auto_holds();
+        let release_face = self.release_face_exposure();
+        self.osd_values = None;
+        Task::batch([
+            unlock,
+            release_holds,
+            release_face,
+            self.switch_to_camera(new_index),
+        ])
+    }
+
+    /// `do_camera_switch` after the lock is released
+    fn switch_to_camera(&mut self, new_index: usize) -> Task<cosmic::Action<Message>> {
         // If the target camera has no libcamera path (hotplug placeholder),
         // we need a full re-enumeration first.
         let needs_enumeration = self
@@ -144,11 +171,7 @@ impl AppModel {
         // blur_frame_mirror: whether the old camera's preview was mirrored
         // blur_frame_zoom: the zoom the last frame was displayed at — must be
         // read before the `zoom_level = 1.0` reset below.
-        self.blur_frame_rotation = self
-            .available_cameras
-            .get(self.current_camera_index)
-            .map(|c| c.rotation)
-            .unwrap_or_default();
+        self.blur_frame_rotation = self.current_camera_rotation();
         self.blur_frame_mirror = self.should_mirror_preview();
         self.blur_frame_zoom = self.current_zoom_level();
 
@@ -165,6 +188,10 @@ impl AppModel {
         // switch instead of blurring.
         self.current_camera_index = new_index;
         self.zoom_level = 1.0;
+        self.clear_software_white_balance();
+        if self.available_cameras.get(new_index).map(|c| &c.path) != self.failover_camera.as_ref() {
+            self.failover_camera = None;
+        }
         self.photo_aspect_ratio = self.config.photo_aspect_ratio;
 
         // If switching to a back camera with flash enabled and permission errors,
@@ -181,6 +208,7 @@ impl AppModel {
         }
 
         // Turn off hardware flash when switching cameras (safety measure)
+        self.flash.torch = false;
         self.turn_off_flash_hardware();
 
         self.switch_camera_or_mode(new_index, self.mode);
@@ -194,11 +222,51 @@ impl AppModel {
         self.query_exposure_controls_task()
     }
 
-    /// Build human-readable camera dropdown labels from a list of camera devices.
-    fn build_camera_dropdown_labels(
-        cameras: &[crate::backends::camera::types::CameraDevice],
-    ) -> Vec<String> {
-        cameras.iter().map(|cam| cam.name.clone()).collect()
+    /// Rebuild the camera, standby camera and second camera dropdown labels from `available_cameras`.
+    fn refresh_camera_dropdowns(&mut self) {
+        self.camera_dropdown_options = self
+            .available_cameras
+            .iter()
+            .map(|cam| cam.name.clone())
+            .collect();
+        self.standby_camera_dropdown_options = std::iter::once(fl!("settings-standby-camera-none"))
+            .chain(self.camera_dropdown_options.iter().cloned())
+            .collect();
+        self.pip_camera_dropdown_options = std::iter::once(fl!("settings-pip-camera-off"))
+            .chain(self.camera_dropdown_options.iter().cloned())
+            .collect();
+    }
+
+    /// Index of the configured standby camera, if it is connected and not
+    /// already the active camera.
+    fn standby_camera_index(&self) -> Option<usize> {
+        let path = self.config.standby_camera_path.as_deref()?;
+        self.available_cameras
+            .iter()
+            .position(|c| c.path == path)
+            .filter(|&index| index != self.current_camera_index)
+    }
+
+    /// Index of the configured second camera, if it is connected and not the
+    /// active camera.
+    pub(crate) fn pip_camera_index(&self) -> Option<usize> {
+        let path = self.config.pip_camera_path.as_deref()?;
+        self.available_cameras
+            .iter()
+            .position(|c| c.path == path)
+            .filter(|&index| index != self.current_camera_index)
+    }
+
+    /// Compositor for photos, recordings and the virtual camera while a
+    /// second camera is running.
+    pub(crate) fn pip_overlay(&self) -> Option<crate::backends::camera::pip::PipOverlay> {
+        self.pip_camera_index()?;
+        let manager = self.backend_manager.as_ref()?;
+        Some(crate::backends::camera::pip::PipOverlay::new(
+            manager.pip_frame(),
+            self.config.dual_camera_layout,
+            self.config.pip_corner,
+        ))
     }
 
     pub(crate) fn handle_switch_camera(&mut self) -> Task<cosmic::Action<Message>> {
@@ -228,6 +296,52 @@ impl AppModel {
         Task::none()
     }
 
+    pub(crate) fn handle_camera_frame_ready(&mut self) -> Task<cosmic::Action<Message>> {
+        // A scheduled check takes the newest frame anyway
+        if self.frame_pacer.poll_scheduled {
+            return Task::none();
+        }
+        self.present_camera_frame()
+    }
+
+    pub(crate) fn handle_present_camera_frame(&mut self) -> Task<cosmic::Action<Message>> {
+        self.frame_pacer.poll_scheduled = false;
+        self.present_camera_frame()
+    }
+
+    /// Take the newest frame from the preview mailbox once the renderer has
+    /// taken the previous one, checking back shortly until it has
+    fn present_camera_frame(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::app::frame_pacer::POLL_INTERVAL;
+
+        let now = std::time::Instant::now();
+        let uploaded = crate::app::video_primitive::last_preview_upload();
+        if !self.frame_pacer.ready(now, uploaded) {
+            self.frame_pacer.poll_scheduled = true;
+            return Self::delay_task(
+                POLL_INTERVAL.as_millis() as u64,
+                Message::PresentCameraFrame,
+            );
+        }
+        let Some(frame) = self.preview_mailbox.take() else {
+            return Task::none();
+        };
+        let key = frame.data.as_ptr() as usize;
+        let captured_at = frame.captured_at;
+        let task = self.handle_camera_frame(frame);
+        // Stale and skipped frames never reach the renderer
+        if self
+            .current_frame
+            .as_ref()
+            .is_some_and(|frame| frame.data.as_ptr() as usize == key)
+        {
+            self.frame_pacer.on_present(now, key, captured_at, uploaded);
+        } else {
+            self.frame_pacer.forget();
+        }
+        task
+    }
+
     pub(crate) fn handle_camera_frame(
         &mut self,
         frame: Arc<crate::backends::camera::types::CameraFrame>,
@@ -261,17 +375,19 @@ impl AppModel {
             return Task::none();
         }
 
-        // Send frame to virtual camera if streaming from camera (not file source)
-        if self.virtual_camera.is_streaming()
-            && !self.virtual_camera.is_file_source()
-            && !self.virtual_camera.send_frame(Arc::clone(&frame))
+        // Recording and virtual camera frames are delivered by the capture
+        // thread's frame hub (bypasses UI for lower latency / fewer drops).
+        // File source frames never pass the capture thread, so frame
+        // analysis gets them from here.
+        if self.virtual_camera.is_file_source()
+            && let Some(manager) = self.backend_manager.as_ref()
         {
-            debug!("Failed to send frame to virtual camera (channel closed)");
+            let frame_hub = manager.frame_hub();
+            for kind in ConsumerKind::FRAME_ANALYSIS {
+                frame_hub.publish_to(kind, &frame);
+            }
         }
 
-        // Recording frames are sent directly from the capture thread via
-        // set_recording_sender (bypasses UI for lower latency / fewer drops).
-
         // Drop stale frames from the old camera/mode that were already queued
         // in the iced message queue when the switch happened — but only AFTER
         // the transition blur has ended. During the blur period we want stale
@@ -284,6 +400,21 @@ impl AppModel {
             return Task::none();
         }
 
+        self.sync_recorder_warmup();
+        self.sync_color_space();
+
+        if let Some(probe) = self.latency_probe.as_mut()
+            && let Some(outcome) = match crate::app::latency_probe::mean_luma(&frame) {
+                Some(luma) => probe.on_frame(luma, std::time::Instant::now()),
+                // Nothing to see the flash in
+                None => Some(crate::app::latency_probe::ProbeOutcome::NoFlashSeen),
+            }
+        {
+            info!(?outcome, "Latency measurement finished");
+            self.latency_probe = None;
+            self.measured_latency = Some(outcome);
+        }
+
         // Track whether this frame is from a file source (for mirror handling)
         let is_file_source = self.virtual_camera.is_file_source();
 
@@ -291,10 +422,7 @@ impl AppModel {
         let frame_rotation = if is_file_source {
             crate::backends::camera::types::SensorRotation::None
         } else {
-            self.available_cameras
-                .get(self.current_camera_index)
-                .map(|c| c.rotation)
-                .unwrap_or_default()
+            self.current_camera_rotation()
         };
 
         if let Some(task) = self.transition_state.on_frame_received(frame.captured_at) {
@@ -334,9 +462,31 @@ impl AppModel {
             }
         }
 
+        // A photo held for adaptive preview resolution is taken from the
+        // first frame at the full format, or a late one if it doesn't come
+        if let Some(held_since) = self.adaptive_preview.held_since() {
+            let full = self
+                .active_format
+                .as_ref()
+                .is_none_or(|format| frame.width >= format.width);
+            let timed_out = self.clock.now().duration_since(held_since)
+                >= crate::app::adaptive_preview::HOLD_TIMEOUT;
+            if full || timed_out {
+                let shot = self.adaptive_preview.release();
+                self.current_frame = Some(frame);
+                self.current_frame_is_file_source = is_file_source;
+                self.current_frame_rotation = frame_rotation;
+                if shot && self.mode == CameraMode::Photo {
+                    return self.capture_photo();
+                }
+                return Task::none();
+            }
+        }
+
         self.current_frame = Some(frame);
         self.current_frame_is_file_source = is_file_source;
         self.current_frame_rotation = frame_rotation;
+        self.track_adaptive_preview();
         Task::none()
     }
 
@@ -355,7 +505,7 @@ impl AppModel {
         self.current_camera_index = camera_index;
         self.available_formats = formats.clone();
 
-        self.camera_dropdown_options = Self::build_camera_dropdown_labels(&self.available_cameras);
+        self.refresh_camera_dropdowns();
 
         self.select_format_from_cache(self.mode);
 
@@ -483,9 +633,40 @@ impl AppModel {
         });
 
         self.available_cameras = new_cameras;
-        self.camera_dropdown_options = Self::build_camera_dropdown_labels(&self.available_cameras);
+        self.refresh_camera_dropdowns();
 
         if !current_camera_still_available {
+            // Keep the recording and virtual camera alive on the standby
+            // camera. The recorder and virtual camera outputs stay open, so
+            // only the capture stream has to move over.
+            let session_active =
+                self.recording.is_recording() || self.virtual_camera.is_streaming();
+            // `current_camera_index` is stale here, so look the standby up
+            // directly instead of via `standby_camera_index`
+            let standby_index = self
+                .config
+                .standby_camera_path
+                .as_deref()
+                .and_then(|path| self.available_cameras.iter().position(|c| c.path == path));
+            if session_active && let Some(standby_index) = standby_index {
+                warn!(
+                    failed = ?old_current.as_ref().map(|c| c.name.as_str()),
+                    standby = %self.available_cameras[standby_index].name,
+                    "Camera disconnected mid-session, failing over to standby camera"
+                );
+                self.failover_camera = Some(self.available_cameras[standby_index].path.clone());
+                self.pending_hotplug_switch = None;
+                self.current_camera_index = standby_index;
+                self.update_idle_inhibit();
+                return Task::perform(
+                    async move {
+                        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                        standby_index
+                    },
+                    |index| cosmic::Action::App(Message::SelectCamera(index)),
+                );
+            }
+
             // Stop recording if the camera used for recording is disconnected
             if self.recording.is_recording() {
                 info!("Camera disconnected during recording, stopping recording gracefully");
@@ -600,8 +781,7 @@ impl AppModel {
                 "Removed unplugged camera from list without stopping stream"
             );
 
-            self.camera_dropdown_options =
-                Self::build_camera_dropdown_labels(&self.available_cameras);
+            self.refresh_camera_dropdowns();
 
             if self.current_camera_index >= self.available_cameras.len() {
                 self.current_camera_index = 0;
@@ -661,7 +841,7 @@ impl AppModel {
             });
         }
 
-        self.camera_dropdown_options = Self::build_camera_dropdown_labels(&self.available_cameras);
+        self.refresh_camera_dropdowns();
 
         // First camera appeared (e.g. user plugged in a USB webcam after the
         // app started with none available) — take the inhibit (issue #365).
@@ -670,12 +850,179 @@ impl AppModel {
         Task::none()
     }
 
+    /// Fail over to the standby camera when the active camera stopped
+    /// delivering frames during a recording or virtual camera stream.
+    pub(crate) fn handle_failover_watchdog_tick(&mut self) -> Task<cosmic::Action<Message>> {
+        let session_active = self.recording.is_recording() || self.virtual_camera.is_streaming();
+        // A pending switch hasn't produced its first frame yet; that's not a stall
+        if !session_active
+            || self.current_frame_is_file_source
+            || self.pending_persist_camera.is_some()
+        {
+            return Task::none();
+        }
+        let Some(stalled_for) = self.current_frame.as_ref().map(|f| f.captured_at.elapsed()) else {
+            return Task::none();
+        };
+        let timeout =
+            std::time::Duration::from_millis(crate::constants::timing::FAILOVER_STALL_TIMEOUT_MS);
+        if stalled_for < timeout {
+            return Task::none();
+        }
+        let Some(standby_index) = self.standby_camera_index() else {
+            return Task::none();
+        };
+
+        warn!(
+            stalled_ms = stalled_for.as_millis() as u64,
+            failed = ?self.available_cameras.get(self.current_camera_index).map(|c| c.name.as_str()),
+            standby = %self.available_cameras[standby_index].name,
+            "Camera stalled mid-session, failing over to standby camera"
+        );
+        self.failover_camera = Some(self.available_cameras[standby_index].path.clone());
+        self.do_camera_switch(standby_index)
+    }
+
+    pub(crate) fn handle_select_standby_camera(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        // Index 0 is "None"; the rest mirror `available_cameras`
+        let path = match index {
+            0 => None,
+            n => match self.available_cameras.get(n - 1) {
+                Some(camera) => Some(camera.path.clone()),
+                None => return Task::none(),
+            },
+        };
+        info!(standby = ?path, "Selected standby camera");
+        self.config.standby_camera_path = path;
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save standby camera");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_pip_camera(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        // Index 0 is "Off"; the rest mirror `available_cameras`
+        let path = match index {
+            0 => None,
+            n => match self.available_cameras.get(n - 1) {
+                Some(camera) => Some(camera.path.clone()),
+                None => return Task::none(),
+            },
+        };
+        info!(pip = ?path, "Selected second camera");
+        self.config.pip_camera_path = path;
+        self.pip_preview = None;
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save second camera");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_pip_corner(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::PipCorner;
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        if let Some(&corner) = PipCorner::ALL.get(index) {
+            self.config.pip_corner = corner;
+            info!(?corner, "Selected picture-in-picture corner");
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save picture-in-picture corner");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_dual_camera_layout(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::DualCameraLayout;
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        if let Some(&layout) = DualCameraLayout::ALL.get(index) {
+            self.config.dual_camera_layout = layout;
+            info!(?layout, "Selected dual camera layout");
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save dual camera layout");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_secondary_resolution(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::SecondaryResolution;
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        if let Some(&resolution) = SecondaryResolution::ALL.get(index) {
+            // The subscription restarts the second camera at the new width
+            self.config.secondary_resolution = resolution;
+            info!(?resolution, "Selected second camera resolution");
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save second camera resolution");
+            }
+        }
+        Task::none()
+    }
+
+    /// Keep the latest secondary camera frame for the preview. The
+    /// recording and virtual camera paths read the manager's shared frame
+    /// directly instead.
+    pub(crate) fn handle_pip_frame(
+        &mut self,
+        frame: Arc<crate::backends::camera::types::CameraFrame>,
+    ) -> Task<cosmic::Action<Message>> {
+        if self.pip_camera_index().is_none() || frame.height == 0 {
+            return Task::none();
+        }
+        let handle = cosmic::widget::image::Handle::from_rgba(
+            frame.width,
+            frame.height,
+            frame.data.to_vec(),
+        );
+        self.pip_preview = Some((handle, frame.width as f32 / frame.height as f32));
+        Task::none()
+    }
+
     pub(crate) fn handle_start_camera_transition(&mut self) -> Task<cosmic::Action<Message>> {
         info!("Starting camera transition with blur effect");
         self.blur_frame_rotation = self.current_frame_rotation;
         self.blur_frame_mirror = self.should_mirror_preview();
         self.blur_frame_zoom = self.current_zoom_level();
         let _ = self.transition_state.start();
+        // Latency depends on the camera and format being switched away from
+        self.latency_probe = None;
+        self.measured_latency = None;
         Task::none()
     }
 
@@ -685,6 +1032,129 @@ impl AppModel {
         Task::none()
     }
 
+    pub(crate) fn handle_toggle_capture_card_mode(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        // The pipeline restarts with the new buffering, which would cut a
+        // recording short
+        if self.recording.is_recording() {
+            return Task::none();
+        }
+        self.config.capture_card_mode = !self.config.capture_card_mode;
+        info!(
+            enabled = self.config.capture_card_mode,
+            "Capture card mode toggled"
+        );
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save capture card mode setting");
+        }
+        self.measured_latency = None;
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_hdr_input(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::HdrInput;
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        if let Some(&input) = HdrInput::ALL.get(index) {
+            self.config.hdr_input = input;
+            info!(?input, "Selected HDR input");
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save HDR input setting");
+            }
+            let signalled = self
+                .signalled_colorimetry
+                .as_ref()
+                .and_then(|(_, _, colorimetry)| *colorimetry)
+                .map(|colorimetry| {
+                    crate::shaders::HdrTransfer::from_v4l2_xfer_func(colorimetry.xfer_func)
+                })
+                .unwrap_or_default();
+            crate::shaders::set_hdr_transfer(input.resolve(signalled));
+        }
+        Task::none()
+    }
+
+    /// Tell the converters how the current camera encodes its frames: the
+    /// YUV matrix and range, and the transfer 10-bit frames are tone-mapped
+    /// from (the user's choice, or what the driver reports)
+    fn sync_color_space(&mut self) {
+        use crate::shaders::{ColorSpace, HdrTransfer};
+
+        let path = self
+            .available_cameras
+            .get(self.current_camera_index)
+            .and_then(|camera| camera.v4l2_path())
+            .unwrap_or_default();
+        let fourcc = self
+            .active_format
+            .as_ref()
+            .map(|format| format.pixel_format.as_str())
+            .unwrap_or_default();
+        if self
+            .signalled_colorimetry
+            .as_ref()
+            .is_some_and(|(seen_path, seen_fourcc, _)| seen_path == path && seen_fourcc == fourcc)
+        {
+            return;
+        }
+
+        let colorimetry = if path.is_empty() {
+            None
+        } else {
+            crate::backends::camera::v4l2_utils::current_colorimetry(path)
+        };
+        // libjpeg-turbo decodes to JFIF whatever the driver says
+        let color_space = if fourcc == "MJPEG" || fourcc.contains("MJPG") {
+            ColorSpace::JPEG
+        } else {
+            colorimetry
+                .as_ref()
+                .map(ColorSpace::from_v4l2)
+                .unwrap_or_default()
+        };
+        let signalled = colorimetry
+            .map(|colorimetry| HdrTransfer::from_v4l2_xfer_func(colorimetry.xfer_func))
+            .unwrap_or_default();
+        let transfer = self.config.hdr_input.resolve(signalled);
+        info!(
+            path,
+            fourcc,
+            ?colorimetry,
+            ?color_space,
+            ?transfer,
+            "Camera color space"
+        );
+        crate::shaders::set_color_space(color_space);
+        crate::shaders::set_hdr_transfer(transfer);
+        self.signalled_colorimetry = Some((path.to_string(), fourcc.to_string(), colorimetry));
+    }
+
+    pub(crate) fn handle_measure_latency(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.current_frame.is_none() || self.latency_probe.is_some() {
+            return Task::none();
+        }
+        info!("Measuring glass-to-glass latency");
+        // The drawer would cover part of the flashing window
+        self.close_all_pickers();
+        if self.core.window.show_context {
+            self.core.window.show_context = false;
+            self.sync_audio_probe();
+        }
+        self.measured_latency = None;
+        self.latency_probe = Some(crate::app::latency_probe::LatencyProbe::new(
+            std::time::Instant::now(),
+        ));
+        Task::none()
+    }
+
     pub(crate) fn handle_toggle_mirror_preview(&mut self) -> Task<cosmic::Action<Message>> {
         use cosmic::cosmic_config::CosmicConfigEntry;
 
@@ -719,6 +1189,26 @@ impl AppModel {
         Task::none()
     }
 
+    pub(crate) fn handle_toggle_persist_camera_controls(
+        &mut self,
+    ) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        self.config.persist_camera_controls = !self.config.persist_camera_controls;
+        self.control_overrides.clear();
+        info!(
+            persist = self.config.persist_camera_controls,
+            "Persistent camera controls toggled"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save persistent controls setting");
+        }
+        Task::none()
+    }
+
     pub(crate) fn handle_toggle_haptic_feedback(&mut self) -> Task<cosmic::Action<Message>> {
         use cosmic::cosmic_config::CosmicConfigEntry;
 
@@ -736,6 +1226,90 @@ impl AppModel {
         Task::none()
     }
 
+    pub(crate) fn handle_toggle_silent_mode(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        self.config.silent_mode = !self.config.silent_mode;
+        info!(silent = self.config.silent_mode, "Silent mode toggled");
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save silent mode setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_capture_sounds(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        self.config.capture_sounds = !self.config.capture_sounds;
+        info!(
+            enabled = self.config.capture_sounds,
+            "Capture sounds toggled"
+        );
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save capture sounds setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_capture_sound_volume(
+        &mut self,
+        volume: u32,
+    ) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        let volume = volume.min(100);
+        if self.config.capture_sound_volume == volume {
+            return Task::none();
+        }
+        self.config.capture_sound_volume = volume;
+        debug!(volume, "Set capture sound volume");
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save capture sound volume");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_preview_gestures(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        self.config.preview_gestures = !self.config.preview_gestures;
+        info!(
+            preview_gestures = self.config.preview_gestures,
+            "Preview gestures toggled"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save preview gestures setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_keyboard_navigation(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        self.config.keyboard_navigation = !self.config.keyboard_navigation;
+        self.core.set_keyboard_nav(self.config.keyboard_navigation);
+        info!(
+            keyboard_navigation = self.config.keyboard_navigation,
+            "Keyboard navigation toggled"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save keyboard navigation setting");
+        }
+        Task::none()
+    }
+
     pub(crate) fn handle_toggle_virtual_camera_enabled(&mut self) -> Task<cosmic::Action<Message>> {
         use cosmic::cosmic_config::CosmicConfigEntry;
 
@@ -779,23 +1353,93 @@ impl AppModel {
         fit_anim_task
     }
 
+    pub(crate) fn handle_toggle_look_here_marker(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        self.config.look_here_marker = !self.config.look_here_marker;
+        info!(
+            look_here_marker = self.config.look_here_marker,
+            "Look here marker toggled"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save look here marker setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_camera_location(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::CameraLocation;
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        let Some(&location) = CameraLocation::ALL.get(index) else {
+            return Task::none();
+        };
+        let Some(camera) = self.available_cameras.get(self.current_camera_index) else {
+            return Task::none();
+        };
+        info!(camera = %camera.path, ?location, "Selected camera location");
+        if location == CameraLocation::default() {
+            self.config.camera_locations.remove(&camera.path);
+        } else {
+            self.config
+                .camera_locations
+                .insert(camera.path.clone(), location);
+        }
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save camera location");
+        }
+        Task::none()
+    }
+
     // =========================================================================
     // Privacy Cover Detection
     // =========================================================================
 
     /// Handle privacy cover status change
+    ///
+    /// Closing the cover (or engaging the privacy switch) stops the camera
+    /// pipeline, so the device is released and the preview shows the blocked
+    /// screen rather than a frozen frame; a running recording is stopped
+    /// first so its file is finalized. Opening it starts the pipeline again.
     pub(crate) fn handle_privacy_cover_status_changed(
         &mut self,
         is_closed: bool,
     ) -> Task<cosmic::Action<Message>> {
-        if self.privacy_cover_closed != is_closed {
-            info!(
-                privacy_cover_closed = is_closed,
-                "Privacy cover status changed"
-            );
-            self.privacy_cover_closed = is_closed;
+        if self.privacy_cover_closed == is_closed {
+            return Task::none();
         }
-        Task::none()
+        info!(
+            privacy_cover_closed = is_closed,
+            "Privacy cover status changed"
+        );
+        self.privacy_cover_closed = is_closed;
+
+        if !is_closed {
+            // The camera subscription comes back with a fresh cancel flag
+            self.camera_stream_restart_counter = self.camera_stream_restart_counter.wrapping_add(1);
+            return Task::none();
+        }
+
+        let stop_recording = if self.recording.is_recording() {
+            warn!("Camera blocked by its privacy switch, stopping the recording");
+            self.handle_toggle_recording()
+        } else {
+            Task::none()
+        };
+        self.camera_cancel_flag
+            .store(true, std::sync::atomic::Ordering::Release);
+        self.camera_cancel_flag = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        self.current_frame = None;
+        stop_recording
     }
 
     /// Check privacy cover status for the current camera
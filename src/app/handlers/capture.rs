@@ -4,9 +4,13 @@
 //!
 //! Handles photo capture, video recording, flash, zoom, and timer functionality.
 
-use crate::app::state::{AppModel, CameraMode, Message, RecordingState, TimelapseState};
+use crate::app::state::{
+    AnimatedClipState, AppModel, CameraMode, Message, RecordingState, TimelapseState,
+};
 use crate::backends::camera::types::RecordingFrame;
 use crate::backends::camera::v4l2_controls::read_exposure_metadata;
+use crate::errors::AppError;
+use crate::media::animation::{AnimationFormat, AnimationOptions};
 use crate::pipelines::photo::burst_mode::BurstModeConfig;
 use crate::pipelines::photo::burst_mode::burst::{
     calculate_adaptive_params, estimate_scene_brightness,
@@ -24,17 +28,29 @@ struct AppsrcRecordingConfig {
     format: crate::backends::camera::types::CameraFormat,
     output_path: PathBuf,
     sensor_rotation: crate::backends::camera::types::SensorRotation,
+    /// Rotation left to players through the container's orientation tag
+    orientation: crate::backends::camera::types::SensorRotation,
     audio_device: Option<String>,
     /// Native sample rate of the selected audio source; 0 if unknown.
     audio_source_rate_hz: u32,
     selected_encoder: Option<crate::media::encoders::video::EncoderInfo>,
-    bitrate_kbps: u32,
+    encoder_config: crate::pipelines::video::EncoderConfig,
+    /// Screen to record with the camera inset, instead of the camera alone
+    screen_cast: Option<Arc<crate::backends::screen_cast::ScreenCast>>,
 }
 
 /// Delay in ms before resetting burst mode state after successful capture
 const BURST_MODE_SUCCESS_DISPLAY_MS: u64 = 2000;
 /// Delay in ms before resetting burst mode state after an error
 const BURST_MODE_ERROR_DISPLAY_MS: u64 = 3000;
+/// How long the flash is lit before the photo is taken, for auto exposure
+/// to settle on the extra light (and a screen's backlight to come up)
+const FLASH_LEAD_MS: u64 = 1000;
+/// How long the flash stays lit after the shutter, until a still requested
+/// from the camera has surely been exposed
+const FLASH_TAIL_MS: u64 = 250;
+/// Zoom factor per volume-key press when the keys are set to zoom
+const VOLUME_KEY_ZOOM_STEP: f32 = 1.25;
 
 impl AppModel {
     // =========================================================================
@@ -70,13 +86,56 @@ impl AppModel {
         }
     }
 
-    /// Turn off hardware flash LEDs (safe to call even if no hardware)
+    /// Turn off hardware flash LEDs, or put them back to the torch when
+    /// that is on (safe to call even if no hardware)
     pub(crate) fn turn_off_flash_hardware(&self) {
-        if !self.flash.hardware.devices.is_empty() {
+        if self.flash.torch && self.use_hardware_flash() {
+            crate::flash::all_on(&self.flash.hardware.devices);
+        } else if !self.flash.hardware.devices.is_empty() {
             crate::flash::all_off(&self.flash.hardware.devices);
         }
     }
 
+    /// Light the flash ahead of a photo: the LEDs of a back camera that has
+    /// them, otherwise the whole window turns white with the backlight at
+    /// full, like a phone's front flash
+    pub(crate) fn fire_flash(&mut self) {
+        if self.use_hardware_flash() {
+            info!("Flash enabled - firing hardware flash before capture");
+            crate::flash::all_fire(
+                &self.flash.hardware.devices,
+                std::time::Duration::from_millis(FLASH_LEAD_MS + FLASH_TAIL_MS),
+            );
+        } else {
+            info!("Flash enabled - showing screen flash before capture");
+            self.flash.active = true;
+            self.sync_screen_boost();
+        }
+    }
+
+    /// Put out the LEDs or the screen flash, and the screen brightness with it
+    pub(crate) fn end_flash(&mut self) {
+        self.turn_off_flash_hardware();
+        self.flash.active = false;
+        self.sync_screen_boost();
+    }
+
+    /// Turn the torch on or off, as a flashlight
+    pub(crate) fn handle_toggle_torch(&mut self) -> Task<cosmic::Action<Message>> {
+        if !self.flash.torch && self.is_back_camera() && self.flash.hardware.has_error() {
+            warn!("Flash hardware detected but not writable — showing permission error");
+            self.flash.error_popup = self.flash.hardware.permission_error.clone();
+            return Task::none();
+        }
+        self.flash.torch = !self.flash.torch && self.use_hardware_flash();
+        info!(torch = self.flash.torch, "Torch toggled");
+        // A recording with the flash on keeps it lit until it stops
+        if !(self.flash.enabled && self.recording.is_recording()) {
+            self.turn_off_flash_hardware();
+        }
+        Task::none()
+    }
+
     /// Handle dismissing the flash permission error popup
     pub(crate) fn handle_dismiss_flash_error(&mut self) -> Task<cosmic::Action<Message>> {
         self.flash.error_popup = None;
@@ -139,8 +198,9 @@ impl AppModel {
 
     /// Check if burst mode would be triggered based on current scene brightness
     ///
-    /// Returns true if Auto mode would use more than 1 frame (actual burst capture)
-    /// or if a fixed frame count > 1 is set, AND the user hasn't overridden it.
+    /// Returns true if Auto mode would use more than 1 frame (actual burst capture),
+    /// if a fixed frame count > 1 is set, or if night mode switched HDR+ on for
+    /// a dark scene, AND the user hasn't overridden it.
     pub fn would_use_burst_mode(&self) -> bool {
         use crate::config::BurstModeSetting;
 
@@ -150,7 +210,7 @@ impl AppModel {
         }
 
         match self.config.burst_mode_setting {
-            BurstModeSetting::Off => false,
+            BurstModeSetting::Off => self.night_mode_engaged,
             BurstModeSetting::Frames4
             | BurstModeSetting::Frames6
             | BurstModeSetting::Frames8
@@ -162,6 +222,14 @@ impl AppModel {
         }
     }
 
+    /// Whether photos are taken as a burst for super-resolution
+    ///
+    /// Cameras that capture raw Bayer bursts (multistream) keep using HDR+,
+    /// which is the only pipeline that reads them.
+    pub fn would_use_super_resolution(&self) -> bool {
+        self.config.super_resolution && !self.is_current_camera_multistream()
+    }
+
     /// Build camera metadata (name, driver, exposure info) for photo encoding.
     fn build_camera_metadata(&self) -> crate::pipelines::photo::CameraMetadata {
         self.available_cameras
@@ -188,16 +256,124 @@ impl AppModel {
         self.capture_photo_with_frame(None)
     }
 
+    /// Crop of `frame`, in sensor space, that a photo keeps:
+    /// - Cover mode: the screen-visible area, then the aspect ratio
+    /// - Fit mode: the aspect ratio applied to the full frame
+    ///
+    /// `None` when the whole frame is kept.
+    fn photo_crop_rect(
+        &self,
+        frame: &crate::backends::camera::types::CameraFrame,
+    ) -> Option<(u32, u32, u32, u32)> {
+        // Crop math works in sensor space
+        let rotation = self.current_camera_rotation();
+        let (fw, fh) = if rotation.swaps_dimensions() {
+            (frame.height, frame.width)
+        } else {
+            (frame.width, frame.height)
+        };
+        let portrait = self.screen_is_portrait();
+        let (x, y, w, h) = if self.preview_fit_to_view {
+            self.photo_aspect_ratio.crop_rect(fw, fh, portrait)
+        } else {
+            // Map the on-screen frame rect (the same one the canvas
+            // crop overlay highlights) back to sensor coords via the
+            // preview's Cover scaling. This stays aligned with what
+            // the user sees inside the crop bars even when the UI
+            // bars are asymmetric (top 47 vs bottom ~174) — a
+            // sensor-centered crop would drift off-axis here.
+            crate::app::preview_geometry::cover_capture_crop(
+                fw,
+                fh,
+                self.screen_width,
+                self.screen_height,
+                self.settled_top_ui_height(),
+                self.settled_bottom_ui_height(),
+                self.photo_aspect_ratio.display_ratio(portrait),
+            )
+        };
+        if rotation.swaps_dimensions() {
+            Some((y, x, h, w))
+        } else if x == 0 && y == 0 && w == fw && h == fh {
+            None
+        } else {
+            Some((x, y, w, h))
+        }
+    }
+
     /// Capture a photo, optionally using a pre-captured frame (zero-shutter-lag).
     /// Falls back to `self.current_frame` if `zsl_frame` is `None`.
     fn capture_photo_with_frame(
         &mut self,
-        zsl_frame: Option<Arc<crate::backends::camera::types::CameraFrame>>,
+        mut zsl_frame: Option<Arc<crate::backends::camera::types::CameraFrame>>,
     ) -> Task<cosmic::Action<Message>> {
+        // A preview stepped down by adaptive resolution is too small for the
+        // photo: the full format comes back first and the photo is taken
+        // from its first frame (see `handle_camera_frame`). Multistream
+        // cameras take stills on their own stream and don't need it.
+        if self.config.adaptive_preview && !self.is_current_camera_multistream() {
+            if self.preview_format() != self.active_format {
+                self.adaptive_preview.hold(self.clock.now());
+            }
+            if self.adaptive_preview.held_since().is_some() {
+                self.adaptive_preview.wait_for_shot();
+                return Task::none();
+            }
+            // A frame kept from before the full format came back is too small
+            zsl_frame = zsl_frame.filter(|frame| {
+                self.active_format
+                    .as_ref()
+                    .is_none_or(|format| frame.width >= format.width)
+            });
+        }
+
+        self.play_capture_sound(crate::sound::CaptureSound::Shutter);
+
+        // Photo booth strips are always saved; anything else can go
+        // straight to the clipboard
+        if self.config.photo_to_clipboard && self.photo_booth.is_none() {
+            return self.copy_photo_to_clipboard(zsl_frame);
+        }
+
+        let photo_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+        if !self.check_free_space(&photo_dir) {
+            warn!("Not enough storage space left for a photo");
+            self.animate_capture_scale(1.0);
+            return Task::none();
+        }
+
+        if self.photo_booth.is_some() {
+            return self.take_photo_booth_shot(zsl_frame);
+        }
+
+        // With a second camera running, its frame is taken right here at the
+        // trigger so both cameras are captured together. The multi-frame and
+        // raw-stream paths would drift apart in time, so they're skipped.
+        if !self.current_frame_is_file_source
+            && let Some(pip) = self.pip_overlay()
+            && let Some(secondary) = pip.snapshot()
+        {
+            return self.capture_dual_camera_photo(zsl_frame, pip, secondary);
+        }
+
+        // Product mode takes precedence over HDR+: it averages viewfinder
+        // frames instead of merging them, so it also needs a live stream.
+        if self.config.product_mode && !self.current_frame_is_file_source {
+            return self.capture_product_photo();
+        }
+
+        // Focus stacking drives the lens itself and keeps viewfinder frames
+        if self.would_use_focus_stack() && !self.current_frame_is_file_source {
+            return self.start_focus_stack();
+        }
+
         // Use HDR+ burst mode only if it would actually be used (frame_count > 1)
         // This respects auto-detected brightness and user override.
+        // Super-resolution takes the same burst, whatever the scene.
         // Skip when file source is active — burst needs multiple live frames.
-        if self.would_use_burst_mode() && !self.current_frame_is_file_source {
+        if (self.would_use_burst_mode() || self.would_use_super_resolution())
+            && !self.current_frame_is_file_source
+        {
             return self.capture_burst_mode_photo();
         }
 
@@ -226,51 +402,20 @@ impl AppModel {
         let zoom_level = self.zoom_level;
         let mirror_horizontal = self.should_mirror_captures();
 
-        let rotation = self.current_camera_rotation();
-
-        // Calculate crop rectangle:
-        // - Cover mode: crop to screen-visible area, then apply aspect ratio
-        // - Fit mode: apply aspect ratio directly on the full frame
-        let (fw, fh) = if rotation.swaps_dimensions() {
-            (frame_arc.height, frame_arc.width)
-        } else {
-            (frame_arc.width, frame_arc.height)
-        };
-        let portrait = self.screen_is_portrait();
-        let crop_rect = {
-            let (x, y, w, h) = if self.preview_fit_to_view {
-                self.photo_aspect_ratio.crop_rect(fw, fh, portrait)
-            } else {
-                // Map the on-screen frame rect (the same one the canvas
-                // crop overlay highlights) back to sensor coords via the
-                // preview's Cover scaling. This stays aligned with what
-                // the user sees inside the crop bars even when the UI
-                // bars are asymmetric (top 47 vs bottom ~174) — a
-                // sensor-centered crop would drift off-axis here.
-                crate::app::preview_geometry::cover_capture_crop(
-                    fw,
-                    fh,
-                    self.screen_width,
-                    self.screen_height,
-                    self.settled_top_ui_height(),
-                    self.settled_bottom_ui_height(),
-                    self.photo_aspect_ratio.display_ratio(portrait),
-                )
-            };
-            if rotation.swaps_dimensions() {
-                Some((y, x, h, w))
-            } else if x == 0 && y == 0 && w == fw && h == fh {
-                None
-            } else {
-                Some((x, y, w, h))
-            }
-        };
+        // The saved photo is turned to how the device is held, in its
+        // pixels or its orientation tag
+        let (output_rotation, orientation) = self.saved_rotation();
+        let crop_rect = self.photo_crop_rect(&frame_arc);
 
         // Get the encoding format from config
         let encoding_format: crate::pipelines::photo::EncodingFormat =
             self.config.photo_output_format.into();
 
-        let camera_metadata = self.build_camera_metadata();
+        let camera_metadata = crate::pipelines::photo::CameraMetadata {
+            orientation,
+            ..self.build_camera_metadata()
+        };
+        let output_sharpening = self.config.output_sharpening;
 
         let save_task = Task::perform(
             async move {
@@ -281,8 +426,9 @@ impl AppModel {
                     filter_type,
                     crop_rect,
                     zoom_level,
-                    rotation,
+                    rotation: output_rotation,
                     mirror_horizontal,
+                    output_sharpening,
                     ..Default::default()
                 };
                 let mut pipeline =
@@ -300,6 +446,301 @@ impl AppModel {
         Task::batch([save_task, animation_task])
     }
 
+    /// Process the frame like a photo (crop, zoom, filter, rotation, mirror)
+    /// and put it on the clipboard as a PNG, without saving a file
+    fn copy_photo_to_clipboard(
+        &mut self,
+        zsl_frame: Option<Arc<crate::backends::camera::types::CameraFrame>>,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::pipelines::photo::{
+            EncodingFormat, PhotoEncoder, PostProcessingConfig, PostProcessor,
+        };
+
+        let Some(frame) = zsl_frame.or_else(|| self.current_frame.clone()) else {
+            info!("No frame available to copy");
+            self.animate_capture_scale(1.0);
+            return Task::none();
+        };
+        info!("Copying photo to clipboard...");
+        self.is_capturing = true;
+
+        let config = PostProcessingConfig {
+            filter_type: self.selected_filter,
+            crop_rect: self.photo_crop_rect(&frame),
+            zoom_level: self.zoom_level,
+            rotation: self.capture_rotation(),
+            mirror_horizontal: self.should_mirror_captures(),
+            output_sharpening: self.config.output_sharpening,
+            ..Default::default()
+        };
+        // Copy frame for background task - mapped buffers become invalid when pipeline stops
+        let frame = Arc::new(frame.to_copied());
+        let copy_task = Task::perform(
+            async move {
+                let processed = PostProcessor::new(config).process(frame).await?;
+                let mut encoder = PhotoEncoder::new();
+                encoder.set_format(EncodingFormat::Png);
+                Ok(encoder.encode(processed).await?.data)
+            },
+            |result| cosmic::Action::App(Message::PhotoCopied(result)),
+        );
+
+        let animation_task = Self::delay_task(150, Message::ClearCaptureAnimation);
+        Task::batch([copy_task, animation_task])
+    }
+
+    /// Copy a photo to the clipboard (keyboard shortcut), whatever the
+    /// shutter is set to
+    pub(crate) fn handle_copy_photo_to_clipboard(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.is_capturing {
+            return Task::none();
+        }
+        self.haptic_tap();
+        self.animate_capture_scale(0.82);
+        self.copy_photo_to_clipboard(None)
+    }
+
+    pub(crate) fn handle_photo_copied(
+        &mut self,
+        result: Result<Vec<u8>, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        self.is_capturing = false;
+        match result {
+            Ok(png) => {
+                info!(bytes = png.len(), "Photo copied to clipboard");
+                self.clipboard_notice = Some(self.clock.now());
+                Task::batch([
+                    cosmic::iced::clipboard::write_data(PngClipboard(png))
+                        .map(|_: ()| cosmic::Action::App(Message::Noop)),
+                    Self::delay_task(
+                        crate::constants::timing::CLIPBOARD_NOTICE_MS,
+                        Message::ClipboardNoticeExpired,
+                    ),
+                ])
+            }
+            Err(err) => {
+                error!(error = %err, "Failed to copy photo to clipboard");
+                Task::none()
+            }
+        }
+    }
+
+    pub(crate) fn handle_clipboard_notice_expired(&mut self) -> Task<cosmic::Action<Message>> {
+        let shown_for =
+            std::time::Duration::from_millis(crate::constants::timing::CLIPBOARD_NOTICE_MS);
+        // A later copy restarted the notice
+        if self
+            .clipboard_notice
+            .is_some_and(|shown| self.clock.now().duration_since(shown) >= shown_for)
+        {
+            self.clipboard_notice = None;
+        }
+        Task::none()
+    }
+
+    /// Show a failure above the shutter with what to do about it
+    pub(crate) fn show_error_notice(&mut self, error: AppError) -> Task<cosmic::Action<Message>> {
+        self.error_notice = Some((error, self.clock.now()));
+        Self::delay_task(
+            crate::constants::timing::ERROR_NOTICE_MS,
+            Message::ErrorNoticeExpired,
+        )
+    }
+
+    pub(crate) fn handle_error_notice_expired(&mut self) -> Task<cosmic::Action<Message>> {
+        let shown_for = std::time::Duration::from_millis(crate::constants::timing::ERROR_NOTICE_MS);
+        // A later error restarted the notice
+        if self
+            .error_notice
+            .as_ref()
+            .is_some_and(|(_, shown)| self.clock.now().duration_since(*shown) >= shown_for)
+        {
+            self.error_notice = None;
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_dismiss_error_notice(&mut self) -> Task<cosmic::Action<Message>> {
+        self.error_notice = None;
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_photo_to_clipboard(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        self.config.photo_to_clipboard = !self.config.photo_to_clipboard;
+        info!(
+            enabled = self.config.photo_to_clipboard,
+            "Photo to clipboard toggled"
+        );
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save photo to clipboard setting");
+        }
+        Task::none()
+    }
+
+    /// Capture a photo with the second camera composited in, using the
+    /// secondary frame snapshotted when the shutter fired.
+    ///
+    /// The composite is saved as laid out (no preview crop, zoom or
+    /// mirroring), matching recordings and the virtual camera.
+    fn capture_dual_camera_photo(
+        &mut self,
+        zsl_frame: Option<Arc<crate::backends::camera::types::CameraFrame>>,
+        pip: crate::backends::camera::pip::PipOverlay,
+        secondary: Arc<crate::backends::camera::types::CameraFrame>,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::backends::camera::types::{CameraFrame, FrameData, PixelFormat};
+
+        let Some(frame_arc) = zsl_frame.or_else(|| self.current_frame.clone()) else {
+            info!("No frame available to capture");
+            return Task::none();
+        };
+
+        info!(
+            secondary_width = secondary.width,
+            secondary_height = secondary.height,
+            "Capturing dual camera photo..."
+        );
+        self.is_capturing = true;
+
+        let save_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+        let filter_type = self.selected_filter;
+        let encoding_format: crate::pipelines::photo::EncodingFormat =
+            self.config.photo_output_format.into();
+        let camera_metadata = self.build_camera_metadata();
+        let output_sharpening = self.config.output_sharpening;
+
+        let save_task = Task::perform(
+            async move {
+                use crate::pipelines::photo::{
+                    EncodingQuality, PhotoPipeline, PostProcessingConfig,
+                };
+                let rgba =
+                    crate::pipelines::video::recorder::convert_frame_to_rgba(&frame_arc).await?;
+                let rgba = pip
+                    .composite_with(rgba, frame_arc.width, frame_arc.height, &secondary)
+                    .await;
+                let composite = Arc::new(CameraFrame {
+                    width: frame_arc.width,
+                    height: frame_arc.height,
+                    data: FrameData::Copied(rgba.into()),
+                    format: PixelFormat::RGBA,
+                    stride: frame_arc.width * 4,
+                    yuv_planes: None,
+                    captured_at: frame_arc.captured_at,
+                    sensor_timestamp_ns: frame_arc.sensor_timestamp_ns,
+                    libcamera_metadata: frame_arc.libcamera_metadata.clone(),
+                });
+
+                let config = PostProcessingConfig {
+                    filter_type,
+                    output_sharpening,
+                    ..Default::default()
+                };
+                let mut pipeline =
+                    PhotoPipeline::with_config(config, encoding_format, EncodingQuality::High);
+                pipeline.set_camera_metadata(camera_metadata);
+                pipeline
+                    .capture_and_save(composite, save_dir)
+                    .await
+                    .map(|p| p.display().to_string())
+            },
+            |result| cosmic::Action::App(Message::PhotoSaved(result)),
+        );
+
+        let animation_task = Self::delay_task(150, Message::ClearCaptureAnimation);
+        Task::batch([save_task, animation_task])
+    }
+
+    /// Start a photo booth run: a countdown before each shot, then the
+    /// shots are saved as one collage
+    fn start_photo_booth(&mut self) -> Task<cosmic::Action<Message>> {
+        let total = usize::from(self.config.photo_booth_shots.max(1));
+        info!(
+            shots = total,
+            layout = ?self.config.photo_booth_layout,
+            "Starting photo booth run"
+        );
+        self.photo_booth = Some(crate::app::state::PhotoBoothSession::new(total));
+        self.start_photo_booth_countdown()
+    }
+
+    /// Count down to the next booth shot on the photo timer
+    fn start_photo_booth_countdown(&mut self) -> Task<cosmic::Action<Message>> {
+        self.photo_timer_countdown = Some(self.config.photo_booth_countdown.max(1));
+        self.photo_timer_tick_start = Some(self.clock.now());
+        Self::delay_task(1000, Message::PhotoTimerTick)
+    }
+
+    /// Keep the current frame as the next booth shot. Starts the next
+    /// countdown, or after the last shot hands them all to the collage
+    /// pipeline.
+    fn take_photo_booth_shot(
+        &mut self,
+        zsl_frame: Option<Arc<crate::backends::camera::types::CameraFrame>>,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::pipelines::photo::booth::{PhotoBoothConfig, process_photo_booth, shot_filter};
+
+        let Some(frame) = zsl_frame.or_else(|| self.current_frame.clone()) else {
+            info!("No frame available for photo booth shot, aborting run");
+            self.photo_booth = None;
+            return Task::none();
+        };
+        let Some(session) = self.photo_booth.as_mut() else {
+            return Task::none();
+        };
+
+        // The preview keeps running, so don't hold the pipeline's buffer
+        // for the rest of the run
+        session.shots.push(Arc::new(frame.to_copied()));
+        info!(
+            shot = session.shots.len(),
+            total = session.total,
+            "Photo booth shot taken"
+        );
+        self.is_capturing = true;
+        let animation_task = Self::delay_task(150, Message::ClearCaptureAnimation);
+
+        if !session.is_complete() {
+            return Task::batch([self.start_photo_booth_countdown(), animation_task]);
+        }
+
+        let Some(session) = self.photo_booth.take() else {
+            return animation_task;
+        };
+        let config = PhotoBoothConfig {
+            layout: self.config.photo_booth_layout,
+            filters: (0..session.total)
+                .map(|index| {
+                    shot_filter(
+                        index,
+                        self.selected_filter,
+                        self.config.photo_booth_filter_per_shot,
+                    )
+                })
+                .collect(),
+            rotation: self.capture_rotation(),
+            mirror_horizontal: self.should_mirror_captures(),
+            encoding_format: self.config.photo_output_format.into(),
+            camera_metadata: self.build_camera_metadata(),
+        };
+        let save_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+
+        let save_task = Task::perform(
+            async move {
+                process_photo_booth(session.shots, config, save_dir)
+                    .await
+                    .map(|path| path.display().to_string())
+                    .map_err(AppError::from)
+            },
+            |result| cosmic::Action::App(Message::PhotoSaved(result)),
+        );
+        Task::batch([save_task, animation_task])
+    }
+
     /// Capture a photo from the raw stream (multistream mode)
     ///
     /// Requests a full-resolution raw frame from the dedicated raw stream,
@@ -320,6 +761,9 @@ impl AppModel {
         let mirror_horizontal = self.should_mirror_captures();
 
         let rotation = self.current_camera_rotation();
+        // Crop math works in sensor space; the saved photo is also turned
+        // to how the device is held, in its pixels or its orientation tag
+        let (output_rotation, orientation) = self.saved_rotation();
 
         // For raw stream capture, use native aspect ratio (no crop from preview)
         // The raw frame may have a different aspect ratio than the preview.
@@ -339,7 +783,11 @@ impl AppModel {
         let encoding_format: crate::pipelines::photo::EncodingFormat =
             self.config.photo_output_format.into();
 
-        let camera_metadata = self.build_camera_metadata();
+        let camera_metadata = crate::pipelines::photo::CameraMetadata {
+            orientation,
+            ..self.build_camera_metadata()
+        };
+        let output_sharpening = self.config.output_sharpening;
 
         let save_task = Task::perform(
             async move {
@@ -393,8 +841,9 @@ impl AppModel {
                     filter_type,
                     crop_rect,
                     zoom_level,
-                    rotation,
+                    rotation: output_rotation,
                     mirror_horizontal,
+                    output_sharpening,
                     ..Default::default()
                 };
                 let mut pipeline =
@@ -425,10 +874,23 @@ impl AppModel {
 
         // Determine frame count: use config if set, otherwise use cached auto-detected value
         let frame_count = match self.config.burst_mode_setting.frame_count() {
+            _ if self.would_use_super_resolution() => {
+                use crate::pipelines::photo::burst_mode::super_resolution;
+                info!(
+                    frame_count = super_resolution::FRAME_COUNT,
+                    "Using super-resolution frame count"
+                );
+                super_resolution::FRAME_COUNT
+            }
             Some(count) => {
                 info!(frame_count = count, "Using configured frame count");
                 count
             }
+            None if self.config.burst_tuning.custom => {
+                let count = usize::from(self.config.burst_tuning.clamped().frame_count);
+                info!(frame_count = count, "Using hand-tuned frame count");
+                count
+            }
             None => {
                 // Use the cached auto-detected frame count (updated every 1 second)
                 let auto_count = self.auto_detected_frame_count;
@@ -441,6 +903,7 @@ impl AppModel {
         };
 
         self.is_capturing = true;
+        self.product_capture_active = false;
         self.burst_mode.start_capture(frame_count);
 
         // If flash is enabled, turn it on for the entire burst capture duration
@@ -451,6 +914,7 @@ impl AppModel {
             } else {
                 info!("Flash enabled - keeping screen flash on during burst capture");
                 self.flash.active = true;
+                self.sync_screen_boost();
             }
         }
 
@@ -522,31 +986,87 @@ impl AppModel {
         Task::none()
     }
 
-    /// Handle raw burst frames captured via capture_photo() (multistream mode)
-    pub(crate) fn handle_burst_mode_raw_frames_captured(
-        &mut self,
-        result: Result<Vec<Arc<crate::backends::camera::types::CameraFrame>>, String>,
-    ) -> Task<cosmic::Action<Message>> {
-        match result {
-            Ok(frames) => {
-                info!(
-                    frames = frames.len(),
-                    "Raw burst frames captured successfully"
-                );
-                // Store the raw frames in burst mode state
-                self.burst_mode.set_frames(frames);
-                // Delegate to the existing processing flow
-                self.handle_burst_mode_frames_collected()
-            }
-            Err(e) => {
+    /// Start collecting viewfinder frames for a product shot
+    ///
+    /// Reuses the burst frame buffer; `handle_burst_mode_frames_collected`
+    /// hands the frames to the product pipeline instead of HDR+.
+    fn capture_product_photo(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::pipelines::photo::product::PRODUCT_FRAME_COUNT;
+
+        if self.burst_mode.is_active() {
+            warn!(
+                stage = ?self.burst_mode.stage,
+                "Cannot start product capture: burst already active"
+            );
+            return Task::none();
+        }
+
+        info!(
+            frame_count = PRODUCT_FRAME_COUNT,
+            "Starting product capture - collecting frames from stream..."
+        );
+        self.is_capturing = true;
+        self.product_capture_active = true;
+        self.burst_mode.start_capture(PRODUCT_FRAME_COUNT);
+        Task::none()
+    }
+
+    /// Hand collected frames to the product photo pipeline
+    fn process_product_frames(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::pipelines::photo::product::{ProductShotConfig, process_product_shot};
+
+        self.product_capture_active = false;
+        // The preview keeps running during processing, so release the
+        // pipeline's buffers rather than holding them for the whole merge.
+        let frames: Vec<_> = self
+            .burst_mode
+            .take_frames()
+            .into_iter()
+            .map(|frame| Arc::new(frame.to_copied()))
+            .collect();
+        self.burst_mode.reset();
+
+        let config = ProductShotConfig {
+            transparent_background: self.config.product_transparent_background,
+            rotation: self.capture_rotation(),
+            mirror_horizontal: self.should_mirror_captures(),
+            encoding_format: self.config.photo_output_format.into(),
+            camera_metadata: self.build_camera_metadata(),
+            ..Default::default()
+        };
+        let save_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+
+        Task::perform(
+            async move {
+                process_product_shot(frames, config, save_dir)
+                    .await
+                    .map(|path| path.display().to_string())
+            },
+            |result| cosmic::Action::App(Message::PhotoSaved(result)),
+        )
+    }
+
+    /// Handle raw burst frames captured via capture_photo() (multistream mode)
+    pub(crate) fn handle_burst_mode_raw_frames_captured(
+        &mut self,
+        result: Result<Vec<Arc<crate::backends::camera::types::CameraFrame>>, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        match result {
+            Ok(frames) => {
+                info!(
+                    frames = frames.len(),
+                    "Raw burst frames captured successfully"
+                );
+                // Store the raw frames in burst mode state
+                self.burst_mode.set_frames(frames);
+                // Delegate to the existing processing flow
+                self.handle_burst_mode_frames_collected()
+            }
+            Err(e) => {
                 error!("Failed to capture raw burst frames: {}", e);
                 self.burst_mode.error();
                 self.is_capturing = false;
-                // Turn off flash
-                self.turn_off_flash_hardware();
-                if self.flash.active {
-                    self.flash.active = false;
-                }
+                self.end_flash();
                 Task::none()
             }
         }
@@ -554,6 +1074,10 @@ impl AppModel {
 
     /// Handle when all burst mode frames have been collected
     pub(crate) fn handle_burst_mode_frames_collected(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.product_capture_active {
+            return self.process_product_frames();
+        }
+
         info!(
             frames = self.burst_mode.frames_captured(),
             "Burst mode frames collected, starting processing"
@@ -571,11 +1095,7 @@ impl AppModel {
         self.blur_frame_zoom = self.current_zoom_level();
 
         // Turn off flash now that capture is complete (before processing)
-        self.turn_off_flash_hardware();
-        if self.flash.active {
-            info!("Turning off screen flash - burst capture complete");
-            self.flash.active = false;
-        }
+        self.end_flash();
 
         // Stop the camera stream during HDR+ processing
         // This frees GPU/CPU resources for burst processing
@@ -601,9 +1121,15 @@ impl AppModel {
         let encoding_format: crate::pipelines::photo::EncodingFormat =
             self.config.photo_output_format.into();
 
-        let camera_metadata = self.build_camera_metadata();
-
         let rotation = self.current_camera_rotation();
+        // Crop math works in sensor space; the saved photo is also turned
+        // to how the device is held, in its pixels or its orientation tag
+        let (output_rotation, orientation) = self.saved_rotation();
+
+        let camera_metadata = crate::pipelines::photo::CameraMetadata {
+            orientation,
+            ..self.build_camera_metadata()
+        };
 
         // Calculate crop rectangle based on preview mode and aspect ratio.
         // Cover-mode crop is computed by inverse-mapping the on-screen
@@ -646,12 +1172,30 @@ impl AppModel {
         config.encoding_format = encoding_format;
         config.camera_metadata = camera_metadata;
         config.save_burst_raw_dng = self.config.save_burst_raw;
-        config.rotation = rotation;
+        config.rotation = output_rotation;
         config.mirror_horizontal = self.should_mirror_captures();
-
-        // Calculate adaptive processing parameters based on scene brightness
-        // estimate_scene_brightness assumes RGBA data, so skip for raw Bayer frames
-        if let Some(first_frame) = frames.first()
+        config.output_sharpening = self.config.output_sharpening;
+        config.super_resolution = self.would_use_super_resolution();
+
+        // Processing parameters: hand-tuned ones from the settings, else
+        // adaptive ones from the light meter's brightness and noise reading
+        // when there is one, otherwise from the first frame's brightness
+        // (estimate_scene_brightness assumes RGBA data, so skip for raw Bayer
+        // frames)
+        if self.config.burst_tuning.custom {
+            config.apply_tuning(&self.config.burst_tuning);
+            debug!(tuning = ?self.config.burst_tuning, "Hand-tuned burst mode parameters applied");
+        } else if let Some(plan) = &self.burst_plan {
+            config.shadow_boost = plan.shadow_boost;
+            config.local_contrast = plan.local_contrast;
+            config.robustness = plan.robustness;
+            debug!(
+                shadow_boost = plan.shadow_boost,
+                local_contrast = plan.local_contrast,
+                robustness = plan.robustness,
+                "Metered burst mode parameters applied"
+            );
+        } else if let Some(first_frame) = frames.first()
             && !first_frame.format.is_bayer()
         {
             let (_luminance, brightness) = estimate_scene_brightness(first_frame);
@@ -671,8 +1215,24 @@ impl AppModel {
         // Get selected filter to apply after processing
         let selected_filter = self.selected_filter;
 
+        // Something for the gallery button right away; the merge takes a while.
+        // It is shown as is, so it gets the whole rotation.
+        let preview_task = match frames.first() {
+            Some(first) if !first.format.is_bayer() => Task::perform(
+                crate::pipelines::photo::burst_mode::save_instant_preview(
+                    Arc::clone(first),
+                    save_dir.clone(),
+                    config.crop_rect,
+                    config.rotation.compose(config.camera_metadata.orientation),
+                    config.mirror_horizontal,
+                ),
+                |result| cosmic::Action::App(Message::BurstPreviewSaved(result)),
+            ),
+            _ => Task::none(),
+        };
+
         // Start processing task - BurstModeState handles the communication channels
-        let (progress_atomic, result_tx) = self.burst_mode.start_processing_task();
+        let (progress_atomic, result_tx, cancel) = self.burst_mode.start_processing_task();
 
         // Spawn processing on a dedicated OS thread - completely separate from UI/tokio
         // This ensures the event loop stays responsive even during blocking GPU operations
@@ -690,6 +1250,7 @@ impl AppModel {
                     config,
                     progress_atomic,
                     selected_filter,
+                    cancel,
                 )
                 .await
             });
@@ -697,7 +1258,39 @@ impl AppModel {
         });
 
         // Start a timer to periodically poll progress and check for completion (every 100ms)
-        Self::delay_task(100, Message::PollBurstModeProgress)
+        Task::batch([
+            preview_task,
+            Self::delay_task(100, Message::PollBurstModeProgress),
+        ])
+    }
+
+    /// Show the instant preview of a burst in the gallery button until the
+    /// HDR+ result replaces it
+    pub(crate) fn handle_burst_preview_saved(
+        &mut self,
+        result: Result<
+            (
+                crate::storage::PendingPhoto,
+                crate::storage::GalleryThumbnailData,
+            ),
+            String,
+        >,
+    ) -> Task<cosmic::Action<Message>> {
+        let (pending, thumbnail) = match result {
+            Ok(saved) => saved,
+            Err(e) => {
+                warn!(error = %e, "Failed to save instant preview");
+                return Task::none();
+            }
+        };
+        // The merge beat the preview to it
+        if self.burst_mode.stage != crate::app::state::BurstModeStage::Processing {
+            return Task::perform(pending.discard(), |_| {
+                cosmic::Action::App(Message::RefreshGalleryThumbnail)
+            });
+        }
+        self.burst_preview = Some(pending);
+        self.handle_gallery_thumbnail_loaded(Some(thumbnail))
     }
 
     /// Poll burst mode progress and check for completion
@@ -720,42 +1313,123 @@ impl AppModel {
         Self::delay_task(100, Message::PollBurstModeProgress)
     }
 
-    /// Handle periodic brightness evaluation tick
+    /// Stop HDR+ processing; the first frame, saved before processing
+    /// started, is kept
+    pub(crate) fn handle_cancel_burst_mode(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.burst_mode.cancel_processing() {
+            info!("Cancelling HDR+ processing");
+        }
+        Task::none()
+    }
+
+    /// Whether preview frames should be light metered: Photo mode is idle
+    /// and either HDR+ Auto or night mode needs to know how dark it is
+    pub(crate) fn light_meter_armed(&self) -> bool {
+        use crate::config::{BurstModeSetting, NightMode};
+
+        let hdr_auto = matches!(self.config.burst_mode_setting, BurstModeSetting::Auto)
+            && !self.hdr_override_disabled;
+        (hdr_auto || self.config.night_mode != NightMode::Off)
+            && self.mode == CameraMode::Photo
+            && !self.is_capturing
+            && !self.burst_mode.is_active()
+            && self.photo_timer_countdown.is_none()
+            && !self.current_frame_is_file_source
+    }
+
+    /// Take in a light meter reading
     ///
-    /// Evaluates scene brightness every 1 second and updates auto_detected_frame_count.
-    /// Only runs when in Auto mode and not overridden.
-    pub(crate) fn handle_brightness_evaluation_tick(&mut self) -> Task<cosmic::Action<Message>> {
-        use crate::config::BurstModeSetting;
-        use crate::pipelines::photo::burst_mode::burst::{
-            calculate_adaptive_params, estimate_scene_brightness,
-        };
+    /// Sizes the next HDR+ burst from the measured brightness and noise, and
+    /// lets night mode offer or switch on HDR+ once the scene stays dark.
+    pub(crate) fn handle_light_measured(
+        &mut self,
+        reading: Option<crate::app::frame_processor::tasks::light_meter::LightReading>,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::app::frame_processor::Scene;
+        use crate::config::NightMode;
+        use crate::pipelines::photo::burst_mode::burst::calculate_noise_aware_params;
 
-        // Only evaluate in Auto mode when not overridden
-        if !matches!(self.config.burst_mode_setting, BurstModeSetting::Auto) {
+        let Some(reading) = reading else {
+            return Task::none();
+        };
+        if !self.light_meter_armed() {
             return Task::none();
         }
 
-        if self.hdr_override_disabled {
-            return Task::none();
+        let plan = calculate_noise_aware_params(reading.brightness, reading.noise_sd);
+        if plan.frame_count != self.auto_detected_frame_count {
+            debug!(
+                old_count = self.auto_detected_frame_count,
+                new_count = plan.frame_count,
+                brightness = ?reading.brightness,
+                noise_sd = ?reading.noise_sd,
+                "Auto frame count updated from light reading"
+            );
+            self.auto_detected_frame_count = plan.frame_count;
         }
+        self.burst_plan = Some(plan);
+        self.light_reading = Some(reading);
 
-        // Evaluate brightness from current frame
-        if let Some(frame) = &self.current_frame {
-            let (_luminance, brightness) = estimate_scene_brightness(frame);
-            let params = calculate_adaptive_params(brightness);
-
-            // Only update and log if frame count changed
-            if params.frame_count != self.auto_detected_frame_count {
-                debug!(
-                    old_count = self.auto_detected_frame_count,
-                    new_count = params.frame_count,
-                    brightness = ?brightness,
-                    "Auto frame count updated based on scene brightness"
-                );
-                self.auto_detected_frame_count = params.frame_count;
+        let was_dark = self.night_detector.is_dark();
+        let dark = self.night_detector.observe(&reading);
+        if dark != was_dark {
+            info!(dark, ?reading, "Night detection changed");
+        }
+
+        let hdr_off = !self.config.burst_mode_setting.is_enabled();
+        if hdr_off && !dark {
+            // Overriding night mode lasts until the light comes up
+            self.hdr_override_disabled = false;
+        }
+        let engaged = self.config.night_mode == NightMode::Auto
+            && dark
+            && hdr_off
+            && !self.hdr_override_disabled
+            && self.auto_detected_frame_count > 1;
+        if engaged != self.night_mode_engaged {
+            info!(
+                engaged,
+                frame_count = self.auto_detected_frame_count,
+                "Night mode switched HDR+"
+            );
+            self.night_mode_engaged = engaged;
+        }
+
+        if self.config.night_mode == NightMode::Suggest {
+            let offer = dark
+                && !self.dismissed_scenes.contains(&Scene::LowLight)
+                && self.scene_action(Scene::LowLight).is_some();
+            if offer && self.scene_suggestion.is_none() {
+                debug!("Night mode suggesting HDR+");
+                self.scene_suggestion = Some(Scene::LowLight);
+            } else if !offer && self.scene_suggestion == Some(Scene::LowLight) {
+                self.scene_suggestion = None;
             }
         }
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_night_mode(&mut self, index: usize) -> Task<cosmic::Action<Message>> {
+        use crate::app::frame_processor::Scene;
+        use crate::config::NightMode;
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        let Some(&night_mode) = NightMode::ALL.get(index) else {
+            return Task::none();
+        };
+        info!(?night_mode, "Selected night mode");
+        self.config.night_mode = night_mode;
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save night mode setting");
+        }
 
+        self.night_detector.reset();
+        self.night_mode_engaged = false;
+        if self.scene_suggestion == Some(Scene::LowLight) {
+            self.scene_suggestion = None;
+        }
         Task::none()
     }
 
@@ -774,11 +1448,15 @@ impl AppModel {
             return Task::none();
         }
 
-        // If timer countdown is active, abort it
-        if self.photo_timer_countdown.is_some() {
+        // If timer countdown or a photo booth run is active, abort it
+        if self.photo_timer_countdown.is_some() || self.photo_booth.is_some() {
             return self.handle_abort_photo_timer();
         }
 
+        if self.mode == CameraMode::Photo && self.config.photo_booth {
+            return self.start_photo_booth();
+        }
+
         // In Photo mode with timer set, start countdown
         if self.mode == CameraMode::Photo
             && self.photo_timer_setting != crate::app::state::PhotoTimerSetting::Off
@@ -786,20 +1464,14 @@ impl AppModel {
             let seconds = self.photo_timer_setting.seconds();
             info!(seconds, "Starting photo timer countdown");
             self.photo_timer_countdown = Some(seconds);
-            self.photo_timer_tick_start = Some(std::time::Instant::now());
+            self.photo_timer_tick_start = Some(self.clock.now());
             return Self::delay_task(1000, Message::PhotoTimerTick);
         }
 
         // Normal capture flow (with flash check)
         if self.mode == CameraMode::Photo && self.flash.enabled && !self.flash.active {
-            if self.use_hardware_flash() {
-                info!("Flash enabled - turning on hardware flash before capture");
-                self.turn_on_flash_hardware();
-            } else {
-                info!("Flash enabled - showing screen flash before capture");
-                self.flash.active = true;
-            }
-            return Self::delay_task(1000, Message::FlashComplete);
+            self.fire_flash();
+            return Self::delay_task(FLASH_LEAD_MS, Message::FlashComplete);
         }
         self.capture_photo()
     }
@@ -830,6 +1502,12 @@ impl AppModel {
         let mut config_changed = false;
 
         match self.config.burst_mode_setting {
+            BurstModeSetting::Off if self.night_mode_engaged => {
+                // Night mode turned it on - turn it back off until next toggle
+                self.night_mode_engaged = false;
+                self.hdr_override_disabled = true;
+                info!("Night mode HDR+ overridden");
+            }
             BurstModeSetting::Off => {
                 // Turning ON: go to Auto mode, clear any override
                 self.config.burst_mode_setting = BurstModeSetting::Auto;
@@ -939,9 +1617,26 @@ impl AppModel {
 
     pub(crate) fn handle_flash_complete(&mut self) -> Task<cosmic::Action<Message>> {
         info!("Flash complete - capturing photo");
-        self.turn_off_flash_hardware();
-        self.flash.active = false;
-        self.capture_photo()
+        // Product shots and focus stacks gather frames for a while; a flash
+        // going out halfway through would light only some of them
+        if (self.config.product_mode || self.would_use_focus_stack())
+            && !self.current_frame_is_file_source
+        {
+            self.end_flash();
+            return self.capture_photo();
+        }
+        let capture = self.capture_photo();
+        // A multistream camera's still arrives a frame or two after the
+        // request, so the light stays on a moment longer
+        Task::batch([capture, Self::delay_task(FLASH_TAIL_MS, Message::FlashOff)])
+    }
+
+    pub(crate) fn handle_flash_off(&mut self) -> Task<cosmic::Action<Message>> {
+        // A burst puts the flash out itself once it has all its frames
+        if !self.burst_mode.is_active() {
+            self.end_flash();
+        }
+        Task::none()
     }
 
     pub(crate) fn handle_cycle_photo_timer(&mut self) -> Task<cosmic::Action<Message>> {
@@ -963,20 +1658,19 @@ impl AppModel {
                 self.animate_capture_scale(1.0);
                 // Check if flash is enabled
                 if self.flash.enabled && !self.flash.active {
-                    if self.use_hardware_flash() {
-                        info!("Flash enabled - turning on hardware flash before capture");
-                        self.turn_on_flash_hardware();
-                    } else {
-                        info!("Flash enabled - showing screen flash before capture");
-                        self.flash.active = true;
-                    }
-                    return Self::delay_task(1000, Message::FlashComplete);
+                    self.fire_flash();
+                    return Self::delay_task(FLASH_LEAD_MS, Message::FlashComplete);
                 }
                 return self.capture_photo();
             } else {
                 // Continue countdown
+                self.play_capture_sound(if remaining == 2 {
+                    crate::sound::CaptureSound::CountdownFinal
+                } else {
+                    crate::sound::CaptureSound::CountdownTick
+                });
                 self.photo_timer_countdown = Some(remaining - 1);
-                self.photo_timer_tick_start = Some(std::time::Instant::now());
+                self.photo_timer_tick_start = Some(self.clock.now());
                 info!(remaining = remaining - 1, "Photo timer tick");
                 return Self::delay_task(1000, Message::PhotoTimerTick);
             }
@@ -985,6 +1679,9 @@ impl AppModel {
     }
 
     pub(crate) fn handle_abort_photo_timer(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.photo_booth.take().is_some() {
+            info!("Photo booth run aborted");
+        }
         if self.photo_timer_countdown.is_some() {
             info!("Photo timer countdown aborted");
             self.photo_timer_countdown = None;
@@ -994,6 +1691,63 @@ impl AppModel {
         Task::none()
     }
 
+    /// Whether frames should be checked for the shutter gesture: the setting
+    /// is on and the shutter is idle in Photo mode
+    pub(crate) fn gesture_shutter_armed(&self) -> bool {
+        self.config.gesture_shutter
+            && self.mode == CameraMode::Photo
+            && self.photo_timer_countdown.is_none()
+            && self.photo_booth.is_none()
+            && self.burst_picker.is_none()
+            && !self.is_capturing
+            && !self.flash.active
+            && !self.quick_record.is_pressed()
+            && !self.quick_record.is_recording()
+            && !self.quick_record.is_bursting()
+    }
+
+    /// Start the photo timer once the palm has been held up for
+    /// [`HOLD_DURATION`](crate::app::frame_processor::tasks::gesture_detector::HOLD_DURATION)
+    pub(crate) fn handle_gesture_detection_updated(
+        &mut self,
+        detection: Option<crate::app::frame_processor::GestureDetection>,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::app::frame_processor::tasks::gesture_detector::HOLD_DURATION;
+
+        if detection.is_none() || !self.gesture_shutter_armed() {
+            self.gesture_hold_start = None;
+            return Task::none();
+        }
+
+        let now = self.clock.now();
+        let hold_start = *self.gesture_hold_start.get_or_insert(now);
+        if now.duration_since(hold_start) < HOLD_DURATION {
+            return Task::none();
+        }
+        self.gesture_hold_start = None;
+        self.animate_capture_scale(0.82);
+
+        if self.config.photo_booth {
+            info!("Shutter gesture held - starting photo booth");
+            return self.start_photo_booth();
+        }
+
+        // The gesture always counts down, so there's time to lower the hand
+        let seconds = match self.photo_timer_setting {
+            crate::app::state::PhotoTimerSetting::Off => {
+                crate::app::state::PhotoTimerSetting::Sec3.seconds()
+            }
+            setting => setting.seconds(),
+        };
+        info!(
+            seconds,
+            "Shutter gesture held - starting photo timer countdown"
+        );
+        self.photo_timer_countdown = Some(seconds);
+        self.photo_timer_tick_start = Some(self.clock.now());
+        Self::delay_task(1000, Message::PhotoTimerTick)
+    }
+
     pub(crate) fn handle_zoom_in(&mut self) -> Task<cosmic::Action<Message>> {
         // Multiplicative zoom: each step is ~2% magnification change,
         // so it feels consistent at any zoom level.
@@ -1022,17 +1776,23 @@ impl AppModel {
     /// spawned — the existing one picks up the replaced animation, same
     /// pattern as `start_fit_animation`.
     pub(crate) fn handle_reset_zoom(&mut self) -> Task<cosmic::Action<Message>> {
+        self.animate_zoom_to(1.0)
+    }
+
+    /// Ease the zoom level to `target` (clamped to the 1–10× range)
+    fn animate_zoom_to(&mut self, target: f32) -> Task<cosmic::Action<Message>> {
+        let target = target.clamp(1.0, 10.0);
         let from = self.current_zoom_level();
-        if (from - 1.0).abs() <= 0.001 {
+        if (from - target).abs() <= 0.001 {
             return Task::none();
         }
-        self.zoom_level = 1.0;
+        self.zoom_level = target;
         let was_idle = self.zoom_animation.is_none();
         self.zoom_animation = Some(crate::app::state::ZoomAnimation {
             start: std::time::Instant::now(),
             from,
         });
-        debug!(from, "Zoom reset to 1.0 (animated)");
+        debug!(from, target, "Zoom (animated)");
         if was_idle {
             Self::delay_task(16, Message::ZoomAnimationTick)
         } else {
@@ -1040,6 +1800,29 @@ impl AppModel {
         }
     }
 
+    /// Hardware volume key pressed while the window is focused
+    pub(crate) fn handle_volume_key(
+        &mut self,
+        key: crate::backends::volume_keys::VolumeKey,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::backends::volume_keys::VolumeKey;
+        use crate::config::VolumeKeyAction;
+
+        match self.config.volume_key_action {
+            VolumeKeyAction::Capture => self.handle_capture(),
+            VolumeKeyAction::Zoom if self.mode.supports_fit_and_zoom() => {
+                let target = match key {
+                    VolumeKey::Up => self.zoom_level * VOLUME_KEY_ZOOM_STEP,
+                    VolumeKey::Down => self.zoom_level / VOLUME_KEY_ZOOM_STEP,
+                };
+                self.animate_zoom_to(target)
+            }
+            // Keys aren't grabbed in volume mode; a press can only slip
+            // through while the setting changes
+            VolumeKeyAction::Zoom | VolumeKeyAction::Volume => Task::none(),
+        }
+    }
+
     pub(crate) fn handle_pinch_zoom(&mut self, level: f32) -> Task<cosmic::Action<Message>> {
         let new_zoom = level.clamp(1.0, 10.0);
         if (new_zoom - self.zoom_level).abs() > 0.001 {
@@ -1051,7 +1834,7 @@ impl AppModel {
 
     pub(crate) fn handle_photo_saved(
         &mut self,
-        result: Result<String, String>,
+        result: Result<String, AppError>,
     ) -> Task<cosmic::Action<Message>> {
         // Always release the capture lock here; the separate
         // `ClearCaptureAnimation` task may be dropped on backpressure or panic,
@@ -1062,22 +1845,18 @@ impl AppModel {
                 info!(path = %path, "Photo saved successfully");
                 self.last_media_path = Some(path.clone());
 
-                return Task::done(cosmic::Action::App(Message::RefreshGalleryThumbnail));
+                Task::done(cosmic::Action::App(Message::RefreshGalleryThumbnail))
             }
             Err(err) => {
                 let expected_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
                 error!(
                     error = %err,
                     expected_directory = %expected_dir.display(),
-                    "Failed to save photo. This may be caused by: \
-                     1) Insufficient disk space, \
-                     2) Missing write permissions to the Pictures directory, \
-                     3) Flatpak sandbox restrictions (ensure xdg-pictures access is granted), \
-                     4) XDG_PICTURES_DIR pointing to an inaccessible location"
+                    "Failed to save photo"
                 );
+                self.show_error_notice(err)
             }
         }
-        Task::none()
     }
 
     pub(crate) fn handle_clear_capture_animation(&mut self) -> Task<cosmic::Action<Message>> {
@@ -1120,8 +1899,15 @@ impl AppModel {
         if self.flash.enabled {
             self.flash.enabled = false;
         }
+        self.flash.torch = false;
         self.turn_off_flash_hardware();
 
+        // Likewise the screen brightness; waited for, as the process may
+        // exit right below
+        if let Some(backlight) = self.screen_boost.take() {
+            let _ = backlight.set_in_background(backlight.brightness).join();
+        }
+
         if self.recording.is_recording() {
             info!("Window close requested during recording — signalling EOS first");
             self.pending_close = true;
@@ -1155,10 +1941,17 @@ impl AppModel {
     }
 
     pub(crate) fn handle_toggle_recording(&mut self) -> Task<cosmic::Action<Message>> {
+        // A GIF/WebP clip stops by itself once it has all its frames
+        if self.animated_clip.is_active() {
+            return Task::none();
+        }
         self.haptic_tap();
         if self.recording.is_recording() {
             // Stopping: animate release (scale back up)
             self.animate_capture_scale(1.0);
+            if !self.av_sync_test.is_running() {
+                self.play_capture_sound(crate::sound::CaptureSound::RecordStop);
+            }
             // Turn off torch when stopping recording
             if self.flash.enabled {
                 self.turn_off_flash_hardware();
@@ -1183,6 +1976,13 @@ impl AppModel {
                 error!("No active format for recording");
                 return Task::none();
             }
+            if let Some(format) = AnimationFormat::from_config(self.config.animated_clip_format) {
+                return self.start_animated_clip(format);
+            }
+            // Screen recordings start once the user has picked a screen
+            if self.config.record_screen && !self.current_frame_is_file_source {
+                return self.request_screen_cast();
+            }
             // Animate to recording size (after guards pass)
             self.animate_capture_scale(0.82);
             return Task::done(cosmic::Action::App(Message::StartRecordingAfterDelay));
@@ -1190,6 +1990,43 @@ impl AppModel {
         Task::none()
     }
 
+    /// Open the ScreenCast portal's source picker for a screen recording
+    fn request_screen_cast(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.screen_cast_pending {
+            return Task::none();
+        }
+        self.screen_cast_pending = true;
+        Task::perform(crate::backends::screen_cast::request(), |result| {
+            cosmic::Action::App(Message::ScreenCastReady(result.map(Arc::new)))
+        })
+    }
+
+    pub(crate) fn handle_screen_cast_ready(
+        &mut self,
+        result: Result<Arc<crate::backends::screen_cast::ScreenCast>, AppError>,
+    ) -> Task<cosmic::Action<Message>> {
+        self.screen_cast_pending = false;
+        match result {
+            // The camera or mode may have changed while the dialog was open
+            Ok(cast) if self.mode == CameraMode::Video && !self.recording.is_recording() => {
+                self.screen_cast = Some(cast);
+                self.animate_capture_scale(0.82);
+                Task::done(cosmic::Action::App(Message::StartRecordingAfterDelay))
+            }
+            Ok(_) => Task::none(),
+            // Without a portal there is nothing to pick from; say so
+            Err(e @ AppError::Portal(_)) => {
+                warn!(error = %e, "No screen to record");
+                self.show_error_notice(e)
+            }
+            // Most likely the picker was cancelled
+            Err(e) => {
+                warn!(error = %e, "No screen to record");
+                Task::none()
+            }
+        }
+    }
+
     pub(crate) fn handle_recording_started(
         &mut self,
         path: String,
@@ -1197,13 +2034,21 @@ impl AppModel {
         info!(path = %path, "Recording started successfully");
         self.update_idle_inhibit();
         self.sync_audio_probe();
+        // The sync test listens for its own beep; a chime would be heard first
+        if self.av_sync_test.is_running() {
+            return Task::batch([
+                Self::delay_task(1000, Message::UpdateRecordingDuration),
+                Self::av_sync_recording_started(),
+            ]);
+        }
+        self.play_capture_sound(crate::sound::CaptureSound::RecordStart);
         Self::delay_task(1000, Message::UpdateRecordingDuration)
     }
 
     pub(crate) fn handle_recording_stopped(
         &mut self,
         session: u64,
-        result: Result<String, String>,
+        result: Result<String, AppError>,
     ) -> Task<cosmic::Action<Message>> {
         // If a new recording has already taken over `self.recording`, this
         // stop event is from a previous session and must NOT touch the
@@ -1213,6 +2058,10 @@ impl AppModel {
         // `recorder.stop()`. We still log the result and update
         // `last_media_path` / the gallery for the just-finalized file.
         let is_current = self.recording.session() == Some(session);
+        let events = self
+            .recording_events
+            .take_if(|(events_session, _)| *events_session == session)
+            .map(|(_, events)| events);
 
         if is_current {
             self.recording = RecordingState::Idle;
@@ -1223,6 +2072,17 @@ impl AppModel {
             self.turn_off_flash_hardware();
         }
 
+        // The sync test clip is measured and deleted rather than kept. Its
+        // stop already left `self.recording` idle, so it is never current.
+        if matches!(
+            self.av_sync_test,
+            crate::app::state::AvSyncTestState::Recording { .. }
+        ) && !self.recording.is_recording()
+            && !self.pending_close
+        {
+            return self.measure_av_sync_clip(result);
+        }
+
         if is_current && self.pending_close {
             match &result {
                 Ok(path) => info!(path = %path, "Recording finalized — exiting"),
@@ -1234,28 +2094,68 @@ impl AppModel {
         match result {
             Ok(path) => {
                 info!(session, path = %path, "Recording saved successfully");
+                if let Some(events) = events.filter(|events| !events.is_empty()) {
+                    Self::write_event_subtitles(&path, &events);
+                }
                 self.last_media_path = Some(path.clone());
                 Task::done(cosmic::Action::App(Message::RefreshGalleryThumbnail))
             }
             Err(err) => {
-                let expected_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+                let expected_dir = crate::app::get_video_directory(&self.config.save_folder_name);
                 error!(
                     session,
                     error = %err,
                     expected_directory = %expected_dir.display(),
-                    "Failed to save recording. This may be caused by: \
-                     1) Insufficient disk space, \
-                     2) Missing write permissions to the Pictures directory, \
-                     3) Flatpak sandbox restrictions (ensure xdg-pictures access is granted), \
-                     4) XDG_PICTURES_DIR pointing to an inaccessible location"
+                    "Failed to save recording"
                 );
-                Task::none()
+                self.show_error_notice(err)
+            }
+        }
+    }
+
+    /// Write the event subtitle sidecar of a finished recording
+    fn write_event_subtitles(
+        video_path: &str,
+        events: &crate::pipelines::video::RecordingEventLog,
+    ) {
+        use crate::pipelines::video::RecordingEvent;
+
+        let srt = events.to_srt(|event| match event {
+            RecordingEvent::QrCode(content) => {
+                crate::fl!("recording-event-qr", content = content.as_str())
             }
+            RecordingEvent::Marker(number) => {
+                crate::fl!("recording-event-marker", number = *number)
+            }
+        });
+        let path = crate::pipelines::video::event_subtitles::sidecar_path(std::path::Path::new(
+            video_path,
+        ));
+        tokio::task::spawn_blocking(move || match std::fs::write(&path, srt) {
+            Ok(()) => info!(path = %path.display(), "Saved event subtitles"),
+            Err(err) => warn!(path = %path.display(), %err, "Failed to save event subtitles"),
+        });
+    }
+
+    pub(crate) fn handle_add_recording_marker(&mut self) -> Task<cosmic::Action<Message>> {
+        let at = self.recording.elapsed(self.clock.now());
+        if let Some((_, events)) = self.recording_events.as_mut() {
+            let number = events.add_marker(at);
+            info!(number, at = ?at, "Recording marker added");
         }
+        Task::none()
     }
 
     pub(crate) fn handle_update_recording_duration(&mut self) -> Task<cosmic::Action<Message>> {
         if self.recording.is_recording() {
+            // Stop while the muxer still has room to finalize the file
+            let file_path = self.recording.file_path().map(std::path::PathBuf::from);
+            if let Some(path) = file_path
+                && !self.check_free_space(&path)
+            {
+                warn!("Storage almost full, stopping the recording");
+                return self.handle_toggle_recording();
+            }
             return Self::delay_task(1000, Message::UpdateRecordingDuration);
         }
         Task::none()
@@ -1271,15 +2171,27 @@ impl AppModel {
             || self.recording.is_recording()
             || self.burst_mode.is_active()
             || self.quick_record.is_recording()
+            || self.quick_record.is_bursting()
+            || self.burst_picker.is_some()
         {
             return Task::none();
         }
 
-        // If photo timer is counting down, abort it
-        if self.photo_timer_countdown.is_some() {
+        // If photo timer is counting down or a photo booth run is active,
+        // abort it
+        if self.photo_timer_countdown.is_some() || self.photo_booth.is_some() {
             return self.handle_abort_photo_timer();
         }
 
+        // The full format starts coming back while the shutter is down, for
+        // the photo or recording that follows
+        if self.config.adaptive_preview
+            && !self.is_current_camera_multistream()
+            && self.preview_format() != self.active_format
+        {
+            self.adaptive_preview.hold(self.clock.now());
+        }
+
         // Capture current frame for zero-shutter-lag photo
         let captured_frame = self.current_frame.clone();
 
@@ -1308,6 +2220,10 @@ impl AppModel {
                     return self.handle_abort_photo_timer();
                 }
 
+                if self.config.photo_booth {
+                    return self.start_photo_booth();
+                }
+
                 // In Photo mode with timer set, start countdown
                 // Keep button in pressed state until capture completes
                 if self.mode == CameraMode::Photo
@@ -1316,21 +2232,15 @@ impl AppModel {
                     let seconds = self.photo_timer_setting.seconds();
                     info!(seconds, "Starting photo timer countdown");
                     self.photo_timer_countdown = Some(seconds);
-                    self.photo_timer_tick_start = Some(std::time::Instant::now());
+                    self.photo_timer_tick_start = Some(self.clock.now());
                     return Self::delay_task(1000, Message::PhotoTimerTick);
                 }
 
                 // Screen flash (front camera) or hardware flash (back camera)
                 self.animate_capture_scale(1.0);
                 if self.flash.enabled && !self.flash.active {
-                    if self.use_hardware_flash() {
-                        info!("Flash enabled - turning on hardware flash before capture");
-                        self.turn_on_flash_hardware();
-                    } else {
-                        info!("Flash enabled - showing screen flash before capture");
-                        self.flash.active = true;
-                    }
-                    return Self::delay_task(1000, Message::FlashComplete);
+                    self.fire_flash();
+                    return Self::delay_task(FLASH_LEAD_MS, Message::FlashComplete);
                 }
 
                 // No timer or flash — use the zero-shutter-lag frame
@@ -1349,6 +2259,10 @@ impl AppModel {
                 self.update_idle_inhibit();
                 Task::none()
             }
+            QuickRecordState::Bursting { shots } => {
+                self.animate_capture_scale(1.0);
+                self.finish_rapid_burst(shots)
+            }
             QuickRecordState::Idle => {
                 self.animate_capture_scale(1.0);
                 Task::none()
@@ -1367,44 +2281,196 @@ impl AppModel {
         // Animate to recording scale
         self.animate_capture_scale(0.82);
 
+        if self.config.rapid_burst {
+            // The held frame becomes the first burst shot
+            let first = match std::mem::take(&mut self.quick_record) {
+                QuickRecordState::Pressed { captured_frame, .. } => captured_frame,
+                _ => None,
+            };
+            info!(
+                max_shots = self.config.rapid_burst_max_shots,
+                "Starting rapid burst"
+            );
+            self.quick_record = QuickRecordState::Bursting {
+                shots: first
+                    .map(|frame| vec![Arc::new(frame.to_copied())])
+                    .unwrap_or_default(),
+            };
+            return self.handle_rapid_burst_tick();
+        }
+
         // Discard the captured photo frame, start recording
         self.quick_record = QuickRecordState::Recording;
         self.start_quick_recording()
     }
 
-    /// Start quick-recording using the existing appsrc infrastructure.
-    /// Same as normal video recording but initiated from Photo mode.
-    fn start_quick_recording(&mut self) -> Task<cosmic::Action<Message>> {
-        if self
-            .available_cameras
-            .get(self.current_camera_index)
-            .is_none()
-        {
-            self.quick_record = crate::app::state::QuickRecordState::Idle;
-            self.animate_capture_scale(1.0);
+    /// Take the next rapid burst shot while the shutter is held. Stops at
+    /// the shot limit without waiting for the release.
+    pub(crate) fn handle_rapid_burst_tick(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::app::state::QuickRecordState;
+        use crate::pipelines::photo::rapid_burst::SHOT_INTERVAL_MS;
+
+        let max_shots = usize::from(self.config.rapid_burst_max_shots.max(1));
+        let current_frame = self.current_frame.clone();
+        let QuickRecordState::Bursting { shots } = &mut self.quick_record else {
             return Task::none();
+        };
+        // Skip the tick if the preview hasn't moved on since the last shot
+        if let Some(frame) = current_frame
+            && shots
+                .last()
+                .is_none_or(|last| last.captured_at != frame.captured_at)
+        {
+            // The preview keeps running, so don't hold the pipeline's buffer
+            shots.push(Arc::new(frame.to_copied()));
         }
-        if self.active_format.is_none() {
-            self.quick_record = crate::app::state::QuickRecordState::Idle;
-            self.animate_capture_scale(1.0);
-            return Task::none();
+        if shots.len() < max_shots {
+            return Self::delay_task(SHOT_INTERVAL_MS, Message::RapidBurstTick);
         }
 
-        let camera = &self.available_cameras[self.current_camera_index];
-        let format = self.active_format.as_ref().unwrap();
-
-        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S_%3f");
-        let filename = format!("VID_{}.mp4", timestamp);
-        let save_dir = crate::app::get_video_directory(&self.config.save_folder_name);
-        let output_path = save_dir.join(&filename);
+        let QuickRecordState::Bursting { shots } = std::mem::take(&mut self.quick_record) else {
+            return Task::none();
+        };
+        self.animate_capture_scale(1.0);
+        self.finish_rapid_burst(shots)
+    }
 
-        info!(
-            output = %output_path.display(),
-            "Starting quick-record (long-press in Photo mode)"
-        );
+    /// Save the burst shots as individual photos
+    fn finish_rapid_burst(
+        &mut self,
+        shots: Vec<Arc<crate::backends::camera::types::CameraFrame>>,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::pipelines::photo::rapid_burst::{RapidBurstConfig, save_rapid_burst};
 
-        let sensor_rotation = camera.rotation;
-        let framerate = format.framerate.map(|f| f.as_int()).unwrap_or(30);
+        if shots.is_empty() {
+            info!("Rapid burst ended without any shots");
+            return Task::none();
+        }
+        info!(shots = shots.len(), "Rapid burst finished, saving");
+        self.is_capturing = true;
+
+        let (rotation, orientation) = self.saved_rotation();
+        let config = RapidBurstConfig {
+            filter_type: self.selected_filter,
+            rotation,
+            mirror_horizontal: self.should_mirror_captures(),
+            encoding_format: self.config.photo_output_format.into(),
+            camera_metadata: crate::pipelines::photo::CameraMetadata {
+                orientation,
+                ..self.build_camera_metadata()
+            },
+            output_sharpening: self.config.output_sharpening,
+        };
+        let save_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+
+        let save_task = Task::perform(save_rapid_burst(shots, config, save_dir), |result| {
+            cosmic::Action::App(Message::RapidBurstSaved(result))
+        });
+        let animation_task = Self::delay_task(150, Message::ClearCaptureAnimation);
+        Task::batch([save_task, animation_task])
+    }
+
+    pub(crate) fn handle_rapid_burst_saved(
+        &mut self,
+        result: Result<Vec<crate::pipelines::photo::rapid_burst::BurstShot>, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        self.is_capturing = false;
+        match result {
+            Ok(shots) => {
+                info!(shots = shots.len(), "Rapid burst saved");
+                let picker = crate::app::burst_picker::BurstPicker::new(shots);
+                if let Some(path) = picker.selected_path() {
+                    self.last_media_path = Some(path.display().to_string());
+                }
+                self.burst_picker = Some(picker);
+                Task::done(cosmic::Action::App(Message::RefreshGalleryThumbnail))
+            }
+            Err(err) => {
+                error!(error = %err, "Failed to save rapid burst");
+                Task::none()
+            }
+        }
+    }
+
+    pub(crate) fn handle_select_burst_shot(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(picker) = self.burst_picker.as_mut()
+            && index < picker.shots.len()
+        {
+            picker.selected = index;
+        }
+        Task::none()
+    }
+
+    /// Keep the highlighted burst shot and delete the rest
+    pub(crate) fn handle_keep_burst_shot(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(picker) = self.burst_picker.take() else {
+            return Task::none();
+        };
+        if let Some(path) = picker.selected_path() {
+            self.last_media_path = Some(path.display().to_string());
+        }
+        let discarded = picker.discarded_paths();
+        info!(
+            kept = ?picker.selected_path(),
+            discarded = discarded.len(),
+            "Keeping one burst shot"
+        );
+        Task::perform(
+            async move {
+                for path in discarded {
+                    if let Err(e) = tokio::fs::remove_file(&path).await {
+                        warn!(path = %path.display(), error = %e, "Failed to delete burst shot");
+                    }
+                }
+            },
+            |_| cosmic::Action::App(Message::RefreshGalleryThumbnail),
+        )
+    }
+
+    /// Close the burst picker without deleting anything
+    pub(crate) fn handle_keep_all_burst_shots(&mut self) -> Task<cosmic::Action<Message>> {
+        self.burst_picker = None;
+        Task::none()
+    }
+
+    /// Start quick-recording using the existing appsrc infrastructure.
+    /// Same as normal video recording but initiated from Photo mode.
+    fn start_quick_recording(&mut self) -> Task<cosmic::Action<Message>> {
+        if self
+            .available_cameras
+            .get(self.current_camera_index)
+            .is_none()
+        {
+            self.quick_record = crate::app::state::QuickRecordState::Idle;
+            self.animate_capture_scale(1.0);
+            return Task::none();
+        }
+        if self.active_format.is_none() {
+            self.quick_record = crate::app::state::QuickRecordState::Idle;
+            self.animate_capture_scale(1.0);
+            return Task::none();
+        }
+
+        let format = self.active_format.as_ref().unwrap();
+
+        let output_path = self.new_video_path();
+        if !self.check_free_space(&output_path) {
+            warn!("Not enough storage space left to record");
+            self.quick_record = crate::app::state::QuickRecordState::Idle;
+            self.animate_capture_scale(1.0);
+            return Task::none();
+        }
+
+        info!(
+            output = %output_path.display(),
+            "Starting quick-record (long-press in Photo mode)"
+        );
+
+        let (sensor_rotation, orientation) = self.saved_rotation();
+        let framerate = format.framerate.map(|f| f.as_int()).unwrap_or(30);
 
         // Use viewfinder frame dimensions (not raw format dimensions)
         let (appsrc_width, appsrc_height) = self
@@ -1436,10 +2502,7 @@ impl AppModel {
             .get(self.current_video_encoder_index)
             .cloned();
 
-        let appsrc_bitrate = self
-            .config
-            .bitrate_preset
-            .bitrate_kbps(appsrc_width, appsrc_height);
+        let encoder_config = self.recording_encoder_config(appsrc_width, appsrc_height);
 
         self.start_appsrc_recording(AppsrcRecordingConfig {
             width: appsrc_width,
@@ -1448,10 +2511,12 @@ impl AppModel {
             format: format.clone(),
             output_path,
             sensor_rotation,
+            orientation,
             audio_device,
             audio_source_rate_hz,
             selected_encoder,
-            bitrate_kbps: appsrc_bitrate,
+            encoder_config,
+            screen_cast: None,
         })
     }
 
@@ -1468,10 +2533,12 @@ impl AppModel {
             return Task::none();
         };
 
-        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S_%3f");
-        let filename = format!("VID_{}.mp4", timestamp);
-        let save_dir = crate::app::get_video_directory(&self.config.save_folder_name);
-        let output_path = save_dir.join(&filename);
+        let output_path = self.new_video_path();
+        if !self.check_free_space(&output_path) {
+            warn!("Not enough storage space left to record");
+            self.recording = RecordingState::Idle;
+            return Task::none();
+        }
 
         info!(
             device = %camera.path,
@@ -1488,10 +2555,11 @@ impl AppModel {
             self.turn_on_flash_hardware();
         }
 
-        let sensor_rotation = camera.rotation;
+        let (sensor_rotation, orientation) = self.saved_rotation();
         let width = format.width;
         let height = format.height;
         let framerate = format.framerate.map(|f| f.as_int()).unwrap_or(30);
+        let screen_cast = self.screen_cast.take();
 
         // Only get audio device if audio recording is enabled in settings
         let selected_audio_device = self
@@ -1519,15 +2587,24 @@ impl AppModel {
 
         // For the appsrc pipeline, use the actual viewfinder frame dimensions
         // (not active_format, which may be the raw/Bayer stream resolution).
-        let (appsrc_width, appsrc_height) = self
-            .current_frame
-            .as_ref()
-            .map(|f| (f.width, f.height))
-            .unwrap_or((width, height));
-        let appsrc_bitrate = self
-            .config
-            .bitrate_preset
-            .bitrate_kbps(appsrc_width, appsrc_height);
+        // Screen recordings are as large as the (scaled) screen instead,
+        // and upright already.
+        let (appsrc_width, appsrc_height) = match &screen_cast {
+            // Not every compositor announces the stream size; frames of
+            // another size are letterboxed
+            Some(cast) => cast.recording_size().unwrap_or((1920, 1080)),
+            None => self
+                .current_frame
+                .as_ref()
+                .map(|f| (f.width, f.height))
+                .unwrap_or((width, height)),
+        };
+        let (sensor_rotation, orientation) = if screen_cast.is_some() {
+            Default::default()
+        } else {
+            (sensor_rotation, orientation)
+        };
+        let encoder_config = self.recording_encoder_config(appsrc_width, appsrc_height);
         self.start_appsrc_recording(AppsrcRecordingConfig {
             width: appsrc_width,
             height: appsrc_height,
@@ -1535,13 +2612,103 @@ impl AppModel {
             format: format.clone(),
             output_path,
             sensor_rotation,
+            orientation,
             audio_device,
             audio_source_rate_hz,
             selected_encoder,
-            bitrate_kbps: appsrc_bitrate,
+            encoder_config,
+            screen_cast,
         })
     }
 
+    /// Encoder settings for an appsrc recording of `width` × `height` frames
+    fn recording_encoder_config(
+        &self,
+        width: u32,
+        height: u32,
+    ) -> crate::pipelines::video::EncoderConfig {
+        use crate::pipelines::video::{AudioChannels, AudioQuality, EncoderConfig, VideoQuality};
+
+        // Use Low quality for appsrc path (x264 veryfast preset)
+        // to stay real-time on ARM devices.
+        EncoderConfig {
+            video_quality: VideoQuality::Low,
+            audio_quality: AudioQuality::High,
+            audio_channels: AudioChannels::Mono,
+            width,
+            height,
+            bitrate_override_kbps: Some(self.config.bitrate_preset.bitrate_kbps(width, height)),
+            container: self.config.video_container.container(),
+            intra_only: self.config.bitrate_preset.is_intra_only(),
+        }
+    }
+
+    /// Keep a recording pipeline pre-built for the current format and
+    /// encoder settings while Video mode is idle, so Record doesn't wait on
+    /// the encoder. Called for every preview frame; only talks to the
+    /// warm-up when something the pipeline depends on changed.
+    pub(crate) fn sync_recorder_warmup(&mut self) {
+        use crate::app::state::RecorderWarmupKey;
+        use crate::pipelines::video::warmup;
+
+        if self.mode != CameraMode::Video || self.recording.is_recording() {
+            if self.recorder_warmup.take().is_some() {
+                warmup::release();
+            }
+            return;
+        }
+        let (Some(format), Some(frame)) = (&self.active_format, &self.current_frame) else {
+            return;
+        };
+        let (rotation, orientation) = self.saved_rotation();
+        let key = RecorderWarmupKey {
+            encoder_index: self.current_video_encoder_index,
+            width: frame.width,
+            height: frame.height,
+            framerate: format.framerate,
+            bitrate_preset: self.config.bitrate_preset,
+            video_container: self.config.video_container,
+            rotation,
+            orientation,
+        };
+        if self.recorder_warmup == Some(key) {
+            return;
+        }
+        self.recorder_warmup = Some(key);
+
+        warmup::request(warmup::WarmupRequest {
+            encoder_info: self
+                .available_video_encoders
+                .get(key.encoder_index)
+                .cloned(),
+            encoder_config: self.recording_encoder_config(key.width, key.height),
+            width: key.width,
+            height: key.height,
+            framerate: key.framerate.map(|f| f.as_int()).unwrap_or(30),
+            rotation,
+            orientation,
+        });
+    }
+
+    /// Path for a new video recording, with the extension of the container
+    /// the selected encoder will write
+    fn new_video_path(&self) -> std::path::PathBuf {
+        use crate::media::encoders::video::{VideoCodec, resolve_container, resolve_pcm_container};
+
+        let codec = self
+            .available_video_encoders
+            .get(self.current_video_encoder_index)
+            .map_or(VideoCodec::H264, |e| e.codec);
+        let preferred = self.config.video_container.container();
+        let container = if self.config.bitrate_preset.is_intra_only() {
+            resolve_pcm_container(codec, preferred)
+        } else {
+            resolve_container(codec, preferred)
+        };
+        let save_dir = crate::app::get_video_directory(&self.config.save_folder_name);
+        crate::storage::capture_path(&save_dir, "VID", container.extension())
+    }
+
     /// Start recording using the appsrc pipeline (libcamera backend).
     ///
     /// Frames from the native capture thread are forwarded via an mpsc channel
@@ -1560,28 +2727,66 @@ impl AppModel {
             format,
             output_path,
             sensor_rotation,
+            orientation,
             audio_device,
             audio_source_rate_hz,
             selected_encoder,
-            bitrate_kbps,
+            encoder_config,
+            screen_cast,
         } = config;
-        let mirror_horizontal = self.should_mirror_captures();
+        let mirror_horizontal = screen_cast.is_none() && self.should_mirror_captures();
 
         // Determine pixel format for the appsrc pipeline
-        let pixel_format = self
-            .current_frame
-            .as_ref()
-            .map(|f| f.format)
-            .unwrap_or_else(|| {
-                // Fallback: parse from format string
-                PixelFormat::from_gst_format(&format.pixel_format).unwrap_or(PixelFormat::I420)
-            });
+        let pixel_format = if screen_cast.is_some() {
+            PixelFormat::RGBA
+        } else {
+            self.current_frame
+                .as_ref()
+                .map(|f| f.format)
+                .unwrap_or_else(|| {
+                    // Fallback: parse from format string
+                    PixelFormat::from_gst_format(&format.pixel_format).unwrap_or(PixelFormat::I420)
+                })
+        };
 
         // Check if we can use the VA-API JPEG zero-copy path:
         // - Camera outputs MJPEG
         // - A VA-API JPEG decoder is available that handles this camera's
         //   chroma subsampling (e.g. 4:2:0 → I420, 4:2:2 → Y42B)
         // - No sensor rotation needed (GPU JPEG decode → encoder is direct)
+        // - No picture-in-picture inset to composite, no stabilization, no
+        //   denoising and no timestamp stamp (frames never reach RGBA), and
+        //   no timecode (the JPEG pusher logs none)
+        //
+        // Screen recordings put the screen where the camera picture goes
+        // and the camera in the inset, where a second camera would be. The
+        // camera effects and filter are left off: they'd apply to the screen.
+        let screen_inset: crate::backends::camera::pip::SharedPipFrame = Default::default();
+        let (pip, stabilization, denoise) = if screen_cast.is_some() {
+            (
+                Some(crate::backends::camera::pip::PipOverlay::new(
+                    Arc::clone(&screen_inset),
+                    crate::config::DualCameraLayout::PictureInPicture,
+                    self.config.pip_corner,
+                )),
+                crate::config::VideoStabilization::Off,
+                crate::pipelines::video::DenoiseSettings::default(),
+            )
+        } else {
+            (
+                self.pip_overlay(),
+                self.config.video_stabilization,
+                self.video_denoise_settings(),
+            )
+        };
+        let timecode = self.config.recording_timecode;
+        let timestamp_overlay = self.config.recording_timestamp_overlay;
+        // A screen is never anamorphic
+        let desqueeze = if self.config.desqueeze_recordings && screen_cast.is_none() {
+            self.config.anamorphic_desqueeze.factor()
+        } else {
+            1.0
+        };
         let is_mjpeg = format.pixel_format == "MJPEG" || format.pixel_format.contains("MJPG");
         let decoded_yuv_format = self
             .current_frame
@@ -1595,13 +2800,39 @@ impl AppModel {
         };
         let use_jpeg_pipeline = is_mjpeg
             && va_jpeg_dec.is_some()
-            && sensor_rotation == crate::backends::camera::types::SensorRotation::None;
+            && sensor_rotation == crate::backends::camera::types::SensorRotation::None
+            && pip.is_none()
+            && stabilization == crate::config::VideoStabilization::Off
+            && !denoise.is_enabled()
+            && !timecode
+            && !timestamp_overlay
+            && desqueeze == 1.0;
+        // HDR frames go to the encoder untouched when nothing needs to be
+        // drawn on them; the filtered pipeline would record the SDR tone map
+        let hdr_transfer = crate::shaders::frame_transfer(pixel_format);
+        let use_hdr_pipeline = hdr_transfer.is_hdr()
+            && screen_cast.is_none()
+            && !self.current_frame_is_file_source
+            && sensor_rotation == crate::backends::camera::types::SensorRotation::None
+            && !mirror_horizontal
+            && pip.is_none()
+            && stabilization == crate::config::VideoStabilization::Off
+            && !denoise.is_enabled()
+            && !timecode
+            && !timestamp_overlay
+            && desqueeze == 1.0;
 
         if use_jpeg_pipeline {
             info!(
                 va_jpeg_dec = ?va_jpeg_dec,
                 "Using VA-API JPEG zero-copy recording pipeline"
             );
+        } else if use_hdr_pipeline {
+            info!(
+                pixel_format = ?pixel_format,
+                transfer = ?hdr_transfer,
+                "Using HDR passthrough recording pipeline"
+            );
         } else {
             info!(
                 pixel_format = ?pixel_format,
@@ -1618,10 +2849,36 @@ impl AppModel {
 
         let path_for_message = output_path.display().to_string();
 
-        // When a file source is active (--preview-source), there is no capture
-        // thread to forward frames. Spawn a task that pushes the static frame
-        // into the recording channel at the configured framerate.
-        if self.current_frame_is_file_source {
+        // A screen recording is driven by the screen's frames; the camera's
+        // only refresh the inset.
+        let mut screen_capture = None;
+        if let Some(cast) = screen_cast {
+            use crate::backends::screen_cast::{ScreenCapture, feed_camera_inset};
+
+            match ScreenCapture::start(cast, (width, height), frame_tx) {
+                Ok(capture) => screen_capture = Some(capture),
+                Err(e) => {
+                    error!(error = %e, "Failed to start screen capture");
+                    self.animate_capture_scale(1.0);
+                    return Task::none();
+                }
+            }
+            let (camera_tx, camera_rx) = tokio::sync::mpsc::channel(2);
+            tokio::spawn(feed_camera_inset(
+                camera_rx,
+                screen_inset,
+                self.capture_rotation(),
+                self.should_mirror_captures(),
+            ));
+            if let Some(ref manager) = self.backend_manager {
+                manager.set_recording_sender(Some(camera_tx));
+                manager.set_jpeg_recording_mode(false);
+            }
+        } else if self.current_frame_is_file_source {
+            // When a file source is active (--preview-source), there is no
+            // capture thread to forward frames. Spawn a task that pushes the
+            // static frame into the recording channel at the configured
+            // framerate.
             if let Some(ref frame) = self.current_frame {
                 let frame = Arc::new(frame.as_ref().clone());
                 let tx = frame_tx;
@@ -1665,12 +2922,25 @@ impl AppModel {
             path_for_message.clone(),
             stop_tx,
             Some(audio_levels.clone()),
+            self.clock.now(),
         );
+        self.recording_events = self
+            .config
+            .record_event_subtitles
+            .then(|| (session, crate::pipelines::video::RecordingEventLog::new()));
 
         let backend_manager = self.backend_manager.clone();
+        let live_filter = if screen_capture.is_some() {
+            Arc::new(std::sync::atomic::AtomicU32::new(0))
+        } else {
+            self.recording_filter_code.clone()
+        };
         let va_jpeg_dec_name = va_jpeg_dec.map(|s| s.to_string());
-        let live_filter = self.recording_filter_code.clone();
         let record_audio = self.config.record_audio;
+        let audio_processing = self.audio_processing();
+        let record_system_audio = self.config.record_system_audio;
+        self.sync_audio_offset();
+        let audio_sync_offset = self.audio_sync_offset.clone();
 
         let recording_task = Task::perform(
             async move {
@@ -1687,19 +2957,7 @@ impl AppModel {
                     let _guard = rt_handle.enter();
 
                     use crate::pipelines::video::{
-                        AppsrcRecorderConfig, AudioChannels, AudioQuality, EncoderConfig,
-                        RecorderConfig, VideoQuality, VideoRecorder,
-                    };
-
-                    // Use Low quality for appsrc path (x264 veryfast preset)
-                    // to stay real-time on ARM devices.
-                    let config = EncoderConfig {
-                        video_quality: VideoQuality::Low,
-                        audio_quality: AudioQuality::High,
-                        audio_channels: AudioChannels::Mono,
-                        width,
-                        height,
-                        bitrate_override_kbps: Some(bitrate_kbps),
+                        AppsrcRecorderConfig, RecorderConfig, VideoRecorder,
                     };
 
                     let make_appsrc_config =
@@ -1710,7 +2968,7 @@ impl AppModel {
                                     height,
                                     framerate,
                                     output_path: output_path.clone(),
-                                    encoder_config: config.clone(),
+                                    encoder_config: encoder_config.clone(),
                                     // Honor the user's `record_audio` preference directly. With
                                     // an empty device list (e.g. PipeWire not enumerating, fallback
                                     // also empty), `audio_device` is None — let `pulsesrc` open the
@@ -1718,13 +2976,23 @@ impl AppModel {
                                     enable_audio: record_audio,
                                     audio_device: audio_device.as_deref(),
                                     audio_source_rate_hz,
+                                    audio_processing,
+                                    system_audio: record_system_audio,
                                     encoder_info: selected_encoder.as_ref(),
                                     rotation: sensor_rotation,
                                     mirror_horizontal,
+                                    orientation,
                                     audio_levels,
+                                    audio_sync_offset: audio_sync_offset.clone(),
                                 },
                                 pixel_format,
                                 live_filter_code: live_filter.clone(),
+                                pip: pip.clone(),
+                                stabilization,
+                                denoise,
+                                timecode,
+                                timestamp_overlay,
+                                desqueeze,
                             }
                         };
 
@@ -1759,18 +3027,38 @@ impl AppModel {
                                 )?
                             }
                         }
+                    } else if use_hdr_pipeline {
+                        match VideoRecorder::new_from_appsrc_hdr(
+                            make_appsrc_config(audio_levels.clone()),
+                            hdr_transfer,
+                            frame_rx,
+                        ) {
+                            Ok(r) => r,
+                            Err(e) => {
+                                warn!(
+                                    error = %e,
+                                    "HDR pipeline failed, falling back to legacy"
+                                );
+                                // The old rx was consumed, as in the JPEG fallback
+                                let (fallback_tx, fallback_rx) = tokio::sync::mpsc::channel(15);
+                                if let Some(ref manager) = backend_manager_inner {
+                                    manager.set_recording_sender(Some(fallback_tx));
+                                }
+                                VideoRecorder::new_from_appsrc(
+                                    make_appsrc_config(audio_levels),
+                                    fallback_rx,
+                                )?
+                            }
+                        }
                     } else {
                         VideoRecorder::new_from_appsrc(make_appsrc_config(audio_levels), frame_rx)?
                     };
 
                     recorder.start()?;
-                    let path = output_path.display().to_string();
-                    Ok::<_, String>((recorder, path))
+                    Ok::<_, AppError>(recorder)
                 })
                 .await
-                .unwrap_or_else(|e| Err(format!("Task join error: {}", e)))?;
-
-                let (recorder, path) = recorder;
+                .unwrap_or_else(|e| Err(AppError::Other(format!("Task join error: {}", e))))?;
 
                 // Wait for stop signal
                 let _ = stop_rx.await;
@@ -1781,15 +3069,19 @@ impl AppModel {
                     manager.set_recording_sender(None);
                     manager.set_jpeg_recording_mode(false);
                 }
+                // A screen capture holds the recorder's sender instead
+                drop(screen_capture);
 
                 // Give a brief moment for EOS to propagate before stopping the pipeline.
                 tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
 
+                // The recorder may have switched container (e.g. WebM for AV1),
+                // so report the path it actually wrote
                 tokio::task::spawn_blocking(move || {
-                    recorder.stop().map(|_| path).map_err(|e| e.to_string())
+                    recorder.stop().map(|path| path.display().to_string())
                 })
                 .await
-                .unwrap_or_else(|e| Err(format!("Task join error: {}", e)))
+                .unwrap_or_else(|e| Err(AppError::Other(format!("Task join error: {}", e))))
             },
             move |result| cosmic::Action::App(Message::RecordingStopped { session, result }),
         );
@@ -1839,7 +3131,16 @@ impl AppModel {
         self.camera_stream_restart_counter = self.camera_stream_restart_counter.wrapping_add(1);
         self.camera_cancel_flag = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
 
-        match result {
+        // The instant preview has served its purpose whatever the outcome;
+        // the thumbnail is refreshed once it is gone
+        let preview_task = match self.burst_preview.take() {
+            Some(pending) => Task::perform(pending.discard(), |_| {
+                cosmic::Action::App(Message::RefreshGalleryThumbnail)
+            }),
+            None => Task::none(),
+        };
+
+        let result_task = match result {
             Ok(path) => {
                 info!(path, "Burst mode capture complete");
                 self.burst_mode.complete();
@@ -1853,6 +3154,11 @@ impl AppModel {
 
                 Task::batch([saved_task, reset_task])
             }
+            Err(e) if e == crate::pipelines::photo::burst_mode::CANCELLED => {
+                info!("Burst mode processing cancelled");
+                self.burst_mode.reset();
+                Task::none()
+            }
             Err(e) => {
                 error!(error = %e, "Burst mode capture failed");
                 self.burst_mode.error();
@@ -1860,7 +3166,50 @@ impl AppModel {
                 // Reset after showing error
                 Self::delay_task(BURST_MODE_ERROR_DISPLAY_MS, Message::ResetBurstModeState)
             }
+        };
+        Task::batch([preview_task, result_task])
+    }
+
+    // =========================================================================
+    // Storage Space
+    // =========================================================================
+
+    /// Check the space left on the filesystem a capture is saved to (`path`
+    /// or its nearest existing ancestor) and update the storage warning.
+    ///
+    /// Returns `false` when it is too full for a new capture, or for a
+    /// running recording to go on.
+    pub(crate) fn check_free_space(&mut self, path: &std::path::Path) -> bool {
+        use crate::storage::{SpaceLevel, available_space, space_level};
+
+        // Unknown (statvfs failed): don't stand in the way
+        let Some(free) = available_space(path) else {
+            return true;
+        };
+        let warn_at = u64::from(self.config.low_space_warning_mb) * 1_000_000;
+        let level = space_level(free, warn_at);
+        if level == SpaceLevel::Plenty {
+            self.storage_warning = None;
+            self.storage_warning_dismissed = false;
+            return true;
         }
+
+        // A dismissed warning comes back when the disk gets fuller
+        if self.storage_warning.map(|(shown, _)| shown) != Some(level) {
+            self.storage_warning_dismissed = false;
+            warn!(
+                free = %crate::storage::format_size(free),
+                ?level,
+                "Low storage space"
+            );
+        }
+        self.storage_warning = Some((level, free));
+        level != SpaceLevel::Full
+    }
+
+    pub(crate) fn handle_dismiss_storage_warning(&mut self) -> Task<cosmic::Action<Message>> {
+        self.storage_warning_dismissed = true;
+        Task::none()
     }
 
     // =========================================================================
@@ -1946,7 +3295,7 @@ impl AppModel {
         let (frame_tx, frame_rx) = tokio::sync::mpsc::unbounded_channel();
 
         self.timelapse = TimelapseState::Running {
-            start_time: std::time::Instant::now(),
+            start_time: self.clock.now(),
             shots_taken: 0,
             interval_ms,
             frame_sender: frame_tx,
@@ -1965,7 +3314,7 @@ impl AppModel {
             .unwrap_or((1920, 1080));
         let bitrate_kbps = Some(self.config.bitrate_preset.bitrate_kbps(w, h));
         let live_filter_code = Arc::clone(&self.recording_filter_code);
-        let rotation = self.current_camera_rotation();
+        let rotation = self.capture_rotation();
         let mirror_horizontal = self.should_mirror_captures();
 
         // Spawn the encoder task — it runs until the channel is closed
@@ -2019,7 +3368,7 @@ impl AppModel {
         let shots = self.timelapse.shots_taken();
         let start_time = match &self.timelapse {
             TimelapseState::Running { start_time, .. } => *start_time,
-            _ => std::time::Instant::now(),
+            _ => self.clock.now(),
         };
 
         // Transition to Finalising — the sender is dropped, closing the channel
@@ -2076,6 +3425,106 @@ impl AppModel {
         Self::delay_task(interval_ms, Message::TimelapseTick)
     }
 
+    // =========================================================================
+    // Animated Clip Handlers
+    // =========================================================================
+
+    /// Start a GIF/WebP clip in place of a video recording. Preview frames
+    /// are sampled at the clip frame rate into an export task, which writes
+    /// the file once the last frame has been sent.
+    fn start_animated_clip(&mut self, format: AnimationFormat) -> Task<cosmic::Action<Message>> {
+        let fps = u32::from(self.config.animated_clip_fps.max(1));
+        let total_frames = fps * u32::from(self.config.animated_clip_seconds.max(1));
+        info!(?format, fps, total_frames, "Starting animated clip");
+        self.animate_capture_scale(0.82);
+
+        let (frame_tx, frame_rx) = tokio::sync::mpsc::unbounded_channel();
+        self.animated_clip = AnimatedClipState::Capturing {
+            format,
+            frames_sent: 0,
+            total_frames,
+            frame_sender: frame_tx,
+        };
+
+        let folder_name = self.config.save_folder_name.clone();
+        let options = AnimationOptions {
+            fps,
+            loop_forever: self.config.animated_clip_loop,
+        };
+        let live_filter_code = Arc::clone(&self.recording_filter_code);
+        let rotation = self.capture_rotation();
+        let mirror_horizontal = self.should_mirror_captures();
+
+        let export_task = Task::perform(
+            async move {
+                let video_dir = crate::app::get_video_directory(&folder_name);
+                if let Err(e) = std::fs::create_dir_all(&video_dir) {
+                    return Err(format!("Failed to create video directory: {e}"));
+                }
+                let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S_%3f");
+                let output_base = video_dir.join(format!("clip_{timestamp}"));
+                crate::pipelines::video::animated_clip::run_clip_capture(
+                    frame_rx,
+                    output_base,
+                    format,
+                    options,
+                    live_filter_code,
+                    rotation,
+                    mirror_horizontal,
+                )
+                .await
+            },
+            |result| cosmic::Action::App(Message::AnimatedClipSaved(result)),
+        );
+
+        self.animated_clip_send_current_frame();
+        let tick_task = Self::delay_task(1000 / u64::from(fps), Message::AnimatedClipTick);
+        Task::batch([export_task, tick_task])
+    }
+
+    /// Send the current preview frame to the clip export task
+    fn animated_clip_send_current_frame(&mut self) {
+        if let Some(frame) = &self.current_frame {
+            self.animated_clip.send_frame(Arc::clone(frame));
+        }
+        if !self.animated_clip.is_capturing() {
+            self.animate_capture_scale(1.0);
+        }
+    }
+
+    pub(crate) fn handle_animated_clip_tick(&mut self) -> Task<cosmic::Action<Message>> {
+        if !self.animated_clip.is_capturing() {
+            return Task::none();
+        }
+        self.animated_clip_send_current_frame();
+        if !self.animated_clip.is_capturing() {
+            return Task::none();
+        }
+        let fps = u64::from(self.config.animated_clip_fps.max(1));
+        Self::delay_task(1000 / fps, Message::AnimatedClipTick)
+    }
+
+    pub(crate) fn handle_animated_clip_saved(
+        &mut self,
+        result: Result<String, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        if self.animated_clip.is_capturing() {
+            self.animate_capture_scale(1.0);
+        }
+        self.animated_clip = AnimatedClipState::Idle;
+        match result {
+            Ok(path) => {
+                info!(path = %path, "Animated clip saved");
+                self.last_media_path = Some(path);
+                Task::done(cosmic::Action::App(Message::RefreshGalleryThumbnail))
+            }
+            Err(e) => {
+                error!(error = %e, "Animated clip export failed");
+                Task::none()
+            }
+        }
+    }
+
     pub(crate) fn handle_set_timelapse_interval(
         &mut self,
         index: usize,
@@ -2091,19 +3540,1233 @@ impl AppModel {
         }
         Task::none()
     }
-}
 
-/// Call org.freedesktop.ScreenSaver.Inhibit to prevent idle/sleep.
-/// Returns a guard that keeps the D-Bus connection alive (required by cosmic-idle).
-fn screensaver_inhibit() -> Result<crate::app::state::IdleInhibitGuard, String> {
-    let conn = zbus::blocking::Connection::session()
-        .map_err(|e| format!("D-Bus session connection: {e}"))?;
-    let cookie: u32 = conn
-        .call_method(
-            Some("org.freedesktop.ScreenSaver"),
-            "/org/freedesktop/ScreenSaver",
-            Some("org.freedesktop.ScreenSaver"),
-            "Inhibit",
+    // =========================================================================
+    // Still Extraction Handlers
+    // =========================================================================
+
+    pub(crate) fn handle_set_still_extraction_interval(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+        // Index 0 is "scene changes", the rest follow `TimelapseInterval::ALL`
+        let interval = match index.checked_sub(1) {
+            None => None,
+            Some(i) => match crate::config::TimelapseInterval::ALL.get(i) {
+                Some(&interval) => Some(interval),
+                None => return Task::none(),
+            },
+        };
+        self.config.still_extraction_interval = interval;
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save still extraction interval");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_extract_stills(&self) -> Task<cosmic::Action<Message>> {
+        if self.still_extraction.is_running() {
+            return Task::none();
+        }
+        info!("Opening file picker for still extraction");
+
+        let video_dir = crate::app::get_video_directory(&self.config.save_folder_name);
+        Task::perform(
+            async move {
+                use rfd::AsyncFileDialog;
+
+                AsyncFileDialog::new()
+                    .set_directory(video_dir)
+                    .add_filter(
+                        crate::fl!("still-extraction-file-filter-name"),
+                        crate::constants::file_formats::VIDEO_EXTENSIONS,
+                    )
+                    .pick_file()
+                    .await
+                    .map(|file| file.path().to_path_buf())
+            },
+            |path| cosmic::Action::App(Message::ExtractStillsFrom(path)),
+        )
+    }
+
+    pub(crate) fn handle_extract_stills_from(
+        &mut self,
+        source: Option<std::path::PathBuf>,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::pipelines::photo::EncodingFormat;
+        use crate::pipelines::video::{ExtractionControl, StillExtractionConfig, StillSelection};
+
+        let Some(source) = source else {
+            return Task::none();
+        };
+        if self.still_extraction.is_running() {
+            return Task::none();
+        }
+
+        let selection = match self.config.still_extraction_interval {
+            Some(interval) => {
+                StillSelection::Interval(std::time::Duration::from_millis(interval.millis()))
+            }
+            None => StillSelection::SceneChange,
+        };
+        // Video frames carry no raw sensor data, so DNG falls back to JPEG
+        let encoding_format = match self.config.photo_output_format.into() {
+            EncodingFormat::Dng => EncodingFormat::Jpeg,
+            format => format,
+        };
+        let config = StillExtractionConfig {
+            selection,
+            output_dir: crate::app::get_photo_directory(&self.config.save_folder_name),
+            encoding_format,
+        };
+        let control = ExtractionControl::default();
+        self.still_extraction = crate::app::state::StillExtractionState::Running {
+            source: source.clone(),
+            control: control.clone(),
+        };
+
+        Task::batch([
+            Task::perform(
+                crate::pipelines::video::extract_stills(source, config, control),
+                |result| cosmic::Action::App(Message::StillExtractionFinished(result)),
+            ),
+            Self::delay_task(500, Message::StillExtractionTick),
+        ])
+    }
+
+    pub(crate) fn handle_still_extraction_tick(&self) -> Task<cosmic::Action<Message>> {
+        // Nothing to do but redraw the progress while the job runs
+        if self.still_extraction.is_running() {
+            return Self::delay_task(500, Message::StillExtractionTick);
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_cancel_still_extraction(&self) -> Task<cosmic::Action<Message>> {
+        if let crate::app::state::StillExtractionState::Running { control, .. } =
+            &self.still_extraction
+        {
+            info!("Cancelling still extraction");
+            control.cancel();
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_still_extraction_finished(
+        &mut self,
+        result: Result<Vec<std::path::PathBuf>, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        match result {
+            Ok(paths) => {
+                info!(count = paths.len(), "Stills extracted");
+                self.still_extraction =
+                    crate::app::state::StillExtractionState::Finished(Ok(paths.len()));
+                if let Some(last) = paths.last() {
+                    self.last_media_path = Some(last.display().to_string());
+                    return Task::done(cosmic::Action::App(Message::RefreshGalleryThumbnail));
+                }
+                Task::none()
+            }
+            Err(err) => {
+                error!(error = %err, "Still extraction failed");
+                self.still_extraction = crate::app::state::StillExtractionState::Finished(Err(err));
+                Task::none()
+            }
+        }
+    }
+
+    // =========================================================================
+    // Post-Capture Stabilization Handlers
+    // =========================================================================
+
+    pub(crate) fn handle_stabilize_video(&self) -> Task<cosmic::Action<Message>> {
+        if self.post_stabilization.is_running() {
+            return Task::none();
+        }
+        info!("Opening file picker for post-capture stabilization");
+
+        let video_dir = crate::app::get_video_directory(&self.config.save_folder_name);
+        Task::perform(
+            async move {
+                use rfd::AsyncFileDialog;
+
+                AsyncFileDialog::new()
+                    .set_directory(video_dir)
+                    .add_filter(
+                        crate::fl!("still-extraction-file-filter-name"),
+                        crate::constants::file_formats::VIDEO_EXTENSIONS,
+                    )
+                    .pick_file()
+                    .await
+                    .map(|file| file.path().to_path_buf())
+            },
+            |path| cosmic::Action::App(Message::StabilizeVideoFrom(path)),
+        )
+    }
+
+    /// Write a stabilized copy next to the video, at the recording
+    /// stabilization strength (Medium while recording stabilization is off)
+    pub(crate) fn handle_stabilize_video_from(
+        &mut self,
+        source: Option<std::path::PathBuf>,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::VideoStabilization;
+        use crate::pipelines::video::{ExtractionControl, PostStabilizationConfig, VideoQuality};
+
+        let Some(source) = source else {
+            return Task::none();
+        };
+        if self.post_stabilization.is_running() {
+            return Task::none();
+        }
+
+        let strength = match self.config.video_stabilization {
+            VideoStabilization::Off => VideoStabilization::Medium,
+            strength => strength,
+        };
+        let output_dir = source
+            .parent()
+            .map(std::path::Path::to_path_buf)
+            .unwrap_or_else(|| crate::app::get_video_directory(&self.config.save_folder_name));
+        let config = PostStabilizationConfig {
+            strength,
+            output_dir,
+            video_quality: VideoQuality::High,
+        };
+        let control = ExtractionControl::default();
+        self.post_stabilization = crate::app::state::PostStabilizationState::Running {
+            source: source.clone(),
+            control: control.clone(),
+        };
+
+        Task::batch([
+            Task::perform(
+                crate::pipelines::video::stabilize_video(source, config, control),
+                |result| cosmic::Action::App(Message::PostStabilizationFinished(result)),
+            ),
+            Self::delay_task(500, Message::PostStabilizationTick),
+        ])
+    }
+
+    pub(crate) fn handle_post_stabilization_tick(&self) -> Task<cosmic::Action<Message>> {
+        // Nothing to do but redraw the progress while the job runs
+        if self.post_stabilization.is_running() {
+            return Self::delay_task(500, Message::PostStabilizationTick);
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_cancel_post_stabilization(&self) -> Task<cosmic::Action<Message>> {
+        if let crate::app::state::PostStabilizationState::Running { control, .. } =
+            &self.post_stabilization
+        {
+            info!("Cancelling post-capture stabilization");
+            control.cancel();
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_post_stabilization_finished(
+        &mut self,
+        result: Result<std::path::PathBuf, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::app::state::PostStabilizationState;
+
+        match result {
+            Ok(path) => {
+                info!(path = %path.display(), "Stabilized copy saved");
+                self.last_media_path = Some(path.display().to_string());
+                self.post_stabilization = PostStabilizationState::Finished(Ok(path));
+                Task::done(cosmic::Action::App(Message::RefreshGalleryThumbnail))
+            }
+            Err(err) => {
+                error!(error = %err, "Post-capture stabilization failed");
+                self.post_stabilization = PostStabilizationState::Finished(Err(err));
+                Task::none()
+            }
+        }
+    }
+
+    // =========================================================================
+    // Document Scanning Handlers
+    // =========================================================================
+
+    /// Whether frames should be checked for a new page: auto-capture is on
+    /// and Document mode is idle
+    pub(crate) fn document_auto_capture_armed(&self) -> bool {
+        self.config.document_auto_capture
+            && self.mode == CameraMode::Document
+            && !self.document.is_busy()
+            && !self.transition_state.ui_disabled
+    }
+
+    pub(crate) fn handle_capture_document_page(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::app::state::DocumentPage;
+        use crate::pipelines::photo::{
+            EncodingFormat, PhotoEncoder, PostProcessingConfig, PostProcessor,
+        };
+
+        if self.mode != CameraMode::Document || self.document.is_busy() {
+            return Task::none();
+        }
+        let Some(frame) = &self.current_frame else {
+            info!("No frame available to scan");
+            return Task::none();
+        };
+        // Copy frame for background task - mapped buffers become invalid when pipeline stops
+        let frame = Arc::new(frame.to_copied());
+
+        self.document.capturing = true;
+        self.document.feed.page_captured();
+        self.haptic_tap();
+        self.animate_capture_scale(0.82);
+
+        // Pages are never mirrored: a selfie-mirrored page would be unreadable
+        let config = PostProcessingConfig {
+            filter_type: self.selected_filter,
+            zoom_level: self.zoom_level,
+            rotation: self.capture_rotation(),
+            output_sharpening: self.config.output_sharpening,
+            ..Default::default()
+        };
+        let page_task = Task::perform(
+            async move {
+                let processed = PostProcessor::new(config).process(frame).await?;
+                let mut encoder = PhotoEncoder::new();
+                encoder.set_format(EncodingFormat::Jpeg);
+                let encoded = encoder.encode(processed).await?;
+                Ok(DocumentPage {
+                    jpeg: encoded.data,
+                    width: encoded.width,
+                    height: encoded.height,
+                })
+            },
+            |result| cosmic::Action::App(Message::DocumentPageCaptured(result)),
+        );
+
+        let animation_task = Self::delay_task(150, Message::ClearCaptureAnimation);
+        Task::batch([page_task, animation_task])
+    }
+
+    pub(crate) fn handle_document_page_captured(
+        &mut self,
+        result: Result<crate::app::state::DocumentPage, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        self.document.capturing = false;
+        match result {
+            Ok(page) => {
+                self.document.pages.push(page);
+                info!(pages = self.document.pages.len(), "Document page captured");
+            }
+            Err(err) => error!(error = %err, "Failed to capture document page"),
+        }
+        Task::none()
+    }
+
+    /// Capture the page in view once auto-capture sees it has settled
+    pub(crate) fn handle_page_signature_updated(
+        &mut self,
+        signature: Option<crate::app::frame_processor::PageSignature>,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(signature) = signature else {
+            return Task::none();
+        };
+        if !self.document_auto_capture_armed() {
+            return Task::none();
+        }
+
+        if self.document.feed.observe(signature, self.clock.now()) {
+            info!("New page settled - capturing");
+            return self.handle_capture_document_page();
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_document_auto_capture(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        self.config.document_auto_capture = !self.config.document_auto_capture;
+        info!(
+            enabled = self.config.document_auto_capture,
+            "Document auto-capture toggled"
+        );
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save document auto-capture setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_save_document(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.document.pages.is_empty() || self.document.is_busy() {
+            return Task::none();
+        }
+        self.document.saving = true;
+
+        // The pages stay in the session until the PDF is on disk
+        let pages = self.document.pages.clone();
+        let save_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+        let ocr_language = self.searchable_pdf_language();
+        info!(pages = pages.len(), "Saving document");
+
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    write_pdf(&pages, &save_dir, "DOC", ocr_language.as_deref())
+                })
+                .await
+                .map_err(|e| format!("Document save task error: {}", e))?
+            },
+            |result| cosmic::Action::App(Message::DocumentSaved(result)),
+        )
+    }
+
+    pub(crate) fn handle_document_saved(
+        &mut self,
+        result: Result<String, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        self.document.saving = false;
+        match result {
+            Ok(path) => {
+                info!(path = %path, pages = self.document.pages.len(), "Document saved");
+                self.document.pages.clear();
+                self.document.feed.reset();
+                // The gallery button reveals the PDF in the file manager
+                self.last_media_path = Some(path);
+            }
+            // The pages are kept so saving can be retried
+            Err(err) => error!(error = %err, "Failed to save document"),
+        }
+        Task::none()
+    }
+
+    /// Tesseract language for the text layer of saved PDFs, or `None` for
+    /// image-only PDFs
+    fn searchable_pdf_language(&self) -> Option<String> {
+        (crate::media::ocr::AVAILABLE && self.config.searchable_pdf)
+            .then(|| self.config.ocr_language.clone())
+    }
+
+    pub(crate) fn handle_toggle_searchable_pdf(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        self.config.searchable_pdf = !self.config.searchable_pdf;
+        info!(
+            enabled = self.config.searchable_pdf,
+            "Searchable PDF toggled"
+        );
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save searchable PDF setting");
+        }
+        Task::none()
+    }
+
+    /// Read the text on the scanned pages and put it on the clipboard
+    pub(crate) fn handle_copy_document_text(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.document.pages.is_empty() || self.document.is_busy() {
+            return Task::none();
+        }
+        self.document.reading_text = true;
+
+        let pages = self.document.pages.clone();
+        let language = self.config.ocr_language.clone();
+        info!(pages = pages.len(), "Reading document text");
+
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    let texts = pages
+                        .iter()
+                        .map(|page| {
+                            crate::media::ocr::recognize(&page.jpeg, &language)
+                                .map(|ocr| ocr.text())
+                        })
+                        .collect::<Result<Vec<_>, String>>()?;
+                    // Pages are separated like paragraphs, with a form feed
+                    // for editors that paginate
+                    Ok(texts.join("\n\x0c\n"))
+                })
+                .await
+                .map_err(|e| format!("Text recognition task error: {}", e))?
+            },
+            |result| cosmic::Action::App(Message::DocumentTextRecognized(result)),
+        )
+    }
+
+    pub(crate) fn handle_document_text_recognized(
+        &mut self,
+        result: Result<String, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        self.document.reading_text = false;
+        match result {
+            Ok(text) if text.trim().is_empty() => {
+                info!("No text found on the scanned pages");
+                Task::none()
+            }
+            Ok(text) => {
+                info!(chars = text.chars().count(), "Copying document text");
+                cosmic::iced::clipboard::write(text).map(|_: ()| cosmic::Action::App(Message::Noop))
+            }
+            Err(err) => {
+                error!(error = %err, "Failed to read document text");
+                Task::none()
+            }
+        }
+    }
+
+    pub(crate) fn handle_discard_document(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.document.saving {
+            return Task::none();
+        }
+        info!(
+            pages = self.document.pages.len(),
+            "Discarding scanned document"
+        );
+        self.document.pages.clear();
+        self.document.feed.reset();
+        Task::none()
+    }
+
+    // =========================================================================
+    // Whiteboard Handlers
+    // =========================================================================
+
+    /// Whether frames should be checked for the board: Whiteboard mode is
+    /// idle. Runs with auto-snapshot off too, for the board outline.
+    pub(crate) fn whiteboard_detection_armed(&self) -> bool {
+        self.mode == CameraMode::Whiteboard
+            && !self.whiteboard.is_busy()
+            && !self.transition_state.ui_disabled
+    }
+
+    /// Board corners locked for the current camera
+    pub(crate) fn whiteboard_keystone(&self) -> Option<crate::media::whiteboard::BoardQuad> {
+        let camera = self.available_cameras.get(self.current_camera_index)?;
+        self.config
+            .whiteboard_keystones
+            .get(&camera.path)
+            .map(|&keystone| keystone.into())
+    }
+
+    pub(crate) fn handle_capture_whiteboard(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::app::state::{DocumentPage, WhiteboardSnapshot};
+        use crate::config::OutputSharpening;
+        use crate::media::whiteboard::{self, BoardQuad};
+        use crate::pipelines::photo::{PostProcessingConfig, PostProcessor};
+
+        if self.mode != CameraMode::Whiteboard || self.whiteboard.is_busy() {
+            return Task::none();
+        }
+        let Some(frame) = &self.current_frame else {
+            info!("No frame available to snapshot");
+            return Task::none();
+        };
+        // Copy frame for background task - mapped buffers become invalid when pipeline stops
+        let frame = Arc::new(frame.to_copied());
+
+        self.whiteboard.capturing = true;
+        self.whiteboard.feed.snapshot_taken();
+        self.haptic_tap();
+        self.animate_capture_scale(0.82);
+
+        // The board is located in the raw frame, so the frame is only turned
+        // upright (no zoom, filter or mirror) and the corners turned with it.
+        // Sharpening halos would read as faint strokes.
+        let rotation = self.capture_rotation();
+        let quad = self
+            .whiteboard_keystone()
+            .or(self.whiteboard.detected)
+            .unwrap_or(BoardQuad::FULL)
+            .rotated(rotation);
+        let config = PostProcessingConfig {
+            rotation,
+            output_sharpening: OutputSharpening {
+                amount: 0,
+                ..OutputSharpening::default()
+            },
+            ..Default::default()
+        };
+        let save_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+
+        let snapshot_task = Task::perform(
+            async move {
+                let processed = PostProcessor::new(config).process(frame).await?;
+                tokio::task::spawn_blocking(move || {
+                    let board = whiteboard::enhance(&whiteboard::rectify(&processed.image, &quad));
+                    let (width, height) = board.dimensions();
+                    let mut jpeg = Vec::new();
+                    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, 92)
+                        .encode_image(&board)
+                        .map_err(|e| format!("Failed to encode whiteboard page: {}", e))?;
+
+                    std::fs::create_dir_all(&save_dir).map_err(|e| {
+                        format!(
+                            "Failed to create output directory '{}': {}",
+                            save_dir.display(),
+                            e
+                        )
+                    })?;
+                    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S_%3f");
+                    let path = save_dir.join(format!("WB_{timestamp}.png"));
+                    board
+                        .save_with_format(&path, image::ImageFormat::Png)
+                        .map_err(|e| {
+                            format!("Failed to save snapshot to '{}': {}", path.display(), e)
+                        })?;
+                    Ok(WhiteboardSnapshot {
+                        path: path.display().to_string(),
+                        page: DocumentPage {
+                            jpeg,
+                            width,
+                            height,
+                        },
+                    })
+                })
+                .await
+                .map_err(|e| format!("Whiteboard snapshot task error: {}", e))?
+            },
+            |result| cosmic::Action::App(Message::WhiteboardSnapshotSaved(result)),
+        );
+
+        let animation_task = Self::delay_task(150, Message::ClearCaptureAnimation);
+        Task::batch([snapshot_task, animation_task])
+    }
+
+    pub(crate) fn handle_whiteboard_snapshot_saved(
+        &mut self,
+        result: Result<crate::app::state::WhiteboardSnapshot, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        self.whiteboard.capturing = false;
+        match result {
+            Ok(snapshot) => {
+                info!(path = %snapshot.path, "Whiteboard snapshot saved");
+                self.whiteboard.pages.push(snapshot.page);
+                self.last_media_path = Some(snapshot.path);
+            }
+            Err(err) => error!(error = %err, "Failed to save whiteboard snapshot"),
+        }
+        Task::none()
+    }
+
+    /// Track the board outline and snapshot new writing once it settles
+    pub(crate) fn handle_board_signature_updated(
+        &mut self,
+        signature: Option<crate::app::frame_processor::BoardSignature>,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(signature) = signature else {
+            return Task::none();
+        };
+        if !self.whiteboard_detection_armed() {
+            return Task::none();
+        }
+
+        self.whiteboard.detected = signature.quad;
+        if self.config.whiteboard_auto_snapshot
+            && self.whiteboard.feed.observe(signature, self.clock.now())
+        {
+            info!("Board writing settled - taking a snapshot");
+            return self.handle_capture_whiteboard();
+        }
+        Task::none()
+    }
+
+    /// Lock the detected board corners for this camera, or unlock them
+    pub(crate) fn handle_toggle_whiteboard_lock(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        let Some(camera) = self.available_cameras.get(self.current_camera_index) else {
+            return Task::none();
+        };
+        let path = camera.path.clone();
+        if self.config.whiteboard_keystones.remove(&path).is_some() {
+            info!(camera = %path, "Whiteboard keystone unlocked");
+        } else if let Some(quad) = self.whiteboard.detected {
+            info!(camera = %path, ?quad, "Whiteboard keystone locked");
+            self.config.whiteboard_keystones.insert(path, quad.into());
+        } else {
+            info!("No board detected to lock");
+            return Task::none();
+        }
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save whiteboard keystone");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_whiteboard_auto_snapshot(
+        &mut self,
+    ) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        self.config.whiteboard_auto_snapshot = !self.config.whiteboard_auto_snapshot;
+        info!(
+            enabled = self.config.whiteboard_auto_snapshot,
+            "Whiteboard auto-snapshot toggled"
+        );
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save whiteboard auto-snapshot setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_save_whiteboard_pdf(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.whiteboard.pages.is_empty() || self.whiteboard.is_busy() {
+            return Task::none();
+        }
+        self.whiteboard.saving = true;
+
+        let pages = self.whiteboard.pages.clone();
+        let save_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+        let ocr_language = self.searchable_pdf_language();
+        info!(pages = pages.len(), "Saving whiteboard PDF");
+
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    write_pdf(&pages, &save_dir, "WB", ocr_language.as_deref())
+                })
+                .await
+                .map_err(|e| format!("Whiteboard PDF task error: {}", e))?
+            },
+            |result| cosmic::Action::App(Message::WhiteboardPdfSaved(result)),
+        )
+    }
+
+    pub(crate) fn handle_whiteboard_pdf_saved(
+        &mut self,
+        result: Result<String, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        self.whiteboard.saving = false;
+        match result {
+            Ok(path) => {
+                info!(path = %path, pages = self.whiteboard.pages.len(), "Whiteboard PDF saved");
+                self.whiteboard.pages.clear();
+                self.last_media_path = Some(path);
+            }
+            // The snapshots are kept so saving can be retried
+            Err(err) => error!(error = %err, "Failed to save whiteboard PDF"),
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_discard_whiteboard(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.whiteboard.saving {
+            return Task::none();
+        }
+        info!(
+            snapshots = self.whiteboard.pages.len(),
+            "Discarding whiteboard session"
+        );
+        self.whiteboard.pages.clear();
+        self.whiteboard.feed.reset();
+        Task::none()
+    }
+
+    // =========================================================================
+    // Barcode Scanning
+    // =========================================================================
+
+    /// Whether frames should be checked for barcodes: Scan mode is running
+    pub(crate) fn barcode_detection_armed(&self) -> bool {
+        self.mode == CameraMode::Scan && !self.scan.paused && !self.transition_state.ui_disabled
+    }
+
+    pub(crate) fn handle_toggle_scanning(&mut self) -> Task<cosmic::Action<Message>> {
+        self.scan.paused = !self.scan.paused;
+        info!(paused = self.scan.paused, "Scanning toggled");
+        Task::none()
+    }
+
+    pub(crate) fn handle_barcodes_detected(
+        &mut self,
+        detections: Vec<crate::app::frame_processor::BarcodeDetection>,
+    ) -> Task<cosmic::Action<Message>> {
+        if !self.barcode_detection_armed() {
+            return Task::none();
+        }
+
+        let now = chrono::Local::now();
+        let mut added = 0;
+        for detection in &detections {
+            if self
+                .scan
+                .list
+                .record(&detection.format, &detection.content, now)
+            {
+                info!(
+                    format = %detection.format,
+                    content = %detection.content,
+                    "Scanned new code"
+                );
+                added += 1;
+            }
+        }
+        // One beep per sample, however many codes it brought in
+        if added > 0 {
+            if self.config.scan_beep && !self.config.silent_mode {
+                crate::sound::beep();
+            }
+            self.haptic_tap();
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_scan_beep(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        self.config.scan_beep = !self.config.scan_beep;
+        info!(enabled = self.config.scan_beep, "Scan beep toggled");
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save scan beep setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_export_scans(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.scan.list.is_empty() || self.scan.exporting {
+            return Task::none();
+        }
+        self.scan.exporting = true;
+
+        let list = self.scan.list.clone();
+        let save_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+        info!(codes = list.codes.len(), "Exporting scanned codes");
+
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    crate::media::scan_list::write_csv(&list, &save_dir, "SCAN")
+                })
+                .await
+                .map_err(|e| format!("Scan export task error: {}", e))?
+            },
+            |result| cosmic::Action::App(Message::ScansExported(result)),
+        )
+    }
+
+    pub(crate) fn handle_scans_exported(
+        &mut self,
+        result: Result<String, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        self.scan.exporting = false;
+        match result {
+            // The list is kept: scanning can go on and be exported again
+            Ok(path) => {
+                info!(path = %path, codes = self.scan.list.codes.len(), "Scanned codes exported");
+                self.last_media_path = Some(path);
+            }
+            Err(err) => error!(error = %err, "Failed to export scanned codes"),
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_clear_scans(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.scan.exporting {
+            return Task::none();
+        }
+        info!(codes = self.scan.list.codes.len(), "Clearing scanned codes");
+        self.scan.list.clear();
+        Task::none()
+    }
+
+    // =========================================================================
+    // Focus Stacking
+    // =========================================================================
+
+    /// Whether the shutter takes a focus-stacked photo: the setting is on
+    /// and the camera has manual focus
+    pub(crate) fn would_use_focus_stack(&self) -> bool {
+        self.config.focus_stack && self.available_exposure_controls.focus.available
+    }
+
+    /// Start sweeping the lens across its focus range
+    fn start_focus_stack(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::pipelines::photo::burst_mode::focus_stack::focus_positions;
+
+        if self.focus_stack.is_some() || self.burst_mode.is_active() {
+            warn!("Cannot start focus sweep: a capture is already running");
+            return Task::none();
+        }
+        let range = &self.available_exposure_controls.focus;
+        let positions = focus_positions(
+            range.min,
+            range.max,
+            range.step,
+            usize::from(self.config.focus_stack_steps),
+        );
+        let settings = self.exposure_settings.as_ref();
+        let restore_focus_auto = settings.and_then(|s| s.focus_auto).unwrap_or(false);
+        info!(
+            ?positions,
+            autofocus = restore_focus_auto,
+            "Starting focus sweep"
+        );
+
+        self.is_capturing = true;
+        self.focus_stack = Some(crate::app::state::FocusStackSession {
+            restore_focus: settings.and_then(|s| s.focus_absolute),
+            restore_focus_auto,
+            frames: Vec::with_capacity(positions.len()),
+            positions,
+        });
+        self.move_focus_stack_lens(restore_focus_auto)
+    }
+
+    /// Send the lens to the sweep's next position, turning autofocus off
+    /// first if it was on
+    fn move_focus_stack_lens(&self, disable_auto: bool) -> Task<cosmic::Action<Message>> {
+        use crate::backends::camera::v4l2_controls;
+
+        let Some(position) = self
+            .focus_stack
+            .as_ref()
+            .and_then(|session| session.next_position())
+        else {
+            return Task::none();
+        };
+        let Some(focus_path) = self
+            .get_focus_device_path()
+            .or_else(|| self.get_v4l2_device_path())
+        else {
+            return Task::done(cosmic::Action::App(Message::FocusStackMoved(Err(
+                "No focus control for this camera".to_string(),
+            ))));
+        };
+        debug!(position, path = %focus_path, "Moving lens for focus sweep");
+        Task::perform(
+            async move {
+                if disable_auto {
+                    v4l2_controls::set_control(&focus_path, v4l2_controls::V4L2_CID_FOCUS_AUTO, 0)?;
+                }
+                v4l2_controls::set_control(
+                    &focus_path,
+                    v4l2_controls::V4L2_CID_FOCUS_ABSOLUTE,
+                    position,
+                )
+            },
+            |result| cosmic::Action::App(Message::FocusStackMoved(result)),
+        )
+    }
+
+    pub(crate) fn handle_focus_stack_moved(
+        &mut self,
+        result: Result<(), String>,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::pipelines::photo::burst_mode::focus_stack::SETTLE_MS;
+
+        if self.focus_stack.is_none() {
+            return Task::none();
+        }
+        if let Err(e) = result {
+            error!(error = %e, "Focus sweep failed to move the lens");
+            return self.finish_focus_stack(false);
+        }
+        Self::delay_task(SETTLE_MS, Message::FocusStackCapture)
+    }
+
+    /// Keep the current frame for this focus position, then move on or
+    /// merge the sweep
+    pub(crate) fn handle_focus_stack_capture(&mut self) -> Task<cosmic::Action<Message>> {
+        let frame = self.current_frame.clone();
+        let Some(session) = self.focus_stack.as_mut() else {
+            return Task::none();
+        };
+        let Some(frame) = frame else {
+            warn!("No frame available for focus sweep, aborting");
+            return self.finish_focus_stack(false);
+        };
+
+        // The preview keeps running, so don't hold the pipeline's buffer
+        // for the rest of the sweep
+        session.frames.push(Arc::new(frame.to_copied()));
+        debug!(
+            frame = session.frames.len(),
+            total = session.positions.len(),
+            "Focus sweep frame kept"
+        );
+        if session.is_complete() {
+            return self.finish_focus_stack(true);
+        }
+        self.move_focus_stack_lens(false)
+    }
+
+    /// Put the lens back as the user had it and, if the sweep completed,
+    /// merge its frames
+    fn finish_focus_stack(&mut self, merge: bool) -> Task<cosmic::Action<Message>> {
+        use crate::backends::camera::v4l2_controls;
+        use crate::pipelines::photo::burst_mode::focus_stack::{
+            FocusStackConfig, process_focus_stack,
+        };
+
+        let Some(session) = self.focus_stack.take() else {
+            return Task::none();
+        };
+        let (restore_focus_auto, restore_focus) =
+            (session.restore_focus_auto, session.restore_focus);
+
+        let restore_task = match self
+            .get_focus_device_path()
+            .or_else(|| self.get_v4l2_device_path())
+        {
+            Some(focus_path) => Task::perform(
+                async move {
+                    if restore_focus_auto {
+                        v4l2_controls::set_control(
+                            &focus_path,
+                            v4l2_controls::V4L2_CID_FOCUS_AUTO,
+                            1,
+                        )
+                    } else if let Some(position) = restore_focus {
+                        v4l2_controls::set_control(
+                            &focus_path,
+                            v4l2_controls::V4L2_CID_FOCUS_ABSOLUTE,
+                            position,
+                        )
+                    } else {
+                        Ok(())
+                    }
+                },
+                |result| {
+                    cosmic::Action::App(match result {
+                        Ok(_) => Message::ExposureControlApplied,
+                        Err(e) => Message::ExposureControlFailed(e),
+                    })
+                },
+            ),
+            None => Task::none(),
+        };
+
+        if !merge {
+            self.is_capturing = false;
+            return restore_task;
+        }
+
+        let (rotation, orientation) = self.saved_rotation();
+        let config = FocusStackConfig {
+            encoding_format: self.config.photo_output_format.into(),
+            camera_metadata: crate::pipelines::photo::CameraMetadata {
+                orientation,
+                ..self.build_camera_metadata()
+            },
+            rotation,
+            mirror_horizontal: self.should_mirror_captures(),
+            output_sharpening: self.config.output_sharpening,
+        };
+        let save_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+        let frames = session.frames;
+        let save_task = Task::perform(
+            async move {
+                process_focus_stack(frames, config, save_dir)
+                    .await
+                    .map(|path| path.display().to_string())
+                    .map_err(AppError::from)
+            },
+            |result| cosmic::Action::App(Message::PhotoSaved(result)),
+        );
+        Task::batch([restore_task, save_task])
+    }
+
+    // =========================================================================
+    // Scene Suggestions
+    // =========================================================================
+
+    /// Whether frames should be classified for a scene suggestion: the
+    /// setting is on and Photo mode is idle
+    pub(crate) fn scene_suggestions_armed(&self) -> bool {
+        self.config.scene_suggestions
+            && self.mode == CameraMode::Photo
+            && !self.is_capturing
+            && self.photo_timer_countdown.is_none()
+            && !self.transition_state.ui_disabled
+    }
+
+    /// What applying the suggestion for a scene does, or `None` if the
+    /// matching setting is already in use (or the camera can't do it)
+    pub(crate) fn scene_action(
+        &self,
+        scene: crate::app::frame_processor::Scene,
+    ) -> Option<Message> {
+        use crate::app::frame_processor::Scene;
+
+        match scene {
+            Scene::LowLight => (!(self.config.burst_mode_setting.is_enabled()
+                || self.night_mode_engaged)
+                || self.hdr_override_disabled)
+                .then_some(Message::ToggleBurstMode),
+            Scene::Document => Some(Message::SetMode(CameraMode::Document)),
+            Scene::QrCodes => (!self.qr_detection_enabled).then_some(Message::ToggleQrDetection),
+            Scene::Macro => {
+                let focus_auto = self
+                    .exposure_settings
+                    .as_ref()
+                    .and_then(|settings| settings.focus_auto)
+                    .unwrap_or(false);
+                (self.available_exposure_controls.has_focus_auto && !focus_auto)
+                    .then_some(Message::ToggleFocusAuto)
+            }
+            Scene::Portrait => {
+                (!self.config.face_exposure_priority).then_some(Message::ToggleFaceExposurePriority)
+            }
+        }
+    }
+
+    pub(crate) fn handle_scene_classified(
+        &mut self,
+        scene: Option<crate::app::frame_processor::Scene>,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::app::frame_processor::Scene;
+
+        if !self.scene_suggestions_armed() {
+            self.scene_feed.reset();
+            self.scene_suggestion = None;
+            return Task::none();
+        }
+
+        // With night mode on, the light meter decides when it is dark
+        let night_mode = self.config.night_mode != crate::config::NightMode::Off;
+        let scene = scene.filter(|&scene| !(night_mode && scene == Scene::LowLight));
+        let suggestion = self.scene_feed.observe(scene).filter(|&scene| {
+            !self.dismissed_scenes.contains(&scene) && self.scene_action(scene).is_some()
+        });
+        // Keep a low-light suggestion night mode made
+        let suggestion = match self.scene_suggestion {
+            Some(Scene::LowLight) if night_mode && suggestion.is_none() => Some(Scene::LowLight),
+            _ => suggestion,
+        };
+        if suggestion != self.scene_suggestion {
+            debug!(?suggestion, "Scene suggestion changed");
+            self.scene_suggestion = suggestion;
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_apply_scene_suggestion(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(scene) = self.scene_suggestion.take() else {
+            return Task::none();
+        };
+        // Once taken up, the suggestion has done its job for this session
+        self.dismissed_scenes.push(scene);
+        self.scene_feed.reset();
+        let Some(action) = self.scene_action(scene) else {
+            return Task::none();
+        };
+        info!(?scene, "Applying scene suggestion");
+        Task::done(cosmic::Action::App(action))
+    }
+
+    pub(crate) fn handle_dismiss_scene_suggestion(&mut self) -> Task<cosmic::Action<Message>> {
+        if let Some(scene) = self.scene_suggestion.take() {
+            info!(?scene, "Scene suggestion dismissed");
+            self.dismissed_scenes.push(scene);
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_scene_suggestions(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        self.config.scene_suggestions = !self.config.scene_suggestions;
+        info!(
+            enabled = self.config.scene_suggestions,
+            "Scene suggestions toggled"
+        );
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save scene suggestions setting");
+        }
+        self.scene_feed.reset();
+        self.scene_suggestion = None;
+        Task::none()
+    }
+}
+
+/// A PNG offered on the clipboard
+struct PngClipboard(Vec<u8>);
+
+impl cosmic::iced::clipboard::mime::AsMimeTypes for PngClipboard {
+    fn available(&self) -> std::borrow::Cow<'static, [String]> {
+        std::borrow::Cow::Owned(vec!["image/png".to_string()])
+    }
+
+    fn as_bytes(&self, mime_type: &str) -> Option<std::borrow::Cow<'static, [u8]>> {
+        (mime_type == "image/png").then(|| std::borrow::Cow::Owned(self.0.clone()))
+    }
+}
+
+/// Write `pages` as one PDF named `<prefix>_<timestamp>.pdf` in `save_dir`
+/// and return its path. With `ocr_language` the pages are read first and
+/// get a searchable text layer; a page that can't be read is kept as an
+/// image only.
+fn write_pdf(
+    pages: &[crate::app::state::DocumentPage],
+    save_dir: &std::path::Path,
+    prefix: &str,
+    ocr_language: Option<&str>,
+) -> Result<String, String> {
+    use crate::media::pdf::{PdfPage, jpeg_pages_to_pdf};
+
+    let texts: Vec<crate::media::ocr::OcrPage> = pages
+        .iter()
+        .map(|page| {
+            let Some(language) = ocr_language else {
+                return Default::default();
+            };
+            crate::media::ocr::recognize(&page.jpeg, language).unwrap_or_else(|err| {
+                warn!(error = %err, "Saving page without a text layer");
+                Default::default()
+            })
+        })
+        .collect();
+    let pdf_pages: Vec<PdfPage<'_>> = pages
+        .iter()
+        .zip(&texts)
+        .map(|(page, text)| PdfPage {
+            jpeg: &page.jpeg,
+            width: page.width,
+            height: page.height,
+            words: &text.words,
+        })
+        .collect();
+    let pdf = jpeg_pages_to_pdf(&pdf_pages);
+
+    std::fs::create_dir_all(save_dir).map_err(|e| {
+        format!(
+            "Failed to create output directory '{}': {}",
+            save_dir.display(),
+            e
+        )
+    })?;
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S_%3f");
+    let path = save_dir.join(format!("{prefix}_{timestamp}.pdf"));
+    std::fs::write(&path, pdf)
+        .map_err(|e| format!("Failed to save PDF to '{}': {}", path.display(), e))?;
+    Ok(path.display().to_string())
+}
+
+/// Call org.freedesktop.ScreenSaver.Inhibit to prevent idle/sleep.
+/// Returns a guard that keeps the D-Bus connection alive (required by cosmic-idle).
+fn screensaver_inhibit() -> Result<crate::app::state::IdleInhibitGuard, String> {
+    let conn = zbus::blocking::Connection::session()
+        .map_err(|e| format!("D-Bus session connection: {e}"))?;
+    let cookie: u32 = conn
+        .call_method(
+            Some("org.freedesktop.ScreenSaver"),
+            "/org/freedesktop/ScreenSaver",
+            Some("org.freedesktop.ScreenSaver"),
+            "Inhibit",
             &("Camera", "Camera active"),
         )
         .map_err(|e| format!("ScreenSaver.Inhibit: {e}"))?
@@ -2155,10 +4818,11 @@ async fn process_burst_mode_frames_with_atomic(
     config: BurstModeConfig,
     progress_atomic: Arc<std::sync::atomic::AtomicU32>,
     filter: crate::app::FilterType,
+    cancel: tokio_util::sync::CancellationToken,
 ) -> Result<String, String> {
     use crate::pipelines::photo::burst_mode::{
         ProgressCallback, SaveOutputParams, export_burst_frames_dng, process_burst_mode,
-        save_output,
+        save_output, super_resolution::process_super_resolution,
     };
 
     info!(
@@ -2177,6 +4841,7 @@ async fn process_burst_mode_frames_with_atomic(
     let save_burst_raw_dng = config.save_burst_raw_dng;
     let rotation = config.rotation;
     let mirror_horizontal = config.mirror_horizontal;
+    let super_resolution = config.super_resolution;
 
     // Export raw burst frames as DNG if enabled (before processing)
     if save_burst_raw_dng {
@@ -2216,7 +4881,17 @@ async fn process_burst_mode_frames_with_atomic(
     });
 
     // Process using the unified GPU pipeline with progress reporting
-    let merged = process_burst_mode(frames, config, Some(progress_callback)).await?;
+    let (merged, crop_rect) = if super_resolution {
+        use crate::pipelines::photo::burst_mode::super_resolution::SCALE;
+        let merged =
+            process_super_resolution(frames, config, Some(progress_callback), cancel).await?;
+        // The crop was worked out on the frames, the output is larger
+        let crop_rect = crop_rect.map(|(x, y, w, h)| (x * SCALE, y * SCALE, w * SCALE, h * SCALE));
+        (merged, crop_rect)
+    } else {
+        let merged = process_burst_mode(frames, config, Some(progress_callback), cancel).await?;
+        (merged, crop_rect)
+    };
 
     // Save output with optional crop, filter, rotation, and selected encoding format
     let output_path = save_output(
@@ -2228,7 +4903,7 @@ async fn process_burst_mode_frames_with_atomic(
             camera_metadata,
             filter: Some(filter),
             rotation,
-            filename_suffix: Some("_HDR+"),
+            filename_suffix: Some(if super_resolution { "_SR" } else { "_HDR+" }),
             mirror_horizontal,
         },
     )
@@ -5,10 +5,13 @@
 //! Handles color adjustment controls including contrast, saturation, sharpness,
 //! hue, and white balance.
 
+use crate::app::frame_processor::GrayPoint;
+use crate::app::frame_processor::tasks::white_point;
 use crate::app::state::{AppModel, Message};
 use crate::backends::camera::v4l2_controls;
+use crate::shaders::WhiteBalanceGains;
 use cosmic::Task;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 impl AppModel {
     // =========================================================================
@@ -32,6 +35,7 @@ impl AppModel {
         if let Some(ref mut settings) = self.color_settings {
             settings.contrast = Some(value);
         }
+        self.remember_control(v4l2_controls::V4L2_CID_CONTRAST, value);
         debug!(value, "Setting contrast");
         self.set_v4l2_control(v4l2_controls::V4L2_CID_CONTRAST, value)
     }
@@ -40,6 +44,7 @@ impl AppModel {
         if let Some(ref mut settings) = self.color_settings {
             settings.saturation = Some(value);
         }
+        self.remember_control(v4l2_controls::V4L2_CID_SATURATION, value);
         debug!(value, "Setting saturation");
         self.set_v4l2_control(v4l2_controls::V4L2_CID_SATURATION, value)
     }
@@ -48,6 +53,7 @@ impl AppModel {
         if let Some(ref mut settings) = self.color_settings {
             settings.sharpness = Some(value);
         }
+        self.remember_control(v4l2_controls::V4L2_CID_SHARPNESS, value);
         debug!(value, "Setting sharpness");
         self.set_v4l2_control(v4l2_controls::V4L2_CID_SHARPNESS, value)
     }
@@ -56,6 +62,7 @@ impl AppModel {
         if let Some(ref mut settings) = self.color_settings {
             settings.hue = Some(value);
         }
+        self.remember_control(v4l2_controls::V4L2_CID_HUE, value);
         debug!(value, "Setting hue");
         self.set_v4l2_control(v4l2_controls::V4L2_CID_HUE, value)
     }
@@ -71,6 +78,14 @@ impl AppModel {
         if let Some(ref mut settings) = self.color_settings {
             settings.white_balance_auto = Some(new_value);
         }
+        self.remember_control(
+            v4l2_controls::V4L2_CID_AUTO_WHITE_BALANCE,
+            if new_value { 1 } else { 0 },
+        );
+        if new_value {
+            // Auto white balance drives the temperature
+            self.forget_controls(&[v4l2_controls::V4L2_CID_WHITE_BALANCE_TEMPERATURE]);
+        }
 
         let Some(device_path) = self.get_v4l2_device_path() else {
             return Task::none();
@@ -127,10 +142,122 @@ impl AppModel {
         if let Some(ref mut settings) = self.color_settings {
             settings.white_balance_temperature = Some(value);
         }
+        self.remember_control(v4l2_controls::V4L2_CID_WHITE_BALANCE_TEMPERATURE, value);
         debug!(temperature = value, "Setting white balance temperature");
         self.set_v4l2_control(v4l2_controls::V4L2_CID_WHITE_BALANCE_TEMPERATURE, value)
     }
 
+    pub(crate) fn handle_start_white_balance_pick(&mut self) -> Task<cosmic::Action<Message>> {
+        // The pickers would cover the preview being clicked on
+        self.close_all_pickers();
+        self.wb_eyedropper_active = true;
+        info!("White balance eyedropper armed");
+        Task::none()
+    }
+
+    pub(crate) fn handle_cancel_white_balance_pick(&mut self) -> Task<cosmic::Action<Message>> {
+        self.wb_eyedropper_active = false;
+        self.color_picker_visible = true;
+        Task::none()
+    }
+
+    pub(crate) fn handle_white_balance_point_picked(
+        &mut self,
+        x: f32,
+        y: f32,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(frame) = self.current_frame.clone() else {
+            return self.handle_cancel_white_balance_pick();
+        };
+        Task::perform(white_point::sample(frame, x, y), |gray| {
+            cosmic::Action::App(Message::WhiteBalanceSampled(gray))
+        })
+    }
+
+    /// Balance the camera on the sampled gray point: through its temperature
+    /// control when it has one, otherwise with software gains
+    pub(crate) fn handle_white_balance_sampled(
+        &mut self,
+        gray: Option<GrayPoint>,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(gray) = gray else {
+            // Stay armed so the user can pick a better spot
+            warn!("Gray point too dark or clipped, pick again");
+            return Task::none();
+        };
+        self.wb_eyedropper_active = false;
+        self.color_picker_visible = true;
+
+        let controls = &self.available_exposure_controls;
+        if !controls.white_balance_temperature.available {
+            let gains = white_point::correction_gains(gray);
+            info!(?gains, "Applying software white balance");
+            self.software_white_balance = gains;
+            crate::shaders::set_software_white_balance(gains);
+            return Task::none();
+        }
+
+        let has_wb_auto = controls.has_white_balance_auto;
+        let range = controls.white_balance_temperature.clone();
+        let current = self
+            .color_settings
+            .as_ref()
+            .and_then(|s| s.white_balance_temperature)
+            .unwrap_or(range.default);
+        let temperature = white_point::corrected_temperature(current, gray, &range);
+        info!(
+            current,
+            temperature, "Setting white balance from gray point"
+        );
+
+        if let Some(ref mut settings) = self.color_settings {
+            if has_wb_auto {
+                settings.white_balance_auto = Some(false);
+            }
+            settings.white_balance_temperature = Some(temperature);
+        }
+        if has_wb_auto {
+            self.remember_control(v4l2_controls::V4L2_CID_AUTO_WHITE_BALANCE, 0);
+        }
+        self.remember_control(
+            v4l2_controls::V4L2_CID_WHITE_BALANCE_TEMPERATURE,
+            temperature,
+        );
+
+        let Some(device_path) = self.get_v4l2_device_path() else {
+            return Task::none();
+        };
+        Task::perform(
+            async move {
+                // Most drivers ignore the temperature while auto is on
+                if has_wb_auto {
+                    v4l2_controls::set_control(
+                        &device_path,
+                        v4l2_controls::V4L2_CID_AUTO_WHITE_BALANCE,
+                        0,
+                    )?;
+                }
+                v4l2_controls::set_control(
+                    &device_path,
+                    v4l2_controls::V4L2_CID_WHITE_BALANCE_TEMPERATURE,
+                    temperature,
+                )
+            },
+            |result| {
+                cosmic::Action::App(match result {
+                    Ok(_) => Message::ExposureControlApplied,
+                    Err(e) => Message::ExposureControlFailed(e),
+                })
+            },
+        )
+    }
+
+    /// Drop the eyedropper's software white balance gains
+    pub(crate) fn clear_software_white_balance(&mut self) {
+        self.software_white_balance = WhiteBalanceGains::IDENTITY;
+        crate::shaders::set_software_white_balance(WhiteBalanceGains::IDENTITY);
+    }
+
     pub(crate) fn handle_reset_color_settings(&mut self) -> Task<cosmic::Action<Message>> {
         info!("Resetting color settings to defaults");
         self.reset_color_settings_to_defaults()
@@ -138,6 +265,16 @@ impl AppModel {
 
     /// Reset color settings to defaults (helper for filter selection and reset button)
     pub(crate) fn reset_color_settings_to_defaults(&mut self) -> Task<cosmic::Action<Message>> {
+        self.forget_controls(&[
+            v4l2_controls::V4L2_CID_CONTRAST,
+            v4l2_controls::V4L2_CID_SATURATION,
+            v4l2_controls::V4L2_CID_SHARPNESS,
+            v4l2_controls::V4L2_CID_HUE,
+            v4l2_controls::V4L2_CID_AUTO_WHITE_BALANCE,
+            v4l2_controls::V4L2_CID_WHITE_BALANCE_TEMPERATURE,
+        ]);
+        self.clear_software_white_balance();
+
         let controls = &self.available_exposure_controls;
         let Some(device_path) = self.get_v4l2_device_path() else {
             return Task::none();
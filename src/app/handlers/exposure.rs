@@ -7,10 +7,13 @@
 use crate::app::exposure_picker::{
     AvailableExposureControls, ColorSettings, ExposureMode, ExposureSettings, MeteringMode,
 };
+use crate::app::frame_processor::tasks::exposure_assist;
 use crate::app::state::{AppModel, Message};
 use crate::backends::camera::v4l2_controls;
 use cosmic::Task;
-use tracing::{debug, info};
+use std::collections::HashMap;
+use std::time::Instant;
+use tracing::{debug, error, info, warn};
 
 impl AppModel {
     // =========================================================================
@@ -21,6 +24,7 @@ impl AppModel {
         let opening = !self.exposure_picker_visible;
         self.close_all_pickers();
         self.exposure_picker_visible = opening;
+        self.exposure_suggestion = None;
         if opening {
             // Clear base exposure time when opening (will be captured on first slider move)
             self.base_exposure_time = None;
@@ -45,11 +49,21 @@ impl AppModel {
         if let Some(ref mut settings) = self.exposure_settings {
             settings.mode = mode;
         }
+        self.exposure_suggestion = None;
 
         // Sync segmented button model
         let position = if mode == ExposureMode::Manual { 1 } else { 0 };
         self.exposure_mode_model.activate_position(position);
 
+        self.remember_control(v4l2_controls::V4L2_CID_EXPOSURE_AUTO, mode.to_v4l2_value());
+        if mode != ExposureMode::Manual {
+            // The camera drives exposure time and gain in the auto modes
+            self.forget_controls(&[
+                v4l2_controls::V4L2_CID_EXPOSURE_ABSOLUTE,
+                v4l2_controls::V4L2_CID_GAIN,
+            ]);
+        }
+
         // Apply to camera via V4L2
         let Some(device_path) = self.get_v4l2_device_path() else {
             return Task::none();
@@ -101,6 +115,9 @@ impl AppModel {
         if let Some(ref mut settings) = self.exposure_settings {
             settings.exposure_compensation = value;
         }
+        // Face priority picks up from the user's new bias
+        self.face_exposure_bias = None;
+        self.remember_control(v4l2_controls::V4L2_CID_AUTO_EXPOSURE_BIAS, value);
 
         // Apply to camera via V4L2
         let Some(device_path) = self.get_v4l2_device_path() else {
@@ -136,6 +153,8 @@ impl AppModel {
         if let Some(ref mut settings) = self.exposure_settings {
             settings.exposure_compensation = 0;
         }
+        self.face_exposure_bias = None;
+        self.forget_controls(&[v4l2_controls::V4L2_CID_AUTO_EXPOSURE_BIAS]);
 
         let Some(device_path) = self.get_v4l2_device_path() else {
             return Task::none();
@@ -166,6 +185,11 @@ impl AppModel {
         if let Some(ref mut settings) = self.exposure_settings {
             settings.exposure_time = Some(value);
         }
+        self.remember_control(
+            v4l2_controls::V4L2_CID_EXPOSURE_AUTO,
+            ExposureMode::Manual.to_v4l2_value(),
+        );
+        self.remember_control(v4l2_controls::V4L2_CID_EXPOSURE_ABSOLUTE, value);
 
         // Apply to camera via V4L2
         let Some(device_path) = self.get_v4l2_device_path() else {
@@ -230,6 +254,11 @@ impl AppModel {
         if let Some(ref mut settings) = self.exposure_settings {
             settings.gain = Some(value);
         }
+        self.remember_control(
+            v4l2_controls::V4L2_CID_EXPOSURE_AUTO,
+            ExposureMode::Manual.to_v4l2_value(),
+        );
+        self.remember_control(v4l2_controls::V4L2_CID_GAIN, value);
 
         // Apply to camera via V4L2
         let Some(device_path) = self.get_v4l2_device_path() else {
@@ -291,6 +320,51 @@ impl AppModel {
         )
     }
 
+    pub(crate) fn handle_suggest_exposure(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(frame) = self.current_frame.clone() else {
+            return Task::none();
+        };
+        let controls = &self.available_exposure_controls;
+        let settings = self.exposure_settings.as_ref();
+        let inputs = exposure_assist::ExposureInputs {
+            exposure_time: settings
+                .and_then(|s| s.exposure_time)
+                .unwrap_or(controls.exposure_time.default),
+            exposure_range: controls.exposure_time.clone(),
+            gain: controls.gain.available.then(|| {
+                settings
+                    .and_then(|s| s.gain)
+                    .unwrap_or(controls.gain.default)
+            }),
+            gain_range: controls.gain.clone(),
+        };
+
+        Task::perform(exposure_assist::suggest(frame, inputs), |suggestion| {
+            cosmic::Action::App(Message::ExposureSuggested(suggestion))
+        })
+    }
+
+    pub(crate) fn handle_apply_exposure_suggestion(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(suggestion) = self.exposure_suggestion.take() else {
+            return Task::none();
+        };
+        info!(?suggestion, "Applying suggested exposure");
+
+        // Both handlers re-apply the other control, so store the new exposure
+        // time before the gain goes out and each write carries both values
+        if let Some(ref mut settings) = self.exposure_settings {
+            settings.exposure_time = Some(suggestion.exposure_time);
+        }
+        let gain_task = match suggestion.gain {
+            Some(gain) => self.handle_set_gain(gain),
+            None => Task::none(),
+        };
+        Task::batch([
+            gain_task,
+            self.handle_set_exposure_time(suggestion.exposure_time),
+        ])
+    }
+
     pub(crate) fn handle_set_iso_sensitivity(
         &mut self,
         value: i32,
@@ -299,6 +373,7 @@ impl AppModel {
         if let Some(ref mut settings) = self.exposure_settings {
             settings.iso = Some(value);
         }
+        self.remember_control(v4l2_controls::V4L2_CID_ISO_SENSITIVITY, value);
 
         // Apply to camera via V4L2
         let Some(device_path) = self.get_v4l2_device_path() else {
@@ -332,6 +407,10 @@ impl AppModel {
         if let Some(ref mut settings) = self.exposure_settings {
             settings.metering_mode = Some(mode);
         }
+        self.remember_control(
+            v4l2_controls::V4L2_CID_EXPOSURE_METERING,
+            mode.to_v4l2_value(),
+        );
 
         // Apply to camera via V4L2
         let Some(device_path) = self.get_v4l2_device_path() else {
@@ -368,12 +447,14 @@ impl AppModel {
             true
         };
 
+        let value = if new_value { 1 } else { 0 };
+        self.remember_control(v4l2_controls::V4L2_CID_EXPOSURE_AUTO_PRIORITY, value);
+
         // Apply to camera via V4L2
         let Some(device_path) = self.get_v4l2_device_path() else {
             return Task::none();
         };
 
-        let value = if new_value { 1 } else { 0 };
         debug!(enabled = new_value, "Setting auto exposure priority");
 
         Task::perform(
@@ -400,6 +481,7 @@ impl AppModel {
         if let Some(ref mut settings) = self.exposure_settings {
             settings.backlight_compensation = Some(value);
         }
+        self.remember_control(v4l2_controls::V4L2_CID_BACKLIGHT_COMPENSATION, value);
         debug!(backlight = value, "Setting backlight compensation");
         self.set_v4l2_control(v4l2_controls::V4L2_CID_BACKLIGHT_COMPENSATION, value)
     }
@@ -411,6 +493,7 @@ impl AppModel {
         if let Some(ref mut settings) = self.exposure_settings {
             settings.focus_absolute = Some(value);
         }
+        self.remember_control(v4l2_controls::V4L2_CID_FOCUS_ABSOLUTE, value);
 
         let focus_path = self
             .get_focus_device_path()
@@ -455,6 +538,11 @@ impl AppModel {
         };
 
         let value = if new_value { 1 } else { 0 };
+        self.remember_control(v4l2_controls::V4L2_CID_FOCUS_AUTO, value);
+        if new_value {
+            // Autofocus drives the lens position
+            self.forget_controls(&[v4l2_controls::V4L2_CID_FOCUS_ABSOLUTE]);
+        }
         debug!(enabled = new_value, path = %focus_path, "Setting focus auto");
 
         Task::perform(
@@ -470,6 +558,179 @@ impl AppModel {
         )
     }
 
+    /// Long-press on the preview: lock the current automatic exposure and
+    /// focus, or release the lock if one is held.
+    pub(crate) fn handle_toggle_exposure_focus_lock(&mut self) -> Task<cosmic::Action<Message>> {
+        self.haptic_tap();
+        if self.ae_af_lock.is_some() {
+            return self.release_exposure_focus_lock();
+        }
+
+        let Some(device_path) = self.get_v4l2_device_path() else {
+            info!("Exposure/focus lock needs a V4L2 camera");
+            return Task::none();
+        };
+        let focus_path = self.get_focus_device_path();
+        // Exposure held by AE-L stays with the button
+        let owned: Vec<_> = self.auto_holds.keys().copied().collect();
+
+        Task::perform(
+            async move {
+                v4l2_controls::lock_exposure_and_focus(&device_path, focus_path.as_deref(), &owned)
+            },
+            |result| cosmic::Action::App(Message::ExposureFocusLocked(result)),
+        )
+    }
+
+    pub(crate) fn handle_exposure_focus_locked(
+        &mut self,
+        result: Result<v4l2_controls::AeAfLock, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        match result {
+            Ok(lock) => {
+                info!(?lock, "Exposure and focus locked");
+                self.ae_af_lock = Some(lock);
+                // The pickers show auto/manual state that may have changed
+                self.query_exposure_controls_task()
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to lock exposure and focus");
+                Task::none()
+            }
+        }
+    }
+
+    /// Release the exposure/focus lock, if any. Called on a second long-press
+    /// and before switching camera, so an emulated lock never leaves the old
+    /// camera stuck in manual exposure.
+    pub(crate) fn release_exposure_focus_lock(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(lock) = self.ae_af_lock.take() else {
+            return Task::none();
+        };
+        let Some(device_path) = self.get_v4l2_device_path() else {
+            return Task::none();
+        };
+        let focus_path = self.get_focus_device_path();
+        info!(?lock, "Releasing exposure and focus lock");
+
+        Task::perform(
+            async move {
+                v4l2_controls::unlock_exposure_and_focus(&device_path, focus_path.as_deref(), &lock)
+            },
+            |result| {
+                cosmic::Action::App(match result {
+                    Ok(_) => Message::ExposureControlApplied,
+                    Err(e) => Message::ExposureControlFailed(e),
+                })
+            },
+        )
+    }
+
+    /// AE-L / AWB-L button: hold the automatic exposure or white balance at
+    /// its current value, or release the hold
+    pub(crate) fn handle_toggle_auto_hold(
+        &mut self,
+        control: v4l2_controls::AutoControl,
+    ) -> Task<cosmic::Action<Message>> {
+        self.haptic_tap();
+        if self.auto_holds.contains_key(&control) {
+            // The pickers show the automatic mode again afterwards
+            return self
+                .release_auto_hold(control)
+                .chain(self.query_exposure_controls_task());
+        }
+
+        // Already held by a long-press lock: the button takes that hold over,
+        // so releasing the lock leaves it in place
+        if let Some(hold) = self.ae_af_lock.as_mut().and_then(|lock| lock.take(control)) {
+            info!(
+                ?control,
+                "Automatic control held by the exposure/focus lock"
+            );
+            self.auto_holds.insert(control, hold);
+            return Task::none();
+        }
+
+        let Some(device_path) = self.get_v4l2_device_path() else {
+            info!(?control, "Holding automatic controls needs a V4L2 camera");
+            return Task::none();
+        };
+        let focus_path = self.get_focus_device_path();
+
+        Task::perform(
+            async move { v4l2_controls::hold_auto(&device_path, focus_path.as_deref(), control) },
+            move |result| cosmic::Action::App(Message::AutoHeld(control, result)),
+        )
+    }
+
+    pub(crate) fn handle_auto_held(
+        &mut self,
+        control: v4l2_controls::AutoControl,
+        result: Result<v4l2_controls::AutoHold, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        match result {
+            Ok(hold) => {
+                info!(?control, ?hold, "Automatic control held");
+                self.auto_holds.insert(control, hold);
+                // The pickers show auto/manual state that may have changed
+                self.query_exposure_controls_task()
+            }
+            Err(e) => {
+                warn!(?control, error = %e, "Failed to hold automatic control");
+                Task::none()
+            }
+        }
+    }
+
+    /// Release an AE-L / AWB-L hold, if held. A long-press lock that would
+    /// have held the control takes the hold over instead.
+    fn release_auto_hold(
+        &mut self,
+        control: v4l2_controls::AutoControl,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(hold) = self.auto_holds.remove(&control) else {
+            return Task::none();
+        };
+        if let Some(lock) = self.ae_af_lock.as_mut()
+            && v4l2_controls::AeAfLock::CONTROLS.contains(&control)
+        {
+            info!(
+                ?control,
+                "Automatic control stays held by the exposure/focus lock"
+            );
+            lock.holds.push((control, hold));
+            return Task::none();
+        }
+        let Some(device_path) = self.get_v4l2_device_path() else {
+            return Task::none();
+        };
+        let focus_path = self.get_focus_device_path();
+        info!(?control, ?hold, "Releasing automatic control");
+
+        Task::perform(
+            async move {
+                v4l2_controls::release_auto(&device_path, focus_path.as_deref(), control, &hold)
+            },
+            |result| {
+                cosmic::Action::App(match result {
+                    Ok(_) => Message::ExposureControlApplied,
+                    Err(e) => Message::ExposureControlFailed(e),
+                })
+            },
+        )
+    }
+
+    /// Release every AE-L / AWB-L hold. Called before switching camera, so
+    /// an emulated hold never leaves the old camera in manual mode.
+    pub(crate) fn release_auto_holds(&mut self) -> Task<cosmic::Action<Message>> {
+        let controls: Vec<_> = self.auto_holds.keys().copied().collect();
+        Task::batch(
+            controls
+                .into_iter()
+                .map(|control| self.release_auto_hold(control)),
+        )
+    }
+
     /// Reset all exposure settings to camera defaults (preserving current mode)
     pub(crate) fn handle_reset_exposure_settings(&mut self) -> Task<cosmic::Action<Message>> {
         let Some(device_path) = self.get_v4l2_device_path() else {
@@ -484,6 +745,15 @@ impl AppModel {
             .unwrap_or_default();
 
         info!(?current_mode, "Resetting exposure settings to defaults");
+        self.face_exposure_bias = None;
+        self.forget_controls(&[
+            v4l2_controls::V4L2_CID_EXPOSURE_ABSOLUTE,
+            v4l2_controls::V4L2_CID_GAIN,
+            v4l2_controls::V4L2_CID_ISO_SENSITIVITY,
+            v4l2_controls::V4L2_CID_AUTO_EXPOSURE_BIAS,
+            v4l2_controls::V4L2_CID_BACKLIGHT_COMPENSATION,
+            v4l2_controls::V4L2_CID_EXPOSURE_AUTO_PRIORITY,
+        ]);
 
         // Update local state to defaults
         if let Some(ref mut settings) = self.exposure_settings {
@@ -574,9 +844,422 @@ impl AppModel {
         self.available_exposure_controls = *controls;
         self.exposure_settings = Some(settings);
         self.color_settings = Some(color_settings);
+
+        // The camera just (re)connected, so its controls may be back at the
+        // driver defaults
+        self.reapply_camera_controls_task(true)
+    }
+
+    // =========================================================================
+    // Face Exposure Priority
+    // =========================================================================
+
+    /// Whether frames should be checked for a face to meter on: the setting
+    /// is on, auto exposure is running unlocked, and the camera has a
+    /// metering region or an exposure bias to steer with
+    pub(crate) fn face_exposure_armed(&self) -> bool {
+        let controls = &self.available_exposure_controls;
+        self.config.face_exposure_priority
+            && self.ae_af_lock.is_none()
+            && !self
+                .auto_holds
+                .contains_key(&v4l2_controls::AutoControl::Exposure)
+            && !self.transition_state.ui_disabled
+            && matches!(
+                self.exposure_settings.as_ref().map(|s| s.mode),
+                Some(ExposureMode::Auto | ExposureMode::AperturePriority)
+            )
+            && (controls.has_region_of_interest || controls.exposure_bias.available)
+            && self.get_v4l2_device_path().is_some()
+    }
+
+    /// Steer auto exposure toward the detected face
+    ///
+    /// Cameras with a UVC region of interest meter on the face rectangle
+    /// directly. Others get an exposure bias worked out from how bright the
+    /// face came out, on top of the user's own compensation.
+    pub(crate) fn handle_face_detection_updated(
+        &mut self,
+        face: Option<crate::app::frame_processor::FaceDetection>,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::app::frame_processor::tasks::face_detector::LOST_GRACE;
+
+        if !self.face_exposure_armed() {
+            return self.release_face_exposure();
+        }
+        let Some(face) = face else {
+            let lost = self
+                .face_last_seen
+                .is_some_and(|seen| seen.elapsed() >= LOST_GRACE);
+            return if lost {
+                debug!("Face lost - metering the whole frame again");
+                self.release_face_exposure()
+            } else {
+                Task::none()
+            };
+        };
+        self.face_last_seen = Some(Instant::now());
+
+        if self.available_exposure_controls.has_region_of_interest {
+            let (Some(device_path), Some(frame)) =
+                (self.get_v4l2_device_path(), self.current_frame.as_ref())
+            else {
+                return Task::none();
+            };
+            let (frame_width, frame_height) = (frame.width as f32, frame.height as f32);
+            let rect = (
+                (face.bounds.x * frame_width) as i32,
+                (face.bounds.y * frame_height) as i32,
+                ((face.bounds.width * frame_width) as u32).max(1),
+                ((face.bounds.height * frame_height) as u32).max(1),
+            );
+            self.face_region_active = true;
+            debug!(?rect, luma = face.luma, "Metering on face");
+            return Task::perform(
+                async move {
+                    v4l2_controls::set_rect_control(
+                        &device_path,
+                        v4l2_controls::V4L2_CID_UVC_REGION_OF_INTEREST_RECT,
+                        rect,
+                    )?;
+                    v4l2_controls::set_control(
+                        &device_path,
+                        v4l2_controls::V4L2_CID_UVC_REGION_OF_INTEREST_AUTO,
+                        v4l2_controls::V4L2_UVC_REGION_OF_INTEREST_AUTO_EXPOSURE,
+                    )
+                },
+                |result| {
+                    cosmic::Action::App(match result {
+                        Ok(_) => Message::ExposureControlApplied,
+                        Err(e) => Message::ExposureControlFailed(e),
+                    })
+                },
+            );
+        }
+
+        let user_bias = self
+            .exposure_settings
+            .as_ref()
+            .map_or(0, |s| s.exposure_compensation);
+        let current_bias = self.face_exposure_bias.unwrap_or(user_bias);
+        let Some(bias) = exposure_assist::face_exposure_bias(
+            face.luma,
+            user_bias,
+            current_bias,
+            &self.available_exposure_controls.exposure_bias,
+        ) else {
+            return Task::none();
+        };
+        debug!(
+            luma = face.luma,
+            ev = bias as f32 / 1000.0,
+            "Biasing exposure for face"
+        );
+        // Not remembered: the stored value stays the user's compensation
+        self.face_exposure_bias = Some(bias);
+        self.set_v4l2_control(v4l2_controls::V4L2_CID_AUTO_EXPOSURE_BIAS, bias)
+    }
+
+    /// Hand metering back to the whole frame: restore the user's exposure
+    /// bias and reset the region of interest to the full frame
+    pub(crate) fn release_face_exposure(&mut self) -> Task<cosmic::Action<Message>> {
+        self.face_last_seen = None;
+        let mut tasks = Vec::new();
+        if self.face_exposure_bias.take().is_some() {
+            let user_bias = self
+                .exposure_settings
+                .as_ref()
+                .map_or(0, |s| s.exposure_compensation);
+            tasks
+                .push(self.set_v4l2_control(v4l2_controls::V4L2_CID_AUTO_EXPOSURE_BIAS, user_bias));
+        }
+        if std::mem::take(&mut self.face_region_active)
+            && let (Some(device_path), Some(frame)) =
+                (self.get_v4l2_device_path(), self.current_frame.as_ref())
+        {
+            let rect = (0, 0, frame.width, frame.height);
+            tasks.push(Task::perform(
+                async move {
+                    v4l2_controls::set_rect_control(
+                        &device_path,
+                        v4l2_controls::V4L2_CID_UVC_REGION_OF_INTEREST_RECT,
+                        rect,
+                    )
+                },
+                |result| {
+                    cosmic::Action::App(match result {
+                        Ok(_) => Message::ExposureControlApplied,
+                        Err(e) => Message::ExposureControlFailed(e),
+                    })
+                },
+            ));
+        }
+        Task::batch(tasks)
+    }
+
+    pub(crate) fn handle_toggle_face_exposure_priority(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        self.config.face_exposure_priority = !self.config.face_exposure_priority;
+        info!(
+            enabled = self.config.face_exposure_priority,
+            "Face exposure priority toggled"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save face exposure priority setting");
+        }
+        if self.config.face_exposure_priority {
+            Task::none()
+        } else {
+            self.release_face_exposure()
+        }
+    }
+
+    // =========================================================================
+    // Persistent Controls
+    // =========================================================================
+
+    /// Controls stored for the current camera
+    fn stored_camera_controls(&self) -> Option<&HashMap<u32, i32>> {
+        let camera = self.available_cameras.get(self.current_camera_index)?;
+        self.config.camera_controls.get(&camera.path)
+    }
+
+    /// Remember a user-set control value for the current camera so it can be
+    /// restored when the camera reconnects
+    pub(crate) fn remember_control(&mut self, control_id: u32, value: i32) {
+        if !self.config.persist_camera_controls {
+            return;
+        }
+        let Some(camera) = self.available_cameras.get(self.current_camera_index) else {
+            return;
+        };
+        let previous = self
+            .config
+            .camera_controls
+            .entry(camera.path.clone())
+            .or_default()
+            .insert(control_id, value);
+        if previous != Some(value) {
+            self.save_camera_controls();
+        }
+    }
+
+    /// Stop restoring the given controls for the current camera (e.g. after
+    /// they were reset to defaults or handed back to an auto mode)
+    pub(crate) fn forget_controls(&mut self, control_ids: &[u32]) {
+        let Some(camera) = self.available_cameras.get(self.current_camera_index) else {
+            return;
+        };
+        let path = camera.path.clone();
+        let Some(controls) = self.config.camera_controls.get_mut(&path) else {
+            return;
+        };
+
+        let before = controls.len();
+        controls.retain(|id, _| !control_ids.contains(id));
+        if controls.len() == before {
+            return;
+        }
+        if controls.is_empty() {
+            self.config.camera_controls.remove(&path);
+        }
+        self.save_camera_controls();
+    }
+
+    /// Save only the per-camera controls map — this runs on every slider step,
+    /// so `write_entry` (which rewrites every config field) is too slow
+    fn save_camera_controls(&self) {
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = cosmic::cosmic_config::ConfigSet::set(
+                handler,
+                "camera_controls",
+                &self.config.camera_controls,
+            )
+        {
+            error!(?err, "Failed to save camera controls");
+        }
+    }
+
+    /// Create a task that rewrites the stored controls the device no longer
+    /// matches
+    pub(crate) fn reapply_camera_controls_task(
+        &self,
+        on_connect: bool,
+    ) -> Task<cosmic::Action<Message>> {
+        if !self.config.persist_camera_controls {
+            return Task::none();
+        }
+        let Some(mut controls) = self
+            .stored_camera_controls()
+            .filter(|controls| !controls.is_empty())
+            .cloned()
+        else {
+            return Task::none();
+        };
+        // The bias face-priority exposure applied isn't drift, nor are the
+        // modes and values AE-L / AWB-L and a long-press lock hold
+        if self.face_exposure_bias.is_some() {
+            controls.remove(&v4l2_controls::V4L2_CID_AUTO_EXPOSURE_BIAS);
+        }
+        let lock_holds = self.ae_af_lock.iter().flat_map(|lock| &lock.holds);
+        for control in self
+            .auto_holds
+            .keys()
+            .chain(lock_holds.map(|(control, _)| control))
+        {
+            for id in control.control_ids() {
+                controls.remove(id);
+            }
+        }
+        let Some(device_path) = self.get_v4l2_device_path() else {
+            return Task::none();
+        };
+        let focus_path = self.get_focus_device_path();
+
+        Task::perform(
+            async move {
+                v4l2_controls::reapply_controls(&device_path, focus_path.as_deref(), &controls)
+            },
+            move |drifted| {
+                cosmic::Action::App(Message::CameraControlsReapplied {
+                    drifted,
+                    on_connect,
+                })
+            },
+        )
+    }
+
+    pub(crate) fn handle_camera_controls_reapplied(
+        &mut self,
+        drifted: Vec<(u32, i32)>,
+        on_connect: bool,
+    ) -> Task<cosmic::Action<Message>> {
+        let now = Instant::now();
+
+        // Store what the driver actually accepted so values rounded to the
+        // control's step don't show up as drift on every check
+        for &(control_id, actual) in &drifted {
+            self.remember_control(control_id, actual);
+        }
+
+        if on_connect {
+            // A fresh connection starts without any history of overrides
+            self.control_overrides.clear();
+            if drifted.is_empty() {
+                return Task::none();
+            }
+            info!(count = drifted.len(), "Restored stored camera controls");
+            // Refresh the pickers with the restored values
+            return self.query_exposure_controls_task();
+        }
+
+        if drifted.is_empty() {
+            self.control_overrides.expire(now);
+            return Task::none();
+        }
+
+        self.control_overrides.record(now);
+        warn!(
+            count = drifted.len(),
+            conflict = self.control_overrides.is_conflicting(),
+            "Camera controls were changed by another application, re-applied"
+        );
         Task::none()
     }
 
+    // =========================================================================
+    // Exposure/focus OSD
+    // =========================================================================
+
+    pub(crate) fn handle_toggle_exposure_osd(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        self.config.exposure_osd = !self.config.exposure_osd;
+        info!(enabled = self.config.exposure_osd, "Exposure OSD toggled");
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save exposure OSD setting");
+        }
+        // Don't show another camera's (or a stale) reading when it comes back
+        self.osd_values = None;
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_false_color(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        self.config.false_color = !self.config.false_color;
+        info!(enabled = self.config.false_color, "False color toggled");
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save false color setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_color_vision_assist(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::ColorVisionAssist;
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        if let Some(&assist) = ColorVisionAssist::ALL.get(index) {
+            self.config.color_vision_assist = assist;
+            info!(?assist, "Selected color vision assist");
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save color vision assist setting");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_preview_scaling(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::PreviewScaling;
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        if let Some(&scaling) = PreviewScaling::ALL.get(index) {
+            self.config.preview_scaling = scaling;
+            info!(?scaling, "Selected preview scaling");
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save preview scaling setting");
+            }
+        }
+        Task::none()
+    }
+
+    /// Values for the exposure OSD: those libcamera reported with the
+    /// current frame, else the last V4L2 read-back
+    pub(crate) fn exposure_osd_values(&self) -> Option<crate::app::exposure_osd::OsdValues> {
+        use crate::app::exposure_osd::OsdValues;
+
+        if !self.config.exposure_osd {
+            return None;
+        }
+        let values = match self
+            .current_frame
+            .as_ref()
+            .and_then(|frame| frame.libcamera_metadata.as_ref())
+        {
+            Some(metadata) => OsdValues::from_metadata(metadata),
+            None => self.osd_values?,
+        };
+        (!values.is_empty()).then_some(values)
+    }
+
     // =========================================================================
     // V4L2 Helpers (used by exposure and color handlers)
     // =========================================================================
@@ -652,4 +1335,175 @@ impl AppModel {
             Task::none()
         }
     }
+
+    // =========================================================================
+    // Extension Unit Controls
+    // =========================================================================
+
+    /// Create a task that discovers the UVC extension unit controls of the
+    /// current camera and reads their current values
+    pub(crate) fn query_extension_units_task(&self) -> Task<cosmic::Action<Message>> {
+        use crate::app::extension_units_page::format_hex_bytes;
+        use crate::app::state::XuControlState;
+
+        let Some(device_path) = self.get_v4l2_device_path() else {
+            return Task::done(cosmic::Action::App(Message::ExtensionUnitsQueried(
+                Vec::new(),
+            )));
+        };
+
+        Task::perform(
+            async move {
+                let mut controls = Vec::new();
+                for unit in v4l2_controls::discover_extension_units(&device_path) {
+                    let guid = unit.guid_string();
+                    for &selector in &unit.selectors {
+                        let Some(info) =
+                            v4l2_controls::query_xu_control(&device_path, unit.unit_id, selector)
+                        else {
+                            continue;
+                        };
+                        let value = info
+                            .can_get
+                            .then(|| {
+                                v4l2_controls::get_xu_control(
+                                    &device_path,
+                                    info.unit_id,
+                                    info.selector,
+                                    info.len,
+                                )
+                                .ok()
+                            })
+                            .flatten();
+                        controls.push(XuControlState {
+                            guid: guid.clone(),
+                            edit: value.as_deref().map(format_hex_bytes).unwrap_or_default(),
+                            info,
+                            value,
+                            error: None,
+                        });
+                    }
+                }
+                controls
+            },
+            |controls| cosmic::Action::App(Message::ExtensionUnitsQueried(controls)),
+        )
+    }
+
+    pub(crate) fn handle_extension_units_queried(
+        &mut self,
+        controls: Vec<crate::app::state::XuControlState>,
+    ) -> Task<cosmic::Action<Message>> {
+        info!(count = controls.len(), "Extension unit controls queried");
+        self.xu_controls = controls;
+        Task::none()
+    }
+
+    pub(crate) fn handle_xu_edit_value(
+        &mut self,
+        index: usize,
+        text: String,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(control) = self.xu_controls.get_mut(index) {
+            control.edit = text;
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_xu_apply_value(&mut self, index: usize) -> Task<cosmic::Action<Message>> {
+        use crate::app::extension_units_page::parse_hex_bytes;
+
+        let Some(control) = self.xu_controls.get_mut(index) else {
+            return Task::none();
+        };
+        match parse_hex_bytes(&control.edit) {
+            Some(data) if data.len() == control.info.len as usize => {
+                self.write_xu_control(index, data)
+            }
+            _ => {
+                control.error = Some(format!("Expected {} hex bytes", control.info.len));
+                Task::none()
+            }
+        }
+    }
+
+    pub(crate) fn handle_xu_apply_preset(
+        &mut self,
+        index: usize,
+        value: u8,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(control) = self.xu_controls.get(index) else {
+            return Task::none();
+        };
+        let Some(preset) = v4l2_controls::XuPreset::find(&control.guid, control.info.selector)
+        else {
+            return Task::none();
+        };
+
+        // Presets only own one byte; keep the rest of the payload as read
+        let mut data = control
+            .value
+            .clone()
+            .unwrap_or_else(|| vec![0; control.info.len as usize]);
+        let Some(byte) = data.get_mut(preset.offset) else {
+            return Task::none();
+        };
+        *byte = value;
+        self.write_xu_control(index, data)
+    }
+
+    pub(crate) fn handle_xu_control_applied(
+        &mut self,
+        index: usize,
+        result: Result<Vec<u8>, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::app::extension_units_page::format_hex_bytes;
+
+        let Some(control) = self.xu_controls.get_mut(index) else {
+            return Task::none();
+        };
+        match result {
+            Ok(data) => {
+                control.edit = format_hex_bytes(&data);
+                control.value = Some(data);
+                control.error = None;
+            }
+            Err(e) => control.error = Some(e),
+        }
+        Task::none()
+    }
+
+    /// Write an extension unit payload and read it back
+    fn write_xu_control(&self, index: usize, data: Vec<u8>) -> Task<cosmic::Action<Message>> {
+        let (Some(device_path), Some(control)) =
+            (self.get_v4l2_device_path(), self.xu_controls.get(index))
+        else {
+            return Task::none();
+        };
+        let info = control.info.clone();
+
+        debug!(
+            unit = info.unit_id,
+            selector = info.selector,
+            ?data,
+            "Writing extension unit control"
+        );
+        Task::perform(
+            async move {
+                v4l2_controls::set_xu_control(&device_path, info.unit_id, info.selector, &data)?;
+                // The device may clamp or ignore parts of the payload
+                if info.can_get {
+                    v4l2_controls::get_xu_control(
+                        &device_path,
+                        info.unit_id,
+                        info.selector,
+                        info.len,
+                    )
+                } else {
+                    Ok(data)
+                }
+            },
+            move |result| cosmic::Action::App(Message::XuControlApplied(index, result)),
+        )
+    }
 }
@@ -17,12 +17,133 @@ impl AppModel {
     // Format Selection Handlers
     // =========================================================================
 
-    /// Get the current camera's sensor rotation
+    /// Get the current camera's rotation: the sensor's, plus the user's
+    /// correction for this camera
     pub(crate) fn current_camera_rotation(&self) -> crate::backends::camera::types::SensorRotation {
         self.available_cameras
             .get(self.current_camera_index)
             .map(|c| c.rotation)
             .unwrap_or_default()
+            .compose(self.camera_orientation().rotation())
+    }
+
+    /// Rotation and flips the user set for the current camera
+    pub(crate) fn camera_orientation(&self) -> crate::config::CameraOrientation {
+        self.available_cameras
+            .get(self.current_camera_index)
+            .and_then(|camera| self.config.camera_orientations.get(&camera.path))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Change the current camera's orientation with `change` and save it
+    fn update_camera_orientation(
+        &mut self,
+        change: impl FnOnce(&mut crate::config::CameraOrientation),
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(path) = self
+            .available_cameras
+            .get(self.current_camera_index)
+            .map(|camera| camera.path.clone())
+        else {
+            return Task::none();
+        };
+        let mut orientation = self.camera_orientation();
+        change(&mut orientation);
+        info!(camera = %path, ?orientation, "Camera orientation changed");
+        if orientation == Default::default() {
+            self.config.camera_orientations.remove(&path);
+        } else {
+            self.config.camera_orientations.insert(path, orientation);
+        }
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save camera orientation");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_camera_rotation(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(&rotation) = crate::config::CameraOrientation::ROTATIONS.get(index) else {
+            return Task::none();
+        };
+        self.update_camera_orientation(|orientation| orientation.rotation = rotation)
+    }
+
+    pub(crate) fn handle_toggle_camera_mirror(&mut self) -> Task<cosmic::Action<Message>> {
+        self.update_camera_orientation(|orientation| orientation.mirror = !orientation.mirror)
+    }
+
+    pub(crate) fn handle_toggle_camera_flip(&mut self) -> Task<cosmic::Action<Message>> {
+        self.update_camera_orientation(|orientation| orientation.flip = !orientation.flip)
+    }
+
+    /// Rotation correction for captured output: the sensor rotation plus
+    /// how the device is held. The preview keeps the sensor rotation alone:
+    /// it shows the scene as seen through the screen, and only the saved
+    /// output is turned upright.
+    pub(crate) fn capture_rotation(&self) -> crate::backends::camera::types::SensorRotation {
+        self.current_camera_rotation()
+            .compose(self.device_orientation)
+    }
+
+    /// [`Self::capture_rotation`] for saved photos and recordings, split into
+    /// the part turned into the pixels and the part left to viewers through
+    /// the EXIF or container orientation tag. Unless the user wants it baked
+    /// in, the device orientation goes into the tag and the picture is
+    /// stored as the preview showed it. A mirrored capture is baked whole:
+    /// its flip would otherwise end up on the wrong axis once turned.
+    pub(crate) fn saved_rotation(
+        &self,
+    ) -> (
+        crate::backends::camera::types::SensorRotation,
+        crate::backends::camera::types::SensorRotation,
+    ) {
+        if self.config.bake_rotation || self.should_mirror_captures() {
+            (self.capture_rotation(), Default::default())
+        } else {
+            (self.current_camera_rotation(), self.device_orientation)
+        }
+    }
+
+    pub(crate) fn handle_toggle_bake_rotation(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.bake_rotation = !self.config.bake_rotation;
+        info!(
+            bake = self.config.bake_rotation,
+            "Toggled baking the rotation into captures"
+        );
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save rotation setting");
+        }
+        // A pre-built recording pipeline carries the old orientation tag
+        self.sync_recorder_warmup();
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_adaptive_preview(&mut self) -> Task<cosmic::Action<Message>> {
+        let was_reduced = self.preview_format() != self.active_format;
+        self.config.adaptive_preview = !self.config.adaptive_preview;
+        info!(
+            enabled = self.config.adaptive_preview,
+            "Toggled adaptive preview resolution"
+        );
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save adaptive preview setting");
+        }
+        // Measuring starts over when it's turned back on
+        self.adaptive_preview.reset();
+        if was_reduced {
+            self.start_blur_transition();
+        }
+        Task::none()
     }
 
     /// Cycle to the next or previous mode in the ordered mode list.
@@ -309,52 +430,31 @@ impl AppModel {
         self.query_exposure_controls_task()
     }
 
-    pub(crate) fn handle_picker_select_resolution(
-        &mut self,
-        width: u32,
-    ) -> Task<cosmic::Action<Message>> {
-        self.picker_selected_resolution = Some(width);
-        let current_fps = self.active_format.as_ref().and_then(|f| f.framerate);
-
-        let matching_formats: Vec<(usize, &crate::backends::camera::types::CameraFormat)> = self
-            .available_formats
-            .iter()
-            .enumerate()
-            .filter(|(_, fmt)| fmt.width == width)
-            .collect();
-
-        if !matching_formats.is_empty() {
-            let format_to_apply = if let Some(target_fps) = current_fps {
-                let target_int = target_fps.as_int() as i32;
-                matching_formats
-                    .iter()
-                    .find(|(_, fmt)| fmt.framerate == Some(target_fps))
-                    .or_else(|| {
-                        matching_formats
-                            .iter()
-                            .filter(|(_, fmt)| fmt.framerate.is_some())
-                            .min_by_key(|(_, fmt)| {
-                                let fps = fmt.framerate.unwrap().as_int() as i32;
-                                (fps - target_int).abs()
-                            })
-                    })
-                    .or_else(|| matching_formats.first())
-            } else {
-                matching_formats.first()
-            };
+    /// Re-read the current camera's USB connection from sysfs
+    pub(crate) fn refresh_usb_link(&mut self) {
+        use crate::backends::camera::usb_bandwidth::UsbLink;
 
-            if let Some(&(index, _)) = format_to_apply {
-                self.active_format = self.available_formats.get(index).cloned();
+        self.usb_link = self
+            .get_v4l2_device_path()
+            .and_then(|path| UsbLink::for_device(&path));
+    }
 
-                if let Some(fmt) = &self.active_format {
-                    info!(width, format = %fmt, "Applied resolution with framerate preservation");
-                    self.photo_aspect_ratio = self.config.photo_aspect_ratio;
-                }
-                self.zoom_level = 1.0; // Reset zoom when changing resolution
-                self.save_settings();
-                self.start_blur_transition();
-            }
-        }
+    /// Bandwidth advice for the active format over the current camera's
+    /// USB connection
+    pub(crate) fn active_format_usb_advice(
+        &self,
+    ) -> Option<crate::backends::camera::usb_bandwidth::BandwidthAdvice> {
+        crate::backends::camera::usb_bandwidth::advise(
+            self.active_format.as_ref()?,
+            self.usb_link.as_ref()?,
+        )
+    }
+
+    pub(crate) fn handle_format_picker_search(
+        &mut self,
+        query: String,
+    ) -> Task<cosmic::Action<Message>> {
+        self.format_picker_query = query;
         Task::none()
     }
 
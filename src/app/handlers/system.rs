@@ -8,7 +8,20 @@
 use crate::app::state::{AppModel, FilterType, Message, RecordingState};
 use cosmic::Task;
 use cosmic::cosmic_config::CosmicConfigEntry;
-use tracing::{error, info};
+use tracing::{debug, error, info, warn};
+
+/// How long the sync test records before the flash, so the camera and
+/// microphone have settled and the clip has a dark, quiet start
+const AV_SYNC_LEAD_MS: u64 = 1500;
+
+/// How long the sync test flash stays on
+const AV_SYNC_FLASH_MS: u64 = 300;
+
+/// How long the sync test keeps recording after the flash
+const AV_SYNC_TAIL_MS: u64 = 1000;
+
+/// When a sync test whose recording never got going is given up
+const AV_SYNC_TIMEOUT_MS: u64 = 10_000;
 
 impl AppModel {
     // =========================================================================
@@ -49,17 +62,352 @@ impl AppModel {
         &mut self,
         data: Option<crate::storage::GalleryThumbnailData>,
     ) -> Task<cosmic::Action<Message>> {
+        let mut task = Task::none();
         if let Some((handle, rgba, width, height, path)) = data {
+            // A capture made since startup, not a processing preview
+            if self.gallery_loaded
+                && self.gallery_thumbnail_path.as_ref() != Some(&path)
+                && !crate::storage::is_pending_photo(&path)
+            {
+                let share = if self.config.share_after_capture {
+                    self.offer_share(path.clone())
+                } else {
+                    Task::none()
+                };
+                task = Task::batch([share, self.find_new_uploads()]);
+            }
             self.gallery_thumbnail = Some(handle);
             self.gallery_thumbnail_rgba = Some((rgba, width, height));
             self.last_media_path = Some(path.display().to_string());
+            self.gallery_thumbnail_path = Some(path);
         } else {
             self.gallery_thumbnail = None;
             self.gallery_thumbnail_rgba = None;
+            self.gallery_thumbnail_path = None;
+        }
+        if !self.gallery_loaded {
+            self.gallery_loaded = true;
+            self.upload_since = Some(std::time::SystemTime::now());
+        }
+        task
+    }
+
+    // =========================================================================
+    // Share Handlers
+    // =========================================================================
+
+    /// Show the collapsed "Share" prompt for a new capture for a few seconds
+    fn offer_share(&mut self, path: std::path::PathBuf) -> Task<cosmic::Action<Message>> {
+        // Don't collapse a menu the user is choosing from
+        if self.share_menu.as_ref().is_some_and(|menu| menu.expanded) {
+            return Task::none();
+        }
+        self.share_menu = Some(crate::app::state::ShareMenu {
+            path: path.clone(),
+            expanded: false,
+        });
+        Self::delay_task(
+            crate::constants::timing::SHARE_PROMPT_MS,
+            Message::SharePromptExpired(path),
+        )
+    }
+
+    pub(crate) fn handle_show_share_menu(
+        &mut self,
+        path: Option<std::path::PathBuf>,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(path) = path.or_else(|| self.last_media_path.as_ref().map(Into::into)) else {
+            return Task::none();
+        };
+        self.load_share_targets();
+        self.share_menu = Some(crate::app::state::ShareMenu {
+            path,
+            expanded: true,
+        });
+        Task::none()
+    }
+
+    pub(crate) fn handle_share(
+        &mut self,
+        target: crate::share::ShareTarget,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::share::ShareTarget;
+
+        let Some(menu) = self.share_menu.take() else {
+            return Task::none();
+        };
+        info!(path = %menu.path.display(), ?target, "Sharing capture");
+        let done = |result| cosmic::Action::App(Message::ShareDone(result));
+        match target {
+            ShareTarget::OpenWith => Task::perform(crate::share::open_with(menu.path), done),
+            ShareTarget::Email => Task::perform(crate::share::email(menu.path), done),
+            ShareTarget::Custom(index) => {
+                let result = match self.share_targets.targets.get(index) {
+                    Some(custom) => crate::share::run_custom(custom, &menu.path),
+                    None => Err(format!("No share target {index}")),
+                };
+                self.handle_share_done(result)
+            }
+        }
+    }
+
+    pub(crate) fn handle_share_done(
+        &mut self,
+        result: Result<(), String>,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Err(e) = result {
+            warn!(error = %e, "Failed to share capture");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_dismiss_share_menu(&mut self) -> Task<cosmic::Action<Message>> {
+        self.share_menu = None;
+        Task::none()
+    }
+
+    pub(crate) fn handle_share_prompt_expired(
+        &mut self,
+        path: std::path::PathBuf,
+    ) -> Task<cosmic::Action<Message>> {
+        if self
+            .share_menu
+            .as_ref()
+            .is_some_and(|menu| !menu.expanded && menu.path == path)
+        {
+            self.share_menu = None;
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_share_after_capture(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.share_after_capture = !self.config.share_after_capture;
+        info!(
+            enabled = self.config.share_after_capture,
+            "Toggled share prompt after capture"
+        );
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save share prompt setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_edit_share_targets(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(path) = crate::share::targets_path() else {
+            return Task::none();
+        };
+        if let Err(e) = crate::share::ensure_targets_file(&path) {
+            error!(error = %e, "Failed to create share targets file");
+            self.share_targets_error = Some(e);
+            return Task::none();
+        }
+        info!(path = %path.display(), "Opening share targets file");
+        if let Err(e) = open::that_detached(&path) {
+            error!(error = %e, "Failed to open share targets file");
+        }
+        self.load_share_targets();
+        Task::none()
+    }
+
+    // =========================================================================
+    // Upload Handlers
+    // =========================================================================
+
+    /// Look for captures saved since startup that haven't been uploaded yet.
+    /// Scanning the folders rather than taking the latest capture also picks
+    /// up every shot of a burst.
+    fn find_new_uploads(&self) -> Task<cosmic::Action<Message>> {
+        let Some(since) = self.upload_since else {
+            return Task::none();
+        };
+        if !self.config.upload.enabled {
+            return Task::none();
+        }
+        let dirs = vec![
+            crate::app::get_photo_directory(&self.config.save_folder_name),
+            crate::app::get_video_directory(&self.config.save_folder_name),
+        ];
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    crate::storage::thumbnails::list_captures(&dirs)
+                        .into_iter()
+                        .take_while(|(_, modified)| *modified >= since)
+                        .map(|(path, _)| path)
+                        .filter(|path| !crate::storage::is_pending_photo(path))
+                        .collect()
+                })
+                .await
+                .unwrap_or_default()
+            },
+            |paths| cosmic::Action::App(Message::QueueUploads(paths)),
+        )
+    }
+
+    pub(crate) fn handle_queue_uploads(
+        &mut self,
+        paths: Vec<std::path::PathBuf>,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::upload::UploadStatus;
+
+        let recording = self.recording.file_path().map(std::path::PathBuf::from);
+        let mut tasks = Vec::new();
+        for path in paths {
+            // Still being written
+            if recording.as_ref() == Some(&path) || self.upload_status.contains_key(&path) {
+                continue;
+            }
+            self.upload_status
+                .insert(path.clone(), UploadStatus::Uploading);
+            tasks.push(self.upload_task(path));
+        }
+        if !tasks.is_empty() {
+            info!(count = tasks.len(), "Queued captures for upload");
+        }
+        Task::batch(tasks)
+    }
+
+    fn upload_task(&self, path: std::path::PathBuf) -> Task<cosmic::Action<Message>> {
+        let settings = self.config.upload.clone();
+        Task::perform(
+            crate::upload::upload(settings, path.clone()),
+            move |result| cosmic::Action::App(Message::UploadFinished(path.clone(), result)),
+        )
+    }
+
+    pub(crate) fn handle_upload_finished(
+        &mut self,
+        path: std::path::PathBuf,
+        result: Result<(), String>,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::upload::UploadStatus;
+
+        let status = match result {
+            Ok(()) => UploadStatus::Uploaded,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Upload failed");
+                UploadStatus::Failed(e)
+            }
+        };
+        self.upload_status.insert(path, status);
+        Task::none()
+    }
+
+    pub(crate) fn handle_retry_failed_uploads(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::upload::UploadStatus;
+
+        let failed: Vec<_> = self
+            .upload_status
+            .iter()
+            .filter(|(_, status)| matches!(status, UploadStatus::Failed(_)))
+            .map(|(path, _)| path.clone())
+            .collect();
+        let tasks: Vec<_> = failed
+            .into_iter()
+            .map(|path| {
+                self.upload_status
+                    .insert(path.clone(), UploadStatus::Uploading);
+                self.upload_task(path)
+            })
+            .collect();
+        Task::batch(tasks)
+    }
+
+    pub(crate) fn handle_toggle_auto_upload(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.upload.enabled = !self.config.upload.enabled;
+        self.upload_form.settings.enabled = self.config.upload.enabled;
+        info!(enabled = self.config.upload.enabled, "Toggled auto upload");
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save auto upload setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_upload_provider(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(&provider) = crate::config::UploadProvider::ALL.get(index) {
+            self.upload_form.settings.provider = provider;
+            self.upload_form.status = None;
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_upload_form_input(
+        &mut self,
+        field: crate::app::state::UploadField,
+        text: String,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::app::state::UploadField;
+
+        let form = &mut self.upload_form;
+        match field {
+            UploadField::Url => form.settings.url = text,
+            UploadField::Bucket => form.settings.bucket = text,
+            UploadField::Region => form.settings.region = text,
+            UploadField::Username => form.settings.username = text,
+            UploadField::Secret => form.secret = text,
         }
+        form.status = None;
         Task::none()
     }
 
+    pub(crate) fn handle_save_upload_settings(&mut self) -> Task<cosmic::Action<Message>> {
+        let mut settings = self.upload_form.settings.clone();
+        settings.url = settings.url.trim().to_string();
+        settings.enabled = self.config.upload.enabled;
+        self.config.upload = settings.clone();
+        info!(provider = ?settings.provider, url = %settings.url, "Saved upload settings");
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save upload settings");
+        }
+
+        let secret = std::mem::take(&mut self.upload_form.secret);
+        if secret.is_empty() {
+            return self.handle_upload_settings_saved(Ok(()));
+        }
+        Task::perform(crate::upload::store_secret(settings, secret), |result| {
+            cosmic::Action::App(Message::UploadSettingsSaved(result))
+        })
+    }
+
+    pub(crate) fn handle_upload_settings_saved(
+        &mut self,
+        result: Result<(), String>,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Err(e) = &result {
+            error!(error = %e, "Failed to store upload secret");
+        }
+        self.upload_form.status = Some(result);
+        Task::none()
+    }
+
+    /// Re-read the custom share targets
+    pub(crate) fn load_share_targets(&mut self) {
+        let Some(path) = crate::share::targets_path() else {
+            self.share_targets_error = Some("No config directory".to_string());
+            return;
+        };
+        match crate::share::ShareTargets::load(&path) {
+            Ok(targets) => {
+                debug!(count = targets.targets.len(), "Loaded share targets");
+                self.share_targets = targets;
+                self.share_targets_error = None;
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to load share targets");
+                self.share_targets_error = Some(e);
+            }
+        }
+    }
+
     // =========================================================================
     // Filter Handlers
     // =========================================================================
@@ -68,6 +416,16 @@ impl AppModel {
         &mut self,
         filter: FilterType,
     ) -> Task<cosmic::Action<Message>> {
+        self.set_filter(filter);
+
+        // Close the filter drawer after selection
+        self.core.window.show_context = false;
+
+        Task::none()
+    }
+
+    /// Switch the live filter for the preview, the recorder and the virtual camera
+    fn set_filter(&mut self, filter: FilterType) {
         self.selected_filter = filter;
         // Update the shared atomic so the recording pusher picks up the change
         self.recording_filter_code.store(
@@ -80,10 +438,107 @@ impl AppModel {
         if self.virtual_camera.is_streaming() {
             self.virtual_camera.set_filter(filter);
         }
+    }
 
-        // Close the filter drawer after selection
-        self.core.window.show_context = false;
+    pub(crate) fn handle_import_lut(&self) -> Task<cosmic::Action<Message>> {
+        info!("Opening file picker for LUT import");
+
+        Task::perform(
+            async {
+                use crate::app::filter_picker::lut_library;
+                use rfd::AsyncFileDialog;
+
+                let file = AsyncFileDialog::new()
+                    .add_filter(
+                        crate::fl!("filters-lut-file-filter-name"),
+                        &[lut_library::LUT_EXTENSION],
+                    )
+                    .pick_file()
+                    .await?;
+
+                let path = file.path().to_path_buf();
+                Some(
+                    tokio::task::spawn_blocking(move || lut_library::import(&path))
+                        .await
+                        .unwrap_or_else(|e| Err(format!("LUT import task failed: {e}"))),
+                )
+            },
+            |result| cosmic::Action::App(Message::LutImported(result)),
+        )
+    }
+
+    pub(crate) fn handle_lut_imported(
+        &mut self,
+        result: Option<Result<String, String>>,
+    ) -> Task<cosmic::Action<Message>> {
+        match result {
+            None => Task::none(),
+            Some(Ok(name)) => {
+                info!(name = %name, "LUT imported");
+                self.imported_luts = crate::app::filter_picker::lut_library::list();
+                self.handle_select_lut(name)
+            }
+            Some(Err(err)) => {
+                warn!(%err, "Failed to import LUT");
+                self.lut_error = Some(err);
+                Task::none()
+            }
+        }
+    }
+
+    pub(crate) fn handle_select_lut(&self, name: String) -> Task<cosmic::Action<Message>> {
+        Task::perform(
+            async move {
+                let lookup = name.clone();
+                let result = tokio::task::spawn_blocking(move || {
+                    crate::app::filter_picker::lut_library::load(&lookup)
+                })
+                .await
+                .unwrap_or_else(|e| Err(format!("LUT load task failed: {e}")))
+                .map(std::sync::Arc::new);
+                (name, result)
+            },
+            |(name, result)| cosmic::Action::App(Message::LutLoaded(name, result)),
+        )
+    }
+
+    pub(crate) fn handle_lut_loaded(
+        &mut self,
+        name: String,
+        result: Result<std::sync::Arc<crate::shaders::CubeLut>, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        match result {
+            Ok(lut) => {
+                info!(name = %name, size = lut.size, "LUT applied");
+                crate::shaders::set_active_lut(Some(&lut));
+                self.selected_lut = Some(name);
+                self.lut_error = None;
+                self.handle_select_filter(FilterType::Lut)
+            }
+            Err(err) => {
+                warn!(name = %name, %err, "Failed to load LUT");
+                self.lut_error = Some(err);
+                Task::none()
+            }
+        }
+    }
 
+    pub(crate) fn handle_remove_lut(&mut self, name: String) -> Task<cosmic::Action<Message>> {
+        if let Err(err) = crate::app::filter_picker::lut_library::remove(&name) {
+            warn!(name = %name, %err, "Failed to remove LUT");
+            self.lut_error = Some(err);
+            return Task::none();
+        }
+        info!(name = %name, "LUT removed");
+        self.imported_luts = crate::app::filter_picker::lut_library::list();
+
+        if self.selected_lut.as_deref() == Some(name.as_str()) {
+            self.selected_lut = None;
+            crate::shaders::set_active_lut(None);
+            if self.selected_filter == FilterType::Lut {
+                self.set_filter(FilterType::Standard);
+            }
+        }
         Task::none()
     }
 
@@ -225,6 +680,7 @@ impl AppModel {
             info!(index, "Selected audio device index");
             self.current_audio_device_index = index;
         }
+        self.sync_audio_offset();
         self.sync_audio_probe();
         Task::none()
     }
@@ -307,6 +763,7 @@ impl AppModel {
             self.current_audio_device_index = 0;
         }
 
+        self.sync_audio_offset();
         self.sync_audio_probe();
         Task::none()
     }
@@ -371,64 +828,921 @@ impl AppModel {
         Task::none()
     }
 
-    pub(crate) fn handle_select_audio_encoder(
-        &mut self,
-        index: usize,
-    ) -> Task<cosmic::Action<Message>> {
-        use crate::config::AudioEncoder;
+    pub(crate) fn handle_toggle_record_system_audio(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.recording.is_recording() {
+            return Task::none();
+        }
+
         use cosmic::cosmic_config::CosmicConfigEntry;
 
-        if index < AudioEncoder::ALL.len() {
-            let encoder = AudioEncoder::ALL[index];
-            info!(?encoder, "Selected audio encoder");
-            self.config.audio_encoder = encoder;
+        self.config.record_system_audio = !self.config.record_system_audio;
+        info!(
+            record_system_audio = self.config.record_system_audio,
+            "Toggled record system audio"
+        );
 
-            if let Some(handler) = self.config_handler.as_ref()
-                && let Err(err) = self.config.write_entry(handler)
-            {
-                error!(?err, "Failed to save audio encoder selection");
-            }
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save record system audio setting");
         }
+        self.sync_audio_probe();
         Task::none()
     }
 
-    pub(crate) fn handle_toggle_save_burst_raw(&mut self) -> Task<cosmic::Action<Message>> {
-        self.config.save_burst_raw = !self.config.save_burst_raw;
-        info!(
-            save_burst_raw = self.config.save_burst_raw,
-            "Toggled save burst raw frames"
-        );
+    pub(crate) fn handle_set_audio_gain(&mut self, gain_db: i32) -> Task<cosmic::Action<Message>> {
+        use crate::pipelines::audio_level::{MAX_GAIN_DB, MIN_GAIN_DB};
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        if self.recording.is_recording() {
+            return Task::none();
+        }
+
+        let gain_db = gain_db.clamp(MIN_GAIN_DB, MAX_GAIN_DB);
+        if gain_db == self.config.audio_gain_db {
+            return Task::none();
+        }
+        self.config.audio_gain_db = gain_db;
+        debug!(gain_db, "Set microphone gain");
 
         if let Some(handler) = self.config_handler.as_ref()
             && let Err(err) = self.config.write_entry(handler)
         {
-            error!(?err, "Failed to save burst raw setting");
+            error!(?err, "Failed to save microphone gain");
         }
+        self.sync_audio_probe();
         Task::none()
     }
 
-    pub(crate) fn handle_select_composition_guide(
+    /// Unlike the other audio settings this one stays live while recording:
+    /// the recorder re-times microphone buffers from the shared value.
+    pub(crate) fn handle_set_audio_sync_offset(
         &mut self,
-        index: usize,
+        offset_ms: i32,
     ) -> Task<cosmic::Action<Message>> {
-        use crate::config::CompositionGuide;
-        if let Some(&guide) = CompositionGuide::ALL.get(index) {
-            self.config.composition_guide = guide;
-            info!(?guide, "Selected composition guide");
+        use crate::pipelines::audio_level::MAX_SYNC_OFFSET_MS;
 
-            if let Some(handler) = self.config_handler.as_ref()
-                && let Err(err) = self.config.write_entry(handler)
-            {
-                error!(?err, "Failed to save composition guide setting");
-            }
+        let offset_ms = offset_ms.clamp(-MAX_SYNC_OFFSET_MS, MAX_SYNC_OFFSET_MS);
+        let key = self.audio_sync_key();
+        let current = self.config.audio_sync_offsets.get(&key).copied();
+        if current.unwrap_or(0) == offset_ms {
+            return Task::none();
+        }
+        if offset_ms == 0 {
+            self.config.audio_sync_offsets.remove(&key);
+        } else {
+            self.config.audio_sync_offsets.insert(key, offset_ms);
+        }
+        debug!(offset_ms, "Set audio sync offset");
+        self.sync_audio_offset();
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save audio sync offset");
         }
         Task::none()
     }
 
-    pub(crate) fn handle_reset_all_settings(&mut self) -> Task<cosmic::Action<Message>> {
-        info!("Resetting all settings to defaults");
-        // Snapshot the pre-reset animated values so the fit/fill change
-        // animates rather than snaps when reset flips preview_fit_to_view
+    // =========================================================================
+    // A/V sync test
+    // =========================================================================
+
+    /// Record a few seconds with the window black, flashing it white and
+    /// beeping once in the middle. The clip is measured and then deleted.
+    pub(crate) fn handle_start_av_sync_test(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::app::state::AvSyncTestState;
+
+        if !self.config.record_audio
+            || self.recording.is_recording()
+            || self.av_sync_test.is_running()
+            || self.current_frame.is_none()
+        {
+            return Task::none();
+        }
+        info!("Starting A/V sync test");
+        // The drawer would cover part of the flashing window
+        self.close_all_pickers();
+        self.core.window.show_context = false;
+        self.av_sync_test = AvSyncTestState::Recording { flashing: false };
+        self.sync_audio_probe();
+        Task::batch([
+            Task::done(cosmic::Action::App(Message::StartRecordingAfterDelay)),
+            Self::delay_task(AV_SYNC_TIMEOUT_MS, Message::AvSyncStop),
+        ])
+    }
+
+    /// The sync test clip started recording: schedule the flash
+    pub(crate) fn av_sync_recording_started() -> Task<cosmic::Action<Message>> {
+        Self::delay_task(AV_SYNC_LEAD_MS, Message::AvSyncFlash)
+    }
+
+    pub(crate) fn handle_av_sync_flash(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::app::state::AvSyncTestState;
+
+        let AvSyncTestState::Recording { flashing } = &mut self.av_sync_test else {
+            return Task::none();
+        };
+        *flashing = true;
+        crate::sound::sync_beep();
+        Self::delay_task(AV_SYNC_FLASH_MS, Message::AvSyncFlashEnd)
+    }
+
+    pub(crate) fn handle_av_sync_flash_end(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::app::state::AvSyncTestState;
+
+        let AvSyncTestState::Recording { flashing } = &mut self.av_sync_test else {
+            return Task::none();
+        };
+        *flashing = false;
+        Self::delay_task(AV_SYNC_TAIL_MS, Message::AvSyncStop)
+    }
+
+    pub(crate) fn handle_av_sync_stop(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::app::state::AvSyncTestState;
+
+        if !matches!(self.av_sync_test, AvSyncTestState::Recording { .. }) {
+            return Task::none();
+        }
+        if self.recording.is_recording() {
+            return self.handle_toggle_recording();
+        }
+        warn!("A/V sync test recording did not start");
+        self.av_sync_test =
+            AvSyncTestState::Finished(Err("The test recording did not start".to_string()));
+        Task::none()
+    }
+
+    /// The sync test clip is finished: measure it on a blocking thread and
+    /// delete it
+    pub(crate) fn measure_av_sync_clip(
+        &mut self,
+        result: Result<String, crate::errors::AppError>,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::app::state::AvSyncTestState;
+
+        let path = match result {
+            Ok(path) => std::path::PathBuf::from(path),
+            Err(err) => {
+                self.av_sync_test = AvSyncTestState::Finished(Err(err.to_string()));
+                return Task::none();
+            }
+        };
+        self.av_sync_test = AvSyncTestState::Measuring;
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    let result = crate::pipelines::video::av_sync::measure_offset(&path);
+                    if let Err(err) = std::fs::remove_file(&path) {
+                        warn!(path = %path.display(), %err, "Failed to delete sync test clip");
+                    }
+                    result
+                })
+                .await
+                .unwrap_or_else(|e| Err(format!("Sync test measurement panicked: {e}")))
+            },
+            |result| cosmic::Action::App(Message::AvSyncMeasured(result)),
+        )
+    }
+
+    pub(crate) fn handle_av_sync_measured(
+        &mut self,
+        result: Result<i32, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        match &result {
+            Ok(correction_ms) => info!(correction_ms, "A/V sync test finished"),
+            Err(err) => warn!(%err, "A/V sync test failed"),
+        }
+        self.av_sync_test = crate::app::state::AvSyncTestState::Finished(result);
+        // Back to where the test was started, to show the result
+        self.handle_open_settings_page(crate::app::state::SettingsPage::Video)
+    }
+
+    pub(crate) fn handle_apply_av_sync_offset(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::app::state::AvSyncTestState;
+
+        let AvSyncTestState::Finished(Ok(correction_ms)) = self.av_sync_test else {
+            return Task::none();
+        };
+        self.av_sync_test = AvSyncTestState::Idle;
+        let offset_ms = self
+            .config
+            .audio_sync_offsets
+            .get(&self.audio_sync_key())
+            .copied()
+            .unwrap_or(0);
+        self.handle_set_audio_sync_offset(offset_ms + correction_ms)
+    }
+
+    pub(crate) fn handle_toggle_noise_suppression(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.recording.is_recording() {
+            return Task::none();
+        }
+
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        self.config.audio_noise_suppression = !self.config.audio_noise_suppression;
+        info!(
+            noise_suppression = self.config.audio_noise_suppression,
+            "Toggled noise suppression"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save noise suppression setting");
+        }
+        self.sync_audio_probe();
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_event_subtitles(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.recording.is_recording() {
+            return Task::none();
+        }
+
+        self.config.record_event_subtitles = !self.config.record_event_subtitles;
+        info!(
+            event_subtitles = self.config.record_event_subtitles,
+            "Toggled event subtitles"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save event subtitles setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_recording_timecode(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.recording.is_recording() {
+            return Task::none();
+        }
+
+        self.config.recording_timecode = !self.config.recording_timecode;
+        info!(
+            timecode = self.config.recording_timecode,
+            "Toggled recording timecode"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save recording timecode setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_timestamp_overlay(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.recording.is_recording() {
+            return Task::none();
+        }
+
+        self.config.recording_timestamp_overlay = !self.config.recording_timestamp_overlay;
+        info!(
+            timestamp_overlay = self.config.recording_timestamp_overlay,
+            "Toggled recording timestamp overlay"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save recording timestamp overlay setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_record_screen(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.recording.is_recording() || self.screen_cast_pending {
+            return Task::none();
+        }
+
+        self.config.record_screen = !self.config.record_screen;
+        info!(
+            record_screen = self.config.record_screen,
+            "Toggled screen recording"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save screen recording setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_audio_encoder(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::AudioEncoder;
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        if index < AudioEncoder::ALL.len() {
+            let encoder = AudioEncoder::ALL[index];
+            info!(?encoder, "Selected audio encoder");
+            self.config.audio_encoder = encoder;
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save audio encoder selection");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_video_container(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::VideoContainer;
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        if index < VideoContainer::ALL.len() {
+            let container = VideoContainer::ALL[index];
+            info!(?container, "Selected video container");
+            self.config.video_container = container;
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save video container selection");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_video_stabilization(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::VideoStabilization;
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        // The pusher picks the strength up when recording starts
+        if self.recording.is_recording() {
+            return Task::none();
+        }
+        if let Some(&strength) = VideoStabilization::ALL.get(index) {
+            info!(?strength, "Selected video stabilization");
+            self.config.video_stabilization = strength;
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save video stabilization setting");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_video_denoise(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::VideoDenoise;
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        // Recordings and the virtual camera pick it up when they start
+        if self.recording.is_recording() {
+            return Task::none();
+        }
+        if let Some(&strength) = VideoDenoise::ALL.get(index) {
+            info!(?strength, "Selected video denoise");
+            self.config.video_denoise = strength;
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save video denoise setting");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_video_denoise_auto(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        if self.recording.is_recording() {
+            return Task::none();
+        }
+        self.config.video_denoise_auto = !self.config.video_denoise_auto;
+        info!(
+            video_denoise_auto = self.config.video_denoise_auto,
+            "Toggled automatic video denoise"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save automatic video denoise setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_anamorphic_desqueeze(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::AnamorphicDesqueeze;
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        // The preview follows at once; recordings pick it up when they start
+        if let Some(&desqueeze) = AnamorphicDesqueeze::ALL.get(index) {
+            info!(?desqueeze, "Selected anamorphic desqueeze");
+            self.config.anamorphic_desqueeze = desqueeze;
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save anamorphic desqueeze setting");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_desqueeze_recordings(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        if self.recording.is_recording() {
+            return Task::none();
+        }
+        self.config.desqueeze_recordings = !self.config.desqueeze_recordings;
+        info!(
+            desqueeze_recordings = self.config.desqueeze_recordings,
+            "Toggled desqueezed recordings"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save desqueezed recordings setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_save_burst_raw(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.save_burst_raw = !self.config.save_burst_raw;
+        info!(
+            save_burst_raw = self.config.save_burst_raw,
+            "Toggled save burst raw frames"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save burst raw setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_super_resolution(&mut self) -> Task<cosmic::Action<Message>> {
+        // The running burst was captured for the other pipeline
+        if self.burst_mode.is_active() {
+            warn!("Cannot toggle super-resolution during active capture");
+            return Task::none();
+        }
+        self.config.super_resolution = !self.config.super_resolution;
+        info!(
+            super_resolution = self.config.super_resolution,
+            "Toggled super-resolution"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save super-resolution setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_product_mode(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.product_mode = !self.config.product_mode;
+        info!(
+            product_mode = self.config.product_mode,
+            "Toggled product mode"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save product mode setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_product_transparent_background(
+        &mut self,
+    ) -> Task<cosmic::Action<Message>> {
+        self.config.product_transparent_background = !self.config.product_transparent_background;
+        info!(
+            transparent = self.config.product_transparent_background,
+            "Toggled product transparent background"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save product background setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_photo_booth(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.photo_booth = !self.config.photo_booth;
+        info!(photo_booth = self.config.photo_booth, "Toggled photo booth");
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save photo booth setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_photo_booth_layout(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::PhotoBoothLayout;
+        if let Some(&layout) = PhotoBoothLayout::ALL.get(index) {
+            self.config.photo_booth_layout = layout;
+            info!(?layout, "Selected photo booth layout");
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save photo booth layout");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_photo_booth_shots(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(&shots) = crate::pipelines::photo::booth::SHOT_COUNTS.get(index) {
+            self.config.photo_booth_shots = shots;
+            info!(shots, "Selected photo booth shot count");
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save photo booth shot count");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_focus_stack(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.focus_stack.is_some() {
+            warn!("Cannot toggle focus stacking during a focus sweep");
+            return Task::none();
+        }
+        self.config.focus_stack = !self.config.focus_stack;
+        info!(
+            focus_stack = self.config.focus_stack,
+            "Toggled focus stacking"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save focus stacking setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_low_space_warning(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(&mb) = crate::storage::LOW_SPACE_WARNING_MB.get(index) {
+            self.config.low_space_warning_mb = mb;
+            info!(mb, "Selected low storage warning threshold");
+            // Re-evaluated at the next capture
+            self.storage_warning = None;
+            self.storage_warning_dismissed = false;
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save low storage warning threshold");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_focus_stack_steps(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::pipelines::photo::burst_mode::focus_stack::STEP_COUNTS;
+
+        if let Some(&steps) = STEP_COUNTS.get(index) {
+            self.config.focus_stack_steps = steps;
+            info!(steps, "Selected focus stack sweep length");
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save focus stack sweep length");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_photo_booth_countdown(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(&seconds) = crate::pipelines::photo::booth::COUNTDOWN_SECONDS.get(index) {
+            self.config.photo_booth_countdown = seconds;
+            info!(seconds, "Selected photo booth countdown");
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save photo booth countdown");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_photo_booth_filter_per_shot(
+        &mut self,
+    ) -> Task<cosmic::Action<Message>> {
+        self.config.photo_booth_filter_per_shot = !self.config.photo_booth_filter_per_shot;
+        info!(
+            per_shot = self.config.photo_booth_filter_per_shot,
+            "Toggled photo booth filter per shot"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save photo booth filter setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_rapid_burst(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.rapid_burst = !self.config.rapid_burst;
+        info!(enabled = self.config.rapid_burst, "Toggled rapid burst");
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save rapid burst setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_rapid_burst_max_shots(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(&shots) = crate::pipelines::photo::rapid_burst::MAX_SHOTS.get(index) {
+            self.config.rapid_burst_max_shots = shots;
+            info!(shots, "Selected rapid burst shot limit");
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save rapid burst shot limit");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_output_sharpening(
+        &mut self,
+        amount: u8,
+    ) -> Task<cosmic::Action<Message>> {
+        let amount = amount.min(crate::config::OutputSharpening::MAX_AMOUNT);
+        if amount == self.config.output_sharpening.amount {
+            return Task::none();
+        }
+        self.config.output_sharpening.amount = amount;
+        debug!(amount, "Set output sharpening");
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save output sharpening");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_burst_tuning(
+        &mut self,
+        tuning: crate::config::BurstTuning,
+    ) -> Task<cosmic::Action<Message>> {
+        let tuning = tuning.clamped();
+        if tuning == self.config.burst_tuning {
+            return Task::none();
+        }
+        self.config.burst_tuning = tuning;
+        debug!(?tuning, "Set HDR+ tuning");
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save HDR+ tuning");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_gesture_shutter(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.gesture_shutter = !self.config.gesture_shutter;
+        self.gesture_hold_start = None;
+        info!(
+            enabled = self.config.gesture_shutter,
+            "Toggled gesture shutter"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save gesture shutter setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_animated_clip_format(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::AnimatedClipFormat;
+        if let Some(&format) = AnimatedClipFormat::ALL.get(index) {
+            self.config.animated_clip_format = format;
+            info!(?format, "Selected animated clip format");
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save animated clip format");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_animated_clip_seconds(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(&seconds) = crate::media::animation::CLIP_SECONDS.get(index) {
+            self.config.animated_clip_seconds = seconds;
+            info!(seconds, "Selected animated clip length");
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save animated clip length");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_animated_clip_fps(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(&fps) = crate::media::animation::CLIP_FPS.get(index) {
+            self.config.animated_clip_fps = fps;
+            info!(fps, "Selected animated clip frame rate");
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save animated clip frame rate");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_animated_clip_loop(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.animated_clip_loop = !self.config.animated_clip_loop;
+        info!(
+            loop_forever = self.config.animated_clip_loop,
+            "Toggled animated clip loop"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save animated clip loop setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_composition_guide(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::CompositionGuide;
+        if let Some(&guide) = CompositionGuide::ALL.get(index) {
+            self.config.composition_guide = guide;
+            info!(?guide, "Selected composition guide");
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save composition guide setting");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_one_handed_layout(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::OneHandedLayout;
+        if let Some(&layout) = OneHandedLayout::ALL.get(index) {
+            self.config.one_handed_layout = layout;
+            info!(?layout, "Selected one-handed layout");
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save one-handed layout setting");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_volume_key_action(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::config::VolumeKeyAction;
+        if let Some(&action) = VolumeKeyAction::ALL.get(index) {
+            self.config.volume_key_action = action;
+            info!(?action, "Selected volume-key action");
+            crate::backends::volume_keys::set_enabled(action != VolumeKeyAction::Volume);
+
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save volume-key setting");
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_layout_editor_mode(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        // Indexes the default-mode options, which list the editable modes in
+        // the same order
+        if let Some(&mode) = crate::app::control_layout::EDITABLE_MODES.get(index) {
+            self.layout_editor_mode = mode;
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_control_placement(
+        &mut self,
+        control: crate::app::control_layout::Control,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(&placement) = control.placements().get(index) else {
+            return Task::none();
+        };
+        let mode = self.layout_editor_mode;
+        self.config.control_layout.set(mode, control, placement);
+        info!(?mode, ?control, ?placement, "Moved control");
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save control layout");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_reset_control_layout(&mut self) -> Task<cosmic::Action<Message>> {
+        let mode = self.layout_editor_mode;
+        self.config.control_layout.reset(mode);
+        info!(?mode, "Reset control layout");
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save control layout");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_reset_all_settings(&mut self) -> Task<cosmic::Action<Message>> {
+        info!("Resetting all settings to defaults");
+        // Snapshot the pre-reset animated values so the fit/fill change
+        // animates rather than snaps when reset flips preview_fit_to_view
         // (or drops out of View mode).
         let from_fit = self.capture_fit_state();
 
@@ -590,6 +1904,89 @@ impl AppModel {
         Task::none()
     }
 
+    // =========================================================================
+    // Automation Rules
+    // =========================================================================
+
+    pub(crate) fn handle_reload_automation_rules(&mut self) -> Task<cosmic::Action<Message>> {
+        self.load_automation_rules();
+        Task::none()
+    }
+
+    /// Re-read the automation rules file into the settings page.
+    pub(crate) fn load_automation_rules(&mut self) {
+        let Some(path) = crate::automation::rules_path() else {
+            self.automation_rules_error = Some("No config directory".to_string());
+            return;
+        };
+        match crate::automation::RuleSet::load(&path) {
+            Ok(rules) => {
+                debug!(count = rules.rules.len(), "Loaded automation rules");
+                self.automation_rules = rules;
+                self.automation_rules_error = None;
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to load automation rules");
+                self.automation_rules_error = Some(e);
+            }
+        }
+    }
+
+    pub(crate) fn handle_toggle_automation_rule(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(path) = crate::automation::rules_path() else {
+            return Task::none();
+        };
+        // Never write back a file we failed to parse — it would drop the
+        // user's rules.
+        if self.automation_rules_error.is_some() {
+            return Task::none();
+        }
+        let Some(rule) = self.automation_rules.rules.get_mut(index) else {
+            return Task::none();
+        };
+        rule.enabled = !rule.enabled;
+        info!(rule = %rule.name, enabled = rule.enabled, "Toggled automation rule");
+
+        if let Err(e) = self.automation_rules.save(&path) {
+            error!(error = %e, "Failed to save automation rules");
+            self.automation_rules_error = Some(e);
+            return Task::none();
+        }
+        Self::reload_daemon_rules()
+    }
+
+    /// Open the rules file in the user's editor, creating it from the
+    /// bundled example the first time.
+    pub(crate) fn handle_edit_automation_rules(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(path) = crate::automation::rules_path() else {
+            return Task::none();
+        };
+        if let Err(e) = crate::automation::ensure_rules_file(&path) {
+            error!(error = %e, "Failed to create automation rules file");
+            self.automation_rules_error = Some(e);
+            return Task::none();
+        }
+        info!(path = %path.display(), "Opening automation rules file");
+        if let Err(e) = open::that_detached(&path) {
+            error!(error = %e, "Failed to open automation rules file");
+        }
+        self.load_automation_rules();
+        Task::none()
+    }
+
+    /// Tell a running daemon to pick up the edited rules.
+    fn reload_daemon_rules() -> Task<cosmic::Action<Message>> {
+        Task::perform(crate::automation::reload_daemon_rules(), |result| {
+            if let Err(e) = result {
+                warn!(error = %e, "Failed to notify daemon about rule changes");
+            }
+            cosmic::Action::App(Message::Noop)
+        })
+    }
+
     // =========================================================================
     // Helper Functions
     // =========================================================================
@@ -671,8 +2068,23 @@ impl AppModel {
         detections: Vec<crate::app::frame_processor::QrDetection>,
     ) -> Task<cosmic::Action<Message>> {
         let count = detections.len();
+        if self.recording.is_recording()
+            && let Some((_, events)) = self.recording_events.as_mut()
+        {
+            let at = self.recording.elapsed(self.clock.now());
+            events.qr_codes_seen(at, detections.iter().map(|d| d.content.as_str()));
+        }
+        if self.config.qr_history {
+            let now = chrono::Utc::now().timestamp();
+            let mut added = false;
+            for detection in &detections {
+                added |= self.qr_history.record_scan(&detection.content, now);
+            }
+            if added {
+                self.persist_qr_history();
+            }
+        }
         self.qr_detections = detections;
-        self.last_qr_detection_time = Some(std::time::Instant::now());
 
         if count > 0 {
             info!(count, "QR detections updated");
@@ -681,6 +2093,72 @@ impl AppModel {
         Task::none()
     }
 
+    pub(crate) fn handle_qr_run_action(
+        &mut self,
+        content: String,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::app::frame_processor::QrAction;
+        use crate::qr_history::QrHistoryAction;
+
+        let action = QrAction::parse(&content);
+        if self.config.qr_history {
+            let done = match action {
+                QrAction::Wifi { .. } => QrHistoryAction::JoinedWifi,
+                QrAction::Text(_) | QrAction::Contact(_) | QrAction::Event(_) => {
+                    QrHistoryAction::Copied
+                }
+                _ => QrHistoryAction::Opened,
+            };
+            if self
+                .qr_history
+                .record_action(&content, done, chrono::Utc::now().timestamp())
+            {
+                self.persist_qr_history();
+            }
+        }
+        Task::done(cosmic::Action::App(
+            crate::app::qr_overlay::action_button::action_to_message(&action),
+        ))
+    }
+
+    pub(crate) fn handle_qr_history_query(
+        &mut self,
+        query: String,
+    ) -> Task<cosmic::Action<Message>> {
+        self.qr_history_query = query;
+        Task::none()
+    }
+
+    pub(crate) fn handle_clear_qr_history(&mut self) -> Task<cosmic::Action<Message>> {
+        info!(
+            entries = self.qr_history.entries.len(),
+            "Clearing QR history"
+        );
+        self.qr_history.clear();
+        self.persist_qr_history();
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_qr_history(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.qr_history = !self.config.qr_history;
+        self.persist_config_async();
+        info!(enabled = self.config.qr_history, "QR history toggled");
+        Task::none()
+    }
+
+    /// Write the QR history in the background
+    fn persist_qr_history(&self) {
+        let Some(path) = crate::qr_history::history_path() else {
+            return;
+        };
+        let history = self.qr_history.clone();
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = history.save(&path) {
+                error!(error = %e, "Failed to save the QR history");
+            }
+        });
+    }
+
     pub(crate) fn handle_qr_open_url(&self, url: String) -> Task<cosmic::Action<Message>> {
         info!(url = %url, "Opening URL from QR code");
         match open::that_detached(&url) {
@@ -695,19 +2173,46 @@ impl AppModel {
     }
 
     pub(crate) fn handle_qr_connect_wifi(
-        &self,
+        &mut self,
         ssid: String,
         password: Option<String>,
         security: String,
         hidden: bool,
     ) -> Task<cosmic::Action<Message>> {
-        // Use NetworkManager D-Bus API - works in both native and flatpak
-        Task::perform(
-            crate::network_manager::connect_wifi(ssid, password, security, hidden),
-            |_| cosmic::Action::App(Message::Noop),
+        if let Some(join) = &self.wifi_join
+            && join.ssid == ssid
+            && join.status.is_pending()
+        {
+            return Task::none();
+        }
+        self.wifi_join = Some(crate::app::state::WifiJoin {
+            ssid: ssid.clone(),
+            status: crate::network_manager::WifiJoinStatus::Connecting,
+        });
+
+        // Use NetworkManager D-Bus API - works in both native and flatpak.
+        // Each step is reported on the code's button.
+        Task::run(
+            crate::network_manager::join_wifi(ssid.clone(), password, security, hidden),
+            move |status| cosmic::Action::App(Message::WifiJoinProgress(ssid.clone(), status)),
         )
     }
 
+    pub(crate) fn handle_wifi_join_progress(
+        &mut self,
+        ssid: String,
+        status: crate::network_manager::WifiJoinStatus,
+    ) -> Task<cosmic::Action<Message>> {
+        // A later join replaced this one
+        if let Some(join) = self.wifi_join.as_mut()
+            && join.ssid == ssid
+        {
+            info!(ssid = %ssid, ?status, "WiFi join progress");
+            join.status = status;
+        }
+        Task::none()
+    }
+
     pub(crate) fn handle_qr_copy_text(&self, text: String) -> Task<cosmic::Action<Message>> {
         info!(
             text_length = text.len(),
@@ -718,6 +2223,111 @@ impl AppModel {
         cosmic::iced::clipboard::write(text).map(|_: ()| cosmic::Action::App(Message::Noop))
     }
 
+    // =========================================================================
+    // Show QR Handlers
+    // =========================================================================
+
+    pub(crate) fn handle_open_qr_share(&mut self) -> Task<cosmic::Action<Message>> {
+        self.close_all_pickers();
+        self.qr_share.get_or_insert_with(Default::default);
+        info!("QR code composer opened");
+        Task::none()
+    }
+
+    pub(crate) fn handle_close_qr_share(&mut self) -> Task<cosmic::Action<Message>> {
+        let task = if self.qr_share.as_ref().is_some_and(|share| share.fullscreen) {
+            self.handle_toggle_qr_share_fullscreen()
+        } else {
+            Task::none()
+        };
+        self.qr_share = None;
+        task
+    }
+
+    pub(crate) fn handle_select_qr_share_kind(
+        &mut self,
+        kind: crate::app::qr_share::QrShareKind,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(share) = self.qr_share.as_mut() {
+            share.set_kind(kind);
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_qr_share_input(
+        &mut self,
+        field: crate::app::qr_share::QrShareField,
+        text: String,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(share) = self.qr_share.as_mut() {
+            share.set_input(field, text);
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_qr_share_hidden(
+        &mut self,
+        hidden: bool,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(share) = self.qr_share.as_mut() {
+            share.set_hidden(hidden);
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_qr_share_use_current_wifi(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(share) = self.qr_share.as_mut() else {
+            return Task::none();
+        };
+        share.loading_wifi = true;
+        share.wifi_error = None;
+        Task::perform(crate::network_manager::active_wifi(), |result| {
+            cosmic::Action::App(Message::QrShareWifiLoaded(result))
+        })
+    }
+
+    pub(crate) fn handle_qr_share_wifi_loaded(
+        &mut self,
+        result: Result<crate::network_manager::WifiCredentials, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(share) = self.qr_share.as_mut() else {
+            return Task::none();
+        };
+        share.loading_wifi = false;
+        match result {
+            Ok(credentials) => share.set_wifi(credentials),
+            Err(e) => {
+                warn!(error = %e, "Failed to read the active WiFi connection");
+                share.wifi_error = Some(e);
+            }
+        }
+        Task::none()
+    }
+
+    /// Show the QR code alone on a full-screen white window, with the
+    /// backlight raised like the mirror light, or go back to the composer
+    pub(crate) fn handle_toggle_qr_share_fullscreen(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::iced::window::{self, Mode};
+
+        let Some(share) = self.qr_share.as_mut() else {
+            return Task::none();
+        };
+        share.fullscreen = !share.fullscreen;
+        let mode = if share.fullscreen {
+            Mode::Fullscreen
+        } else {
+            Mode::Windowed
+        };
+        if share.fullscreen && self.core.window.show_context {
+            self.core.window.show_context = false;
+            self.sync_audio_probe();
+        }
+        self.sync_screen_boost();
+        self.core
+            .main_window_id()
+            .map_or_else(Task::none, |id| window::set_mode(id, mode))
+    }
+
     // =========================================================================
     // Insights Handlers
     // =========================================================================
@@ -778,6 +2388,7 @@ impl AppModel {
                     Vec::new()
                 };
                 self.insights.libcamera_formats = self.available_formats.clone();
+                self.refresh_usb_link();
             }
         }
 
@@ -932,6 +2543,8 @@ impl AppModel {
         use crate::backends::camera::libcamera::native::diagnostics as diag;
 
         self.insights.cpu_decode_time_us = diag::get_mjpeg_decode_time_us();
+        self.insights.dropped_frames = self.preview_mailbox.dropped();
+        self.insights.duplicated_frames = self.frame_pacer.duplicated();
 
         if let Some(stream) = &self.insights.preview_stream
             && let Some((w, h)) = stream.resolution.split_once('x')
@@ -1174,6 +2787,7 @@ impl AppModel {
             serde_json::json!({
                 "frame_latency_us": ins.frame_latency_us,
                 "dropped_frames": ins.dropped_frames,
+                "duplicated_frames": ins.duplicated_frames,
                 "frame_size_decoded": ins.frame_size_decoded,
                 "gpu_conversion_time_us": ins.gpu_conversion_time_us,
                 "copy_time_us": ins.copy_time_us,
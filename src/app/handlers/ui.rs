@@ -4,6 +4,7 @@
 //!
 //! Handles context pages, pickers, and tools menu.
 
+use crate::app::camera_preview::gestures::Gesture;
 use crate::app::state::{AppModel, ContextPage, Message, SettingsPage};
 use cosmic::Task;
 use cosmic::iced::core::widget::Id;
@@ -12,7 +13,7 @@ use cosmic::iced::core::widget::operation::{
     scrollable::{AbsoluteOffset, Scrollable},
 };
 use cosmic::iced::core::{Rectangle, Vector};
-use tracing::{error, info};
+use tracing::{debug, error, info};
 
 /// Widget operation that snaps every scrollable it visits back to the top.
 struct ResetScroll;
@@ -91,6 +92,12 @@ impl AppModel {
         self.sync_audio_probe();
         // Reset the shared drawer scrollable so the new page starts at the top.
         if self.core.window.show_context {
+            if context_page == ContextPage::ExtensionUnits {
+                return Task::batch([
+                    reset_context_drawer_scroll(),
+                    self.query_extension_units_task(),
+                ]);
+            }
             reset_context_drawer_scroll()
         } else {
             Task::none()
@@ -104,6 +111,43 @@ impl AppModel {
         self.color_picker_visible = false;
         self.tools_menu_visible = false;
         self.motor_picker_visible = false;
+        self.wb_eyedropper_active = false;
+    }
+
+    /// Act on a touch gesture from the camera preview: swiping sideways
+    /// changes mode (like the carousel), swiping up/down or double-tapping
+    /// flips the camera, and a long-press toggles the exposure/focus lock.
+    /// Ignored while a capture is running (the bottom bar is hidden then
+    /// too) and while a picker covers the preview.
+    pub(crate) fn handle_preview_gesture(
+        &mut self,
+        gesture: Gesture,
+    ) -> Task<cosmic::Action<Message>> {
+        let capturing = self.recording.is_recording()
+            || self.quick_record.is_recording()
+            || self.timelapse.is_active()
+            || self.virtual_camera.is_streaming()
+            || self.burst_mode.is_active()
+            || self.photo_timer_countdown.is_some();
+        let picker_open = self.format_picker_visible
+            || self.exposure_picker_visible
+            || self.color_picker_visible
+            || self.tools_menu_visible
+            || self.motor_picker_visible;
+        if capturing || picker_open || self.transition_state.ui_disabled {
+            debug!(?gesture, "Ignoring preview gesture");
+            return Task::none();
+        }
+
+        info!(?gesture, "Preview gesture");
+        match gesture {
+            Gesture::SwipeLeft => self.handle_cycle_mode(true),
+            Gesture::SwipeRight => self.handle_cycle_mode(false),
+            Gesture::SwipeUp | Gesture::SwipeDown | Gesture::DoubleTap => {
+                self.handle_switch_camera()
+            }
+            Gesture::LongPress => self.handle_toggle_exposure_focus_lock(),
+        }
     }
 
     pub(crate) fn handle_toggle_format_picker(&mut self) -> Task<cosmic::Action<Message>> {
@@ -111,7 +155,8 @@ impl AppModel {
         self.close_all_pickers();
         self.format_picker_visible = opening;
         if opening {
-            self.picker_selected_resolution = self.active_format.as_ref().map(|f| f.width);
+            self.format_picker_query.clear();
+            self.refresh_usb_link();
         }
         Task::none()
     }
@@ -144,6 +189,112 @@ impl AppModel {
         self.start_fit_animation(from)
     }
 
+    /// Open the focus loupe on the middle of the preview, or close it
+    pub(crate) fn handle_toggle_loupe(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::app::camera_preview::loupe::Loupe;
+
+        self.loupe = match self.loupe {
+            Some(_) => None,
+            None => {
+                // The pickers would cover the preview being inspected
+                self.close_all_pickers();
+                Some(Loupe::centered(cosmic::iced::Size::new(
+                    self.screen_width,
+                    self.screen_height,
+                )))
+            }
+        };
+        info!(open = self.loupe.is_some(), "Focus loupe toggled");
+        Task::none()
+    }
+
+    pub(crate) fn handle_loupe_moved(
+        &mut self,
+        anchor: cosmic::iced::Point,
+        center: (f32, f32),
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(loupe) = self.loupe.as_mut()
+            && !loupe.pinned
+        {
+            loupe.anchor = anchor;
+            loupe.center = center;
+        }
+        Task::none()
+    }
+
+    /// Pin the loupe where the preview was clicked, or unpin it so it
+    /// follows the pointer from there
+    pub(crate) fn handle_toggle_loupe_pin(
+        &mut self,
+        anchor: cosmic::iced::Point,
+        center: (f32, f32),
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(loupe) = self.loupe.as_mut() {
+            loupe.pinned = !loupe.pinned;
+            loupe.anchor = anchor;
+            loupe.center = center;
+            debug!(pinned = loupe.pinned, ?center, "Focus loupe pin toggled");
+        }
+        Task::none()
+    }
+
+    /// Enter or leave mirror mode. The window goes full screen, covering
+    /// the panels, the preview is mirrored whatever the selfie setting says,
+    /// and everything but the light toggle and the way out is hidden. The
+    /// screen light comes on with it if the user left it on last time.
+    pub(crate) fn handle_toggle_mirror_mode(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::iced::window::{self, Mode};
+
+        self.mirror_mode = !self.mirror_mode;
+        if self.mirror_mode {
+            self.close_all_pickers();
+            if self.core.window.show_context {
+                self.core.window.show_context = false;
+                self.sync_audio_probe();
+            }
+        }
+        self.sync_screen_boost();
+        info!(mirror = self.mirror_mode, "Mirror mode toggled");
+
+        let mode = if self.mirror_mode {
+            Mode::Fullscreen
+        } else {
+            Mode::Windowed
+        };
+        self.core
+            .main_window_id()
+            .map_or_else(Task::none, |id| window::set_mode(id, mode))
+    }
+
+    pub(crate) fn handle_toggle_mirror_light(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.mirror_light = !self.config.mirror_light;
+        self.persist_config_async();
+        self.sync_screen_boost();
+        info!(light = self.config.mirror_light, "Mirror light toggled");
+        Task::none()
+    }
+
+    /// Drive the screen backlight to full while the mirror light, a screen
+    /// flash or a full-screen QR code is lit, and put it back to the level
+    /// it had after. Without a backlight (external monitors) the light is
+    /// just the white on screen.
+    pub(crate) fn sync_screen_boost(&mut self) {
+        let on = (self.mirror_mode && self.config.mirror_light)
+            || (self.flash.active && !self.use_hardware_flash())
+            || self.qr_share.as_ref().is_some_and(|share| share.fullscreen);
+        if on && self.screen_boost.is_none() {
+            match crate::backlight::Backlight::find() {
+                Some(backlight) => {
+                    backlight.set_in_background(backlight.max_brightness);
+                    self.screen_boost = Some(backlight);
+                }
+                None => info!("No backlight to raise for the screen light"),
+            }
+        } else if !on && let Some(backlight) = self.screen_boost.take() {
+            backlight.set_in_background(backlight.brightness);
+        }
+    }
+
     pub(crate) fn handle_toggle_device_info(&mut self) -> Task<cosmic::Action<Message>> {
         self.device_info_visible = !self.device_info_visible;
         info!(visible = self.device_info_visible, "Device info toggled");
@@ -192,6 +343,20 @@ impl AppModel {
         self.context_page = ContextPage::Settings;
         self.core.window.show_context = true;
         self.settings_page = page;
+        if page == SettingsPage::Video {
+            let video_dir = crate::app::get_video_directory(&self.config.save_folder_name);
+            self.video_disk_free_bytes = crate::storage::available_space(&video_dir);
+        }
+        if page == SettingsPage::Automation {
+            self.load_automation_rules();
+            self.load_share_targets();
+        }
+        if page == SettingsPage::Upload {
+            self.upload_form = crate::app::state::UploadForm {
+                settings: self.config.upload.clone(),
+                ..Default::default()
+            };
+        }
         self.sync_audio_probe();
         reset_context_drawer_scroll()
     }
@@ -8,6 +8,8 @@
 use crate::app::state::{
     AppModel, FileSource, FilterType, Message, VideoPlaybackCommand, VirtualCameraState,
 };
+use crate::backends::camera::frame_hub::{ConsumerKind, QueuePolicy};
+use crate::backends::virtual_camera::{LoopMode, PlaybackOptions, Playlist};
 use cosmic::Task;
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
@@ -25,6 +27,15 @@ struct VideoStreamParams<'a> {
     initial_paused: bool,
 }
 
+/// How the preview plays a video file source
+struct VideoPreviewOptions {
+    initial_position: f64,
+    initial_paused: bool,
+    playback: PlaybackOptions,
+    /// Restart at the end instead of finishing
+    repeat: bool,
+}
+
 impl AppModel {
     // =========================================================================
     // Virtual Camera Handlers
@@ -50,6 +61,9 @@ impl AppModel {
             if let Some(sender) = self.virtual_camera.take_stop_sender() {
                 let _ = sender.send(());
             }
+            if let Some(manager) = &self.backend_manager {
+                manager.unsubscribe_frames(ConsumerKind::VirtualCamera);
+            }
             // Set to Idle immediately so UI updates (button state changes)
             // but don't send VirtualCameraStopped - the streaming thread will send it
             // when it actually stops. This avoids duplicate messages.
@@ -74,21 +88,43 @@ impl AppModel {
             return Task::none();
         };
 
-        let width = format.width;
-        let height = format.height;
         let filter_type = self.selected_filter;
+        let pip = self.pip_overlay();
+        let denoise = self.video_denoise_settings();
+        // Consumers get upright frames, so the output size follows the
+        // rotation fixed at start
+        let rotation = self.capture_rotation();
+        // The selfie mirror is left to the consumer app, which mirrors its
+        // own self view; the camera's mirror correction is part of the
+        // picture
+        let mirror = self.camera_orientation().mirrored();
+        let (width, height) = if rotation.swaps_dimensions() {
+            (format.height, format.width)
+        } else {
+            (format.width, format.height)
+        };
 
         info!(
             width,
             height,
             ?filter_type,
+            %rotation,
             "Starting virtual camera streaming from camera"
         );
 
+        // Take frames straight from the capture thread, alongside the
+        // preview and any recording, rather than from the UI. Only the
+        // newest frame is kept, so slow filtering never backs up the hub.
+        let Some(frame_hub) = self.backend_manager.as_ref().map(|m| m.frame_hub()) else {
+            error!("No camera backend for virtual camera");
+            return Task::none();
+        };
+        let mut frames = frame_hub.subscribe(ConsumerKind::VirtualCamera, QueuePolicy::Latest);
+
         let (stop_tx, mut stop_rx) = tokio::sync::oneshot::channel();
-        let (frame_tx, mut frame_rx) = tokio::sync::mpsc::unbounded_channel();
         let (filter_tx, mut filter_rx) = tokio::sync::watch::channel(filter_type);
-        self.virtual_camera = VirtualCameraState::start(stop_tx, frame_tx, filter_tx, false);
+        self.virtual_camera =
+            VirtualCameraState::start(stop_tx, filter_tx, false, self.clock.now());
 
         // Start the virtual camera streaming on a DEDICATED THREAD
         // This is critical: CPU filtering is blocking and must NOT run on the async executor
@@ -99,12 +135,15 @@ impl AppModel {
 
         // Spawn the dedicated thread immediately (fire-and-forget from thread perspective)
         std::thread::spawn(move || {
-            use crate::backends::camera::types::{CameraFrame, FrameData, PixelFormat};
+            use crate::backends::camera::types::{
+                CameraFrame, FrameData, PixelFormat, SensorRotation,
+            };
             use crate::backends::virtual_camera::VirtualCameraManager;
 
             // Create and start the virtual camera on this dedicated thread
             let mut manager = VirtualCameraManager::new();
             manager.set_filter(filter_type);
+            manager.set_flip_horizontal(mirror);
 
             // Build a tokio runtime once. The virtual-camera output pipeline
             // accepts RGBA only, so YUV-format frames (e.g. MJPEG → I420 from
@@ -125,6 +164,10 @@ impl AppModel {
                 }
             };
 
+            let mut denoiser = denoise
+                .is_enabled()
+                .then(|| rt.block_on(crate::pipelines::video::VideoDenoiser::new(denoise)));
+
             let result = (|| {
                 if let Err(e) = manager.start(width, height) {
                     return Err(format!("Failed to start virtual camera: {}", e));
@@ -132,9 +175,8 @@ impl AppModel {
 
                 info!("Virtual camera started on dedicated thread, processing frames");
 
-                // Process frames until channel closes
+                // Process frames until the subscription ends
                 let mut frame_count = 0u64;
-                let mut dropped_count = 0u64;
 
                 loop {
                     // Check for an explicit stop signal first — the channel-close
@@ -152,31 +194,86 @@ impl AppModel {
                         info!(?new_filter, "Virtual camera filter updated");
                     }
 
-                    // Wait for at least one frame (blocking is OK on dedicated thread)
-                    let first_frame = match frame_rx.blocking_recv() {
-                        Some(f) => f,
-                        None => {
-                            info!("Frame channel closed, stopping virtual camera");
-                            break;
-                        }
+                    // Wait for the newest frame (blocking is OK on dedicated
+                    // thread); frames that arrived meanwhile were replaced
+                    let Some(latest_frame) = frames.blocking_recv() else {
+                        info!("Frame subscription ended, stopping virtual camera");
+                        break;
                     };
 
-                    // Drain any additional frames, keeping only the latest
-                    let mut latest_frame = first_frame;
-                    while let Ok(newer_frame) = frame_rx.try_recv() {
-                        latest_frame = newer_frame;
-                        dropped_count += 1;
-                    }
-
                     frame_count += 1;
                     if frame_count.is_multiple_of(30) {
+                        let dropped = frame_hub
+                            .stats()
+                            .iter()
+                            .filter(|stats| stats.kind == ConsumerKind::VirtualCamera)
+                            .map(|stats| stats.dropped)
+                            .sum::<u64>();
                         debug!(
                             frame = frame_count,
-                            dropped = dropped_count,
-                            "Processing virtual camera frame"
+                            dropped, "Processing virtual camera frame"
                         );
                     }
 
+                    // Bake in the denoising, the second camera and the
+                    // rotation correction. This yields an RGBA frame, so the
+                    // conversion below is skipped.
+                    let latest_frame = if denoiser.is_some()
+                        || pip.is_some()
+                        || rotation != SensorRotation::None
+                    {
+                        match rt.block_on(async {
+                            let rgba = crate::pipelines::video::recorder::convert_frame_to_rgba(
+                                &latest_frame,
+                            )
+                            .await?;
+                            let rgba = match denoiser.as_mut() {
+                                Some(denoiser) => {
+                                    denoiser
+                                        .denoise(rgba, latest_frame.width, latest_frame.height)
+                                        .await
+                                }
+                                None => rgba,
+                            };
+                            let rgba = match &pip {
+                                Some(pip) => {
+                                    pip.composite(rgba, latest_frame.width, latest_frame.height)
+                                        .await
+                                }
+                                None => rgba,
+                            };
+                            if rotation == SensorRotation::None {
+                                return Ok((rgba, latest_frame.width, latest_frame.height));
+                            }
+                            crate::shaders::rotate_rgba_gpu(
+                                &rgba,
+                                latest_frame.width,
+                                latest_frame.height,
+                                rotation,
+                                false,
+                            )
+                            .await
+                        }) {
+                            Ok((rgba, out_width, out_height)) => std::sync::Arc::new(CameraFrame {
+                                width: out_width,
+                                height: out_height,
+                                data: FrameData::Copied(rgba.into()),
+                                format: PixelFormat::RGBA,
+                                stride: out_width * 4,
+                                yuv_planes: None,
+                                captured_at: latest_frame.captured_at,
+                                sensor_timestamp_ns: latest_frame.sensor_timestamp_ns,
+                                libcamera_metadata: latest_frame.libcamera_metadata.clone(),
+                            }),
+                            Err(e) => {
+                                warn!(?e, "Composite / rotation failed; dropping frame");
+                                continue;
+                            }
+                        }
+                    } else {
+                        latest_frame
+                    };
+
                     // If the camera delivered YUV (or any non-RGBA format),
                     // run it through the shared GPU convert pipeline to get
                     // tightly-packed RGBA that the virtual-camera appsrc
@@ -263,7 +360,6 @@ impl AppModel {
 
         // Create channels
         let (stop_tx, stop_rx) = tokio::sync::oneshot::channel();
-        let (frame_tx, _frame_rx) = tokio::sync::mpsc::unbounded_channel();
         let (filter_tx, mut filter_rx) = tokio::sync::watch::channel(filter_type);
 
         // Create preview channel for sending frames back to UI
@@ -279,7 +375,7 @@ impl AppModel {
             tokio::sync::mpsc::unbounded_channel::<VideoPlaybackCommand>();
 
         // Use start_file_source to mark this as file source streaming
-        self.virtual_camera = VirtualCameraState::start(stop_tx, frame_tx, filter_tx, true);
+        self.virtual_camera = VirtualCameraState::start(stop_tx, filter_tx, true, self.clock.now());
 
         // For video files, keep the current progress (with stored seek position) until
         // the streaming thread sends actual progress updates. This prevents the slider
@@ -685,6 +781,9 @@ impl AppModel {
         result: Result<(), String>,
     ) -> Task<cosmic::Action<Message>> {
         self.virtual_camera = VirtualCameraState::Idle;
+        if let Some(manager) = &self.backend_manager {
+            manager.unsubscribe_frames(ConsumerKind::VirtualCamera);
+        }
         self.update_idle_inhibit();
         // Clear the file source preview receiver (only relevant for file source streaming)
         self.file_source_preview_receiver = None;
@@ -789,35 +888,111 @@ impl AppModel {
                     .pick_file()
                     .await;
 
-                if let Some(file) = file {
-                    let path = file.path().to_path_buf();
-                    let extension = path
-                        .extension()
-                        .and_then(|e| e.to_str())
-                        .map(|e| e.to_lowercase())
-                        .unwrap_or_default();
-
-                    // Determine if it's an image or video based on extension
-                    use crate::constants::file_formats;
-                    if file_formats::is_image_extension(&extension) {
-                        Some(FileSource::Image(path))
-                    } else if file_formats::is_video_extension(&extension) {
-                        Some(FileSource::Video(path))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
+                // Determine if it's an image or video based on extension
+                file.and_then(|file| FileSource::from_path(file.path().to_path_buf()))
             },
             |file_source| cosmic::Action::App(Message::VirtualCameraFileSelected(file_source)),
         )
     }
 
+    pub(crate) fn handle_open_virtual_camera_folder(&self) -> Task<cosmic::Action<Message>> {
+        info!("Opening folder picker for virtual camera source");
+
+        Task::perform(
+            async {
+                rfd::AsyncFileDialog::new()
+                    .pick_folder()
+                    .await
+                    .map(|folder| folder.path().to_path_buf())
+            },
+            |dir| cosmic::Action::App(Message::VirtualCameraFolderSelected(dir)),
+        )
+    }
+
+    pub(crate) fn handle_virtual_camera_folder_selected(
+        &mut self,
+        dir: Option<std::path::PathBuf>,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(dir) = dir else {
+            info!("Folder picker cancelled");
+            return Task::none();
+        };
+
+        match Playlist::scan(&dir) {
+            Ok(playlist) => {
+                info!(path = %dir.display(), entries = playlist.len(), "Virtual camera playlist selected");
+                let first = playlist.current().clone();
+                self.preview_playlist = Some(playlist);
+                self.handle_virtual_camera_file_selected(Some(first))
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to load virtual camera playlist");
+                Task::none()
+            }
+        }
+    }
+
+    /// Move on once the file source has finished playing. Notices from an
+    /// entry the playlist already left are ignored.
+    pub(crate) fn handle_preview_source_ended(
+        &mut self,
+        generation: Option<u64>,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(generation) = generation else {
+            // A single video that doesn't loop: leave it on its last frame,
+            // ready to play again from the start
+            self.video_preview_control_tx = None;
+            self.video_preview_stop_tx = None;
+            self.video_preview_seek_position = 0.0;
+            self.video_file_paused = true;
+            return Task::none();
+        };
+        if self
+            .preview_playlist
+            .as_ref()
+            .is_none_or(|playlist| playlist.generation() != generation)
+        {
+            return Task::none();
+        }
+
+        // The virtual camera keeps streaming the entry it started on; an
+        // image is checked again later so the preview cycles on afterwards
+        if self.virtual_camera.is_streaming() {
+            return self.end_image_after_dwell();
+        }
+
+        self.stop_video_preview_playback();
+        let loop_mode = self.preview_playback.loop_mode;
+        let next = self
+            .preview_playlist
+            .as_mut()
+            .and_then(|playlist| playlist.advance(loop_mode).cloned());
+        match next {
+            Some(next) => {
+                debug!(?next, "Playlist moving to next entry");
+                self.handle_virtual_camera_file_selected(Some(next))
+            }
+            None => {
+                info!("Playlist finished");
+                self.video_file_paused = true;
+                Task::none()
+            }
+        }
+    }
+
     pub(crate) fn handle_virtual_camera_file_selected(
         &mut self,
         file_source: Option<FileSource>,
     ) -> Task<cosmic::Action<Message>> {
+        // Picking a single file leaves the playlist
+        if self
+            .preview_playlist
+            .as_ref()
+            .is_some_and(|playlist| file_source.as_ref() != Some(playlist.current()))
+        {
+            self.preview_playlist = None;
+        }
+
         if let Some(ref source) = file_source {
             info!(?source, "Virtual camera file source selected");
 
@@ -830,17 +1005,24 @@ impl AppModel {
             self.virtual_camera_file_source = file_source;
 
             // Reset seek position when a new file is selected
-            // Start in paused state since the video isn't playing yet (just showing preview frame)
+            // Start in paused state since the video isn't playing yet (just showing preview frame),
+            // unless it is part of a playlist, which plays through by itself
             self.video_preview_seek_position = 0.0;
-            self.video_file_paused = true;
+            self.video_file_paused = self.preview_playlist.is_none();
+            let capture = self.preview_playback.capture;
 
             // Load preview frame (and duration for videos) asynchronously
             return Task::perform(
                 async move {
-                    use crate::backends::virtual_camera::{get_video_duration, load_preview_frame};
+                    use crate::backends::virtual_camera::{
+                        fit_frame_to_capture, get_video_duration, load_preview_frame,
+                    };
 
                     let frame = match load_preview_frame(&path) {
-                        Ok(frame) => Some(Arc::new(frame)),
+                        Ok(frame) => Some(Arc::new(match capture {
+                            Some(capture) => fit_frame_to_capture(frame, capture),
+                            None => frame,
+                        })),
                         Err(e) => {
                             warn!(?e, "Failed to load preview frame");
                             None
@@ -915,7 +1097,27 @@ impl AppModel {
             }
         }
 
-        Task::none()
+        self.end_image_after_dwell()
+    }
+
+    /// For an image in a playlist that moves on, notify its end once it has
+    /// been shown for its duration
+    fn end_image_after_dwell(&self) -> Task<cosmic::Action<Message>> {
+        let Some(playlist) = &self.preview_playlist else {
+            return Task::none();
+        };
+        if !matches!(playlist.current(), FileSource::Image(_))
+            || playlist.len() < 2
+            || self.preview_playback.loop_mode == LoopMode::One
+        {
+            return Task::none();
+        }
+
+        let generation = playlist.generation();
+        let dwell = self.preview_playback.image_dwell();
+        Task::perform(tokio::time::sleep(dwell), move |_| {
+            cosmic::Action::App(Message::PreviewSourceEnded(Some(generation)))
+        })
     }
 
     pub(crate) fn handle_clear_virtual_camera_file(&mut self) -> Task<cosmic::Action<Message>> {
@@ -923,6 +1125,7 @@ impl AppModel {
         // Stop any preview playback
         self.stop_video_preview_playback();
         self.virtual_camera_file_source = None;
+        self.preview_playlist = None;
         self.current_frame_is_file_source = false;
         self.video_file_progress = None;
         self.video_preview_seek_position = 0.0;
@@ -1088,17 +1291,29 @@ impl AppModel {
 
         let initial_position = self.video_preview_seek_position;
         let initial_paused = self.video_file_paused;
+        let playback = self.preview_playback;
+        let playlist_len = self.preview_playlist.as_ref().map_or(1, |p| p.len());
+        let repeat = playback.loop_mode.repeats_entry(playlist_len);
+        let generation = self.preview_playlist.as_ref().map(|p| p.generation());
+        // Set by the thread when the video ran out rather than being stopped
+        let ended = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let thread_ended = Arc::clone(&ended);
 
         // Spawn preview playback thread
         std::thread::spawn(move || {
-            Self::run_video_preview_playback(
+            let finished = Self::run_video_preview_playback(
                 path,
-                initial_position,
-                initial_paused,
+                VideoPreviewOptions {
+                    initial_position,
+                    initial_paused,
+                    playback,
+                    repeat,
+                },
                 stop_rx,
                 control_rx,
                 frame_tx,
             );
+            thread_ended.store(finished, std::sync::atomic::Ordering::Release);
         });
 
         // Return a task that receives frames and sends messages
@@ -1112,8 +1327,12 @@ impl AppModel {
                     )
                 })
             })
-            .chain(futures::stream::once(async {
-                Message::VideoPreviewPlaybackStopped
+            .chain(futures::stream::once(async move {
+                if ended.load(std::sync::atomic::Ordering::Acquire) {
+                    Message::PreviewSourceEnded(generation)
+                } else {
+                    Message::VideoPreviewPlaybackStopped
+                }
             })),
             cosmic::Action::App,
         )
@@ -1127,11 +1346,12 @@ impl AppModel {
         self.video_preview_control_tx = None;
     }
 
-    /// Run video preview playback in a background thread
+    /// Run video preview playback in a background thread. Returns whether
+    /// the video finished (reached its end without repeating, or could not
+    /// be played) rather than being stopped.
     fn run_video_preview_playback(
         path: std::path::PathBuf,
-        initial_position: f64,
-        initial_paused: bool,
+        options: VideoPreviewOptions,
         mut stop_rx: tokio::sync::oneshot::Receiver<()>,
         mut control_rx: tokio::sync::mpsc::UnboundedReceiver<VideoPlaybackCommand>,
         frame_tx: tokio::sync::mpsc::UnboundedSender<(
@@ -1140,14 +1360,21 @@ impl AppModel {
             f64,
             f64,
         )>,
-    ) {
+    ) -> bool {
         use crate::backends::virtual_camera::VideoDecoder;
 
-        let decoder = match VideoDecoder::new(&path) {
+        let VideoPreviewOptions {
+            initial_position,
+            initial_paused,
+            playback,
+            repeat,
+        } = options;
+
+        let decoder = match VideoDecoder::with_playback(&path, playback.rate, playback.capture) {
             Ok(d) => d,
             Err(e) => {
                 warn!(?e, "Failed to create preview decoder");
-                return;
+                return true;
             }
         };
 
@@ -1163,13 +1390,14 @@ impl AppModel {
             decoder.set_paused(true);
         }
         let mut last_frame_time = std::time::Instant::now();
-        let frame_duration = vc_timing::IMAGE_STREAM_FRAME_DURATION; // ~30fps
+        // ~30fps, or the simulated capture's framerate
+        let frame_duration = playback.frame_duration();
 
         loop {
             // Check for stop signal
             match stop_rx.try_recv() {
                 Ok(()) | Err(tokio::sync::oneshot::error::TryRecvError::Closed) => {
-                    break;
+                    return false;
                 }
                 Err(tokio::sync::oneshot::error::TryRecvError::Empty) => {}
             }
@@ -1232,7 +1460,7 @@ impl AppModel {
                     .send((frame_arc, position, duration, progress))
                     .is_err()
                 {
-                    break; // Receiver dropped
+                    return false; // Receiver dropped
                 }
 
                 // Re-pause after a seek-while-paused frame pull.
@@ -1253,9 +1481,12 @@ impl AppModel {
                     decoder.set_paused(true);
                 }
                 if decoder.is_eos() {
+                    if !repeat {
+                        return true;
+                    }
                     // Video ended, loop
                     if decoder.restart().is_err() {
-                        break;
+                        return true;
                     }
                 }
             }
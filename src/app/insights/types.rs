@@ -35,8 +35,11 @@ pub struct InsightsState {
     // Performance metrics
     /// Frame latency in microseconds
     pub frame_latency_us: u64,
-    /// Total dropped frames count
+    /// Preview frames replaced before the UI took them
     pub dropped_frames: u64,
+    /// Extra frame intervals preview frames stayed on screen because the
+    /// next one was late
+    pub duplicated_frames: u64,
     /// Frame size after decoding in bytes
     pub frame_size_decoded: usize,
     /// GPU compute shader conversion time in microseconds
@@ -92,6 +92,11 @@ impl AppModel {
             sections.push(self.build_recording_section().into());
         }
 
+        // USB connection section (USB cameras only)
+        if let Some(link) = &self.usb_link {
+            sections.push(self.build_usb_section(link).into());
+        }
+
         // Audio section
         sections.push(self.build_audio_section().into());
 
@@ -136,6 +141,7 @@ impl AppModel {
         let copy_button =
             widget::button::icon(widget::icon::from_name("edit-copy-symbolic").symbolic(true))
                 .extra_small()
+                .tooltip(fl!("a11y-copy"))
                 .on_press(Message::CopyPipelineString);
 
         let pipeline_label = fl!("insights-pipeline-full-libcamera");
@@ -204,6 +210,13 @@ impl AppModel {
             ),
         );
 
+        // Duplicated frames
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-duplicated-frames")).control(
+                widget::text::body(format!("{}", self.insights.duplicated_frames)),
+            ),
+        );
+
         // Frame size
         let decoded_mb = self.insights.frame_size_decoded as f64 / (1024.0 * 1024.0);
         section = section.add(
@@ -935,6 +948,65 @@ impl AppModel {
         section
     }
 
+    /// Build the USB connection section: the link the camera got, what the
+    /// active format needs from it, and advice if it falls short
+    fn build_usb_section(
+        &self,
+        link: &crate::backends::camera::usb_bandwidth::UsbLink,
+    ) -> widget::settings::Section<'_, Message> {
+        use crate::backends::camera::usb_bandwidth::format_bytes_per_sec;
+
+        let mut section = widget::settings::section().title(fl!("insights-usb"));
+
+        section = section
+            .add(
+                widget::settings::item::builder(fl!("insights-usb-speed"))
+                    .control(widget::text::body(link.speed.label())),
+            )
+            .add(
+                widget::settings::item::builder(fl!("insights-usb-camera-max"))
+                    .control(widget::text::body(link.camera_max.label())),
+            )
+            .add(
+                widget::settings::item::builder(fl!("insights-usb-controller-max"))
+                    .control(widget::text::body(link.controller_max.label())),
+            )
+            .add(
+                widget::settings::item::builder(fl!("insights-usb-hubs"))
+                    .control(widget::text::body(link.hubs.to_string())),
+            )
+            .add(
+                widget::settings::item::builder(fl!("insights-usb-shared"))
+                    .control(widget::text::body(link.shared_cameras.to_string())),
+            );
+
+        if let Some(needed) = self.active_format.as_ref().and_then(format_bytes_per_sec) {
+            let mb = |bytes: u64| bytes as f64 / 1_000_000.0;
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-usb-format-bandwidth")).control(
+                    widget::text::body(format!(
+                        "{:.1} / {:.1} MB/s",
+                        mb(needed),
+                        mb(link.speed.budget_bytes_per_sec())
+                    )),
+                ),
+            );
+        }
+
+        if let Some(advice) = self.active_format_usb_advice()
+            && let Some(format) = &self.active_format
+        {
+            let text = crate::app::format_picker::view::usb_advice_text(advice, format);
+            section = section.add(widget::settings::item_row(vec![
+                widget::text::body(text)
+                    .class(cosmic::theme::style::iced::Text::Custom(error_text_style))
+                    .into(),
+            ]));
+        }
+
+        section
+    }
+
     /// Build the Backend section (libcamera-specific info)
     fn build_backend_section(&self) -> widget::settings::Section<'_, Message> {
         let mut section = widget::settings::section().title(fl!("insights-backend"));
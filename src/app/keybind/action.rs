@@ -11,6 +11,11 @@ pub enum Action {
     /// Take a still photo. In Video mode while recording, this triggers the
     /// "photo during recording" button without interrupting the video.
     PhotoSnapshot,
+    /// Copy a photo to the clipboard instead of saving it
+    CopyPhoto,
+    /// Add a numbered marker to the event subtitles of the video being
+    /// recorded. Only fires while recording.
+    AddRecordingMarker,
 
     // Camera
     SwitchCamera,
@@ -40,6 +45,8 @@ pub enum Action {
     ResetZoom,
     TogglePreviewFit,
     CyclePhotoAspectRatio,
+    /// Open or close the 1:1 focus loupe
+    ToggleLoupe,
 
     // App
     OpenGallery,
@@ -66,6 +73,8 @@ impl Action {
         // Capture
         Action::Capture,
         Action::PhotoSnapshot,
+        Action::CopyPhoto,
+        Action::AddRecordingMarker,
         // Camera
         Action::SwitchCamera,
         Action::ToggleFocusAuto,
@@ -88,6 +97,7 @@ impl Action {
         Action::ResetZoom,
         Action::TogglePreviewFit,
         Action::CyclePhotoAspectRatio,
+        Action::ToggleLoupe,
         // App
         Action::OpenGallery,
         Action::ToggleAbout,
@@ -98,7 +108,10 @@ impl Action {
 
     pub fn category(self) -> ActionCategory {
         match self {
-            Action::Capture | Action::PhotoSnapshot => ActionCategory::Capture,
+            Action::Capture
+            | Action::PhotoSnapshot
+            | Action::CopyPhoto
+            | Action::AddRecordingMarker => ActionCategory::Capture,
             Action::SwitchCamera | Action::ToggleFocusAuto | Action::ToggleFlash => {
                 ActionCategory::Camera
             }
@@ -114,7 +127,8 @@ impl Action {
             | Action::ZoomOut
             | Action::ResetZoom
             | Action::TogglePreviewFit
-            | Action::CyclePhotoAspectRatio => ActionCategory::Zoom,
+            | Action::CyclePhotoAspectRatio
+            | Action::ToggleLoupe => ActionCategory::Zoom,
             Action::OpenGallery
             | Action::ToggleAbout
             | Action::ResetAllSettings
@@ -136,6 +150,8 @@ impl Action {
             // Spacebar arrives as Key::Character(" "), not a Named variant.
             Action::Capture => kb(vec![], Key::Character(" ".into())),
             Action::PhotoSnapshot => kb(vec![], Key::Named(Named::Enter)),
+            Action::CopyPhoto => kb(ctrl(), Key::Character("c".into())),
+            Action::AddRecordingMarker => kb(vec![], Key::Character("k".into())),
 
             Action::SwitchCamera => kb(vec![], Key::Character("s".into())),
             Action::ToggleFocusAuto => kb(vec![], Key::Character("a".into())),
@@ -164,6 +180,7 @@ impl Action {
             // takes `v` ("view fit").
             Action::TogglePreviewFit => kb(vec![], Key::Character("v".into())),
             Action::CyclePhotoAspectRatio => kb(ctrl(), Key::Character("a".into())),
+            Action::ToggleLoupe => kb(vec![], Key::Character("z".into())),
 
             Action::OpenGallery => kb(vec![], Key::Character("g".into())),
             Action::ToggleAbout => kb(vec![], Key::Named(Named::F1)),
@@ -186,6 +203,8 @@ impl Action {
             // recording. This mapping is the message the subscription emits
             // when that gate passes.
             Action::PhotoSnapshot => Message::Capture,
+            Action::CopyPhoto => Message::CopyPhotoToClipboard,
+            Action::AddRecordingMarker => Message::AddRecordingMarker,
 
             Action::SwitchCamera => Message::SwitchCamera,
             Action::ToggleFocusAuto => Message::ToggleFocusAuto,
@@ -208,6 +227,7 @@ impl Action {
             Action::ResetZoom => Message::ResetZoom,
             Action::TogglePreviewFit => Message::TogglePreviewFit,
             Action::CyclePhotoAspectRatio => Message::CyclePhotoAspectRatio,
+            Action::ToggleLoupe => Message::ToggleLoupe,
 
             Action::OpenGallery => Message::OpenGallery,
             Action::ToggleAbout => Message::OpenSettingsPage(SettingsPage::About),
@@ -222,6 +242,8 @@ impl Action {
         match self {
             Action::Capture => fl!("action-capture"),
             Action::PhotoSnapshot => fl!("action-photo-snapshot"),
+            Action::CopyPhoto => fl!("action-copy-photo"),
+            Action::AddRecordingMarker => fl!("action-add-recording-marker"),
             Action::SwitchCamera => fl!("action-switch-camera"),
             Action::ToggleFocusAuto => fl!("action-toggle-focus-auto"),
             Action::ToggleFlash => fl!("action-toggle-flash"),
@@ -240,6 +262,7 @@ impl Action {
             Action::ResetZoom => fl!("action-reset-zoom"),
             Action::TogglePreviewFit => fl!("action-toggle-preview-fit"),
             Action::CyclePhotoAspectRatio => fl!("action-cycle-photo-aspect-ratio"),
+            Action::ToggleLoupe => fl!("action-toggle-loupe"),
             Action::OpenGallery => fl!("action-open-gallery"),
             Action::ToggleAbout => fl!("about"),
             Action::ResetAllSettings => fl!("settings-reset-all"),
@@ -37,6 +37,7 @@ pub fn view<'a>(app: &'a AppModel) -> context_drawer::ContextDrawer<'a, Message>
                         widget::icon::from_name("edit-undo-symbolic").symbolic(true),
                     )
                     .extra_small()
+                    .tooltip(fl!("a11y-reset"))
                     .on_press(Message::ResetKeyBindToDefault(action)),
                 );
             }
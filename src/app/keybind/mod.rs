@@ -71,6 +71,9 @@ fn dispatch_capture(mode: CameraMode, has_file_source: bool) -> Option<Message>
         // Timelapse mirrors Video: Space toggles the capture session on/off.
         CameraMode::Video => Message::ToggleRecording,
         CameraMode::Timelapse => Message::ToggleTimelapse,
+        CameraMode::Document => Message::CaptureDocumentPage,
+        CameraMode::Whiteboard => Message::CaptureWhiteboard,
+        CameraMode::Scan => Message::ToggleScanning,
         CameraMode::Virtual => Message::ToggleVirtualCamera,
         CameraMode::View => return None,
     })
@@ -84,6 +87,7 @@ pub fn subscription(
     mode: CameraMode,
     has_file_source: bool,
     is_video_recording: bool,
+    keyboard_nav: bool,
 ) -> Subscription<Message> {
     #[derive(Hash)]
     struct KeyShortcutsId {
@@ -91,6 +95,7 @@ pub fn subscription(
         mode: CameraMode,
         has_file_source: bool,
         is_video_recording: bool,
+        keyboard_nav: bool,
     }
 
     let id = KeyShortcutsId {
@@ -98,6 +103,7 @@ pub fn subscription(
         mode,
         has_file_source,
         is_video_recording,
+        keyboard_nav,
     };
     let map = bindings.into_map();
 
@@ -119,10 +125,11 @@ pub fn subscription(
             return None;
         };
 
-        // Esc closes drawers/pickers. libcosmic's keyboard_nav is disabled, so we
-        // emit Message::Escape ourselves; the update handler routes it to on_escape().
+        // Esc closes drawers/pickers. libcosmic's keyboard_nav calls on_escape()
+        // itself; with it disabled we emit Message::Escape, which the update
+        // handler routes to on_escape().
         if let Key::Named(Named::Escape) = &key {
-            return Some(Message::Escape);
+            return (!keyboard_nav).then_some(Message::Escape);
         }
 
         // Match against `modified_key` (layout-aware: Shift+/ on US and
@@ -147,6 +154,10 @@ pub fn subscription(
         if action == Action::PhotoSnapshot {
             return is_video_recording.then_some(Message::Capture);
         }
+        // Markers only mean something on a recording's timeline
+        if action == Action::AddRecordingMarker {
+            return is_video_recording.then_some(Message::AddRecordingMarker);
+        }
         Some(action.message())
     })
 }
@@ -205,6 +216,9 @@ mod tests {
             Some(Message::ToggleTimelapse) => "toggle-timelapse",
             Some(Message::ToggleVirtualCamera) => "toggle-virtual-camera",
             Some(Message::ToggleVideoPlayPause) => "toggle-video-play-pause",
+            Some(Message::CaptureDocumentPage) => "capture-document-page",
+            Some(Message::CaptureWhiteboard) => "capture-whiteboard",
+            Some(Message::ToggleScanning) => "toggle-scanning",
             _ => "other",
         }
     }
@@ -224,6 +238,18 @@ mod tests {
             tag(dispatch_capture(CameraMode::Virtual, false)),
             "toggle-virtual-camera"
         );
+        assert_eq!(
+            tag(dispatch_capture(CameraMode::Document, false)),
+            "capture-document-page"
+        );
+        assert_eq!(
+            tag(dispatch_capture(CameraMode::Whiteboard, false)),
+            "capture-whiteboard"
+        );
+        assert_eq!(
+            tag(dispatch_capture(CameraMode::Scan, false)),
+            "toggle-scanning"
+        );
         assert_eq!(tag(dispatch_capture(CameraMode::View, false)), "none");
     }
 
@@ -233,6 +259,9 @@ mod tests {
             CameraMode::Photo,
             CameraMode::Video,
             CameraMode::Timelapse,
+            CameraMode::Document,
+            CameraMode::Whiteboard,
+            CameraMode::Scan,
             CameraMode::Virtual,
             CameraMode::View,
         ] {
@@ -0,0 +1,187 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Glass-to-glass latency measurement
+//!
+//! With a capture card fed from this computer's display (or any camera
+//! pointed at the screen), the window is held black and then flashed white.
+//! The time from the flash to the first preview frame that brightens covers
+//! everything in between: the display, the card, USB, the capture pipeline
+//! and the app. The flash is repeated [`ROUNDS`] times and the median kept,
+//! so one late frame doesn't skew the result.
+//!
+//! The flash starts when the window is asked to turn white, so the result
+//! includes up to one display refresh more than the chain itself.
+
+use crate::backends::camera::types::{CameraFrame, PixelFormat};
+use std::time::{Duration, Instant};
+
+/// Flashes measured per run
+pub const ROUNDS: usize = 5;
+
+/// How long the window stays black before each flash, so the preview
+/// settles on the dark screen and auto exposure catches up
+const DARK_TIME: Duration = Duration::from_millis(600);
+
+/// How long a flash is waited for before the run gives up
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Rise in mean luma (0-1) over the dark baseline that counts as the flash
+const RISE: f32 = 0.15;
+
+/// Points sampled along each axis for the mean luma
+const SAMPLE_GRID: usize = 16;
+
+#[derive(Debug, Clone, Copy)]
+enum Phase {
+    /// Window black since `since`; `baseline` is the latest frame's luma
+    Dark {
+        since: Instant,
+        baseline: Option<f32>,
+    },
+    /// Window white since `since`
+    Flash { since: Instant, baseline: f32 },
+}
+
+/// Result of a finished run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeOutcome {
+    /// Median latency over all rounds
+    Measured(Duration),
+    /// The preview never brightened: the camera doesn't see the screen
+    NoFlashSeen,
+}
+
+/// A latency measurement in progress
+#[derive(Debug, Clone)]
+pub struct LatencyProbe {
+    phase: Phase,
+    samples: Vec<Duration>,
+}
+
+impl LatencyProbe {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            phase: Phase::Dark {
+                since: now,
+                baseline: None,
+            },
+            samples: Vec::with_capacity(ROUNDS),
+        }
+    }
+
+    /// Whether the window should be white rather than black
+    pub fn is_flashing(&self) -> bool {
+        matches!(self.phase, Phase::Flash { .. })
+    }
+
+    /// Feed the mean luma of a preview frame that arrived at `now`.
+    /// Returns the outcome once the run is over.
+    pub fn on_frame(&mut self, luma: f32, now: Instant) -> Option<ProbeOutcome> {
+        match self.phase {
+            Phase::Dark { since, .. } if now.duration_since(since) < DARK_TIME => {
+                self.phase = Phase::Dark {
+                    since,
+                    baseline: Some(luma),
+                };
+            }
+            Phase::Dark { baseline, .. } => {
+                self.phase = Phase::Flash {
+                    since: now,
+                    baseline: baseline.unwrap_or(luma),
+                };
+            }
+            Phase::Flash { since, baseline } if luma >= baseline + RISE => {
+                self.samples.push(now.duration_since(since));
+                if self.samples.len() == ROUNDS {
+                    self.samples.sort();
+                    return Some(ProbeOutcome::Measured(self.samples[ROUNDS / 2]));
+                }
+                self.phase = Phase::Dark {
+                    since: now,
+                    baseline: None,
+                };
+            }
+            Phase::Flash { since, .. } if now.duration_since(since) > TIMEOUT => {
+                return Some(ProbeOutcome::NoFlashSeen);
+            }
+            Phase::Flash { .. } => {}
+        }
+        None
+    }
+}
+
+/// Mean luma (0-1) of a frame, sampled on a [`SAMPLE_GRID`]² grid. `None`
+/// for formats without a byte of luma per pixel to sample.
+pub fn mean_luma(frame: &CameraFrame) -> Option<f32> {
+    let (offset, bpp) = match frame.format {
+        PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::I420 | PixelFormat::Gray8 => (0, 1),
+        PixelFormat::YUYV | PixelFormat::YVYU => (0, 2),
+        PixelFormat::UYVY | PixelFormat::VYUY => (1, 2),
+        // Green dominates perceived brightness; plenty for a black/white flash
+        PixelFormat::RGBA
+        | PixelFormat::BGRA
+        | PixelFormat::ABGR
+        | PixelFormat::RGBx
+        | PixelFormat::BGRx => (1, 4),
+        PixelFormat::RGB24 => (1, 3),
+        // High byte of each little-endian 16-bit luma sample
+        PixelFormat::P010 => (1, 2),
+        PixelFormat::Y210 => (1, 4),
+        _ => return None,
+    };
+    let data: &[u8] = &frame.data;
+    let (width, height) = (frame.width as usize, frame.height as usize);
+    let stride = if frame.stride > 0 {
+        frame.stride as usize
+    } else {
+        width * bpp
+    };
+
+    let mut sum = 0u32;
+    for gy in 0..SAMPLE_GRID {
+        let y = (gy * 2 + 1) * height / (SAMPLE_GRID * 2);
+        for gx in 0..SAMPLE_GRID {
+            let x = (gx * 2 + 1) * width / (SAMPLE_GRID * 2);
+            sum += u32::from(*data.get(y * stride + x * bpp + offset)?);
+        }
+    }
+    Some(sum as f32 / (SAMPLE_GRID * SAMPLE_GRID) as f32 / 255.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency_is_the_median_delay_to_the_flash() {
+        let start = Instant::now();
+        let mut probe = LatencyProbe::new(start);
+        let mut now = start;
+        let mut outcome = None;
+        for delay_ms in [40, 50, 90, 45, 55] {
+            // Frames of the dark screen until the flash starts
+            while !probe.is_flashing() {
+                now += Duration::from_millis(10);
+                assert_eq!(probe.on_frame(0.05, now), None);
+            }
+            now += Duration::from_millis(delay_ms);
+            outcome = probe.on_frame(0.8, now);
+        }
+        assert_eq!(
+            outcome,
+            Some(ProbeOutcome::Measured(Duration::from_millis(50)))
+        );
+
+        let mut blind = LatencyProbe::new(start);
+        let mut now = start;
+        while !blind.is_flashing() {
+            now += Duration::from_millis(10);
+            blind.on_frame(0.3, now);
+        }
+        assert_eq!(blind.on_frame(0.35, now + TIMEOUT / 2), None);
+        assert_eq!(
+            blind.on_frame(0.35, now + TIMEOUT * 2),
+            Some(ProbeOutcome::NoFlashSeen)
+        );
+    }
+}
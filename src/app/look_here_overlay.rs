@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! "Look here" marker
+//!
+//! A small pulsing dot at the window edge nearest the physical camera,
+//! shown in Virtual mode and while the virtual camera is streaming. On a
+//! call it is natural to look at the other person's face rather than the
+//! lens; the marker is a gentle reminder of where the lens is. The camera
+//! location is set per device, since a laptop's built-in camera sits above
+//! the screen while a USB camera may be clipped anywhere.
+
+use crate::app::state::{AppModel, CameraMode, Message};
+use crate::config::CameraLocation;
+use cosmic::Element;
+use cosmic::iced::{Color, Length, Point, Rectangle, Size};
+use cosmic::widget::canvas;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Time between animation frames while the marker is shown
+pub const ANIMATION_INTERVAL: Duration = Duration::from_millis(33);
+
+/// One pulse of the ring, in milliseconds
+const PULSE_PERIOD_MS: u128 = 1600;
+/// Gap between the dot's centre and the window edge
+const EDGE_MARGIN: f32 = 14.0;
+const DOT_RADIUS: f32 = 5.0;
+/// Radius the ring grows to before it fades out
+const RING_RADIUS: f32 = 16.0;
+const MARKER_COLOR: Color = Color::from_rgb(0.3, 0.85, 0.45);
+
+struct LookHereProgram {
+    location: CameraLocation,
+    /// Position in the current pulse (0-1)
+    phase: f32,
+}
+
+impl canvas::Program<Message, cosmic::Theme> for LookHereProgram {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &(),
+        renderer: &cosmic::Renderer,
+        _theme: &cosmic::Theme,
+        bounds: Rectangle,
+        _cursor: cosmic::iced::mouse::Cursor,
+    ) -> Vec<canvas::Geometry<cosmic::Renderer>> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let center = marker_center(self.location, bounds.size());
+
+        let ring = canvas::path::Path::circle(
+            center,
+            DOT_RADIUS + (RING_RADIUS - DOT_RADIUS) * self.phase,
+        );
+        frame.stroke(
+            &ring,
+            canvas::Stroke::default()
+                .with_color(Color {
+                    a: 0.8 * (1.0 - self.phase),
+                    ..MARKER_COLOR
+                })
+                .with_width(2.0),
+        );
+        frame.fill(
+            &canvas::path::Path::circle(center, DOT_RADIUS),
+            MARKER_COLOR,
+        );
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// Centre of the marker for a camera location in a window of `size`
+fn marker_center(location: CameraLocation, size: Size) -> Point {
+    let (x, y) = location.anchor();
+    let place = |fraction: f32, extent: f32| {
+        let margin = EDGE_MARGIN.min(extent / 2.0);
+        (fraction * extent).clamp(margin, extent - margin)
+    };
+    Point::new(place(x, size.width), place(y, size.height))
+}
+
+impl AppModel {
+    /// Whether the "look here" marker is on screen
+    pub(crate) fn look_here_visible(&self) -> bool {
+        self.config.look_here_marker
+            && (self.mode == CameraMode::Virtual || self.virtual_camera.is_streaming())
+    }
+
+    /// Where the active camera sits relative to the screen
+    pub(crate) fn current_camera_location(&self) -> CameraLocation {
+        self.available_cameras
+            .get(self.current_camera_index)
+            .and_then(|camera| self.config.camera_locations.get(&camera.path))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Build the "look here" marker overlay
+    pub fn build_look_here_overlay(&self) -> Element<'_, Message> {
+        if !self.look_here_visible() {
+            return cosmic::widget::Space::new()
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into();
+        }
+
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        cosmic::widget::Canvas::new(LookHereProgram {
+            location: self.current_camera_location(),
+            phase: (millis % PULSE_PERIOD_MS) as f32 / PULSE_PERIOD_MS as f32,
+        })
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marker_stays_inside_the_window() {
+        let size = Size::new(800.0, 600.0);
+        assert_eq!(
+            marker_center(CameraLocation::TopCenter, size),
+            Point::new(400.0, EDGE_MARGIN)
+        );
+        assert_eq!(
+            marker_center(CameraLocation::BottomRight, size),
+            Point::new(800.0 - EDGE_MARGIN, 600.0 - EDGE_MARGIN)
+        );
+        assert_eq!(
+            marker_center(CameraLocation::Left, size),
+            Point::new(EDGE_MARGIN, 300.0)
+        );
+
+        // A window smaller than the margins gets the dot in its middle
+        assert_eq!(
+            marker_center(CameraLocation::BottomRight, Size::new(10.0, 10.0)),
+            Point::new(5.0, 5.0)
+        );
+    }
+}
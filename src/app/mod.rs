@@ -11,12 +11,13 @@
 //! - `camera_preview`: Camera preview display widget
 //! - `controls`: Capture button and recording UI
 //! - `bottom_bar`: Gallery, mode switcher, camera switcher
+//! - `control_layout`: Per-mode placement of the optional overlay controls
 //! - `settings`: Settings drawer UI
 //! - `format_picker`: Format/resolution picker UI and logic
 //! - `dropdowns`: Dropdown management
 //! - `camera_ops`: Camera operations (switching cameras, changing formats)
-//! - `ui`: UI widget building (legacy)
 //! - `view`: Main view rendering
+//! - `adaptive_layout`: Window-size driven layout variants
 //! - `update`: Message handling
 //!
 //! # Main Types
@@ -25,36 +26,53 @@
 //! - `Message`: All possible user interactions and system events
 //! - `CameraMode`: Photo or Video capture modes
 
+pub mod adaptive_layout;
+mod adaptive_preview;
 mod bottom_bar;
+mod burst_picker;
 mod camera_ops;
 mod camera_preview;
+mod clock;
 mod composition_overlay;
+pub mod control_layout;
 mod controls;
+#[cfg(feature = "dev-console")]
+mod dev_console;
 mod dropdowns;
+mod exposure_osd;
 pub mod exposure_picker;
+mod extension_units_page;
 mod filter_picker;
 mod format_picker;
+mod frame_pacer;
 pub mod frame_processor;
+mod frame_tap;
 mod frosted_backdrop;
 mod gallery_primitive;
 mod gallery_widget;
 mod handlers;
 pub mod insights;
 pub mod keybind;
+mod latency_probe;
+mod look_here_overlay;
 mod motor_picker;
+mod overlay_snapshot;
 mod overlay_style;
 mod preview_geometry;
+mod qr_history_page;
 pub mod qr_overlay;
+mod qr_share;
 pub mod settings;
 mod state;
-mod ui;
 mod update;
 mod utils;
 mod video_primitive;
 mod video_widget;
 mod view;
+mod whiteboard_overlay;
 
 // Re-export public API
+use crate::backends::camera::frame_hub::{ConsumerKind, FrameHub, QueuePolicy};
 use crate::config::Config;
 use crate::fl;
 use cosmic::app::context_drawer;
@@ -64,11 +82,11 @@ use cosmic::widget::{self, about::About};
 use cosmic::{Element, Task};
 use iced_futures::subscription;
 pub use state::{
-    AppFlags, AppModel, BurstModeStage, BurstModeState, CameraMode, ContextPage, FileSource,
-    FilterType, Message, PhotoAspectRatio, PhotoTimerSetting, PrewarmResults, RecordingState,
-    TimelapseState, VirtualCameraState,
+    AnimatedClipState, AppFlags, AppModel, BurstModeStage, BurstModeState, CameraMode, ContextPage,
+    FileSource, FilterType, Message, PhotoAspectRatio, PhotoTimerSetting, PrewarmResults,
+    RecordingState, TimelapseState, VirtualCameraState,
 };
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
 /// Helper to create a subscription with an ID and a stream, replacing the removed `run_with_id`.
@@ -105,6 +123,39 @@ where
     subscription::from_recipe(IdStream { id, stream })
 }
 
+/// Subscription that attaches a frame hub consumer keeping only the newest
+/// frame, and hands that frame to `analyse` every `interval`.
+///
+/// The consumer is attached once the subscription runs and detached when
+/// iced drops it, so frame analysis costs nothing while it is switched off.
+fn frame_analysis_subscription<I, F, Fut>(
+    id: I,
+    frame_hub: Arc<FrameHub>,
+    kind: ConsumerKind,
+    interval: std::time::Duration,
+    mut analyse: F,
+) -> Subscription<Message>
+where
+    I: std::hash::Hash + 'static,
+    F: FnMut(Arc<crate::backends::camera::types::CameraFrame>) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = Message> + Send,
+{
+    subscription_with_id(
+        id,
+        cosmic::iced::stream::channel(1, async move |mut output| {
+            let mut frames = frame_hub.subscribe(kind, QueuePolicy::Latest);
+            while let Some(frame) = frames.recv().await {
+                // Copy frame for background task - mapped buffers become invalid when pipeline stops
+                let frame = Arc::new(frame.to_copied());
+                if output.send(analyse(frame).await).await.is_err() {
+                    break;
+                }
+                tokio::time::sleep(interval).await;
+            }
+        }),
+    )
+}
+
 /// Get the photo save directory
 ///
 /// Uses XDG Pictures directory for proper flatpak compatibility.
@@ -247,11 +298,6 @@ impl cosmic::Application for AppModel {
     ) -> (Self, Task<cosmic::Action<Self::Message>>) {
         let init_start = std::time::Instant::now();
 
-        // Disable libcosmic's built-in keyboard navigation (Tab/Shift+Tab/Esc/F11/Ctrl+F).
-        // Tab focus traversal is removed by user request; Esc handling is restored by our
-        // own keybind subscription, which emits Message::Escape on Esc presses.
-        core.set_keyboard_nav(false);
-
         // Create the about widget
         let about = About::default()
             .name(fl!("camera"))
@@ -292,6 +338,11 @@ impl cosmic::Application for AppModel {
                 }
             };
 
+        // libcosmic's built-in keyboard navigation (Tab/Shift+Tab/Esc/F11/Ctrl+F)
+        // follows the setting. With it off, Esc is still handled by our own
+        // keybind subscription, which emits Message::Escape on Esc presses.
+        core.set_keyboard_nav(config.keyboard_navigation);
+
         // Publish the overlay effect before the first draw: the colour roots in
         // `overlay_style` read a global, not `self.config`.
         crate::app::overlay_style::init_overlay_effect(config.overlay_effect);
@@ -384,13 +435,45 @@ impl cosmic::Application for AppModel {
         // Read the Copy flags before `flags.preview_source` is moved below.
         let preview_spoof_recording = flags.preview_spoof_recording;
         let preview_fake_camera = flags.preview_fake_camera;
+        let dev_console = flags.dev_console;
+        let start_in_mirror = flags.mirror;
+        let mirror_light = flags.mirror_light;
+        let preview_playback = flags.preview_playback;
+        let frame_tap = if flags.frame_tap {
+            frame_tap::FrameTap::start()
+                .map_err(|e| error!(error = %e, "Frame tap unavailable"))
+                .ok()
+        } else {
+            None
+        };
+
+        // A preview source directory plays as a playlist, starting on its
+        // first entry
+        let preview_playlist = flags
+            .preview_source
+            .as_ref()
+            .filter(|path| path.is_dir())
+            .and_then(|dir| {
+                crate::backends::virtual_camera::Playlist::scan(dir)
+                    .inspect(|playlist| {
+                        info!(path = %dir.display(), entries = playlist.len(), "Using directory as preview source");
+                    })
+                    .map_err(|e| error!(error = %e, "Preview source directory unusable"))
+                    .ok()
+            });
 
         // Convert preview source path to FileSource if provided
         let preview_file_source = flags.preview_source.and_then(|path| {
+            if let Some(playlist) = &preview_playlist {
+                return Some(playlist.current().clone());
+            }
             if !path.exists() {
                 error!(path = %path.display(), "Preview source file not found");
                 return None;
             }
+            if path.is_dir() {
+                return None;
+            }
             let ext = path
                 .extension()
                 .and_then(|e| e.to_str())
@@ -435,13 +518,24 @@ impl cosmic::Application for AppModel {
             photo_btn_scale_to: 1.0,
             photo_btn_anim_start: None,
             recording: RecordingState::default(),
-            preview_spoof_recording,
+            clock: crate::app::clock::system(),
             recording_session_counter: 0,
+            recording_events: None,
+            recorder_warmup: None,
+            screen_cast_pending: false,
+            screen_cast: None,
+            storage_warning: None,
+            storage_warning_dismissed: false,
             virtual_camera: VirtualCameraState::default(),
             virtual_camera_file_source: preview_file_source,
+            preview_playlist,
+            preview_playback,
             current_frame_is_file_source: has_preview_source,
+            dev_console,
+            frame_tap,
             current_frame_rotation: crate::backends::camera::types::SensorRotation::None,
             blur_frame_rotation: crate::backends::camera::types::SensorRotation::None,
+            device_orientation: crate::backends::camera::types::SensorRotation::None,
             blur_frame_mirror: false,
             blur_frame_zoom: 1.0,
             video_file_progress: None,
@@ -455,9 +549,18 @@ impl cosmic::Application for AppModel {
             format_picker_visible: false,
             exposure_picker_visible: false,
             color_picker_visible: false,
+            wb_eyedropper_active: false,
+            loupe: None,
+            software_white_balance: crate::shaders::WhiteBalanceGains::IDENTITY,
             tools_menu_visible: false,
             motor_picker_visible: false,
             exposure_settings: None,
+            ae_af_lock: None,
+            auto_holds: std::collections::HashMap::new(),
+            osd_values: None,
+            latency_probe: None,
+            measured_latency: None,
+            signalled_colorimetry: None,
             color_settings: None,
             available_exposure_controls:
                 crate::app::exposure_picker::AvailableExposureControls::default(),
@@ -470,13 +573,56 @@ impl cosmic::Application for AppModel {
                 model
             },
             base_exposure_time: None,
+            exposure_suggestion: None,
+            control_overrides: Default::default(),
+            failover_camera: None,
+            standby_camera_dropdown_options: Vec::new(),
+            pip_camera_dropdown_options: Vec::new(),
+            pip_corner_dropdown_options: vec![
+                fl!("pip-corner-top-left"),
+                fl!("pip-corner-top-right"),
+                fl!("pip-corner-bottom-left"),
+                fl!("pip-corner-bottom-right"),
+            ],
+            camera_location_dropdown_options: vec![
+                fl!("camera-location-top-left"),
+                fl!("camera-location-top-center"),
+                fl!("camera-location-top-right"),
+                fl!("camera-location-left"),
+                fl!("camera-location-right"),
+                fl!("camera-location-bottom-left"),
+                fl!("camera-location-bottom-center"),
+                fl!("camera-location-bottom-right"),
+            ],
+            dual_camera_layout_dropdown_options: vec![
+                fl!("dual-camera-layout-pip"),
+                fl!("dual-camera-layout-side-by-side"),
+            ],
+            secondary_resolution_dropdown_options: vec![
+                fl!("secondary-resolution-low"),
+                fl!("secondary-resolution-hd"),
+                fl!("secondary-resolution-full-hd"),
+            ],
+            pip_preview: None,
             burst_mode: BurstModeState::default(),
+            burst_preview: None,
+            product_capture_active: false,
             auto_detected_frame_count: 1, // Start with 1 (no HDR+) until first brightness evaluation
             hdr_override_disabled: false,
+            light_reading: None,
+            burst_plan: None,
+            night_detector: Default::default(),
+            night_mode_engaged: false,
             selected_filter: FilterType::default(),
+            imported_luts: filter_picker::lut_library::list(),
+            selected_lut: None,
+            lut_error: None,
             recording_filter_code: std::sync::Arc::new(std::sync::atomic::AtomicU32::new(0)),
+            audio_sync_offset: Default::default(),
+            av_sync_test: Default::default(),
             flash: state::FlashState {
                 enabled: false,
+                torch: false,
                 active: false,
                 hardware: {
                     let hw = crate::flash::FlashHardware::detect();
@@ -497,18 +643,39 @@ impl cosmic::Application for AppModel {
             photo_timer_setting: PhotoTimerSetting::default(),
             photo_timer_countdown: None,
             photo_timer_tick_start: None,
+            photo_booth: None,
+            focus_stack: None,
+            burst_picker: None,
             photo_aspect_ratio: initial_aspect_ratio,
             zoom_level: 1.0,
             zoom_animation: None,
             preview_fit_to_view: initial_preview_fit,
             fit_animation: None,
             ui_hidden: false,
+            mirror_mode: false,
+            screen_boost: None,
+            qr_share: None,
             last_bug_report_path: None,
             last_media_path: None,
             pending_close: false,
             gallery_thumbnail: None,
             gallery_thumbnail_rgba: None,
-            picker_selected_resolution: None,
+            gallery_thumbnail_path: None,
+            gallery_loaded: false,
+            share_menu: None,
+            clipboard_notice: None,
+            error_notice: None,
+            share_targets: crate::share::ShareTargets::default(),
+            share_targets_error: None,
+            upload_status: std::collections::HashMap::new(),
+            upload_since: None,
+            upload_form: crate::app::state::UploadForm::default(),
+            upload_provider_dropdown_options: vec![
+                fl!("upload-provider-webdav"),
+                fl!("upload-provider-s3"),
+            ],
+            format_picker_query: String::new(),
+            usb_link: None,
             pending_hotplug_switch: None,
             backend_manager: Some(backend_manager),
             camera_cancel_flag: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
@@ -516,12 +683,16 @@ impl cosmic::Application for AppModel {
             still_capture_requested: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
             latest_still_frame: std::sync::Arc::new(std::sync::Mutex::new(None)),
             still_frame_notify: std::sync::Arc::new(tokio::sync::Notify::new()),
+            preview_mailbox: Default::default(),
+            frame_pacer: Default::default(),
             current_frame: None,
             available_cameras,
             current_camera_index,
             pending_persist_camera: None,
             available_formats: available_formats.clone(),
             active_format: initial_format,
+            adaptive_preview: Default::default(),
+            video_disk_free_bytes: None,
             available_audio_devices,
             current_audio_device_index,
             available_video_encoders,
@@ -570,6 +741,37 @@ impl cosmic::Application for AppModel {
                 .iter()
                 .map(|e| e.display_name().to_string())
                 .collect(),
+            video_container_dropdown_options: crate::config::VideoContainer::ALL
+                .iter()
+                .map(|c| match c {
+                    crate::config::VideoContainer::Auto => fl!("settings-container-auto"),
+                    c => c.display_name().to_string(),
+                })
+                .collect(),
+            video_stabilization_dropdown_options: vec![
+                fl!("stabilization-off"),
+                fl!("stabilization-low"),
+                fl!("stabilization-medium"),
+                fl!("stabilization-high"),
+            ],
+            video_denoise_dropdown_options: vec![
+                fl!("denoise-off"),
+                fl!("denoise-low"),
+                fl!("denoise-medium"),
+                fl!("denoise-high"),
+            ],
+            anamorphic_desqueeze_dropdown_options: crate::config::AnamorphicDesqueeze::ALL
+                .iter()
+                .map(|d| match d {
+                    crate::config::AnamorphicDesqueeze::Off => fl!("desqueeze-off"),
+                    d => format!("{}×", d.factor()),
+                })
+                .collect(),
+            night_mode_dropdown_options: vec![
+                fl!("night-mode-off"),
+                fl!("night-mode-suggest"),
+                fl!("night-mode-auto"),
+            ],
             composition_guide_dropdown_options: vec![
                 fl!("guide-none"),
                 fl!("guide-rule-of-thirds"),
@@ -581,6 +783,76 @@ impl cosmic::Application for AppModel {
                 fl!("guide-diagonal"),
                 fl!("guide-crosshair"),
             ],
+            one_handed_layout_dropdown_options: vec![
+                fl!("one-handed-off"),
+                fl!("one-handed-left"),
+                fl!("one-handed-right"),
+            ],
+            volume_key_action_dropdown_options: vec![
+                fl!("volume-key-capture"),
+                fl!("volume-key-zoom"),
+                fl!("volume-key-volume"),
+            ],
+            color_vision_assist_dropdown_options: vec![
+                fl!("color-vision-off"),
+                fl!("color-vision-correct-protanopia"),
+                fl!("color-vision-correct-deuteranopia"),
+                fl!("color-vision-correct-tritanopia"),
+                fl!("color-vision-simulate-protanopia"),
+                fl!("color-vision-simulate-deuteranopia"),
+                fl!("color-vision-simulate-tritanopia"),
+            ],
+            preview_scaling_dropdown_options: vec![
+                fl!("preview-scaling-nearest"),
+                fl!("preview-scaling-bilinear"),
+                fl!("preview-scaling-lanczos"),
+            ],
+            hdr_input_dropdown_options: vec![
+                fl!("hdr-input-auto"),
+                fl!("hdr-input-sdr"),
+                fl!("hdr-input-pq"),
+                fl!("hdr-input-hlg"),
+            ],
+            photo_booth_layout_dropdown_options: vec![
+                fl!("photo-booth-layout-strip"),
+                fl!("photo-booth-layout-grid"),
+            ],
+            photo_booth_shots_dropdown_options: crate::pipelines::photo::booth::SHOT_COUNTS
+                .iter()
+                .map(|&count| fl!("photo-booth-shot-count", count = count))
+                .collect(),
+            photo_booth_countdown_dropdown_options:
+                crate::pipelines::photo::booth::COUNTDOWN_SECONDS
+                    .iter()
+                    .map(|&seconds| fl!("photo-booth-countdown-seconds", seconds = seconds))
+                    .collect(),
+            rapid_burst_max_shots_dropdown_options: crate::pipelines::photo::rapid_burst::MAX_SHOTS
+                .iter()
+                .map(|&count| fl!("rapid-burst-shot-count", count = count))
+                .collect(),
+            focus_stack_steps_dropdown_options:
+                crate::pipelines::photo::burst_mode::focus_stack::STEP_COUNTS
+                    .iter()
+                    .map(|&count| fl!("focus-stack-step-count", count = count))
+                    .collect(),
+            low_space_warning_dropdown_options: crate::storage::LOW_SPACE_WARNING_MB
+                .iter()
+                .map(|&mb| crate::storage::format_size(u64::from(mb) * 1_000_000))
+                .collect(),
+            camera_rotation_dropdown_options: crate::config::CameraOrientation::ROTATIONS
+                .iter()
+                .map(|degrees| format!("{degrees}°"))
+                .collect(),
+            control_placement_dropdown_options: vec![
+                fl!("control-placement-top-bar"),
+                fl!("control-placement-tools-menu"),
+                fl!("control-placement-hidden"),
+            ],
+            control_visibility_dropdown_options: vec![
+                fl!("control-placement-shown"),
+                fl!("control-placement-hidden"),
+            ],
+            layout_editor_mode: CameraMode::Photo,
             default_mode_dropdown_options: {
                 let mut opts = vec![fl!("mode-photo"), fl!("mode-video"), fl!("mode-timelapse")];
                 if virtual_camera_enabled {
@@ -589,10 +861,33 @@ impl cosmic::Application for AppModel {
                 opts
             },
             timelapse: TimelapseState::default(),
+            animated_clip: AnimatedClipState::default(),
+            animated_clip_format_dropdown_options: vec![
+                fl!("animated-clip-format-off"),
+                fl!("animated-clip-format-gif"),
+                fl!("animated-clip-format-webp"),
+            ],
+            animated_clip_seconds_dropdown_options: crate::media::animation::CLIP_SECONDS
+                .iter()
+                .map(|&seconds| fl!("animated-clip-seconds", seconds = seconds))
+                .collect(),
+            animated_clip_fps_dropdown_options: crate::media::animation::CLIP_FPS
+                .iter()
+                .map(|&fps| fl!("animated-clip-fps", fps = fps))
+                .collect(),
             timelapse_interval_dropdown_options: crate::config::TimelapseInterval::ALL
                 .iter()
                 .map(|i| i.display_name().to_string())
                 .collect(),
+            still_extraction: Default::default(),
+            still_extraction_dropdown_options: std::iter::once(fl!("still-extraction-scenes"))
+                .chain(
+                    crate::config::TimelapseInterval::ALL
+                        .iter()
+                        .map(|i| i.display_name().to_string()),
+                )
+                .collect(),
+            post_stabilization: Default::default(),
             device_info_visible: false,
             audio_probe: None,
             probe_audio_levels: None,
@@ -603,13 +898,35 @@ impl cosmic::Application for AppModel {
             // QR detection enabled by default
             qr_detection_enabled: true,
             qr_detections: Vec::new(),
-            last_qr_detection_time: None,
+            wifi_join: None,
+            qr_history: crate::qr_history::history_path()
+                .map(|path| {
+                    crate::qr_history::QrHistory::load(&path).unwrap_or_else(|e| {
+                        warn!(error = %e, "Failed to load the QR history");
+                        Default::default()
+                    })
+                })
+                .unwrap_or_default(),
+            qr_history_query: String::new(),
+            gesture_hold_start: None,
+            face_last_seen: None,
+            face_exposure_bias: None,
+            face_region_active: false,
+            document: Default::default(),
+            whiteboard: Default::default(),
+            scan: Default::default(),
+            scene_feed: Default::default(),
+            scene_suggestion: None,
+            dismissed_scenes: Vec::new(),
             // Privacy cover detection
             privacy_cover_closed: false,
             idle_inhibit: None,
             idle_inhibit_fd: None,
             // Insights drawer
             insights: Default::default(),
+            xu_controls: Vec::new(),
+            automation_rules: Default::default(),
+            automation_rules_error: None,
         };
 
         // Make context drawer overlay the content instead of reserving space
@@ -631,9 +948,12 @@ impl cosmic::Application for AppModel {
         // be captured without running the encoder. `recording` is set to
         // `Recording` so every is_recording()-gated widget renders correctly, but
         // no GStreamer pipeline is started and no frames are diverted from the
-        // preview (we never call `set_recording_sender`); the indicator shows a
-        // fixed placeholder duration. See preview/capture-previews.sh.
+        // preview (we never call `set_recording_sender`). The app clock is frozen
+        // 7 s after the recording started so the indicator reads a believable
+        // 00:07 on every run. See preview/capture-previews.sh.
         if preview_spoof_recording {
+            use crate::app::clock::{Clock, ManualClock};
+            let clock = std::sync::Arc::new(ManualClock::new(std::time::Instant::now()));
             app.mode = CameraMode::Video;
             app.recording_session_counter += 1;
             let (stop_tx, _stop_rx) = tokio::sync::oneshot::channel();
@@ -642,7 +962,10 @@ impl cosmic::Application for AppModel {
                 String::new(),
                 stop_tx,
                 Some(Default::default()),
+                clock.now(),
             );
+            clock.advance(std::time::Duration::from_secs(7));
+            app.clock = clock;
             app.update_mode_options();
         }
 
@@ -658,8 +981,9 @@ impl cosmic::Application for AppModel {
             // from `--preview-source`; with a file source active the camera
             // subscription never runs, so these devices are never opened. The
             // prewarm enumeration handle, if any, is simply dropped.
-            let (cameras, formats) =
-                crate::backends::camera::synthetic::synthetic_preview_cameras();
+            let (cameras, formats) = crate::backends::camera::synthetic::synthetic_preview_cameras(
+                app.preview_playback.capture,
+            );
             Task::done(cosmic::Action::App(Message::CamerasInitialized(
                 cameras, 0, formats,
             )))
@@ -735,11 +1059,14 @@ impl cosmic::Application for AppModel {
             )
         };
 
-        // Load initial gallery thumbnail
+        // Load initial gallery thumbnail, once previews of photos a crash
+        // left unprocessed are out of the way, then bring the thumbnail
+        // cache up to date for the rest of the captures in the background
         let folder_name = app.config.save_folder_name.clone();
         let folder_name2 = folder_name.clone();
         let load_thumbnail_task = Task::perform(
             async move {
+                crate::storage::remove_pending_photos(get_photo_directory(&folder_name)).await;
                 crate::storage::load_latest_thumbnail(
                     get_photo_directory(&folder_name),
                     get_video_directory(&folder_name2),
@@ -747,7 +1074,14 @@ impl cosmic::Application for AppModel {
                 .await
             },
             |handle| cosmic::Action::App(Message::GalleryThumbnailLoaded(handle)),
-        );
+        )
+        .chain(Task::perform(
+            crate::storage::thumbnails::fill_cache(vec![
+                get_photo_directory(&app.config.save_folder_name),
+                get_video_directory(&app.config.save_folder_name),
+            ]),
+            |_| cosmic::Action::App(Message::Noop),
+        ));
 
         // If a preview source was provided via CLI, trigger loading it
         let preview_source_task = if let Some(ref source) = app.virtual_camera_file_source {
@@ -759,6 +1093,17 @@ impl cosmic::Application for AppModel {
             Task::none()
         };
 
+        // `camera mirror` goes straight to mirror mode, with the light on if
+        // asked for (not saved; the setting is the user's choice in the app)
+        let mirror_task = if start_in_mirror {
+            if mirror_light {
+                app.config.mirror_light = true;
+            }
+            Task::done(cosmic::Action::App(Message::ToggleMirrorMode))
+        } else {
+            Task::none()
+        };
+
         // Log available encoders in parallel (non-critical, doesn't block camera init)
         let encoder_log_task = Task::perform(
             async { crate::pipelines::video::check_available_encoders() },
@@ -781,6 +1126,11 @@ impl cosmic::Application for AppModel {
             |result| cosmic::Action::App(Message::GpuPipelinesWarmed(result)),
         );
 
+        // Leave the volume keys to the system if the user asked for that
+        crate::backends::volume_keys::set_enabled(
+            app.config.volume_key_action != crate::config::VolumeKeyAction::Volume,
+        );
+
         // Apply the theme from config on startup.
         // On non-COSMIC desktops with System theme, query the XDG portal for the
         // actual color scheme so we don't briefly flash the wrong theme.
@@ -817,6 +1167,7 @@ impl cosmic::Application for AppModel {
                 encoder_log_task,
                 load_thumbnail_task,
                 preview_source_task,
+                mirror_task,
                 gpu_warmup_task,
                 theme_task,
             ]),
@@ -856,6 +1207,8 @@ impl cosmic::Application for AppModel {
             ContextPage::Filters => self.filters_view(),
             ContextPage::Insights => self.insights_view(),
             ContextPage::KeyBindings => keybind::key_bindings_page::view(self),
+            ContextPage::ExtensionUnits => extension_units_page::view(self),
+            ContextPage::QrHistory => qr_history_page::view(self),
         })
     }
 
@@ -870,11 +1223,41 @@ impl cosmic::Application for AppModel {
             return Task::none();
         }
 
+        // Stop a latency measurement, which hides every other control
+        if self.latency_probe.take().is_some() {
+            info!("Latency measurement cancelled");
+            return Task::none();
+        }
+
+        // Leave mirror mode, whose only other way out is a small button
+        if self.mirror_mode {
+            return self.handle_toggle_mirror_mode();
+        }
+
+        // Leave the full-screen QR code, then the composer
+        if let Some(share) = &self.qr_share {
+            return if share.fullscreen {
+                self.handle_toggle_qr_share_fullscreen()
+            } else {
+                self.handle_close_qr_share()
+            };
+        }
+
         // Abort photo timer countdown if active
         if self.photo_timer_countdown.is_some() {
             return self.handle_abort_photo_timer();
         }
 
+        // Leave the white balance eyedropper
+        if self.wb_eyedropper_active {
+            return self.handle_cancel_white_balance_pick();
+        }
+
+        // Close the focus loupe
+        if self.loupe.is_some() {
+            return self.handle_toggle_loupe();
+        }
+
         // Close color picker and return to tools menu
         if self.color_picker_visible {
             self.color_picker_visible = false;
@@ -938,11 +1321,12 @@ impl cosmic::Application for AppModel {
             .get(self.current_camera_index)
             .cloned();
         let camera_index = self.current_camera_index;
-        let current_format = self.active_format.clone();
+        let current_format = self.preview_format();
         let cancel_flag = Arc::clone(&self.camera_cancel_flag);
         let still_capture_requested = Arc::clone(&self.still_capture_requested);
         let latest_still_frame = Arc::clone(&self.latest_still_frame);
         let still_frame_notify = Arc::clone(&self.still_frame_notify);
+        let preview_mailbox = Arc::clone(&self.preview_mailbox);
         // Create a unique ID based on format properties to trigger restart when format changes
         let format_id = current_format
             .as_ref()
@@ -955,9 +1339,12 @@ impl cosmic::Application for AppModel {
         // Restart counter forces subscription to restart (e.g., after HDR+ processing)
         let restart_counter = self.camera_stream_restart_counter;
 
-        // Get the shared recording sender Arc so the capture thread can forward
-        // frames directly to the appsrc recording pipeline (libcamera only).
-        let recording_sender = self.backend_manager.as_ref().map(|m| m.recording_sender());
+        // Capture card mode configures the pipeline, so toggling it restarts it
+        let low_latency = self.capture_card_active();
+
+        // Get the manager's frame hub so the capture thread fans frames out to
+        // the recorder and the virtual camera directly (libcamera only).
+        let frame_hub = self.backend_manager.as_ref().map(|m| m.frame_hub());
         let jpeg_recording_mode = self
             .backend_manager
             .as_ref()
@@ -971,6 +1358,10 @@ impl cosmic::Application for AppModel {
         let camera_sub = if file_source_active {
             // No camera subscription when file source is active (file source handles preview)
             Subscription::none()
+        } else if self.privacy_cover_closed {
+            // The device stays released while its privacy switch is engaged;
+            // the privacy poll below brings the subscription back
+            Subscription::none()
         } else {
             subscription_with_id(
                 (
@@ -981,6 +1372,7 @@ impl cosmic::Application for AppModel {
                     // This allows preview to continue during recording
                     cameras_initialized,
                     restart_counter, // Forces restart (HDR+ processing, mode switch with role change)
+                    low_latency,
                 ),
                 cosmic::iced::stream::channel(100, async move |mut output| {
                     info!(camera_index, "Camera subscription started");
@@ -989,7 +1381,6 @@ impl cosmic::Application for AppModel {
                     // and ensures proper cleanup before creating new pipelines
 
                     let mut frame_count = 0u64;
-                    let mut last_forward = std::time::Instant::now();
                     let mut is_first_pipeline = true;
                     loop {
                         // Check cancel flag at the start of each loop iteration
@@ -1159,11 +1550,15 @@ impl cosmic::Application for AppModel {
                                     format.clone()
                                 };
 
-                                // Use the shared recording sender from the manager,
-                                // or a dummy Arc if no manager is available.
-                                let rec_sender = recording_sender
-                                    .clone()
-                                    .unwrap_or_else(|| Arc::new(Mutex::new(None)));
+                                // Use the manager's frame hub. Without a manager
+                                // nothing else can attach, so a private hub only
+                                // feeds the preview.
+                                let hub = frame_hub.clone().unwrap_or_else(|| {
+                                    warn!(
+                                        "No camera backend manager: only the preview gets frames"
+                                    );
+                                    Arc::new(crate::backends::camera::frame_hub::FrameHub::new())
+                                });
 
                                 match NativeLibcameraPipeline::new(
                                     camera_name,
@@ -1174,10 +1569,11 @@ impl cosmic::Application for AppModel {
                                         still_requested: Arc::clone(&still_capture_requested),
                                         still_frame: Arc::clone(&latest_still_frame),
                                         still_frame_notify: Arc::clone(&still_frame_notify),
-                                        recording_sender: rec_sender,
+                                        hub,
                                         jpeg_recording_mode: Arc::clone(&jpeg_recording_mode),
                                         cancel_flag: Arc::clone(&cancel_flag),
                                     },
+                                    low_latency,
                                 ) {
                                     Ok(pipeline) => {
                                         info!("Native libcamera pipeline started");
@@ -1185,6 +1581,8 @@ impl cosmic::Application for AppModel {
                                     }
                                     Err(e) => {
                                         error!(error = %e, "Failed to create libcamera pipeline");
+                                        let _ =
+                                            output.send(Message::CameraStartFailed(e.into())).await;
                                         None
                                     }
                                 }
@@ -1230,30 +1628,9 @@ impl cosmic::Application for AppModel {
                                                 latest_frame = newer_frame;
                                                 drained_count += 1;
                                             }
-
-                                            // Frame pacing: smooth out bursty ISP delivery.
-                                            // If we forwarded a frame recently, wait until the
-                                            // next target interval before sending another.
-                                            // This turns ISP bursts (3-4 frames at once) into
-                                            // evenly-spaced UI updates.
-                                            const MIN_FRAME_INTERVAL: tokio::time::Duration =
-                                                tokio::time::Duration::from_millis(30);
-                                            let since_last = last_forward.elapsed();
-                                            if since_last < MIN_FRAME_INTERVAL && drained_count > 0
-                                            {
-                                                let wait =
-                                                    MIN_FRAME_INTERVAL.saturating_sub(since_last);
-                                                tokio::time::sleep(wait).await;
-                                                // After sleeping, drain again to get the absolute
-                                                // latest frame
-                                                while let Ok(newer_frame) = receiver.try_recv() {
-                                                    latest_frame = newer_frame;
-                                                    drained_count += 1;
-                                                }
-                                            }
+                                            preview_mailbox.count_dropped(u64::from(drained_count));
 
                                             frame_count += 1;
-                                            last_forward = std::time::Instant::now();
                                             // Calculate frame latency (time from capture to subscription delivery)
                                             let latency_us =
                                                 latest_frame.captured_at.elapsed().as_micros();
@@ -1269,34 +1646,23 @@ impl cosmic::Application for AppModel {
                                                 );
                                             }
 
-                                            // Use try_send to avoid blocking the subscription when UI is busy
-                                            // Dropping frames is fine for live preview - we want the latest frame
-                                            match output.try_send(Message::CameraFrame(Arc::new(
-                                                latest_frame,
-                                            ))) {
-                                                Ok(_) => {
-                                                    if frame_count.is_multiple_of(30) {
-                                                        debug!(
-                                                            frame = frame_count,
-                                                            "Frame forwarded to UI"
-                                                        );
-                                                    }
-                                                }
-                                                Err(e) => {
-                                                    // Always log dropped frames for diagnostics
-                                                    tracing::warn!(
-                                                        frame = frame_count,
-                                                        error = ?e,
-                                                        "Frame dropped (UI channel full) - stuttering likely"
+                                            // Leave the frame in the mailbox, replacing one the UI
+                                            // hasn't taken yet, and wake the UI only if it had
+                                            // taken the last one: a busy UI never falls behind
+                                            // by more than a frame (see `frame_pacer`)
+                                            if preview_mailbox.post(Arc::new(latest_frame))
+                                                && let Err(e) =
+                                                    output.try_send(Message::CameraFrameReady)
+                                            {
+                                                if e.is_disconnected() {
+                                                    info!(
+                                                        "Output channel disconnected - Camera subscription being cancelled"
                                                     );
-                                                    // Check if channel is closed (subscription cancelled)
-                                                    if e.is_disconnected() {
-                                                        info!(
-                                                            "Output channel disconnected - Camera subscription being cancelled"
-                                                        );
-                                                        break;
-                                                    }
+                                                    break;
                                                 }
+                                                // Nothing will come for this frame; the next one
+                                                // has to wake the UI instead
+                                                preview_mailbox.take();
                                             }
                                         }
                                         Ok(None) => {
@@ -1324,6 +1690,40 @@ impl cosmic::Application for AppModel {
             )
         }; // End of camera_sub if/else
 
+        // Secondary camera (picture-in-picture or side by side). Keyed on its
+        // path and capture width, so choosing another camera or resolution
+        // (or making it the active one) restarts or stops it; dropping the
+        // subscription drops the `SecondaryCamera` handle, which stops its
+        // pipeline.
+        let pip_camera = self
+            .pip_camera_index()
+            .map(|index| self.available_cameras[index].clone());
+        let pip_width = self.config.secondary_resolution.width();
+        let pip_sub = match (pip_camera, self.backend_manager.clone()) {
+            (Some(device), Some(manager)) if !file_source_active => subscription_with_id(
+                ("pip_camera", device.path.clone(), pip_width),
+                cosmic::iced::stream::channel(4, async move |mut output| {
+                    let (tx, mut rx) = tokio::sync::mpsc::channel(2);
+                    let _camera = match manager.start_pip_camera(&device, pip_width, tx) {
+                        Ok(camera) => camera,
+                        Err(e) => {
+                            // Stay idle rather than retrying in a loop; the
+                            // subscription restarts when the selection changes
+                            warn!(error = %e, "Second camera unavailable");
+                            std::future::pending::<()>().await;
+                            return;
+                        }
+                    };
+                    while let Some(frame) = rx.recv().await {
+                        if output.send(Message::PipFrame(frame)).await.is_err() {
+                            break;
+                        }
+                    }
+                }),
+            ),
+            _ => Subscription::none(),
+        };
+
         // Camera hotplug monitoring subscription
         // Monitors /dev/video* device nodes instead of calling enumerate_cameras(),
         // which returns stale cached results when a capture pipeline is active.
@@ -1453,29 +1853,150 @@ impl cosmic::Application for AppModel {
             }),
         );
 
-        // QR detection subscription (samples frames at 1 FPS)
-        let should_detect_qr = self.qr_detection_enabled
-            && self
-                .last_qr_detection_time
-                .map(|t| t.elapsed() >= std::time::Duration::from_secs(1))
-                .unwrap_or(true);
+        // QR detection subscription: a frame hub consumer that keeps only
+        // the newest frame and scans it at 1 FPS (Scan mode decodes QR codes
+        // with its other barcodes)
+        let should_detect_qr = self.qr_detection_enabled && self.mode != CameraMode::Scan;
 
-        let qr_detection_sub = match (should_detect_qr, &self.current_frame) {
-            (true, Some(frame)) => {
-                // Copy frame for background task - mapped buffers become invalid when pipeline stops
-                let frame = Arc::new(frame.to_copied());
+        let qr_detection_sub = match (should_detect_qr, self.backend_manager.as_ref()) {
+            (true, Some(manager)) => {
+                let frame_hub = manager.frame_hub();
+                let ocr_language =
+                    crate::media::ocr::AVAILABLE.then(|| self.config.ocr_language.clone());
                 subscription_with_id(
-                    ("qr_detection", frame.captured_at),
+                    ("qr_detection", ocr_language.clone()),
                     cosmic::iced::stream::channel(1, async move |mut output| {
-                        let detector = frame_processor::tasks::QrDetector::new();
-                        let detections = detector.detect(frame).await;
-                        let _ = output.send(Message::QrDetectionsUpdated(detections)).await;
+                        let mut detector = frame_processor::tasks::QrDetector::new();
+                        if let Some(language) = ocr_language {
+                            detector = detector.with_ocr_fallback(language);
+                        }
+                        // Attached once the subscription runs and detached
+                        // when iced drops it
+                        let mut frames =
+                            frame_hub.subscribe(ConsumerKind::QrScanner, QueuePolicy::Latest);
+                        while let Some(frame) = frames.recv().await {
+                            // Copy frame for background task - mapped buffers become invalid when pipeline stops
+                            let frame = Arc::new(frame.to_copied());
+                            let detections = detector.detect(frame).await;
+                            if output
+                                .send(Message::QrDetectionsUpdated(detections))
+                                .await
+                                .is_err()
+                            {
+                                break;
+                            }
+                            tokio::time::sleep(
+                                frame_processor::tasks::qr_detector::SAMPLE_INTERVAL,
+                            )
+                            .await;
+                        }
                     }),
                 )
             }
             _ => Subscription::none(),
         };
 
+        let frame_hub = self.backend_manager.as_ref().map(|m| m.frame_hub());
+
+        // Gesture shutter subscription (samples frames at 5 FPS while armed)
+        let gesture_detection_sub = match (self.gesture_shutter_armed(), frame_hub.clone()) {
+            (true, Some(frame_hub)) => frame_analysis_subscription(
+                "gesture_detection",
+                frame_hub,
+                ConsumerKind::GestureDetector,
+                frame_processor::tasks::gesture_detector::SAMPLE_INTERVAL,
+                |frame| async move {
+                    let detector = frame_processor::tasks::GestureDetector::new();
+                    Message::GestureDetectionUpdated(detector.detect(frame).await)
+                },
+            ),
+            _ => Subscription::none(),
+        };
+
+        // Face exposure subscription (samples frames at 2 FPS while
+        // face-priority exposure is armed)
+        let face_detection_sub = match (self.face_exposure_armed(), frame_hub.clone()) {
+            (true, Some(frame_hub)) => frame_analysis_subscription(
+                "face_detection",
+                frame_hub,
+                ConsumerKind::FaceDetector,
+                frame_processor::tasks::face_detector::SAMPLE_INTERVAL,
+                |frame| async move {
+                    let detector = frame_processor::tasks::FaceDetector::new();
+                    Message::FaceDetectionUpdated(detector.detect(frame).await)
+                },
+            ),
+            _ => Subscription::none(),
+        };
+
+        // Document page detection subscription (samples frames at 4 FPS while
+        // auto-capture is waiting for a page)
+        let page_detection_sub = match (self.document_auto_capture_armed(), frame_hub.clone()) {
+            (true, Some(frame_hub)) => frame_analysis_subscription(
+                "page_detection",
+                frame_hub,
+                ConsumerKind::PageDetector,
+                frame_processor::tasks::page_detector::SAMPLE_INTERVAL,
+                |frame| async move {
+                    let detector = frame_processor::tasks::PageDetector::new();
+                    Message::PageSignatureUpdated(detector.detect(frame).await)
+                },
+            ),
+            _ => Subscription::none(),
+        };
+
+        // Whiteboard detection subscription (samples frames at 2 FPS in
+        // Whiteboard mode, for the board outline and auto-snapshot)
+        let board_detection_sub = match (self.whiteboard_detection_armed(), frame_hub.clone()) {
+            (true, Some(frame_hub)) => frame_analysis_subscription(
+                "board_detection",
+                frame_hub,
+                ConsumerKind::BoardDetector,
+                frame_processor::tasks::board_detector::SAMPLE_INTERVAL,
+                |frame| async move {
+                    let detector = frame_processor::tasks::BoardDetector::new();
+                    Message::BoardSignatureUpdated(detector.detect(frame).await)
+                },
+            ),
+            _ => Subscription::none(),
+        };
+
+        // Barcode detection subscription (samples frames at 4 FPS while Scan
+        // mode is running)
+        let barcode_detection_sub = match (self.barcode_detection_armed(), frame_hub.clone()) {
+            (true, Some(frame_hub)) => frame_analysis_subscription(
+                "barcode_detection",
+                frame_hub,
+                ConsumerKind::BarcodeScanner,
+                frame_processor::tasks::barcode_detector::SAMPLE_INTERVAL,
+                |frame| async move {
+                    let detector = frame_processor::tasks::BarcodeDetector::new();
+                    Message::BarcodesDetected(detector.detect(frame).await)
+                },
+            ),
+            _ => Subscription::none(),
+        };
+
+        // Scene classification subscription (samples a frame every couple of
+        // seconds in Photo mode, for the suggestion chip)
+        let scene_classification_sub = match (self.scene_suggestions_armed(), frame_hub.clone()) {
+            (true, Some(frame_hub)) => {
+                // QR codes are already found while QR detection is on
+                let look_for_qr = !self.qr_detection_enabled;
+                frame_analysis_subscription(
+                    ("scene_classification", look_for_qr),
+                    frame_hub,
+                    ConsumerKind::SceneClassifier,
+                    frame_processor::tasks::scene_classifier::SAMPLE_INTERVAL,
+                    move |frame| async move {
+                        let classifier = frame_processor::tasks::SceneClassifier::new(look_for_qr);
+                        Message::SceneClassified(classifier.classify(frame).await)
+                    },
+                )
+            }
+            _ => Subscription::none(),
+        };
+
         // File source preview subscription - receives frames from file streaming thread
         let file_source_preview_sub = if let Some(ref receiver) = self.file_source_preview_receiver
         {
@@ -1518,6 +2039,14 @@ impl cosmic::Application for AppModel {
             Subscription::none()
         };
 
+        // Keeps the "look here" marker pulsing even when frames stall
+        let look_here_animation_sub = if self.look_here_visible() {
+            cosmic::iced::time::every(look_here_overlay::ANIMATION_INTERVAL)
+                .map(|_| Message::LookHereAnimationFrame)
+        } else {
+            Subscription::none()
+        };
+
         // Privacy cover status polling subscription (every 3 seconds)
         // Only runs if the camera has privacy control support
         let privacy_polling_sub = if self.available_exposure_controls.has_privacy {
@@ -1555,24 +2084,84 @@ impl cosmic::Application for AppModel {
             Subscription::none()
         };
 
-        // Brightness evaluation subscription (every 1 second when in Auto mode)
-        // Updates auto_detected_frame_count based on scene brightness
-        // Initial delay of 2 seconds at startup to allow camera to stabilize
-        let brightness_eval_sub = if self.mode == CameraMode::Photo
-            && matches!(
-                self.config.burst_mode_setting,
-                crate::config::BurstModeSetting::Auto
-            )
-            && !self.hdr_override_disabled
-            && self.current_frame.is_some()
-        {
-            // Evaluate brightness every 1 second
-            let interval = std::time::Duration::from_secs(1);
-            cosmic::iced::time::every(interval).map(|_| Message::BrightnessEvaluationTick)
+        // Exposure OSD read-back (twice a second). libcamera frames carry
+        // these values themselves, so only V4L2 cameras are polled.
+        let has_frame_metadata = self
+            .current_frame
+            .as_ref()
+            .is_some_and(|frame| frame.libcamera_metadata.is_some());
+        let exposure_osd_sub = if self.config.exposure_osd && !has_frame_metadata {
+            if let Some(path) = self.get_v4l2_device_path() {
+                let focus_path = self.get_focus_device_path();
+                subscription_with_id(
+                    ("exposure_osd_polling", path.clone()),
+                    cosmic::iced::stream::channel(1, async move |mut output| {
+                        use crate::app::exposure_osd::{OsdValues, POLL_INTERVAL};
+                        loop {
+                            let path = path.clone();
+                            let focus_path = focus_path.clone();
+                            let values = tokio::task::spawn_blocking(move || {
+                                OsdValues::read_v4l2(&path, focus_path.as_deref())
+                            })
+                            .await
+                            .unwrap_or_default();
+
+                            if output.send(Message::OsdValuesRead(values)).await.is_err() {
+                                break;
+                            }
+
+                            tokio::time::sleep(POLL_INTERVAL).await;
+                        }
+                    }),
+                )
+            } else {
+                Subscription::none()
+            }
         } else {
             Subscription::none()
         };
 
+        // Light meter subscription (measures brightness and noise on the GPU
+        // every second in Photo mode, for HDR+ Auto and night mode)
+        let light_meter_sub = match (self.light_meter_armed(), frame_hub.clone()) {
+            (true, Some(frame_hub)) => frame_analysis_subscription(
+                "light_meter",
+                frame_hub,
+                ConsumerKind::LightMeter,
+                frame_processor::tasks::light_meter::SAMPLE_INTERVAL,
+                |frame| async move {
+                    let meter = frame_processor::tasks::LightMeter::new();
+                    Message::LightMeasured(meter.measure(frame).await)
+                },
+            ),
+            _ => Subscription::none(),
+        };
+
+        // Debug frame tap (`--frame-tap`). Frames are only converted and
+        // sent while an inspector is connected.
+        let frame_tap_sub = match (&self.frame_tap, frame_hub) {
+            (Some(tap), Some(frame_hub)) => {
+                let tap = tap.clone();
+                let settings = self.frame_tap_settings();
+                subscription_with_id(
+                    ("frame_tap", settings.to_string()),
+                    cosmic::iced::stream::channel(1, async move |_output| {
+                        let mut frames =
+                            frame_hub.subscribe(ConsumerKind::FrameTap, QueuePolicy::Latest);
+                        while let Some(frame) = frames.recv().await {
+                            if tap.has_clients() {
+                                let stats = frame_tap::frame_tap_stats(&frame, &settings);
+                                // Copy frame for background task - mapped buffers become invalid when pipeline stops
+                                tap.send(Arc::new(frame.to_copied()), stats).await;
+                            }
+                            tokio::time::sleep(frame_tap::SEND_INTERVAL).await;
+                        }
+                    }),
+                )
+            }
+            _ => Subscription::none(),
+        };
+
         // Update insights metrics every 500ms when the Insights drawer is open
         let insights_update_sub =
             if self.context_page == ContextPage::Insights && self.core.window.show_context {
@@ -1582,6 +2171,47 @@ impl cosmic::Application for AppModel {
                 Subscription::none()
             };
 
+        // Watch for other apps changing the controls stored for this camera
+        let has_stored_controls = self
+            .available_cameras
+            .get(self.current_camera_index)
+            .and_then(|cam| self.config.camera_controls.get(&cam.path))
+            .is_some_and(|controls| !controls.is_empty());
+        let control_watchdog_sub = if self.config.persist_camera_controls
+            && has_stored_controls
+            && self.current_frame.is_some()
+        {
+            let interval = std::time::Duration::from_secs(
+                crate::constants::timing::CONTROL_WATCHDOG_INTERVAL_SECS,
+            );
+            cosmic::iced::time::every(interval).map(|_| Message::CameraControlsWatchdogTick)
+        } else {
+            Subscription::none()
+        };
+
+        // Watch for the active camera stalling while a standby camera could
+        // keep the recording or virtual camera alive
+        let standby_available = self
+            .config
+            .standby_camera_path
+            .as_ref()
+            .is_some_and(|path| {
+                self.available_cameras
+                    .get(self.current_camera_index)
+                    .is_some_and(|cam| &cam.path != path)
+                    && self.available_cameras.iter().any(|cam| &cam.path == path)
+            });
+        let failover_watchdog_sub = if standby_available
+            && (self.recording.is_recording() || self.virtual_camera.is_streaming())
+        {
+            let interval = std::time::Duration::from_millis(
+                crate::constants::timing::FAILOVER_WATCHDOG_INTERVAL_MS,
+            );
+            cosmic::iced::time::every(interval).map(|_| Message::FailoverWatchdogTick)
+        } else {
+            Subscription::none()
+        };
+
         // 100 ms audio level snapshot — only while a level source is active.
         let audio_level_sub = if self.audio_probe.is_some() || self.recording.is_recording() {
             let interval = std::time::Duration::from_millis(100);
@@ -1669,6 +2299,7 @@ impl cosmic::Application for AppModel {
                 self.mode,
                 self.virtual_camera_file_source.is_some(),
                 self.recording.is_recording(),
+                self.config.keyboard_navigation,
             )
         };
 
@@ -1677,13 +2308,14 @@ impl cosmic::Application for AppModel {
         // The `volume_keys` backend reads `/dev/input/event*` directly,
         // grabs each device exclusively while the window is focused
         // (so the compositor doesn't *also* change the volume) and
-        // forwards presses as `Message::Capture` — same as the spacebar.
+        // forwards presses as `Message::VolumeKey`, which maps them to
+        // capture or zoom per the volume-key setting.
         let volume_keys_sub = subscription_with_id(
             "volume_keys",
             cosmic::iced::stream::channel(8, async move |mut output| {
                 let mut rx = crate::backends::volume_keys::start();
-                while let Some(_key) = rx.recv().await {
-                    if output.send(Message::Capture).await.is_err() {
+                while let Some(key) = rx.recv().await {
+                    if output.send(Message::VolumeKey(key)).await.is_err() {
                         break;
                     }
                 }
@@ -1709,23 +2341,73 @@ impl cosmic::Application for AppModel {
             }
         });
 
+        // Device orientation from the accelerometer, added to the sensor
+        // rotation for captured output. Without iio-sensor-proxy this just
+        // idles and captures use the sensor rotation alone.
+        let orientation_sub = subscription_with_id(
+            "device_orientation",
+            cosmic::iced::stream::channel(4, async move |mut output| {
+                let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+                let watcher = tokio::spawn(async move {
+                    if let Err(e) = crate::backends::orientation::watch(tx).await {
+                        debug!(error = %e, "Device orientation unavailable");
+                    }
+                });
+                while let Some(rotation) = rx.recv().await {
+                    if output
+                        .send(Message::DeviceOrientationChanged(rotation))
+                        .await
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+                watcher.abort();
+                std::future::pending::<()>().await;
+            }),
+        );
+
+        // Developer console on stdin (`--console`, dev-console feature)
+        #[cfg(feature = "dev-console")]
+        let dev_console_sub = if self.dev_console {
+            dev_console::subscription()
+        } else {
+            Subscription::none()
+        };
+        #[cfg(not(feature = "dev-console"))]
+        let dev_console_sub = Subscription::none();
+
         Subscription::batch([
             config_sub,
             camera_sub,
+            pip_sub,
             hotplug_sub,
             audio_hotplug_sub,
             qr_detection_sub,
+            gesture_detection_sub,
+            face_detection_sub,
+            page_detection_sub,
+            board_detection_sub,
+            barcode_detection_sub,
+            scene_classification_sub,
             file_source_preview_sub,
             timer_animation_sub,
+            look_here_animation_sub,
             privacy_polling_sub,
-            brightness_eval_sub,
+            exposure_osd_sub,
+            light_meter_sub,
+            frame_tap_sub,
             insights_update_sub,
+            control_watchdog_sub,
+            failover_watchdog_sub,
             audio_level_sub,
             portal_theme_sub,
             cosmic_theme_sub,
             keybind_sub,
             volume_keys_sub,
             window_focus_sub,
+            orientation_sub,
+            dev_console_sub,
         ])
     }
 
@@ -38,6 +38,7 @@ impl AppModel {
             .push(widget::text::heading(fl!("ptz-title")).width(Length::Fill))
             .push(
                 widget::button::icon(widget::icon::from_name("edit-undo-symbolic"))
+                    .tooltip(fl!("a11y-reset"))
                     .on_press(Message::ResetPanTilt)
                     .class(cosmic::theme::Button::Text)
                     .padding(4),
@@ -0,0 +1,366 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Headless description of the time-dependent preview overlays.
+//!
+//! The recording, streaming and timelapse indicators and the photo timer
+//! countdown are drawn from an [`OverlaySnapshot`] rather than straight from
+//! the app state, so what they show can be checked without a camera, a GPU
+//! or a display. [`OverlaySnapshot::render`] turns the snapshot into stable
+//! text; the tests below compare that against `snapshots/*.snap` with the
+//! time pinned by a [`ManualClock`](crate::app::clock::ManualClock). Run
+//! with `UPDATE_SNAPSHOTS=1` to accept an intended change.
+
+use crate::app::state::{
+    AnimatedClipState, AppModel, PhotoBoothSession, RecordingState, TimelapseState,
+    VirtualCameraState,
+};
+use crate::media::animation::AnimationFormat;
+use std::fmt::Write as _;
+use std::time::{Duration, Instant};
+
+/// How long a countdown number takes to fade out. The last 100 ms of each
+/// second stay fully transparent before the next number appears.
+const COUNTDOWN_FADE: Duration = Duration::from_millis(900);
+
+/// State the overlays are derived from, borrowed from `AppModel`
+pub struct OverlayInputs<'a> {
+    pub recording: &'a RecordingState,
+    pub virtual_camera: &'a VirtualCameraState,
+    pub timelapse: &'a TimelapseState,
+    pub animated_clip: &'a AnimatedClipState,
+    pub photo_timer_countdown: Option<u8>,
+    pub photo_timer_tick_start: Option<Instant>,
+    pub photo_booth: Option<&'a PhotoBoothSession>,
+}
+
+/// Timelapse indicator contents
+#[derive(Clone, Debug, PartialEq)]
+pub enum TimelapseIndicator {
+    Capturing { shots: u32, elapsed: String },
+    Saving,
+}
+
+/// Animated clip indicator contents
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClipIndicator {
+    Capturing {
+        format: AnimationFormat,
+        frame: u32,
+        total: u32,
+    },
+    Saving(AnimationFormat),
+}
+
+/// Photo timer countdown overlay contents
+#[derive(Clone, Debug, PartialEq)]
+pub struct CountdownOverlay {
+    pub remaining: u8,
+    /// Text opacity of the fading number
+    pub opacity: f32,
+    /// `(shot, total)` during a photo booth run
+    pub booth_shot: Option<(usize, usize)>,
+}
+
+/// Everything the time-dependent overlays show at one instant
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OverlaySnapshot {
+    /// Recording timer, `MM:SS`
+    pub recording: Option<String>,
+    pub streaming: bool,
+    pub timelapse: Option<TimelapseIndicator>,
+    pub clip: Option<ClipIndicator>,
+    pub countdown: Option<CountdownOverlay>,
+}
+
+impl OverlaySnapshot {
+    /// Derive the overlays from `inputs` as they look at `now`
+    pub fn capture(inputs: &OverlayInputs<'_>, now: Instant) -> Self {
+        let recording = inputs
+            .recording
+            .is_recording()
+            .then(|| format_duration(inputs.recording.elapsed_duration(now)));
+
+        let timelapse = if inputs.timelapse.is_finalising() {
+            Some(TimelapseIndicator::Saving)
+        } else if inputs.timelapse.is_running() {
+            Some(TimelapseIndicator::Capturing {
+                shots: inputs.timelapse.shots_taken(),
+                elapsed: format_duration(inputs.timelapse.elapsed_duration(now)),
+            })
+        } else {
+            None
+        };
+
+        let clip = match inputs.animated_clip {
+            AnimatedClipState::Idle => None,
+            AnimatedClipState::Capturing {
+                format,
+                frames_sent,
+                total_frames,
+                ..
+            } => Some(ClipIndicator::Capturing {
+                format: *format,
+                frame: *frames_sent,
+                total: *total_frames,
+            }),
+            AnimatedClipState::Exporting { format } => Some(ClipIndicator::Saving(*format)),
+        };
+
+        let countdown = inputs
+            .photo_timer_countdown
+            .map(|remaining| CountdownOverlay {
+                remaining,
+                opacity: inputs.photo_timer_tick_start.map_or(1.0, |start| {
+                    countdown_opacity(now.saturating_duration_since(start))
+                }),
+                booth_shot: inputs
+                    .photo_booth
+                    .map(|session| (session.next_shot(), session.total)),
+            });
+
+        Self {
+            recording,
+            streaming: inputs.virtual_camera.is_streaming(),
+            timelapse,
+            clip,
+            countdown,
+        }
+    }
+
+    /// Stable text form for snapshot comparison, one overlay per line
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        if let Some(elapsed) = &self.recording {
+            let _ = writeln!(out, "recording: {elapsed}");
+        }
+        if self.streaming {
+            let _ = writeln!(out, "streaming: live");
+        }
+        match &self.timelapse {
+            Some(TimelapseIndicator::Capturing { shots, elapsed }) => {
+                let _ = writeln!(out, "timelapse: {shots} shots - {elapsed}");
+            }
+            Some(TimelapseIndicator::Saving) => {
+                let _ = writeln!(out, "timelapse: saving");
+            }
+            None => {}
+        }
+        match &self.clip {
+            Some(ClipIndicator::Capturing {
+                format,
+                frame,
+                total,
+            }) => {
+                let _ = writeln!(out, "clip: {} {frame}/{total}", format.label());
+            }
+            Some(ClipIndicator::Saving(format)) => {
+                let _ = writeln!(out, "clip: saving {}", format.label());
+            }
+            None => {}
+        }
+        if let Some(countdown) = &self.countdown {
+            let _ = write!(
+                out,
+                "countdown: {} (opacity {:.2})",
+                countdown.remaining, countdown.opacity
+            );
+            if let Some((shot, total)) = countdown.booth_shot {
+                let _ = write!(out, " shot {shot} of {total}");
+            }
+            out.push('\n');
+        }
+        if out.is_empty() {
+            out.push_str("(no overlays)\n");
+        }
+        out
+    }
+}
+
+impl AppModel {
+    /// The overlays as they look now on the app clock
+    pub(crate) fn overlay_snapshot(&self) -> OverlaySnapshot {
+        let inputs = OverlayInputs {
+            recording: &self.recording,
+            virtual_camera: &self.virtual_camera,
+            timelapse: &self.timelapse,
+            animated_clip: &self.animated_clip,
+            photo_timer_countdown: self.photo_timer_countdown,
+            photo_timer_tick_start: self.photo_timer_tick_start,
+            photo_booth: self.photo_booth.as_ref(),
+        };
+        OverlaySnapshot::capture(&inputs, self.clock.now())
+    }
+}
+
+/// Format duration as MM:SS
+pub fn format_duration(seconds: u64) -> String {
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Opacity of a countdown number `elapsed` into its second: 1.0 when it
+/// appears, fading linearly to 0.0
+pub fn countdown_opacity(elapsed: Duration) -> f32 {
+    (1.0 - elapsed.as_secs_f32() / COUNTDOWN_FADE.as_secs_f32()).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::clock::{Clock, ManualClock};
+    use std::path::PathBuf;
+
+    /// Compare `actual` against `snapshots/<name>.snap`, or rewrite the file
+    /// when `UPDATE_SNAPSHOTS` is set
+    fn assert_snapshot(name: &str, actual: &str) {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src/app/snapshots")
+            .join(format!("{name}.snap"));
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::write(&path, actual).expect("write snapshot");
+            return;
+        }
+        let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+            panic!(
+                "missing snapshot {} (run with UPDATE_SNAPSHOTS=1)",
+                path.display()
+            )
+        });
+        assert_eq!(
+            actual, expected,
+            "snapshot {name} changed (run with UPDATE_SNAPSHOTS=1 to accept)"
+        );
+    }
+
+    static IDLE_CLIP: AnimatedClipState = AnimatedClipState::Idle;
+
+    fn idle_inputs<'a>(
+        recording: &'a RecordingState,
+        virtual_camera: &'a VirtualCameraState,
+        timelapse: &'a TimelapseState,
+    ) -> OverlayInputs<'a> {
+        OverlayInputs {
+            recording,
+            virtual_camera,
+            timelapse,
+            animated_clip: &IDLE_CLIP,
+            photo_timer_countdown: None,
+            photo_timer_tick_start: None,
+            photo_booth: None,
+        }
+    }
+
+    #[test]
+    fn idle_has_no_overlays() {
+        let clock = ManualClock::new(Instant::now());
+        let (recording, virtual_camera, timelapse) = Default::default();
+        let inputs = idle_inputs(&recording, &virtual_camera, &timelapse);
+        assert_snapshot(
+            "idle",
+            &OverlaySnapshot::capture(&inputs, clock.now()).render(),
+        );
+    }
+
+    #[test]
+    fn recording_timer_follows_the_clock() {
+        let clock = ManualClock::new(Instant::now());
+        let (stop_tx, _stop_rx) = tokio::sync::oneshot::channel();
+        let recording = RecordingState::start(1, String::new(), stop_tx, None, clock.now());
+        let (virtual_camera, timelapse) = Default::default();
+        let inputs = idle_inputs(&recording, &virtual_camera, &timelapse);
+
+        // Reads 00:00, 00:07, 00:59 and 60:00
+        let mut frames = String::new();
+        for step in [0, 7, 52, 3541] {
+            clock.advance(Duration::from_secs(step));
+            frames.push_str(&OverlaySnapshot::capture(&inputs, clock.now()).render());
+        }
+        assert_snapshot("recording_timer", &frames);
+    }
+
+    #[test]
+    fn timelapse_and_streaming_together() {
+        let clock = ManualClock::new(Instant::now());
+        let start = clock.now();
+        let (frame_tx, _frame_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (stop_tx, _stop_rx) = tokio::sync::oneshot::channel();
+        let (filter_tx, _filter_rx) = tokio::sync::watch::channel(Default::default());
+        let recording = RecordingState::default();
+        let virtual_camera = VirtualCameraState::start(stop_tx, filter_tx, false, start);
+        let mut timelapse = TimelapseState::Running {
+            start_time: start,
+            shots_taken: 0,
+            interval_ms: 1000,
+            frame_sender: frame_tx,
+        };
+        for _ in 0..12 {
+            timelapse.increment_shots();
+        }
+        clock.advance(Duration::from_secs(40));
+
+        let mut frames = OverlaySnapshot::capture(
+            &idle_inputs(&recording, &virtual_camera, &timelapse),
+            clock.now(),
+        )
+        .render();
+        let timelapse = TimelapseState::Finalising {
+            start_time: start,
+            shots_taken: 12,
+        };
+        frames.push_str(
+            &OverlaySnapshot::capture(
+                &idle_inputs(&recording, &virtual_camera, &timelapse),
+                clock.now(),
+            )
+            .render(),
+        );
+        assert_snapshot("timelapse_streaming", &frames);
+    }
+
+    #[test]
+    fn animated_clip_progress_then_saving() {
+        let clock = ManualClock::new(Instant::now());
+        let (recording, virtual_camera, timelapse) = Default::default();
+        let (frame_tx, _frame_rx) = tokio::sync::mpsc::unbounded_channel();
+        let capturing = AnimatedClipState::Capturing {
+            format: AnimationFormat::Gif,
+            frames_sent: 12,
+            total_frames: 45,
+            frame_sender: frame_tx,
+        };
+        let exporting = AnimatedClipState::Exporting {
+            format: AnimationFormat::WebP,
+        };
+
+        let mut frames = String::new();
+        for animated_clip in [&capturing, &exporting] {
+            let inputs = OverlayInputs {
+                animated_clip,
+                ..idle_inputs(&recording, &virtual_camera, &timelapse)
+            };
+            frames.push_str(&OverlaySnapshot::capture(&inputs, clock.now()).render());
+        }
+        assert_snapshot("animated_clip", &frames);
+    }
+
+    #[test]
+    fn countdown_fades_within_each_second() {
+        let clock = ManualClock::new(Instant::now());
+        let (recording, virtual_camera, timelapse) = Default::default();
+        let booth = PhotoBoothSession::new(4);
+        let mut frames = String::new();
+        for (remaining, booth) in [(3, None), (2, Some(&booth))] {
+            let tick_start = clock.now();
+            for _ in 0..4 {
+                let inputs = OverlayInputs {
+                    photo_timer_countdown: Some(remaining),
+                    photo_timer_tick_start: Some(tick_start),
+                    photo_booth: booth,
+                    ..idle_inputs(&recording, &virtual_camera, &timelapse)
+                };
+                frames.push_str(&OverlaySnapshot::capture(&inputs, clock.now()).render());
+                clock.advance(Duration::from_millis(300));
+            }
+        }
+        assert_snapshot("countdown", &frames);
+    }
+}
@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! QR code history page, opened from the QR overlay or the Settings drawer.
+//!
+//! Lists the codes the scanner decoded, newest first, with when each was
+//! scanned and what was done with it. A search field narrows the list, each
+//! code can be acted on or copied again, and the whole history can be
+//! cleared or turned off.
+
+use crate::app::frame_processor::QrAction;
+use crate::app::state::{AppModel, ContextPage, Message};
+use crate::fl;
+use crate::qr_history::{QrHistoryAction, QrHistoryEntry};
+use cosmic::app::context_drawer;
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget::{self, icon};
+
+/// Characters of a code's content shown in the list; the rest is elided
+const CONTENT_PREVIEW_CHARS: usize = 80;
+
+pub fn view(app: &AppModel) -> context_drawer::ContextDrawer<'_, Message> {
+    let spacing = cosmic::theme::spacing();
+    let history = &app.qr_history;
+
+    let settings = widget::settings::section()
+        .add(
+            widget::settings::item::builder(fl!("qr-history-keep"))
+                .description(fl!("qr-history-keep-description"))
+                .toggler(app.config.qr_history, |_| Message::ToggleQrHistory),
+        )
+        .add(
+            widget::settings::item::builder(fl!("qr-history-count", count = history.entries.len()))
+                .control(
+                    widget::button::destructive(fl!("qr-history-clear")).on_press_maybe(
+                        (!history.entries.is_empty()).then_some(Message::ClearQrHistory),
+                    ),
+                ),
+        );
+
+    let mut column = widget::column::with_capacity(3)
+        .spacing(spacing.space_m)
+        .push(settings);
+
+    if !history.entries.is_empty() {
+        column = column.push(
+            widget::search_input(fl!("qr-history-search"), app.qr_history_query.as_str())
+                .on_input(Message::QrHistoryQuery)
+                .on_clear(Message::QrHistoryQuery(String::new())),
+        );
+    }
+
+    let mut list = widget::settings::section();
+    let mut shown = 0;
+    for entry in history.search(&app.qr_history_query) {
+        list = list.add(entry_row(entry));
+        shown += 1;
+    }
+    column = column.push(if shown > 0 {
+        cosmic::Element::from(list)
+    } else if history.entries.is_empty() {
+        widget::text::body(fl!("qr-history-empty")).into()
+    } else {
+        widget::text::body(fl!("qr-history-no-match")).into()
+    });
+
+    context_drawer::context_drawer(
+        widget::scrollable(column).width(Length::Fill),
+        Message::ToggleContextPage(ContextPage::QrHistory),
+    )
+    .title(fl!("qr-history-title"))
+    .actions(app.settings_back_button())
+}
+
+/// A scanned code: its content, when it was scanned and what was done, with
+/// its action and a copy button
+fn entry_row(entry: &QrHistoryEntry) -> cosmic::Element<'_, Message> {
+    let spacing = cosmic::theme::spacing();
+
+    let mut title: String = entry.content.chars().take(CONTENT_PREVIEW_CHARS).collect();
+    if title.len() < entry.content.len() {
+        title.push('…');
+    }
+    let mut description = chrono::DateTime::from_timestamp(entry.scanned_at, 0)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default();
+    for action in &entry.actions {
+        description.push_str(" · ");
+        description.push_str(&match action {
+            QrHistoryAction::Opened => fl!("qr-history-opened"),
+            QrHistoryAction::JoinedWifi => fl!("qr-history-joined"),
+            QrHistoryAction::Copied => fl!("qr-history-copied"),
+        });
+    }
+
+    let controls = widget::row::with_capacity(2)
+        .spacing(spacing.space_xxs)
+        .align_y(Alignment::Center)
+        .push(
+            widget::button::standard(QrAction::parse(&entry.content).action_label())
+                .on_press(Message::QrRunAction(entry.content.clone())),
+        )
+        .push(
+            widget::button::icon(icon::from_name("edit-copy-symbolic").symbolic(true))
+                .tooltip(fl!("a11y-copy"))
+                .on_press(Message::QrCopyText(entry.content.clone())),
+        );
+
+    widget::settings::item::builder(title)
+        .description(description)
+        .control(controls)
+        .into()
+}
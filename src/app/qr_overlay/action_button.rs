@@ -2,10 +2,66 @@
 
 //! Action buttons for QR code detections
 //!
-//! This module provides utilities for converting QR actions to app messages.
+//! This module provides utilities for converting QR actions to app messages,
+//! and the buttons shown below each code.
 
-use crate::app::frame_processor::{QrAction, urlencoding_encode};
-use crate::app::state::Message;
+use crate::app::frame_processor::{QrAction, QrDetection, urlencoding_encode};
+use crate::app::state::{ContextPage, Message, WifiJoin};
+use crate::fl;
+use crate::network_manager::{CONNECTIVITY_CHECK_URL, WifiJoinStatus};
+use cosmic::{Element, widget};
+
+/// Buttons below a detected code: its action, and a way to the history of
+/// scanned codes
+pub fn detection_buttons<'a>(
+    detection: &QrDetection,
+    wifi_join: Option<&WifiJoin>,
+) -> Element<'a, Message> {
+    let history = widget::button::icon(
+        widget::icon::from_name("document-open-recent-symbolic").symbolic(true),
+    )
+    .tooltip(fl!("a11y-qr-history"))
+    .on_press(Message::ToggleContextPage(ContextPage::QrHistory));
+    widget::row::with_capacity(2)
+        .push(action_button(detection, wifi_join))
+        .push(history)
+        .spacing(cosmic::theme::spacing().space_xxs)
+        .align_y(cosmic::iced::Alignment::Center)
+        .into()
+}
+
+/// Button for a detected code. A WiFi code whose network is being joined
+/// shows how far the join got instead of its action: while it runs the
+/// button is inactive, a captive portal turns it into the way to the
+/// sign-in page, and a failure into a retry.
+fn action_button<'a>(
+    detection: &QrDetection,
+    wifi_join: Option<&WifiJoin>,
+) -> Element<'a, Message> {
+    let action = &detection.action;
+    // Through the history, which records what was done with the code
+    let message = Message::QrRunAction(detection.content.clone());
+    let status = match (action, wifi_join) {
+        (QrAction::Wifi { ssid, .. }, Some(join)) if *ssid == join.ssid => &join.status,
+        _ => {
+            return widget::button::suggested(action.action_label())
+                .on_press(message)
+                .into();
+        }
+    };
+    match status {
+        WifiJoinStatus::Connecting => widget::button::suggested(fl!("qr-wifi-connecting")).into(),
+        WifiJoinStatus::Checking => widget::button::suggested(fl!("qr-wifi-checking")).into(),
+        WifiJoinStatus::Online => widget::button::standard(fl!("qr-wifi-online")).into(),
+        WifiJoinStatus::NoInternet => widget::button::standard(fl!("qr-wifi-no-internet")).into(),
+        WifiJoinStatus::CaptivePortal => widget::button::suggested(fl!("qr-wifi-sign-in"))
+            .on_press(Message::QrOpenUrl(CONNECTIVITY_CHECK_URL.to_string()))
+            .into(),
+        WifiJoinStatus::Failed(_) => widget::button::destructive(fl!("qr-wifi-retry"))
+            .on_press(message)
+            .into(),
+    }
+}
 
 /// Convert a QR action to the appropriate app message
 pub fn action_to_message(action: &QrAction) -> Message {
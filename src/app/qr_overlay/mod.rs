@@ -6,7 +6,8 @@
 //! as overlays on top of the camera preview. It includes:
 //!
 //! - Transparent boxes with themed borders around detected QR codes
-//! - Context-aware action buttons based on QR code content
+//! - Context-aware action buttons based on QR code content, which follow a
+//!   WiFi join through to the internet check
 //!
 //! # Coordinate System
 //!
@@ -18,7 +19,7 @@ pub mod action_button;
 mod widget;
 
 use crate::app::frame_processor::{QrAction, QrDetection};
-use crate::app::state::Message;
+use crate::app::state::{Message, WifiJoin};
 use cosmic::Element;
 use cosmic::iced::{Color, Length};
 
@@ -45,8 +46,13 @@ const BUTTON_GAP: f32 = 8.0;
 /// `top_bar_h` / `bottom_bar_h` are the animated UI bar heights — in Contain
 /// mode the video is letterboxed inside the content area between them, so
 /// those values are needed to know where the visible video actually sits.
+///
+/// `wifi_join` is the latest WiFi join started from a code; the button of
+/// that network's code reports its progress.
+#[allow(clippy::too_many_arguments)]
 pub fn build_qr_overlay<'a>(
     detections: &[QrDetection],
+    wifi_join: Option<&WifiJoin>,
     frame_width: u32,
     frame_height: u32,
     cover_blend: f32,
@@ -63,6 +69,7 @@ pub fn build_qr_overlay<'a>(
 
     widget::QrOverlayWidget::new(
         detections.to_vec(),
+        wifi_join,
         frame_width,
         frame_height,
         cover_blend,
@@ -11,12 +11,11 @@ use super::{
     get_action_color, transform_detection_to_screen,
 };
 use crate::app::frame_processor::QrDetection;
-use crate::app::state::Message;
+use crate::app::state::{Message, WifiJoin};
 use cosmic::iced::advanced::widget::{Operation, Tree};
 use cosmic::iced::advanced::{Clipboard, Layout, Shell, Widget, layout, mouse, renderer};
 
 use cosmic::iced::{Border, Color, Element, Event, Length, Point, Rectangle, Size};
-use cosmic::widget;
 use cosmic::{Renderer, Theme};
 
 /// Custom widget for rendering QR code detection overlays
@@ -39,8 +38,10 @@ pub struct QrOverlayWidget<'a> {
 
 impl<'a> QrOverlayWidget<'a> {
     /// Create a new QR overlay widget
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         detections: Vec<QrDetection>,
+        wifi_join: Option<&WifiJoin>,
         frame_width: u32,
         frame_height: u32,
         cover_blend: f32,
@@ -51,12 +52,7 @@ impl<'a> QrOverlayWidget<'a> {
         // Create button elements for each detection
         let buttons: Vec<Element<'a, Message, Theme, Renderer>> = detections
             .iter()
-            .map(|detection| {
-                let label = detection.action.action_label();
-                let message = super::action_button::action_to_message(&detection.action);
-
-                widget::button::suggested(label).on_press(message).into()
-            })
+            .map(|detection| super::action_button::detection_buttons(detection, wifi_join))
             .collect();
 
         Self {
@@ -145,7 +141,7 @@ impl<'a> Widget<Message, Theme, Renderer> for QrOverlayWidget<'a> {
             .zip(tree.children.iter_mut())
             .map(|(((center_x, top_y), button), child_tree)| {
                 // Layout the button with its intrinsic size
-                let button_limits = layout::Limits::new(Size::ZERO, Size::new(200.0, 50.0));
+                let button_limits = layout::Limits::new(Size::ZERO, Size::new(280.0, 50.0));
                 let mut button_node =
                     button
                         .as_widget_mut()
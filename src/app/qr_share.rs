@@ -0,0 +1,387 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! "Show QR" tool
+//!
+//! The other way round from the QR scanner: text, a link or WiFi
+//! credentials are turned into a QR code for another device to scan off the
+//! screen. A WiFi code can be filled in from the network this machine is on,
+//! read through [`network_manager`](crate::network_manager). Full screen puts
+//! the code alone on a white window, with the backlight raised like the
+//! mirror light so phones pick it up at a glance.
+
+use crate::app::frame_processor::{WifiSecurity, wifi_payload};
+use crate::app::overlay_style::{OVERLAY_CONTAINER, POPUP_PANEL, overlay_chip_button_class};
+use crate::app::state::{AppModel, Message};
+use crate::fl;
+use crate::network_manager::WifiCredentials;
+use cosmic::Element;
+use cosmic::iced::widget::image::FilterMethod;
+use cosmic::iced::{Alignment, Background, Color, Length};
+use cosmic::widget;
+
+/// Light modules around the code, as the QR specification asks for
+const QUIET_ZONE: usize = 4;
+
+/// Pixels per module in the rendered image; drawn larger with nearest
+/// filtering so the edges stay sharp
+const MODULE_PIXELS: usize = 4;
+
+/// Side of the code in the composer
+const PREVIEW_SIZE: f32 = 220.0;
+
+/// Share of the shorter window side the code fills in full screen
+const FULLSCREEN_FILL: f32 = 0.8;
+
+/// What the code carries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QrShareKind {
+    /// Text or a link, encoded as typed
+    #[default]
+    Text,
+    /// WiFi network credentials
+    Wifi,
+}
+
+/// Text field of the "Show QR" composer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QrShareField {
+    Text,
+    Ssid,
+    Password,
+}
+
+/// Content of the "Show QR" composer and the code made from it
+#[derive(Debug, Clone)]
+pub struct QrShare {
+    pub kind: QrShareKind,
+    pub text: String,
+    pub ssid: String,
+    pub password: String,
+    pub security: WifiSecurity,
+    pub hidden: bool,
+    /// Code for what is filled in; None while there is nothing to encode
+    pub code: Option<Result<widget::image::Handle, String>>,
+    /// Showing the code alone, full screen
+    pub fullscreen: bool,
+    /// Reading the current WiFi network
+    pub loading_wifi: bool,
+    /// Why the current WiFi network couldn't be read
+    pub wifi_error: Option<String>,
+}
+
+impl Default for QrShare {
+    fn default() -> Self {
+        Self {
+            kind: QrShareKind::Text,
+            text: String::new(),
+            ssid: String::new(),
+            password: String::new(),
+            security: WifiSecurity::None,
+            hidden: false,
+            code: None,
+            fullscreen: false,
+            loading_wifi: false,
+            wifi_error: None,
+        }
+    }
+}
+
+impl QrShare {
+    /// Content of the code for what is filled in, if anything
+    pub fn payload(&self) -> Option<String> {
+        match self.kind {
+            QrShareKind::Text => (!self.text.is_empty()).then(|| self.text.clone()),
+            QrShareKind::Wifi => (!self.ssid.is_empty()).then(|| {
+                let password = Some(self.password.as_str()).filter(|p| !p.is_empty());
+                wifi_payload(&self.ssid, password, &self.security, self.hidden)
+            }),
+        }
+    }
+
+    pub fn set_kind(&mut self, kind: QrShareKind) {
+        self.kind = kind;
+        self.update_code();
+    }
+
+    pub fn set_input(&mut self, field: QrShareField, text: String) {
+        match field {
+            QrShareField::Text => self.text = text,
+            QrShareField::Ssid => self.ssid = text,
+            QrShareField::Password => {
+                // Typing a password makes the network WPA unless it was read
+                // as something else; clearing it makes it open
+                if text.is_empty() {
+                    self.security = WifiSecurity::None;
+                } else if self.security == WifiSecurity::None {
+                    self.security = WifiSecurity::Wpa;
+                }
+                self.password = text;
+            }
+        }
+        self.update_code();
+    }
+
+    pub fn set_hidden(&mut self, hidden: bool) {
+        self.hidden = hidden;
+        self.update_code();
+    }
+
+    /// Fill the WiFi fields in from the network this machine is on
+    pub fn set_wifi(&mut self, credentials: WifiCredentials) {
+        self.kind = QrShareKind::Wifi;
+        self.security = WifiSecurity::parse(match credentials.security.as_str() {
+            "OPEN" => "nopass",
+            other => other,
+        });
+        self.ssid = credentials.ssid;
+        self.password = credentials.password.unwrap_or_default();
+        self.hidden = credentials.hidden;
+        self.wifi_error = None;
+        self.update_code();
+    }
+
+    fn update_code(&mut self) {
+        self.code = self.payload().map(|payload| {
+            render(&payload).map(|(side, rgba)| widget::image::Handle::from_rgba(side, side, rgba))
+        });
+    }
+}
+
+/// Render `payload` as a QR code: dark modules on white, with the quiet
+/// zone. Returns the side length in pixels and the RGBA data.
+pub fn render(payload: &str) -> Result<(u32, Vec<u8>), String> {
+    let code = qrcode::QrCode::new(payload.as_bytes()).map_err(|e| match e {
+        qrcode::types::QrError::DataTooLong => fl!("qr-share-too-long"),
+        e => e.to_string(),
+    })?;
+    let modules = code.width();
+    let side = (modules + 2 * QUIET_ZONE) * MODULE_PIXELS;
+    let mut rgba = vec![255u8; side * side * 4];
+    for (index, color) in code.to_colors().into_iter().enumerate() {
+        if color != qrcode::Color::Dark {
+            continue;
+        }
+        let left = (index % modules + QUIET_ZONE) * MODULE_PIXELS;
+        let top = (index / modules + QUIET_ZONE) * MODULE_PIXELS;
+        for y in top..top + MODULE_PIXELS {
+            let row = (y * side + left) * 4;
+            for pixel in rgba[row..row + MODULE_PIXELS * 4].chunks_exact_mut(4) {
+                pixel[..3].fill(0);
+            }
+        }
+    }
+    Ok((side as u32, rgba))
+}
+
+impl AppModel {
+    /// Build the "Show QR" composer overlay
+    pub fn build_qr_share(&self) -> Element<'_, Message> {
+        let Some(share) = &self.qr_share else {
+            return widget::Space::new()
+                .width(Length::Shrink)
+                .height(Length::Shrink)
+                .into();
+        };
+        let spacing = cosmic::theme::spacing();
+
+        let tab = |label: String, kind: QrShareKind| {
+            widget::button::custom(widget::text::body(label))
+                .padding([spacing.space_xxs, spacing.space_s])
+                .on_press(Message::SelectQrShareKind(kind))
+                .class(if share.kind == kind {
+                    cosmic::theme::Button::Suggested
+                } else {
+                    cosmic::theme::Button::Standard
+                })
+        };
+        let tabs = widget::Row::new()
+            .push(tab(fl!("qr-share-text"), QrShareKind::Text))
+            .push(tab(fl!("qr-share-wifi"), QrShareKind::Wifi))
+            .spacing(spacing.space_xs);
+
+        let mut fields = widget::Column::new().spacing(spacing.space_xs);
+        match share.kind {
+            QrShareKind::Text => {
+                fields = fields.push(
+                    widget::text_input(fl!("qr-share-text-placeholder"), share.text.as_str())
+                        .on_input(|text| Message::QrShareInput(QrShareField::Text, text)),
+                );
+            }
+            QrShareKind::Wifi => {
+                let mut use_current = widget::button::standard(fl!("qr-share-current-wifi"));
+                if !share.loading_wifi {
+                    use_current = use_current.on_press(Message::QrShareUseCurrentWifi);
+                }
+                fields = fields
+                    .push(
+                        widget::text_input(fl!("qr-share-ssid"), share.ssid.as_str())
+                            .on_input(|text| Message::QrShareInput(QrShareField::Ssid, text)),
+                    )
+                    .push(
+                        widget::secure_input(
+                            fl!("qr-share-password"),
+                            share.password.as_str(),
+                            None,
+                            true,
+                        )
+                        .on_input(|text| Message::QrShareInput(QrShareField::Password, text)),
+                    )
+                    .push(
+                        widget::Row::new()
+                            .push(widget::text::body(fl!("qr-share-hidden")))
+                            .push(
+                                widget::Space::new()
+                                    .width(Length::Fill)
+                                    .height(Length::Shrink),
+                            )
+                            .push(
+                                widget::toggler(share.hidden).on_toggle(Message::SetQrShareHidden),
+                            )
+                            .align_y(Alignment::Center),
+                    )
+                    .push(use_current);
+                if let Some(error) = &share.wifi_error {
+                    fields = fields.push(widget::text::caption(error.clone()));
+                }
+            }
+        }
+
+        let code: Element<'_, Message> = match &share.code {
+            Some(Ok(handle)) => widget::image(handle.clone())
+                .width(Length::Fixed(PREVIEW_SIZE))
+                .height(Length::Fixed(PREVIEW_SIZE))
+                .filter_method(FilterMethod::Nearest)
+                .into(),
+            Some(Err(e)) => widget::text::body(e.clone()).into(),
+            None => widget::text::caption(fl!("qr-share-empty")).into(),
+        };
+        let code = widget::container(code)
+            .width(Length::Fill)
+            .height(Length::Fixed(PREVIEW_SIZE))
+            .align_x(cosmic::iced::alignment::Horizontal::Center)
+            .align_y(cosmic::iced::alignment::Vertical::Center);
+
+        let mut show = widget::button::suggested(fl!("qr-share-fullscreen"));
+        if matches!(share.code, Some(Ok(_))) {
+            show = show.on_press(Message::ToggleQrShareFullscreen);
+        }
+        let buttons = widget::Row::new()
+            .push(
+                widget::Space::new()
+                    .width(Length::Fill)
+                    .height(Length::Shrink),
+            )
+            .push(widget::button::standard(fl!("qr-share-close")).on_press(Message::CloseQrShare))
+            .push(show)
+            .spacing(spacing.space_s);
+
+        let content = widget::Column::new()
+            .push(widget::text::heading(fl!("qr-share-title")))
+            .push(tabs)
+            .push(fields)
+            .push(code)
+            .push(buttons)
+            .spacing(spacing.space_s)
+            .width(Length::Fixed(320.0));
+
+        let panel = widget::mouse_area(self.frosted_panel(
+            widget::container(content).padding(spacing.space_m).into(),
+            POPUP_PANEL,
+        ))
+        .on_press(Message::Noop);
+
+        widget::mouse_area(
+            widget::container(panel)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .padding([self.top_ui_height(), 0.0, self.bottom_ui_height(), 0.0])
+                .align_x(cosmic::iced::alignment::Horizontal::Center)
+                .align_y(cosmic::iced::alignment::Vertical::Center),
+        )
+        .on_press(Message::CloseQrShare)
+        .into()
+    }
+
+    /// Build the full-screen code: the code alone on white, and a way back
+    pub fn build_qr_share_fullscreen(&self) -> Element<'_, Message> {
+        let spacing = cosmic::theme::spacing();
+        let size = FULLSCREEN_FILL * self.screen_width.min(self.screen_height);
+        let code: Element<'_, Message> = match self.qr_share.as_ref().and_then(|s| s.code.as_ref())
+        {
+            Some(Ok(handle)) => widget::image(handle.clone())
+                .width(Length::Fixed(size))
+                .height(Length::Fixed(size))
+                .filter_method(FilterMethod::Nearest)
+                .into(),
+            _ => widget::Space::new()
+                .width(Length::Shrink)
+                .height(Length::Shrink)
+                .into(),
+        };
+
+        let back = widget::button::custom(
+            widget::icon::from_name("view-restore-symbolic")
+                .symbolic(true)
+                .size(16),
+        )
+        .name(fl!("a11y-qr-share-exit-fullscreen"))
+        .padding(spacing.space_xs)
+        .on_press(Message::ToggleQrShareFullscreen)
+        .class(overlay_chip_button_class());
+
+        cosmic::iced::widget::stack![
+            widget::container(code)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .align_x(cosmic::iced::alignment::Horizontal::Center)
+                .align_y(cosmic::iced::alignment::Vertical::Center)
+                .style(|_| widget::container::Style {
+                    background: Some(Background::Color(Color::WHITE)),
+                    ..Default::default()
+                }),
+            widget::container(self.frosted_panel(back.into(), OVERLAY_CONTAINER))
+                .width(Length::Fill)
+                .align_x(cosmic::iced::alignment::Horizontal::Right)
+                .padding(spacing.space_s)
+        ]
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_have_a_quiet_zone_and_dark_finders() {
+        let (side, rgba) = render("https://example.com").unwrap();
+        let modules = side as usize / MODULE_PIXELS;
+        assert_eq!(side as usize % MODULE_PIXELS, 0);
+        assert!(modules > 2 * QUIET_ZONE);
+        let pixel = |x: usize, y: usize| &rgba[(y * side as usize + x) * 4..][..4];
+        assert_eq!(pixel(0, 0), [255, 255, 255, 255]);
+        // Top-left corner of the top-left finder pattern
+        let corner = QUIET_ZONE * MODULE_PIXELS;
+        assert_eq!(pixel(corner, corner), [0, 0, 0, 255]);
+        assert_eq!(pixel(corner - 1, corner), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn password_sets_the_security() {
+        let mut share = QrShare {
+            kind: QrShareKind::Wifi,
+            ..QrShare::default()
+        };
+        share.set_input(QrShareField::Ssid, "Home".into());
+        assert_eq!(share.payload().as_deref(), Some("WIFI:T:nopass;S:Home;;"));
+        share.set_input(QrShareField::Password, "secret".into());
+        assert_eq!(share.security, WifiSecurity::Wpa);
+        assert_eq!(
+            share.payload().as_deref(),
+            Some("WIFI:T:WPA;S:Home;P:secret;;")
+        );
+        assert!(matches!(share.code, Some(Ok(_))));
+    }
+}
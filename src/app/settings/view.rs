@@ -3,7 +3,7 @@
 //! Settings drawer view
 
 use crate::app::state::{AppModel, ContextPage, Message, SettingsPage};
-use crate::config::{AppTheme, AudioEncoder, PhotoOutputFormat, TimelapseInterval};
+use crate::config::{AppTheme, AudioEncoder, PhotoOutputFormat, TimelapseInterval, VideoContainer};
 use crate::constants::BitratePreset;
 use crate::fl;
 use cosmic::Element;
@@ -29,6 +29,23 @@ fn disabled_text(value: String) -> Element<'static, Message> {
         .into()
 }
 
+/// A settings slider with its value read out beside it.
+fn slider_row<'a>(
+    slider: impl Into<Element<'a, Message>>,
+    readout: String,
+) -> Element<'a, Message> {
+    widget::Row::new()
+        .push(widget::container(slider).width(Length::Fixed(140.0)))
+        .push(widget::space::horizontal().width(Length::Fixed(8.0)))
+        .push(
+            widget::text::caption(readout)
+                .font(cosmic::font::mono())
+                .size(11),
+        )
+        .align_y(Alignment::Center)
+        .into()
+}
+
 impl AppModel {
     /// Build the Settings context drawer for the current sub-page.
     ///
@@ -52,9 +69,18 @@ impl AppModel {
             SettingsPage::Appearance => {
                 self.settings_subpage(fl!("settings-appearance"), self.appearance_sections())
             }
+            SettingsPage::Controls => {
+                self.settings_subpage(fl!("settings-controls"), self.controls_sections())
+            }
             SettingsPage::VirtualCamera => {
                 self.settings_subpage(fl!("virtual-camera-title"), self.virtual_camera_sections())
             }
+            SettingsPage::Automation => {
+                self.settings_subpage(fl!("settings-automation"), self.automation_sections())
+            }
+            SettingsPage::Upload => {
+                self.settings_subpage(fl!("settings-upload"), self.upload_sections())
+            }
             SettingsPage::BugReports => {
                 self.settings_subpage(fl!("settings-bug-reports"), self.bug_reports_sections())
             }
@@ -68,6 +94,7 @@ impl AppModel {
     /// reached from that menu.
     pub(crate) fn settings_back_button(&self) -> Element<'_, Message> {
         widget::button::icon(icon::from_name("go-previous-symbolic").symbolic(true))
+            .tooltip(fl!("a11y-back"))
             .on_press(Message::OpenSettingsPage(SettingsPage::Root))
             .into()
     }
@@ -106,11 +133,29 @@ impl AppModel {
                 Message::OpenSettingsPage(SettingsPage::Appearance),
                 true,
             ))
+            .add(self.settings_nav_row(
+                "view-grid-symbolic",
+                fl!("settings-controls"),
+                Message::OpenSettingsPage(SettingsPage::Controls),
+                true,
+            ))
             .add(self.settings_nav_row(
                 "camera-web-symbolic",
                 fl!("virtual-camera-title"),
                 Message::OpenSettingsPage(SettingsPage::VirtualCamera),
                 true,
+            ))
+            .add(self.settings_nav_row(
+                "system-run-symbolic",
+                fl!("settings-automation"),
+                Message::OpenSettingsPage(SettingsPage::Automation),
+                true,
+            ))
+            .add(self.settings_nav_row(
+                "folder-remote-symbolic",
+                fl!("settings-upload"),
+                Message::OpenSettingsPage(SettingsPage::Upload),
+                true,
             ));
 
         let tools = widget::list_column()
@@ -126,6 +171,18 @@ impl AppModel {
                 Message::ToggleContextPage(ContextPage::KeyBindings),
                 true,
             ))
+            .add(self.settings_nav_row(
+                "document-open-recent-symbolic",
+                fl!("qr-history-title"),
+                Message::ToggleContextPage(ContextPage::QrHistory),
+                true,
+            ))
+            .add(self.settings_nav_row(
+                "preferences-system-symbolic",
+                fl!("extension-units-title"),
+                Message::ToggleContextPage(ContextPage::ExtensionUnits),
+                true,
+            ))
             .add(self.settings_nav_row(
                 "dialog-warning-symbolic",
                 fl!("settings-bug-reports"),
@@ -234,6 +291,39 @@ impl AppModel {
             .position(|m| *m == self.config.default_mode)
             .unwrap_or(0);
 
+        // Standby index: 0 = None, N = camera N-1; a configured camera that
+        // isn't connected shows no selection
+        let current_standby_index = match self.config.standby_camera_path.as_deref() {
+            None => Some(0),
+            Some(path) => self
+                .available_cameras
+                .iter()
+                .position(|c| c.path == path)
+                .map(|i| i + 1),
+        };
+
+        // Second camera index uses the same 0 = Off mapping
+        let current_pip_index = match self.config.pip_camera_path.as_deref() {
+            None => Some(0),
+            Some(path) => self
+                .available_cameras
+                .iter()
+                .position(|c| c.path == path)
+                .map(|i| i + 1),
+        };
+        let current_pip_corner_index = crate::config::PipCorner::ALL
+            .iter()
+            .position(|c| *c == self.config.pip_corner)
+            .unwrap_or(0);
+        let current_dual_layout_index = crate::config::DualCameraLayout::ALL
+            .iter()
+            .position(|l| *l == self.config.dual_camera_layout)
+            .unwrap_or(0);
+        let current_secondary_resolution_index = crate::config::SecondaryResolution::ALL
+            .iter()
+            .position(|r| *r == self.config.secondary_resolution)
+            .unwrap_or(0);
+
         // Custom device row with label, info button, and dropdown
         let device_control: Element<'_, Message> = if is_recording {
             disabled_text(
@@ -257,6 +347,7 @@ impl AppModel {
             .push(
                 widget::button::icon(icon::from_name("dialog-information-symbolic").symbolic(true))
                     .extra_small()
+                    .tooltip(fl!("a11y-device-info"))
                     .on_press(Message::ToggleDeviceInfo),
             )
             .push(widget::space::horizontal())
@@ -277,7 +368,157 @@ impl AppModel {
                         Some(current_default_mode_index),
                         Message::SelectDefaultMode,
                     )),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-standby-camera"))
+                    .description(fl!("settings-standby-camera-description"))
+                    .control(widget::dropdown(
+                        &self.standby_camera_dropdown_options,
+                        current_standby_index,
+                        Message::SelectStandbyCamera,
+                    )),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-pip-camera"))
+                    .description(fl!("settings-pip-camera-description"))
+                    .control(widget::dropdown(
+                        &self.pip_camera_dropdown_options,
+                        current_pip_index,
+                        Message::SelectPipCamera,
+                    )),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-dual-camera-layout"))
+                    .description(fl!("settings-dual-camera-layout-description"))
+                    .control(widget::dropdown(
+                        &self.dual_camera_layout_dropdown_options,
+                        Some(current_dual_layout_index),
+                        Message::SelectDualCameraLayout,
+                    )),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-secondary-resolution"))
+                    .description(fl!("settings-secondary-resolution-description"))
+                    .control(widget::dropdown(
+                        &self.secondary_resolution_dropdown_options,
+                        Some(current_secondary_resolution_index),
+                        Message::SelectSecondaryResolution,
+                    )),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-pip-corner")).control(
+                    widget::dropdown(
+                        &self.pip_corner_dropdown_options,
+                        Some(current_pip_corner_index),
+                        Message::SelectPipCorner,
+                    ),
+                ),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-persist-controls"))
+                    .description(fl!("settings-persist-controls-description"))
+                    .toggler(self.config.persist_camera_controls, |_| {
+                        Message::TogglePersistCameraControls
+                    }),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-face-exposure"))
+                    .description(fl!("settings-face-exposure-description"))
+                    .toggler(self.config.face_exposure_priority, |_| {
+                        Message::ToggleFaceExposurePriority
+                    }),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-scene-suggestions"))
+                    .description(fl!("settings-scene-suggestions-description"))
+                    .toggler(self.config.scene_suggestions, |_| {
+                        Message::ToggleSceneSuggestions
+                    }),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-exposure-osd"))
+                    .description(fl!("settings-exposure-osd-description"))
+                    .toggler(self.config.exposure_osd, |_| Message::ToggleExposureOsd),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-false-color"))
+                    .description(fl!("settings-false-color-description"))
+                    .toggler(self.config.false_color, |_| Message::ToggleFalseColor),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-color-vision"))
+                    .description(fl!("settings-color-vision-description"))
+                    .control(widget::dropdown(
+                        &self.color_vision_assist_dropdown_options,
+                        crate::config::ColorVisionAssist::ALL
+                            .iter()
+                            .position(|a| *a == self.config.color_vision_assist),
+                        Message::SelectColorVisionAssist,
+                    )),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-preview-scaling"))
+                    .description(fl!("settings-preview-scaling-description"))
+                    .control(widget::dropdown(
+                        &self.preview_scaling_dropdown_options,
+                        crate::config::PreviewScaling::ALL
+                            .iter()
+                            .position(|s| *s == self.config.preview_scaling),
+                        Message::SelectPreviewScaling,
+                    )),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-adaptive-preview"))
+                    .description(fl!("settings-adaptive-preview-description"))
+                    .toggler(self.config.adaptive_preview, |_| {
+                        Message::ToggleAdaptivePreview
+                    }),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-capture-card-mode"))
+                    .description(fl!("settings-capture-card-mode-description"))
+                    .control(
+                        widget::toggler(self.config.capture_card_mode).on_toggle_maybe(
+                            (!self.recording.is_recording())
+                                .then_some(|_| Message::ToggleCaptureCardMode),
+                        ),
+                    ),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-hdr-input"))
+                    .description(fl!("settings-hdr-input-description"))
+                    .control(widget::dropdown(
+                        &self.hdr_input_dropdown_options,
+                        crate::config::HdrInput::ALL
+                            .iter()
+                            .position(|i| *i == self.config.hdr_input),
+                        Message::SelectHdrInput,
+                    )),
+            );
+
+        // Latency can only be measured on a running capture card
+        if self.capture_card_active() {
+            use crate::app::latency_probe::ProbeOutcome;
+
+            let description = match self.measured_latency {
+                Some(ProbeOutcome::Measured(latency)) => {
+                    fl!("capture-card-latency", ms = latency.as_millis() as u64)
+                }
+                Some(ProbeOutcome::NoFlashSeen) => fl!("settings-latency-no-flash"),
+                None => fl!("settings-latency-description"),
+            };
+            camera_section = camera_section.add(
+                widget::settings::item::builder(fl!("settings-latency"))
+                    .description(description)
+                    .control(
+                        widget::button::standard(fl!("settings-latency-measure")).on_press_maybe(
+                            self.current_frame
+                                .is_some()
+                                .then_some(Message::MeasureLatency),
+                        ),
+                    ),
             );
+        }
 
         // Add device info panel if visible
         if self.device_info_visible {
@@ -300,12 +541,47 @@ impl AppModel {
             );
         }
 
-        vec![camera_section.into(), mirror_section.into()]
+        // Per-camera orientation correction, for every output, and how the
+        // rotation reaches saved files
+        let orientation = self.camera_orientation();
+        let orientation_section = widget::settings::section()
+            .title(fl!("settings-camera-orientation"))
+            .add(
+                widget::settings::item::builder(fl!("settings-camera-rotation"))
+                    .description(fl!("settings-camera-rotation-description"))
+                    .control(widget::dropdown(
+                        &self.camera_rotation_dropdown_options,
+                        crate::config::CameraOrientation::ROTATIONS
+                            .iter()
+                            .position(|&degrees| degrees == orientation.rotation),
+                        Message::SelectCameraRotation,
+                    )),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-camera-mirror"))
+                    .description(fl!("settings-camera-mirror-description"))
+                    .toggler(orientation.mirror, |_| Message::ToggleCameraMirror),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-camera-flip"))
+                    .toggler(orientation.flip, |_| Message::ToggleCameraFlip),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-bake-rotation"))
+                    .description(fl!("settings-bake-rotation-description"))
+                    .toggler(self.config.bake_rotation, |_| Message::ToggleBakeRotation),
+            );
+
+        vec![
+            camera_section.into(),
+            mirror_section.into(),
+            orientation_section.into(),
+        ]
     }
 
     /// Photo sub-page: output format and HDR+ settings.
     fn photo_sections(&self) -> Vec<Element<'_, Message>> {
-        use crate::config::BurstModeSetting;
+        use crate::config::{BurstModeSetting, BurstTuning};
         // Index 0 = Off, 1 = Auto, 2 = 4 frames, 3 = 6 frames, 4 = 8 frames, 5 = 50 frames
         let current_hdr_index = match self.config.burst_mode_setting {
             BurstModeSetting::Off => 0,
@@ -341,6 +617,64 @@ impl AppModel {
                         Some(current_hdr_index),
                         Message::SetBurstModeFrameCount,
                     )),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-super-resolution"))
+                    .description(fl!("settings-super-resolution-description"))
+                    .toggler(self.config.super_resolution, |_| {
+                        Message::ToggleSuperResolution
+                    }),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-night-mode"))
+                    .description(fl!("settings-night-mode-description"))
+                    .control(widget::dropdown(
+                        &self.night_mode_dropdown_options,
+                        crate::config::NightMode::ALL
+                            .iter()
+                            .position(|mode| *mode == self.config.night_mode),
+                        Message::SetNightMode,
+                    )),
+            )
+            .add({
+                let amount = self.config.output_sharpening.amount;
+                let sharpening_row = widget::Row::new()
+                    .push(
+                        widget::slider(
+                            0..=crate::config::OutputSharpening::MAX_AMOUNT,
+                            amount,
+                            Message::SetOutputSharpening,
+                        )
+                        .step(5)
+                        .width(Length::Fixed(140.0)),
+                    )
+                    .push(widget::space::horizontal().width(Length::Fixed(8.0)))
+                    .push(
+                        widget::text::caption(fl!(
+                            "settings-sharpening-value",
+                            amount = amount.to_string()
+                        ))
+                        .font(cosmic::font::mono())
+                        .size(11),
+                    )
+                    .align_y(Alignment::Center);
+                widget::settings::item::builder(fl!("settings-sharpening"))
+                    .description(fl!("settings-sharpening-description"))
+                    .control(sharpening_row)
+            })
+            .add(
+                widget::settings::item::builder(fl!("settings-gesture-shutter"))
+                    .description(fl!("settings-gesture-shutter-description"))
+                    .toggler(self.config.gesture_shutter, |_| {
+                        Message::ToggleGestureShutter
+                    }),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-photo-to-clipboard"))
+                    .description(fl!("settings-photo-to-clipboard-description"))
+                    .toggler(self.config.photo_to_clipboard, |_| {
+                        Message::TogglePhotoToClipboard
+                    }),
             );
 
         if self.config.burst_mode_setting != BurstModeSetting::Off {
@@ -351,7 +685,327 @@ impl AppModel {
             );
         }
 
-        vec![photo_section.into()]
+        let tuning = self.config.burst_tuning;
+        let mut tuning_section = widget::settings::section()
+            .title(fl!("settings-burst-tuning"))
+            .add(
+                widget::settings::item::builder(fl!("settings-burst-tuning-custom"))
+                    .description(fl!("settings-burst-tuning-custom-description"))
+                    .toggler(tuning.custom, move |custom| {
+                        Message::SetBurstTuning(BurstTuning { custom, ..tuning })
+                    }),
+            );
+        if tuning.custom {
+            tuning_section = tuning_section
+                .add(
+                    widget::settings::item::builder(fl!("settings-burst-tuning-frames"))
+                        .description(fl!("settings-burst-tuning-frames-description"))
+                        .control(slider_row(
+                            widget::slider(
+                                BurstTuning::MIN_FRAMES..=BurstTuning::MAX_FRAMES,
+                                tuning.frame_count,
+                                move |frame_count| {
+                                    Message::SetBurstTuning(BurstTuning {
+                                        frame_count,
+                                        ..tuning
+                                    })
+                                },
+                            ),
+                            tuning.frame_count.to_string(),
+                        )),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("settings-burst-tuning-robustness"))
+                        .description(fl!("settings-burst-tuning-robustness-description"))
+                        .control(slider_row(
+                            widget::slider(
+                                0..=BurstTuning::MAX_ROBUSTNESS,
+                                tuning.robustness,
+                                move |robustness| {
+                                    Message::SetBurstTuning(BurstTuning {
+                                        robustness,
+                                        ..tuning
+                                    })
+                                },
+                            )
+                            .step(10),
+                            fl!(
+                                "settings-burst-tuning-percent",
+                                amount = tuning.robustness.to_string()
+                            ),
+                        )),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("settings-burst-tuning-shadows"))
+                        .description(fl!("settings-burst-tuning-shadows-description"))
+                        .control(slider_row(
+                            widget::slider(
+                                0..=BurstTuning::MAX_PERCENT,
+                                tuning.shadow_boost,
+                                move |shadow_boost| {
+                                    Message::SetBurstTuning(BurstTuning {
+                                        shadow_boost,
+                                        ..tuning
+                                    })
+                                },
+                            )
+                            .step(5),
+                            fl!(
+                                "settings-burst-tuning-percent",
+                                amount = tuning.shadow_boost.to_string()
+                            ),
+                        )),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("settings-burst-tuning-contrast"))
+                        .description(fl!("settings-burst-tuning-contrast-description"))
+                        .control(slider_row(
+                            widget::slider(
+                                0..=BurstTuning::MAX_PERCENT,
+                                tuning.local_contrast,
+                                move |local_contrast| {
+                                    Message::SetBurstTuning(BurstTuning {
+                                        local_contrast,
+                                        ..tuning
+                                    })
+                                },
+                            )
+                            .step(5),
+                            fl!(
+                                "settings-burst-tuning-percent",
+                                amount = tuning.local_contrast.to_string()
+                            ),
+                        )),
+                );
+        }
+
+        let mut product_section = widget::settings::section()
+            .title(fl!("settings-product"))
+            .add(
+                widget::settings::item::builder(fl!("settings-product-mode"))
+                    .description(fl!("settings-product-mode-description"))
+                    .toggler(self.config.product_mode, |_| Message::ToggleProductMode),
+            );
+        if self.config.product_mode {
+            product_section = product_section.add(
+                widget::settings::item::builder(fl!("settings-product-transparent"))
+                    .description(fl!("settings-product-transparent-description"))
+                    .toggler(self.config.product_transparent_background, |_| {
+                        Message::ToggleProductTransparentBackground
+                    }),
+            );
+        }
+
+        let mut focus_stack_section = widget::settings::section()
+            .title(fl!("settings-focus-stack"))
+            .add(
+                widget::settings::item::builder(fl!("settings-focus-stack-mode"))
+                    .description(fl!("settings-focus-stack-mode-description"))
+                    .toggler(self.config.focus_stack, |_| Message::ToggleFocusStack),
+            );
+        if self.config.focus_stack {
+            let steps_index = crate::pipelines::photo::burst_mode::focus_stack::STEP_COUNTS
+                .iter()
+                .position(|&n| n == self.config.focus_stack_steps);
+            focus_stack_section = focus_stack_section.add(
+                widget::settings::item::builder(fl!("settings-focus-stack-steps")).control(
+                    widget::dropdown(
+                        &self.focus_stack_steps_dropdown_options,
+                        steps_index,
+                        Message::SelectFocusStackSteps,
+                    ),
+                ),
+            );
+        }
+
+        let mut booth_section = widget::settings::section()
+            .title(fl!("settings-photo-booth"))
+            .add(
+                widget::settings::item::builder(fl!("settings-photo-booth-mode"))
+                    .description(fl!("settings-photo-booth-mode-description"))
+                    .toggler(self.config.photo_booth, |_| Message::TogglePhotoBooth),
+            );
+        if self.config.photo_booth {
+            use crate::config::PhotoBoothLayout;
+            use crate::pipelines::photo::booth::{COUNTDOWN_SECONDS, SHOT_COUNTS};
+
+            let layout_index = PhotoBoothLayout::ALL
+                .iter()
+                .position(|&l| l == self.config.photo_booth_layout);
+            let shots_index = SHOT_COUNTS
+                .iter()
+                .position(|&n| n == self.config.photo_booth_shots);
+            let countdown_index = COUNTDOWN_SECONDS
+                .iter()
+                .position(|&s| s == self.config.photo_booth_countdown);
+            booth_section = booth_section
+                .add(
+                    widget::settings::item::builder(fl!("settings-photo-booth-layout")).control(
+                        widget::dropdown(
+                            &self.photo_booth_layout_dropdown_options,
+                            layout_index,
+                            Message::SelectPhotoBoothLayout,
+                        ),
+                    ),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("settings-photo-booth-shots")).control(
+                        widget::dropdown(
+                            &self.photo_booth_shots_dropdown_options,
+                            shots_index,
+                            Message::SelectPhotoBoothShots,
+                        ),
+                    ),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("settings-photo-booth-countdown"))
+                        .description(fl!("settings-photo-booth-countdown-description"))
+                        .control(widget::dropdown(
+                            &self.photo_booth_countdown_dropdown_options,
+                            countdown_index,
+                            Message::SelectPhotoBoothCountdown,
+                        )),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("settings-photo-booth-filters"))
+                        .description(fl!("settings-photo-booth-filters-description"))
+                        .toggler(self.config.photo_booth_filter_per_shot, |_| {
+                            Message::TogglePhotoBoothFilterPerShot
+                        }),
+                );
+        }
+
+        let mut rapid_burst_section = widget::settings::section()
+            .title(fl!("settings-rapid-burst"))
+            .add(
+                widget::settings::item::builder(fl!("settings-rapid-burst-mode"))
+                    .description(fl!("settings-rapid-burst-mode-description"))
+                    .toggler(self.config.rapid_burst, |_| Message::ToggleRapidBurst),
+            );
+        if self.config.rapid_burst {
+            let max_shots_index = crate::pipelines::photo::rapid_burst::MAX_SHOTS
+                .iter()
+                .position(|&n| n == self.config.rapid_burst_max_shots);
+            rapid_burst_section = rapid_burst_section.add(
+                widget::settings::item::builder(fl!("settings-rapid-burst-max-shots")).control(
+                    widget::dropdown(
+                        &self.rapid_burst_max_shots_dropdown_options,
+                        max_shots_index,
+                        Message::SelectRapidBurstMaxShots,
+                    ),
+                ),
+            );
+        }
+
+        let mut sections = vec![
+            photo_section.into(),
+            tuning_section.into(),
+            product_section.into(),
+            focus_stack_section.into(),
+            booth_section.into(),
+            rapid_burst_section.into(),
+        ];
+        if crate::media::ocr::AVAILABLE {
+            sections.push(
+                widget::settings::section()
+                    .title(fl!("settings-documents"))
+                    .add(
+                        widget::settings::item::builder(fl!("settings-searchable-pdf"))
+                            .description(fl!(
+                                "settings-searchable-pdf-description",
+                                language = self.config.ocr_language.as_str()
+                            ))
+                            .toggler(self.config.searchable_pdf, |_| Message::ToggleSearchablePdf),
+                    )
+                    .into(),
+            );
+        }
+        sections
+    }
+
+    /// Why recordings won't use the chosen container, if they won't
+    fn container_compatibility_note(&self) -> Option<String> {
+        let requested = self.config.video_container.container();
+        let encoder = self
+            .available_video_encoders
+            .get(self.current_video_encoder_index)?;
+        if self.config.bitrate_preset.is_intra_only() {
+            let container =
+                crate::media::encoders::video::resolve_pcm_container(encoder.codec, requested);
+            return (Some(container) != requested).then(|| {
+                fl!(
+                    "settings-container-edit-friendly",
+                    container = container.extension().to_uppercase()
+                )
+            });
+        }
+        let requested = requested?;
+        if encoder.codec.supports_container(requested) {
+            return None;
+        }
+        Some(fl!(
+            "settings-container-incompatible",
+            encoder = encoder.display_name.clone(),
+            container = encoder.codec.container_format().extension().to_uppercase()
+        ))
+    }
+
+    /// Estimated recording size per minute for the active format and quality,
+    /// plus how much recording time still fits in the video folder.
+    fn recording_size_estimate(&self) -> String {
+        use crate::media::encoders::audio::AudioQuality;
+        use crate::media::encoders::video::{estimate_bytes_per_minute, recording_capacity};
+
+        let Some(format) = self.active_format.as_ref() else {
+            return String::new();
+        };
+        let video_kbps = self
+            .config
+            .bitrate_preset
+            .bitrate_kbps(format.width, format.height);
+        // Encoders scale the H.264-calibrated preset to their codec
+        let video_kbps = self
+            .available_video_encoders
+            .get(self.current_video_encoder_index)
+            .map_or(video_kbps, |e| e.codec.scale_bitrate_kbps(video_kbps));
+        // Recordings use the high audio preset, or PCM when edit-friendly.
+        // The desktop-audio track is stereo, so twice the mono rate.
+        let track_bps = if self.config.bitrate_preset.is_intra_only() {
+            crate::media::encoders::audio::PCM_BITRATE_BPS
+        } else {
+            AudioQuality::High.bitrate_bps() as u32
+        };
+        let audio_tracks =
+            u32::from(self.config.record_audio) + 2 * u32::from(self.config.record_system_audio);
+        let audio_bps = (audio_tracks > 0).then_some(track_bps * audio_tracks);
+        let per_minute = estimate_bytes_per_minute(video_kbps, audio_bps);
+
+        let estimate = fl!(
+            "settings-quality-estimate",
+            size = crate::storage::format_size(per_minute)
+        );
+        let Some(free) = self.video_disk_free_bytes else {
+            return estimate;
+        };
+
+        let capacity = recording_capacity(free, per_minute);
+        let minutes = capacity.as_secs() / 60;
+        let time = fl!(
+            "settings-quality-duration",
+            hours = minutes / 60,
+            minutes = minutes % 60
+        );
+        if capacity.as_secs() < crate::constants::timing::LOW_DISK_RECORDING_WARNING_SECS {
+            format!(
+                "{estimate}\n{}",
+                fl!("settings-quality-low-space", time = time)
+            )
+        } else {
+            format!(
+                "{estimate}\n{}",
+                fl!("settings-quality-capacity", time = time)
+            )
+        }
     }
 
     /// Video sub-page: encoder, quality, and audio settings.
@@ -368,6 +1022,15 @@ impl AppModel {
             .position(|e| *e == self.config.audio_encoder)
             .unwrap_or(0); // Default to Opus (index 0)
 
+        let current_container_index = VideoContainer::ALL
+            .iter()
+            .position(|c| *c == self.config.video_container)
+            .unwrap_or(0); // Default to Automatic (index 0)
+        let mut container_item = widget::settings::item::builder(fl!("settings-container"));
+        if let Some(note) = self.container_compatibility_note() {
+            container_item = container_item.description(note);
+        }
+
         let mut video_section = if is_recording {
             widget::settings::section()
                 .title(fl!("settings-video"))
@@ -381,6 +1044,14 @@ impl AppModel {
                         ),
                     ),
                 )
+                .add(
+                    container_item.control(disabled_text(
+                        self.video_container_dropdown_options
+                            .get(current_container_index)
+                            .cloned()
+                            .unwrap_or_default(),
+                    )),
+                )
                 .add(
                     widget::settings::item::builder(fl!("settings-quality")).control(
                         disabled_text(
@@ -397,6 +1068,14 @@ impl AppModel {
                             .on_toggle_maybe(None::<fn(bool) -> Message>),
                     ),
                 )
+                .add(
+                    widget::settings::item::builder(fl!("settings-record-system-audio"))
+                        .description(fl!("settings-record-system-audio-description"))
+                        .control(
+                            widget::toggler(self.config.record_system_audio)
+                                .on_toggle_maybe(None::<fn(bool) -> Message>),
+                        ),
+                )
         } else {
             widget::settings::section()
                 .title(fl!("settings-video"))
@@ -409,102 +1088,404 @@ impl AppModel {
                         ),
                     ),
                 )
+                .add(container_item.control(widget::dropdown(
+                    &self.video_container_dropdown_options,
+                    Some(current_container_index),
+                    Message::SelectVideoContainer,
+                )))
                 .add(
-                    widget::settings::item::builder(fl!("settings-quality")).control(
-                        widget::dropdown(
+                    widget::settings::item::builder(fl!("settings-quality"))
+                        .description(self.recording_size_estimate())
+                        .control(widget::dropdown(
                             &self.bitrate_preset_dropdown_options,
                             Some(current_bitrate_index),
                             Message::SelectBitratePreset,
-                        ),
-                    ),
+                        )),
                 )
                 .add(
                     widget::settings::item::builder(fl!("settings-record-audio"))
                         .toggler(self.config.record_audio, |_| Message::ToggleRecordAudio),
                 )
+                .add(
+                    widget::settings::item::builder(fl!("settings-record-system-audio"))
+                        .description(fl!("settings-record-system-audio-description"))
+                        .toggler(self.config.record_system_audio, |_| {
+                            Message::ToggleRecordSystemAudio
+                        }),
+                )
         };
 
-        // Only show audio encoder and microphone selection when audio is enabled
+        let marker_key = self
+            .bindings
+            .keybind_for(crate::app::keybind::Action::AddRecordingMarker)
+            .map(crate::app::keybind::format_keybind)
+            .unwrap_or_else(|| fl!("shortcuts-help-unbound"));
+        video_section = video_section.add(
+            widget::settings::item::builder(fl!("settings-event-subtitles"))
+                .description(fl!(
+                    "settings-event-subtitles-description",
+                    key = marker_key
+                ))
+                .control(
+                    widget::toggler(self.config.record_event_subtitles).on_toggle_maybe(
+                        (!is_recording).then_some(|_| Message::ToggleEventSubtitles),
+                    ),
+                ),
+        );
+        video_section = video_section
+            .add(
+                widget::settings::item::builder(fl!("settings-recording-timecode"))
+                    .description(fl!("settings-recording-timecode-description"))
+                    .control(
+                        widget::toggler(self.config.recording_timecode).on_toggle_maybe(
+                            (!is_recording).then_some(|_| Message::ToggleRecordingTimecode),
+                        ),
+                    ),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-timestamp-overlay"))
+                    .description(fl!("settings-timestamp-overlay-description"))
+                    .control(
+                        widget::toggler(self.config.recording_timestamp_overlay).on_toggle_maybe(
+                            (!is_recording).then_some(|_| Message::ToggleTimestampOverlay),
+                        ),
+                    ),
+            );
+        let record_screen = widget::toggler(self.config.record_screen)
+            .on_toggle_maybe((!is_recording).then_some(|_| Message::ToggleRecordScreen));
+        video_section = video_section.add(
+            widget::settings::item::builder(fl!("settings-record-screen"))
+                .description(fl!("settings-record-screen-description"))
+                .control(record_screen),
+        );
+
+        let stabilization = self.config.video_stabilization;
+        let stabilization_index = crate::config::VideoStabilization::ALL
+            .iter()
+            .position(|s| *s == stabilization)
+            .unwrap_or(0);
+        let stabilization_item = widget::settings::item::builder(fl!("settings-stabilization"))
+            .description(if stabilization == crate::config::VideoStabilization::Off {
+                fl!("settings-stabilization-off-description")
+            } else {
+                let kept = 1.0 - 2.0 * stabilization.crop_margin();
+                fl!(
+                    "settings-stabilization-description",
+                    percent = format!("{:.0}", kept * 100.0)
+                )
+            });
+        video_section = video_section.add(if is_recording {
+            stabilization_item.control(disabled_text(
+                self.video_stabilization_dropdown_options
+                    .get(stabilization_index)
+                    .cloned()
+                    .unwrap_or_default(),
+            ))
+        } else {
+            stabilization_item.control(widget::dropdown(
+                &self.video_stabilization_dropdown_options,
+                Some(stabilization_index),
+                Message::SelectVideoStabilization,
+            ))
+        });
+        video_section = video_section.add(self.post_stabilization_item());
+
+        // Picked up when a recording or the virtual camera starts
+        let denoise = self.config.video_denoise;
+        let denoise_index = crate::config::VideoDenoise::ALL
+            .iter()
+            .position(|d| *d == denoise)
+            .unwrap_or(0);
+        let denoise_item = widget::settings::item::builder(fl!("settings-denoise"))
+            .description(fl!("settings-denoise-description"));
+        video_section = video_section.add(if is_recording {
+            denoise_item.control(disabled_text(
+                self.video_denoise_dropdown_options
+                    .get(denoise_index)
+                    .cloned()
+                    .unwrap_or_default(),
+            ))
+        } else {
+            denoise_item.control(widget::dropdown(
+                &self.video_denoise_dropdown_options,
+                Some(denoise_index),
+                Message::SelectVideoDenoise,
+            ))
+        });
+        if denoise != crate::config::VideoDenoise::Off {
+            video_section = video_section.add(
+                widget::settings::item::builder(fl!("settings-denoise-auto"))
+                    .description(fl!("settings-denoise-auto-description"))
+                    .control(
+                        widget::toggler(self.config.video_denoise_auto).on_toggle_maybe(
+                            (!is_recording).then_some(|_| Message::ToggleVideoDenoiseAuto),
+                        ),
+                    ),
+            );
+        }
+
+        // The preview follows the factor at once; recordings when they start
+        let desqueeze = self.config.anamorphic_desqueeze;
+        let desqueeze_index = crate::config::AnamorphicDesqueeze::ALL
+            .iter()
+            .position(|d| *d == desqueeze)
+            .unwrap_or(0);
+        video_section = video_section.add(
+            widget::settings::item::builder(fl!("settings-desqueeze"))
+                .description(fl!("settings-desqueeze-description"))
+                .control(widget::dropdown(
+                    &self.anamorphic_desqueeze_dropdown_options,
+                    Some(desqueeze_index),
+                    Message::SelectAnamorphicDesqueeze,
+                )),
+        );
+        if desqueeze != crate::config::AnamorphicDesqueeze::Off {
+            video_section = video_section.add(
+                widget::settings::item::builder(fl!("settings-desqueeze-recordings"))
+                    .description(fl!("settings-desqueeze-recordings-description"))
+                    .control(
+                        widget::toggler(self.config.desqueeze_recordings).on_toggle_maybe(
+                            (!is_recording).then_some(|_| Message::ToggleDesqueezeRecordings),
+                        ),
+                    ),
+            );
+        }
+
+        // Both audio tracks share the encoder choice
+        let any_audio = self.config.record_audio || self.config.record_system_audio;
+        if any_audio {
+            let encoder_item = widget::settings::item::builder(fl!("settings-audio-encoder"));
+            video_section = video_section.add(if is_recording {
+                encoder_item.control(disabled_text(
+                    self.audio_encoder_dropdown_options
+                        .get(current_audio_encoder_index)
+                        .cloned()
+                        .unwrap_or_default(),
+                ))
+            } else {
+                encoder_item.control(widget::dropdown(
+                    &self.audio_encoder_dropdown_options,
+                    Some(current_audio_encoder_index),
+                    Message::SelectAudioEncoder,
+                ))
+            });
+        }
+
+        // Only show microphone selection when microphone audio is enabled
+        if self.config.record_audio {
+            if is_recording {
+                video_section = video_section.add(
+                    widget::settings::item::builder(fl!("settings-microphone")).control(
+                        disabled_text(
+                            self.audio_dropdown_options
+                                .get(self.current_audio_device_index)
+                                .cloned()
+                                .unwrap_or_default(),
+                        ),
+                    ),
+                );
+            } else {
+                video_section = video_section.add(
+                    widget::settings::item::builder(fl!("settings-microphone")).control(
+                        widget::dropdown(
+                            &self.audio_dropdown_options,
+                            Some(self.current_audio_device_index),
+                            Message::SelectAudioDevice,
+                        ),
+                    ),
+                );
+            }
+        }
+
         if self.config.record_audio {
+            use crate::pipelines::audio_level::{
+                MAX_GAIN_DB, MIN_GAIN_DB, noise_suppressor_description,
+            };
+
+            let gain_label = fl!(
+                "settings-mic-gain-value",
+                gain = format!("{:+}", self.config.audio_gain_db)
+            );
+            let suppressor_available = noise_suppressor_description().is_some();
+            let mut noise_item = widget::settings::item::builder(fl!("settings-noise-suppression"));
+            if !suppressor_available {
+                noise_item = noise_item.description(fl!("settings-noise-suppression-unavailable"));
+            }
+
             if is_recording {
                 video_section = video_section
                     .add(
-                        widget::settings::item::builder(fl!("settings-audio-encoder")).control(
-                            disabled_text(
-                                self.audio_encoder_dropdown_options
-                                    .get(current_audio_encoder_index)
-                                    .cloned()
-                                    .unwrap_or_default(),
-                            ),
-                        ),
+                        widget::settings::item::builder(fl!("settings-mic-gain"))
+                            .control(disabled_text(gain_label)),
                     )
                     .add(
-                        widget::settings::item::builder(fl!("settings-microphone")).control(
-                            disabled_text(
-                                self.audio_dropdown_options
-                                    .get(self.current_audio_device_index)
-                                    .cloned()
-                                    .unwrap_or_default(),
-                            ),
+                        noise_item.control(
+                            widget::toggler(self.config.audio_noise_suppression)
+                                .on_toggle_maybe(None::<fn(bool) -> Message>),
                         ),
                     );
             } else {
+                let gain_row = widget::Row::new()
+                    .push(
+                        widget::slider(
+                            MIN_GAIN_DB..=MAX_GAIN_DB,
+                            self.config.audio_gain_db,
+                            Message::SetAudioGain,
+                        )
+                        .width(Length::Fixed(140.0)),
+                    )
+                    .push(widget::space::horizontal().width(Length::Fixed(8.0)))
+                    .push(
+                        widget::text::caption(gain_label)
+                            .font(cosmic::font::mono())
+                            .size(11),
+                    )
+                    .align_y(Alignment::Center);
                 video_section = video_section
                     .add(
-                        widget::settings::item::builder(fl!("settings-audio-encoder")).control(
-                            widget::dropdown(
-                                &self.audio_encoder_dropdown_options,
-                                Some(current_audio_encoder_index),
-                                Message::SelectAudioEncoder,
-                            ),
-                        ),
+                        widget::settings::item::builder(fl!("settings-mic-gain")).control(gain_row),
                     )
-                    .add(
-                        widget::settings::item::builder(fl!("settings-microphone")).control(
-                            widget::dropdown(
-                                &self.audio_dropdown_options,
-                                Some(self.current_audio_device_index),
-                                Message::SelectAudioDevice,
-                            ),
+                    .add(noise_item.control(
+                        widget::toggler(self.config.audio_noise_suppression).on_toggle_maybe(
+                            suppressor_available.then_some(|_| Message::ToggleNoiseSuppression),
                         ),
-                    );
+                    ));
             }
         }
 
+        // Sync offset stays adjustable while recording so the user can
+        // line audio up against what they see.
         if self.config.record_audio {
-            use crate::app::controls::audio_meter::{AudioMeterStyle, audio_meter};
-
-            let meter_row = match self.current_audio_levels() {
-                Some(levels) => widget::Row::new()
-                    .push(widget::text::body(fl!("settings-mic-level")))
-                    .push(widget::space::horizontal().width(Length::Fill))
-                    .push(audio_meter(
-                        levels.output_peak_db,
-                        levels.output_rms_db,
-                        AudioMeterStyle {
-                            width: 120.0,
-                            height: 10.0,
-                            show_peak: true,
-                        },
-                    ))
-                    .push(widget::space::horizontal().width(Length::Fixed(8.0)))
-                    .push(
-                        widget::text::caption(format!("{:.0} dB", levels.output_rms_db))
-                            .font(cosmic::font::mono())
-                            .size(11),
+            use crate::pipelines::audio_level::MAX_SYNC_OFFSET_MS;
+
+            let offset_ms = self
+                .config
+                .audio_sync_offsets
+                .get(&self.audio_sync_key())
+                .copied()
+                .unwrap_or(0);
+            let offset_row = widget::Row::new()
+                .push(
+                    widget::slider(
+                        -MAX_SYNC_OFFSET_MS..=MAX_SYNC_OFFSET_MS,
+                        offset_ms,
+                        Message::SetAudioSyncOffset,
                     )
-                    .align_y(Alignment::Center),
-                None => widget::Row::new()
-                    .push(widget::text::body(fl!("settings-mic-level")))
-                    .push(widget::space::horizontal().width(Length::Fill))
-                    .push(widget::text::caption(fl!("settings-mic-level-initializing")).size(11))
-                    .align_y(Alignment::Center),
-            };
+                    .step(10)
+                    .width(Length::Fixed(140.0)),
+                )
+                .push(widget::space::horizontal().width(Length::Fixed(8.0)))
+                .push(
+                    widget::text::caption(fl!(
+                        "settings-audio-sync-value",
+                        offset = format!("{:+}", offset_ms)
+                    ))
+                    .font(cosmic::font::mono())
+                    .size(11),
+                )
+                .align_y(Alignment::Center);
+            video_section = video_section.add(
+                widget::settings::item::builder(fl!("settings-audio-sync"))
+                    .description(fl!("settings-audio-sync-description"))
+                    .control(offset_row),
+            );
+            video_section = video_section.add(self.av_sync_test_item());
+        }
 
+        let levels = self.current_audio_levels();
+        if self.config.record_audio {
+            let meter_row = audio_level_row(
+                fl!("settings-mic-level"),
+                levels.map(|l| (l.output_peak_db, l.output_rms_db)),
+            );
             video_section = video_section.add(widget::settings::item_row(vec![meter_row.into()]));
         }
+        if self.config.record_system_audio {
+            let meter_row = audio_level_row(
+                fl!("settings-system-audio-level"),
+                levels.map(|l| (l.system_peak_db, l.system_rms_db)),
+            );
+            video_section = video_section.add(widget::settings::item_row(vec![meter_row.into()]));
+        }
+
+        let warning_index = crate::storage::LOW_SPACE_WARNING_MB
+            .iter()
+            .position(|&mb| mb == self.config.low_space_warning_mb);
+        let storage_section = widget::settings::section()
+            .title(fl!("settings-storage"))
+            .add(
+                widget::settings::item::builder(fl!("settings-low-space-warning"))
+                    .description(fl!("settings-low-space-warning-description"))
+                    .control(widget::dropdown(
+                        &self.low_space_warning_dropdown_options,
+                        warning_index,
+                        Message::SelectLowSpaceWarning,
+                    )),
+            );
+
+        vec![
+            video_section.into(),
+            self.animated_clip_section().into(),
+            storage_section.into(),
+        ]
+    }
+
+    /// Record-as-clip settings: GIF/WebP output, length, frame rate, looping.
+    fn animated_clip_section(&self) -> widget::settings::Section<'_, Message> {
+        use crate::config::AnimatedClipFormat;
+        use crate::media::animation::{CLIP_FPS, CLIP_SECONDS};
+
+        let format_index = AnimatedClipFormat::ALL
+            .iter()
+            .position(|&f| f == self.config.animated_clip_format);
+        let mut section = widget::settings::section()
+            .title(fl!("settings-animated-clip"))
+            .add(
+                widget::settings::item::builder(fl!("settings-animated-clip-format"))
+                    .description(fl!("settings-animated-clip-format-description"))
+                    .control(widget::dropdown(
+                        &self.animated_clip_format_dropdown_options,
+                        format_index,
+                        Message::SelectAnimatedClipFormat,
+                    )),
+            );
+        if self.config.animated_clip_format == AnimatedClipFormat::Off {
+            return section;
+        }
 
-        vec![video_section.into()]
+        let seconds_index = CLIP_SECONDS
+            .iter()
+            .position(|&s| s == self.config.animated_clip_seconds);
+        let fps_index = CLIP_FPS
+            .iter()
+            .position(|&f| f == self.config.animated_clip_fps);
+        section = section
+            .add(
+                widget::settings::item::builder(fl!("settings-animated-clip-length")).control(
+                    widget::dropdown(
+                        &self.animated_clip_seconds_dropdown_options,
+                        seconds_index,
+                        Message::SelectAnimatedClipSeconds,
+                    ),
+                ),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-animated-clip-fps")).control(
+                    widget::dropdown(
+                        &self.animated_clip_fps_dropdown_options,
+                        fps_index,
+                        Message::SelectAnimatedClipFps,
+                    ),
+                ),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-animated-clip-loop"))
+                    .description(fl!("settings-animated-clip-loop-description"))
+                    .toggler(self.config.animated_clip_loop, |_| {
+                        Message::ToggleAnimatedClipLoop
+                    }),
+            );
+        section
     }
 
     /// Timelapse sub-page: capture interval.
@@ -541,7 +1522,145 @@ impl AppModel {
                 )
         };
 
-        vec![timelapse_section.into()]
+        vec![timelapse_section.into(), self.still_extraction_section()]
+    }
+
+    /// Still extraction from a recorded video: the interval (or scene
+    /// changes), the start button, and the running or last job's progress.
+    fn still_extraction_section(&self) -> Element<'_, Message> {
+        use crate::app::state::StillExtractionState;
+
+        // Index 0 is "scene changes", the intervals follow
+        let current_index = self.config.still_extraction_interval.map_or(0, |interval| {
+            TimelapseInterval::ALL
+                .iter()
+                .position(|i| *i == interval)
+                .map_or(0, |i| i + 1)
+        });
+        let running = self.still_extraction.is_running();
+
+        let mut section = widget::settings::section()
+            .title(fl!("settings-still-extraction"))
+            .add(
+                widget::settings::item::builder(fl!("settings-still-extraction-interval"))
+                    .description(fl!("settings-still-extraction-description"))
+                    .control(if running {
+                        disabled_text(
+                            self.still_extraction_dropdown_options
+                                .get(current_index)
+                                .cloned()
+                                .unwrap_or_default(),
+                        )
+                    } else {
+                        widget::dropdown(
+                            &self.still_extraction_dropdown_options,
+                            Some(current_index),
+                            Message::SetStillExtractionInterval,
+                        )
+                        .into()
+                    }),
+            );
+
+        let status: Element<'_, Message> = match &self.still_extraction {
+            StillExtractionState::Idle => widget::space::horizontal().into(),
+            StillExtractionState::Running { control, .. } => widget::text::caption(fl!(
+                "still-extraction-progress",
+                percent = (control.progress() * 100.0).round() as u32
+            ))
+            .into(),
+            StillExtractionState::Finished(Ok(count)) => {
+                widget::text::caption(fl!("still-extraction-done", count = *count)).into()
+            }
+            StillExtractionState::Finished(Err(error)) => {
+                widget::text::caption(error.clone()).into()
+            }
+        };
+        let button = if running {
+            widget::button::standard(fl!("still-extraction-cancel"))
+                .on_press(Message::CancelStillExtraction)
+        } else {
+            widget::button::standard(fl!("settings-still-extraction-start"))
+                .on_press(Message::ExtractStills)
+        };
+        let controls = widget::Row::new()
+            .push(status)
+            .push(widget::space::horizontal())
+            .push(button)
+            .align_y(Alignment::Center);
+        section = section.add(widget::settings::item_row(vec![controls.into()]));
+
+        section.into()
+    }
+
+    /// The A/V sync test: what it does or found, and the buttons to run it
+    /// and apply its result
+    fn av_sync_test_item(&self) -> Element<'_, Message> {
+        use crate::app::state::AvSyncTestState;
+
+        let description = match &self.av_sync_test {
+            AvSyncTestState::Idle => fl!("settings-av-sync-test-description"),
+            AvSyncTestState::Recording { .. } | AvSyncTestState::Measuring => {
+                fl!("settings-av-sync-test-running")
+            }
+            AvSyncTestState::Finished(Ok(correction_ms)) => fl!(
+                "settings-av-sync-test-result",
+                offset = format!("{:+}", correction_ms)
+            ),
+            AvSyncTestState::Finished(Err(error)) => error.clone(),
+        };
+        let mut buttons = widget::Row::new()
+            .spacing(cosmic::theme::spacing().space_xs)
+            .align_y(Alignment::Center);
+        if let AvSyncTestState::Finished(Ok(_)) = self.av_sync_test {
+            buttons = buttons.push(
+                widget::button::suggested(fl!("settings-av-sync-test-apply"))
+                    .on_press(Message::ApplyAvSyncOffset),
+            );
+        }
+        let can_start = !self.av_sync_test.is_running()
+            && !self.recording.is_recording()
+            && self.current_frame.is_some();
+        buttons = buttons.push(
+            widget::button::standard(fl!("settings-av-sync-test-start"))
+                .on_press_maybe(can_start.then_some(Message::StartAvSyncTest)),
+        );
+        widget::settings::item::builder(fl!("settings-av-sync-test"))
+            .description(description)
+            .control(buttons)
+            .into()
+    }
+
+    /// Stabilizing an already recorded video: the running or last job's
+    /// progress and the start or cancel button
+    fn post_stabilization_item(&self) -> Element<'_, Message> {
+        use crate::app::state::PostStabilizationState;
+
+        let description = match &self.post_stabilization {
+            PostStabilizationState::Idle => fl!("settings-stabilize-video-description"),
+            PostStabilizationState::Running { control, .. } => fl!(
+                "post-stabilization-progress",
+                percent = (control.progress() * 100.0).round() as u32
+            ),
+            PostStabilizationState::Finished(Ok(path)) => fl!(
+                "post-stabilization-done",
+                name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            ),
+            PostStabilizationState::Finished(Err(error)) => error.clone(),
+        };
+        let button = if self.post_stabilization.is_running() {
+            widget::button::standard(fl!("post-stabilization-cancel"))
+                .on_press(Message::CancelPostStabilization)
+        } else {
+            widget::button::standard(fl!("settings-stabilize-video-start"))
+                .on_press(Message::StabilizeVideo)
+        };
+        widget::settings::item::builder(fl!("settings-stabilize-video"))
+            .description(description)
+            .control(button)
+            .into()
     }
 
     /// Appearance sub-page: theme, overlay effect, composition guide, and
@@ -592,7 +1711,65 @@ impl AppModel {
                 )),
         );
 
-        let mut sections = vec![appearance_section.into(), composition_guide_section.into()];
+        let current_one_handed_index = crate::config::OneHandedLayout::ALL
+            .iter()
+            .position(|l| *l == self.config.one_handed_layout)
+            .unwrap_or(0);
+
+        let layout_section = widget::settings::section().add(
+            widget::settings::item::builder(fl!("settings-one-handed"))
+                .description(fl!("settings-one-handed-description"))
+                .control(widget::dropdown(
+                    &self.one_handed_layout_dropdown_options,
+                    Some(current_one_handed_index),
+                    Message::SelectOneHandedLayout,
+                )),
+        );
+
+        let mut sound_section = widget::settings::section()
+            .title(fl!("settings-sounds"))
+            .add(
+                widget::settings::item::builder(fl!("settings-silent-mode"))
+                    .description(fl!("settings-silent-mode-description"))
+                    .toggler(self.config.silent_mode, |_| Message::ToggleSilentMode),
+            );
+        if !self.config.silent_mode {
+            sound_section = sound_section.add(
+                widget::settings::item::builder(fl!("settings-capture-sounds"))
+                    .description(fl!("settings-capture-sounds-description"))
+                    .toggler(self.config.capture_sounds, |_| Message::ToggleCaptureSounds),
+            );
+            if self.config.capture_sounds {
+                let volume_row = widget::Row::new()
+                    .push(
+                        widget::slider(
+                            0..=100,
+                            self.config.capture_sound_volume,
+                            Message::SetCaptureSoundVolume,
+                        )
+                        .step(5)
+                        .width(Length::Fixed(140.0)),
+                    )
+                    .push(widget::space::horizontal().width(Length::Fixed(8.0)))
+                    .push(
+                        widget::text::caption(format!("{}%", self.config.capture_sound_volume))
+                            .font(cosmic::font::mono())
+                            .size(11),
+                    )
+                    .align_y(Alignment::Center);
+                sound_section = sound_section.add(
+                    widget::settings::item::builder(fl!("settings-capture-sound-volume"))
+                        .control(volume_row),
+                );
+            }
+        }
+
+        let mut sections = vec![
+            appearance_section.into(),
+            composition_guide_section.into(),
+            layout_section.into(),
+            sound_section.into(),
+        ];
 
         // Haptic feedback (only where the device has haptics)
         if crate::backends::haptic::is_available() {
@@ -609,19 +1786,352 @@ impl AppModel {
         sections
     }
 
+    /// Controls sub-page: the layout editor. Pick a mode, then place each
+    /// of its optional controls in the top bar, the tools menu, or nowhere.
+    fn controls_sections(&self) -> Vec<Element<'_, Message>> {
+        use crate::app::control_layout::{Control, EDITABLE_MODES};
+
+        let mode = self.layout_editor_mode;
+        let mode_index = EDITABLE_MODES.iter().position(|m| *m == mode);
+
+        let mode_section = widget::settings::section().add(
+            widget::settings::item::builder(fl!("settings-controls-mode"))
+                .description(fl!("settings-controls-description"))
+                .control(widget::dropdown(
+                    &self.default_mode_dropdown_options,
+                    mode_index,
+                    Message::SelectLayoutEditorMode,
+                )),
+        );
+
+        let mut layout_section = widget::settings::section().title(fl!("settings-controls-layout"));
+        for control in Control::ALL
+            .into_iter()
+            .filter(|control| control.applies_to(mode))
+        {
+            let placements = control.placements();
+            let options = if placements.len() == 2 {
+                &self.control_visibility_dropdown_options
+            } else {
+                &self.control_placement_dropdown_options
+            };
+            let current = placements
+                .iter()
+                .position(|p| *p == self.config.control_layout.placement(mode, control));
+            layout_section = layout_section.add(
+                widget::settings::item::builder(control_label(control)).control(widget::dropdown(
+                    options,
+                    current,
+                    move |index| Message::SetControlPlacement(control, index),
+                )),
+            );
+        }
+
+        let mut reset = widget::button::standard(fl!("settings-controls-reset"));
+        if self.config.control_layout.is_customized(mode) {
+            reset = reset.on_press(Message::ResetControlLayout);
+        }
+        layout_section = layout_section.add(widget::settings::item_row(vec![reset.into()]));
+
+        let gestures_section = widget::settings::section()
+            .add(
+                widget::settings::item::builder(fl!("settings-preview-gestures"))
+                    .description(fl!("settings-preview-gestures-description"))
+                    .toggler(self.config.preview_gestures, |_| {
+                        Message::TogglePreviewGestures
+                    }),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-keyboard-navigation"))
+                    .description(fl!("settings-keyboard-navigation-description"))
+                    .toggler(self.config.keyboard_navigation, |_| {
+                        Message::ToggleKeyboardNavigation
+                    }),
+            );
+
+        let mut sections = vec![
+            mode_section.into(),
+            layout_section.into(),
+            gestures_section.into(),
+        ];
+
+        // Volume keys (only where the device has them)
+        if crate::backends::volume_keys::is_available() {
+            let current_volume_key_index = crate::config::VolumeKeyAction::ALL
+                .iter()
+                .position(|a| *a == self.config.volume_key_action)
+                .unwrap_or(0);
+            let volume_keys_section = widget::settings::section().add(
+                widget::settings::item::builder(fl!("settings-volume-keys"))
+                    .description(fl!("settings-volume-keys-description"))
+                    .control(widget::dropdown(
+                        &self.volume_key_action_dropdown_options,
+                        Some(current_volume_key_index),
+                        Message::SelectVolumeKeyAction,
+                    )),
+            );
+            sections.push(volume_keys_section.into());
+        }
+
+        sections
+    }
+
     /// Virtual camera sub-page.
     fn virtual_camera_sections(&self) -> Vec<Element<'_, Message>> {
-        let virtual_camera_section = widget::settings::section().add(
-            widget::settings::item::builder(fl!("virtual-camera-title"))
-                .description(fl!("virtual-camera-description"))
-                .toggler(self.config.virtual_camera_enabled, |_| {
-                    Message::ToggleVirtualCameraEnabled
-                }),
-        );
+        let current_location_index = crate::config::CameraLocation::ALL
+            .iter()
+            .position(|l| *l == self.current_camera_location())
+            .unwrap_or(0);
+
+        let virtual_camera_section = widget::settings::section()
+            .add(
+                widget::settings::item::builder(fl!("virtual-camera-title"))
+                    .description(fl!("virtual-camera-description"))
+                    .toggler(self.config.virtual_camera_enabled, |_| {
+                        Message::ToggleVirtualCameraEnabled
+                    }),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-look-here"))
+                    .description(fl!("settings-look-here-description"))
+                    .toggler(self.config.look_here_marker, |_| {
+                        Message::ToggleLookHereMarker
+                    }),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-camera-location"))
+                    .description(fl!("settings-camera-location-description"))
+                    .control(widget::dropdown(
+                        &self.camera_location_dropdown_options,
+                        Some(current_location_index),
+                        Message::SelectCameraLocation,
+                    )),
+            );
 
         vec![virtual_camera_section.into()]
     }
 
+    /// Automation sub-page: the rules the headless daemon runs, each with an
+    /// enable toggle, plus buttons to edit and reload the rules file.
+    fn automation_sections(&self) -> Vec<Element<'_, Message>> {
+        let mut rules_section = widget::settings::section()
+            .title(fl!("settings-automation-rules"))
+            .add(widget::settings::item_row(vec![
+                widget::text::caption(fl!("settings-automation-description")).into(),
+            ]));
+
+        if let Some(error) = &self.automation_rules_error {
+            rules_section = rules_section.add(widget::settings::item_row(vec![
+                widget::text::body(error.clone()).into(),
+            ]));
+        } else if self.automation_rules.rules.is_empty() {
+            rules_section = rules_section.add(widget::settings::item_row(vec![
+                widget::text::body(fl!("settings-automation-empty")).into(),
+            ]));
+        }
+
+        for (index, rule) in self.automation_rules.rules.iter().enumerate() {
+            let mut toggler = widget::toggler(rule.enabled);
+            // Toggling rewrites the file, which would lose rules we failed to parse
+            if self.automation_rules_error.is_none() {
+                toggler = toggler.on_toggle(move |_| Message::ToggleAutomationRule(index));
+            }
+            rules_section = rules_section.add(
+                widget::settings::item::builder(rule.name.clone())
+                    .description(automation_rule_summary(rule))
+                    .control(toggler),
+            );
+        }
+
+        let buttons = widget::Row::new()
+            .push(
+                widget::button::standard(fl!("settings-automation-edit"))
+                    .on_press(Message::EditAutomationRules),
+            )
+            .push(widget::space::horizontal().width(Length::Fixed(8.0)))
+            .push(
+                widget::button::standard(fl!("settings-automation-reload"))
+                    .on_press(Message::ReloadAutomationRules),
+            );
+        rules_section = rules_section.add(widget::settings::item_row(vec![buttons.into()]));
+
+        let mut share_section = widget::settings::section()
+            .title(fl!("settings-sharing"))
+            .add(
+                widget::settings::item::builder(fl!("settings-share-after-capture"))
+                    .description(fl!("settings-share-after-capture-description"))
+                    .toggler(self.config.share_after_capture, |_| {
+                        Message::ToggleShareAfterCapture
+                    }),
+            );
+        if let Some(error) = &self.share_targets_error {
+            share_section = share_section.add(widget::settings::item_row(vec![
+                widget::text::body(error.clone()).into(),
+            ]));
+        }
+        let target_names: Vec<&str> = self
+            .share_targets
+            .targets
+            .iter()
+            .map(|target| target.name.as_str())
+            .collect();
+        let targets_description = if target_names.is_empty() {
+            fl!("settings-share-targets-empty")
+        } else {
+            target_names.join(", ")
+        };
+        share_section = share_section.add(
+            widget::settings::item::builder(fl!("settings-share-targets"))
+                .description(targets_description)
+                .control(
+                    widget::button::standard(fl!("settings-share-targets-edit"))
+                        .on_press(Message::EditShareTargets),
+                ),
+        );
+
+        vec![rules_section.into(), share_section.into()]
+    }
+
+    /// Upload sub-page: the remote new captures are sent to, and how the
+    /// uploads of this session went.
+    fn upload_sections(&self) -> Vec<Element<'_, Message>> {
+        use crate::app::state::UploadField;
+        use crate::config::UploadProvider;
+        use crate::upload::UploadStatus;
+
+        let form = &self.upload_form;
+        let saved = &self.config.upload;
+        let can_enable = !saved.url.is_empty() && !saved.username.is_empty();
+        let toggle_section = widget::settings::section().add(
+            widget::settings::item::builder(fl!("settings-upload-enabled"))
+                .description(fl!("settings-upload-enabled-description"))
+                .control(widget::toggler(saved.enabled).on_toggle_maybe(
+                    (can_enable || saved.enabled).then_some(|_| Message::ToggleAutoUpload),
+                )),
+        );
+
+        let field = |label: String, placeholder: &'static str, value: &str, field| {
+            widget::settings::item::builder(label).control(
+                widget::text_input(placeholder, value.to_string())
+                    .on_input(move |text| Message::UploadFormInput(field, text))
+                    .width(Length::Fixed(240.0)),
+            )
+        };
+        let provider_index = UploadProvider::ALL
+            .iter()
+            .position(|&p| p == form.settings.provider);
+        let is_s3 = form.settings.provider == UploadProvider::S3;
+        let mut remote_section = widget::settings::section()
+            .title(fl!("settings-upload-remote"))
+            .add(
+                widget::settings::item::builder(fl!("settings-upload-provider")).control(
+                    widget::dropdown(
+                        &self.upload_provider_dropdown_options,
+                        provider_index,
+                        Message::SelectUploadProvider,
+                    ),
+                ),
+            );
+        remote_section = if is_s3 {
+            remote_section
+                .add(field(
+                    fl!("settings-upload-endpoint"),
+                    "https://s3.example.com",
+                    &form.settings.url,
+                    UploadField::Url,
+                ))
+                .add(field(
+                    fl!("settings-upload-bucket"),
+                    "photos",
+                    &form.settings.bucket,
+                    UploadField::Bucket,
+                ))
+                .add(field(
+                    fl!("settings-upload-region"),
+                    "us-east-1",
+                    &form.settings.region,
+                    UploadField::Region,
+                ))
+                .add(field(
+                    fl!("settings-upload-access-key"),
+                    "",
+                    &form.settings.username,
+                    UploadField::Username,
+                ))
+        } else {
+            remote_section
+                .add(field(
+                    fl!("settings-upload-folder"),
+                    "https://cloud.example.com/remote.php/dav/files/me/Camera",
+                    &form.settings.url,
+                    UploadField::Url,
+                ))
+                .add(field(
+                    fl!("settings-upload-username"),
+                    "",
+                    &form.settings.username,
+                    UploadField::Username,
+                ))
+        };
+        let secret_label = if is_s3 {
+            fl!("settings-upload-secret-key")
+        } else {
+            fl!("settings-upload-password")
+        };
+        remote_section = remote_section.add(
+            widget::settings::item::builder(secret_label)
+                .description(fl!("settings-upload-secret-description"))
+                .control(
+                    widget::secure_input("", form.secret.as_str(), None, true)
+                        .on_input(|text| Message::UploadFormInput(UploadField::Secret, text))
+                        .width(Length::Fixed(240.0)),
+                ),
+        );
+        let status = match &form.status {
+            Some(Ok(())) => fl!("settings-upload-saved"),
+            Some(Err(e)) => e.clone(),
+            None => String::new(),
+        };
+        remote_section = remote_section.add(widget::settings::item_row(vec![
+            widget::button::standard(fl!("settings-upload-save"))
+                .on_press(Message::SaveUploadSettings)
+                .into(),
+            widget::space::horizontal().width(Length::Fixed(8.0)).into(),
+            widget::text::body(status).into(),
+        ]));
+
+        let count = |wanted: fn(&UploadStatus) -> bool| {
+            self.upload_status.values().filter(|s| wanted(s)).count()
+        };
+        let uploaded = count(|s| *s == UploadStatus::Uploaded);
+        let uploading = count(|s| *s == UploadStatus::Uploading);
+        let failed = count(|s| matches!(s, UploadStatus::Failed(_)));
+        let mut session_section = widget::settings::section()
+            .title(fl!("settings-upload-session"))
+            .add(widget::settings::item_row(vec![
+                widget::text::body(fl!(
+                    "settings-upload-session-counts",
+                    uploaded = uploaded,
+                    uploading = uploading,
+                    failed = failed
+                ))
+                .into(),
+            ]));
+        if failed > 0 {
+            session_section = session_section.add(widget::settings::item_row(vec![
+                widget::button::standard(fl!("settings-upload-retry"))
+                    .on_press(Message::RetryFailedUploads)
+                    .into(),
+            ]));
+        }
+
+        vec![
+            toggle_section.into(),
+            remote_section.into(),
+            session_section.into(),
+        ]
+    }
+
     /// Bug reports sub-page.
     fn bug_reports_sections(&self) -> Vec<Element<'_, Message>> {
         let bug_report_button = widget::button::standard(fl!("settings-report-bug"))
@@ -719,3 +2229,92 @@ impl AppModel {
             .into()
     }
 }
+
+/// Localized name of a control in the layout editor.
+fn control_label(control: crate::app::control_layout::Control) -> String {
+    use crate::app::control_layout::Control;
+
+    match control {
+        Control::Flash => fl!("tools-flash"),
+        Control::Torch => fl!("tools-torch"),
+        Control::HdrPlus => fl!("tools-hdr"),
+        Control::Timer => fl!("tools-timer"),
+        Control::AspectRatio => fl!("tools-aspect"),
+        Control::Exposure => fl!("tools-exposure"),
+        Control::Color => fl!("tools-color"),
+        Control::Filter => fl!("tools-filter"),
+        Control::Motor => fl!("tools-motor"),
+        Control::Mirror => fl!("tools-mirror"),
+        Control::ShowQr => fl!("tools-show-qr"),
+        Control::Loupe => fl!("tools-loupe"),
+        Control::Gallery => fl!("control-gallery"),
+        Control::CameraSwitcher => fl!("control-camera-switcher"),
+        Control::Zoom => fl!("control-zoom"),
+    }
+}
+
+/// Settings row with a live level meter, or an "initializing" caption
+/// until the first `(peak_db, rms_db)` reading arrives.
+fn audio_level_row(label: String, levels: Option<(f64, f64)>) -> widget::Row<'static, Message> {
+    use crate::app::controls::audio_meter::{AudioMeterStyle, audio_meter};
+
+    let row = widget::Row::new()
+        .push(widget::text::body(label))
+        .push(widget::space::horizontal().width(Length::Fill));
+    match levels {
+        Some((peak_db, rms_db)) => row
+            .push(audio_meter(
+                peak_db,
+                rms_db,
+                AudioMeterStyle {
+                    width: 120.0,
+                    height: 10.0,
+                    show_peak: true,
+                },
+            ))
+            .push(widget::space::horizontal().width(Length::Fixed(8.0)))
+            .push(
+                widget::text::caption(format!("{:.0} dB", rms_db))
+                    .font(cosmic::font::mono())
+                    .size(11),
+            ),
+        None => row.push(widget::text::caption(fl!("settings-mic-level-initializing")).size(11)),
+    }
+    .align_y(Alignment::Center)
+}
+
+/// One-line, localized description of what an automation rule does, e.g.
+/// "On motion, 22:00-06:00: record 30 s, notify".
+fn automation_rule_summary(rule: &crate::automation::Rule) -> String {
+    use crate::automation::{Action, Trigger};
+
+    let mut when = match rule.trigger {
+        Trigger::Motion => fl!("automation-trigger-motion"),
+        Trigger::Every(seconds) => fl!("automation-trigger-every", seconds = seconds),
+    };
+    if let Some(window) = rule.between {
+        when = fl!(
+            "automation-trigger-between",
+            trigger = when,
+            window = window.to_string()
+        );
+    }
+    let actions: Vec<String> = rule
+        .actions
+        .iter()
+        .map(|action| match action {
+            Action::Photo => fl!("automation-action-photo"),
+            Action::Record { seconds } => fl!("automation-action-record", seconds = *seconds),
+            Action::Notify { .. } => fl!("automation-action-notify"),
+            Action::Run { command } => fl!(
+                "automation-action-run",
+                command = command.first().cloned().unwrap_or_default()
+            ),
+        })
+        .collect();
+    fl!(
+        "automation-rule-summary",
+        trigger = when,
+        actions = actions.join(", ")
+    )
+}
@@ -10,6 +10,8 @@ use crate::backends::audio::AudioDevice;
 use crate::backends::camera::CameraBackendManager;
 use crate::backends::camera::types::{CameraDevice, CameraFormat, CameraFrame};
 use crate::config::Config;
+use crate::errors::AppError;
+use crate::media::animation::AnimationFormat;
 use crate::media::encoders::video::EncoderInfo;
 use crate::pipelines::video::SharedAudioLevels;
 use cosmic::cosmic_config;
@@ -67,11 +69,18 @@ impl RecordingState {
         matches!(self, RecordingState::Recording { .. })
     }
 
-    /// Get the elapsed recording duration
-    pub fn elapsed_duration(&self) -> u64 {
+    /// Get the elapsed recording duration in seconds as of `now`
+    pub fn elapsed_duration(&self, now: Instant) -> u64 {
+        self.elapsed(now).as_secs()
+    }
+
+    /// Get the elapsed recording time as of `now`
+    pub fn elapsed(&self, now: Instant) -> std::time::Duration {
         match self {
-            RecordingState::Idle => 0,
-            RecordingState::Recording { start_time, .. } => start_time.elapsed().as_secs(),
+            RecordingState::Idle => std::time::Duration::ZERO,
+            RecordingState::Recording { start_time, .. } => {
+                now.saturating_duration_since(*start_time)
+            }
         }
     }
 
@@ -83,16 +92,17 @@ impl RecordingState {
         }
     }
 
-    /// Start recording
+    /// Start recording at `start_time` (read from `AppModel::clock`)
     pub fn start(
         session: u64,
         file_path: String,
         stop_sender: tokio::sync::oneshot::Sender<()>,
         audio_levels: Option<SharedAudioLevels>,
+        start_time: Instant,
     ) -> Self {
         RecordingState::Recording {
             session,
-            start_time: Instant::now(),
+            start_time,
             file_path,
             stop_sender: Some(stop_sender),
             audio_levels,
@@ -114,6 +124,61 @@ impl RecordingState {
             RecordingState::Recording { session, .. } => Some(*session),
         }
     }
+
+    /// File the current recording is written to, or `None` if idle.
+    pub fn file_path(&self) -> Option<&str> {
+        match self {
+            RecordingState::Idle => None,
+            RecordingState::Recording { file_path, .. } => Some(file_path),
+        }
+    }
+}
+
+/// Short animated GIF/WebP capture, started by the record button in Video
+/// mode when a clip format is selected
+#[derive(Debug, Default)]
+pub enum AnimatedClipState {
+    #[default]
+    Idle,
+    /// Sampling preview frames into the export task
+    Capturing {
+        format: AnimationFormat,
+        frames_sent: u32,
+        total_frames: u32,
+        frame_sender: tokio::sync::mpsc::UnboundedSender<Arc<CameraFrame>>,
+    },
+    /// All frames sent; the export task is writing the file
+    Exporting { format: AnimationFormat },
+}
+
+impl AnimatedClipState {
+    pub fn is_active(&self) -> bool {
+        !matches!(self, AnimatedClipState::Idle)
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        matches!(self, AnimatedClipState::Capturing { .. })
+    }
+
+    /// Send a frame to the export task. The last frame of the clip drops
+    /// the sender, which makes the task start exporting.
+    pub fn send_frame(&mut self, frame: Arc<CameraFrame>) {
+        let AnimatedClipState::Capturing {
+            format,
+            frames_sent,
+            total_frames,
+            frame_sender,
+        } = self
+        else {
+            return;
+        };
+        if frame_sender.send(frame).is_ok() {
+            *frames_sent += 1;
+        }
+        if *frames_sent >= *total_frames || frame_sender.is_closed() {
+            *self = AnimatedClipState::Exporting { format: *format };
+        }
+    }
 }
 
 /// State machine for long-press-to-record in Photo mode.
@@ -129,6 +194,10 @@ pub enum QuickRecordState {
     },
     /// Recording is active (threshold exceeded).
     Recording,
+    /// Rapid burst is taking shots while the shutter is held.
+    Bursting {
+        shots: Vec<std::sync::Arc<crate::backends::camera::types::CameraFrame>>,
+    },
 }
 
 impl QuickRecordState {
@@ -139,6 +208,10 @@ impl QuickRecordState {
     pub fn is_recording(&self) -> bool {
         matches!(self, Self::Recording)
     }
+
+    pub fn is_bursting(&self) -> bool {
+        matches!(self, Self::Bursting { .. })
+    }
 }
 
 /// Virtual camera streaming state machine
@@ -153,8 +226,6 @@ pub enum VirtualCameraState {
         start_time: Instant,
         /// Channel to signal stop
         stop_sender: Option<tokio::sync::oneshot::Sender<()>>,
-        /// Channel to send frames to the virtual camera pipeline
-        frame_sender: tokio::sync::mpsc::UnboundedSender<Arc<CameraFrame>>,
         /// Channel to send filter updates to the virtual camera pipeline
         filter_sender: tokio::sync::watch::Sender<FilterType>,
         /// Whether streaming from a file source (image/video)
@@ -179,11 +250,13 @@ impl VirtualCameraState {
         matches!(self, VirtualCameraState::Streaming { .. })
     }
 
-    /// Get the elapsed streaming duration
-    pub fn elapsed_duration(&self) -> u64 {
+    /// Get the elapsed streaming duration in seconds as of `now`
+    pub fn elapsed_duration(&self, now: Instant) -> u64 {
         match self {
             VirtualCameraState::Idle => 0,
-            VirtualCameraState::Streaming { start_time, .. } => start_time.elapsed().as_secs(),
+            VirtualCameraState::Streaming { start_time, .. } => {
+                now.saturating_duration_since(*start_time).as_secs()
+            }
         }
     }
 
@@ -195,28 +268,19 @@ impl VirtualCameraState {
         }
     }
 
-    /// Send a frame to the virtual camera pipeline
-    pub fn send_frame(&self, frame: Arc<CameraFrame>) -> bool {
-        match self {
-            VirtualCameraState::Idle => false,
-            VirtualCameraState::Streaming { frame_sender, .. } => frame_sender.send(frame).is_ok(),
-        }
-    }
-
-    /// Start streaming
+    /// Start streaming at `start_time` (read from `AppModel::clock`)
     ///
     /// When `is_file_source` is true, the stream originates from a file (image/video)
     /// rather than a live camera.
     pub fn start(
         stop_sender: tokio::sync::oneshot::Sender<()>,
-        frame_sender: tokio::sync::mpsc::UnboundedSender<Arc<CameraFrame>>,
         filter_sender: tokio::sync::watch::Sender<FilterType>,
         is_file_source: bool,
+        start_time: Instant,
     ) -> Self {
         VirtualCameraState::Streaming {
-            start_time: Instant::now(),
+            start_time,
             stop_sender: Some(stop_sender),
-            frame_sender,
             filter_sender,
             is_file_source,
         }
@@ -241,6 +305,149 @@ impl VirtualCameraState {
     }
 }
 
+/// Audio/video sync test: a short recording of a flash and a beep
+#[derive(Debug, Default)]
+pub enum AvSyncTestState {
+    /// No test has run
+    #[default]
+    Idle,
+    /// Recording the test clip; the window is white while `flashing` and
+    /// black otherwise
+    Recording { flashing: bool },
+    /// Decoding the clip
+    Measuring,
+    /// Correction to the sync offset in milliseconds, or why there is none
+    Finished(Result<i32, String>),
+}
+
+impl AvSyncTestState {
+    pub fn is_running(&self) -> bool {
+        matches!(
+            self,
+            AvSyncTestState::Recording { .. } | AvSyncTestState::Measuring
+        )
+    }
+}
+
+/// Batch job extracting stills from a recorded video
+#[derive(Debug, Default)]
+pub enum StillExtractionState {
+    /// No job has run
+    #[default]
+    Idle,
+    /// Walking the video
+    Running {
+        /// Video the stills come from
+        source: std::path::PathBuf,
+        /// Progress and cancellation of the job
+        control: crate::pipelines::video::ExtractionControl,
+    },
+    /// Last job finished with this many stills, or failed
+    Finished(Result<usize, String>),
+}
+
+impl StillExtractionState {
+    pub fn is_running(&self) -> bool {
+        matches!(self, StillExtractionState::Running { .. })
+    }
+}
+
+/// Batch job writing a stabilized copy of a recorded video
+#[derive(Debug, Default)]
+pub enum PostStabilizationState {
+    /// No job has run
+    #[default]
+    Idle,
+    /// Stabilizing the video
+    Running {
+        /// Video being stabilized
+        source: std::path::PathBuf,
+        /// Progress and cancellation of the job
+        control: crate::pipelines::video::ExtractionControl,
+    },
+    /// Last job saved this copy, or failed
+    Finished(Result<std::path::PathBuf, String>),
+}
+
+impl PostStabilizationState {
+    pub fn is_running(&self) -> bool {
+        matches!(self, PostStabilizationState::Running { .. })
+    }
+}
+
+/// One scanned page, kept encoded until the document is saved
+#[derive(Debug, Clone)]
+pub struct DocumentPage {
+    pub jpeg: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// The document being scanned in Document mode
+#[derive(Default)]
+pub struct DocumentSession {
+    /// Pages in capture order
+    pub pages: Vec<DocumentPage>,
+    /// Decides when auto-capture takes the page in view
+    pub feed: crate::app::frame_processor::tasks::PageFeed,
+    /// A page is being encoded
+    pub capturing: bool,
+    /// The PDF is being written
+    pub saving: bool,
+    /// The pages' text is being read for the clipboard
+    pub reading_text: bool,
+}
+
+impl DocumentSession {
+    /// Whether a page capture, text recognition or the PDF write is in
+    /// flight
+    pub fn is_busy(&self) -> bool {
+        self.capturing || self.saving || self.reading_text
+    }
+}
+
+/// A cleaned-up whiteboard snapshot: saved as a PNG, and kept as a JPEG
+/// page for the session PDF
+#[derive(Debug, Clone)]
+pub struct WhiteboardSnapshot {
+    /// Path of the saved PNG
+    pub path: String,
+    pub page: DocumentPage,
+}
+
+/// The snapshots taken in Whiteboard mode
+#[derive(Default)]
+pub struct WhiteboardSession {
+    /// Snapshots in capture order, as PDF pages
+    pub pages: Vec<DocumentPage>,
+    /// Decides when auto-snapshot takes the board
+    pub feed: crate::app::frame_processor::tasks::BoardFeed,
+    /// Board corners found in the latest sampled frame
+    pub detected: Option<crate::media::whiteboard::BoardQuad>,
+    /// A snapshot is being processed
+    pub capturing: bool,
+    /// The PDF is being written
+    pub saving: bool,
+}
+
+impl WhiteboardSession {
+    /// Whether a snapshot or the PDF write is in flight
+    pub fn is_busy(&self) -> bool {
+        self.capturing || self.saving
+    }
+}
+
+/// The codes collected in Scan mode
+#[derive(Default)]
+pub struct ScanSession {
+    /// Codes found so far, each once
+    pub list: crate::media::scan_list::ScanList,
+    /// Decoding is paused (capture button or keyboard)
+    pub paused: bool,
+    /// The CSV is being written
+    pub exporting: bool,
+}
+
 /// Timelapse capture state machine
 ///
 /// Frames are sent directly to a video encoder via a channel — no photos
@@ -307,12 +514,14 @@ impl TimelapseState {
         !matches!(self, TimelapseState::Idle)
     }
 
-    /// Get elapsed duration in seconds
-    pub fn elapsed_duration(&self) -> u64 {
+    /// Get elapsed duration in seconds as of `now`
+    pub fn elapsed_duration(&self, now: Instant) -> u64 {
         match self {
             TimelapseState::Idle => 0,
             TimelapseState::Running { start_time, .. }
-            | TimelapseState::Finalising { start_time, .. } => start_time.elapsed().as_secs(),
+            | TimelapseState::Finalising { start_time, .. } => {
+                now.saturating_duration_since(*start_time).as_secs()
+            }
         }
     }
 
@@ -394,6 +603,9 @@ pub struct BurstModeState {
     /// Channel receiver for processing result
     /// Only present during Processing stage
     result_rx: Option<std::sync::mpsc::Receiver<Result<String, String>>>,
+    /// Stops the processing task when cancelled
+    /// Only present during Processing stage
+    cancel: Option<tokio_util::sync::CancellationToken>,
 }
 
 /// Burst mode processing stages
@@ -496,15 +708,18 @@ impl BurstModeState {
         self.frame_buffer.clear();
         self.progress_atomic = None;
         self.result_rx = None;
+        self.cancel = None;
     }
 
     /// Start processing and set up communication channels.
-    /// Returns the atomic counter that the processing task should update.
+    /// Returns the atomic counter that the processing task should update and
+    /// the token it should stop on.
     pub fn start_processing_task(
         &mut self,
     ) -> (
         Arc<std::sync::atomic::AtomicU32>,
         std::sync::mpsc::Sender<Result<String, String>>,
+        tokio_util::sync::CancellationToken,
     ) {
         self.stage = BurstModeStage::Processing;
         self.processing_progress = 0.0;
@@ -517,7 +732,30 @@ impl BurstModeState {
         let (result_tx, result_rx) = std::sync::mpsc::channel();
         self.result_rx = Some(result_rx);
 
-        (progress_atomic, result_tx)
+        let cancel = tokio_util::sync::CancellationToken::new();
+        self.cancel = Some(cancel.clone());
+
+        (progress_atomic, result_tx, cancel)
+    }
+
+    /// Ask the processing task to stop. Its result still arrives, as an
+    /// error, once it has let go of the GPU.
+    /// Returns false if nothing was being processed.
+    pub fn cancel_processing(&mut self) -> bool {
+        match &self.cancel {
+            Some(cancel) if self.stage == BurstModeStage::Processing => {
+                cancel.cancel();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether cancelling was asked for and the task hasn't finished yet
+    pub fn is_cancelling(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(tokio_util::sync::CancellationToken::is_cancelled)
     }
 
     /// Poll progress from the processing task.
@@ -545,6 +783,7 @@ impl BurstModeState {
                     // Clear processing state
                     self.progress_atomic = None;
                     self.result_rx = None;
+                    self.cancel = None;
                     Some(result)
                 }
                 Err(std::sync::mpsc::TryRecvError::Empty) => None,
@@ -552,6 +791,7 @@ impl BurstModeState {
                     // Channel closed unexpectedly
                     self.progress_atomic = None;
                     self.result_rx = None;
+                    self.cancel = None;
                     Some(Err("Processing task terminated unexpectedly".to_string()))
                 }
             }
@@ -564,6 +804,7 @@ impl BurstModeState {
     pub fn clear_processing_state(&mut self) {
         self.progress_atomic = None;
         self.result_rx = None;
+        self.cancel = None;
     }
 }
 
@@ -576,19 +817,112 @@ impl Default for BurstModeState {
             target_frame_count: 8, // Will be overwritten when capture starts
             progress_atomic: None,
             result_rx: None,
+            cancel: None,
         }
     }
 }
 
+/// A photo booth run in progress
+#[derive(Debug)]
+pub struct PhotoBoothSession {
+    /// Number of shots the run takes
+    pub total: usize,
+    /// Frames taken so far
+    pub shots: Vec<Arc<CameraFrame>>,
+}
+
+impl PhotoBoothSession {
+    pub fn new(total: usize) -> Self {
+        Self {
+            total,
+            shots: Vec::with_capacity(total),
+        }
+    }
+
+    /// 1-based number of the shot the current countdown leads to
+    pub fn next_shot(&self) -> usize {
+        self.shots.len() + 1
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.shots.len() >= self.total
+    }
+}
+
+/// A focus sweep in progress
+#[derive(Debug)]
+pub struct FocusStackSession {
+    /// Focus positions to visit, in order
+    pub positions: Vec<i32>,
+    /// Frames kept so far, one per visited position
+    pub frames: Vec<Arc<CameraFrame>>,
+    /// Manual focus position to return to afterwards
+    pub restore_focus: Option<i32>,
+    /// Whether autofocus was on before the sweep
+    pub restore_focus_auto: bool,
+}
+
+impl FocusStackSession {
+    pub fn is_complete(&self) -> bool {
+        self.frames.len() >= self.positions.len()
+    }
+
+    /// Position the lens goes to for the next frame
+    pub fn next_position(&self) -> Option<i32> {
+        self.positions.get(self.frames.len()).copied()
+    }
+}
+
+/// Share chip above the shutter, for the latest capture or one picked from
+/// the gallery button
+#[derive(Debug, Clone)]
+pub struct ShareMenu {
+    /// Capture being shared
+    pub path: std::path::PathBuf,
+    /// Showing the targets, rather than the "Share" prompt offered after a
+    /// capture
+    pub expanded: bool,
+}
+
+/// A WiFi network being joined from a QR code, shown on the code's button
+#[derive(Debug, Clone)]
+pub struct WifiJoin {
+    pub ssid: String,
+    pub status: crate::network_manager::WifiJoinStatus,
+}
+
+/// Upload settings being edited, saved together with "Save"
+#[derive(Debug, Clone, Default)]
+pub struct UploadForm {
+    pub settings: crate::config::UploadSettings,
+    /// Password or secret key; left empty to keep the stored one
+    pub secret: String,
+    /// Outcome of the last save
+    pub status: Option<Result<(), String>>,
+}
+
+/// Text field of the upload settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UploadField {
+    Url,
+    Bucket,
+    Region,
+    Username,
+    Secret,
+}
+
 /// Grouped flash-related state.
 ///
-/// Keeping these four fields together makes it obvious which fields belong to
+/// Keeping these fields together makes it obvious which fields belong to
 /// the flash subsystem and reduces the risk of forgetting one during a reset
 /// (e.g. switching cameras must clear both `enabled` and the error popup).
 #[derive(Default)]
 pub struct FlashState {
     /// Flash enabled for photo capture (user toggle).
     pub enabled: bool,
+    /// Torch kept lit as a flashlight, outside of any capture (user toggle,
+    /// back cameras with flash hardware only).
+    pub torch: bool,
     /// Flash overlay is currently being drawn (screen-flash path).
     pub active: bool,
     /// Detected flash hardware (LED nodes + permission status).
@@ -598,6 +932,20 @@ pub struct FlashState {
     pub error_popup: Option<String>,
 }
 
+/// Everything the pre-built recording pipeline depends on, cheap to build
+/// and compare on every preview frame
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RecorderWarmupKey {
+    pub encoder_index: usize,
+    pub width: u32,
+    pub height: u32,
+    pub framerate: Option<crate::backends::camera::types::Framerate>,
+    pub bitrate_preset: crate::constants::BitratePreset,
+    pub video_container: crate::config::VideoContainer,
+    pub rotation: crate::backends::camera::types::SensorRotation,
+    pub orientation: crate::backends::camera::types::SensorRotation,
+}
+
 /// The application model stores app-specific state used to describe its interface and
 /// drive its logic.
 #[cfg_attr(test, derive(Default))]
@@ -635,28 +983,54 @@ pub struct AppModel {
     pub photo_btn_anim_start: Option<std::time::Instant>,
     /// Recording state (idle, recording, or paused)
     pub recording: RecordingState,
-    /// Preview harness only: when set, the recording indicator shows a fixed
-    /// placeholder duration instead of the live elapsed time, so the "recording
-    /// in progress" screenshot is deterministic. Enabled by
-    /// `--preview-spoof-recording`; see `AppFlags::preview_spoof_recording`.
-    pub preview_spoof_recording: bool,
+    /// Time source for the recording/streaming/timelapse timers and the
+    /// countdown fade. The system clock, except under
+    /// `--preview-spoof-recording`, which freezes it so the screenshot is
+    /// deterministic.
+    pub clock: crate::app::clock::SharedClock,
     /// Monotonic counter that mints a unique ID for each recording session.
     /// `handle_start_recording` increments this before assigning the new
     /// session; `handle_recording_stopped` uses it to ignore late stop events
     /// from a previous session whose async finalizer outlived its UI lifetime.
     pub recording_session_counter: u64,
+    /// Events logged for the subtitle sidecar, with the session they belong
+    /// to (`None` when `Config::record_event_subtitles` was off at start)
+    pub recording_events: Option<(u64, crate::pipelines::video::RecordingEventLog)>,
+    /// Settings the pre-built recording pipeline was last requested for
+    /// (`None` while none is wanted)
+    pub recorder_warmup: Option<RecorderWarmupKey>,
+    /// The ScreenCast portal's source picker is open for a screen recording
+    pub screen_cast_pending: bool,
+    /// Screen the next recording captures, picked but not yet recording
+    pub screen_cast: Option<Arc<crate::backends::screen_cast::ScreenCast>>,
+    /// How full the output filesystem was at the last check, with the
+    /// bytes left; `None` while there is plenty of space
+    pub storage_warning: Option<(crate::storage::SpaceLevel, u64)>,
+    /// The low storage chip was closed; it comes back if the disk fills up
+    pub storage_warning_dismissed: bool,
     /// Virtual camera state (idle or streaming)
     pub virtual_camera: VirtualCameraState,
     /// File source for virtual camera (image or video to stream instead of camera)
     pub virtual_camera_file_source: Option<FileSource>,
+    /// Directory the file source cycles through, if one was picked
+    pub preview_playlist: Option<crate::backends::virtual_camera::Playlist>,
+    /// Loop mode, rate and simulated capture format of file sources
+    pub preview_playback: crate::backends::virtual_camera::PlaybackOptions,
     /// Whether the current frame is from a file source (vs camera)
     pub current_frame_is_file_source: bool,
+    /// Developer console reads commands from stdin (`dev-console` feature)
+    pub dev_console: bool,
+    /// Debug frame tap socket (`--frame-tap`)
+    pub frame_tap: Option<crate::app::frame_tap::FrameTap>,
     /// Rotation of the camera that produced the current frame
     /// (used during blur transitions to maintain correct rotation)
     pub current_frame_rotation: crate::backends::camera::types::SensorRotation,
     /// Rotation of the camera that produced the blur frame
     /// (captured at start of blur transition to maintain correct rotation during transition)
     pub blur_frame_rotation: crate::backends::camera::types::SensorRotation,
+    /// How the device is held, as a rotation correction on top of the
+    /// sensor's (None without an accelerometer)
+    pub device_orientation: crate::backends::camera::types::SensorRotation,
     /// Whether the blur frame was mirrored (captured at start of blur transition)
     pub blur_frame_mirror: bool,
     /// Digital zoom the blur frame was rendered at (captured at start of blur
@@ -688,6 +1062,13 @@ pub struct AppModel {
     pub exposure_picker_visible: bool,
     /// Whether the color picker is visible (iOS-style popup)
     pub color_picker_visible: bool,
+    /// Whether the next click on the preview picks the white balance gray point
+    pub wb_eyedropper_active: bool,
+    /// Focus loupe over the preview, while it is open
+    pub loupe: Option<crate::app::camera_preview::loupe::Loupe>,
+    /// Gains the GPU filter passes apply for cameras without a white balance
+    /// temperature control (mirrors `crate::shaders::software_white_balance`)
+    pub software_white_balance: crate::shaders::WhiteBalanceGains,
     /// Whether the tools menu is visible (iOS-style popup)
     pub tools_menu_visible: bool,
 
@@ -697,6 +1078,30 @@ pub struct AppModel {
 
     /// Current exposure settings for active camera
     pub exposure_settings: Option<ExposureSettings>,
+    /// Exposure/focus lock set by long-pressing the preview, with what it
+    /// takes to undo it
+    pub ae_af_lock: Option<crate::backends::camera::v4l2_controls::AeAfLock>,
+    /// Automatic exposure and white balance held by the AE-L and AWB-L
+    /// buttons, with what it takes to release each
+    pub auto_holds: std::collections::HashMap<
+        crate::backends::camera::v4l2_controls::AutoControl,
+        crate::backends::camera::v4l2_controls::AutoHold,
+    >,
+    /// Exposure, gain, white balance and focus last read back from V4L2 for
+    /// the on-screen display (libcamera frames carry their own)
+    pub osd_values: Option<crate::app::exposure_osd::OsdValues>,
+    /// Glass-to-glass latency measurement in progress; the window flashes
+    /// black and white while it runs
+    pub latency_probe: Option<crate::app::latency_probe::LatencyProbe>,
+    /// Result of the last latency measurement of the active capture card
+    pub measured_latency: Option<crate::app::latency_probe::ProbeOutcome>,
+    /// Colorimetry the driver reported for the current format, keyed by
+    /// device path and FourCC so it is only queried after a switch
+    pub signalled_colorimetry: Option<(
+        String,
+        String,
+        Option<crate::backends::camera::v4l2_utils::V4l2Colorimetry>,
+    )>,
     /// Current color/image adjustment settings for active camera
     pub color_settings: Option<ColorSettings>,
     /// Available exposure controls for current camera (queried from V4L2)
@@ -706,19 +1111,65 @@ pub struct AppModel {
     /// Base exposure time (in 100µs units) captured when entering manual mode
     /// Used to calculate EV-based adjustments in non-advanced mode
     pub base_exposure_time: Option<i32>,
+    /// Manual exposure proposed by the histogram assist, awaiting apply
+    pub exposure_suggestion: Option<crate::app::frame_processor::ExposureSuggestion>,
+    /// Tracks other apps overriding the controls stored in `config.camera_controls`
+    pub control_overrides: ControlOverrideTracker,
+    /// Path of the standby camera that took over after the active camera
+    /// stalled or was unplugged (cleared when another camera is selected)
+    pub failover_camera: Option<String>,
+    /// Standby camera dropdown options ("None" followed by the camera names)
+    pub standby_camera_dropdown_options: Vec<String>,
+    /// Second camera dropdown options ("Off" followed by the camera names)
+    pub pip_camera_dropdown_options: Vec<String>,
+    /// Picture-in-picture corner dropdown options
+    pub pip_corner_dropdown_options: Vec<String>,
+    /// Camera location dropdown options for the "look here" marker
+    pub camera_location_dropdown_options: Vec<String>,
+    /// Dual camera layout dropdown options
+    pub dual_camera_layout_dropdown_options: Vec<String>,
+    /// Second camera resolution dropdown options
+    pub secondary_resolution_dropdown_options: Vec<String>,
+    /// Latest second camera frame for the preview, with its width / height
+    /// ratio
+    pub pip_preview: Option<(cosmic::widget::image::Handle, f32)>,
     /// Burst mode state (enabled, capture/processing progress)
     pub burst_mode: BurstModeState,
+    /// Quick preview shown in the gallery while the HDR+ merge runs
+    pub burst_preview: Option<crate::storage::PendingPhoto>,
+    /// Frames currently being collected by `burst_mode` are for a product
+    /// shot rather than an HDR+ merge
+    pub product_capture_active: bool,
     /// Auto-detected frame count based on current scene brightness (1-8)
-    /// Updated every 1 second when in Auto mode via BrightnessEvaluationTick
+    /// Updated every 1 second by the light meter
     pub auto_detected_frame_count: usize,
     /// User override to disable HDR+ even when auto-detection suggests using it
     /// Reset when switching burst mode settings or on app restart
     pub hdr_override_disabled: bool,
+    /// Latest light meter reading of the preview
+    pub light_reading: Option<crate::app::frame_processor::tasks::light_meter::LightReading>,
+    /// Burst parameters sized from the latest light reading
+    pub burst_plan: Option<crate::pipelines::photo::burst_mode::burst::AdaptiveBurstParams>,
+    /// Debounces light readings into whether the scene is dark
+    pub night_detector: crate::app::frame_processor::tasks::NightDetector,
+    /// Night mode switched HDR+ on for the dark scene (HDR+ setting is Off)
+    pub night_mode_engaged: bool,
     /// Currently selected filter
     pub selected_filter: FilterType,
+    /// Names of the LUTs imported into the filter picker
+    pub imported_luts: Vec<String>,
+    /// Imported LUT applied by [`FilterType::Lut`] (mirrors `crate::shaders::set_active_lut`)
+    pub selected_lut: Option<String>,
+    /// Last LUT import or load failure, shown in the filter picker
+    pub lut_error: Option<String>,
     /// Live filter code shared with the recording pusher (AtomicU32).
     /// Updated on every filter change so the recorder sees it in real-time.
     pub recording_filter_code: std::sync::Arc<std::sync::atomic::AtomicU32>,
+    /// Live audio sync offset of the selected microphone, shared with the
+    /// recorder so drawer changes apply mid-recording.
+    pub audio_sync_offset: crate::pipelines::audio_level::SharedSyncOffset,
+    /// Audio/video sync test in progress or its last result
+    pub av_sync_test: AvSyncTestState,
     /// All flash-related state, grouped to keep reset/configuration
     /// transitions in one place. See [`FlashState`].
     pub flash: FlashState,
@@ -728,6 +1179,12 @@ pub struct AppModel {
     pub photo_timer_countdown: Option<u8>,
     /// When the current countdown second started (for fade animation)
     pub photo_timer_tick_start: Option<Instant>,
+    /// Photo booth run in progress; its countdowns use `photo_timer_countdown`
+    pub photo_booth: Option<PhotoBoothSession>,
+    /// Focus sweep in progress for a focus-stacked shot
+    pub focus_stack: Option<FocusStackSession>,
+    /// Best-shot picker shown after a rapid burst is saved
+    pub burst_picker: Option<crate::app::burst_picker::BurstPicker>,
     /// Photo aspect ratio (native, 4:3, 16:9, 1:1)
     pub photo_aspect_ratio: PhotoAspectRatio,
     /// Current (settled) zoom level (1.0 = no zoom, 2.0 = 2x zoom, etc.)
@@ -745,6 +1202,14 @@ pub struct AppModel {
     /// deliberately not persisted: restoring it at launch would open the app
     /// with no visible controls. Esc and the shortcut both bring the UI back.
     pub ui_hidden: bool,
+    /// Mirror mode: the preview full screen and mirrored, with only a light
+    /// toggle and a way out. Session-only like `ui_hidden`.
+    pub mirror_mode: bool,
+    /// Backlight driven to full for the screen light, holding the level to
+    /// go back to
+    pub screen_boost: Option<crate::backlight::Backlight>,
+    /// "Show QR" composer, while open
+    pub qr_share: Option<crate::app::qr_share::QrShare>,
     /// Path to last generated bug report
     pub last_bug_report_path: Option<String>,
     /// Path to the most recently saved photo or video (for gallery pre-selection)
@@ -758,8 +1223,36 @@ pub struct AppModel {
     pub gallery_thumbnail: Option<cosmic::widget::image::Handle>,
     /// Gallery thumbnail RGBA data for custom rendering (Arc for cheap cloning)
     pub gallery_thumbnail_rgba: Option<(Arc<Vec<u8>>, u32, u32)>,
-    /// Currently selected resolution in the picker (width for grouping)
-    pub picker_selected_resolution: Option<u32>,
+    /// Capture the gallery thumbnail shows
+    pub gallery_thumbnail_path: Option<std::path::PathBuf>,
+    /// The gallery thumbnail was loaded at startup; captures that replace it
+    /// from now on are offered for sharing
+    pub gallery_loaded: bool,
+    /// Share chip, if shown
+    pub share_menu: Option<ShareMenu>,
+    /// When a photo was last copied to the clipboard, while its notice shows
+    pub clipboard_notice: Option<Instant>,
+    /// Failure shown above the shutter with what to do about it, and when
+    /// it went up
+    pub error_notice: Option<(AppError, Instant)>,
+    /// Custom share targets (re-read when the share menu or settings open)
+    pub share_targets: crate::share::ShareTargets,
+    /// Error from reading the share targets file
+    pub share_targets_error: Option<String>,
+    /// Upload state of the captures made since `upload_since`
+    pub upload_status: std::collections::HashMap<std::path::PathBuf, crate::upload::UploadStatus>,
+    /// Captures saved after this are uploaded (set when the gallery
+    /// thumbnail was loaded at startup)
+    pub upload_since: Option<std::time::SystemTime>,
+    /// Upload settings page draft
+    pub upload_form: UploadForm,
+    /// Upload provider dropdown options (WebDAV, S3)
+    pub upload_provider_dropdown_options: Vec<String>,
+    /// Search text narrowing the format picker's list
+    pub format_picker_query: String,
+    /// USB connection of the current camera (None for non-USB cameras),
+    /// re-read when the format picker opens or Insights sees a new camera
+    pub usb_link: Option<crate::backends::camera::usb_bandwidth::UsbLink>,
     /// V4L2 device path the user is trying to switch to (set when switching
     /// to a hotplugged camera that needs full re-enumeration).
     pub pending_hotplug_switch: Option<String>,
@@ -776,6 +1269,10 @@ pub struct AppModel {
     /// Notifier fired by the capture thread when a new still frame is stored.
     /// Lets `wait_for_still_frame` await on a notification instead of polling.
     pub still_frame_notify: std::sync::Arc<tokio::sync::Notify>,
+    /// Newest preview frame from the camera subscription, waiting for the UI
+    pub preview_mailbox: Arc<crate::app::frame_pacer::FrameMailbox<Arc<CameraFrame>>>,
+    /// Paces taking frames from `preview_mailbox` to the renderer
+    pub frame_pacer: crate::app::frame_pacer::FramePacer,
     /// Current camera frame
     pub current_frame: Option<Arc<CameraFrame>>,
     /// Available camera devices
@@ -791,6 +1288,11 @@ pub struct AppModel {
     pub available_formats: Vec<CameraFormat>,
     /// Currently active format being used by camera
     pub active_format: Option<CameraFormat>,
+    /// Smaller format the preview steps down to with
+    /// `Config::adaptive_preview`; see `AppModel::preview_format`
+    pub adaptive_preview: crate::app::adaptive_preview::AdaptivePreview,
+    /// Free space in the video folder, refreshed when the Video settings page opens
+    pub video_disk_free_bytes: Option<u64>,
     /// Available audio input devices
     pub available_audio_devices: Vec<AudioDevice>,
     /// Current audio device index
@@ -826,10 +1328,50 @@ pub struct AppModel {
     pub photo_output_format_dropdown_options: Vec<String>,
     /// Audio encoder dropdown options (Opus, AAC)
     pub audio_encoder_dropdown_options: Vec<String>,
+    /// Video container dropdown options (cached for UI)
+    pub video_container_dropdown_options: Vec<String>,
+    /// Video stabilization dropdown options (Off, Low, Medium, High)
+    pub video_stabilization_dropdown_options: Vec<String>,
+    /// Video denoise dropdown options (Off, Low, Medium, High)
+    pub video_denoise_dropdown_options: Vec<String>,
+    /// Anamorphic desqueeze dropdown options (Off, 1.33x, 1.5x, 2x)
+    pub anamorphic_desqueeze_dropdown_options: Vec<String>,
+    /// Night mode dropdown options (Off, Suggest, Auto)
+    pub night_mode_dropdown_options: Vec<String>,
     /// Composition guide dropdown options
     pub composition_guide_dropdown_options: Vec<String>,
+    /// One-handed layout dropdown options (Off, Left, Right)
+    pub one_handed_layout_dropdown_options: Vec<String>,
+    /// Volume-key action dropdown options (Capture, Zoom, Volume)
+    pub volume_key_action_dropdown_options: Vec<String>,
+    /// Color vision assist dropdown options, in `ColorVisionAssist::ALL` order
+    pub color_vision_assist_dropdown_options: Vec<String>,
+    /// Preview scaling dropdown options, in `PreviewScaling::ALL` order
+    pub preview_scaling_dropdown_options: Vec<String>,
+    /// HDR input dropdown options, in `HdrInput::ALL` order
+    pub hdr_input_dropdown_options: Vec<String>,
+    /// Photo booth layout dropdown options (Strip, Grid)
+    pub photo_booth_layout_dropdown_options: Vec<String>,
+    /// Photo booth shot count dropdown options
+    pub photo_booth_shots_dropdown_options: Vec<String>,
+    /// Photo booth countdown dropdown options
+    pub photo_booth_countdown_dropdown_options: Vec<String>,
+    /// Rapid burst shot limit dropdown options
+    pub rapid_burst_max_shots_dropdown_options: Vec<String>,
+    /// Focus stack sweep length dropdown options
+    pub focus_stack_steps_dropdown_options: Vec<String>,
+    /// Low storage warning threshold dropdown options
+    pub low_space_warning_dropdown_options: Vec<String>,
+    /// Camera rotation dropdown options (0°, 90°, 180°, 270°)
+    pub camera_rotation_dropdown_options: Vec<String>,
     /// Default mode dropdown options (Photo, Video, Timelapse, Virtual)
     pub default_mode_dropdown_options: Vec<String>,
+    /// Control layout dropdown options (Top bar, Tools menu, Hidden)
+    pub control_placement_dropdown_options: Vec<String>,
+    /// Control layout dropdown options for fixed-spot controls (Shown, Hidden)
+    pub control_visibility_dropdown_options: Vec<String>,
+    /// Mode whose control layout the Controls settings page is editing
+    pub layout_editor_mode: CameraMode,
     /// Whether the device info panel is visible
     pub device_info_visible: bool,
 
@@ -859,8 +1401,48 @@ pub struct AppModel {
     pub qr_detection_enabled: bool,
     /// Current QR code detections (updated at 1 FPS)
     pub qr_detections: Vec<QrDetection>,
-    /// Last time QR detection was processed
-    pub last_qr_detection_time: Option<Instant>,
+    /// Latest WiFi join started from a QR code
+    pub wifi_join: Option<WifiJoin>,
+    /// Scanned QR codes, newest first
+    pub qr_history: crate::qr_history::QrHistory,
+    /// Search text narrowing the QR history page
+    pub qr_history_query: String,
+
+    // ===== Gesture Shutter =====
+    /// When the shutter gesture was first seen in the current unbroken run
+    pub gesture_hold_start: Option<Instant>,
+
+    // ===== Face Exposure Priority =====
+    /// When a face was last found, so a missed detection or a turned head
+    /// doesn't make the exposure jump straight back
+    pub face_last_seen: Option<Instant>,
+    /// Exposure bias applied for the detected face (0.001 EV), on cameras
+    /// without a metering region. Never remembered; the user's own bias
+    /// stays in the exposure settings and is restored when the face goes.
+    pub face_exposure_bias: Option<i32>,
+    /// Whether the camera's region of interest is set to a face
+    pub face_region_active: bool,
+
+    // ===== Document Scanning =====
+    /// Pages scanned so far in Document mode
+    pub document: DocumentSession,
+
+    // ===== Whiteboard =====
+    /// Snapshots taken so far in Whiteboard mode
+    pub whiteboard: WhiteboardSession,
+
+    // ===== Barcode Scanning =====
+    /// Codes collected so far in Scan mode
+    pub scan: ScanSession,
+
+    // ===== Scene Suggestions =====
+    /// Debounces classified scenes into the suggestion shown
+    pub scene_feed: crate::app::frame_processor::tasks::SceneFeed,
+    /// Suggestion chip currently shown above the capture controls
+    pub scene_suggestion: Option<crate::app::frame_processor::Scene>,
+    /// Scenes whose suggestion was applied or dismissed this session, so
+    /// the same chip doesn't keep coming back
+    pub dismissed_scenes: Vec<crate::app::frame_processor::Scene>,
 
     // ===== Privacy Cover Detection =====
     /// Whether the camera privacy cover is closed (blocking the camera)
@@ -877,10 +1459,84 @@ pub struct AppModel {
     pub timelapse: TimelapseState,
     /// Timelapse interval dropdown options (cached for UI)
     pub timelapse_interval_dropdown_options: Vec<String>,
+    /// Still extraction job
+    pub still_extraction: StillExtractionState,
+    /// Still extraction dropdown options: scene changes, then every
+    /// `TimelapseInterval` (cached for UI)
+    pub still_extraction_dropdown_options: Vec<String>,
+    /// Post-capture stabilization job
+    pub post_stabilization: PostStabilizationState,
+
+    // ===== Animated Clips =====
+    /// GIF/WebP clip capture state
+    pub animated_clip: AnimatedClipState,
+    /// Clip format dropdown options (Video, GIF, WebP)
+    pub animated_clip_format_dropdown_options: Vec<String>,
+    /// Clip length dropdown options
+    pub animated_clip_seconds_dropdown_options: Vec<String>,
+    /// Clip frame rate dropdown options
+    pub animated_clip_fps_dropdown_options: Vec<String>,
 
     // ===== Insights Drawer =====
     /// Insights drawer diagnostic state
     pub insights: super::insights::InsightsState,
+
+    // ===== Extension Unit Controls =====
+    /// UVC extension unit controls of the current camera (queried when the page opens)
+    pub xu_controls: Vec<XuControlState>,
+
+    // ===== Automation =====
+    /// Rules from the automation rules file (loaded when the page opens)
+    pub automation_rules: crate::automation::RuleSet,
+    /// Why the rules file couldn't be read or written, if it couldn't
+    pub automation_rules_error: Option<String>,
+}
+
+/// Detects another application repeatedly changing the V4L2 controls the
+/// user set in this app
+#[derive(Debug, Clone, Default)]
+pub struct ControlOverrideTracker {
+    /// When stored controls were found overridden, oldest first
+    overrides: Vec<Instant>,
+}
+
+impl ControlOverrideTracker {
+    /// Record that stored controls had been changed and were re-applied
+    pub fn record(&mut self, now: Instant) {
+        self.expire(now);
+        self.overrides.push(now);
+    }
+
+    /// Forget overrides that fell out of the conflict window
+    pub fn expire(&mut self, now: Instant) {
+        let window =
+            std::time::Duration::from_secs(crate::constants::timing::CONTROL_CONFLICT_WINDOW_SECS);
+        self.overrides.retain(|&t| now.duration_since(t) < window);
+    }
+
+    /// Whether controls were overridden often enough to indicate that another
+    /// app keeps fighting over them
+    pub fn is_conflicting(&self) -> bool {
+        self.overrides.len() >= crate::constants::timing::CONTROL_CONFLICT_THRESHOLD
+    }
+
+    pub fn clear(&mut self) {
+        self.overrides.clear();
+    }
+}
+
+/// One UVC extension unit control shown on the extension units page
+#[derive(Debug, Clone)]
+pub struct XuControlState {
+    /// GUID of the extension unit the control belongs to
+    pub guid: String,
+    pub info: crate::backends::camera::v4l2_controls::XuControlInfo,
+    /// Current payload, if the control is readable
+    pub value: Option<Vec<u8>>,
+    /// Hex text in the raw value editor
+    pub edit: String,
+    /// Last error reported for this control
+    pub error: Option<String>,
 }
 
 /// In-flight animation between Cover and Contain preview modes (and the
@@ -984,6 +1640,13 @@ pub enum CameraMode {
     Virtual,
     /// Timelapse mode - captures photos at a configurable interval
     Timelapse,
+    /// Document mode - scans pages, by hand or automatically, into a PDF
+    Document,
+    /// Whiteboard mode - keystone-corrected, cleaned-up board snapshots
+    Whiteboard,
+    /// Scan mode - continuously decodes barcodes and QR codes into a
+    /// de-duplicated list that can be exported as CSV
+    Scan,
     /// View mode — minimal-UI live preview. No capture controls; only the
     /// mode carousel, fit/fill toggle, and zoom button are shown, and the
     /// top/bottom UI scrim is fully transparent.
@@ -992,10 +1655,13 @@ pub enum CameraMode {
 
 impl CameraMode {
     /// All available camera modes
-    pub const ALL: [CameraMode; 5] = [
+    pub const ALL: [CameraMode; 8] = [
         CameraMode::Photo,
         CameraMode::Video,
         CameraMode::Timelapse,
+        CameraMode::Document,
+        CameraMode::Whiteboard,
+        CameraMode::Scan,
         CameraMode::Virtual,
         CameraMode::View,
     ];
@@ -1025,6 +1691,24 @@ pub enum FileSource {
     Video(std::path::PathBuf),
 }
 
+impl FileSource {
+    /// File source for a media file, by its extension
+    pub fn from_path(path: std::path::PathBuf) -> Option<Self> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .unwrap_or_default();
+        if crate::constants::file_formats::is_image_extension(&extension) {
+            Some(Self::Image(path))
+        } else if crate::constants::file_formats::is_video_extension(&extension) {
+            Some(Self::Video(path))
+        } else {
+            None
+        }
+    }
+}
+
 /// Application initialization flags
 ///
 /// Results from pre-warming work done before the iced event loop starts.
@@ -1048,8 +1732,11 @@ pub struct PrewarmResults {
 #[derive(Default)]
 pub struct AppFlags {
     /// Optional file to use as the camera preview source instead of a real camera.
-    /// Can be an image (PNG, JPG, JPEG, WEBP) or video (MP4, WEBM, MKV).
+    /// Can be an image (PNG, JPG, JPEG, WEBP) or video (MP4, WEBM, MKV), or a
+    /// directory of them to cycle through.
     pub preview_source: Option<std::path::PathBuf>,
+    /// How the preview source plays: looping, rate and simulated capture format
+    pub preview_playback: crate::backends::virtual_camera::PlaybackOptions,
     /// Preview harness only: start in Video mode with a spoofed active recording
     /// — the recording indicator and a fixed elapsed timer are shown, but no
     /// encoder pipeline is started. Lets the screenshot harness capture the
@@ -1061,6 +1748,14 @@ pub struct AppFlags {
     /// file source. Lets the harness run with no camera and no dma-buf provider.
     /// See `crate::backends::camera::synthetic`.
     pub preview_fake_camera: bool,
+    /// Read developer console commands from stdin (`dev-console` feature)
+    pub dev_console: bool,
+    /// Stream the preview to a debug inspector over a local socket
+    pub frame_tap: bool,
+    /// Start in mirror mode (`camera mirror`)
+    pub mirror: bool,
+    /// Turn the mirror-mode screen light on for this session
+    pub mirror_light: bool,
     /// Pre-warmed results from background thread started before the event loop.
     /// If present, init() skips the synchronous enumeration.
     pub prewarm: Option<std::thread::JoinHandle<PrewarmResults>>,
@@ -1306,6 +2001,16 @@ pub enum FilterType {
     ChromaticAberration,
     /// Pencil - pencil sketch drawing
     Pencil,
+    /// Portrait 400 - warm, soft-contrast colour negative with fine grain
+    FilmPortrait,
+    /// Slide 50 - saturated, contrasty slide film with almost no grain
+    FilmSlide,
+    /// Pan 400 - black & white film with coarse grain
+    FilmPan,
+    /// Tungsten 800 - cool-balanced high-speed film with halation
+    FilmTungsten,
+    /// Custom LUT - the imported `.cube` LUT selected in the filter picker
+    Lut,
 }
 
 impl FilterType {
@@ -1331,6 +2036,11 @@ impl FilterType {
             FilterType::Solarize => 12,
             FilterType::ChromaticAberration => 13,
             FilterType::Pencil => 14,
+            FilterType::FilmPortrait => 15,
+            FilterType::FilmSlide => 16,
+            FilterType::FilmPan => 17,
+            FilterType::FilmTungsten => 18,
+            FilterType::Lut => 19,
         }
     }
 
@@ -1364,6 +2074,11 @@ impl FilterType {
             12 => FilterType::Solarize,
             13 => FilterType::ChromaticAberration,
             14 => FilterType::Pencil,
+            15 => FilterType::FilmPortrait,
+            16 => FilterType::FilmSlide,
+            17 => FilterType::FilmPan,
+            18 => FilterType::FilmTungsten,
+            19 => FilterType::Lut,
             _ => FilterType::Standard,
         }
     }
@@ -1378,6 +2093,10 @@ pub enum ContextPage {
     Insights,
     /// Keyboard shortcuts rebinding page (opened from the Settings drawer).
     KeyBindings,
+    /// Vendor-specific UVC extension unit controls (opened from the Settings drawer).
+    ExtensionUnits,
+    /// Scanned QR codes (opened from the QR overlay or the Settings drawer).
+    QrHistory,
 }
 
 /// Which sub-page is shown inside the Settings context drawer.
@@ -1394,7 +2113,10 @@ pub enum SettingsPage {
     Video,
     Timelapse,
     Appearance,
+    Controls,
     VirtualCamera,
+    Automation,
+    Upload,
     BugReports,
     About,
 }
@@ -1455,6 +2177,12 @@ pub enum Message {
     SetExposureTime(i32),
     /// Set gain value
     SetGain(i32),
+    /// Analyze the live histogram and propose manual exposure values
+    SuggestExposure,
+    /// Histogram analysis finished (`None` when no frame could be analyzed)
+    ExposureSuggested(Option<crate::app::frame_processor::ExposureSuggestion>),
+    /// Apply the proposed exposure time and gain
+    ApplyExposureSuggestion,
     /// Set ISO sensitivity
     SetIsoSensitivity(i32),
     /// Set metering mode
@@ -1471,6 +2199,34 @@ pub enum Message {
     ExposureControlApplied,
     /// White balance toggled, with optional temperature value when switching to manual
     WhiteBalanceToggled(Option<i32>),
+    /// Exposure and focus locked from a long-press (or the lock failed)
+    ExposureFocusLocked(Result<crate::backends::camera::v4l2_controls::AeAfLock, String>),
+    /// AE-L / AWB-L: hold an automatic control at its current value, or
+    /// release the hold
+    ToggleAutoHold(crate::backends::camera::v4l2_controls::AutoControl),
+    /// An automatic control was held (or the hold failed)
+    AutoHeld(
+        crate::backends::camera::v4l2_controls::AutoControl,
+        Result<crate::backends::camera::v4l2_controls::AutoHold, String>,
+    ),
+    /// Toggle the exposure/focus on-screen display
+    ToggleExposureOsd,
+    /// Exposure and focus values read back from V4L2 for the OSD
+    OsdValuesRead(crate::app::exposure_osd::OsdValues),
+    /// Toggle the false color exposure map on the preview
+    ToggleFalseColor,
+    /// Select the color vision assist for the preview by index
+    SelectColorVisionAssist(usize),
+    /// Select the preview scaling filter (index into `PreviewScaling::ALL`)
+    SelectPreviewScaling(usize),
+    /// Toggle adaptive preview resolution
+    ToggleAdaptivePreview,
+    /// Toggle low-latency handling of HDMI capture cards
+    ToggleCaptureCardMode,
+    /// Select how 10-bit frames are interpreted (index into `HdrInput::ALL`)
+    SelectHdrInput(usize),
+    /// Measure glass-to-glass latency by flashing the window
+    MeasureLatency,
     /// Exposure control change failed
     ExposureControlFailed(String),
     /// Base exposure time captured (for non-advanced EV slider)
@@ -1485,6 +2241,19 @@ pub enum Message {
     ResetExposureSettings,
     /// Exposure mode selected via segmented button
     ExposureModeSelected(cosmic::widget::segmented_button::Entity),
+    /// Stored per-camera controls were compared with the device and the
+    /// drifted ones rewritten. `on_connect` marks the pass that runs right
+    /// after the camera's controls were queried.
+    CameraControlsReapplied {
+        drifted: Vec<(u32, i32)>,
+        on_connect: bool,
+    },
+    /// Periodic check that no other app changed the stored controls
+    CameraControlsWatchdogTick,
+    /// Toggle remembering exposure and color controls per camera
+    TogglePersistCameraControls,
+    /// Toggle metering auto exposure on a detected face
+    ToggleFaceExposurePriority,
 
     // ===== Color Controls =====
     /// Toggle color picker visibility
@@ -1503,6 +2272,14 @@ pub enum Message {
     ToggleAutoWhiteBalance,
     /// Set white balance temperature (Kelvin)
     SetWhiteBalanceTemperature(i32),
+    /// Arm the eyedropper: the next click on the preview picks a gray point
+    StartWhiteBalancePick,
+    /// Leave the eyedropper without picking
+    CancelWhiteBalancePick,
+    /// Gray point picked on the preview (normalized frame coordinates)
+    WhiteBalancePointPicked(f32, f32),
+    /// Gray point sampled from the frame (None if too dark or clipped)
+    WhiteBalanceSampled(Option<crate::app::frame_processor::GrayPoint>),
     /// Reset all color settings to defaults
     ResetColorSettings,
 
@@ -1513,6 +2290,10 @@ pub enum Message {
     SelectCamera(usize),
     /// New camera frame received from pipeline
     CameraFrame(Arc<CameraFrame>),
+    /// The camera subscription left a frame in `preview_mailbox`
+    CameraFrameReady,
+    /// Check again whether the renderer is ready for the waiting frame
+    PresentCameraFrame,
     /// Cameras initialized asynchronously during startup
     CamerasInitialized(
         Vec<crate::backends::camera::types::CameraDevice>,
@@ -1537,13 +2318,46 @@ pub enum Message {
     StartCameraTransition,
     /// Clear blur transition after delay
     ClearTransitionBlur,
+    /// Select the standby camera (0 = none, N = camera N-1)
+    SelectStandbyCamera(usize),
+    /// Select the second camera (0 = off, N = camera N-1)
+    SelectPipCamera(usize),
+    /// Select the picture-in-picture corner by dropdown index
+    SelectPipCorner(usize),
+    /// Select the dual camera layout by dropdown index
+    SelectDualCameraLayout(usize),
+    /// Select the second camera's capture resolution by dropdown index
+    SelectSecondaryResolution(usize),
+    /// New frame from the second camera
+    PipFrame(Arc<CameraFrame>),
+    /// Periodic check that the active camera is still delivering frames
+    FailoverWatchdogTick,
     /// Toggle mirror preview (horizontal flip)
     ToggleMirrorPreview,
     /// Toggle whether the same mirroring also applies to captured media
     /// (photos / videos / timelapse output).
     ToggleMirrorCaptures,
+    /// Toggle turning captures upright in their pixels instead of tagging them
+    ToggleBakeRotation,
+    /// Select the current camera's extra rotation (index into
+    /// `CameraOrientation::ROTATIONS`)
+    SelectCameraRotation(usize),
+    /// Toggle the current camera's left-right flip
+    ToggleCameraMirror,
+    /// Toggle the current camera's upside-down flip
+    ToggleCameraFlip,
     /// Toggle haptic feedback
     ToggleHapticFeedback,
+    /// Toggle silencing every app sound
+    ToggleSilentMode,
+    /// Toggle the shutter, countdown and recording sounds
+    ToggleCaptureSounds,
+    /// Set the capture sound volume in percent
+    SetCaptureSoundVolume(u32),
+    /// Toggle swipe / double-tap / long-press gestures on the preview
+    TogglePreviewGestures,
+    /// Toggle Tab focus traversal between the controls
+    ToggleKeyboardNavigation,
 
     // ===== Motor/PTZ Controls =====
     /// Toggle motor controls picker visibility
@@ -1572,8 +2386,8 @@ pub enum Message {
     SelectFramerate(String),
     /// Select codec from dropdown
     SelectCodec(String),
-    /// Select resolution in picker (grouped view)
-    PickerSelectResolution(u32),
+    /// Search text typed into the format picker
+    FormatPickerSearch(String),
     /// Select specific format in picker
     PickerSelectFormat(usize),
     /// Select bitrate preset
@@ -1600,15 +2414,32 @@ pub enum Message {
     BurstModeComplete(Result<String, String>),
     /// Poll burst mode processing progress (timer-based)
     PollBurstModeProgress,
+    /// Stop HDR+ processing from the processing overlay
+    CancelBurstMode,
+    /// Instant preview of a burst saved, with its gallery thumbnail
+    BurstPreviewSaved(
+        Result<
+            (
+                crate::storage::PendingPhoto,
+                crate::storage::GalleryThumbnailData,
+            ),
+            String,
+        >,
+    ),
     /// Reset burst mode state after completion/error
     ResetBurstModeState,
-    /// Periodic brightness evaluation tick (every 1 second in Auto mode)
-    /// Updates auto_detected_frame_count based on scene brightness
-    BrightnessEvaluationTick,
+    /// Light meter reading of a preview frame (every 1 second in Photo mode)
+    LightMeasured(Option<crate::app::frame_processor::tasks::light_meter::LightReading>),
+    /// Select what night mode does in the dark (index into NightMode::ALL)
+    SetNightMode(usize),
     /// Cycle photo aspect ratio (native -> 4:3 -> 16:9 -> 1:1 -> native)
     CyclePhotoAspectRatio,
     /// Flash duration complete, now capture the photo
     FlashComplete,
+    /// Turn the torch (flashlight) on or off
+    ToggleTorch,
+    /// The photo taken with the flash has its frame; put the flash out
+    FlashOff,
     /// Cycle photo timer setting (off -> 3s -> 5s -> 10s -> off)
     CyclePhotoTimer,
     /// Photo timer tick (countdown)
@@ -1623,10 +2454,25 @@ pub enum Message {
     ZoomOut,
     /// Reset zoom to 1.0
     ResetZoom,
+    /// Hardware volume key pressed (mapped by the volume-key setting)
+    VolumeKey(crate::backends::volume_keys::VolumeKey),
+    /// Select what the volume keys do by dropdown index
+    SelectVolumeKeyAction(usize),
     /// Toggle between Cover (fill) and Contain (fit) preview mode
     TogglePreviewFit,
     /// Show/hide all overlay chrome, leaving just the live preview
     ToggleUiChrome,
+    /// Open or close the focus loupe
+    ToggleLoupe,
+    /// Pointer moved over the preview: where, and the sensor UV under it
+    LoupeMoved(cosmic::iced::Point, (f32, f32)),
+    /// Preview clicked with the loupe open: pin it there, or let it follow
+    /// the pointer again
+    ToggleLoupePin(cosmic::iced::Point, (f32, f32)),
+    /// Enter or leave mirror mode
+    ToggleMirrorMode,
+    /// Turn the mirror-mode screen light on or off
+    ToggleMirrorLight,
     /// Animation tick for fit/fill transition
     FitAnimationTick,
     /// Animation tick for the zoom-reset transition
@@ -1635,6 +2481,8 @@ pub enum Message {
     /// volume-key `EVIOCGRAB` and dispatch gate so we only consume the
     /// hardware shutter buttons while the camera is in focus.
     WindowFocusChanged(bool),
+    /// The accelerometer reports the device is held differently
+    DeviceOrientationChanged(crate::backends::camera::types::SensorRotation),
     /// Window control: close
     WindowClose,
     /// Window control: minimize
@@ -1645,8 +2493,22 @@ pub enum Message {
     WindowDrag,
     /// Pinch-to-zoom: set absolute zoom level from touch gesture
     PinchZoom(f32),
-    /// Photo was saved successfully with the given file path
-    PhotoSaved(Result<String, String>),
+    /// Swipe, double-tap or long-press on the camera preview
+    PreviewGesture(crate::app::camera_preview::gestures::Gesture),
+    /// Photo was saved with the given file path, or failed
+    PhotoSaved(Result<String, AppError>),
+    /// Copy a processed photo to the clipboard instead of saving it
+    CopyPhotoToClipboard,
+    /// Photo processed for the clipboard (PNG or error)
+    PhotoCopied(Result<Vec<u8>, String>),
+    /// Hide the "Copied to clipboard" notice
+    ClipboardNoticeExpired,
+    /// Hide the error notice once it has been up long enough
+    ErrorNoticeExpired,
+    /// The user closed the error notice
+    DismissErrorNotice,
+    /// Toggle the shutter copying photos to the clipboard
+    TogglePhotoToClipboard,
     /// Clear capture animation after brief delay
     ClearCaptureAnimation,
     /// Toggle video recording
@@ -1658,18 +2520,32 @@ pub enum Message {
     /// uses it to drop stale events from an already-superseded session.
     RecordingStopped {
         session: u64,
-        result: Result<String, String>,
+        result: Result<String, AppError>,
     },
     /// Update recording duration (every second)
     UpdateRecordingDuration,
+    /// Add a numbered marker to the recording's event subtitles
+    AddRecordingMarker,
     /// Start recording after camera is released
     StartRecordingAfterDelay,
+    /// The user picked a screen to record in the portal dialog (or didn't)
+    ScreenCastReady(Result<Arc<crate::backends::screen_cast::ScreenCast>, AppError>),
     /// Capture button pressed down (for quick-record state machine)
     CaptureButtonPressed,
     /// Capture button released (for quick-record state machine)
     CaptureButtonReleased,
     /// Long-press threshold reached — start quick recording
     QuickRecordThreshold,
+    /// Time to take the next rapid burst shot
+    RapidBurstTick,
+    /// Rapid burst shots saved (in capture order) or error
+    RapidBurstSaved(Result<Vec<crate::pipelines::photo::rapid_burst::BurstShot>, String>),
+    /// Highlight a shot in the burst picker, by capture index
+    SelectBurstShot(usize),
+    /// Keep the highlighted burst shot and delete the others
+    KeepBurstShot,
+    /// Close the burst picker keeping every shot
+    KeepAllBurstShots,
 
     // ===== Virtual Camera =====
     /// Toggle virtual camera streaming (start/stop)
@@ -1684,6 +2560,14 @@ pub enum Message {
     OpenVirtualCameraFile,
     /// File selected for virtual camera streaming
     VirtualCameraFileSelected(Option<FileSource>),
+    /// Open folder picker to select a directory of images/videos to cycle through
+    OpenVirtualCameraFolder,
+    /// Folder selected as a file source playlist
+    VirtualCameraFolderSelected(Option<std::path::PathBuf>),
+    /// A file source finished playing (a video reached its end, or a playlist
+    /// image was shown for its duration). Carries the generation of the
+    /// playlist entry it belongs to, if any.
+    PreviewSourceEnded(Option<u64>),
     /// Clear the virtual camera file source (use camera instead)
     ClearVirtualCameraFile,
     /// File source preview frame loaded (for displaying before streaming starts)
@@ -1711,10 +2595,52 @@ pub enum Message {
     RefreshGalleryThumbnail,
     /// Gallery thumbnail loaded
     GalleryThumbnailLoaded(Option<crate::storage::GalleryThumbnailData>),
+    /// Show the share targets for a capture (`None` for the latest one)
+    ShowShareMenu(Option<std::path::PathBuf>),
+    /// Share the capture in the share menu
+    Share(crate::share::ShareTarget),
+    /// A portal share request finished
+    ShareDone(Result<(), String>),
+    /// Close the share chip
+    DismissShareMenu,
+    /// The "Share" prompt for this capture was shown long enough
+    SharePromptExpired(std::path::PathBuf),
+    /// Toggle offering to share each new capture
+    ToggleShareAfterCapture,
+    /// Open the share targets file in the default editor
+    EditShareTargets,
+
+    // ===== Uploads =====
+    /// Toggle uploading new captures
+    ToggleAutoUpload,
+    /// Select the upload provider by index
+    SelectUploadProvider(usize),
+    /// Edit a text field of the upload settings
+    UploadFormInput(UploadField, String),
+    /// Save the upload settings and store the secret
+    SaveUploadSettings,
+    /// The upload secret was stored (or not)
+    UploadSettingsSaved(Result<(), String>),
+    /// New captures found to upload
+    QueueUploads(Vec<std::path::PathBuf>),
+    /// A capture's upload finished, retries included
+    UploadFinished(std::path::PathBuf, Result<(), String>),
+    /// Try the failed uploads again
+    RetryFailedUploads,
 
     // ===== Filters =====
     /// Select a filter
     SelectFilter(FilterType),
+    /// Open a file chooser to import a `.cube` LUT
+    ImportLut,
+    /// LUT import finished (`None` if the chooser was cancelled)
+    LutImported(Option<Result<String, String>>),
+    /// Load an imported LUT and apply it as the custom LUT filter
+    SelectLut(String),
+    /// Imported LUT loaded from disk
+    LutLoaded(String, Result<Arc<crate::shaders::CubeLut>, String>),
+    /// Delete an imported LUT
+    RemoveLut(String),
 
     // ===== Settings & Device Selection =====
     /// Configuration updated
@@ -1738,18 +2664,110 @@ pub enum Message {
     SelectPhotoOutputFormat(usize),
     /// Toggle recording audio with video
     ToggleRecordAudio,
+    /// Toggle recording desktop audio as a second track
+    ToggleRecordSystemAudio,
+    /// Set the selected microphone's audio sync offset in milliseconds
+    SetAudioSyncOffset(i32),
+    /// Record a flash and a beep to measure the selected microphone's sync
+    StartAvSyncTest,
+    /// Flash the window and beep during the sync test recording
+    AvSyncFlash,
+    /// End the sync test flash
+    AvSyncFlashEnd,
+    /// Stop the sync test recording
+    AvSyncStop,
+    /// Sync test clip measured: correction in milliseconds, or an error
+    AvSyncMeasured(Result<i32, String>),
+    /// Add the measured correction to the microphone's sync offset
+    ApplyAvSyncOffset,
     /// Fired by the 100 ms subscription whenever a level source is active.
     AudioLevelTick,
     /// Select audio encoder (Opus, AAC)
     SelectAudioEncoder(usize),
+    /// Set microphone gain in dB (settings slider)
+    SetAudioGain(i32),
+    /// Set output sharpening strength in percent (settings slider)
+    SetOutputSharpening(u8),
+    /// Change the hand-tuned HDR+ parameters
+    SetBurstTuning(crate::config::BurstTuning),
+    /// Toggle noise suppression on the recorded audio
+    ToggleNoiseSuppression,
+    /// Toggle the event subtitle sidecar for recordings
+    ToggleEventSubtitles,
+    /// Toggle the per-frame timecode sidecar for recordings
+    ToggleRecordingTimecode,
+    /// Toggle the date and time burned into recordings
+    ToggleTimestampOverlay,
+    /// Toggle recording the screen with the camera inset
+    ToggleRecordScreen,
+    /// Select the low storage warning threshold by index
+    SelectLowSpaceWarning(usize),
+    /// Close the low storage chip
+    DismissStorageWarning,
+    /// Select output container for video recordings
+    SelectVideoContainer(usize),
+    /// Select video stabilization strength
+    SelectVideoStabilization(usize),
+    /// Select low-light video denoise strength
+    SelectVideoDenoise(usize),
+    /// Toggle denoising only when the picture is noisy
+    ToggleVideoDenoiseAuto,
+    /// Select the anamorphic desqueeze factor
+    SelectAnamorphicDesqueeze(usize),
+    /// Toggle desqueezing recordings as well as the preview
+    ToggleDesqueezeRecordings,
     /// Toggle saving raw burst frames as DNG (debugging feature)
     ToggleSaveBurstRaw,
+    /// Toggle reconstructing bursts at twice the resolution instead of HDR+
+    ToggleSuperResolution,
+    /// Toggle product photo mode
+    ToggleProductMode,
+    /// Toggle transparent background for product photos
+    ToggleProductTransparentBackground,
+    /// Toggle photo booth mode
+    TogglePhotoBooth,
+    /// Select photo booth collage layout by index
+    SelectPhotoBoothLayout(usize),
+    /// Select photo booth shot count by index
+    SelectPhotoBoothShots(usize),
+    /// Select photo booth countdown by index
+    SelectPhotoBoothCountdown(usize),
+    /// Toggle a different filter for each photo booth shot
+    TogglePhotoBoothFilterPerShot,
+    /// Toggle focus stacking for photos
+    ToggleFocusStack,
+    /// Select the number of focus positions in a sweep by index
+    SelectFocusStackSteps(usize),
+    /// The lens was sent to the next focus position of a sweep
+    FocusStackMoved(Result<(), String>),
+    /// The lens had time to settle; keep the frame for this position
+    FocusStackCapture,
+    /// Toggle rapid burst on long press
+    ToggleRapidBurst,
+    /// Select the rapid burst shot limit by index
+    SelectRapidBurstMaxShots(usize),
+    /// Toggle the raised-palm gesture shutter
+    ToggleGestureShutter,
     /// Select composition guide overlay by dropdown index
     SelectCompositionGuide(usize),
+    /// Select the one-handed layout shutter corner by dropdown index
+    SelectOneHandedLayout(usize),
     /// Reset all settings to defaults
     ResetAllSettings,
+    /// Pick which mode the control layout editor shows, by dropdown index
+    SelectLayoutEditorMode(usize),
+    /// Place a control in the edited mode, by index into `Control::placements`
+    SetControlPlacement(crate::app::control_layout::Control, usize),
+    /// Restore the stock control layout for the edited mode
+    ResetControlLayout,
     /// Toggle virtual camera feature enabled
     ToggleVirtualCameraEnabled,
+    /// Toggle the "look here" marker shown in Virtual mode
+    ToggleLookHereMarker,
+    /// Set where the active camera sits relative to the screen (dropdown index)
+    SelectCameraLocation(usize),
+    /// "Look here" marker animation frame
+    LookHereAnimationFrame,
 
     // ===== Timelapse =====
     /// Start/stop timelapse capture
@@ -1764,6 +2782,92 @@ pub enum Message {
     SetTimelapseInterval(usize),
     /// Timelapse video assembly completed (path or error)
     TimelapseAssemblyComplete(Result<String, String>),
+    /// Set how stills are picked from a video (dropdown index)
+    SetStillExtractionInterval(usize),
+    /// Open a file chooser to pick a video to extract stills from
+    ExtractStills,
+    /// Extract stills from this video (`None` if the file chooser was cancelled)
+    ExtractStillsFrom(Option<std::path::PathBuf>),
+    /// Redraw the still extraction progress
+    StillExtractionTick,
+    /// Stop the running still extraction
+    CancelStillExtraction,
+    /// Still extraction finished (saved paths or error)
+    StillExtractionFinished(Result<Vec<std::path::PathBuf>, String>),
+    /// Open a file chooser to pick a recorded video to stabilize
+    StabilizeVideo,
+    /// Write a stabilized copy of this video (`None` if the file chooser was cancelled)
+    StabilizeVideoFrom(Option<std::path::PathBuf>),
+    /// Redraw the stabilization progress
+    PostStabilizationTick,
+    /// Stop the running stabilization
+    CancelPostStabilization,
+    /// Stabilization finished (the copy's path or error)
+    PostStabilizationFinished(Result<std::path::PathBuf, String>),
+
+    // ===== Document Scanning =====
+    /// Capture the page in view (capture button, keyboard or auto-capture)
+    CaptureDocumentPage,
+    /// A scanned page finished encoding
+    DocumentPageCaptured(Result<DocumentPage, String>),
+    /// Toggle automatic capture of settled pages
+    ToggleDocumentAutoCapture,
+    /// Write the scanned pages to a PDF
+    SaveDocument,
+    /// Document PDF written (path or error)
+    DocumentSaved(Result<String, String>),
+    /// Drop the scanned pages
+    DiscardDocument,
+    /// Read the text on the scanned pages and copy it to the clipboard
+    CopyDocumentText,
+    /// Document text read (all pages, or error)
+    DocumentTextRecognized(Result<String, String>),
+    /// Toggle the text layer of document and whiteboard PDFs
+    ToggleSearchablePdf,
+
+    // ===== Whiteboard =====
+    /// Snapshot the board (capture button, keyboard or auto-snapshot)
+    CaptureWhiteboard,
+    /// A whiteboard snapshot finished processing
+    WhiteboardSnapshotSaved(Result<WhiteboardSnapshot, String>),
+    /// Lock the detected board corners as this camera's keystone, or unlock
+    ToggleWhiteboardLock,
+    /// Toggle automatic snapshots when the writing changes
+    ToggleWhiteboardAutoSnapshot,
+    /// Write the session's snapshots to a PDF
+    SaveWhiteboardPdf,
+    /// Whiteboard PDF written (path or error)
+    WhiteboardPdfSaved(Result<String, String>),
+    /// Drop the session's snapshots (the PNGs stay on disk)
+    DiscardWhiteboard,
+
+    // ===== Barcode Scanning =====
+    /// Pause or resume decoding in Scan mode
+    ToggleScanning,
+    /// Barcode detection finished for a sampled frame (Scan mode)
+    BarcodesDetected(Vec<crate::app::frame_processor::BarcodeDetection>),
+    /// Toggle the beep for each new code
+    ToggleScanBeep,
+    /// Write the scanned codes to a CSV
+    ExportScans,
+    /// Scan CSV written (path or error)
+    ScansExported(Result<String, String>),
+    /// Drop the scanned codes
+    ClearScans,
+
+    // ===== Animated Clips =====
+    /// Time to sample the next animated clip frame
+    AnimatedClipTick,
+    /// Animated clip export finished (path or error)
+    AnimatedClipSaved(Result<String, String>),
+    /// Select the record button output (video, GIF or WebP) by dropdown index
+    SelectAnimatedClipFormat(usize),
+    /// Select the animated clip length by dropdown index
+    SelectAnimatedClipSeconds(usize),
+    /// Select the animated clip frame rate by dropdown index
+    SelectAnimatedClipFps(usize),
+    /// Toggle looping of animated clips
+    ToggleAnimatedClipLoop,
 
     // ===== System & Recovery =====
     /// Camera backend recovery started
@@ -1772,6 +2876,8 @@ pub enum Message {
     CameraRecoverySucceeded,
     /// Camera backend recovery failed
     CameraRecoveryFailed(String),
+    /// The camera pipeline failed to start; it is retried shortly
+    CameraStartFailed(AppError),
     /// Audio backend recovery started
     AudioRecoveryStarted { attempt: u32, max_attempts: u32 },
     /// Audio backend recovery succeeded
@@ -1790,6 +2896,22 @@ pub enum Message {
     ToggleQrDetection,
     /// QR detection results updated
     QrDetectionsUpdated(Vec<QrDetection>),
+    /// Gesture shutter check finished for a sampled frame
+    GestureDetectionUpdated(Option<crate::app::frame_processor::GestureDetection>),
+    /// Face check for face-priority exposure finished for a sampled frame
+    FaceDetectionUpdated(Option<crate::app::frame_processor::FaceDetection>),
+    /// Page detection finished for a sampled frame (Document mode)
+    PageSignatureUpdated(Option<crate::app::frame_processor::PageSignature>),
+    /// Board detection finished for a sampled frame (Whiteboard mode)
+    BoardSignatureUpdated(Option<crate::app::frame_processor::BoardSignature>),
+    /// Scene classification finished for a sampled frame (Photo mode)
+    SceneClassified(Option<crate::app::frame_processor::Scene>),
+    /// Apply the setting the scene suggestion chip offers
+    ApplySceneSuggestion,
+    /// Hide the scene suggestion chip for the rest of the session
+    DismissSceneSuggestion,
+    /// Toggle scene suggestions on/off
+    ToggleSceneSuggestions,
     /// Open URL from QR code
     QrOpenUrl(String),
     /// Connect to WiFi network from QR code
@@ -1799,8 +2921,35 @@ pub enum Message {
         security: String,
         hidden: bool,
     },
+    /// Run the action of a QR code's content (from the overlay or the
+    /// history) and note it in the history
+    QrRunAction(String),
+    /// Search text typed on the QR history page
+    QrHistoryQuery(String),
+    /// Forget every scanned QR code
+    ClearQrHistory,
+    /// Turn the QR history on or off
+    ToggleQrHistory,
+    /// Progress of joining the WiFi network (SSID) from a QR code
+    WifiJoinProgress(String, crate::network_manager::WifiJoinStatus),
     /// Copy text from QR code to clipboard
     QrCopyText(String),
+    /// Open the "Show QR" composer
+    OpenQrShare,
+    /// Close the "Show QR" composer
+    CloseQrShare,
+    /// Choose what the shown QR code carries
+    SelectQrShareKind(crate::app::qr_share::QrShareKind),
+    /// Text typed into a "Show QR" field
+    QrShareInput(crate::app::qr_share::QrShareField, String),
+    /// Mark the shared WiFi network as hidden
+    SetQrShareHidden(bool),
+    /// Fill the "Show QR" WiFi fields from the active connection
+    QrShareUseCurrentWifi,
+    /// Active WiFi connection read for "Show QR"
+    QrShareWifiLoaded(Result<crate::network_manager::WifiCredentials, String>),
+    /// Show the QR code full screen, or go back to the composer
+    ToggleQrShareFullscreen,
 
     // ===== Privacy Cover Detection =====
     /// Privacy cover status changed (true = cover closed/camera blocked)
@@ -1821,6 +2970,26 @@ pub enum Message {
     /// GPU shader pipelines precompiled at startup
     GpuPipelinesWarmed(Result<(), String>),
 
+    // ===== Extension Unit Controls =====
+    /// Extension unit controls discovered for the current camera
+    ExtensionUnitsQueried(Vec<XuControlState>),
+    /// Edit the raw hex value of an extension unit control (by index)
+    XuEditValue(usize, String),
+    /// Write the edited raw hex value of an extension unit control
+    XuApplyValue(usize),
+    /// Write a preset value into an extension unit control's payload
+    XuApplyPreset(usize, u8),
+    /// Result of writing an extension unit control (new payload on success)
+    XuControlApplied(usize, Result<Vec<u8>, String>),
+
+    // ===== Automation =====
+    /// Enable or disable an automation rule (by index) and save the rules file
+    ToggleAutomationRule(usize),
+    /// Open the automation rules file in the default editor
+    EditAutomationRules,
+    /// Re-read the automation rules file
+    ReloadAutomationRules,
+
     // ===== Keyboard shortcuts =====
     /// Open the keyboard-shortcuts rebinding page (a context drawer).
     OpenKeyBindingsPage,
@@ -1839,6 +3008,10 @@ pub enum Message {
     /// Reset every action's binding to its default.
     ResetAllKeyBindings,
 
+    /// Command from the developer console
+    #[cfg(feature = "dev-console")]
+    DevConsole(crate::app::dev_console::ConsoleRequest),
+
     /// No-op message for async tasks that don't need a response
     Noop,
 }
@@ -1931,29 +3104,93 @@ impl AppModel {
         self.audio_levels_snapshot.as_ref()
     }
 
+    /// Microphone gain and noise-suppression settings for the audio branch.
+    pub fn audio_processing(&self) -> crate::pipelines::audio_level::AudioProcessing {
+        crate::pipelines::audio_level::AudioProcessing {
+            gain_db: self.config.audio_gain_db,
+            noise_suppression: self.config.audio_noise_suppression,
+        }
+    }
+
+    /// Low-light denoise settings for a recording or virtual camera stream.
+    pub fn video_denoise_settings(&self) -> crate::pipelines::video::DenoiseSettings {
+        crate::pipelines::video::DenoiseSettings {
+            strength: self.config.video_denoise,
+            auto: self.config.video_denoise_auto,
+        }
+    }
+
+    /// Whether the active camera is an HDMI capture card being run for the
+    /// lowest latency.
+    pub fn capture_card_active(&self) -> bool {
+        self.config.capture_card_mode
+            && self
+                .available_cameras
+                .get(self.current_camera_index)
+                .is_some_and(crate::backends::camera::capture_card::is_capture_card)
+    }
+
+    /// Config key for the selected microphone's sync offset: its node name,
+    /// or empty for the system default.
+    pub fn audio_sync_key(&self) -> String {
+        self.available_audio_devices
+            .get(self.current_audio_device_index)
+            .map(|d| d.node_name.clone())
+            .unwrap_or_default()
+    }
+
+    /// Publish the selected microphone's configured sync offset to the
+    /// shared handle a running recorder reads.
+    pub fn sync_audio_offset(&self) {
+        let offset_ms = self
+            .config
+            .audio_sync_offsets
+            .get(&self.audio_sync_key())
+            .copied()
+            .unwrap_or(0);
+        self.audio_sync_offset
+            .store(offset_ms, std::sync::atomic::Ordering::Relaxed);
+    }
+
     /// Start, stop, or restart the audio-level probe to match the current
     /// app state. Idempotent — safe to call from any handler whose work
-    /// might change `record_audio`, the selected device, the recording
-    /// state, or the settings-drawer visibility.
+    /// might change `record_audio`, `record_system_audio`, the selected
+    /// device, the audio processing settings, the recording state, or the
+    /// settings-drawer visibility.
     pub fn sync_audio_probe(&mut self) {
         let drawer_open =
             self.context_page == ContextPage::Settings && self.core.window.show_context;
-        let want = drawer_open && self.config.record_audio && !self.recording.is_recording();
+        let desired_sources = crate::pipelines::audio_probe::ProbeSources {
+            microphone: self.config.record_audio,
+            system: self.config.record_system_audio,
+        };
+        let want = drawer_open
+            && (desired_sources.microphone || desired_sources.system)
+            && !self.recording.is_recording();
 
         let selected_device = self
             .available_audio_devices
             .get(self.current_audio_device_index);
         let desired_device: Option<String> = selected_device.map(|d| d.node_name.clone());
         let desired_rate_hz: u32 = selected_device.map(|d| d.sample_rate).unwrap_or(0);
+        let desired_processing = self.audio_processing();
 
-        // Tear down if running and no longer wanted, or if the device changed.
-        if let Some(probe) = self.audio_probe.take() {
+        // Tear down if running and no longer wanted, or if the sources,
+        // device or noise-suppression stage changed. Gain is adjusted in
+        // place.
+        if let Some(mut probe) = self.audio_probe.take() {
             let device_changed = probe.device().map(str::to_string) != desired_device;
-            if !want || device_changed {
+            let suppression_changed =
+                probe.processing().noise_suppression != desired_processing.noise_suppression;
+            let sources_changed = probe.sources() != desired_sources;
+            if !want || sources_changed || device_changed || suppression_changed {
                 probe.stop();
                 self.probe_audio_levels = None;
             } else {
                 // Still valid — put it back.
+                if probe.processing().gain_db != desired_processing.gain_db {
+                    probe.set_gain(desired_processing.gain_db);
+                }
                 self.audio_probe = Some(probe);
             }
         }
@@ -1963,6 +3200,8 @@ impl AppModel {
             match crate::pipelines::audio_probe::AudioLevelProbe::start(
                 desired_device.as_deref(),
                 desired_rate_hz,
+                desired_processing,
+                desired_sources,
             ) {
                 Ok(probe) => {
                     self.probe_audio_levels = Some(probe.levels());
@@ -1976,3 +3215,47 @@ impl AppModel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn burst_cancel_needs_processing() {
+        let mut burst = BurstModeState::default();
+        assert!(!burst.cancel_processing());
+        assert!(!burst.is_cancelling());
+
+        let (_, _result_tx, cancel) = burst.start_processing_task();
+        assert!(!burst.is_cancelling());
+        assert!(burst.cancel_processing());
+        assert!(cancel.is_cancelled());
+        assert!(burst.is_cancelling());
+    }
+
+    #[test]
+    fn burst_cancel_clears_with_the_result() {
+        let mut burst = BurstModeState::default();
+        let (_, result_tx, _) = burst.start_processing_task();
+        burst.cancel_processing();
+
+        result_tx
+            .send(Err(
+                crate::pipelines::photo::burst_mode::CANCELLED.to_string()
+            ))
+            .unwrap();
+        assert!(burst.try_get_result().is_some_and(|result| result.is_err()));
+        assert!(!burst.is_cancelling());
+        assert!(!burst.cancel_processing());
+    }
+
+    #[test]
+    fn burst_reset_drops_the_token() {
+        let mut burst = BurstModeState::default();
+        burst.start_processing_task();
+        burst.cancel_processing();
+        burst.reset();
+        assert!(!burst.is_cancelling());
+        assert!(!burst.cancel_processing());
+    }
+}
@@ -82,6 +82,12 @@ impl AppModel {
             Message::ResetExposureCompensation => self.handle_reset_exposure_compensation(),
             Message::SetExposureTime(value) => self.handle_set_exposure_time(value),
             Message::SetGain(value) => self.handle_set_gain(value),
+            Message::SuggestExposure => self.handle_suggest_exposure(),
+            Message::ExposureSuggested(suggestion) => {
+                self.exposure_suggestion = suggestion;
+                Task::none()
+            }
+            Message::ApplyExposureSuggestion => self.handle_apply_exposure_suggestion(),
             Message::SetIsoSensitivity(value) => self.handle_set_iso_sensitivity(value),
             Message::SetMeteringMode(mode) => self.handle_set_metering_mode(mode),
             Message::ToggleAutoExposurePriority => self.handle_toggle_auto_exposure_priority(),
@@ -94,6 +100,10 @@ impl AppModel {
                     if let Some(ref mut settings) = self.color_settings {
                         settings.white_balance_temperature = Some(temp_value);
                     }
+                    self.remember_control(
+                        crate::backends::camera::v4l2_controls::V4L2_CID_WHITE_BALANCE_TEMPERATURE,
+                        temp_value,
+                    );
                     info!(
                         temperature = temp_value,
                         "White balance switched to manual, preserved auto temperature"
@@ -101,6 +111,23 @@ impl AppModel {
                 }
                 Task::none()
             }
+            Message::ExposureFocusLocked(result) => self.handle_exposure_focus_locked(result),
+            Message::ToggleAutoHold(control) => self.handle_toggle_auto_hold(control),
+            Message::AutoHeld(control, result) => self.handle_auto_held(control, result),
+            Message::ToggleExposureOsd => self.handle_toggle_exposure_osd(),
+            Message::ToggleFalseColor => self.handle_toggle_false_color(),
+            Message::SelectColorVisionAssist(index) => {
+                self.handle_select_color_vision_assist(index)
+            }
+            Message::SelectPreviewScaling(index) => self.handle_select_preview_scaling(index),
+            Message::ToggleAdaptivePreview => self.handle_toggle_adaptive_preview(),
+            Message::ToggleCaptureCardMode => self.handle_toggle_capture_card_mode(),
+            Message::SelectHdrInput(index) => self.handle_select_hdr_input(index),
+            Message::MeasureLatency => self.handle_measure_latency(),
+            Message::OsdValuesRead(values) => {
+                self.osd_values = Some(values);
+                Task::none()
+            }
             Message::ExposureControlFailed(error) => {
                 warn!(error = %error, "Exposure control failed");
                 Task::none()
@@ -117,6 +144,13 @@ impl AppModel {
             Message::ToggleFocusAuto => self.handle_toggle_focus_auto(),
             Message::ResetExposureSettings => self.handle_reset_exposure_settings(),
             Message::ExposureModeSelected(entity) => self.handle_exposure_mode_selected(entity),
+            Message::CameraControlsReapplied {
+                drifted,
+                on_connect,
+            } => self.handle_camera_controls_reapplied(drifted, on_connect),
+            Message::CameraControlsWatchdogTick => self.reapply_camera_controls_task(false),
+            Message::TogglePersistCameraControls => self.handle_toggle_persist_camera_controls(),
+            Message::ToggleFaceExposurePriority => self.handle_toggle_face_exposure_priority(),
 
             // ===== Color Controls =====
             Message::ToggleColorPicker => self.handle_toggle_color_picker(),
@@ -130,11 +164,17 @@ impl AppModel {
                 self.handle_set_white_balance_temperature(value)
             }
             Message::ResetColorSettings => self.handle_reset_color_settings(),
+            Message::StartWhiteBalancePick => self.handle_start_white_balance_pick(),
+            Message::CancelWhiteBalancePick => self.handle_cancel_white_balance_pick(),
+            Message::WhiteBalancePointPicked(x, y) => self.handle_white_balance_point_picked(x, y),
+            Message::WhiteBalanceSampled(gray) => self.handle_white_balance_sampled(gray),
 
             // ===== Camera Control =====
             Message::SwitchCamera => self.handle_switch_camera(),
             Message::SelectCamera(index) => self.handle_select_camera(index),
             Message::CameraFrame(frame) => self.handle_camera_frame(frame),
+            Message::CameraFrameReady => self.handle_camera_frame_ready(),
+            Message::PresentCameraFrame => self.handle_present_camera_frame(),
             Message::CamerasInitialized(cameras, index, formats) => {
                 self.handle_cameras_initialized(cameras, index, formats)
             }
@@ -147,10 +187,31 @@ impl AppModel {
             Message::AudioListChanged(devices) => self.handle_audio_list_changed(devices),
             Message::StartCameraTransition => self.handle_start_camera_transition(),
             Message::ClearTransitionBlur => self.handle_clear_transition_blur(),
+            Message::SelectStandbyCamera(index) => self.handle_select_standby_camera(index),
+            Message::SelectPipCamera(index) => self.handle_select_pip_camera(index),
+            Message::SelectPipCorner(index) => self.handle_select_pip_corner(index),
+            Message::SelectDualCameraLayout(index) => self.handle_select_dual_camera_layout(index),
+            Message::SelectSecondaryResolution(index) => {
+                self.handle_select_secondary_resolution(index)
+            }
+            Message::PipFrame(frame) => self.handle_pip_frame(frame),
+            Message::FailoverWatchdogTick => self.handle_failover_watchdog_tick(),
             Message::ToggleMirrorPreview => self.handle_toggle_mirror_preview(),
             Message::ToggleMirrorCaptures => self.handle_toggle_mirror_captures(),
+            Message::ToggleBakeRotation => self.handle_toggle_bake_rotation(),
+            Message::SelectCameraRotation(index) => self.handle_select_camera_rotation(index),
+            Message::ToggleCameraMirror => self.handle_toggle_camera_mirror(),
+            Message::ToggleCameraFlip => self.handle_toggle_camera_flip(),
             Message::ToggleHapticFeedback => self.handle_toggle_haptic_feedback(),
+            Message::ToggleSilentMode => self.handle_toggle_silent_mode(),
+            Message::ToggleCaptureSounds => self.handle_toggle_capture_sounds(),
+            Message::SetCaptureSoundVolume(volume) => self.handle_set_capture_sound_volume(volume),
+            Message::TogglePreviewGestures => self.handle_toggle_preview_gestures(),
+            Message::ToggleKeyboardNavigation => self.handle_toggle_keyboard_navigation(),
             Message::ToggleVirtualCameraEnabled => self.handle_toggle_virtual_camera_enabled(),
+            Message::ToggleLookHereMarker => self.handle_toggle_look_here_marker(),
+            Message::SelectCameraLocation(index) => self.handle_select_camera_location(index),
+            Message::LookHereAnimationFrame => Task::none(),
 
             // ===== Format Selection =====
             Message::SetMode(mode) => self.handle_set_mode(mode),
@@ -159,7 +220,7 @@ impl AppModel {
             Message::SelectResolution(resolution) => self.handle_select_resolution(resolution),
             Message::SelectFramerate(framerate) => self.handle_select_framerate(framerate),
             Message::SelectCodec(codec) => self.handle_select_codec(codec),
-            Message::PickerSelectResolution(width) => self.handle_picker_select_resolution(width),
+            Message::FormatPickerSearch(query) => self.handle_format_picker_search(query),
             Message::PickerSelectFormat(index) => self.handle_picker_select_format(index),
             Message::SelectBitratePreset(index) => self.handle_select_bitrate_preset(index),
 
@@ -176,23 +237,29 @@ impl AppModel {
             }
             Message::BurstModeComplete(result) => self.handle_burst_mode_complete(result),
             Message::PollBurstModeProgress => self.handle_poll_burst_mode_progress(),
+            Message::CancelBurstMode => self.handle_cancel_burst_mode(),
+            Message::BurstPreviewSaved(result) => self.handle_burst_preview_saved(result),
             Message::ResetBurstModeState => {
                 self.burst_mode.reset();
                 // Ensure flash is turned off when burst mode resets (safety measure)
-                self.turn_off_flash_hardware();
-                self.flash.active = false;
+                self.end_flash();
                 debug!("Burst mode state reset");
                 Task::none()
             }
-            Message::BrightnessEvaluationTick => self.handle_brightness_evaluation_tick(),
+            Message::LightMeasured(reading) => self.handle_light_measured(reading),
+            Message::SetNightMode(index) => self.handle_set_night_mode(index),
             Message::CyclePhotoAspectRatio => self.handle_cycle_photo_aspect_ratio(),
             Message::FlashComplete => self.handle_flash_complete(),
+            Message::FlashOff => self.handle_flash_off(),
+            Message::ToggleTorch => self.handle_toggle_torch(),
             Message::CyclePhotoTimer => self.handle_cycle_photo_timer(),
             Message::PhotoTimerTick => self.handle_photo_timer_tick(),
             Message::PhotoTimerAnimationFrame => Task::none(),
             Message::AbortPhotoTimer => self.handle_abort_photo_timer(),
             Message::ZoomIn => self.handle_zoom_in(),
             Message::ZoomOut => self.handle_zoom_out(),
+            Message::VolumeKey(key) => self.handle_volume_key(key),
+            Message::SelectVolumeKeyAction(index) => self.handle_select_volume_key_action(index),
             Message::ResetZoom => self.handle_reset_zoom(),
             Message::TogglePreviewFit => {
                 // Snapshot the animated values before toggling so a
@@ -205,6 +272,11 @@ impl AppModel {
                 self.start_fit_animation(from)
             }
             Message::ToggleUiChrome => self.handle_toggle_ui_chrome(),
+            Message::ToggleLoupe => self.handle_toggle_loupe(),
+            Message::LoupeMoved(anchor, center) => self.handle_loupe_moved(anchor, center),
+            Message::ToggleLoupePin(anchor, center) => self.handle_toggle_loupe_pin(anchor, center),
+            Message::ToggleMirrorMode => self.handle_toggle_mirror_mode(),
+            Message::ToggleMirrorLight => self.handle_toggle_mirror_light(),
             Message::FitAnimationTick => self.tick_animation_until(
                 crate::app::view::FIT_ANIMATION_DURATION,
                 Message::FitAnimationTick,
@@ -222,12 +294,24 @@ impl AppModel {
                 crate::backends::volume_keys::set_focused(focused);
                 Task::none()
             }
+            Message::DeviceOrientationChanged(rotation) => {
+                debug!(%rotation, "Device orientation changed");
+                self.device_orientation = rotation;
+                Task::none()
+            }
             Message::WindowClose => self.handle_window_close(),
             Message::WindowMinimize => self.core.minimize(None),
             Message::WindowToggleMaximize => self.core.toggle_maximize(None),
             Message::WindowDrag => self.core.drag(None),
             Message::PinchZoom(level) => self.handle_pinch_zoom(level),
+            Message::PreviewGesture(gesture) => self.handle_preview_gesture(gesture),
             Message::PhotoSaved(result) => self.handle_photo_saved(result),
+            Message::CopyPhotoToClipboard => self.handle_copy_photo_to_clipboard(),
+            Message::PhotoCopied(result) => self.handle_photo_copied(result),
+            Message::ClipboardNoticeExpired => self.handle_clipboard_notice_expired(),
+            Message::ErrorNoticeExpired => self.handle_error_notice_expired(),
+            Message::DismissErrorNotice => self.handle_dismiss_error_notice(),
+            Message::TogglePhotoToClipboard => self.handle_toggle_photo_to_clipboard(),
             Message::ClearCaptureAnimation => self.handle_clear_capture_animation(),
             Message::ToggleRecording => self.handle_toggle_recording(),
             Message::RecordingStarted(path) => self.handle_recording_started(path),
@@ -235,10 +319,17 @@ impl AppModel {
                 self.handle_recording_stopped(session, result)
             }
             Message::UpdateRecordingDuration => self.handle_update_recording_duration(),
+            Message::AddRecordingMarker => self.handle_add_recording_marker(),
             Message::StartRecordingAfterDelay => self.handle_start_recording_after_delay(),
+            Message::ScreenCastReady(result) => self.handle_screen_cast_ready(result),
             Message::CaptureButtonPressed => self.handle_capture_button_pressed(),
             Message::CaptureButtonReleased => self.handle_capture_button_released(),
             Message::QuickRecordThreshold => self.handle_quick_record_threshold(),
+            Message::RapidBurstTick => self.handle_rapid_burst_tick(),
+            Message::RapidBurstSaved(result) => self.handle_rapid_burst_saved(result),
+            Message::SelectBurstShot(index) => self.handle_select_burst_shot(index),
+            Message::KeepBurstShot => self.handle_keep_burst_shot(),
+            Message::KeepAllBurstShots => self.handle_keep_all_burst_shots(),
 
             // ===== Timelapse =====
             Message::NextMode => self.handle_cycle_mode(true),
@@ -246,10 +337,67 @@ impl AppModel {
             Message::ToggleTimelapse => self.handle_toggle_timelapse(),
             Message::TimelapseTick => self.handle_timelapse_tick(),
             Message::SetTimelapseInterval(i) => self.handle_set_timelapse_interval(i),
+            Message::SetStillExtractionInterval(i) => self.handle_set_still_extraction_interval(i),
+            Message::ExtractStills => self.handle_extract_stills(),
+            Message::ExtractStillsFrom(path) => self.handle_extract_stills_from(path),
+            Message::StillExtractionTick => self.handle_still_extraction_tick(),
+            Message::CancelStillExtraction => self.handle_cancel_still_extraction(),
+            Message::StillExtractionFinished(result) => {
+                self.handle_still_extraction_finished(result)
+            }
+            Message::StabilizeVideo => self.handle_stabilize_video(),
+            Message::StabilizeVideoFrom(path) => self.handle_stabilize_video_from(path),
+            Message::PostStabilizationTick => self.handle_post_stabilization_tick(),
+            Message::CancelPostStabilization => self.handle_cancel_post_stabilization(),
+            Message::PostStabilizationFinished(result) => {
+                self.handle_post_stabilization_finished(result)
+            }
             Message::TimelapseAssemblyComplete(result) => {
                 self.handle_timelapse_assembly_complete(result)
             }
 
+            // ===== Document Scanning =====
+            Message::CaptureDocumentPage => self.handle_capture_document_page(),
+            Message::DocumentPageCaptured(result) => self.handle_document_page_captured(result),
+            Message::ToggleDocumentAutoCapture => self.handle_toggle_document_auto_capture(),
+            Message::CopyDocumentText => self.handle_copy_document_text(),
+            Message::DocumentTextRecognized(result) => self.handle_document_text_recognized(result),
+            Message::ToggleSearchablePdf => self.handle_toggle_searchable_pdf(),
+            Message::SaveDocument => self.handle_save_document(),
+            Message::DocumentSaved(result) => self.handle_document_saved(result),
+            Message::DiscardDocument => self.handle_discard_document(),
+
+            // ===== Whiteboard =====
+            Message::CaptureWhiteboard => self.handle_capture_whiteboard(),
+            Message::WhiteboardSnapshotSaved(result) => {
+                self.handle_whiteboard_snapshot_saved(result)
+            }
+            Message::ToggleWhiteboardLock => self.handle_toggle_whiteboard_lock(),
+            Message::ToggleWhiteboardAutoSnapshot => self.handle_toggle_whiteboard_auto_snapshot(),
+            Message::SaveWhiteboardPdf => self.handle_save_whiteboard_pdf(),
+            Message::WhiteboardPdfSaved(result) => self.handle_whiteboard_pdf_saved(result),
+            Message::DiscardWhiteboard => self.handle_discard_whiteboard(),
+
+            // ===== Barcode Scanning =====
+            Message::ToggleScanning => self.handle_toggle_scanning(),
+            Message::BarcodesDetected(detections) => self.handle_barcodes_detected(detections),
+            Message::ToggleScanBeep => self.handle_toggle_scan_beep(),
+            Message::ExportScans => self.handle_export_scans(),
+            Message::ScansExported(result) => self.handle_scans_exported(result),
+            Message::ClearScans => self.handle_clear_scans(),
+
+            // ===== Animated Clips =====
+            Message::AnimatedClipTick => self.handle_animated_clip_tick(),
+            Message::AnimatedClipSaved(result) => self.handle_animated_clip_saved(result),
+            Message::SelectAnimatedClipFormat(index) => {
+                self.handle_select_animated_clip_format(index)
+            }
+            Message::SelectAnimatedClipSeconds(index) => {
+                self.handle_select_animated_clip_seconds(index)
+            }
+            Message::SelectAnimatedClipFps(index) => self.handle_select_animated_clip_fps(index),
+            Message::ToggleAnimatedClipLoop => self.handle_toggle_animated_clip_loop(),
+
             // ===== Virtual Camera =====
             Message::ToggleVirtualCamera => self.handle_toggle_virtual_camera(),
             Message::VirtualCameraStarted => self.handle_virtual_camera_started(),
@@ -259,6 +407,11 @@ impl AppModel {
             Message::VirtualCameraFileSelected(file_source) => {
                 self.handle_virtual_camera_file_selected(file_source)
             }
+            Message::OpenVirtualCameraFolder => self.handle_open_virtual_camera_folder(),
+            Message::VirtualCameraFolderSelected(dir) => {
+                self.handle_virtual_camera_folder_selected(dir)
+            }
+            Message::PreviewSourceEnded(generation) => self.handle_preview_source_ended(generation),
             Message::ClearVirtualCameraFile => self.handle_clear_virtual_camera_file(),
             Message::FileSourcePreviewLoaded(frame, duration) => {
                 self.handle_file_source_preview_loaded(frame, duration)
@@ -279,9 +432,29 @@ impl AppModel {
             Message::OpenGallery => self.handle_open_gallery(),
             Message::RefreshGalleryThumbnail => self.handle_refresh_gallery_thumbnail(),
             Message::GalleryThumbnailLoaded(data) => self.handle_gallery_thumbnail_loaded(data),
+            Message::ShowShareMenu(path) => self.handle_show_share_menu(path),
+            Message::Share(target) => self.handle_share(target),
+            Message::ShareDone(result) => self.handle_share_done(result),
+            Message::DismissShareMenu => self.handle_dismiss_share_menu(),
+            Message::SharePromptExpired(path) => self.handle_share_prompt_expired(path),
+            Message::ToggleShareAfterCapture => self.handle_toggle_share_after_capture(),
+            Message::EditShareTargets => self.handle_edit_share_targets(),
+            Message::ToggleAutoUpload => self.handle_toggle_auto_upload(),
+            Message::SelectUploadProvider(index) => self.handle_select_upload_provider(index),
+            Message::UploadFormInput(field, text) => self.handle_upload_form_input(field, text),
+            Message::SaveUploadSettings => self.handle_save_upload_settings(),
+            Message::UploadSettingsSaved(result) => self.handle_upload_settings_saved(result),
+            Message::QueueUploads(paths) => self.handle_queue_uploads(paths),
+            Message::UploadFinished(path, result) => self.handle_upload_finished(path, result),
+            Message::RetryFailedUploads => self.handle_retry_failed_uploads(),
 
             // ===== Filters =====
             Message::SelectFilter(filter) => self.handle_select_filter(filter),
+            Message::ImportLut => self.handle_import_lut(),
+            Message::LutImported(result) => self.handle_lut_imported(result),
+            Message::SelectLut(name) => self.handle_select_lut(name),
+            Message::LutLoaded(name, result) => self.handle_lut_loaded(name, result),
+            Message::RemoveLut(name) => self.handle_remove_lut(name),
 
             // ===== Settings =====
             Message::UpdateConfig(config) => self.handle_update_config(config),
@@ -298,10 +471,67 @@ impl AppModel {
                 self.handle_select_photo_output_format(index)
             }
             Message::ToggleRecordAudio => self.handle_toggle_record_audio(),
+            Message::ToggleRecordSystemAudio => self.handle_toggle_record_system_audio(),
+            Message::SetAudioSyncOffset(offset_ms) => self.handle_set_audio_sync_offset(offset_ms),
+            Message::StartAvSyncTest => self.handle_start_av_sync_test(),
+            Message::AvSyncFlash => self.handle_av_sync_flash(),
+            Message::AvSyncFlashEnd => self.handle_av_sync_flash_end(),
+            Message::AvSyncStop => self.handle_av_sync_stop(),
+            Message::AvSyncMeasured(result) => self.handle_av_sync_measured(result),
+            Message::ApplyAvSyncOffset => self.handle_apply_av_sync_offset(),
+            Message::SetAudioGain(gain_db) => self.handle_set_audio_gain(gain_db),
+            Message::SetOutputSharpening(amount) => self.handle_set_output_sharpening(amount),
+            Message::SetBurstTuning(tuning) => self.handle_set_burst_tuning(tuning),
+            Message::ToggleNoiseSuppression => self.handle_toggle_noise_suppression(),
+            Message::ToggleEventSubtitles => self.handle_toggle_event_subtitles(),
+            Message::ToggleRecordingTimecode => self.handle_toggle_recording_timecode(),
+            Message::ToggleTimestampOverlay => self.handle_toggle_timestamp_overlay(),
+            Message::ToggleRecordScreen => self.handle_toggle_record_screen(),
+            Message::SelectLowSpaceWarning(index) => self.handle_select_low_space_warning(index),
+            Message::DismissStorageWarning => self.handle_dismiss_storage_warning(),
             Message::SelectAudioEncoder(index) => self.handle_select_audio_encoder(index),
+            Message::SelectVideoContainer(index) => self.handle_select_video_container(index),
+            Message::SelectVideoStabilization(index) => {
+                self.handle_select_video_stabilization(index)
+            }
+            Message::SelectVideoDenoise(index) => self.handle_select_video_denoise(index),
+            Message::SelectAnamorphicDesqueeze(index) => {
+                self.handle_select_anamorphic_desqueeze(index)
+            }
+            Message::ToggleDesqueezeRecordings => self.handle_toggle_desqueeze_recordings(),
+            Message::ToggleVideoDenoiseAuto => self.handle_toggle_video_denoise_auto(),
             Message::ToggleSaveBurstRaw => self.handle_toggle_save_burst_raw(),
+            Message::ToggleSuperResolution => self.handle_toggle_super_resolution(),
+            Message::ToggleProductMode => self.handle_toggle_product_mode(),
+            Message::ToggleProductTransparentBackground => {
+                self.handle_toggle_product_transparent_background()
+            }
+            Message::TogglePhotoBooth => self.handle_toggle_photo_booth(),
+            Message::SelectPhotoBoothLayout(index) => self.handle_select_photo_booth_layout(index),
+            Message::SelectPhotoBoothShots(index) => self.handle_select_photo_booth_shots(index),
+            Message::SelectPhotoBoothCountdown(index) => {
+                self.handle_select_photo_booth_countdown(index)
+            }
+            Message::TogglePhotoBoothFilterPerShot => {
+                self.handle_toggle_photo_booth_filter_per_shot()
+            }
+            Message::ToggleFocusStack => self.handle_toggle_focus_stack(),
+            Message::SelectFocusStackSteps(index) => self.handle_select_focus_stack_steps(index),
+            Message::FocusStackMoved(result) => self.handle_focus_stack_moved(result),
+            Message::FocusStackCapture => self.handle_focus_stack_capture(),
+            Message::ToggleRapidBurst => self.handle_toggle_rapid_burst(),
+            Message::SelectRapidBurstMaxShots(index) => {
+                self.handle_select_rapid_burst_max_shots(index)
+            }
+            Message::ToggleGestureShutter => self.handle_toggle_gesture_shutter(),
             Message::SelectCompositionGuide(index) => self.handle_select_composition_guide(index),
+            Message::SelectOneHandedLayout(index) => self.handle_select_one_handed_layout(index),
             Message::ResetAllSettings => self.handle_reset_all_settings(),
+            Message::SelectLayoutEditorMode(index) => self.handle_select_layout_editor_mode(index),
+            Message::SetControlPlacement(control, index) => {
+                self.handle_set_control_placement(control, index)
+            }
+            Message::ResetControlLayout => self.handle_reset_control_layout(),
 
             // ===== System & Recovery =====
             Message::CameraRecoveryStarted {
@@ -310,6 +540,8 @@ impl AppModel {
             } => self.handle_camera_recovery_started(attempt, max_attempts),
             Message::CameraRecoverySucceeded => self.handle_camera_recovery_succeeded(),
             Message::CameraRecoveryFailed(error) => self.handle_camera_recovery_failed(error),
+            // Retries keep the notice up for as long as the camera fails
+            Message::CameraStartFailed(error) => self.show_error_notice(error),
             Message::AudioRecoveryStarted {
                 attempt,
                 max_attempts,
@@ -325,6 +557,20 @@ impl AppModel {
             Message::QrDetectionsUpdated(detections) => {
                 self.handle_qr_detections_updated(detections)
             }
+            Message::GestureDetectionUpdated(detection) => {
+                self.handle_gesture_detection_updated(detection)
+            }
+            Message::FaceDetectionUpdated(face) => self.handle_face_detection_updated(face),
+            Message::PageSignatureUpdated(signature) => {
+                self.handle_page_signature_updated(signature)
+            }
+            Message::BoardSignatureUpdated(signature) => {
+                self.handle_board_signature_updated(signature)
+            }
+            Message::SceneClassified(scene) => self.handle_scene_classified(scene),
+            Message::ApplySceneSuggestion => self.handle_apply_scene_suggestion(),
+            Message::DismissSceneSuggestion => self.handle_dismiss_scene_suggestion(),
+            Message::ToggleSceneSuggestions => self.handle_toggle_scene_suggestions(),
             Message::QrOpenUrl(url) => self.handle_qr_open_url(url),
             Message::QrConnectWifi {
                 ssid,
@@ -332,7 +578,20 @@ impl AppModel {
                 security,
                 hidden,
             } => self.handle_qr_connect_wifi(ssid, password, security, hidden),
+            Message::QrRunAction(content) => self.handle_qr_run_action(content),
+            Message::QrHistoryQuery(query) => self.handle_qr_history_query(query),
+            Message::ClearQrHistory => self.handle_clear_qr_history(),
+            Message::ToggleQrHistory => self.handle_toggle_qr_history(),
+            Message::WifiJoinProgress(ssid, status) => self.handle_wifi_join_progress(ssid, status),
             Message::QrCopyText(text) => self.handle_qr_copy_text(text),
+            Message::OpenQrShare => self.handle_open_qr_share(),
+            Message::CloseQrShare => self.handle_close_qr_share(),
+            Message::SelectQrShareKind(kind) => self.handle_select_qr_share_kind(kind),
+            Message::QrShareInput(field, text) => self.handle_qr_share_input(field, text),
+            Message::SetQrShareHidden(hidden) => self.handle_set_qr_share_hidden(hidden),
+            Message::QrShareUseCurrentWifi => self.handle_qr_share_use_current_wifi(),
+            Message::QrShareWifiLoaded(result) => self.handle_qr_share_wifi_loaded(result),
+            Message::ToggleQrShareFullscreen => self.handle_toggle_qr_share_fullscreen(),
 
             // ===== Privacy Cover Detection =====
             Message::PrivacyCoverStatusChanged(is_closed) => {
@@ -359,6 +618,22 @@ impl AppModel {
                 Task::none()
             }
 
+            // ===== Extension Unit Controls =====
+            Message::ExtensionUnitsQueried(controls) => {
+                self.handle_extension_units_queried(controls)
+            }
+            Message::XuEditValue(index, text) => self.handle_xu_edit_value(index, text),
+            Message::XuApplyValue(index) => self.handle_xu_apply_value(index),
+            Message::XuApplyPreset(index, value) => self.handle_xu_apply_preset(index, value),
+            Message::XuControlApplied(index, result) => {
+                self.handle_xu_control_applied(index, result)
+            }
+
+            // ===== Automation =====
+            Message::ToggleAutomationRule(index) => self.handle_toggle_automation_rule(index),
+            Message::EditAutomationRules => self.handle_edit_automation_rules(),
+            Message::ReloadAutomationRules => self.handle_reload_automation_rules(),
+
             // ===== Keyboard shortcuts =====
             Message::OpenKeyBindingsPage => {
                 self.context_page = ContextPage::KeyBindings;
@@ -420,6 +695,9 @@ impl AppModel {
                 Task::none()
             }
 
+            #[cfg(feature = "dev-console")]
+            Message::DevConsole(request) => self.handle_dev_console(request),
+
             Message::Noop => Task::none(),
         }
     }
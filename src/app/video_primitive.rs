@@ -9,6 +9,7 @@
 
 use crate::app::state::FilterType;
 use crate::backends::camera::types::{FrameData, PixelFormat, YuvPlanes};
+use crate::config::{ColorVisionAssist, PreviewScaling};
 use cosmic::iced::Rectangle;
 
 /// Video ID for the normal camera preview (no blur).
@@ -20,15 +21,18 @@ pub const VIDEO_ID_BLUR: u64 = 1;
 /// cache) and scissored to the panel rectangle while positioned at full-preview
 /// geometry, so the blurred slice lines up with the sharp preview behind it.
 pub const VIDEO_ID_FROSTED: u64 = 2;
-/// Video ID for the filter picker's thumbnail grid: one id for all fifteen
-/// swatches, because they are the same frame under fifteen filters and a filter
+/// Video ID for the focus loupe: the live preview's frame again, cropped to
+/// one sensor pixel per physical screen pixel around `loupe_center`.
+pub const VIDEO_ID_LOUPE: u64 = 3;
+/// Video ID for the filter picker's thumbnail grid: one id for every swatch,
+/// because they are the same frame under each filter in turn and a filter
 /// is a property of the *binding*, not of the texture (see [`source_texture_id`]
 /// and `VideoPipeline::bindings`).
 pub const VIDEO_ID_FILTER_PREVIEW: u64 = 99;
 use iced_wgpu::graphics::Viewport;
 use iced_wgpu::primitive::{Pipeline as PipelineTrait, Primitive as PrimitiveTrait};
 use iced_wgpu::wgpu;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, LazyLock, Mutex};
 
 // ---------------------------------------------------------------------------
@@ -163,10 +167,22 @@ pub fn get_gpu_frame_size() -> u64 {
     GPU_FRAME_SIZE.load(Ordering::Relaxed)
 }
 
+// Pixel data address of the last preview frame the renderer took, for the
+// frame pacer (`crate::app::frame_pacer`)
+static PREVIEW_UPLOADED: AtomicUsize = AtomicUsize::new(0);
+
+/// Pixel data address of the last preview frame the renderer took, whether
+/// it was uploaded or skipped
+pub fn last_preview_upload() -> usize {
+    PREVIEW_UPLOADED.load(Ordering::Relaxed)
+}
+
 /// Default UV texture dimensions when yuv_planes is not available
 fn default_uv_size(format: PixelFormat, width: u32, height: u32) -> (u32, u32) {
     match format {
-        PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::I420 => (width / 2, height / 2),
+        PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::I420 | PixelFormat::P010 => {
+            (width / 2, height / 2)
+        }
         PixelFormat::YUYV | PixelFormat::UYVY | PixelFormat::YVYU | PixelFormat::VYUY => {
             (width / 2, height)
         }
@@ -218,6 +234,65 @@ impl VideoFrame {
     }
 }
 
+/// Set in a uniform's `filter_mode` to draw the false color exposure map
+/// instead of the filter (see `texture_filters.wgsl`). Clear of every filter
+/// code, and never part of a binding key.
+const FALSE_COLOR_FLAG: u32 = 0x100;
+
+/// First bit of a uniform's `filter_mode` holding the color vision assist
+/// (see `texture_filters.wgsl`): three bits, 0 for off, bit 2 for simulation
+/// and the low two for the deficiency.
+const COLOR_VISION_SHIFT: u32 = 9;
+
+/// First bit of a uniform's `filter_mode` holding the preview scaling filter
+/// (see `video_shader.wgsl`): two bits, 0 for bilinear so every uniform that
+/// doesn't set it keeps the sampler's own filtering.
+const SCALING_SHIFT: u32 = 12;
+
+/// Shader code of a preview scaling filter, before shifting into place
+fn scaling_code(scaling: PreviewScaling) -> u32 {
+    match scaling {
+        PreviewScaling::Bilinear => 0,
+        PreviewScaling::Nearest => 1,
+        PreviewScaling::Lanczos => 2,
+    }
+}
+
+/// Shader code of a color vision assist, before shifting into place
+fn color_vision_code(assist: ColorVisionAssist) -> u32 {
+    match assist {
+        ColorVisionAssist::Off => 0,
+        ColorVisionAssist::CorrectProtanopia => 1,
+        ColorVisionAssist::CorrectDeuteranopia => 2,
+        ColorVisionAssist::CorrectTritanopia => 3,
+        ColorVisionAssist::SimulateProtanopia => 4 | 1,
+        ColorVisionAssist::SimulateDeuteranopia => 4 | 2,
+        ColorVisionAssist::SimulateTritanopia => 4 | 3,
+    }
+}
+
+/// Crop (u_min, v_min, u_max, v_max) that shows a `view_px` physical-pixel
+/// rect of a `texture`-sized frame at 1:1, centred on `center` (sensor UV) as
+/// far as the frame's edges allow. The view is in display orientation, so a
+/// quarter-turn `rotation` swaps which of its sides spans the texture's width.
+fn loupe_crop_uv(
+    center: (f32, f32),
+    view_px: (f32, f32),
+    texture: (u32, u32),
+    rotation: u32,
+) -> (f32, f32, f32, f32) {
+    let (span_w, span_h) = if rotation == 1 || rotation == 3 {
+        (view_px.1, view_px.0)
+    } else {
+        view_px
+    };
+    let half_u = (span_w / texture.0.max(1) as f32 / 2.0).min(0.5);
+    let half_v = (span_h / texture.1.max(1) as f32 / 2.0).min(0.5);
+    let u = center.0.clamp(half_u, 1.0 - half_u);
+    let v = center.1.clamp(half_v, 1.0 - half_v);
+    (u - half_u, v - half_v, u + half_u, v + half_v)
+}
+
 /// Viewport and content fit data for Cover mode
 #[repr(C)]
 #[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
@@ -301,11 +376,27 @@ struct ViewportUniform {
     /// Appended after `panel_rect` for the same reason `panel_rect` was appended
     /// after `letterbox_color`: every earlier offset is untouched, so the four
     /// shaders that stop short of it stay valid against the same, larger buffer.
-    /// Only `video_shader_frosted.wgsl` declares it.
+    /// Only `video_shader_frosted.wgsl` reads it.
     noise: f32,
-    /// Pads the struct to 128 bytes. A WGSL uniform's size must round up to a
-    /// 16-byte multiple, and `noise` alone would leave it at 116.
-    _pad: [f32; 3],
+    /// Software white balance gains (r, g, b) applied before the filter, from
+    /// [`crate::shaders::software_white_balance`]. Written by every pass that
+    /// applies the filter; 1.0 elsewhere.
+    ///
+    /// Fills what used to be the padding after `noise`, so the struct stays at
+    /// 128 bytes. Declared as three scalars in WGSL, since a `vec3` would be
+    /// 16-byte aligned and land past the end. Only `video_shader.wgsl` and
+    /// `video_shader_blur.wgsl` declare it.
+    wb_gains: [f32; 3],
+    /// Anamorphic desqueeze: the source frame is treated as this many times
+    /// wider in the content-fit math (1.0 = none). Read by the passes that
+    /// sample the source frame — the preview, the filter pre-blur and the blur
+    /// chain's pass 0 — and left at 1.0 by every pass after them.
+    ///
+    /// Appended past the old 128-byte end, so every earlier offset is kept and
+    /// the shaders that stop short of it stay valid against the larger buffer.
+    desqueeze: f32,
+    /// Pads the struct to a multiple of 16 bytes, as WGSL sizes it
+    _padding: [f32; 3],
 }
 
 impl Default for ViewportUniform {
@@ -333,7 +424,9 @@ impl Default for ViewportUniform {
             letterbox_color: [0.0, 0.0, 0.0, 1.0],
             panel_rect: [0.0; 4],
             noise: 0.0,
-            _pad: [0.0; 3],
+            wb_gains: [1.0; 3],
+            desqueeze: 1.0,
+            _padding: [0.0; 3],
         }
     }
 }
@@ -585,6 +678,19 @@ pub struct VideoPrimitive {
     /// `blur_target_sigma` that `prepare()` had to invert into one using the
     /// live frame-to-screen scale.
     pub blur_params: CompositorBlurParams,
+    /// Draw the false color exposure map instead of the image, in the sharp
+    /// preview and in pass 0 of the blur chain alike
+    pub false_color: bool,
+    /// Color vision deficiency the image is daltonized for or simulated as
+    pub color_vision: ColorVisionAssist,
+    /// Resampling filter of the sharp preview
+    pub scaling: PreviewScaling,
+    /// Anamorphic desqueeze factor (1.0 = none)
+    pub desqueeze: f32,
+    /// Sensor UV the focus loupe is centred on. When set, `prepare()` replaces
+    /// `crop_uv` with the 1:1 crop around it, since only it knows the physical
+    /// size of the widget.
+    pub loupe_center: Option<(f32, f32)>,
 }
 
 impl Clone for VideoPrimitive {
@@ -606,7 +712,7 @@ impl Clone for VideoPrimitive {
     /// `FrameData` (an `Arc`), so this copies a refcount, and the duplicate
     /// upload each clone would otherwise drive is deduplicated by
     /// `last_frame_ptr` — the same mechanism that already absorbs the filter
-    /// picker's fifteen widgets.
+    /// picker's swatch widgets.
     ///
     /// `frosted_final_binding` and `preblur_binding` are deliberately not carried
     /// across: each is the product of one draw's `prepare()`, so a clone taken
@@ -638,6 +744,11 @@ impl Clone for VideoPrimitive {
             zoom_level: self.zoom_level,
             letterbox_color: self.letterbox_color,
             blur_params: self.blur_params,
+            false_color: self.false_color,
+            color_vision: self.color_vision,
+            scaling: self.scaling,
+            desqueeze: self.desqueeze,
+            loupe_center: self.loupe_center,
         }
     }
 }
@@ -671,9 +782,9 @@ impl VideoPrimitive {
 
 /// The `VideoPipeline::textures` key a `video_id` uploads through.
 ///
-/// It is NOT the identity: [`VIDEO_ID_FROSTED`] and [`VIDEO_ID_FILTER_PREVIEW`]
-/// upload through [`VIDEO_ID_NORMAL`]'s entry, because all three are the same
-/// pixels. Every frosted primitive is minted from the preview's own
+/// It is NOT the identity: [`VIDEO_ID_FROSTED`], [`VIDEO_ID_FILTER_PREVIEW`] and
+/// [`VIDEO_ID_LOUPE`] upload through [`VIDEO_ID_NORMAL`]'s entry, because all
+/// four are the same pixels. Every frosted primitive is minted from the preview's own
 /// `current_frame` `Arc` (see `frosted_backdrop::make_primitive`), and so is
 /// every filter swatch (`filter_picker::view`), so keying the source texture by
 /// `video_id` meant uploading that `Arc` two or three times per frame — ~20 MB of
@@ -690,7 +801,7 @@ impl VideoPrimitive {
 /// Only the SOURCE texture is shared. The bindings, the blur targets, the
 /// uniforms and the transforms all stay keyed by `video_id`, because those
 /// genuinely differ per consumer (see [`BlurTargets`]). That is what makes the
-/// picker's fifteen filters free of this: `bindings` is keyed by
+/// picker's swatches free of this: `bindings` is keyed by
 /// `(video_id, filter_mode)`, so each swatch keeps its own filter uniform over
 /// the one shared texture, exactly as it already did under its own id.
 ///
@@ -699,7 +810,7 @@ impl VideoPrimitive {
 /// so it must not be re-pointed at whatever the live preview last uploaded.
 fn source_texture_id(video_id: u64) -> u64 {
     match video_id {
-        VIDEO_ID_FROSTED | VIDEO_ID_FILTER_PREVIEW => VIDEO_ID_NORMAL,
+        VIDEO_ID_FROSTED | VIDEO_ID_FILTER_PREVIEW | VIDEO_ID_LOUPE => VIDEO_ID_NORMAL,
         other => other,
     }
 }
@@ -742,7 +853,13 @@ struct YuvConvertParams {
     y_stride: u32,
     uv_stride: u32,
     v_stride: u32,
-    _pad: [u32; 2],
+    /// HDR transfer to tone-map from (`HdrTransfer::shader_code`)
+    transfer: u32,
+    /// YUV matrix (`ColorSpace::matrix_code`)
+    matrix: u32,
+    /// 1 when YUV samples are full range
+    full_range: u32,
+    _pad: [u32; 3],
 }
 
 /// YUV textures for a video source (for YUV→RGBA conversion)
@@ -777,6 +894,9 @@ pub struct VideoPipeline {
     bind_group_layout_rgba: wgpu::BindGroupLayout,
     bind_group_layout_rgb: wgpu::BindGroupLayout,
     sampler: wgpu::Sampler,
+    /// Active imported LUT, bound at binding 3 of both layouts. Fixed-size,
+    /// so a LUT change never invalidates the cached bindings.
+    lut: crate::shaders::LutTexture,
     // Shared textures by video_id (single upload per source)
     textures: std::collections::HashMap<u64, VideoTexture>,
     // Per-filter bindings keyed by (video_id, filter_mode)
@@ -947,6 +1067,11 @@ impl VideoPrimitive {
             // equal on-screen thickness; the frosted backdrop overrides it with
             // the compositor's own entry.
             blur_params: TRANSITION_BLUR_PARAMS,
+            false_color: false,
+            color_vision: ColorVisionAssist::Off,
+            scaling: PreviewScaling::Bilinear,
+            desqueeze: 1.0,
+            loupe_center: None,
         }
     }
 
@@ -1204,6 +1329,17 @@ impl PrimitiveTrait for VideoPrimitive {
             let content_fit_mode = cover_blend;
 
             let filter_mode = self.filter_type.gpu_filter_code();
+            // The flags go into the uniforms only: the binding stays keyed by
+            // the filter alone
+            let mut shaded_filter_mode = filter_mode;
+            if self.false_color {
+                shaded_filter_mode |= FALSE_COLOR_FLAG;
+            }
+            shaded_filter_mode |= color_vision_code(self.color_vision) << COLOR_VISION_SHIFT;
+            shaded_filter_mode |= scaling_code(self.scaling) << SCALING_SHIFT;
+            pipeline.lut.sync(queue);
+            let gains = crate::shaders::software_white_balance();
+            let wb_gains = [gains.red, gains.green, gains.blue];
 
             let (panel_rect, corner_radius_px) =
                 corner_sdf_params(raw_physical_bounds, self.corner_radius, scale);
@@ -1212,8 +1348,25 @@ impl PrimitiveTrait for VideoPrimitive {
             // This allows sharing the source texture while having per-filter uniforms
             pipeline.get_or_create_binding(device, self.video_id, filter_mode);
 
+            // The loupe's crop follows the texture and the physical size of
+            // the widget, neither of which the view knows
+            let crop_uv = match self.loupe_center {
+                Some(center) => pipeline
+                    .textures
+                    .get(&source_texture_id(self.video_id))
+                    .map(|tex| {
+                        loupe_crop_uv(
+                            center,
+                            (raw_physical_bounds.2, raw_physical_bounds.3),
+                            (tex.width, tex.height),
+                            self.rotation,
+                        )
+                    }),
+                None => self.crop_uv,
+            };
+
             // Crop UV values (default to the full image if not set).
-            let (crop_min, crop_max) = self.crop_uv.map_or(
+            let (crop_min, crop_max) = crop_uv.map_or(
                 ([0.0f32, 0.0], [1.0f32, 1.0]),
                 |(u_min, v_min, u_max, v_max)| ([u_min, v_min], [u_max, v_max]),
             );
@@ -1246,7 +1399,8 @@ impl PrimitiveTrait for VideoPrimitive {
                     let pass0_uniform = ViewportUniform {
                         viewport_size: [width, height],
                         content_fit_mode,
-                        filter_mode,
+                        filter_mode: shaded_filter_mode,
+                        wb_gains,
                         mirror_horizontal: if self.mirror_horizontal { 1 } else { 0 },
                         crop_uv_min: crop_min,
                         crop_uv_max: crop_max,
@@ -1257,6 +1411,7 @@ impl PrimitiveTrait for VideoPrimitive {
                         // already has it, so they leave `zoom_level` at the 1.0
                         // default and must keep doing so or the zoom compounds.
                         zoom_level: self.zoom_level,
+                        desqueeze: self.desqueeze,
                         rotation: self.rotation,
                         bar_top_height: bar_top,
                         bar_bottom_height: bar_bottom,
@@ -1273,7 +1428,8 @@ impl PrimitiveTrait for VideoPrimitive {
                     let uniform_data = ViewportUniform {
                         viewport_size: [width, height],
                         content_fit_mode,
-                        filter_mode,
+                        filter_mode: shaded_filter_mode,
+                        wb_gains,
                         corner_radius: corner_radius_px,
                         panel_rect,
                         mirror_horizontal: if self.mirror_horizontal { 1 } else { 0 },
@@ -1282,6 +1438,7 @@ impl PrimitiveTrait for VideoPrimitive {
                         crop_uv_min: crop_min,
                         crop_uv_max: crop_max,
                         zoom_level: self.zoom_level,
+                        desqueeze: self.desqueeze,
                         rotation: self.rotation,
                         bar_top_height: bar_top,
                         bar_bottom_height: bar_bottom,
@@ -1330,7 +1487,8 @@ impl PrimitiveTrait for VideoPrimitive {
                     let pb_uniform = ViewportUniform {
                         viewport_size: [intermediate.width as f32, intermediate.height as f32],
                         content_fit_mode: 0.0, // Contain — identity given the matched viewport_size
-                        filter_mode,
+                        filter_mode: shaded_filter_mode,
+                        wb_gains,
                         corner_radius: corner_radius_px,
                         panel_rect,
                         letterbox_color: self.letterbox_color,
@@ -1368,6 +1526,12 @@ impl PrimitiveTrait for VideoPrimitive {
                                     binding: 2,
                                     resource: pb_viewport_buffer.as_entire_binding(),
                                 },
+                                wgpu::BindGroupEntry {
+                                    binding: 3,
+                                    resource: wgpu::BindingResource::TextureView(
+                                        pipeline.lut.view(),
+                                    ),
+                                },
                             ],
                         });
                         queue.write_buffer(
@@ -1503,6 +1667,12 @@ impl PrimitiveTrait for VideoPrimitive {
                                         binding: 2,
                                         resource: viewport_buffer.as_entire_binding(),
                                     },
+                                    wgpu::BindGroupEntry {
+                                        binding: 3,
+                                        resource: wgpu::BindingResource::TextureView(
+                                            pipeline.lut.view(),
+                                        ),
+                                    },
                                 ],
                             });
                             queue.write_buffer(
@@ -1631,6 +1801,13 @@ impl VideoPipeline {
                         },
                         count: None,
                     },
+                    // Imported LUT
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 3,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: crate::shaders::LutTexture::BINDING_TYPE,
+                        count: None,
+                    },
                 ],
             });
 
@@ -1716,6 +1893,13 @@ impl VideoPipeline {
                         },
                         count: None,
                     },
+                    // Imported LUT
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 3,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: crate::shaders::LutTexture::BINDING_TYPE,
+                        count: None,
+                    },
                 ],
             });
 
@@ -2001,6 +2185,7 @@ impl VideoPipeline {
             bind_group_layout_rgba,
             bind_group_layout_rgb,
             sampler,
+            lut: crate::shaders::LutTexture::new(device),
             textures: std::collections::HashMap::new(),
             bindings: std::collections::HashMap::new(),
             blur_targets: std::sync::RwLock::new(std::collections::HashMap::new()),
@@ -2035,6 +2220,9 @@ impl VideoPipeline {
         // may hand back the same block and this dedup will freeze the preview on
         // the stale texture.
         let frame_data_ptr = frame.data.as_ptr() as usize;
+        if tex_id == VIDEO_ID_NORMAL {
+            PREVIEW_UPLOADED.store(frame_data_ptr, Ordering::Relaxed);
+        }
 
         // Check if texture exists and needs resizing
         let needs_creation = match self.textures.get(&tex_id) {
@@ -2306,8 +2494,9 @@ impl VideoPipeline {
                     },
                 );
             }
-            // Semi-planar 4:2:0 formats: NV12, NV21
-            PixelFormat::NV12 | PixelFormat::NV21 => {
+            // Semi-planar 4:2:0 formats: NV12, NV21, and P010 (16-bit samples,
+            // uploaded as RG8/RGBA8 byte pairs so the texel counts match NV12)
+            PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::P010 => {
                 // NV12: Use offsets to slice Y and UV planes from buffer
                 if let Some(ref yuv_planes) = frame.yuv_planes {
                     let uv_width = frame.width / 2;
@@ -2435,7 +2624,8 @@ impl VideoPipeline {
                 }
             }
             // Grayscale: single channel R8 format
-            PixelFormat::Gray8 => {
+            // Y210: one RGBA8 texel per pixel (16-bit Y plus half the chroma pair)
+            PixelFormat::Gray8 | PixelFormat::Y210 => {
                 queue.write_texture(
                     wgpu::TexelCopyTextureInfo {
                         texture: &yuv_textures.tex_y,
@@ -2464,8 +2654,8 @@ impl VideoPipeline {
                 );
                 return;
             }
-            // ABGR/BGRA: Upload as RGBA8, shader will swizzle channels
-            PixelFormat::ABGR | PixelFormat::BGRA => {
+            // ABGR/BGRA/RGBx/BGRx: Upload as RGBA8, shader will swizzle channels
+            PixelFormat::ABGR | PixelFormat::BGRA | PixelFormat::RGBx | PixelFormat::BGRx => {
                 queue.write_texture(
                     wgpu::TexelCopyTextureInfo {
                         texture: &yuv_textures.tex_y,
@@ -2507,6 +2697,7 @@ impl VideoPipeline {
         // Update uniform buffer with conversion parameters
         // Use the PixelFormat method to get format code
         let format_code = frame.format.gpu_format_code();
+        let color_space = crate::shaders::frame_color_space(frame.format);
 
         let params = YuvConvertParams {
             width: frame.width,
@@ -2515,7 +2706,10 @@ impl VideoPipeline {
             y_stride: frame.stride,
             uv_stride: frame.yuv_planes.as_ref().map(|p| p.uv_stride).unwrap_or(0),
             v_stride: frame.yuv_planes.as_ref().map(|p| p.v_stride).unwrap_or(0),
-            _pad: [0, 0],
+            transfer: crate::shaders::frame_transfer(frame.format).shader_code(),
+            matrix: color_space.matrix_code(),
+            full_range: u32::from(color_space.full_range),
+            _pad: [0; 3],
         };
 
         if let Some(ref uniform_buffer) = self.yuv_uniform_buffer {
@@ -2639,10 +2833,17 @@ impl VideoPipeline {
             PixelFormat::YUYV | PixelFormat::UYVY | PixelFormat::YVYU | PixelFormat::VYUY => {
                 wgpu::TextureFormat::Rgba8Unorm
             }
-            // RGBA, RGB24, ABGR, BGRA: full RGBA texture
-            PixelFormat::RGBA | PixelFormat::RGB24 | PixelFormat::ABGR | PixelFormat::BGRA => {
-                wgpu::TextureFormat::Rgba8Unorm
-            }
+            // RGBA, RGB24, ABGR, BGRA, RGBx, BGRx: full RGBA texture
+            PixelFormat::RGBA
+            | PixelFormat::RGB24
+            | PixelFormat::ABGR
+            | PixelFormat::BGRA
+            | PixelFormat::RGBx
+            | PixelFormat::BGRx => wgpu::TextureFormat::Rgba8Unorm,
+            // Y210: 16-bit Y and 16-bit chroma per pixel as RGBA8
+            PixelFormat::Y210 => wgpu::TextureFormat::Rgba8Unorm,
+            // P010: 16-bit Y as a little-endian byte pair
+            PixelFormat::P010 => wgpu::TextureFormat::Rg8Unorm,
             // Y plane or grayscale: single channel
             _ => wgpu::TextureFormat::R8Unorm,
         };
@@ -2651,6 +2852,8 @@ impl VideoPipeline {
         let uv_format = match format {
             // NV12/NV21: interleaved UV/VU as Rg8
             PixelFormat::NV12 | PixelFormat::NV21 => wgpu::TextureFormat::Rg8Unorm,
+            // P010: interleaved 16-bit UV as two byte pairs
+            PixelFormat::P010 => wgpu::TextureFormat::Rgba8Unorm,
             // I420 and others: R8 for U/V planes
             _ => wgpu::TextureFormat::R8Unorm,
         };
@@ -2786,6 +2989,10 @@ impl VideoPipeline {
                     binding: 2,
                     resource: viewport_buffer.as_entire_binding(),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(self.lut.view()),
+                },
             ],
         });
 
@@ -2892,6 +3099,10 @@ impl VideoPipeline {
                         binding: 2,
                         resource: viewport_buffer.as_entire_binding(),
                     },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: wgpu::BindingResource::TextureView(self.lut.view()),
+                    },
                 ],
             });
             FilterBinding {
@@ -3433,11 +3644,91 @@ mod tests {
         assert_eq!(offset_of!(ViewportUniform, panel_rect), 96);
         // `noise` is appended last, after every offset the other shaders rely on.
         assert_eq!(offset_of!(ViewportUniform, noise), 112);
-        assert_eq!(size_of::<ViewportUniform>(), 128);
+        // The white balance gains fill the tail as three scalars.
+        assert_eq!(offset_of!(ViewportUniform, wb_gains), 116);
+        // The desqueeze factor starts a new 16-byte row past the old end.
+        assert_eq!(offset_of!(ViewportUniform, desqueeze), 128);
+        assert_eq!(size_of::<ViewportUniform>(), 144);
         assert_eq!(size_of::<ViewportUniform>() % 16, 0);
         assert_eq!(align_of::<ViewportUniform>(), 4);
     }
 
+    /// The false color flag rides in `filter_mode`, so it must match the
+    /// prelude's copy and stay clear of every filter code.
+    #[test]
+    fn false_color_flag_matches_the_shader() {
+        assert!(
+            crate::shaders::TEXTURE_FILTER_FUNCTIONS
+                .contains(&format!("FALSE_COLOR_FLAG: u32 = {FALSE_COLOR_FLAG:#x}u;"))
+        );
+        assert!(FilterType::Lut.gpu_filter_code() < FALSE_COLOR_FLAG);
+    }
+
+    /// The color vision assist sits above the false color flag in
+    /// `filter_mode`, in the bits the prelude reads it from
+    #[test]
+    fn color_vision_codes_fit_their_bits() {
+        assert!(
+            crate::shaders::TEXTURE_FILTER_FUNCTIONS
+                .contains(&format!("COLOR_VISION_SHIFT: u32 = {COLOR_VISION_SHIFT}u;"))
+        );
+        assert!(FALSE_COLOR_FLAG < 1 << COLOR_VISION_SHIFT);
+        let mut codes: Vec<u32> = ColorVisionAssist::ALL
+            .iter()
+            .map(|&assist| color_vision_code(assist))
+            .collect();
+        assert_eq!(codes[0], 0);
+        assert!(codes.iter().all(|&code| code < 8));
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), ColorVisionAssist::ALL.len());
+    }
+
+    /// The scaling filter sits above the color vision assist, where the
+    /// preview shader reads it, with bilinear as the unset default
+    #[test]
+    fn scaling_codes_fit_their_bits() {
+        assert!(
+            include_str!("video_shader.wgsl")
+                .contains(&format!("SCALING_SHIFT: u32 = {SCALING_SHIFT}u;"))
+        );
+        assert!(8 << COLOR_VISION_SHIFT <= 1 << SCALING_SHIFT);
+        assert_eq!(scaling_code(PreviewScaling::default()), 0);
+        let mut codes: Vec<u32> = PreviewScaling::ALL
+            .iter()
+            .map(|&s| scaling_code(s))
+            .collect();
+        assert!(codes.iter().all(|&code| code < 4));
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), PreviewScaling::ALL.len());
+    }
+
+    /// The loupe shows one texel per physical pixel and stays inside the
+    /// frame when pointed at its edge
+    #[test]
+    fn loupe_crop_is_one_to_one_and_clamped() {
+        let (u0, v0, u1, v1) = loupe_crop_uv((0.5, 0.5), (192.0, 108.0), (1920, 1080), 0);
+        assert!(((u1 - u0) * 1920.0 - 192.0).abs() < 1e-3);
+        assert!(((v1 - v0) * 1080.0 - 108.0).abs() < 1e-3);
+        assert!((u0 + u1 - 1.0).abs() < 1e-6 && (v0 + v1 - 1.0).abs() < 1e-6);
+
+        let (u0, v0, _, _) = loupe_crop_uv((0.0, 0.0), (192.0, 108.0), (1920, 1080), 0);
+        assert_eq!((u0, v0), (0.0, 0.0));
+
+        // A portrait view of a sideways sensor spans the texture's width
+        // with its height
+        let (u0, v0, u1, v1) = loupe_crop_uv((0.5, 0.5), (108.0, 192.0), (1920, 1080), 1);
+        assert!(((u1 - u0) * 1920.0 - 192.0).abs() < 1e-3);
+        assert!(((v1 - v0) * 1080.0 - 108.0).abs() < 1e-3);
+
+        // A view bigger than the frame shows all of it
+        assert_eq!(
+            loupe_crop_uv((0.2, 0.9), (4000.0, 3000.0), (640, 480), 0),
+            (0.0, 0.0, 1.0, 1.0)
+        );
+    }
+
     /// The format the blur chain's ping-pong targets ACTUALLY have on device.
     ///
     /// `VideoPipeline::new` is handed iced's surface format, and
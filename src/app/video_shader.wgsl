@@ -11,7 +11,7 @@ var sampler_video: sampler;
 struct ViewportUniform {
     viewport_size: vec2<f32>,   // Full widget size
     content_fit_mode: f32,      // 0.0 = Contain, 1.0 = Cover (interpolated during animation)
-    filter_mode: u32,           // Filter index (0-15)
+    filter_mode: u32,           // Filter index (0-19)
     corner_radius: f32,         // Corner radius in pixels (0 = no rounding)
     mirror_horizontal: u32,     // 0 = normal, 1 = mirrored horizontally
     uv_offset: vec2<f32>,       // UV offset for scroll clipping (0-1)
@@ -28,11 +28,21 @@ struct ViewportUniform {
     // The rect (x, y, w, h) the corners are cut from, in PHYSICAL px of the
     // render target, i.e. the same space as `@builtin(position)`.
     panel_rect: vec4<f32>,
+    noise: f32,                 // Unused here — read by the frosted composite
+    // Software white balance gains, applied before the filter (1.0 = unchanged)
+    wb_gain_r: f32,
+    wb_gain_g: f32,
+    wb_gain_b: f32,
+    desqueeze: f32,             // Anamorphic desqueeze: source width multiplier (1.0 = none)
 }
 
 @group(0) @binding(2)
 var<uniform> viewport: ViewportUniform;
 
+// Imported LUT for filter mode 19 (identity when none is active)
+@group(0) @binding(3)
+var lut_texture: texture_3d<f32>;
+
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
     @location(0) tex_coords: vec2<f32>,
@@ -43,6 +53,65 @@ struct VertexOutput {
 // geometry prelude (src/shaders/geometry.wgsl), both concatenated ahead of this
 // file in `VideoPipeline::new`.
 
+// Preview scaling filter: two bits of `filter_mode` from this shift, 0 for
+// the sampler's bilinear. Mirrors `SCALING_SHIFT` in `video_primitive.rs`.
+const SCALING_SHIFT: u32 = 12u;
+const SCALING_NEAREST: u32 = 1u;
+const SCALING_LANCZOS: u32 = 2u;
+
+// Lanczos taps along each axis. Each is a bilinear fetch, so up to a 2x
+// downscale the taps between them still weigh every texel under the kernel.
+const LANCZOS_TAPS: i32 = 6;
+
+fn lanczos2(x: f32) -> f32 {
+    let ax = abs(x);
+    if (ax < 1e-4) {
+        return 1.0;
+    }
+    if (ax >= 2.0) {
+        return 0.0;
+    }
+    let px = 3.14159265 * x;
+    return 2.0 * sin(px) * sin(px * 0.5) / (px * px);
+}
+
+// Sample the frame at `uv` with the preview scaling filter. `footprint` is
+// how many texels one screen pixel spans along each texture axis.
+fn sample_scaled(uv: vec2<f32>, footprint: vec2<f32>) -> vec4<f32> {
+    let scaling = (viewport.filter_mode >> SCALING_SHIFT) & 3u;
+    let dims = vec2<f32>(textureDimensions(texture_rgba));
+    if (scaling == SCALING_NEAREST) {
+        let texel = clamp(vec2<i32>(floor(uv * dims)), vec2<i32>(0), vec2<i32>(dims) - 1);
+        return textureLoad(texture_rgba, texel, 0);
+    }
+    if (scaling != SCALING_LANCZOS) {
+        return textureSampleLevel(texture_rgba, sampler_video, uv, 0.0);
+    }
+
+    // Lanczos-2, stretched by the footprint when scaling down so the kernel
+    // covers every texel under the pixel instead of skipping some, which is
+    // what makes fine detail shimmer. Taps sit `stretch` texels apart, from
+    // -2 to +3 steps around the texel centre left of `pos`, so at 1:1 and
+    // when scaling up they land exactly on texel centres.
+    let stretch = max(footprint, vec2<f32>(1.0));
+    let pos = uv * dims;
+    let base = floor(pos - 0.5) + 0.5;
+    var sum = vec4<f32>(0.0);
+    var weight_sum = 0.0;
+    for (var j = 0; j < LANCZOS_TAPS; j++) {
+        let y = base.y + f32(j - LANCZOS_TAPS / 2 + 1) * stretch.y;
+        let wy = lanczos2((y - pos.y) / stretch.y);
+        for (var i = 0; i < LANCZOS_TAPS; i++) {
+            let x = base.x + f32(i - LANCZOS_TAPS / 2 + 1) * stretch.x;
+            let w = lanczos2((x - pos.x) / stretch.x) * wy;
+            sum += w * textureSampleLevel(texture_rgba, sampler_video, vec2<f32>(x, y) / dims, 0.0);
+            weight_sum += w;
+        }
+    }
+    // Lanczos lobes go negative; clamp the ringing they add at hard edges
+    return clamp(sum / max(weight_sum, 1e-4), vec4<f32>(0.0), vec4<f32>(1.0));
+}
+
 // Vertex shader - creates a fullscreen quad
 @vertex
 fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
@@ -113,7 +182,10 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         if (viewport.rotation == 1u || viewport.rotation == 3u) {
             crop_range = vec2<f32>(crop_range.y, crop_range.x);
         }
-        let effective_tex = tex_size * crop_range;
+        // An anamorphic frame is shown wider than it was recorded. Stretching
+        // the display-orientation width here makes the fit below size it that
+        // way, so Contain letterboxes and Cover crops the desqueezed picture.
+        let effective_tex = tex_size * crop_range * vec2<f32>(viewport.desqueeze, 1.0);
 
         // Content area between UI bars (for contain centering)
         let content_height = viewport.viewport_size.y - viewport.bar_top_height - viewport.bar_bottom_height;
@@ -169,17 +241,24 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         tex_coords = (tex_coords - vec2<f32>(0.5, 0.5)) * inv_zoom + vec2<f32>(0.5, 0.5);
     }
 
-    // Sample RGBA texture
-    var pixel = textureSample(texture_rgba, sampler_video, tex_coords);
-    var color = pixel.rgb;
+    // Sample RGBA texture with the preview scaling filter. The derivatives
+    // of the final coordinates give the scale; a quarter-turn rotation only
+    // swaps which screen axis each texture axis follows, which `fwidth` sums
+    // over anyway.
+    let footprint = fwidth(tex_coords) * vec2<f32>(textureDimensions(texture_rgba));
+    var pixel = sample_scaled(tex_coords, footprint);
+    let wb_gains = vec3<f32>(viewport.wb_gain_r, viewport.wb_gain_g, viewport.wb_gain_b);
+    var color = min(pixel.rgb * wb_gains, vec3<f32>(1.0));
 
-    // Apply the filter (0-14) using the shared preludes.
+    // Apply the filter (0-19) using the shared preludes.
     color = apply_texture_filter(
         color,
         viewport.filter_mode,
         tex_coords,
         texture_rgba,
         sampler_video,
+        wb_gains,
+        lut_texture,
     );
 
     // Round the corners off the widget's own rect, exactly as the frosted
@@ -51,11 +51,20 @@ struct ViewportUniform {
     dim_factor: f32,            // Unused here — applied by the final composite
     letterbox_color: vec4<f32>, // RGBA fill for letterbox (alpha unused)
     panel_rect: vec4<f32>,      // Unused here — read by the final composite
+    noise: f32,                 // Unused here — read by the final composite
+    wb_gain_r: f32,             // Software white balance gains, applied with the filter
+    wb_gain_g: f32,
+    wb_gain_b: f32,
+    desqueeze: f32,             // Anamorphic desqueeze; applied HERE, like the zoom
 }
 
 @group(0) @binding(2)
 var<uniform> viewport: ViewportUniform;
 
+// Imported LUT for filter mode 19, shared with the sharp preview's binding
+@group(0) @binding(3)
+var lut_texture: texture_3d<f32>;
+
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
     @location(0) tex_coords: vec2<f32>,
@@ -118,7 +127,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         if (viewport.rotation == 1u || viewport.rotation == 3u) {
             crop_range = vec2<f32>(crop_range.y, crop_range.x);
         }
-        let effective_tex = tex_size_dim * crop_range;
+        // Desqueezed exactly as video_shader.wgsl does.
+        let effective_tex = tex_size_dim * crop_range * vec2<f32>(viewport.desqueeze, 1.0);
 
         let content_height = viewport.viewport_size.y - viewport.bar_top_height - viewport.bar_bottom_height;
         let content_center_y = (viewport.bar_top_height + content_height * 0.5) / viewport.viewport_size.y;
@@ -185,7 +195,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // ONE bilinear tap. This pass resamples; it does not blur. See the header
     // for why the 37-tap ring rosette that used to live here was the cause of
     // the banding, and `video_shader_kawase.wgsl` for what replaced it.
-    var rgb_val = textureSample(texture_blur, sampler_blur, tex_coords).rgb;
+    let wb_gains = vec3<f32>(viewport.wb_gain_r, viewport.wb_gain_g, viewport.wb_gain_b);
+    var rgb_val = min(textureSample(texture_blur, sampler_blur, tex_coords).rgb * wb_gains, vec3<f32>(1.0));
 
     // Apply the filter here — the one pass that sees the source frame, so the
     // filter is visible in the blur exactly as it is in the sharp preview. The
@@ -206,6 +217,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         tex_coords,
         texture_blur,
         sampler_blur,
+        wb_gains,
+        lut_texture,
     );
 
     // Opaque, always. The Kawase passes normalize by `sum.a` and treat a = 0 as
@@ -34,6 +34,12 @@ struct ViewportUniform {
     kawase_offset: f32,        // Unused here — read by the Kawase passes
     dim_factor: f32,           // Unused here — applied by the frosted composite
     letterbox_color: vec4<f32>, // unused here; struct must match the shared ViewportUniform
+    panel_rect: vec4<f32>,      // Unused here — read by the composites
+    noise: f32,                 // Unused here — read by the frosted composite
+    wb_gain_r: f32,             // Unused here — applied by the filter pass
+    wb_gain_g: f32,
+    wb_gain_b: f32,
+    desqueeze: f32,             // Anamorphic desqueeze: source width multiplier (1.0 = none)
 }
 
 @group(0) @binding(2)
@@ -89,7 +95,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         if (viewport.rotation == 1u || viewport.rotation == 3u) {
             crop_range = vec2<f32>(crop_range.y, crop_range.x);
         }
-        let effective_tex = tex_size * crop_range;
+        // Desqueezed exactly as video_shader.wgsl does.
+        let effective_tex = tex_size * crop_range * vec2<f32>(viewport.desqueeze, 1.0);
         let content_height = viewport.viewport_size.y - viewport.bar_top_height - viewport.bar_bottom_height;
         let content_center_y = (viewport.bar_top_height + content_height * 0.5) / viewport.viewport_size.y;
         let contain_zoom = min(viewport.viewport_size.x / effective_tex.x, content_height / effective_tex.y);
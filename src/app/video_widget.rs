@@ -8,6 +8,7 @@
 //! 3. Persistent textures across frames
 //! 4. Native RGBA format for simplified processing
 
+use crate::app::camera_preview::gestures::GestureRecognizer;
 use crate::app::state::{FilterType, Message};
 use crate::app::video_primitive::{VideoFrame, VideoPrimitive};
 use crate::backends::camera::types::{CameraFrame, PixelFormat};
@@ -21,16 +22,19 @@ use cosmic::{Renderer, Theme};
 use iced_wgpu::primitive::Renderer as PrimitiveRenderer;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 
-/// Internal state for tracking pinch-to-zoom gestures
+/// Internal state for tracking pinch-to-zoom and single-finger gestures
 #[derive(Default)]
-struct PinchState {
+struct TouchState {
     /// Active finger positions (up to 2 tracked)
     fingers: HashMap<touch::Finger, Point>,
     /// Distance between two fingers when pinch started
     initial_distance: Option<f32>,
     /// Zoom level when pinch gesture started
     zoom_at_pinch_start: f32,
+    /// Swipe / double-tap / long-press recognition
+    gestures: GestureRecognizer,
 }
 
 /// Content fit mode for video scaling
@@ -73,6 +77,8 @@ pub struct VideoWidgetConfig {
     pub zoom_level: f32,
     /// Whether scroll wheel zoom is enabled
     pub scroll_zoom_enabled: bool,
+    /// Whether swipe / double-tap / long-press gestures are reported
+    pub gestures_enabled: bool,
     /// Blend between Contain (0.0) and Cover (1.0) for animated transitions.
     /// When `None`, uses `content_fit.blend()`.
     pub cover_blend: Option<f32>,
@@ -85,6 +91,16 @@ pub struct VideoWidgetConfig {
     /// to fill the letterbox in Contain / Fit mode instead of returning
     /// transparent — otherwise the COSMIC window background leaks through.
     pub letterbox_color: [f32; 4],
+    /// Show the false color exposure map instead of the image
+    pub false_color: bool,
+    /// Daltonize or simulate a color vision deficiency
+    pub color_vision: crate::config::ColorVisionAssist,
+    /// Resampling filter the frame is scaled to the widget with
+    pub scaling: crate::config::PreviewScaling,
+    /// Anamorphic desqueeze: horizontal stretch of the picture (1.0 = none)
+    pub desqueeze: f32,
+    /// Sensor UV to show at 1:1 around, for the focus loupe (overrides `crop_uv`)
+    pub loupe_center: Option<(f32, f32)>,
 }
 
 /// Video widget that renders camera frames using a custom GPU primitive
@@ -96,6 +112,8 @@ pub struct VideoWidget {
     content_fit: VideoContentFit,
     /// Enable scroll wheel zoom (only for main camera preview, not filter picker)
     scroll_zoom_enabled: bool,
+    /// Report touch gestures (only for the main camera preview)
+    gestures_enabled: bool,
     /// Current zoom level (passed through for pinch gesture reference)
     zoom_level: f32,
     /// Shader blend: 0.0 = Contain, 1.0 = Cover
@@ -119,6 +137,11 @@ impl VideoWidget {
         primitive.crop_uv = config.crop_uv;
         primitive.zoom_level = config.zoom_level;
         primitive.letterbox_color = config.letterbox_color;
+        primitive.false_color = config.false_color;
+        primitive.color_vision = config.color_vision;
+        primitive.scaling = config.scaling;
+        primitive.desqueeze = config.desqueeze;
+        primitive.loupe_center = config.loupe_center;
 
         // Calculate aspect ratio from frame dimensions, adjusted for crop and rotation
         // For 90° and 270° rotations, swap width and height
@@ -150,6 +173,8 @@ impl VideoWidget {
         } else {
             16.0 / 9.0 // Default aspect ratio
         };
+        // An anamorphic picture is shown wider than the frame it came in
+        let aspect_ratio = aspect_ratio * config.desqueeze;
 
         // Create VideoFrame (supports RGBA and YUV formats)
         // IMPORTANT: We share the FrameData without copying to maintain zero-copy from GStreamer
@@ -159,8 +184,14 @@ impl VideoWidget {
             } else {
                 // Fallback based on format
                 match frame.format {
-                    PixelFormat::RGBA | PixelFormat::ABGR | PixelFormat::BGRA => frame.width * 4,
+                    PixelFormat::RGBA
+                    | PixelFormat::ABGR
+                    | PixelFormat::BGRA
+                    | PixelFormat::RGBx
+                    | PixelFormat::BGRx
+                    | PixelFormat::Y210 => frame.width * 4,
                     PixelFormat::RGB24 => frame.width * 3, // 3 bytes per pixel
+                    PixelFormat::P010 => frame.width * 2,  // 16-bit Y plane
                     PixelFormat::YUYV
                     | PixelFormat::UYVY
                     | PixelFormat::YVYU
@@ -196,6 +227,7 @@ impl VideoWidget {
             aspect_ratio,
             content_fit: config.content_fit,
             scroll_zoom_enabled: config.scroll_zoom_enabled,
+            gestures_enabled: config.gestures_enabled,
             zoom_level: config.zoom_level,
             cover_blend: config
                 .cover_blend
@@ -208,11 +240,11 @@ impl VideoWidget {
 
 impl Widget<crate::app::Message, Theme, Renderer> for VideoWidget {
     fn tag(&self) -> tree::Tag {
-        tree::Tag::of::<PinchState>()
+        tree::Tag::of::<TouchState>()
     }
 
     fn state(&self) -> tree::State {
-        tree::State::new(PinchState::default())
+        tree::State::new(TouchState::default())
     }
 
     fn size(&self) -> Size<Length> {
@@ -263,16 +295,25 @@ impl Widget<crate::app::Message, Theme, Renderer> for VideoWidget {
         shell: &mut Shell<'_, Message>,
         _viewport: &Rectangle,
     ) {
+        let bounds = layout.bounds();
+
+        if self.gestures_enabled {
+            self.track_gestures(
+                tree.state.downcast_mut::<TouchState>(),
+                event,
+                bounds,
+                shell,
+            );
+        }
+
         // Only handle zoom gestures if enabled (photo mode main preview)
         if !self.scroll_zoom_enabled {
             return;
         }
 
-        let bounds = layout.bounds();
-
         // Handle touch events for pinch-to-zoom
         if let Event::Touch(touch_event) = event {
-            let pinch = tree.state.downcast_mut::<PinchState>();
+            let pinch = tree.state.downcast_mut::<TouchState>();
 
             match touch_event {
                 touch::Event::FingerPressed { id, position } => {
@@ -369,6 +410,56 @@ impl Widget<crate::app::Message, Theme, Renderer> for VideoWidget {
     }
 }
 
+impl VideoWidget {
+    /// Feed touch events to the gesture recognizer and publish what it
+    /// recognises. Only presses on the uncovered part of the preview (between
+    /// the top and bottom bars) start a gesture, so swipes on the mode
+    /// carousel or taps on the bar buttons never double as preview gestures.
+    fn track_gestures(
+        &self,
+        state: &mut TouchState,
+        event: &Event,
+        bounds: Rectangle,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        let now = Instant::now();
+        let gesture = match event {
+            Event::Touch(touch::Event::FingerPressed { id, position }) => {
+                let uncovered = Rectangle {
+                    y: bounds.y + self.bar_top_px,
+                    height: (bounds.height - self.bar_top_px - self.bar_bottom_px).max(0.0),
+                    ..bounds
+                };
+                if uncovered.contains(*position) {
+                    state.gestures.finger_pressed(*id, *position, now);
+                }
+                None
+            }
+            Event::Touch(touch::Event::FingerMoved { id, position }) => {
+                state.gestures.finger_moved(*id, *position);
+                None
+            }
+            Event::Touch(touch::Event::FingerLifted { id, position }) => {
+                state.gestures.finger_lifted(*id, *position, now)
+            }
+            Event::Touch(touch::Event::FingerLost { .. }) => {
+                state.gestures.cancel();
+                None
+            }
+            // Any other event (redraws included) is a chance to notice a
+            // finger that has been held long enough.
+            _ => state.gestures.poll(now),
+        };
+
+        if let Some(gesture) = gesture {
+            shell.publish(Message::PreviewGesture(gesture));
+        }
+        if state.gestures.awaiting_long_press() {
+            shell.request_redraw(); // for long-press detection
+        }
+    }
+}
+
 impl<'a> From<VideoWidget> for Element<'a, crate::app::Message, Theme, Renderer> {
     fn from(widget: VideoWidget) -> Self {
         Element::new(widget)
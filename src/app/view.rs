@@ -9,13 +9,20 @@
 //! - Bottom bar (bottom_bar module)
 //! - Format picker overlay (format_picker module)
 
+use crate::app::adaptive_layout::{LayoutVariant, Side};
 use crate::app::bottom_bar::slide_h::SlideH;
+use crate::app::camera_preview::eyedropper::{EyedropperLayer, PreviewMapping};
+use crate::app::camera_preview::loupe::{LOUPE_SIZE, Loupe, LoupeLayer, loupe_origin};
+use crate::app::control_layout::{Control, Placement};
+use crate::app::overlay_snapshot::CountdownOverlay;
 use crate::app::overlay_style::{
     OVERLAY_CONTAINER, PICKER_PANEL, POPUP_PANEL, overlay_chip_button_class,
 };
 use crate::app::preview_geometry::TOP_BAR_HEIGHT;
 use crate::app::qr_overlay::build_qr_overlay;
 use crate::app::state::{AppModel, BurstModeStage, CameraMode, FilterType, Message};
+use crate::app::video_primitive::VIDEO_ID_LOUPE;
+use crate::app::video_widget;
 use crate::constants::resolution_thresholds;
 use crate::constants::ui;
 use crate::fl;
@@ -29,6 +36,8 @@ use tracing::info;
 const FLASH_ICON: &[u8] = include_bytes!("../../resources/button_icons/flash.svg");
 /// Flash off icon SVG (lightning bolt with strike-through)
 const FLASH_OFF_ICON: &[u8] = include_bytes!("../../resources/button_icons/flash-off.svg");
+/// Torch icon SVG (flashlight)
+const TORCH_ICON: &[u8] = include_bytes!("../../resources/button_icons/torch.svg");
 /// Timer off icon SVG
 const TIMER_OFF_ICON: &[u8] = include_bytes!("../../resources/button_icons/timer-off.svg");
 /// Timer 3s icon SVG
@@ -63,14 +72,26 @@ const MOON_ICON: &[u8] = include_bytes!("../../resources/button_icons/moon.svg")
 const MOON_OFF_ICON: &[u8] = include_bytes!("../../resources/button_icons/moon-off.svg");
 /// Camera tilt/motor control icon SVG
 const CAMERA_TILT_ICON: &[u8] = include_bytes!("../../resources/button_icons/camera-tilt.svg");
+/// QR code icon for the "Show QR" tool
+const QR_CODE_ICON: &[u8] = include_bytes!("../../resources/button_icons/qr-code.svg");
 
 /// Burst mode progress bar dimensions
 const BURST_MODE_PROGRESS_BAR_WIDTH: f32 = 200.0;
 const BURST_MODE_PROGRESS_BAR_HEIGHT: f32 = 8.0;
 
+/// Height of the instant preview above the HDR+ progress
+const BURST_MODE_PREVIEW_HEIGHT: f32 = 160.0;
+
 /// Fallback aspect ratio used before the first window-resize event arrives.
 const FALLBACK_ASPECT_RATIO: f32 = 16.0 / 9.0;
 
+/// Width of the white border mirror mode lights the face with, as a share
+/// of the window's shorter side
+const MIRROR_LIGHT_BORDER: f32 = 0.12;
+
+/// Width of the accent ring around the focus loupe
+const LOUPE_RING: f32 = 2.0;
+
 impl AppModel {
     /// Current window aspect ratio, populated from `on_window_resize`. Returns
     /// 16:9 as a fallback before the first resize event.
@@ -92,6 +113,17 @@ impl AppModel {
             && self.screen_height > self.screen_width
     }
 
+    /// Layout variant for the current window size and one-handed setting.
+    /// The capture-area and scrim heights below depend on it, so the view
+    /// and the preview geometry always agree on where the shutter is.
+    pub fn layout_variant(&self) -> LayoutVariant {
+        LayoutVariant::select(
+            self.screen_width,
+            self.screen_height,
+            self.config.one_handed_layout,
+        )
+    }
+
     /// Settled top-bar scrim / shader bar height. 0 in View mode and while
     /// the chrome is hidden (the preview takes the full window in fit/fill);
     /// `TOP_BAR_HEIGHT` otherwise.
@@ -124,6 +156,8 @@ impl AppModel {
     ///   padding and the zoom row's `control_spacing` bottom padding) make
     ///   that line coincide with the midpoint between the capture circle
     ///   and the zoom/fit row above it.
+    /// - **Compact landscape**: the shutter is on the side rail, so every
+    ///   mode uses the non-Photo height below.
     /// - **Other modes**: a quarter of the capture button's bottom padding
     ///   (`space_xs / 4`) above the carousel's top edge — close to the
     ///   carousel but with a small visual gap so the bar doesn't appear
@@ -138,7 +172,7 @@ impl AppModel {
         }
         let spacing = cosmic::theme::spacing();
         let bottom_bar_h = crate::app::bottom_bar::BOTTOM_BAR_HEIGHT;
-        if self.mode == CameraMode::Photo {
+        if self.mode == CameraMode::Photo && !self.layout_variant().has_capture_rail() {
             let capture_h = crate::app::controls::capture_button::CAPTURE_BUTTON_OUTER_SIZE
                 + 2.0 * f32::from(spacing.space_xs);
             bottom_bar_h + capture_h
@@ -164,10 +198,11 @@ impl AppModel {
 
     /// Settled height of the empty placeholder above the bottom bar. 0 in
     /// View (no capture button — fit/zoom row sits flush above the
-    /// carousel) and while the chrome is hidden; the capture button area
+    /// carousel), while the chrome is hidden, and in the compact landscape
+    /// layout (the shutter is on the side rail); the capture button area
     /// otherwise.
     pub fn settled_capture_area_height(&self) -> f32 {
-        if self.mode.is_view_only() || self.ui_hidden {
+        if self.mode.is_view_only() || self.ui_hidden || self.layout_variant().has_capture_rail() {
             0.0
         } else {
             let spacing = cosmic::theme::spacing();
@@ -202,7 +237,11 @@ impl AppModel {
     ///
     /// Used by `build_overlay_popup` to keep popups out of the chip strip.
     fn zoom_chip_strip_height(&self) -> f32 {
-        if self.mode.supports_fit_and_zoom() && !self.tools_menu_visible && !self.ui_hidden {
+        if self.mode.supports_fit_and_zoom()
+            && !self.tools_menu_visible
+            && !self.ui_hidden
+            && self.control_placement(Control::Zoom) != Placement::Hidden
+        {
             let spacing = cosmic::theme::spacing();
             f32::from(spacing.space_l) + f32::from(spacing.space_xs)
         } else {
@@ -213,7 +252,7 @@ impl AppModel {
 
 /// Build a centered overlay popup dialog with icon, title, body text, and optional button
 ///
-/// Used for modal-style popups (privacy warning, flash error). Frosted like the
+/// Used for modal-style popups (flash error). Frosted like the
 /// rest of the overlay chrome: a live-blurred preview backdrop behind the theme's
 /// translucent surface when frosting is on, opaque when it's off. The blur is
 /// what keeps the text legible, so this no longer needs the near-opaque hardcoded
@@ -286,15 +325,27 @@ fn build_overlay_popup<'a>(
         .into()
 }
 
+/// A control resolved against the current mode and the user's layout,
+/// ready to render as a top-bar chip or a tools-menu tile.
+struct ToolEntry {
+    placement: Placement,
+    icon: widget::icon::Handle,
+    label: String,
+    message: Message,
+    active: bool,
+}
+
 /// Create an icon button with a themed background for use on camera preview overlays.
 /// `highlighted = true` switches to the accent (Suggested) class so toggle-state
-/// buttons (flash, HDR, tools menu) show their active state visually.
+/// buttons (flash, HDR, tools menu) show their active state visually. `label`
+/// names the button for screen readers and its tooltip.
 fn overlay_icon_button<'a, M: Clone + 'static>(
     handle: impl Into<widget::icon::Handle>,
+    label: String,
     message: Option<M>,
     highlighted: bool,
 ) -> Element<'a, M> {
-    let mut button = widget::button::icon(handle).extra_small();
+    let mut button = widget::button::icon(handle).extra_small().tooltip(label);
     if highlighted {
         button = button.class(cosmic::theme::Button::Suggested);
     }
@@ -437,6 +488,28 @@ impl AppModel {
         // Camera preview from camera_preview module
         let camera_preview = self.build_camera_preview();
 
+        // Latency measurement or A/V sync test - the whole window black or
+        // white, no UI
+        let test_flash = match self.av_sync_test {
+            crate::app::state::AvSyncTestState::Recording { flashing } => Some(flashing),
+            _ => self.latency_probe.as_ref().map(|probe| probe.is_flashing()),
+        };
+        if let Some(flashing) = test_flash {
+            let color = if flashing { Color::WHITE } else { Color::BLACK };
+            return widget::container(
+                widget::Space::new()
+                    .width(Length::Fill)
+                    .height(Length::Fill),
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(move |_theme| widget::container::Style {
+                background: Some(Background::Color(color)),
+                ..Default::default()
+            })
+            .into();
+        }
+
         // Flash mode - show only preview with white overlay, no UI
         // Only show screen flash overlay for front cameras (back cameras use hardware LED)
         if self.flash.active && !self.use_hardware_flash() {
@@ -483,23 +556,45 @@ impl AppModel {
             .into();
         }
 
+        if self.mirror_mode {
+            return self.build_mirror_view(camera_preview);
+        }
+
+        if self.qr_share.as_ref().is_some_and(|share| share.fullscreen) {
+            return self.build_qr_share_fullscreen();
+        }
+
         // Build top bar
         let top_bar = self.build_top_bar();
 
         // Zoom/fit row is shown in modes that allow manual zoom and the
         // fit-to-view toggle (Photo, View), and never while the chrome is
         // hidden.
-        let show_zoom_label = self.mode.supports_fit_and_zoom() && !self.ui_hidden;
+        let show_zoom_label = self.mode.supports_fit_and_zoom()
+            && !self.ui_hidden
+            && self.control_placement(Control::Zoom) != Placement::Hidden;
 
         // Capture button area - changes based on recording/streaming state and video file selection
         // Check if we have video file controls (play/pause button for video file sources)
         let play_pause_button = self.build_video_play_pause_button();
         let has_video_controls = play_pause_button.is_some();
+        let layout = self.layout_variant();
+        let recording_row = (self.recording.is_recording() && !self.quick_record.is_recording())
+            || self.virtual_camera.is_streaming();
 
-        let capture_button_only = if (self.recording.is_recording()
-            && !self.quick_record.is_recording())
-            || self.virtual_camera.is_streaming()
-        {
+        let mut capture_rail: Option<Element<'_, Message>> = None;
+
+        let capture_button_only = if layout.has_capture_rail() {
+            // Compact landscape: the shutter moves to a rail on the right
+            // edge and this row collapses to zero height.
+            if !self.mode.is_view_only() && !self.ui_hidden {
+                capture_rail = Some(self.build_capture_rail(play_pause_button, recording_row));
+            }
+            widget::Space::new()
+                .width(Length::Fill)
+                .height(Length::Shrink)
+                .into()
+        } else if recording_row {
             // Mirror the bottom bar's three-column layout so the stop circle
             // sits where the carousel does and the photo button lines up with
             // the camera-switch position. `three_col_row` is the shared shape;
@@ -510,7 +605,7 @@ impl AppModel {
             let slide = std::sync::Arc::clone(&self.carousel_button_slide);
 
             let spacing = cosmic::theme::spacing();
-            let side_width = ui::PLACEHOLDER_BUTTON_WIDTH;
+            let side_width = layout.side_button_size();
             let center_width = crate::app::bottom_bar::mode_carousel::carousel_width_for_modes(
                 &self.available_modes(),
             );
@@ -532,6 +627,15 @@ impl AppModel {
                     .into()
             };
 
+            // One-handed on the left: the photo button swaps to the thumb's
+            // side, sliding with the gallery button instead of the switcher.
+            let (left_slot, right_slot): (Element<'_, Message>, Element<'_, Message>) =
+                if layout == LayoutVariant::OneHanded(Side::Left) {
+                    (SlideH::new(photo_button, slide, 1.0).into(), left_slot)
+                } else {
+                    (left_slot, SlideH::new(photo_button, slide, -1.0).into())
+                };
+
             // Vertical padding matches build_capture_button so the circle
             // doesn't shift when the layout flips between idle and recording.
             crate::app::bottom_bar::three_col_row(
@@ -549,7 +653,7 @@ impl AppModel {
                     std::sync::Arc::clone(&self.carousel_button_slide),
                 )
                 .into(),
-                SlideH::new(photo_button, slide, -1.0).into(),
+                right_slot,
                 [spacing.space_xs, spacing.space_m],
             )
         } else if has_video_controls {
@@ -591,6 +695,19 @@ impl AppModel {
                 .width(Length::Fill);
 
             row.into()
+        } else if let LayoutVariant::OneHanded(side) = layout {
+            // One-handed: shutter in the chosen bottom corner, in line with
+            // the gallery / switcher column below it. Same vertical padding
+            // as build_capture_button so the capture area height holds.
+            let spacing = cosmic::theme::spacing();
+            widget::container(self.build_capture_circle())
+                .width(Length::Fill)
+                .align_x(match side {
+                    Side::Left => cosmic::iced::alignment::Horizontal::Left,
+                    Side::Right => cosmic::iced::alignment::Horizontal::Right,
+                })
+                .padding([spacing.space_xs, spacing.space_m])
+                .into()
         } else {
             // Normal single capture button
             self.build_capture_button()
@@ -652,9 +769,65 @@ impl AppModel {
                 bottom_controls = bottom_controls.push(capture_button_area).push(bottom_area);
             }
 
-            // Bottom section: zoom label + bottom controls
+            // Bottom section: storage warning + error notice + clipboard notice + share chip
+            // + scene suggestion + zoom label + bottom controls
             let mut bottom_section = widget::Column::new().width(Length::Fill);
 
+            if !self.tools_menu_visible
+                && let Some(chip) = self.build_storage_warning_chip()
+            {
+                bottom_section = bottom_section.push(
+                    widget::container(chip)
+                        .width(Length::Fill)
+                        .center_x(Length::Fill)
+                        .padding([0, 0, control_spacing, 0]),
+                );
+            }
+
+            if !self.tools_menu_visible
+                && let Some(chip) = self.build_error_notice_chip()
+            {
+                bottom_section = bottom_section.push(
+                    widget::container(chip)
+                        .width(Length::Fill)
+                        .center_x(Length::Fill)
+                        .padding([0, 0, control_spacing, 0]),
+                );
+            }
+
+            if self.clipboard_notice.is_some() && !self.tools_menu_visible {
+                bottom_section = bottom_section.push(
+                    widget::container(self.build_clipboard_notice_chip())
+                        .width(Length::Fill)
+                        .center_x(Length::Fill)
+                        .padding([0, 0, control_spacing, 0]),
+                );
+            }
+
+            if !self.ui_hidden
+                && !self.tools_menu_visible
+                && let Some(chip) = self.build_share_chip()
+            {
+                bottom_section = bottom_section.push(
+                    widget::container(chip)
+                        .width(Length::Fill)
+                        .center_x(Length::Fill)
+                        .padding([0, 0, control_spacing, 0]),
+                );
+            }
+
+            if !self.ui_hidden
+                && !self.tools_menu_visible
+                && let Some(chip) = self.build_scene_suggestion_chip()
+            {
+                bottom_section = bottom_section.push(
+                    widget::container(chip)
+                        .width(Length::Fill)
+                        .center_x(Length::Fill)
+                        .padding([0, 0, control_spacing, 0]),
+                );
+            }
+
             // Hide the fit/zoom row while the tools menu is open so the two
             // don't visually compete — the menu itself is shown as an overlay.
             if show_zoom_label && !self.tools_menu_visible {
@@ -709,16 +882,19 @@ impl AppModel {
             // The shader handles the Cover/Contain blend via cover_blend(), so
             // the preview always uses Cover layout (fills the window).  The shader
             // zooms out to show the full frame in Contain mode, with transparent
-            // letterbox areas.
-            let camera_layer: Element<'_, Message> = camera_preview;
+            // letterbox areas. The side-by-side dual camera layout gives it
+            // the left half of the window.
+            let camera_preview = self.with_loupe_layer(camera_preview);
+            let camera_layer: Element<'_, Message> = self.side_by_side_preview(camera_preview);
 
             let mut main_stack = cosmic::iced::widget::stack![
                 camera_layer,
                 self.frosted_bars(),
                 self.build_crop_overlay(),
                 self.build_composition_overlay(),
+                self.build_whiteboard_overlay(),
                 self.build_qr_overlay(),
-                self.build_privacy_warning(),
+                self.build_pip_inset(),
                 widget::container(top_bar)
                     .width(Length::Fill)
                     .align_y(cosmic::iced::alignment::Vertical::Top),
@@ -728,12 +904,38 @@ impl AppModel {
                     .align_y(cosmic::iced::alignment::Vertical::Bottom)
             ];
 
+            // Above the bars, so the marker is visible right at the edge
+            main_stack = main_stack.push(self.build_look_here_overlay());
+
             if self.flash.error_popup.is_some() {
                 main_stack = main_stack.push(self.build_flash_error_popup());
             }
 
-            if let Some(remaining) = self.photo_timer_countdown {
-                main_stack = main_stack.push(self.build_timer_overlay(remaining));
+            if let Some(rail) = capture_rail {
+                main_stack = main_stack.push(
+                    widget::container(rail)
+                        .width(Length::Fill)
+                        .height(Length::Fill)
+                        .align_x(cosmic::iced::alignment::Horizontal::Right)
+                        .center_y(Length::Fill)
+                        .padding([0, spacing.space_m]),
+                );
+            }
+
+            if let Some(countdown) = self.overlay_snapshot().countdown {
+                main_stack = main_stack.push(self.build_timer_overlay(countdown));
+            }
+
+            if self.wb_eyedropper_active {
+                main_stack = main_stack.push(self.build_wb_eyedropper());
+            }
+
+            if let Some(loupe) = self
+                .loupe
+                .as_ref()
+                .and_then(|loupe| self.build_loupe(loupe))
+            {
+                main_stack = main_stack.push(loupe);
             }
 
             main_stack.width(Length::Fill).height(Length::Fill).into()
@@ -763,6 +965,16 @@ impl AppModel {
             main_stack = main_stack.push(self.build_motor_picker());
         }
 
+        // Add rapid burst best-shot picker after a burst is saved
+        if self.burst_picker.is_some() {
+            main_stack = main_stack.push(self.build_burst_picker());
+        }
+
+        // Add the "Show QR" composer while it is open
+        if self.qr_share.is_some() {
+            main_stack = main_stack.push(self.build_qr_share());
+        }
+
         // Add tools menu overlay if visible
         if self.tools_menu_visible {
             main_stack = main_stack.push(self.build_tools_menu());
@@ -779,6 +991,29 @@ impl AppModel {
             .into()
     }
 
+    /// Shutter rail for the compact landscape layout, centred on the right
+    /// edge of the preview. Carries the same controls as the capture row it
+    /// replaces: the photo button above the stop circle while recording or
+    /// streaming, the play/pause button above the shutter for a video file
+    /// source.
+    fn build_capture_rail<'a>(
+        &'a self,
+        play_pause_button: Option<Element<'a, Message>>,
+        recording: bool,
+    ) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+        let mut rail = widget::Column::new()
+            .spacing(spacing.space_s)
+            .align_x(Alignment::Center);
+        if recording {
+            rail = rail.push(self.build_photo_during_recording_button());
+        }
+        if let Some(pp_button) = play_pause_button {
+            rail = rail.push(pp_button);
+        }
+        rail.push(self.build_capture_circle()).into()
+    }
+
     /// Build the top bar with recording indicator and format button
     fn build_top_bar(&self) -> Element<'_, Message> {
         // View mode and hidden chrome strip every top-bar button (and the
@@ -817,18 +1052,66 @@ impl AppModel {
             row = row.push(widget::space::horizontal().width(spacing.space_s));
         }
 
+        // Show clip indicator while a GIF/WebP clip is captured or saved
+        if let Some(indicator) = self.build_animated_clip_indicator() {
+            row = row.push(indicator);
+            row = row.push(widget::space::horizontal().width(spacing.space_s));
+        }
+
         // Show streaming indicator when streaming virtual camera
         if let Some(indicator) = self.build_streaming_indicator() {
             row = row.push(indicator);
             row = row.push(widget::space::horizontal().width(spacing.space_s));
         }
 
+        // Show failover indicator when the standby camera took over
+        if let Some(indicator) = self.build_failover_indicator() {
+            row = row.push(indicator);
+            row = row.push(widget::space::horizontal().width(spacing.space_s));
+        }
+
+        // Show lock indicator while a long-press exposure/focus lock is held
+        if let Some(indicator) = self.build_lock_indicator() {
+            row = row.push(indicator);
+            row = row.push(widget::space::horizontal().width(spacing.space_s));
+        }
+
+        // Show that a capture card runs for low latency, and how low
+        if let Some(indicator) = self.build_capture_card_indicator() {
+            row = row.push(indicator);
+            row = row.push(widget::space::horizontal().width(spacing.space_s));
+        }
+
+        // Show the live exposure/focus values when the OSD is on
+        if let Some(indicator) = self.build_exposure_osd_indicator() {
+            row = row.push(indicator);
+            row = row.push(widget::space::horizontal().width(spacing.space_s));
+        }
+
         // Show timelapse indicator when timelapse is running
         if let Some(indicator) = self.build_timelapse_indicator() {
             row = row.push(indicator);
             row = row.push(widget::space::horizontal().width(spacing.space_s));
         }
 
+        // Show the scanned page count and document actions in Document mode
+        if let Some(indicator) = self.build_document_indicator() {
+            row = row.push(indicator);
+            row = row.push(widget::space::horizontal().width(spacing.space_s));
+        }
+
+        // Show the snapshot count and board actions in Whiteboard mode
+        if let Some(indicator) = self.build_whiteboard_indicator() {
+            row = row.push(indicator);
+            row = row.push(widget::space::horizontal().width(spacing.space_s));
+        }
+
+        // Show the code count and list actions in Scan mode
+        if let Some(indicator) = self.build_scan_indicator() {
+            row = row.push(indicator);
+            row = row.push(widget::space::horizontal().width(spacing.space_s));
+        }
+
         // Show format/resolution button in both photo and video modes
         // Hide button when:
         // - Format picker is visible
@@ -859,23 +1142,14 @@ impl AppModel {
                 .height(Length::Shrink),
         );
 
-        // Top-bar toggle buttons (flash, HDR, file, tools) are always
-        // shown. Picker overlays appear on top of them but never replace them.
-        // Flash toggle button (Photo mode, or Video/Timelapse mode with hardware flash for torch)
-        if self.mode == CameraMode::Photo
-            || ((self.mode == CameraMode::Video || self.mode == CameraMode::Timelapse)
-                && self.use_hardware_flash())
-        {
-            let flash_icon_bytes = if self.flash.enabled {
-                FLASH_ICON
-            } else {
-                FLASH_OFF_ICON
-            };
-            let flash_icon = widget::icon::from_svg_bytes(flash_icon_bytes).symbolic(true);
-
+        // Controls the user placed in the top bar (flash and HDR+ by
+        // default). Picker overlays appear on top of them but never replace
+        // them.
+        let entries = self.tool_entries();
+        for entry in entries.iter().filter(|e| e.placement == Placement::Bar) {
             if is_disabled {
                 row = row.push(
-                    widget::container(widget::icon(flash_icon).size(20))
+                    widget::container(widget::icon(entry.icon.clone()).size(20))
                         .style(|_theme| widget::container::Style {
                             text_color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.3)),
                             ..Default::default()
@@ -884,9 +1158,10 @@ impl AppModel {
                 );
             } else {
                 row = row.push(overlay_icon_button(
-                    flash_icon,
-                    Some(Message::ToggleFlash),
-                    self.flash.enabled,
+                    entry.icon.clone(),
+                    entry.label.clone(),
+                    Some(entry.message.clone()),
+                    entry.active,
                 ));
             }
 
@@ -896,41 +1171,6 @@ impl AppModel {
                     .width(Length::Fixed(5.0))
                     .height(Length::Shrink),
             );
-
-            if self.should_show_burst_button() {
-                // Show moon-off icon when HDR+ is disabled (by override or setting)
-                let is_hdr_active = self.would_use_burst_mode();
-                let moon_icon_bytes = if is_hdr_active {
-                    MOON_ICON
-                } else {
-                    MOON_OFF_ICON
-                };
-                let moon_icon = widget::icon::from_svg_bytes(moon_icon_bytes).symbolic(true);
-
-                if is_disabled {
-                    row = row.push(
-                        widget::container(widget::icon(moon_icon).size(20))
-                            .style(|_theme| widget::container::Style {
-                                text_color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.3)),
-                                ..Default::default()
-                            })
-                            .padding([4, 8]),
-                    );
-                } else {
-                    row = row.push(overlay_icon_button(
-                        moon_icon,
-                        Some(Message::ToggleBurstMode),
-                        is_hdr_active,
-                    ));
-                }
-
-                // 5px spacing
-                row = row.push(
-                    widget::Space::new()
-                        .width(Length::Fixed(5.0))
-                        .height(Length::Shrink),
-                );
-            }
         }
 
         // File open button (only in Virtual mode, hidden when streaming)
@@ -938,7 +1178,8 @@ impl AppModel {
             let has_file = self.virtual_camera_file_source.is_some();
             if is_disabled {
                 let file_button =
-                    widget::button::icon(icon::from_name("document-open-symbolic").symbolic(true));
+                    widget::button::icon(icon::from_name("document-open-symbolic").symbolic(true))
+                        .tooltip(fl!("a11y-open-file"));
                 row = row.push(widget::container(file_button).style(|_theme| {
                     widget::container::Style {
                         text_color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.3)),
@@ -946,16 +1187,32 @@ impl AppModel {
                     }
                 }));
             } else {
-                let message = if has_file {
-                    Message::ClearVirtualCameraFile
+                let (message, label) = if has_file {
+                    (Message::ClearVirtualCameraFile, fl!("a11y-clear-file"))
                 } else {
-                    Message::OpenVirtualCameraFile
+                    (Message::OpenVirtualCameraFile, fl!("a11y-open-file"))
                 };
                 row = row.push(overlay_icon_button(
                     icon::from_name("document-open-symbolic").symbolic(true),
+                    label,
                     Some(message),
                     has_file,
                 ));
+                // A folder plays as a playlist; clearing goes through the
+                // button above
+                if !has_file {
+                    row = row.push(
+                        widget::Space::new()
+                            .width(Length::Fixed(5.0))
+                            .height(Length::Shrink),
+                    );
+                    row = row.push(overlay_icon_button(
+                        icon::from_name("folder-open-symbolic").symbolic(true),
+                        fl!("a11y-open-folder"),
+                        Some(Message::OpenVirtualCameraFolder),
+                        false,
+                    ));
+                }
             }
 
             // 5px spacing
@@ -967,11 +1224,13 @@ impl AppModel {
         }
 
         // Tools menu button (opens overlay with timer, aspect ratio, exposure, filter, motor)
-        // Highlight when tools menu is open or any tool setting is non-default
+        // Highlight when tools menu is open or any tool setting is non-default.
+        // Dropped when the layout leaves the menu empty.
         let tools_active = self.tools_menu_visible || self.has_non_default_tool_settings();
         let tools_icon = widget::icon::from_svg_bytes(TOOLS_GRID_ICON).symbolic(true);
+        let has_tools = entries.iter().any(|e| e.placement == Placement::Tools);
 
-        if is_disabled {
+        if has_tools && is_disabled {
             row = row.push(
                 widget::container(widget::icon(tools_icon).size(20))
                     .style(|_theme| widget::container::Style {
@@ -980,9 +1239,10 @@ impl AppModel {
                     })
                     .padding([4, 8]),
             );
-        } else {
+        } else if has_tools {
             row = row.push(overlay_icon_button(
                 tools_icon,
+                fl!("action-toggle-tools-menu"),
                 Some(Message::ToggleToolsMenu),
                 tools_active,
             ));
@@ -993,6 +1253,7 @@ impl AppModel {
             row = row.push(
                 widget::button::icon(icon::from_name("preferences-system-symbolic").symbolic(true))
                     .extra_small()
+                    .tooltip(fl!("settings-title"))
                     .on_press(Message::ToggleContextPage(
                         crate::app::state::ContextPage::Settings,
                     )),
@@ -1009,16 +1270,19 @@ impl AppModel {
             .push(
                 widget::button::icon(icon::from_name("window-minimize-symbolic").symbolic(true))
                     .extra_small()
+                    .tooltip(fl!("a11y-minimize"))
                     .on_press(Message::WindowMinimize),
             )
             .push(
                 widget::button::icon(icon::from_name("window-maximize-symbolic").symbolic(true))
                     .extra_small()
+                    .tooltip(fl!("a11y-maximize"))
                     .on_press(Message::WindowToggleMaximize),
             )
             .push(
                 widget::button::icon(icon::from_name("window-close-symbolic").symbolic(true))
                     .extra_small()
+                    .tooltip(fl!("a11y-close"))
                     .on_press(Message::WindowClose),
             );
 
@@ -1164,14 +1428,176 @@ impl AppModel {
         }
     }
 
+    /// Build the scene suggestion chip shown above the zoom row
+    ///
+    /// Tapping the label applies the suggested setting; the close button
+    /// hides the suggestion for the rest of the session.
+    fn build_scene_suggestion_chip(&self) -> Option<Element<'_, Message>> {
+        use crate::app::frame_processor::Scene;
+
+        let scene = self.scene_suggestion?;
+        // Night mode offers HDR+ even with scene suggestions off
+        let from_night_mode = scene == Scene::LowLight && self.light_meter_armed();
+        if !self.scene_suggestions_armed() && !from_night_mode {
+            return None;
+        }
+        let label = match scene {
+            Scene::LowLight => fl!("scene-low-light"),
+            Scene::Document => fl!("scene-document"),
+            Scene::QrCodes => fl!("scene-qr"),
+            Scene::Macro => fl!("scene-macro"),
+            Scene::Portrait => fl!("scene-portrait"),
+        };
+
+        let chip = widget::Row::new()
+            .push(
+                widget::button::text(label)
+                    .on_press(Message::ApplySceneSuggestion)
+                    .class(overlay_chip_button_class()),
+            )
+            .push(
+                widget::button::icon(icon::from_name("window-close-symbolic").symbolic(true))
+                    .extra_small()
+                    .tooltip(fl!("a11y-dismiss"))
+                    .on_press(Message::DismissSceneSuggestion),
+            )
+            .align_y(Alignment::Center);
+        Some(self.frosted_panel(chip.into(), OVERLAY_CONTAINER))
+    }
+
+    /// Chip telling how little space is left on the output filesystem.
+    /// Once closed it stays hidden, unless the disk fills up to the point
+    /// where captures are refused.
+    fn build_storage_warning_chip(&self) -> Option<Element<'_, Message>> {
+        use crate::storage::SpaceLevel;
+
+        let (level, free) = self.storage_warning?;
+        if self.storage_warning_dismissed && level != SpaceLevel::Full {
+            return None;
+        }
+        let free = crate::storage::format_size(free);
+        let label = match level {
+            SpaceLevel::Full => fl!("storage-full", free = free),
+            _ => fl!("storage-low", free = free),
+        };
+
+        let chip = widget::Row::new()
+            .push(
+                widget::icon::from_name("dialog-warning-symbolic")
+                    .symbolic(true)
+                    .size(16),
+            )
+            .push(widget::text(label).size(14))
+            .push(
+                widget::button::icon(icon::from_name("window-close-symbolic").symbolic(true))
+                    .extra_small()
+                    .tooltip(fl!("a11y-dismiss"))
+                    .on_press(Message::DismissStorageWarning),
+            )
+            .spacing(8)
+            .padding([0, 0, 0, 8])
+            .align_y(Alignment::Center);
+        Some(self.frosted_panel(chip.into(), OVERLAY_CONTAINER))
+    }
+
+    /// What failed, and below it what to do about it
+    fn build_error_notice_chip(&self) -> Option<Element<'_, Message>> {
+        let (error, _) = self.error_notice.as_ref()?;
+        let mut text = widget::Column::new().push(widget::text(error.summary()).size(14));
+        if let Some(remedy) = error.remedy() {
+            text = text.push(widget::text(remedy).size(12));
+        }
+
+        let chip = widget::Row::new()
+            .push(
+                widget::icon::from_name("dialog-error-symbolic")
+                    .symbolic(true)
+                    .size(16),
+            )
+            .push(text.spacing(2))
+            .push(
+                widget::button::icon(icon::from_name("window-close-symbolic").symbolic(true))
+                    .extra_small()
+                    .tooltip(fl!("a11y-dismiss"))
+                    .on_press(Message::DismissErrorNotice),
+            )
+            .spacing(8)
+            .padding([4, 0, 4, 8])
+            .align_y(Alignment::Center);
+        Some(self.frosted_panel(chip.into(), OVERLAY_CONTAINER))
+    }
+
+    /// Brief notice that a photo went to the clipboard rather than a file
+    fn build_clipboard_notice_chip(&self) -> Element<'_, Message> {
+        let chip = widget::Row::new()
+            .push(
+                widget::icon::from_name("edit-copy-symbolic")
+                    .symbolic(true)
+                    .size(16),
+            )
+            .push(widget::text(fl!("photo-copied")).size(14))
+            .spacing(8)
+            .padding([4, 8])
+            .align_y(Alignment::Center);
+        self.frosted_panel(chip.into(), OVERLAY_CONTAINER)
+    }
+
+    /// Share chip: a "Share" prompt after a capture, expanding into the
+    /// share targets
+    fn build_share_chip(&self) -> Option<Element<'_, Message>> {
+        use crate::share::ShareTarget;
+
+        let menu = self.share_menu.as_ref()?;
+        let button = |label: String, message: Message| {
+            widget::button::text(label)
+                .on_press(message)
+                .class(overlay_chip_button_class())
+        };
+
+        let mut chip = widget::Row::new().align_y(Alignment::Center);
+        if menu.expanded {
+            chip = chip
+                .push(button(
+                    fl!("share-open-with"),
+                    Message::Share(ShareTarget::OpenWith),
+                ))
+                .push(button(
+                    fl!("share-email"),
+                    Message::Share(ShareTarget::Email),
+                ));
+            for (index, target) in self.share_targets.targets.iter().enumerate() {
+                chip = chip.push(button(
+                    target.name.clone(),
+                    Message::Share(ShareTarget::Custom(index)),
+                ));
+            }
+        } else {
+            chip = chip.push(button(
+                fl!("share"),
+                Message::ShowShareMenu(Some(menu.path.clone())),
+            ));
+        }
+        chip = chip.push(
+            widget::button::icon(icon::from_name("window-close-symbolic").symbolic(true))
+                .extra_small()
+                .tooltip(fl!("a11y-dismiss"))
+                .on_press(Message::DismissShareMenu),
+        );
+        Some(self.frosted_panel(chip.into(), OVERLAY_CONTAINER))
+    }
+
     /// Build the QR code overlay layer
     ///
     /// This creates an overlay that shows detected QR codes with bounding boxes
     /// and action buttons. The overlay widget handles coordinate transformation
     /// at render time to correctly position elements over the video content.
     fn build_qr_overlay(&self) -> Element<'_, Message> {
-        // Only show overlay if QR detection is enabled and we have detections
-        if !self.qr_detection_enabled || self.qr_detections.is_empty() {
+        // Only show overlay if QR detection is enabled and we have detections.
+        // Scan mode lists codes instead of offering their actions.
+        if !self.qr_detection_enabled
+            || self.qr_detections.is_empty()
+            || self.mode == CameraMode::Scan
+        {
             return widget::Space::new()
                 .width(Length::Fill)
                 .height(Length::Fill)
@@ -1194,6 +1620,7 @@ impl AppModel {
         // window in Contain mode where the video is actually letterboxed.
         build_qr_overlay(
             &self.qr_detections,
+            self.wifi_join.as_ref(),
             frame.width,
             frame.height,
             self.cover_blend(),
@@ -1203,107 +1630,242 @@ impl AppModel {
         )
     }
 
+    /// Build the white balance eyedropper: a hint chip over a layer that
+    /// turns the next click on the preview into a gray-point pick
+    fn build_wb_eyedropper(&self) -> Element<'_, Message> {
+        let spacing = cosmic::theme::spacing();
+        let (frame_width, frame_height) = self
+            .current_frame
+            .as_ref()
+            .map(|frame| (frame.width, frame.height))
+            .unwrap_or_default();
+
+        let layer = EyedropperLayer::new(PreviewMapping {
+            frame_width,
+            frame_height,
+            cover_blend: self.cover_blend(),
+            top_bar_h: self.top_ui_height(),
+            bottom_bar_h: self.bottom_ui_height(),
+            mirrored: self.should_mirror_preview(),
+            zoom_level: self.current_zoom_level(),
+        });
+
+        let hint = widget::container(
+            widget::container(widget::text(fl!("color-pick-white-hint")).size(13))
+                .padding([spacing.space_xxs, spacing.space_s])
+                .style(OVERLAY_CONTAINER.style()),
+        )
+        .center_x(Length::Fill)
+        .padding([TOP_BAR_HEIGHT as u16 + spacing.space_xs, 0, 0, 0]);
+
+        cosmic::iced::widget::stack![layer, hint]
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
+    /// Put the layer that steers the focus loupe over the preview, while the
+    /// loupe is open. It goes under the bars, so their buttons still work.
+    fn with_loupe_layer<'a>(
+        &'a self,
+        camera_preview: Element<'a, Message>,
+    ) -> Element<'a, Message> {
+        let Some(loupe) = self.loupe.as_ref() else {
+            return camera_preview;
+        };
+        let Some(config) = self.preview_video_config(VIDEO_ID_LOUPE, self.preview_transforms())
+        else {
+            return camera_preview;
+        };
+        let (frame_width, frame_height) = self
+            .current_frame
+            .as_ref()
+            .map(|frame| (frame.width, frame.height))
+            .unwrap_or_default();
+        // The mapping works on the picture as displayed
+        let (frame_width, frame_height) = if config.rotation == 1 || config.rotation == 3 {
+            (frame_height, frame_width)
+        } else {
+            (frame_width, frame_height)
+        };
+
+        let layer = LoupeLayer::new(
+            PreviewMapping {
+                frame_width,
+                frame_height,
+                cover_blend: self.cover_blend(),
+                top_bar_h: self.top_ui_height(),
+                bottom_bar_h: self.bottom_ui_height(),
+                mirrored: config.mirror_horizontal,
+                zoom_level: config.zoom_level,
+            },
+            config.rotation,
+            loupe.pinned,
+        );
+        cosmic::iced::widget::stack![camera_preview, layer]
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
+    /// Build the focus loupe: the sensor's pixels at 1:1 around the point it
+    /// is on, beside that point, with a hint on how to pin it
+    fn build_loupe(&self, loupe: &Loupe) -> Option<Element<'_, Message>> {
+        let frame = self.current_frame.as_ref()?;
+        let preview = self.preview_video_config(VIDEO_ID_LOUPE, self.preview_transforms())?;
+        let spacing = cosmic::theme::spacing();
+        let theme = cosmic::theme::active();
+        let cosmic = theme.cosmic();
+        let radius = cosmic.corner_radii.radius_m[0];
+        let accent: Color = cosmic.accent_color().into();
+
+        // Only the preview's orientation and look carry over: the crop is
+        // the loupe's own, with nothing zoomed, squeezed or letterboxed
+        let config = video_widget::VideoWidgetConfig {
+            content_fit: video_widget::VideoContentFit::Cover,
+            corner_radius: radius,
+            crop_uv: None,
+            zoom_level: 1.0,
+            scroll_zoom_enabled: false,
+            gestures_enabled: false,
+            cover_blend: Some(0.0),
+            bar_top_px: 0.0,
+            bar_bottom_px: 0.0,
+            desqueeze: 1.0,
+            loupe_center: Some(loupe.center),
+            ..preview
+        };
+        let lens = widget::container(video_widget::video_widget(frame.clone(), config))
+            .width(Length::Fixed(LOUPE_SIZE))
+            .height(Length::Fixed(LOUPE_SIZE))
+            .padding(LOUPE_RING)
+            .style(move |_theme| widget::container::Style {
+                background: Some(Background::Color(accent)),
+                border: cosmic::iced::Border {
+                    radius: (radius + LOUPE_RING).into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        let origin = loupe_origin(
+            loupe.anchor,
+            cosmic::iced::Size::new(self.screen_width, self.screen_height),
+        );
+        let placed = widget::container(lens)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding([origin.y, 0.0, 0.0, origin.x]);
+
+        let hint_text = if loupe.pinned {
+            fl!("loupe-pinned-hint")
+        } else {
+            fl!("loupe-hint")
+        };
+        let hint = widget::container(
+            widget::container(widget::text(hint_text).size(13))
+                .padding([spacing.space_xxs, spacing.space_s])
+                .style(OVERLAY_CONTAINER.style()),
+        )
+        .center_x(Length::Fill)
+        .padding([TOP_BAR_HEIGHT as u16 + spacing.space_xs, 0, 0, 0]);
+
+        Some(
+            cosmic::iced::widget::stack![placed, hint]
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into(),
+        )
+    }
+
     /// Build the tools menu overlay
     ///
     /// Shows timer, aspect ratio, exposure, filter buttons
     /// in a floating panel aligned to the top-right with large icon buttons in a 2-row grid.
-    fn build_tools_menu(&self) -> Element<'_, Message> {
+    /// Mirror mode: the preview edge to edge, or inside a white border when
+    /// the screen light is on, with only the light toggle and the way out
+    fn build_mirror_view<'a>(
+        &'a self,
+        camera_preview: Element<'a, Message>,
+    ) -> Element<'a, Message> {
         let spacing = cosmic::theme::spacing();
-        let is_photo_mode = self.mode == CameraMode::Photo;
-
-        // Collect all tool buttons for the grid
-        let mut buttons: Vec<Element<'_, Message>> = Vec::new();
-
-        // Timer button (Photo mode only)
-        if is_photo_mode {
-            let timer_active =
-                self.photo_timer_setting != crate::app::state::PhotoTimerSetting::Off;
-            let timer_icon_bytes = match self.photo_timer_setting {
-                crate::app::state::PhotoTimerSetting::Off => TIMER_OFF_ICON,
-                crate::app::state::PhotoTimerSetting::Sec3 => TIMER_3_ICON,
-                crate::app::state::PhotoTimerSetting::Sec5 => TIMER_5_ICON,
-                crate::app::state::PhotoTimerSetting::Sec10 => TIMER_10_ICON,
-            };
-            let timer_icon = widget::icon::from_svg_bytes(timer_icon_bytes).symbolic(true);
-            buttons.push(self.build_tools_grid_button(
-                timer_icon,
-                fl!("tools-timer"),
-                Message::CyclePhotoTimer,
-                timer_active,
-            ));
-
-            // Aspect ratio button (Photo mode only). Square ratios (Native,
-            // 1:1) are orientation-agnostic; the others swap to their
-            // portrait companion icon when the window is taller than wide
-            // so the label matches the rotated preview.
-            let aspect_active = self.is_aspect_ratio_changed();
-            let portrait = self.screen_is_portrait();
-            let aspect_icon_bytes = match self.photo_aspect_ratio {
-                crate::app::state::PhotoAspectRatio::Native => ASPECT_NATIVE_ICON,
-                crate::app::state::PhotoAspectRatio::Ratio1x1 => ASPECT_1_1_ICON,
-                crate::app::state::PhotoAspectRatio::Ratio4x3 if portrait => ASPECT_3_4_ICON,
-                crate::app::state::PhotoAspectRatio::Ratio4x3 => ASPECT_4_3_ICON,
-                crate::app::state::PhotoAspectRatio::Ratio16x9 if portrait => ASPECT_9_16_ICON,
-                crate::app::state::PhotoAspectRatio::Ratio16x9 => ASPECT_16_9_ICON,
-                crate::app::state::PhotoAspectRatio::Ratio2x1 if portrait => ASPECT_1_2_ICON,
-                crate::app::state::PhotoAspectRatio::Ratio2x1 => ASPECT_2_1_ICON,
-            };
-            let aspect_icon = widget::icon::from_svg_bytes(aspect_icon_bytes).symbolic(true);
-            buttons.push(self.build_tools_grid_button(
-                aspect_icon,
-                fl!("tools-aspect"),
-                Message::CyclePhotoAspectRatio,
-                aspect_active,
-            ));
-        }
+        let light = self.config.mirror_light;
+        let border = if light {
+            MIRROR_LIGHT_BORDER * self.screen_width.min(self.screen_height)
+        } else {
+            0.0
+        };
 
-        // Exposure button
-        if self.available_exposure_controls.has_any_essential() {
-            let exposure_icon = widget::icon::from_svg_bytes(EXPOSURE_ICON).symbolic(true);
-            buttons.push(self.build_tools_grid_button(
-                exposure_icon,
-                fl!("tools-exposure"),
-                Message::ToggleExposurePicker,
-                self.is_exposure_changed(),
-            ));
-        }
+        let picture = widget::container(camera_preview)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(border)
+            .style(move |theme| widget::container::Style {
+                background: Some(Background::Color(if light {
+                    Color::WHITE
+                } else {
+                    theme.cosmic().bg_color().into()
+                })),
+                ..Default::default()
+            });
 
-        // Color button (for contrast, saturation, white balance, etc.)
-        if self.available_exposure_controls.has_any_image_controls()
-            || self.available_exposure_controls.has_any_white_balance()
-        {
-            buttons.push(self.build_tools_grid_button(
-                icon::from_name("applications-graphics-symbolic").symbolic(true),
-                fl!("tools-color"),
-                Message::ToggleColorPicker,
-                self.is_color_changed(),
-            ));
-        }
+        let chip = |icon_name: &'static str,
+                    label: String,
+                    message: Message,
+                    active: bool|
+         -> Element<'a, Message> {
+            let button =
+                widget::button::custom(widget::icon::from_name(icon_name).symbolic(true).size(16))
+                    .name(label)
+                    .padding(spacing.space_xs)
+                    .on_press(message)
+                    .class(if active {
+                        cosmic::theme::Button::Suggested
+                    } else {
+                        overlay_chip_button_class()
+                    });
+            if active {
+                button.into()
+            } else {
+                self.frosted_panel(button.into(), OVERLAY_CONTAINER)
+            }
+        };
+        let controls = widget::Row::new()
+            .push(chip(
+                "display-brightness-symbolic",
+                fl!("a11y-mirror-light"),
+                Message::ToggleMirrorLight,
+                light,
+            ))
+            .push(chip(
+                "view-restore-symbolic",
+                fl!("a11y-mirror-exit"),
+                Message::ToggleMirrorMode,
+                false,
+            ))
+            .spacing(spacing.space_xs);
+
+        cosmic::iced::widget::stack![
+            picture,
+            widget::container(controls)
+                .width(Length::Fill)
+                .align_x(cosmic::iced::alignment::Horizontal::Right)
+                .padding(spacing.space_s)
+        ]
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
 
-        // Filter button (photo, video, timelapse, and virtual-camera modes)
-        if self.mode == CameraMode::Photo
-            || self.mode == CameraMode::Video
-            || self.mode == CameraMode::Timelapse
-            || self.mode == CameraMode::Virtual
-        {
-            let filter_active = self.selected_filter != FilterType::Standard;
-            buttons.push(self.build_tools_grid_button(
-                widget::icon::from_svg_bytes(FILTER_ICON).symbolic(true),
-                fl!("tools-filter"),
-                Message::ToggleContextPage(crate::app::state::ContextPage::Filters),
-                filter_active,
-            ));
-        }
+    fn build_tools_menu(&self) -> Element<'_, Message> {
+        let spacing = cosmic::theme::spacing();
 
-        // Motor/PTZ button (shows when camera has motor controls)
-        if self.has_motor_controls() {
-            buttons.push(self.build_tools_grid_button(
-                widget::icon::from_svg_bytes(CAMERA_TILT_ICON).symbolic(true),
-                fl!("tools-motor"),
-                Message::ToggleMotorPicker,
-                self.motor_picker_visible,
-            ));
-        }
+        // Tiles for the controls the user keeps in the menu
+        let buttons: Vec<Element<'_, Message>> = self
+            .tool_entries()
+            .into_iter()
+            .filter(|e| e.placement == Placement::Tools)
+            .map(|e| self.build_tools_grid_button(e.icon, e.label, e.message, e.active))
+            .collect();
 
         // Distribute buttons into 2 rows
         let items_per_row = buttons.len().div_ceil(2); // Ceiling division
@@ -1359,6 +1921,201 @@ impl AppModel {
         .into()
     }
 
+    /// The flash, HDR+ and tools-menu controls available right now, with
+    /// where the user's layout puts each one in the current mode. Hidden
+    /// controls are left out.
+    fn tool_entries(&self) -> Vec<ToolEntry> {
+        let mut entries = Vec::new();
+        let mut push = |control: Control,
+                        icon: widget::icon::Handle,
+                        label: String,
+                        message: Message,
+                        active: bool| {
+            let placement = self.control_placement(control);
+            if placement != Placement::Hidden {
+                entries.push(ToolEntry {
+                    placement,
+                    icon,
+                    label,
+                    message,
+                    active,
+                });
+            }
+        };
+
+        // Flash (Photo mode, or Video/Timelapse mode with hardware flash for torch)
+        let flash_available = self.mode == CameraMode::Photo
+            || ((self.mode == CameraMode::Video || self.mode == CameraMode::Timelapse)
+                && self.use_hardware_flash());
+        if flash_available {
+            let flash_icon_bytes = if self.flash.enabled {
+                FLASH_ICON
+            } else {
+                FLASH_OFF_ICON
+            };
+            push(
+                Control::Flash,
+                widget::icon::from_svg_bytes(flash_icon_bytes).symbolic(true),
+                fl!("tools-flash"),
+                Message::ToggleFlash,
+                self.flash.enabled,
+            );
+        }
+
+        // Torch, wherever there are LEDs to light
+        if flash_available && self.use_hardware_flash() {
+            push(
+                Control::Torch,
+                widget::icon::from_svg_bytes(TORCH_ICON).symbolic(true),
+                fl!("tools-torch"),
+                Message::ToggleTorch,
+                self.flash.torch,
+            );
+        }
+
+        if flash_available && self.should_show_burst_button() {
+            // Show moon-off icon when HDR+ is disabled (by override or setting)
+            let is_hdr_active = self.would_use_burst_mode();
+            let moon_icon_bytes = if is_hdr_active {
+                MOON_ICON
+            } else {
+                MOON_OFF_ICON
+            };
+            push(
+                Control::HdrPlus,
+                widget::icon::from_svg_bytes(moon_icon_bytes).symbolic(true),
+                fl!("tools-hdr"),
+                Message::ToggleBurstMode,
+                is_hdr_active,
+            );
+        }
+
+        // Timer and aspect ratio (Photo mode only)
+        if self.mode == CameraMode::Photo {
+            let timer_active =
+                self.photo_timer_setting != crate::app::state::PhotoTimerSetting::Off;
+            let timer_icon_bytes = match self.photo_timer_setting {
+                crate::app::state::PhotoTimerSetting::Off => TIMER_OFF_ICON,
+                crate::app::state::PhotoTimerSetting::Sec3 => TIMER_3_ICON,
+                crate::app::state::PhotoTimerSetting::Sec5 => TIMER_5_ICON,
+                crate::app::state::PhotoTimerSetting::Sec10 => TIMER_10_ICON,
+            };
+            push(
+                Control::Timer,
+                widget::icon::from_svg_bytes(timer_icon_bytes).symbolic(true),
+                fl!("tools-timer"),
+                Message::CyclePhotoTimer,
+                timer_active,
+            );
+
+            // Square ratios (Native, 1:1) are orientation-agnostic; the
+            // others swap to their portrait companion icon when the window
+            // is taller than wide so the label matches the rotated preview.
+            let portrait = self.screen_is_portrait();
+            let aspect_icon_bytes = match self.photo_aspect_ratio {
+                crate::app::state::PhotoAspectRatio::Native => ASPECT_NATIVE_ICON,
+                crate::app::state::PhotoAspectRatio::Ratio1x1 => ASPECT_1_1_ICON,
+                crate::app::state::PhotoAspectRatio::Ratio4x3 if portrait => ASPECT_3_4_ICON,
+                crate::app::state::PhotoAspectRatio::Ratio4x3 => ASPECT_4_3_ICON,
+                crate::app::state::PhotoAspectRatio::Ratio16x9 if portrait => ASPECT_9_16_ICON,
+                crate::app::state::PhotoAspectRatio::Ratio16x9 => ASPECT_16_9_ICON,
+                crate::app::state::PhotoAspectRatio::Ratio2x1 if portrait => ASPECT_1_2_ICON,
+                crate::app::state::PhotoAspectRatio::Ratio2x1 => ASPECT_2_1_ICON,
+            };
+            push(
+                Control::AspectRatio,
+                widget::icon::from_svg_bytes(aspect_icon_bytes).symbolic(true),
+                fl!("tools-aspect"),
+                Message::CyclePhotoAspectRatio,
+                self.is_aspect_ratio_changed(),
+            );
+        }
+
+        if self.available_exposure_controls.has_any_essential() {
+            push(
+                Control::Exposure,
+                widget::icon::from_svg_bytes(EXPOSURE_ICON).symbolic(true),
+                fl!("tools-exposure"),
+                Message::ToggleExposurePicker,
+                self.is_exposure_changed(),
+            );
+        }
+
+        // Color (contrast, saturation, white balance, etc.). Always shown:
+        // the white balance eyedropper works even without camera controls.
+        push(
+            Control::Color,
+            icon::from_name("applications-graphics-symbolic")
+                .symbolic(true)
+                .handle(),
+            fl!("tools-color"),
+            Message::ToggleColorPicker,
+            self.is_color_changed(),
+        );
+
+        // Filter (photo, video, timelapse, document, and virtual-camera modes)
+        if self.mode == CameraMode::Photo
+            || self.mode == CameraMode::Video
+            || self.mode == CameraMode::Timelapse
+            || self.mode == CameraMode::Document
+            || self.mode == CameraMode::Virtual
+        {
+            push(
+                Control::Filter,
+                widget::icon::from_svg_bytes(FILTER_ICON).symbolic(true),
+                fl!("tools-filter"),
+                Message::ToggleContextPage(crate::app::state::ContextPage::Filters),
+                self.selected_filter != FilterType::Standard,
+            );
+        }
+
+        // Mirror mode (Photo mode only)
+        if self.mode == CameraMode::Photo {
+            push(
+                Control::Mirror,
+                icon::from_name("object-flip-horizontal-symbolic")
+                    .symbolic(true)
+                    .handle(),
+                fl!("tools-mirror"),
+                Message::ToggleMirrorMode,
+                false,
+            );
+        }
+
+        // Show QR: a code for another device to scan
+        push(
+            Control::ShowQr,
+            widget::icon::from_svg_bytes(QR_CODE_ICON).symbolic(true),
+            fl!("tools-show-qr"),
+            Message::OpenQrShare,
+            self.qr_share.is_some(),
+        );
+
+        // Focus loupe, wherever there is a live preview to check
+        if self.current_frame.is_some() {
+            push(
+                Control::Loupe,
+                icon::from_name("zoom-in-symbolic").symbolic(true).handle(),
+                fl!("tools-loupe"),
+                Message::ToggleLoupe,
+                self.loupe.is_some(),
+            );
+        }
+
+        // Motor/PTZ (shows when camera has motor controls)
+        if self.has_motor_controls() {
+            push(
+                Control::Motor,
+                widget::icon::from_svg_bytes(CAMERA_TILT_ICON).symbolic(true),
+                fl!("tools-motor"),
+                Message::ToggleMotorPicker,
+                self.motor_picker_visible,
+            );
+        }
+
+        entries
+    }
+
     /// Build a grid button with large icon and text label below (outside the button)
     fn build_tools_grid_button<'a>(
         &self,
@@ -1381,6 +2138,7 @@ impl AppModel {
     ) -> Element<'a, Message> {
         // Icon button with appropriate styling
         let mut button = widget::button::custom(widget::icon(icon_handle.into()).size(32))
+            .name(label.clone())
             .class(if is_active {
                 cosmic::theme::Button::Suggested
             } else {
@@ -1476,26 +2234,107 @@ impl AppModel {
                 image_changed || wb_auto_off
             })
             .unwrap_or(false)
+            || !self.software_white_balance.is_identity()
     }
 
-    /// Build the privacy cover warning overlay
-    ///
-    /// Shows a centered warning when the camera's privacy cover is closed.
-    fn build_privacy_warning(&self) -> Element<'_, Message> {
-        if !self.privacy_cover_closed {
+    /// Build the picture-in-picture inset: the secondary camera in the chosen
+    /// corner of the preview, sized like the inset baked into recordings and
+    /// the virtual camera, and kept clear of the top and bottom bars.
+    fn build_pip_inset(&self) -> Element<'_, Message> {
+        use crate::config::{DualCameraLayout, PipCorner};
+        use cosmic::iced::alignment::{Horizontal, Vertical};
+
+        let inset = self
+            .second_camera_preview()
+            .filter(|_| self.config.dual_camera_layout == DualCameraLayout::PictureInPicture);
+        let rect = inset.and_then(|(_, aspect)| {
+            crate::backends::camera::pip::inset_rect(
+                (self.screen_width as u32, self.screen_height as u32),
+                ((aspect * 1000.0) as u32, 1000),
+                PipCorner::TopLeft,
+            )
+        });
+        let (Some((handle, _)), Some(rect)) = (inset, rect) else {
             return widget::Space::new()
                 .width(Length::Fill)
                 .height(Length::Fill)
                 .into();
-        }
+        };
 
-        build_overlay_popup(
-            self,
-            widget::text("\u{26A0}").size(48).into(),
-            &fl!("privacy-cover-closed"),
-            &fl!("privacy-cover-hint"),
-            None,
-        )
+        let image = widget::image(handle.clone())
+            .width(Length::Fixed(rect.width as f32))
+            .height(Length::Fixed(rect.height as f32))
+            .content_fit(cosmic::iced::ContentFit::Cover);
+        // The padding shows the container background as the same light
+        // outline the GPU compositor draws
+        let framed = widget::container(image)
+            .padding(2)
+            .style(|theme: &cosmic::Theme| widget::container::Style {
+                background: Some(Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.85))),
+                border: cosmic::iced::Border {
+                    radius: theme.cosmic().corner_radii.radius_s.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+        let margin = rect.x as f32;
+        let (align_x, align_y) = match self.config.pip_corner {
+            PipCorner::TopLeft => (Horizontal::Left, Vertical::Top),
+            PipCorner::TopRight => (Horizontal::Right, Vertical::Top),
+            PipCorner::BottomLeft => (Horizontal::Left, Vertical::Bottom),
+            PipCorner::BottomRight => (Horizontal::Right, Vertical::Bottom),
+        };
+        widget::container(framed)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(align_x)
+            .align_y(align_y)
+            .padding([
+                self.settled_top_ui_height() + margin,
+                margin,
+                self.settled_bottom_ui_height() + margin,
+                margin,
+            ])
+            .into()
+    }
+
+    /// Latest second camera frame, while one is running in a capture mode
+    fn second_camera_preview(&self) -> Option<&(widget::image::Handle, f32)> {
+        self.pip_preview
+            .as_ref()
+            .filter(|_| !self.mode.is_view_only() && self.pip_camera_index().is_some())
+    }
+
+    /// Split the preview in two for the side-by-side dual camera layout.
+    /// Each half cover-crops its camera around the centre, matching what
+    /// the GPU compositor writes into photos and recordings.
+    fn side_by_side_preview<'a>(
+        &'a self,
+        camera_preview: Element<'a, Message>,
+    ) -> Element<'a, Message> {
+        let Some((handle, _)) = self.second_camera_preview().filter(|_| {
+            self.config.dual_camera_layout == crate::config::DualCameraLayout::SideBySide
+        }) else {
+            return camera_preview;
+        };
+
+        let secondary = widget::image(handle.clone())
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .content_fit(cosmic::iced::ContentFit::Cover);
+        widget::Row::new()
+            .push(
+                widget::container(camera_preview)
+                    .width(Length::FillPortion(1))
+                    .height(Length::Fill),
+            )
+            .push(
+                widget::container(secondary)
+                    .width(Length::FillPortion(1))
+                    .height(Length::Fill),
+            )
+            .into()
     }
 
     /// Build the burst mode progress overlay
@@ -1563,8 +2402,25 @@ impl AppModel {
             ..Default::default()
         });
 
-        // Build the overlay content
-        let overlay_content = widget::Column::new()
+        // Build the overlay content, led by the instant preview of the shot
+        // once it is saved
+        let mut overlay_content = widget::Column::new();
+        if self.burst_preview.is_some()
+            && let Some(handle) = &self.gallery_thumbnail
+        {
+            overlay_content = overlay_content
+                .push(
+                    widget::image(handle.clone())
+                        .height(Length::Fixed(BURST_MODE_PREVIEW_HEIGHT))
+                        .content_fit(cosmic::iced::ContentFit::Contain),
+                )
+                .push(
+                    widget::Space::new()
+                        .width(Length::Shrink)
+                        .height(Length::Fixed(16.0)),
+                );
+        }
+        overlay_content = overlay_content
             .push(
                 widget::text(status_text)
                     .size(32)
@@ -1590,6 +2446,21 @@ impl AppModel {
             .push(widget::text(format!("{}%", progress_percent)).size(14))
             .align_x(Alignment::Center);
 
+        // Merging can take a while; let the user give up on it
+        if self.burst_mode.stage == BurstModeStage::Processing {
+            let mut cancel = widget::button::standard(fl!("burst-mode-cancel"));
+            if !self.burst_mode.is_cancelling() {
+                cancel = cancel.on_press(Message::CancelBurstMode);
+            }
+            overlay_content = overlay_content
+                .push(
+                    widget::Space::new()
+                        .width(Length::Shrink)
+                        .height(Length::Fixed(16.0)),
+                )
+                .push(cancel);
+        }
+
         // Semi-transparent background panel
         let overlay_panel = self.frosted_panel(
             widget::container(overlay_content).padding(24).into(),
@@ -1631,27 +2502,30 @@ impl AppModel {
     /// Build the timer countdown overlay
     ///
     /// Shows large countdown number with fade effect during photo timer countdown.
-    fn build_timer_overlay(&self, remaining: u8) -> Element<'_, Message> {
-        // Calculate fade opacity based on elapsed time since tick start
-        // Opacity starts at 1.0 and fades to 0.0 over the second
-        let opacity = if let Some(tick_start) = self.photo_timer_tick_start {
-            let elapsed_ms = tick_start.elapsed().as_millis() as f32;
-            // Fade out over 900ms (leave 100ms fully transparent before next number)
-            (1.0 - (elapsed_ms / 900.0)).max(0.0)
-        } else {
-            1.0
+    fn build_timer_overlay(&self, countdown: CountdownOverlay) -> Element<'_, Message> {
+        // Large countdown number, fading out over each second (see
+        // `countdown_opacity`); a photo booth run also says which shot is coming
+        let opacity = countdown.opacity;
+        let number = widget::text(countdown.remaining.to_string())
+            .size(400) // Very large to fill preview
+            .font(cosmic::font::bold());
+        let countdown: Element<'_, Message> = match countdown.booth_shot {
+            Some((shot, total)) => widget::column()
+                .push(number)
+                .push(
+                    widget::text(fl!("photo-booth-progress", shot = shot, total = total))
+                        .size(32)
+                        .font(cosmic::font::bold()),
+                )
+                .align_x(Alignment::Center)
+                .into(),
+            None => number.into(),
         };
-
-        // Large countdown number with fade effect
-        let countdown_text = widget::container(
-            widget::text(remaining.to_string())
-                .size(400) // Very large to fill preview
-                .font(cosmic::font::bold()),
-        )
-        .style(move |_theme| widget::container::Style {
-            text_color: Some(Color::from_rgba(1.0, 1.0, 1.0, opacity)),
-            ..Default::default()
-        });
+        let countdown_text =
+            widget::container(countdown).style(move |_theme| widget::container::Style {
+                text_color: Some(Color::from_rgba(1.0, 1.0, 1.0, opacity)),
+                ..Default::default()
+            });
 
         widget::container(countdown_text)
             .width(Length::Fill)
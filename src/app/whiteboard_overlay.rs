@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Whiteboard outline overlay
+//!
+//! Outlines the board area snapshots are cut from in Whiteboard mode: the
+//! keystone locked for the camera as a solid line, or the board found in
+//! the latest sampled frame as a fainter one. The corners are in raw frame
+//! coordinates and go through the preview's rotation and mirroring.
+
+use crate::app::qr_overlay::calculate_video_bounds;
+use crate::app::state::{AppModel, CameraMode, Message};
+use crate::media::whiteboard::BoardQuad;
+use cosmic::Element;
+use cosmic::iced::{Color, Length, Point, Rectangle};
+use cosmic::widget::canvas;
+
+/// Locked keystone outline
+const LOCKED_COLOR: Color = Color::from_rgba(0.3, 0.85, 0.45, 0.9);
+/// Detected board outline, dimmer until locked
+const DETECTED_COLOR: Color = Color::from_rgba(1.0, 1.0, 1.0, 0.5);
+const LINE_WIDTH: f32 = 2.0;
+
+struct BoardOutlineProgram {
+    /// Corners in normalized displayed-frame coordinates
+    quad: BoardQuad,
+    locked: bool,
+    mirrored: bool,
+    /// Displayed (rotated) frame size
+    frame_width: u32,
+    frame_height: u32,
+    cover_blend: f32,
+    top_bar_h: f32,
+    bottom_bar_h: f32,
+}
+
+impl canvas::Program<Message, cosmic::Theme> for BoardOutlineProgram {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &(),
+        renderer: &cosmic::Renderer,
+        _theme: &cosmic::Theme,
+        bounds: Rectangle,
+        _cursor: cosmic::iced::mouse::Cursor,
+    ) -> Vec<canvas::Geometry<cosmic::Renderer>> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let (offset_x, offset_y, video_w, video_h) = calculate_video_bounds(
+            bounds.width,
+            bounds.height,
+            self.frame_width,
+            self.frame_height,
+            self.cover_blend,
+            self.top_bar_h,
+            self.bottom_bar_h,
+        );
+
+        let to_screen = |(x, y): (f32, f32)| {
+            let x = if self.mirrored { 1.0 - x } else { x };
+            Point::new(offset_x + x * video_w, offset_y + y * video_h)
+        };
+        let outline = canvas::path::Path::new(|builder| {
+            builder.move_to(to_screen(self.quad.corners[0]));
+            for &corner in &self.quad.corners[1..] {
+                builder.line_to(to_screen(corner));
+            }
+            builder.close();
+        });
+        frame.stroke(
+            &outline,
+            canvas::Stroke::default()
+                .with_color(if self.locked {
+                    LOCKED_COLOR
+                } else {
+                    DETECTED_COLOR
+                })
+                .with_width(LINE_WIDTH),
+        );
+
+        vec![frame.into_geometry()]
+    }
+}
+
+impl AppModel {
+    /// Build the whiteboard outline overlay (Whiteboard mode only)
+    pub fn build_whiteboard_overlay(&self) -> Element<'_, Message> {
+        let empty = || -> Element<'_, Message> {
+            cosmic::widget::Space::new()
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into()
+        };
+        if self.mode != CameraMode::Whiteboard {
+            return empty();
+        }
+        let locked = self.whiteboard_keystone();
+        let Some(quad) = locked.or(self.whiteboard.detected) else {
+            return empty();
+        };
+        let Some(frame) = &self.current_frame else {
+            return empty();
+        };
+
+        let rotation = self.current_frame_rotation;
+        let (frame_width, frame_height) = if rotation.swaps_dimensions() {
+            (frame.height, frame.width)
+        } else {
+            (frame.width, frame.height)
+        };
+
+        cosmic::widget::Canvas::new(BoardOutlineProgram {
+            quad: quad.rotated(rotation),
+            locked: locked.is_some(),
+            mirrored: self.should_mirror_preview(),
+            frame_width,
+            frame_height,
+            cover_blend: self.cover_blend(),
+            top_bar_h: self.top_ui_height(),
+            bottom_bar_h: self.bottom_ui_height(),
+        })
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+}
@@ -0,0 +1,520 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Automation rules
+//!
+//! A small rules engine for unattended deployments. Each rule pairs a
+//! trigger with an optional time-of-day window and a list of actions, e.g.
+//! "when motion is detected between 22:00 and 06:00, record a 30 s clip and
+//! send a notification". Rules live in a TOML file ([`rules_path`]) that the
+//! settings drawer edits and the headless daemon executes:
+//!
+//! ```toml
+//! [[rule]]
+//! name = "Night watch"
+//! trigger = "motion"
+//! between = "22:00-06:00"
+//! cooldown = 120
+//! actions = [
+//!     { type = "record", seconds = 30 },
+//!     { type = "notify", message = "Motion at the front door" },
+//!     { type = "run", command = ["/usr/local/bin/upload-clip"] },
+//! ]
+//! ```
+//!
+//! The engine itself is pure: it maps events to the actions that should run
+//! and leaves executing them to the caller, so it can be tested without a
+//! camera.
+
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Well-known bus name claimed by the headless daemon
+pub const DAEMON_BUS_NAME: &str = "io.github.cosmic_utils.camera.Daemon";
+/// Object path the daemon's control interface is served at
+pub const DAEMON_OBJECT_PATH: &str = "/io/github/cosmic_utils/camera/Daemon";
+
+/// Application ID, used for the config directory and notification icon
+const APP_ID: &str = <crate::app::AppModel as cosmic::Application>::APP_ID;
+
+/// File name of the rules file inside the app's config directory
+const RULES_FILE_NAME: &str = "rules.toml";
+
+/// Written when the user opens the rules file for the first time, so there is
+/// a working example to adapt rather than an empty file.
+const EXAMPLE_RULES: &str = r#"# Automation rules for the camera daemon (`camera daemon`).
+#
+# trigger:  "motion" or { every = <seconds> }
+# between:  optional "HH:MM-HH:MM" window, may wrap past midnight
+# cooldown: minimum seconds between two firings of the same rule
+# actions:  { type = "photo" }
+#           { type = "record", seconds = <n> }
+#           { type = "notify", message = "<text>" }
+#           { type = "run", command = ["<program>", "<arg>", ...] }
+#
+# `run` commands get CAMERA_RULE and, once a photo or clip was written by the
+# same rule, CAMERA_FILE in their environment.
+
+[[rule]]
+name = "Night watch"
+enabled = false
+trigger = "motion"
+between = "22:00-06:00"
+cooldown = 120
+actions = [
+    { type = "record", seconds = 30 },
+    { type = "notify", message = "Motion detected" },
+]
+"#;
+
+/// Contents of a rules file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleSet {
+    #[serde(default, rename = "rule")]
+    pub rules: Vec<Rule>,
+}
+
+/// A single "when … then …" rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rule {
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub trigger: Trigger,
+    /// Only fire inside this local time-of-day window
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub between: Option<TimeWindow>,
+    /// Minimum seconds between two firings
+    #[serde(default = "default_cooldown")]
+    pub cooldown: u64,
+    pub actions: Vec<Action>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_cooldown() -> u64 {
+    30
+}
+
+/// What makes a rule fire
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Trigger {
+    /// Motion detected in the live stream
+    Motion,
+    /// Every N seconds
+    Every(u64),
+}
+
+/// What a rule does when it fires. Actions run in the order listed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum Action {
+    /// Save a photo of the current frame
+    Photo,
+    /// Record a clip; a clip already running is extended instead
+    Record { seconds: u64 },
+    /// Send a desktop notification
+    Notify { message: String },
+    /// Run an external command (the "hook")
+    Run { command: Vec<String> },
+}
+
+/// Local time-of-day window, written as `"HH:MM-HH:MM"`. A window whose end
+/// is before its start wraps past midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TimeWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl TimeWindow {
+    /// Whether `time` falls inside the window (start inclusive, end exclusive)
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl std::str::FromStr for TimeWindow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| format!("Invalid time window '{s}', expected HH:MM-HH:MM"))?;
+        let parse = |t: &str| {
+            NaiveTime::parse_from_str(t.trim(), "%H:%M")
+                .map_err(|e| format!("Invalid time '{}': {e}", t.trim()))
+        };
+        Ok(Self {
+            start: parse(start)?,
+            end: parse(end)?,
+        })
+    }
+}
+
+impl TryFrom<String> for TimeWindow {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<TimeWindow> for String {
+    fn from(window: TimeWindow) -> Self {
+        window.to_string()
+    }
+}
+
+impl std::fmt::Display for TimeWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )
+    }
+}
+
+/// Default location of the rules file
+pub fn rules_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_ID).join(RULES_FILE_NAME))
+}
+
+impl RuleSet {
+    /// Parse rules from TOML text
+    pub fn parse(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|e| format!("Invalid rules file: {e}"))
+    }
+
+    /// Load rules from `path`. A missing file is an empty rule set.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("Failed to read {}: {e}", path.display())),
+        }
+    }
+
+    /// Write rules back to `path`, creating its directory if needed
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let text =
+            toml::to_string_pretty(self).map_err(|e| format!("Failed to serialize rules: {e}"))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        std::fs::write(path, text).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+    }
+
+    /// Number of rules that will actually run
+    pub fn enabled_count(&self) -> usize {
+        self.rules.iter().filter(|r| r.enabled).count()
+    }
+}
+
+/// Create the rules file with a commented example if it doesn't exist yet
+pub fn ensure_rules_file(path: &Path) -> Result<(), String> {
+    if path.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    std::fs::write(path, EXAMPLE_RULES)
+        .map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+/// Something the engine reacts to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// The motion detector reported a change
+    Motion,
+    /// Periodic tick from the caller's main loop
+    Tick,
+}
+
+/// A rule that fired, with the actions to run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Firing {
+    pub rule: String,
+    pub actions: Vec<Action>,
+}
+
+/// Evaluates enabled rules against incoming events
+pub struct RuleEngine {
+    rules: Vec<Rule>,
+    /// When each rule last fired (index-aligned with `rules`)
+    last_fired: Vec<Option<Instant>>,
+    started: Instant,
+}
+
+impl RuleEngine {
+    pub fn new(rule_set: RuleSet, now: Instant) -> Self {
+        let rules: Vec<Rule> = rule_set.rules.into_iter().filter(|r| r.enabled).collect();
+        let last_fired = vec![None; rules.len()];
+        Self {
+            rules,
+            last_fired,
+            started: now,
+        }
+    }
+
+    /// Number of enabled rules
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether any rule needs the motion detector running
+    pub fn needs_motion(&self) -> bool {
+        self.rules.iter().any(|r| r.trigger == Trigger::Motion)
+    }
+
+    /// Return the rules that fire for `event`. `now` drives cooldowns and
+    /// intervals; `local_time` is the wall-clock time for `between` windows.
+    pub fn evaluate(&mut self, event: Event, now: Instant, local_time: NaiveTime) -> Vec<Firing> {
+        let mut firings = Vec::new();
+        for (rule, last_fired) in self.rules.iter().zip(self.last_fired.iter_mut()) {
+            let triggered = match (rule.trigger, event) {
+                (Trigger::Motion, Event::Motion) => {
+                    last_fired.is_none_or(|t| now - t >= Duration::from_secs(rule.cooldown))
+                }
+                (Trigger::Every(seconds), Event::Tick) => {
+                    let since = last_fired.unwrap_or(self.started);
+                    now - since >= Duration::from_secs(seconds.max(1))
+                }
+                _ => false,
+            };
+            if !triggered || rule.between.is_some_and(|w| !w.contains(local_time)) {
+                continue;
+            }
+            *last_fired = Some(now);
+            firings.push(Firing {
+                rule: rule.name.clone(),
+                actions: rule.actions.clone(),
+            });
+        }
+        firings
+    }
+}
+
+/// Send a desktop notification through `org.freedesktop.Notifications`
+pub fn send_notification(summary: &str, body: &str) -> Result<(), String> {
+    let connection = zbus::blocking::Connection::session()
+        .map_err(|e| format!("No session bus for notifications: {e}"))?;
+    let hints: std::collections::HashMap<&str, zbus::zvariant::Value<'_>> =
+        std::collections::HashMap::new();
+    connection
+        .call_method(
+            Some("org.freedesktop.Notifications"),
+            "/org/freedesktop/Notifications",
+            Some("org.freedesktop.Notifications"),
+            "Notify",
+            &(
+                "Camera",
+                0u32,
+                APP_ID,
+                summary,
+                body,
+                Vec::<&str>::new(),
+                hints,
+                -1i32,
+            ),
+        )
+        .map_err(|e| format!("Failed to send notification: {e}"))?;
+    Ok(())
+}
+
+/// Start a hook command without waiting for it. The rule name and the last
+/// file the rule wrote are passed as `CAMERA_RULE` / `CAMERA_FILE`.
+pub fn run_hook(command: &[String], rule: &str, file: Option<&Path>) -> Result<(), String> {
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| format!("Rule '{rule}' has an empty run command"))?;
+    let mut cmd = std::process::Command::new(program);
+    cmd.args(args).env("CAMERA_RULE", rule);
+    if let Some(file) = file {
+        cmd.env("CAMERA_FILE", file);
+    }
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to run '{program}': {e}"))?;
+    info!(program, rule, "Started automation hook");
+
+    // Reap the child so it doesn't linger as a zombie
+    std::thread::spawn(move || match child.wait() {
+        Ok(status) if !status.success() => warn!(?status, "Automation hook failed"),
+        Err(e) => warn!(error = %e, "Failed to wait for automation hook"),
+        _ => {}
+    });
+    Ok(())
+}
+
+/// Ask a running daemon to re-read the rules file. Succeeds quietly when no
+/// daemon is running.
+pub async fn reload_daemon_rules() -> Result<(), String> {
+    let connection = zbus::Connection::session()
+        .await
+        .map_err(|e| format!("No session bus: {e}"))?;
+    match connection
+        .call_method(
+            Some(DAEMON_BUS_NAME),
+            DAEMON_OBJECT_PATH,
+            Some(DAEMON_BUS_NAME),
+            "ReloadRules",
+            &(),
+        )
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(zbus::Error::MethodError(name, _, _))
+            if name.as_str() == "org.freedesktop.DBus.Error.ServiceUnknown" =>
+        {
+            Ok(())
+        }
+        Err(e) => Err(format!("Failed to reload daemon rules: {e}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn rule(trigger: Trigger, between: Option<&str>, cooldown: u64) -> Rule {
+        Rule {
+            name: "test".into(),
+            enabled: true,
+            trigger,
+            between: between.map(|w| w.parse().unwrap()),
+            cooldown,
+            actions: vec![Action::Photo],
+        }
+    }
+
+    #[test]
+    fn example_rules_parse() {
+        let rules = RuleSet::parse(EXAMPLE_RULES).unwrap();
+        assert_eq!(rules.rules.len(), 1);
+        let night = &rules.rules[0];
+        assert!(!night.enabled);
+        assert_eq!(night.trigger, Trigger::Motion);
+        assert_eq!(night.between.unwrap().to_string(), "22:00-06:00");
+        assert_eq!(
+            night.actions,
+            vec![
+                Action::Record { seconds: 30 },
+                Action::Notify {
+                    message: "Motion detected".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rules_round_trip_through_toml() {
+        let rules = RuleSet {
+            rules: vec![
+                rule(Trigger::Every(300), None, 30),
+                Rule {
+                    actions: vec![Action::Run {
+                        command: vec!["notify-send".into(), "hi".into()],
+                    }],
+                    ..rule(Trigger::Motion, Some("08:30-17:00"), 10)
+                },
+            ],
+        };
+        let text = toml::to_string_pretty(&rules).unwrap();
+        assert_eq!(RuleSet::parse(&text).unwrap(), rules);
+    }
+
+    #[test]
+    fn time_window_wraps_past_midnight() {
+        let night: TimeWindow = "22:00-06:00".parse().unwrap();
+        assert!(night.contains(time(23, 0)));
+        assert!(night.contains(time(2, 0)));
+        assert!(!night.contains(time(6, 0)));
+        assert!(!night.contains(time(12, 0)));
+
+        let day: TimeWindow = "08:00-18:00".parse().unwrap();
+        assert!(day.contains(time(8, 0)));
+        assert!(!day.contains(time(18, 0)));
+        assert!("8-18".parse::<TimeWindow>().is_err());
+    }
+
+    #[test]
+    fn motion_rule_respects_window_and_cooldown() {
+        let start = Instant::now();
+        let mut engine = RuleEngine::new(
+            RuleSet {
+                rules: vec![rule(Trigger::Motion, Some("22:00-06:00"), 60)],
+            },
+            start,
+        );
+
+        assert!(
+            engine
+                .evaluate(Event::Motion, start, time(12, 0))
+                .is_empty()
+        );
+        assert_eq!(engine.evaluate(Event::Motion, start, time(23, 0)).len(), 1);
+        let soon = start + Duration::from_secs(30);
+        assert!(engine.evaluate(Event::Motion, soon, time(23, 0)).is_empty());
+        let later = start + Duration::from_secs(61);
+        assert_eq!(engine.evaluate(Event::Motion, later, time(23, 1)).len(), 1);
+        assert!(engine.evaluate(Event::Tick, later, time(23, 1)).is_empty());
+    }
+
+    #[test]
+    fn interval_rule_fires_on_ticks() {
+        let start = Instant::now();
+        let mut engine = RuleEngine::new(
+            RuleSet {
+                rules: vec![rule(Trigger::Every(10), None, 0)],
+            },
+            start,
+        );
+        assert!(!engine.needs_motion());
+        let noon = time(12, 0);
+        assert!(engine.evaluate(Event::Tick, start, noon).is_empty());
+        let t = start + Duration::from_secs(10);
+        assert_eq!(engine.evaluate(Event::Tick, t, noon).len(), 1);
+        assert!(engine.evaluate(Event::Tick, t, noon).is_empty());
+        assert!(engine.evaluate(Event::Motion, t, noon).is_empty());
+    }
+
+    #[test]
+    fn disabled_rules_are_skipped() {
+        let start = Instant::now();
+        let engine = RuleEngine::new(
+            RuleSet {
+                rules: vec![Rule {
+                    enabled: false,
+                    ..rule(Trigger::Motion, None, 0)
+                }],
+            },
+            start,
+        );
+        assert!(engine.is_empty());
+        assert!(!engine.needs_motion());
+    }
+}
@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! HDMI capture card detection
+//!
+//! UVC HDMI grabbers enumerate as ordinary webcams. They are recognised by
+//! the USB vendor of the bridge chip or brand they are built around, and
+//! failing that by a name that says what they are. A camera monitored
+//! through one wants the lowest latency the app can manage rather than the
+//! usual smooth, filtered preview; see `Config::capture_card_mode`.
+
+use super::types::CameraDevice;
+use std::path::Path;
+
+/// libcamera buffers queued for a capture card: one being filled, one
+/// being read and one spare, where webcams get libcamera's default
+pub const LOW_LATENCY_BUFFER_COUNT: u32 = 3;
+
+/// USB vendors that only make capture hardware
+const CAPTURE_CARD_VENDORS: &[u16] = &[
+    0x534d, // MacroSilicon (MS2109 / MS2130 "USB Video" grabbers)
+    0x345f, // UltraSemi ("USB3 Video" grabbers)
+    0x1edb, // Blackmagic Design
+    0x07ca, // AVerMedia
+    0x2935, // Magewell
+    0x1164, // YUAN High-Tech
+];
+
+/// Name fragments of capture cards from vendors that also make webcams
+const CAPTURE_CARD_NAMES: &[&str] = &[
+    "hdmi",
+    "cam link",
+    "capture",
+    "hd60",
+    "live gamer",
+    "usb3 video",
+];
+
+/// Whether a camera is a UVC HDMI capture card
+pub fn is_capture_card(device: &CameraDevice) -> bool {
+    let vendor = device.v4l2_path().and_then(usb_vendor);
+    let card = device.device_info.as_ref().map(|info| info.card.as_str());
+    matches(&device.name, card, vendor)
+}
+
+fn matches(name: &str, card: Option<&str>, vendor: Option<u16>) -> bool {
+    if vendor.is_some_and(|vendor| CAPTURE_CARD_VENDORS.contains(&vendor)) {
+        return true;
+    }
+    [Some(name), card].into_iter().flatten().any(|name| {
+        let name = name.to_lowercase();
+        CAPTURE_CARD_NAMES.iter().any(|hint| name.contains(hint))
+    })
+}
+
+/// USB vendor ID of a V4L2 device (e.g. `/dev/video0`), if it is on USB
+fn usb_vendor(device_path: &str) -> Option<u16> {
+    let node = Path::new(device_path).file_name()?;
+    let usb_device = super::usb_bandwidth::usb_device_dir(node)?;
+    let vendor = super::usb_bandwidth::read_attr(&usb_device, "idVendor")?;
+    u16::from_str_radix(&vendor, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grabbers_are_told_from_webcams() {
+        // A MacroSilicon grabber calls itself nothing more than "USB Video"
+        assert!(matches(
+            "USB Video",
+            Some("USB Video: USB Video"),
+            Some(0x534d)
+        ));
+        assert!(matches("Cam Link 4K", None, Some(0x0fd9)));
+        assert!(matches("Game Capture HD60 S+", None, None));
+        // Elgato also makes webcams
+        assert!(!matches("Elgato Facecam", None, Some(0x0fd9)));
+        assert!(!matches(
+            "Integrated Camera",
+            Some("Integrated Camera"),
+            Some(0x04f2)
+        ));
+    }
+}
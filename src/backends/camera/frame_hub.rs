@@ -0,0 +1,536 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Frame fan-out hub
+//!
+//! The capture thread hands each frame to the [`FrameHub`] once and the hub
+//! passes the same `Arc<CameraFrame>` to every attached consumer: the
+//! preview, the recorder, the virtual camera and any analysis that wants
+//! live frames. Each consumer has its own [`QueuePolicy`] and counters, so
+//! a slow consumer only ever drops its own frames.
+//!
+//! Consumers either [`FrameHub::subscribe`] and get a queue built to their
+//! policy, or [`FrameHub::attach`] a channel of their own (the preview and
+//! the recorder already have one). A consumer whose receiving end is gone
+//! is detached on the next frame.
+
+use super::types::{CameraFrame, FrameSender, RecordingFrame};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+use tokio::sync::mpsc::error::TrySendError;
+use tracing::debug;
+
+/// What a consumer does with the frames, for stats and replacement
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ConsumerKind {
+    Preview,
+    Recording,
+    VirtualCamera,
+    QrScanner,
+    MotionDetection,
+    GestureDetector,
+    FaceDetector,
+    PageDetector,
+    BoardDetector,
+    BarcodeScanner,
+    SceneClassifier,
+    LightMeter,
+    FrameTap,
+}
+
+impl ConsumerKind {
+    /// Consumers that analyse the preview in the app rather than deliver
+    /// frames somewhere
+    pub const FRAME_ANALYSIS: [ConsumerKind; 9] = [
+        ConsumerKind::QrScanner,
+        ConsumerKind::GestureDetector,
+        ConsumerKind::FaceDetector,
+        ConsumerKind::PageDetector,
+        ConsumerKind::BoardDetector,
+        ConsumerKind::BarcodeScanner,
+        ConsumerKind::SceneClassifier,
+        ConsumerKind::LightMeter,
+        ConsumerKind::FrameTap,
+    ];
+
+    /// Short name for logs and diagnostics
+    pub fn label(self) -> &'static str {
+        match self {
+            ConsumerKind::Preview => "preview",
+            ConsumerKind::Recording => "recording",
+            ConsumerKind::VirtualCamera => "virtual camera",
+            ConsumerKind::QrScanner => "QR scanner",
+            ConsumerKind::MotionDetection => "motion detection",
+            ConsumerKind::GestureDetector => "gesture detector",
+            ConsumerKind::FaceDetector => "face detector",
+            ConsumerKind::PageDetector => "page detector",
+            ConsumerKind::BoardDetector => "board detector",
+            ConsumerKind::BarcodeScanner => "barcode scanner",
+            ConsumerKind::SceneClassifier => "scene classifier",
+            ConsumerKind::LightMeter => "light meter",
+            ConsumerKind::FrameTap => "frame tap",
+        }
+    }
+}
+
+/// How a consumer's queue behaves when frames arrive faster than it reads
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum QueuePolicy {
+    /// Queue up to this many frames and drop new ones while full. For
+    /// consumers that need what they get in order, like the recorder.
+    Bounded(usize),
+    /// Keep only the newest unread frame. For consumers that only care
+    /// about the present, like the virtual camera and frame analysis.
+    Latest,
+}
+
+/// Outcome of offering a frame to a consumer
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Offer {
+    Delivered,
+    /// Delivered, pushing out an unread frame
+    Replaced,
+    /// Not delivered because the consumer's queue is full
+    Dropped,
+    /// The consumer is gone and should be detached
+    Closed,
+}
+
+/// Receiving end of a consumer, as the hub sees it
+pub trait FrameSink: Send {
+    /// Offer a decoded frame
+    fn offer(&mut self, frame: &Arc<CameraFrame>) -> Offer;
+
+    /// Offer a frame that wasn't decoded (raw MJPEG for the recorder).
+    /// `None` when this consumer only takes decoded frames.
+    fn offer_encoded(&mut self, _frame: &RecordingFrame) -> Option<Offer> {
+        None
+    }
+}
+
+/// Identifies an attached consumer for [`FrameHub::detach`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ConsumerId(u64);
+
+/// A consumer's counters at one point in time
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ConsumerStats {
+    pub kind: ConsumerKind,
+    pub policy: QueuePolicy,
+    pub delivered: u64,
+    pub dropped: u64,
+}
+
+struct Consumer {
+    id: ConsumerId,
+    kind: ConsumerKind,
+    policy: QueuePolicy,
+    sink: Box<dyn FrameSink>,
+    delivered: u64,
+    dropped: u64,
+}
+
+impl Consumer {
+    /// Count an offer; `false` when the consumer should be detached
+    fn record(&mut self, offer: Offer) -> bool {
+        match offer {
+            Offer::Delivered => self.delivered += 1,
+            Offer::Replaced => {
+                self.delivered += 1;
+                self.dropped += 1;
+            }
+            Offer::Dropped => self.dropped += 1,
+            Offer::Closed => {
+                debug!(
+                    consumer = self.kind.label(),
+                    delivered = self.delivered,
+                    dropped = self.dropped,
+                    "Frame consumer closed"
+                );
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Distributes every captured frame to its consumers
+#[derive(Default)]
+pub struct FrameHub {
+    consumers: Mutex<Vec<Consumer>>,
+    next_id: AtomicU64,
+}
+
+impl FrameHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attach a consumer with its own sink. `policy` describes how the
+    /// sink queues, for [`FrameHub::stats`].
+    pub fn attach(
+        &self,
+        kind: ConsumerKind,
+        policy: QueuePolicy,
+        sink: Box<dyn FrameSink>,
+    ) -> ConsumerId {
+        let id = ConsumerId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        debug!(consumer = kind.label(), ?policy, "Frame consumer attached");
+        self.lock().push(Consumer {
+            id,
+            kind,
+            policy,
+            sink,
+            delivered: 0,
+            dropped: 0,
+        });
+        id
+    }
+
+    /// Attach a consumer and return its queue
+    pub fn subscribe(&self, kind: ConsumerKind, policy: QueuePolicy) -> FrameSubscription {
+        match policy {
+            QueuePolicy::Bounded(capacity) => {
+                let (tx, rx) = tokio::sync::mpsc::channel(capacity.max(1));
+                self.attach(kind, policy, Box::new(tx));
+                FrameSubscription::Bounded(rx)
+            }
+            QueuePolicy::Latest => {
+                let slot = Arc::new(LatestSlot::default());
+                self.attach(kind, policy, Box::new(LatestSink(Arc::clone(&slot))));
+                FrameSubscription::Latest(slot)
+            }
+        }
+    }
+
+    /// Make `sink` the only consumer of `kind`, or detach every consumer
+    /// of `kind` when `None`. For consumers that run one at a time.
+    pub fn replace(
+        &self,
+        kind: ConsumerKind,
+        policy: QueuePolicy,
+        sink: Option<Box<dyn FrameSink>>,
+    ) {
+        self.detach_kind(kind);
+        if let Some(sink) = sink {
+            self.attach(kind, policy, sink);
+        }
+    }
+
+    /// Make `sender` the recorder, or detach the recorder when `None`
+    pub fn set_recorder(&self, sender: Option<tokio::sync::mpsc::Sender<RecordingFrame>>) {
+        let policy = QueuePolicy::Bounded(sender.as_ref().map_or(0, |tx| tx.max_capacity()));
+        self.replace(
+            ConsumerKind::Recording,
+            policy,
+            sender.map(|tx| Box::new(tx) as Box<dyn FrameSink>),
+        );
+    }
+
+    pub fn detach(&self, id: ConsumerId) {
+        self.lock().retain(|consumer| consumer.id != id);
+    }
+
+    /// Detach every consumer of `kind`. Their subscriptions end.
+    pub fn detach_kind(&self, kind: ConsumerKind) {
+        self.lock().retain(|consumer| consumer.kind != kind);
+    }
+
+    pub fn has_consumer(&self, kind: ConsumerKind) -> bool {
+        self.lock().iter().any(|consumer| consumer.kind == kind)
+    }
+
+    /// Offer `frame` to every consumer
+    pub fn publish(&self, frame: &Arc<CameraFrame>) {
+        self.publish_inner(frame, None);
+    }
+
+    /// Offer `frame` to every consumer except those of `skip`, which got
+    /// this frame some other way
+    pub fn publish_except(&self, frame: &Arc<CameraFrame>, skip: ConsumerKind) {
+        self.publish_inner(frame, Some(skip));
+    }
+
+    /// Offer `frame` to the consumers of `kind` only. For frames that
+    /// don't come from the capture thread, like a file source's.
+    pub fn publish_to(&self, kind: ConsumerKind, frame: &Arc<CameraFrame>) {
+        self.lock().retain_mut(|consumer| {
+            if consumer.kind != kind {
+                return true;
+            }
+            let offer = consumer.sink.offer(frame);
+            consumer.record(offer)
+        });
+    }
+
+    /// Offer an undecoded frame to the consumers of `kind` that take them.
+    /// `None` when none do.
+    pub fn publish_encoded(&self, kind: ConsumerKind, frame: &RecordingFrame) -> Option<Offer> {
+        let mut result = None;
+        self.lock().retain_mut(|consumer| {
+            if consumer.kind != kind {
+                return true;
+            }
+            let Some(offer) = consumer.sink.offer_encoded(frame) else {
+                return true;
+            };
+            result = Some(offer);
+            consumer.record(offer)
+        });
+        result
+    }
+
+    /// Counters for every attached consumer
+    pub fn stats(&self) -> Vec<ConsumerStats> {
+        self.lock()
+            .iter()
+            .map(|consumer| ConsumerStats {
+                kind: consumer.kind,
+                policy: consumer.policy,
+                delivered: consumer.delivered,
+                dropped: consumer.dropped,
+            })
+            .collect()
+    }
+
+    fn publish_inner(&self, frame: &Arc<CameraFrame>, skip: Option<ConsumerKind>) {
+        self.lock().retain_mut(|consumer| {
+            if Some(consumer.kind) == skip {
+                return true;
+            }
+            let offer = consumer.sink.offer(frame);
+            consumer.record(offer)
+        });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Consumer>> {
+        self.consumers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl std::fmt::Debug for FrameHub {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FrameHub")
+            .field("consumers", &self.stats())
+            .finish()
+    }
+}
+
+/// Queue handed out by [`FrameHub::subscribe`]. Ends once the consumer is
+/// detached from the hub.
+pub enum FrameSubscription {
+    Bounded(tokio::sync::mpsc::Receiver<Arc<CameraFrame>>),
+    Latest(Arc<LatestSlot>),
+}
+
+impl FrameSubscription {
+    /// Wait for the next frame; `None` once detached
+    pub async fn recv(&mut self) -> Option<Arc<CameraFrame>> {
+        match self {
+            FrameSubscription::Bounded(rx) => rx.recv().await,
+            FrameSubscription::Latest(slot) => loop {
+                if let Some(frame) = slot.take() {
+                    return Some(frame);
+                }
+                if slot.closed.load(Ordering::Acquire) {
+                    return None;
+                }
+                slot.notify.notified().await;
+            },
+        }
+    }
+
+    /// [`FrameSubscription::recv`] for dedicated threads
+    pub fn blocking_recv(&mut self) -> Option<Arc<CameraFrame>> {
+        futures::executor::block_on(self.recv())
+    }
+
+    /// The next frame if one is waiting
+    pub fn try_recv(&mut self) -> Option<Arc<CameraFrame>> {
+        match self {
+            FrameSubscription::Bounded(rx) => rx.try_recv().ok(),
+            FrameSubscription::Latest(slot) => slot.take(),
+        }
+    }
+}
+
+/// Single-frame mailbox behind [`QueuePolicy::Latest`]
+#[derive(Default)]
+pub struct LatestSlot {
+    frame: Mutex<Option<Arc<CameraFrame>>>,
+    notify: Notify,
+    /// Set when the hub lets go of the sink
+    closed: AtomicBool,
+}
+
+impl LatestSlot {
+    fn take(&self) -> Option<Arc<CameraFrame>> {
+        self.frame.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+}
+
+struct LatestSink(Arc<LatestSlot>);
+
+impl FrameSink for LatestSink {
+    fn offer(&mut self, frame: &Arc<CameraFrame>) -> Offer {
+        // Only the hub holds the slot: the subscription was dropped
+        if Arc::strong_count(&self.0) == 1 {
+            return Offer::Closed;
+        }
+        let previous = self
+            .0
+            .frame
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .replace(Arc::clone(frame));
+        self.0.notify.notify_one();
+        if previous.is_some() {
+            Offer::Replaced
+        } else {
+            Offer::Delivered
+        }
+    }
+}
+
+impl Drop for LatestSink {
+    fn drop(&mut self) {
+        self.0.closed.store(true, Ordering::Release);
+        self.0.notify.notify_one();
+    }
+}
+
+impl FrameSink for tokio::sync::mpsc::Sender<Arc<CameraFrame>> {
+    fn offer(&mut self, frame: &Arc<CameraFrame>) -> Offer {
+        match self.try_send(Arc::clone(frame)) {
+            Ok(()) => Offer::Delivered,
+            Err(TrySendError::Full(_)) => Offer::Dropped,
+            Err(TrySendError::Closed(_)) => Offer::Closed,
+        }
+    }
+}
+
+/// The preview channel takes frames by value
+impl FrameSink for FrameSender {
+    fn offer(&mut self, frame: &Arc<CameraFrame>) -> Offer {
+        match self.try_send(CameraFrame::clone(frame)) {
+            Ok(()) => Offer::Delivered,
+            Err(e) if e.is_disconnected() => Offer::Closed,
+            Err(_) => Offer::Dropped,
+        }
+    }
+}
+
+/// The recorder also takes raw MJPEG for hardware decoding
+impl FrameSink for tokio::sync::mpsc::Sender<RecordingFrame> {
+    fn offer(&mut self, frame: &Arc<CameraFrame>) -> Offer {
+        self.offer_encoded(&RecordingFrame::Decoded(Arc::clone(frame)))
+            .unwrap_or(Offer::Closed)
+    }
+
+    fn offer_encoded(&mut self, frame: &RecordingFrame) -> Option<Offer> {
+        let offer = match self.try_send(frame.clone()) {
+            Ok(()) => Offer::Delivered,
+            Err(TrySendError::Full(_)) => Offer::Dropped,
+            Err(TrySendError::Closed(_)) => Offer::Closed,
+        };
+        match offer {
+            Offer::Delivered => crate::pipelines::video::stats::rec_stats_capture_sent(),
+            Offer::Dropped => crate::pipelines::video::stats::rec_stats_capture_dropped(),
+            _ => {}
+        }
+        Some(offer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::camera::types::{FrameData, PixelFormat};
+
+    fn frame(width: u32) -> Arc<CameraFrame> {
+        Arc::new(CameraFrame {
+            width,
+            height: 1,
+            data: FrameData::Copied(Arc::from(vec![0u8; width as usize * 4])),
+            format: PixelFormat::RGBA,
+            stride: width * 4,
+            yuv_planes: None,
+            captured_at: std::time::Instant::now(),
+            sensor_timestamp_ns: None,
+            libcamera_metadata: None,
+        })
+    }
+
+    fn stats_of(hub: &FrameHub, kind: ConsumerKind) -> ConsumerStats {
+        hub.stats()
+            .into_iter()
+            .find(|stats| stats.kind == kind)
+            .unwrap()
+    }
+
+    #[test]
+    fn slow_consumer_drops_only_its_own_frames() {
+        let hub = FrameHub::new();
+        let mut recorder = hub.subscribe(ConsumerKind::Recording, QueuePolicy::Bounded(2));
+        let mut vcam = hub.subscribe(ConsumerKind::VirtualCamera, QueuePolicy::Latest);
+
+        for width in 1..=4 {
+            hub.publish(&frame(width));
+        }
+
+        // The bounded queue kept the first two, the latest slot the newest
+        assert_eq!(recorder.try_recv().unwrap().width, 1);
+        assert_eq!(recorder.try_recv().unwrap().width, 2);
+        assert!(recorder.try_recv().is_none());
+        assert_eq!(vcam.try_recv().unwrap().width, 4);
+
+        let recording = stats_of(&hub, ConsumerKind::Recording);
+        assert_eq!((recording.delivered, recording.dropped), (2, 2));
+        let virtual_camera = stats_of(&hub, ConsumerKind::VirtualCamera);
+        assert_eq!((virtual_camera.delivered, virtual_camera.dropped), (4, 3));
+    }
+
+    #[test]
+    fn dropped_subscriptions_are_detached() {
+        let hub = FrameHub::new();
+        let subscription = hub.subscribe(ConsumerKind::QrScanner, QueuePolicy::Latest);
+        hub.publish(&frame(1));
+        assert!(hub.has_consumer(ConsumerKind::QrScanner));
+
+        drop(subscription);
+        hub.publish(&frame(2));
+        assert!(!hub.has_consumer(ConsumerKind::QrScanner));
+    }
+
+    #[test]
+    fn detaching_ends_the_subscription() {
+        let hub = FrameHub::new();
+        let mut subscription = hub.subscribe(ConsumerKind::VirtualCamera, QueuePolicy::Latest);
+        hub.publish(&frame(1));
+        hub.detach_kind(ConsumerKind::VirtualCamera);
+
+        // A frame already waiting is still handed out
+        assert_eq!(subscription.blocking_recv().unwrap().width, 1);
+        assert!(subscription.blocking_recv().is_none());
+    }
+
+    #[test]
+    fn skipped_consumer_gets_nothing() {
+        let hub = FrameHub::new();
+        let mut recorder = hub.subscribe(ConsumerKind::Recording, QueuePolicy::Bounded(4));
+        let mut preview = hub.subscribe(ConsumerKind::Preview, QueuePolicy::Latest);
+        hub.publish_except(&frame(1), ConsumerKind::Recording);
+        assert!(recorder.try_recv().is_none());
+        assert!(preview.try_recv().is_some());
+    }
+
+    #[test]
+    fn targeted_publish_reaches_only_that_kind() {
+        let hub = FrameHub::new();
+        let mut qr = hub.subscribe(ConsumerKind::QrScanner, QueuePolicy::Latest);
+        let mut motion = hub.subscribe(ConsumerKind::MotionDetection, QueuePolicy::Latest);
+        hub.publish_to(ConsumerKind::QrScanner, &frame(1));
+        assert_eq!(qr.try_recv().unwrap().width, 1);
+        assert!(motion.try_recv().is_none());
+    }
+}
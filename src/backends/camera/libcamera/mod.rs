@@ -38,6 +38,7 @@ pub(crate) mod native;
 pub(crate) use native::{NativeLibcameraPipeline, PipelineSharedState};
 
 use super::CameraBackend;
+use super::frame_hub::{ConsumerKind, FrameHub, FrameSubscription, QueuePolicy};
 use super::types::*;
 use super::v4l2_utils;
 use libcamera::camera_manager::CameraManager;
@@ -620,10 +621,11 @@ impl CameraBackend for LibcameraBackend {
                 still_requested,
                 still_frame,
                 still_frame_notify: Arc::new(tokio::sync::Notify::new()),
-                recording_sender: Arc::new(Mutex::new(None)),
+                hub: Arc::new(FrameHub::new()),
                 jpeg_recording_mode: Arc::new(AtomicBool::new(false)),
                 cancel_flag: Arc::new(AtomicBool::new(false)),
             },
+            false,
         )?;
 
         // Store state
@@ -769,15 +771,19 @@ impl CameraBackend for LibcameraBackend {
 /// Opaque handle that keeps a camera pipeline alive. Drop to stop.
 pub struct CameraPipelineHandle {
     _pipeline: NativeLibcameraPipeline,
-    recording_sender: Arc<Mutex<Option<tokio::sync::mpsc::Sender<RecordingFrame>>>>,
+    hub: Arc<FrameHub>,
 }
 
 impl CameraPipelineHandle {
     /// Set or clear the recording frame sender for video capture.
     pub fn set_recording_sender(&self, sender: Option<tokio::sync::mpsc::Sender<RecordingFrame>>) {
-        if let Ok(mut guard) = self.recording_sender.lock() {
-            *guard = sender;
-        }
+        self.hub.set_recorder(sender);
+    }
+
+    /// Receive live frames alongside the preview channel, until the
+    /// subscription is dropped
+    pub fn subscribe_frames(&self, kind: ConsumerKind, policy: QueuePolicy) -> FrameSubscription {
+        self.hub.subscribe(kind, policy)
     }
 }
 
@@ -790,8 +796,7 @@ pub fn create_pipeline(
     format: &CameraFormat,
 ) -> Result<(CameraPipelineHandle, FrameReceiver), String> {
     let (sender, receiver) = futures::channel::mpsc::channel(10);
-    let recording_sender: Arc<Mutex<Option<tokio::sync::mpsc::Sender<RecordingFrame>>>> =
-        Arc::new(Mutex::new(None));
+    let hub = Arc::new(FrameHub::new());
     let pipeline = NativeLibcameraPipeline::new(
         &device.path,
         format,
@@ -801,16 +806,17 @@ pub fn create_pipeline(
             still_requested: Arc::new(AtomicBool::new(false)),
             still_frame: Arc::new(Mutex::new(None)),
             still_frame_notify: Arc::new(tokio::sync::Notify::new()),
-            recording_sender: Arc::clone(&recording_sender),
+            hub: Arc::clone(&hub),
             jpeg_recording_mode: Arc::new(AtomicBool::new(false)),
             cancel_flag: Arc::new(AtomicBool::new(false)),
         },
+        false,
     )
     .map_err(|e| format!("{}", e))?;
     Ok((
         CameraPipelineHandle {
             _pipeline: pipeline,
-            recording_sender,
+            hub,
         },
         receiver,
     ))
@@ -11,6 +11,7 @@ use super::diagnostics::{
     PREVIEW_FRAME_COUNT, STILL_FRAME_COUNT, StreamDiag, publish_diagnostics,
 };
 use super::pixel_formats::{map_pixel_format, pixel_format_name};
+use crate::backends::camera::frame_hub::{ConsumerKind, FrameHub, Offer};
 use crate::backends::camera::types::*;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
@@ -32,12 +33,15 @@ pub(crate) struct CaptureThreadParams {
     pub(crate) still_frame_notify: Arc<tokio::sync::Notify>,
     pub(crate) preview_frame_count: Arc<AtomicU64>,
     pub(crate) still_frame_count: Arc<AtomicU64>,
-    pub(crate) frame_sender: FrameSender,
-    pub(crate) recording_sender: Arc<Mutex<Option<tokio::sync::mpsc::Sender<RecordingFrame>>>>,
+    /// Every viewfinder frame is published here once
+    pub(crate) hub: Arc<FrameHub>,
     pub(crate) jpeg_recording_mode: Arc<AtomicBool>,
     /// Cancel flag — checked before creating CameraManager to abort if a newer
     /// mode switch has superseded this one.
     pub(crate) cancel_flag: Arc<AtomicBool>,
+    /// Capture card mode: fewest buffers, uncompressed frames when the card
+    /// offers them at this size
+    pub(crate) low_latency: bool,
 }
 
 /// Result of capture thread initialization (sent back to main thread)
@@ -204,7 +208,9 @@ fn compute_yuv_planes(
     stride: u32,
 ) -> Option<YuvPlanes> {
     match format {
-        PixelFormat::NV12 | PixelFormat::NV21 => {
+        // P010 is laid out like NV12 with 16-bit samples, so the same
+        // strides hold in bytes
+        PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::P010 => {
             let y_size = (stride * height) as usize;
             let uv_stride = stride; // interleaved UV, same stride as Y
             let uv_height = height.div_ceil(2);
@@ -440,7 +446,7 @@ fn capture_thread_setup_and_run(
     // Acquire exclusive access
     let mut active_cam = cam
         .acquire()
-        .map_err(|e| BackendError::InitializationFailed(format!("Camera acquire: {}", e)))?;
+        .map_err(|e| BackendError::from_device_io("Camera acquire", &e))?;
 
     let (config, is_multistream) = configure_streams(&cam, &mut active_cam, &params)?;
 
@@ -484,7 +490,7 @@ fn capture_thread_setup_and_run(
     // Start camera
     active_cam
         .start(None)
-        .map_err(|e| BackendError::InitializationFailed(format!("Camera start: {}", e)))?;
+        .map_err(|e| BackendError::from_device_io("Camera start", &e))?;
 
     // Queue all requests
     for req in requests {
@@ -571,6 +577,9 @@ fn capture_thread_setup_and_run(
 }
 
 /// Generate camera configuration with stream roles and apply to hardware.
+/// Formats the GPU renders without a CPU decode, in order of preference
+const UNCOMPRESSED_FOURCCS: [[u8; 4]; 2] = [*b"YUYV", *b"NV12"];
+
 fn configure_streams(
     cam: &libcamera::camera::Camera<'_>,
     active_cam: &mut libcamera::camera::ActiveCamera<'_>,
@@ -608,6 +617,29 @@ fn configure_streams(
             height = params.preview_height,
             "Set viewfinder size"
         );
+
+        if params.low_latency {
+            // A frame waits in every queued buffer, and an MJPEG frame also
+            // waits for its decode
+            vf_cfg
+                .set_buffer_count(crate::backends::camera::capture_card::LOW_LATENCY_BUFFER_COUNT);
+            let uncompressed = {
+                let formats = vf_cfg.formats();
+                let pixel_formats = formats.pixel_formats();
+                UNCOMPRESSED_FOURCCS.iter().find_map(|fourcc| {
+                    (&*pixel_formats).into_iter().find(|&pf| {
+                        pf.fourcc().to_le_bytes() == *fourcc && formats.sizes(pf).contains(&size)
+                    })
+                })
+            };
+            if let Some(pf) = uncompressed {
+                vf_cfg.set_pixel_format(pf);
+            }
+            info!(
+                uncompressed = uncompressed.is_some(),
+                "Configured viewfinder for low latency"
+            );
+        }
     }
 
     let status = config.validate();
@@ -860,36 +892,32 @@ fn run_capture_loop(
                 let jpeg_sent_to_recorder = if formats.vf_is_mjpeg
                     && params.jpeg_recording_mode.load(Ordering::Relaxed)
                 {
-                    if let Ok(guard) = params.recording_sender.lock()
-                        && let Some(ref tx) = *guard
-                    {
-                        let seq = metadata.sequence;
-                        let send_result = tx.try_send(RecordingFrame::Jpeg {
-                            data: Arc::from(data_slice),
-                            width: formats.vf_size.width,
-                            height: formats.vf_size.height,
-                            sensor_timestamp_ns,
-                            sequence: seq,
-                        });
-                        if send_result.is_err() {
-                            crate::pipelines::video::stats::rec_stats_capture_dropped();
-                            if frame_num.is_multiple_of(LOG_EVERY_N_FRAMES) {
+                    let seq = metadata.sequence;
+                    let jpeg = RecordingFrame::Jpeg {
+                        data: Arc::from(data_slice),
+                        width: formats.vf_size.width,
+                        height: formats.vf_size.height,
+                        sensor_timestamp_ns,
+                        sequence: seq,
+                    };
+                    match params.hub.publish_encoded(ConsumerKind::Recording, &jpeg) {
+                        Some(offer) => {
+                            if offer == Offer::Dropped
+                                && frame_num.is_multiple_of(LOG_EVERY_N_FRAMES)
+                            {
                                 warn!(frame = frame_num, seq = ?seq, "JPEG recording frame dropped (channel full)");
                             }
-                        } else {
-                            crate::pipelines::video::stats::rec_stats_capture_sent();
-                        }
-                        if frame_num.is_multiple_of(LOG_EVERY_N_FRAMES) {
-                            debug!(
-                                frame = frame_num,
-                                seq = ?seq,
-                                sensor_ts_ms = ?sensor_timestamp_ns.map(|t| t / 1_000_000),
-                                "Sent JPEG to recorder"
-                            );
+                            if frame_num.is_multiple_of(LOG_EVERY_N_FRAMES) {
+                                debug!(
+                                    frame = frame_num,
+                                    seq = ?seq,
+                                    sensor_ts_ms = ?sensor_timestamp_ns.map(|t| t / 1_000_000),
+                                    "Sent JPEG to recorder"
+                                );
+                            }
+                            true
                         }
-                        true
-                    } else {
-                        false
+                        None => false,
                     }
                 } else {
                     false
@@ -1015,8 +1043,7 @@ fn run_capture_loop(
 /// Dispatch a completed viewfinder frame to all consumers:
 /// 1. Store as latest preview (for still capture fallback)
 /// 2. Handle single-stream still capture
-/// 3. Send to recording (VF→encoder via appsrc)
-/// 4. Send to UI preview channel
+/// 3. Fan out to the preview, recording and other consumers via the hub
 fn dispatch_viewfinder_frame(
     frame: CameraFrame,
     frame_num: u64,
@@ -1050,36 +1077,26 @@ fn dispatch_viewfinder_frame(
         }
     }
 
-    // Send ViewFinder frames to recording (via appsrc encoder pipeline).
-    // Skip if raw JPEG was already sent to the recorder in JPEG mode.
-    if !skip_recording
-        && let Ok(guard) = params.recording_sender.lock()
-        && let Some(ref tx) = *guard
-    {
-        let seq = frame.libcamera_metadata.as_ref().and_then(|m| m.sequence);
-        let send_result = tx.try_send(RecordingFrame::Decoded(Arc::new(frame.clone())));
-        if send_result.is_err() {
-            crate::pipelines::video::stats::rec_stats_capture_dropped();
-        } else {
-            crate::pipelines::video::stats::rec_stats_capture_sent();
-        }
-        if frame_num.is_multiple_of(LOG_EVERY_N_FRAMES) {
+    // Fan the frame out to the preview, recorder, virtual camera and any
+    // other consumer. Skip the recorder if raw JPEG was already sent to it
+    // in JPEG mode.
+    let frame = Arc::new(frame);
+    if skip_recording {
+        params.hub.publish_except(&frame, ConsumerKind::Recording);
+    } else {
+        params.hub.publish(&frame);
+    }
+    if frame_num.is_multiple_of(LOG_EVERY_N_FRAMES) {
+        for stats in params.hub.stats() {
             debug!(
                 frame = frame_num,
-                seq = ?seq,
-                sensor_ts_ms = ?frame.sensor_timestamp_ns.map(|t| t / 1_000_000),
-                dropped = send_result.is_err(),
-                "Sent decoded frame to recorder"
+                consumer = stats.kind.label(),
+                delivered = stats.delivered,
+                dropped = stats.dropped,
+                "Frame consumer stats"
             );
         }
     }
-
-    // Send to UI (preview)
-    if let Err(e) = params.frame_sender.try_send(frame)
-        && frame_num.is_multiple_of(LOG_EVERY_N_FRAMES)
-    {
-        debug!(error = ?e, "Preview frame dropped (channel full)");
-    }
 }
 
 /// Extract per-frame metadata from a completed request
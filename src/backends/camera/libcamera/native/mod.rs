@@ -23,9 +23,12 @@
 //! │  still_requested ────┼────────►│  FrameBuffers         │
 //! │  latest_preview ◄────┼─────────│  Request loop         │
 //! │  latest_still   ◄────┼─────────│                       │
-//! │  frame_sender   ◄────┼─────────│  (all libcamera ops)  │
+//! │  frame hub      ◄────┼─────────│  (all libcamera ops)  │
 //! └──────────────────────┘         └───────────────────────┘
 //! ```
+//!
+//! The preview channel is attached to the frame hub for the pipeline's
+//! lifetime; the recorder and other consumers attach to the same hub.
 
 mod capture_thread;
 pub mod diagnostics;
@@ -33,6 +36,7 @@ pub(crate) mod pixel_formats;
 
 pub use diagnostics::is_capture_active;
 
+use crate::backends::camera::frame_hub::{ConsumerId, ConsumerKind, FrameHub, QueuePolicy};
 use crate::backends::camera::types::*;
 use capture_thread::{CaptureThreadInitResult, CaptureThreadParams, capture_thread_main};
 use diagnostics::clear_global_diagnostics;
@@ -49,7 +53,8 @@ pub(crate) struct PipelineSharedState {
     /// Notifier fired by the capture thread when a new still frame is stored.
     /// Allows consumers to await rather than poll.
     pub(crate) still_frame_notify: Arc<tokio::sync::Notify>,
-    pub(crate) recording_sender: Arc<Mutex<Option<tokio::sync::mpsc::Sender<RecordingFrame>>>>,
+    /// Fan-out hub shared with the recorder and other frame consumers
+    pub(crate) hub: Arc<FrameHub>,
     pub(crate) jpeg_recording_mode: Arc<AtomicBool>,
     /// Cancel flag from the subscription — allows the capture thread to abort
     /// before creating a CameraManager if a newer mode switch superseded this one.
@@ -75,10 +80,11 @@ pub(crate) struct NativeLibcameraPipeline {
     preview_frame_count: Arc<AtomicU64>,
     /// Still frame counter
     still_frame_count: Arc<AtomicU64>,
-    /// Dynamically-settable sender for direct recording path (bypasses UI thread).
-    /// Set to Some(tx) when recording starts, None when recording stops.
-    /// Kept alive here so the Arc is not dropped while the capture thread holds a clone.
-    _recording_sender: Arc<Mutex<Option<tokio::sync::mpsc::Sender<RecordingFrame>>>>,
+    /// Hub the capture thread publishes to. The recorder attaches here when
+    /// recording starts (bypassing the UI thread).
+    hub: Arc<FrameHub>,
+    /// This pipeline's preview channel in `hub`
+    preview_consumer: ConsumerId,
 }
 
 impl NativeLibcameraPipeline {
@@ -89,16 +95,19 @@ impl NativeLibcameraPipeline {
     /// * `preview_format` - Format for preview stream (typically 1080p or lower)
     /// * `supports_multistream` - Whether camera supports dual-stream capture
     /// * `shared` - Shared communication handles (frame sender, still capture, recording)
+    /// * `low_latency` - Capture card mode: minimal buffering, uncompressed frames
     pub(crate) fn new(
         camera_id: &str,
         preview_format: &CameraFormat,
         supports_multistream: bool,
         shared: PipelineSharedState,
+        low_latency: bool,
     ) -> BackendResult<Self> {
         info!(
             camera = camera_id,
             preview = %preview_format,
             multistream = supports_multistream,
+            low_latency,
             "Creating native libcamera pipeline"
         );
 
@@ -124,12 +133,25 @@ impl NativeLibcameraPipeline {
             still_frame_notify: Arc::clone(&shared.still_frame_notify),
             preview_frame_count: Arc::clone(&preview_frame_count),
             still_frame_count: Arc::clone(&still_frame_count),
-            frame_sender: shared.frame_sender,
-            recording_sender: Arc::clone(&shared.recording_sender),
+            hub: Arc::clone(&shared.hub),
             jpeg_recording_mode: Arc::clone(&shared.jpeg_recording_mode),
             cancel_flag: Arc::clone(&shared.cancel_flag),
+            low_latency,
         };
 
+        // In capture card mode the preview shows the newest frame or nothing,
+        // rather than catching up through a queue
+        let preview_policy = if low_latency {
+            QueuePolicy::Latest
+        } else {
+            QueuePolicy::Bounded(crate::constants::latency::FRAME_CHANNEL_CAPACITY)
+        };
+        let preview_consumer = shared.hub.attach(
+            ConsumerKind::Preview,
+            preview_policy,
+            Box::new(shared.frame_sender),
+        );
+
         // Spawn capture thread - it owns all libcamera objects
         let capture_thread = std::thread::Builder::new()
             .name("libcamera-capture".to_string())
@@ -138,14 +160,24 @@ impl NativeLibcameraPipeline {
             })
             .map_err(|e| {
                 BackendError::InitializationFailed(format!("Spawn capture thread: {}", e))
-            })?;
+            });
 
         // Wait for initialization result from capture thread
-        let init_result = init_rx.recv().map_err(|_| {
-            BackendError::InitializationFailed(
-                "Capture thread died during initialization".to_string(),
-            )
-        })??;
+        let init_result = capture_thread.and_then(|thread| {
+            let result = init_rx.recv().map_err(|_| {
+                BackendError::InitializationFailed(
+                    "Capture thread died during initialization".to_string(),
+                )
+            })??;
+            Ok((thread, result))
+        });
+        let (capture_thread, init_result) = match init_result {
+            Ok(started) => started,
+            Err(e) => {
+                shared.hub.detach(preview_consumer);
+                return Err(e);
+            }
+        };
 
         info!(
             multistream = init_result.is_multistream,
@@ -160,7 +192,8 @@ impl NativeLibcameraPipeline {
             latest_still: shared.still_frame,
             preview_frame_count,
             still_frame_count,
-            _recording_sender: shared.recording_sender,
+            hub: shared.hub,
+            preview_consumer,
         })
     }
 
@@ -220,6 +253,7 @@ impl Drop for NativeLibcameraPipeline {
         {
             error!("Capture thread panicked: {:?}", e);
         }
+        self.hub.detach(self.preview_consumer);
 
         let (preview_count, still_count) = self.frame_counts();
         info!(
@@ -13,18 +13,25 @@ use tracing::debug;
 ///   DRM ABGR8888 → memory R,G,B,A → PixelFormat::RGBA
 ///   DRM ARGB8888 → memory B,G,R,A → PixelFormat::BGRA
 ///   DRM RGBA8888 → memory A,B,G,R → PixelFormat::ABGR
+///
+/// The X variants leave the fourth byte undefined, so they get their own
+/// formats that the shader makes opaque.
 pub(crate) fn map_pixel_format(pf: libcamera::pixel_format::PixelFormat) -> Option<PixelFormat> {
     match DrmFourcc::try_from(pf.fourcc()) {
-        Ok(DrmFourcc::Abgr8888) | Ok(DrmFourcc::Xbgr8888) => Some(PixelFormat::RGBA),
-        Ok(DrmFourcc::Argb8888) | Ok(DrmFourcc::Xrgb8888) => Some(PixelFormat::BGRA),
+        Ok(DrmFourcc::Abgr8888) => Some(PixelFormat::RGBA),
+        Ok(DrmFourcc::Xbgr8888) => Some(PixelFormat::RGBx),
+        Ok(DrmFourcc::Argb8888) => Some(PixelFormat::BGRA),
+        Ok(DrmFourcc::Xrgb8888) => Some(PixelFormat::BGRx),
         Ok(DrmFourcc::Rgba8888) => Some(PixelFormat::ABGR),
         Ok(DrmFourcc::Nv12) => Some(PixelFormat::NV12),
         Ok(DrmFourcc::Nv21) => Some(PixelFormat::NV21),
+        Ok(DrmFourcc::P010) => Some(PixelFormat::P010),
         Ok(DrmFourcc::Yuv420) => Some(PixelFormat::I420),
         Ok(DrmFourcc::Yuyv) => Some(PixelFormat::YUYV),
         Ok(DrmFourcc::Uyvy) => Some(PixelFormat::UYVY),
         Ok(DrmFourcc::Yvyu) => Some(PixelFormat::YVYU),
         Ok(DrmFourcc::Vyuy) => Some(PixelFormat::VYUY),
+        Ok(DrmFourcc::Y210) => Some(PixelFormat::Y210),
         Ok(DrmFourcc::Rgb888) | Ok(DrmFourcc::Bgr888) => Some(PixelFormat::RGB24),
         // Single-channel 8-bit luma (DRM R8 ≡ V4L2 GREY / Y8). Used by IR and
         // monochrome sensors — e.g. the laptop "Integrated I" face-auth camera
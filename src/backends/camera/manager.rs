@@ -5,7 +5,13 @@
 //! The manager provides:
 //! - Backend lifecycle management (initialization, shutdown)
 //! - Thread-safe backend access
+//! - The [`FrameHub`] that fans captured frames out to the preview, the
+//!   recorder and other consumers
+//! - The optional secondary camera for picture-in-picture and side-by-side
+//!   capture (see [`super::pip`])
 
+use super::frame_hub::{ConsumerKind, FrameHub, FrameSubscription, QueuePolicy};
+use super::pip::{SecondaryCamera, SharedPipFrame};
 use super::types::*;
 use super::{CameraBackend, create_backend};
 use std::sync::Arc;
@@ -13,13 +19,6 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Mutex, RwLock};
 use tracing::info;
 
-/// Shared recording sender type.
-///
-/// This Arc lives in the manager (not inside any pipeline) so it survives
-/// pipeline restarts and is accessible from both the subscription's capture
-/// thread and the recording start/stop code.
-pub type SharedRecordingSender = Arc<Mutex<Option<tokio::sync::mpsc::Sender<RecordingFrame>>>>;
-
 /// Internal manager state
 struct ManagerState {
     /// The active backend instance
@@ -33,12 +32,16 @@ struct ManagerState {
 #[derive(Clone)]
 pub struct CameraBackendManager {
     state: Arc<RwLock<ManagerState>>,
-    /// Shared recording sender — written by recording start/stop,
-    /// read by the capture thread (via Arc clone passed to the pipeline).
-    recording_sender: SharedRecordingSender,
+    /// Frame fan-out hub. It lives here (not inside any pipeline) so it
+    /// survives pipeline restarts: the capture thread publishes to it, and
+    /// recording and the virtual camera attach to it directly.
+    frame_hub: Arc<FrameHub>,
     /// When true, the capture thread sends raw JPEG bytes (not decoded frames)
     /// to the recording channel for GPU-accelerated decode via VA-API.
     jpeg_recording_mode: Arc<AtomicBool>,
+    /// Latest picture-in-picture frame, written by the secondary camera and
+    /// read by recordings and the virtual camera.
+    pip_frame: SharedPipFrame,
 }
 
 impl Default for CameraBackendManager {
@@ -57,8 +60,9 @@ impl CameraBackendManager {
 
         Self {
             state: Arc::new(RwLock::new(state)),
-            recording_sender: Arc::new(Mutex::new(None)),
+            frame_hub: Arc::new(FrameHub::new()),
             jpeg_recording_mode: Arc::new(AtomicBool::new(false)),
+            pip_frame: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -157,10 +161,10 @@ impl CameraBackendManager {
 
     /// Set (or clear) the direct recording sender.
     ///
-    /// This writes to a shared Arc that the capture thread reads from,
-    /// independent of which pipeline instance is active.
+    /// This attaches the recorder to the shared frame hub, independent of
+    /// which pipeline instance is active.
     pub fn set_recording_sender(&self, sender: Option<tokio::sync::mpsc::Sender<RecordingFrame>>) {
-        *self.recording_sender.lock().unwrap() = sender;
+        self.frame_hub.set_recorder(sender);
     }
 
     /// Enable or disable JPEG recording mode.
@@ -176,11 +180,51 @@ impl CameraBackendManager {
         Arc::clone(&self.jpeg_recording_mode)
     }
 
-    /// Get a clone of the shared recording sender Arc.
+    /// Get a clone of the frame hub Arc.
     ///
-    /// Pass this to the pipeline so the capture thread can read from it.
-    pub fn recording_sender(&self) -> SharedRecordingSender {
-        Arc::clone(&self.recording_sender)
+    /// Pass this to the pipeline so the capture thread can publish to it.
+    pub fn frame_hub(&self) -> Arc<FrameHub> {
+        Arc::clone(&self.frame_hub)
+    }
+
+    /// Receive live frames from the active camera with the given queue
+    /// policy, until the subscription is dropped or `kind` is detached
+    pub fn subscribe_frames(&self, kind: ConsumerKind, policy: QueuePolicy) -> FrameSubscription {
+        self.frame_hub.subscribe(kind, policy)
+    }
+
+    /// Stop delivering frames to every consumer of `kind`
+    pub fn unsubscribe_frames(&self, kind: ConsumerKind) {
+        self.frame_hub.detach_kind(kind);
+    }
+
+    /// Start the secondary camera (picture-in-picture or side by side),
+    /// scaled to `width`.
+    ///
+    /// Runs alongside the primary pipeline and publishes into the shared
+    /// PiP frame; frames are also sent on `preview` for the UI. The secondary
+    /// camera runs until the returned handle is dropped.
+    pub fn start_pip_camera(
+        &self,
+        device: &CameraDevice,
+        width: u32,
+        preview: tokio::sync::mpsc::Sender<Arc<CameraFrame>>,
+    ) -> BackendResult<SecondaryCamera> {
+        if self
+            .current_device()
+            .is_some_and(|current| current.path == device.path)
+        {
+            return Err(BackendError::Other(
+                "Second camera is the active camera".to_string(),
+            ));
+        }
+        info!(device = %device.name, width, "Starting second camera");
+        SecondaryCamera::start(device, width, Arc::clone(&self.pip_frame), preview)
+    }
+
+    /// Get a clone of the shared picture-in-picture frame Arc.
+    pub fn pip_frame(&self) -> SharedPipFrame {
+        Arc::clone(&self.pip_frame)
     }
 
     /// Get current device
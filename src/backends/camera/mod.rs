@@ -28,10 +28,14 @@
 //!       └──────────┘
 //! ```
 
+pub mod capture_card;
+pub mod frame_hub;
 pub mod libcamera;
 pub mod manager;
+pub mod pip;
 pub mod synthetic;
 pub mod types;
+pub mod usb_bandwidth;
 pub mod v4l2_controls;
 pub mod v4l2_utils;
 
@@ -0,0 +1,445 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Secondary camera for picture-in-picture and side-by-side capture
+//!
+//! libcamera allows a single `CameraManager` per process, and the primary
+//! pipeline already owns it, so the secondary camera is opened through a
+//! plain GStreamer `v4l2src` pipeline instead, at its own resolution. It
+//! keeps only the latest frame in a [`SharedPipFrame`] held by the
+//! [`CameraBackendManager`]. Consumers — the recording pusher, the virtual
+//! camera thread and photo capture — read that slot through a [`PipOverlay`]
+//! and composite it on the GPU, so the second camera is baked into every
+//! output without the primary pipeline knowing about it.
+//!
+//! [`CameraBackendManager`]: super::CameraBackendManager
+
+use super::types::{BackendError, BackendResult, CameraDevice, CameraFrame};
+use crate::config::{DualCameraLayout, PipCorner};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, info, warn};
+
+/// Latest frame from the secondary camera (None while it isn't running).
+///
+/// Lives in the manager so it outlives the secondary pipeline and can be
+/// handed to recordings and the virtual camera before the first frame.
+pub type SharedPipFrame = Arc<Mutex<Option<Arc<CameraFrame>>>>;
+
+/// Longer inset side as a fraction of the frame's shorter side
+const INSET_SCALE: f32 = 0.4;
+/// Gap between the inset and the frame edges, as a fraction of the frame's
+/// shorter side
+const INSET_MARGIN: f32 = 0.03;
+/// Corner radius as a fraction of the inset's shorter side
+const INSET_RADIUS: f32 = 0.08;
+/// Light outline around a picture-in-picture inset, in output pixels
+const INSET_BORDER: f32 = 2.0;
+
+/// A normalised sub-rectangle of a frame (0..1 on both axes)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UvRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl UvRect {
+    /// The whole frame
+    pub const FULL: UvRect = UvRect {
+        x: 0.0,
+        y: 0.0,
+        width: 1.0,
+        height: 1.0,
+    };
+
+    /// Centred crop of a `source`-sized frame that fills a `target`-sized
+    /// area without distortion
+    pub fn cover(source: (u32, u32), target: (u32, u32)) -> Self {
+        let src_aspect = source.0 as f32 / source.1.max(1) as f32;
+        let dst_aspect = target.0 as f32 / target.1.max(1) as f32;
+        if src_aspect > dst_aspect {
+            let width = dst_aspect / src_aspect;
+            Self {
+                x: (1.0 - width) / 2.0,
+                y: 0.0,
+                width,
+                height: 1.0,
+            }
+        } else {
+            let height = src_aspect / dst_aspect;
+            Self {
+                x: 0.0,
+                y: (1.0 - height) / 2.0,
+                width: 1.0,
+                height,
+            }
+        }
+    }
+}
+
+/// Where the secondary camera lands in an output frame, in output pixels
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InsetRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    pub radius: f32,
+    /// Outline width (0 = none)
+    pub border: f32,
+    /// Part of the secondary frame shown in the rectangle
+    pub crop: UvRect,
+}
+
+/// How both cameras are laid out in an output frame
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DualComposite {
+    /// Part of the primary frame stretched across the whole output. Only
+    /// the area outside the inset is visible.
+    pub base_crop: UvRect,
+    pub inset: InsetRect,
+}
+
+/// Lay out a `source`-sized secondary frame over a `frame`-sized output.
+///
+/// Picture-in-picture keeps the primary frame as it is and insets the
+/// secondary camera in `corner`. Side by side gives each camera one half,
+/// each showing a centred crop so the same subject lines up in both.
+pub fn dual_composite(
+    frame: (u32, u32),
+    source: (u32, u32),
+    layout: DualCameraLayout,
+    corner: PipCorner,
+) -> Option<DualComposite> {
+    match layout {
+        DualCameraLayout::PictureInPicture => Some(DualComposite {
+            base_crop: UvRect::FULL,
+            inset: inset_rect(frame, source, corner)?,
+        }),
+        DualCameraLayout::SideBySide => {
+            let (frame_w, frame_h) = frame;
+            if frame_w < 2 || frame_h == 0 || source.0 == 0 || source.1 == 0 {
+                return None;
+            }
+            let left = frame_w / 2;
+            let right = frame_w - left;
+            let primary = UvRect::cover(frame, (left, frame_h));
+            Some(DualComposite {
+                // Scale the left-half crop up to the full output width
+                base_crop: UvRect {
+                    width: primary.width * frame_w as f32 / left as f32,
+                    ..primary
+                },
+                inset: InsetRect {
+                    x: left,
+                    y: 0,
+                    width: right,
+                    height: frame_h,
+                    radius: 0.0,
+                    border: 0.0,
+                    crop: UvRect::cover(source, (right, frame_h)),
+                },
+            })
+        }
+    }
+}
+
+/// Place a `source`-sized inset in `corner` of a `frame`-sized output,
+/// keeping the source aspect ratio. The size depends on the frame's shorter
+/// side so portrait and landscape outputs get the same inset.
+pub fn inset_rect(frame: (u32, u32), source: (u32, u32), corner: PipCorner) -> Option<InsetRect> {
+    let (frame_w, frame_h) = frame;
+    let (src_w, src_h) = source;
+    if frame_w == 0 || frame_h == 0 || src_w == 0 || src_h == 0 {
+        return None;
+    }
+
+    let short_side = frame_w.min(frame_h) as f32;
+    let long = short_side * INSET_SCALE;
+    let aspect = src_w as f32 / src_h as f32;
+    let (width, height) = if aspect >= 1.0 {
+        (long, long / aspect)
+    } else {
+        (long * aspect, long)
+    };
+    let width = (width.round() as u32).clamp(1, frame_w);
+    let height = (height.round() as u32).clamp(1, frame_h);
+    let margin = (short_side * INSET_MARGIN).round() as u32;
+
+    let left = margin.min(frame_w - width);
+    let right = frame_w.saturating_sub(width + margin);
+    let top = margin.min(frame_h - height);
+    let bottom = frame_h.saturating_sub(height + margin);
+    let (x, y) = match corner {
+        PipCorner::TopLeft => (left, top),
+        PipCorner::TopRight => (right, top),
+        PipCorner::BottomLeft => (left, bottom),
+        PipCorner::BottomRight => (right, bottom),
+    };
+
+    Some(InsetRect {
+        x,
+        y,
+        width,
+        height,
+        radius: width.min(height) as f32 * INSET_RADIUS,
+        border: INSET_BORDER,
+        crop: UvRect::FULL,
+    })
+}
+
+/// A running secondary camera pipeline.
+///
+/// Owns the GStreamer pipeline and its reader thread; dropping it stops
+/// both and clears the shared frame so outputs stop drawing the inset.
+pub struct SecondaryCamera {
+    pipeline: gstreamer::Pipeline,
+    stop: Arc<AtomicBool>,
+    reader: Option<std::thread::JoinHandle<()>>,
+    shared: SharedPipFrame,
+}
+
+impl SecondaryCamera {
+    /// Open `device` scaled to `width` and start publishing its frames into
+    /// `shared`. Each frame is also offered to `preview` (dropped when the UI
+    /// is behind).
+    pub(super) fn start(
+        device: &CameraDevice,
+        width: u32,
+        shared: SharedPipFrame,
+        preview: tokio::sync::mpsc::Sender<Arc<CameraFrame>>,
+    ) -> BackendResult<Self> {
+        use gstreamer::prelude::*;
+
+        let path = device
+            .v4l2_path()
+            .filter(|p| p.starts_with("/dev/video"))
+            .ok_or_else(|| {
+                BackendError::DeviceNotFound(format!(
+                    "{} has no V4L2 capture node for a second camera",
+                    device.name
+                ))
+            })?;
+
+        gstreamer::init()
+            .map_err(|e| BackendError::Other(format!("GStreamer init failed: {}", e)))?;
+
+        let pipeline_str = format!(
+            "v4l2src device=\"{path}\" ! decodebin ! videoconvert ! videoscale ! \
+             video/x-raw,format=RGBA,width={width},pixel-aspect-ratio=1/1 ! \
+             appsink name=sink max-buffers=1 drop=true sync=false"
+        );
+        let pipeline = gstreamer::parse::launch(&pipeline_str)
+            .map_err(|e| {
+                BackendError::InitializationFailed(format!("Second camera pipeline: {}", e))
+            })?
+            .downcast::<gstreamer::Pipeline>()
+            .map_err(|_| BackendError::Other("Failed to downcast to Pipeline".into()))?;
+        let appsink = pipeline
+            .by_name("sink")
+            .ok_or_else(|| BackendError::Other("Failed to find appsink".into()))?
+            .downcast::<gstreamer_app::AppSink>()
+            .map_err(|_| BackendError::Other("Failed to downcast to AppSink".into()))?;
+
+        pipeline.set_state(gstreamer::State::Playing).map_err(|e| {
+            let _ = pipeline.set_state(gstreamer::State::Null);
+            BackendError::InitializationFailed(format!("Failed to start second camera: {:?}", e))
+        })?;
+        info!(device = %device.name, path, width, "Second camera started");
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let reader = {
+            let stop = Arc::clone(&stop);
+            let shared = Arc::clone(&shared);
+            std::thread::Builder::new()
+                .name("pip-camera".into())
+                .spawn(move || {
+                    while !stop.load(Ordering::Acquire) {
+                        let Some(sample) =
+                            appsink.try_pull_sample(gstreamer::ClockTime::from_mseconds(100))
+                        else {
+                            if appsink.is_eos() {
+                                warn!("Second camera stream ended");
+                                break;
+                            }
+                            continue;
+                        };
+                        match crate::backends::virtual_camera::extract_frame_from_sample(&sample) {
+                            Ok(frame) => {
+                                let frame = Arc::new(frame);
+                                *shared.lock().unwrap() = Some(Arc::clone(&frame));
+                                let _ = preview.try_send(frame);
+                            }
+                            Err(e) => debug!(error = %e, "Dropping PiP sample"),
+                        }
+                    }
+                })
+                .map_err(|e| BackendError::Other(format!("Failed to spawn PiP reader: {}", e)))?
+        };
+
+        Ok(Self {
+            pipeline,
+            stop,
+            reader: Some(reader),
+            shared,
+        })
+    }
+}
+
+impl Drop for SecondaryCamera {
+    fn drop(&mut self) {
+        use gstreamer::prelude::*;
+
+        self.stop.store(true, Ordering::Release);
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+        let _ = self.pipeline.set_state(gstreamer::State::Null);
+        *self.shared.lock().unwrap() = None;
+        info!("Second camera stopped");
+    }
+}
+
+/// Composites the secondary camera into output frames
+#[derive(Clone)]
+pub struct PipOverlay {
+    frame: SharedPipFrame,
+    layout: DualCameraLayout,
+    corner: PipCorner,
+}
+
+impl PipOverlay {
+    pub fn new(frame: SharedPipFrame, layout: DualCameraLayout, corner: PipCorner) -> Self {
+        Self {
+            frame,
+            layout,
+            corner,
+        }
+    }
+
+    /// The secondary camera's latest frame. Photo capture takes this when
+    /// the shutter fires so both cameras are captured at the same moment,
+    /// however long the primary frame then takes to process.
+    pub fn snapshot(&self) -> Option<Arc<CameraFrame>> {
+        self.frame.lock().unwrap().clone()
+    }
+
+    /// Draw the latest secondary frame into tightly packed RGBA `base`.
+    ///
+    /// Returns `base` unchanged when the secondary camera has no frame yet
+    /// or the GPU pass fails, so a flaky second camera never costs output
+    /// frames.
+    pub async fn composite(&self, base: Vec<u8>, width: u32, height: u32) -> Vec<u8> {
+        match self.snapshot() {
+            Some(secondary) => self.composite_with(base, width, height, &secondary).await,
+            None => base,
+        }
+    }
+
+    /// Like [`Self::composite`], with an explicit secondary frame
+    pub async fn composite_with(
+        &self,
+        base: Vec<u8>,
+        width: u32,
+        height: u32,
+        secondary: &CameraFrame,
+    ) -> Vec<u8> {
+        let Some(layout) = dual_composite(
+            (width, height),
+            (secondary.width, secondary.height),
+            self.layout,
+            self.corner,
+        ) else {
+            return base;
+        };
+
+        match crate::shaders::composite_pip_gpu_rgba(&base, width, height, secondary, layout).await
+        {
+            Ok(out) => out,
+            Err(e) => {
+                warn!(error = %e, "Second camera composite failed");
+                base
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inset_keeps_source_aspect() {
+        let rect = inset_rect((1920, 1080), (640, 480), PipCorner::BottomRight).unwrap();
+        assert_eq!((rect.width, rect.height), (432, 324));
+
+        let rect = inset_rect((1920, 1080), (480, 640), PipCorner::BottomRight).unwrap();
+        assert_eq!((rect.width, rect.height), (324, 432));
+    }
+
+    #[test]
+    fn inset_sits_in_the_requested_corner() {
+        let frame = (1920, 1080);
+        let source = (640, 360);
+        let margin = 32; // 3% of 1080, rounded
+
+        let tl = inset_rect(frame, source, PipCorner::TopLeft).unwrap();
+        assert_eq!((tl.x, tl.y), (margin, margin));
+
+        let br = inset_rect(frame, source, PipCorner::BottomRight).unwrap();
+        assert_eq!(br.x + br.width + margin, 1920);
+        assert_eq!(br.y + br.height + margin, 1080);
+
+        let tr = inset_rect(frame, source, PipCorner::TopRight).unwrap();
+        assert_eq!((tr.x, tr.y), (br.x, tl.y));
+    }
+
+    #[test]
+    fn inset_is_orientation_independent() {
+        let landscape = inset_rect((1920, 1080), (640, 360), PipCorner::TopLeft).unwrap();
+        let portrait = inset_rect((1080, 1920), (640, 360), PipCorner::TopLeft).unwrap();
+        assert_eq!(
+            (landscape.width, landscape.height),
+            (portrait.width, portrait.height)
+        );
+    }
+
+    #[test]
+    fn cover_crop_is_centred() {
+        let crop = UvRect::cover((1920, 1080), (960, 1080));
+        assert!((crop.width - 0.5).abs() < 1e-6);
+        assert!((crop.x - 0.25).abs() < 1e-6);
+        assert_eq!(crop.height, 1.0);
+
+        let crop = UvRect::cover((640, 480), (1280, 480));
+        assert_eq!(crop.width, 1.0);
+        assert!((crop.y - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn side_by_side_splits_the_frame() {
+        let layout = dual_composite(
+            (1920, 1080),
+            (1280, 720),
+            DualCameraLayout::SideBySide,
+            PipCorner::TopLeft,
+        )
+        .unwrap();
+        assert_eq!((layout.inset.x, layout.inset.width), (960, 960));
+        assert_eq!(layout.inset.height, 1080);
+        assert_eq!(layout.inset.border, 0.0);
+
+        // The left half shows the centre half of the primary frame: output
+        // x = 0 maps to u = 0.25 and x = 960 (the split) to u = 0.75
+        let base = layout.base_crop;
+        assert!((base.x - 0.25).abs() < 1e-6);
+        assert!((base.x + base.width * 0.5 - 0.75).abs() < 1e-6);
+    }
+
+    #[test]
+    fn empty_sizes_have_no_inset() {
+        assert!(inset_rect((0, 1080), (640, 360), PipCorner::TopLeft).is_none());
+        assert!(inset_rect((1920, 1080), (0, 0), PipCorner::TopLeft).is_none());
+    }
+}
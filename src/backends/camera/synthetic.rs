@@ -16,6 +16,7 @@
 //! devices are pure metadata.
 
 use crate::backends::camera::types::{CameraDevice, CameraFormat, Framerate};
+use crate::backends::virtual_camera::SimulatedCapture;
 
 /// Two synthetic cameras — a back and a front, so the camera switcher renders
 /// (it only appears with more than one device) — plus a plausible set of
@@ -24,8 +25,11 @@ use crate::backends::camera::types::{CameraDevice, CameraFormat, Framerate};
 /// The frames themselves come from `--preview-source`; these values only drive
 /// the chrome (camera list, resolution/framerate/mode pickers). The first entry
 /// is the one the app starts on, so its `path` must be non-empty for
-/// `pick_startup_camera_index` to select it.
-pub fn synthetic_preview_cameras() -> (Vec<CameraDevice>, Vec<CameraFormat>) {
+/// `pick_startup_camera_index` to select it. With `--preview-capture` the
+/// cameras offer just that format, matching the frames the source delivers.
+pub fn synthetic_preview_cameras(
+    capture: Option<SimulatedCapture>,
+) -> (Vec<CameraDevice>, Vec<CameraFormat>) {
     let device = |name: &str, id: &str, location: &str| CameraDevice {
         name: name.to_string(),
         path: id.to_string(),
@@ -42,7 +46,18 @@ pub fn synthetic_preview_cameras() -> (Vec<CameraDevice>, Vec<CameraFormat>) {
         device("Front Camera", "preview-front", "front"),
     ];
 
-    (cameras, synthetic_formats())
+    let formats = match capture {
+        Some(capture) => vec![CameraFormat {
+            width: capture.width,
+            height: capture.height,
+            framerate: Some(Framerate::from_int(capture.framerate)),
+            hardware_accelerated: true,
+            pixel_format: "MJPG".to_string(),
+        }],
+        None => synthetic_formats(),
+    };
+
+    (cameras, formats)
 }
 
 /// A small spread of common resolutions and framerates. Photo mode selects the
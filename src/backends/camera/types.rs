@@ -140,6 +140,12 @@ impl SensorRotation {
         }
     }
 
+    /// Correction for this rotation followed by `other` (e.g. the sensor
+    /// mounting plus how the device is currently held)
+    pub fn compose(self, other: SensorRotation) -> SensorRotation {
+        Self::from_degrees_int((self.degrees() + other.degrees()) as i32)
+    }
+
     /// Check if rotation swaps width and height
     pub fn swaps_dimensions(&self) -> bool {
         matches!(self, SensorRotation::Rotate90 | SensorRotation::Rotate270)
@@ -321,6 +327,18 @@ pub enum PixelFormat {
     /// GBRG Bayer pattern - Raw sensor data requiring debayering
     /// Row 0: G B G B..., Row 1: R G R G...
     BayerGBRG,
+    /// RGBx - 32-bit RGB with an unused fourth byte (R G B X byte order)
+    /// The padding byte is undefined, so the shader makes the result opaque
+    RGBx,
+    /// BGRx - 32-bit BGR with an unused fourth byte (B G R X byte order)
+    /// Common from capture cards and ISPs that never fill in alpha
+    BGRx,
+    /// P010 - Semi-planar 4:2:0 like NV12, 16 bits per sample (little endian)
+    /// with 10-bit values in the high bits. From HDMI capture cards and ISPs
+    P010,
+    /// Y210 - Packed 4:2:2 like YUYV, 16 bits per sample (little endian)
+    /// with 10-bit values in the high bits
+    Y210,
 }
 
 impl PixelFormat {
@@ -335,9 +353,17 @@ impl PixelFormat {
                 | Self::NV21
                 | Self::YVYU
                 | Self::VYUY
+                | Self::P010
+                | Self::Y210
         )
     }
 
+    /// Check if this format has 10-bit samples (P010, Y210), the only ones
+    /// that can carry an HDR signal
+    pub fn is_10bit(&self) -> bool {
+        matches!(self, Self::P010 | Self::Y210)
+    }
+
     /// Check if this format is a raw Bayer pattern requiring debayering
     pub fn is_bayer(&self) -> bool {
         matches!(
@@ -357,7 +383,7 @@ impl PixelFormat {
         }
     }
 
-    /// Check if this format requires GPU conversion (YUV, ABGR, BGRA, Gray8, RGB24, Bayer)
+    /// Check if this format requires GPU conversion (YUV, ABGR, BGRA, RGBx, BGRx, Gray8, RGB24, Bayer)
     /// These formats go through the compute shader for conversion to RGBA
     pub fn needs_gpu_conversion(&self) -> bool {
         !matches!(self, Self::RGBA)
@@ -382,6 +408,10 @@ impl PixelFormat {
             Self::BayerBGGR => 13,
             Self::BayerGRBG => 14,
             Self::BayerGBRG => 15,
+            Self::P010 => 16,
+            Self::Y210 => 17,
+            Self::RGBx => 18,
+            Self::BGRx => 19,
         }
     }
 
@@ -389,10 +419,13 @@ impl PixelFormat {
     pub fn bytes_per_pixel(&self) -> f32 {
         match self {
             Self::RGBA | Self::ABGR | Self::BGRA => 4.0, // 4 bytes per pixel
+            Self::RGBx | Self::BGRx => 4.0,              // 4 bytes, padding unused
             Self::NV12 | Self::NV21 | Self::I420 => 1.5, // 4:2:0 subsampling
             Self::YUYV | Self::UYVY | Self::YVYU | Self::VYUY => 2.0, // 4:2:2 subsampling
             Self::Gray8 => 1.0,                          // Single channel
             Self::RGB24 => 3.0,                          // 3 bytes per pixel
+            Self::P010 => 3.0,                           // 16-bit 4:2:0
+            Self::Y210 => 4.0,                           // 16-bit 4:2:2
             // Bayer patterns: 1 byte per pixel (8-bit) or 2 bytes (10/12/16-bit)
             Self::BayerRGGB | Self::BayerBGGR | Self::BayerGRBG | Self::BayerGBRG => 1.0,
         }
@@ -416,6 +449,10 @@ impl PixelFormat {
             Self::VYUY => "VYUY",
             Self::Gray8 => "GRAY8",
             Self::RGB24 => "RGB",
+            Self::RGBx => "RGBx",
+            Self::BGRx => "BGRx",
+            Self::P010 => "P010_10LE",
+            Self::Y210 => "Y210",
             // Bayer formats — GStreamer uses video/x-bayer, not video/x-raw,
             // so these cannot appear in raw caps; return a placeholder.
             Self::BayerRGGB => "RGGB",
@@ -428,9 +465,13 @@ impl PixelFormat {
     /// Parse format from GStreamer format string
     pub fn from_gst_format(format: &str) -> Option<Self> {
         match format {
-            "RGBA" | "RGBx" | "xRGB" | "ARGB" => Some(Self::RGBA),
+            "RGBA" | "xRGB" | "ARGB" => Some(Self::RGBA),
+            "RGBx" => Some(Self::RGBx),
             "ABGR" | "xBGR" => Some(Self::ABGR),
-            "BGRA" | "BGRx" => Some(Self::BGRA),
+            "BGRA" => Some(Self::BGRA),
+            "BGRx" => Some(Self::BGRx),
+            "P010_10LE" | "P010" => Some(Self::P010),
+            "Y210" => Some(Self::Y210),
             "NV12" => Some(Self::NV12),
             "NV21" => Some(Self::NV21),
             "I420" | "YV12" => Some(Self::I420),
@@ -684,6 +725,10 @@ pub enum BackendError {
     InitializationFailed(String),
     /// Camera device not found
     DeviceNotFound(String),
+    /// Camera is held by another process
+    Busy(String),
+    /// Not allowed to open the camera device
+    PermissionDenied(String),
     /// Format not supported
     FormatNotSupported(String),
     /// Backend crashed or became unresponsive
@@ -700,6 +745,8 @@ impl std::fmt::Display for BackendError {
             BackendError::NotAvailable(msg) => write!(f, "Backend not available: {}", msg),
             BackendError::InitializationFailed(msg) => write!(f, "Initialization failed: {}", msg),
             BackendError::DeviceNotFound(msg) => write!(f, "Device not found: {}", msg),
+            BackendError::Busy(msg) => write!(f, "Device busy: {}", msg),
+            BackendError::PermissionDenied(msg) => write!(f, "Permission denied: {}", msg),
             BackendError::FormatNotSupported(msg) => write!(f, "Format not supported: {}", msg),
             BackendError::Crashed(msg) => write!(f, "Backend crashed: {}", msg),
             BackendError::IoError(msg) => write!(f, "I/O error: {}", msg),
@@ -709,3 +756,16 @@ impl std::fmt::Display for BackendError {
 }
 
 impl std::error::Error for BackendError {}
+
+impl BackendError {
+    /// Error for a failed call on the camera device, keeping a camera in use
+    /// elsewhere and a missing permission apart from other failures
+    pub fn from_device_io(context: &str, err: &std::io::Error) -> Self {
+        let msg = format!("{}: {}", context, err);
+        match err.kind() {
+            std::io::ErrorKind::ResourceBusy => BackendError::Busy(msg),
+            std::io::ErrorKind::PermissionDenied => BackendError::PermissionDenied(msg),
+            _ => BackendError::InitializationFailed(msg),
+        }
+    }
+}
@@ -0,0 +1,377 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! USB bandwidth diagnostics for UVC cameras
+//!
+//! A USB camera lists every format it can produce, whether or not the
+//! connection it is plugged into can carry it. A USB 3 camera behind a
+//! USB 2 hub or extension cable enumerates at USB 2 speed and then drops
+//! frames, or fails to start, as soon as an uncompressed or 4K format is
+//! picked. This module reads the link the camera ended up on from sysfs
+//! ([`UsbLink`]), estimates what a format needs, and explains the mismatch
+//! ([`BandwidthAdvice`]).
+
+use super::types::CameraFormat;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Compressed size of an MJPEG frame, in bytes per pixel. Scenes vary a
+/// lot; this is a busy scene at a typical camera quality setting.
+const MJPEG_BYTES_PER_PIXEL: f64 = 0.15;
+
+/// Size of an H.264/H.265 frame, in bytes per pixel
+const H26X_BYTES_PER_PIXEL: f64 = 0.02;
+
+/// Share of the link a format may take before other cameras on the same
+/// bus are likely to squeeze it
+const SHARED_BUS_SHARE: f64 = 0.5;
+
+/// USB link speed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UsbSpeed {
+    /// 1.5 Mbit/s
+    Low,
+    /// 12 Mbit/s
+    Full,
+    /// 480 Mbit/s (USB 2)
+    High,
+    /// 5 Gbit/s (USB 3)
+    Super,
+    /// 10 Gbit/s and up (USB 3.1 and later)
+    SuperPlus,
+}
+
+impl UsbSpeed {
+    /// Parse the `speed` attribute of a sysfs USB device (Mbit/s)
+    pub fn from_sysfs(speed: &str) -> Option<Self> {
+        match speed.trim() {
+            "1.5" => Some(Self::Low),
+            "12" => Some(Self::Full),
+            "480" => Some(Self::High),
+            "5000" => Some(Self::Super),
+            "10000" | "20000" => Some(Self::SuperPlus),
+            _ => None,
+        }
+    }
+
+    /// Fastest speed of a device from the `version` attribute of a sysfs
+    /// USB device (the bcdUSB it reports, e.g. " 3.20")
+    pub fn from_usb_version(version: &str) -> Option<Self> {
+        let (major, minor) = version.trim().split_once('.')?;
+        let major: u32 = major.parse().ok()?;
+        let minor: u32 = minor.parse().ok()?;
+        Some(match (major, minor) {
+            (0..=1, _) => Self::Full,
+            (2, _) => Self::High,
+            (3, 0..=9) => Self::Super,
+            _ => Self::SuperPlus,
+        })
+    }
+
+    /// Most isochronous video data the link carries, in bytes per second
+    ///
+    /// USB 2 moves at most three 1024-byte packets per 125 µs microframe to
+    /// one endpoint; USB 3 bursts 16 of them, and USB 3.1 doubles that.
+    pub fn budget_bytes_per_sec(self) -> u64 {
+        match self {
+            Self::Low => 0,
+            Self::Full => 1_023 * 1_000,
+            Self::High => 3 * 1_024 * 8_000,
+            Self::Super => 3 * 16 * 1_024 * 8_000,
+            Self::SuperPlus => 2 * 3 * 16 * 1_024 * 8_000,
+        }
+    }
+
+    /// Slowest speed that carries `bytes_per_sec`, if any does
+    pub fn slowest_for(bytes_per_sec: u64) -> Option<Self> {
+        [Self::Full, Self::High, Self::Super, Self::SuperPlus]
+            .into_iter()
+            .find(|speed| speed.budget_bytes_per_sec() >= bytes_per_sec)
+    }
+
+    /// Short name, as printed on ports and cables
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Low | Self::Full => "USB 1.1",
+            Self::High => "USB 2.0",
+            Self::Super => "USB 3.0",
+            Self::SuperPlus => "USB 3.1",
+        }
+    }
+}
+
+/// How a camera is connected over USB
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsbLink {
+    /// Speed the camera is connected at
+    pub speed: UsbSpeed,
+    /// Fastest speed the camera itself supports
+    pub camera_max: UsbSpeed,
+    /// Fastest speed the host controller the camera is on supports
+    pub controller_max: UsbSpeed,
+    /// Hubs between the camera and the computer
+    pub hubs: usize,
+    /// Other cameras on the same bus, sharing its bandwidth
+    pub shared_cameras: usize,
+}
+
+impl UsbLink {
+    /// Read the link of a V4L2 device (e.g. `/dev/video0`) from sysfs
+    ///
+    /// Returns `None` for cameras that aren't on USB, like CSI sensors.
+    pub fn for_device(device_path: &str) -> Option<Self> {
+        let node = Path::new(device_path).file_name()?;
+        let usb_device = usb_device_dir(node)?;
+
+        let speed = UsbSpeed::from_sysfs(&read_attr(&usb_device, "speed")?)?;
+        let camera_max = read_attr(&usb_device, "version")
+            .and_then(|version| UsbSpeed::from_usb_version(&version))
+            .unwrap_or(speed)
+            .max(speed);
+        let hubs = read_attr(&usb_device, "devpath")
+            .map(|devpath| hub_count(&devpath))
+            .unwrap_or(0);
+        let controller_max = controller_max_speed(&usb_device).unwrap_or(speed);
+        let shared_cameras = read_attr(&usb_device, "busnum")
+            .map(|bus| other_cameras_on_bus(bus.trim(), &usb_device))
+            .unwrap_or(0);
+
+        let link = Self {
+            speed,
+            camera_max,
+            controller_max,
+            hubs,
+            shared_cameras,
+        };
+        debug!(device_path, ?link, "Read USB link");
+        Some(link)
+    }
+}
+
+/// Why a format may not arrive intact over a camera's USB link
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BandwidthAdvice {
+    /// The camera and the computer both do `required`, but something in
+    /// between (a hub, a cable or a USB 2 port) holds the link back
+    SlowPort {
+        required: UsbSpeed,
+        connected: UsbSpeed,
+    },
+    /// The format needs `required`, faster than any port on the computer
+    SlowController {
+        required: UsbSpeed,
+        controller: UsbSpeed,
+    },
+    /// The format needs more than the camera's own USB connection carries,
+    /// even though the camera lists it
+    BeyondCamera { connected: UsbSpeed },
+    /// The format fits the link, but takes enough of it that other cameras
+    /// on the same bus may crowd it out
+    SharedBus { cameras: usize },
+}
+
+/// Estimated data rate of a format, in bytes per second
+///
+/// Returns `None` for formats without a frame rate or of an unknown
+/// pixel format.
+pub fn format_bytes_per_sec(format: &CameraFormat) -> Option<u64> {
+    let fps = format.framerate?.as_f64();
+    let bytes_per_pixel = match format.pixel_format.trim().to_ascii_uppercase().as_str() {
+        "MJPG" | "MJPEG" | "JPEG" => MJPEG_BYTES_PER_PIXEL,
+        "H264" | "H265" | "HEVC" => H26X_BYTES_PER_PIXEL,
+        "YUYV" | "YUY2" | "UYVY" | "YVYU" | "VYUY" | "Y16" | "RGBP" => 2.0,
+        "NV12" | "NV21" | "I420" | "YU12" | "YV12" | "M420" => 1.5,
+        "GREY" | "Y8" | "BA81" | "GBRG" | "GRBG" | "RGGB" => 1.0,
+        "RGB3" | "BGR3" => 3.0,
+        _ => return None,
+    };
+    Some((format.width as f64 * format.height as f64 * bytes_per_pixel * fps) as u64)
+}
+
+/// What to tell the user about running `format` over `link`, if anything
+pub fn advise(format: &CameraFormat, link: &UsbLink) -> Option<BandwidthAdvice> {
+    let needed = format_bytes_per_sec(format)?;
+    let budget = link.speed.budget_bytes_per_sec();
+
+    if needed > budget {
+        let advice = match UsbSpeed::slowest_for(needed) {
+            Some(required) if required <= link.camera_max && required <= link.controller_max => {
+                BandwidthAdvice::SlowPort {
+                    required,
+                    connected: link.speed,
+                }
+            }
+            Some(required) if required <= link.camera_max => BandwidthAdvice::SlowController {
+                required,
+                controller: link.controller_max,
+            },
+            _ => BandwidthAdvice::BeyondCamera {
+                connected: link.speed,
+            },
+        };
+        return Some(advice);
+    }
+
+    (link.shared_cameras > 0 && needed as f64 > budget as f64 * SHARED_BUS_SHARE).then_some(
+        BandwidthAdvice::SharedBus {
+            cameras: link.shared_cameras,
+        },
+    )
+}
+
+/// Hubs between a device and its root port, from its `devpath` ("2.3" is
+/// port 3 of a hub on root port 2)
+fn hub_count(devpath: &str) -> usize {
+    devpath.trim().matches('.').count()
+}
+
+/// Read and trim a sysfs attribute
+pub(super) fn read_attr(dir: &Path, name: &str) -> Option<String> {
+    std::fs::read_to_string(dir.join(name))
+        .ok()
+        .map(|value| value.trim().to_string())
+}
+
+/// USB device directory of a video4linux node (`video0`)
+///
+/// `/sys/class/video4linux/videoN/device` is the USB interface; the device
+/// is its parent.
+pub(super) fn usb_device_dir(node: &std::ffi::OsStr) -> Option<PathBuf> {
+    let interface = std::fs::canonicalize(
+        Path::new("/sys/class/video4linux")
+            .join(node)
+            .join("device"),
+    )
+    .ok()?;
+    let device = interface.parent()?;
+    device.join("speed").exists().then(|| device.to_path_buf())
+}
+
+/// Fastest root hub on the controller a USB device hangs off
+///
+/// An xHCI controller has a USB 2 and a USB 3 root hub side by side under
+/// the same PCI device, and a USB 3 camera that only got a USB 2 link sits
+/// under the former.
+fn controller_max_speed(usb_device: &Path) -> Option<UsbSpeed> {
+    let root_hub = usb_device.ancestors().find(|dir| {
+        dir.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("usb"))
+    })?;
+    let controller = root_hub.parent()?;
+    std::fs::read_dir(controller)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("usb"))
+        .filter_map(|entry| UsbSpeed::from_sysfs(&read_attr(&entry.path(), "speed")?))
+        .max()
+}
+
+/// Number of other USB cameras on bus `busnum`
+fn other_cameras_on_bus(busnum: &str, own_device: &Path) -> usize {
+    let Ok(entries) = std::fs::read_dir("/sys/class/video4linux") else {
+        return 0;
+    };
+    // A camera has several video nodes (capture, metadata), so count the
+    // USB devices rather than the nodes
+    let mut devices: Vec<PathBuf> = entries
+        .flatten()
+        .filter_map(|entry| usb_device_dir(&entry.file_name()))
+        .filter(|device| device != own_device)
+        .filter(|device| read_attr(device, "busnum").as_deref() == Some(busnum))
+        .collect();
+    devices.sort();
+    devices.dedup();
+    devices.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::camera::types::Framerate;
+
+    fn format(width: u32, height: u32, fps: u32, pixel_format: &str) -> CameraFormat {
+        CameraFormat {
+            width,
+            height,
+            framerate: Some(Framerate::from_int(fps)),
+            hardware_accelerated: false,
+            pixel_format: pixel_format.to_string(),
+        }
+    }
+
+    fn link(speed: UsbSpeed, camera_max: UsbSpeed, controller_max: UsbSpeed) -> UsbLink {
+        UsbLink {
+            speed,
+            camera_max,
+            controller_max,
+            hubs: 0,
+            shared_cameras: 0,
+        }
+    }
+
+    #[test]
+    fn sysfs_attributes_parse() {
+        assert_eq!(UsbSpeed::from_sysfs("480\n"), Some(UsbSpeed::High));
+        assert_eq!(UsbSpeed::from_sysfs("20000"), Some(UsbSpeed::SuperPlus));
+        assert_eq!(UsbSpeed::from_sysfs("fast"), None);
+        assert_eq!(UsbSpeed::from_usb_version(" 2.00"), Some(UsbSpeed::High));
+        assert_eq!(UsbSpeed::from_usb_version(" 3.00"), Some(UsbSpeed::Super));
+        assert_eq!(
+            UsbSpeed::from_usb_version(" 3.20"),
+            Some(UsbSpeed::SuperPlus)
+        );
+        assert_eq!(hub_count("2"), 0);
+        assert_eq!(hub_count("1.4.2"), 2);
+    }
+
+    #[test]
+    fn usb3_camera_on_a_usb2_port() {
+        let slow = link(UsbSpeed::High, UsbSpeed::Super, UsbSpeed::Super);
+        assert_eq!(
+            advise(&format(3840, 2160, 30, "MJPG"), &slow),
+            Some(BandwidthAdvice::SlowPort {
+                required: UsbSpeed::Super,
+                connected: UsbSpeed::High,
+            })
+        );
+        // Compressed HD is fine over USB 2
+        assert_eq!(advise(&format(1920, 1080, 30, "MJPG"), &slow), None);
+
+        // The same camera on a computer without USB 3
+        let old = link(UsbSpeed::High, UsbSpeed::Super, UsbSpeed::High);
+        assert_eq!(
+            advise(&format(1920, 1080, 30, "YUYV"), &old),
+            Some(BandwidthAdvice::SlowController {
+                required: UsbSpeed::Super,
+                controller: UsbSpeed::High,
+            })
+        );
+    }
+
+    #[test]
+    fn usb2_camera_listing_more_than_it_can_send() {
+        let usb2 = link(UsbSpeed::High, UsbSpeed::High, UsbSpeed::Super);
+        assert_eq!(
+            advise(&format(1920, 1080, 30, "YUYV"), &usb2),
+            Some(BandwidthAdvice::BeyondCamera {
+                connected: UsbSpeed::High
+            })
+        );
+        assert_eq!(advise(&format(640, 480, 30, "YUYV"), &usb2), None);
+        // Unknown formats get no advice rather than a guess
+        assert_eq!(advise(&format(1920, 1080, 30, "XXXX"), &usb2), None);
+    }
+
+    #[test]
+    fn shared_bus_only_matters_for_heavy_formats() {
+        let shared = UsbLink {
+            shared_cameras: 1,
+            ..link(UsbSpeed::High, UsbSpeed::High, UsbSpeed::High)
+        };
+        assert_eq!(
+            advise(&format(1280, 720, 10, "YUYV"), &shared),
+            Some(BandwidthAdvice::SharedBus { cameras: 1 })
+        );
+        assert_eq!(advise(&format(1280, 720, 30, "MJPG"), &shared), None);
+    }
+}
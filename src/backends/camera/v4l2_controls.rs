@@ -3,10 +3,12 @@
 //! V4L2 camera control interface
 //!
 //! Provides functions to query and set V4L2 camera controls for exposure,
-//! gain, ISO, and metering settings.
+//! gain, ISO, and metering settings, plus raw access to vendor-specific UVC
+//! extension unit (XU) controls.
 //!
 //! Inspired by [cameractrls](https://github.com/soyersoyer/cameractrls).
 
+use std::collections::HashMap;
 use std::fs::File;
 use std::os::unix::io::AsRawFd;
 use tracing::{debug, warn};
@@ -14,11 +16,14 @@ use tracing::{debug, warn};
 // ===== V4L2 Control Class Bases =====
 const V4L2_CTRL_CLASS_USER: u32 = 0x00980000;
 const V4L2_CTRL_CLASS_CAMERA: u32 = 0x009a0000;
+const V4L2_CTRL_CLASS_FLASH: u32 = 0x009c0000;
 const V4L2_CTRL_CLASS_IMAGE_SOURCE: u32 = 0x009e0000;
 
 const V4L2_CID_BASE: u32 = V4L2_CTRL_CLASS_USER | 0x900;
 const V4L2_CID_CAMERA_CLASS_BASE: u32 = V4L2_CTRL_CLASS_CAMERA | 0x900;
+const V4L2_CID_FLASH_CLASS_BASE: u32 = V4L2_CTRL_CLASS_FLASH | 0x900;
 const V4L2_CID_IMAGE_SOURCE_CLASS_BASE: u32 = V4L2_CTRL_CLASS_IMAGE_SOURCE | 0x900;
+const V4L2_CID_CAMERA_UVC_BASE: u32 = V4L2_CID_CAMERA_CLASS_BASE + 0x1000;
 
 // ===== V4L2 Control IDs (User Class) =====
 
@@ -65,6 +70,8 @@ pub const V4L2_CID_ISO_SENSITIVITY_AUTO: u32 = V4L2_CID_CAMERA_CLASS_BASE + 24;
 pub const V4L2_CID_EXPOSURE_METERING: u32 = V4L2_CID_CAMERA_CLASS_BASE + 25;
 /// Privacy control - when 1 (TRUE), camera cannot capture (privacy cover closed)
 pub const V4L2_CID_PRIVACY: u32 = V4L2_CID_CAMERA_CLASS_BASE + 16;
+/// Lock bits for the automatic exposure, white balance and focus algorithms
+pub const V4L2_CID_3A_LOCK: u32 = V4L2_CID_CAMERA_CLASS_BASE + 27;
 
 // ===== V4L2 Control IDs (Camera Class - PTZ) =====
 
@@ -91,6 +98,43 @@ pub const V4L2_CID_PAN_SPEED: u32 = V4L2_CID_CAMERA_CLASS_BASE + 32;
 /// Tilt speed (continuous movement)
 pub const V4L2_CID_TILT_SPEED: u32 = V4L2_CID_CAMERA_CLASS_BASE + 33;
 
+// ===== V4L2 Control IDs (Camera Class - UVC 1.5) =====
+
+/// Region of interest rectangle (`v4l2_rect` compound control, in pixels of
+/// the current format)
+pub const V4L2_CID_UVC_REGION_OF_INTEREST_RECT: u32 = V4L2_CID_CAMERA_UVC_BASE + 1;
+/// Which automatic algorithms follow the region of interest (bitmask)
+pub const V4L2_CID_UVC_REGION_OF_INTEREST_AUTO: u32 = V4L2_CID_CAMERA_UVC_BASE + 2;
+
+/// Auto exposure meters on the region of interest
+pub const V4L2_UVC_REGION_OF_INTEREST_AUTO_EXPOSURE: i32 = 1 << 0;
+
+// ===== V4L2 Control IDs (Flash Class) =====
+
+/// LED mode: none, flash or torch
+pub const V4L2_CID_FLASH_LED_MODE: u32 = V4L2_CID_FLASH_CLASS_BASE + 1;
+/// What triggers the flash: software strobe or an external pin
+pub const V4L2_CID_FLASH_STROBE_SOURCE: u32 = V4L2_CID_FLASH_CLASS_BASE + 2;
+/// Strobe the flash (button)
+pub const V4L2_CID_FLASH_STROBE: u32 = V4L2_CID_FLASH_CLASS_BASE + 3;
+/// Stop a running strobe (button)
+pub const V4L2_CID_FLASH_STROBE_STOP: u32 = V4L2_CID_FLASH_CLASS_BASE + 4;
+/// Hardware timeout of a strobe in microseconds
+pub const V4L2_CID_FLASH_TIMEOUT: u32 = V4L2_CID_FLASH_CLASS_BASE + 6;
+/// Flash intensity in flash mode (driver units, usually mA)
+pub const V4L2_CID_FLASH_INTENSITY: u32 = V4L2_CID_FLASH_CLASS_BASE + 7;
+/// LED intensity in torch mode (driver units, usually mA)
+pub const V4L2_CID_FLASH_TORCH_INTENSITY: u32 = V4L2_CID_FLASH_CLASS_BASE + 8;
+
+/// LED off
+pub const V4L2_FLASH_LED_MODE_NONE: i32 = 0;
+/// LED fires when strobed
+pub const V4L2_FLASH_LED_MODE_FLASH: i32 = 1;
+/// LED lit continuously
+pub const V4L2_FLASH_LED_MODE_TORCH: i32 = 2;
+/// Strobe started by `V4L2_CID_FLASH_STROBE`
+pub const V4L2_FLASH_STROBE_SOURCE_SOFTWARE: i32 = 0;
+
 // ===== V4L2 Control IDs (Image Source Class) =====
 
 /// Analogue gain (image source class)
@@ -107,6 +151,15 @@ pub const V4L2_EXPOSURE_SHUTTER_PRIORITY: i32 = 2;
 /// Auto exposure time, manual iris (aperture priority)
 pub const V4L2_EXPOSURE_APERTURE_PRIORITY: i32 = 3;
 
+// ===== V4L2 3A Lock Bits =====
+
+/// Hold the current automatic exposure
+pub const V4L2_LOCK_EXPOSURE: i32 = 1 << 0;
+/// Hold the current automatic white balance
+pub const V4L2_LOCK_WHITE_BALANCE: i32 = 1 << 1;
+/// Hold the current automatic focus position
+pub const V4L2_LOCK_FOCUS: i32 = 1 << 2;
+
 // ===== V4L2 Exposure Metering Menu Values =====
 
 /// Average metering across entire frame
@@ -140,6 +193,29 @@ const VIDIOC_S_CTRL: libc::c_ulong = 0xC008561C;
 const VIDIOC_QUERYCTRL: libc::c_ulong = 0xC0445624;
 /// Query menu item (v4l2_querymenu: 44 bytes)
 const VIDIOC_QUERYMENU: libc::c_ulong = 0xC02C5625;
+/// Set extended controls (`_IOWR('V', 72, struct v4l2_ext_controls)`). The
+/// struct contains a pointer, so its size differs between 32- and 64-bit.
+const VIDIOC_S_EXT_CTRLS: libc::c_ulong =
+    0xC000_5648 | ((std::mem::size_of::<V4l2ExtControls>() as libc::c_ulong) << 16);
+
+// ===== UVC Extension Unit Constants =====
+
+/// UVC request codes for `UVCIOC_CTRL_QUERY`
+const UVC_SET_CUR: u8 = 0x01;
+const UVC_GET_CUR: u8 = 0x81;
+const UVC_GET_LEN: u8 = 0x85;
+const UVC_GET_INFO: u8 = 0x86;
+
+/// GET_INFO capability bits
+const UVC_CONTROL_CAP_GET: u8 = 0x01;
+const UVC_CONTROL_CAP_SET: u8 = 0x02;
+
+/// USB descriptor types/subtypes needed to find extension units
+const USB_DT_INTERFACE: u8 = 0x04;
+const USB_DT_CS_INTERFACE: u8 = 0x24;
+const USB_CLASS_VIDEO: u8 = 0x0e;
+const UVC_SC_VIDEOCONTROL: u8 = 0x01;
+const UVC_VC_EXTENSION_UNIT: u8 = 0x06;
 
 // ===== V4L2 ioctl Structures =====
 
@@ -173,6 +249,60 @@ struct V4l2Querymenu {
     reserved: u32,
 }
 
+/// V4L2 rectangle, the payload of rectangle compound controls
+#[repr(C)]
+struct V4l2Rect {
+    left: i32,
+    top: i32,
+    width: u32,
+    height: u32,
+}
+
+/// Value union of `struct v4l2_ext_control`; 8 bytes on every target
+#[repr(C)]
+union V4l2ExtControlValue {
+    /// Only sizes the union like the kernel's
+    #[allow(dead_code)]
+    value64: i64,
+    ptr: *mut libc::c_void,
+}
+
+/// One extended control (`struct v4l2_ext_control`, packed). Compound
+/// controls pass their payload through the pointer member.
+#[repr(C, packed)]
+struct V4l2ExtControl {
+    id: u32,
+    size: u32,
+    reserved2: u32,
+    value: V4l2ExtControlValue,
+}
+
+/// Extended control set (`struct v4l2_ext_controls`)
+#[repr(C)]
+struct V4l2ExtControls {
+    which: u32,
+    count: u32,
+    error_idx: u32,
+    request_fd: i32,
+    reserved: u32,
+    controls: *mut V4l2ExtControl,
+}
+
+/// UVC extension unit query structure (`struct uvc_xu_control_query`)
+#[repr(C)]
+struct UvcXuControlQuery {
+    unit: u8,
+    selector: u8,
+    query: u8,
+    size: u16,
+    data: *mut u8,
+}
+
+/// Query/set an extension unit control (`_IOWR('u', 0x21, struct uvc_xu_control_query)`)
+/// The struct contains a pointer, so its size differs between 32- and 64-bit.
+const UVCIOC_CTRL_QUERY: libc::c_ulong =
+    0xC000_7521 | ((std::mem::size_of::<UvcXuControlQuery>() as libc::c_ulong) << 16);
+
 // ===== Public Types =====
 
 /// Information about a V4L2 control
@@ -229,6 +359,68 @@ pub struct MenuItem {
     pub name: String,
 }
 
+/// A UVC extension unit as described by the device's USB descriptors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionUnit {
+    pub unit_id: u8,
+    pub guid: [u8; 16],
+    /// Control selectors advertised in `bmControls` (1-based)
+    pub selectors: Vec<u8>,
+}
+
+impl ExtensionUnit {
+    /// GUID in the canonical `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx` form
+    pub fn guid_string(&self) -> String {
+        format_guid(&self.guid)
+    }
+}
+
+/// Runtime information about one extension unit control
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XuControlInfo {
+    pub unit_id: u8,
+    pub selector: u8,
+    /// Payload size in bytes (from GET_LEN)
+    pub len: u16,
+    pub can_get: bool,
+    pub can_set: bool,
+}
+
+/// A known vendor control that lives inside an extension unit payload
+#[derive(Debug)]
+pub struct XuPreset {
+    /// Extension unit GUID this control belongs to
+    pub guid: &'static str,
+    pub selector: u8,
+    /// Human-readable control name
+    pub name: &'static str,
+    /// Byte within the payload that holds the value
+    pub offset: usize,
+    /// Selectable values for that byte
+    pub options: &'static [(&'static str, u8)],
+}
+
+/// Known extension unit controls, keyed by unit GUID and selector
+///
+/// Layouts follow [cameractrls](https://github.com/soyersoyer/cameractrls).
+pub const XU_PRESETS: &[XuPreset] = &[XuPreset {
+    // Logitech peripheral unit (C920, C922, C930e, BRIO, ...)
+    guid: "ffe52d21-8030-4e2c-82d9-f587d00540bd",
+    selector: 0x09,
+    name: "LED",
+    offset: 1,
+    options: &[("Off", 0x00), ("On", 0x01), ("Blink", 0x02), ("Auto", 0x03)],
+}];
+
+impl XuPreset {
+    /// Find the preset for a control, if the device is a known one
+    pub fn find(guid: &str, selector: u8) -> Option<&'static XuPreset> {
+        XU_PRESETS
+            .iter()
+            .find(|p| p.guid.eq_ignore_ascii_case(guid) && p.selector == selector)
+    }
+}
+
 // ===== Helper Functions =====
 
 /// Extract a null-terminated string from a fixed-size byte array
@@ -237,6 +429,88 @@ fn extract_name(bytes: &[u8; 32]) -> String {
     String::from_utf8_lossy(&bytes[..name_len]).to_string()
 }
 
+/// Format a UVC GUID (first three fields little-endian, as on the wire)
+fn format_guid(g: &[u8; 16]) -> String {
+    format!(
+        "{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
+        g[3],
+        g[2],
+        g[1],
+        g[0],
+        g[5],
+        g[4],
+        g[7],
+        g[6],
+        g[8],
+        g[9],
+        g[10],
+        g[11],
+        g[12],
+        g[13],
+        g[14],
+        g[15]
+    )
+}
+
+/// Parse extension unit descriptors out of a raw USB configuration descriptor blob
+///
+/// Only units inside a VideoControl interface are returned; audio-class
+/// interfaces reuse the same subtype number for unrelated units.
+pub fn parse_extension_units(descriptors: &[u8]) -> Vec<ExtensionUnit> {
+    let mut units = Vec::new();
+    let mut in_video_control = false;
+    let mut pos = 0;
+
+    while pos + 2 <= descriptors.len() {
+        let len = descriptors[pos] as usize;
+        if len < 2 || pos + len > descriptors.len() {
+            break;
+        }
+        let desc = &descriptors[pos..pos + len];
+        pos += len;
+
+        match desc[1] {
+            USB_DT_INTERFACE if len >= 7 => {
+                in_video_control = desc[5] == USB_CLASS_VIDEO && desc[6] == UVC_SC_VIDEOCONTROL;
+            }
+            USB_DT_CS_INTERFACE if in_video_control && len >= 24 => {
+                if desc[2] != UVC_VC_EXTENSION_UNIT {
+                    continue;
+                }
+                let num_in_pins = desc[21] as usize;
+                let Some(&control_size) = desc.get(22 + num_in_pins) else {
+                    continue;
+                };
+                let start = 23 + num_in_pins;
+                let Some(bitmap) = desc.get(start..start + control_size as usize) else {
+                    continue;
+                };
+
+                let mut guid = [0u8; 16];
+                guid.copy_from_slice(&desc[4..20]);
+                let selectors = bitmap
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(byte, bits)| {
+                        (0..8)
+                            .filter(move |bit| bits & (1 << bit) != 0)
+                            .map(move |bit| (byte * 8 + bit + 1) as u8)
+                    })
+                    .collect();
+
+                units.push(ExtensionUnit {
+                    unit_id: desc[3],
+                    guid,
+                    selectors,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    units
+}
+
 // ===== Public Functions =====
 
 /// Query if a control exists and get its information
@@ -315,6 +589,17 @@ pub fn set_control(device_path: &str, control_id: u32, value: i32) -> Result<(),
         .write(true)
         .open(device_path)
         .map_err(|e| format!("Failed to open device: {}", e))?;
+    set_control_on(&file, device_path, control_id, value)
+}
+
+/// Set value of a control on a device that is already open, for devices
+/// that must stay open while the value applies (flash sub-devices)
+pub fn set_control_on(
+    file: &File,
+    device_path: &str,
+    control_id: u32,
+    value: i32,
+) -> Result<(), String> {
     let fd = file.as_raw_fd();
 
     let mut ctrl = V4l2Control {
@@ -357,6 +642,69 @@ pub fn set_control(device_path: &str, control_id: u32, value: i32) -> Result<(),
     Ok(())
 }
 
+/// Set a rectangle compound control, such as the UVC region of interest
+pub fn set_rect_control(
+    device_path: &str,
+    control_id: u32,
+    (left, top, width, height): (i32, i32, u32, u32),
+) -> Result<(), String> {
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(device_path)
+        .map_err(|e| format!("Failed to open device: {}", e))?;
+    let fd = file.as_raw_fd();
+
+    let mut rect = V4l2Rect {
+        left,
+        top,
+        width,
+        height,
+    };
+    let mut control = V4l2ExtControl {
+        id: control_id,
+        size: std::mem::size_of::<V4l2Rect>() as u32,
+        reserved2: 0,
+        value: V4l2ExtControlValue {
+            ptr: (&mut rect as *mut V4l2Rect).cast(),
+        },
+    };
+    let mut controls = V4l2ExtControls {
+        // V4L2_CTRL_WHICH_CUR_VAL
+        which: 0,
+        count: 1,
+        error_idx: 0,
+        request_fd: 0,
+        reserved: 0,
+        controls: &mut control,
+    };
+
+    let result = unsafe {
+        libc::syscall(
+            libc::SYS_ioctl,
+            fd,
+            VIDIOC_S_EXT_CTRLS,
+            &mut controls as *mut V4l2ExtControls,
+        )
+    };
+
+    if result < 0 {
+        let errno = std::io::Error::last_os_error();
+        warn!(
+            device_path,
+            control_id,
+            left,
+            top,
+            width,
+            height,
+            ?errno,
+            "Failed to set V4L2 rectangle control"
+        );
+        return Err(format!("Failed to set control: {}", errno));
+    }
+    Ok(())
+}
+
 /// Query all menu items for a menu-type control
 pub fn query_menu_items(device_path: &str, control_id: u32, max_index: i32) -> Vec<MenuItem> {
     let file = match File::open(device_path) {
@@ -402,6 +750,394 @@ pub fn has_control(device_path: &str, control_id: u32) -> bool {
         .unwrap_or(false)
 }
 
+/// An automatic control that can be held at the value it has settled on
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AutoControl {
+    /// Auto exposure (AE-L), with auto gain where the camera has it
+    Exposure,
+    /// Auto white balance (AWB-L)
+    WhiteBalance,
+    /// Continuous autofocus, held by the long-press lock
+    Focus,
+}
+
+impl AutoControl {
+    /// Bit of this control in `V4L2_CID_3A_LOCK`
+    fn lock_bit(self) -> i32 {
+        match self {
+            AutoControl::Exposure => V4L2_LOCK_EXPOSURE,
+            AutoControl::WhiteBalance => V4L2_LOCK_WHITE_BALANCE,
+            AutoControl::Focus => V4L2_LOCK_FOCUS,
+        }
+    }
+
+    /// Controls a hold changes, which aren't drift while it lasts
+    pub fn control_ids(self) -> &'static [u32] {
+        match self {
+            AutoControl::Exposure => &[
+                V4L2_CID_EXPOSURE_AUTO,
+                V4L2_CID_EXPOSURE_ABSOLUTE,
+                V4L2_CID_AUTOGAIN,
+                V4L2_CID_GAIN,
+            ],
+            AutoControl::WhiteBalance => &[
+                V4L2_CID_AUTO_WHITE_BALANCE,
+                V4L2_CID_WHITE_BALANCE_TEMPERATURE,
+            ],
+            AutoControl::Focus => &[V4L2_CID_FOCUS_AUTO, V4L2_CID_FOCUS_ABSOLUTE],
+        }
+    }
+
+    /// Each automatic part as (mode control, value it sets, manual mode)
+    fn emulation(self) -> &'static [(u32, u32, i32)] {
+        match self {
+            AutoControl::Exposure => &[
+                (
+                    V4L2_CID_EXPOSURE_AUTO,
+                    V4L2_CID_EXPOSURE_ABSOLUTE,
+                    V4L2_EXPOSURE_MANUAL,
+                ),
+                (V4L2_CID_AUTOGAIN, V4L2_CID_GAIN, 0),
+            ],
+            AutoControl::WhiteBalance => &[(
+                V4L2_CID_AUTO_WHITE_BALANCE,
+                V4L2_CID_WHITE_BALANCE_TEMPERATURE,
+                0,
+            )],
+            AutoControl::Focus => &[(V4L2_CID_FOCUS_AUTO, V4L2_CID_FOCUS_ABSOLUTE, 0)],
+        }
+    }
+
+    /// Device the emulated hold switches: focus lives on the lens actuator
+    /// (`focus_path`) where the camera has one
+    fn path<'a>(self, device_path: &'a str, focus_path: Option<&'a str>) -> &'a str {
+        match self {
+            AutoControl::Focus => focus_path.unwrap_or(device_path),
+            _ => device_path,
+        }
+    }
+}
+
+/// How an automatic control was held, so the hold can be released
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AutoHold {
+    /// Through the control's bit in the driver's `V4L2_CID_3A_LOCK`
+    Native,
+    /// Emulated by switching to manual with the value auto had settled on.
+    /// Stores the mode controls to restore and their automatic values.
+    Emulated(Vec<(u32, i32)>),
+}
+
+/// `current` 3A lock bits with `bit` set or cleared
+fn with_lock_bit(current: i32, bit: i32, held: bool) -> i32 {
+    if held { current | bit } else { current & !bit }
+}
+
+/// Hold an automatic control at its current value
+///
+/// Uses the 3A lock when the device has one, leaving the other bits alone.
+/// Otherwise reads the value auto mode settled on, switches to manual and
+/// writes it back, so the picture stays as it was however the framing
+/// changes. Focus controls go to `focus_path` (the lens actuator) when given.
+pub fn hold_auto(
+    device_path: &str,
+    focus_path: Option<&str>,
+    control: AutoControl,
+) -> Result<AutoHold, String> {
+    if has_control(device_path, V4L2_CID_3A_LOCK) {
+        let current = get_control(device_path, V4L2_CID_3A_LOCK).unwrap_or(0);
+        set_control(
+            device_path,
+            V4L2_CID_3A_LOCK,
+            with_lock_bit(current, control.lock_bit(), true),
+        )?;
+        return Ok(AutoHold::Native);
+    }
+
+    let path = control.path(device_path, focus_path);
+    let mut restore = Vec::new();
+    for &(mode_id, value_id, manual) in control.emulation() {
+        let Some(mode) = get_control(path, mode_id) else {
+            continue;
+        };
+        if mode == manual {
+            continue;
+        }
+        // Read the settled value before leaving auto mode
+        let value = get_control(path, value_id);
+        set_control(path, mode_id, manual)?;
+        if let Some(value) = value {
+            set_control(path, value_id, value)?;
+        }
+        restore.push((mode_id, mode));
+    }
+
+    if restore.is_empty() {
+        return Err(format!("{control:?} is not automatic"));
+    }
+    Ok(AutoHold::Emulated(restore))
+}
+
+/// Undo [`hold_auto`]
+pub fn release_auto(
+    device_path: &str,
+    focus_path: Option<&str>,
+    control: AutoControl,
+    hold: &AutoHold,
+) -> Result<(), String> {
+    match hold {
+        AutoHold::Native => {
+            let current = get_control(device_path, V4L2_CID_3A_LOCK).unwrap_or(0);
+            set_control(
+                device_path,
+                V4L2_CID_3A_LOCK,
+                with_lock_bit(current, control.lock_bit(), false),
+            )
+        }
+        AutoHold::Emulated(restore) => {
+            let path = control.path(device_path, focus_path);
+            restore
+                .iter()
+                .try_for_each(|&(mode_id, mode)| set_control(path, mode_id, mode))
+        }
+    }
+}
+
+/// Holds taken by the long-press exposure/focus lock, so it can be undone
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AeAfLock {
+    pub holds: Vec<(AutoControl, AutoHold)>,
+}
+
+impl AeAfLock {
+    /// Controls a long-press locks
+    pub const CONTROLS: [AutoControl; 2] = [AutoControl::Exposure, AutoControl::Focus];
+
+    /// Take `control`'s hold out of the lock, e.g. for an AE-L button that
+    /// now owns it
+    pub fn take(&mut self, control: AutoControl) -> Option<AutoHold> {
+        let index = self.holds.iter().position(|(c, _)| *c == control)?;
+        Some(self.holds.remove(index).1)
+    }
+}
+
+/// Lock the current automatic exposure and focus
+///
+/// Holds each through [`hold_auto`], skipping the controls in `owned` that
+/// an AE-L hold already has, so undoing the lock leaves them held.
+pub fn lock_exposure_and_focus(
+    device_path: &str,
+    focus_path: Option<&str>,
+    owned: &[AutoControl],
+) -> Result<AeAfLock, String> {
+    let mut lock = AeAfLock::default();
+    let mut error = None;
+    for control in AeAfLock::CONTROLS {
+        if owned.contains(&control) {
+            continue;
+        }
+        match hold_auto(device_path, focus_path, control) {
+            Ok(hold) => lock.holds.push((control, hold)),
+            Err(e) => error = Some(e),
+        }
+    }
+    if lock.holds.is_empty() {
+        return Err(error.unwrap_or_else(|| "No automatic exposure or focus to lock".to_string()));
+    }
+    Ok(lock)
+}
+
+/// Undo [`lock_exposure_and_focus`]
+pub fn unlock_exposure_and_focus(
+    device_path: &str,
+    focus_path: Option<&str>,
+    lock: &AeAfLock,
+) -> Result<(), String> {
+    lock.holds
+        .iter()
+        .rev()
+        .try_for_each(|(control, hold)| release_auto(device_path, focus_path, *control, hold))
+}
+
+/// Controls that switch other controls between automatic and manual.
+///
+/// These have to be written first: e.g. the exposure time is read-only
+/// while auto exposure is on.
+const MODE_CONTROLS: &[u32] = &[
+    V4L2_CID_EXPOSURE_AUTO,
+    V4L2_CID_AUTO_WHITE_BALANCE,
+    V4L2_CID_FOCUS_AUTO,
+    V4L2_CID_AUTOGAIN,
+];
+
+/// Order stored control values so mode switches come before the values they unlock
+pub fn apply_order(controls: &HashMap<u32, i32>) -> Vec<(u32, i32)> {
+    let mut ordered: Vec<(u32, i32)> = controls.iter().map(|(&id, &v)| (id, v)).collect();
+    ordered.sort_by_key(|&(id, _)| (!MODE_CONTROLS.contains(&id), id));
+    ordered
+}
+
+/// Write stored control values that differ from the device's current values
+///
+/// Focus controls go to `focus_path` (the lens actuator) when given.
+/// Controls the device can't read are skipped. Returns the controls that had
+/// drifted and were rewritten, with the value the device reports afterwards
+/// (drivers may round to the control's step).
+pub fn reapply_controls(
+    device_path: &str,
+    focus_path: Option<&str>,
+    controls: &HashMap<u32, i32>,
+) -> Vec<(u32, i32)> {
+    let mut drifted = Vec::new();
+
+    for (control_id, value) in apply_order(controls) {
+        let path = match control_id {
+            V4L2_CID_FOCUS_ABSOLUTE | V4L2_CID_FOCUS_AUTO => focus_path.unwrap_or(device_path),
+            _ => device_path,
+        };
+        let Some(current) = get_control(path, control_id) else {
+            continue;
+        };
+        if current == value {
+            continue;
+        }
+
+        match set_control(path, control_id, value) {
+            Ok(()) => {
+                debug!(
+                    control_id = format!("0x{:08x}", control_id),
+                    current, value, "Re-applied stored control"
+                );
+                let actual = get_control(path, control_id).unwrap_or(value);
+                drifted.push((control_id, actual));
+            }
+            Err(e) => warn!(
+                control_id = format!("0x{:08x}", control_id),
+                error = %e,
+                "Failed to re-apply stored control"
+            ),
+        }
+    }
+
+    drifted
+}
+
+/// Issue a raw UVC extension unit query, filling or sending `data`
+fn xu_query(
+    device_path: &str,
+    unit_id: u8,
+    selector: u8,
+    query: u8,
+    data: &mut [u8],
+) -> Result<(), String> {
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(device_path)
+        .map_err(|e| format!("Failed to open device: {}", e))?;
+    let fd = file.as_raw_fd();
+
+    let mut xq = UvcXuControlQuery {
+        unit: unit_id,
+        selector,
+        query,
+        size: data.len() as u16,
+        data: data.as_mut_ptr(),
+    };
+
+    let result = unsafe {
+        libc::syscall(
+            libc::SYS_ioctl,
+            fd,
+            UVCIOC_CTRL_QUERY,
+            &mut xq as *mut UvcXuControlQuery,
+        )
+    };
+
+    if result < 0 {
+        let errno = std::io::Error::last_os_error();
+        debug!(
+            device_path,
+            unit_id,
+            selector,
+            query,
+            ?errno,
+            "UVC extension unit query failed"
+        );
+        return Err(format!("Extension unit query failed: {}", errno));
+    }
+
+    Ok(())
+}
+
+/// Query size and capabilities of an extension unit control
+pub fn query_xu_control(device_path: &str, unit_id: u8, selector: u8) -> Option<XuControlInfo> {
+    let mut len = [0u8; 2];
+    xu_query(device_path, unit_id, selector, UVC_GET_LEN, &mut len).ok()?;
+    let mut info = [0u8; 1];
+    xu_query(device_path, unit_id, selector, UVC_GET_INFO, &mut info).ok()?;
+
+    Some(XuControlInfo {
+        unit_id,
+        selector,
+        len: u16::from_le_bytes(len),
+        can_get: info[0] & UVC_CONTROL_CAP_GET != 0,
+        can_set: info[0] & UVC_CONTROL_CAP_SET != 0,
+    })
+}
+
+/// Read the current payload of an extension unit control
+pub fn get_xu_control(
+    device_path: &str,
+    unit_id: u8,
+    selector: u8,
+    len: u16,
+) -> Result<Vec<u8>, String> {
+    let mut data = vec![0u8; len as usize];
+    xu_query(device_path, unit_id, selector, UVC_GET_CUR, &mut data)?;
+    Ok(data)
+}
+
+/// Write a new payload to an extension unit control
+pub fn set_xu_control(
+    device_path: &str,
+    unit_id: u8,
+    selector: u8,
+    data: &[u8],
+) -> Result<(), String> {
+    let mut data = data.to_vec();
+    xu_query(device_path, unit_id, selector, UVC_SET_CUR, &mut data).inspect_err(|e| {
+        warn!(device_path, unit_id, selector, error = %e, "Failed to set XU control");
+    })
+}
+
+/// Discover the extension units of a UVC device from its sysfs USB descriptors
+///
+/// Returns an empty list for non-USB devices (e.g. libcamera sensors).
+pub fn discover_extension_units(device_path: &str) -> Vec<ExtensionUnit> {
+    let Some(node) = std::path::Path::new(device_path).file_name() else {
+        return Vec::new();
+    };
+    // /sys/class/video4linux/videoN/device is the USB interface; the raw
+    // descriptors live on its parent USB device.
+    let interface = std::path::Path::new("/sys/class/video4linux")
+        .join(node)
+        .join("device");
+    let Some(descriptors) = std::fs::canonicalize(&interface)
+        .ok()
+        .and_then(|p| p.parent().map(|d| d.join("descriptors")))
+        .and_then(|p| std::fs::read(p).ok())
+    else {
+        debug!(
+            device_path,
+            "No USB descriptors found for extension unit discovery"
+        );
+        return Vec::new();
+    };
+
+    parse_extension_units(&descriptors)
+}
+
 /// Exposure metadata read from camera
 #[derive(Debug, Clone, Default)]
 pub struct ExposureMetadata {
@@ -451,6 +1187,49 @@ pub fn read_exposure_metadata(device_path: &str) -> ExposureMetadata {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_apply_order_puts_mode_controls_first() {
+        let controls = HashMap::from([
+            (V4L2_CID_FOCUS_ABSOLUTE, 120),
+            (V4L2_CID_EXPOSURE_ABSOLUTE, 300),
+            (V4L2_CID_FOCUS_AUTO, 0),
+            (V4L2_CID_EXPOSURE_AUTO, 1),
+            (V4L2_CID_CONTRAST, 40),
+        ]);
+        let order: Vec<u32> = apply_order(&controls)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        let pos = |id| order.iter().position(|&o| o == id).unwrap();
+        assert!(pos(V4L2_CID_EXPOSURE_AUTO) < pos(V4L2_CID_EXPOSURE_ABSOLUTE));
+        assert!(pos(V4L2_CID_FOCUS_AUTO) < pos(V4L2_CID_FOCUS_ABSOLUTE));
+        assert!(pos(V4L2_CID_FOCUS_AUTO) < pos(V4L2_CID_CONTRAST));
+    }
+
+    #[test]
+    fn test_holds_only_touch_their_lock_bit() {
+        let focus_locked = V4L2_LOCK_FOCUS;
+        let held = with_lock_bit(focus_locked, AutoControl::Exposure.lock_bit(), true);
+        assert_eq!(held, V4L2_LOCK_FOCUS | V4L2_LOCK_EXPOSURE);
+        let held = with_lock_bit(held, AutoControl::WhiteBalance.lock_bit(), true);
+        let released = with_lock_bit(held, AutoControl::Exposure.lock_bit(), false);
+        assert_eq!(released, V4L2_LOCK_FOCUS | V4L2_LOCK_WHITE_BALANCE);
+    }
+
+    #[test]
+    fn test_lock_hands_a_hold_over() {
+        let mut lock = AeAfLock {
+            holds: vec![
+                (AutoControl::Exposure, AutoHold::Native),
+                (AutoControl::Focus, AutoHold::Native),
+            ],
+        };
+        assert_eq!(lock.take(AutoControl::Exposure), Some(AutoHold::Native));
+        assert_eq!(lock.take(AutoControl::Exposure), None);
+        assert_eq!(lock.holds, vec![(AutoControl::Focus, AutoHold::Native)]);
+        assert_eq!(AutoControl::Focus.lock_bit(), V4L2_LOCK_FOCUS);
+    }
+
     #[test]
     fn test_control_id_values() {
         // Verify control IDs match expected values
@@ -460,6 +1239,69 @@ mod tests {
         assert_eq!(V4L2_CID_EXPOSURE_METERING, 0x009a0919);
         assert_eq!(V4L2_CID_ISO_SENSITIVITY, 0x009a0917);
         assert_eq!(V4L2_CID_GAIN, 0x00980913);
+        assert_eq!(V4L2_CID_UVC_REGION_OF_INTEREST_RECT, 0x009a1901);
+        assert_eq!(V4L2_CID_UVC_REGION_OF_INTEREST_AUTO, 0x009a1902);
+    }
+
+    #[test]
+    fn test_ext_ctrl_layout() {
+        // Matches the kernel's packed v4l2_ext_control and the ioctl number
+        // it encodes on 64-bit
+        assert_eq!(std::mem::size_of::<V4l2ExtControl>(), 20);
+        if cfg!(target_pointer_width = "64") {
+            assert_eq!(VIDIOC_S_EXT_CTRLS, 0xC0205648);
+        }
+    }
+
+    /// VideoControl interface followed by a Logitech-style extension unit
+    /// with selectors 1, 2 and 9, then an audio interface whose feature unit
+    /// shares the subtype number and must be ignored.
+    fn sample_descriptors() -> Vec<u8> {
+        let mut d = Vec::new();
+        d.extend_from_slice(&[9, USB_DT_INTERFACE, 0, 0, 0, USB_CLASS_VIDEO, 0x01, 0, 0]);
+
+        let mut xu = vec![0, USB_DT_CS_INTERFACE, UVC_VC_EXTENSION_UNIT, 11];
+        xu.extend_from_slice(&[
+            0x21, 0x2d, 0xe5, 0xff, 0x30, 0x80, 0x2c, 0x4e, 0x82, 0xd9, 0xf5, 0x87, 0xd0, 0x05,
+            0x40, 0xbd,
+        ]);
+        // bNumControls, bNrInPins, baSourceID, bControlSize, bmControls, iExtension
+        xu.extend_from_slice(&[3, 1, 2, 2, 0b0000_0011, 0b0000_0001, 0]);
+        xu[0] = xu.len() as u8;
+        d.extend(xu);
+
+        // Audio control interface with a same-subtype unit
+        d.extend_from_slice(&[9, USB_DT_INTERFACE, 1, 0, 0, 0x01, 0x01, 0, 0]);
+        let mut audio_unit = vec![0u8; 26];
+        audio_unit[..4].copy_from_slice(&[26, USB_DT_CS_INTERFACE, UVC_VC_EXTENSION_UNIT, 5]);
+        d.extend(audio_unit);
+        d
+    }
+
+    #[test]
+    fn test_parse_extension_units() {
+        let units = parse_extension_units(&sample_descriptors());
+        assert_eq!(units.len(), 1);
+        assert_eq!(units[0].unit_id, 11);
+        assert_eq!(units[0].selectors, vec![1, 2, 9]);
+        assert_eq!(
+            units[0].guid_string(),
+            "ffe52d21-8030-4e2c-82d9-f587d00540bd"
+        );
+    }
+
+    #[test]
+    fn test_parse_truncated_descriptors() {
+        let mut d = sample_descriptors();
+        d.truncate(20);
+        assert!(parse_extension_units(&d).is_empty());
+    }
+
+    #[test]
+    fn test_xu_preset_lookup() {
+        let preset = XuPreset::find("FFE52D21-8030-4E2C-82D9-F587D00540BD", 0x09).unwrap();
+        assert_eq!(preset.name, "LED");
+        assert!(XuPreset::find("ffe52d21-8030-4e2c-82d9-f587d00540bd", 0x01).is_none());
     }
 
     #[test]
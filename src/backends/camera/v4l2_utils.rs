@@ -525,3 +525,78 @@ pub fn enumerate_v4l2_formats(device_path: &str) -> Vec<V4l2FormatInfo> {
 
     formats
 }
+
+// ===== Current Format Colorimetry =====
+
+/// VIDIOC_G_FMT ioctl number (v4l2_format: 208 bytes on 64-bit, nr=4)
+const VIDIOC_G_FMT: libc::c_ulong = 0xC0D05604;
+
+/// V4L2 single-planar pixel format (`v4l2_pix_format`)
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct V4l2PixFormat {
+    width: u32,
+    height: u32,
+    pixelformat: u32,
+    field: u32,
+    bytesperline: u32,
+    sizeimage: u32,
+    colorspace: u32,
+    private: u32,
+    flags: u32,
+    ycbcr_enc: u32,
+    quantization: u32,
+    xfer_func: u32,
+}
+
+/// V4L2 format structure for VIDIOC_G_FMT, with the union read as a pixel
+/// format. The union holds pointers, so it starts 8-byte aligned.
+#[repr(C)]
+struct V4l2Format {
+    buf_type: u32,
+    _align: u32,
+    pix: V4l2PixFormat,
+    _raw: [u8; 152],
+}
+
+/// Colorimetry the driver reports for the current capture format, as raw
+/// V4L2 enum values (`v4l2_colorspace`, `v4l2_ycbcr_encoding`,
+/// `v4l2_quantization`, `v4l2_xfer_func`; 0 means the colorspace's default)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct V4l2Colorimetry {
+    pub colorspace: u32,
+    pub ycbcr_enc: u32,
+    pub quantization: u32,
+    pub xfer_func: u32,
+}
+
+/// Query the colorimetry of a device's current capture format.
+///
+/// Works while another process streams from the device. Returns `None` if
+/// the device cannot be opened or the ioctl fails.
+pub fn current_colorimetry(device_path: &str) -> Option<V4l2Colorimetry> {
+    let file = std::fs::File::open(device_path).ok()?;
+    let mut format: V4l2Format = unsafe { std::mem::zeroed() };
+    format.buf_type = V4L2_BUF_TYPE_VIDEO_CAPTURE;
+
+    let ret = unsafe {
+        libc::syscall(
+            libc::SYS_ioctl,
+            file.as_raw_fd(),
+            VIDIOC_G_FMT,
+            &mut format as *mut V4l2Format,
+        )
+    };
+    if ret < 0 {
+        return None;
+    }
+
+    let colorimetry = V4l2Colorimetry {
+        colorspace: format.pix.colorspace,
+        ycbcr_enc: format.pix.ycbcr_enc,
+        quantization: format.pix.quantization,
+        xfer_func: format.pix.xfer_func,
+    };
+    debug!(device_path, ?colorimetry, "Got V4L2 colorimetry");
+    Some(colorimetry)
+}
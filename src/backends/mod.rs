@@ -6,6 +6,7 @@
 //! - Camera capture via libcamera
 //! - Audio device enumeration via PipeWire
 //! - Virtual camera output via PipeWire
+//! - Desktop capture via the ScreenCast portal
 //!
 //! # Architecture
 //!
@@ -35,9 +36,12 @@
 //! - [`audio`]: Audio device enumeration and selection
 //! - [`camera`]: Camera backend with device enumeration and frame capture
 //! - [`virtual_camera`]: Virtual camera sink for streaming filtered video
+//! - [`screen_cast`]: Desktop stream for screen recordings with the camera inset
 
 pub mod audio;
 pub mod camera;
 pub mod haptic;
+pub mod orientation;
+pub mod screen_cast;
 pub mod virtual_camera;
 pub mod volume_keys;
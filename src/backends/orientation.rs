@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Device orientation from iio-sensor-proxy
+//!
+//! Phones and convertibles report how they're held through the
+//! `net.hadess.SensorProxy` service on the system bus. The app adds this to
+//! the sensor's mounting rotation for captured photos, recordings and the
+//! virtual camera, so a shot taken with the phone on its side comes out
+//! landscape. Saved files get it as an orientation tag unless the user asks
+//! for it to be baked into the pixels. The accelerometer claim is tied to the bus connection, so it
+//! is released when [`watch`] returns or its future is dropped.
+
+use crate::backends::camera::types::SensorRotation;
+use futures::StreamExt;
+use tracing::{debug, info};
+
+const SERVICE: &str = "net.hadess.SensorProxy";
+const PATH: &str = "/net/hadess/SensorProxy";
+
+/// Rotation correction for an iio-sensor-proxy orientation string, or
+/// `None` when the orientation is unknown (e.g. lying flat)
+pub fn parse_orientation(orientation: &str) -> Option<SensorRotation> {
+    // Holding the device rotated clockwise (left edge up) turns the sensor
+    // with it, so the image needs the same correction as a sensor mounted
+    // 90° clockwise.
+    match orientation {
+        "normal" => Some(SensorRotation::None),
+        "left-up" => Some(SensorRotation::Rotate90),
+        "bottom-up" => Some(SensorRotation::Rotate180),
+        "right-up" => Some(SensorRotation::Rotate270),
+        _ => None,
+    }
+}
+
+/// Claim the accelerometer and send its orientation on `tx`: once at
+/// start, then on every change. Unknown orientations are skipped so the
+/// last known one sticks.
+///
+/// Returns an error when there's no system bus, no iio-sensor-proxy or no
+/// accelerometer; `Ok` once `tx` closes or the service goes away.
+pub async fn watch(tx: tokio::sync::mpsc::Sender<SensorRotation>) -> Result<(), String> {
+    let connection = zbus::Connection::system()
+        .await
+        .map_err(|e| format!("Failed to connect to system D-Bus: {}", e))?;
+    let proxy = zbus::Proxy::new(&connection, SERVICE, PATH, SERVICE)
+        .await
+        .map_err(|e| format!("Failed to create sensor proxy: {}", e))?;
+
+    let has_accelerometer: bool = proxy
+        .get_property("HasAccelerometer")
+        .await
+        .map_err(|e| format!("iio-sensor-proxy unavailable: {}", e))?;
+    if !has_accelerometer {
+        return Err("No accelerometer".to_string());
+    }
+    proxy
+        .call_method("ClaimAccelerometer", &())
+        .await
+        .map_err(|e| format!("Failed to claim accelerometer: {}", e))?;
+    info!("Accelerometer claimed for device orientation");
+
+    let mut changes = proxy
+        .receive_property_changed::<String>("AccelerometerOrientation")
+        .await;
+
+    let initial: String = proxy
+        .get_property("AccelerometerOrientation")
+        .await
+        .unwrap_or_default();
+    if let Some(rotation) = parse_orientation(&initial)
+        && tx.send(rotation).await.is_err()
+    {
+        return Ok(());
+    }
+
+    while let Some(change) = changes.next().await {
+        let Ok(orientation) = change.get().await else {
+            continue;
+        };
+        debug!(%orientation, "Device orientation changed");
+        if let Some(rotation) = parse_orientation(&orientation)
+            && tx.send(rotation).await.is_err()
+        {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_orientations_map_to_rotations() {
+        assert_eq!(parse_orientation("normal"), Some(SensorRotation::None));
+        assert_eq!(parse_orientation("left-up"), Some(SensorRotation::Rotate90));
+        assert_eq!(
+            parse_orientation("bottom-up"),
+            Some(SensorRotation::Rotate180)
+        );
+        assert_eq!(
+            parse_orientation("right-up"),
+            Some(SensorRotation::Rotate270)
+        );
+    }
+
+    #[test]
+    fn undefined_orientation_is_ignored() {
+        assert_eq!(parse_orientation("undefined"), None);
+        assert_eq!(parse_orientation(""), None);
+    }
+
+    #[test]
+    fn device_rotation_composes_with_the_sensor() {
+        assert_eq!(
+            SensorRotation::Rotate270.compose(SensorRotation::Rotate90),
+            SensorRotation::None
+        );
+        assert_eq!(
+            SensorRotation::Rotate90.compose(SensorRotation::Rotate180),
+            SensorRotation::Rotate270
+        );
+    }
+}
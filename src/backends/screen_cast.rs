@@ -0,0 +1,301 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Desktop capture through the ScreenCast portal
+//!
+//! Screen recordings with the camera inset ask the desktop for a monitor or
+//! window through `org.freedesktop.portal.ScreenCast`, which shows the
+//! compositor's own picker and hands back a PipeWire remote with a single
+//! stream on it. [`ScreenCapture`] reads that stream through GStreamer's
+//! `pipewiresrc` as RGBA frames; the recorder takes them as its primary
+//! picture and composites the camera on top with the same picture-in-picture
+//! stage the virtual camera and second camera use.
+
+use crate::backends::camera::pip::SharedPipFrame;
+use crate::backends::camera::types::{
+    BackendError, BackendResult, CameraFrame, FrameData, PixelFormat, RecordingFrame,
+    SensorRotation,
+};
+use crate::errors::{AppResult, PortalError};
+use crate::pipelines::video::recorder::read_clock_boottime_ns;
+use ashpd::desktop::PersistMode;
+use ashpd::desktop::screencast::{CursorMode, Screencast, SourceType};
+use std::os::fd::{AsRawFd, OwnedFd};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{debug, info, warn};
+
+/// Widest frame recorded from the screen; larger desktops are scaled down
+/// so a 4K monitor doesn't outrun the encoder
+pub const MAX_WIDTH: u32 = 1920;
+
+/// A screen stream the user picked in the portal dialog
+#[derive(Debug)]
+pub struct ScreenCast {
+    /// PipeWire remote the stream lives on
+    fd: OwnedFd,
+    node_id: u32,
+    /// Stream size as announced by the compositor, if it told
+    size: Option<(u32, u32)>,
+}
+
+impl ScreenCast {
+    /// Size frames are recorded at, if the compositor announced one
+    pub fn recording_size(&self) -> Option<(u32, u32)> {
+        self.size.map(recording_size)
+    }
+}
+
+/// Frame size for a `size`-sized screen: scaled down to [`MAX_WIDTH`] with
+/// the aspect ratio kept, and even on both sides as encoders require
+pub fn recording_size(size: (u32, u32)) -> (u32, u32) {
+    let (width, height) = size;
+    let (width, height) = if width > MAX_WIDTH {
+        let scaled = (height as u64 * MAX_WIDTH as u64 / width.max(1) as u64) as u32;
+        (MAX_WIDTH, scaled)
+    } else {
+        (width, height)
+    };
+    ((width & !1).max(2), (height & !1).max(2))
+}
+
+/// Ask the desktop for a screen to record
+///
+/// Shows the portal's source picker. Fails when the user cancels it or no
+/// ScreenCast portal is running ([`PortalError::Missing`]).
+pub async fn request() -> AppResult<ScreenCast> {
+    let proxy = Screencast::new().await.map_err(|e| {
+        warn!(error = %e, "ScreenCast portal unavailable");
+        PortalError::Missing("ScreenCast".to_string())
+    })?;
+    let session = proxy
+        .create_session()
+        .await
+        .map_err(|e| format!("Failed to create screen cast session: {}", e))?;
+    proxy
+        .select_sources(
+            &session,
+            CursorMode::Embedded,
+            SourceType::Monitor | SourceType::Window,
+            false,
+            None,
+            PersistMode::DoNot,
+        )
+        .await
+        .map_err(|e| format!("Failed to select screen sources: {}", e))?;
+    let response = proxy
+        .start(&session, None)
+        .await
+        .and_then(|request| request.response())
+        .map_err(|e| format!("Screen cast was not started: {}", e))?;
+    let stream = response
+        .streams()
+        .first()
+        .ok_or_else(|| "The portal returned no screen stream".to_string())?;
+    let node_id = stream.pipe_wire_node_id();
+    let size = stream
+        .size()
+        .filter(|&(w, h)| w > 0 && h > 0)
+        .map(|(w, h)| (w as u32, h as u32));
+    let fd = proxy
+        .open_pipe_wire_remote(&session)
+        .await
+        .map_err(|e| format!("Failed to open the PipeWire remote: {}", e))?;
+
+    info!(node_id, ?size, "Screen cast started");
+    Ok(ScreenCast { fd, node_id, size })
+}
+
+/// A running screen capture.
+///
+/// Owns the GStreamer pipeline and its reader thread; dropping it stops
+/// both, which closes the frame channel handed to [`ScreenCapture::start`].
+pub struct ScreenCapture {
+    pipeline: gstreamer::Pipeline,
+    stop: Arc<AtomicBool>,
+    reader: Option<std::thread::JoinHandle<()>>,
+    /// Keeps the PipeWire remote open for as long as the pipeline reads it
+    _cast: Arc<ScreenCast>,
+}
+
+impl ScreenCapture {
+    /// Start reading `cast` at `size` and send every frame on `tx`,
+    /// stamped with the capture time so a still screen doesn't speed the
+    /// recording up.
+    pub fn start(
+        cast: Arc<ScreenCast>,
+        size: (u32, u32),
+        tx: tokio::sync::mpsc::Sender<RecordingFrame>,
+    ) -> BackendResult<Self> {
+        use gstreamer::prelude::*;
+
+        gstreamer::init()
+            .map_err(|e| BackendError::Other(format!("GStreamer init failed: {}", e)))?;
+
+        let (width, height) = size;
+        let pipeline_str = format!(
+            "pipewiresrc fd={fd} path={node} always-copy=true ! videoconvert ! videoscale ! \
+             video/x-raw,format=RGBA,width={width},height={height},pixel-aspect-ratio=1/1 ! \
+             appsink name=sink max-buffers=1 drop=true sync=false",
+            fd = cast.fd.as_raw_fd(),
+            node = cast.node_id,
+        );
+        let pipeline = gstreamer::parse::launch(&pipeline_str)
+            .map_err(|e| BackendError::InitializationFailed(format!("Screen pipeline: {}", e)))?
+            .downcast::<gstreamer::Pipeline>()
+            .map_err(|_| BackendError::Other("Failed to downcast to Pipeline".into()))?;
+        let appsink = pipeline
+            .by_name("sink")
+            .ok_or_else(|| BackendError::Other("Failed to find appsink".into()))?
+            .downcast::<gstreamer_app::AppSink>()
+            .map_err(|_| BackendError::Other("Failed to downcast to AppSink".into()))?;
+
+        pipeline.set_state(gstreamer::State::Playing).map_err(|e| {
+            let _ = pipeline.set_state(gstreamer::State::Null);
+            BackendError::InitializationFailed(format!("Failed to start screen capture: {:?}", e))
+        })?;
+        info!(
+            node_id = cast.node_id,
+            width, height, "Screen capture started"
+        );
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let reader = {
+            let stop = Arc::clone(&stop);
+            std::thread::Builder::new()
+                .name("screen-capture".into())
+                .spawn(move || {
+                    while !stop.load(Ordering::Acquire) {
+                        let Some(sample) =
+                            appsink.try_pull_sample(gstreamer::ClockTime::from_mseconds(100))
+                        else {
+                            if appsink.is_eos() {
+                                warn!("Screen stream ended");
+                                break;
+                            }
+                            continue;
+                        };
+                        let frame =
+                            match crate::backends::virtual_camera::extract_frame_from_sample(
+                                &sample,
+                            ) {
+                                Ok(frame) => frame,
+                                Err(e) => {
+                                    debug!(error = %e, "Dropping screen sample");
+                                    continue;
+                                }
+                            };
+                        let frame = CameraFrame {
+                            sensor_timestamp_ns: Some(read_clock_boottime_ns()),
+                            ..frame
+                        };
+                        // The recorder is behind: skip rather than queue
+                        // stale desktop frames
+                        if let Err(tokio::sync::mpsc::error::TrySendError::Closed(_)) =
+                            tx.try_send(RecordingFrame::Decoded(Arc::new(frame)))
+                        {
+                            break;
+                        }
+                    }
+                })
+                .map_err(|e| {
+                    BackendError::Other(format!("Failed to spawn screen reader: {}", e))
+                })?
+        };
+
+        Ok(Self {
+            pipeline,
+            stop,
+            reader: Some(reader),
+            _cast: cast,
+        })
+    }
+}
+
+impl Drop for ScreenCapture {
+    fn drop(&mut self) {
+        use gstreamer::prelude::*;
+
+        self.stop.store(true, Ordering::Release);
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+        let _ = self.pipeline.set_state(gstreamer::State::Null);
+        info!("Screen capture stopped");
+    }
+}
+
+/// Keep `slot` holding the camera's latest frame as upright RGBA, for the
+/// recorder to inset over the screen. Camera frames arrive in whatever
+/// format the sensor delivers; the inset compositor only takes RGBA.
+///
+/// Returns, clearing `slot`, once `rx` closes.
+pub async fn feed_camera_inset(
+    mut rx: tokio::sync::mpsc::Receiver<RecordingFrame>,
+    slot: SharedPipFrame,
+    rotation: SensorRotation,
+    mirror: bool,
+) {
+    while let Some(frame) = rx.recv().await {
+        let RecordingFrame::Decoded(frame) = frame else {
+            continue;
+        };
+        let rgba = match crate::pipelines::video::recorder::convert_frame_to_rgba(&frame).await {
+            Ok(rgba) => rgba,
+            Err(e) => {
+                debug!(error = %e, "Dropping camera frame for the screen inset");
+                continue;
+            }
+        };
+        let (data, width, height) = if rotation != SensorRotation::None || mirror {
+            match crate::shaders::rotate_rgba_gpu(
+                &rgba,
+                frame.width,
+                frame.height,
+                rotation,
+                mirror,
+            )
+            .await
+            {
+                Ok(rotated) => rotated,
+                Err(e) => {
+                    debug!(error = %e, "Failed to rotate the screen inset");
+                    continue;
+                }
+            }
+        } else {
+            (rgba, frame.width, frame.height)
+        };
+        let inset = CameraFrame {
+            width,
+            height,
+            data: FrameData::Copied(Arc::from(data.into_boxed_slice())),
+            format: PixelFormat::RGBA,
+            stride: width * 4,
+            yuv_planes: None,
+            captured_at: frame.captured_at,
+            sensor_timestamp_ns: frame.sensor_timestamp_ns,
+            libcamera_metadata: None,
+        };
+        *slot.lock().unwrap() = Some(Arc::new(inset));
+    }
+    *slot.lock().unwrap() = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_screens_are_scaled_to_the_width_limit() {
+        assert_eq!(recording_size((3840, 2160)), (1920, 1080));
+        assert_eq!(recording_size((2560, 1600)), (1920, 1200));
+        // Ultrawide: height rounded down to even
+        assert_eq!(recording_size((3440, 1440)), (1920, 802));
+    }
+
+    #[test]
+    fn small_screens_keep_their_size_rounded_to_even() {
+        assert_eq!(recording_size((1366, 768)), (1366, 768));
+        assert_eq!(recording_size((1001, 601)), (1000, 600));
+    }
+}
@@ -6,6 +6,7 @@
 //! for use with the virtual camera output. Videos also stream audio
 //! to a virtual microphone via PipeWire.
 
+use super::playlist::SimulatedCapture;
 use crate::backends::camera::types::{
     BackendError, BackendResult, CameraFrame, FrameData, PixelFormat,
 };
@@ -191,7 +192,7 @@ pub fn get_video_duration(path: &Path) -> BackendResult<f64> {
 }
 
 /// Extract frame dimensions and data from a GStreamer sample
-fn extract_frame_from_sample(sample: &gstreamer::Sample) -> BackendResult<CameraFrame> {
+pub fn extract_frame_from_sample(sample: &gstreamer::Sample) -> BackendResult<CameraFrame> {
     let caps = sample
         .caps()
         .ok_or_else(|| BackendError::Other("No caps on sample".into()))?;
@@ -226,6 +227,124 @@ fn extract_frame_from_sample(sample: &gstreamer::Sample) -> BackendResult<Camera
     })
 }
 
+/// Sequential reader over every frame of a video file
+///
+/// Unlike [`VideoDecoder`] it neither plays at the video's native rate nor
+/// drops frames, and has no audio branch: it is for batch jobs that walk a
+/// whole recording as fast as it decodes.
+pub struct VideoFrameReader {
+    pipeline: gstreamer::Pipeline,
+    appsink: gstreamer_app::AppSink,
+    duration: Option<f64>,
+    /// Timestamp of the last frame, for buffers that carry none
+    last_timestamp: f64,
+}
+
+impl VideoFrameReader {
+    /// Open a video file and preroll it
+    pub fn open(path: &Path) -> BackendResult<Self> {
+        use gstreamer::prelude::*;
+
+        info!(path = %path.display(), "Opening video for sequential frame reading");
+
+        gstreamer::init()
+            .map_err(|e| BackendError::Other(format!("GStreamer init failed: {}", e)))?;
+
+        let path_str = escape_gst_string(&path.to_string_lossy());
+        let pipeline_str = format!(
+            "filesrc location=\"{}\" ! decodebin3 ! \
+             videoconvert ! video/x-raw,format=RGBA ! \
+             appsink name=sink max-buffers=4 drop=false sync=false",
+            path_str
+        );
+
+        let pipeline = gstreamer::parse::launch(&pipeline_str)
+            .map_err(|e| BackendError::Other(format!("Failed to create pipeline: {}", e)))?
+            .downcast::<gstreamer::Pipeline>()
+            .map_err(|_| BackendError::Other("Failed to downcast to Pipeline".into()))?;
+
+        let appsink = pipeline
+            .by_name("sink")
+            .ok_or_else(|| BackendError::Other("Failed to find appsink".into()))?
+            .downcast::<gstreamer_app::AppSink>()
+            .map_err(|_| BackendError::Other("Failed to downcast to AppSink".into()))?;
+
+        pipeline
+            .set_state(gstreamer::State::Paused)
+            .map_err(|e| BackendError::Other(format!("Failed to pause pipeline: {:?}", e)))?;
+        if let Err(e) = wait_for_pipeline_ready(&pipeline, 5) {
+            let _ = pipeline.set_state(gstreamer::State::Null);
+            return Err(e);
+        }
+
+        let duration = pipeline
+            .query_duration::<gstreamer::ClockTime>()
+            .map(|d| d.seconds_f64());
+
+        pipeline
+            .set_state(gstreamer::State::Playing)
+            .map_err(|e| BackendError::Other(format!("Failed to start pipeline: {:?}", e)))?;
+
+        Ok(Self {
+            pipeline,
+            appsink,
+            duration,
+            last_timestamp: 0.0,
+        })
+    }
+
+    /// Total duration in seconds, if the container reports one
+    pub fn duration(&self) -> Option<f64> {
+        self.duration
+    }
+
+    /// Next frame and its timestamp in seconds, or `None` at the end
+    pub fn next_frame(&mut self) -> BackendResult<Option<(f64, CameraFrame)>> {
+        use gstreamer::prelude::*;
+
+        let Some(sample) = self
+            .appsink
+            .try_pull_sample(gstreamer::ClockTime::from_seconds(
+                vc_timing::VIDEO_FRAME_TIMEOUT_SECS,
+            ))
+        else {
+            if self.appsink.is_eos() {
+                return Ok(None);
+            }
+            if let Some(bus) = self.pipeline.bus() {
+                while let Some(msg) = bus.pop() {
+                    if let gstreamer::MessageView::Error(err) = msg.view() {
+                        return Err(BackendError::Other(format!(
+                            "Video pipeline error: {}",
+                            err.error()
+                        )));
+                    }
+                }
+            }
+            return Err(BackendError::Other(
+                "Timeout waiting for video frame".into(),
+            ));
+        };
+
+        let timestamp = sample
+            .buffer()
+            .and_then(|buffer| buffer.pts())
+            .map(|pts| pts.seconds_f64())
+            .unwrap_or(self.last_timestamp);
+        self.last_timestamp = timestamp;
+
+        extract_frame_from_sample(&sample).map(|frame| Some((timestamp, frame)))
+    }
+}
+
+impl Drop for VideoFrameReader {
+    fn drop(&mut self) {
+        use gstreamer::prelude::*;
+
+        let _ = self.pipeline.set_state(gstreamer::State::Null);
+    }
+}
+
 /// Create a video frame extraction pipeline with appsink
 fn create_frame_extraction_pipeline(
     path: &Path,
@@ -293,9 +412,7 @@ fn load_video_first_frame(path: &Path) -> BackendResult<CameraFrame> {
 pub fn load_image_as_frame(path: &Path) -> BackendResult<CameraFrame> {
     info!(path = %path.display(), "Loading image file");
 
-    let img = image::open(path).map_err(|e| {
-        BackendError::Other(format!("Failed to load image '{}': {}", path.display(), e))
-    })?;
+    let img = crate::storage::open_photo(path).map_err(BackendError::Other)?;
 
     let rgba = img.to_rgba8();
     let width = rgba.width();
@@ -317,6 +434,37 @@ pub fn load_image_as_frame(path: &Path) -> BackendResult<CameraFrame> {
     })
 }
 
+/// Crop an RGBA frame to the aspect ratio of a simulated capture and scale
+/// it to its size, as a camera in that format would have framed the scene
+pub fn fit_frame_to_capture(frame: CameraFrame, capture: SimulatedCapture) -> CameraFrame {
+    if frame.format != PixelFormat::RGBA
+        || (frame.width, frame.height) == (capture.width, capture.height)
+    {
+        return frame;
+    }
+    let Some(image) =
+        image::RgbaImage::from_raw(frame.stride / 4, frame.height, frame.data.as_ref().to_vec())
+    else {
+        return frame;
+    };
+    let image = image::DynamicImage::ImageRgba8(image)
+        .crop_imm(0, 0, frame.width, frame.height)
+        .resize_to_fill(
+            capture.width,
+            capture.height,
+            image::imageops::FilterType::Triangle,
+        )
+        .into_rgba8();
+
+    CameraFrame {
+        data: FrameData::Copied(Arc::from(image.into_raw().into_boxed_slice())),
+        width: capture.width,
+        height: capture.height,
+        stride: capture.width * 4,
+        ..frame
+    }
+}
+
 /// Video file decoder for streaming frames with audio
 ///
 /// Uses GStreamer to decode video files and provides frames as CameraFrame.
@@ -334,6 +482,8 @@ pub struct VideoDecoder {
     height: u32,
     /// Whether the video has audio
     has_audio: bool,
+    /// Playback speed, kept across seeks and restarts
+    rate: f64,
 }
 
 impl VideoDecoder {
@@ -342,21 +492,47 @@ impl VideoDecoder {
     /// The pipeline decodes video to RGBA format and optionally
     /// forwards audio to a PipeWire virtual microphone.
     pub fn new(path: &Path) -> BackendResult<Self> {
+        Self::with_playback(path, 1.0, None)
+    }
+
+    /// Create a video decoder that plays at `rate` and, with a `capture`
+    /// format, delivers frames cropped and scaled to its size at its framerate
+    pub fn with_playback(
+        path: &Path,
+        rate: f64,
+        capture: Option<SimulatedCapture>,
+    ) -> BackendResult<Self> {
         use gstreamer::prelude::*;
 
-        info!(path = %path.display(), "Creating video decoder with audio support");
+        info!(path = %path.display(), rate, ?capture, "Creating video decoder with audio support");
 
         gstreamer::init()
             .map_err(|e| BackendError::Other(format!("GStreamer init failed: {}", e)))?;
 
         let path_str = escape_gst_string(&path.to_string_lossy());
 
+        // A simulated capture crops to its aspect ratio, then scales and
+        // drops or duplicates frames to match it
+        let (capture_elements, capture_caps) = match capture {
+            Some(capture) => (
+                format!(
+                    "aspectratiocrop aspect-ratio={}/{} ! videoscale ! videorate ! ",
+                    capture.width, capture.height
+                ),
+                format!(
+                    ",width={},height={},pixel-aspect-ratio=1/1,framerate={}/1",
+                    capture.width, capture.height, capture.framerate
+                ),
+            ),
+            None => (String::new(), String::new()),
+        };
+
         // Create video pipeline: filesrc → decodebin3 → videoconvert → appsink
         // Note: sync=true is important to play video at correct speed (matches video's native framerate)
         let video_pipeline_str = format!(
             "filesrc location=\"{}\" ! decodebin3 name=decode ! \
-             queue ! videoconvert ! video/x-raw,format=RGBA ! appsink name=videosink emit-signals=true sync=true",
-            path_str
+             queue ! videoconvert ! {}video/x-raw,format=RGBA{} ! appsink name=videosink emit-signals=true sync=true",
+            path_str, capture_elements, capture_caps
         );
 
         let video_pipeline = gstreamer::parse::launch(&video_pipeline_str)
@@ -439,14 +615,19 @@ impl VideoDecoder {
 
         info!(width, height, has_audio, "Video decoder created");
 
-        Ok(Self {
+        let decoder = Self {
             video_pipeline,
             video_appsink,
             audio_pipeline,
             width,
             height,
             has_audio,
-        })
+            rate,
+        };
+        if rate != 1.0 {
+            decoder.seek_pipelines(gstreamer::SeekFlags::ACCURATE, gstreamer::ClockTime::ZERO);
+        }
+        Ok(decoder)
     }
 
     /// Create audio pipeline that sends audio to a PipeWire virtual microphone
@@ -589,29 +770,38 @@ impl VideoDecoder {
 
     /// Restart the video from the beginning (for looping)
     pub fn restart(&self) -> BackendResult<()> {
+        debug!("Restarting video for loop");
+
+        // Seek both pipelines to the beginning
+        self.seek_pipelines(gstreamer::SeekFlags::KEY_UNIT, gstreamer::ClockTime::ZERO);
+
+        Ok(())
+    }
+
+    /// Flush-seek the video pipeline and the audio pipeline, if any, to
+    /// `position` at the playback rate
+    fn seek_pipelines(&self, flags: gstreamer::SeekFlags, position: gstreamer::ClockTime) {
         use gstreamer::prelude::*;
 
-        debug!("Restarting video for loop");
+        let seek = |pipeline: &gstreamer::Pipeline| {
+            pipeline.seek(
+                self.rate,
+                gstreamer::SeekFlags::FLUSH | flags,
+                gstreamer::SeekType::Set,
+                position,
+                gstreamer::SeekType::End,
+                gstreamer::ClockTime::ZERO,
+            )
+        };
 
-        // Seek video pipeline to beginning
-        if let Err(e) = self.video_pipeline.seek_simple(
-            gstreamer::SeekFlags::FLUSH | gstreamer::SeekFlags::KEY_UNIT,
-            gstreamer::ClockTime::ZERO,
-        ) {
+        if let Err(e) = seek(&self.video_pipeline) {
             warn!(?e, "Video seek failed");
         }
-
-        // Also restart audio pipeline if present
         if let Some(ref audio_pipeline) = self.audio_pipeline
-            && let Err(e) = audio_pipeline.seek_simple(
-                gstreamer::SeekFlags::FLUSH | gstreamer::SeekFlags::KEY_UNIT,
-                gstreamer::ClockTime::ZERO,
-            )
+            && let Err(e) = seek(audio_pipeline)
         {
             warn!(?e, "Audio seek failed");
         }
-
-        Ok(())
     }
 
     /// Get video dimensions
@@ -658,29 +848,11 @@ impl VideoDecoder {
     /// is that GStreamer decodes from the previous keyframe up to the target
     /// position, but for typical playback GOPs that's well under a second.
     pub fn seek(&self, position_secs: f64) {
-        use gstreamer::prelude::*;
-
         let position =
             gstreamer::ClockTime::from_nseconds((position_secs * 1_000_000_000.0) as u64);
         debug!(position_secs, "Seeking video to position");
 
-        // Seek video pipeline
-        if let Err(e) = self.video_pipeline.seek_simple(
-            gstreamer::SeekFlags::FLUSH | gstreamer::SeekFlags::ACCURATE,
-            position,
-        ) {
-            warn!(?e, "Video seek failed");
-        }
-
-        // Also seek audio pipeline if present
-        if let Some(ref audio_pipeline) = self.audio_pipeline
-            && let Err(e) = audio_pipeline.seek_simple(
-                gstreamer::SeekFlags::FLUSH | gstreamer::SeekFlags::ACCURATE,
-                position,
-            )
-        {
-            warn!(?e, "Audio seek failed");
-        }
+        self.seek_pipelines(gstreamer::SeekFlags::ACCURATE, position);
     }
 
     /// Pause or resume playback
@@ -22,6 +22,8 @@ struct FilterParams {
     height: u32,
     filter_mode: u32,
     _padding: u32,
+    /// Software white balance gains (rgb, alpha unused)
+    wb_gains: [f32; 4],
 }
 
 /// Blur parameters for pre-blur compute shader
@@ -60,6 +62,8 @@ pub struct GpuFilterRenderer {
     sampler: wgpu::Sampler,
     // Uniform buffer
     uniform_buffer: wgpu::Buffer,
+    // Active imported LUT
+    lut: crate::shaders::LutTexture,
     // Pre-blur uniform buffer
     preblur_uniform_buffer: wgpu::Buffer,
     // Current dimensions
@@ -89,7 +93,7 @@ impl GpuFilterRenderer {
         // Create shader with shared filter functions
         let shader_source = format!(
             "{}\n{}",
-            include_str!("../../shaders/filters.wgsl"),
+            crate::shaders::FILTER_FUNCTIONS,
             include_str!("../../shaders/filter_compute.wgsl")
         );
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
@@ -141,6 +145,13 @@ impl GpuFilterRenderer {
                     ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                     count: None,
                 },
+                // Imported LUT
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: crate::shaders::LutTexture::BINDING_TYPE,
+                    count: None,
+                },
             ],
         });
 
@@ -254,6 +265,8 @@ impl GpuFilterRenderer {
             mapped_at_creation: false,
         });
 
+        let lut = crate::shaders::LutTexture::new(&device);
+
         Ok(Self {
             device,
             queue,
@@ -267,6 +280,7 @@ impl GpuFilterRenderer {
             preblur_bind_group_layout,
             sampler,
             uniform_buffer,
+            lut,
             preblur_uniform_buffer,
             width: 0,
             height: 0,
@@ -347,8 +361,8 @@ impl GpuFilterRenderer {
             ));
         }
 
-        // For standard filter, just copy the data (handle stride)
-        if filter == FilterType::Standard {
+        // For standard filter without white balance, just copy the data (handle stride)
+        if !crate::shaders::needs_filter_pass(filter) {
             return self.passthrough_frame(frame);
         }
 
@@ -386,6 +400,7 @@ impl GpuFilterRenderer {
             height: frame.height,
             filter_mode: filter as u32,
             _padding: 0,
+            wb_gains: crate::shaders::software_white_balance().to_uniform(),
         };
         self.queue
             .write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&params));
@@ -455,6 +470,7 @@ impl GpuFilterRenderer {
         };
 
         // Main filter pass
+        self.lut.sync(&self.queue);
         let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("vcam_filter_bind_group"),
             layout: &self.bind_group_layout,
@@ -475,6 +491,10 @@ impl GpuFilterRenderer {
                     binding: 3,
                     resource: wgpu::BindingResource::Sampler(&self.sampler),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: wgpu::BindingResource::TextureView(self.lut.view()),
+                },
             ],
         });
 
@@ -26,17 +26,24 @@
 //!        ▼
 //!   Video Apps (Zoom, Teams, etc.)
 //! ```
+//!
+//! Camera frames come straight from the capture thread that also feeds the
+//! preview and recorder, so the device is only opened once. The PipeWire
+//! node is published with a higher session priority than hardware cameras,
+//! making it the default pick for other applications while it streams.
 
 mod file_source;
 mod gpu_filter;
 mod pipeline;
+mod playlist;
 
 pub use file_source::{
-    VideoDecoder, get_video_duration, load_image_as_frame, load_preview_frame,
-    load_video_frame_at_position,
+    VideoDecoder, VideoFrameReader, extract_frame_from_sample, fit_frame_to_capture,
+    get_video_duration, load_image_as_frame, load_preview_frame, load_video_frame_at_position,
 };
 pub use gpu_filter::GpuFilterRenderer;
 pub use pipeline::VirtualCameraPipeline;
+pub use playlist::{LoopMode, PlaybackOptions, Playlist, SimulatedCapture};
 
 use crate::app::FilterType;
 use crate::backends::camera::types::{BackendError, BackendResult, CameraFrame};
@@ -156,8 +163,8 @@ impl VirtualCameraManager {
             .as_ref()
             .ok_or_else(|| BackendError::Other("Virtual camera not started".into()))?;
 
-        // For standard filter, just pass through the frame data
-        if self.current_filter == FilterType::Standard {
+        // For standard filter without white balance, just pass through the frame data
+        if !crate::shaders::needs_filter_pass(self.current_filter) {
             return self.push_passthrough_frame(pipeline, frame);
         }
 
@@ -214,7 +221,12 @@ impl VirtualCameraManager {
                                 );
                             }
                             // Pass owned Vec directly - zero-copy to GStreamer
-                            return pipeline.push_frame_rgba(rgba_data, frame.width, frame.height);
+                            return self.push_fitted(
+                                pipeline,
+                                rgba_data,
+                                frame.width,
+                                frame.height,
+                            );
                         }
                         Err(e) => {
                             warn!(error = ?e, "GPU filter failed, using passthrough");
@@ -247,7 +259,7 @@ impl VirtualCameraManager {
 
         // If stride matches expected row size and no flip needed, use data directly
         if stride == row_bytes && !self.flip_horizontal {
-            return pipeline.push_frame_rgba(frame.data.clone(), frame.width, frame.height);
+            return self.push_fitted(pipeline, frame.data.clone(), frame.width, frame.height);
         }
 
         // Copy data (and apply horizontal flip if needed)
@@ -272,7 +284,30 @@ impl VirtualCameraManager {
         }
 
         // Pass owned Vec - zero-copy to GStreamer
-        pipeline.push_frame_rgba(rgba_data, frame.width, frame.height)
+        self.push_fitted(pipeline, rgba_data, frame.width, frame.height)
+    }
+
+    /// Push packed RGBA, letterboxing it into the output size when the frame
+    /// size differs (e.g. a standby camera took over mid-stream)
+    fn push_fitted<T: AsRef<[u8]> + Send + 'static>(
+        &self,
+        pipeline: &VirtualCameraPipeline,
+        rgba_data: T,
+        width: u32,
+        height: u32,
+    ) -> BackendResult<()> {
+        let (out_width, out_height) = self.output_size;
+        if (width, height) == (out_width, out_height) {
+            return pipeline.push_frame_rgba(rgba_data, width, height);
+        }
+        let fitted = crate::media::formats::conversions::letterbox_rgba(
+            rgba_data.as_ref(),
+            width,
+            height,
+            out_width,
+            out_height,
+        );
+        pipeline.push_frame_rgba(fitted, out_width, out_height)
     }
 
     /// Flip RGBA data horizontally in place
@@ -15,6 +15,13 @@ use tracing::{debug, error, info, warn};
 
 static FRAME_COUNTER: AtomicU64 = AtomicU64::new(0);
 
+/// Session priority of the virtual camera node. While we stream, the real
+/// camera is held by our own capture, so other applications get the shared
+/// virtual node as their default camera instead of contending for the
+/// device. WirePlumber gives hardware camera nodes priorities in the
+/// 1000s, so this sits above them.
+const VIRTUAL_CAMERA_SESSION_PRIORITY: i32 = 2100;
+
 /// Virtual camera GStreamer pipeline
 ///
 /// Uses pipewiresink to create a virtual camera device that other
@@ -101,6 +108,8 @@ impl VirtualCameraPipeline {
             .field("media.role", "Camera")
             .field("node.name", "camera-virtual")
             .field("node.description", "Camera (Virtual)")
+            .field("priority.session", VIRTUAL_CAMERA_SESSION_PRIORITY)
+            .field("priority.driver", VIRTUAL_CAMERA_SESSION_PRIORITY)
             .build();
         pipewiresink.set_property("stream-properties", &stream_props);
 
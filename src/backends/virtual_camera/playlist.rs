@@ -0,0 +1,282 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Playlists and playback options for file sources
+//!
+//! A file source may be a directory (`--preview-source` or the folder picker):
+//! its images and videos then play one after another in file-name order, each
+//! video to its end and each image for a fixed time. [`PlaybackOptions`]
+//! control whether that repeats, how fast it plays, and which capture format
+//! the frames imitate, so a demo or test setup can stand in for a camera at a
+//! given resolution and framerate.
+//!
+//! Only the preview cycles: while the virtual camera streams a file source,
+//! it keeps streaming the entry it started on.
+
+use crate::app::FileSource;
+use crate::constants::virtual_camera as vc_timing;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Framerate of a simulated capture when only its size is given
+const DEFAULT_CAPTURE_FRAMERATE: u32 = 30;
+
+/// What happens when a file source reaches its end
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LoopMode {
+    /// Start over from the first entry after the last
+    #[default]
+    All,
+    /// Repeat the current entry
+    One,
+    /// Stop on the last frame of the last entry
+    Once,
+}
+
+impl LoopMode {
+    /// Whether a video entry restarts by itself at its end rather than
+    /// handing over to the next entry
+    pub fn repeats_entry(self, playlist_len: usize) -> bool {
+        match self {
+            Self::All => playlist_len <= 1,
+            Self::One => true,
+            Self::Once => false,
+        }
+    }
+}
+
+impl FromStr for LoopMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "all" => Ok(Self::All),
+            "one" => Ok(Self::One),
+            "once" => Ok(Self::Once),
+            _ => Err(format!(
+                "unknown loop mode '{s}' (expected all, one or once)"
+            )),
+        }
+    }
+}
+
+/// Capture format a file source imitates: frames are cropped to its aspect
+/// ratio, scaled to its size and paced at its framerate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulatedCapture {
+    pub width: u32,
+    pub height: u32,
+    pub framerate: u32,
+}
+
+impl SimulatedCapture {
+    /// Time between frames
+    pub fn frame_duration(self) -> Duration {
+        Duration::from_secs(1) / self.framerate
+    }
+}
+
+impl FromStr for SimulatedCapture {
+    type Err = String;
+
+    /// `WIDTHxHEIGHT` or `WIDTHxHEIGHT@FPS`, e.g. `1280x720@30`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (size, framerate) = match s.split_once('@') {
+            Some((size, fps)) => (
+                size,
+                fps.trim()
+                    .parse()
+                    .map_err(|e| format!("invalid framerate: {e}"))?,
+            ),
+            None => (s, DEFAULT_CAPTURE_FRAMERATE),
+        };
+        let (w, h) = size
+            .split_once('x')
+            .ok_or_else(|| "expected WIDTHxHEIGHT[@FPS] (e.g. 1280x720@30)".to_string())?;
+        let width: u32 = w
+            .trim()
+            .parse()
+            .map_err(|e| format!("invalid width: {e}"))?;
+        let height: u32 = h
+            .trim()
+            .parse()
+            .map_err(|e| format!("invalid height: {e}"))?;
+        if width == 0 || height == 0 || framerate == 0 {
+            return Err("capture size and framerate must be positive".to_string());
+        }
+        Ok(Self {
+            width,
+            height,
+            framerate,
+        })
+    }
+}
+
+/// How file sources play in the preview
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackOptions {
+    pub loop_mode: LoopMode,
+    /// Playback speed, 1.0 being real time; also shortens or lengthens the
+    /// time an image is shown
+    pub rate: f64,
+    /// How long an image in a playlist is shown at normal speed
+    pub image_duration: Duration,
+    /// Capture format to imitate, or the files' own
+    pub capture: Option<SimulatedCapture>,
+}
+
+impl Default for PlaybackOptions {
+    fn default() -> Self {
+        Self {
+            loop_mode: LoopMode::All,
+            rate: 1.0,
+            image_duration: Duration::from_secs(5),
+            capture: None,
+        }
+    }
+}
+
+impl PlaybackOptions {
+    /// Time between preview frames
+    pub fn frame_duration(&self) -> Duration {
+        self.capture
+            .map(SimulatedCapture::frame_duration)
+            .unwrap_or(vc_timing::IMAGE_STREAM_FRAME_DURATION)
+    }
+
+    /// How long an image in a playlist stays up at the playback rate
+    pub fn image_dwell(&self) -> Duration {
+        self.image_duration.div_f64(self.rate)
+    }
+}
+
+/// Media files of a directory, played in file-name order
+#[derive(Debug, Clone, PartialEq)]
+pub struct Playlist {
+    entries: Vec<FileSource>,
+    index: usize,
+    /// Bumped on every move, so a timer or decoder that finishes after the
+    /// playlist moved on can tell its entry is no longer playing
+    generation: u64,
+}
+
+impl Playlist {
+    /// Collect the images and videos in `dir`; other files and
+    /// subdirectories are skipped
+    pub fn scan(dir: &Path) -> Result<Self, String> {
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file())
+            .collect();
+        paths.sort();
+        Self::from_entries(
+            paths
+                .into_iter()
+                .filter_map(FileSource::from_path)
+                .collect(),
+        )
+        .ok_or_else(|| format!("No images or videos in {}", dir.display()))
+    }
+
+    fn from_entries(entries: Vec<FileSource>) -> Option<Self> {
+        (!entries.is_empty()).then_some(Self {
+            entries,
+            index: 0,
+            generation: 0,
+        })
+    }
+
+    /// Entry playing now
+    pub fn current(&self) -> &FileSource {
+        &self.entries[self.index]
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Move on from the current entry. Returns the entry to play next, or
+    /// `None` when a [`LoopMode::Once`] playlist is over.
+    pub fn advance(&mut self, loop_mode: LoopMode) -> Option<&FileSource> {
+        let next = match loop_mode {
+            LoopMode::All => (self.index + 1) % self.entries.len(),
+            LoopMode::One => self.index,
+            LoopMode::Once if self.index + 1 < self.entries.len() => self.index + 1,
+            LoopMode::Once => return None,
+        };
+        self.index = next;
+        self.generation += 1;
+        Some(self.current())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn scan_keeps_media_in_name_order() {
+        let dir = std::env::temp_dir().join(format!("camera-playlist-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("nested.png")).unwrap();
+        for name in ["b.mp4", "a.JPG", "notes.txt", "c.webp"] {
+            std::fs::write(dir.join(name), b"").unwrap();
+        }
+
+        let playlist = Playlist::scan(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            playlist.entries,
+            vec![
+                FileSource::Image(dir.join("a.JPG")),
+                FileSource::Video(dir.join("b.mp4")),
+                FileSource::Image(dir.join("c.webp")),
+            ]
+        );
+    }
+
+    #[test]
+    fn advance_follows_the_loop_mode() {
+        let entries = vec![
+            FileSource::Image(PathBuf::from("a.png")),
+            FileSource::Video(PathBuf::from("b.mp4")),
+        ];
+        let mut playlist = Playlist::from_entries(entries.clone()).unwrap();
+
+        assert_eq!(playlist.advance(LoopMode::One), Some(&entries[0]));
+        assert_eq!(playlist.advance(LoopMode::All), Some(&entries[1]));
+        assert_eq!(playlist.advance(LoopMode::All), Some(&entries[0]));
+        assert_eq!(playlist.advance(LoopMode::Once), Some(&entries[1]));
+        assert_eq!(playlist.advance(LoopMode::Once), None);
+        assert_eq!(playlist.current(), &entries[1]);
+        assert_eq!(playlist.generation(), 4);
+    }
+
+    #[test]
+    fn capture_format_parses_with_optional_framerate() {
+        assert_eq!(
+            "1280x720@60".parse(),
+            Ok(SimulatedCapture {
+                width: 1280,
+                height: 720,
+                framerate: 60,
+            })
+        );
+        assert_eq!(
+            "640x480".parse::<SimulatedCapture>().map(|c| c.framerate),
+            Ok(DEFAULT_CAPTURE_FRAMERATE)
+        );
+        assert!("1280x720@0".parse::<SimulatedCapture>().is_err());
+        assert!("1280".parse::<SimulatedCapture>().is_err());
+    }
+}
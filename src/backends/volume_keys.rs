@@ -6,7 +6,8 @@
 //! the Wayland compositor typically consumes for system audio, never
 //! delivering them as keyboard events to the focused app. This backend
 //! opens the matching `/dev/input/event*` nodes directly and forwards
-//! key-down events on a channel, so the app can wire them up to capture.
+//! key-down events on a channel, so the app can map them to capture or
+//! zoom.
 //!
 //! Focus-aware grab: while the camera window has keyboard focus we open
 //! each volume-key device and call `EVIOCGRAB(1)` so the compositor
@@ -15,7 +16,9 @@
 //! `EVIOCGRAB(0)` for release because some downstream kernels
 //! (postmarketOS / Pixel 3a sdm670 6.19 observed) refuse the
 //! ungrab ioctl with `EBUSY`, leaving the device stuck. Close-on-blur
-//! is portable: `close(2)` always tears down the grab.
+//! is portable: `close(2)` always tears down the grab. When the user keeps
+//! the keys for system volume ([`set_enabled`]`(false)`), devices are never
+//! grabbed, as if the window were unfocused.
 //!
 //! Works in Flatpak as long as the manifest grants `--device=all` (or
 //! `--device=input`) so `/dev/input/event*` is visible inside the
@@ -59,20 +62,29 @@ const EVIOCGRAB: libc::c_ulong = 0x40044590;
 /// noticed within ~50 ms.
 const POLL_INTERVAL_MS: libc::c_int = 50;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VolumeKey {
     Up,
     Down,
 }
 
 struct Handle {
-    /// Mirrors the iced window-focus state. Reader threads observe this
-    /// to decide whether to keep the device open and grabbed.
+    /// Window focused and keys enabled. Reader threads observe this to
+    /// decide whether to keep the device open and grabbed.
     focused: Arc<AtomicBool>,
+    /// Mirrors the iced window-focus state
+    window_focused: AtomicBool,
 }
 
 static HANDLE: OnceLock<Handle> = OnceLock::new();
 
+/// Whether the app wants the keys at all. Lives outside [`Handle`] so it
+/// can be set from the config before [`start`] runs.
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Set once [`start`] finds at least one volume-key device
+static AVAILABLE: AtomicBool = AtomicBool::new(false);
+
 /// Build the `EVIOCGBIT(ev, len)` ioctl request number.
 const fn eviocgbit(ev: u32, len: u32) -> libc::c_ulong {
     // _IOC packing: dir<<30 | size<<16 | type<<8 | nr (64-bit Linux).
@@ -151,12 +163,14 @@ pub fn start() -> UnboundedReceiver<VolumeKey> {
     let focused = Arc::new(AtomicBool::new(false));
     let _ = HANDLE.set(Handle {
         focused: Arc::clone(&focused),
+        window_focused: AtomicBool::new(false),
     });
     let devices = detect_volume_devices();
     if devices.is_empty() {
         debug!("No volume-key devices found");
         return rx;
     }
+    AVAILABLE.store(true, Ordering::Release);
     for dev_path in devices {
         let tx = tx.clone();
         let focused = Arc::clone(&focused);
@@ -171,6 +185,12 @@ pub fn start() -> UnboundedReceiver<VolumeKey> {
     rx
 }
 
+/// Whether the device has volume keys the app can read. False until
+/// [`start`] has scanned the input devices.
+pub fn is_available() -> bool {
+    AVAILABLE.load(Ordering::Acquire)
+}
+
 /// Update the focus state. Reader threads observe this atomic and
 /// (re)open + grab the device on `true → false → true` transitions.
 ///
@@ -180,9 +200,27 @@ pub fn set_focused(focused: bool) {
         info!(focused, "volume_keys::set_focused called before start()");
         return;
     };
-    let prev = handle.focused.swap(focused, Ordering::AcqRel);
-    if prev != focused {
-        info!(focused, "volume_keys focus updated");
+    handle.window_focused.store(focused, Ordering::Release);
+    update_grab(handle);
+}
+
+/// Take the keys from the compositor while focused (`true`, the default),
+/// or leave them alone so they keep controlling the system volume.
+pub fn set_enabled(enabled: bool) {
+    let prev = ENABLED.swap(enabled, Ordering::AcqRel);
+    if prev != enabled {
+        info!(enabled, "volume_keys enabled updated");
+    }
+    if let Some(handle) = HANDLE.get() {
+        update_grab(handle);
+    }
+}
+
+fn update_grab(handle: &Handle) {
+    let grab = handle.window_focused.load(Ordering::Acquire) && ENABLED.load(Ordering::Acquire);
+    let prev = handle.focused.swap(grab, Ordering::AcqRel);
+    if prev != grab {
+        info!(grab, "volume_keys focus updated");
     }
 }
 
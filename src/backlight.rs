@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Screen backlight control via logind
+//!
+//! Lets the screen double as a light: the built-in panel is found under
+//! `/sys/class/backlight` and driven to full brightness, then put back to
+//! the level it had. Writing the sysfs file needs root, so the level is set
+//! through logind's `SetBrightness`, which any user may call for the seat
+//! they are sitting at.
+
+use std::path::Path;
+use tracing::{info, warn};
+
+/// A backlight device and the level it was at when it was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backlight {
+    /// Device name, e.g. `intel_backlight`
+    pub name: String,
+    /// Brightness when the device was read
+    pub brightness: u32,
+    /// Maximum brightness value (from `max_brightness` file)
+    pub max_brightness: u32,
+}
+
+/// Preference for a backlight `type`, lower first. The kernel documents
+/// firmware interfaces as the most reliable and raw registers as the least.
+fn type_rank(kind: &str) -> u8 {
+    match kind {
+        "firmware" => 0,
+        "platform" => 1,
+        "raw" => 2,
+        _ => 3,
+    }
+}
+
+impl Backlight {
+    /// The panel's backlight, if the system has one (laptops, phones and
+    /// tablets; external monitors don't)
+    pub fn find() -> Option<Backlight> {
+        let read = |path: &Path| std::fs::read_to_string(path).ok();
+        let mut found: Vec<(u8, Backlight)> = std::fs::read_dir("/sys/class/backlight")
+            .ok()?
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                let max_brightness = read(&path.join("max_brightness"))?.trim().parse().ok()?;
+                let brightness = read(&path.join("brightness"))?.trim().parse().ok()?;
+                let kind = read(&path.join("type")).unwrap_or_default();
+                (max_brightness > 0).then(|| {
+                    (
+                        type_rank(kind.trim()),
+                        Backlight {
+                            name: entry.file_name().to_string_lossy().into_owned(),
+                            brightness,
+                            max_brightness,
+                        },
+                    )
+                })
+            })
+            .collect();
+        found.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.name.cmp(&b.1.name)));
+        found.into_iter().next().map(|(_, backlight)| backlight)
+    }
+
+    /// Set the brightness, clamped to the device's range. Blocking.
+    pub fn set(&self, value: u32) -> Result<(), String> {
+        let value = value.min(self.max_brightness);
+        let conn = zbus::blocking::Connection::system()
+            .map_err(|e| format!("D-Bus system connection: {e}"))?;
+        conn.call_method(
+            Some("org.freedesktop.login1"),
+            "/org/freedesktop/login1/session/auto",
+            Some("org.freedesktop.login1.Session"),
+            "SetBrightness",
+            &("backlight", self.name.as_str(), value),
+        )
+        .map_err(|e| format!("logind SetBrightness: {e}"))?;
+        info!(device = %self.name, value, "Set screen brightness");
+        Ok(())
+    }
+
+    /// Set the brightness on a background thread, logging a failure
+    pub fn set_in_background(&self, value: u32) -> std::thread::JoinHandle<()> {
+        let backlight = self.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = backlight.set(value) {
+                warn!(device = %backlight.name, error = %e, "Failed to set screen brightness");
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn firmware_backlights_are_preferred() {
+        let mut kinds = ["raw", "unknown", "firmware", "platform"];
+        kinds.sort_by_key(|kind| type_rank(kind));
+        assert_eq!(kinds, ["firmware", "platform", "raw", "unknown"]);
+    }
+}
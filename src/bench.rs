@@ -0,0 +1,612 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Pipeline benchmark
+//!
+//! `camera bench` times the parts of the pipeline that decide whether a
+//! machine keeps up: delivery of live camera frames, GPU conversion of each
+//! pixel format the cameras produce, video encoding at common resolutions
+//! and the stages of the HDR+ burst pipeline. Apart from the camera, every
+//! measurement runs on the same synthetic input everywhere, so reports from
+//! different machines compare.
+//!
+//! The report is Markdown on stdout, ready to paste into a performance
+//! issue; progress goes to stderr.
+
+use camera::backends::camera::CameraBackend;
+use camera::backends::camera::libcamera::{LibcameraBackend, create_pipeline};
+use camera::backends::camera::types::{CameraFrame, FrameData, PixelFormat};
+use camera::gpu::wgpu;
+use camera::media::encoders::video::{
+    EncoderInfo, EncoderLimit, VideoQuality, create_encoder_from_info, enumerate_video_encoders,
+};
+use camera::pipelines::photo::burst_mode::{BurstModeConfig, ProgressCallback, process_burst_mode};
+use camera::pipelines::video::recorder::read_clock_boottime_ns;
+use camera::shaders::{GpuFrameInput, ProcessingQuality, get_gpu_convert_pipeline};
+use futures::StreamExt;
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+/// Frame size the conversions are timed at
+const CONVERT_SIZE: (u32, u32) = (1920, 1080);
+
+/// Conversions timed per pixel format, after one untimed to build pipelines
+const CONVERT_ITERATIONS: u32 = 30;
+
+/// Resolutions each encoder is timed at
+const ENCODE_SIZES: [(u32, u32); 3] = [(1280, 720), (1920, 1080), (3840, 2160)];
+
+/// Frames encoded per resolution
+const ENCODE_FRAMES: u32 = 90;
+
+/// Longest an encoder may take for [`ENCODE_FRAMES`]
+const ENCODE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Size and length of the synthetic burst: 8 MP, the default burst
+const BURST_SIZE: (u32, u32) = (3264, 2448);
+const BURST_FRAMES: usize = 8;
+
+/// Auto-exposure settling time before capture latency is measured
+const CAPTURE_WARMUP: Duration = Duration::from_millis(500);
+
+/// Burst stages as the pipeline reports them: each ends when progress
+/// reaches its mark (see `process_burst_mode`). Tiled bursts align and merge
+/// tile by tile, so for them the two split by progress rather than by work.
+const BURST_STAGES: [(&str, f32); 4] = [
+    ("Setup and reference frame", 0.10),
+    ("Alignment", 0.60),
+    ("Merge", 0.80),
+    ("Demosaic and tone mapping", 1.0),
+];
+
+/// Options for `camera bench`
+pub struct BenchOptions {
+    pub camera_index: usize,
+    /// How long to measure capture latency for
+    pub seconds: u64,
+    /// Skip the camera, for machines without one
+    pub no_camera: bool,
+}
+
+/// Frame delivery from the live camera
+struct CaptureResult {
+    camera: String,
+    format: String,
+    frames: usize,
+    fps: f64,
+    /// Whether latencies start at the sensor timestamp; otherwise they start
+    /// when the capture thread received the frame
+    from_sensor: bool,
+    median: Duration,
+    p95: Duration,
+}
+
+/// One pixel format through the GPU converter
+struct ConversionResult {
+    format: &'static str,
+    per_frame: Result<Duration, String>,
+}
+
+/// One encoder at each of [`ENCODE_SIZES`]
+struct EncoderResult {
+    name: String,
+    fps: Vec<Result<f64, String>>,
+}
+
+/// The burst pipeline on the synthetic burst
+struct BurstResult {
+    stages: Vec<(&'static str, Duration)>,
+    total: Duration,
+}
+
+#[derive(Default)]
+struct Report {
+    system: Vec<(&'static str, String)>,
+    capture: Option<Result<CaptureResult, String>>,
+    conversions: Vec<ConversionResult>,
+    encoders: Vec<EncoderResult>,
+    burst: Option<Result<BurstResult, String>>,
+}
+
+/// Run the benchmark and print its report
+pub fn run(options: BenchOptions) -> Result<(), Box<dyn std::error::Error>> {
+    gst::init()?;
+    let rt = tokio::runtime::Runtime::new()?;
+    let mut report = Report {
+        system: system_info(&rt),
+        ..Default::default()
+    };
+
+    if !options.no_camera {
+        eprintln!("Measuring capture latency...");
+        report.capture = Some(measure_capture(&rt, options.camera_index, options.seconds));
+    }
+
+    eprintln!("Measuring format conversion...");
+    report.conversions = rt.block_on(measure_conversions());
+
+    let encoders = benchmark_encoders();
+    for info in &encoders {
+        eprintln!("Measuring {}...", info.display_name);
+        report.encoders.push(EncoderResult {
+            name: info.display_name.clone(),
+            fps: ENCODE_SIZES
+                .iter()
+                .map(|&(width, height)| encode_fps(info, width, height))
+                .collect(),
+        });
+    }
+
+    eprintln!("Measuring the burst pipeline...");
+    report.burst = Some(rt.block_on(measure_burst()));
+
+    print!("{}", report.to_markdown());
+    Ok(())
+}
+
+/// Machine details that explain the numbers
+fn system_info(rt: &tokio::runtime::Runtime) -> Vec<(&'static str, String)> {
+    let mut info = vec![("Version", env!("CARGO_PKG_VERSION").to_string())];
+    if let Ok(output) = Command::new("uname").arg("-r").output()
+        && let Ok(kernel) = String::from_utf8(output.stdout)
+    {
+        info.push(("Kernel", kernel.trim().to_string()));
+    }
+    if let Ok(cpuinfo) = std::fs::read_to_string("/proc/cpuinfo")
+        && let Some(model) = cpuinfo
+            .lines()
+            .find_map(|line| line.strip_prefix("model name"))
+            .and_then(|rest| rest.split_once(':'))
+    {
+        info.push(("CPU", model.1.trim().to_string()));
+    }
+    if let Ok(threads) = std::thread::available_parallelism() {
+        info.push(("Threads", threads.to_string()));
+    }
+    match rt.block_on(camera::gpu::get_shared_gpu()) {
+        Ok(gpu) => info.push((
+            "GPU",
+            format!("{} ({:?})", gpu.info.adapter_name, gpu.info.backend),
+        )),
+        Err(e) => info.push(("GPU", format!("unavailable ({e})"))),
+    }
+    info.push(("GStreamer", gst::version_string().to_string()));
+    info
+}
+
+/// Time from capture to delivery for the live frames of a camera
+fn measure_capture(
+    rt: &tokio::runtime::Runtime,
+    camera_index: usize,
+    seconds: u64,
+) -> Result<CaptureResult, String> {
+    let backend = LibcameraBackend::new();
+    let cameras = backend.enumerate_cameras();
+    let camera = cameras
+        .get(camera_index)
+        .ok_or_else(|| format!("No camera at index {camera_index}"))?;
+    let formats = backend.get_formats(camera, true);
+    if formats.is_empty() {
+        return Err("No formats available for camera".to_string());
+    }
+    let format = crate::cli::select_video_format(&formats);
+    let (_handle, mut receiver) = create_pipeline(camera, &format)?;
+
+    let warmup_end = Instant::now() + CAPTURE_WARMUP;
+    let end = warmup_end + Duration::from_secs(seconds.max(1));
+    let mut latencies = Vec::new();
+    let mut from_sensor = true;
+    let mut first = None;
+    while let Some(remaining) = end.checked_duration_since(Instant::now()) {
+        let Ok(Some(frame)) = rt.block_on(tokio::time::timeout(remaining, receiver.next())) else {
+            break;
+        };
+        let received = Instant::now();
+        if received < warmup_end {
+            continue;
+        }
+        first.get_or_insert(received);
+        let latency = match frame.sensor_timestamp_ns {
+            Some(sensor) => Duration::from_nanos(read_clock_boottime_ns().saturating_sub(sensor)),
+            None => {
+                from_sensor = false;
+                received.duration_since(frame.captured_at)
+            }
+        };
+        latencies.push(latency);
+    }
+
+    let first = first.ok_or("The camera delivered no frames")?;
+    let elapsed = first.elapsed().as_secs_f64();
+    latencies.sort();
+    Ok(CaptureResult {
+        camera: camera.name.clone(),
+        format: format.to_string(),
+        frames: latencies.len(),
+        fps: if elapsed > 0.0 {
+            (latencies.len() - 1) as f64 / elapsed
+        } else {
+            0.0
+        },
+        from_sensor,
+        median: percentile(&latencies, 0.5),
+        p95: percentile(&latencies, 0.95),
+    })
+}
+
+/// The `p` quantile of sorted `values`
+fn percentile(values: &[Duration], p: f64) -> Duration {
+    if values.is_empty() {
+        return Duration::ZERO;
+    }
+    values[((values.len() - 1) as f64 * p).round() as usize]
+}
+
+/// Pixel formats cameras deliver, with the row stride each is timed at.
+/// Bayer comes unpacked and CSI-2 packed, which take different paths.
+fn conversion_formats(width: u32) -> [(&'static str, PixelFormat, u32); 13] {
+    [
+        ("NV12", PixelFormat::NV12, width),
+        ("I420", PixelFormat::I420, width),
+        ("YUYV", PixelFormat::YUYV, width * 2),
+        ("UYVY", PixelFormat::UYVY, width * 2),
+        ("P010", PixelFormat::P010, width * 2),
+        ("Y210", PixelFormat::Y210, width * 4),
+        ("Gray8", PixelFormat::Gray8, width),
+        ("RGB24", PixelFormat::RGB24, width * 3),
+        ("BGRA", PixelFormat::BGRA, width * 4),
+        ("RGBx", PixelFormat::RGBx, width * 4),
+        ("BGRx", PixelFormat::BGRx, width * 4),
+        ("Bayer RGGB 8-bit", PixelFormat::BayerRGGB, width),
+        (
+            "Bayer RGGB 10-bit CSI-2",
+            PixelFormat::BayerRGGB,
+            width * 5 / 4,
+        ),
+    ]
+}
+
+/// Time each pixel format through the preview conversion, upload included
+async fn measure_conversions() -> Vec<ConversionResult> {
+    let (width, height) = CONVERT_SIZE;
+    let mut guard = match get_gpu_convert_pipeline().await {
+        Ok(guard) => guard,
+        Err(e) => {
+            return conversion_formats(width)
+                .iter()
+                .map(|&(format, ..)| ConversionResult {
+                    format,
+                    per_frame: Err(e.clone()),
+                })
+                .collect();
+        }
+    };
+    let Some(pipeline) = guard.as_mut() else {
+        return Vec::new();
+    };
+
+    // Mid-grey: YUV chroma at 128 keeps it neutral, and every format takes
+    // the same path whatever the content
+    let plane = vec![128u8; (width * 4 * height) as usize];
+    conversion_formats(width)
+        .into_iter()
+        .map(|(name, format, stride)| {
+            let chroma = matches!(
+                format,
+                PixelFormat::NV12 | PixelFormat::I420 | PixelFormat::P010
+            );
+            let (uv_stride, v_stride) = match format {
+                PixelFormat::NV12 => (width, 0),
+                PixelFormat::P010 => (width * 2, 0),
+                PixelFormat::I420 => (width / 2, width / 2),
+                _ => (0, 0),
+            };
+            let input = GpuFrameInput {
+                width,
+                height,
+                format,
+                y_data: &plane[..(stride * height) as usize],
+                y_stride: stride,
+                uv_data: chroma.then(|| &plane[..(uv_stride * height / 2) as usize]),
+                uv_stride,
+                v_data: (format == PixelFormat::I420)
+                    .then(|| &plane[..(v_stride * height / 2) as usize]),
+                v_stride,
+                colour_gains: None,
+                colour_correction_matrix: None,
+                black_level: None,
+                quality: ProcessingQuality::Preview,
+            };
+            let per_frame = (|| -> Result<Duration, String> {
+                pipeline.convert(&input)?;
+                let _ = pipeline.device().poll(wgpu::PollType::wait_indefinitely());
+                let start = Instant::now();
+                for _ in 0..CONVERT_ITERATIONS {
+                    pipeline.convert(&input)?;
+                }
+                let _ = pipeline.device().poll(wgpu::PollType::wait_indefinitely());
+                Ok(start.elapsed() / CONVERT_ITERATIONS)
+            })();
+            ConversionResult {
+                format: name,
+                per_frame,
+            }
+        })
+        .collect()
+}
+
+/// The encoders recording would pick from: the first hardware and the
+/// first software encoder of each codec
+fn benchmark_encoders() -> Vec<EncoderInfo> {
+    let mut picked: Vec<EncoderInfo> = Vec::new();
+    for info in enumerate_video_encoders() {
+        if !picked
+            .iter()
+            .any(|p| p.codec == info.codec && p.is_hardware == info.is_hardware)
+        {
+            picked.push(info);
+        }
+    }
+    picked
+}
+
+/// Frames per second `info` encodes a moving test pattern at
+fn encode_fps(info: &EncoderInfo, width: u32, height: u32) -> Result<f64, String> {
+    if let Some(EncoderLimit::TooLarge { .. }) = info.check_format(width, height, None) {
+        return Err("too large".to_string());
+    }
+    let encoder = create_encoder_from_info(info, VideoQuality::High, width, height)?.encoder;
+    let pipeline = gst::parse::launch(&format!(
+        "videotestsrc num-buffers={ENCODE_FRAMES} horizontal-speed=4 \
+         ! video/x-raw,format=NV12,width={width},height={height},framerate=30/1 \
+         ! videoconvert name=convert ! fakesink name=sink sync=false"
+    ))
+    .map_err(|e| format!("Failed to parse benchmark pipeline: {e}"))?
+    .downcast::<gst::Pipeline>()
+    .map_err(|_| "Benchmark pipeline is not a pipeline".to_string())?;
+
+    // Splice the configured encoder in between the converter and the sink
+    let convert = pipeline.by_name("convert").ok_or("Missing converter")?;
+    let sink = pipeline.by_name("sink").ok_or("Missing sink")?;
+    convert.unlink(&sink);
+    pipeline
+        .add(&encoder)
+        .map_err(|e| format!("Failed to add encoder: {e}"))?;
+    gst::Element::link_many([&convert, &encoder, &sink])
+        .map_err(|e| format!("Failed to link encoder: {e}"))?;
+
+    let start = Instant::now();
+    pipeline
+        .set_state(gst::State::Playing)
+        .map_err(|e| format!("Failed to start encoder: {e}"))?;
+    let result = match pipeline.bus().and_then(|bus| {
+        bus.timed_pop_filtered(
+            gst::ClockTime::from_nseconds(ENCODE_TIMEOUT.as_nanos() as u64),
+            &[gst::MessageType::Eos, gst::MessageType::Error],
+        )
+    }) {
+        Some(msg) => match msg.view() {
+            gst::MessageView::Error(err) => Err(err.error().to_string()),
+            _ => Ok(ENCODE_FRAMES as f64 / start.elapsed().as_secs_f64()),
+        },
+        None => Err("timed out".to_string()),
+    };
+    pipeline.set_state(gst::State::Null).ok();
+    result
+}
+
+/// Run a synthetic burst through the burst pipeline, timing its stages
+async fn measure_burst() -> Result<BurstResult, String> {
+    let frames = synthetic_burst();
+    let marks: Arc<Mutex<Vec<(f32, Duration)>>> = Arc::default();
+    let start = Instant::now();
+    let progress: ProgressCallback = {
+        let marks = Arc::clone(&marks);
+        Arc::new(move |value: f32| {
+            marks
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push((value, start.elapsed()));
+        })
+    };
+    process_burst_mode(
+        frames,
+        BurstModeConfig::default(),
+        Some(progress),
+        CancellationToken::new(),
+    )
+    .await?;
+    let total = start.elapsed();
+    let marks = marks.lock().unwrap_or_else(|e| e.into_inner());
+    Ok(BurstResult {
+        stages: split_stages(&marks),
+        total,
+    })
+}
+
+/// Stage durations from the pipeline's `(progress, elapsed)` reports
+fn split_stages(marks: &[(f32, Duration)]) -> Vec<(&'static str, Duration)> {
+    let mut stages = Vec::new();
+    let mut stage_start = Duration::ZERO;
+    for (name, end) in BURST_STAGES {
+        let Some(&(_, at)) = marks.iter().find(|(value, _)| *value >= end) else {
+            break;
+        };
+        stages.push((name, at.saturating_sub(stage_start)));
+        stage_start = at;
+    }
+    stages
+}
+
+/// A textured RGBA burst with a little camera shake and sensor noise, so
+/// alignment and merging have real work to do
+fn synthetic_burst() -> Vec<Arc<CameraFrame>> {
+    let (width, height) = BURST_SIZE;
+    let mut seed = 0x2545_f491_u32;
+    (0..BURST_FRAMES)
+        .map(|i| {
+            let (dx, dy) = (i as u32 % 3, i as u32 / 3);
+            let mut data = Vec::with_capacity((width * height * 4) as usize);
+            for y in 0..height {
+                for x in 0..width {
+                    // xorshift noise on top of a checkerboard and gradients
+                    seed ^= seed << 13;
+                    seed ^= seed >> 17;
+                    seed ^= seed << 5;
+                    let (sx, sy) = (x + dx, y + dy);
+                    let check = if (sx / 32 + sy / 32) % 2 == 0 {
+                        60
+                    } else {
+                        180
+                    };
+                    let noise = (seed & 15) as u8;
+                    data.extend_from_slice(&[
+                        check + noise,
+                        (sx * 255 / width) as u8 / 2 + noise,
+                        (sy * 255 / height) as u8 / 2 + noise,
+                        255,
+                    ]);
+                }
+            }
+            Arc::new(CameraFrame {
+                width,
+                height,
+                data: FrameData::Copied(data.into()),
+                format: PixelFormat::RGBA,
+                stride: width * 4,
+                yuv_planes: None,
+                captured_at: Instant::now(),
+                sensor_timestamp_ns: None,
+                libcamera_metadata: None,
+            })
+        })
+        .collect()
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.2} ms", duration.as_secs_f64() * 1000.0)
+}
+
+impl Report {
+    fn to_markdown(&self) -> String {
+        let mut md = String::from("# Camera Benchmark\n\n## System\n\n");
+        for (label, value) in &self.system {
+            md.push_str(&format!("**{label}:** {value}\n"));
+        }
+
+        if let Some(capture) = &self.capture {
+            md.push_str("\n## Capture Latency\n\n");
+            match capture {
+                Ok(c) => {
+                    let from = if c.from_sensor {
+                        "sensor timestamp"
+                    } else {
+                        "capture thread"
+                    };
+                    md.push_str(&format!(
+                        "**Camera:** {} ({})\n\n\
+                         | Frames | FPS | Median | 95th percentile |\n\
+                         |---|---|---|---|\n\
+                         | {} | {:.1} | {} | {} |\n\n\
+                         Measured from the {from} to delivery.\n",
+                        c.camera,
+                        c.format,
+                        c.frames,
+                        c.fps,
+                        millis(c.median),
+                        millis(c.p95),
+                    ));
+                }
+                Err(e) => md.push_str(&format!("Skipped: {e}\n")),
+            }
+        }
+
+        let (width, height) = CONVERT_SIZE;
+        md.push_str(&format!(
+            "\n## Format Conversion ({width}x{height}, GPU)\n\n\
+             | Format | Per frame | Megapixels/s |\n|---|---|---|\n"
+        ));
+        for c in &self.conversions {
+            match &c.per_frame {
+                Ok(per_frame) => md.push_str(&format!(
+                    "| {} | {} | {:.0} |\n",
+                    c.format,
+                    millis(*per_frame),
+                    (width * height) as f64 / per_frame.as_secs_f64() / 1e6,
+                )),
+                Err(e) => md.push_str(&format!("| {} | failed: {e} | |\n", c.format)),
+            }
+        }
+
+        md.push_str("\n## Video Encoders (FPS)\n\n| Encoder |");
+        for (width, height) in ENCODE_SIZES {
+            md.push_str(&format!(" {width}x{height} |"));
+        }
+        md.push_str(&format!("\n|---|{}\n", "---|".repeat(ENCODE_SIZES.len())));
+        if self.encoders.is_empty() {
+            md.push_str("| No video encoders found |\n");
+        }
+        for e in &self.encoders {
+            md.push_str(&format!("| {} |", e.name));
+            for fps in &e.fps {
+                match fps {
+                    Ok(fps) => md.push_str(&format!(" {fps:.1} |")),
+                    Err(e) => md.push_str(&format!(" {e} |")),
+                }
+            }
+            md.push('\n');
+        }
+
+        if let Some(burst) = &self.burst {
+            let (width, height) = BURST_SIZE;
+            md.push_str(&format!(
+                "\n## Burst Pipeline ({BURST_FRAMES} frames, {width}x{height})\n\n"
+            ));
+            match burst {
+                Ok(b) => {
+                    md.push_str("| Stage | Time |\n|---|---|\n");
+                    for (stage, duration) in &b.stages {
+                        md.push_str(&format!("| {stage} | {} |\n", millis(*duration)));
+                    }
+                    md.push_str(&format!("| **Total** | {} |\n", millis(b.total)));
+                }
+                Err(e) => md.push_str(&format!("Failed: {e}\n")),
+            }
+        }
+        md
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn burst_stages_end_where_progress_reaches_their_mark() {
+        let ms = Duration::from_millis;
+        // An RGBA burst: merge reports 0.85, past the merge mark
+        let marks = [
+            (0.0, ms(0)),
+            (0.05, ms(40)),
+            (0.10, ms(100)),
+            (0.35, ms(300)),
+            (0.60, ms(500)),
+            (0.85, ms(650)),
+            (1.0, ms(700)),
+        ];
+        let stages = split_stages(&marks);
+        let durations: Vec<_> = stages.iter().map(|(_, d)| *d).collect();
+        assert_eq!(durations, [ms(100), ms(400), ms(150), ms(50)]);
+
+        // A failed run only reports the stages it finished
+        assert_eq!(split_stages(&marks[..3]).len(), 1);
+
+        let sorted: Vec<_> = (1..=20).map(ms).collect();
+        assert_eq!(percentile(&sorted, 0.5), ms(11));
+        assert_eq!(percentile(&sorted, 0.95), ms(19));
+        assert_eq!(percentile(&[], 0.5), Duration::ZERO);
+    }
+}
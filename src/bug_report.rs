@@ -642,6 +642,10 @@ impl BugReportGenerator {
             "- **Dropped Frames:** {}\n",
             insights.dropped_frames
         ));
+        info.push_str(&format!(
+            "- **Duplicated Frames:** {}\n",
+            insights.duplicated_frames
+        ));
         let frame_mb = insights.frame_size_decoded as f64 / (1024.0 * 1024.0);
         info.push_str(&format!("- **Frame Size:** {:.2} MB\n", frame_mb));
         if insights.cpu_decode_time_us > 0 {
@@ -6,10 +6,18 @@
 //! - Listing available cameras
 //! - Taking photos
 //! - Recording videos
+//! - Capturing HDR+ bursts
+//! - Recording timelapses
+//! - Extracting stills from recorded videos
+//! - Stabilizing recorded videos
+//!
+//! `camera bench` and `camera selftest` live in their own modules (see
+//! `bench` and `selftest`).
 
 use camera::backends::camera::CameraBackend;
 use camera::backends::camera::libcamera::{LibcameraBackend, create_pipeline};
 use camera::backends::camera::types::{CameraFormat, CameraFrame};
+use camera::config::VideoStabilization;
 use camera::pipelines::photo::PhotoPipeline;
 use camera::pipelines::video::{
     AppsrcRecorderConfig, EncoderConfig, RecorderConfig, VideoRecorder,
@@ -296,6 +304,29 @@ pub fn record_video(
     let encoder_config = EncoderConfig::default();
     let rotation = camera.rotation;
 
+    // Warn up front if the planned recording won't fit on the target disk
+    let per_minute = camera::media::encoders::video::estimate_bytes_per_minute(
+        encoder_config
+            .bitrate_override_kbps
+            .unwrap_or_else(|| encoder_config.video_quality.bitrate_kbps(width, height)),
+        enable_audio.then(|| encoder_config.audio_quality.bitrate_bps() as u32),
+    );
+    let expected_size = per_minute * duration / 60;
+    println!(
+        "Estimated size: {}",
+        camera::storage::format_size(expected_size)
+    );
+    if let Some(free) = camera::storage::available_space(&output_path)
+        && free < expected_size
+    {
+        let capacity = camera::media::encoders::video::recording_capacity(free, per_minute);
+        println!(
+            "Warning: only {} free, the disk will fill after about {} seconds",
+            camera::storage::format_size(free),
+            capacity.as_secs()
+        );
+    }
+
     let rt = tokio::runtime::Runtime::new()?;
     let recorder = rt.block_on(async {
         let rt_handle = tokio::runtime::Handle::current();
@@ -312,19 +343,29 @@ pub fn record_video(
                         enable_audio,
                         audio_device: None,
                         audio_source_rate_hz: 0,
+                        audio_processing: Default::default(),
+                        system_audio: false,
                         encoder_info: None,
                         rotation,
                         mirror_horizontal: false,
+                        orientation: Default::default(),
                         audio_levels: Default::default(),
+                        audio_sync_offset: Default::default(),
                     },
                     pixel_format,
                     live_filter_code: std::sync::Arc::new(std::sync::atomic::AtomicU32::new(0)),
+                    pip: None,
+                    stabilization: Default::default(),
+                    denoise: Default::default(),
+                    timecode: false,
+                    timestamp_overlay: false,
+                    desqueeze: 1.0,
                 },
                 frame_rx,
             )
         })
         .await
-        .unwrap_or_else(|e| Err(format!("Task join error: {}", e)))
+        .unwrap_or_else(|e| Err(format!("Task join error: {}", e).into()))
     })?;
 
     // Start recording
@@ -369,6 +410,285 @@ pub fn record_video(
     Ok(())
 }
 
+/// Capture a burst from the specified camera and merge it with the HDR+ pipeline
+pub fn capture_burst(
+    camera_index: usize,
+    frame_count: usize,
+    output: Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use camera::pipelines::photo::burst_mode::{
+        BurstModeConfig, SaveOutputParams, process_burst_mode as run_burst_mode, save_output,
+    };
+    use camera::pipelines::photo::{CameraMetadata, EncodingFormat};
+    use tokio_util::sync::CancellationToken;
+
+    if !(2..=50).contains(&frame_count) {
+        return Err(format!("Frame count must be between 2 and 50 (got {frame_count})").into());
+    }
+
+    let output_dir = output.unwrap_or_else(get_default_photo_dir);
+    if output_dir.exists() && !output_dir.is_dir() {
+        return Err(format!("Output path is not a directory: {}", output_dir.display()).into());
+    }
+    std::fs::create_dir_all(&output_dir)?;
+
+    // Initialize GStreamer
+    gstreamer::init()?;
+
+    let backend = LibcameraBackend::new();
+    let cameras = backend.enumerate_cameras();
+    if cameras.is_empty() {
+        return Err("No cameras found".into());
+    }
+
+    if camera_index >= cameras.len() {
+        return Err(format!(
+            "Camera index {} out of range (0-{})",
+            camera_index,
+            cameras.len() - 1
+        )
+        .into());
+    }
+
+    let camera = &cameras[camera_index];
+    println!("Using camera: {}", camera.name);
+
+    // Burst frames come from the preview stream, so pick a video-style format
+    // that sustains a real framerate rather than the slowest full-res mode.
+    let formats = backend.get_formats(camera, true);
+    if formats.is_empty() {
+        return Err("No formats available for camera".into());
+    }
+
+    let format = select_video_format(&formats);
+    println!("Burst format: {}x{}", format.width, format.height);
+
+    let (_handle, mut receiver) =
+        create_pipeline(camera, &format).map_err(|e| -> Box<dyn std::error::Error> { e.into() })?;
+
+    // Let auto-exposure settle before collecting frames
+    let warmup_end = Instant::now() + Duration::from_millis(500);
+    while Instant::now() < warmup_end {
+        if receiver.try_recv().is_err() {
+            std::thread::sleep(Duration::from_millis(16));
+        }
+    }
+
+    println!("Capturing {} frames...", frame_count);
+    let start = Instant::now();
+    let timeout = Duration::from_secs(10);
+    let mut frames: Vec<Arc<CameraFrame>> = Vec::with_capacity(frame_count);
+    while frames.len() < frame_count {
+        if start.elapsed() > timeout {
+            return Err(format!(
+                "Timeout waiting for burst frames ({} of {} captured)",
+                frames.len(),
+                frame_count
+            )
+            .into());
+        }
+        match receiver.try_recv() {
+            // Copy out of the mapped buffer: the capture thread recycles it
+            // while the rest of the burst is still being collected.
+            Ok(f) => frames.push(Arc::new(f.to_copied())),
+            _ => std::thread::sleep(Duration::from_millis(4)),
+        }
+    }
+
+    println!("Processing...");
+    let config = BurstModeConfig {
+        frame_count,
+        rotation: camera.rotation,
+        ..Default::default()
+    };
+
+    let camera_metadata = CameraMetadata {
+        camera_name: Some(camera.name.clone()),
+        camera_driver: None,
+        exposure_time: None,
+        iso: None,
+        gain: None,
+        orientation: Default::default(),
+    };
+
+    let rt = tokio::runtime::Runtime::new()?;
+    let output_path = rt.block_on(async {
+        let start = Instant::now();
+        let merged = run_burst_mode(frames, config, None, CancellationToken::new()).await?;
+        println!("Processing time: {:.2}s", start.elapsed().as_secs_f64());
+
+        save_output(
+            &merged,
+            SaveOutputParams {
+                output_dir,
+                crop_rect: None,
+                encoding_format: EncodingFormat::Jpeg,
+                camera_metadata,
+                filter: None,
+                rotation: camera.rotation,
+                filename_suffix: Some("_HDR+"),
+                mirror_horizontal: false,
+            },
+        )
+        .await
+    })?;
+
+    println!("Photo saved: {}", output_path.display());
+    Ok(())
+}
+
+/// Record a timelapse using the specified camera
+pub fn record_timelapse(
+    camera_index: usize,
+    interval_secs: f64,
+    duration: u64,
+    output: Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !interval_secs.is_finite() || interval_secs <= 0.0 {
+        return Err("Interval must be a positive number of seconds".into());
+    }
+    let interval = Duration::from_secs_f64(interval_secs);
+
+    // Refuse to overwrite an existing file when the user has specified one.
+    if let Some(path) = output.as_ref()
+        && path.exists()
+    {
+        return Err(format!(
+            "Output file already exists: {} (refusing to overwrite)",
+            path.display()
+        )
+        .into());
+    }
+
+    // Initialize GStreamer
+    gstreamer::init()?;
+
+    let backend = LibcameraBackend::new();
+    let cameras = backend.enumerate_cameras();
+    if cameras.is_empty() {
+        return Err("No cameras found".into());
+    }
+
+    if camera_index >= cameras.len() {
+        return Err(format!(
+            "Camera index {} out of range (0-{})",
+            camera_index,
+            cameras.len() - 1
+        )
+        .into());
+    }
+
+    let camera = &cameras[camera_index];
+    println!("Using camera: {}", camera.name);
+
+    let formats = backend.get_formats(camera, true);
+    if formats.is_empty() {
+        return Err("No formats available for camera".into());
+    }
+
+    let format = select_video_format(&formats);
+    println!("Timelapse format: {}x{}", format.width, format.height);
+
+    // Determine output path (the encoder picks the final extension)
+    let output_path = if let Some(path) = output {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        path
+    } else {
+        let dir = get_default_video_dir();
+        std::fs::create_dir_all(&dir)?;
+        let timestamp = Local::now().format("%Y%m%d_%H%M%S_%3f");
+        dir.join(format!("timelapse_{}.mp4", timestamp))
+    };
+
+    let expected_frames = (duration as f64 / interval_secs).floor() as u64 + 1;
+    println!("Interval: {:.2} seconds", interval_secs);
+    println!(
+        "Duration: {} seconds (~{} frames)",
+        duration, expected_frames
+    );
+
+    let (_handle, mut receiver) =
+        create_pipeline(camera, &format).map_err(|e| -> Box<dyn std::error::Error> { e.into() })?;
+
+    let rt = tokio::runtime::Runtime::new()?;
+    let (frame_tx, frame_rx) = tokio::sync::mpsc::unbounded_channel();
+    let bitrate_kbps =
+        camera::constants::BitratePreset::default().bitrate_kbps(format.width, format.height);
+    let encoder = rt.spawn(camera::pipelines::video::timelapse::run_timelapse_encoder(
+        frame_rx,
+        output_path,
+        None,
+        Some(bitrate_kbps),
+        Arc::new(std::sync::atomic::AtomicU32::new(0)),
+        camera.rotation,
+        false,
+    ));
+
+    // Set up Ctrl+C handler
+    let stop_flag = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let stop_flag_clone = stop_flag.clone();
+    ctrlc::set_handler(move || {
+        stop_flag_clone.store(true, std::sync::atomic::Ordering::SeqCst);
+    })?;
+
+    println!();
+    println!("Capturing... (press Ctrl+C to stop early)");
+
+    // Keep draining the preview stream so the latest frame is always fresh,
+    // and hand one to the encoder every interval.
+    let start = Instant::now();
+    let target_duration = Duration::from_secs(duration);
+    let mut latest: Option<CameraFrame> = None;
+    let mut next_shot = start;
+    let mut shots: u64 = 0;
+
+    while start.elapsed() <= target_duration {
+        if stop_flag.load(std::sync::atomic::Ordering::SeqCst) {
+            println!();
+            println!("Stopping early...");
+            break;
+        }
+
+        while let Ok(f) = receiver.try_recv() {
+            latest = Some(f);
+        }
+
+        if Instant::now() >= next_shot
+            && let Some(frame) = latest.take()
+        {
+            if frame_tx.send(Arc::new(frame.to_copied())).is_err() {
+                return Err("Timelapse encoder stopped unexpectedly".into());
+            }
+            shots += 1;
+            next_shot += interval;
+
+            let elapsed = start.elapsed().as_secs();
+            print!(
+                "\rFrames: {}  Elapsed: {:02}:{:02}",
+                shots,
+                elapsed / 60,
+                elapsed % 60
+            );
+            std::io::Write::flush(&mut std::io::stdout())?;
+        }
+
+        std::thread::sleep(Duration::from_millis(16));
+    }
+    println!();
+
+    // Closing the channel makes the encoder finalise the file
+    drop(frame_tx);
+    println!("Finalising video...");
+    let final_path = rt
+        .block_on(encoder)
+        .map_err(|e| format!("Encoder task failed: {}", e))??;
+    println!("Timelapse saved: {}", final_path);
+
+    Ok(())
+}
+
 /// Select the best format for photo capture (highest resolution)
 fn select_photo_format(formats: &[CameraFormat]) -> CameraFormat {
     formats
@@ -379,7 +699,7 @@ fn select_photo_format(formats: &[CameraFormat]) -> CameraFormat {
 }
 
 /// Select the best format for video recording (balanced resolution and framerate)
-fn select_video_format(formats: &[CameraFormat]) -> CameraFormat {
+pub(crate) fn select_video_format(formats: &[CameraFormat]) -> CameraFormat {
     // Prefer 1080p at 30fps, otherwise highest resolution with reasonable framerate
     let target_height = 1080;
     let target_fps: u32 = 30;
@@ -434,6 +754,7 @@ pub fn process_burst_mode(
         BurstModeConfig, SaveOutputParams, process_burst_mode as run_burst_mode, save_output,
     };
     use camera::pipelines::photo::{CameraMetadata, EncodingFormat};
+    use tokio_util::sync::CancellationToken;
 
     // Collect all image paths from input (can be files or directories)
     let image_paths = collect_image_paths(&input)?;
@@ -487,7 +808,7 @@ pub fn process_burst_mode(
     let rt = tokio::runtime::Runtime::new()?;
     let result = rt.block_on(async {
         let start = std::time::Instant::now();
-        let result = run_burst_mode(frames, config.clone(), None).await?;
+        let result = run_burst_mode(frames, config.clone(), None, CancellationToken::new()).await?;
         let duration = start.elapsed();
         println!("Processing time: {:.2}s", duration.as_secs_f64());
         println!("Output size: {}x{}", result.width, result.height);
@@ -501,6 +822,7 @@ pub fn process_burst_mode(
         exposure_time: None,
         iso: None,
         gain: None,
+        orientation: Default::default(),
     };
 
     let output_path = rt.block_on(async {
@@ -526,6 +848,90 @@ pub fn process_burst_mode(
     Ok(())
 }
 
+/// Extract stills from a recorded video into the photo directory
+pub fn extract_stills(
+    input: PathBuf,
+    interval: f64,
+    scenes: bool,
+    output: Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use camera::pipelines::photo::EncodingFormat;
+    use camera::pipelines::video::{
+        ExtractionControl, StillExtractionConfig, StillSelection, extract_stills,
+    };
+
+    if !input.is_file() {
+        return Err(format!("Input video not found: {}", input.display()).into());
+    }
+    let selection = if scenes {
+        StillSelection::SceneChange
+    } else if interval.is_finite() && interval > 0.0 {
+        StillSelection::Interval(Duration::from_secs_f64(interval))
+    } else {
+        return Err("Interval must be a positive number of seconds".into());
+    };
+    let output_dir = output.unwrap_or_else(get_default_photo_dir);
+
+    gstreamer::init()?;
+
+    println!("Extracting stills from: {}", input.display());
+    println!("Output directory: {}", output_dir.display());
+
+    let config = StillExtractionConfig {
+        selection,
+        output_dir,
+        encoding_format: EncodingFormat::Jpeg,
+    };
+    let rt = tokio::runtime::Runtime::new()?;
+    let saved = rt.block_on(extract_stills(input, config, ExtractionControl::default()))?;
+
+    for path in &saved {
+        println!("  {}", path.display());
+    }
+    println!("Saved {} stills", saved.len());
+
+    Ok(())
+}
+
+/// Write a stabilized copy of a recorded video
+pub fn stabilize_video(
+    input: PathBuf,
+    strength: VideoStabilization,
+    output: Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use camera::pipelines::video::{
+        ExtractionControl, PostStabilizationConfig, VideoQuality, stabilize_video,
+    };
+
+    if !input.is_file() {
+        return Err(format!("Input video not found: {}", input.display()).into());
+    }
+    let output_dir = output
+        .or_else(|| input.parent().map(Path::to_path_buf))
+        .unwrap_or_else(get_default_video_dir);
+
+    gstreamer::init()?;
+
+    println!("Stabilizing: {}", input.display());
+    println!("Strength: {:?}", strength);
+    println!("Output directory: {}", output_dir.display());
+    println!("Processing...");
+
+    let config = PostStabilizationConfig {
+        strength,
+        output_dir,
+        video_quality: VideoQuality::High,
+    };
+    let start = Instant::now();
+    let rt = tokio::runtime::Runtime::new()?;
+    let path = rt.block_on(stabilize_video(input, config, ExtractionControl::default()))?;
+
+    println!("Processing time: {:.2}s", start.elapsed().as_secs_f64());
+    println!("Saved to: {}", path.display());
+
+    Ok(())
+}
+
 /// Collect all image paths from input (files or directories, recursive).
 fn collect_image_paths(input: &[PathBuf]) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
     fn visit_dir(dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::backends::camera::types::SensorRotation;
 use crate::constants::BitratePreset;
+use crate::media::whiteboard::BoardQuad;
+use crate::shaders::HdrTransfer;
 use cosmic::cosmic_config::{self, CosmicConfigEntry, cosmic_config_derive::CosmicConfigEntry};
 use cosmic::{Theme, theme};
 use serde::{Deserialize, Serialize};
@@ -92,6 +95,23 @@ impl BurstModeSetting {
     ];
 }
 
+/// What to do when the preview turns dark in Photo mode
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum NightMode {
+    /// Leave HDR+ to the HDR+ setting
+    Off,
+    /// Offer HDR+ while it is dark
+    #[default]
+    Suggest,
+    /// Take photos with HDR+ while it is dark, even with HDR+ off
+    Auto,
+}
+
+impl NightMode {
+    /// Get all options, in dropdown order
+    pub const ALL: [NightMode; 3] = [NightMode::Off, NightMode::Suggest, NightMode::Auto];
+}
+
 /// Audio encoder preference
 #[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub enum AudioEncoder {
@@ -115,6 +135,160 @@ impl AudioEncoder {
     pub const ALL: [AudioEncoder; 2] = [AudioEncoder::Opus, AudioEncoder::AAC];
 }
 
+/// Video container preference
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum VideoContainer {
+    /// Use the codec's default container (MP4, or WebM for AV1)
+    #[default]
+    Auto,
+    /// MP4 (H.264, HEVC, AV1)
+    Mp4,
+    /// Matroska (any codec)
+    Mkv,
+    /// WebM (AV1)
+    WebM,
+    /// QuickTime (H.264, HEVC)
+    Mov,
+}
+
+impl VideoContainer {
+    /// Get display name for this container
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            VideoContainer::Auto => "Automatic",
+            VideoContainer::Mp4 => "MP4",
+            VideoContainer::Mkv => "MKV",
+            VideoContainer::WebM => "WebM",
+            VideoContainer::Mov => "MOV",
+        }
+    }
+
+    /// The container to request from the encoder module (`None` = codec default)
+    pub fn container(&self) -> Option<crate::media::encoders::video::ContainerFormat> {
+        use crate::media::encoders::video::ContainerFormat;
+        match self {
+            VideoContainer::Auto => None,
+            VideoContainer::Mp4 => Some(ContainerFormat::MP4),
+            VideoContainer::Mkv => Some(ContainerFormat::Matroska),
+            VideoContainer::WebM => Some(ContainerFormat::WebM),
+            VideoContainer::Mov => Some(ContainerFormat::MOV),
+        }
+    }
+
+    /// Get all container options
+    pub const ALL: [VideoContainer; 5] = [
+        VideoContainer::Auto,
+        VideoContainer::Mp4,
+        VideoContainer::Mkv,
+        VideoContainer::WebM,
+        VideoContainer::Mov,
+    ];
+}
+
+/// Stabilization of recorded video. Stronger settings smooth out more of
+/// the camera's motion but crop more of the frame to make room for it.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum VideoStabilization {
+    /// Record the full frame as the camera sees it
+    #[default]
+    Off,
+    /// Steadies small hand shake
+    Low,
+    /// Steadies walking and handheld pans
+    Medium,
+    /// Holds the frame as still as the crop allows
+    High,
+}
+
+impl VideoStabilization {
+    /// Fraction of the frame cropped from each edge
+    pub fn crop_margin(&self) -> f32 {
+        match self {
+            VideoStabilization::Off => 0.0,
+            VideoStabilization::Low => 0.05,
+            VideoStabilization::Medium => 0.08,
+            VideoStabilization::High => 0.12,
+        }
+    }
+
+    /// Get all options, in dropdown order
+    pub const ALL: [VideoStabilization; 4] = [
+        VideoStabilization::Off,
+        VideoStabilization::Low,
+        VideoStabilization::Medium,
+        VideoStabilization::High,
+    ];
+}
+
+/// Noise reduction for recorded and streamed video. Stronger settings
+/// smooth more grain but soften fine detail and can trail fast motion.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum VideoDenoise {
+    /// Frames go out as the camera delivers them
+    #[default]
+    Off,
+    /// Takes the edge off sensor grain
+    Low,
+    /// Cleans up a dim room
+    Medium,
+    /// For very dark scenes that hold fairly still
+    High,
+}
+
+impl VideoDenoise {
+    /// Blend of the spatial pass and largest weight of the previous frame
+    pub fn blend(&self) -> (f32, f32) {
+        match self {
+            VideoDenoise::Off => (0.0, 0.0),
+            VideoDenoise::Low => (0.3, 0.5),
+            VideoDenoise::Medium => (0.5, 0.7),
+            VideoDenoise::High => (0.8, 0.85),
+        }
+    }
+
+    /// Get all options, in dropdown order
+    pub const ALL: [VideoDenoise; 4] = [
+        VideoDenoise::Off,
+        VideoDenoise::Low,
+        VideoDenoise::Medium,
+        VideoDenoise::High,
+    ];
+}
+
+/// Horizontal stretch that undoes an anamorphic lens adapter's squeeze
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum AnamorphicDesqueeze {
+    /// Show the picture as the camera delivers it
+    #[default]
+    Off,
+    /// 1.33x, the common adapter for 16:9 sensors
+    X1_33,
+    /// 1.5x
+    X1_5,
+    /// 2x, classic CinemaScope on a 4:3 frame
+    X2,
+}
+
+impl AnamorphicDesqueeze {
+    /// Horizontal scale factor (1.0 = none)
+    pub fn factor(&self) -> f32 {
+        match self {
+            AnamorphicDesqueeze::Off => 1.0,
+            AnamorphicDesqueeze::X1_33 => 1.33,
+            AnamorphicDesqueeze::X1_5 => 1.5,
+            AnamorphicDesqueeze::X2 => 2.0,
+        }
+    }
+
+    /// Get all options, in dropdown order
+    pub const ALL: [AnamorphicDesqueeze; 4] = [
+        AnamorphicDesqueeze::Off,
+        AnamorphicDesqueeze::X1_33,
+        AnamorphicDesqueeze::X1_5,
+        AnamorphicDesqueeze::X2,
+    ];
+}
+
 /// Timelapse interval setting
 #[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub enum TimelapseInterval {
@@ -218,6 +392,398 @@ impl CompositionGuide {
     ];
 }
 
+/// What the hardware volume keys do while the camera window is focused
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum VolumeKeyAction {
+    /// Either key takes a photo or starts / stops recording, like the
+    /// on-screen shutter
+    #[default]
+    Capture,
+    /// Volume up zooms in, volume down zooms out
+    Zoom,
+    /// Leave the keys to the system volume
+    Volume,
+}
+
+impl VolumeKeyAction {
+    /// Get all options, in dropdown order
+    pub const ALL: [VolumeKeyAction; 3] = [
+        VolumeKeyAction::Capture,
+        VolumeKeyAction::Zoom,
+        VolumeKeyAction::Volume,
+    ];
+}
+
+/// Color vision deficiency the preview is adapted to. Only the preview is
+/// changed: captures and the capture filters are left alone.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ColorVisionAssist {
+    #[default]
+    Off,
+    /// Daltonize for red-blindness: move the red-green differences a
+    /// protanope can't see onto green and blue
+    CorrectProtanopia,
+    /// Daltonize for green-blindness
+    CorrectDeuteranopia,
+    /// Daltonize for blue-blindness: move the blue-yellow differences onto
+    /// red and green
+    CorrectTritanopia,
+    /// Show the preview as someone with protanopia sees it
+    SimulateProtanopia,
+    /// Show the preview as someone with deuteranopia sees it
+    SimulateDeuteranopia,
+    /// Show the preview as someone with tritanopia sees it
+    SimulateTritanopia,
+}
+
+impl ColorVisionAssist {
+    /// Get all options, in dropdown order
+    pub const ALL: [ColorVisionAssist; 7] = [
+        ColorVisionAssist::Off,
+        ColorVisionAssist::CorrectProtanopia,
+        ColorVisionAssist::CorrectDeuteranopia,
+        ColorVisionAssist::CorrectTritanopia,
+        ColorVisionAssist::SimulateProtanopia,
+        ColorVisionAssist::SimulateDeuteranopia,
+        ColorVisionAssist::SimulateTritanopia,
+    ];
+}
+
+/// How the GPU resamples the camera frame to the preview's size. Only the
+/// preview is affected.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PreviewScaling {
+    /// Nearest texel: every sensor pixel stays a hard-edged block
+    Nearest,
+    /// One bilinear tap per pixel: cheapest smooth scaling, but a frame much
+    /// larger than the window shimmers as texels are skipped
+    #[default]
+    Bilinear,
+    /// Lanczos-2, widened when scaling down so skipped texels don't shimmer:
+    /// sharp and stable, at 36 taps per pixel
+    Lanczos,
+}
+
+impl PreviewScaling {
+    /// Get all options, in dropdown order
+    pub const ALL: [PreviewScaling; 3] = [
+        PreviewScaling::Nearest,
+        PreviewScaling::Bilinear,
+        PreviewScaling::Lanczos,
+    ];
+}
+
+/// How 10-bit camera frames are interpreted. Capture cards rarely tell
+/// whether their HDMI source is HDR, so it can be set by hand.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum HdrInput {
+    /// HDR when the driver reports a PQ transfer, else SDR
+    #[default]
+    Auto,
+    /// Always SDR
+    Sdr,
+    /// PQ (HDR10)
+    Pq,
+    /// Hybrid log-gamma (broadcast HDR)
+    Hlg,
+}
+
+impl HdrInput {
+    /// Get all options, in dropdown order
+    pub const ALL: [HdrInput; 4] = [HdrInput::Auto, HdrInput::Sdr, HdrInput::Pq, HdrInput::Hlg];
+
+    /// Transfer of a camera that signals `signalled`
+    pub fn resolve(self, signalled: HdrTransfer) -> HdrTransfer {
+        match self {
+            HdrInput::Auto => signalled,
+            HdrInput::Sdr => HdrTransfer::Sdr,
+            HdrInput::Pq => HdrTransfer::Pq,
+            HdrInput::Hlg => HdrTransfer::Hlg,
+        }
+    }
+}
+
+/// How photo-booth shots are arranged in the saved collage
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PhotoBoothLayout {
+    /// One column, like a booth print strip
+    #[default]
+    Strip,
+    /// Two columns (2×2 for four shots)
+    Grid,
+}
+
+impl PhotoBoothLayout {
+    /// Get all options, in dropdown order
+    pub const ALL: [PhotoBoothLayout; 2] = [PhotoBoothLayout::Strip, PhotoBoothLayout::Grid];
+}
+
+/// Output sharpening (unsharp mask) applied to saved photos
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct OutputSharpening {
+    /// Strength in percent of the detail added back; 0 turns sharpening off
+    pub amount: u8,
+    /// Blur radius in tenths of a pixel; 0 picks one from the photo resolution
+    pub radius_tenths: u8,
+    /// Detail smaller than this many 8-bit levels is left alone as noise
+    pub threshold: u8,
+}
+
+impl OutputSharpening {
+    /// Strongest amount offered by the settings slider
+    pub const MAX_AMOUNT: u8 = 200;
+}
+
+impl Default for OutputSharpening {
+    fn default() -> Self {
+        Self {
+            amount: 50,
+            radius_tenths: 0,
+            threshold: 3,
+        }
+    }
+}
+
+/// Remote that new captures are uploaded to
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum UploadProvider {
+    /// A WebDAV folder, such as Nextcloud's `remote.php/dav/files/<user>/…`
+    #[default]
+    WebDav,
+    /// A bucket on an S3-compatible object store
+    S3,
+}
+
+impl UploadProvider {
+    pub const ALL: [UploadProvider; 2] = [UploadProvider::WebDav, UploadProvider::S3];
+}
+
+/// Automatic upload of new captures. The password or secret key is kept in
+/// the desktop's secret service, not here.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct UploadSettings {
+    pub enabled: bool,
+    pub provider: UploadProvider,
+    /// WebDAV folder URL, or the S3 endpoint
+    pub url: String,
+    /// S3 bucket name (unused for WebDAV)
+    pub bucket: String,
+    /// S3 region (unused for WebDAV)
+    pub region: String,
+    /// WebDAV user name, or the S3 access key ID
+    pub username: String,
+}
+
+/// Hand-tuned HDR+ parameters, used in place of the ones picked from the
+/// scene's brightness and noise while `custom` is on. Fractions are stored
+/// in percent, as the config can't hold floats.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BurstTuning {
+    /// Use these values instead of the scene-adaptive ones
+    pub custom: bool,
+    /// Frames in an Auto burst; fixed frame count settings keep their count
+    pub frame_count: u8,
+    /// Merge robustness in percent; higher rejects less and denoises more
+    pub robustness: u16,
+    /// Shadow lift of the tone mapping in percent
+    pub shadow_boost: u8,
+    /// Local contrast enhancement in percent
+    pub local_contrast: u8,
+}
+
+impl BurstTuning {
+    /// Shortest burst offered (one frame is no burst)
+    pub const MIN_FRAMES: u8 = 2;
+    /// Longest burst offered
+    pub const MAX_FRAMES: u8 = 16;
+    /// Strongest robustness offered, in percent
+    pub const MAX_ROBUSTNESS: u16 = 300;
+    /// Strongest shadow boost and local contrast offered, in percent
+    pub const MAX_PERCENT: u8 = 100;
+
+    /// The same tuning with every value brought into its range
+    pub fn clamped(self) -> Self {
+        Self {
+            frame_count: self.frame_count.clamp(Self::MIN_FRAMES, Self::MAX_FRAMES),
+            robustness: self.robustness.min(Self::MAX_ROBUSTNESS),
+            shadow_boost: self.shadow_boost.min(Self::MAX_PERCENT),
+            local_contrast: self.local_contrast.min(Self::MAX_PERCENT),
+            ..self
+        }
+    }
+}
+
+impl Default for BurstTuning {
+    /// The burst pipeline's own defaults
+    fn default() -> Self {
+        Self {
+            custom: false,
+            frame_count: 8,
+            robustness: 100,
+            shadow_boost: 20,
+            local_contrast: 15,
+        }
+    }
+}
+
+/// Record button output in Video mode: a normal video, or a short
+/// animated clip
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum AnimatedClipFormat {
+    /// Record a video file
+    #[default]
+    Off,
+    /// Animated GIF
+    Gif,
+    /// Animated WebP
+    WebP,
+}
+
+impl AnimatedClipFormat {
+    /// Get all options, in dropdown order
+    pub const ALL: [AnimatedClipFormat; 3] = [
+        AnimatedClipFormat::Off,
+        AnimatedClipFormat::Gif,
+        AnimatedClipFormat::WebP,
+    ];
+}
+
+/// One-handed phone layout: which bottom corner the shutter moves to on a
+/// portrait phone-sized window
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum OneHandedLayout {
+    /// Centred shutter
+    #[default]
+    Off,
+    /// Shutter in the bottom-left corner
+    Left,
+    /// Shutter in the bottom-right corner
+    Right,
+}
+
+impl OneHandedLayout {
+    /// Get all options, in dropdown order
+    pub const ALL: [OneHandedLayout; 3] = [
+        OneHandedLayout::Off,
+        OneHandedLayout::Left,
+        OneHandedLayout::Right,
+    ];
+}
+
+/// How the second camera is combined with the active one
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum DualCameraLayout {
+    /// Small rounded inset in a corner
+    #[default]
+    PictureInPicture,
+    /// Each camera fills one half of the frame, for lens comparisons and
+    /// stereo rigs
+    SideBySide,
+}
+
+impl DualCameraLayout {
+    /// Get all options, in dropdown order
+    pub const ALL: [DualCameraLayout; 2] = [
+        DualCameraLayout::PictureInPicture,
+        DualCameraLayout::SideBySide,
+    ];
+}
+
+/// Capture width of the second camera. It's opened independently of the
+/// active camera, so it doesn't have to match its format.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum SecondaryResolution {
+    /// 640 px wide, plenty for an inset
+    #[default]
+    Low,
+    /// 1280 px wide
+    Hd,
+    /// 1920 px wide, for side-by-side output
+    FullHd,
+}
+
+impl SecondaryResolution {
+    /// Get all options, in dropdown order
+    pub const ALL: [SecondaryResolution; 3] = [
+        SecondaryResolution::Low,
+        SecondaryResolution::Hd,
+        SecondaryResolution::FullHd,
+    ];
+
+    /// Width the second camera is scaled to; height follows its aspect ratio
+    pub fn width(self) -> u32 {
+        match self {
+            SecondaryResolution::Low => 640,
+            SecondaryResolution::Hd => 1280,
+            SecondaryResolution::FullHd => 1920,
+        }
+    }
+}
+
+/// Corner of the frame the picture-in-picture camera is inset into
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PipCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+impl PipCorner {
+    /// Get all options, in dropdown order
+    pub const ALL: [PipCorner; 4] = [
+        PipCorner::TopLeft,
+        PipCorner::TopRight,
+        PipCorner::BottomLeft,
+        PipCorner::BottomRight,
+    ];
+}
+
+/// Where a camera sits relative to the screen, for the "look here" marker
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum CameraLocation {
+    TopLeft,
+    /// Above the middle of the screen, like most laptop cameras
+    #[default]
+    TopCenter,
+    TopRight,
+    Left,
+    Right,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+impl CameraLocation {
+    /// Get all options, in dropdown order
+    pub const ALL: [CameraLocation; 8] = [
+        CameraLocation::TopLeft,
+        CameraLocation::TopCenter,
+        CameraLocation::TopRight,
+        CameraLocation::Left,
+        CameraLocation::Right,
+        CameraLocation::BottomLeft,
+        CameraLocation::BottomCenter,
+        CameraLocation::BottomRight,
+    ];
+
+    /// Point on the window edge nearest the camera, as fractions of the
+    /// window width and height
+    pub fn anchor(self) -> (f32, f32) {
+        match self {
+            CameraLocation::TopLeft => (0.0, 0.0),
+            CameraLocation::TopCenter => (0.5, 0.0),
+            CameraLocation::TopRight => (1.0, 0.0),
+            CameraLocation::Left => (0.0, 0.5),
+            CameraLocation::Right => (1.0, 0.5),
+            CameraLocation::BottomLeft => (0.0, 1.0),
+            CameraLocation::BottomCenter => (0.5, 1.0),
+            CameraLocation::BottomRight => (1.0, 1.0),
+        }
+    }
+}
+
 /// Application theme preference
 #[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub enum AppTheme {
@@ -415,6 +981,67 @@ pub struct FormatSettings {
 /// Backwards compatibility alias
 pub type VideoSettings = FormatSettings;
 
+/// Whiteboard corners locked for a camera, in ten-thousandths of the frame
+/// so the config stays comparable
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct WhiteboardKeystone {
+    /// Clockwise from top-left
+    pub corners: [(u16, u16); 4],
+}
+
+impl From<BoardQuad> for WhiteboardKeystone {
+    fn from(quad: BoardQuad) -> Self {
+        let scale = |v: f32| (v.clamp(0.0, 1.0) * 10_000.0).round() as u16;
+        Self {
+            corners: quad.corners.map(|(x, y)| (scale(x), scale(y))),
+        }
+    }
+}
+
+impl From<WhiteboardKeystone> for BoardQuad {
+    fn from(keystone: WhiteboardKeystone) -> Self {
+        let scale = |v: u16| f32::from(v) / 10_000.0;
+        Self {
+            corners: keystone.corners.map(|(x, y)| (scale(x), scale(y))),
+        }
+    }
+}
+
+/// User correction of a camera's picture, on top of the rotation the
+/// sensor reports: for a camera mounted sideways or upside down, or one
+/// filming through a mirror or a teleprompter
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CameraOrientation {
+    /// Extra clockwise rotation in degrees: 0, 90, 180 or 270
+    pub rotation: u32,
+    /// Flip left to right
+    pub mirror: bool,
+    /// Flip upside down
+    pub flip: bool,
+}
+
+impl CameraOrientation {
+    /// Rotation steps offered in the settings, in degrees
+    pub const ROTATIONS: [u32; 4] = [0, 90, 180, 270];
+
+    /// Rotation to apply after the sensor's. A vertical flip is a mirror
+    /// turned half way round, so it is folded in here and in
+    /// [`Self::mirrored`] and every output only needs rotation and mirror.
+    pub fn rotation(&self) -> SensorRotation {
+        let rotation = SensorRotation::from_degrees_int(self.rotation as i32);
+        if self.flip {
+            rotation.compose(SensorRotation::Rotate180)
+        } else {
+            rotation
+        }
+    }
+
+    /// Whether the picture ends up mirrored left to right
+    pub fn mirrored(&self) -> bool {
+        self.mirror != self.flip
+    }
+}
+
 #[derive(Debug, Clone, CosmicConfigEntry, Eq, PartialEq, Serialize, Deserialize)]
 #[version = 20]
 pub struct Config {
@@ -427,6 +1054,8 @@ pub struct Config {
     pub default_mode: crate::app::CameraMode,
     /// Folder name for saving captures (photos go to XDG Pictures, videos go to XDG Videos)
     pub save_folder_name: String,
+    /// Free space below which captures warn, in megabytes
+    pub low_space_warning_mb: u32,
     /// Last camera path that successfully delivered a frame. Used as the
     /// preferred selection on next launch.
     pub last_camera_path: Option<String>,
@@ -444,6 +1073,49 @@ pub struct Config {
     pub video_settings: HashMap<String, FormatSettings>,
     /// Photo mode settings per camera (key = camera device path)
     pub photo_settings: HashMap<String, FormatSettings>,
+    /// User-set V4L2 control values per camera (key = camera device path,
+    /// then V4L2 control ID). Re-applied whenever the camera (re)connects.
+    pub camera_controls: HashMap<String, HashMap<u32, i32>>,
+    /// Remember exposure and color controls per camera and restore them
+    /// when they are reset by a reconnect or changed by another app
+    pub persist_camera_controls: bool,
+    /// Meter auto exposure on a detected face instead of the whole frame,
+    /// so backlit faces aren't left in shadow
+    pub face_exposure_priority: bool,
+    /// Suggest a capture setting suited to the scene in view (dim light,
+    /// a page, a QR code, a face, a close-up) in Photo mode
+    pub scene_suggestions: bool,
+    /// Show the live exposure time, gain, white balance and focus position
+    /// over the preview
+    pub exposure_osd: bool,
+    /// Show the preview as a false color exposure map, with clipped, crushed,
+    /// middle grey and skin tone luma ranges in flat colors
+    pub false_color: bool,
+    /// Daltonize or simulate a color vision deficiency on the preview
+    pub color_vision_assist: ColorVisionAssist,
+    /// Resampling filter the preview is scaled to the window with
+    pub preview_scaling: PreviewScaling,
+    /// Run the preview at a lower resolution while the window is small or
+    /// frames are being missed; photos are still taken at full resolution
+    pub adaptive_preview: bool,
+    /// Run HDMI capture cards for the lowest latency: minimal buffering,
+    /// uncompressed frames where offered and no preview filter
+    pub capture_card_mode: bool,
+    /// How 10-bit frames are interpreted: SDR, or HDR tone-mapped for the
+    /// preview and photos and kept in recordings
+    pub hdr_input: HdrInput,
+    /// Camera switched to automatically when the active camera stalls or is
+    /// unplugged while recording or streaming (None = no failover)
+    pub standby_camera_path: Option<String>,
+    /// Second camera composited with the active one in the preview, photos,
+    /// recordings and the virtual camera (None = off)
+    pub pip_camera_path: Option<String>,
+    /// How the second camera is combined with the active one
+    pub dual_camera_layout: DualCameraLayout,
+    /// Capture width of the second camera
+    pub secondary_resolution: SecondaryResolution,
+    /// Corner the picture-in-picture inset sits in
+    pub pip_corner: PipCorner,
     /// Last selected video encoder index
     pub last_video_encoder_index: Option<usize>,
     /// Bug report submission URL (GitHub issues URL)
@@ -455,30 +1127,167 @@ pub struct Config {
     /// front-facing camera is selected. Default off — captured media is
     /// stored as the sensor delivered it (matches Android / iOS defaults).
     pub mirror_captures: bool,
+    /// Turn saved photos and recordings upright in their pixels rather than
+    /// tagging how the device was held (EXIF / container orientation), for
+    /// viewers and services that ignore the tag
+    pub bake_rotation: bool,
     /// Video encoder bitrate preset (Low, Medium, High)
     pub bitrate_preset: BitratePreset,
     /// Virtual camera feature enabled (disabled by default)
     pub virtual_camera_enabled: bool,
+    /// Show a marker where the camera is in Virtual mode, to help keep eye
+    /// contact on calls
+    pub look_here_marker: bool,
+    /// Light the face in mirror mode: a white border around the picture and
+    /// the screen at full brightness
+    pub mirror_light: bool,
+    /// Keep a history of the QR codes scanned and what was done with them
+    pub qr_history: bool,
+    /// Where each camera sits relative to the screen (key = camera device
+    /// path); cameras not listed are above the middle
+    pub camera_locations: HashMap<String, CameraLocation>,
     /// Photo output format (JPEG, PNG, or DNG)
     pub photo_output_format: PhotoOutputFormat,
     /// Save raw burst frames as DNG files (for debugging burst mode pipeline)
     pub save_burst_raw: bool,
     /// Burst mode setting (Off, Auto, or fixed frame count)
     pub burst_mode_setting: BurstModeSetting,
+    /// Recommend or switch to HDR+ when the preview is measured to be dark
+    pub night_mode: NightMode,
+    /// Hand-tuned HDR+ frame count, merge and tone mapping parameters
+    pub burst_tuning: BurstTuning,
+    /// Burst captures are reconstructed at twice the resolution instead of
+    /// being merged by HDR+
+    pub super_resolution: bool,
+    /// Product photo mode: average frames, whiten the background and crop to the object
+    pub product_mode: bool,
+    /// Export product photos as PNG with a transparent background
+    pub product_transparent_background: bool,
+    /// Photo booth: the shutter takes a series of shots and saves a collage
+    pub photo_booth: bool,
+    /// Collage layout for photo booth shots
+    pub photo_booth_layout: PhotoBoothLayout,
+    /// Number of shots in a photo booth run
+    pub photo_booth_shots: u8,
+    /// Countdown before each photo booth shot, in seconds
+    pub photo_booth_countdown: u8,
+    /// Give each photo booth shot a different filter
+    pub photo_booth_filter_per_shot: bool,
+    /// The shutter sweeps the focus range and merges the frames into one
+    /// photo that is sharp throughout
+    pub focus_stack: bool,
+    /// Number of focus positions in a sweep
+    pub focus_stack_steps: u8,
+    /// Holding the shutter in Photo mode takes a rapid burst of stills
+    /// instead of starting a quick recording
+    pub rapid_burst: bool,
+    /// Most shots in one rapid burst
+    pub rapid_burst_max_shots: u8,
+    /// Holding up an open palm for a second starts the photo timer
+    pub gesture_shutter: bool,
+    /// The shutter copies photos to the clipboard instead of saving them
+    pub photo_to_clipboard: bool,
+    /// Unsharp mask applied to saved photos and burst results
+    pub output_sharpening: OutputSharpening,
+    /// Record a short animated GIF/WebP clip instead of a video
+    pub animated_clip_format: AnimatedClipFormat,
+    /// Animated clip length in seconds
+    pub animated_clip_seconds: u8,
+    /// Animated clip frame rate
+    pub animated_clip_fps: u8,
+    /// Loop animated clips forever instead of playing once
+    pub animated_clip_loop: bool,
     /// Record audio with video
     pub record_audio: bool,
+    /// Record desktop audio as a separate track
+    pub record_system_audio: bool,
     /// Audio encoder preference (Opus or AAC)
     pub audio_encoder: AudioEncoder,
+    /// Microphone gain in dB on top of the default makeup gain
+    pub audio_gain_db: i32,
+    /// Run recorded audio through a noise suppressor (RNNoise or WebRTC)
+    pub audio_noise_suppression: bool,
+    /// Audio-to-video sync offset in milliseconds per input device (key =
+    /// PipeWire node name, empty for the system default). Positive delays
+    /// the audio.
+    pub audio_sync_offsets: HashMap<String, i32>,
+    /// Output container for video recordings
+    pub video_container: VideoContainer,
+    /// Save an SRT sidecar next to each recording listing the QR codes seen
+    /// and the markers added while recording
+    pub record_event_subtitles: bool,
+    /// Log each frame's time of day and SMPTE timecode to a JSON sidecar
+    /// next to each recording
+    pub recording_timecode: bool,
+    /// Burn the date and time into recordings, dashcam style
+    pub recording_timestamp_overlay: bool,
+    /// Record a screen picked through the desktop portal, with the camera
+    /// inset in the `pip_corner`, instead of the camera alone
+    pub record_screen: bool,
+    /// Offer to share each new capture with a chip above the shutter
+    pub share_after_capture: bool,
+    /// Upload new captures to a WebDAV or S3 remote
+    pub upload: UploadSettings,
+    /// Stabilization strength for recordings
+    pub video_stabilization: VideoStabilization,
+    /// Noise reduction strength for recordings and the virtual camera
+    pub video_denoise: VideoDenoise,
+    /// Only denoise while the picture is measurably noisy, as in low light
+    pub video_denoise_auto: bool,
+    /// Anamorphic desqueeze applied to the preview
+    pub anamorphic_desqueeze: AnamorphicDesqueeze,
+    /// Also desqueeze recordings, rather than leaving them squeezed for the
+    /// editor to stretch
+    pub desqueeze_recordings: bool,
     /// Composition guide overlay for camera preview
     pub composition_guide: CompositionGuide,
+    /// Shutter corner for the one-handed phone layout
+    pub one_handed_layout: OneHandedLayout,
     /// Timelapse capture interval
     pub timelapse_interval: TimelapseInterval,
+    /// Spacing of stills extracted from a video (`None` = one per scene change)
+    pub still_extraction_interval: Option<TimelapseInterval>,
+    /// In Document mode, capture each page once it has been put down and
+    /// held still
+    pub document_auto_capture: bool,
+    /// Whiteboard corners locked per camera (key = camera device path), so
+    /// a mounted camera keeps its keystone correction across sessions
+    pub whiteboard_keystones: HashMap<String, WhiteboardKeystone>,
+    /// Rotation and flips set per camera (key = camera device path)
+    pub camera_orientations: HashMap<String, CameraOrientation>,
+    /// In Whiteboard mode, snapshot the board whenever the writing has
+    /// changed and settled
+    pub whiteboard_auto_snapshot: bool,
+    /// In Scan mode, beep for each new code
+    pub scan_beep: bool,
+    /// Add a searchable text layer to document and whiteboard PDFs (builds
+    /// with the `ocr` feature only)
+    pub searchable_pdf: bool,
+    /// Tesseract language(s) for text recognition, e.g. "eng" or "eng+deu"
+    pub ocr_language: String,
     /// Haptic feedback on capture, mode switch, etc.
     pub haptic_feedback: bool,
+    /// Play no sounds at all, whatever the capture sound and scan beep
+    /// settings say
+    pub silent_mode: bool,
+    /// Shutter click, self-timer beeps and recording start/stop chimes
+    pub capture_sounds: bool,
+    /// Volume of the capture sounds, in percent
+    pub capture_sound_volume: u32,
+    /// Swipe, double-tap and long-press gestures on the preview
+    pub preview_gestures: bool,
+    /// Tab and Shift+Tab move a visible focus between the controls, and
+    /// Enter or Space activates the focused one
+    pub keyboard_navigation: bool,
+    /// What the hardware volume keys do on phones
+    pub volume_key_action: VolumeKeyAction,
     /// Photo aspect ratio preference
     pub photo_aspect_ratio: crate::app::PhotoAspectRatio,
     /// Show entire frame (Contain) instead of filling the window (Cover)
     pub preview_fit_to_view: bool,
+    /// Per-mode placement of the optional overlay controls. Only contains
+    /// user overrides; anything else uses the stock layout.
+    pub control_layout: crate::app::control_layout::ControlLayout,
     /// User-rebound keyboard shortcuts. Only contains user overrides;
     /// the full default set is computed at runtime.
     /// An empty SerializedKeyBind means the action is intentionally unbound.
@@ -495,29 +1304,104 @@ impl Default for Config {
             overlay_effect: OverlayEffect::default(), // System on COSMIC, Translucent elsewhere
             default_mode: crate::app::CameraMode::default(), // Default to Photo
             save_folder_name: crate::constants::DEFAULT_SAVE_FOLDER.to_string(),
+            low_space_warning_mb: 1000, // Warn below 1 GB
             last_camera_path: None,
             pending_camera_path: None,
             failed_camera_paths: Vec::new(),
             video_settings: HashMap::new(),
             photo_settings: HashMap::new(),
+            camera_controls: HashMap::new(),
+            persist_camera_controls: true, // Restore controls on reconnect by default
+            face_exposure_priority: false, // Meter the whole frame
+            scene_suggestions: true,       // Suggest settings for the scene
+            exposure_osd: false,           // Exposure/focus OSD hidden
+            false_color: false,            // Normal preview
+            color_vision_assist: ColorVisionAssist::Off, // Colors as captured
+            preview_scaling: PreviewScaling::Bilinear, // Cheap smooth scaling
+            adaptive_preview: false,       // Preview at the selected resolution
+            capture_card_mode: true,       // Low latency for HDMI grabbers
+            hdr_input: HdrInput::Auto,     // Trust the driver
+            standby_camera_path: None,     // No standby camera
+            pip_camera_path: None,         // No second camera
+            dual_camera_layout: DualCameraLayout::default(), // Corner inset
+            secondary_resolution: SecondaryResolution::default(), // 640 px wide
+            pip_corner: PipCorner::default(), // Bottom-right inset
             last_video_encoder_index: None,
             bug_report_url:
                 "https://github.com/cosmic-utils/camera/issues/new?template=bug_report_from_app.yml"
                     .to_string(),
             mirror_preview: true,   // Default to mirrored (selfie mode)
             mirror_captures: false, // Captured media unmirrored by default
+            bake_rotation: false,   // Device orientation as a tag, lossless and fast
             bitrate_preset: BitratePreset::default(), // Default to Medium
             virtual_camera_enabled: false, // Disabled by default
+            look_here_marker: false, // No marker over the preview
+            mirror_light: false,    // Mirror mode shows just the picture
+            qr_history: true,       // Scanned codes can be found again later
+            camera_locations: HashMap::new(), // Every camera above the screen
             photo_output_format: PhotoOutputFormat::default(), // Default to JPEG
             save_burst_raw: false,  // Disabled by default (debugging feature)
             burst_mode_setting: BurstModeSetting::default(), // Default to Auto
+            night_mode: NightMode::default(), // Offer HDR+ in the dark
+            burst_tuning: BurstTuning::default(), // Adapt HDR+ to the scene
+            super_resolution: false, // HDR+ merges bursts by default
+            product_mode: false,    // Regular photos by default
+            product_transparent_background: false, // White background by default
+            photo_booth: false,     // Single photos by default
+            photo_booth_layout: PhotoBoothLayout::default(), // Strip
+            photo_booth_shots: 4,   // Four shots, like a booth print
+            photo_booth_countdown: 3, // Three seconds before each shot
+            photo_booth_filter_per_shot: false, // Selected filter on every shot
+            focus_stack: false,     // Single focus position by default
+            focus_stack_steps: 5,   // Five positions across the range
+            rapid_burst: false,     // Long press records video by default
+            rapid_burst_max_shots: 20, // Two seconds of shots at 10 per second
+            gesture_shutter: false, // Only the shutter button takes photos
+            photo_to_clipboard: false, // Photos are saved
+            output_sharpening: OutputSharpening::default(), // Moderate, radius from resolution
+            animated_clip_format: AnimatedClipFormat::default(), // Record regular videos
+            animated_clip_seconds: 3, // Long enough for a reaction, short enough to share
+            animated_clip_fps: 15,  // Smooth enough while keeping GIFs small
+            animated_clip_loop: true, // Clips loop like most GIFs do
             record_audio: true,     // Enable audio recording by default
+            record_system_audio: false, // Microphone only by default
             audio_encoder: AudioEncoder::default(), // Default to Opus
+            audio_gain_db: 0,       // No extra gain
+            audio_noise_suppression: false, // Raw microphone by default
+            audio_sync_offsets: HashMap::new(), // No offset for any device
+            video_container: VideoContainer::default(), // Codec's default container
+            record_event_subtitles: false, // Just the video file by default
+            recording_timecode: false, // No timecode sidecar
+            recording_timestamp_overlay: false, // Clean picture
+            record_screen: false,   // The camera alone
+            share_after_capture: true, // Brief "Share" prompt
+            upload: UploadSettings::default(), // Off
+            video_stabilization: VideoStabilization::default(), // Full frame, no crop
+            video_denoise: VideoDenoise::default(), // Frames as the camera delivers them
+            video_denoise_auto: true, // Only when it's dark enough to need it
+            anamorphic_desqueeze: AnamorphicDesqueeze::default(), // Picture as delivered
+            desqueeze_recordings: false, // Record squeezed, like the camera
             composition_guide: CompositionGuide::default(), // Default to None
+            one_handed_layout: OneHandedLayout::default(), // Centred shutter
             timelapse_interval: TimelapseInterval::default(), // Default to 2 fps
+            still_extraction_interval: Some(TimelapseInterval::Min1), // A still a minute
+            document_auto_capture: true, // Hands-free page feeding
+            whiteboard_keystones: HashMap::new(), // Follow the detected board
+            camera_orientations: HashMap::new(), // As the sensor reports
+            whiteboard_auto_snapshot: true, // Keep each board state
+            scan_beep: true,        // Confirms a scan without looking
+            searchable_pdf: true,   // Text layer whenever OCR is built in
+            ocr_language: crate::media::ocr::DEFAULT_LANGUAGE.to_string(), // English
             haptic_feedback: true,  // Enable haptic feedback by default
+            silent_mode: false,     // Sounds follow their own settings
+            capture_sounds: false,  // Silent captures
+            capture_sound_volume: 60, // Audible without startling
+            preview_gestures: true, // Touch gestures on by default
+            keyboard_navigation: true, // Every control reachable from the keyboard
+            volume_key_action: VolumeKeyAction::default(), // Volume keys are a shutter
             photo_aspect_ratio: crate::app::PhotoAspectRatio::default(),
             preview_fit_to_view: false,
+            control_layout: Default::default(), // Stock layout
             key_bindings: std::collections::HashMap::new(),
         }
     }
@@ -527,6 +1411,57 @@ impl Default for Config {
 mod tests {
     use super::*;
 
+    #[test]
+    fn vertical_flip_is_a_half_turn_mirror() {
+        let flip = CameraOrientation {
+            flip: true,
+            ..Default::default()
+        };
+        assert_eq!(flip.rotation(), SensorRotation::Rotate180);
+        assert!(flip.mirrored());
+
+        // Both flips together are just a half turn
+        let both = CameraOrientation {
+            rotation: 90,
+            mirror: true,
+            flip: true,
+        };
+        assert_eq!(both.rotation(), SensorRotation::Rotate270);
+        assert!(!both.mirrored());
+
+        assert_eq!(
+            CameraOrientation::default().rotation(),
+            SensorRotation::None
+        );
+    }
+
+    #[test]
+    fn burst_tuning_stays_in_range() {
+        let wild = BurstTuning {
+            custom: true,
+            frame_count: 200,
+            robustness: 1000,
+            shadow_boost: 150,
+            local_contrast: 0,
+        };
+        let tuning = wild.clamped();
+        assert!(tuning.custom);
+        assert_eq!(tuning.frame_count, BurstTuning::MAX_FRAMES);
+        assert_eq!(tuning.robustness, BurstTuning::MAX_ROBUSTNESS);
+        assert_eq!(tuning.shadow_boost, BurstTuning::MAX_PERCENT);
+        assert_eq!(tuning.local_contrast, 0);
+        assert_eq!(
+            BurstTuning {
+                frame_count: 1,
+                ..wild
+            }
+            .clamped()
+            .frame_count,
+            BurstTuning::MIN_FRAMES
+        );
+        assert_eq!(BurstTuning::default().clamped(), BurstTuning::default());
+    }
+
     /// Off-COSMIC there is no frosting flag to follow, so `System` would mean
     /// exactly `Translucent`. It must not be offered.
     #[test]
@@ -18,14 +18,18 @@ pub enum BitratePreset {
     Medium,
     /// High bitrate - larger files, better quality
     High,
+    /// Edit-friendly - intra-only video and PCM audio in MKV/MOV, for
+    /// footage that gets cut and graded afterwards
+    EditFriendly,
 }
 
 impl BitratePreset {
     /// Get all preset variants for UI iteration
-    pub const ALL: [BitratePreset; 3] = [
+    pub const ALL: [BitratePreset; 4] = [
         BitratePreset::Low,
         BitratePreset::Medium,
         BitratePreset::High,
+        BitratePreset::EditFriendly,
     ];
 
     /// Get display name for the preset
@@ -34,9 +38,15 @@ impl BitratePreset {
             BitratePreset::Low => "Low",
             BitratePreset::Medium => "Medium",
             BitratePreset::High => "High",
+            BitratePreset::EditFriendly => "Edit-friendly",
         }
     }
 
+    /// Whether recordings encode every frame as a key frame with PCM audio
+    pub fn is_intra_only(&self) -> bool {
+        matches!(self, BitratePreset::EditFriendly)
+    }
+
     /// Get bitrate in kbps for a given resolution
     ///
     /// Bitrates are tuned for good quality at each resolution tier:
@@ -45,6 +55,9 @@ impl BitratePreset {
     /// - Full HD (1920x1080): Low=4, Medium=8, High=16 Mbps
     /// - 2K (2560x1440): Low=8, Medium=16, High=32 Mbps
     /// - 4K (3840x2160): Low=15, Medium=30, High=50 Mbps
+    ///
+    /// Edit-friendly is three times High: without inter-frame prediction
+    /// every frame costs as much as a key frame.
     pub fn bitrate_kbps(&self, width: u32, _height: u32) -> u32 {
         self.bitrate_for_tier(get_resolution_tier(width))
     }
@@ -67,6 +80,7 @@ impl BitratePreset {
             (ResolutionTier::FourK, BitratePreset::Low) => 15_000,
             (ResolutionTier::FourK, BitratePreset::Medium) => 30_000,
             (ResolutionTier::FourK, BitratePreset::High) => 50_000,
+            (tier, BitratePreset::EditFriendly) => BitratePreset::High.bitrate_for_tier(tier) * 3,
         }
     }
 }
@@ -144,8 +158,11 @@ pub const DEFAULT_SAVE_FOLDER: &str = "Camera";
 
 /// UI Constants
 pub mod ui {
-    /// Button width for format picker
-    pub const PICKER_BUTTON_WIDTH: f32 = 50.0;
+    /// Width of the format picker's list
+    pub const PICKER_LIST_WIDTH: f32 = 360.0;
+
+    /// Tallest the format picker grows before its list scrolls
+    pub const PICKER_LIST_MAX_HEIGHT: f32 = 420.0;
 
     /// Capture button size (inner)
     pub const CAPTURE_BUTTON_INNER: f32 = 60.0;
@@ -171,12 +188,6 @@ pub mod ui {
     /// Standard icon button width (for layout balancing)
     pub const ICON_BUTTON_WIDTH: f32 = 44.0;
 
-    /// Picker label text size
-    pub const PICKER_LABEL_TEXT_SIZE: u16 = 12;
-
-    /// Picker label width
-    pub const PICKER_LABEL_WIDTH: f32 = 80.0;
-
     /// Resolution label text size in top bar
     pub const RES_LABEL_TEXT_SIZE: u16 = 14;
 
@@ -212,9 +223,6 @@ pub mod resolution_thresholds {
 pub mod formats {
     /// Common frame rates to try when exact enumeration fails
     pub const COMMON_FRAMERATES: &[u32] = &[30, 60, 15, 24];
-
-    /// Default resolution for picker selection
-    pub const DEFAULT_PICKER_RESOLUTION: u32 = 1920;
 }
 
 /// GStreamer pipeline constants
@@ -248,6 +256,37 @@ pub mod timing {
 
     /// Pipeline playing state timeout on start
     pub const START_TIMEOUT_SECS: u64 = 5;
+
+    /// Warn in the video settings when less recording time than this fits on disk
+    pub const LOW_DISK_RECORDING_WARNING_SECS: u64 = 10 * 60;
+
+    /// How often stored V4L2 controls are compared against the device
+    pub const CONTROL_WATCHDOG_INTERVAL_SECS: u64 = 3;
+
+    /// Window in which repeated control overrides count as a conflict
+    pub const CONTROL_CONFLICT_WINDOW_SECS: u64 = 60;
+
+    /// Overrides within the window before another app is flagged as fighting
+    /// over the controls
+    pub const CONTROL_CONFLICT_THRESHOLD: usize = 3;
+
+    /// How often the active camera is checked for stalls while recording or
+    /// streaming with a standby camera configured
+    pub const FAILOVER_WATCHDOG_INTERVAL_MS: u64 = 500;
+
+    /// Time without a new frame after which the active camera is considered
+    /// stalled and the standby camera takes over
+    pub const FAILOVER_STALL_TIMEOUT_MS: u64 = 2000;
+
+    /// How long the "Share" prompt stays above the shutter after a capture
+    pub const SHARE_PROMPT_MS: u64 = 6000;
+
+    /// How long "Copied to clipboard" stays above the shutter
+    pub const CLIPBOARD_NOTICE_MS: u64 = 2000;
+
+    /// How long an error notice stays above the shutter, long enough to
+    /// read its remedy
+    pub const ERROR_NOTICE_MS: u64 = 8000;
 }
 
 /// Frame latency optimization constants
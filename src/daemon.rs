@@ -0,0 +1,710 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Headless daemon mode
+//!
+//! Runs the camera without a window for kiosk and monitoring deployments.
+//! Photos are taken on a fixed schedule and/or when motion is detected in
+//! the live stream, and are written to the storage directory configured in
+//! the GUI. Automation rules (see [`camera::automation`]) can additionally
+//! record clips, send notifications and run hooks. The running daemon can be
+//! controlled over D-Bus:
+//!
+//! ```text
+//! busctl --user call io.github.cosmic_utils.camera.Daemon \
+//!     /io/github/cosmic_utils/camera/Daemon \
+//!     io.github.cosmic_utils.camera.Daemon TakePhoto
+//! ```
+
+use camera::automation::{self, Action, Event, Firing, RuleEngine, RuleSet};
+use camera::backends::camera::CameraBackend;
+use camera::backends::camera::frame_hub::{ConsumerKind, QueuePolicy};
+use camera::backends::camera::libcamera::{
+    CameraPipelineHandle, LibcameraBackend, create_pipeline,
+};
+use camera::backends::camera::types::{CameraFrame, PixelFormat, SensorRotation};
+use camera::config::Config;
+use camera::media::encoders::video::{
+    ContainerFormat, EncoderInfo, VideoCodec, enumerate_video_encoders,
+};
+use camera::pipelines::photo::PhotoPipeline;
+use camera::pipelines::video::{
+    AppsrcRecorderConfig, EncoderConfig, RecorderConfig, VideoRecorder,
+};
+use cosmic::cosmic_config::{self, CosmicConfigEntry};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Well-known bus name claimed by the daemon
+const BUS_NAME: &str = automation::DAEMON_BUS_NAME;
+/// Object path the control interface is served at
+const OBJECT_PATH: &str = automation::DAEMON_OBJECT_PATH;
+/// Application ID used to look up the GUI configuration
+const APP_ID: &str = "io.github.cosmic_utils.camera";
+
+/// Width/height of the luma grid used for motion detection
+const MOTION_GRID_SIZE: usize = 32;
+/// Per-cell luma difference (0-255) that counts a cell as changed
+const MOTION_CELL_DELTA: u8 = 24;
+
+/// Daemon options from the command line
+pub struct DaemonOptions {
+    pub camera_index: usize,
+    /// Seconds between scheduled photos (None disables the schedule)
+    pub interval: Option<u64>,
+    /// Take a photo whenever motion is detected
+    pub motion: bool,
+    /// Fraction of the frame (0.0-1.0) that must change to count as motion
+    pub motion_threshold: f32,
+    /// Minimum seconds between two motion-triggered photos
+    pub cooldown: u64,
+    /// Output directory (default: configured photo directory)
+    pub output: Option<PathBuf>,
+    /// Automation rules file (default: [`automation::rules_path`])
+    pub rules: Option<PathBuf>,
+}
+
+/// State shared between the capture loop and the D-Bus interface
+#[derive(Default)]
+struct DaemonState {
+    stop: AtomicBool,
+    paused: AtomicBool,
+    capture_requested: AtomicBool,
+    reload_rules: AtomicBool,
+    photos_taken: AtomicU64,
+    last_photo: Mutex<String>,
+}
+
+/// D-Bus control interface for the running daemon
+struct DaemonInterface {
+    state: Arc<DaemonState>,
+}
+
+#[zbus::interface(name = "io.github.cosmic_utils.camera.Daemon")]
+impl DaemonInterface {
+    /// Take a photo as soon as the next frame arrives
+    fn take_photo(&self) {
+        self.state.capture_requested.store(true, Ordering::SeqCst);
+    }
+
+    /// Suspend scheduled and motion-triggered photos
+    fn pause(&self) {
+        self.state.paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Resume scheduled and motion-triggered photos
+    fn resume(&self) {
+        self.state.paused.store(false, Ordering::SeqCst);
+    }
+
+    /// Re-read the automation rules file
+    fn reload_rules(&self) {
+        self.state.reload_rules.store(true, Ordering::SeqCst);
+    }
+
+    /// Shut the daemon down
+    fn stop(&self) {
+        self.state.stop.store(true, Ordering::SeqCst);
+    }
+
+    #[zbus(property)]
+    fn paused(&self) -> bool {
+        self.state.paused.load(Ordering::SeqCst)
+    }
+
+    #[zbus(property)]
+    fn photos_taken(&self) -> u64 {
+        self.state.photos_taken.load(Ordering::SeqCst)
+    }
+
+    #[zbus(property)]
+    fn last_photo(&self) -> String {
+        self.state
+            .last_photo
+            .lock()
+            .map(|p| p.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Why a photo was taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Trigger {
+    Schedule,
+    Motion,
+    DBus,
+    Rule,
+}
+
+/// A clip being recorded by an automation rule
+struct ActiveClip {
+    recorder: VideoRecorder,
+    path: PathBuf,
+    /// Recording stops at this instant unless another rule extends it
+    until: Instant,
+}
+
+/// Frame-differencing motion detector
+///
+/// Each frame is reduced to a small luma grid; motion is reported when the
+/// fraction of cells whose brightness changed by more than
+/// [`MOTION_CELL_DELTA`] exceeds the configured threshold.
+struct MotionDetector {
+    threshold: f32,
+    previous: Option<Vec<u8>>,
+}
+
+impl MotionDetector {
+    fn new(threshold: f32) -> Self {
+        Self {
+            threshold: threshold.clamp(0.0, 1.0),
+            previous: None,
+        }
+    }
+
+    /// Feed a luma grid, returning true if it differs enough from the last one
+    fn update(&mut self, grid: Vec<u8>) -> bool {
+        let motion = match self.previous.as_ref() {
+            Some(prev) if prev.len() == grid.len() && !grid.is_empty() => {
+                let changed = prev
+                    .iter()
+                    .zip(&grid)
+                    .filter(|(a, b)| a.abs_diff(**b) > MOTION_CELL_DELTA)
+                    .count();
+                changed as f32 / grid.len() as f32 > self.threshold
+            }
+            _ => false,
+        };
+        self.previous = Some(grid);
+        motion
+    }
+}
+
+/// Byte offset and step of an approximate luma sample for packed/planar formats
+///
+/// Returns `(offset_within_pixel, bytes_per_pixel)` for the first plane, or
+/// `None` for formats motion detection does not support (e.g. raw Bayer).
+fn luma_layout(format: PixelFormat) -> Option<(usize, usize)> {
+    match format {
+        PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::I420 | PixelFormat::Gray8 => {
+            Some((0, 1))
+        }
+        PixelFormat::YUYV | PixelFormat::YVYU => Some((0, 2)),
+        PixelFormat::UYVY | PixelFormat::VYUY => Some((1, 2)),
+        // Green dominates perceived brightness; good enough for differencing
+        PixelFormat::RGBA
+        | PixelFormat::BGRA
+        | PixelFormat::ABGR
+        | PixelFormat::RGBx
+        | PixelFormat::BGRx => Some((1, 4)),
+        PixelFormat::RGB24 => Some((1, 3)),
+        // High byte of each little-endian 16-bit luma sample
+        PixelFormat::P010 => Some((1, 2)),
+        PixelFormat::Y210 => Some((1, 4)),
+        _ => None,
+    }
+}
+
+/// Downsample a frame to a `MOTION_GRID_SIZE`² luma grid
+fn luma_grid(frame: &CameraFrame) -> Option<Vec<u8>> {
+    let (offset, bpp) = luma_layout(frame.format)?;
+    let data: &[u8] = &frame.data;
+    let (width, height) = (frame.width as usize, frame.height as usize);
+    let stride = if frame.stride > 0 {
+        frame.stride as usize
+    } else {
+        width * bpp
+    };
+    if width < MOTION_GRID_SIZE || height < MOTION_GRID_SIZE {
+        return None;
+    }
+
+    let mut grid = Vec::with_capacity(MOTION_GRID_SIZE * MOTION_GRID_SIZE);
+    for gy in 0..MOTION_GRID_SIZE {
+        // Sample the centre of each cell
+        let y = (gy * 2 + 1) * height / (MOTION_GRID_SIZE * 2);
+        for gx in 0..MOTION_GRID_SIZE {
+            let x = (gx * 2 + 1) * width / (MOTION_GRID_SIZE * 2);
+            grid.push(*data.get(y * stride + x * bpp + offset)?);
+        }
+    }
+    Some(grid)
+}
+
+/// Load the saved GUI configuration (output folders, audio preference)
+fn load_config() -> Config {
+    cosmic_config::Config::new(APP_ID, Config::VERSION)
+        .ok()
+        .map(|handler| match Config::get_entry(&handler) {
+            Ok(config) => config,
+            Err((errs, config)) => {
+                warn!(?errs, "Errors loading config, using partial config");
+                config
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// Load automation rules from `path`, or the default rules file
+fn load_rules(path: Option<&Path>) -> Result<RuleSet, String> {
+    match path.map(Path::to_path_buf).or_else(automation::rules_path) {
+        Some(path) => RuleSet::load(&path),
+        None => Ok(RuleSet::default()),
+    }
+}
+
+/// Capture `frame` and record the result in the shared state
+fn save_photo(
+    rt: &tokio::runtime::Runtime,
+    photo_pipeline: &PhotoPipeline,
+    frame: &CameraFrame,
+    output_dir: &Path,
+    state: &DaemonState,
+    trigger: Trigger,
+) -> Option<PathBuf> {
+    match rt.block_on(
+        photo_pipeline.capture_and_save(Arc::new(frame.to_copied()), output_dir.to_path_buf()),
+    ) {
+        Ok(path) => {
+            let count = state.photos_taken.fetch_add(1, Ordering::SeqCst) + 1;
+            println!("[{:?}] Photo {} saved: {}", trigger, count, path.display());
+            if let Ok(mut last) = state.last_photo.lock() {
+                *last = path.display().to_string();
+            }
+            Some(path)
+        }
+        Err(e) => {
+            warn!(error = %e, ?trigger, "Failed to save photo");
+            None
+        }
+    }
+}
+
+/// Fixed inputs for rule-triggered clips
+struct ClipSettings<'a> {
+    handle: &'a CameraPipelineHandle,
+    framerate: u32,
+    rotation: SensorRotation,
+    enable_audio: bool,
+    video_dir: &'a Path,
+    /// Encoder the app records with, `None` to let the recorder pick
+    encoder: Option<&'a EncoderInfo>,
+    /// Container the user prefers (`None` = the codec's default)
+    container: Option<ContainerFormat>,
+    /// Log each frame's time of day next to the clip
+    timecode: bool,
+    /// Burn the date and time into the clip
+    timestamp_overlay: bool,
+    /// Anamorphic desqueeze of the clip (1.0 = none)
+    desqueeze: f32,
+}
+
+/// Start recording a rule-triggered clip from the live stream
+fn start_clip(
+    rt: &tokio::runtime::Runtime,
+    settings: &ClipSettings<'_>,
+    frame: &CameraFrame,
+    seconds: u64,
+) -> Result<ActiveClip, String> {
+    let video_dir = settings.video_dir;
+    std::fs::create_dir_all(video_dir)
+        .map_err(|e| format!("Failed to create {}: {e}", video_dir.display()))?;
+    let encoder_config = EncoderConfig {
+        container: settings.container,
+        ..Default::default()
+    };
+    // Named for the container the encoder will actually write, like the
+    // app's recordings
+    let codec = settings.encoder.map_or(VideoCodec::H264, |e| e.codec);
+    let container = encoder_config.container_for(codec);
+    let output_path = camera::storage::capture_path(video_dir, "VID", container.extension());
+
+    let (frame_tx, frame_rx) = tokio::sync::mpsc::channel(15);
+    // The appsrc pusher spawns onto the current runtime
+    let _guard = rt.enter();
+    let recorder = VideoRecorder::new_from_appsrc(
+        AppsrcRecorderConfig {
+            base: RecorderConfig {
+                width: frame.width,
+                height: frame.height,
+                framerate: settings.framerate,
+                output_path: output_path.clone(),
+                encoder_config,
+                enable_audio: settings.enable_audio,
+                audio_device: None,
+                audio_source_rate_hz: 0,
+                audio_processing: Default::default(),
+                system_audio: false,
+                encoder_info: settings.encoder,
+                rotation: settings.rotation,
+                mirror_horizontal: false,
+                orientation: SensorRotation::None,
+                audio_levels: Default::default(),
+                audio_sync_offset: Default::default(),
+            },
+            pixel_format: frame.format,
+            live_filter_code: Arc::new(std::sync::atomic::AtomicU32::new(0)),
+            pip: None,
+            stabilization: Default::default(),
+            denoise: Default::default(),
+            timecode: settings.timecode,
+            timestamp_overlay: settings.timestamp_overlay,
+            desqueeze: settings.desqueeze,
+        },
+        frame_rx,
+    )?;
+    settings.handle.set_recording_sender(Some(frame_tx));
+    recorder.start()?;
+
+    Ok(ActiveClip {
+        recorder,
+        path: output_path,
+        until: Instant::now() + Duration::from_secs(seconds),
+    })
+}
+
+/// Stop a rule-triggered clip and finalize the file
+fn finish_clip(handle: &CameraPipelineHandle, clip: ActiveClip) {
+    handle.set_recording_sender(None);
+    // Let EOS propagate before finalizing
+    std::thread::sleep(Duration::from_millis(300));
+    match clip.recorder.stop() {
+        Ok(path) => println!("[Rule] Clip saved: {}", path.display()),
+        Err(e) => warn!(error = %e, "Failed to finalize automation clip"),
+    }
+}
+
+/// Run the headless capture daemon until stopped via Ctrl+C or D-Bus
+pub fn run(options: DaemonOptions) -> Result<(), Box<dyn std::error::Error>> {
+    if options.interval == Some(0) {
+        return Err("Interval must be at least 1 second".into());
+    }
+
+    // An explicit rules file must load; the default one is optional
+    let rules = match load_rules(options.rules.as_deref()) {
+        Ok(rules) => rules,
+        Err(e) if options.rules.is_none() => {
+            warn!(error = %e, "Ignoring automation rules");
+            RuleSet::default()
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let mut engine = RuleEngine::new(rules, Instant::now());
+
+    if options.interval.is_none() && !options.motion && engine.is_empty() {
+        return Err(
+            "Nothing to do: pass --interval and/or --motion, or enable automation rules".into(),
+        );
+    }
+
+    let config = load_config();
+    let output_dir = options
+        .output
+        .clone()
+        .unwrap_or_else(|| camera::app::get_photo_directory(&config.save_folder_name));
+    let video_dir = options
+        .output
+        .clone()
+        .unwrap_or_else(|| camera::app::get_video_directory(&config.save_folder_name));
+    std::fs::create_dir_all(&output_dir)?;
+
+    // Initialize GStreamer
+    gstreamer::init()?;
+
+    let backend = LibcameraBackend::new();
+    let cameras = backend.enumerate_cameras();
+    if cameras.is_empty() {
+        return Err("No cameras found".into());
+    }
+
+    if options.camera_index >= cameras.len() {
+        return Err(format!(
+            "Camera index {} out of range (0-{})",
+            options.camera_index,
+            cameras.len() - 1
+        )
+        .into());
+    }
+
+    let camera = &cameras[options.camera_index];
+    println!("Using camera: {}", camera.name);
+
+    let formats = backend.get_formats(camera, false);
+    let format = formats
+        .iter()
+        .max_by_key(|f| f.width * f.height)
+        .cloned()
+        .ok_or("No formats available for camera")?;
+    println!("Capture format: {}x{}", format.width, format.height);
+    println!("Saving to: {}", output_dir.display());
+    if !engine.is_empty() {
+        println!("Automation rules: {}", engine.len());
+    }
+
+    let state = Arc::new(DaemonState::default());
+
+    // The D-Bus interface is optional: kiosks without a session bus still work
+    // with the schedule and motion triggers alone.
+    let _connection = match zbus::blocking::connection::Builder::session()
+        .and_then(|b| b.name(BUS_NAME))
+        .and_then(|b| {
+            b.serve_at(
+                OBJECT_PATH,
+                DaemonInterface {
+                    state: Arc::clone(&state),
+                },
+            )
+        })
+        .and_then(|b| b.build())
+    {
+        Ok(conn) => {
+            println!("D-Bus control: {} {}", BUS_NAME, OBJECT_PATH);
+            Some(conn)
+        }
+        Err(e) => {
+            warn!(error = %e, "D-Bus control interface unavailable");
+            None
+        }
+    };
+
+    let stop_flag = Arc::clone(&state);
+    ctrlc::set_handler(move || {
+        stop_flag.stop.store(true, Ordering::SeqCst);
+    })?;
+
+    let (handle, mut receiver) =
+        create_pipeline(camera, &format).map_err(|e| -> Box<dyn std::error::Error> { e.into() })?;
+
+    let rt = tokio::runtime::Runtime::new()?;
+    let photo_pipeline = PhotoPipeline::new();
+    let mut detector = (options.motion || engine.needs_motion())
+        .then(|| MotionDetector::new(options.motion_threshold));
+    // Motion detection only needs the present, so it keeps the newest frame
+    // rather than queueing behind the preview channel
+    let subscribe_motion =
+        || handle.subscribe_frames(ConsumerKind::MotionDetection, QueuePolicy::Latest);
+    let mut motion_frames = detector.as_ref().map(|_| subscribe_motion());
+    let interval = options.interval.map(Duration::from_secs);
+    let cooldown = Duration::from_secs(options.cooldown);
+    // Record with the encoder chosen in the app, as it does by default
+    let video_encoders = enumerate_video_encoders();
+    let clip_encoder = config
+        .last_video_encoder_index
+        .and_then(|index| video_encoders.get(index))
+        .or(video_encoders.first());
+    let clip_settings = ClipSettings {
+        handle: &handle,
+        framerate: format.framerate.map(|f| f.as_int()).unwrap_or(30),
+        rotation: camera.rotation,
+        enable_audio: config.record_audio,
+        video_dir: &video_dir,
+        encoder: clip_encoder,
+        container: config.video_container.container(),
+        timecode: config.recording_timecode,
+        timestamp_overlay: config.recording_timestamp_overlay,
+        desqueeze: if config.desqueeze_recordings {
+            config.anamorphic_desqueeze.factor()
+        } else {
+            1.0
+        },
+    };
+
+    // Give auto-exposure time to settle before the first scheduled shot
+    let start = Instant::now();
+    let mut next_scheduled = start + Duration::from_millis(500);
+    let mut last_motion_shot: Option<Instant> = None;
+    let mut latest: Option<CameraFrame> = None;
+    let mut clip: Option<ActiveClip> = None;
+
+    println!("Daemon running (press Ctrl+C to stop)");
+    info!(
+        interval = ?options.interval,
+        motion = options.motion,
+        rules = engine.len(),
+        "Camera daemon started"
+    );
+
+    while !state.stop.load(Ordering::SeqCst) {
+        let mut trigger = None;
+        let mut motion = false;
+
+        if state.reload_rules.swap(false, Ordering::SeqCst) {
+            match load_rules(options.rules.as_deref()) {
+                Ok(rules) => {
+                    engine = RuleEngine::new(rules, Instant::now());
+                    if engine.needs_motion() && detector.is_none() {
+                        detector = Some(MotionDetector::new(options.motion_threshold));
+                        motion_frames = Some(subscribe_motion());
+                    }
+                    println!("Automation rules reloaded: {}", engine.len());
+                }
+                Err(e) => warn!(error = %e, "Failed to reload automation rules"),
+            }
+        }
+
+        while let Ok(f) = receiver.try_recv() {
+            latest = Some(f);
+        }
+        if let Some(detector) = detector.as_mut()
+            && let Some(f) = motion_frames.as_mut().and_then(|frames| frames.try_recv())
+            && let Some(grid) = luma_grid(&f)
+            && detector.update(grid)
+        {
+            motion = true;
+        }
+
+        if options.motion && motion && last_motion_shot.is_none_or(|t| t.elapsed() >= cooldown) {
+            trigger = Some(Trigger::Motion);
+        }
+
+        let now = Instant::now();
+        if let Some(interval) = interval
+            && now >= next_scheduled
+        {
+            // Skip missed slots instead of bursting to catch up
+            while next_scheduled <= now {
+                next_scheduled += interval;
+            }
+            trigger.get_or_insert(Trigger::Schedule);
+        }
+
+        if state.paused.load(Ordering::SeqCst) {
+            trigger = None;
+        }
+        if state.capture_requested.swap(false, Ordering::SeqCst) {
+            trigger = Some(Trigger::DBus);
+        }
+
+        if !state.paused.load(Ordering::SeqCst) && !engine.is_empty() {
+            let local_time = chrono::Local::now().time();
+            let mut firings = engine.evaluate(Event::Tick, now, local_time);
+            if motion {
+                firings.extend(engine.evaluate(Event::Motion, now, local_time));
+            }
+            for firing in firings {
+                let Firing { rule, actions } = firing;
+                println!("[Rule] {} fired", rule);
+                // Last file written by this firing, handed to hooks
+                let mut last_file: Option<PathBuf> = None;
+                for action in actions {
+                    match action {
+                        Action::Photo => {
+                            if let Some(frame) = latest.as_ref() {
+                                last_file = save_photo(
+                                    &rt,
+                                    &photo_pipeline,
+                                    frame,
+                                    &output_dir,
+                                    &state,
+                                    Trigger::Rule,
+                                )
+                                .or(last_file);
+                            }
+                        }
+                        Action::Record { seconds } => {
+                            if let Some(active) = clip.as_mut() {
+                                active.until = active.until.max(now + Duration::from_secs(seconds));
+                                last_file = Some(active.path.clone());
+                            } else if let Some(frame) = latest.as_ref() {
+                                match start_clip(&rt, &clip_settings, frame, seconds) {
+                                    Ok(active) => {
+                                        println!("[Rule] Recording {}s clip", seconds);
+                                        last_file = Some(active.path.clone());
+                                        clip = Some(active);
+                                    }
+                                    Err(e) => {
+                                        warn!(error = %e, rule = %rule, "Failed to start clip")
+                                    }
+                                }
+                            }
+                        }
+                        Action::Notify { message } => {
+                            if let Err(e) = automation::send_notification(&rule, &message) {
+                                warn!(error = %e, rule = %rule, "Failed to send notification");
+                            }
+                        }
+                        Action::Run { command } => {
+                            if let Err(e) =
+                                automation::run_hook(&command, &rule, last_file.as_deref())
+                            {
+                                warn!(error = %e, rule = %rule, "Failed to run hook");
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(trigger) = trigger
+            && let Some(frame) = latest.take()
+        {
+            if trigger == Trigger::Motion {
+                last_motion_shot = Some(now);
+            }
+            save_photo(&rt, &photo_pipeline, &frame, &output_dir, &state, trigger);
+        }
+
+        if clip.as_ref().is_some_and(|c| now >= c.until)
+            && let Some(finished) = clip.take()
+        {
+            finish_clip(&handle, finished);
+        }
+
+        std::thread::sleep(Duration::from_millis(16));
+    }
+
+    if let Some(active) = clip.take() {
+        finish_clip(&handle, active);
+    }
+
+    println!(
+        "Daemon stopped ({} photos taken)",
+        state.photos_taken.load(Ordering::SeqCst)
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_frame_never_reports_motion() {
+        let mut detector = MotionDetector::new(0.05);
+        assert!(!detector.update(vec![0; 16]));
+    }
+
+    #[test]
+    fn static_scene_has_no_motion() {
+        let mut detector = MotionDetector::new(0.05);
+        detector.update(vec![100; 16]);
+        // Sensor noise below the per-cell delta is ignored
+        assert!(!detector.update(vec![110; 16]));
+    }
+
+    #[test]
+    fn large_change_reports_motion() {
+        let mut detector = MotionDetector::new(0.1);
+        detector.update(vec![100; 16]);
+        let mut next = vec![100; 16];
+        next[..4].fill(200);
+        assert!(detector.update(next));
+    }
+
+    #[test]
+    fn change_below_threshold_is_ignored() {
+        let mut detector = MotionDetector::new(0.5);
+        detector.update(vec![100; 16]);
+        let mut next = vec![100; 16];
+        next[..4].fill(200);
+        assert!(!detector.update(next));
+    }
+
+    #[test]
+    fn bayer_formats_are_unsupported() {
+        assert_eq!(luma_layout(PixelFormat::BayerRGGB), None);
+        assert_eq!(luma_layout(PixelFormat::UYVY), Some((1, 2)));
+    }
+}
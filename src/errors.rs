@@ -1,10 +1,25 @@
 // SPDX-License-Identifier: GPL-3.0-only
-// Error types prepared for future unified error handling
-#![allow(dead_code)]
 
 //! Error types for the camera application
+//!
+//! Errors are sorted by what the user can do about them: a camera another
+//! app holds, missing device or folder permissions, a desktop portal that
+//! isn't installed, an encoder that fails, a full disk. Backends and
+//! pipelines return them where the cause can be told apart (from the OS
+//! error, the GStreamer error domain, or the portal call that failed), and
+//! the UI shows [`AppError::summary`] with [`AppError::remedy`] rather than a
+//! generic failure. `Display` gives the technical text, for logs.
+//!
+//! Most of the code still returns `Result<_, String>`: such errors become
+//! [`AppError::Other`] with `?`, and an [`AppError`] turns back into a
+//! `String` for callers that haven't moved over.
 
+use crate::backends::camera::types::BackendError;
+use crate::fl;
+use gstreamer::glib;
 use std::fmt;
+use std::io::ErrorKind;
+use std::path::Path;
 
 /// Result type alias using AppError
 pub type AppResult<T> = Result<T, AppError>;
@@ -18,10 +33,12 @@ pub enum AppError {
     Recording(RecordingError),
     /// Photo capture errors
     Photo(PhotoError),
+    /// Saving to disk failed
+    Storage(StorageError),
+    /// A desktop portal request failed
+    Portal(PortalError),
     /// Configuration errors
     Config(String),
-    /// Storage/filesystem errors
-    Storage(String),
     /// Generic error with message
     Other(String),
 }
@@ -41,6 +58,8 @@ pub enum CameraError {
     BackendError(String),
     /// Camera is busy or in use
     Busy,
+    /// Not allowed to open the camera device
+    PermissionDenied,
 }
 
 /// Recording-specific errors
@@ -52,6 +71,8 @@ pub enum RecordingError {
     StopFailed(String),
     /// Encoder not available
     EncoderNotAvailable(String),
+    /// The encoder failed or a plugin it needs is missing
+    EncoderFailed(String),
     /// No audio device available
     NoAudioDevice,
     /// Recording already in progress
@@ -73,14 +94,124 @@ pub enum PhotoError {
     SaveFailed(String),
 }
 
+/// Errors writing captures to disk
+#[derive(Debug, Clone)]
+pub enum StorageError {
+    /// No space left on the disk
+    DiskFull(String),
+    /// Not allowed to write to the save folder, e.g. outside the sandbox's
+    /// reach
+    PermissionDenied(String),
+    /// The save folder is on a read-only file system
+    ReadOnly(String),
+    /// Any other I/O failure
+    Io(String),
+}
+
+/// Desktop portal errors
+#[derive(Debug, Clone)]
+pub enum PortalError {
+    /// No portal implements the interface, e.g. "ScreenCast"
+    Missing(String),
+}
+
+impl StorageError {
+    /// Sort an I/O error on `path` by what can be done about it
+    pub fn from_io(err: &std::io::Error, path: &Path) -> Self {
+        Self::sorted(err.kind(), format!("{}: {}", path.display(), err))
+    }
+
+    fn sorted(kind: ErrorKind, detail: String) -> Self {
+        match kind {
+            ErrorKind::StorageFull => Self::DiskFull(detail),
+            ErrorKind::PermissionDenied => Self::PermissionDenied(detail),
+            ErrorKind::ReadOnlyFilesystem => Self::ReadOnly(detail),
+            _ => Self::Io(detail),
+        }
+    }
+}
+
+impl AppError {
+    /// Sort an error a recording pipeline posted on its bus by its
+    /// GStreamer domain and code
+    pub fn from_recording_pipeline(err: &glib::Error) -> Self {
+        use gstreamer::{CoreError, ResourceError, StreamError};
+
+        let detail = err.to_string();
+        if let Some(code) = err.kind::<ResourceError>() {
+            return match code {
+                ResourceError::NoSpaceLeft => StorageError::DiskFull(detail).into(),
+                ResourceError::NotAuthorized => StorageError::PermissionDenied(detail).into(),
+                ResourceError::OpenWrite | ResourceError::Write | ResourceError::Close => {
+                    StorageError::Io(detail).into()
+                }
+                _ => RecordingError::PipelineError(detail).into(),
+            };
+        }
+        let encoder_failed = matches!(err.kind::<CoreError>(), Some(CoreError::MissingPlugin))
+            || matches!(
+                err.kind::<StreamError>(),
+                Some(StreamError::Encode | StreamError::CodecNotFound)
+            );
+        if encoder_failed {
+            RecordingError::EncoderFailed(detail).into()
+        } else {
+            RecordingError::PipelineError(detail).into()
+        }
+    }
+
+    /// One line for the UI saying what went wrong
+    pub fn summary(&self) -> String {
+        match self {
+            Self::Camera(CameraError::NoCameraFound) => fl!("error-no-camera"),
+            Self::Camera(CameraError::Disconnected) => fl!("error-camera-disconnected"),
+            Self::Camera(CameraError::Busy) => fl!("error-camera-busy"),
+            Self::Camera(CameraError::PermissionDenied) => fl!("error-camera-permission"),
+            Self::Camera(_) => fl!("error-camera-failed"),
+            Self::Recording(
+                RecordingError::EncoderNotAvailable(_) | RecordingError::EncoderFailed(_),
+            ) => fl!("error-encoder"),
+            Self::Recording(_) => fl!("error-recording-failed"),
+            Self::Photo(_) => fl!("error-photo-failed"),
+            Self::Storage(StorageError::DiskFull(_)) => fl!("error-disk-full"),
+            Self::Storage(StorageError::PermissionDenied(_) | StorageError::ReadOnly(_)) => {
+                fl!("error-folder-not-writable")
+            }
+            Self::Storage(StorageError::Io(_)) => fl!("error-save-failed"),
+            Self::Portal(PortalError::Missing(_)) => fl!("error-portal-missing"),
+            Self::Config(_) | Self::Other(_) => fl!("error-generic"),
+        }
+    }
+
+    /// What the user can do about it, when that is known
+    pub fn remedy(&self) -> Option<String> {
+        Some(match self {
+            Self::Camera(CameraError::Disconnected) => fl!("error-camera-disconnected-remedy"),
+            Self::Camera(CameraError::Busy) => fl!("error-camera-busy-remedy"),
+            Self::Camera(CameraError::PermissionDenied) => fl!("error-camera-permission-remedy"),
+            Self::Recording(
+                RecordingError::EncoderNotAvailable(_) | RecordingError::EncoderFailed(_),
+            ) => fl!("error-encoder-remedy"),
+            Self::Storage(StorageError::DiskFull(_)) => fl!("error-disk-full-remedy"),
+            Self::Storage(StorageError::PermissionDenied(_)) => {
+                fl!("error-folder-permission-remedy")
+            }
+            Self::Storage(StorageError::ReadOnly(_)) => fl!("error-folder-read-only-remedy"),
+            Self::Portal(PortalError::Missing(_)) => fl!("error-portal-missing-remedy"),
+            _ => return None,
+        })
+    }
+}
+
 impl fmt::Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             AppError::Camera(e) => write!(f, "Camera error: {}", e),
             AppError::Recording(e) => write!(f, "Recording error: {}", e),
             AppError::Photo(e) => write!(f, "Photo error: {}", e),
+            AppError::Storage(e) => write!(f, "Storage error: {}", e),
+            AppError::Portal(e) => write!(f, "Portal error: {}", e),
             AppError::Config(msg) => write!(f, "Configuration error: {}", msg),
-            AppError::Storage(msg) => write!(f, "Storage error: {}", msg),
             AppError::Other(msg) => write!(f, "{}", msg),
         }
     }
@@ -95,6 +226,7 @@ impl fmt::Display for CameraError {
             CameraError::InvalidFormat(msg) => write!(f, "Invalid format: {}", msg),
             CameraError::BackendError(msg) => write!(f, "Backend error: {}", msg),
             CameraError::Busy => write!(f, "Camera is busy"),
+            CameraError::PermissionDenied => write!(f, "Permission to open the camera denied"),
         }
     }
 }
@@ -105,6 +237,7 @@ impl fmt::Display for RecordingError {
             RecordingError::StartFailed(msg) => write!(f, "Failed to start recording: {}", msg),
             RecordingError::StopFailed(msg) => write!(f, "Failed to stop recording: {}", msg),
             RecordingError::EncoderNotAvailable(msg) => write!(f, "Encoder not available: {}", msg),
+            RecordingError::EncoderFailed(msg) => write!(f, "Encoder failed: {}", msg),
             RecordingError::NoAudioDevice => write!(f, "No audio device available"),
             RecordingError::AlreadyRecording => write!(f, "Recording already in progress"),
             RecordingError::PipelineError(msg) => write!(f, "Pipeline error: {}", msg),
@@ -123,13 +256,34 @@ impl fmt::Display for PhotoError {
     }
 }
 
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::DiskFull(msg) => write!(f, "Disk full: {}", msg),
+            StorageError::PermissionDenied(msg) => write!(f, "Permission denied: {}", msg),
+            StorageError::ReadOnly(msg) => write!(f, "Read-only file system: {}", msg),
+            StorageError::Io(msg) => write!(f, "I/O error: {}", msg),
+        }
+    }
+}
+
+impl fmt::Display for PortalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PortalError::Missing(interface) => write!(f, "{} portal not available", interface),
+        }
+    }
+}
+
 impl std::error::Error for AppError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             AppError::Camera(e) => Some(e),
             AppError::Recording(e) => Some(e),
             AppError::Photo(e) => Some(e),
-            AppError::Config(_) | AppError::Storage(_) | AppError::Other(_) => None,
+            AppError::Storage(e) => Some(e),
+            AppError::Portal(e) => Some(e),
+            AppError::Config(_) | AppError::Other(_) => None,
         }
     }
 }
@@ -137,6 +291,8 @@ impl std::error::Error for AppError {
 impl std::error::Error for CameraError {}
 impl std::error::Error for RecordingError {}
 impl std::error::Error for PhotoError {}
+impl std::error::Error for StorageError {}
+impl std::error::Error for PortalError {}
 
 // Conversions from sub-errors to AppError
 impl From<CameraError> for AppError {
@@ -157,6 +313,32 @@ impl From<PhotoError> for AppError {
     }
 }
 
+impl From<StorageError> for AppError {
+    fn from(err: StorageError) -> Self {
+        AppError::Storage(err)
+    }
+}
+
+impl From<PortalError> for AppError {
+    fn from(err: PortalError) -> Self {
+        AppError::Portal(err)
+    }
+}
+
+impl From<BackendError> for AppError {
+    fn from(err: BackendError) -> Self {
+        let camera = match err {
+            BackendError::Busy(_) => CameraError::Busy,
+            BackendError::PermissionDenied(_) => CameraError::PermissionDenied,
+            BackendError::DeviceNotFound(_) => CameraError::Disconnected,
+            BackendError::FormatNotSupported(msg) => CameraError::InvalidFormat(msg),
+            BackendError::InitializationFailed(msg) => CameraError::InitializationFailed(msg),
+            other => CameraError::BackendError(other.to_string()),
+        };
+        AppError::Camera(camera)
+    }
+}
+
 // Conversion from String for backward compatibility
 impl From<String> for AppError {
     fn from(msg: String) -> Self {
@@ -170,10 +352,17 @@ impl From<&str> for AppError {
     }
 }
 
+// And back, for callers still returning `Result<_, String>`
+impl From<AppError> for String {
+    fn from(err: AppError) -> Self {
+        err.to_string()
+    }
+}
+
 // Conversions for I/O errors
 impl From<std::io::Error> for AppError {
     fn from(err: std::io::Error) -> Self {
-        AppError::Storage(err.to_string())
+        AppError::Storage(StorageError::sorted(err.kind(), err.to_string()))
     }
 }
 
@@ -182,3 +371,84 @@ impl From<std::io::Error> for PhotoError {
         PhotoError::SaveFailed(err.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn os_error(code: i32) -> std::io::Error {
+        std::io::Error::from_raw_os_error(code)
+    }
+
+    #[test]
+    fn io_errors_are_sorted_by_cause() {
+        let path = Path::new("/home/user/Pictures/Camera");
+        // ENOSPC, EACCES, EROFS, EIO
+        assert!(matches!(
+            StorageError::from_io(&os_error(28), path),
+            StorageError::DiskFull(_)
+        ));
+        assert!(matches!(
+            StorageError::from_io(&os_error(13), path),
+            StorageError::PermissionDenied(_)
+        ));
+        assert!(matches!(
+            StorageError::from_io(&os_error(30), path),
+            StorageError::ReadOnly(detail) if detail.starts_with("/home/user/Pictures/Camera: ")
+        ));
+        assert!(matches!(
+            AppError::from(os_error(5)),
+            AppError::Storage(StorageError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn backend_errors_keep_busy_and_permission_apart() {
+        assert!(matches!(
+            AppError::from(BackendError::Busy("acquire".into())),
+            AppError::Camera(CameraError::Busy)
+        ));
+        assert!(matches!(
+            AppError::from(BackendError::PermissionDenied("acquire".into())),
+            AppError::Camera(CameraError::PermissionDenied)
+        ));
+        assert!(matches!(
+            AppError::from(BackendError::Crashed("gone".into())),
+            AppError::Camera(CameraError::BackendError(_))
+        ));
+    }
+
+    #[test]
+    fn pipeline_errors_are_sorted_by_domain() {
+        use gstreamer::{CoreError, ResourceError, StreamError};
+
+        let sorted = |err: glib::Error| AppError::from_recording_pipeline(&err);
+        assert!(matches!(
+            sorted(glib::Error::new(ResourceError::NoSpaceLeft, "No space")),
+            AppError::Storage(StorageError::DiskFull(_))
+        ));
+        assert!(matches!(
+            sorted(glib::Error::new(StreamError::Encode, "Encode failed")),
+            AppError::Recording(RecordingError::EncoderFailed(_))
+        ));
+        assert!(matches!(
+            sorted(glib::Error::new(CoreError::MissingPlugin, "No x264enc")),
+            AppError::Recording(RecordingError::EncoderFailed(_))
+        ));
+        assert!(matches!(
+            sorted(glib::Error::new(StreamError::Failed, "Internal data flow")),
+            AppError::Recording(RecordingError::PipelineError(_))
+        ));
+    }
+
+    #[test]
+    fn actionable_errors_have_a_remedy() {
+        assert!(AppError::from(CameraError::Busy).remedy().is_some());
+        assert!(
+            AppError::from(StorageError::DiskFull(String::new()))
+                .remedy()
+                .is_some()
+        );
+        assert!(AppError::Other("unknown".into()).remedy().is_none());
+    }
+}
@@ -1,15 +1,31 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-//! Hardware flash LED control via Linux sysfs
+//! Hardware flash LED control via Linux sysfs or V4L2
 //!
 //! Discovers and controls flash LEDs exposed at `/sys/class/leds/*:flash`.
-//! Uses torch mode (brightness file) which is group-writable by `feedbackd`,
-//! avoiding the root-only `flash_strobe`/`flash_brightness` interface.
+//! Uses torch mode (brightness file) which is group-writable by `feedbackd`;
+//! the `flash_strobe`/`flash_brightness` interface is only used where it is
+//! writable too, which usually means root.
+//!
+//! Phones whose flash driver only registers a V4L2 flash sub-device
+//! (`/dev/v4l-subdev*` with the flash control class) are driven through
+//! its controls instead. Either way a photo's flash is a hardware strobe
+//! when the chip's timeout is long enough to cover it, so the LED goes out
+//! on time even if the app doesn't, and the torch otherwise.
 
+use crate::backends::camera::v4l2_controls::{
+    self, V4L2_CID_FLASH_INTENSITY, V4L2_CID_FLASH_LED_MODE, V4L2_CID_FLASH_STROBE,
+    V4L2_CID_FLASH_STROBE_SOURCE, V4L2_CID_FLASH_STROBE_STOP, V4L2_CID_FLASH_TIMEOUT,
+    V4L2_CID_FLASH_TORCH_INTENSITY, V4L2_FLASH_LED_MODE_FLASH, V4L2_FLASH_LED_MODE_NONE,
+    V4L2_FLASH_LED_MODE_TORCH, V4L2_FLASH_STROBE_SOURCE_SOFTWARE,
+};
+use std::fs::File;
 use std::io;
 use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
-use tracing::{info, warn};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
 
 /// Flash operating mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -34,15 +50,55 @@ impl FlashMode {
     }
 }
 
-/// A flash LED device discovered via sysfs
+/// A flash LED device discovered via sysfs or V4L2
 #[derive(Debug, Clone)]
 pub struct FlashDevice {
-    /// Sysfs path, e.g. `/sys/class/leds/white:flash`
-    path: PathBuf,
-    /// Maximum brightness value (from `max_brightness` file)
-    max_brightness: u32,
-    /// Human-readable name (directory basename)
+    /// Human-readable name (LED directory basename or sub-device node)
     name: String,
+    control: FlashControl,
+}
+
+/// How a flash LED is driven
+#[derive(Debug, Clone)]
+enum FlashControl {
+    /// LED class device
+    Led {
+        /// Sysfs path, e.g. `/sys/class/leds/white:flash`
+        path: PathBuf,
+        /// Maximum brightness value (from `max_brightness` file)
+        max_brightness: u32,
+        /// Strobe limits, when the flash files are writable
+        strobe: Option<StrobeLimits>,
+    },
+    /// V4L2 flash sub-device, kept open for as long as the app runs: some
+    /// drivers turn the LED off when the last handle closes
+    V4l2 {
+        /// Device node, e.g. `/dev/v4l-subdev3`
+        path: String,
+        file: Arc<File>,
+        /// Maximum `V4L2_CID_FLASH_TORCH_INTENSITY`
+        torch_max: u32,
+        strobe: Option<StrobeLimits>,
+    },
+}
+
+/// What a hardware-timed strobe can do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct StrobeLimits {
+    /// Flash intensity at full, in driver units
+    max_intensity: u32,
+    /// Longest timeout the chip accepts
+    max_timeout_us: u32,
+}
+
+impl StrobeLimits {
+    /// Timeout for a strobe lasting `duration`, if the chip can hold it that
+    /// long
+    fn timeout_us(&self, duration: Duration) -> Option<u32> {
+        u32::try_from(duration.as_micros())
+            .ok()
+            .filter(|&us| us <= self.max_timeout_us)
+    }
 }
 
 impl FlashDevice {
@@ -51,22 +107,113 @@ impl FlashDevice {
         &self.name
     }
 
-    /// Set raw brightness value (0 = off, max_brightness = full)
-    pub fn set_brightness(&self, value: u32) -> io::Result<()> {
-        let clamped = value.min(self.max_brightness);
-        std::fs::write(self.path.join("brightness"), clamped.to_string())
-    }
-
-    /// Turn off the LED
+    /// Turn off the LED, stopping a strobe still running
     pub fn off(&self) -> io::Result<()> {
-        self.set_brightness(0)
+        match &self.control {
+            FlashControl::Led { path, strobe, .. } => {
+                if strobe.is_some() {
+                    std::fs::write(path.join("flash_strobe"), "0")?;
+                }
+                std::fs::write(path.join("brightness"), "0")
+            }
+            FlashControl::V4l2 {
+                path, file, strobe, ..
+            } => {
+                if strobe.is_some() {
+                    // Fails when no strobe is running, which is fine
+                    let _ =
+                        v4l2_controls::set_control_on(file, path, V4L2_CID_FLASH_STROBE_STOP, 1);
+                }
+                v4l2_controls::set_control_on(
+                    file,
+                    path,
+                    V4L2_CID_FLASH_LED_MODE,
+                    V4L2_FLASH_LED_MODE_NONE,
+                )
+                .map_err(io::Error::other)
+            }
+        }
     }
 
     /// Turn on at a fraction of max brightness (0.0 = off, 1.0 = full)
     pub fn torch(&self, intensity: f32) -> io::Result<()> {
         let clamped = intensity.clamp(0.0, 1.0);
-        let value = (clamped * self.max_brightness as f32).round() as u32;
-        self.set_brightness(value)
+        match &self.control {
+            FlashControl::Led {
+                path,
+                max_brightness,
+                ..
+            } => {
+                let value = (clamped * *max_brightness as f32).round() as u32;
+                std::fs::write(path.join("brightness"), value.to_string())
+            }
+            FlashControl::V4l2 { .. } if clamped == 0.0 => self.off(),
+            FlashControl::V4l2 {
+                path,
+                file,
+                torch_max,
+                ..
+            } => {
+                let value = (clamped * *torch_max as f32).round().max(1.0) as i32;
+                v4l2_controls::set_control_on(
+                    file,
+                    path,
+                    V4L2_CID_FLASH_LED_MODE,
+                    V4L2_FLASH_LED_MODE_TORCH,
+                )
+                .and_then(|()| {
+                    v4l2_controls::set_control_on(file, path, V4L2_CID_FLASH_TORCH_INTENSITY, value)
+                })
+                .map_err(io::Error::other)
+            }
+        }
+    }
+
+    /// Strobe the flash at full intensity for `duration`, timed by the
+    /// chip. `Ok(false)` when it can't strobe for that long (or at all).
+    pub fn strobe(&self, duration: Duration) -> io::Result<bool> {
+        match &self.control {
+            FlashControl::Led {
+                path,
+                strobe: Some(limits),
+                ..
+            } => {
+                let Some(timeout) = limits.timeout_us(duration) else {
+                    return Ok(false);
+                };
+                std::fs::write(
+                    path.join("flash_brightness"),
+                    limits.max_intensity.to_string(),
+                )?;
+                std::fs::write(path.join("flash_timeout"), timeout.to_string())?;
+                std::fs::write(path.join("flash_strobe"), "1")?;
+                Ok(true)
+            }
+            FlashControl::V4l2 {
+                path,
+                file,
+                strobe: Some(limits),
+                ..
+            } => {
+                let Some(timeout) = limits.timeout_us(duration) else {
+                    return Ok(false);
+                };
+                let set = |id, value: i32| v4l2_controls::set_control_on(file, path, id, value);
+                set(V4L2_CID_FLASH_LED_MODE, V4L2_FLASH_LED_MODE_FLASH)
+                    .and_then(|()| {
+                        set(
+                            V4L2_CID_FLASH_STROBE_SOURCE,
+                            V4L2_FLASH_STROBE_SOURCE_SOFTWARE,
+                        )
+                    })
+                    .and_then(|()| set(V4L2_CID_FLASH_TIMEOUT, timeout as i32))
+                    .and_then(|()| set(V4L2_CID_FLASH_INTENSITY, limits.max_intensity as i32))
+                    .and_then(|()| set(V4L2_CID_FLASH_STROBE, 1))
+                    .map_err(io::Error::other)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
     }
 }
 
@@ -143,11 +290,20 @@ impl FlashHardware {
                 .open(&brightness_path)
             {
                 Ok(_) => {
-                    info!(name = name_str, max_brightness, "Discovered flash LED");
-                    devices.push(FlashDevice {
-                        path: led_path,
+                    let strobe = led_strobe_limits(&led_path);
+                    info!(
+                        name = name_str,
                         max_brightness,
+                        strobe = strobe.is_some(),
+                        "Discovered flash LED"
+                    );
+                    devices.push(FlashDevice {
                         name: name_str.to_string(),
+                        control: FlashControl::Led {
+                            path: led_path,
+                            max_brightness,
+                            strobe,
+                        },
                     });
                 }
                 Err(_) => {
@@ -162,6 +318,12 @@ impl FlashHardware {
 
         devices.sort_by(|a, b| a.name.cmp(&b.name));
 
+        // The V4L2 flash interface locks the LED class files while open,
+        // so it is only a fallback for LEDs sysfs can't drive
+        if devices.is_empty() {
+            devices = detect_v4l2_flash();
+        }
+
         // Build permission error message if we found hardware but can't write
         let permission_error = if !permission_failures.is_empty() && devices.is_empty() {
             Some(Self::build_permission_error(&permission_failures))
@@ -224,6 +386,113 @@ impl FlashHardware {
     }
 }
 
+/// Strobe limits of an LED class device whose flash files are writable
+fn led_strobe_limits(led_path: &Path) -> Option<StrobeLimits> {
+    let read = |name: &str| -> Option<u32> {
+        std::fs::read_to_string(led_path.join(name))
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+    };
+    let writable = |name: &str| {
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(led_path.join(name))
+            .is_ok()
+    };
+    if !["flash_strobe", "flash_brightness", "flash_timeout"]
+        .iter()
+        .all(|name| writable(name))
+    {
+        return None;
+    }
+    Some(StrobeLimits {
+        max_intensity: read("max_flash_brightness")?,
+        max_timeout_us: read("max_flash_timeout")?,
+    })
+}
+
+/// Flash sub-devices among `/dev/v4l-subdev*` that can be opened for writing
+fn detect_v4l2_flash() -> Vec<FlashDevice> {
+    let Ok(entries) = std::fs::read_dir("/dev") else {
+        return Vec::new();
+    };
+    let mut devices = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !name.starts_with("v4l-subdev") {
+            continue;
+        }
+        let path = entry.path().to_string_lossy().into_owned();
+        if v4l2_controls::query_control(&path, V4L2_CID_FLASH_LED_MODE).is_none() {
+            continue;
+        }
+        let file = match std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+        {
+            Ok(file) => file,
+            Err(e) => {
+                warn!(path = %path, error = %e, "V4L2 flash found but not writable");
+                continue;
+            }
+        };
+        let max = |id| {
+            v4l2_controls::query_control(&path, id)
+                .filter(|info| !info.is_disabled() && info.maximum > 0)
+                .map(|info| info.maximum as u32)
+        };
+        let Some(torch_max) = max(V4L2_CID_FLASH_TORCH_INTENSITY) else {
+            debug!(path = %path, "V4L2 flash without torch mode, skipped");
+            continue;
+        };
+        let strobe = if v4l2_controls::has_control(&path, V4L2_CID_FLASH_STROBE) {
+            max(V4L2_CID_FLASH_INTENSITY)
+                .zip(max(V4L2_CID_FLASH_TIMEOUT))
+                .map(|(max_intensity, max_timeout_us)| StrobeLimits {
+                    max_intensity,
+                    max_timeout_us,
+                })
+        } else {
+            None
+        };
+        info!(
+            path = %path,
+            torch_max,
+            strobe = strobe.is_some(),
+            "Discovered V4L2 flash"
+        );
+        devices.push(FlashDevice {
+            name,
+            control: FlashControl::V4l2 {
+                path,
+                file: Arc::new(file),
+                torch_max,
+                strobe,
+            },
+        });
+    }
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    devices
+}
+
+/// Fire the flash for a photo lasting `duration`: a hardware strobe where
+/// the chip can hold one that long, the torch at full otherwise
+pub fn all_fire(devices: &[FlashDevice], duration: Duration) {
+    for dev in devices {
+        match dev.strobe(duration) {
+            Ok(true) => continue,
+            Ok(false) => {}
+            Err(e) => warn!(device = %dev.name, error = %e, "Flash strobe failed, using torch"),
+        }
+        if let Err(e) = dev.torch(1.0) {
+            warn!(device = %dev.name, error = %e, "Failed to turn on flash LED");
+        }
+    }
+}
+
 /// Turn on all discovered flash devices at full brightness
 pub fn all_on(devices: &[FlashDevice]) {
     for dev in devices {
@@ -241,3 +510,21 @@ pub fn all_off(devices: &[FlashDevice]) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strobes_longer_than_the_chip_allows_fall_back() {
+        let limits = StrobeLimits {
+            max_intensity: 1000,
+            max_timeout_us: 1_500_000,
+        };
+        assert_eq!(
+            limits.timeout_us(Duration::from_millis(1250)),
+            Some(1_250_000)
+        );
+        assert_eq!(limits.timeout_us(Duration::from_secs(2)), None);
+    }
+}
@@ -35,6 +35,9 @@ pub struct GpuDeviceInfo {
     pub backend: wgpu::Backend,
     /// Whether low-priority queue was successfully configured (always false now)
     pub low_priority_enabled: bool,
+    /// Key under which compiled pipelines may be cached on disk, `None` when
+    /// the device can't tell or lacks `PIPELINE_CACHE`
+    pub pipeline_cache_key: Option<String>,
 }
 
 /// Shared GPU context holding a single device and queue for all compute pipelines.
@@ -90,6 +93,7 @@ pub fn try_seed_shared_gpu_from_renderer(device: Arc<wgpu::Device>, queue: Arc<w
             adapter_name: "renderer-shared".to_string(),
             backend: wgpu::Backend::Vulkan,
             low_priority_enabled: false,
+            pipeline_cache_key: None,
         },
     };
     match SHARED_GPU.set(Ok(ctx)) {
@@ -187,7 +191,8 @@ async fn create_low_priority_compute_device(
     let (device, queue) = adapter
         .request_device(&wgpu::DeviceDescriptor {
             label: Some(label),
-            required_features: adapter.features() & wgpu::Features::TEXTURE_FORMAT_16BIT_NORM,
+            required_features: adapter.features()
+                & (wgpu::Features::TEXTURE_FORMAT_16BIT_NORM | wgpu::Features::PIPELINE_CACHE),
             required_limits: adapter_limits.clone(),
             memory_hints: wgpu::MemoryHints::Performance,
             trace: wgpu::Trace::Off,
@@ -200,6 +205,11 @@ async fn create_low_priority_compute_device(
         adapter_name: adapter_info.name.clone(),
         backend: adapter_info.backend,
         low_priority_enabled: false,
+        pipeline_cache_key: device
+            .features()
+            .contains(wgpu::Features::PIPELINE_CACHE)
+            .then(|| wgpu::util::pipeline_cache_key(&adapter_info))
+            .flatten(),
     };
 
     Ok((Arc::new(device), Arc::new(queue), info))
@@ -24,7 +24,9 @@
 //! ```
 
 pub mod app;
+pub mod automation;
 pub mod backends;
+pub mod backlight;
 pub mod bug_report;
 pub mod config;
 pub mod constants;
@@ -35,11 +37,15 @@ pub mod i18n;
 pub mod media;
 pub mod network_manager;
 pub mod pipelines;
+pub mod qr_history;
 pub mod shaders;
+pub mod share;
+pub mod sound;
 pub mod storage;
 pub mod terminal;
 #[cfg(test)]
 pub(crate) mod test_gpu;
+pub mod upload;
 
 // Re-export commonly used types
 pub use app::frame_processor::{QrAction, QrDetection};
@@ -1,11 +1,17 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use camera::app::AppModel;
+use camera::backends::virtual_camera::{LoopMode, PlaybackOptions, SimulatedCapture};
+use camera::config::VideoStabilization;
 use camera::i18n;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
+use std::time::Duration;
 
+mod bench;
 mod cli;
+mod daemon;
+mod selftest;
 
 #[derive(Parser)]
 #[command(name = "camera")]
@@ -18,10 +24,33 @@ struct Cli {
 
     /// Use an image or video file as the camera preview source instead of a real camera.
     /// Useful for testing, demos, or taking screenshots with consistent content.
-    /// Supported formats: PNG, JPG, JPEG, WEBP (images) or MP4, WEBM, MKV (videos)
-    #[arg(long, value_name = "FILE")]
+    /// Supported formats: PNG, JPG, JPEG, WEBP (images) or MP4, WEBM, MKV (videos).
+    /// A directory plays its images and videos one after another, in name order.
+    #[arg(long, value_name = "FILE|DIR")]
     preview_source: Option<PathBuf>,
 
+    /// What the preview source does at its end: `all` starts the directory
+    /// (or file) over, `one` repeats the current file, `once` stops on the
+    /// last frame
+    #[arg(long, value_name = "all|one|once", default_value = "all")]
+    preview_loop: LoopMode,
+
+    /// Playback speed of the preview source, e.g. `0.5` or `2`; also
+    /// scales how long directory images are shown
+    #[arg(long, value_name = "RATE", default_value = "1", value_parser = parse_playback_rate)]
+    preview_rate: f64,
+
+    /// How long each image of a preview source directory is shown
+    #[arg(long, value_name = "SECONDS", default_value = "5", value_parser = parse_image_duration)]
+    preview_image_duration: Duration,
+
+    /// Make the preview source imitate a camera capturing at this size and
+    /// framerate, formatted as `WIDTHxHEIGHT[@FPS]` (e.g. `1280x720@30`).
+    /// Frames are cropped and scaled to it, and `--preview-fake-camera`
+    /// offers it as the only format.
+    #[arg(long, value_name = "WIDTHxHEIGHT[@FPS]")]
+    preview_capture: Option<SimulatedCapture>,
+
     /// Override the preview-mode window size, formatted as `WIDTHxHEIGHT`
     /// (e.g. `400x880` for a modern Linux-phone aspect). Only takes effect
     /// alongside `--preview-source`. Defaults to 900x700.
@@ -31,7 +60,7 @@ struct Cli {
     /// Preview harness only: boot straight into Video mode showing an active
     /// recording indicator, without starting the encoder. Lets the screenshot
     /// harness capture the "recording in progress" state without spending CI
-    /// resources on a real encode. The elapsed timer shows a fixed placeholder.
+    /// resources on a real encode. The app clock is frozen so the timer always reads 00:07.
     #[arg(long)]
     preview_spoof_recording: bool,
 
@@ -42,6 +71,18 @@ struct Cli {
     /// on any runner. Meaningful only alongside `--preview-source`.
     #[arg(long)]
     preview_fake_camera: bool,
+
+    /// Stream small copies of the preview and pipeline statistics to a local
+    /// socket, for `scripts/frame-tap.py` to inspect colour or stride issues
+    #[arg(long)]
+    frame_tap: bool,
+
+    /// Developer builds only: read Rhai commands from stdin while the app
+    /// runs, e.g. `switch_camera(1); capture()`. Piped input runs as one
+    /// script.
+    #[cfg(feature = "dev-console")]
+    #[arg(long)]
+    console: bool,
 }
 
 fn parse_window_size(s: &str) -> Result<(f32, f32), String> {
@@ -65,6 +106,25 @@ fn parse_window_size(s: &str) -> Result<(f32, f32), String> {
     Ok((width, height))
 }
 
+fn parse_playback_rate(s: &str) -> Result<f64, String> {
+    let rate: f64 = s.trim().parse().map_err(|e| format!("invalid rate: {e}"))?;
+    if !(rate.is_finite() && rate > 0.0) {
+        return Err("playback rate must be positive".to_string());
+    }
+    Ok(rate)
+}
+
+fn parse_image_duration(s: &str) -> Result<Duration, String> {
+    let seconds: f64 = s
+        .trim()
+        .parse()
+        .map_err(|e| format!("invalid duration: {e}"))?;
+    Duration::try_from_secs_f64(seconds)
+        .ok()
+        .filter(|duration| !duration.is_zero())
+        .ok_or_else(|| "image duration must be positive".to_string())
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Run in terminal mode (renders camera to terminal)
@@ -73,6 +133,13 @@ enum Commands {
     /// List available cameras
     List,
 
+    /// Open full screen as a mirror, with almost no controls
+    Mirror {
+        /// Light the face with the screen: a white border and full brightness
+        #[arg(short, long)]
+        light: bool,
+    },
+
     /// Take a photo
     Photo {
         /// Camera index to use (from 'camera list')
@@ -103,6 +170,108 @@ enum Commands {
         audio: bool,
     },
 
+    /// Capture a burst and merge it through the HDR+ pipeline
+    Burst {
+        /// Camera index to use (from 'camera list')
+        #[arg(short, long, default_value = "0")]
+        camera: usize,
+
+        /// Number of frames to capture and merge (2-50)
+        #[arg(short, long, default_value = "8")]
+        frames: usize,
+
+        /// Output directory (default: ~/Pictures/camera)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Record a timelapse video
+    Timelapse {
+        /// Camera index to use (from 'camera list')
+        #[arg(short, long, default_value = "0")]
+        camera: usize,
+
+        /// Seconds between captured frames
+        #[arg(short, long, default_value = "1")]
+        interval: f64,
+
+        /// Capture duration in seconds
+        #[arg(short, long, default_value = "60")]
+        duration: u64,
+
+        /// Output file path (default: ~/Videos/camera/timelapse_TIMESTAMP.mp4)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Run headless, taking scheduled or motion-triggered photos and running automation rules
+    Daemon {
+        /// Camera index to use (from 'camera list')
+        #[arg(short, long, default_value = "0")]
+        camera: usize,
+
+        /// Take a photo every N seconds
+        #[arg(short, long)]
+        interval: Option<u64>,
+
+        /// Take a photo whenever motion is detected
+        #[arg(short, long)]
+        motion: bool,
+
+        /// Fraction of the frame that must change to count as motion (0.0-1.0)
+        #[arg(long, default_value = "0.05")]
+        motion_threshold: f32,
+
+        /// Minimum seconds between motion-triggered photos
+        #[arg(long, default_value = "10")]
+        cooldown: u64,
+
+        /// Output directory (default: the photo folder configured in the app)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Automation rules file (default: the rules edited in the app's settings)
+        #[arg(long)]
+        rules: Option<PathBuf>,
+    },
+
+    /// Measure pipeline performance on this machine and print a report
+    Bench {
+        /// Camera index to use (from 'camera list')
+        #[arg(short, long, default_value = "0")]
+        camera: usize,
+
+        /// Seconds to measure capture latency for
+        #[arg(short, long, default_value = "5")]
+        seconds: u64,
+
+        /// Skip the capture latency measurement
+        #[arg(long)]
+        no_camera: bool,
+    },
+
+    /// Take a photo and record a short video without a window, then check
+    /// both files decode completely
+    Selftest {
+        /// Read frames from a v4l2loopback device (created if there is none)
+        /// instead of a synthetic test pattern
+        #[arg(long)]
+        loopback: bool,
+
+        /// Length of the test recording in seconds
+        #[arg(short, long, default_value = "3")]
+        seconds: u64,
+
+        /// Directory for the test files (default: a temporary directory,
+        /// removed after a successful run)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Keep the test files even when every step passed
+        #[arg(long)]
+        keep: bool,
+    },
+
     /// Process images through computational photography pipelines
     Process {
         #[command(subcommand)]
@@ -122,6 +291,59 @@ enum ProcessMode {
         #[arg(short, long)]
         output: Option<PathBuf>,
     },
+
+    /// Extract stills from a recorded video, at an interval or at scene changes
+    Stills {
+        /// Video file to extract stills from
+        input: PathBuf,
+
+        /// Seconds between stills
+        #[arg(short, long, default_value = "60", conflicts_with = "scenes")]
+        interval: f64,
+
+        /// Take a still whenever the scene changes instead of at an interval
+        #[arg(short, long)]
+        scenes: bool,
+
+        /// Output directory (default: ~/Pictures/camera)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Write a stabilized copy of a recorded video
+    Stabilize {
+        /// Video file to stabilize
+        input: PathBuf,
+
+        /// How strongly to steady the picture
+        #[arg(short, long, value_enum, default_value_t = StabilizeStrength::Medium)]
+        strength: StabilizeStrength,
+
+        /// Output directory (default: next to the input)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Strength for `process stabilize`; stabilization is never off here
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum StabilizeStrength {
+    /// Steadies small hand shake
+    Low,
+    /// Steadies walking and handheld pans
+    Medium,
+    /// Holds the frame as still as the crop allows
+    High,
+}
+
+impl From<StabilizeStrength> for VideoStabilization {
+    fn from(strength: StabilizeStrength) -> Self {
+        match strength {
+            StabilizeStrength::Low => VideoStabilization::Low,
+            StabilizeStrength::Medium => VideoStabilization::Medium,
+            StabilizeStrength::High => VideoStabilization::High,
+        }
+    }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -153,6 +375,31 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     tracing::info!("camera app starting");
 
+    #[cfg(feature = "dev-console")]
+    let dev_console = cli.console;
+    #[cfg(not(feature = "dev-console"))]
+    let dev_console = false;
+
+    let preview_playback = PlaybackOptions {
+        loop_mode: cli.preview_loop,
+        rate: cli.preview_rate,
+        image_duration: cli.preview_image_duration,
+        capture: cli.preview_capture,
+    };
+
+    // `mirror` is set in mirror mode, to whether its screen light was asked for
+    let gui_flags = |mirror: Option<bool>| camera::app::AppFlags {
+        preview_source: cli.preview_source,
+        preview_playback,
+        preview_spoof_recording: cli.preview_spoof_recording,
+        preview_fake_camera: cli.preview_fake_camera,
+        dev_console,
+        frame_tap: cli.frame_tap,
+        mirror: mirror.is_some(),
+        mirror_light: mirror == Some(true),
+        prewarm: None,
+    };
+
     match cli.command {
         Some(Commands::Terminal) => camera::terminal::run(),
         Some(Commands::List) => cli::list_cameras(),
@@ -163,23 +410,78 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             output,
             audio,
         }) => cli::record_video(camera, duration, output, audio),
+        Some(Commands::Burst {
+            camera,
+            frames,
+            output,
+        }) => cli::capture_burst(camera, frames, output),
+        Some(Commands::Timelapse {
+            camera,
+            interval,
+            duration,
+            output,
+        }) => cli::record_timelapse(camera, interval, duration, output),
+        Some(Commands::Daemon {
+            camera,
+            interval,
+            motion,
+            motion_threshold,
+            cooldown,
+            output,
+            rules,
+        }) => daemon::run(daemon::DaemonOptions {
+            camera_index: camera,
+            interval,
+            motion,
+            motion_threshold,
+            cooldown,
+            output,
+            rules,
+        }),
+        Some(Commands::Bench {
+            camera,
+            seconds,
+            no_camera,
+        }) => bench::run(bench::BenchOptions {
+            camera_index: camera,
+            seconds,
+            no_camera,
+        }),
+        Some(Commands::Selftest {
+            loopback,
+            seconds,
+            output,
+            keep,
+        }) => selftest::run(selftest::SelftestOptions {
+            loopback,
+            seconds,
+            output,
+            keep,
+        }),
         Some(Commands::Process { mode }) => match mode {
             ProcessMode::BurstMode { input, output } => cli::process_burst_mode(input, output),
+            ProcessMode::Stills {
+                input,
+                interval,
+                scenes,
+                output,
+            } => cli::extract_stills(input, interval, scenes, output),
+            ProcessMode::Stabilize {
+                input,
+                strength,
+                output,
+            } => cli::stabilize_video(input, strength.into(), output),
         },
-        None => run_gui(
-            cli.preview_source,
-            cli.preview_window,
-            cli.preview_spoof_recording,
-            cli.preview_fake_camera,
-        ),
+        Some(Commands::Mirror { light }) => run_gui(gui_flags(Some(light)), cli.preview_window),
+        None => run_gui(gui_flags(None), cli.preview_window),
     }
 }
 
+/// Run the app window. `flags` come from the command line; the pre-warm
+/// handle is filled in here.
 fn run_gui(
-    preview_source: Option<PathBuf>,
+    mut flags: camera::app::AppFlags,
     preview_window: Option<(f32, f32)>,
-    preview_spoof_recording: bool,
-    preview_fake_camera: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Start pre-warming on background threads BEFORE the iced event loop.
     // This overlaps GStreamer init, device enumeration, and camera discovery
@@ -256,18 +558,12 @@ fn run_gui(
     // When a preview source is provided, set a fixed window size — defaults
     // to 900x700 (Flathub's recommended standard-display size) but the user
     // can override via `--preview-window WxH` to capture phone-aspect shots.
-    if preview_source.is_some() {
+    if flags.preview_source.is_some() {
         let (w, h) = preview_window.unwrap_or((900.0, 700.0));
         settings = settings.size(cosmic::iced::Size::new(w, h));
     }
 
-    // Create app flags with pre-warm handle
-    let flags = camera::app::AppFlags {
-        preview_source,
-        preview_spoof_recording,
-        preview_fake_camera,
-        prewarm: Some(prewarm_handle),
-    };
+    flags.prewarm = Some(prewarm_handle);
 
     // Starts the application's event loop with flags
     cosmic::app::run::<AppModel>(settings, flags)?;
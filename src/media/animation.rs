@@ -0,0 +1,383 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Animated GIF and WebP export for short clips
+//!
+//! Takes a sequence of RGBA frames and writes a looping animation. GIF is
+//! limited to 256 colours, so the frames are mapped onto one palette built
+//! from all of them with median cut; a shared palette keeps flat areas from
+//! flickering the way per-frame palettes do. WebP goes through GStreamer's
+//! `webpenc`, which quantizes nothing and needs no palette.
+
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use image::RgbaImage;
+use image::codecs::gif::{GifEncoder, Repeat};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Widest frame written to an animation. Camera frames are scaled down to
+/// this to keep GIFs to a few megabytes.
+pub const MAX_WIDTH: u32 = 640;
+
+/// Clip lengths offered in settings, in seconds
+pub const CLIP_SECONDS: [u8; 4] = [2, 3, 4, 5];
+
+/// Clip frame rates offered in settings
+pub const CLIP_FPS: [u8; 3] = [10, 15, 20];
+
+/// Colours in a GIF palette
+const GIF_PALETTE_SIZE: usize = 256;
+
+/// Pixels sampled across all frames when building the palette
+const PALETTE_SAMPLES: usize = 1 << 16;
+
+/// Animated image container
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AnimationFormat {
+    Gif,
+    WebP,
+}
+
+impl AnimationFormat {
+    /// Map the clip format setting; `None` when clips are off
+    pub fn from_config(format: crate::config::AnimatedClipFormat) -> Option<Self> {
+        match format {
+            crate::config::AnimatedClipFormat::Off => None,
+            crate::config::AnimatedClipFormat::Gif => Some(AnimationFormat::Gif),
+            crate::config::AnimatedClipFormat::WebP => Some(AnimationFormat::WebP),
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            AnimationFormat::Gif => "gif",
+            AnimationFormat::WebP => "webp",
+        }
+    }
+
+    /// Short name for status badges
+    pub fn label(self) -> &'static str {
+        match self {
+            AnimationFormat::Gif => "GIF",
+            AnimationFormat::WebP => "WebP",
+        }
+    }
+}
+
+/// Playback settings stored in the animation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AnimationOptions {
+    /// Frames per second
+    pub fps: u32,
+    /// Repeat forever instead of playing once
+    pub loop_forever: bool,
+}
+
+/// Write `frames` as an animation next to `output_base`, which gets the
+/// format's extension. Returns the written path.
+pub fn export(
+    format: AnimationFormat,
+    frames: Vec<RgbaImage>,
+    options: AnimationOptions,
+    output_base: &Path,
+) -> Result<PathBuf, String> {
+    if frames.is_empty() {
+        return Err("No frames to export".into());
+    }
+    let path = output_base.with_extension(format.extension());
+    match format {
+        AnimationFormat::Gif => encode_gif(frames, options, &path)?,
+        AnimationFormat::WebP => encode_webp(frames, options, &path)?,
+    }
+    info!(path = %path.display(), ?format, "Animated clip saved");
+    Ok(path)
+}
+
+/// Build a palette of at most `max_colors` colours for `frames` with
+/// median cut over a sample of their pixels
+pub fn build_palette(frames: &[RgbaImage], max_colors: usize) -> Vec<[u8; 3]> {
+    let total: usize = frames
+        .iter()
+        .map(|f| (f.width() * f.height()) as usize)
+        .sum();
+    let step = (total / PALETTE_SAMPLES).max(1);
+    let mut samples: Vec<[u8; 3]> = frames
+        .iter()
+        .flat_map(|f| f.pixels())
+        .step_by(step)
+        .map(|p| [p[0], p[1], p[2]])
+        .collect();
+    if samples.is_empty() {
+        return vec![[0, 0, 0]];
+    }
+
+    // Repeatedly split the box with the widest channel range at its median
+    let mut boxes = vec![(0, samples.len())];
+    while boxes.len() < max_colors {
+        let widest = boxes
+            .iter()
+            .enumerate()
+            .map(|(i, &(start, end))| {
+                let (channel, range) = widest_channel(&samples[start..end]);
+                (i, channel, range)
+            })
+            .filter(|&(_, _, range)| range > 0)
+            .max_by_key(|&(_, _, range)| range);
+        let Some((i, channel, _)) = widest else {
+            break;
+        };
+        let (start, end) = boxes[i];
+        samples[start..end].sort_unstable_by_key(|p| p[channel]);
+        let mid = start + (end - start) / 2;
+        boxes[i] = (start, mid);
+        boxes.push((mid, end));
+    }
+
+    boxes
+        .iter()
+        .map(|&(start, end)| average(&samples[start..end]))
+        .collect()
+}
+
+/// Replace every pixel of `frame` with its nearest palette colour (and make
+/// it opaque). Lookups are cached per 15-bit colour.
+pub fn quantize(frame: &mut RgbaImage, palette: &[[u8; 3]]) {
+    let mut cache = vec![u16::MAX; 1 << 15];
+    for pixel in frame.pixels_mut() {
+        let key = (usize::from(pixel[0] >> 3) << 10)
+            | (usize::from(pixel[1] >> 3) << 5)
+            | usize::from(pixel[2] >> 3);
+        if cache[key] == u16::MAX {
+            cache[key] = nearest(palette, [pixel[0], pixel[1], pixel[2]]) as u16;
+        }
+        let [r, g, b] = palette[usize::from(cache[key])];
+        pixel.0 = [r, g, b, 255];
+    }
+}
+
+fn widest_channel(samples: &[[u8; 3]]) -> (usize, u8) {
+    (0..3)
+        .map(|c| {
+            let (min, max) = samples.iter().fold((u8::MAX, u8::MIN), |(lo, hi), p| {
+                (lo.min(p[c]), hi.max(p[c]))
+            });
+            (c, max.saturating_sub(min))
+        })
+        .max_by_key(|&(_, range)| range)
+        .unwrap_or((0, 0))
+}
+
+fn average(samples: &[[u8; 3]]) -> [u8; 3] {
+    let n = samples.len().max(1) as u64;
+    let mut sum = [0u64; 3];
+    for p in samples {
+        for (total, &v) in sum.iter_mut().zip(p) {
+            *total += u64::from(v);
+        }
+    }
+    sum.map(|s| (s / n) as u8)
+}
+
+fn nearest(palette: &[[u8; 3]], color: [u8; 3]) -> usize {
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, p)| {
+            (0..3)
+                .map(|c| {
+                    let d = i32::from(p[c]) - i32::from(color[c]);
+                    d * d
+                })
+                .sum::<i32>()
+        })
+        .map_or(0, |(i, _)| i)
+}
+
+fn encode_gif(
+    mut frames: Vec<RgbaImage>,
+    options: AnimationOptions,
+    path: &Path,
+) -> Result<(), String> {
+    let palette = build_palette(&frames, GIF_PALETTE_SIZE);
+    for frame in &mut frames {
+        quantize(frame, &palette);
+    }
+
+    let file = std::fs::File::create(path).map_err(|e| format!("Failed to create GIF: {e}"))?;
+    let mut encoder = GifEncoder::new_with_speed(BufWriter::new(file), 10);
+    encoder
+        .set_repeat(if options.loop_forever {
+            Repeat::Infinite
+        } else {
+            Repeat::Finite(0)
+        })
+        .map_err(|e| format!("Failed to set GIF repeat: {e}"))?;
+
+    let delay = image::Delay::from_numer_denom_ms(1000, options.fps.max(1));
+    for frame in frames {
+        encoder
+            .encode_frame(image::Frame::from_parts(frame, 0, 0, delay))
+            .map_err(|e| format!("Failed to encode GIF frame: {e}"))?;
+    }
+    Ok(())
+}
+
+fn encode_webp(
+    frames: Vec<RgbaImage>,
+    options: AnimationOptions,
+    path: &Path,
+) -> Result<(), String> {
+    let (width, height) = frames[0].dimensions();
+    // webpenc: 0 loops means forever
+    let loops = if options.loop_forever { 0 } else { 1 };
+    let pipeline = gst::parse::launch(&format!(
+        "appsrc name=src format=time ! videoconvert ! \
+         webpenc animated=true animation-loops={loops} quality=85 ! \
+         filesink name=sink"
+    ))
+    .map_err(|e| format!("Failed to build WebP pipeline: {e}"))?
+    .downcast::<gst::Pipeline>()
+    .map_err(|_| "WebP pipeline is not a pipeline")?;
+
+    let sink = pipeline
+        .by_name("sink")
+        .ok_or("WebP pipeline has no sink")?;
+    sink.set_property("location", path.to_string_lossy().as_ref());
+    let appsrc = pipeline
+        .by_name("src")
+        .and_then(|e| e.downcast::<gst_app::AppSrc>().ok())
+        .ok_or("WebP pipeline has no appsrc")?;
+    appsrc.set_caps(Some(
+        &gst::Caps::builder("video/x-raw")
+            .field("format", "RGBA")
+            .field("width", width as i32)
+            .field("height", height as i32)
+            .field(
+                "framerate",
+                gst::Fraction::new(options.fps.max(1) as i32, 1),
+            )
+            .build(),
+    ));
+
+    pipeline
+        .set_state(gst::State::Playing)
+        .map_err(|e| format!("set PLAYING: {e:?}"))?;
+
+    let frame_duration =
+        gst::ClockTime::from_nseconds(1_000_000_000 / u64::from(options.fps.max(1)));
+    for (index, frame) in frames.into_iter().enumerate() {
+        if frame.dimensions() != (width, height) {
+            continue;
+        }
+        let mut buffer = gst::Buffer::from_mut_slice(frame.into_raw());
+        {
+            let buf = buffer.get_mut().unwrap();
+            buf.set_pts(frame_duration * index as u64);
+            buf.set_duration(frame_duration);
+        }
+        if let Err(e) = appsrc.push_buffer(buffer) {
+            pipeline.set_state(gst::State::Null).ok();
+            return Err(format!("push_buffer: {e}"));
+        }
+    }
+    let _ = appsrc.end_of_stream();
+
+    let bus = pipeline.bus().ok_or("No pipeline bus")?;
+    let result = loop {
+        match bus.timed_pop(gst::ClockTime::from_seconds(30)) {
+            Some(msg) => match msg.view() {
+                gst::MessageView::Eos(..) => break Ok(()),
+                gst::MessageView::Error(e) => {
+                    break Err(format!(
+                        "GStreamer error: {} ({})",
+                        e.error(),
+                        e.debug().unwrap_or_default()
+                    ));
+                }
+                _ => {}
+            },
+            None => break Err("Timeout waiting for WebP EOS".into()),
+        }
+    };
+    pipeline.set_state(gst::State::Null).ok();
+    if result.is_err() {
+        let _ = std::fs::remove_file(path);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32, color: [u8; 3]) -> RgbaImage {
+        RgbaImage::from_pixel(
+            width,
+            height,
+            image::Rgba([color[0], color[1], color[2], 255]),
+        )
+    }
+
+    #[test]
+    fn few_colours_are_kept_exactly() {
+        let frames = [
+            solid(4, 4, [255, 0, 0]),
+            solid(4, 4, [0, 255, 0]),
+            solid(4, 4, [0, 0, 255]),
+        ];
+        let mut palette = build_palette(&frames, 256);
+        palette.sort_unstable();
+        assert_eq!(palette, vec![[0, 0, 255], [0, 255, 0], [255, 0, 0]]);
+    }
+
+    #[test]
+    fn palette_is_capped() {
+        let gradient = RgbaImage::from_fn(256, 64, |x, y| {
+            image::Rgba([x as u8, (y * 4) as u8, (x ^ y) as u8, 255])
+        });
+        let palette = build_palette(std::slice::from_ref(&gradient), 256);
+        assert!(palette.len() <= 256);
+        assert!(palette.len() > 200);
+    }
+
+    #[test]
+    fn quantize_maps_to_nearest_palette_colour() {
+        let mut frame = solid(2, 2, [250, 10, 5]);
+        frame.put_pixel(0, 0, image::Rgba([12, 12, 240, 128]));
+        quantize(&mut frame, &[[255, 0, 0], [0, 0, 255]]);
+        assert_eq!(frame.get_pixel(0, 0).0, [0, 0, 255, 255]);
+        assert_eq!(frame.get_pixel(1, 1).0, [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn gif_keeps_every_frame() {
+        use image::AnimationDecoder;
+
+        let dir = std::env::temp_dir().join(format!("camera-gif-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let frames = vec![
+            solid(8, 6, [255, 0, 0]),
+            solid(8, 6, [0, 255, 0]),
+            solid(8, 6, [0, 0, 255]),
+        ];
+        let options = AnimationOptions {
+            fps: 10,
+            loop_forever: true,
+        };
+        let path = export(AnimationFormat::Gif, frames, options, &dir.join("clip")).unwrap();
+        assert_eq!(path.extension().unwrap(), "gif");
+
+        let file = std::io::BufReader::new(std::fs::File::open(&path).unwrap());
+        let decoded = image::codecs::gif::GifDecoder::new(file)
+            .unwrap()
+            .into_frames()
+            .collect_frames()
+            .unwrap();
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded[1].buffer().get_pixel(4, 3).0, [0, 255, 0, 255]);
+        assert_eq!(decoded[0].delay().numer_denom_ms(), (100, 1));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -5,6 +5,8 @@
 //! This module implements audio encoder selection with priority:
 //! 1. Opus (best quality, all channel configs)
 //! 2. AAC (good fallback)
+//!
+//! Edit-friendly recordings skip compression and store 16-bit PCM instead.
 
 use gstreamer as gst;
 use gstreamer::prelude::*;
@@ -17,6 +19,8 @@ pub enum AudioCodec {
     Opus,
     /// AAC codec (fallback - good compatibility)
     AAC,
+    /// Uncompressed 16-bit PCM (edit-friendly recordings)
+    Pcm,
 }
 
 impl AudioCodec {
@@ -25,6 +29,7 @@ impl AudioCodec {
         match self {
             AudioCodec::Opus => "audio/x-opus",
             AudioCodec::AAC => "audio/mpeg,mpegversion=4",
+            AudioCodec::Pcm => "audio/x-raw,format=S16LE",
         }
     }
 }
@@ -93,6 +98,9 @@ pub struct SelectedAudioEncoder {
     pub codec: AudioCodec,
 }
 
+/// Bitrate of the PCM stored by edit-friendly recordings (48 kHz mono S16)
+pub const PCM_BITRATE_BPS: u32 = 48_000 * 16;
+
 /// Sample rates accepted by `opusenc` (libopus is fixed to this set).
 pub const OPUS_SUPPORTED_RATES: &[u32] = &[8000, 12000, 16000, 24000, 48000];
 
@@ -173,6 +181,31 @@ pub fn select_audio_encoder(
     Err("No audio encoder available. Please install gstreamer1-plugins-base (opusenc) or gstreamer1-plugins-bad (avenc_aac)".to_string())
 }
 
+/// Select uncompressed PCM audio
+///
+/// The "encoder" is a capsfilter pinning the branch to 16-bit samples, so
+/// the audio reaches the muxer exactly as captured. Only Matroska and MOV
+/// accept it.
+pub fn select_pcm_audio_encoder() -> Result<SelectedAudioEncoder, String> {
+    gst::init().map_err(|e| format!("Failed to initialize GStreamer: {}", e))?;
+
+    let encoder = gst::ElementFactory::make("capsfilter")
+        .property(
+            "caps",
+            gst::Caps::builder("audio/x-raw")
+                .field("format", "S16LE")
+                .build(),
+        )
+        .build()
+        .map_err(|e| format!("Failed to create PCM capsfilter: {}", e))?;
+    info!(codec = "PCM", "Selected audio encoder");
+
+    Ok(SelectedAudioEncoder {
+        encoder,
+        codec: AudioCodec::Pcm,
+    })
+}
+
 /// Configure Opus encoder
 fn configure_opus_encoder(encoder: &gst::Element, quality: AudioQuality, _channels: AudioChannels) {
     let bitrate = quality.bitrate_bps();
@@ -249,5 +282,6 @@ mod tests {
     fn test_codec_caps() {
         assert_eq!(AudioCodec::Opus.caps_string(), "audio/x-opus");
         assert!(AudioCodec::AAC.caps_string().contains("audio/mpeg"));
+        assert!(AudioCodec::Pcm.caps_string().starts_with("audio/x-raw"));
     }
 }
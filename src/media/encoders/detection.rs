@@ -23,14 +23,21 @@ pub fn detect_video_encoders() -> Vec<String> {
 
     let encoders = [
         // Hardware AV1
-        "vaapiavcenc",
+        "vaav1enc",
         "nvav1enc",
+        "qsvav1enc",
+        "amfav1enc",
+        // Software AV1
+        "svtav1enc",
+        "av1enc",
         // Hardware HEVC/H.265
         "vaapih265enc",
+        "vah265enc",
         "nvh265enc",
         "v4l2h265enc",
         // Hardware H.264
         "vaapih264enc",
+        "vah264enc",
         "nvh264enc",
         "v4l2h264enc",
         // Software HEVC/H.265
@@ -193,6 +200,26 @@ pub fn probe_single_encoder(encoder_name: &str) -> bool {
     probe_gst_pipeline(&pipeline_desc)
 }
 
+/// Whether a video encoder takes 10-bit raw input, so HDR can be recorded
+/// without losing precision
+pub fn accepts_10bit_input(encoder_name: &str) -> bool {
+    gst::init().ok();
+    let Some(factory) = gst::ElementFactory::find(encoder_name) else {
+        return false;
+    };
+    factory
+        .static_pad_templates()
+        .iter()
+        .filter(|template| template.direction() == gst::PadDirection::Sink)
+        .any(|template| lists_10bit_format(&template.caps().to_string()))
+}
+
+/// Whether sink caps list a 10-bit YUV format
+fn lists_10bit_format(caps: &str) -> bool {
+    const FORMATS: [&str; 5] = ["P010_10LE", "I420_10LE", "I422_10LE", "Y444_10LE", "Y210"];
+    FORMATS.iter().any(|format| caps.contains(format))
+}
+
 /// Probe all given video encoder names and return the names of the broken ones.
 ///
 /// Hardware encoders are tested sequentially (they may share a single V4L2 or
@@ -287,4 +314,17 @@ mod tests {
         let _ = detect_video_encoders();
         let _ = detect_audio_encoders();
     }
+
+    #[test]
+    fn ten_bit_formats_are_found_in_sink_caps() {
+        assert!(lists_10bit_format(
+            "video/x-raw, format=(string){ NV12, P010_10LE }, width=(int)[ 1, 4096 ]"
+        ));
+        assert!(lists_10bit_format(
+            "video/x-raw, format=(string){ I420, Y444, I420_10LE }"
+        ));
+        assert!(!lists_10bit_format(
+            "video/x-raw, format=(string){ I420, NV12, Y444 }"
+        ));
+    }
 }
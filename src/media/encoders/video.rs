@@ -15,7 +15,14 @@ use tracing::{debug, info, warn};
 /// See: https://github.com/cosmic-utils/camera/issues/171
 /// - svtav1enc (SVT-AV1): No file is created when recording
 /// - av1enc (AOM AV1): Recording terminates immediately with unplayable output
-const BLACKLISTED_ENCODERS: &[&str] = &["svtav1enc", "av1enc"];
+///
+/// Native installs use the system GStreamer plugins, where both work.
+const FLATPAK_BLACKLISTED_ENCODERS: &[&str] = &["svtav1enc", "av1enc"];
+
+/// Whether an encoder is known to be broken in the current environment
+fn is_blacklisted(element_name: &str) -> bool {
+    crate::constants::app_info::is_flatpak() && FLATPAK_BLACKLISTED_ENCODERS.contains(&element_name)
+}
 
 /// Video codec types in priority order
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -29,15 +36,62 @@ pub enum VideoCodec {
 }
 
 impl VideoCodec {
-    /// Get the container format for this codec
+    /// Identify the codec produced by a GStreamer encoder element
+    pub fn from_encoder_name(element_name: &str) -> Option<Self> {
+        if element_name.contains("av1") {
+            Some(VideoCodec::AV1)
+        } else if element_name.contains("h265")
+            || element_name.contains("hevc")
+            || element_name.contains("x265")
+        {
+            Some(VideoCodec::HEVC)
+        } else if element_name.contains("h264") || element_name.contains("x264") {
+            Some(VideoCodec::H264)
+        } else {
+            None
+        }
+    }
+
+    /// Get the preferred container format for this codec
     pub fn container_format(&self) -> ContainerFormat {
+        self.containers()[0]
+    }
+
+    /// Containers that can carry this codec, most preferred first
+    pub fn containers(&self) -> &'static [ContainerFormat] {
         match self {
-            VideoCodec::AV1 => ContainerFormat::WebM,
-            VideoCodec::HEVC => ContainerFormat::MP4,
-            VideoCodec::H264 => ContainerFormat::MP4,
+            VideoCodec::AV1 => &[
+                ContainerFormat::WebM,
+                ContainerFormat::Matroska,
+                ContainerFormat::MP4,
+            ],
+            VideoCodec::HEVC | VideoCodec::H264 => &[
+                ContainerFormat::MP4,
+                ContainerFormat::Matroska,
+                ContainerFormat::MOV,
+            ],
         }
     }
 
+    /// Whether `container` can carry this codec
+    pub fn supports_container(&self, container: ContainerFormat) -> bool {
+        self.containers().contains(&container)
+    }
+
+    /// Bitrate needed relative to H.264 for comparable visual quality
+    pub fn bitrate_factor(&self) -> f64 {
+        match self {
+            VideoCodec::AV1 => 0.6,
+            VideoCodec::HEVC => 0.7,
+            VideoCodec::H264 => 1.0,
+        }
+    }
+
+    /// Scale an H.264-calibrated bitrate preset to this codec
+    pub fn scale_bitrate_kbps(&self, h264_kbps: u32) -> u32 {
+        ((h264_kbps as f64 * self.bitrate_factor()) as u32).max(500)
+    }
+
     /// Get the file extension for this codec's container
     pub fn file_extension(&self) -> &'static str {
         self.container_format().extension()
@@ -60,6 +114,10 @@ pub enum ContainerFormat {
     MP4,
     /// WebM container (open format)
     WebM,
+    /// Matroska container (accepts every codec, fallback when other muxers are missing)
+    Matroska,
+    /// QuickTime container (preferred by Apple and editing software)
+    MOV,
 }
 
 impl ContainerFormat {
@@ -68,6 +126,8 @@ impl ContainerFormat {
         match self {
             ContainerFormat::MP4 => "mp4",
             ContainerFormat::WebM => "webm",
+            ContainerFormat::Matroska => "mkv",
+            ContainerFormat::MOV => "mov",
         }
     }
 
@@ -76,7 +136,67 @@ impl ContainerFormat {
         match self {
             ContainerFormat::MP4 => "mp4mux",
             ContainerFormat::WebM => "webmmux",
+            ContainerFormat::Matroska => "matroskamux",
+            ContainerFormat::MOV => "qtmux",
+        }
+    }
+
+    /// Whether the muxer element is installed
+    pub fn is_available(&self) -> bool {
+        let _ = gst::init();
+        gst::ElementFactory::find(self.muxer_name()).is_some()
+    }
+}
+
+/// Pick the most preferred container for `codec` whose muxer is installed
+///
+/// Falls back to Matroska, which every codec supports.
+pub fn select_container(codec: VideoCodec) -> ContainerFormat {
+    codec
+        .containers()
+        .iter()
+        .copied()
+        .find(|c| c.is_available())
+        .unwrap_or(ContainerFormat::Matroska)
+}
+
+/// Container to record `codec` into, honoring the user's choice when the
+/// container can carry the codec and its muxer is installed
+pub fn resolve_container(codec: VideoCodec, preferred: Option<ContainerFormat>) -> ContainerFormat {
+    match preferred {
+        Some(container) if codec.supports_container(container) && container.is_available() => {
+            container
         }
+        Some(container) => {
+            let fallback = select_container(codec);
+            warn!(
+                ?codec,
+                ?container,
+                ?fallback,
+                "Requested container can't be used for this codec"
+            );
+            fallback
+        }
+        None => select_container(codec),
+    }
+}
+
+/// Container for `codec` when the recording carries uncompressed PCM audio
+///
+/// Only Matroska and MOV can hold PCM; Matroska is used unless MOV was
+/// requested and fits the codec.
+pub fn resolve_pcm_container(
+    codec: VideoCodec,
+    preferred: Option<ContainerFormat>,
+) -> ContainerFormat {
+    match preferred {
+        Some(ContainerFormat::MOV)
+            if codec.supports_container(ContainerFormat::MOV)
+                && ContainerFormat::MOV.is_available() =>
+        {
+            ContainerFormat::MOV
+        }
+        _ => ContainerFormat::Matroska,
     }
 }
 
@@ -130,6 +250,27 @@ impl VideoQuality {
     }
 }
 
+/// Muxer overhead added on top of the elementary stream bitrates (~2%)
+const CONTAINER_OVERHEAD: f64 = 1.02;
+
+/// Estimate the size of one minute of recording
+///
+/// All encoders are configured for a target bitrate (see
+/// [`configure_video_encoder`]), so the file size follows from the video and
+/// audio bitrates regardless of codec or framerate.
+pub fn estimate_bytes_per_minute(video_kbps: u32, audio_bps: Option<u32>) -> u64 {
+    let bits_per_second = video_kbps as f64 * 1000.0 + audio_bps.unwrap_or(0) as f64;
+    (bits_per_second / 8.0 * 60.0 * CONTAINER_OVERHEAD) as u64
+}
+
+/// How long a recording at `bytes_per_minute` can run before filling `free_bytes`
+pub fn recording_capacity(free_bytes: u64, bytes_per_minute: u64) -> std::time::Duration {
+    if bytes_per_minute == 0 {
+        return std::time::Duration::MAX;
+    }
+    std::time::Duration::from_secs_f64(free_bytes as f64 / bytes_per_minute as f64 * 60.0)
+}
+
 /// Information about an available encoder
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EncoderInfo {
@@ -145,6 +286,62 @@ pub struct EncoderInfo {
     pub priority: u32,
 }
 
+/// Why an encoder may not record a camera format as it is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncoderLimit {
+    /// Larger than the encoder accepts; the recording would fail to start
+    TooLarge { max_width: u32, max_height: u32 },
+    /// More pixels per second than a software encoder keeps up with, so
+    /// frames would be dropped
+    TooFast,
+}
+
+/// Pixels per second software H.264 keeps up with on a typical laptop (4K30)
+const SOFTWARE_H264_PIXEL_RATE: f64 = 3840.0 * 2160.0 * 30.0;
+
+/// Pixels per second software HEVC and AV1 keep up with (1080p30)
+const SOFTWARE_HEVC_AV1_PIXEL_RATE: f64 = 1920.0 * 1080.0 * 30.0;
+
+impl EncoderInfo {
+    /// Largest frame the encoder accepts, for encoders with a known limit
+    pub fn max_size(&self) -> Option<(u32, u32)> {
+        match self.element_name.as_str() {
+            // The Raspberry Pi's encoder tops out at 1080p
+            "v4l2h264enc" => Some((1920, 1920)),
+            "v4l2h265enc" => Some((4096, 4096)),
+            "openh264enc" => Some((4096, 2304)),
+            _ if self.is_hardware => Some(match self.codec {
+                VideoCodec::H264 => (4096, 4096),
+                VideoCodec::HEVC | VideoCodec::AV1 => (8192, 8192),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Check whether the encoder records `width`x`height` at `fps`
+    pub fn check_format(&self, width: u32, height: u32, fps: Option<f64>) -> Option<EncoderLimit> {
+        if let Some((max_width, max_height)) = self.max_size()
+            && (width > max_width || height > max_height)
+        {
+            return Some(EncoderLimit::TooLarge {
+                max_width,
+                max_height,
+            });
+        }
+        if !self.is_hardware {
+            let pixel_rate = width as f64 * height as f64 * fps.unwrap_or(30.0);
+            let limit = match self.codec {
+                VideoCodec::H264 => SOFTWARE_H264_PIXEL_RATE,
+                VideoCodec::HEVC | VideoCodec::AV1 => SOFTWARE_HEVC_AV1_PIXEL_RATE,
+            };
+            if pixel_rate > limit {
+                return Some(EncoderLimit::TooFast);
+            }
+        }
+        None
+    }
+}
+
 /// Selected video encoder with configuration
 pub struct SelectedVideoEncoder {
     /// The encoder element
@@ -161,6 +358,27 @@ pub struct SelectedVideoEncoder {
     pub extension: &'static str,
 }
 
+impl SelectedVideoEncoder {
+    /// Switch to a different container, replacing the muxer
+    ///
+    /// Fails if the container can't carry the codec or its muxer is missing.
+    pub fn set_container(&mut self, container: ContainerFormat) -> Result<(), String> {
+        if !self.codec.supports_container(container) {
+            return Err(format!(
+                "{:?} video can't be stored in {}",
+                self.codec,
+                container.extension()
+            ));
+        }
+        self.muxer = gst::ElementFactory::make(container.muxer_name())
+            .build()
+            .map_err(|e| format!("Failed to create muxer {}: {}", container.muxer_name(), e))?;
+        self.container = container;
+        self.extension = container.extension();
+        Ok(())
+    }
+}
+
 /// Enumerate all available video encoders
 ///
 /// Returns a list of available encoders sorted by priority
@@ -243,7 +461,7 @@ pub fn enumerate_video_encoders() -> Vec<EncoderInfo> {
 
     for (element_name, display_name, codec, is_hardware, priority) in &encoder_specs {
         // Skip blacklisted encoders
-        if BLACKLISTED_ENCODERS.contains(element_name) {
+        if is_blacklisted(element_name) {
             continue;
         }
 
@@ -313,7 +531,7 @@ pub fn create_encoder_from_info_with_bitrate(
     };
 
     // Create muxer
-    let container = info.codec.container_format();
+    let container = select_container(info.codec);
     let muxer = gst::ElementFactory::make(container.muxer_name())
         .build()
         .map_err(|e| format!("Failed to create muxer {}: {}", container.muxer_name(), e))?;
@@ -324,16 +542,16 @@ pub fn create_encoder_from_info_with_bitrate(
         muxer,
         codec: info.codec,
         container,
-        extension: info.codec.file_extension(),
+        extension: container.extension(),
     })
 }
 
 /// Select the best available video encoder
 ///
 /// Priority order:
-/// 1. Hardware AV1 (vaapiavcenc, nvav1enc)
-/// 2. Hardware HEVC/H.265 (vaapih265enc, nvh265enc)
-/// 3. Hardware H.264 (vaapih264enc, nvh264enc)
+/// 1. Hardware AV1 (vaav1enc, nvav1enc)
+/// 2. Hardware HEVC/H.265 (vaapih265enc, vah265enc, nvh265enc)
+/// 3. Hardware H.264 (vaapih264enc, vah264enc, nvh264enc)
 /// 4. Software H.264 (x264enc)
 /// 5. Software HEVC/H.265 (x265enc)
 /// 6. Software H.264 (openh264enc)
@@ -366,14 +584,16 @@ pub fn select_video_encoder_with_bitrate(
     // Try encoders in priority order
     let encoders = [
         // Hardware AV1
-        ("vaapiavcenc", VideoCodec::AV1, true),
+        ("vaav1enc", VideoCodec::AV1, true),
         ("nvav1enc", VideoCodec::AV1, true),
         // Hardware HEVC
         ("vaapih265enc", VideoCodec::HEVC, true),
+        ("vah265enc", VideoCodec::HEVC, true),
         ("nvh265enc", VideoCodec::HEVC, true),
         ("v4l2h265enc", VideoCodec::HEVC, true),
         // Hardware H.264
         ("vaapih264enc", VideoCodec::H264, true),
+        ("vah264enc", VideoCodec::H264, true),
         ("nvh264enc", VideoCodec::H264, true),
         ("v4l2h264enc", VideoCodec::H264, true),
         // Software H.264 (preferred — fast, widely compatible)
@@ -420,7 +640,7 @@ pub fn select_video_encoder_with_bitrate(
             };
 
             // Create muxer
-            let container = codec.container_format();
+            let container = select_container(*codec);
             let muxer = gst::ElementFactory::make(container.muxer_name())
                 .build()
                 .map_err(|e| format!("Failed to create muxer {}: {}", container.muxer_name(), e))?;
@@ -431,7 +651,7 @@ pub fn select_video_encoder_with_bitrate(
                 muxer,
                 codec: *codec,
                 container,
-                extension: codec.file_extension(),
+                extension: container.extension(),
             });
         }
     }
@@ -439,6 +659,26 @@ pub fn select_video_encoder_with_bitrate(
     Err("No video encoder available. Please install gstreamer1-plugins-ugly (x264enc) or gstreamer1-plugin-openh264".to_string())
 }
 
+/// Find a replacement for an encoder that rejected the encoding session
+///
+/// Hardware encoders can be listed in the registry yet fail at runtime
+/// (missing driver libraries, unsupported profile, session limits). Prefers
+/// another working encoder of the same codec so bitrate and container stay
+/// the same, then any working encoder in priority order.
+pub fn fallback_video_encoder(failed: &str) -> Option<EncoderInfo> {
+    let codec = VideoCodec::from_encoder_name(failed);
+    let (same_codec, other): (Vec<_>, Vec<_>) = enumerate_video_encoders()
+        .into_iter()
+        // V4L2 encoders can't be probed safely while the camera is streaming
+        .filter(|e| e.element_name != failed && !e.element_name.starts_with("v4l2"))
+        .partition(|e| Some(e.codec) == codec);
+
+    same_codec
+        .into_iter()
+        .chain(other)
+        .find(|e| super::detection::probe_single_encoder(&e.element_name))
+}
+
 /// Configure encoder based on type and quality
 pub fn configure_video_encoder(
     encoder: &gst::Element,
@@ -448,8 +688,11 @@ pub fn configure_video_encoder(
     height: u32,
     bitrate_override_kbps: Option<u32>,
 ) {
-    // Use bitrate override if provided, otherwise calculate from quality preset
+    // Use bitrate override if provided, otherwise calculate from quality preset.
+    // Both are calibrated for H.264; HEVC and AV1 reach the same quality with less.
     let bitrate = bitrate_override_kbps.unwrap_or_else(|| quality.bitrate_kbps(width, height));
+    let bitrate = VideoCodec::from_encoder_name(encoder_name)
+        .map_or(bitrate, |codec| codec.scale_bitrate_kbps(bitrate));
 
     match encoder_name {
         // x264 software encoder
@@ -549,7 +792,7 @@ pub fn configure_video_encoder(
         }
 
         // VA-API AV1 encoder
-        "vaav1enc" | "vaapiavcenc" => {
+        "vaav1enc" => {
             encoder.set_property_from_str("rate-control", "cbr");
             encoder.set_property("bitrate", bitrate);
             debug!("Configured VA-API AV1 encoder: bitrate={} kbps", bitrate);
@@ -581,10 +824,76 @@ pub fn configure_video_encoder(
     }
 }
 
+/// Properties that make an encoder emit only key frames
+///
+/// Values are strings so each can be parsed into whatever integer type the
+/// element declares.
+fn intra_only_properties(encoder_name: &str) -> &'static [(&'static str, &'static str)] {
+    match encoder_name {
+        "x264enc" | "x265enc" => &[("key-int-max", "1")],
+        "openh264enc" => &[("gop-size", "1")],
+        "vaapih264enc" | "vaapih265enc" => &[("keyframe-period", "1"), ("max-bframes", "0")],
+        "vah264enc" | "vah265enc" | "vaav1enc" => &[("key-int-max", "1"), ("b-frames", "0")],
+        "nvh264enc" | "nvh265enc" | "nvav1enc" => &[("gop-size", "1"), ("bframes", "0")],
+        "amfh264enc" | "amfh265enc" | "amfav1enc" => &[("gop-size", "1")],
+        "qsvh264enc" | "qsvh265enc" | "qsvav1enc" => &[("gop-size", "1"), ("b-frames", "0")],
+        // SVT-AV1 and libaom both treat 0 as "every frame is an intra frame"
+        "svtav1enc" => &[("intra-period-length", "0")],
+        "av1enc" => &[("keyframe-max-dist", "0")],
+        _ => &[],
+    }
+}
+
+/// Switch an already configured encoder to intra-only output
+///
+/// Every frame becomes a key frame, so editors can cut anywhere without
+/// decoding a long GOP. Properties the installed plugin version doesn't
+/// have are skipped.
+pub fn configure_intra_only(encoder: &gst::Element, encoder_name: &str) {
+    let properties = intra_only_properties(encoder_name);
+    if properties.is_empty() {
+        warn!(
+            encoder = encoder_name,
+            "Intra-only mode not supported by this encoder"
+        );
+        return;
+    }
+    for (name, value) in properties {
+        if encoder.find_property(name).is_some() {
+            encoder.set_property_from_str(name, value);
+        }
+    }
+    debug!(encoder = encoder_name, "Configured intra-only encoding");
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_pcm_container() {
+        assert_eq!(
+            resolve_pcm_container(VideoCodec::H264, None),
+            ContainerFormat::Matroska
+        );
+        assert_eq!(
+            resolve_pcm_container(VideoCodec::H264, Some(ContainerFormat::MP4)),
+            ContainerFormat::Matroska
+        );
+        // AV1 can't go in MOV, and WebM can't hold PCM
+        assert_eq!(
+            resolve_pcm_container(VideoCodec::AV1, Some(ContainerFormat::MOV)),
+            ContainerFormat::Matroska
+        );
+    }
+
+    #[test]
+    fn test_intra_only_properties() {
+        assert_eq!(intra_only_properties("x264enc"), &[("key-int-max", "1")]);
+        assert!(!intra_only_properties("vah265enc").is_empty());
+        assert!(intra_only_properties("v4l2h264enc").is_empty());
+    }
+
     #[test]
     fn test_codec_extensions() {
         assert_eq!(VideoCodec::H264.file_extension(), "mp4");
@@ -592,6 +901,104 @@ mod tests {
         assert_eq!(VideoCodec::AV1.file_extension(), "webm");
     }
 
+    #[test]
+    fn test_codec_from_encoder_name() {
+        assert_eq!(
+            VideoCodec::from_encoder_name("svtav1enc"),
+            Some(VideoCodec::AV1)
+        );
+        assert_eq!(
+            VideoCodec::from_encoder_name("vah265enc"),
+            Some(VideoCodec::HEVC)
+        );
+        assert_eq!(
+            VideoCodec::from_encoder_name("x265enc"),
+            Some(VideoCodec::HEVC)
+        );
+        assert_eq!(
+            VideoCodec::from_encoder_name("openh264enc"),
+            Some(VideoCodec::H264)
+        );
+        assert_eq!(VideoCodec::from_encoder_name("jpegenc"), None);
+    }
+
+    #[test]
+    fn test_container_compatibility() {
+        assert!(VideoCodec::AV1.supports_container(ContainerFormat::WebM));
+        assert!(VideoCodec::AV1.supports_container(ContainerFormat::MP4));
+        assert!(!VideoCodec::HEVC.supports_container(ContainerFormat::WebM));
+        assert!(!VideoCodec::H264.supports_container(ContainerFormat::WebM));
+        assert!(VideoCodec::H264.supports_container(ContainerFormat::MOV));
+        assert!(!VideoCodec::AV1.supports_container(ContainerFormat::MOV));
+        for codec in [VideoCodec::AV1, VideoCodec::HEVC, VideoCodec::H264] {
+            assert!(codec.supports_container(ContainerFormat::Matroska));
+            assert!(codec.supports_container(codec.container_format()));
+        }
+    }
+
+    #[test]
+    fn test_codec_bitrate_scaling() {
+        let h264 = VideoCodec::H264.scale_bitrate_kbps(10_000);
+        let hevc = VideoCodec::HEVC.scale_bitrate_kbps(10_000);
+        let av1 = VideoCodec::AV1.scale_bitrate_kbps(10_000);
+        assert_eq!(h264, 10_000);
+        assert!(av1 < hevc && hevc < h264);
+        // Never drop below the global minimum
+        assert_eq!(VideoCodec::AV1.scale_bitrate_kbps(600), 500);
+    }
+
+    #[test]
+    fn test_encoder_format_limits() {
+        let encoder = |element_name: &str, codec, is_hardware| EncoderInfo {
+            element_name: element_name.to_string(),
+            display_name: String::new(),
+            codec,
+            is_hardware,
+            priority: 0,
+        };
+
+        let pi = encoder("v4l2h264enc", VideoCodec::H264, true);
+        assert_eq!(pi.check_format(1920, 1080, Some(30.0)), None);
+        assert_eq!(
+            pi.check_format(3840, 2160, Some(30.0)),
+            Some(EncoderLimit::TooLarge {
+                max_width: 1920,
+                max_height: 1920
+            })
+        );
+
+        let x264 = encoder("x264enc", VideoCodec::H264, false);
+        assert_eq!(x264.check_format(3840, 2160, Some(30.0)), None);
+        assert_eq!(
+            x264.check_format(3840, 2160, Some(60.0)),
+            Some(EncoderLimit::TooFast)
+        );
+        let svt = encoder("svtav1enc", VideoCodec::AV1, false);
+        assert_eq!(
+            svt.check_format(3840, 2160, Some(30.0)),
+            Some(EncoderLimit::TooFast)
+        );
+        assert_eq!(
+            encoder("vah265enc", VideoCodec::HEVC, true).check_format(3840, 2160, Some(60.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_size_estimate() {
+        // 8 Mbps video + 128 kbps audio = 1.016 MB/s before overhead
+        let per_minute = estimate_bytes_per_minute(8_000, Some(128_000));
+        assert_eq!(per_minute, (1_016_000.0 * 60.0 * CONTAINER_OVERHEAD) as u64);
+        assert!(estimate_bytes_per_minute(8_000, None) < per_minute);
+    }
+
+    #[test]
+    fn test_recording_capacity() {
+        let capacity = recording_capacity(600, 60);
+        assert_eq!(capacity.as_secs(), 600);
+        assert_eq!(recording_capacity(1, 0), std::time::Duration::MAX);
+    }
+
     #[test]
     fn test_quality_bitrates() {
         // 1920x1080 (Full HD)
@@ -608,5 +1015,9 @@ mod tests {
         assert_eq!(ContainerFormat::WebM.extension(), "webm");
         assert_eq!(ContainerFormat::MP4.muxer_name(), "mp4mux");
         assert_eq!(ContainerFormat::WebM.muxer_name(), "webmmux");
+        assert_eq!(ContainerFormat::Matroska.extension(), "mkv");
+        assert_eq!(ContainerFormat::Matroska.muxer_name(), "matroskamux");
+        assert_eq!(ContainerFormat::MOV.extension(), "mov");
+        assert_eq!(ContainerFormat::MOV.muxer_name(), "qtmux");
     }
 }
@@ -49,10 +49,64 @@ pub fn codec_to_gst_decoder(codec: &Codec) -> Option<&'static str> {
     }
 }
 
+/// Scale a tightly packed RGBA image into a `target_width`×`target_height`
+/// frame, keeping its aspect ratio and filling the remaining area with black
+///
+/// Output pipelines negotiate a fixed size when they start, so frames from a
+/// camera that takes over mid-stream have to be fitted into it.
+pub fn letterbox_rgba(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    target_width: u32,
+    target_height: u32,
+) -> Vec<u8> {
+    let mut output =
+        image::RgbaImage::from_pixel(target_width, target_height, image::Rgba([0, 0, 0, 255]));
+    let Some(source) = image::ImageBuffer::<image::Rgba<u8>, &[u8]>::from_raw(width, height, data)
+    else {
+        return output.into_raw();
+    };
+
+    let scale = (target_width as f32 / width as f32).min(target_height as f32 / height as f32);
+    let scaled_width = ((width as f32 * scale).round() as u32).clamp(1, target_width);
+    let scaled_height = ((height as f32 * scale).round() as u32).clamp(1, target_height);
+    let scaled = image::imageops::resize(
+        &source,
+        scaled_width,
+        scaled_height,
+        image::imageops::FilterType::Triangle,
+    );
+    image::imageops::replace(
+        &mut output,
+        &scaled,
+        ((target_width - scaled_width) / 2) as i64,
+        ((target_height - scaled_height) / 2) as i64,
+    );
+    output.into_raw()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_letterbox_pads_to_target() {
+        let red = [255u8, 0, 0, 255].repeat(4 * 2);
+        let out = letterbox_rgba(&red, 4, 2, 4, 4);
+        assert_eq!(out.len(), 4 * 4 * 4);
+        // Top and bottom rows are padding, the middle two hold the image
+        assert_eq!(&out[0..4], &[0, 0, 0, 255]);
+        assert_eq!(&out[4 * 4..4 * 4 + 4], &[255, 0, 0, 255]);
+        assert_eq!(&out[3 * 16..3 * 16 + 4], &[0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_letterbox_rejects_short_buffer() {
+        let out = letterbox_rgba(&[0u8; 8], 4, 4, 2, 2);
+        assert_eq!(out, [0, 0, 0, 255].repeat(4));
+    }
+
     #[test]
     fn test_codec_to_caps() {
         assert_eq!(codec_to_gst_caps(&Codec::MJPEG), "image/jpeg");
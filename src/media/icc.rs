@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! sRGB ICC profile for saved photos
+//!
+//! The conversion shaders decode every camera format to sRGB, so JPEG and
+//! PNG photos are tagged with an sRGB profile. Color-managed viewers then
+//! show them as the preview did instead of guessing, which matters most on
+//! wide-gamut displays.
+//!
+//! The profile is a minimal ICC v4 matrix/TRC display profile, built once.
+
+use std::sync::OnceLock;
+
+/// D50, the profile connection space illuminant
+const D50: [f64; 3] = [0.9642, 1.0, 0.8249];
+
+/// sRGB primaries adapted to D50 (Bradford), as the ICC's own sRGB profile
+/// has them
+const RED: [f64; 3] = [0.4360747, 0.2225045, 0.0139322];
+const GREEN: [f64; 3] = [0.3850649, 0.7168786, 0.0971045];
+const BLUE: [f64; 3] = [0.1430804, 0.0606169, 0.7141733];
+
+/// Bradford adaptation from the sRGB white (D65) to D50, row-major
+const CHROMATIC_ADAPTATION: [f64; 9] = [
+    1.0478112, 0.0228866, -0.0501270, //
+    0.0295424, 0.9904844, -0.0170491, //
+    -0.0092345, 0.0150436, 0.7521316,
+];
+
+/// sRGB transfer as an ICC parametric curve (type 3: g, a, b, c, d)
+const SRGB_CURVE: [f64; 5] = [2.4, 1.0 / 1.055, 0.055 / 1.055, 1.0 / 12.92, 0.04045];
+
+/// Size of the ICC header
+const HEADER_LEN: usize = 128;
+
+/// The sRGB ICC profile
+pub fn srgb_profile() -> &'static [u8] {
+    static PROFILE: OnceLock<Vec<u8>> = OnceLock::new();
+    PROFILE.get_or_init(build_srgb_profile)
+}
+
+/// ICC `s15Fixed16Number`
+fn s15_fixed16(value: f64) -> [u8; 4] {
+    ((value * 65536.0).round() as i32).to_be_bytes()
+}
+
+/// Type signature and the four reserved bytes every tag starts with
+fn tag_type(signature: &[u8; 4]) -> Vec<u8> {
+    let mut data = signature.to_vec();
+    data.extend_from_slice(&[0; 4]);
+    data
+}
+
+/// `multiLocalizedUnicodeType` with a single en-US string
+fn mluc(text: &str) -> Vec<u8> {
+    let utf16: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+    let mut data = tag_type(b"mluc");
+    data.extend_from_slice(&1u32.to_be_bytes()); // records
+    data.extend_from_slice(&12u32.to_be_bytes()); // record size
+    data.extend_from_slice(b"enUS");
+    data.extend_from_slice(&(utf16.len() as u32).to_be_bytes());
+    data.extend_from_slice(&28u32.to_be_bytes()); // string offset
+    data.extend_from_slice(&utf16);
+    data
+}
+
+/// `XYZType` with one value
+fn xyz(value: [f64; 3]) -> Vec<u8> {
+    let mut data = tag_type(b"XYZ ");
+    data.extend(value.into_iter().flat_map(s15_fixed16));
+    data
+}
+
+fn build_srgb_profile() -> Vec<u8> {
+    let mut chad = tag_type(b"sf32");
+    chad.extend(CHROMATIC_ADAPTATION.into_iter().flat_map(s15_fixed16));
+
+    let mut curve = tag_type(b"para");
+    curve.extend_from_slice(&3u16.to_be_bytes());
+    curve.extend_from_slice(&[0; 2]);
+    curve.extend(SRGB_CURVE.into_iter().flat_map(s15_fixed16));
+
+    // Tag data, then the tags pointing into it; the three channels share
+    // one curve
+    let blocks = [
+        mluc("sRGB"),
+        mluc("No copyright, use freely"),
+        xyz(D50),
+        chad,
+        xyz(RED),
+        xyz(GREEN),
+        xyz(BLUE),
+        curve,
+    ];
+    let tags: [(&[u8; 4], usize); 10] = [
+        (b"desc", 0),
+        (b"cprt", 1),
+        (b"wtpt", 2),
+        (b"chad", 3),
+        (b"rXYZ", 4),
+        (b"gXYZ", 5),
+        (b"bXYZ", 6),
+        (b"rTRC", 7),
+        (b"gTRC", 7),
+        (b"bTRC", 7),
+    ];
+
+    let mut offsets = Vec::with_capacity(blocks.len());
+    let mut offset = HEADER_LEN + 4 + 12 * tags.len();
+    for block in &blocks {
+        offsets.push(offset);
+        // Tag data starts on 4-byte boundaries
+        offset += block.len().next_multiple_of(4);
+    }
+    let size = offset;
+
+    let mut profile = Vec::with_capacity(size);
+    profile.extend_from_slice(&(size as u32).to_be_bytes());
+    profile.extend_from_slice(&[0; 4]); // preferred CMM
+    profile.extend_from_slice(&0x0430_0000u32.to_be_bytes()); // version 4.3
+    profile.extend_from_slice(b"mntr");
+    profile.extend_from_slice(b"RGB ");
+    profile.extend_from_slice(b"XYZ ");
+    for part in [2025u16, 1, 1, 0, 0, 0] {
+        profile.extend_from_slice(&part.to_be_bytes());
+    }
+    profile.extend_from_slice(b"acsp");
+    // Platform, flags, manufacturer, model, attributes, rendering intent
+    // (perceptual)
+    profile.extend_from_slice(&[0; 28]);
+    profile.extend(D50.into_iter().flat_map(s15_fixed16));
+    // Creator, profile ID (not computed) and reserved bytes
+    profile.resize(HEADER_LEN, 0);
+
+    profile.extend_from_slice(&(tags.len() as u32).to_be_bytes());
+    for (signature, block) in tags {
+        profile.extend_from_slice(signature);
+        profile.extend_from_slice(&(offsets[block] as u32).to_be_bytes());
+        profile.extend_from_slice(&(blocks[block].len() as u32).to_be_bytes());
+    }
+    for block in blocks {
+        profile.extend_from_slice(&block);
+        profile.resize(profile.len().next_multiple_of(4), 0);
+    }
+    profile
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn be_u32(bytes: &[u8], at: usize) -> u32 {
+        u32::from_be_bytes(bytes[at..at + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn profile_header_and_tags_are_consistent() {
+        let profile = srgb_profile();
+        assert_eq!(be_u32(profile, 0) as usize, profile.len());
+        assert_eq!(&profile[36..40], b"acsp");
+        assert_eq!(&profile[12..24], b"mntrRGB XYZ ");
+
+        let count = be_u32(profile, HEADER_LEN) as usize;
+        assert_eq!(count, 10);
+        for tag in 0..count {
+            let entry = HEADER_LEN + 4 + 12 * tag;
+            let offset = be_u32(profile, entry + 4) as usize;
+            let size = be_u32(profile, entry + 8) as usize;
+            assert_eq!(offset % 4, 0);
+            assert!(offset + size <= profile.len());
+        }
+    }
+
+    #[test]
+    fn curve_matches_the_srgb_transfer() {
+        let [g, a, b, c, d] = SRGB_CURVE;
+        let eotf = |x: f64| if x >= d { (a * x + b).powf(g) } else { c * x };
+        assert!((eotf(1.0) - 1.0).abs() < 1e-9);
+        assert!((eotf(0.5) - 0.2140).abs() < 1e-3);
+        // Both pieces meet at the breakpoint
+        assert!((eotf(d) - c * d).abs() < 1e-5);
+    }
+}
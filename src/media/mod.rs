@@ -11,6 +11,36 @@
 //! - **Video**: H.264/H.265 with hardware acceleration (VA-API, NVENC)
 //! - **Audio**: AAC encoding with configurable quality
 //!
+//! # Animated Clips
+//!
+//! The [`animation`] module writes short frame sequences as looping GIF or
+//! WebP, with palette quantization for GIF.
+//!
+//! # Color Profiles
+//!
+//! The [`icc`] module builds the sRGB ICC profile saved photos are tagged
+//! with.
+//!
+//! # Document PDFs
+//!
+//! The [`pdf`] module writes scanned document pages, kept as JPEGs, into a
+//! multi-page PDF.
+//!
+//! # Text Recognition
+//!
+//! The [`ocr`] module reads the words on a page with Tesseract, for the
+//! searchable text layer of those PDFs and for copying text.
+//!
+//! # Scan Lists
+//!
+//! The [`scan_list`] module keeps the codes found in Scan mode, each once,
+//! and exports them as CSV.
+//!
+//! # Whiteboard Snapshots
+//!
+//! The [`whiteboard`] module straightens a photographed whiteboard and
+//! cleans its writing up into marker colours on white.
+//!
 //! # Format Detection
 //!
 //! The [`formats`] module provides codec metadata and format conversion utilities
@@ -18,13 +48,25 @@
 //!
 //! # Modules
 //!
+//! - [`animation`]: Animated GIF/WebP export
 //! - [`decoders`]: Hardware decoder detection and pipeline creation
 //! - [`encoders`]: Video/audio encoder selection and configuration
 //! - [`formats`]: Codec metadata and format conversion utilities
+//! - [`icc`]: sRGB ICC profile for saved photos
+//! - [`ocr`]: Text recognition (with the `ocr` feature)
+//! - [`pdf`]: Multi-page PDF export for document scans
+//! - [`scan_list`]: De-duplicated barcode list and its CSV export
+//! - [`whiteboard`]: Keystone correction and stroke enhancement for whiteboards
 
+pub mod animation;
 pub mod decoders;
 pub mod encoders;
 pub mod formats;
+pub mod icc;
+pub mod ocr;
+pub mod pdf;
+pub mod scan_list;
+pub mod whiteboard;
 
 // Re-export commonly used types
 pub use decoders::detect_hw_decoders;
@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Text recognition with Tesseract
+//!
+//! Scanned pages and whiteboard snapshots are read into words with their
+//! positions, which [`pdf`](super::pdf) lays over each page as an invisible
+//! text layer so the saved PDF can be searched and copied from. The same
+//! words joined into lines ([`OcrPage::text`]) are what "Copy text" puts on
+//! the clipboard, and what the QR detector falls back to around a code it
+//! found but could not decode.
+//!
+//! Tesseract is only linked in with the `ocr` feature; without it
+//! [`AVAILABLE`] is false and [`recognize`] always fails.
+
+/// Whether this build can recognize text
+pub const AVAILABLE: bool = cfg!(feature = "ocr");
+
+/// Tesseract language used when none is configured
+pub const DEFAULT_LANGUAGE: &str = "eng";
+
+/// A recognized word, in pixels of the image it was read from
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrWord {
+    pub text: String,
+    pub left: u32,
+    pub top: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Tesseract's confidence, 0 to 100
+    pub confidence: f32,
+    /// Block, paragraph and line the word belongs to
+    line: (u32, u32, u32),
+}
+
+/// The words recognized on one image, in reading order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OcrPage {
+    pub words: Vec<OcrWord>,
+}
+
+impl OcrPage {
+    /// Parse Tesseract's TSV output, keeping the word rows (level 5)
+    pub fn parse_tsv(tsv: &str) -> Self {
+        let words = tsv
+            .lines()
+            .filter_map(|row| {
+                let fields: Vec<&str> = row.splitn(12, '\t').collect();
+                let [
+                    level,
+                    _page,
+                    block,
+                    par,
+                    line,
+                    _word,
+                    left,
+                    top,
+                    width,
+                    height,
+                    conf,
+                    text,
+                ] = fields[..]
+                else {
+                    return None;
+                };
+                let text = text.trim();
+                if level != "5" || text.is_empty() {
+                    return None;
+                }
+                Some(OcrWord {
+                    text: text.to_string(),
+                    left: left.parse().ok()?,
+                    top: top.parse().ok()?,
+                    width: width.parse().ok()?,
+                    height: height.parse().ok()?,
+                    confidence: conf.parse().ok()?,
+                    line: (block.parse().ok()?, par.parse().ok()?, line.parse().ok()?),
+                })
+            })
+            .collect();
+        Self { words }
+    }
+
+    /// The page as plain text: words of a line joined by spaces, and a blank
+    /// line between paragraphs
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        let mut previous: Option<(u32, u32, u32)> = None;
+        for word in &self.words {
+            match previous {
+                Some(line) if line == word.line => text.push(' '),
+                Some((block, par, _)) if (block, par) == (word.line.0, word.line.1) => {
+                    text.push('\n')
+                }
+                Some(_) => text.push_str("\n\n"),
+                None => {}
+            }
+            text.push_str(&word.text);
+            previous = Some(word.line);
+        }
+        text
+    }
+
+    /// Mean word confidence, 0 to 100
+    pub fn confidence(&self) -> f32 {
+        if self.words.is_empty() {
+            return 0.0;
+        }
+        self.words.iter().map(|word| word.confidence).sum::<f32>() / self.words.len() as f32
+    }
+}
+
+/// Read the text in `image` (any format leptonica decodes: JPEG, PNG, ...)
+/// with the Tesseract `language`, e.g. `"eng"` or `"eng+deu"`. Blocking.
+#[cfg(feature = "ocr")]
+pub fn recognize(image: &[u8], language: &str) -> Result<OcrPage, String> {
+    let start = std::time::Instant::now();
+    let mut tess = leptess::LepTess::new(None, language)
+        .map_err(|e| format!("Failed to start Tesseract for '{language}': {e}"))?;
+    tess.set_image_from_mem(image)
+        .map_err(|e| format!("Tesseract could not read the image: {e}"))?;
+    // Pages are laid out at this resolution; frame crops have none of their
+    // own and it is a fair guess for them too
+    tess.set_source_resolution(super::pdf::PAGE_DPI as i32);
+    let tsv = tess
+        .get_tsv_text(0)
+        .map_err(|e| format!("Text recognition failed: {e}"))?;
+    let page = OcrPage::parse_tsv(&tsv);
+    tracing::debug!(
+        words = page.words.len(),
+        elapsed_ms = start.elapsed().as_millis(),
+        "Recognized text"
+    );
+    Ok(page)
+}
+
+/// Read the text in `image`. This build has no OCR engine.
+#[cfg(not(feature = "ocr"))]
+pub fn recognize(_image: &[u8], _language: &str) -> Result<OcrPage, String> {
+    Err("This build has no text recognition (the `ocr` feature is off)".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TSV: &str = "1\t1\t0\t0\t0\t0\t0\t0\t1200\t800\t-1\t\n\
+                       4\t1\t1\t1\t1\t0\t40\t30\t300\t24\t-1\t\n\
+                       5\t1\t1\t1\t1\t1\t40\t30\t120\t24\t96.5\tHello\n\
+                       5\t1\t1\t1\t1\t2\t170\t30\t170\t24\t91.0\tworld,\n\
+                       5\t1\t1\t1\t2\t1\t40\t60\t90\t24\t88.0\tsecond\n\
+                       5\t1\t1\t1\t2\t2\t140\t60\t40\t24\t-1\t \n\
+                       5\t1\t2\t1\t1\t1\t40\t200\t60\t24\t80.5\tNext\n";
+
+    #[test]
+    fn tsv_keeps_words_with_their_boxes() {
+        let page = OcrPage::parse_tsv(TSV);
+        assert_eq!(page.words.len(), 4);
+        let hello = &page.words[0];
+        assert_eq!(hello.text, "Hello");
+        assert_eq!(
+            (hello.left, hello.top, hello.width, hello.height),
+            (40, 30, 120, 24)
+        );
+        assert_eq!(hello.confidence, 96.5);
+        assert_eq!(page.confidence(), (96.5 + 91.0 + 88.0 + 80.5) / 4.0);
+    }
+
+    #[test]
+    fn text_breaks_lines_and_paragraphs() {
+        let page = OcrPage::parse_tsv(TSV);
+        assert_eq!(page.text(), "Hello world,\nsecond\n\nNext");
+        assert_eq!(OcrPage::default().text(), "");
+    }
+}
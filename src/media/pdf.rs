@@ -0,0 +1,244 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Multi-page PDF export for scanned documents
+//!
+//! Each page is one JPEG, embedded as-is with `DCTDecode` so nothing is
+//! re-encoded, on a page sized from the image at [`PAGE_DPI`]. The file is
+//! written by hand: a catalog, a page tree, and per page an image, a
+//! one-line content stream that draws it, and the page itself.
+//!
+//! Pages read with [`ocr`](super::ocr) also get their words in the content
+//! stream, drawn invisibly (text render mode 3) over where they appear in
+//! the image, so viewers can search and select the text. Words use the
+//! built-in Helvetica, stretched to the width Tesseract measured; the font
+//! object goes after the pages and is only written when a page has text.
+
+use super::ocr::OcrWord;
+use std::fmt::Write as _;
+
+/// Resolution pages are laid out at. A 1080p frame gives a page a little
+/// larger than A4 in landscape.
+pub const PAGE_DPI: f32 = 150.0;
+
+/// One JPEG page
+pub struct PdfPage<'a> {
+    /// Baseline or progressive JPEG with three (RGB) components
+    pub jpeg: &'a [u8],
+    pub width: u32,
+    pub height: u32,
+    /// Recognized words, in pixels of the JPEG; empty for an image-only page
+    pub words: &'a [OcrWord],
+}
+
+/// Average Helvetica glyph width, in ems, used to stretch a word over its box
+const AVERAGE_GLYPH_WIDTH: f32 = 0.5;
+
+/// `text` as a PDF string literal in WinAnsi encoding. Characters outside
+/// Latin-1 become `?`; they only matter for search, never for looks.
+fn pdf_string(text: &str) -> Vec<u8> {
+    let mut out = vec![b'('];
+    for ch in text.chars() {
+        match ch {
+            '(' | ')' | '\\' => out.extend_from_slice(&[b'\\', ch as u8]),
+            ' '..='~' | '\u{a0}'..='\u{ff}' => out.push(ch as u32 as u8),
+            _ => out.push(b'?'),
+        }
+    }
+    out.push(b')');
+    out
+}
+
+/// Content that lays `words` invisibly over a page of `page_height` points,
+/// scaling pixels by `scale` points each
+fn text_layer(words: &[OcrWord], page_height: f32, scale: f32) -> Vec<u8> {
+    let mut out = b"\nBT 3 Tr".to_vec();
+    for word in words {
+        let size = (word.height as f32 * scale).max(1.0);
+        let natural = size * AVERAGE_GLYPH_WIDTH * word.text.chars().count() as f32;
+        let stretch = 100.0 * word.width as f32 * scale / natural.max(0.01);
+        let x = word.left as f32 * scale;
+        // Baseline a fifth of the box up, above the descenders
+        let y = page_height - (word.top + word.height) as f32 * scale + size * 0.2;
+        out.extend_from_slice(
+            format!("\n/F1 {size:.2} Tf {stretch:.1} Tz 1 0 0 1 {x:.2} {y:.2} Tm ").as_bytes(),
+        );
+        out.extend_from_slice(&pdf_string(&word.text));
+        out.extend_from_slice(b" Tj");
+    }
+    out.extend_from_slice(b"\nET");
+    out
+}
+
+/// Write `pages` as a PDF, one image per page
+pub fn jpeg_pages_to_pdf(pages: &[PdfPage<'_>]) -> Vec<u8> {
+    // Objects 1 and 2 are the catalog and the page tree; each page then
+    // takes three: image, content stream, page. The font comes last.
+    let page_object = |index: usize| 5 + index * 3;
+    let font_object = 3 + pages.len() * 3;
+    let has_text = pages.iter().any(|page| !page.words.is_empty());
+
+    let mut pdf = Vec::new();
+    let mut offsets = Vec::new();
+    pdf.extend_from_slice(b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n");
+
+    let mut start_object = |pdf: &mut Vec<u8>, number: usize| {
+        debug_assert_eq!(offsets.len() + 1, number);
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{number} 0 obj\n").as_bytes());
+    };
+
+    start_object(&mut pdf, 1);
+    pdf.extend_from_slice(b"<< /Type /Catalog /Pages 2 0 R >>\nendobj\n");
+
+    start_object(&mut pdf, 2);
+    let mut kids = String::new();
+    for index in 0..pages.len() {
+        let _ = write!(kids, "{} 0 R ", page_object(index));
+    }
+    pdf.extend_from_slice(
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>\nendobj\n",
+            kids.trim_end(),
+            pages.len()
+        )
+        .as_bytes(),
+    );
+
+    for (index, page) in pages.iter().enumerate() {
+        let image = page_object(index) - 2;
+        let contents = page_object(index) - 1;
+        let page_width = page.width as f32 * 72.0 / PAGE_DPI;
+        let page_height = page.height as f32 * 72.0 / PAGE_DPI;
+
+        start_object(&mut pdf, image);
+        pdf.extend_from_slice(
+            format!(
+                "<< /Type /XObject /Subtype /Image /Width {} /Height {} \
+                 /ColorSpace /DeviceRGB /BitsPerComponent 8 /Filter /DCTDecode \
+                 /Length {} >>\nstream\n",
+                page.width,
+                page.height,
+                page.jpeg.len()
+            )
+            .as_bytes(),
+        );
+        pdf.extend_from_slice(page.jpeg);
+        pdf.extend_from_slice(b"\nendstream\nendobj\n");
+
+        let mut draw =
+            format!("q {page_width:.2} 0 0 {page_height:.2} 0 0 cm /Im0 Do Q").into_bytes();
+        let mut fonts = String::new();
+        if !page.words.is_empty() {
+            draw.extend(text_layer(page.words, page_height, 72.0 / PAGE_DPI));
+            fonts = format!(" /Font << /F1 {font_object} 0 R >>");
+        }
+        start_object(&mut pdf, contents);
+        pdf.extend_from_slice(format!("<< /Length {} >>\nstream\n", draw.len()).as_bytes());
+        pdf.extend_from_slice(&draw);
+        pdf.extend_from_slice(b"\nendstream\nendobj\n");
+
+        start_object(&mut pdf, page_object(index));
+        pdf.extend_from_slice(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {page_width:.2} {page_height:.2}] \
+                 /Resources << /XObject << /Im0 {image} 0 R >>{fonts} >> /Contents {contents} 0 R >>\n\
+                 endobj\n"
+            )
+            .as_bytes(),
+        );
+    }
+
+    if has_text {
+        start_object(&mut pdf, font_object);
+        pdf.extend_from_slice(
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica \
+              /Encoding /WinAnsiEncoding >>\nendobj\n",
+        );
+    }
+
+    let xref = pdf.len();
+    let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", offsets.len() + 1);
+    for offset in &offsets {
+        let _ = writeln!(table, "{offset:010} 00000 n ");
+    }
+    let _ = write!(
+        table,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+        offsets.len() + 1
+    );
+    pdf.extend_from_slice(table.as_bytes());
+    pdf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xref_points_at_every_object() {
+        let jpeg = [0xFF, 0xD8, 0xFF, 0xD9];
+        let pages = [
+            PdfPage {
+                jpeg: &jpeg,
+                width: 300,
+                height: 150,
+                words: &[],
+            },
+            PdfPage {
+                jpeg: &jpeg,
+                width: 150,
+                height: 300,
+                words: &[],
+            },
+        ];
+        let pdf = jpeg_pages_to_pdf(&pages);
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.contains("/Kids [5 0 R 8 0 R] /Count 2"));
+        assert!(text.contains("/MediaBox [0 0 144.00 72.00]"));
+
+        let xref = text.rfind("\nxref\n").expect("xref table") + 1;
+        let entries: Vec<usize> = text[xref..]
+            .lines()
+            .skip(3)
+            .take_while(|line| line.ends_with(" n "))
+            .map(|line| line[..10].parse().unwrap())
+            .collect();
+        assert_eq!(entries.len(), 8);
+        for (index, offset) in entries.into_iter().enumerate() {
+            let header = format!("{} 0 obj\n", index + 1);
+            assert_eq!(&pdf[offset..offset + header.len()], header.as_bytes());
+        }
+    }
+
+    #[test]
+    fn recognized_words_get_an_invisible_layer() {
+        let jpeg = [0xFF, 0xD8, 0xFF, 0xD9];
+        let ocr =
+            crate::media::ocr::OcrPage::parse_tsv("5\t1\t1\t1\t1\t1\t30\t60\t150\t30\t95\tf(x)\n");
+        let pages = [PdfPage {
+            jpeg: &jpeg,
+            width: 300,
+            height: 150,
+            words: &ocr.words,
+        }];
+        let pdf = jpeg_pages_to_pdf(&pages);
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.contains("/Font << /F1 6 0 R >>"));
+        assert!(text.contains("6 0 obj\n<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica"));
+        // 30 px at 150 dpi is 14.4 pt; four glyphs at half an em span 28.8 pt
+        // of the word's 72 pt box
+        assert!(
+            text.contains(
+                "BT 3 Tr\n/F1 14.40 Tf 250.0 Tz 1 0 0 1 14.40 31.68 Tm (f\\(x\\)) Tj\nET"
+            )
+        );
+    }
+
+    #[test]
+    fn strings_are_escaped_and_latin1() {
+        assert_eq!(pdf_string("a\\b"), b"(a\\\\b)");
+        assert_eq!(pdf_string("café €"), b"(caf\xe9 ?)");
+    }
+}
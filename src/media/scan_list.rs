@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Scan mode's list of codes and its CSV export
+//!
+//! Every code is listed once, the first time it is seen: a code held in
+//! front of the camera is decoded several times a second, and an inventory
+//! sweep wants each item once rather than once per frame. The list is
+//! written as RFC 4180 CSV with a header row, which spreadsheets and
+//! inventory tools import as is.
+
+use chrono::{DateTime, Local};
+use std::borrow::Cow;
+use std::path::Path;
+
+/// Header row of the exported CSV
+const CSV_HEADER: &str = "scanned_at,format,content";
+
+/// A code in the list
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScannedCode {
+    /// Symbology, e.g. `EAN_13`
+    pub format: String,
+    /// Decoded content
+    pub content: String,
+    /// When it was first seen
+    pub scanned_at: DateTime<Local>,
+}
+
+/// Codes scanned so far, in the order they were first seen
+#[derive(Debug, Clone, Default)]
+pub struct ScanList {
+    pub codes: Vec<ScannedCode>,
+}
+
+impl ScanList {
+    /// Add a code unless it is already listed. Returns whether it was new.
+    pub fn record(&mut self, format: &str, content: &str, scanned_at: DateTime<Local>) -> bool {
+        if self
+            .codes
+            .iter()
+            .any(|code| code.format == format && code.content == content)
+        {
+            return false;
+        }
+        self.codes.push(ScannedCode {
+            format: format.to_string(),
+            content: content.to_string(),
+            scanned_at,
+        });
+        true
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.codes.clear();
+    }
+
+    /// The list as CSV, header first, one row per code
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(CSV_HEADER);
+        csv.push_str("\r\n");
+        for code in &self.codes {
+            csv.push_str(&code.scanned_at.to_rfc3339());
+            csv.push(',');
+            csv.push_str(&csv_field(&code.format));
+            csv.push(',');
+            csv.push_str(&csv_field(&code.content));
+            csv.push_str("\r\n");
+        }
+        csv
+    }
+}
+
+/// Quote a field that contains a separator, quote or line break
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\r', '\n']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Write the list to a timestamped CSV in `save_dir`. Returns its path.
+/// Blocking.
+pub fn write_csv(list: &ScanList, save_dir: &Path, prefix: &str) -> Result<String, String> {
+    std::fs::create_dir_all(save_dir).map_err(|e| {
+        format!(
+            "Failed to create output directory '{}': {}",
+            save_dir.display(),
+            e
+        )
+    })?;
+    let timestamp = Local::now().format("%Y%m%d_%H%M%S_%3f");
+    let path = save_dir.join(format!("{prefix}_{timestamp}.csv"));
+    std::fs::write(&path, list.to_csv())
+        .map_err(|e| format!("Failed to save CSV to '{}': {}", path.display(), e))?;
+    Ok(path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn codes_are_listed_once_and_quoted_as_needed() {
+        let at = Local.with_ymd_and_hms(2026, 3, 1, 9, 30, 0).unwrap();
+        let mut list = ScanList::default();
+        assert!(list.record("EAN_13", "4006381333931", at));
+        assert!(!list.record("EAN_13", "4006381333931", at));
+        assert!(list.record("QR_CODE", "Shelf 4, \"top\"", at));
+        assert!(list.record("QR_CODE", "4006381333931", at));
+
+        let csv = list.to_csv();
+        let rows: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[0], CSV_HEADER);
+        assert!(rows[1].ends_with(",EAN_13,4006381333931"));
+        assert!(rows[2].ends_with(",QR_CODE,\"Shelf 4, \"\"top\"\"\""));
+        assert_eq!(rows[4], "");
+    }
+}
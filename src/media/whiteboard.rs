@@ -0,0 +1,289 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Whiteboard snapshot processing
+//!
+//! A snapshot goes through two steps. [`rectify`] undoes the keystone of a
+//! camera looking at the board from an angle, mapping the board's four
+//! corners ([`BoardQuad`]) onto a rectangle. [`enhance`] then cleans it up
+//! the way a scanner app would: the board colour and uneven lighting are
+//! estimated with a large box blur and divided out, pixels clearly darker
+//! than their surroundings become strokes (an adaptive threshold), and
+//! each stroke is snapped to the marker colour it is closest to, so glare
+//! and shadows drop out and the writing comes back crisp on white.
+
+use crate::backends::camera::types::SensorRotation;
+use image::RgbImage;
+
+/// Darkening relative to the local background at which a pixel starts to
+/// count as ink
+const INK_THRESHOLD: f32 = 0.18;
+
+/// Width of the soft edge around [`INK_THRESHOLD`], which keeps stroke
+/// outlines anti-aliased
+const INK_SOFTNESS: f32 = 0.08;
+
+/// Chroma (0-1) below which a stroke is taken for black marker
+const MIN_MARKER_CHROMA: f32 = 0.12;
+
+/// Rendered marker colours
+const BLACK: [f32; 3] = [0.12, 0.12, 0.14];
+const RED: [f32; 3] = [0.82, 0.13, 0.13];
+const GREEN: [f32; 3] = [0.07, 0.55, 0.2];
+const BLUE: [f32; 3] = [0.08, 0.27, 0.76];
+
+/// The board's corners in normalized frame coordinates (0-1), clockwise
+/// from top-left
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoardQuad {
+    pub corners: [(f32, f32); 4],
+}
+
+impl BoardQuad {
+    /// The whole frame
+    pub const FULL: BoardQuad = BoardQuad {
+        corners: [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)],
+    };
+
+    /// The same quad in a frame corrected for `rotation`, as the photo
+    /// pipeline rotates it
+    pub fn rotated(&self, rotation: SensorRotation) -> BoardQuad {
+        let map = |(u, v): (f32, f32)| match rotation {
+            SensorRotation::None => (u, v),
+            SensorRotation::Rotate90 => (v, 1.0 - u),
+            SensorRotation::Rotate180 => (1.0 - u, 1.0 - v),
+            SensorRotation::Rotate270 => (1.0 - v, u),
+        };
+        let mapped = self.corners.map(map);
+        // Keep the corners clockwise from the new top-left
+        let start = (0..4)
+            .min_by(|&a, &b| {
+                let (ax, ay) = mapped[a];
+                let (bx, by) = mapped[b];
+                (ax + ay).total_cmp(&(bx + by))
+            })
+            .unwrap_or(0);
+        BoardQuad {
+            corners: std::array::from_fn(|i| mapped[(start + i) % 4]),
+        }
+    }
+}
+
+/// Projective map from the unit square onto a quad
+struct Homography {
+    a: f32,
+    b: f32,
+    c: f32,
+    d: f32,
+    e: f32,
+    f: f32,
+    g: f32,
+    h: f32,
+}
+
+impl Homography {
+    /// Square-to-quad mapping (Heckbert): (0,0), (1,0), (1,1), (0,1) go to
+    /// the quad's corners in order
+    fn square_to_quad(corners: [(f32, f32); 4]) -> Self {
+        let [(x0, y0), (x1, y1), (x2, y2), (x3, y3)] = corners;
+        let sx = x0 - x1 + x2 - x3;
+        let sy = y0 - y1 + y2 - y3;
+        let (dx1, dx2, dy1, dy2) = (x1 - x2, x3 - x2, y1 - y2, y3 - y2);
+        let den = dx1 * dy2 - dx2 * dy1;
+        // A parallelogram (or a degenerate quad) maps affinely
+        let (g, h) = if den.abs() < 1e-9 {
+            (0.0, 0.0)
+        } else {
+            ((sx * dy2 - dx2 * sy) / den, (dx1 * sy - sx * dy1) / den)
+        };
+        Self {
+            a: x1 - x0 + g * x1,
+            b: x3 - x0 + h * x3,
+            c: x0,
+            d: y1 - y0 + g * y1,
+            e: y3 - y0 + h * y3,
+            f: y0,
+            g,
+            h,
+        }
+    }
+
+    fn map(&self, u: f32, v: f32) -> (f32, f32) {
+        let w = self.g * u + self.h * v + 1.0;
+        (
+            (self.a * u + self.b * v + self.c) / w,
+            (self.d * u + self.e * v + self.f) / w,
+        )
+    }
+}
+
+/// Map the board inside `quad` onto an upright rectangle
+///
+/// The output keeps the longer of each pair of opposite edges, so the
+/// nearer, larger side of the board sets the resolution.
+pub fn rectify(image: &RgbImage, quad: &BoardQuad) -> RgbImage {
+    let (width, height) = (image.width() as f32, image.height() as f32);
+    let corners = quad.corners.map(|(x, y)| (x * width, y * height));
+    let length = |a: (f32, f32), b: (f32, f32)| (a.0 - b.0).hypot(a.1 - b.1);
+    let [tl, tr, br, bl] = corners;
+    let out_width = length(tl, tr).max(length(bl, br)).round().clamp(1.0, width) as u32;
+    let out_height = length(tl, bl)
+        .max(length(tr, br))
+        .round()
+        .clamp(1.0, height) as u32;
+
+    let homography = Homography::square_to_quad(corners);
+    RgbImage::from_fn(out_width, out_height, |x, y| {
+        let u = (x as f32 + 0.5) / out_width as f32;
+        let v = (y as f32 + 0.5) / out_height as f32;
+        let (sx, sy) = homography.map(u, v);
+        image::Rgb(sample_bilinear(image, sx - 0.5, sy - 0.5))
+    })
+}
+
+fn sample_bilinear(image: &RgbImage, x: f32, y: f32) -> [u8; 3] {
+    let max_x = image.width() as i64 - 1;
+    let max_y = image.height() as i64 - 1;
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let px = |x: i64, y: i64| image.get_pixel(x.clamp(0, max_x) as u32, y.clamp(0, max_y) as u32);
+    let (x0, y0) = (x0 as i64, y0 as i64);
+    let (p00, p10, p01, p11) = (
+        px(x0, y0),
+        px(x0 + 1, y0),
+        px(x0, y0 + 1),
+        px(x0 + 1, y0 + 1),
+    );
+    std::array::from_fn(|c| {
+        let top = f32::from(p00[c]) * (1.0 - fx) + f32::from(p10[c]) * fx;
+        let bottom = f32::from(p01[c]) * (1.0 - fx) + f32::from(p11[c]) * fx;
+        (top * (1.0 - fy) + bottom * fy).round() as u8
+    })
+}
+
+/// Clean a rectified board up into marker strokes on white
+pub fn enhance(image: &RgbImage) -> RgbImage {
+    let (width, height) = image.dimensions();
+    let radius = (width.max(height) / 32).max(4);
+    let background = box_blur(image, radius);
+
+    RgbImage::from_fn(width, height, |x, y| {
+        let pixel = image.get_pixel(x, y);
+        let bg = background[(y * width + x) as usize];
+        // Divide the board colour and lighting out
+        let normalized: [f32; 3] =
+            std::array::from_fn(|c| (f32::from(pixel[c]) / bg[c].max(1.0)).min(1.0));
+        let luma = 0.299 * normalized[0] + 0.587 * normalized[1] + 0.114 * normalized[2];
+
+        let darkening = 1.0 - luma;
+        let ink = ((darkening - INK_THRESHOLD) / INK_SOFTNESS + 0.5).clamp(0.0, 1.0);
+        let marker = marker_color(normalized);
+        image::Rgb(std::array::from_fn(|c| {
+            ((1.0 - ink + ink * marker[c]) * 255.0).round() as u8
+        }))
+    })
+}
+
+/// Closest marker colour to a stroke pixel (background-normalized RGB)
+fn marker_color([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+    if chroma < MIN_MARKER_CHROMA {
+        return BLACK;
+    }
+    let hue = if max == r {
+        60.0 * ((g - b) / chroma).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / chroma + 2.0)
+    } else {
+        60.0 * ((r - g) / chroma + 4.0)
+    };
+    match hue {
+        h if !(45.0..290.0).contains(&h) => RED,
+        h if h < 170.0 => GREEN,
+        _ => BLUE,
+    }
+}
+
+/// Mean of each channel over a `(2 * radius + 1)` square around every
+/// pixel, from a summed-area table
+fn box_blur(image: &RgbImage, radius: u32) -> Vec<[f32; 3]> {
+    let (width, height) = (image.width() as usize, image.height() as usize);
+    let stride = width + 1;
+    let mut table = vec![[0u64; 3]; stride * (height + 1)];
+    for y in 0..height {
+        let mut row = [0u64; 3];
+        for x in 0..width {
+            let pixel = image.get_pixel(x as u32, y as u32);
+            for c in 0..3 {
+                row[c] += u64::from(pixel[c]);
+                table[(y + 1) * stride + x + 1][c] = table[y * stride + x + 1][c] + row[c];
+            }
+        }
+    }
+
+    let radius = radius as usize;
+    let mut out = Vec::with_capacity(width * height);
+    for y in 0..height {
+        let (y0, y1) = (y.saturating_sub(radius), (y + radius + 1).min(height));
+        for x in 0..width {
+            let (x0, x1) = (x.saturating_sub(radius), (x + radius + 1).min(width));
+            let area = ((x1 - x0) * (y1 - y0)) as f32;
+            out.push(std::array::from_fn(|c| {
+                let sum = table[y1 * stride + x1][c] + table[y0 * stride + x0][c]
+                    - table[y0 * stride + x1][c]
+                    - table[y1 * stride + x0][c];
+                sum as f32 / area
+            }));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn homography_maps_the_corners() {
+        let corners = [(10.0, 20.0), (90.0, 5.0), (100.0, 80.0), (0.0, 70.0)];
+        let homography = Homography::square_to_quad(corners);
+        for ((u, v), (x, y)) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+            .into_iter()
+            .zip(corners)
+        {
+            let (mx, my) = homography.map(u, v);
+            assert!((mx - x).abs() < 1e-3 && (my - y).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn rotated_quad_starts_at_the_new_top_left() {
+        let quad = BoardQuad {
+            corners: [(0.1, 0.2), (0.9, 0.2), (0.9, 0.8), (0.1, 0.8)],
+        };
+        assert_eq!(quad.rotated(SensorRotation::None), quad);
+        let turned = quad.rotated(SensorRotation::Rotate90);
+        let close = |(ax, ay): (f32, f32), (bx, by): (f32, f32)| {
+            (ax - bx).abs() < 1e-5 && (ay - by).abs() < 1e-5
+        };
+        assert!(close(turned.corners[0], (0.2, 0.1)));
+        assert!(close(turned.corners[2], (0.8, 0.9)));
+    }
+
+    #[test]
+    fn enhancement_whitens_the_board_and_keeps_marker_colours() {
+        // A dim, bluish-grey board with a black and a red stroke
+        let image = RgbImage::from_fn(64, 64, |x, _| match x {
+            20..=22 => image::Rgb([20, 20, 25]),
+            40..=42 => image::Rgb([150, 30, 30]),
+            _ => image::Rgb([160, 165, 180]),
+        });
+        let enhanced = enhance(&image);
+        assert_eq!(enhanced.get_pixel(5, 30).0, [255, 255, 255]);
+        let black = enhanced.get_pixel(21, 30).0;
+        assert!(black.iter().all(|&c| c < 60), "{black:?}");
+        let red = enhanced.get_pixel(41, 30).0;
+        assert!(red[0] > 150 && red[1] < 80 && red[2] < 80, "{red:?}");
+    }
+}
@@ -2,24 +2,126 @@
 //!
 //! This module provides WiFi connection functionality via NetworkManager's D-Bus API,
 //! which works in both native and flatpak environments (with appropriate D-Bus permissions).
+//!
+//! Joining a network is reported step by step ([`WifiJoinStatus`]): once
+//! NetworkManager has the connection up, the internet is checked with
+//! NetworkManager's own connectivity check, or with the same probe it uses
+//! when that check is turned off, so a captive portal's sign-in page is
+//! noticed instead of the join silently "succeeding".
 
+use futures::Stream;
 use std::collections::HashMap;
-use tracing::{error, info};
-use zbus::zvariant::{ObjectPath, Value};
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value};
+
+/// Settings or secrets of a NetworkManager connection, by setting name
+type ConnectionSettings = HashMap<String, HashMap<String, OwnedValue>>;
+
+/// The WiFi network this machine is connected to, as needed to share it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WifiCredentials {
+    pub ssid: String,
+    /// Password (None for open networks)
+    pub password: Option<String>,
+    /// Security type, in the terms of [`join_wifi`] ("WPA", "WPA3", "WEP", "OPEN")
+    pub security: String,
+    pub hidden: bool,
+}
+
+/// Page the connectivity probe fetches. A captive portal intercepts it, so
+/// opening it in a browser leads to the portal's sign-in page.
+pub const CONNECTIVITY_CHECK_URL: &str = "http://nmcheck.gnome.org/check_network_status.txt";
+
+/// What [`CONNECTIVITY_CHECK_URL`] answers when nothing is in the way
+const CONNECTIVITY_CHECK_RESPONSE: &str = "NetworkManager is online";
+
+/// How long NetworkManager gets to associate and get an address
+const ACTIVATION_TIMEOUT: Duration = Duration::from_secs(45);
+
+/// Interval between reads of the connection's activation state
+const ACTIVATION_POLL_INTERVAL: Duration = Duration::from_millis(250);
 
-/// Connect to a WiFi network using NetworkManager's D-Bus API
+/// NM_ACTIVE_CONNECTION_STATE_ACTIVATED
+const ACTIVE_CONNECTION_ACTIVATED: u32 = 2;
+
+/// Where joining a WiFi network stands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WifiJoinStatus {
+    /// NetworkManager is associating and getting an address
+    Connecting,
+    /// Joined; checking whether the internet can be reached
+    Checking,
+    /// Joined, with internet access
+    Online,
+    /// Joined, but a captive portal wants a sign-in first
+    CaptivePortal,
+    /// Joined, without internet access
+    NoInternet,
+    /// Joining failed
+    Failed(String),
+}
+
+impl WifiJoinStatus {
+    /// Whether the join is still going
+    pub fn is_pending(&self) -> bool {
+        matches!(self, Self::Connecting | Self::Checking)
+    }
+}
+
+/// Join a WiFi network and check the internet behind it, reporting each
+/// step. The stream ends with the outcome: [`WifiJoinStatus::Online`],
+/// [`CaptivePortal`](WifiJoinStatus::CaptivePortal),
+/// [`NoInternet`](WifiJoinStatus::NoInternet) or
+/// [`Failed`](WifiJoinStatus::Failed).
 ///
 /// # Arguments
 /// * `ssid` - The network SSID
 /// * `password` - Optional password (None for open networks)
 /// * `security` - Security type (e.g., "WPA", "WPA2", "WPA3", "WEP", "OPEN")
 /// * `hidden` - Whether the network is hidden
-pub async fn connect_wifi(
+pub fn join_wifi(
     ssid: String,
     password: Option<String>,
     security: String,
     hidden: bool,
-) -> Result<(), String> {
+) -> impl Stream<Item = WifiJoinStatus> {
+    async_stream::stream! {
+        yield WifiJoinStatus::Connecting;
+        let joined = async {
+            let connection = zbus::Connection::system()
+                .await
+                .map_err(|e| format!("Failed to connect to system D-Bus: {}", e))?;
+            let active = connect_wifi(&connection, &ssid, password.as_deref(), &security, hidden)
+                .await?;
+            wait_for_activation(&connection, &active).await?;
+            Ok::<_, String>(connection)
+        }
+        .await;
+        match joined {
+            Ok(connection) => {
+                yield WifiJoinStatus::Checking;
+                let status = check_connectivity(&connection).await;
+                info!(ssid = %ssid, ?status, "WiFi joined");
+                yield status;
+            }
+            Err(e) => {
+                error!(ssid = %ssid, error = %e, "Failed to join WiFi");
+                yield WifiJoinStatus::Failed(e);
+            }
+        }
+    }
+}
+
+/// Add the network and ask NetworkManager to connect to it. Returns the
+/// active connection, which is still activating.
+async fn connect_wifi(
+    connection: &zbus::Connection,
+    ssid: &str,
+    password: Option<&str>,
+    security: &str,
+    hidden: bool,
+) -> Result<OwnedObjectPath, String> {
     info!(
         ssid = %ssid,
         security = %security,
@@ -28,20 +130,15 @@ pub async fn connect_wifi(
         "Connecting to WiFi via NetworkManager D-Bus"
     );
 
-    // Connect to the system bus
-    let connection = zbus::Connection::system()
-        .await
-        .map_err(|e| format!("Failed to connect to system D-Bus: {}", e))?;
-
     // Build connection settings
-    let settings = build_connection_settings(&ssid, password.as_deref(), &security, hidden);
+    let settings = build_connection_settings(ssid, password, security, hidden);
 
     // Find a suitable WiFi device
-    let device_path = find_wifi_device(&connection).await?;
+    let device_path = find_wifi_device(connection).await?;
 
     // Use AddAndActivateConnection to add and connect in one step
     let nm_proxy = zbus::Proxy::new(
-        &connection,
+        connection,
         "org.freedesktop.NetworkManager",
         "/org/freedesktop/NetworkManager",
         "org.freedesktop.NetworkManager",
@@ -71,9 +168,9 @@ pub async fn connect_wifi(
                 connection = %connection_path,
                 active = %active_connection_path,
                 ssid = %ssid,
-                "WiFi connection activated successfully"
+                "WiFi connection activation started"
             );
-            Ok(())
+            Ok(active_connection_path)
         }
         Err(e) => {
             error!(ssid = %ssid, error = %e, "Failed to activate WiFi connection");
@@ -82,6 +179,240 @@ pub async fn connect_wifi(
     }
 }
 
+/// Wait until the active connection is up, or NetworkManager gives up on it
+async fn wait_for_activation(
+    connection: &zbus::Connection,
+    active: &OwnedObjectPath,
+) -> Result<(), String> {
+    let proxy = zbus::Proxy::new(
+        connection,
+        "org.freedesktop.NetworkManager",
+        active.as_str(),
+        "org.freedesktop.NetworkManager.Connection.Active",
+    )
+    .await
+    .map_err(|e| format!("Failed to create active connection proxy: {}", e))?;
+
+    let deadline = Instant::now() + ACTIVATION_TIMEOUT;
+    loop {
+        // The object disappears when activation fails, e.g. on a wrong password
+        let state: u32 = proxy
+            .get_property("State")
+            .await
+            .map_err(|_| "NetworkManager could not join the network".to_string())?;
+        match state {
+            ACTIVE_CONNECTION_ACTIVATED => return Ok(()),
+            // Deactivating or deactivated
+            3 | 4 => return Err("NetworkManager could not join the network".to_string()),
+            _ => {}
+        }
+        if Instant::now() >= deadline {
+            return Err("Timed out joining the network".to_string());
+        }
+        tokio::time::sleep(ACTIVATION_POLL_INTERVAL).await;
+    }
+}
+
+/// Whether the internet can be reached, asking NetworkManager first and
+/// probing ourselves when its check is turned off
+async fn check_connectivity(connection: &zbus::Connection) -> WifiJoinStatus {
+    let state = match zbus::Proxy::new(
+        connection,
+        "org.freedesktop.NetworkManager",
+        "/org/freedesktop/NetworkManager",
+        "org.freedesktop.NetworkManager",
+    )
+    .await
+    {
+        Ok(nm_proxy) => nm_proxy
+            .call::<_, _, u32>("CheckConnectivity", &())
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "NetworkManager connectivity check failed");
+                0
+            }),
+        Err(_) => 0,
+    };
+    match connectivity_status(state) {
+        Some(status) => status,
+        None => probe_connectivity().await,
+    }
+}
+
+/// Status for an NMConnectivityState, or None when NetworkManager doesn't
+/// know (its check is turned off)
+fn connectivity_status(state: u32) -> Option<WifiJoinStatus> {
+    match state {
+        // NM_CONNECTIVITY_FULL
+        4 => Some(WifiJoinStatus::Online),
+        // NM_CONNECTIVITY_PORTAL
+        2 => Some(WifiJoinStatus::CaptivePortal),
+        // NM_CONNECTIVITY_NONE, NM_CONNECTIVITY_LIMITED
+        1 | 3 => Some(WifiJoinStatus::NoInternet),
+        _ => None,
+    }
+}
+
+/// Fetch [`CONNECTIVITY_CHECK_URL`] without following redirects
+async fn probe_connectivity() -> WifiJoinStatus {
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap_or_default();
+    match client.get(CONNECTIVITY_CHECK_URL).send().await {
+        Ok(response) => {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            probe_status(status, &body)
+        }
+        Err(e) => {
+            info!(error = %e, "Connectivity probe failed");
+            WifiJoinStatus::NoInternet
+        }
+    }
+}
+
+/// Read the connectivity probe's answer: anything but the expected page
+/// (a redirect, a login form) is a captive portal
+fn probe_status(status: reqwest::StatusCode, body: &str) -> WifiJoinStatus {
+    if status.is_success() && body.trim_start().starts_with(CONNECTIVITY_CHECK_RESPONSE) {
+        WifiJoinStatus::Online
+    } else {
+        WifiJoinStatus::CaptivePortal
+    }
+}
+
+/// Read the SSID and password of the active WiFi connection
+///
+/// NetworkManager only hands out the password after polkit agrees, which for
+/// a connection the user owns or saved normally needs no prompt.
+pub async fn active_wifi() -> Result<WifiCredentials, String> {
+    let connection = zbus::Connection::system()
+        .await
+        .map_err(|e| format!("Failed to connect to system D-Bus: {}", e))?;
+    let nm_proxy = zbus::Proxy::new(
+        &connection,
+        "org.freedesktop.NetworkManager",
+        "/org/freedesktop/NetworkManager",
+        "org.freedesktop.NetworkManager",
+    )
+    .await
+    .map_err(|e| format!("Failed to create NetworkManager proxy: {}", e))?;
+    let active: Vec<OwnedObjectPath> = nm_proxy
+        .get_property("ActiveConnections")
+        .await
+        .map_err(|e| format!("Failed to get active connections: {}", e))?;
+
+    for active_path in active {
+        let active_proxy = zbus::Proxy::new(
+            &connection,
+            "org.freedesktop.NetworkManager",
+            active_path.as_str(),
+            "org.freedesktop.NetworkManager.Connection.Active",
+        )
+        .await
+        .map_err(|e| format!("Failed to create active connection proxy: {}", e))?;
+        let kind: String = active_proxy.get_property("Type").await.unwrap_or_default();
+        if kind != "802-11-wireless" {
+            continue;
+        }
+        let settings_path: OwnedObjectPath = active_proxy
+            .get_property("Connection")
+            .await
+            .map_err(|e| format!("Failed to get connection settings path: {}", e))?;
+        let settings_proxy = zbus::Proxy::new(
+            &connection,
+            "org.freedesktop.NetworkManager",
+            settings_path.as_str(),
+            "org.freedesktop.NetworkManager.Settings.Connection",
+        )
+        .await
+        .map_err(|e| format!("Failed to create connection proxy: {}", e))?;
+        let settings: ConnectionSettings = settings_proxy
+            .call("GetSettings", &())
+            .await
+            .map_err(|e| format!("Failed to read connection settings: {}", e))?;
+        let secrets: Option<ConnectionSettings> =
+            if settings.contains_key("802-11-wireless-security") {
+                Some(
+                    settings_proxy
+                        .call("GetSecrets", &("802-11-wireless-security",))
+                        .await
+                        .map_err(|e| format!("Failed to read the WiFi password: {}", e))?,
+                )
+            } else {
+                None
+            };
+        let credentials = wifi_credentials(&settings, secrets.as_ref())?;
+        info!(ssid = %credentials.ssid, security = %credentials.security, "Read active WiFi connection");
+        return Ok(credentials);
+    }
+
+    Err("Not connected to a WiFi network".to_string())
+}
+
+/// Pick the SSID, security and password out of a WiFi connection's settings
+/// and secrets
+fn wifi_credentials(
+    settings: &ConnectionSettings,
+    secrets: Option<&ConnectionSettings>,
+) -> Result<WifiCredentials, String> {
+    fn field<'a>(
+        settings: &'a ConnectionSettings,
+        setting: &str,
+        key: &str,
+    ) -> Option<&'a Value<'static>> {
+        settings.get(setting)?.get(key).map(|value| &**value)
+    }
+    let string = |value: Option<&Value>| match value {
+        Some(Value::Str(s)) => Some(s.to_string()),
+        _ => None,
+    };
+
+    let ssid = match field(settings, "802-11-wireless", "ssid") {
+        Some(Value::Array(bytes)) => bytes
+            .iter()
+            .filter_map(|byte| match byte {
+                Value::U8(byte) => Some(*byte),
+                _ => None,
+            })
+            .collect::<Vec<u8>>(),
+        _ => return Err("The WiFi connection has no SSID".to_string()),
+    };
+    let ssid = String::from_utf8_lossy(&ssid).into_owned();
+    let hidden = matches!(
+        field(settings, "802-11-wireless", "hidden"),
+        Some(Value::Bool(true))
+    );
+
+    let key_mgmt = string(field(settings, "802-11-wireless-security", "key-mgmt"));
+    let (security, secret_key) = match key_mgmt.as_deref() {
+        None => ("OPEN", None),
+        Some("none") => ("WEP", Some("wep-key0")),
+        Some("wpa-psk") => ("WPA", Some("psk")),
+        Some("sae") => ("WPA3", Some("psk")),
+        Some(other) => {
+            return Err(format!(
+                "WiFi networks using '{}' can't be shared as a QR code",
+                other
+            ));
+        }
+    };
+    let password =
+        secret_key.and_then(|key| string(field(secrets?, "802-11-wireless-security", key)));
+    if secret_key.is_some() && password.is_none() {
+        return Err("NetworkManager did not share the WiFi password".to_string());
+    }
+
+    Ok(WifiCredentials {
+        ssid,
+        password,
+        security: security.to_string(),
+        hidden,
+    })
+}
+
 /// Build the connection settings dictionary for NetworkManager
 fn build_connection_settings<'a>(
     ssid: &'a str,
@@ -198,3 +529,65 @@ async fn find_wifi_device(
 
     Err("No WiFi device found".to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(entries: &[(&str, &str, Value<'_>)]) -> ConnectionSettings {
+        let mut settings = ConnectionSettings::new();
+        for (setting, key, value) in entries {
+            settings
+                .entry(setting.to_string())
+                .or_default()
+                .insert(key.to_string(), value.try_to_owned().unwrap());
+        }
+        settings
+    }
+
+    #[test]
+    fn wifi_credentials_come_from_settings_and_secrets() {
+        let wpa = settings(&[
+            ("802-11-wireless", "ssid", Value::new(b"Home".to_vec())),
+            ("802-11-wireless", "hidden", Value::new(true)),
+            ("802-11-wireless-security", "key-mgmt", Value::new("sae")),
+        ]);
+        let secrets = settings(&[("802-11-wireless-security", "psk", Value::new("hunter22"))]);
+        assert_eq!(
+            wifi_credentials(&wpa, Some(&secrets)),
+            Ok(WifiCredentials {
+                ssid: "Home".to_string(),
+                password: Some("hunter22".to_string()),
+                security: "WPA3".to_string(),
+                hidden: true,
+            })
+        );
+        assert!(wifi_credentials(&wpa, None).is_err());
+
+        let open = settings(&[("802-11-wireless", "ssid", Value::new(b"Cafe".to_vec()))]);
+        let open = wifi_credentials(&open, None).unwrap();
+        assert_eq!((open.security.as_str(), open.password), ("OPEN", None));
+    }
+
+    #[test]
+    fn portals_are_told_apart_from_the_internet() {
+        use reqwest::StatusCode;
+        assert_eq!(connectivity_status(4), Some(WifiJoinStatus::Online));
+        assert_eq!(connectivity_status(2), Some(WifiJoinStatus::CaptivePortal));
+        assert_eq!(connectivity_status(3), Some(WifiJoinStatus::NoInternet));
+        assert_eq!(connectivity_status(0), None);
+
+        assert_eq!(
+            probe_status(StatusCode::OK, "NetworkManager is online\n"),
+            WifiJoinStatus::Online
+        );
+        assert_eq!(
+            probe_status(StatusCode::FOUND, ""),
+            WifiJoinStatus::CaptivePortal
+        );
+        assert_eq!(
+            probe_status(StatusCode::OK, "<html>Hotel WiFi login</html>"),
+            WifiJoinStatus::CaptivePortal
+        );
+    }
+}
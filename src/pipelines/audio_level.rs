@@ -5,6 +5,8 @@
 
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicI32, Ordering};
 
 use gstreamer as gst;
 use gstreamer::prelude::*;
@@ -16,6 +18,10 @@ use gstreamer::prelude::*;
 /// settings shows what the recording will record.
 pub const PULSESRC_SLAVE_METHOD: &str = "skew";
 
+/// PulseAudio name for the monitor of the default output, i.e. whatever the
+/// desktop is currently playing. Follows the default sink when it changes.
+pub const SYSTEM_AUDIO_DEVICE: &str = "@DEFAULT_MONITOR@";
+
 /// Shared parameters for the dynamics-processing chain used by both the
 /// recorder pipeline and the settings audio probe. Single source of truth
 /// avoids the two pipelines drifting apart.
@@ -34,6 +40,89 @@ pub mod dynamics {
     pub const LIMITER_RATIO: f32 = 0.05;
 }
 
+/// Lowest user-selectable microphone gain, in dB relative to the default
+/// makeup gain.
+pub const MIN_GAIN_DB: i32 = -12;
+/// Highest user-selectable microphone gain. The limiter after the makeup
+/// stage keeps even the top of the range from clipping the encoder.
+pub const MAX_GAIN_DB: i32 = 12;
+
+/// Largest audio sync offset either way, in milliseconds.
+pub const MAX_SYNC_OFFSET_MS: i32 = 500;
+
+/// Live audio-to-video offset in milliseconds, shared between the settings
+/// drawer and a running recorder so it can be adjusted mid-recording.
+/// Positive values delay the audio.
+pub type SharedSyncOffset = Arc<AtomicI32>;
+
+/// Sample rate both supported noise suppressors operate at. `audiornnoise`
+/// only accepts 48 kHz; `webrtcdsp` accepts it among others.
+const NOISE_SUPPRESSION_RATE_HZ: u32 = 48_000;
+
+/// User-tunable stages of the capture chain, shared by the recorder and the
+/// settings probe so the meter reacts to the same gain and suppression the
+/// recording will get.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct AudioProcessing {
+    /// Extra gain on top of [`dynamics::MAKEUP_GAIN`], in dB.
+    pub gain_db: i32,
+    /// Run the capture through a noise suppressor before the dynamics chain.
+    pub noise_suppression: bool,
+}
+
+impl AudioProcessing {
+    /// Linear volume for the makeup-gain `volume` element.
+    pub fn makeup_volume(&self) -> f64 {
+        let gain_db = self.gain_db.clamp(MIN_GAIN_DB, MAX_GAIN_DB);
+        dynamics::MAKEUP_GAIN * 10f64.powf(f64::from(gain_db) / 20.0)
+    }
+
+    /// Bin description of the noise-suppression stage, or `None` when it is
+    /// disabled or no suppressor plugin is installed.
+    pub fn noise_suppressor(&self) -> Option<&'static str> {
+        if self.noise_suppression {
+            noise_suppressor_description()
+        } else {
+            None
+        }
+    }
+
+    /// Sample rate the capsfilter should pin. The suppressors need 48 kHz;
+    /// otherwise the source rate flows through as in
+    /// [`opus_target_rate`](crate::media::encoders::audio::opus_target_rate).
+    pub fn target_rate(&self, source_rate_hz: u32) -> u32 {
+        if self.noise_suppressor().is_some() {
+            NOISE_SUPPRESSION_RATE_HZ
+        } else {
+            crate::media::encoders::audio::opus_target_rate(source_rate_hz)
+        }
+    }
+}
+
+/// Pick the noise suppressor to use, probing the registry once.
+///
+/// RNNoise (`audiornnoise`, gst-plugins-rs) is preferred — it handles
+/// keyboard and fan noise better than the WebRTC suppressor and needs no
+/// echo probe. `webrtcdsp` (gst-plugins-bad) is the fallback, with echo
+/// cancellation and AGC disabled so it only suppresses noise and leaves
+/// levelling to the dynamics chain. Each is wrapped in `audioconvert` so it
+/// can negotiate its preferred sample format.
+pub fn noise_suppressor_description() -> Option<&'static str> {
+    static SUPPRESSOR: OnceLock<Option<&'static str>> = OnceLock::new();
+    *SUPPRESSOR.get_or_init(|| {
+        if gst::ElementFactory::find("audiornnoise").is_some() {
+            Some("audioconvert ! audiornnoise ! audioconvert")
+        } else if gst::ElementFactory::find("webrtcdsp").is_some() {
+            Some(
+                "audioconvert ! webrtcdsp echo-cancel=false gain-control=false \
+                 noise-suppression=true noise-suppression-level=high ! audioconvert",
+            )
+        } else {
+            None
+        }
+    })
+}
+
 /// Live audio level data shared between a GStreamer pipeline and the UI.
 #[derive(Debug, Clone)]
 pub struct AudioLevels {
@@ -45,6 +134,10 @@ pub struct AudioLevels {
     pub output_peak_db: f64,
     /// Mono output RMS level in dB (after mix).
     pub output_rms_db: f64,
+    /// Desktop-audio track peak level in dB (loudest channel).
+    pub system_peak_db: f64,
+    /// Desktop-audio track RMS level in dB (loudest channel).
+    pub system_rms_db: f64,
 }
 
 impl Default for AudioLevels {
@@ -54,6 +147,8 @@ impl Default for AudioLevels {
             input_rms_db: Vec::new(),
             output_peak_db: -100.0,
             output_rms_db: -100.0,
+            system_peak_db: -100.0,
+            system_rms_db: -100.0,
         }
     }
 }
@@ -102,6 +197,10 @@ pub fn install_level_sync_handler(pipeline: &gst::Pipeline, levels: &SharedAudio
             } else if src_name == "audio-level-output" {
                 lock.output_peak_db = peak_db.first().copied().unwrap_or(-100.0);
                 lock.output_rms_db = rms_db.first().copied().unwrap_or(-100.0);
+            } else if src_name == "audio-level-system" {
+                let loudest = |levels: &[f64]| levels.iter().copied().fold(-100.0, f64::max);
+                lock.system_peak_db = loudest(&peak_db);
+                lock.system_rms_db = loudest(&rms_db);
             }
         }
 
@@ -109,3 +208,104 @@ pub fn install_level_sync_handler(pipeline: &gst::Pipeline, levels: &SharedAudio
         gst::BusSyncReply::Drop
     });
 }
+
+/// Shift a timestamp by `offset_ms`, clamped to [`MAX_SYNC_OFFSET_MS`].
+/// `None` when an advance would move it before the start of the stream.
+fn shift_timestamp(ts: gst::ClockTime, offset_ms: i32) -> Option<gst::ClockTime> {
+    let offset_ms = offset_ms.clamp(-MAX_SYNC_OFFSET_MS, MAX_SYNC_OFFSET_MS);
+    let offset = gst::ClockTime::from_mseconds(u64::from(offset_ms.unsigned_abs()));
+    if offset_ms >= 0 {
+        ts.checked_add(offset)
+    } else {
+        ts.checked_sub(offset)
+    }
+}
+
+/// Re-time every buffer leaving `pad` by the current value of `offset`.
+///
+/// Installed on the microphone encoder's src pad, so the shift lands on the
+/// muxer's input for that track only. The offset is read per buffer, which
+/// makes changes take effect immediately; buffers an advance would push
+/// before zero are dropped.
+pub fn install_sync_offset_probe(pad: &gst::Pad, offset: SharedSyncOffset) {
+    pad.add_probe(gst::PadProbeType::BUFFER, move |_, info| {
+        let offset_ms = offset.load(Ordering::Relaxed);
+        if offset_ms == 0 {
+            return gst::PadProbeReturn::Ok;
+        }
+        let Some(gst::PadProbeData::Buffer(ref mut buffer)) = info.data else {
+            return gst::PadProbeReturn::Ok;
+        };
+        let buffer = buffer.make_mut();
+        if let Some(pts) = buffer.pts() {
+            let Some(shifted) = shift_timestamp(pts, offset_ms) else {
+                return gst::PadProbeReturn::Drop;
+            };
+            buffer.set_pts(shifted);
+        }
+        if let Some(dts) = buffer.dts() {
+            buffer.set_dts(shift_timestamp(dts, offset_ms));
+        }
+        gst::PadProbeReturn::Ok
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sync_offset_shifts_both_ways_and_clamps() {
+        let ts = gst::ClockTime::from_seconds(2);
+        assert_eq!(
+            shift_timestamp(ts, 120),
+            Some(gst::ClockTime::from_mseconds(2_120))
+        );
+        assert_eq!(
+            shift_timestamp(ts, -120),
+            Some(gst::ClockTime::from_mseconds(1_880))
+        );
+        assert_eq!(
+            shift_timestamp(ts, 10_000),
+            Some(gst::ClockTime::from_mseconds(2_500))
+        );
+        assert_eq!(
+            shift_timestamp(gst::ClockTime::from_mseconds(100), -200),
+            None
+        );
+    }
+
+    #[test]
+    fn zero_gain_keeps_default_makeup() {
+        let processing = AudioProcessing::default();
+        assert_eq!(processing.makeup_volume(), dynamics::MAKEUP_GAIN);
+    }
+
+    #[test]
+    fn gain_is_applied_in_decibels_and_clamped() {
+        let boosted = AudioProcessing {
+            gain_db: 6,
+            ..Default::default()
+        };
+        let ratio = boosted.makeup_volume() / dynamics::MAKEUP_GAIN;
+        assert!((ratio - 1.995).abs() < 0.01, "+6 dB should roughly double");
+
+        let over = AudioProcessing {
+            gain_db: MAX_GAIN_DB + 20,
+            ..Default::default()
+        };
+        let max = AudioProcessing {
+            gain_db: MAX_GAIN_DB,
+            ..Default::default()
+        };
+        assert_eq!(over.makeup_volume(), max.makeup_volume());
+    }
+
+    #[test]
+    fn disabled_suppression_keeps_opus_rate() {
+        let processing = AudioProcessing::default();
+        assert_eq!(processing.noise_suppressor(), None);
+        assert_eq!(processing.target_rate(16_000), 16_000);
+        assert_eq!(processing.target_rate(44_100), 48_000);
+    }
+}
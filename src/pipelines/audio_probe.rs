@@ -12,22 +12,39 @@
 //! [`SharedAudioLevels`] mutex that the UI then snapshots on a 100 ms tick.
 //!
 //! The probe is owned by `AppModel` and torn down whenever the settings
-//! drawer closes, `record_audio` is toggled off, the selected device
-//! changes, or a real recording starts.
+//! drawer closes, `record_audio` is toggled off, the selected device or
+//! noise-suppression setting changes, or a real recording starts. Gain
+//! changes are applied to the running pipeline.
+//!
+//! When desktop audio recording is enabled, a second independent chain
+//! (monitor source → stereo capsfilter → `audio-level-system` level) runs in
+//! the same pipeline so both meters update together.
 
 use gstreamer as gst;
 use gstreamer::prelude::*;
 use tracing::{info, warn};
 
 use crate::pipelines::audio_level::{
-    PULSESRC_SLAVE_METHOD, SharedAudioLevels, dynamics, install_level_sync_handler,
+    AudioProcessing, PULSESRC_SLAVE_METHOD, SYSTEM_AUDIO_DEVICE, SharedAudioLevels, dynamics,
+    install_level_sync_handler,
 };
 
+/// Which sources the probe meters. Mirrors the recorder's audio tracks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProbeSources {
+    /// Meter the microphone through the recorder's processing chain
+    pub microphone: bool,
+    /// Meter the desktop audio monitor
+    pub system: bool,
+}
+
 /// Running probe pipeline. Drop or call [`AudioLevelProbe::stop`] to tear down.
 pub struct AudioLevelProbe {
     pipeline: gst::Pipeline,
     levels: SharedAudioLevels,
     device: Option<String>,
+    processing: AudioProcessing,
+    sources: ProbeSources,
 }
 
 impl AudioLevelProbe {
@@ -36,13 +53,28 @@ impl AudioLevelProbe {
     /// system default. `source_rate_hz` is the source's native sample rate
     /// (0 for "unknown") — the probe pins the capsfilter to the same Opus-
     /// compatible rate the recorder will use, so the meter reflects what the
-    /// recording actually captures.
-    pub fn start(device: Option<&str>, source_rate_hz: u32) -> Result<Self, String> {
+    /// recording actually captures. `processing` mirrors the recorder's gain
+    /// and noise-suppression stages for the same reason. `sources` picks
+    /// which chains are built; at least one must be enabled.
+    pub fn start(
+        device: Option<&str>,
+        source_rate_hz: u32,
+        processing: AudioProcessing,
+        sources: ProbeSources,
+    ) -> Result<Self, String> {
+        if !sources.microphone && !sources.system {
+            return Err("No audio sources to probe".to_string());
+        }
+
         let device_str = device
             .map(|d| format!("device=\"{}\" ", d.replace('"', "\\\"")))
             .unwrap_or_default();
 
-        let target_rate = crate::media::encoders::audio::opus_target_rate(source_rate_hz);
+        let target_rate = processing.target_rate(source_rate_hz);
+        let suppressor = processing
+            .noise_suppressor()
+            .map(|desc| format!("! {desc} "))
+            .unwrap_or_default();
 
         // Same compressor + makeup-gain chain the recorder uses, so the meter
         // shows what the recording captures, not the raw mic. See
@@ -52,13 +84,14 @@ impl AudioLevelProbe {
         // `VideoRecorder::create_audio_branch` for the rationale. Dynamics
         // parameters come from `dynamics::*` so the meter reflects exactly
         // what the recording will capture.
-        let desc = format!(
+        let microphone = format!(
             "pulsesrc name=probe-src {device_str}slave-method={slave} do-timestamp=true provide-clock=false \
              ! audioconvert \
              ! audioresample \
              ! capsfilter caps=audio/x-raw,channels=1,rate={rate} \
+             {suppressor}\
              ! audiodynamic mode=compressor characteristics=soft-knee threshold={ct} ratio={cr} \
-             ! volume volume={mg} \
+             ! volume name=probe-gain volume={mg} \
              ! audiodynamic mode=compressor characteristics=hard-knee threshold={lt} ratio={lr} \
              ! level name=audio-level-output post-messages=true interval=100000000 \
              ! fakesink sync=false",
@@ -66,10 +99,28 @@ impl AudioLevelProbe {
             rate = target_rate,
             ct = dynamics::COMPRESSOR_THRESHOLD,
             cr = dynamics::COMPRESSOR_RATIO,
-            mg = dynamics::MAKEUP_GAIN,
+            mg = processing.makeup_volume(),
             lt = dynamics::LIMITER_THRESHOLD,
             lr = dynamics::LIMITER_RATIO,
         );
+        let system = format!(
+            "pulsesrc name=probe-system-src device=\"{device}\" slave-method={slave} do-timestamp=true provide-clock=false \
+             ! audioconvert \
+             ! audioresample \
+             ! capsfilter caps=audio/x-raw,channels=2,rate=48000 \
+             ! level name=audio-level-system post-messages=true interval=100000000 \
+             ! fakesink sync=false",
+            device = SYSTEM_AUDIO_DEVICE,
+            slave = PULSESRC_SLAVE_METHOD,
+        );
+        let desc = [
+            sources.microphone.then_some(microphone),
+            sources.system.then_some(system),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
 
         info!(desc = %desc, "Starting audio-level probe pipeline");
 
@@ -89,6 +140,8 @@ impl AudioLevelProbe {
             pipeline,
             levels,
             device: device.map(|d| d.to_string()),
+            processing,
+            sources,
         })
     }
 
@@ -103,6 +156,25 @@ impl AudioLevelProbe {
         self.device.as_deref()
     }
 
+    /// The gain and noise-suppression settings the probe is running with.
+    pub fn processing(&self) -> AudioProcessing {
+        self.processing
+    }
+
+    /// The sources the probe is metering.
+    pub fn sources(&self) -> ProbeSources {
+        self.sources
+    }
+
+    /// Apply a new gain to the running pipeline without restarting it, so
+    /// dragging the gain slider doesn't interrupt the meter.
+    pub fn set_gain(&mut self, gain_db: i32) {
+        self.processing.gain_db = gain_db;
+        if let Some(volume) = self.pipeline.by_name("probe-gain") {
+            volume.set_property("volume", self.processing.makeup_volume());
+        }
+    }
+
     /// Stop the pipeline and release GStreamer resources.
     pub fn stop(self) {
         if let Some(bus) = self.pipeline.bus() {
@@ -0,0 +1,210 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Photo booth collage
+//!
+//! A photo-booth run takes several shots, each after its own countdown (the
+//! app drives this with the photo timer), and saves them as one image: a
+//! vertical strip or a two-column grid on a white card. Every shot goes
+//! through the regular post-processor so it can carry its own filter, and
+//! the collage is written by the photo encoder in the configured format.
+
+use super::processing::ProcessedImage;
+use super::{
+    CameraMetadata, EncodingFormat, EncodingQuality, PhotoEncoder, PostProcessingConfig,
+    PostProcessor,
+};
+use crate::app::FilterType;
+use crate::backends::camera::types::{CameraFrame, SensorRotation};
+use crate::config::PhotoBoothLayout;
+use image::RgbImage;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::info;
+
+/// Shot counts offered in settings
+pub const SHOT_COUNTS: [u8; 4] = [2, 3, 4, 6];
+
+/// Countdowns before each shot (seconds) offered in settings
+pub const COUNTDOWN_SECONDS: [u8; 3] = [3, 5, 10];
+
+/// Filters cycled through when every shot gets its own
+const SHOT_FILTERS: [FilterType; 4] = [
+    FilterType::Standard,
+    FilterType::Mono,
+    FilterType::Sepia,
+    FilterType::Vivid,
+];
+
+/// Gap between shots and around the card, as a fraction of a shot's
+/// longer side
+const GAP_FRACTION: f32 = 0.04;
+
+const CARD_COLOR: image::Rgb<u8> = image::Rgb([255, 255, 255]);
+
+/// Photo booth collage configuration
+#[derive(Debug, Clone)]
+pub struct PhotoBoothConfig {
+    pub layout: PhotoBoothLayout,
+    /// Filter applied to each shot, in shot order
+    pub filters: Vec<FilterType>,
+    /// Sensor rotation to correct each shot's orientation
+    pub rotation: SensorRotation,
+    /// Mirror the shots horizontally
+    pub mirror_horizontal: bool,
+    pub encoding_format: EncodingFormat,
+    pub camera_metadata: CameraMetadata,
+}
+
+/// Filter for shot `index`: the selected one, or with `per_shot` a fixed
+/// sequence so the strip looks like a classic booth print
+pub fn shot_filter(index: usize, selected: FilterType, per_shot: bool) -> FilterType {
+    if per_shot {
+        SHOT_FILTERS[index % SHOT_FILTERS.len()]
+    } else {
+        selected
+    }
+}
+
+/// Process the shots, lay them out and save the collage
+///
+/// Returns the path of the saved image.
+pub async fn process_photo_booth(
+    frames: Vec<Arc<CameraFrame>>,
+    config: PhotoBoothConfig,
+    output_dir: PathBuf,
+) -> Result<PathBuf, String> {
+    if frames.is_empty() {
+        return Err("No shots taken for the photo booth".to_string());
+    }
+
+    info!(
+        shots = frames.len(),
+        layout = ?config.layout,
+        "Processing photo booth collage"
+    );
+
+    let mut shots = Vec::with_capacity(frames.len());
+    for (index, frame) in frames.into_iter().enumerate() {
+        let filter_type = config
+            .filters
+            .get(index)
+            .copied()
+            .unwrap_or(FilterType::Standard);
+        let post_processor = PostProcessor::new(PostProcessingConfig {
+            filter_type,
+            rotation: config.rotation,
+            mirror_horizontal: config.mirror_horizontal,
+            ..Default::default()
+        });
+        shots.push(post_processor.process(frame).await?.image);
+    }
+
+    let layout = config.layout;
+    let collage = tokio::task::spawn_blocking(move || compose_collage(&shots, layout))
+        .await
+        .map_err(|e| format!("Photo booth task error: {}", e))?;
+    let (width, height) = collage.dimensions();
+
+    let mut encoder = PhotoEncoder::new();
+    encoder.set_format(config.encoding_format);
+    encoder.set_quality(EncodingQuality::High);
+    encoder.set_camera_metadata(config.camera_metadata);
+    let encoded = encoder
+        .encode(ProcessedImage {
+            image: collage,
+            width,
+            height,
+        })
+        .await?;
+    let path = encoder.save(encoded, output_dir).await?;
+
+    info!(path = %path.display(), width, height, "Photo booth collage saved");
+    Ok(path)
+}
+
+/// Columns and rows of the collage for `count` shots
+fn grid_size(layout: PhotoBoothLayout, count: u32) -> (u32, u32) {
+    match layout {
+        PhotoBoothLayout::Strip => (1, count),
+        PhotoBoothLayout::Grid => (count.min(2), count.div_ceil(2)),
+    }
+}
+
+/// Place the shots on a white card, left to right then top to bottom.
+/// Shots that differ in size from the first (the camera changed mid-run)
+/// are scaled to match.
+fn compose_collage(shots: &[RgbImage], layout: PhotoBoothLayout) -> RgbImage {
+    let Some(first) = shots.first() else {
+        return RgbImage::new(0, 0);
+    };
+    let (cell_width, cell_height) = first.dimensions();
+    let gap = ((cell_width.max(cell_height) as f32 * GAP_FRACTION).round() as u32).max(1);
+    let (columns, rows) = grid_size(layout, shots.len() as u32);
+
+    let mut card = RgbImage::from_pixel(
+        columns * cell_width + (columns + 1) * gap,
+        rows * cell_height + (rows + 1) * gap,
+        CARD_COLOR,
+    );
+    for (index, shot) in shots.iter().enumerate() {
+        let (column, row) = (index as u32 % columns, index as u32 / columns);
+        let x = (gap + column * (cell_width + gap)) as i64;
+        let y = (gap + row * (cell_height + gap)) as i64;
+        if shot.dimensions() == (cell_width, cell_height) {
+            image::imageops::replace(&mut card, shot, x, y);
+        } else {
+            let scaled = image::imageops::resize(
+                shot,
+                cell_width,
+                cell_height,
+                image::imageops::FilterType::Triangle,
+            );
+            image::imageops::replace(&mut card, &scaled, x, y);
+        }
+    }
+    card
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shot(value: u8) -> RgbImage {
+        RgbImage::from_pixel(100, 50, image::Rgb([value, value, value]))
+    }
+
+    #[test]
+    fn strip_stacks_shots_vertically() {
+        let card = compose_collage(&[shot(10), shot(20), shot(30)], PhotoBoothLayout::Strip);
+        // gap = 4% of 100 px
+        assert_eq!(card.dimensions(), (108, 166));
+        assert_eq!(card.get_pixel(50, 4 + 25).0, [10; 3]);
+        assert_eq!(card.get_pixel(50, 4 + 54 + 25).0, [20; 3]);
+        assert_eq!(card.get_pixel(50, 4 + 108 + 25).0, [30; 3]);
+        assert_eq!(card.get_pixel(1, 1).0, [255; 3]);
+    }
+
+    #[test]
+    fn grid_leaves_an_odd_last_cell_blank() {
+        let card = compose_collage(&[shot(10), shot(20), shot(30)], PhotoBoothLayout::Grid);
+        assert_eq!(card.dimensions(), (212, 112));
+        assert_eq!(card.get_pixel(4 + 104 + 50, 4 + 25).0, [20; 3]);
+        assert_eq!(card.get_pixel(4 + 50, 4 + 54 + 25).0, [30; 3]);
+        assert_eq!(card.get_pixel(4 + 104 + 50, 4 + 54 + 25).0, [255; 3]);
+    }
+
+    #[test]
+    fn mismatched_shots_are_scaled_to_the_first() {
+        let small = RgbImage::from_pixel(50, 25, image::Rgb([40, 40, 40]));
+        let card = compose_collage(&[shot(10), small], PhotoBoothLayout::Strip);
+        assert_eq!(card.dimensions(), (108, 112));
+        assert_eq!(card.get_pixel(50, 4 + 54 + 25).0, [40; 3]);
+    }
+
+    #[test]
+    fn per_shot_filters_cycle() {
+        assert_eq!(shot_filter(1, FilterType::Noir, false), FilterType::Noir);
+        assert_eq!(shot_filter(1, FilterType::Noir, true), FilterType::Mono);
+        assert_eq!(shot_filter(4, FilterType::Noir, true), FilterType::Standard);
+    }
+}
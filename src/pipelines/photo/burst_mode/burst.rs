@@ -239,6 +239,40 @@ pub fn calculate_adaptive_params(brightness: SceneBrightness) -> AdaptiveBurstPa
     }
 }
 
+/// Noise, in 8-bit levels, a merged burst should be left with
+const TARGET_NOISE_SD: f32 = 1.5;
+
+/// Largest burst sized from the measured noise (HDR+ paper: 2-8 images)
+const MAX_NOISE_AWARE_FRAMES: usize = 8;
+
+/// Noise level the brightness-based robustness is tuned for
+const REFERENCE_NOISE_SD: f32 = 3.0;
+
+/// Calculate burst parameters from scene brightness and measured noise
+///
+/// Merging N frames divides the noise by about sqrt(N), so the burst is
+/// made long enough to bring the measured noise down to
+/// [`TARGET_NOISE_SD`], but never shorter than the brightness alone calls
+/// for. Robustness follows the noise too: a clean sensor in a dim scene
+/// doesn't need the aggressive merge a noisy one does. Shadow boost and
+/// local contrast, the exposure of the merged result, stay with the
+/// brightness.
+pub fn calculate_noise_aware_params(
+    brightness: SceneBrightness,
+    noise_sd: Option<f32>,
+) -> AdaptiveBurstParams {
+    let mut params = calculate_adaptive_params(brightness);
+    let Some(noise_sd) = noise_sd.filter(|sd| sd.is_finite() && *sd > 0.0) else {
+        return params;
+    };
+    if params.frame_count > 1 {
+        let needed = (noise_sd / TARGET_NOISE_SD).powi(2).ceil() as usize;
+        params.frame_count = needed.clamp(params.frame_count, MAX_NOISE_AWARE_FRAMES);
+        params.robustness *= (noise_sd / REFERENCE_NOISE_SD).clamp(0.5, 1.5);
+    }
+    params
+}
+
 /// Estimate scene brightness from a single frame
 ///
 /// Computes average luminance from the frame using BT.601 coefficients.
@@ -614,4 +648,29 @@ mod tests {
         // Darker scenes should have higher motion threshold (more lenient)
         assert!(dark.motion_threshold > bright.motion_threshold);
     }
+
+    #[test]
+    fn test_noise_aware_params() {
+        // Without a measurement the brightness decides
+        let medium = calculate_noise_aware_params(SceneBrightness::Medium, None);
+        assert_eq!(medium.frame_count, 4);
+
+        // A noisy sensor needs a longer burst than the brightness suggests
+        let noisy = calculate_noise_aware_params(SceneBrightness::Medium, Some(3.6));
+        assert_eq!(noisy.frame_count, 6);
+        assert!(noisy.robustness > medium.robustness);
+
+        // A clean one never gets a shorter burst, but a gentler merge
+        let clean = calculate_noise_aware_params(SceneBrightness::Low, Some(1.0));
+        assert_eq!(clean.frame_count, 6);
+        assert!(clean.robustness < calculate_adaptive_params(SceneBrightness::Low).robustness);
+
+        // Capped at the HDR+ maximum
+        let dark = calculate_noise_aware_params(SceneBrightness::VeryDark, Some(20.0));
+        assert_eq!(dark.frame_count, MAX_NOISE_AWARE_FRAMES);
+
+        // Bright scenes stay single frame
+        let bright = calculate_noise_aware_params(SceneBrightness::VeryBright, Some(5.0));
+        assert_eq!(bright.frame_count, 1);
+    }
 }
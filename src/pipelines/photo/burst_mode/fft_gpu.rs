@@ -17,6 +17,7 @@
 use super::GpuAlignedFrame;
 use crate::gpu::wgpu;
 use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info};
 
 const TILE_SIZE: u32 = 16;
@@ -84,12 +85,13 @@ impl FftMergePipeline {
     /// Create a compute pipeline with common defaults (delegates to `gpu_helpers`).
     fn create_pipeline(
         device: &wgpu::Device,
+        cache: Option<&wgpu::PipelineCache>,
         label: &str,
         layout: &wgpu::PipelineLayout,
         module: &wgpu::ShaderModule,
         entry_point: &str,
     ) -> wgpu::ComputePipeline {
-        super::gpu_helpers::create_pipeline(device, label, layout, module, entry_point)
+        super::gpu_helpers::create_pipeline(device, cache, label, layout, module, entry_point)
     }
 
     /// Create a new FFT merge pipeline using an existing GPU device and queue
@@ -102,6 +104,7 @@ impl FftMergePipeline {
         device: Arc<wgpu::Device>,
         queue: Arc<wgpu::Queue>,
         max_storage_buffer_size: u64,
+        cache: Option<&wgpu::PipelineCache>,
     ) -> Result<Self, String> {
         info!("Initializing FFT merge GPU pipeline (using shared device)");
         let init_start = std::time::Instant::now();
@@ -145,6 +148,7 @@ impl FftMergePipeline {
         let pipelines = FftPipelineSet {
             init: Self::create_pipeline(
                 &device,
+                cache,
                 "init_output_pipeline",
                 &pipeline_layout,
                 &shader_module,
@@ -152,6 +156,7 @@ impl FftMergePipeline {
             ),
             rms: Self::create_pipeline(
                 &device,
+                cache,
                 "calculate_rms_pipeline",
                 &pipeline_layout,
                 &shader_module,
@@ -159,6 +164,7 @@ impl FftMergePipeline {
             ),
             mismatch: Self::create_pipeline(
                 &device,
+                cache,
                 "calculate_mismatch_pipeline",
                 &pipeline_layout,
                 &shader_module,
@@ -166,6 +172,7 @@ impl FftMergePipeline {
             ),
             highlights_norm: Self::create_pipeline(
                 &device,
+                cache,
                 "calculate_highlights_norm_pipeline",
                 &pipeline_layout,
                 &shader_module,
@@ -173,6 +180,7 @@ impl FftMergePipeline {
             ),
             normalize_mismatch: Self::create_pipeline(
                 &device,
+                cache,
                 "normalize_mismatch_pipeline",
                 &pipeline_layout,
                 &shader_module,
@@ -180,6 +188,7 @@ impl FftMergePipeline {
             ),
             add_reference: Self::create_pipeline(
                 &device,
+                cache,
                 "add_reference_pipeline",
                 &pipeline_layout,
                 &shader_module,
@@ -187,6 +196,7 @@ impl FftMergePipeline {
             ),
             merge: Self::create_pipeline(
                 &device,
+                cache,
                 "merge_tile_pipeline",
                 &pipeline_layout,
                 &shader_module,
@@ -194,6 +204,7 @@ impl FftMergePipeline {
             ),
             normalize: Self::create_pipeline(
                 &device,
+                cache,
                 "normalize_output_pipeline",
                 &pipeline_layout,
                 &shader_module,
@@ -201,6 +212,7 @@ impl FftMergePipeline {
             ),
             reduce_artifacts: Self::create_pipeline(
                 &device,
+                cache,
                 "reduce_tile_artifacts_pipeline",
                 &pipeline_layout,
                 &shader_module,
@@ -235,6 +247,7 @@ impl FftMergePipeline {
 
         let spatial_denoise_init_pipeline = Self::create_pipeline(
             &device,
+            cache,
             "spatial_denoise_init_pipeline",
             &spatial_denoise_pipeline_layout,
             &spatial_denoise_shader,
@@ -242,6 +255,7 @@ impl FftMergePipeline {
         );
         let spatial_denoise_pipeline = Self::create_pipeline(
             &device,
+            cache,
             "spatial_denoise_pipeline",
             &spatial_denoise_pipeline_layout,
             &spatial_denoise_shader,
@@ -249,6 +263,7 @@ impl FftMergePipeline {
         );
         let spatial_denoise_normalize_pipeline = Self::create_pipeline(
             &device,
+            cache,
             "spatial_denoise_normalize_pipeline",
             &spatial_denoise_pipeline_layout,
             &spatial_denoise_shader,
@@ -282,6 +297,7 @@ impl FftMergePipeline {
 
         let chroma_denoise_pipeline = Self::create_pipeline(
             &device,
+            cache,
             "chroma_denoise_pipeline",
             &chroma_denoise_pipeline_layout,
             &chroma_denoise_shader,
@@ -751,9 +767,11 @@ impl FftMergePipeline {
     /// * `height` - Frame height
     /// * `noise_sd` - Estimated noise standard deviation
     /// * `robustness` - Robustness parameter (higher = more aggressive merge)
+    /// * `cancel` - Checked before each aligned frame is merged
     ///
     /// # Returns
     /// Merged frame data (RGBA u8)
+    #[allow(clippy::too_many_arguments)]
     pub async fn merge_gpu(
         &self,
         reference: &[u8],
@@ -762,6 +780,7 @@ impl FftMergePipeline {
         height: u32,
         noise_sd: f32,
         robustness: f32,
+        cancel: &CancellationToken,
     ) -> Result<Vec<u8>, String> {
         let tile_size = TILE_SIZE;
         let pipelines = &self.pipelines;
@@ -1001,6 +1020,7 @@ impl FftMergePipeline {
 
         // Step 3: Process each aligned frame - GPU-to-GPU copy, no CPU involved!
         for (frame_idx, gpu_frame) in aligned_frames.iter().enumerate() {
+            super::check_cancelled(cancel)?;
             let frame_start = std::time::Instant::now();
 
             // GPU-to-GPU buffer copy - no CPU round-trip!
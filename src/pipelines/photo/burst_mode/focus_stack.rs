@@ -0,0 +1,247 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Focus stacking for macro shots
+//!
+//! A macro lens has too little depth of field to keep a whole object sharp.
+//! The app sweeps the lens across the focus range (V4L2 focus control, or
+//! the lens actuator of a libcamera sensor) and keeps a frame at each
+//! position; this module merges them into one image that is in focus
+//! everywhere.
+//!
+//! Every frame is aligned to the middle one with the burst alignment, which
+//! takes out hand shake and most of the focus breathing. Each pixel is then
+//! a weighted mean over the frames, weighted by how sharp that frame is
+//! around it (local Laplacian energy, from the sharpness shader).
+
+use super::params::SharpnessParams;
+use super::{
+    BurstModeGpuPipeline, MergedFrame, SaveOutputParams, convert_frame_to_rgba, save_output,
+    sharpen_output, u8_to_f32_normalized,
+};
+use crate::backends::camera::types::{CameraFrame, SensorRotation};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// Number of focus positions a sweep can be set to
+pub const STEP_COUNTS: [u8; 4] = [3, 5, 7, 9];
+
+/// How long the lens is given to settle at each position before its frame
+/// is kept
+pub const SETTLE_MS: u64 = 400;
+
+/// Focus stack configuration
+#[derive(Debug, Clone)]
+pub struct FocusStackConfig {
+    /// Output encoding format
+    pub encoding_format: crate::pipelines::photo::EncodingFormat,
+    /// Camera metadata for the saved file
+    pub camera_metadata: crate::pipelines::photo::CameraMetadata,
+    /// Sensor rotation to correct the image orientation
+    pub rotation: SensorRotation,
+    /// Mirror the image horizontally
+    pub mirror_horizontal: bool,
+    /// Unsharp mask applied to the merged image
+    pub output_sharpening: crate::config::OutputSharpening,
+}
+
+/// Focus positions of a sweep from `min` to `max`, evenly spaced and
+/// rounded to the control's `step`
+pub fn focus_positions(min: i32, max: i32, step: i32, count: usize) -> Vec<i32> {
+    if count < 2 || max <= min {
+        return vec![min];
+    }
+    let step = step.max(1);
+    let mut positions: Vec<i32> = (0..count)
+        .map(|i| {
+            let value = min as f64 + (max - min) as f64 * i as f64 / (count - 1) as f64;
+            let snapped = min + ((value - min as f64) / step as f64).round() as i32 * step;
+            snapped.clamp(min, max)
+        })
+        .collect();
+    // Narrow ranges with coarse steps repeat positions
+    positions.dedup();
+    positions
+}
+
+/// Merge a focus sweep and save it
+///
+/// Returns the path of the saved image.
+pub async fn process_focus_stack(
+    frames: Vec<Arc<CameraFrame>>,
+    config: FocusStackConfig,
+    output_dir: PathBuf,
+) -> Result<PathBuf, String> {
+    let total_start = std::time::Instant::now();
+    if frames.len() < 2 {
+        return Err("Focus stacking needs at least two frames".to_string());
+    }
+    if frames.iter().any(|frame| frame.format.is_bayer()) {
+        return Err("Focus stacking needs RGB frames, not raw Bayer".to_string());
+    }
+    info!(frames = frames.len(), "Processing focus stack");
+
+    let gpu = BurstModeGpuPipeline::shared().await?;
+    let merged = gpu.merge_focus_stack(&frames).await?;
+    let merged = sharpen_output(merged, config.output_sharpening).await;
+
+    let path = save_output(
+        &merged,
+        SaveOutputParams {
+            output_dir,
+            crop_rect: None,
+            encoding_format: config.encoding_format,
+            camera_metadata: config.camera_metadata,
+            filter: None,
+            rotation: config.rotation,
+            filename_suffix: Some("_STACK"),
+            mirror_horizontal: config.mirror_horizontal,
+        },
+    )
+    .await?;
+    info!(
+        path = %path.display(),
+        total_elapsed_ms = total_start.elapsed().as_millis(),
+        "Focus stack saved"
+    );
+    Ok(path)
+}
+
+impl BurstModeGpuPipeline {
+    /// Align the sweep to its middle frame and merge it by local sharpness
+    async fn merge_focus_stack(&self, frames: &[Arc<CameraFrame>]) -> Result<MergedFrame, String> {
+        // The middle of the sweep is magnified halfway between the ends
+        let ref_idx = frames.len() / 2;
+        let reference = &frames[ref_idx];
+        let (width, height) = (reference.width, reference.height);
+        let pixel_count = (width * height) as usize;
+        let frame_bytes = (pixel_count * 4 * std::mem::size_of::<f32>()) as u64;
+        if frame_bytes > self.tile_budget().largest_buffer {
+            return Err(format!(
+                "{}x{} frames are too large to focus stack on this GPU",
+                width, height
+            ));
+        }
+        let workgroups = (width.div_ceil(16), height.div_ceil(16), 1);
+
+        let buffers = self.create_alignment_buffers(width, height);
+        let ref_rgba = convert_frame_to_rgba(reference).await?;
+        let ref_f32 = u8_to_f32_normalized(&ref_rgba);
+        drop(ref_rgba);
+        let ref_buffer = self.create_storage_buffer("focus_stack_reference", frame_bytes);
+        self.queue
+            .write_buffer(&ref_buffer, 0, bytemuck::cast_slice(&ref_f32));
+        drop(ref_f32);
+        let ref_pyramids = self.build_reference_pyramid(&ref_buffer, width, height, &buffers);
+
+        // Starts zeroed; the shader's score output goes unused here
+        let accum = self.create_storage_buffer("focus_stack_accum", frame_bytes);
+        let unused = self.create_storage_buffer("focus_stack_unused", 4);
+        let params_buffer = self.create_uniform_buffer(
+            "focus_stack_params",
+            std::mem::size_of::<SharpnessParams>() as u64,
+        );
+        let params = SharpnessParams {
+            width,
+            height,
+            tile_size: super::SHARPNESS_TILE_SIZE,
+            n_tiles_x: width.div_ceil(super::SHARPNESS_TILE_SIZE),
+            n_tiles_y: height.div_ceil(super::SHARPNESS_TILE_SIZE),
+            _padding: [0; 3],
+        };
+        self.queue
+            .write_buffer(&params_buffer, 0, bytemuck::cast_slice(&[params]));
+
+        let mut merged_frames = 0;
+        for (idx, frame) in frames.iter().enumerate() {
+            if frame.width != width || frame.height != height {
+                warn!(
+                    frame = idx,
+                    frame_width = frame.width,
+                    frame_height = frame.height,
+                    "Skipping focus stack frame with mismatched dimensions"
+                );
+                continue;
+            }
+
+            // Aligned frames are dropped once accumulated, so only one is
+            // held on the GPU at a time
+            let aligned = if idx == ref_idx {
+                None
+            } else {
+                let rgba = convert_frame_to_rgba(frame).await?;
+                let rgba_f32 = u8_to_f32_normalized(&rgba);
+                drop(rgba);
+                self.queue
+                    .write_buffer(&buffers.comp_rgba, 0, bytemuck::cast_slice(&rgba_f32));
+                drop(rgba_f32);
+                Some(
+                    self.align_single_frame_from_buffer(
+                        &ref_pyramids,
+                        width,
+                        height,
+                        &buffers,
+                        (0.0, 0.0),
+                    )
+                    .await?,
+                )
+            };
+            let input = aligned.as_ref().map_or(&ref_buffer, |frame| &frame.buffer);
+
+            let bg = self.bind_group(
+                "focus_stack_accumulate_bg",
+                &self.sharpness_layout,
+                &[input, &accum, &unused, &params_buffer],
+            );
+            self.dispatch_compute(
+                "focus_stack_accumulate",
+                &self.focus_stack_accumulate,
+                &bg,
+                workgroups,
+            );
+            merged_frames += 1;
+            debug!(frame = idx, "Focus stack frame accumulated");
+            self.yield_to_compositor().await;
+        }
+
+        let bg = self.bind_group(
+            "focus_stack_normalize_bg",
+            &self.sharpness_layout,
+            &[&ref_buffer, &accum, &unused, &params_buffer],
+        );
+        self.dispatch_compute(
+            "focus_stack_normalize",
+            &self.focus_stack_normalize,
+            &bg,
+            workgroups,
+        );
+
+        let result_f32 = self.read_buffer::<f32>(&accum, pixel_count * 4).await?;
+        let data = result_f32
+            .iter()
+            .map(|&x| (x.clamp(0.0, 1.0) * 255.0) as u8)
+            .collect();
+        info!(merged_frames, width, height, "Focus stack merged");
+
+        Ok(MergedFrame {
+            data,
+            width,
+            height,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sweep_covers_the_focus_range() {
+        assert_eq!(focus_positions(0, 1000, 1, 5), [0, 250, 500, 750, 1000]);
+        // Snapped to the control's step, ends included
+        assert_eq!(focus_positions(0, 255, 5, 4), [0, 85, 170, 255]);
+        // Coarse steps over a narrow range don't repeat positions
+        assert_eq!(focus_positions(0, 10, 10, 5), [0, 10]);
+        assert_eq!(focus_positions(50, 50, 1, 5), [50]);
+    }
+}
@@ -59,6 +59,7 @@ pub fn create_layout(
 /// Create a compute pipeline with common defaults
 pub fn create_pipeline(
     device: &wgpu::Device,
+    cache: Option<&wgpu::PipelineCache>,
     label: &str,
     layout: &wgpu::PipelineLayout,
     module: &wgpu::ShaderModule,
@@ -70,6 +71,6 @@ pub fn create_pipeline(
         module,
         entry_point: Some(entry_point),
         compilation_options: Default::default(),
-        cache: None,
+        cache,
     })
 }
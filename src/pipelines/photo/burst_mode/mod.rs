@@ -39,14 +39,19 @@
 mod bayer_planes;
 pub mod burst;
 pub mod fft_gpu;
+pub mod focus_stack;
 mod gpu_helpers;
 pub mod params;
+mod pipeline_cache;
+pub mod super_resolution;
+mod tiling;
 
-use crate::backends::camera::types::{CameraFrame, PixelFormat, SensorRotation};
+use crate::backends::camera::types::{CameraFrame, FrameData, PixelFormat, SensorRotation};
 use crate::gpu::{self, wgpu};
 use crate::shaders::{GpuFrameInput, get_gpu_convert_pipeline};
 use bayer_planes::{BayerPlanes, extract_bayer_planes};
 use std::sync::{Arc, RwLock};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
 /// Progress callback for burst mode processing
@@ -55,6 +60,23 @@ use tracing::{debug, info, warn};
 /// The callback should be cheap to call as it may be invoked frequently.
 pub type ProgressCallback = Arc<dyn Fn(f32) + Send + Sync>;
 
+/// Longest side of the instant preview shown while a burst is processed
+const INSTANT_PREVIEW_SIZE: u32 = 1280;
+
+/// JPEG quality of the instant preview
+const INSTANT_PREVIEW_QUALITY: u8 = 80;
+
+/// Error returned by [`process_burst_mode`] when its token was cancelled
+pub const CANCELLED: &str = "Burst processing cancelled";
+
+/// Stop between GPU passes once the burst was cancelled
+fn check_cancelled(cancel: &CancellationToken) -> Result<(), String> {
+    if cancel.is_cancelled() {
+        return Err(CANCELLED.to_string());
+    }
+    Ok(())
+}
+
 /// Buffer usage patterns for GPU memory allocation
 #[derive(Debug, Clone, Copy)]
 enum BufferKind {
@@ -78,6 +100,7 @@ const NOISE_ESTIMATE_SHADER: &str = include_str!("../../../shaders/burst_mode/no
 pub(crate) const CA_ESTIMATE_SHADER: &str =
     include_str!("../../../shaders/burst_mode/ca_estimate.wgsl");
 const BAYER_FINISH_SHADER: &str = include_str!("../../../shaders/burst_mode/bayer_finish.wgsl");
+const SUPER_RES_SHADER: &str = include_str!("../../../shaders/burst_mode/super_res.wgsl");
 
 // Common utilities reference (see common.wgsl for documentation)
 #[cfg(test)]
@@ -189,6 +212,11 @@ pub struct BurstModeConfig {
     pub rotation: SensorRotation,
     /// Mirror the final HDR+ output horizontally (selfie / front-camera).
     pub mirror_horizontal: bool,
+    /// Unsharp mask applied after tone mapping
+    pub output_sharpening: crate::config::OutputSharpening,
+    /// Reconstruct at twice the resolution instead of merging for noise
+    /// (see [`super_resolution`])
+    pub super_resolution: bool,
 }
 
 impl Default for BurstModeConfig {
@@ -206,10 +234,22 @@ impl Default for BurstModeConfig {
             camera_metadata: super::CameraMetadata::default(),
             rotation: SensorRotation::None, // No rotation by default
             mirror_horizontal: false,
+            output_sharpening: crate::config::OutputSharpening::default(),
+            super_resolution: false,
         }
     }
 }
 
+impl BurstModeConfig {
+    /// Take the merge and tone mapping parameters from hand-tuned settings
+    pub fn apply_tuning(&mut self, tuning: &crate::config::BurstTuning) {
+        let tuning = tuning.clamped();
+        self.robustness = f32::from(tuning.robustness) / 100.0;
+        self.shadow_boost = f32::from(tuning.shadow_boost) / 100.0;
+        self.local_contrast = f32::from(tuning.local_contrast) / 100.0;
+    }
+}
+
 /// Burst mode processing stages (internal to pipeline)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum BurstModePipelineStage {
@@ -265,6 +305,40 @@ struct ReferencePyramids {
     lum: Vec<wgpu::Buffer>,
 }
 
+/// Frame-to-frame motion tracking state for [`BurstModeGpuPipeline::track_motion`]
+///
+/// Video frames are aligned to the one before rather than to a fixed
+/// reference, so the tracker keeps the previous frame's luminance pyramid
+/// and swaps it with the comparison pyramid after each frame.
+pub struct MotionTracker {
+    width: u32,
+    height: u32,
+    buffers: AlignmentBuffers,
+    reference: ReferencePyramids,
+    /// `reference` holds a frame (false until the first one is fed)
+    has_reference: bool,
+}
+
+impl MotionTracker {
+    /// Size of the frames the tracker expects
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+}
+
+/// Component-wise median of per-tile alignment offsets
+fn median_offset(offsets: &[[f32; 2]]) -> Option<(f32, f32)> {
+    if offsets.is_empty() {
+        return None;
+    }
+    let median = |axis: usize| {
+        let mut values: Vec<f32> = offsets.iter().map(|o| o[axis]).collect();
+        values.sort_by(f32::total_cmp);
+        values[values.len() / 2]
+    };
+    Some((median(0), median(1)))
+}
+
 /// Pre-allocated buffers for frame alignment, reused across multiple frames.
 /// This avoids creating/destroying GPU buffers for each frame in the burst.
 struct AlignmentBuffers {
@@ -350,6 +424,11 @@ impl StagingBufferPool {
     fn return_large(&mut self, buffer: wgpu::Buffer) {
         self.large = Some(buffer);
     }
+
+    /// Drop the pooled buffers; they are recreated on the next readback.
+    fn clear(&mut self) {
+        *self = Self::new();
+    }
 }
 
 /// GPU pipeline for burst mode processing
@@ -369,6 +448,9 @@ pub struct BurstModeGpuPipeline {
     // Sharpness pipelines
     sharpness_tiles: wgpu::ComputePipeline,
     sharpness_reduce: wgpu::ComputePipeline,
+    // Focus stacking (per-pixel sharpness weighting, in the sharpness shader)
+    focus_stack_accumulate: wgpu::ComputePipeline,
+    focus_stack_normalize: wgpu::ComputePipeline,
 
     // Alignment pipelines
     align_tiles: wgpu::ComputePipeline,
@@ -396,6 +478,10 @@ pub struct BurstModeGpuPipeline {
     // Bayer finishing pipeline (HDR+ Section 6: demosaic merged planes + WB + CCM)
     bayer_finish: wgpu::ComputePipeline,
 
+    // Super-resolution pipelines (drizzle onto a 2x grid)
+    super_res_accumulate: wgpu::ComputePipeline,
+    super_res_normalize: wgpu::ComputePipeline,
+
     // Bind group layouts
     pyramid_layout: wgpu::BindGroupLayout,
     sharpness_layout: wgpu::BindGroupLayout,
@@ -407,6 +493,7 @@ pub struct BurstModeGpuPipeline {
     noise_layout: wgpu::BindGroupLayout,
     ca_layout: wgpu::BindGroupLayout,
     bayer_finish_layout: wgpu::BindGroupLayout,
+    super_res_layout: wgpu::BindGroupLayout,
 
     /// Pooled staging buffers for GPU readback (reduces allocations)
     staging_pool: RwLock<StagingBufferPool>,
@@ -418,6 +505,7 @@ impl BurstModeGpuPipeline {
     /// Create a compute pipeline with common defaults
     fn create_pipeline(
         device: &wgpu::Device,
+        cache: Option<&wgpu::PipelineCache>,
         label: &str,
         layout: &wgpu::PipelineLayout,
         module: &wgpu::ShaderModule,
@@ -429,7 +517,7 @@ impl BurstModeGpuPipeline {
             module,
             entry_point: Some(entry_point),
             compilation_options: Default::default(),
-            cache: None,
+            cache,
         })
     }
 
@@ -564,6 +652,18 @@ impl BurstModeGpuPipeline {
         Ok(result)
     }
 
+    /// GPU memory limits tiled processing plans against
+    fn tile_budget(&self) -> tiling::GpuBudget {
+        tiling::GpuBudget::from_device(&self.device)
+    }
+
+    /// Hand the pooled readback buffers back to the driver, which can hold
+    /// a whole frame; the pipeline itself is kept for later use
+    fn release_staging_buffers(&self) {
+        self.staging_pool.write().unwrap().clear();
+        let _ = self.device.poll(wgpu::PollType::Poll);
+    }
+
     /// Create a new GPU pipeline with all shaders loaded
     pub async fn new() -> Result<Self, String> {
         info!("Initializing burst mode GPU pipeline (all operations GPU-accelerated)");
@@ -582,6 +682,11 @@ impl BurstModeGpuPipeline {
 
         let max_buffer_size = device.limits().max_storage_buffer_binding_size as u64;
 
+        // Driver-compiled pipelines from an earlier run, where supported
+        let cache_key = gpu.info.pipeline_cache_key.as_deref();
+        let pipeline_cache = cache_key.and_then(|key| pipeline_cache::load(&device, key));
+        let cache = pipeline_cache.as_ref();
+
         // Load all shader modules
         let pyramid_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("pyramid_shader"),
@@ -695,6 +800,7 @@ impl BurstModeGpuPipeline {
         // Create compute pipelines
         let pyramid_downsample_gray = Self::create_pipeline(
             &device,
+            cache,
             "pyramid_downsample_gray",
             &pyramid_pipeline_layout,
             &pyramid_module,
@@ -702,6 +808,7 @@ impl BurstModeGpuPipeline {
         );
         let sharpness_tiles = Self::create_pipeline(
             &device,
+            cache,
             "sharpness_tiles",
             &sharpness_pipeline_layout,
             &sharpness_module,
@@ -709,13 +816,31 @@ impl BurstModeGpuPipeline {
         );
         let sharpness_reduce = Self::create_pipeline(
             &device,
+            cache,
             "sharpness_reduce",
             &sharpness_pipeline_layout,
             &sharpness_module,
             "reduce_sharpness",
         );
+        let focus_stack_accumulate = Self::create_pipeline(
+            &device,
+            cache,
+            "focus_stack_accumulate",
+            &sharpness_pipeline_layout,
+            &sharpness_module,
+            "accumulate_focus_weighted",
+        );
+        let focus_stack_normalize = Self::create_pipeline(
+            &device,
+            cache,
+            "focus_stack_normalize",
+            &sharpness_pipeline_layout,
+            &sharpness_module,
+            "normalize_focus_stack",
+        );
         let align_tiles = Self::create_pipeline(
             &device,
+            cache,
             "align_tiles",
             &align_pipeline_layout,
             &align_module,
@@ -723,6 +848,7 @@ impl BurstModeGpuPipeline {
         );
         let align_correct_upsampling = Self::create_pipeline(
             &device,
+            cache,
             "align_correct_upsampling",
             &align_pipeline_layout,
             &align_module,
@@ -730,6 +856,7 @@ impl BurstModeGpuPipeline {
         );
         let rgb_to_luminance = Self::create_pipeline(
             &device,
+            cache,
             "rgb_to_luminance",
             &luminance_pipeline_layout,
             &align_module,
@@ -737,6 +864,7 @@ impl BurstModeGpuPipeline {
         );
         let warp_frame = Self::create_pipeline(
             &device,
+            cache,
             "warp_frame",
             &warp_pipeline_layout,
             &warp_module,
@@ -746,6 +874,7 @@ impl BurstModeGpuPipeline {
         // Tonemap pipelines
         let tonemap_local_lum = Self::create_pipeline(
             &device,
+            cache,
             "tonemap_local_lum",
             &local_lum_pipeline_layout,
             &tonemap_module,
@@ -753,6 +882,7 @@ impl BurstModeGpuPipeline {
         );
         let tonemap_apply = Self::create_pipeline(
             &device,
+            cache,
             "tonemap_apply",
             &tonemap_pipeline_layout,
             &tonemap_module,
@@ -762,6 +892,7 @@ impl BurstModeGpuPipeline {
         // Noise estimation pipelines
         let noise_build_histogram = Self::create_pipeline(
             &device,
+            cache,
             "noise_build_histogram",
             &noise_pipeline_layout,
             &noise_module,
@@ -769,6 +900,7 @@ impl BurstModeGpuPipeline {
         );
         let noise_find_median = Self::create_pipeline(
             &device,
+            cache,
             "noise_find_median",
             &noise_pipeline_layout,
             &noise_module,
@@ -776,6 +908,7 @@ impl BurstModeGpuPipeline {
         );
         let noise_compute_mad = Self::create_pipeline(
             &device,
+            cache,
             "noise_compute_mad",
             &noise_pipeline_layout,
             &noise_module,
@@ -783,6 +916,7 @@ impl BurstModeGpuPipeline {
         );
         let noise_finalize = Self::create_pipeline(
             &device,
+            cache,
             "noise_finalize",
             &noise_pipeline_layout,
             &noise_module,
@@ -792,6 +926,7 @@ impl BurstModeGpuPipeline {
         // CA estimation pipelines
         let ca_init_bins = Self::create_pipeline(
             &device,
+            cache,
             "ca_init_bins",
             &ca_pipeline_layout,
             &ca_module,
@@ -799,6 +934,7 @@ impl BurstModeGpuPipeline {
         );
         let ca_estimate_offsets = Self::create_pipeline(
             &device,
+            cache,
             "ca_estimate_offsets",
             &ca_pipeline_layout,
             &ca_module,
@@ -806,6 +942,7 @@ impl BurstModeGpuPipeline {
         );
         let ca_fit_model = Self::create_pipeline(
             &device,
+            cache,
             "ca_fit_model",
             &ca_pipeline_layout,
             &ca_module,
@@ -825,15 +962,49 @@ impl BurstModeGpuPipeline {
             });
         let bayer_finish = Self::create_pipeline(
             &device,
+            cache,
             "bayer_finish",
             &bayer_finish_pipeline_layout,
             &bayer_finish_module,
             "demosaic_and_finish",
         );
 
+        // Super-resolution pipelines
+        let super_res_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("super_res_shader"),
+            source: wgpu::ShaderSource::Wgsl(SUPER_RES_SHADER.into()),
+        });
+        let super_res_layout = Self::create_super_res_layout(&device);
+        let super_res_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("super_res_pipeline_layout"),
+                bind_group_layouts: &[&super_res_layout],
+                immediate_size: 0,
+            });
+        let super_res_accumulate = Self::create_pipeline(
+            &device,
+            cache,
+            "super_res_accumulate",
+            &super_res_pipeline_layout,
+            &super_res_module,
+            "accumulate_frame",
+        );
+        let super_res_normalize = Self::create_pipeline(
+            &device,
+            cache,
+            "super_res_normalize",
+            &super_res_pipeline_layout,
+            &super_res_module,
+            "normalize_accum",
+        );
+
         // Initialize FFT pipeline (eagerly, for fail-fast behavior)
         let fft_pipeline =
-            fft_gpu::FftMergePipeline::new(device.clone(), queue.clone(), max_buffer_size)?;
+            fft_gpu::FftMergePipeline::new(device.clone(), queue.clone(), max_buffer_size, cache)?;
+
+        if let (Some(pipeline_cache), Some(key)) = (&pipeline_cache, cache_key) {
+            pipeline_cache::save(pipeline_cache, key);
+        }
 
         info!("Night mode GPU pipeline initialized successfully");
 
@@ -843,6 +1014,8 @@ impl BurstModeGpuPipeline {
             pyramid_downsample_gray,
             sharpness_tiles,
             sharpness_reduce,
+            focus_stack_accumulate,
+            focus_stack_normalize,
             align_tiles,
             align_correct_upsampling,
             rgb_to_luminance,
@@ -857,6 +1030,8 @@ impl BurstModeGpuPipeline {
             ca_estimate_offsets,
             ca_fit_model,
             bayer_finish,
+            super_res_accumulate,
+            super_res_normalize,
             pyramid_layout,
             sharpness_layout,
             align_layout,
@@ -867,6 +1042,7 @@ impl BurstModeGpuPipeline {
             noise_layout,
             ca_layout,
             bayer_finish_layout,
+            super_res_layout,
             fft_pipeline,
             staging_pool: RwLock::new(StagingBufferPool::new()),
         })
@@ -954,6 +1130,21 @@ impl BurstModeGpuPipeline {
         )
     }
 
+    fn create_super_res_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        use BindingKind::*;
+        gpu_helpers::create_layout(
+            device,
+            "super_res_layout",
+            &[
+                StorageRead,      // 0: frame being accumulated
+                StorageRead,      // 1: reference frame
+                StorageRead,      // 2: alignment
+                StorageReadWrite, // 3: accumulation
+                Uniform,          // 4: params
+            ],
+        )
+    }
+
     fn create_local_lum_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
         use BindingKind::*;
         gpu_helpers::create_layout(
@@ -1419,6 +1610,7 @@ impl BurstModeGpuPipeline {
     pub async fn select_reference_frame(
         &self,
         frames: &[Arc<CameraFrame>],
+        cancel: &CancellationToken,
     ) -> Result<usize, String> {
         if frames.is_empty() {
             return Err("No frames provided".to_string());
@@ -1439,6 +1631,7 @@ impl BurstModeGpuPipeline {
         let mut ref_idx = 0;
 
         for (idx, frame) in frames[..search_count].iter().enumerate() {
+            check_cancelled(cancel)?;
             let sharpness = self.compute_sharpness(frame).await?;
             debug!(frame = idx, sharpness, "Frame sharpness");
 
@@ -1576,6 +1769,7 @@ impl BurstModeGpuPipeline {
         frames: &[Arc<CameraFrame>],
         ref_idx: usize,
         progress: &Option<ProgressCallback>,
+        cancel: &CancellationToken,
     ) -> Result<Vec<GpuAlignedFrame>, String> {
         let align_start = std::time::Instant::now();
         debug!(
@@ -1650,6 +1844,7 @@ impl BurstModeGpuPipeline {
             if idx == ref_idx {
                 continue;
             }
+            check_cancelled(cancel)?;
 
             // Skip frames with different dimensions
             if frame.width != width || frame.height != height {
@@ -1892,6 +2087,77 @@ impl BurstModeGpuPipeline {
             "comp",
         );
 
+        let (final_n_tiles_x, final_n_tiles_y) = self
+            .align_luminance_pyramids(ref_pyramids, width, height, buffers)
+            .await;
+
+        // Enable CA correction if coefficients are non-zero
+        let enable_ca = if ca_r_coeff.abs() > 0.0001 || ca_b_coeff.abs() > 0.0001 {
+            1u32
+        } else {
+            0u32
+        };
+        let warp_params = WarpParams {
+            width,
+            height,
+            n_tiles_x: final_n_tiles_x,
+            n_tiles_y: final_n_tiles_y,
+            tile_size: WARP_TILE_SIZE,
+            tile_step: WARP_TILE_SIZE / 2,
+            use_bilinear: 1,
+            _padding0: 0,
+            center_x: width as f32 / 2.0,
+            center_y: height as f32 / 2.0,
+            ca_r_coeff,
+            ca_b_coeff,
+            enable_ca_correction: enable_ca,
+            _padding: 0,
+            _padding2: 0,
+            _padding3: 0,
+        };
+        self.queue.write_buffer(
+            &buffers.warp_params,
+            0,
+            bytemuck::cast_slice(&[warp_params]),
+        );
+
+        let warp_bg = self.bind_group(
+            "warp_bg",
+            &self.warp_layout,
+            &[
+                &buffers.comp_rgba,
+                &output_buffer,
+                &buffers.align[0],
+                &buffers.warp_params,
+            ],
+        );
+
+        self.dispatch_compute(
+            "warp_pass",
+            &self.warp_frame,
+            &warp_bg,
+            (width.div_ceil(16), height.div_ceil(16), 1),
+        );
+
+        Ok(GpuAlignedFrame {
+            buffer: output_buffer,
+            width,
+            height,
+        })
+    }
+
+    /// Hierarchical luminance alignment of the comparison pyramid in
+    /// `buffers.comp_lum` against `ref_pyramids` (4 levels, coarse-to-fine)
+    ///
+    /// Leaves the per-tile offsets in `buffers.align`, finest level first,
+    /// and returns the tile grid of level 0.
+    async fn align_luminance_pyramids(
+        &self,
+        ref_pyramids: &ReferencePyramids,
+        width: u32,
+        height: u32,
+        buffers: &AlignmentBuffers,
+    ) -> (u32, u32) {
         // Build pyramid dimensions for tile calculations
         let level_dims: Vec<(u32, u32)> = (0..PYRAMID_LEVELS)
             .map(|level| {
@@ -2010,62 +2276,83 @@ impl BurstModeGpuPipeline {
             prev_tile_step = tile_step;
         }
 
-        // Warp frame using final alignment (L0)
-        let (final_n_tiles_x, final_n_tiles_y) = level_tile_counts[0];
+        level_tile_counts[0]
+    }
 
-        // Enable CA correction if coefficients are non-zero
-        let enable_ca = if ca_r_coeff.abs() > 0.0001 || ca_b_coeff.abs() > 0.0001 {
-            1u32
-        } else {
-            0u32
-        };
-        let warp_params = WarpParams {
+    /// Set up motion tracking for a stream of `width`x`height` RGBA frames
+    pub fn create_motion_tracker(&self, width: u32, height: u32) -> MotionTracker {
+        let reference = (0..PYRAMID_LEVELS)
+            .map(|level| {
+                let scale = 1 << level;
+                let size = (width.div_ceil(scale) * height.div_ceil(scale)) as usize;
+                self.create_storage_buffer_readonly(
+                    &format!("motion_ref_lum_L{}", level),
+                    (size * std::mem::size_of::<f32>()) as u64,
+                )
+            })
+            .collect();
+        MotionTracker {
             width,
             height,
-            n_tiles_x: final_n_tiles_x,
-            n_tiles_y: final_n_tiles_y,
-            tile_size: WARP_TILE_SIZE,
-            tile_step: WARP_TILE_SIZE / 2,
-            use_bilinear: 1,
-            _padding0: 0,
-            center_x: width as f32 / 2.0,
-            center_y: height as f32 / 2.0,
-            ca_r_coeff,
-            ca_b_coeff,
-            enable_ca_correction: enable_ca,
-            _padding: 0,
-            _padding2: 0,
-            _padding3: 0,
-        };
-        self.queue.write_buffer(
-            &buffers.warp_params,
-            0,
-            bytemuck::cast_slice(&[warp_params]),
-        );
-
-        let warp_bg = self.bind_group(
-            "warp_bg",
-            &self.warp_layout,
-            &[
-                &buffers.comp_rgba,
-                &output_buffer,
-                &buffers.align[0],
-                &buffers.warp_params,
-            ],
-        );
+            buffers: self.create_alignment_buffers(width, height),
+            reference: ReferencePyramids { lum: reference },
+            has_reference: false,
+        }
+    }
 
-        self.dispatch_compute(
-            "warp_pass",
-            &self.warp_frame,
-            &warp_bg,
-            (width.div_ceil(16), height.div_ceil(16), 1),
-        );
+    /// Global translation of `rgba` since the previous frame fed to `tracker`
+    ///
+    /// `rgba` is tightly packed at the tracker's size. The result is in
+    /// pixels of that size, positive when the picture moved right or down,
+    /// and `None` for the first frame. It is the median of the per-tile
+    /// offsets of the burst alignment, so a subject moving through part of
+    /// the frame doesn't pull it away from the camera's own motion.
+    pub async fn track_motion(
+        &self,
+        tracker: &mut MotionTracker,
+        rgba: &[u8],
+    ) -> Result<Option<(f32, f32)>, String> {
+        let (width, height) = (tracker.width, tracker.height);
+        let expected = width as usize * height as usize * 4;
+        if rgba.len() != expected {
+            return Err(format!(
+                "Motion frame is {} bytes, expected {} for {}x{}",
+                rgba.len(),
+                expected,
+                width,
+                height
+            ));
+        }
 
-        Ok(GpuAlignedFrame {
-            buffer: output_buffer,
+        let buffers = &tracker.buffers;
+        let rgba_f32 = u8_to_f32_normalized(rgba);
+        self.queue
+            .write_buffer(&buffers.comp_rgba, 0, bytemuck::cast_slice(&rgba_f32));
+        self.build_luminance_pyramid(
+            &buffers.comp_rgba,
+            &buffers.comp_lum,
             width,
             height,
-        })
+            buffers,
+            "motion",
+        );
+
+        let motion = if tracker.has_reference {
+            let (n_tiles_x, n_tiles_y) = self
+                .align_luminance_pyramids(&tracker.reference, width, height, buffers)
+                .await;
+            let offsets: Vec<[f32; 2]> = self
+                .read_buffer(&buffers.align[0], (n_tiles_x * n_tiles_y) as usize)
+                .await?;
+            median_offset(&offsets)
+        } else {
+            None
+        };
+
+        // This frame's pyramid is the reference for the next one
+        std::mem::swap(&mut tracker.buffers.comp_lum, &mut tracker.reference.lum);
+        tracker.has_reference = true;
+        Ok(motion)
     }
 
     // Note: Legacy CPU-path functions removed (align_single_frame, align_single_frame_gpu, merge_frames, merge_spatial, merge_fft).
@@ -2081,6 +2368,7 @@ impl BurstModeGpuPipeline {
         reference: &CameraFrame,
         aligned: &[GpuAlignedFrame],
         config: &BurstModeConfig,
+        cancel: &CancellationToken,
     ) -> Result<MergedFrame, String> {
         debug!(
             frames = aligned.len() + 1,
@@ -2113,6 +2401,7 @@ impl BurstModeGpuPipeline {
                 height,
                 noise_sd,
                 config.robustness,
+                cancel,
             )
             .await?;
 
@@ -2128,6 +2417,59 @@ impl BurstModeGpuPipeline {
         &self,
         merged: &MergedFrame,
         config: &BurstModeConfig,
+    ) -> Result<MergedFrame, String> {
+        self.tonemap(merged, config, None).await
+    }
+
+    /// Apply tone mapping in overlapping tiles if the frame is too large for
+    /// the GPU to take whole
+    async fn apply_tonemap_tiled(
+        &self,
+        merged: &MergedFrame,
+        config: &BurstModeConfig,
+        cancel: &CancellationToken,
+    ) -> Result<MergedFrame, String> {
+        let Some(tiles) = tiling::plan(
+            merged.width,
+            merged.height,
+            tiling::Footprint::tonemap(),
+            self.tile_budget(),
+        ) else {
+            return self.apply_tonemap(merged, config).await;
+        };
+
+        // One scene brightness for all tiles, so they share a tone curve
+        let scene_brightness = tiling::mean_luminance(&merged.data);
+        debug!(
+            tiles = tiles.len(),
+            scene_brightness, "Tone mapping in tiles"
+        );
+        let mut blender = tiling::TileBlender::new(merged.width, merged.height);
+        for tile in &tiles {
+            check_cancelled(cancel)?;
+            let part = MergedFrame {
+                data: tiling::crop_rgba(&merged.data, merged.width, tile),
+                width: tile.width,
+                height: tile.height,
+            };
+            let mapped = self.tonemap(&part, config, Some(scene_brightness)).await?;
+            blender.add(tile, &mapped.data);
+            self.yield_to_compositor().await;
+        }
+        Ok(MergedFrame {
+            data: blender.finish(),
+            width: merged.width,
+            height: merged.height,
+        })
+    }
+
+    /// Tone mapping, with the scene brightness measured on `merged` unless
+    /// given (a tile of a larger frame takes the whole frame's)
+    async fn tonemap(
+        &self,
+        merged: &MergedFrame,
+        config: &BurstModeConfig,
+        scene_brightness: Option<f32>,
     ) -> Result<MergedFrame, String> {
         debug!("Applying tone mapping (GPU)");
 
@@ -2223,7 +2565,7 @@ impl BurstModeGpuPipeline {
             .write_buffer(&block_size_buffer, 0, bytemuck::cast_slice(&[block_size]));
 
         // Create global brightness accumulator buffer (for adaptive shadow boost - HDR+ paper Section 6)
-        // [0] = fixed-point sum (value * 65536), [1] = count
+        // [0] = fixed-point sum (value * 256), [1] = count
         let brightness_accum_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("brightness_accum_buffer"),
             size: 8, // 2 x u32
@@ -2298,11 +2640,12 @@ impl BurstModeGpuPipeline {
         brightness_staging.unmap();
 
         // Compute average brightness (convert from fixed-point)
-        let avg_brightness = if count > 0.0 {
-            (sum_fixed / count / 65536.0) as f32
+        let measured_brightness = if count > 0.0 {
+            (sum_fixed / count / 256.0) as f32
         } else {
             0.5 // Fallback to mid-gray
         };
+        let avg_brightness = scene_brightness.unwrap_or(measured_brightness);
 
         // Adaptive shadow boost based on scene brightness (HDR+ paper Section 6)
         // Bright scenes need less/no shadow lifting to avoid washed-out appearance
@@ -2581,6 +2924,7 @@ impl BurstModeGpuPipeline {
         planes_list: &[BayerPlanes],
         ref_idx: usize,
         progress: &Option<ProgressCallback>,
+        cancel: &CancellationToken,
     ) -> Result<Vec<GpuAlignedFrame>, String> {
         let align_start = std::time::Instant::now();
         let ref_planes = &planes_list[ref_idx];
@@ -2644,6 +2988,7 @@ impl BurstModeGpuPipeline {
             if idx == ref_idx {
                 continue;
             }
+            check_cancelled(cancel)?;
 
             if planes.width != width || planes.height != height {
                 warn!(
@@ -2699,6 +3044,7 @@ impl BurstModeGpuPipeline {
         ref_planes: &BayerPlanes,
         aligned: &[GpuAlignedFrame],
         config: &BurstModeConfig,
+        cancel: &CancellationToken,
     ) -> Result<(wgpu::Buffer, u32, u32), String> {
         let width = ref_planes.width;
         let height = ref_planes.height;
@@ -2734,6 +3080,7 @@ impl BurstModeGpuPipeline {
                 height,
                 noise_sd,
                 config.robustness,
+                cancel,
             )
             .await?;
 
@@ -2763,19 +3110,46 @@ impl BurstModeGpuPipeline {
 /// - 0.10 - 0.60: Frame alignment (distributed across frames)
 /// - 0.60 - 0.85: Frame merging
 /// - 0.85 - 1.00: Tone mapping
+///
+/// Bursts too large for the GPU (see [`tiling`]) are aligned, merged and
+/// tone mapped in overlapping tiles instead; their alignment and merging
+/// report progress per tile from 0.10 to 0.80.
+///
+/// Cancelling `cancel` stops processing at the next frame, tile or stage
+/// with [`CANCELLED`], dropping the GPU buffers staged for the burst.
 pub async fn process_burst_mode(
     frames: Vec<Arc<CameraFrame>>,
     config: BurstModeConfig,
     progress: Option<ProgressCallback>,
+    cancel: CancellationToken,
 ) -> Result<MergedFrame, String> {
     // Detect Bayer input and route to appropriate pipeline
     let is_bayer = frames.first().map(|f| f.format.is_bayer()).unwrap_or(false);
 
-    if is_bayer {
-        return process_burst_mode_bayer(frames, config, progress).await;
-    }
+    let result = if is_bayer {
+        process_burst_mode_bayer(frames, config, progress, &cancel).await
+    } else {
+        process_burst_mode_rgba(frames, config, progress, &cancel).await
+    };
+    release_if_cancelled(result, &cancel).await
+}
 
-    process_burst_mode_rgba(frames, config, progress).await
+/// Turn the result of a cancelled run into [`CANCELLED`], handing back
+/// the GPU memory it had pooled
+async fn release_if_cancelled(
+    result: Result<MergedFrame, String>,
+    cancel: &CancellationToken,
+) -> Result<MergedFrame, String> {
+    if cancel.is_cancelled() {
+        info!("Burst processing cancelled");
+        // Buffers local to the stages went with them; the shared pipeline
+        // still pools a frame-sized readback buffer
+        if let Ok(gpu) = BurstModeGpuPipeline::shared().await {
+            gpu.release_staging_buffers();
+        }
+        return Err(CANCELLED.to_string());
+    }
+    result
 }
 
 /// Bayer-domain burst processing pipeline (HDR+ paper-correct)
@@ -2791,6 +3165,7 @@ async fn process_burst_mode_bayer(
     frames: Vec<Arc<CameraFrame>>,
     config: BurstModeConfig,
     progress: Option<ProgressCallback>,
+    cancel: &CancellationToken,
 ) -> Result<MergedFrame, String> {
     if frames.is_empty() {
         return Err("Burst mode requires at least one frame".to_string());
@@ -2831,19 +3206,31 @@ async fn process_burst_mode_bayer(
 
     // Step 2: Initialize GPU pipeline (5% - 8%)
     let step_start = std::time::Instant::now();
-    let gpu = BurstModeGpuPipeline::new().await?;
+    let gpu = BurstModeGpuPipeline::shared().await?;
     info!(
         elapsed_ms = step_start.elapsed().as_millis(),
         "GPU pipeline initialized"
     );
     report(0.08);
 
+    if let Some(tiles) = tiling::plan(
+        planes_list[0].width,
+        planes_list[0].height,
+        tiling::Footprint::bayer_burst(planes_list.len()),
+        gpu.tile_budget(),
+    ) {
+        let merged =
+            merge_tiled_bayer(&gpu, &planes_list, &tiles, &config, &progress, cancel).await?;
+        return finish_tiled(&gpu, merged, &config, &progress, cancel, total_start).await;
+    }
+
     // Step 3: Select reference frame from first 3 using sharpness (8% - 10%)
     let step_start = std::time::Instant::now();
     let search_count = planes_list.len().min(3);
     let mut max_sharpness = f32::MIN;
     let mut ref_idx = 0;
     for (idx, planes) in planes_list[..search_count].iter().enumerate() {
+        check_cancelled(cancel)?;
         let sharpness = gpu.compute_sharpness_from_planes(planes).await?;
         debug!(frame = idx, sharpness, "Bayer frame sharpness");
         if sharpness > max_sharpness {
@@ -2862,7 +3249,7 @@ async fn process_burst_mode_bayer(
     // Step 4: Align frames at half-res using Bayer grayscale (10% - 60%)
     let step_start = std::time::Instant::now();
     let aligned = gpu
-        .align_bayer_frames_gpu(&planes_list, ref_idx, &progress)
+        .align_bayer_frames_gpu(&planes_list, ref_idx, &progress, cancel)
         .await?;
     info!(
         elapsed_ms = step_start.elapsed().as_millis(),
@@ -2875,7 +3262,7 @@ async fn process_burst_mode_bayer(
     let step_start = std::time::Instant::now();
     let ref_planes = &planes_list[ref_idx];
     let (merged_buffer, half_w, half_h) = gpu
-        .merge_bayer_frames_gpu(ref_planes, &aligned, &config)
+        .merge_bayer_frames_gpu(ref_planes, &aligned, &config, cancel)
         .await?;
     info!(
         elapsed_ms = step_start.elapsed().as_millis(),
@@ -2885,6 +3272,7 @@ async fn process_burst_mode_bayer(
 
     // Drop aligned frames to free GPU memory
     drop(aligned);
+    check_cancelled(cancel)?;
 
     // Step 6: Demosaic merged Bayer planes → full-res RGBA (80% - 90%)
     // HDR+ Section 6: single demosaic after merge (not N demosaics before merge)
@@ -2901,6 +3289,7 @@ async fn process_burst_mode_bayer(
         "Demosaic complete (full-res output)"
     );
     report(0.90);
+    check_cancelled(cancel)?;
 
     // Step 7: Apply tone mapping (90% - 100%)
     let step_start = std::time::Instant::now();
@@ -2909,6 +3298,7 @@ async fn process_burst_mode_bayer(
         elapsed_ms = step_start.elapsed().as_millis(),
         "Tone mapping complete"
     );
+    let tonemapped = sharpen_output(tonemapped, config.output_sharpening).await;
     report(1.0);
 
     info!(
@@ -2926,6 +3316,7 @@ async fn process_burst_mode_rgba(
     frames: Vec<Arc<CameraFrame>>,
     config: BurstModeConfig,
     progress: Option<ProgressCallback>,
+    cancel: &CancellationToken,
 ) -> Result<MergedFrame, String> {
     let total_start = std::time::Instant::now();
     info!(
@@ -2942,16 +3333,88 @@ async fn process_burst_mode_rgba(
     // Initialize GPU pipeline (0% - 5%)
     report(0.0);
     let step_start = std::time::Instant::now();
-    let gpu = BurstModeGpuPipeline::new().await?;
+    let gpu = BurstModeGpuPipeline::shared().await?;
     info!(
         elapsed_ms = step_start.elapsed().as_millis(),
         "GPU pipeline initialized"
     );
     report(0.05);
 
+    if let Some(tiles) = rgba_tiles(&gpu, &frames) {
+        let merged = merge_tiled_rgba(&gpu, &frames, &tiles, &config, &progress, cancel).await?;
+        return finish_tiled(&gpu, merged, &config, &progress, cancel, total_start).await;
+    }
+
+    let merged = merge_whole_rgba(&gpu, &frames, &config, &progress, cancel).await?;
+
+    // Apply tone mapping (85% - 100%)
+    let step_start = std::time::Instant::now();
+    let tonemapped = gpu.apply_tonemap(&merged, &config).await?;
+    info!(
+        elapsed_ms = step_start.elapsed().as_millis(),
+        "Tone mapping complete"
+    );
+    let tonemapped = sharpen_output(tonemapped, config.output_sharpening).await;
+    report(1.0);
+
+    info!(
+        total_elapsed_ms = total_start.elapsed().as_millis(),
+        "RGBA burst processing complete"
+    );
+
+    Ok(tonemapped)
+}
+
+/// Align and merge RGBA frames for their noise reduction alone, without
+/// tone mapping or sharpening. For captures that finish the merged frame
+/// their own way, like product shots.
+pub async fn merge_rgba_frames(
+    frames: &[Arc<CameraFrame>],
+    config: &BurstModeConfig,
+    cancel: &CancellationToken,
+) -> Result<MergedFrame, String> {
+    if frames.is_empty() {
+        return Err("Burst mode requires at least one frame".to_string());
+    }
+    let gpu = BurstModeGpuPipeline::shared().await?;
+    let merged = match rgba_tiles(&gpu, frames) {
+        Some(tiles) => merge_tiled_rgba(&gpu, frames, &tiles, config, &None, cancel).await,
+        None => merge_whole_rgba(&gpu, frames, config, &None, cancel).await,
+    };
+    release_if_cancelled(merged, cancel).await
+}
+
+/// Tiles to merge an RGBA burst in, or `None` if it fits the GPU whole
+fn rgba_tiles(
+    gpu: &BurstModeGpuPipeline,
+    frames: &[Arc<CameraFrame>],
+) -> Option<Vec<tiling::Tile>> {
+    let first = frames.first()?;
+    tiling::plan(
+        first.width,
+        first.height,
+        tiling::Footprint::rgba_burst(frames.len()),
+        gpu.tile_budget(),
+    )
+}
+
+/// Align and merge an RGBA burst that fits the GPU whole (5% - 85%)
+async fn merge_whole_rgba(
+    gpu: &BurstModeGpuPipeline,
+    frames: &[Arc<CameraFrame>],
+    config: &BurstModeConfig,
+    progress: &Option<ProgressCallback>,
+    cancel: &CancellationToken,
+) -> Result<MergedFrame, String> {
+    let report = |value: f32| {
+        if let Some(cb) = progress {
+            cb(value);
+        }
+    };
+
     // Select reference frame (5% - 10%)
     let step_start = std::time::Instant::now();
-    let ref_idx = gpu.select_reference_frame(&frames).await?;
+    let ref_idx = gpu.select_reference_frame(frames, cancel).await?;
     info!(
         elapsed_ms = step_start.elapsed().as_millis(),
         reference = ref_idx,
@@ -2962,7 +3425,7 @@ async fn process_burst_mode_rgba(
     // Align frames - GPU-only, no CPU round-trip (10% - 60%)
     let step_start = std::time::Instant::now();
     let aligned = gpu
-        .align_frames_gpu_with_progress(&frames, ref_idx, &progress)
+        .align_frames_gpu_with_progress(frames, ref_idx, progress, cancel)
         .await?;
     info!(
         elapsed_ms = step_start.elapsed().as_millis(),
@@ -2974,7 +3437,7 @@ async fn process_burst_mode_rgba(
     // Merge frames - GPU-only (60% - 85%)
     let step_start = std::time::Instant::now();
     let merged = gpu
-        .merge_frames_gpu(&frames[ref_idx], &aligned, &config)
+        .merge_frames_gpu(&frames[ref_idx], &aligned, config, cancel)
         .await?;
     info!(
         elapsed_ms = step_start.elapsed().as_millis(),
@@ -2983,24 +3446,238 @@ async fn process_burst_mode_rgba(
     report(0.85);
 
     drop(aligned);
+    check_cancelled(cancel)?;
+    Ok(merged)
+}
 
-    // Apply tone mapping (85% - 100%)
+/// Align and merge a Bayer burst in tiles of its planes, returning the
+/// demosaiced result (10% - 80%)
+async fn merge_tiled_bayer(
+    gpu: &BurstModeGpuPipeline,
+    planes_list: &[BayerPlanes],
+    tiles: &[tiling::Tile],
+    config: &BurstModeConfig,
+    progress: &Option<ProgressCallback>,
+    cancel: &CancellationToken,
+) -> Result<MergedFrame, String> {
+    let report = |value: f32| {
+        if let Some(cb) = progress {
+            cb(value);
+        }
+    };
+    let (width, height) = (planes_list[0].width, planes_list[0].height);
+    let planes_list: Vec<&BayerPlanes> = planes_list
+        .iter()
+        .filter(|planes| planes.width == width && planes.height == height)
+        .collect();
+    info!(
+        tiles = tiles.len(),
+        half_width = width,
+        half_height = height,
+        "Burst too large for the GPU, processing in tiles (Bayer)"
+    );
+
+    // Reference frame: the sharpest middle of the first 3
+    let center = tiling::center_tile(tiles, width, height);
+    let mut max_sharpness = f32::MIN;
+    let mut ref_idx = 0;
+    for (idx, planes) in planes_list.iter().take(3).enumerate() {
+        check_cancelled(cancel)?;
+        let sharpness = gpu
+            .compute_sharpness_from_planes(&tiling::crop_planes(planes, &center))
+            .await?;
+        debug!(
+            frame = idx,
+            sharpness, "Bayer frame sharpness (center tile)"
+        );
+        if sharpness > max_sharpness {
+            max_sharpness = sharpness;
+            ref_idx = idx;
+        }
+    }
+    report(0.10);
+
+    let mut blender = tiling::TileBlender::new(width * 2, height * 2);
+    for (i, tile) in tiles.iter().enumerate() {
+        let tile_start = std::time::Instant::now();
+        let tile_planes: Vec<BayerPlanes> = planes_list
+            .iter()
+            .map(|planes| tiling::crop_planes(planes, tile))
+            .collect();
+        let aligned = gpu
+            .align_bayer_frames_gpu(&tile_planes, ref_idx, &None, cancel)
+            .await?;
+        let (merged_buffer, half_w, half_h) = gpu
+            .merge_bayer_frames_gpu(&tile_planes[ref_idx], &aligned, config, cancel)
+            .await?;
+        drop(aligned);
+        let ref_planes = &tile_planes[ref_idx];
+        let demosaiced = gpu
+            .demosaic_bayer_planes(
+                &merged_buffer,
+                half_w,
+                half_h,
+                ref_planes.colour_gains,
+                ref_planes.colour_correction_matrix,
+            )
+            .await?;
+        blender.add(&tile.scaled(2), &demosaiced.data);
+        info!(
+            tile = i,
+            elapsed_ms = tile_start.elapsed().as_millis(),
+            "Tile merged"
+        );
+        report(0.10 + 0.70 * (i + 1) as f32 / tiles.len() as f32);
+    }
+
+    Ok(MergedFrame {
+        data: blender.finish(),
+        width: width * 2,
+        height: height * 2,
+    })
+}
+
+/// Align and merge an RGBA burst in tiles (10% - 80%)
+async fn merge_tiled_rgba(
+    gpu: &BurstModeGpuPipeline,
+    frames: &[Arc<CameraFrame>],
+    tiles: &[tiling::Tile],
+    config: &BurstModeConfig,
+    progress: &Option<ProgressCallback>,
+    cancel: &CancellationToken,
+) -> Result<MergedFrame, String> {
+    let report = |value: f32| {
+        if let Some(cb) = progress {
+            cb(value);
+        }
+    };
+    let (width, height) = (frames[0].width, frames[0].height);
+    info!(
+        tiles = tiles.len(),
+        width, height, "Burst too large for the GPU, processing in tiles"
+    );
+
+    // Tiles are cut from RGBA copies, converted once
+    let mut rgba_frames = Vec::with_capacity(frames.len());
+    for (idx, frame) in frames.iter().enumerate() {
+        if frame.width != width || frame.height != height {
+            warn!(frame = idx, "Skipping frame with mismatched dimensions");
+            continue;
+        }
+        rgba_frames.push(convert_frame_to_rgba(frame).await?);
+    }
+    let tile_frames = |tile: &tiling::Tile| -> Vec<Arc<CameraFrame>> {
+        rgba_frames
+            .iter()
+            .map(|rgba| {
+                Arc::new(CameraFrame {
+                    width: tile.width,
+                    height: tile.height,
+                    data: FrameData::Copied(tiling::crop_rgba(rgba, width, tile).into()),
+                    format: PixelFormat::RGBA,
+                    stride: tile.width * 4,
+                    yuv_planes: None,
+                    captured_at: frames[0].captured_at,
+                    sensor_timestamp_ns: None,
+                    libcamera_metadata: None,
+                })
+            })
+            .collect()
+    };
+
+    // Reference frame: the sharpest middle of the first 3
+    let center = tiling::center_tile(tiles, width, height);
+    let ref_idx = gpu
+        .select_reference_frame(&tile_frames(&center), cancel)
+        .await?;
+    report(0.10);
+
+    let mut blender = tiling::TileBlender::new(width, height);
+    for (i, tile) in tiles.iter().enumerate() {
+        let tile_start = std::time::Instant::now();
+        let tile_frames = tile_frames(tile);
+        let aligned = gpu
+            .align_frames_gpu_with_progress(&tile_frames, ref_idx, &None, cancel)
+            .await?;
+        let merged = gpu
+            .merge_frames_gpu(&tile_frames[ref_idx], &aligned, config, cancel)
+            .await?;
+        drop(aligned);
+        blender.add(tile, &merged.data);
+        info!(
+            tile = i,
+            elapsed_ms = tile_start.elapsed().as_millis(),
+            "Tile merged"
+        );
+        report(0.10 + 0.70 * (i + 1) as f32 / tiles.len() as f32);
+    }
+
+    Ok(MergedFrame {
+        data: blender.finish(),
+        width,
+        height,
+    })
+}
+
+/// Tone map and sharpen a burst merged in tiles (80% - 100%)
+async fn finish_tiled(
+    gpu: &BurstModeGpuPipeline,
+    merged: MergedFrame,
+    config: &BurstModeConfig,
+    progress: &Option<ProgressCallback>,
+    cancel: &CancellationToken,
+    total_start: std::time::Instant,
+) -> Result<MergedFrame, String> {
+    if let Some(cb) = progress {
+        cb(0.80);
+    }
     let step_start = std::time::Instant::now();
-    let tonemapped = gpu.apply_tonemap(&merged, &config).await?;
+    let tonemapped = gpu.apply_tonemap_tiled(&merged, config, cancel).await?;
+    drop(merged);
     info!(
         elapsed_ms = step_start.elapsed().as_millis(),
         "Tone mapping complete"
     );
-    report(1.0);
+    let tonemapped = sharpen_output(tonemapped, config.output_sharpening).await;
+    if let Some(cb) = progress {
+        cb(1.0);
+    }
 
     info!(
         total_elapsed_ms = total_start.elapsed().as_millis(),
-        "RGBA burst processing complete"
+        width = tonemapped.width,
+        height = tonemapped.height,
+        "Tiled burst processing complete"
     );
-
     Ok(tonemapped)
 }
 
+/// Output sharpening of the tone-mapped result. A failed pass keeps the
+/// unsharpened frame rather than losing the burst.
+async fn sharpen_output(
+    frame: MergedFrame,
+    sharpening: crate::config::OutputSharpening,
+) -> MergedFrame {
+    let Some(mask) = crate::shaders::UnsharpMask::for_photo(sharpening, frame.width, frame.height)
+    else {
+        return frame;
+    };
+    let step_start = std::time::Instant::now();
+    match crate::shaders::sharpen_rgba_gpu(&frame.data, frame.width, frame.height, mask).await {
+        Ok(data) => {
+            info!(
+                elapsed_ms = step_start.elapsed().as_millis(),
+                "Output sharpening complete"
+            );
+            MergedFrame { data, ..frame }
+        }
+        Err(e) => {
+            warn!(error = %e, "Output sharpening failed, keeping tone-mapped frame");
+            frame
+        }
+    }
+}
+
 /// Parameters for saving burst mode output
 pub struct SaveOutputParams<'a> {
     pub output_dir: std::path::PathBuf,
@@ -3053,51 +3730,95 @@ pub async fn save_output(
         .await
         .map_err(|e| format!("Failed to create output directory: {}", e))?;
 
-    // Apply filter to the RGBA data if specified and not Standard
-    let image_data = match filter {
-        Some(f) if f != crate::app::FilterType::Standard => {
-            info!(filter = ?f, "Applying filter to burst mode output");
-            apply_filter_gpu_rgba(&frame.data, frame.width, frame.height, f)
-                .await
-                .map_err(|e| format!("Failed to apply filter: {}", e))?
-        }
-        _ => frame.data.clone(),
+    // Apply filter (and software white balance) to the RGBA data if needed
+    let filter = filter.unwrap_or_default();
+    let image_data = if crate::shaders::needs_filter_pass(filter) {
+        info!(?filter, "Applying filter to burst mode output");
+        apply_filter_gpu_rgba(&frame.data, frame.width, frame.height, filter)
+            .await
+            .map_err(|e| format!("Failed to apply filter: {}", e))?
+    } else {
+        frame.data.clone()
     };
 
     let img: ImageBuffer<Rgba<u8>, _> =
         ImageBuffer::from_raw(frame.width, frame.height, image_data)
             .ok_or("Failed to create image buffer")?;
 
-    let dynamic_img = image::DynamicImage::ImageRgba8(img);
+    let rgb_img = crop_and_orient(
+        image::DynamicImage::ImageRgba8(img),
+        crop_rect,
+        rotation,
+        mirror_horizontal,
+    );
+    let (width, height) = rgb_img.dimensions();
+
+    // Create a PhotoEncoder for the selected format
+    let mut encoder = PhotoEncoder::new();
+    encoder.set_format(encoding_format);
+    encoder.set_quality(EncodingQuality::High);
+    encoder.set_camera_metadata(camera_metadata);
+
+    // Create processed image from RGB data
+    let processed = super::processing::ProcessedImage {
+        image: rgb_img,
+        width,
+        height,
+    };
+
+    // Encode and save using the standard photo pipeline
+    let encoded = encoder.encode(processed).await?;
+
+    // Save the encoded data
+    let output_path_clone = output_path.clone();
+    let data = encoded.data;
+    tokio::task::spawn_blocking(move || {
+        std::fs::write(&output_path_clone, data).map_err(|e| format!("Failed to save image: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+
+    Ok(output_path)
+}
+
+/// Crop to the aspect ratio, then turn and mirror as the photo is seen
+fn crop_and_orient(
+    img: image::DynamicImage,
+    crop_rect: Option<(u32, u32, u32, u32)>,
+    rotation: SensorRotation,
+    mirror_horizontal: bool,
+) -> image::RgbImage {
+    use image::GenericImageView;
 
     // Apply crop if specified (for aspect ratio)
+    let (img_width, img_height) = img.dimensions();
     let cropped_img = if let Some((x, y, w, h)) = crop_rect {
         // Validate crop bounds
-        let x = x.min(frame.width.saturating_sub(1));
-        let y = y.min(frame.height.saturating_sub(1));
-        let w = w.min(frame.width - x);
-        let h = h.min(frame.height - y);
+        let x = x.min(img_width.saturating_sub(1));
+        let y = y.min(img_height.saturating_sub(1));
+        let w = w.min(img_width - x);
+        let h = h.min(img_height - y);
 
         if w > 0 && h > 0 {
             debug!(
                 x,
                 y, w, h, "Applying aspect ratio crop to burst mode output"
             );
-            dynamic_img.crop_imm(x, y, w, h)
+            img.crop_imm(x, y, w, h)
         } else {
-            dynamic_img
+            img
         }
     } else {
-        dynamic_img
+        img
     };
 
     let rgb_img = cropped_img.to_rgb8();
 
     // Apply rotation correction if needed
-    let (rgb_img, width, height) = if rotation != SensorRotation::None {
+    let rgb_img = if rotation != SensorRotation::None {
         use image::imageops;
         debug!(rotation = ?rotation, "Applying rotation correction to burst mode output");
-        let rotated = match rotation {
+        match rotation {
             SensorRotation::None => rgb_img,
             // 90 CW sensor -> rotate 90 CCW to correct
             SensorRotation::Rotate90 => imageops::rotate270(&rgb_img),
@@ -3105,51 +3826,90 @@ pub async fn save_output(
             SensorRotation::Rotate180 => imageops::rotate180(&rgb_img),
             // 270 CW sensor -> rotate 90 CW to correct
             SensorRotation::Rotate270 => imageops::rotate90(&rgb_img),
-        };
-        let (w, h) = rotated.dimensions();
-        (rotated, w, h)
+        }
     } else {
-        let (w, h) = rgb_img.dimensions();
-        (rgb_img, w, h)
+        rgb_img
     };
 
     // Mirror horizontally if requested (front-camera selfie mode). Done after
     // rotation so the user-visible orientation is upright before flipping.
-    let rgb_img = if mirror_horizontal {
+    if mirror_horizontal {
         debug!("Mirroring burst output horizontally");
         let mut img = rgb_img;
         image::imageops::flip_horizontal_in_place(&mut img);
         img
     } else {
         rgb_img
-    };
-
-    // Create a PhotoEncoder for the selected format
-    let mut encoder = PhotoEncoder::new();
-    encoder.set_format(encoding_format);
-    encoder.set_quality(EncodingQuality::High);
-    encoder.set_camera_metadata(camera_metadata);
-
-    // Create processed image from RGB data
-    let processed = super::processing::ProcessedImage {
-        image: rgb_img,
-        width,
-        height,
-    };
+    }
+}
 
-    // Encode and save using the standard photo pipeline
-    let encoded = encoder.encode(processed).await?;
+/// Quick, thumbnail-quality JPEG of a burst frame, cropped and turned like
+/// the HDR+ result will be, that stands in for it in the gallery while the
+/// burst is processed (see [`crate::storage::PendingPhoto`])
+///
+/// Raw Bayer bursts get none; their frames need a demosaic first.
+pub async fn save_instant_preview(
+    frame: Arc<CameraFrame>,
+    output_dir: std::path::PathBuf,
+    crop_rect: Option<(u32, u32, u32, u32)>,
+    rotation: SensorRotation,
+    mirror_horizontal: bool,
+) -> Result<
+    (
+        crate::storage::PendingPhoto,
+        crate::storage::GalleryThumbnailData,
+    ),
+    String,
+> {
+    if frame.format.is_bayer() {
+        return Err("No instant preview for raw Bayer bursts".to_string());
+    }
+    let rgba = convert_frame_to_rgba(&frame).await?;
+    let (width, height) = (frame.width, frame.height);
+
+    let (jpeg, preview) = tokio::task::spawn_blocking(move || {
+        let img = image::RgbaImage::from_raw(width, height, rgba)
+            .ok_or("Frame data doesn't match its size")?;
+        let scale = (width.max(height) as f32 / INSTANT_PREVIEW_SIZE as f32).max(1.0);
+        let small = image::imageops::thumbnail(
+            &img,
+            ((width as f32 / scale) as u32).max(1),
+            ((height as f32 / scale) as u32).max(1),
+        );
+        let crop_rect = crop_rect.map(|(x, y, w, h)| {
+            let s = |v: u32| (v as f32 / scale) as u32;
+            (s(x), s(y), s(w).max(1), s(h).max(1))
+        });
+        let preview = crop_and_orient(
+            image::DynamicImage::ImageRgba8(small),
+            crop_rect,
+            rotation,
+            mirror_horizontal,
+        );
 
-    // Save the encoded data
-    let output_path_clone = output_path.clone();
-    let data = encoded.data;
-    tokio::task::spawn_blocking(move || {
-        std::fs::write(&output_path_clone, data).map_err(|e| format!("Failed to save image: {}", e))
+        let mut jpeg = Vec::new();
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, INSTANT_PREVIEW_QUALITY)
+            .encode_image(&preview)
+            .map_err(|e| format!("Failed to encode instant preview: {}", e))?;
+        Ok::<_, String>((jpeg, preview))
     })
     .await
     .map_err(|e| format!("Task join error: {}", e))??;
 
-    Ok(output_path)
+    let pending = crate::storage::PendingPhoto::write(&output_dir, &jpeg).await?;
+    let (preview_width, preview_height) = preview.dimensions();
+    let rgba = image::DynamicImage::ImageRgb8(preview)
+        .into_rgba8()
+        .into_raw();
+    let thumbnail = (
+        cosmic::widget::image::Handle::from_bytes(jpeg),
+        Arc::new(rgba),
+        preview_width,
+        preview_height,
+        pending.path().to_path_buf(),
+    );
+    info!(path = %pending.path().display(), "Instant preview saved");
+    Ok((pending, thumbnail))
 }
 
 /// Export raw burst frames as PNG files for testing/debugging
@@ -3319,6 +4079,30 @@ mod tests {
         let config = BurstModeConfig::default();
         assert_eq!(config.frame_count, 8);
         assert!(!config.export_raw_frames);
+
+        // The default tuning is the pipeline's own defaults
+        let mut tuned = BurstModeConfig::default();
+        tuned.apply_tuning(&crate::config::BurstTuning::default());
+        assert_eq!(tuned.robustness, config.robustness);
+        assert_eq!(tuned.shadow_boost, config.shadow_boost);
+        assert_eq!(tuned.local_contrast, config.local_contrast);
+    }
+
+    #[test]
+    fn test_check_cancelled_reports_cancelled() {
+        let cancel = CancellationToken::new();
+        assert!(check_cancelled(&cancel).is_ok());
+        cancel.cancel();
+        assert_eq!(check_cancelled(&cancel), Err(CANCELLED.to_string()));
+    }
+
+    #[test]
+    fn test_median_offset_ignores_local_motion() {
+        // Most tiles see the camera pan; a few follow a subject
+        let mut offsets = vec![[3.0, -1.0]; 7];
+        offsets.extend([[-12.0, 8.0]; 3]);
+        assert_eq!(median_offset(&offsets), Some((3.0, -1.0)));
+        assert_eq!(median_offset(&[]), None);
     }
 
     /// Validate that a WGSL shader compiles successfully using naga
@@ -3363,6 +4147,11 @@ mod tests {
         validate_shader("warp", WARP_SHADER);
     }
 
+    #[test]
+    fn test_super_res_shader_validates() {
+        validate_shader("super_res", SUPER_RES_SHADER);
+    }
+
     #[test]
     fn test_tonemap_shader_validates() {
         validate_shader("tonemap", TONEMAP_SHADER);
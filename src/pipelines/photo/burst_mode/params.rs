@@ -211,6 +211,27 @@ pub struct BayerFinishParams {
     pub _pad2: u32,       // offset 92
 } // total: 96 bytes
 
+/// Parameters for the super-resolution accumulation shader
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct SuperResParams {
+    pub width: u32,
+    pub height: u32,
+    pub out_width: u32,
+    pub out_height: u32,
+    pub n_tiles_x: u32,
+    pub n_tiles_y: u32,
+    pub tile_step: u32,
+    /// 1 for the reference frame, which is accumulated without displacement
+    pub is_reference: u32,
+    /// Gaussian kernel width, in output pixels
+    pub kernel_sigma: f32,
+    /// Colour difference to the reference a frame is still trusted at
+    pub robust_tolerance: f32,
+    pub _padding0: u32,
+    pub _padding1: u32,
+}
+
 // Size assertions to catch WGSL/Rust struct mismatches at compile time
 const _: () = assert!(std::mem::size_of::<SharpnessParams>() == 32);
 const _: () = assert!(std::mem::size_of::<NoiseParams>() == 32);
@@ -223,5 +244,6 @@ const _: () = assert!(std::mem::size_of::<CAEstimateParams>() == 32);
 const _: () = assert!(std::mem::size_of::<MergeParams>() == 56);
 const _: () = assert!(std::mem::size_of::<SpatialDenoiseParams>() == 40);
 const _: () = assert!(std::mem::size_of::<ChromaDenoiseParams>() == 16);
+const _: () = assert!(std::mem::size_of::<SuperResParams>() == 48);
 // BayerFinishParams: 4*u32(16) + 2*f32(8) + 2*pad(8) + 3*vec4(48) + u32(4) + 3*pad(12) = 96
 const _: () = assert!(std::mem::size_of::<BayerFinishParams>() == 96);
@@ -0,0 +1,168 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Keeping the burst pipeline warm
+//!
+//! Building [`BurstModeGpuPipeline`] compiles every burst shader, which takes
+//! long enough to be felt on each night-mode shot. The pipeline is therefore
+//! shared by everything in the process that needs it (captures, the light
+//! meter, video denoise and stabilization) and only dropped after it has sat
+//! unused for [`IDLE_TIMEOUT`], handing its GPU memory back.
+//!
+//! When the driver supports it, the compiled pipelines are also written to
+//! the user's cache directory, so the first shot after a restart skips most
+//! of the compilation too.
+
+use super::BurstModeGpuPipeline;
+use crate::gpu::wgpu;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// How long the shared pipeline is kept after its last use
+pub const IDLE_TIMEOUT: Duration = Duration::from_secs(120);
+
+const APP_ID: &str = <crate::app::AppModel as cosmic::Application>::APP_ID;
+
+/// The shared pipeline and when it was last handed out
+struct Cached {
+    pipeline: Arc<BurstModeGpuPipeline>,
+    last_used: Instant,
+}
+
+static SHARED: OnceLock<tokio::sync::Mutex<Option<Cached>>> = OnceLock::new();
+
+fn shared_slot() -> &'static tokio::sync::Mutex<Option<Cached>> {
+    SHARED.get_or_init(|| tokio::sync::Mutex::new(None))
+}
+
+impl BurstModeGpuPipeline {
+    /// The process-wide pipeline, created on first use and again after it
+    /// was released for being idle
+    pub async fn shared() -> Result<Arc<Self>, String> {
+        let mut guard = shared_slot().lock().await;
+        if let Some(cached) = guard.as_mut() {
+            cached.last_used = Instant::now();
+            return Ok(Arc::clone(&cached.pipeline));
+        }
+
+        let start = Instant::now();
+        let pipeline = Arc::new(Self::new().await?);
+        info!(
+            elapsed_ms = start.elapsed().as_millis(),
+            "Burst pipeline ready"
+        );
+        *guard = Some(Cached {
+            pipeline: Arc::clone(&pipeline),
+            last_used: Instant::now(),
+        });
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(release_when_idle());
+        }
+        Ok(pipeline)
+    }
+}
+
+/// Drop the shared pipeline once nobody holds it and it went unused for
+/// [`IDLE_TIMEOUT`]
+async fn release_when_idle() {
+    loop {
+        tokio::time::sleep(IDLE_TIMEOUT).await;
+        let mut guard = shared_slot().lock().await;
+        let Some(cached) = guard.as_ref() else {
+            return;
+        };
+        if !is_idle(
+            Arc::strong_count(&cached.pipeline),
+            cached.last_used.elapsed(),
+        ) {
+            continue;
+        }
+        *guard = None;
+        info!("Released the idle burst pipeline");
+        return;
+    }
+}
+
+/// Whether a pipeline with `holders` references, last handed out
+/// `unused_for` ago, can be released. Any holder besides the cache itself
+/// (a video denoiser or stabilizer) keeps it.
+fn is_idle(holders: usize, unused_for: Duration) -> bool {
+    holders <= 1 && unused_for >= IDLE_TIMEOUT
+}
+
+/// Where compiled pipelines for a device are kept
+fn cache_path(key: &str) -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| {
+        dir.join(APP_ID)
+            .join("wgpu")
+            .join(format!("burst-{key}.bin"))
+    })
+}
+
+/// Pipeline cache for `device`, seeded from disk when an earlier run left one
+pub(super) fn load(device: &wgpu::Device, key: &str) -> Option<wgpu::PipelineCache> {
+    let path = cache_path(key)?;
+    let data = std::fs::read(&path).ok();
+    debug!(
+        path = %path.display(),
+        bytes = data.as_ref().map_or(0, Vec::len),
+        "Loading burst pipeline cache"
+    );
+    // SAFETY: the data was written by `save` from this app's own cache with
+    // the same key, and `fallback` makes wgpu start empty if the driver or
+    // adapter no longer accepts it
+    let cache = unsafe {
+        device.create_pipeline_cache(&wgpu::PipelineCacheDescriptor {
+            label: Some("burst_pipeline_cache"),
+            data: data.as_deref(),
+            fallback: true,
+        })
+    };
+    Some(cache)
+}
+
+/// Write the compiled pipelines out for the next run
+pub(super) fn save(cache: &wgpu::PipelineCache, key: &str) {
+    let (Some(path), Some(data)) = (cache_path(key), cache.get_data()) else {
+        return;
+    };
+    let result = (|| {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        // Written aside and renamed, so a crash never leaves half a cache
+        let temp = path.with_extension("tmp");
+        std::fs::write(&temp, &data)?;
+        std::fs::rename(&temp, &path)
+    })();
+    match result {
+        Ok(()) => debug!(path = %path.display(), bytes = data.len(), "Saved burst pipeline cache"),
+        Err(e) => warn!(path = %path.display(), error = %e, "Failed to save burst pipeline cache"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_an_unheld_pipeline_past_the_timeout_is_idle() {
+        assert!(is_idle(1, IDLE_TIMEOUT));
+        assert!(is_idle(1, IDLE_TIMEOUT * 2));
+        // Handed out recently
+        assert!(!is_idle(1, IDLE_TIMEOUT - Duration::from_secs(1)));
+        // Still held by a video denoiser
+        assert!(!is_idle(2, IDLE_TIMEOUT * 2));
+    }
+
+    #[test]
+    fn cache_file_is_per_key_under_the_app_cache() {
+        let Some(path) = cache_path("vulkan-1234") else {
+            // No cache directory in this environment
+            return;
+        };
+        assert!(path.ends_with(format!("{APP_ID}/wgpu/burst-vulkan-1234.bin")));
+        assert_ne!(cache_path("vulkan-5678"), Some(path));
+    }
+}
@@ -0,0 +1,307 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Multi-frame super-resolution
+//!
+//! Uses the same burst capture and sub-pixel alignment as HDR+, but instead
+//! of merging the frames to remove noise, it drops each one onto a grid
+//! twice as fine as the sensor's (drizzle-style). Hand shake moves every
+//! frame by a fraction of a pixel, so between them the frames sample the
+//! scene in between the reference frame's pixels, which a single frame
+//! can't resolve. A frame that disagrees with the reference (moving
+//! subject, failed alignment) counts for less there, so motion falls back
+//! to the upscaled reference instead of ghosting.
+//!
+//! Only RGB bursts are supported; the output is four times the pixels of a
+//! frame and has to fit in one GPU buffer.
+
+use super::params::SuperResParams;
+use super::{
+    BurstModeConfig, BurstModeGpuPipeline, MergedFrame, ProgressCallback, WARP_TILE_SIZE,
+    check_cancelled, convert_frame_to_rgba, release_if_cancelled, sharpen_output,
+    u8_to_f32_normalized,
+};
+use crate::backends::camera::types::CameraFrame;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+/// Output size relative to the input frames
+pub const SCALE: u32 = 2;
+
+/// Frames captured for a super-resolution shot: enough distinct sub-pixel
+/// offsets to fill the finer grid
+pub const FRAME_COUNT: usize = 8;
+
+/// Gaussian kernel width each input pixel is spread with, in output pixels
+const KERNEL_SIGMA: f32 = 0.75;
+
+/// Colour difference to the reference (0-1 scale) at which a frame's
+/// weight drops to about a third, before [`BurstModeConfig::robustness`]
+const ROBUST_TOLERANCE: f32 = 0.08;
+
+/// Reconstruct a burst at [`SCALE`] times its resolution
+///
+/// Progress is reported in the same 0-1 range as [`super::process_burst_mode`].
+pub async fn process_super_resolution(
+    frames: Vec<Arc<CameraFrame>>,
+    config: BurstModeConfig,
+    progress: Option<ProgressCallback>,
+    cancel: CancellationToken,
+) -> Result<MergedFrame, String> {
+    let result = super_resolve(frames, config, progress, &cancel).await;
+    release_if_cancelled(result, &cancel).await
+}
+
+async fn super_resolve(
+    frames: Vec<Arc<CameraFrame>>,
+    config: BurstModeConfig,
+    progress: Option<ProgressCallback>,
+    cancel: &CancellationToken,
+) -> Result<MergedFrame, String> {
+    let total_start = std::time::Instant::now();
+    let Some(first) = frames.first() else {
+        return Err("Super-resolution requires at least one frame".to_string());
+    };
+    if first.format.is_bayer() {
+        return Err("Super-resolution needs RGB frames, not raw Bayer".to_string());
+    }
+    info!(
+        frames = frames.len(),
+        width = first.width,
+        height = first.height,
+        "Processing super-resolution capture"
+    );
+
+    let report = |value: f32| {
+        if let Some(cb) = &progress {
+            cb(value);
+        }
+    };
+
+    // Initialize GPU pipeline (0% - 5%)
+    report(0.0);
+    let gpu = BurstModeGpuPipeline::shared().await?;
+    let output_bytes = u64::from(first.width * SCALE) * u64::from(first.height * SCALE) * 16;
+    if output_bytes > gpu.tile_budget().largest_buffer {
+        return Err(format!(
+            "{}x{} frames are too large to super-resolve on this GPU",
+            first.width, first.height
+        ));
+    }
+    report(0.05);
+
+    // Select reference frame (5% - 10%)
+    let ref_idx = gpu.select_reference_frame(&frames, cancel).await?;
+    report(0.10);
+
+    // Align and accumulate (10% - 85%), normalize and read back (85% - 90%)
+    let merged = gpu
+        .accumulate_super_resolution(&frames, ref_idx, &config, &progress, cancel)
+        .await?;
+    check_cancelled(cancel)?;
+
+    // Output sharpening (90% - 100%), no tone mapping: the frames already
+    // went through the camera's ISP
+    report(0.90);
+    let merged = sharpen_output(merged, config.output_sharpening).await;
+    report(1.0);
+
+    info!(
+        total_elapsed_ms = total_start.elapsed().as_millis(),
+        width = merged.width,
+        height = merged.height,
+        "Super-resolution processing complete"
+    );
+    Ok(merged)
+}
+
+impl BurstModeGpuPipeline {
+    /// Align every frame against the reference and gather it onto the fine
+    /// grid, then normalize and read the result back
+    async fn accumulate_super_resolution(
+        &self,
+        frames: &[Arc<CameraFrame>],
+        ref_idx: usize,
+        config: &BurstModeConfig,
+        progress: &Option<ProgressCallback>,
+        cancel: &CancellationToken,
+    ) -> Result<MergedFrame, String> {
+        let reference = &frames[ref_idx];
+        let (width, height) = (reference.width, reference.height);
+        let (out_width, out_height) = (width * SCALE, height * SCALE);
+        let pixel_count = (width * height) as usize;
+        let out_pixel_count = (out_width * out_height) as usize;
+        let workgroups = (out_width.div_ceil(16), out_height.div_ceil(16), 1);
+
+        let buffers = self.create_alignment_buffers(width, height);
+
+        // Reference stays on the GPU for alignment and the robustness check
+        let ref_rgba = convert_frame_to_rgba(reference).await?;
+        let ref_f32 = u8_to_f32_normalized(&ref_rgba);
+        drop(ref_rgba);
+        let ref_buffer = self.create_storage_buffer(
+            "super_res_reference",
+            (pixel_count * 4 * std::mem::size_of::<f32>()) as u64,
+        );
+        self.queue
+            .write_buffer(&ref_buffer, 0, bytemuck::cast_slice(&ref_f32));
+        drop(ref_f32);
+        let ref_pyramids = self.build_reference_pyramid(&ref_buffer, width, height, &buffers);
+
+        // Starts zeroed
+        let accum = self.create_storage_buffer(
+            "super_res_accum",
+            (out_pixel_count * 4 * std::mem::size_of::<f32>()) as u64,
+        );
+        let params_buffer = self.create_uniform_buffer(
+            "super_res_params",
+            std::mem::size_of::<SuperResParams>() as u64,
+        );
+        let params = |n_tiles: (u32, u32), is_reference: bool| SuperResParams {
+            width,
+            height,
+            out_width,
+            out_height,
+            n_tiles_x: n_tiles.0,
+            n_tiles_y: n_tiles.1,
+            tile_step: WARP_TILE_SIZE / 2,
+            is_reference: u32::from(is_reference),
+            kernel_sigma: KERNEL_SIGMA,
+            robust_tolerance: ROBUST_TOLERANCE * config.robustness.max(0.1),
+            _padding0: 0,
+            _padding1: 0,
+        };
+
+        // The reference first, so every output pixel has a sample; its
+        // alignment binding goes unread
+        self.queue.write_buffer(
+            &params_buffer,
+            0,
+            bytemuck::cast_slice(&[params((1, 1), true)]),
+        );
+        let ref_bg = self.bind_group(
+            "super_res_reference_bg",
+            &self.super_res_layout,
+            &[
+                &ref_buffer,
+                &ref_buffer,
+                &buffers.align[0],
+                &accum,
+                &params_buffer,
+            ],
+        );
+        self.dispatch_compute(
+            "super_res_accumulate",
+            &self.super_res_accumulate,
+            &ref_bg,
+            workgroups,
+        );
+
+        let total = frames.len() - 1;
+        let mut accumulated = 0;
+        for (idx, frame) in frames.iter().enumerate() {
+            if idx == ref_idx {
+                continue;
+            }
+            check_cancelled(cancel)?;
+
+            if frame.width != width || frame.height != height {
+                warn!(
+                    frame = idx,
+                    frame_width = frame.width,
+                    frame_height = frame.height,
+                    "Skipping frame with mismatched dimensions"
+                );
+                continue;
+            }
+
+            let frame_start = std::time::Instant::now();
+            let comp_rgba = convert_frame_to_rgba(frame).await?;
+            let comp_f32 = u8_to_f32_normalized(&comp_rgba);
+            drop(comp_rgba);
+            self.queue
+                .write_buffer(&buffers.comp_rgba, 0, bytemuck::cast_slice(&comp_f32));
+            drop(comp_f32);
+
+            // Per-tile offsets land in `buffers.align[0]`; the frame is
+            // sampled through them directly instead of being warped first
+            self.build_luminance_pyramid(
+                &buffers.comp_rgba,
+                &buffers.comp_lum,
+                width,
+                height,
+                &buffers,
+                "comp",
+            );
+            let n_tiles = self
+                .align_luminance_pyramids(&ref_pyramids, width, height, &buffers)
+                .await;
+
+            self.queue.write_buffer(
+                &params_buffer,
+                0,
+                bytemuck::cast_slice(&[params(n_tiles, false)]),
+            );
+            let bg = self.bind_group(
+                "super_res_frame_bg",
+                &self.super_res_layout,
+                &[
+                    &buffers.comp_rgba,
+                    &ref_buffer,
+                    &buffers.align[0],
+                    &accum,
+                    &params_buffer,
+                ],
+            );
+            self.dispatch_compute(
+                "super_res_accumulate",
+                &self.super_res_accumulate,
+                &bg,
+                workgroups,
+            );
+
+            accumulated += 1;
+            debug!(
+                frame = idx,
+                elapsed_ms = frame_start.elapsed().as_millis(),
+                "Frame accumulated onto the super-resolution grid"
+            );
+            if let Some(cb) = progress {
+                cb(0.10 + 0.75 * accumulated as f32 / total.max(1) as f32);
+            }
+            self.yield_to_compositor().await;
+        }
+        check_cancelled(cancel)?;
+
+        let normalize_bg = self.bind_group(
+            "super_res_normalize_bg",
+            &self.super_res_layout,
+            &[
+                &ref_buffer,
+                &ref_buffer,
+                &buffers.align[0],
+                &accum,
+                &params_buffer,
+            ],
+        );
+        self.dispatch_compute(
+            "super_res_normalize",
+            &self.super_res_normalize,
+            &normalize_bg,
+            workgroups,
+        );
+
+        let result_f32 = self.read_buffer::<f32>(&accum, out_pixel_count * 4).await?;
+        let data = result_f32
+            .iter()
+            .map(|&x| (x.clamp(0.0, 1.0) * 255.0) as u8)
+            .collect();
+        info!(accumulated, out_width, out_height, "Frames accumulated");
+
+        Ok(MergedFrame {
+            data,
+            width: out_width,
+            height: out_height,
+        })
+    }
+}
@@ -0,0 +1,372 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Tiled burst processing for large sensors and small GPUs
+//!
+//! The burst pipeline keeps every frame on the GPU as RGBA f32, 16 bytes a
+//! pixel: 192 MB per 12 MP frame. That is more than many drivers let a
+//! shader bind in one buffer (`max_storage_buffer_binding_size`, 128 MB by
+//! default), and eight of them are more than an integrated GPU can spare.
+//!
+//! When [`plan`] finds a burst won't fit, the frames are cut into
+//! overlapping tiles that are aligned and merged one at a time, and
+//! [`TileBlender`] feathers the overlaps back together so no seams show.
+//! Tone mapping is tiled the same way, with the scene brightness measured
+//! once on the whole merged frame so every tile gets the same curve.
+
+use super::bayer_planes::BayerPlanes;
+use crate::gpu::wgpu;
+
+/// GPU memory the pipeline may use at once. wgpu can't tell how much video
+/// memory is free, so this is sized for integrated GPUs sharing system RAM.
+const WORKING_SET_BUDGET: u64 = 1536 << 20;
+
+/// Pixels tiles share with their neighbours; well over the alignment search
+/// range, so tile edges align as well as their middles
+const OVERLAP: u32 = 128;
+
+/// Smallest tile side; the coarsest pyramid level needs a few alignment
+/// tiles to work with
+const MIN_TILE_SIDE: u32 = 512;
+
+/// Bytes of an RGBA f32 pixel
+const RGBA_F32: u64 = 16;
+
+/// GPU buffers a frame must fit in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct GpuBudget {
+    /// Largest buffer a shader can bind
+    pub largest_buffer: u64,
+    /// All buffers of one pass together
+    pub working_set: u64,
+}
+
+impl GpuBudget {
+    pub fn from_device(device: &wgpu::Device) -> Self {
+        let limits = device.limits();
+        Self {
+            largest_buffer: (limits.max_storage_buffer_binding_size as u64)
+                .min(limits.max_buffer_size),
+            working_set: WORKING_SET_BUDGET,
+        }
+    }
+}
+
+/// GPU memory a pass needs per pixel of its input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Footprint {
+    /// Bytes per pixel of the largest single buffer
+    pub largest_buffer: u64,
+    /// Bytes per pixel of all buffers together
+    pub working_set: u64,
+}
+
+impl Footprint {
+    /// Aligning and merging an RGBA burst: the aligned frames, plus the
+    /// reference, pyramids and FFT merge buffers
+    pub fn rgba_burst(frame_count: usize) -> Self {
+        Self {
+            largest_buffer: RGBA_F32,
+            working_set: (frame_count as u64 + 8) * RGBA_F32,
+        }
+    }
+
+    /// Aligning, merging and demosaicing a Bayer burst, per pixel of the
+    /// half-resolution planes; the demosaiced output has four pixels for
+    /// each of them
+    pub fn bayer_burst(frame_count: usize) -> Self {
+        Self {
+            largest_buffer: 4 * RGBA_F32,
+            working_set: (frame_count as u64 + 8) * RGBA_F32 + 2 * 4 * RGBA_F32,
+        }
+    }
+
+    /// Tone mapping: input, output and readback
+    pub fn tonemap() -> Self {
+        Self {
+            largest_buffer: RGBA_F32,
+            working_set: 3 * RGBA_F32,
+        }
+    }
+}
+
+/// Rectangle of a frame processed on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Tile {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Tile {
+    /// The same tile in a frame `factor` times as large
+    pub fn scaled(self, factor: u32) -> Self {
+        Self {
+            x: self.x * factor,
+            y: self.y * factor,
+            width: self.width * factor,
+            height: self.height * factor,
+        }
+    }
+}
+
+/// Tiles to process a `width`x`height` frame in, `None` if it fits whole
+pub(crate) fn plan(
+    width: u32,
+    height: u32,
+    footprint: Footprint,
+    budget: GpuBudget,
+) -> Option<Vec<Tile>> {
+    let max_pixels = (budget.largest_buffer / footprint.largest_buffer)
+        .min(budget.working_set / footprint.working_set);
+    if u64::from(width) * u64::from(height) <= max_pixels {
+        return None;
+    }
+
+    // Square tiles in steps of 64, so pyramid levels stay whole
+    let side = ((max_pixels as f64).sqrt() as u32 / 64 * 64).max(MIN_TILE_SIDE);
+    let columns = split(width, side);
+    let rows = split(height, side);
+    Some(
+        rows.iter()
+            .flat_map(|&(y, tile_height)| {
+                columns.iter().map(move |&(x, tile_width)| Tile {
+                    x,
+                    y,
+                    width: tile_width,
+                    height: tile_height,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Cut `len` pixels into evenly sized, overlapping spans of at most `side`,
+/// as (start, length)
+fn split(len: u32, side: u32) -> Vec<(u32, u32)> {
+    if len <= side {
+        return vec![(0, len)];
+    }
+    let count = (len - OVERLAP).div_ceil(side - OVERLAP);
+    let span = (len + (count - 1) * OVERLAP).div_ceil(count);
+    (0..count)
+        .map(|i| ((i * (span - OVERLAP)).min(len - span), span))
+        .collect()
+}
+
+/// The tile in the middle of the plan, where reference frame selection
+/// looks for sharpness
+pub(crate) fn center_tile(tiles: &[Tile], width: u32, height: u32) -> Tile {
+    let (cx, cy) = (width / 2, height / 2);
+    tiles
+        .iter()
+        .copied()
+        .find(|t| (t.x..t.x + t.width).contains(&cx) && (t.y..t.y + t.height).contains(&cy))
+        .unwrap_or(tiles[0])
+}
+
+/// A tile of a tightly packed RGBA u8 image
+pub(crate) fn crop_rgba(data: &[u8], width: u32, tile: &Tile) -> Vec<u8> {
+    let row_bytes = tile.width as usize * 4;
+    let mut out = Vec::with_capacity(row_bytes * tile.height as usize);
+    for y in tile.y..tile.y + tile.height {
+        let start = (y as usize * width as usize + tile.x as usize) * 4;
+        out.extend_from_slice(&data[start..start + row_bytes]);
+    }
+    out
+}
+
+/// A tile of Bayer planes, in plane pixels
+pub(crate) fn crop_planes(planes: &BayerPlanes, tile: &Tile) -> BayerPlanes {
+    let row_floats = tile.width as usize * 4;
+    let mut data = Vec::with_capacity(row_floats * tile.height as usize);
+    for y in tile.y..tile.y + tile.height {
+        let start = (y as usize * planes.width as usize + tile.x as usize) * 4;
+        data.extend_from_slice(&planes.data[start..start + row_floats]);
+    }
+    BayerPlanes {
+        data,
+        width: tile.width,
+        height: tile.height,
+        colour_gains: planes.colour_gains,
+        colour_correction_matrix: planes.colour_correction_matrix,
+        bit_depth: planes.bit_depth,
+    }
+}
+
+/// Mean BT.601 luma (0-1) of an RGBA u8 image, as the tone mapper measures
+/// scene brightness
+pub(crate) fn mean_luminance(data: &[u8]) -> f32 {
+    let pixels = data.len() / 4;
+    if pixels == 0 {
+        return 0.5;
+    }
+    let sum: f64 = data
+        .chunks_exact(4)
+        .map(|p| 0.299 * f64::from(p[0]) + 0.587 * f64::from(p[1]) + 0.114 * f64::from(p[2]))
+        .sum();
+    (sum / (pixels as f64 * 255.0)) as f32
+}
+
+/// Puts processed tiles back together, cross-fading where they overlap
+pub(crate) struct TileBlender {
+    width: u32,
+    height: u32,
+    /// Weighted RGBA sums
+    sum: Vec<f32>,
+    /// Sum of weights per pixel
+    weight: Vec<f32>,
+}
+
+impl TileBlender {
+    pub fn new(width: u32, height: u32) -> Self {
+        let pixels = width as usize * height as usize;
+        Self {
+            width,
+            height,
+            sum: vec![0.0; pixels * 4],
+            weight: vec![0.0; pixels],
+        }
+    }
+
+    /// Add a processed tile (RGBA u8, `tile.width`x`tile.height`)
+    ///
+    /// Weights ramp down over the overlap towards edges shared with other
+    /// tiles and stay whole at the frame's own edges.
+    pub fn add(&mut self, tile: &Tile, data: &[u8]) {
+        let ramp = |pos: u32, len: u32, start: u32, total: u32| -> f32 {
+            let mut weight = 1.0f32;
+            if start > 0 {
+                weight = weight.min((pos as f32 + 0.5) / OVERLAP as f32);
+            }
+            if start + len < total {
+                weight = weight.min(((len - pos) as f32 - 0.5) / OVERLAP as f32);
+            }
+            weight.clamp(0.0, 1.0)
+        };
+        for ty in 0..tile.height {
+            let wy = ramp(ty, tile.height, tile.y, self.height);
+            for tx in 0..tile.width {
+                let w = wy * ramp(tx, tile.width, tile.x, self.width);
+                let src = (ty as usize * tile.width as usize + tx as usize) * 4;
+                let dst = (tile.y + ty) as usize * self.width as usize + (tile.x + tx) as usize;
+                for c in 0..4 {
+                    self.sum[dst * 4 + c] += w * f32::from(data[src + c]);
+                }
+                self.weight[dst] += w;
+            }
+        }
+    }
+
+    /// The blended frame as RGBA u8
+    pub fn finish(self) -> Vec<u8> {
+        self.sum
+            .chunks_exact(4)
+            .zip(&self.weight)
+            .flat_map(|(sum, &weight)| {
+                let weight = weight.max(f32::EPSILON);
+                [0, 1, 2, 3].map(|c| (sum[c] / weight).round().clamp(0.0, 255.0) as u8)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL_GPU: GpuBudget = GpuBudget {
+        largest_buffer: 128 << 20,
+        working_set: WORKING_SET_BUDGET,
+    };
+
+    #[test]
+    fn large_bursts_are_tiled() {
+        // 1080p fits whole, 12 MP doesn't
+        assert!(plan(1920, 1080, Footprint::rgba_burst(8), SMALL_GPU).is_none());
+        let tiles = plan(4000, 3000, Footprint::rgba_burst(8), SMALL_GPU).unwrap();
+        assert!(tiles.len() > 1);
+
+        // Tiles fit and together cover the frame
+        let max_pixels = SMALL_GPU.working_set / Footprint::rgba_burst(8).working_set;
+        let mut covered = vec![false; 4000 * 3000];
+        for tile in &tiles {
+            assert!(u64::from(tile.width * tile.height) <= max_pixels);
+            for y in tile.y..tile.y + tile.height {
+                for x in tile.x..tile.x + tile.width {
+                    covered[(y * 4000 + x) as usize] = true;
+                }
+            }
+        }
+        assert!(covered.iter().all(|&c| c));
+
+        let center = center_tile(&tiles, 4000, 3000);
+        assert!((center.x..center.x + center.width).contains(&2000));
+    }
+
+    #[test]
+    fn spans_overlap_evenly() {
+        assert_eq!(split(900, 1024), vec![(0, 900)]);
+        let spans = split(3000, 1024);
+        assert_eq!(spans.len(), 4);
+        for pair in spans.windows(2) {
+            assert_eq!(pair[0].0 + pair[0].1 - pair[1].0, OVERLAP);
+        }
+        let (start, len) = spans[spans.len() - 1];
+        assert_eq!(start + len, 3000);
+    }
+
+    #[test]
+    fn blending_has_no_seams() {
+        let (width, height) = (600, 8);
+        let tiles = split(width, 400)
+            .into_iter()
+            .map(|(x, w)| Tile {
+                x,
+                y: 0,
+                width: w,
+                height,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(tiles.len(), 2);
+
+        // Tiles of a flat image blend back to it exactly
+        let mut blender = TileBlender::new(width, height);
+        for tile in &tiles {
+            blender.add(tile, &vec![90; (tile.width * tile.height * 4) as usize]);
+        }
+        assert!(blender.finish().iter().all(|&v| v == 90));
+
+        // Tiles that disagree cross-fade instead of stepping
+        let mut blender = TileBlender::new(width, height);
+        blender.add(&tiles[0], &vec![0; (tiles[0].width * height * 4) as usize]);
+        blender.add(
+            &tiles[1],
+            &vec![200; (tiles[1].width * height * 4) as usize],
+        );
+        let row: Vec<u8> = blender
+            .finish()
+            .chunks(4)
+            .take(width as usize)
+            .map(|p| p[0])
+            .collect();
+        assert!(row.windows(2).all(|w| w[1] >= w[0] && w[1] - w[0] <= 4));
+        assert_eq!((row[0], row[width as usize - 1]), (0, 200));
+    }
+
+    #[test]
+    fn crops_keep_their_rows() {
+        // 3x2 image holding the pixel index
+        let data: Vec<u8> = (0..6u8).flat_map(|i| [i, i, i, 255]).collect();
+        let tile = Tile {
+            x: 1,
+            y: 0,
+            width: 2,
+            height: 2,
+        };
+        let crop: Vec<u8> = crop_rgba(&data, 3, &tile).chunks(4).map(|p| p[0]).collect();
+        assert_eq!(crop, [1, 2, 4, 5]);
+        assert_eq!(tile.scaled(2).width, 4);
+        assert!((mean_luminance(&[255, 255, 255, 255]) - 1.0).abs() < 1e-6);
+    }
+}
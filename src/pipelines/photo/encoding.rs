@@ -6,11 +6,21 @@
 //! - JPEG (with quality control)
 //! - PNG (lossless)
 //!
+//! A rotation left to viewers ([`CameraMetadata::orientation`]) is written as
+//! the EXIF Orientation tag: an APP1 segment in JPEG, an `eXIf` chunk in PNG
+//! and the TIFF tag in DNG. Exposure time, ISO and (from libcamera's frame
+//! metadata) focus distance go in the same EXIF block; DNG files also record
+//! the white balance the camera chose as AsShotNeutral.
+//!
+//! JPEG and PNG files embed the sRGB ICC profile ([`crate::media::icc`]) the
+//! conversion to RGB produced them in.
+//!
 //! All encoding operations run asynchronously to avoid blocking.
 
 use super::processing::ProcessedImage;
-use crate::backends::camera::types::PixelFormat;
-use image::RgbImage;
+use crate::backends::camera::types::{CameraFrame, FrameMetadata, PixelFormat, SensorRotation};
+use crate::errors::{AppResult, PhotoError, StorageError};
+use image::{RgbImage, RgbaImage};
 use std::path::PathBuf;
 use tracing::{debug, error, info};
 
@@ -104,9 +114,47 @@ pub struct CameraMetadata {
     pub iso: Option<u32>,
     /// Gain value (camera-specific units)
     pub gain: Option<i32>,
+    /// Analogue gain multiplier applied by the ISP (libcamera)
+    pub analogue_gain: Option<f32>,
+    /// White balance colour temperature in Kelvin (libcamera)
+    pub colour_temperature: Option<u32>,
+    /// White balance gains [R, B] applied by the ISP (libcamera)
+    pub colour_gains: Option<[f32; 2]>,
+    /// Lens position in dioptres, 0 at infinity (libcamera)
+    pub lens_position: Option<f32>,
+    /// Rotation correction viewers still have to apply, written as the
+    /// orientation tag; `None` when it is turned into the pixels
+    pub orientation: SensorRotation,
+}
+
+impl CameraMetadata {
+    /// This metadata with what libcamera reported for the captured frame,
+    /// which describes the frame itself rather than the camera's state when
+    /// the controls were read
+    pub fn with_frame_metadata(self, frame: &FrameMetadata) -> Self {
+        let total_gain = frame
+            .analogue_gain
+            .map(|gain| gain * frame.digital_gain.unwrap_or(1.0));
+        Self {
+            exposure_time: frame
+                .exposure_time
+                .map(|us| us as f64 / 1_000_000.0)
+                .or(self.exposure_time),
+            // libcamera's convention: unity gain is ISO 100
+            iso: total_gain
+                .map(|gain| (gain * 100.0).round() as u32)
+                .or(self.iso),
+            analogue_gain: frame.analogue_gain.or(self.analogue_gain),
+            colour_temperature: frame.colour_temperature.or(self.colour_temperature),
+            colour_gains: frame.colour_gains.or(self.colour_gains),
+            lens_position: frame.lens_position.or(self.lens_position),
+            ..self
+        }
+    }
 }
 
 /// Photo encoder
+#[derive(Clone)]
 pub struct PhotoEncoder {
     format: EncodingFormat,
     quality: EncodingQuality,
@@ -143,6 +191,16 @@ impl PhotoEncoder {
         self.camera_metadata = metadata;
     }
 
+    /// This encoder with the metadata libcamera reported for `frame` folded
+    /// into its camera metadata
+    pub fn for_frame(&self, frame: &CameraFrame) -> Self {
+        let mut encoder = self.clone();
+        if let Some(metadata) = &frame.libcamera_metadata {
+            encoder.camera_metadata = encoder.camera_metadata.with_frame_metadata(metadata);
+        }
+        encoder
+    }
+
     /// Encode raw Bayer data directly as DNG (bypasses post-processing)
     ///
     /// This writes the raw sensor data into a CFA-pattern DNG file with proper
@@ -199,8 +257,13 @@ impl PhotoEncoder {
         // Run encoding in background task (CPU-bound)
         tokio::task::spawn_blocking(move || {
             let data = match format {
-                EncodingFormat::Jpeg => Self::encode_jpeg(processed.image, quality)?,
-                EncodingFormat::Png => Self::encode_png(processed.image)?,
+                EncodingFormat::Jpeg => tag_jpeg_exif(
+                    Self::encode_jpeg(processed.image, quality)?,
+                    &camera_metadata,
+                ),
+                EncodingFormat::Png => {
+                    tag_png_exif(Self::encode_png(processed.image)?, &camera_metadata)
+                }
                 EncodingFormat::Dng => Self::encode_dng(
                     &processed.image,
                     processed.width,
@@ -222,6 +285,31 @@ impl PhotoEncoder {
         .map_err(|e| format!("Encoding task error: {}", e))?
     }
 
+    /// Encode an image with a transparent background as PNG, whatever the
+    /// format setting, since only PNG keeps the alpha channel
+    pub async fn encode_transparent(&self, image: RgbaImage) -> Result<EncodedImage, String> {
+        let (width, height) = image.dimensions();
+        info!(width, height, "Starting transparent PNG encoding");
+
+        let camera_metadata = self.camera_metadata.clone();
+        tokio::task::spawn_blocking(move || {
+            let png = Self::encode_png_pixels(
+                image.as_raw(),
+                width,
+                height,
+                image::ExtendedColorType::Rgba8,
+            )?;
+            Ok(EncodedImage {
+                data: tag_png_exif(png, &camera_metadata),
+                format: EncodingFormat::Png,
+                width,
+                height,
+            })
+        })
+        .await
+        .map_err(|e| format!("Encoding task error: {}", e))?
+    }
+
     /// Save encoded image to disk asynchronously
     ///
     /// Generates a timestamped filename and saves to the specified directory.
@@ -232,12 +320,9 @@ impl PhotoEncoder {
     ///
     /// # Returns
     /// * `Ok(PathBuf)` - Path to saved file
-    /// * `Err(String)` - Error message
-    pub async fn save(
-        &self,
-        encoded: EncodedImage,
-        output_dir: PathBuf,
-    ) -> Result<PathBuf, String> {
+    /// * `Err(AppError)` - Why the file couldn't be written (disk full,
+    ///   permission denied, ...)
+    pub async fn save(&self, encoded: EncodedImage, output_dir: PathBuf) -> AppResult<PathBuf> {
         debug!(
             output_dir = %output_dir.display(),
             format = ?encoded.format,
@@ -252,11 +337,7 @@ impl PhotoEncoder {
                 error = %e,
                 "Failed to create output directory - check filesystem permissions and path validity"
             );
-            return Err(format!(
-                "Failed to create output directory '{}': {}",
-                output_dir.display(),
-                e
-            ));
+            return Err(StorageError::from_io(&e, &output_dir).into());
         }
 
         // Generate filename with timestamp (millisecond precision so two rapid captures don't collide).
@@ -284,11 +365,7 @@ impl PhotoEncoder {
                     error = %io_err,
                     "Failed to write photo to disk - check disk space and permissions"
                 );
-                Err(format!(
-                    "Failed to save photo to '{}': {}",
-                    filepath_for_error.display(),
-                    io_err
-                ))
+                Err(StorageError::from_io(&io_err, &filepath_for_error).into())
             }
             Err(join_err) => {
                 error!(
@@ -296,22 +373,27 @@ impl PhotoEncoder {
                     error = %join_err,
                     "Save task panicked or was cancelled"
                 );
-                Err(format!("Save task error: {}", join_err))
+                Err(PhotoError::SaveFailed(format!("Save task error: {}", join_err)).into())
             }
         }
     }
 
     /// Encode image as JPEG
     fn encode_jpeg(image: RgbImage, quality: EncodingQuality) -> Result<Vec<u8>, String> {
+        use image::ImageEncoder;
+
         let mut buffer = Vec::new();
         let mut cursor = std::io::Cursor::new(&mut buffer);
 
         // Create JPEG encoder with quality setting
         let mut encoder =
             image::codecs::jpeg::JpegEncoder::new_with_quality(&mut cursor, quality.jpeg_quality());
+        encoder
+            .set_icc_profile(crate::media::icc::srgb_profile().to_vec())
+            .map_err(|e| format!("JPEG ICC profile: {}", e))?;
 
         encoder
-            .encode(
+            .write_image(
                 image.as_raw(),
                 image.width(),
                 image.height(),
@@ -324,13 +406,30 @@ impl PhotoEncoder {
 
     /// Encode image as PNG
     fn encode_png(image: RgbImage) -> Result<Vec<u8>, String> {
+        Self::encode_png_pixels(
+            image.as_raw(),
+            image.width(),
+            image.height(),
+            image::ExtendedColorType::Rgb8,
+        )
+    }
+
+    fn encode_png_pixels(
+        pixels: &[u8],
+        width: u32,
+        height: u32,
+        color_type: image::ExtendedColorType,
+    ) -> Result<Vec<u8>, String> {
+        use image::ImageEncoder;
+
         let mut buffer = Vec::new();
 
-        image
-            .write_to(
-                &mut std::io::Cursor::new(&mut buffer),
-                image::ImageFormat::Png,
-            )
+        let mut encoder = image::codecs::png::PngEncoder::new(std::io::Cursor::new(&mut buffer));
+        encoder
+            .set_icc_profile(crate::media::icc::srgb_profile().to_vec())
+            .map_err(|e| format!("PNG ICC profile: {}", e))?;
+        encoder
+            .write_image(pixels, width, height, color_type)
             .map_err(|e| format!("PNG encoding failed: {}", e))?;
 
         Ok(buffer)
@@ -369,6 +468,12 @@ impl PhotoEncoder {
         );
         ifd.insert(tiff_tags::PhotometricInterpretation, IfdValue::Short(2)); // RGB
         ifd.insert(tiff_tags::SamplesPerPixel, IfdValue::Short(3)); // RGB = 3 samples
+        if camera_metadata.orientation != SensorRotation::None {
+            ifd.insert(
+                tiff_tags::Orientation,
+                IfdValue::Short(exif_orientation(camera_metadata.orientation)),
+            );
+        }
 
         // Strip data
         let offsets: Arc<dyn Offsets + Send + Sync> = Arc::new(DngOffsets { data: raw_data });
@@ -596,15 +701,10 @@ fn set_common_dng_tags(
 
     // Exposure metadata (EXIF tags)
     if let Some(exposure_time) = camera_metadata.exposure_time {
-        // Convert to rational: e.g., 0.033333 -> 1/30
-        // Use microsecond precision for the rational representation
-        let numerator = (exposure_time * 1_000_000.0).round() as u32;
-        let denominator = 1_000_000u32;
-        // Simplify the fraction by finding GCD
-        let g = gcd(numerator, denominator);
+        let (numerator, denominator) = exposure_rational(exposure_time);
         ifd.insert(
             tiff_tags::ExposureTime,
-            IfdValue::Rational(numerator / g, denominator / g),
+            IfdValue::Rational(numerator, denominator),
         );
     }
 
@@ -615,9 +715,197 @@ fn set_common_dng_tags(
         );
     }
 
+    // White balance the ISP applied, as the neutral colour in camera RGB
+    if let Some([red, blue]) = camera_metadata.colour_gains
+        && red > 0.0
+        && blue > 0.0
+    {
+        let neutral =
+            |gain: f32| IfdValue::Rational((1_000_000.0 / gain).round() as u32, 1_000_000);
+        ifd.insert(
+            tiff_tags::AsShotNeutral,
+            IfdValue::List(vec![neutral(red), IfdValue::Rational(1, 1), neutral(blue)]),
+        );
+    }
+
     ifd
 }
 
+/// Exposure time as a rational, e.g. 0.033333 -> 1/30, at microsecond
+/// precision
+fn exposure_rational(seconds: f64) -> (u32, u32) {
+    let numerator = (seconds * 1_000_000.0).round() as u32;
+    let denominator = 1_000_000u32;
+    let g = gcd(numerator, denominator);
+    (numerator / g, denominator / g)
+}
+
+/// EXIF Orientation value for an image that still needs the `rotation`
+/// correction: 1 upright, 3 upside down, 6 and 8 turned a quarter
+/// clockwise and counter-clockwise
+fn exif_orientation(rotation: SensorRotation) -> u16 {
+    // A correction of 90 turns counter-clockwise, like the rest of the
+    // pipeline does it
+    match rotation {
+        SensorRotation::None => 1,
+        SensorRotation::Rotate90 => 8,
+        SensorRotation::Rotate180 => 3,
+        SensorRotation::Rotate270 => 6,
+    }
+}
+
+/// Value of a TIFF tag, all single-valued
+#[derive(Clone, Copy)]
+enum TiffValue {
+    Short(u16),
+    Long(u32),
+    Rational(u32, u32),
+}
+
+/// Size of an IFD with `entries` entries, without out-of-line values
+fn ifd_len(entries: usize) -> usize {
+    2 + 12 * entries + 4
+}
+
+/// Append an IFD whose out-of-line values (rationals) follow it, starting
+/// at `data_offset` from the start of the TIFF structure
+fn write_ifd(tiff: &mut Vec<u8>, entries: &[(u16, TiffValue)], data_offset: usize) {
+    let mut data = Vec::new();
+    tiff.extend_from_slice(&(entries.len() as u16).to_be_bytes());
+    for &(tag, value) in entries {
+        tiff.extend_from_slice(&tag.to_be_bytes());
+        match value {
+            TiffValue::Short(v) => {
+                tiff.extend_from_slice(&3u16.to_be_bytes());
+                tiff.extend_from_slice(&1u32.to_be_bytes());
+                tiff.extend_from_slice(&v.to_be_bytes());
+                tiff.extend_from_slice(&[0, 0]);
+            }
+            TiffValue::Long(v) => {
+                tiff.extend_from_slice(&4u16.to_be_bytes());
+                tiff.extend_from_slice(&1u32.to_be_bytes());
+                tiff.extend_from_slice(&v.to_be_bytes());
+            }
+            TiffValue::Rational(numerator, denominator) => {
+                tiff.extend_from_slice(&5u16.to_be_bytes());
+                tiff.extend_from_slice(&1u32.to_be_bytes());
+                tiff.extend_from_slice(&((data_offset + data.len()) as u32).to_be_bytes());
+                data.extend_from_slice(&numerator.to_be_bytes());
+                data.extend_from_slice(&denominator.to_be_bytes());
+            }
+        }
+    }
+    tiff.extend_from_slice(&0u32.to_be_bytes()); // No next IFD
+    tiff.extend_from_slice(&data);
+}
+
+/// A big-endian TIFF structure with the orientation in IFD0 and the
+/// exposure and focus distance in an Exif IFD: the payload of a JPEG Exif
+/// segment and of a PNG `eXIf` chunk. `None` when there is nothing to tag.
+fn exif_tiff(metadata: &CameraMetadata) -> Option<Vec<u8>> {
+    let mut ifd0 = Vec::new();
+    if metadata.orientation != SensorRotation::None {
+        ifd0.push((
+            0x0112, // Orientation
+            TiffValue::Short(exif_orientation(metadata.orientation)),
+        ));
+    }
+
+    // Entries in ascending tag order, as TIFF requires
+    let mut exif = Vec::new();
+    if let Some(seconds) = metadata.exposure_time.filter(|&t| t > 0.0) {
+        let (numerator, denominator) = exposure_rational(seconds);
+        exif.push((0x829A, TiffValue::Rational(numerator, denominator))); // ExposureTime
+    }
+    if let Some(iso) = metadata.iso {
+        exif.push((0x8827, TiffValue::Short(iso.min(65535) as u16))); // ISOSpeedRatings
+    }
+    if let Some(dioptres) = metadata.lens_position {
+        // SubjectDistance in metres; 0xFFFFFFFF is infinity
+        let distance = if dioptres > 0.0 {
+            TiffValue::Rational((1000.0 / dioptres).round() as u32, 1000)
+        } else {
+            TiffValue::Rational(u32::MAX, 1)
+        };
+        exif.push((0x9206, distance));
+    }
+
+    if ifd0.is_empty() && exif.is_empty() {
+        return None;
+    }
+    let exif_offset = 8 + ifd_len(ifd0.len() + usize::from(!exif.is_empty()));
+    if !exif.is_empty() {
+        ifd0.push((0x8769, TiffValue::Long(exif_offset as u32))); // ExifIFDPointer
+    }
+
+    let mut tiff = Vec::with_capacity(exif_offset + ifd_len(exif.len()) + 8 * exif.len());
+    tiff.extend_from_slice(b"MM\0\x2a");
+    tiff.extend_from_slice(&8u32.to_be_bytes()); // IFD0 right after the header
+    write_ifd(&mut tiff, &ifd0, exif_offset);
+    if !exif.is_empty() {
+        write_ifd(&mut tiff, &exif, exif_offset + ifd_len(exif.len()));
+    }
+    Some(tiff)
+}
+
+/// Add an Exif segment with the camera metadata to `jpeg`, after the JFIF
+/// header when there is one
+fn tag_jpeg_exif(mut jpeg: Vec<u8>, metadata: &CameraMetadata) -> Vec<u8> {
+    if !jpeg.starts_with(&[0xFF, 0xD8]) {
+        return jpeg;
+    }
+    let Some(tiff) = exif_tiff(metadata) else {
+        return jpeg;
+    };
+    let mut at = 2;
+    if jpeg.get(2..4) == Some(&[0xFF, 0xE0])
+        && let Some(len) = jpeg.get(4..6)
+    {
+        at = 4 + usize::from(u16::from_be_bytes([len[0], len[1]]));
+    }
+    let mut segment = vec![0xFF, 0xE1];
+    segment.extend_from_slice(&(2 + 6 + tiff.len() as u16).to_be_bytes());
+    segment.extend_from_slice(b"Exif\0\0");
+    segment.extend_from_slice(&tiff);
+    jpeg.splice(at.min(jpeg.len())..at.min(jpeg.len()), segment);
+    jpeg
+}
+
+/// Add an `eXIf` chunk with the camera metadata to `png`, right after IHDR
+fn tag_png_exif(mut png: Vec<u8>, metadata: &CameraMetadata) -> Vec<u8> {
+    // Signature (8 bytes), then IHDR: length, type, 13 bytes of data, CRC
+    const AFTER_IHDR: usize = 8 + 4 + 4 + 13 + 4;
+    if png.get(12..16) != Some(b"IHDR") {
+        return png;
+    }
+    let Some(tiff) = exif_tiff(metadata) else {
+        return png;
+    };
+    let mut chunk = Vec::with_capacity(12 + tiff.len());
+    chunk.extend_from_slice(&(tiff.len() as u32).to_be_bytes());
+    chunk.extend_from_slice(b"eXIf");
+    chunk.extend_from_slice(&tiff);
+    chunk.extend_from_slice(&crc32(&chunk[4..]).to_be_bytes());
+    png.splice(AFTER_IHDR..AFTER_IHDR, chunk);
+    png
+}
+
+/// CRC-32 as PNG chunks use it (ISO 3309, reflected)
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
 /// Unpack CSI-2 10-bit packed Bayer data to 16-bit little-endian values
 ///
 /// CSI2P packing: every 5 bytes contain 4 pixels.
@@ -697,4 +985,91 @@ mod tests {
         assert_eq!(EncodingQuality::High.jpeg_quality(), 92);
         assert_eq!(EncodingQuality::Maximum.jpeg_quality(), 98);
     }
+
+    fn oriented(orientation: SensorRotation) -> CameraMetadata {
+        CameraMetadata {
+            orientation,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn orientation_is_tagged_only_when_left_to_viewers() {
+        let image = RgbImage::new(4, 2);
+        let jpeg = PhotoEncoder::encode_jpeg(image.clone(), EncodingQuality::High).unwrap();
+        assert_eq!(
+            tag_jpeg_exif(jpeg.clone(), &oriented(SensorRotation::None)),
+            jpeg
+        );
+
+        let tagged = tag_jpeg_exif(jpeg.clone(), &oriented(SensorRotation::Rotate90));
+        assert_eq!(tagged.len(), jpeg.len() + 4 + 6 + 26);
+        let mut decoder = image::ImageReader::new(std::io::Cursor::new(&tagged))
+            .with_guessed_format()
+            .unwrap()
+            .into_decoder()
+            .unwrap();
+        use image::ImageDecoder;
+        assert_eq!(
+            decoder.orientation().unwrap(),
+            image::metadata::Orientation::Rotate270
+        );
+
+        let png = tag_png_exif(
+            PhotoEncoder::encode_png(image).unwrap(),
+            &oriented(SensorRotation::Rotate270),
+        );
+        let mut decoder = image::codecs::png::PngDecoder::new(std::io::Cursor::new(&png)).unwrap();
+        assert_eq!(
+            decoder.orientation().unwrap(),
+            image::metadata::Orientation::Rotate90
+        );
+    }
+
+    #[test]
+    fn photos_carry_the_srgb_profile() {
+        use image::ImageDecoder;
+
+        let image = RgbImage::new(4, 2);
+        let srgb = Some(crate::media::icc::srgb_profile());
+
+        let jpeg = PhotoEncoder::encode_jpeg(image.clone(), EncodingQuality::High).unwrap();
+        let mut decoder =
+            image::codecs::jpeg::JpegDecoder::new(std::io::Cursor::new(&jpeg)).unwrap();
+        assert_eq!(decoder.icc_profile().unwrap().as_deref(), srgb);
+
+        let png = PhotoEncoder::encode_png(image).unwrap();
+        let mut decoder = image::codecs::png::PngDecoder::new(std::io::Cursor::new(&png)).unwrap();
+        assert_eq!(decoder.icc_profile().unwrap().as_deref(), srgb);
+    }
+
+    #[test]
+    fn frame_metadata_is_written_to_an_exif_ifd() {
+        let metadata = CameraMetadata {
+            iso: Some(800),
+            ..Default::default()
+        }
+        .with_frame_metadata(&FrameMetadata {
+            exposure_time: Some(10_000),
+            analogue_gain: Some(2.0),
+            lens_position: Some(2.0),
+            ..Default::default()
+        });
+        assert_eq!(metadata.iso, Some(200));
+
+        let tiff = exif_tiff(&metadata).unwrap();
+        // IFD0 holds only the Exif IFD pointer, which follows it
+        let exif_offset = 8 + ifd_len(1);
+        assert_eq!(tiff[8..10], 1u16.to_be_bytes());
+        assert_eq!(tiff[10..12], 0x8769u16.to_be_bytes());
+        assert_eq!(tiff[18..22], (exif_offset as u32).to_be_bytes());
+        assert_eq!(tiff[exif_offset..exif_offset + 2], 3u16.to_be_bytes());
+        // ExposureTime 1/100 s and SubjectDistance 0.5 m, after the Exif IFD
+        let data = exif_offset + ifd_len(3);
+        assert_eq!(tiff.len(), data + 16);
+        assert_eq!(tiff[data..data + 8], [0, 0, 0, 1, 0, 0, 0, 100]);
+        assert_eq!(tiff[data + 8..], [0, 0, 1, 244, 0, 0, 3, 232]);
+
+        assert!(exif_tiff(&CameraMetadata::default()).is_none());
+    }
 }
@@ -24,15 +24,19 @@
 //! - **Memory Efficient**: Uses Arc for zero-copy frame passing
 //! - **Configurable**: Support for multiple output formats and quality settings
 
+pub mod booth;
 pub mod burst_mode;
 pub mod capture;
 pub mod encoding;
 pub mod processing;
+pub mod product;
+pub mod rapid_burst;
 
 pub use encoding::{CameraMetadata, EncodingFormat, EncodingQuality, PhotoEncoder, RawBayerData};
 pub use processing::{PostProcessingConfig, PostProcessor};
 
 use crate::backends::camera::types::CameraFrame;
+use crate::errors::{AppResult, PhotoError};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tracing::info;
@@ -84,14 +88,17 @@ impl PhotoPipeline {
     ///
     /// # Returns
     /// * `Ok(PathBuf)` - Path to saved photo
-    /// * `Err(String)` - Error message
+    /// * `Err(AppError)` - What failed, with disk and permission problems
+    ///   told apart for the UI
     pub async fn capture_and_save(
         &self,
         frame: Arc<CameraFrame>,
         output_dir: PathBuf,
-    ) -> Result<PathBuf, String> {
+    ) -> AppResult<PathBuf> {
+        let encoder = self.encoder.for_frame(&frame);
+
         // DNG + Bayer: bypass post-processing, encode raw sensor data directly
-        if self.encoder.format() == EncodingFormat::Dng && frame.format.is_bayer() {
+        if encoder.format() == EncodingFormat::Dng && frame.format.is_bayer() {
             info!(
                 width = frame.width,
                 height = frame.height,
@@ -108,8 +115,11 @@ impl PhotoPipeline {
                 format: frame.format,
             };
 
-            let encoded = self.encoder.encode_raw(raw).await?;
-            let output_path = self.encoder.save(encoded, output_dir).await?;
+            let encoded = encoder
+                .encode_raw(raw)
+                .await
+                .map_err(PhotoError::EncodingFailed)?;
+            let output_path = encoder.save(encoded, output_dir).await?;
             return Ok(output_path);
         }
 
@@ -117,10 +127,13 @@ impl PhotoPipeline {
         let processed = self.post_processor.process(frame).await?;
 
         // Stage 2: Encode (async, CPU-bound)
-        let encoded = self.encoder.encode(processed).await?;
+        let encoded = encoder
+            .encode(processed)
+            .await
+            .map_err(PhotoError::EncodingFailed)?;
 
         // Stage 3: Save to disk (async, I/O-bound)
-        let output_path = self.encoder.save(encoded, output_dir).await?;
+        let output_path = encoder.save(encoded, output_dir).await?;
 
         Ok(output_path)
     }
@@ -138,14 +151,15 @@ impl PhotoPipeline {
         frame: Arc<CameraFrame>,
         output_dir: PathBuf,
         mut progress: F,
-    ) -> Result<PathBuf, String>
+    ) -> AppResult<PathBuf>
     where
         F: FnMut(f32) + Send,
     {
         progress(0.0);
+        let encoder = self.encoder.for_frame(&frame);
 
         // DNG + Bayer: bypass post-processing
-        if self.encoder.format() == EncodingFormat::Dng && frame.format.is_bayer() {
+        if encoder.format() == EncodingFormat::Dng && frame.format.is_bayer() {
             let raw = RawBayerData {
                 data: frame.data.to_vec(),
                 width: frame.width,
@@ -155,10 +169,13 @@ impl PhotoPipeline {
             };
             progress(0.33);
 
-            let encoded = self.encoder.encode_raw(raw).await?;
+            let encoded = encoder
+                .encode_raw(raw)
+                .await
+                .map_err(PhotoError::EncodingFailed)?;
             progress(0.66);
 
-            let output_path = self.encoder.save(encoded, output_dir).await?;
+            let output_path = encoder.save(encoded, output_dir).await?;
             progress(1.0);
             return Ok(output_path);
         }
@@ -168,11 +185,14 @@ impl PhotoPipeline {
         progress(0.33);
 
         // Encode
-        let encoded = self.encoder.encode(processed).await?;
+        let encoded = encoder
+            .encode(processed)
+            .await
+            .map_err(PhotoError::EncodingFailed)?;
         progress(0.66);
 
         // Save
-        let output_path = self.encoder.save(encoded, output_dir).await?;
+        let output_path = encoder.save(encoded, output_dir).await?;
         progress(1.0);
 
         Ok(output_path)
@@ -5,7 +5,7 @@
 //! This module handles post-processing operations on captured frames:
 //! - Filter application directly on RGBA data (GPU-accelerated)
 //! - RGBA to RGB conversion (drop alpha channel)
-//! - Sharpening
+//! - Output sharpening (GPU unsharp mask, or the optional CPU kernel)
 //! - Brightness/contrast adjustments
 //!
 //! The pipeline is optimized to apply filters on RGBA data before RGB conversion,
@@ -13,7 +13,11 @@
 
 use crate::app::FilterType;
 use crate::backends::camera::types::{CameraFrame, PixelFormat, SensorRotation};
-use crate::shaders::{GpuFrameInput, apply_filter_gpu_rgba, get_gpu_convert_pipeline};
+use crate::config::OutputSharpening;
+use crate::shaders::{
+    GpuFrameInput, ProcessingQuality, UnsharpMask, apply_filter_gpu_rgba, get_gpu_convert_pipeline,
+    needs_filter_pass, sharpen_rgba_gpu,
+};
 use image::RgbImage;
 use std::sync::Arc;
 use tracing::{debug, info, warn};
@@ -41,6 +45,10 @@ pub struct PostProcessingConfig {
     pub rotation: SensorRotation,
     /// Mirror the image horizontally (selfie capture, mirroring the preview).
     pub mirror_horizontal: bool,
+    /// Demosaic and finishing quality of the RGBA conversion
+    pub quality: ProcessingQuality,
+    /// Unsharp mask applied to the upright photo
+    pub output_sharpening: OutputSharpening,
 }
 
 impl Default for PostProcessingConfig {
@@ -56,6 +64,8 @@ impl Default for PostProcessingConfig {
             zoom_level: 1.0,
             rotation: SensorRotation::None,
             mirror_horizontal: false,
+            quality: ProcessingQuality::Still,
+            output_sharpening: OutputSharpening::default(),
         }
     }
 }
@@ -103,7 +113,7 @@ impl PostProcessor {
 
         // Step 0: Convert to RGBA (with optional integrated filter for Bayer)
         let filtered_rgba: Vec<u8> = if frame.format.is_bayer()
-            && config.filter_type != FilterType::Standard
+            && needs_filter_pass(config.filter_type)
         {
             // Bayer + filter: use integrated debayer+filter pipeline (no GPU round trip)
             debug!(
@@ -111,15 +121,17 @@ impl PostProcessor {
                 filter = ?config.filter_type,
                 "Converting Bayer + filter in single GPU submission"
             );
-            match Self::convert_bayer_and_filter(&frame, config.filter_type).await {
+            match Self::convert_bayer_and_filter(&frame, config.filter_type, config.quality).await {
                 Ok(rgba) => rgba,
                 Err(e) => {
                     warn!(error = %e, "Integrated debayer+filter failed, falling back");
                     // Fallback: separate debayer then filter (both optional enhancements)
-                    let rgba = Self::convert_yuv_to_rgba(&frame).await.unwrap_or_else(|e| {
-                        warn!(error = %e, "Bayer→RGBA fallback also failed, using raw data");
-                        frame.data.to_vec()
-                    });
+                    let rgba = Self::convert_yuv_to_rgba(&frame, config.quality)
+                        .await
+                        .unwrap_or_else(|e| {
+                            warn!(error = %e, "Bayer→RGBA fallback also failed, using raw data");
+                            frame.data.to_vec()
+                        });
                     apply_filter_gpu_rgba(&rgba, frame_width, frame_height, config.filter_type)
                         .await
                         .unwrap_or(rgba)
@@ -127,11 +139,11 @@ impl PostProcessor {
             }
         } else if frame.format.is_yuv() || frame.format.is_bayer() {
             debug!(format = ?frame.format, "Converting frame to RGBA for photo processing");
-            let rgba = Self::convert_yuv_to_rgba(&frame)
+            let rgba = Self::convert_yuv_to_rgba(&frame, config.quality)
                 .await
                 .map_err(|e| format!("Failed to convert to RGBA: {}", e))?;
-            // Apply filter for non-Bayer formats (YUV)
-            if config.filter_type != FilterType::Standard {
+            // Apply filter (and software white balance) for non-Bayer formats (YUV)
+            if needs_filter_pass(config.filter_type) {
                 match apply_filter_gpu_rgba(&rgba, frame_width, frame_height, config.filter_type)
                     .await
                 {
@@ -147,7 +159,7 @@ impl PostProcessor {
             } else {
                 rgba
             }
-        } else if config.filter_type != FilterType::Standard {
+        } else if needs_filter_pass(config.filter_type) {
             // Already RGBA, just apply filter
             let rgba = frame.data.to_vec();
             match apply_filter_gpu_rgba(&rgba, frame_width, frame_height, config.filter_type).await
@@ -186,20 +198,67 @@ impl PostProcessor {
             (cropped_rgba, current_width, current_height)
         };
 
+        // Step 3.5: Rotation correction and mirroring in one GPU pass, the
+        // same one recordings and the virtual camera use. Steps 4.5 / 4.6
+        // redo them on the CPU if the GPU is unavailable.
+        let needs_orienting = config.rotation != SensorRotation::None || config.mirror_horizontal;
+        let (final_rgba, final_width, final_height, oriented) = if needs_orienting {
+            debug!(
+                rotation = ?config.rotation,
+                mirror = config.mirror_horizontal,
+                "Applying rotation correction on GPU"
+            );
+            match crate::shaders::rotate_rgba_gpu(
+                &final_rgba,
+                final_width,
+                final_height,
+                config.rotation,
+                config.mirror_horizontal,
+            )
+            .await
+            {
+                Ok((rotated, w, h)) => (rotated, w, h, true),
+                Err(e) => {
+                    warn!(error = %e, "GPU rotation failed, rotating on CPU");
+                    (final_rgba, final_width, final_height, false)
+                }
+            }
+        } else {
+            (final_rgba, final_width, final_height, true)
+        };
+
+        // Step 3.6: Output sharpening on the upright photo. Skipped (not
+        // redone on the CPU) if the GPU is unavailable.
+        let final_rgba =
+            match UnsharpMask::for_photo(config.output_sharpening, final_width, final_height) {
+                Some(mask) => {
+                    debug!(?mask, "Applying output sharpening on GPU");
+                    match sharpen_rgba_gpu(&final_rgba, final_width, final_height, mask).await {
+                        Ok(sharpened) => sharpened,
+                        Err(e) => {
+                            warn!(error = %e, "GPU sharpening failed, saving unsharpened");
+                            final_rgba
+                        }
+                    }
+                }
+                None => final_rgba,
+            };
+
         // Step 4: Convert filtered RGBA to RGB (drop alpha channel)
         let rgb_image = Self::convert_rgba_to_rgb(&final_rgba, final_width, final_height)?;
 
         // Step 4.5: Apply rotation correction if needed
-        let (rgb_image, final_width, final_height) = if config.rotation != SensorRotation::None {
-            debug!(rotation = ?config.rotation, "Applying rotation correction");
-            Self::apply_rotation(rgb_image, config.rotation)?
-        } else {
-            (rgb_image, final_width, final_height)
-        };
+        let (rgb_image, final_width, final_height) =
+            if !oriented && config.rotation != SensorRotation::None {
+                debug!(rotation = ?config.rotation, "Applying rotation correction");
+                Self::apply_rotation(rgb_image, config.rotation)?
+            } else {
+                (rgb_image, final_width, final_height)
+            };
 
         // Step 4.6: Mirror horizontally if requested (front-camera selfie mode).
         // Done after rotation so the final orientation is upright before flipping.
-        let rgb_image = if config.mirror_horizontal {
+        let rgb_image = if !oriented && config.mirror_horizontal {
             debug!("Mirroring captured image horizontally");
             let mut img = rgb_image;
             image::imageops::flip_horizontal_in_place(&mut img);
@@ -249,6 +308,7 @@ impl PostProcessor {
     async fn convert_bayer_and_filter(
         frame: &CameraFrame,
         filter: FilterType,
+        quality: ProcessingQuality,
     ) -> Result<Vec<u8>, String> {
         let buffer_data = frame.data.as_ref();
 
@@ -272,6 +332,7 @@ impl PostProcessor {
             colour_gains,
             colour_correction_matrix: ccm,
             black_level,
+            quality,
         };
 
         let mut pipeline_guard = get_gpu_convert_pipeline()
@@ -293,14 +354,20 @@ impl PostProcessor {
 
     /// Convert YUV frame to RGBA using GPU compute shader
     ///
-    /// Uses the same compute shader as the preview pipeline for consistency.
-    async fn convert_yuv_to_rgba(frame: &CameraFrame) -> Result<Vec<u8>, String> {
+    /// Uses the same compute shader as the preview pipeline for consistency,
+    /// plus the still-only demosaic and finishing pass at
+    /// [`ProcessingQuality::Still`].
+    async fn convert_yuv_to_rgba(
+        frame: &CameraFrame,
+        quality: ProcessingQuality,
+    ) -> Result<Vec<u8>, String> {
         // RGBA doesn't need conversion
         if frame.format == PixelFormat::RGBA {
             return Ok(frame.data.as_ref().to_vec());
         }
 
-        let input = GpuFrameInput::from_camera_frame(frame)?;
+        let mut input = GpuFrameInput::from_camera_frame(frame)?;
+        input.quality = quality;
 
         // Use GPU compute shader pipeline for conversion
         let mut pipeline_guard = get_gpu_convert_pipeline()
@@ -493,8 +560,8 @@ impl PostProcessor {
 
     /// Apply rotation correction to an RGB image
     ///
-    /// Uses the image crate's rotation methods for efficient CPU rotation.
-    /// Rotation is applied at the end of post-processing to correct sensor orientation.
+    /// CPU fallback for the GPU rotation pass, using the image crate's
+    /// rotation methods.
     fn apply_rotation(
         image: RgbImage,
         rotation: SensorRotation,
@@ -525,6 +592,8 @@ mod tests {
         let config = PostProcessingConfig::default();
         assert!(config.color_correction);
         assert!(!config.sharpening);
+        assert_eq!(config.quality, ProcessingQuality::Still);
+        assert_eq!(config.output_sharpening, OutputSharpening::default());
         assert_eq!(config.brightness, 0.0);
         assert_eq!(config.contrast, 1.0);
         assert_eq!(config.saturation, 1.0);
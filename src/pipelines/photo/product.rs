@@ -0,0 +1,317 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Product photo pipeline
+//!
+//! Produces catalogue-style shots of an object on a white background:
+//!
+//! 1. **Average**: several frames are merged by the burst pipeline
+//!    ([`merge_rgba_frames`]) to suppress sensor noise
+//! 2. **White balance**: channel gains are derived from the frame border so
+//!    the background maps to pure white
+//! 3. **Segment**: pixels that are close to white after balancing are treated
+//!    as background, giving a soft alpha mask
+//! 4. **Crop**: the image is cropped to the foreground bounding box plus a
+//!    margin, optionally with a transparent background (PNG)
+//!
+//! The result is encoded by [`PhotoEncoder`] like any other photo, in the
+//! chosen format and with the camera's EXIF.
+
+use super::burst_mode::{BurstModeConfig, merge_rgba_frames};
+use super::processing::ProcessedImage;
+use super::{
+    CameraMetadata, EncodingFormat, EncodingQuality, PhotoEncoder, PostProcessingConfig,
+    PostProcessor,
+};
+use crate::backends::camera::types::{CameraFrame, FrameData, PixelFormat, SensorRotation};
+use crate::errors::{AppResult, PhotoError};
+use image::{RgbImage, RgbaImage};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info};
+
+/// Number of frames averaged for a product shot
+pub const PRODUCT_FRAME_COUNT: usize = 6;
+
+/// Fraction of the image (per side) sampled as background reference
+const BORDER_FRACTION: f32 = 0.05;
+/// Upper bound on per-channel white-balance gain
+const MAX_GAIN: f32 = 4.0;
+/// Minimum fraction of foreground pixels for a crop to be applied
+const MIN_FOREGROUND_FRACTION: f32 = 0.001;
+
+/// Product photo configuration
+#[derive(Debug, Clone)]
+pub struct ProductShotConfig {
+    /// Export PNG with a transparent background instead of pure white
+    pub transparent_background: bool,
+    /// Distance from white (0-255) below which a pixel is background
+    pub background_tolerance: u8,
+    /// Margin around the detected object, as a fraction of its size
+    pub margin: f32,
+    /// Sensor rotation to correct the image orientation
+    pub rotation: SensorRotation,
+    /// Mirror the image horizontally
+    pub mirror_horizontal: bool,
+    /// Format of an opaque shot; transparent ones are always PNG
+    pub encoding_format: EncodingFormat,
+    /// Written to the photo's EXIF
+    pub camera_metadata: CameraMetadata,
+}
+
+impl Default for ProductShotConfig {
+    fn default() -> Self {
+        Self {
+            transparent_background: false,
+            background_tolerance: 24,
+            margin: 0.1,
+            rotation: SensorRotation::None,
+            mirror_horizontal: false,
+            encoding_format: EncodingFormat::Jpeg,
+            camera_metadata: CameraMetadata::default(),
+        }
+    }
+}
+
+/// Merge frames, white-balance to the background, crop and save
+///
+/// Returns the path of the saved photo (PNG when the background is
+/// transparent, the configured format otherwise).
+pub async fn process_product_shot(
+    frames: Vec<Arc<CameraFrame>>,
+    config: ProductShotConfig,
+    output_dir: PathBuf,
+) -> AppResult<PathBuf> {
+    let Some(first) = frames.first() else {
+        return Err(PhotoError::NoFrameAvailable.into());
+    };
+
+    info!(frames = frames.len(), "Processing product shot");
+
+    let mut encoder = PhotoEncoder::new();
+    encoder.set_format(config.encoding_format);
+    encoder.set_quality(EncodingQuality::High);
+    encoder.set_camera_metadata(config.camera_metadata.clone());
+    let encoder = encoder.for_frame(first);
+
+    let merged = merge_rgba_frames(
+        &frames,
+        &BurstModeConfig::default(),
+        &CancellationToken::new(),
+    )
+    .await?;
+    let merged = Arc::new(CameraFrame {
+        width: merged.width,
+        height: merged.height,
+        data: FrameData::Copied(merged.data.into()),
+        format: PixelFormat::RGBA,
+        stride: merged.width * 4,
+        yuv_planes: None,
+        captured_at: first.captured_at,
+        sensor_timestamp_ns: first.sensor_timestamp_ns,
+        libcamera_metadata: first.libcamera_metadata.clone(),
+    });
+    drop(frames);
+
+    let post_processor = PostProcessor::new(PostProcessingConfig {
+        rotation: config.rotation,
+        mirror_horizontal: config.mirror_horizontal,
+        ..Default::default()
+    });
+    let mut image = post_processor.process(merged).await?.image;
+
+    let transparent = config.transparent_background;
+    let output = tokio::task::spawn_blocking(move || {
+        let gains = background_gains(&image);
+        debug!(?gains, "Product shot background gains");
+        apply_gains(&mut image, gains);
+
+        let mask = foreground_mask(&image, config.background_tolerance);
+        compose_output(&image, &mask, &config)
+    })
+    .await
+    .map_err(|e| format!("Product shot task error: {}", e))?;
+
+    let encoded = if transparent {
+        encoder.encode_transparent(output).await
+    } else {
+        let image = image::DynamicImage::ImageRgba8(output).to_rgb8();
+        let (width, height) = image.dimensions();
+        encoder
+            .encode(ProcessedImage {
+                image,
+                width,
+                height,
+            })
+            .await
+    }
+    .map_err(PhotoError::EncodingFailed)?;
+
+    let path = encoder.save(encoded, output_dir).await?;
+    info!(path = %path.display(), "Product shot saved");
+    Ok(path)
+}
+
+/// Channel gains that map the mean border colour to white
+fn background_gains(image: &RgbImage) -> [f32; 3] {
+    let (width, height) = image.dimensions();
+    let bx = ((width as f32 * BORDER_FRACTION) as u32).max(1);
+    let by = ((height as f32 * BORDER_FRACTION) as u32).max(1);
+
+    let mut sum = [0u64; 3];
+    let mut count = 0u64;
+    for (x, y, pixel) in image.enumerate_pixels() {
+        if x < bx || x >= width.saturating_sub(bx) || y < by || y >= height.saturating_sub(by) {
+            for c in 0..3 {
+                sum[c] += pixel[c] as u64;
+            }
+            count += 1;
+        }
+    }
+
+    let mut gains = [1.0; 3];
+    if count > 0 {
+        for c in 0..3 {
+            let mean = sum[c] as f32 / count as f32;
+            gains[c] = (255.0 / mean.max(1.0)).clamp(1.0, MAX_GAIN);
+        }
+    }
+    gains
+}
+
+fn apply_gains(image: &mut RgbImage, gains: [f32; 3]) {
+    for pixel in image.pixels_mut() {
+        for c in 0..3 {
+            pixel[c] = (pixel[c] as f32 * gains[c]).round().min(255.0) as u8;
+        }
+    }
+}
+
+/// Soft foreground mask (0 = background, 255 = object)
+///
+/// Pixels within `tolerance` of white are background; alpha ramps up over
+/// the next `tolerance` levels to avoid hard edges.
+fn foreground_mask(image: &RgbImage, tolerance: u8) -> Vec<u8> {
+    let tolerance = tolerance.max(1) as u32;
+    image
+        .pixels()
+        .map(|p| {
+            let distance = 255 - p[0].min(p[1]).min(p[2]) as u32;
+            if distance <= tolerance {
+                0
+            } else {
+                ((distance - tolerance) * 255 / tolerance).min(255) as u8
+            }
+        })
+        .collect()
+}
+
+/// Bounding box `(x, y, w, h)` of the opaque part of the mask, with margin
+fn foreground_bounds(
+    mask: &[u8],
+    width: u32,
+    height: u32,
+    margin: f32,
+) -> Option<(u32, u32, u32, u32)> {
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (u32::MAX, u32::MAX, 0, 0);
+    let mut count = 0usize;
+
+    for (i, &alpha) in mask.iter().enumerate() {
+        if alpha >= 128 {
+            let x = i as u32 % width;
+            let y = i as u32 / width;
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+            count += 1;
+        }
+    }
+
+    if (count as f32) < mask.len() as f32 * MIN_FOREGROUND_FRACTION {
+        return None;
+    }
+
+    let mx = ((max_x - min_x + 1) as f32 * margin) as u32;
+    let my = ((max_y - min_y + 1) as f32 * margin) as u32;
+    let x0 = min_x.saturating_sub(mx);
+    let y0 = min_y.saturating_sub(my);
+    let x1 = (max_x + mx).min(width - 1);
+    let y1 = (max_y + my).min(height - 1);
+    Some((x0, y0, x1 - x0 + 1, y1 - y0 + 1))
+}
+
+/// Crop to the object and flatten the background to white or transparent
+fn compose_output(image: &RgbImage, mask: &[u8], config: &ProductShotConfig) -> RgbaImage {
+    let (width, height) = image.dimensions();
+    let (x0, y0, w, h) =
+        foreground_bounds(mask, width, height, config.margin).unwrap_or((0, 0, width, height));
+
+    RgbaImage::from_fn(w, h, |x, y| {
+        let (sx, sy) = (x0 + x, y0 + y);
+        let p = image.get_pixel(sx, sy);
+        let alpha = mask[(sy * width + sx) as usize];
+        if config.transparent_background {
+            image::Rgba([p[0], p[1], p[2], alpha])
+        } else {
+            // Blend towards white so the background is uniformly pure white
+            let blend = |v: u8| (255 - ((255 - v as u32) * alpha as u32 / 255)) as u8;
+            image::Rgba([blend(p[0]), blend(p[1]), blend(p[2]), 255])
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Grey-white background with a dark square in the middle
+    fn test_scene() -> RgbImage {
+        RgbImage::from_fn(100, 100, |x, y| {
+            if (40..60).contains(&x) && (30..70).contains(&y) {
+                image::Rgb([40, 30, 20])
+            } else {
+                image::Rgb([200, 220, 240])
+            }
+        })
+    }
+
+    #[test]
+    fn background_is_balanced_to_white() {
+        let mut image = test_scene();
+        let gains = background_gains(&image);
+        apply_gains(&mut image, gains);
+        let corner = image.get_pixel(0, 0);
+        assert!(corner.0.iter().all(|&v| v >= 254));
+    }
+
+    #[test]
+    fn crop_covers_object_with_margin() {
+        let mut image = test_scene();
+        apply_gains(&mut image, background_gains(&image));
+        let mask = foreground_mask(&image, 24);
+        let (x, y, w, h) = foreground_bounds(&mask, 100, 100, 0.1).unwrap();
+        assert_eq!((x, y, w, h), (38, 26, 24, 48));
+    }
+
+    #[test]
+    fn empty_scene_is_not_cropped() {
+        let image = RgbImage::from_pixel(50, 50, image::Rgb([255, 255, 255]));
+        let mask = foreground_mask(&image, 24);
+        assert_eq!(foreground_bounds(&mask, 50, 50, 0.1), None);
+    }
+
+    #[test]
+    fn transparent_output_clears_background_alpha() {
+        let mut image = test_scene();
+        apply_gains(&mut image, background_gains(&image));
+        let mask = foreground_mask(&image, 24);
+        let config = ProductShotConfig {
+            transparent_background: true,
+            ..Default::default()
+        };
+        let out = compose_output(&image, &mask, &config);
+        assert_eq!(out.get_pixel(0, 0)[3], 0);
+        assert_eq!(out.get_pixel(out.width() / 2, out.height() / 2)[3], 255);
+    }
+}
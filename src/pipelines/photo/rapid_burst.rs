@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Rapid burst: rapid-fire stills with best-shot selection
+//!
+//! Unlike the HDR+ burst, which merges frames into one photo, a rapid burst
+//! keeps every frame taken while the shutter is held as its own photo at
+//! the capture format's resolution. Each is scored with the HDR+ pipeline's
+//! GPU sharpness metric so the app can offer the sharpest one first and
+//! let the user discard the rest.
+
+use super::processing::ProcessedImage;
+use super::{
+    CameraMetadata, EncodingFormat, EncodingQuality, PhotoEncoder, PostProcessingConfig,
+    PostProcessor,
+};
+use crate::app::FilterType;
+use crate::backends::camera::types::{CameraFrame, SensorRotation};
+use crate::config::OutputSharpening;
+use image::imageops::{self, FilterType as ResizeFilter};
+use image::{DynamicImage, RgbaImage};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Maximum shots per burst offered in settings
+pub const MAX_SHOTS: [u8; 3] = [10, 20, 30];
+
+/// Time between shots while the shutter is held
+pub const SHOT_INTERVAL_MS: u64 = 100;
+
+/// Width of the picker thumbnails
+const THUMBNAIL_WIDTH: u32 = 160;
+
+/// Rapid burst save settings
+#[derive(Debug, Clone)]
+pub struct RapidBurstConfig {
+    pub filter_type: FilterType,
+    /// Sensor rotation to correct each shot's orientation
+    pub rotation: SensorRotation,
+    /// Mirror the shots horizontally
+    pub mirror_horizontal: bool,
+    pub encoding_format: EncodingFormat,
+    pub camera_metadata: CameraMetadata,
+    /// Unsharp mask applied to each shot
+    pub output_sharpening: OutputSharpening,
+}
+
+/// One saved burst photo
+#[derive(Debug, Clone)]
+pub struct BurstShot {
+    pub path: PathBuf,
+    /// GPU sharpness score; higher is sharper. 0 when it couldn't be measured.
+    pub sharpness: f32,
+    /// Small upright preview for the picker
+    pub thumbnail: RgbaImage,
+}
+
+/// Save every frame as its own photo and score its sharpness
+///
+/// Shots are returned in capture order.
+pub async fn save_rapid_burst(
+    frames: Vec<Arc<CameraFrame>>,
+    config: RapidBurstConfig,
+    output_dir: PathBuf,
+) -> Result<Vec<BurstShot>, String> {
+    if frames.is_empty() {
+        return Err("No frames captured for the burst".to_string());
+    }
+    info!(shots = frames.len(), "Saving rapid burst");
+
+    // Sharpness is a nice-to-have: without a GPU the shots are still saved
+    let scorer = match super::burst_mode::BurstModeGpuPipeline::shared().await {
+        Ok(pipeline) => Some(pipeline),
+        Err(e) => {
+            warn!(error = %e, "Sharpness scoring unavailable, keeping capture order");
+            None
+        }
+    };
+
+    let post_processor = PostProcessor::new(PostProcessingConfig {
+        filter_type: config.filter_type,
+        rotation: config.rotation,
+        mirror_horizontal: config.mirror_horizontal,
+        output_sharpening: config.output_sharpening,
+        ..Default::default()
+    });
+    let mut encoder = PhotoEncoder::new();
+    encoder.set_format(config.encoding_format);
+    encoder.set_quality(EncodingQuality::High);
+    encoder.set_camera_metadata(config.camera_metadata);
+
+    let mut shots = Vec::with_capacity(frames.len());
+    for frame in frames {
+        let sharpness = match &scorer {
+            Some(scorer) => scorer.compute_sharpness(&frame).await.unwrap_or_else(|e| {
+                warn!(error = %e, "Sharpness scoring failed for a burst shot");
+                0.0
+            }),
+            None => 0.0,
+        };
+
+        let processed = post_processor.process(frame).await?;
+        let thumbnail = thumbnail(&processed);
+        let encoded = encoder.encode(processed).await?;
+        let path = encoder.save(encoded, output_dir.clone()).await?;
+        shots.push(BurstShot {
+            path,
+            sharpness,
+            thumbnail,
+        });
+    }
+
+    info!(shots = shots.len(), "Rapid burst saved");
+    Ok(shots)
+}
+
+/// Shot indices from sharpest to blurriest. Ties keep capture order.
+pub fn rank_by_sharpness(sharpness: &[f32]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..sharpness.len()).collect();
+    order.sort_by(|&a, &b| sharpness[b].total_cmp(&sharpness[a]));
+    order
+}
+
+fn thumbnail(processed: &ProcessedImage) -> RgbaImage {
+    let height = (u64::from(processed.height) * u64::from(THUMBNAIL_WIDTH)
+        / u64::from(processed.width.max(1))) as u32;
+    let small = imageops::resize(
+        &processed.image,
+        THUMBNAIL_WIDTH,
+        height.max(1),
+        ResizeFilter::Triangle,
+    );
+    DynamicImage::ImageRgb8(small).into_rgba8()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sharpest_shot_comes_first() {
+        assert_eq!(rank_by_sharpness(&[0.2, 0.9, 0.5]), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn unscored_shots_keep_capture_order() {
+        assert_eq!(rank_by_sharpness(&[0.0, 0.0, 0.0]), vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Animated clip capture
+//!
+//! Receives preview frames via a channel (sampled at the clip frame rate by
+//! the app), converts each to RGBA on the GPU, scales it down to
+//! [`MAX_WIDTH`] and applies the capture orientation. When the channel
+//! closes the collected frames are written as GIF or WebP by
+//! [`crate::media::animation`].
+
+use super::timelapse::convert_and_filter;
+use crate::backends::camera::types::{CameraFrame, SensorRotation};
+use crate::media::animation::{self, AnimationFormat, AnimationOptions, MAX_WIDTH};
+use image::RgbaImage;
+use image::imageops::{self, FilterType};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU32;
+use tracing::{info, warn};
+
+/// Collect frames from `frame_rx` until it closes, then export them.
+///
+/// `output_base` gets the format's extension. This function is intended to
+/// be spawned as an async task.
+pub async fn run_clip_capture(
+    mut frame_rx: tokio::sync::mpsc::UnboundedReceiver<Arc<CameraFrame>>,
+    output_base: PathBuf,
+    format: AnimationFormat,
+    options: AnimationOptions,
+    live_filter_code: Arc<AtomicU32>,
+    rotation: SensorRotation,
+    mirror_horizontal: bool,
+) -> Result<String, String> {
+    let mut frames = Vec::new();
+    let mut source_size = None;
+
+    while let Some(frame) = frame_rx.recv().await {
+        let size = *source_size.get_or_insert((frame.width, frame.height));
+        if (frame.width, frame.height) != size {
+            warn!(
+                width = frame.width,
+                height = frame.height,
+                "Skipping clip frame with mismatched dimensions"
+            );
+            continue;
+        }
+        let rgba = match convert_and_filter(&frame, &live_filter_code).await {
+            Ok(rgba) => rgba,
+            Err(e) => {
+                warn!(error = %e, "Skipping clip frame (conversion failed)");
+                continue;
+            }
+        };
+        let Some(image) = RgbaImage::from_raw(frame.width, frame.height, rgba) else {
+            continue;
+        };
+        frames.push(prepare_frame(image, rotation, mirror_horizontal));
+    }
+
+    info!(
+        frames = frames.len(),
+        ?format,
+        "Clip capture finished, exporting"
+    );
+    let path = tokio::task::spawn_blocking(move || {
+        animation::export(format, frames, options, &output_base)
+    })
+    .await
+    .map_err(|e| format!("Clip export task failed: {e}"))??;
+    Ok(path.display().to_string())
+}
+
+/// Scale a frame down to [`MAX_WIDTH`] and turn it upright
+fn prepare_frame(image: RgbaImage, rotation: SensorRotation, mirror: bool) -> RgbaImage {
+    let (width, height) = image.dimensions();
+    let image = if width > MAX_WIDTH {
+        let scaled_height = (u64::from(height) * u64::from(MAX_WIDTH) / u64::from(width)) as u32;
+        imageops::resize(
+            &image,
+            MAX_WIDTH,
+            scaled_height.max(1),
+            FilterType::Triangle,
+        )
+    } else {
+        image
+    };
+    // Same directions as the timelapse videoflip: 90 is counter-clockwise
+    let image = match rotation {
+        SensorRotation::None => image,
+        SensorRotation::Rotate90 => imageops::rotate270(&image),
+        SensorRotation::Rotate180 => imageops::rotate180(&image),
+        SensorRotation::Rotate270 => imageops::rotate90(&image),
+    };
+    if mirror {
+        imageops::flip_horizontal(&image)
+    } else {
+        image
+    }
+}
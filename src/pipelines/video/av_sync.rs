@@ -0,0 +1,191 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Audio/video sync measurement
+//!
+//! The sync test records a short clip through the selected camera and
+//! microphone while the window flashes white and a beep plays at the same
+//! moment. The camera has to see the screen and the microphone hear the
+//! speakers. Decoding the clip afterwards shows when the flash and the beep
+//! landed on the recording's own timeline. The gap between the two is how
+//! far the microphone is off from the camera, so it is the correction for
+//! `Config::audio_sync_offsets`.
+//!
+//! The display and the speakers each add their own output latency. That
+//! gap, usually a frame or two, ends up in the result too.
+
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use std::path::Path;
+use tracing::info;
+
+/// Side of the grey thumbnail each video frame is scaled down to
+const THUMBNAIL_SIZE: u32 = 16;
+
+/// Audio samples per envelope step: 2 ms at 48 kHz
+const ENVELOPE_WINDOW: usize = 96;
+
+/// Sample rate the audio is decoded at
+const SAMPLE_RATE: u64 = 48_000;
+
+/// Smallest rise in mean luma (0-1) that counts as the flash
+const MIN_FLASH_RISE: f32 = 0.1;
+
+/// Smallest rise in audio RMS (full scale 1.0) that counts as the beep
+const MIN_BEEP_RISE: f32 = 0.02;
+
+/// Measure a sync test clip. Returns the correction in milliseconds to add
+/// to the microphone's sync offset: positive when the audio came early and
+/// has to be delayed. Blocking.
+pub fn measure_offset(path: &Path) -> Result<i32, String> {
+    let (luma, envelope) = decode(path)?;
+    let flash = onset(&luma, MIN_FLASH_RISE)
+        .ok_or_else(|| "The flash was not seen in the recording".to_string())?;
+    let beep = onset(&envelope, MIN_BEEP_RISE)
+        .ok_or_else(|| "The beep was not heard in the recording".to_string())?;
+    let correction_ms = (flash as i64 - beep as i64) / 1_000_000;
+    info!(
+        flash_ns = flash,
+        beep_ns = beep,
+        correction_ms,
+        "Measured A/V sync"
+    );
+    Ok(correction_ms as i32)
+}
+
+/// Values against presentation time in nanoseconds
+type Series = Vec<(u64, f32)>;
+
+/// Time of the first value at least halfway between the lowest and the
+/// highest of `series`, if they are at least `min_rise` apart
+fn onset(series: &[(u64, f32)], min_rise: f32) -> Option<u64> {
+    let low = series.iter().map(|&(_, v)| v).fold(f32::MAX, f32::min);
+    let high = series.iter().map(|&(_, v)| v).fold(f32::MIN, f32::max);
+    if high - low < min_rise {
+        return None;
+    }
+    let threshold = (low + high) / 2.0;
+    series
+        .iter()
+        .find(|&&(_, v)| v >= threshold)
+        .map(|&(time, _)| time)
+}
+
+/// Decode a clip into the mean luma of each video frame and the RMS
+/// envelope of its audio, both against presentation time in nanoseconds
+fn decode(path: &Path) -> Result<(Series, Series), String> {
+    gst::init().map_err(|e| format!("GStreamer init: {e}"))?;
+
+    let pipeline = gst::parse::launch(&format!(
+        "filesrc name=src ! decodebin name=dec \
+         dec. ! queue ! videoconvert ! videoscale \
+         ! video/x-raw,format=GRAY8,width={THUMBNAIL_SIZE},height={THUMBNAIL_SIZE} \
+         ! appsink name=video sync=false \
+         dec. ! queue ! audioconvert ! audioresample \
+         ! audio/x-raw,format=F32LE,channels=1,rate={SAMPLE_RATE} \
+         ! appsink name=audio sync=false"
+    ))
+    .map_err(|e| format!("Failed to parse sync test pipeline: {e}"))?
+    .downcast::<gst::Pipeline>()
+    .map_err(|_| "Sync test pipeline is not a pipeline".to_string())?;
+    let sink = |name: &str| {
+        pipeline
+            .by_name(name)
+            .and_then(|sink| sink.downcast::<gstreamer_app::AppSink>().ok())
+            .ok_or_else(|| format!("Sync test pipeline has no {name} sink"))
+    };
+    let (video_sink, audio_sink) = (sink("video")?, sink("audio")?);
+    pipeline
+        .by_name("src")
+        .ok_or_else(|| "Sync test pipeline has no source".to_string())?
+        .set_property("location", path.to_string_lossy().as_ref());
+
+    pipeline
+        .set_state(gst::State::Playing)
+        .map_err(|e| format!("Failed to decode {}: {e}", path.display()))?;
+    let bus = pipeline
+        .bus()
+        .ok_or_else(|| "Sync test pipeline has no bus".to_string())?;
+
+    let mut luma = Series::new();
+    let mut envelope = Series::new();
+    let mut result = Ok(());
+    // Both sinks queue without limit, so draining them in turn can't stall
+    // either branch. A clip without sound never brings the audio sink to
+    // EOS; its video ending is as far as there is to decode.
+    while !(video_sink.is_eos() && (audio_sink.is_eos() || envelope.is_empty())) {
+        if let Some(msg) = bus.pop_filtered(&[gst::MessageType::Error]) {
+            if let gst::MessageView::Error(err) = msg.view() {
+                result = Err(format!(
+                    "Failed to decode {}: {}",
+                    path.display(),
+                    err.error()
+                ));
+            }
+            break;
+        }
+        if let Some(sample) = video_sink.try_pull_sample(gst::ClockTime::from_mseconds(10))
+            && let Some(buffer) = sample.buffer()
+            && let Ok(map) = buffer.map_readable()
+        {
+            let mean = map.iter().map(|&v| u32::from(v)).sum::<u32>() as f32
+                / map.len().max(1) as f32
+                / 255.0;
+            luma.push((buffer.pts().map(|t| t.nseconds()).unwrap_or(0), mean));
+        }
+        if let Some(sample) = audio_sink.try_pull_sample(gst::ClockTime::from_mseconds(10))
+            && let Some(buffer) = sample.buffer()
+            && let Ok(map) = buffer.map_readable()
+        {
+            let start = buffer.pts().map(|t| t.nseconds()).unwrap_or(0);
+            let samples: Vec<f32> = map
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect();
+            envelope.extend(rms_envelope(&samples, start));
+        }
+    }
+    pipeline.set_state(gst::State::Null).ok();
+    result.map(|()| (luma, envelope))
+}
+
+/// RMS of each [`ENVELOPE_WINDOW`] of `samples`, the first of which plays
+/// at `start` nanoseconds
+fn rms_envelope(samples: &[f32], start: u64) -> impl Iterator<Item = (u64, f32)> + '_ {
+    samples
+        .chunks(ENVELOPE_WINDOW)
+        .enumerate()
+        .map(move |(i, window)| {
+            let time = start + (i * ENVELOPE_WINDOW) as u64 * 1_000_000_000 / SAMPLE_RATE;
+            let rms = (window.iter().map(|s| s * s).sum::<f32>() / window.len() as f32).sqrt();
+            (time, rms)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flash_and_beep_are_found_where_they_rise() {
+        let ms = |ms: u64| ms * 1_000_000;
+        // 30 fps on a dark screen, flash from the 31st frame
+        let luma: Series = (0..60)
+            .map(|i| (i * 33_333_333, if i >= 30 { 0.9 } else { 0.08 }))
+            .collect();
+        assert_eq!(onset(&luma, MIN_FLASH_RISE), Some(30 * 33_333_333));
+
+        // Quiet room, beep from 960 ms
+        let samples: Vec<f32> = (0..96_000)
+            .map(|i| {
+                let noise = if i % 2 == 0 { 0.002 } else { -0.002 };
+                if i >= 46_080 { 0.5 } else { noise }
+            })
+            .collect();
+        let envelope: Series = rms_envelope(&samples, 0).collect();
+        assert_eq!(onset(&envelope, MIN_BEEP_RISE), Some(ms(960)));
+
+        // Nothing seen at all
+        let flat: Series = (0..10).map(|i| (i, 0.3)).collect();
+        assert_eq!(onset(&flat, MIN_FLASH_RISE), None);
+    }
+}
@@ -0,0 +1,267 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Low-light noise reduction of recorded and streamed video
+//!
+//! Frames go through the GPU denoise pass ([`GpuDenoisePipeline`]), which
+//! needs to know how noisy the picture is. Every [`ESTIMATE_INTERVAL`]
+//! frames the middle of the frame is measured with the burst-mode noise
+//! estimator. In auto mode the same measurement decides whether to denoise
+//! at all: [`NoiseGate`] switches on once the picture is noisy, as in a dim
+//! room, and back off once the light comes up, with some hysteresis so it
+//! doesn't flicker between the two.
+
+use crate::config::VideoDenoise;
+use crate::pipelines::photo::burst_mode::BurstModeGpuPipeline;
+use crate::shaders::{DenoiseStrength, GpuDenoisePipeline};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// Frames between noise measurements
+pub const ESTIMATE_INTERVAL: u64 = 30;
+
+/// Side of the centre crop the noise is measured on
+const ESTIMATE_SIZE: u32 = 512;
+
+/// Noise, in 8-bit levels, above which auto mode starts denoising
+const NOISY_ABOVE: f32 = 3.0;
+
+/// Noise below which auto mode stops denoising again
+const CLEAN_BELOW: f32 = 2.0;
+
+/// Noise assumed until the first measurement comes in
+const DEFAULT_NOISE_SD: f32 = 4.0;
+
+/// Share of a new measurement taken into the running noise level
+const NOISE_SMOOTHING: f32 = 0.5;
+
+/// Denoise settings a recording or virtual camera stream starts with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DenoiseSettings {
+    pub strength: VideoDenoise,
+    /// Only denoise while the picture is noisy
+    pub auto: bool,
+}
+
+impl DenoiseSettings {
+    /// Whether frames may be denoised at all
+    pub fn is_enabled(&self) -> bool {
+        self.strength != VideoDenoise::Off
+    }
+}
+
+/// Running noise level and the auto mode on/off decision
+#[derive(Debug, Clone)]
+pub struct NoiseGate {
+    auto: bool,
+    /// Smoothed noise level, `None` until measured
+    noise_sd: Option<f32>,
+    active: bool,
+}
+
+impl NoiseGate {
+    pub fn new(auto: bool) -> Self {
+        Self {
+            auto,
+            noise_sd: None,
+            // Without auto there is nothing to wait for
+            active: !auto,
+        }
+    }
+
+    /// Take in a noise measurement
+    pub fn update(&mut self, noise_sd: f32) {
+        let smoothed = match self.noise_sd {
+            Some(previous) => previous + (noise_sd - previous) * NOISE_SMOOTHING,
+            None => noise_sd,
+        };
+        self.noise_sd = Some(smoothed);
+        if self.auto {
+            if smoothed > NOISY_ABOVE {
+                self.active = true;
+            } else if smoothed < CLEAN_BELOW {
+                self.active = false;
+            }
+        }
+    }
+
+    /// Whether frames are denoised right now
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Noise level to denoise for
+    pub fn noise_sd(&self) -> f32 {
+        self.noise_sd.unwrap_or(DEFAULT_NOISE_SD)
+    }
+}
+
+/// Per-stream denoiser run on packed RGBA frames
+pub struct VideoDenoiser {
+    strength: VideoDenoise,
+    /// Denoise pass; `None` if it couldn't be created, in which case frames
+    /// go through unchanged
+    gpu: Option<GpuDenoisePipeline>,
+    /// Noise estimator; `None` if it couldn't be created, in which case the
+    /// default noise level is assumed
+    estimator: Option<Arc<BurstModeGpuPipeline>>,
+    gate: NoiseGate,
+    frame_count: u64,
+}
+
+impl VideoDenoiser {
+    pub async fn new(settings: DenoiseSettings) -> Self {
+        let gpu = match GpuDenoisePipeline::new().await {
+            Ok(gpu) => Some(gpu),
+            Err(e) => {
+                warn!(error = %e, "GPU denoise unavailable, frames pass through");
+                None
+            }
+        };
+        let estimator = match BurstModeGpuPipeline::shared().await {
+            Ok(estimator) => Some(estimator),
+            Err(e) => {
+                warn!(error = %e, "Noise estimation unavailable, assuming a dim room");
+                None
+            }
+        };
+        // Auto mode can't tell when it's dark without the estimator
+        let auto = settings.auto && estimator.is_some();
+        info!(strength = ?settings.strength, auto, "Video denoise enabled");
+        Self {
+            strength: settings.strength,
+            gpu,
+            estimator,
+            gate: NoiseGate::new(auto),
+            frame_count: 0,
+        }
+    }
+
+    /// Denoise one tightly packed RGBA frame
+    ///
+    /// Frames are returned as they came while auto mode finds the picture
+    /// clean, or if the denoise pass fails.
+    pub async fn denoise(&mut self, rgba: Vec<u8>, width: u32, height: u32) -> Vec<u8> {
+        if self.frame_count.is_multiple_of(ESTIMATE_INTERVAL) {
+            self.measure_noise(&rgba, width, height).await;
+        }
+        self.frame_count += 1;
+
+        let Some(gpu) = self.gpu.as_mut() else {
+            return rgba;
+        };
+        if !self.gate.is_active() {
+            // Don't blend in a stale frame when the denoiser comes back on
+            gpu.reset();
+            return rgba;
+        }
+
+        let (spatial, temporal) = self.strength.blend();
+        let strength = DenoiseStrength {
+            noise_sd: self.gate.noise_sd(),
+            spatial,
+            temporal,
+        };
+        match gpu.denoise_rgba(&rgba, width, height, strength).await {
+            Ok(denoised) => denoised,
+            Err(e) => {
+                warn!(error = %e, "Failed to denoise frame, using it unchanged");
+                gpu.reset();
+                rgba
+            }
+        }
+    }
+
+    /// Measure the noise in the middle of the frame and feed it to the gate
+    async fn measure_noise(&mut self, rgba: &[u8], width: u32, height: u32) {
+        let Some(estimator) = self.estimator.as_ref() else {
+            return;
+        };
+        let (crop, crop_width, crop_height) = center_crop(rgba, width, height, ESTIMATE_SIZE);
+        match estimator
+            .estimate_noise_gpu(&crop, crop_width, crop_height)
+            .await
+        {
+            Ok(noise_sd) => {
+                let was_active = self.gate.is_active();
+                self.gate.update(noise_sd);
+                debug!(
+                    noise_sd,
+                    smoothed = self.gate.noise_sd(),
+                    active = self.gate.is_active(),
+                    "Measured video noise"
+                );
+                if self.gate.is_active() != was_active {
+                    info!(
+                        active = self.gate.is_active(),
+                        noise_sd = self.gate.noise_sd(),
+                        "Low-light denoise switched"
+                    );
+                }
+            }
+            Err(e) => warn!(error = %e, "Noise estimation failed"),
+        }
+    }
+}
+
+/// Middle `size`x`size` pixels of tightly packed RGBA (all of it if the
+/// frame is smaller)
+fn center_crop(data: &[u8], width: u32, height: u32, size: u32) -> (Vec<u8>, u32, u32) {
+    let crop_width = width.min(size);
+    let crop_height = height.min(size);
+    let left = ((width - crop_width) / 2) as usize;
+    let top = ((height - crop_height) / 2) as usize;
+    let stride = width as usize * 4;
+    let row_bytes = crop_width as usize * 4;
+    let mut out = Vec::with_capacity(row_bytes * crop_height as usize);
+    for y in top..top + crop_height as usize {
+        let start = y * stride + left * 4;
+        out.extend_from_slice(&data[start..start + row_bytes]);
+    }
+    (out, crop_width, crop_height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_mode_switches_with_hysteresis() {
+        let mut gate = NoiseGate::new(true);
+        assert!(!gate.is_active());
+
+        gate.update(1.0);
+        assert!(!gate.is_active());
+        // Lights go down
+        gate.update(6.0);
+        assert!(gate.is_active());
+        // In between the thresholds nothing changes
+        gate.update(2.0);
+        assert!(gate.is_active());
+        // Lights come back up
+        gate.update(1.0);
+        gate.update(1.0);
+        assert!(!gate.is_active());
+
+        // Without auto the gate is always open
+        let mut gate = NoiseGate::new(false);
+        assert!(gate.is_active());
+        assert_eq!(gate.noise_sd(), DEFAULT_NOISE_SD);
+        gate.update(0.5);
+        assert!(gate.is_active());
+    }
+
+    #[test]
+    fn noise_is_measured_on_the_middle_of_the_frame() {
+        // 4x2 frame, red channel holds the column index
+        let data: Vec<u8> = (0..2)
+            .flat_map(|_| (0..4u8).flat_map(|x| [x, 0, 0, 255]))
+            .collect();
+        let (crop, width, height) = center_crop(&data, 4, 2, 2);
+        assert_eq!((width, height), (2, 2));
+        assert_eq!(crop, [1, 0, 0, 255, 2, 0, 0, 255].repeat(2));
+
+        let (crop, width, height) = center_crop(&data, 4, 2, 8);
+        assert_eq!((width, height), (4, 2));
+        assert_eq!(crop, data);
+    }
+}
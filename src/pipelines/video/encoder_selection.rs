@@ -6,15 +6,19 @@
 //! for the recording pipeline.
 
 use crate::media::encoders::{
-    audio::{AudioChannels, AudioQuality, SelectedAudioEncoder, select_audio_encoder},
+    audio::{
+        AudioChannels, AudioQuality, SelectedAudioEncoder, select_audio_encoder,
+        select_pcm_audio_encoder,
+    },
     video::{
-        EncoderInfo, SelectedVideoEncoder, VideoQuality, create_encoder_from_info_with_bitrate,
+        ContainerFormat, EncoderInfo, SelectedVideoEncoder, VideoCodec, VideoQuality,
+        create_encoder_from_info_with_bitrate, resolve_container, resolve_pcm_container,
         select_video_encoder_with_bitrate,
     },
 };
 
 /// Configuration for encoder selection
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct EncoderConfig {
     /// Video quality preset
     pub video_quality: VideoQuality,
@@ -28,6 +32,24 @@ pub struct EncoderConfig {
     pub height: u32,
     /// Optional bitrate override in kbps (takes precedence over quality preset)
     pub bitrate_override_kbps: Option<u32>,
+    /// Preferred output container (`None` = the codec's default). Ignored
+    /// when it can't carry the selected codec.
+    pub container: Option<ContainerFormat>,
+    /// Edit-friendly recording: every frame is a key frame and audio is
+    /// stored as uncompressed PCM
+    pub intra_only: bool,
+}
+
+impl EncoderConfig {
+    /// Container to record `codec` into: the preferred one when it fits,
+    /// restricted to containers that hold PCM for intra-only recordings
+    pub fn container_for(&self, codec: VideoCodec) -> ContainerFormat {
+        if self.intra_only {
+            resolve_pcm_container(codec, self.container)
+        } else {
+            resolve_container(codec, self.container)
+        }
+    }
 }
 
 impl Default for EncoderConfig {
@@ -39,6 +61,8 @@ impl Default for EncoderConfig {
             width: 1920,
             height: 1080,
             bitrate_override_kbps: None,
+            container: None,
+            intra_only: false,
         }
     }
 }
@@ -68,16 +92,17 @@ pub fn select_encoders(
     enable_audio: bool,
 ) -> Result<SelectedEncoders, String> {
     // Select video encoder
-    let video = select_video_encoder_with_bitrate(
+    let mut video = select_video_encoder_with_bitrate(
         config.video_quality,
         config.width,
         config.height,
         config.bitrate_override_kbps,
     )?;
+    apply_container_preference(&mut video, config)?;
 
     // Select audio encoder if enabled
     let audio = if enable_audio {
-        match select_audio_encoder(config.audio_quality, config.audio_channels) {
+        match select_recording_audio_encoder(config) {
             Ok(encoder) => Some(encoder),
             Err(e) => {
                 tracing::warn!(
@@ -110,17 +135,18 @@ pub fn select_encoders_with_video(
     enable_audio: bool,
 ) -> Result<SelectedEncoders, String> {
     // Create specific video encoder
-    let video = create_encoder_from_info_with_bitrate(
+    let mut video = create_encoder_from_info_with_bitrate(
         encoder_info,
         config.video_quality,
         config.width,
         config.height,
         config.bitrate_override_kbps,
     )?;
+    apply_container_preference(&mut video, config)?;
 
     // Select audio encoder if enabled
     let audio = if enable_audio {
-        match select_audio_encoder(config.audio_quality, config.audio_channels) {
+        match select_recording_audio_encoder(config) {
             Ok(encoder) => Some(encoder),
             Err(e) => {
                 tracing::warn!(
@@ -137,6 +163,39 @@ pub fn select_encoders_with_video(
     Ok(SelectedEncoders { video, audio })
 }
 
+/// PCM for edit-friendly recordings, otherwise the best compressed encoder
+fn select_recording_audio_encoder(config: &EncoderConfig) -> Result<SelectedAudioEncoder, String> {
+    if config.intra_only {
+        select_pcm_audio_encoder()
+    } else {
+        select_audio_encoder(config.audio_quality, config.audio_channels)
+    }
+}
+
+/// Select the encoder for the desktop-audio track: same codec as the
+/// microphone track, but stereo
+pub fn select_system_audio_encoder(config: &EncoderConfig) -> Result<SelectedAudioEncoder, String> {
+    select_recording_audio_encoder(&EncoderConfig {
+        audio_channels: AudioChannels::Stereo,
+        ..config.clone()
+    })
+}
+
+/// Swap the muxer for the user's preferred container, if it fits the codec
+fn apply_container_preference(
+    video: &mut SelectedVideoEncoder,
+    config: &EncoderConfig,
+) -> Result<(), String> {
+    if config.container.is_none() && !config.intra_only {
+        return Ok(());
+    }
+    let container = config.container_for(video.codec);
+    if container != video.container {
+        video.set_container(container)?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,5 +206,19 @@ mod tests {
         assert_eq!(config.width, 1920);
         assert_eq!(config.height, 1080);
         assert_eq!(config.audio_channels, AudioChannels::Mono);
+        assert!(!config.intra_only);
+    }
+
+    #[test]
+    fn test_intra_only_uses_pcm_container() {
+        let config = EncoderConfig {
+            intra_only: true,
+            container: Some(ContainerFormat::MP4),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.container_for(VideoCodec::H264),
+            ContainerFormat::Matroska
+        );
     }
 }
@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Subtitle sidecar of events detected while recording
+//!
+//! With `Config::record_event_subtitles` on, QR codes that come into view
+//! and markers added from the keyboard are logged against the recording
+//! clock, then written as an SRT file next to the finished video. Players
+//! load a sidecar that shares the video's file stem automatically, so the
+//! recording doubles as an annotated log for inspection work.
+//!
+//! Labels are supplied by the caller when the file is written, which keeps
+//! this module free of localisation.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long each event stays on screen (shortened if the next event is sooner)
+const CUE_DURATION: Duration = Duration::from_secs(3);
+
+/// A QR code missing from the detections for longer than this is logged
+/// again when it comes back, rather than treated as the same sighting
+const QR_FORGET_AFTER: Duration = Duration::from_secs(5);
+
+/// Something worth a subtitle cue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordingEvent {
+    /// A QR code came into view (decoded content)
+    QrCode(String),
+    /// The user added a marker (numbered from 1)
+    Marker(u32),
+}
+
+/// Events of one recording, with their offset from the start
+#[derive(Debug, Default)]
+pub struct RecordingEventLog {
+    events: Vec<(Duration, RecordingEvent)>,
+    qr_last_seen: HashMap<String, Duration>,
+    markers: u32,
+}
+
+impl RecordingEventLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Log a marker and return its number
+    pub fn add_marker(&mut self, at: Duration) -> u32 {
+        self.markers += 1;
+        self.events.push((at, RecordingEvent::Marker(self.markers)));
+        self.markers
+    }
+
+    /// Feed the QR codes currently in view; codes not seen recently are logged
+    pub fn qr_codes_seen<'a>(&mut self, at: Duration, contents: impl IntoIterator<Item = &'a str>) {
+        for content in contents {
+            let is_new = self
+                .qr_last_seen
+                .get(content)
+                .is_none_or(|&last| at.saturating_sub(last) > QR_FORGET_AFTER);
+            if is_new {
+                self.events
+                    .push((at, RecordingEvent::QrCode(content.to_string())));
+            }
+            self.qr_last_seen.insert(content.to_string(), at);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Render the log as SRT. Events logged at the same instant share a cue.
+    pub fn to_srt(&self, label: impl Fn(&RecordingEvent) -> String) -> String {
+        let mut cues: Vec<(Duration, Vec<String>)> = Vec::new();
+        for (at, event) in &self.events {
+            match cues.last_mut() {
+                Some((start, lines)) if start == at => lines.push(label(event)),
+                _ => cues.push((*at, vec![label(event)])),
+            }
+        }
+
+        let mut srt = String::new();
+        for (index, (start, lines)) in cues.iter().enumerate() {
+            let mut end = *start + CUE_DURATION;
+            if let Some((next, _)) = cues.get(index + 1) {
+                end = end.min(*next);
+            }
+            srt.push_str(&format!(
+                "{}\n{} --> {}\n{}\n\n",
+                index + 1,
+                srt_timestamp(*start),
+                srt_timestamp(end),
+                lines.join("\n")
+            ));
+        }
+        srt
+    }
+}
+
+/// Where the subtitle sidecar of `video` goes
+pub fn sidecar_path(video: &Path) -> PathBuf {
+    video.with_extension("srt")
+}
+
+/// `HH:MM:SS,mmm`
+fn srt_timestamp(offset: Duration) -> String {
+    let millis = offset.as_millis();
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(event: &RecordingEvent) -> String {
+        match event {
+            RecordingEvent::QrCode(content) => format!("QR: {content}"),
+            RecordingEvent::Marker(n) => format!("Marker {n}"),
+        }
+    }
+
+    #[test]
+    fn qr_codes_are_logged_once_per_sighting() {
+        let mut log = RecordingEventLog::new();
+        log.qr_codes_seen(Duration::from_secs(1), ["a"]);
+        log.qr_codes_seen(Duration::from_secs(2), ["a", "b"]);
+        // "a" was out of view for longer than QR_FORGET_AFTER
+        log.qr_codes_seen(Duration::from_secs(10), ["a"]);
+        let logged: Vec<_> = log.events.iter().map(|(_, e)| label(e)).collect();
+        assert_eq!(logged, ["QR: a", "QR: b", "QR: a"]);
+    }
+
+    #[test]
+    fn cues_end_at_the_next_event() {
+        let mut log = RecordingEventLog::new();
+        log.qr_codes_seen(Duration::from_millis(1500), ["x", "y"]);
+        log.add_marker(Duration::from_millis(2500));
+        log.add_marker(Duration::from_secs(3_725));
+        assert_eq!(
+            log.to_srt(label),
+            "1\n00:00:01,500 --> 00:00:02,500\nQR: x\nQR: y\n\n\
+             2\n00:00:02,500 --> 00:00:05,500\nMarker 1\n\n\
+             3\n01:02:05,000 --> 01:02:08,000\nMarker 2\n\n"
+        );
+    }
+}
@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! JSON sidecar of per-frame camera metadata and timecode
+//!
+//! libcamera reports the exposure, gain, white balance and lens position it
+//! applied to every frame. Recordings from the libcamera backend log these
+//! against each frame's presentation time and write them next to the
+//! finished video as `<video>.json`, so grading and analysis tools can see
+//! how the camera's automatic controls moved over the clip.
+//!
+//! With `Config::recording_timecode` on, every frame is also logged with the
+//! wall-clock time it was captured and its time-of-day SMPTE timecode
+//! (`HH:MM:SS:FF`), from any camera. Frames of V4L2 cameras without it carry
+//! nothing to log, and those recordings get no sidecar.
+
+use crate::backends::camera::types::FrameMetadata;
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Metadata of one encoded frame
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FrameMetadataRecord {
+    /// Presentation time in the video, in nanoseconds
+    pub pts_ns: u64,
+    /// When the frame was captured, RFC 3339 with milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wall_clock: Option<String>,
+    /// Time-of-day SMPTE timecode, non-drop-frame
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timecode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<u32>,
+    /// Sensor timestamp, in nanoseconds since boot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sensor_timestamp_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exposure_time_us: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub analogue_gain: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digital_gain: Option<f32>,
+    /// White balance, in Kelvin
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub colour_temperature: Option<u32>,
+    /// Lens position, in dioptres
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lens_position: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lux: Option<f32>,
+}
+
+/// Per-frame metadata of one recording, in encoding order
+#[derive(Debug, Serialize)]
+pub struct FrameMetadataLog {
+    /// Frame rate the timecode counts frames at
+    #[serde(skip)]
+    framerate: u32,
+    frames: Vec<FrameMetadataRecord>,
+}
+
+impl FrameMetadataLog {
+    pub fn new(framerate: u32) -> Self {
+        Self {
+            framerate: framerate.max(1),
+            frames: Vec::new(),
+        }
+    }
+
+    /// Log the frame encoded at `pts_ns`, with the metadata libcamera
+    /// reported for it and when it was captured. A frame with neither is
+    /// not logged.
+    pub fn push(
+        &mut self,
+        pts_ns: u64,
+        metadata: Option<&FrameMetadata>,
+        captured: Option<DateTime<Local>>,
+    ) {
+        if metadata.is_none() && captured.is_none() {
+            return;
+        }
+        let metadata = metadata.cloned().unwrap_or_default();
+        self.frames.push(FrameMetadataRecord {
+            pts_ns,
+            wall_clock: captured.map(|at| at.to_rfc3339_opts(chrono::SecondsFormat::Millis, false)),
+            timecode: captured.map(|at| timecode(&at, self.framerate)),
+            sequence: metadata.sequence,
+            sensor_timestamp_ns: metadata.sensor_timestamp,
+            exposure_time_us: metadata.exposure_time,
+            analogue_gain: metadata.analogue_gain,
+            digital_gain: metadata.digital_gain,
+            colour_temperature: metadata.colour_temperature,
+            lens_position: metadata.lens_position,
+            lux: metadata.lux,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(self).map_err(|e| format!("Failed to serialize frame metadata: {e}"))
+    }
+
+    /// Write the log next to `video`. Returns the sidecar's path. Blocking.
+    pub fn write(&self, video: &Path) -> Result<PathBuf, String> {
+        let path = sidecar_path(video);
+        std::fs::write(&path, self.to_json()?)
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// Where the metadata sidecar of `video` goes
+pub fn sidecar_path(video: &Path) -> PathBuf {
+    video.with_extension("json")
+}
+
+/// Wall-clock time of a frame captured at `captured_at`, given that
+/// `anchor.0` was `anchor.1` on the wall clock
+pub fn wall_clock_at(captured_at: Instant, anchor: (Instant, DateTime<Local>)) -> DateTime<Local> {
+    let (instant, wall) = anchor;
+    let offset = match captured_at.checked_duration_since(instant) {
+        Some(after) => chrono::TimeDelta::from_std(after),
+        None => chrono::TimeDelta::from_std(instant.duration_since(captured_at)).map(|d| -d),
+    };
+    offset
+        .ok()
+        .and_then(|offset| wall.checked_add_signed(offset))
+        .unwrap_or(wall)
+}
+
+/// `HH:MM:SS:FF` time-of-day timecode of `at`, counting frames at
+/// `framerate` within the second
+fn timecode(at: &DateTime<Local>, framerate: u32) -> String {
+    let frame = u64::from(at.timestamp_subsec_nanos().min(999_999_999)) * u64::from(framerate)
+        / 1_000_000_000;
+    format!("{}:{frame:02}", at.format("%H:%M:%S"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_are_logged_without_missing_values() {
+        let mut log = FrameMetadataLog::new(30);
+        log.push(
+            0,
+            Some(&FrameMetadata {
+                sequence: Some(7),
+                exposure_time: Some(16_667),
+                analogue_gain: Some(2.0),
+                colour_temperature: Some(5200),
+                ..Default::default()
+            }),
+            None,
+        );
+        log.push(33_333_333, Some(&FrameMetadata::default()), None);
+        log.push(66_666_666, None, None);
+        assert_eq!(
+            log.to_json().unwrap(),
+            "{\"frames\":[\
+             {\"pts_ns\":0,\"sequence\":7,\"exposure_time_us\":16667,\
+             \"analogue_gain\":2.0,\"colour_temperature\":5200},\
+             {\"pts_ns\":33333333}]}"
+        );
+        assert_eq!(
+            sidecar_path(Path::new("/videos/VID_1.mp4")),
+            Path::new("/videos/VID_1.json")
+        );
+    }
+
+    #[test]
+    fn timecode_counts_frames_within_the_second() {
+        use chrono::TimeZone;
+
+        let second = Local.with_ymd_and_hms(2026, 3, 1, 9, 30, 5).unwrap();
+        assert_eq!(timecode(&second, 30), "09:30:05:00");
+        let late = second + chrono::TimeDelta::milliseconds(990);
+        assert_eq!(timecode(&late, 30), "09:30:05:29");
+        assert_eq!(timecode(&late, 25), "09:30:05:24");
+
+        let start = Instant::now();
+        let later = start + std::time::Duration::from_millis(1500);
+        assert_eq!(
+            wall_clock_at(later, (start, second)),
+            second + chrono::TimeDelta::milliseconds(1500)
+        );
+        assert_eq!(
+            wall_clock_at(start, (later, second)),
+            second - chrono::TimeDelta::milliseconds(1500)
+        );
+
+        let mut log = FrameMetadataLog::new(30);
+        log.push(0, None, Some(late));
+        assert_eq!(log.frames[0].timecode.as_deref(), Some("09:30:05:29"));
+        assert!(
+            log.frames[0]
+                .wall_clock
+                .as_deref()
+                .unwrap()
+                .contains("T09:30:05.990")
+        );
+    }
+}
@@ -7,15 +7,30 @@
 //! - Continues preview during recording
 //! - Supports audio recording
 //! - Provides quality presets
+//! - Optionally stabilizes recordings by tracking and cropping out camera shake
+//! - Optionally reduces noise in low light, for recordings and the virtual camera
+//! - Logs libcamera's per-frame metadata to a JSON sidecar of the recording
 
+pub mod animated_clip;
+pub mod av_sync;
+pub mod denoise;
 pub mod encoder_selection;
+pub mod event_subtitles;
+pub mod frame_metadata;
 pub mod muxer;
+pub mod post_stabilization;
 pub mod recorder;
+pub mod stabilization;
 pub mod stats;
+pub mod still_extraction;
 pub mod timelapse;
+pub mod warmup;
 
 // Re-export commonly used types
+pub use denoise::{DenoiseSettings, VideoDenoiser};
 pub use encoder_selection::EncoderConfig;
+pub use event_subtitles::{RecordingEvent, RecordingEventLog};
+pub use post_stabilization::{PostStabilizationConfig, stabilize_video};
 pub use recorder::{
     AppsrcRecorderConfig, AudioLevels, RecorderConfig, SharedAudioLevels, VideoRecorder,
     check_available_encoders,
@@ -23,6 +38,9 @@ pub use recorder::{
 pub use stats::{
     RecordingDiagnostics, RecordingStatsSnapshot, get_recording_diagnostics, get_recording_stats,
 };
+pub use still_extraction::{
+    ExtractionControl, StillExtractionConfig, StillSelection, extract_stills,
+};
 
 // Re-export encoder types for convenience
 pub use crate::media::encoders::{AudioChannels, AudioQuality, VideoQuality};
@@ -4,6 +4,7 @@
 //!
 //! This module handles muxing audio and video streams into a container format.
 
+use crate::backends::camera::types::SensorRotation;
 use gstreamer as gst;
 use gstreamer::prelude::*;
 use tracing::{debug, info};
@@ -79,6 +80,25 @@ pub fn create_muxer(
     })
 }
 
+/// Pipeline description fragment that tags the video with the rotation
+/// players still have to apply, to go right before the muxer; empty for
+/// `None`
+///
+/// The tag has to reach the muxer as a stream tag event on the video pad,
+/// after caps and segment, which `taginject` takes care of. mp4mux writes it
+/// into the track matrix; Matroska and WebM have nowhere to put it.
+pub fn orientation_tag_desc(orientation: SensorRotation) -> String {
+    // GStreamer names the clockwise turn; a correction of 90 is a
+    // counter-clockwise one
+    let tag = match orientation {
+        SensorRotation::None => return String::new(),
+        SensorRotation::Rotate90 => "rotate-270",
+        SensorRotation::Rotate180 => "rotate-180",
+        SensorRotation::Rotate270 => "rotate-90",
+    };
+    format!("! taginject tags=\"image-orientation={tag}\" ")
+}
+
 /// Link video encoder to muxer
 ///
 /// # Arguments
@@ -0,0 +1,480 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Stabilization of videos that are already recorded
+//!
+//! For clips filmed before stabilization was switched on. The file is
+//! decoded frame by frame ([`VideoFrameReader`]), every frame goes through
+//! the same [`VideoStabilizer`] live recordings use, and the result is
+//! encoded into a `_stabilized` copy; the original is left alone. The
+//! source's audio track is decoded again alongside and re-encoded into the
+//! copy.
+//!
+//! As with still extraction, decoding runs on a blocking thread and hands
+//! frames to the async side over a small channel, so the GPU work on one
+//! frame overlaps decoding the next.
+//!
+//! Pipeline: appsrc (RGBA) → videoconvert → encoder → muxer → filesink,
+//! with filesrc → parsebin → decodebin → audioconvert → audio encoder
+//! feeding the same muxer.
+
+use super::encoder_selection::{EncoderConfig, select_encoders};
+use super::muxer::{create_muxer, link_audio_to_muxer, link_muxer_to_sink, link_video_to_muxer};
+use super::recorder::{ENCODE_COLORIMETRY, RGBA_COLORIMETRY, convert_frame_to_rgba};
+use super::stabilization::VideoStabilizer;
+use super::still_extraction::ExtractionControl;
+use crate::backends::camera::types::CameraFrame;
+use crate::backends::virtual_camera::VideoFrameReader;
+use crate::config::VideoStabilization;
+use crate::media::encoders::VideoQuality;
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{info, warn};
+
+/// Sample rate the source audio is re-encoded at (accepted by every
+/// recording audio encoder, Opus included)
+const AUDIO_RATE: i32 = 48_000;
+
+/// Post-capture stabilization job settings
+#[derive(Debug, Clone)]
+pub struct PostStabilizationConfig {
+    pub strength: VideoStabilization,
+    /// Directory the stabilized copy is saved to
+    pub output_dir: PathBuf,
+    pub video_quality: VideoQuality,
+}
+
+/// Write a stabilized copy of `source` into `config.output_dir`
+///
+/// Returns the copy's path. A cancelled job removes the partial copy and
+/// returns an error.
+pub async fn stabilize_video(
+    source: PathBuf,
+    config: PostStabilizationConfig,
+    control: ExtractionControl,
+) -> Result<PathBuf, String> {
+    if config.strength == VideoStabilization::Off {
+        return Err("Stabilization strength must not be off".into());
+    }
+    info!(
+        source = %source.display(),
+        strength = ?config.strength,
+        output_dir = %config.output_dir.display(),
+        "Stabilizing recorded video"
+    );
+
+    tokio::fs::create_dir_all(&config.output_dir)
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to create output directory '{}': {}",
+                config.output_dir.display(),
+                e
+            )
+        })?;
+
+    let (frame_tx, mut frame_rx) = tokio::sync::mpsc::channel::<(f64, CameraFrame)>(2);
+    let decode_source = source.clone();
+    let decode_control = control.clone();
+    let decoder = tokio::task::spawn_blocking(move || {
+        decode_frames(&decode_source, &decode_control, &frame_tx)
+    });
+
+    let Some((first_timestamp, first_frame)) = frame_rx.recv().await else {
+        decoder
+            .await
+            .map_err(|e| format!("Stabilization task error: {}", e))??;
+        return Err("The video has no frames".into());
+    };
+    let (width, height) = (first_frame.width, first_frame.height);
+
+    let stem = source
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("video")
+        .to_string();
+    let encoding = EncodingPipeline::new(&source, &stem, width, height, &config)?;
+    let output_path = encoding.output_path.clone();
+    let mut stabilizer = VideoStabilizer::new(config.strength).await;
+
+    let mut pending = Some((first_timestamp, first_frame));
+    let mut frames: u64 = 0;
+    loop {
+        let (timestamp, frame) = match pending.take() {
+            Some(item) => item,
+            None => match frame_rx.recv().await {
+                Some(item) => item,
+                None => break,
+            },
+        };
+        if (frame.width, frame.height) != (width, height) {
+            warn!(
+                expected_w = width,
+                expected_h = height,
+                actual_w = frame.width,
+                actual_h = frame.height,
+                "Skipping frame with mismatched dimensions"
+            );
+            continue;
+        }
+        let rgba = match convert_frame_to_rgba(&frame).await {
+            Ok(rgba) => rgba,
+            Err(e) => {
+                warn!(error = %e, timestamp, "Skipping frame (conversion failed)");
+                continue;
+            }
+        };
+        let rgba = stabilizer.stabilize(rgba, width, height).await;
+        if let Err(e) = encoding.push(rgba, timestamp - first_timestamp) {
+            encoding.abort();
+            return Err(e);
+        }
+        frames += 1;
+    }
+
+    let decoded = decoder
+        .await
+        .map_err(|e| format!("Stabilization task error: {}", e))
+        .and_then(|result| result);
+    if let Err(e) = decoded {
+        encoding.abort();
+        return Err(e);
+    }
+    if control.is_cancelled() {
+        encoding.abort();
+        info!("Stabilization cancelled");
+        return Err("Stabilization cancelled".into());
+    }
+
+    encoding.finish()?;
+    info!(path = %output_path.display(), frames, "Stabilized video saved");
+    Ok(output_path)
+}
+
+/// Decode `source` and send every frame
+fn decode_frames(
+    source: &Path,
+    control: &ExtractionControl,
+    frame_tx: &tokio::sync::mpsc::Sender<(f64, CameraFrame)>,
+) -> Result<(), String> {
+    let mut reader = VideoFrameReader::open(source).map_err(|e| e.to_string())?;
+    let duration = reader.duration().filter(|d| *d > 0.0);
+
+    while !control.is_cancelled() {
+        let Some((timestamp, frame)) = reader.next_frame().map_err(|e| e.to_string())? else {
+            break;
+        };
+        if let Some(duration) = duration {
+            control.set_progress(timestamp / duration);
+        }
+        // The receiver is gone only if the encoding side failed
+        if frame_tx.blocking_send((timestamp, frame)).is_err() {
+            warn!("Stabilization encoder stopped, ending decode");
+            break;
+        }
+    }
+    control.set_progress(1.0);
+    Ok(())
+}
+
+/// First free `<stem>_stabilized.<extension>` name in `dir`
+fn unused_output_path(dir: &Path, stem: &str, extension: &str) -> PathBuf {
+    (1..)
+        .map(|n| {
+            let name = if n == 1 {
+                format!("{stem}_stabilized.{extension}")
+            } else {
+                format!("{stem}_stabilized_{n}.{extension}")
+            };
+            dir.join(name)
+        })
+        .find(|path| !path.exists())
+        .unwrap_or_else(|| dir.join(format!("{stem}_stabilized.{extension}")))
+}
+
+/// The GStreamer pipeline writing the stabilized copy
+struct EncodingPipeline {
+    pipeline: gst::Pipeline,
+    appsrc: gst_app::AppSrc,
+    output_path: PathBuf,
+}
+
+impl EncodingPipeline {
+    fn new(
+        source: &Path,
+        stem: &str,
+        width: u32,
+        height: u32,
+        config: &PostStabilizationConfig,
+    ) -> Result<Self, String> {
+        let encoder_config = EncoderConfig {
+            video_quality: config.video_quality,
+            width,
+            height,
+            ..Default::default()
+        };
+        let encoders = select_encoders(&encoder_config, true)
+            .map_err(|e| format!("Encoder selection failed: {e}"))?;
+        let video_enc = encoders.video;
+        let output_path = unused_output_path(&config.output_dir, stem, video_enc.extension);
+
+        let pipeline = gst::Pipeline::new();
+        // Timestamps follow the source, so the caps leave the rate variable
+        let appsrc = gst_app::AppSrc::builder()
+            .caps(
+                &gst::Caps::builder("video/x-raw")
+                    .field("format", "RGBA")
+                    .field("width", width as i32)
+                    .field("height", height as i32)
+                    .field("framerate", gst::Fraction::new(0, 1))
+                    .field("colorimetry", RGBA_COLORIMETRY)
+                    .build(),
+            )
+            .format(gst::Format::Time)
+            .is_live(false)
+            .build();
+        let videoconvert = gst::ElementFactory::make("videoconvert")
+            .build()
+            .map_err(|e| format!("videoconvert: {e}"))?;
+        let encoder = video_enc.encoder;
+        let muxer_cfg = create_muxer(video_enc.muxer, output_path.clone())?;
+
+        pipeline
+            .add_many([
+                appsrc.upcast_ref(),
+                &videoconvert,
+                &encoder,
+                &muxer_cfg.muxer,
+                &muxer_cfg.filesink,
+            ])
+            .map_err(|e| format!("pipeline add: {e}"))?;
+        appsrc
+            .link(&videoconvert)
+            .map_err(|_| "link appsrc→videoconvert")?;
+        videoconvert
+            .link_filtered(
+                &encoder,
+                &gst::Caps::builder("video/x-raw")
+                    .field("colorimetry", ENCODE_COLORIMETRY)
+                    .build(),
+            )
+            .map_err(|_| "link videoconvert→encoder")?;
+        if let Some(parser) = &video_enc.parser {
+            pipeline
+                .add(parser)
+                .map_err(|e| format!("pipeline add parser: {e}"))?;
+            encoder.link(parser).map_err(|_| "link encoder→parser")?;
+            link_video_to_muxer(parser, &muxer_cfg.muxer)?;
+        } else {
+            link_video_to_muxer(&encoder, &muxer_cfg.muxer)?;
+        }
+        link_muxer_to_sink(&muxer_cfg.muxer, &muxer_cfg.filesink)?;
+
+        match encoders.audio {
+            Some(audio) => add_source_audio(&pipeline, source, audio.encoder, &muxer_cfg.muxer)?,
+            None => warn!("No audio encoder available, the stabilized copy will be silent"),
+        }
+
+        pipeline
+            .set_state(gst::State::Playing)
+            .map_err(|e| format!("set PLAYING: {e:?}"))?;
+
+        Ok(Self {
+            pipeline,
+            appsrc,
+            output_path,
+        })
+    }
+
+    /// Push one tightly packed RGBA frame at `seconds` into the copy
+    fn push(&self, rgba: Vec<u8>, seconds: f64) -> Result<(), String> {
+        let mut buffer = gst::Buffer::from_mut_slice(rgba);
+        buffer
+            .get_mut()
+            .ok_or("Frame buffer is shared")?
+            .set_pts(gst::ClockTime::from_nseconds(
+                (seconds.max(0.0) * 1e9) as u64,
+            ));
+        self.appsrc
+            .push_buffer(buffer)
+            .map(|_| ())
+            .map_err(|e| format!("push_buffer: {e}"))
+    }
+
+    /// End the stream and wait for the muxer to finish the file
+    fn finish(self) -> Result<(), String> {
+        let _ = self.appsrc.end_of_stream();
+        let bus = self.pipeline.bus().ok_or("No pipeline bus")?;
+        let result = loop {
+            match bus.timed_pop(gst::ClockTime::from_seconds(30)) {
+                Some(msg) => match msg.view() {
+                    gst::MessageView::Eos(..) => break Ok(()),
+                    gst::MessageView::Error(e) => {
+                        break Err(format!(
+                            "GStreamer error: {} ({})",
+                            e.error(),
+                            e.debug().unwrap_or_default()
+                        ));
+                    }
+                    _ => {}
+                },
+                None => break Err("Timeout waiting for pipeline EOS".into()),
+            }
+        };
+        self.pipeline.set_state(gst::State::Null).ok();
+        if result.is_err() {
+            let _ = std::fs::remove_file(&self.output_path);
+        }
+        result
+    }
+
+    /// Stop and remove the partial copy
+    fn abort(self) {
+        self.pipeline.set_state(gst::State::Null).ok();
+        let _ = std::fs::remove_file(&self.output_path);
+    }
+}
+
+/// Re-encode the source's first audio track into the copy
+///
+/// The source is opened a second time with `parsebin`, which splits the
+/// tracks without decoding them: the audio track is decoded and encoded
+/// again, the video track is discarded undecoded. A source without audio
+/// ends the audio branch straight away so the muxer doesn't wait for it.
+fn add_source_audio(
+    pipeline: &gst::Pipeline,
+    source: &Path,
+    encoder: gst::Element,
+    muxer: &gst::Element,
+) -> Result<(), String> {
+    let make = |factory: &str| {
+        gst::ElementFactory::make(factory)
+            .build()
+            .map_err(|e| format!("{factory}: {e}"))
+    };
+    let filesrc = gst::ElementFactory::make("filesrc")
+        .property("location", source.to_string_lossy().as_ref())
+        .build()
+        .map_err(|e| format!("filesrc: {e}"))?;
+    let parsebin = make("parsebin")?;
+    let queue = make("queue")?;
+    let decodebin = make("decodebin")?;
+    let convert = make("audioconvert")?;
+    let resample = make("audioresample")?;
+    let capsfilter = gst::ElementFactory::make("capsfilter")
+        .property(
+            "caps",
+            gst::Caps::builder("audio/x-raw")
+                .field("rate", AUDIO_RATE)
+                .build(),
+        )
+        .build()
+        .map_err(|e| format!("capsfilter: {e}"))?;
+
+    pipeline
+        .add_many([
+            &filesrc,
+            &parsebin,
+            &queue,
+            &decodebin,
+            &convert,
+            &resample,
+            &capsfilter,
+            &encoder,
+        ])
+        .map_err(|e| format!("pipeline add audio: {e}"))?;
+    filesrc
+        .link(&parsebin)
+        .map_err(|_| "link filesrc→parsebin")?;
+    queue.link(&decodebin).map_err(|_| "link queue→decodebin")?;
+    gst::Element::link_many([&convert, &resample, &capsfilter, &encoder])
+        .map_err(|_| "link audioconvert→encoder")?;
+    link_audio_to_muxer(&encoder, muxer)?;
+
+    let audio_linked = Arc::new(AtomicBool::new(false));
+    {
+        let audio_linked = audio_linked.clone();
+        let pipeline = pipeline.downgrade();
+        let queue = queue.downgrade();
+        parsebin.connect_pad_added(move |_, pad| {
+            let caps = pad.current_caps().unwrap_or_else(|| pad.query_caps(None));
+            let is_audio = caps
+                .structure(0)
+                .is_some_and(|s| s.name().starts_with("audio/"));
+            if is_audio
+                && !audio_linked.swap(true, Ordering::SeqCst)
+                && let Some(sink) = queue.upgrade().and_then(|q| q.static_pad("sink"))
+            {
+                if let Err(e) = pad.link(&sink) {
+                    warn!(?e, "Failed to link source audio");
+                }
+                return;
+            }
+            // Video and any further audio tracks are dropped undecoded
+            let Some(pipeline) = pipeline.upgrade() else {
+                return;
+            };
+            let Ok(fakesink) = gst::ElementFactory::make("fakesink")
+                .property("sync", false)
+                .property("async", false)
+                .build()
+            else {
+                return;
+            };
+            if pipeline.add(&fakesink).is_ok() {
+                let _ = fakesink.sync_state_with_parent();
+                if let Some(sink) = fakesink.static_pad("sink") {
+                    let _ = pad.link(&sink);
+                }
+            }
+        });
+    }
+    {
+        let convert = convert.downgrade();
+        decodebin.connect_pad_added(move |_, pad| {
+            if let Some(sink) = convert.upgrade().and_then(|c| c.static_pad("sink"))
+                && !sink.is_linked()
+                && let Err(e) = pad.link(&sink)
+            {
+                warn!(?e, "Failed to link decoded audio");
+            }
+        });
+    }
+    {
+        let convert = convert.downgrade();
+        parsebin.connect_no_more_pads(move |_| {
+            if audio_linked.load(Ordering::SeqCst) {
+                return;
+            }
+            info!("Source has no audio track");
+            if let Some(sink) = convert.upgrade().and_then(|c| c.static_pad("sink")) {
+                sink.send_event(gst::event::Eos::new());
+            }
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copies_never_overwrite_earlier_ones() {
+        let dir = std::env::temp_dir().join(format!("stabilize-names-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let first = unused_output_path(&dir, "VID_1", "mp4");
+        assert_eq!(first, dir.join("VID_1_stabilized.mp4"));
+        std::fs::write(&first, b"").unwrap();
+        assert_eq!(
+            unused_output_path(&dir, "VID_1", "mp4"),
+            dir.join("VID_1_stabilized_2.mp4")
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
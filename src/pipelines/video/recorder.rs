@@ -9,21 +9,24 @@
 //! - Quality presets
 
 use super::encoder_selection::{EncoderConfig, select_encoders};
+use super::frame_metadata::FrameMetadataLog;
 use super::muxer::link_audio_to_muxer;
 use super::stats::{
     RECORDING_STATS, RecordingDiagnostics, clear_recording_diagnostics,
     publish_recording_diagnostics,
 };
 use crate::backends::camera::types::{CameraFrame, PixelFormat, RecordingFrame, SensorRotation};
+use crate::errors::{AppError, AppResult, RecordingError};
 use crate::media::encoders::video::SelectedVideoEncoder;
-use crate::pipelines::audio_level::PULSESRC_SLAVE_METHOD;
 use crate::pipelines::audio_level::install_level_sync_handler as install_shared_level_sync_handler;
+use crate::pipelines::audio_level::{PULSESRC_SLAVE_METHOD, install_sync_offset_probe};
 use gstreamer as gst;
 use gstreamer::prelude::*;
 use gstreamer_app as gst_app;
+use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use tracing::{debug, error, info, warn};
 
 // ---------------------------------------------------------------------------
@@ -33,7 +36,9 @@ use tracing::{debug, error, info, warn};
 /// How often to emit periodic progress log messages (every Nth frame).
 const LOG_EVERY_N_FRAMES: u64 = 60;
 
-pub use crate::pipelines::audio_level::{AudioLevels, SharedAudioLevels};
+pub use crate::pipelines::audio_level::{
+    AudioLevels, AudioProcessing, SharedAudioLevels, SharedSyncOffset,
+};
 
 /// Common recording configuration.
 pub struct RecorderConfig<'a> {
@@ -56,14 +61,25 @@ pub struct RecorderConfig<'a> {
     /// accepts the rate, and avoids a GStreamer `audioresample` element.
     /// `0` means "unknown" and falls back to 48 kHz.
     pub audio_source_rate_hz: u32,
+    /// User gain and noise-suppression settings for the audio branch
+    pub audio_processing: AudioProcessing,
+    /// Also record desktop audio (the default output's monitor) as a
+    /// separate track
+    pub system_audio: bool,
     /// Specific encoder info (if None, auto-select)
     pub encoder_info: Option<&'a crate::media::encoders::video::EncoderInfo>,
     /// Sensor rotation to correct video orientation
     pub rotation: SensorRotation,
     /// Mirror the recorded video horizontally (selfie / front-camera mode).
     pub mirror_horizontal: bool,
+    /// Rotation correction left to players, written as the container's
+    /// orientation tag
+    pub orientation: SensorRotation,
     /// Pre-created shared audio levels handle (UI reads this for live meters)
     pub audio_levels: SharedAudioLevels,
+    /// Live microphone sync offset, applied at the muxer and adjustable
+    /// while recording
+    pub audio_sync_offset: SharedSyncOffset,
 }
 
 /// Appsrc-specific recording configuration (libcamera backend).
@@ -79,6 +95,20 @@ pub struct AppsrcRecorderConfig<'a> {
     /// Live filter code (read each frame via AtomicU32, updated by UI thread).
     /// Value is `FilterType::gpu_filter_code()`. 0 = Standard (no filter).
     pub live_filter_code: Arc<std::sync::atomic::AtomicU32>,
+    /// Picture-in-picture secondary camera to composite into each frame
+    pub pip: Option<crate::backends::camera::pip::PipOverlay>,
+    /// Stabilization applied to the camera picture before compositing
+    pub stabilization: crate::config::VideoStabilization,
+    /// Low-light noise reduction, applied ahead of stabilization
+    pub denoise: super::DenoiseSettings,
+    /// Log each frame's capture time and time-of-day timecode to the
+    /// metadata sidecar
+    pub timecode: bool,
+    /// Burn the date and time into the picture, dashcam style
+    pub timestamp_overlay: bool,
+    /// Anamorphic desqueeze: stretch the picture this many times wider
+    /// (1.0 = record it as the camera delivers it)
+    pub desqueeze: f32,
 }
 
 /// Video recorder using the new pipeline architecture
@@ -95,19 +125,36 @@ pub struct VideoRecorder {
     /// it before transitioning the pipeline to NULL, avoiding races where the
     /// detached task pushes into a finalising pipeline.
     pusher_handle: Option<tokio::task::JoinHandle<()>>,
+    /// Per-frame camera metadata logged by the pusher, written as a JSON
+    /// sidecar once the file is finalized (libcamera frames only)
+    frame_metadata: Option<Arc<Mutex<FrameMetadataLog>>>,
 }
 
-/// Map sensor rotation to the GStreamer videoflip `video-direction` value.
-/// Returns None for SensorRotation::None (no rotation needed).
-fn rotation_to_flip_direction(rotation: SensorRotation) -> Option<&'static str> {
-    match rotation {
-        SensorRotation::Rotate90 => Some("90l"),
-        SensorRotation::Rotate180 => Some("180"),
-        SensorRotation::Rotate270 => Some("90r"),
-        SensorRotation::None => None,
-    }
+/// Per-frame GPU work the filtered pusher does before a frame is encoded
+struct PusherEffects {
+    /// Noise reduction of the camera picture, applied first
+    denoise: super::DenoiseSettings,
+    /// Stabilization of the camera picture
+    stabilization: crate::config::VideoStabilization,
+    /// Live filter selection, updated by the UI mid-recording
+    live_filter_code: Arc<std::sync::atomic::AtomicU32>,
+    /// Second camera composited in before the filter
+    pip: Option<crate::backends::camera::pip::PipOverlay>,
+    /// Rotation correction, applied last
+    rotation: SensorRotation,
+    /// Horizontal mirror, applied with the rotation
+    mirror_horizontal: bool,
 }
 
+/// Colorimetry of the RGBA frames pushed into recording pipelines: the
+/// conversion shaders output sRGB.
+pub(super) const RGBA_COLORIMETRY: &str = "sRGB";
+
+/// Colorimetry recordings are encoded with. Set explicitly so videoconvert
+/// does not pick BT.601 or BT.709 by resolution and players, which read it
+/// from the bitstream, show the colors of the preview.
+pub(super) const ENCODE_COLORIMETRY: &str = "bt709";
+
 /// OpenH264 maximum pixel count (roughly 3072x3072).
 const OPENH264_MAX_PIXELS: u32 = 9_437_184;
 
@@ -147,7 +194,7 @@ fn openh264_downscale(base_width: u32, base_height: u32, encoder_name: &str) ->
 }
 
 /// Select encoder set: use a specific encoder if provided, otherwise auto-select.
-fn select_encoder_set(
+pub(super) fn select_encoder_set(
     encoder_info: Option<&crate::media::encoders::video::EncoderInfo>,
     encoder_config: &EncoderConfig,
     enable_audio: bool,
@@ -167,14 +214,25 @@ fn select_encoder_set(
 /// constructor only handles its format-specific pipeline description and
 /// pusher spawn.
 struct RecorderSetup {
-    audio_elements: Option<AudioBranch>,
-    encoder_name: String,
-    parser_str: String,
-    muxer_name: String,
+    /// Microphone and/or desktop-audio branches, one muxer track each
+    audio_branches: Vec<AudioBranch>,
+    video: VideoChain,
     output_path: PathBuf,
     frame_duration_ns: i64,
 }
 
+/// Audio inputs of a recording, bundled for [`prepare_recorder`].
+struct AudioInputs<'a> {
+    /// Record the microphone track
+    microphone: bool,
+    device: Option<&'a str>,
+    source_rate_hz: u32,
+    processing: AudioProcessing,
+    sync_offset: &'a SharedSyncOffset,
+    /// Record desktop audio as a second track
+    system: bool,
+}
+
 /// Common setup for both appsrc recorder constructors.
 ///
 /// Handles encoder selection, V4L2 fallback, audio branch creation,
@@ -184,84 +242,276 @@ struct RecorderSetup {
 fn prepare_recorder(
     encoder_info: Option<&crate::media::encoders::video::EncoderInfo>,
     encoder_config: &EncoderConfig,
-    enable_audio: bool,
-    audio_device: Option<&str>,
-    audio_source_rate_hz: u32,
+    audio: &AudioInputs<'_>,
     output_path: PathBuf,
     framerate: u32,
 ) -> Result<RecorderSetup, String> {
-    let encoders = select_encoder_set(encoder_info, encoder_config, enable_audio)?;
-
-    let audio_elements = if let Some(audio_encoder_config) = encoders.audio {
-        VideoRecorder::create_audio_branch(
-            audio_device,
-            audio_source_rate_hz,
+    let encoders = select_encoder_set(encoder_info, encoder_config, audio.microphone)?;
+
+    let mut audio_branches = Vec::new();
+    if let Some(audio_encoder_config) = encoders.audio {
+        audio_branches.extend(VideoRecorder::create_audio_branch(
+            audio.device,
+            audio.source_rate_hz,
+            audio.processing,
+            audio.sync_offset.clone(),
             audio_encoder_config,
-        )?
-    } else {
-        None
-    };
+        )?);
+    }
+    if audio.system {
+        match super::encoder_selection::select_system_audio_encoder(encoder_config) {
+            Ok(encoder) => {
+                audio_branches.push(VideoRecorder::create_system_audio_branch(encoder)?);
+            }
+            Err(e) => warn!(error = %e, "Recording without desktop audio"),
+        }
+    }
 
     info!(
         video_codec = ?encoders.video.codec,
-        audio = audio_elements.is_some(),
+        audio_tracks = audio_branches.len(),
         container = ?encoders.video.container,
         "Selected encoders"
     );
 
-    let output_path = output_path.with_extension(encoders.video.extension);
+    let video = resolve_video_chain(&encoders.video, encoder_config);
+    let output_path = output_path.with_extension(video.extension);
     let frame_duration_ns = 1_000_000_000i64 / framerate as i64;
 
-    let selected_encoder = encoders
-        .video
+    Ok(RecorderSetup {
+        audio_branches,
+        video,
+        output_path,
+        frame_duration_ns,
+    })
+}
+
+/// Elements that will actually encode the video track
+pub(super) struct VideoChain {
+    pub(super) encoder_name: String,
+    /// Parser with its `! ` link prefix, or empty
+    pub(super) parser_str: String,
+    pub(super) muxer_name: String,
+    /// Output file extension for the muxer's container
+    pub(super) extension: &'static str,
+}
+
+/// Resolve the selected video encoder into the elements the appsrc pipeline
+/// uses, replacing V4L2 encoders and hardware encoders that fail their probe.
+pub(super) fn resolve_video_chain(
+    video: &SelectedVideoEncoder,
+    encoder_config: &EncoderConfig,
+) -> VideoChain {
+    let selected_encoder = video
         .encoder
         .factory()
         .map(|f| f.name().to_string())
         .unwrap_or_else(|| "openh264enc".to_string());
 
-    let (encoder_name, parser_str, muxer_name) = if selected_encoder.starts_with("v4l2") {
+    if selected_encoder.starts_with("v4l2") {
         warn!(
             selected = %selected_encoder,
             "V4L2 encoder not compatible with appsrc pipeline, falling back to openh264enc"
         );
-        (
-            "openh264enc".to_string(),
-            "! h264parse".to_string(),
-            "mp4mux".to_string(),
-        )
+        return VideoChain {
+            encoder_name: "openh264enc".to_string(),
+            parser_str: "! h264parse".to_string(),
+            muxer_name: "mp4mux".to_string(),
+            extension: video.extension,
+        };
+    }
+
+    let (parser_str, muxer_name) = parser_and_muxer_names(video);
+    // Probe hardware encoders to catch cases where the element exists in
+    // the registry but can't actually encode (e.g. VA-API backed by NVENC
+    // in a flatpak sandbox that lacks libnvidia-encode.so).
+    let is_software = selected_encoder == "openh264enc"
+        || selected_encoder == "x264enc"
+        || selected_encoder == "x265enc";
+    if !is_software && !hardware_encoder_works(&selected_encoder) {
+        fallback_video_chain(&selected_encoder, encoder_config)
     } else {
-        let (parser, muxer) = parser_and_muxer_names(&encoders.video);
-        // Probe hardware encoders to catch cases where the element exists in
-        // the registry but can't actually encode (e.g. VA-API backed by NVENC
-        // in a flatpak sandbox that lacks libnvidia-encode.so).
-        let is_software = selected_encoder == "openh264enc"
-            || selected_encoder == "x264enc"
-            || selected_encoder == "x265enc";
-        if !is_software
-            && !crate::media::encoders::detection::probe_single_encoder(&selected_encoder)
-        {
-            warn!(
-                selected = %selected_encoder,
-                "Hardware encoder probe failed, falling back to openh264enc"
-            );
-            (
-                "openh264enc".to_string(),
-                "! h264parse".to_string(),
-                "mp4mux".to_string(),
-            )
-        } else {
-            (selected_encoder, parser, muxer)
+        VideoChain {
+            encoder_name: selected_encoder,
+            parser_str,
+            muxer_name,
+            extension: video.extension,
         }
+    }
+}
+
+/// [`probe_single_encoder`] with the result remembered for the session. The
+/// probe runs a short test pipeline, too slow to repeat on every Record.
+///
+/// [`probe_single_encoder`]: crate::media::encoders::detection::probe_single_encoder
+fn hardware_encoder_works(encoder_name: &str) -> bool {
+    static PROBED: OnceLock<Mutex<HashMap<String, bool>>> = OnceLock::new();
+    let probed = PROBED.get_or_init(Default::default);
+    if let Some(&works) = probed
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(encoder_name)
+    {
+        return works;
+    }
+    let works = crate::media::encoders::detection::probe_single_encoder(encoder_name);
+    probed
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(encoder_name.to_string(), works);
+    works
+}
+
+/// Replacement for a rejected hardware encoder. Switches the output
+/// extension when the fallback codec needs a different container (e.g. AV1
+/// in WebM falling back to H.264 in MP4).
+fn fallback_video_chain(failed: &str, encoder_config: &EncoderConfig) -> VideoChain {
+    use crate::media::encoders::video::{VideoCodec, fallback_video_encoder};
+
+    let (encoder_name, codec) = match fallback_video_encoder(failed) {
+        Some(info) => (info.element_name, info.codec),
+        None => ("openh264enc".to_string(), VideoCodec::H264),
     };
+    warn!(
+        selected = %failed,
+        fallback = %encoder_name,
+        "Hardware encoder probe failed, falling back"
+    );
 
-    Ok(RecorderSetup {
-        audio_elements,
+    let container = encoder_config.container_for(codec);
+    let parser_str = codec
+        .parser_name()
+        .map(|p| format!("! {}", p))
+        .unwrap_or_default();
+    VideoChain {
         encoder_name,
         parser_str,
-        muxer_name,
-        output_path,
-        frame_duration_ns,
-    })
+        muxer_name: container.muxer_name().to_string(),
+        extension: container.extension(),
+    }
+}
+
+/// Video half of the RGBA appsrc recorder, from appsrc through the muxer
+pub(super) struct AppsrcVideoPipeline {
+    /// Pipeline description, without the sink
+    pub(super) desc: String,
+    /// Size fed to the encoder, after any OpenH264 downscale
+    pub(super) encode_width: u32,
+    pub(super) encode_height: u32,
+    pub(super) scaled: bool,
+}
+
+/// Describe the video half of the RGBA appsrc recorder for upright frames
+/// of `base_width` × `base_height`, tagged with the `orientation` players
+/// still have to apply, with the date and time burned in when
+/// `timestamp_overlay` is set, and stretched `desqueeze` times wider.
+pub(super) fn appsrc_video_pipeline(
+    video: &VideoChain,
+    base_width: u32,
+    base_height: u32,
+    framerate: u32,
+    orientation: SensorRotation,
+    timestamp_overlay: bool,
+    desqueeze: f32,
+) -> AppsrcVideoPipeline {
+    let (out_width, out_height) = desqueezed_size(base_width, base_height, desqueeze, orientation);
+    // OpenH264 has a maximum resolution limit — downscale if exceeded
+    let (encode_width, encode_height) =
+        openh264_downscale(out_width, out_height, &video.encoder_name);
+
+    // Only insert videoscale/capsfilter when actually needed. Skipping
+    // them for the common case (no scaling) eliminates software
+    // passthrough elements at 12MP+ resolutions.
+    let scaled = encode_width != base_width || encode_height != base_height;
+
+    // Square pixels: videoscale would otherwise keep the display aspect by
+    // tagging a pixel aspect ratio, undoing a desqueeze in the player
+    let processing_chain = if scaled {
+        format!(
+            "! videoconvert ! videoscale \
+             ! capsfilter caps=video/x-raw,format=I420,width={fw},height={fh},\
+               pixel-aspect-ratio=1/1,framerate={fps}/1,colorimetry={colorimetry} \
+             ! videoconvert",
+            fw = encode_width,
+            fh = encode_height,
+            fps = framerate,
+            colorimetry = ENCODE_COLORIMETRY,
+        )
+    } else {
+        format!("! videoconvert ! capsfilter caps=video/x-raw,colorimetry={ENCODE_COLORIMETRY}")
+    };
+
+    // Always RGBA input: the filtered pusher converts each frame to RGBA
+    // (via GPU compute shader), applies the current filter, and pushes RGBA.
+    // This lets the user toggle filters mid-recording.
+    let desc = format!(
+        "appsrc name=camera-appsrc \
+           caps=video/x-raw,format=RGBA,width={w},height={h},framerate={fps}/1,\
+             colorimetry={RGBA_COLORIMETRY} \
+           is-live=true do-timestamp=false format=time \
+           min-latency={lat} max-latency={lat} \
+         ! queue max-size-buffers=5 max-size-time=1000000000 \
+         {overlay}\
+         {processing} \
+         ! {encoder} name=recording-encoder \
+         {parser} \
+         {tag}\
+         ! {muxer} name=recording-muxer",
+        w = base_width,
+        h = base_height,
+        fps = framerate,
+        lat = 1_000_000_000i64 / framerate as i64,
+        overlay = if timestamp_overlay {
+            timestamp_overlay_desc(base_height)
+        } else {
+            String::new()
+        },
+        processing = processing_chain,
+        encoder = video.encoder_name,
+        parser = video.parser_str,
+        tag = super::muxer::orientation_tag_desc(orientation),
+        muxer = video.muxer_name,
+    );
+
+    AppsrcVideoPipeline {
+        desc,
+        encode_width,
+        encode_height,
+        scaled,
+    }
+}
+
+/// Size of a `width` × `height` frame stretched `desqueeze` times along the
+/// axis players show horizontally, which is its height when the
+/// `orientation` tag turns it a quarter turn. Kept even for the encoder.
+fn desqueezed_size(
+    width: u32,
+    height: u32,
+    desqueeze: f32,
+    orientation: SensorRotation,
+) -> (u32, u32) {
+    if desqueeze <= 1.0 {
+        return (width, height);
+    }
+    let stretch = |length: u32| ((length as f32 * desqueeze / 2.0).round() as u32) * 2;
+    if orientation.swaps_dimensions() {
+        (width, stretch(height))
+    } else {
+        (stretch(width), height)
+    }
+}
+
+/// Pipeline description fragment that burns the date and time into the
+/// bottom-left corner of `height`-line frames, on a shaded band so it stays
+/// readable over any scene
+fn timestamp_overlay_desc(height: u32) -> String {
+    // About 1/30 of the picture height, like a dashcam's stamp
+    let font_size = (height / 30).max(10);
+    format!(
+        "! clockoverlay time-format=\"%Y-%m-%d %H:%M:%S\" \
+           halignment=left valignment=bottom shaded-background=true \
+           font-desc=\"Monospace {font_size}px\" "
+    )
 }
 
 /// Extract parser name (with `! ` prefix) and muxer name from a selected video encoder.
@@ -281,7 +531,7 @@ fn parser_and_muxer_names(video: &SelectedVideoEncoder) -> (String, String) {
 
 /// Read `CLOCK_BOOTTIME` in nanoseconds (same clock domain as libcamera
 /// sensor timestamps).
-fn read_clock_boottime_ns() -> u64 {
+pub fn read_clock_boottime_ns() -> u64 {
     use std::mem::MaybeUninit;
     unsafe {
         let mut ts = MaybeUninit::<libc::timespec>::uninit();
@@ -293,26 +543,14 @@ fn read_clock_boottime_ns() -> u64 {
     }
 }
 
-/// Add audio branch elements to the pipeline, link the chain, connect to
-/// the muxer, and install the level sync handler.
+/// Add audio branch elements to the pipeline, link the chain and connect
+/// it to the muxer as its own track.
 fn add_audio_branch_to_pipeline(
     pipeline: &gst::Pipeline,
     audio_branch: &AudioBranch,
-    audio_levels: &SharedAudioLevels,
 ) -> Result<(), String> {
     pipeline
-        .add_many([
-            &audio_branch.source,
-            &audio_branch.queue,
-            &audio_branch.convert,
-            &audio_branch.resample,
-            &audio_branch.capsfilter,
-            &audio_branch.compressor,
-            &audio_branch.makeup_gain,
-            &audio_branch.limiter,
-            &audio_branch.level,
-            &audio_branch.encoder,
-        ])
+        .add_many(audio_branch.elements())
         .map_err(|e| format!("Failed to add audio elements to pipeline: {}", e))?;
 
     VideoRecorder::link_audio_chain(audio_branch)?;
@@ -322,7 +560,11 @@ fn add_audio_branch_to_pipeline(
         .ok_or("Failed to find recording-muxer for audio linking")?;
     link_audio_to_muxer(&audio_branch.encoder, &muxer)?;
 
-    install_shared_level_sync_handler(pipeline, audio_levels);
+    if let Some(offset) = &audio_branch.sync_offset
+        && let Some(pad) = audio_branch.encoder.static_pad("src")
+    {
+        install_sync_offset_probe(&pad, offset.clone());
+    }
 
     Ok(())
 }
@@ -341,8 +583,28 @@ fn build_recorder_pipeline(
     encoder_config: &EncoderConfig,
     encode_width: u32,
     encode_height: u32,
-    audio_elements: Option<&AudioBranch>,
+    audio_branches: &[AudioBranch],
     audio_levels: &SharedAudioLevels,
+) -> Result<(gst::Pipeline, gst_app::AppSrc), String> {
+    let (pipeline, appsrc) = build_video_pipeline(
+        pipeline_desc,
+        encoder_name,
+        encoder_config,
+        encode_width,
+        encode_height,
+    )?;
+    finish_recorder_pipeline(&pipeline, audio_branches, audio_levels)?;
+    Ok((pipeline, appsrc))
+}
+
+/// Parse the pipeline, extract `camera-appsrc` and configure the encoder.
+/// Audio is added later by [`finish_recorder_pipeline`].
+pub(super) fn build_video_pipeline(
+    pipeline_desc: &str,
+    encoder_name: &str,
+    encoder_config: &EncoderConfig,
+    encode_width: u32,
+    encode_height: u32,
 ) -> Result<(gst::Pipeline, gst_app::AppSrc), String> {
     let pipeline = gst::parse::launch(pipeline_desc)
         .map_err(|e| format!("Failed to parse pipeline: {}", e))?
@@ -364,16 +626,37 @@ fn build_recorder_pipeline(
             encode_height,
             encoder_config.bitrate_override_kbps,
         );
+        if encoder_config.intra_only {
+            crate::media::encoders::video::configure_intra_only(&enc_element, encoder_name);
+        }
     }
 
-    if let Some(audio_branch) = audio_elements {
-        add_audio_branch_to_pipeline(&pipeline, audio_branch, audio_levels)?;
-        info!("Audio branch added to recording pipeline");
+    Ok((pipeline, appsrc))
+}
+
+/// Add the audio branches and install the muxer probes. Runs after every
+/// muxer pad exists, since the probes only cover pads present at the time.
+fn finish_recorder_pipeline(
+    pipeline: &gst::Pipeline,
+    audio_branches: &[AudioBranch],
+    audio_levels: &SharedAudioLevels,
+) -> Result<(), String> {
+    for audio_branch in audio_branches {
+        add_audio_branch_to_pipeline(pipeline, audio_branch)?;
+    }
+    if !audio_branches.is_empty() {
+        // One handler serves every branch; it tells them apart by the
+        // `level` element's name.
+        install_shared_level_sync_handler(pipeline, audio_levels);
+        info!(
+            tracks = audio_branches.len(),
+            "Audio branches added to recording pipeline"
+        );
     }
 
-    install_muxer_fixup_probes(&pipeline);
+    install_muxer_fixup_probes(pipeline);
 
-    Ok((pipeline, appsrc))
+    Ok(())
 }
 
 /// Result of PTS computation for a single frame.
@@ -553,6 +836,51 @@ pub(crate) async fn convert_frame_to_rgba(frame: &CameraFrame) -> Result<Vec<u8>
         .map_err(|e| format!("Failed to read RGBA from GPU: {}", e))
 }
 
+/// Copy a P010 or Y210 frame into the tightly packed layout of its
+/// GStreamer caps, dropping row padding. `None` for other formats or a
+/// truncated buffer.
+fn pack_10bit_frame(frame: &CameraFrame) -> Option<Vec<u8>> {
+    let (width, height) = (frame.width as usize, frame.height as usize);
+    let data: &[u8] = &frame.data;
+    let copy_rows = |out: &mut Vec<u8>, offset: usize, stride: usize, row: usize, rows: usize| {
+        for y in 0..rows {
+            let start = offset + y * stride;
+            out.extend_from_slice(data.get(start..start + row)?);
+        }
+        Some(())
+    };
+
+    match frame.format {
+        PixelFormat::P010 => {
+            let planes = frame.yuv_planes?;
+            let row = width * 2;
+            let mut out = Vec::with_capacity(row * height * 3 / 2);
+            copy_rows(
+                &mut out,
+                planes.y_offset,
+                frame.stride as usize,
+                row,
+                height,
+            )?;
+            copy_rows(
+                &mut out,
+                planes.uv_offset,
+                planes.uv_stride as usize,
+                width.div_ceil(2) * 4,
+                height.div_ceil(2),
+            )?;
+            Some(out)
+        }
+        PixelFormat::Y210 => {
+            let row = width * 4;
+            let mut out = Vec::with_capacity(row * height);
+            copy_rows(&mut out, 0, frame.stride as usize, row, height)?;
+            Some(out)
+        }
+        _ => None,
+    }
+}
+
 /// Frame data prepared by a format-specific closure for the common pusher loop.
 struct PusherFrame {
     buffer: gst::Buffer,
@@ -671,6 +999,21 @@ impl VideoRecorder {
     pub fn new_from_appsrc(
         config: AppsrcRecorderConfig<'_>,
         frame_rx: tokio::sync::mpsc::Receiver<RecordingFrame>,
+    ) -> AppResult<Self> {
+        let recorder = Self::build_from_appsrc(config, frame_rx)?;
+
+        // Eagerly start: if a hardware encoder fails (e.g. VA-API backed by
+        // NVENC in a flatpak sandbox), return Err so the caller can retry.
+        // As this is the last fallback, its error is the one the user sees.
+        recorder.start()?;
+
+        Ok(recorder)
+    }
+
+    /// Build the pipeline for [`Self::new_from_appsrc`], unstarted
+    fn build_from_appsrc(
+        config: AppsrcRecorderConfig<'_>,
+        frame_rx: tokio::sync::mpsc::Receiver<RecordingFrame>,
     ) -> Result<Self, String> {
         let AppsrcRecorderConfig {
             base:
@@ -683,13 +1026,23 @@ impl VideoRecorder {
                     enable_audio,
                     audio_device,
                     audio_source_rate_hz,
+                    audio_processing,
+                    system_audio,
                     encoder_info,
                     rotation,
                     mirror_horizontal,
+                    orientation,
                     audio_levels,
+                    audio_sync_offset,
                 },
             pixel_format,
             live_filter_code,
+            pip,
+            stabilization,
+            denoise,
+            timecode,
+            timestamp_overlay,
+            desqueeze,
         } = config;
 
         // Always use the filtered (RGBA) pipeline so the user can toggle
@@ -707,6 +1060,7 @@ impl VideoRecorder {
             audio_device = ?audio_device,
             rotation = %rotation,
             mirror_horizontal,
+            orientation = %orientation,
             "Creating appsrc-based video recorder (libcamera backend)"
         );
 
@@ -719,9 +1073,14 @@ impl VideoRecorder {
         let setup = prepare_recorder(
             encoder_info,
             &encoder_config,
-            enable_audio,
-            audio_device,
-            audio_source_rate_hz,
+            &AudioInputs {
+                microphone: enable_audio,
+                device: audio_device,
+                source_rate_hz: audio_source_rate_hz,
+                processing: audio_processing,
+                sync_offset: &audio_sync_offset,
+                system: system_audio,
+            },
             output_path,
             framerate,
         )?;
@@ -732,115 +1091,104 @@ impl VideoRecorder {
             (width, height)
         };
 
-        // Inverse rotation: sensor mounting angle → correction direction.
-        // Optionally chained with a horizontal mirror flip for front-camera mode.
-        let rotation_flip = rotation_to_flip_direction(rotation)
-            .map(|dir| format!("! videoflip video-direction={dir}"))
-            .unwrap_or_default();
-        let mirror_flip = if mirror_horizontal {
-            "! videoflip video-direction=horiz"
-        } else {
-            ""
-        };
-        let flip_str = format!("{rotation_flip} {mirror_flip}").trim().to_string();
-
-        // OpenH264 has a maximum resolution limit — downscale if exceeded
-        let (final_width, final_height) =
-            openh264_downscale(base_width, base_height, &setup.encoder_name);
-
-        // Only insert videoconvert/videoscale/capsfilter when actually needed.
-        // Skipping these for the common case (no rotation, no scaling, I420 input)
-        // eliminates ~3 software passthrough elements at 12MP+ resolutions.
-        let needs_rotation = !flip_str.is_empty();
-        let needs_scaling = final_width != base_width || final_height != base_height;
-
-        // Always use RGBA input: the filtered pusher converts each frame to RGBA
-        // (via GPU compute shader), applies the current filter, and pushes RGBA.
-        // This lets the user toggle filters mid-recording.
-        let initial_gst_format = "RGBA";
-
-        let processing_chain = if needs_rotation || needs_scaling {
-            format!(
-                "! videoconvert {flip} ! videoscale \
-                 ! capsfilter caps=video/x-raw,format=I420,width={fw},height={fh},framerate={fps}/1 \
-                 ! videoconvert",
-                flip = flip_str,
-                fw = final_width,
-                fh = final_height,
-                fps = framerate,
-            )
-        } else {
-            "! videoconvert".to_string()
-        };
+        // Rotation correction and the front-camera mirror happen in the
+        // pusher's GPU pass, so appsrc already carries upright frames.
+        let needs_rotation = rotation != SensorRotation::None || mirror_horizontal;
 
-        let pipeline_desc = format!(
-            "appsrc name=camera-appsrc \
-               caps=video/x-raw,format={fmt},width={w},height={h},framerate={fps}/1 \
-               is-live=true do-timestamp=false format=time \
-               min-latency={lat} max-latency={lat} \
-             ! queue max-size-buffers=5 max-size-time=1000000000 \
-             {processing} \
-             ! {encoder} name=recording-encoder \
-             {parser} \
-             ! {muxer} name=recording-muxer \
-             ! filesink location={loc}",
-            fmt = initial_gst_format,
-            w = width,
-            h = height,
-            fps = framerate,
-            lat = setup.frame_duration_ns,
-            processing = processing_chain,
-            encoder = setup.encoder_name,
-            parser = setup.parser_str,
-            muxer = setup.muxer_name,
-            loc = setup.output_path.display(),
+        let video = appsrc_video_pipeline(
+            &setup.video,
+            base_width,
+            base_height,
+            framerate,
+            orientation,
+            timestamp_overlay,
+            desqueeze,
         );
+        let (final_width, final_height) = (video.encode_width, video.encode_height);
+        let needs_scaling = video.scaled;
 
-        info!(desc = %pipeline_desc, "Launching appsrc pipeline");
+        let pipeline_desc = format!(
+            "{} ! filesink name=recording-sink location={}",
+            video.desc,
+            setup.output_path.display()
+        );
 
-        let (pipeline, appsrc) = build_recorder_pipeline(
-            &pipeline_desc,
-            &setup.encoder_name,
-            &encoder_config,
-            final_width,
-            final_height,
-            setup.audio_elements.as_ref(),
-            &audio_levels,
-        )?;
+        // A pipeline pre-built for these settings only lacks its output file
+        // and audio; otherwise build one now.
+        let (pipeline, appsrc) = match super::warmup::take(&video.desc, &encoder_config) {
+            Some((pipeline, appsrc)) => {
+                info!(desc = %pipeline_desc, "Using pre-built appsrc pipeline");
+                let finished = pipeline
+                    .by_name("recording-sink")
+                    .ok_or_else(|| "Failed to find recording-sink in pipeline".to_string())
+                    .and_then(|sink| {
+                        sink.set_property("location", setup.output_path.display().to_string());
+                        finish_recorder_pipeline(&pipeline, &setup.audio_branches, &audio_levels)
+                    });
+                if let Err(e) = finished {
+                    let _ = pipeline.set_state(gst::State::Null);
+                    return Err(e);
+                }
+                (pipeline, appsrc)
+            }
+            None => {
+                info!(desc = %pipeline_desc, "Launching appsrc pipeline");
+                build_recorder_pipeline(
+                    &pipeline_desc,
+                    &setup.video.encoder_name,
+                    &encoder_config,
+                    final_width,
+                    final_height,
+                    &setup.audio_branches,
+                    &audio_levels,
+                )?
+            }
+        };
 
         info!(
             initial_filter = initial_filter_code,
             "Pusher will apply live GPU filter (RGBA output)"
         );
-        let pusher_handle =
-            Self::spawn_filtered_pusher(appsrc, frame_rx, framerate, live_filter_code);
+        let frame_metadata = Arc::new(Mutex::new(FrameMetadataLog::new(framerate)));
+        let pusher_handle = Self::spawn_filtered_pusher(
+            appsrc,
+            frame_rx,
+            (width, height),
+            framerate,
+            PusherEffects {
+                denoise,
+                stabilization,
+                live_filter_code,
+                pip,
+                rotation,
+                mirror_horizontal,
+            },
+            Arc::clone(&frame_metadata),
+            timecode,
+        );
 
         // Publish diagnostics for the insights drawer
-        let mode = if needs_rotation || needs_scaling {
-            "Filtered RGBA (videoconvert + rotation/scale)"
-        } else {
-            "Filtered RGBA (videoconvert)"
+        let mode = match (needs_rotation, needs_scaling) {
+            (true, true) => "Filtered RGBA (GPU rotation + videoconvert/scale)",
+            (true, false) => "Filtered RGBA (GPU rotation + videoconvert)",
+            (false, true) => "Filtered RGBA (videoconvert + scale)",
+            (false, false) => "Filtered RGBA (videoconvert)",
         };
         publish_recording_diagnostics(RecordingDiagnostics {
             mode: mode.to_string(),
             pipeline_string: pipeline_desc.clone(),
-            encoder: setup.encoder_name.clone(),
+            encoder: setup.video.encoder_name.clone(),
             resolution: format!("{}x{}", final_width, final_height),
             framerate,
         });
 
-        let recorder = VideoRecorder {
+        Ok(VideoRecorder {
             pipeline,
             file_path: setup.output_path,
             _pulse_volume_guard: pulse_volume_guard,
             pusher_handle: Some(pusher_handle),
-        };
-
-        // Eagerly start: if a hardware encoder fails (e.g. VA-API backed by
-        // NVENC in a flatpak sandbox), return Err so the caller can retry.
-        recorder.start()?;
-
-        Ok(recorder)
+            frame_metadata: Some(frame_metadata),
+        })
     }
 
     /// Spawn the legacy (decoded-frame) pusher task.
@@ -851,13 +1199,30 @@ impl VideoRecorder {
     /// Reads the current filter code from `live_filter_code` each frame so
     /// filter changes during recording are reflected in the output file.
     /// When filter code is 0 (Standard), the RGBA data is pushed without
-    /// running the filter shader.
+    /// running the filter shader. With denoise or stabilization on, the
+    /// camera picture is denoised and stabilized first; with `pip` set, the secondary camera is
+    /// composited in before the filter. Frames that don't match the camera
+    /// `size` (a standby camera took over) are letterboxed to it, then
+    /// rotated upright to match the appsrc caps. The libcamera metadata of
+    /// each pushed frame is logged to `frame_metadata`, with its capture
+    /// time when `timecode` is set.
     fn spawn_filtered_pusher(
         appsrc: gst_app::AppSrc,
         mut frame_rx: tokio::sync::mpsc::Receiver<RecordingFrame>,
+        size: (u32, u32),
         framerate: u32,
-        live_filter_code: Arc<std::sync::atomic::AtomicU32>,
+        effects: PusherEffects,
+        frame_metadata: Arc<Mutex<FrameMetadataLog>>,
+        timecode: bool,
     ) -> tokio::task::JoinHandle<()> {
+        let PusherEffects {
+            denoise,
+            stabilization,
+            live_filter_code,
+            pip,
+            rotation,
+            mirror_horizontal,
+        } = effects;
         tokio::spawn(async move {
             let initial = live_filter_code.load(std::sync::atomic::Ordering::Relaxed);
             info!(
@@ -878,6 +1243,18 @@ impl VideoRecorder {
             let frame_duration_ns = 1_000_000_000u64 / framerate as u64;
             let mut pipeline_playing = false;
             let mut ts_offset: Option<(u64, u64)> = None;
+            // Frames are timed against the wall clock from here
+            let wall_clock_anchor = (start_time, chrono::Local::now());
+            let mut denoiser = if denoise.is_enabled() {
+                Some(super::denoise::VideoDenoiser::new(denoise).await)
+            } else {
+                None
+            };
+            let mut stabilizer = if stabilization != crate::config::VideoStabilization::Off {
+                Some(super::stabilization::VideoStabilizer::new(stabilization).await)
+            } else {
+                None
+            };
 
             while let Some(rec_frame) = frame_rx.recv().await {
                 let frame = match rec_frame {
@@ -898,12 +1275,34 @@ impl VideoRecorder {
                     }
                 };
 
+                // Denoise ahead of stabilization, so motion is measured on
+                // the cleaner picture
+                let rgba = match denoiser.as_mut() {
+                    Some(denoiser) => denoiser.denoise(rgba, frame.width, frame.height).await,
+                    None => rgba,
+                };
+
+                // Stabilize the camera picture alone, so the inset and the
+                // letterbox bars stay put
+                let rgba = match stabilizer.as_mut() {
+                    Some(stabilizer) => stabilizer.stabilize(rgba, frame.width, frame.height).await,
+                    None => rgba,
+                };
+
+                // Composite the picture-in-picture inset first so the filter
+                // grades the whole output, as on the virtual camera
+                let rgba = match &pip {
+                    Some(pip) => pip.composite(rgba, frame.width, frame.height).await,
+                    None => rgba,
+                };
+
                 // Read current filter from shared atomic (UI thread updates this)
                 let filter_code = live_filter_code.load(std::sync::atomic::Ordering::Relaxed);
                 let filter_type = crate::app::FilterType::from_gpu_filter_code(filter_code);
 
-                // Apply GPU filter (skip for Standard — just use the RGBA as-is)
-                let filtered = if filter_type == crate::app::FilterType::Standard {
+                // Apply GPU filter (skip for Standard without white balance —
+                // just use the RGBA as-is)
+                let filtered = if !crate::shaders::needs_filter_pass(filter_type) {
                     rgba
                 } else {
                     match crate::shaders::apply_filter_gpu_rgba(
@@ -922,6 +1321,39 @@ impl VideoRecorder {
                     }
                 };
 
+                let filtered = if (frame.width, frame.height) == size {
+                    filtered
+                } else {
+                    crate::media::formats::conversions::letterbox_rgba(
+                        &filtered,
+                        frame.width,
+                        frame.height,
+                        size.0,
+                        size.1,
+                    )
+                };
+
+                let filtered = if rotation != SensorRotation::None || mirror_horizontal {
+                    match crate::shaders::rotate_rgba_gpu(
+                        &filtered,
+                        size.0,
+                        size.1,
+                        rotation,
+                        mirror_horizontal,
+                    )
+                    .await
+                    {
+                        Ok((rotated, _, _)) => rotated,
+                        Err(e) => {
+                            // An unrotated frame wouldn't match the caps
+                            warn!(error = %e, "Failed to rotate frame, skipping");
+                            continue;
+                        }
+                    }
+                } else {
+                    filtered
+                };
+
                 RECORDING_STATS
                     .last_convert_time_us
                     .store(t0.elapsed().as_micros() as u64, Ordering::Relaxed);
@@ -952,6 +1384,14 @@ impl VideoRecorder {
                     break;
                 }
 
+                let captured = timecode.then(|| {
+                    super::frame_metadata::wall_clock_at(frame.captured_at, wall_clock_anchor)
+                });
+                frame_metadata
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push(pts_ns, frame.libcamera_metadata.as_ref(), captured);
+
                 RECORDING_STATS
                     .pusher_pushed
                     .fetch_add(1, Ordering::Relaxed);
@@ -1005,13 +1445,24 @@ impl VideoRecorder {
                     enable_audio,
                     audio_device,
                     audio_source_rate_hz,
+                    audio_processing,
+                    system_audio,
                     encoder_info,
                     rotation: _,
                     mirror_horizontal,
+                    orientation,
                     audio_levels,
+                    audio_sync_offset,
                 },
             pixel_format: _,
             live_filter_code,
+            pip,
+            stabilization,
+            denoise,
+            // The caller only takes this path with all three off
+            timecode: _,
+            timestamp_overlay: _,
+            desqueeze: _,
         } = config;
 
         if live_filter_code.load(std::sync::atomic::Ordering::Relaxed) != 0 {
@@ -1019,6 +1470,24 @@ impl VideoRecorder {
                 "VA-API JPEG pipeline does not support filters; falling back to legacy".to_string(),
             );
         }
+        if pip.is_some() {
+            return Err(
+                "VA-API JPEG pipeline does not support picture-in-picture; falling back to legacy"
+                    .to_string(),
+            );
+        }
+        if stabilization != crate::config::VideoStabilization::Off {
+            return Err(
+                "VA-API JPEG pipeline does not support stabilization; falling back to legacy"
+                    .to_string(),
+            );
+        }
+        if denoise.is_enabled() {
+            return Err(
+                "VA-API JPEG pipeline does not support denoising; falling back to legacy"
+                    .to_string(),
+            );
+        }
 
         info!(
             width,
@@ -1038,9 +1507,14 @@ impl VideoRecorder {
         let mut setup = prepare_recorder(
             encoder_info,
             &encoder_config,
-            enable_audio,
-            audio_device,
-            audio_source_rate_hz,
+            &AudioInputs {
+                microphone: enable_audio,
+                device: audio_device,
+                source_rate_hz: audio_source_rate_hz,
+                processing: audio_processing,
+                sync_offset: &audio_sync_offset,
+                system: system_audio,
+            },
             output_path,
             framerate,
         )?;
@@ -1050,28 +1524,33 @@ impl VideoRecorder {
         //   nvjpegdec (CUDA memory)   → nvh265enc/nvh264enc (CUDA memory)
         //   vajpegdec (VA-API memory) → vah265enc/vah264enc (VA-API memory)
         let is_nvidia_decoder = va_jpeg_dec.starts_with("nv");
-        if is_nvidia_decoder && !setup.encoder_name.starts_with("nv") {
+        if is_nvidia_decoder && !setup.video.encoder_name.starts_with("nv") {
             use crate::media::encoders::detection::probe_single_encoder;
+            use crate::media::encoders::video::VideoCodec;
             if probe_single_encoder("nvh265enc") {
                 warn!(
                     decoder = va_jpeg_dec,
-                    selected = %setup.encoder_name,
+                    selected = %setup.video.encoder_name,
                     override_to = "nvh265enc",
                     "Overriding encoder to match NVIDIA decoder memory domain"
                 );
-                setup.encoder_name = "nvh265enc".to_string();
-                setup.parser_str = "! h265parse".to_string();
-                setup.muxer_name = "mp4mux".to_string();
+                setup.video.encoder_name = "nvh265enc".to_string();
+                setup.video.parser_str = "! h265parse".to_string();
+                let container = encoder_config.container_for(VideoCodec::HEVC);
+                setup.video.muxer_name = container.muxer_name().to_string();
+                setup.output_path.set_extension(container.extension());
             } else if probe_single_encoder("nvh264enc") {
                 warn!(
                     decoder = va_jpeg_dec,
-                    selected = %setup.encoder_name,
+                    selected = %setup.video.encoder_name,
                     override_to = "nvh264enc",
                     "Overriding encoder to match NVIDIA decoder memory domain"
                 );
-                setup.encoder_name = "nvh264enc".to_string();
-                setup.parser_str = "! h264parse".to_string();
-                setup.muxer_name = "mp4mux".to_string();
+                setup.video.encoder_name = "nvh264enc".to_string();
+                setup.video.parser_str = "! h264parse".to_string();
+                let container = encoder_config.container_for(VideoCodec::H264);
+                setup.video.muxer_name = container.muxer_name().to_string();
+                setup.output_path.set_extension(container.extension());
             } else {
                 warn!(
                     decoder = va_jpeg_dec,
@@ -1100,6 +1579,7 @@ impl VideoRecorder {
              {mirror}\
              ! {encoder} name=recording-encoder \
              {parser} \
+             {tag}\
              ! {muxer} name=recording-muxer \
              ! filesink location={loc}",
             w = width,
@@ -1108,25 +1588,26 @@ impl VideoRecorder {
             lat = setup.frame_duration_ns,
             decoder = va_jpeg_dec,
             mirror = mirror_str,
-            encoder = setup.encoder_name,
-            parser = setup.parser_str,
-            muxer = setup.muxer_name,
+            encoder = setup.video.encoder_name,
+            parser = setup.video.parser_str,
+            tag = super::muxer::orientation_tag_desc(orientation),
+            muxer = setup.video.muxer_name,
             loc = setup.output_path.display(),
         );
 
         info!(desc = %pipeline_desc, "Launching JPEG zero-copy pipeline");
 
-        if setup.audio_elements.is_some() {
+        if !setup.audio_branches.is_empty() {
             info!("A/V sync: audio branch active, video PTS compensated in compute_pts");
         }
 
         let (pipeline, appsrc) = build_recorder_pipeline(
             &pipeline_desc,
-            &setup.encoder_name,
+            &setup.video.encoder_name,
             &encoder_config,
             width,
             height,
-            setup.audio_elements.as_ref(),
+            &setup.audio_branches,
             &audio_levels,
         )?;
 
@@ -1138,12 +1619,16 @@ impl VideoRecorder {
             install_pts_trace_probe(&enc_element, "encoder-out");
         }
 
-        let pusher_handle = Self::spawn_appsrc_jpeg_pusher(appsrc, frame_rx, framerate);
+        let pusher_handle =
+            Self::spawn_appsrc_jpeg_pusher(appsrc, frame_rx, (width, height), framerate);
 
         publish_recording_diagnostics(RecordingDiagnostics {
-            mode: format!("JPEG zero-copy ({} → {})", va_jpeg_dec, setup.encoder_name),
+            mode: format!(
+                "JPEG zero-copy ({} → {})",
+                va_jpeg_dec, setup.video.encoder_name
+            ),
             pipeline_string: pipeline_desc.clone(),
-            encoder: setup.encoder_name.clone(),
+            encoder: setup.video.encoder_name.clone(),
             resolution: format!("{}x{}", width, height),
             framerate,
         });
@@ -1153,6 +1638,7 @@ impl VideoRecorder {
             file_path: setup.output_path,
             _pulse_volume_guard: pulse_volume_guard,
             pusher_handle: Some(pusher_handle),
+            frame_metadata: None,
         };
 
         // Eagerly start the pipeline so failures (e.g. NVIDIA encoder not
@@ -1169,6 +1655,7 @@ impl VideoRecorder {
     fn spawn_appsrc_jpeg_pusher(
         appsrc: gst_app::AppSrc,
         frame_rx: tokio::sync::mpsc::Receiver<RecordingFrame>,
+        size: (u32, u32),
         framerate: u32,
     ) -> tokio::task::JoinHandle<()> {
         spawn_pusher(
@@ -1176,7 +1663,11 @@ impl VideoRecorder {
             frame_rx,
             framerate,
             "JPEG recorder",
-            |rec_frame, _appsrc| match rec_frame {
+            move |rec_frame, _appsrc| match rec_frame {
+                // The JPEG caps are fixed at start; a standby camera with a
+                // different size can't be decoded, so the file holds the last
+                // frame until matching frames arrive again
+                RecordingFrame::Jpeg { width, height, .. } if (width, height) != size => None,
                 RecordingFrame::Jpeg {
                     data,
                     sensor_timestamp_ns,
@@ -1192,6 +1683,225 @@ impl VideoRecorder {
         )
     }
 
+    /// Create an HDR passthrough recording pipeline.
+    ///
+    /// 10-bit P010/Y210 frames go to the encoder as they are, with BT.2100
+    /// colorimetry in the caps, so the file keeps the PQ or HLG signal
+    /// instead of the tone-mapped SDR image the filtered pipeline records.
+    ///
+    /// Fails when the encoder has no 10-bit input or an effect is enabled
+    /// (caller should retry with the legacy `new_from_appsrc` path).
+    pub fn new_from_appsrc_hdr(
+        config: AppsrcRecorderConfig<'_>,
+        transfer: crate::shaders::HdrTransfer,
+        frame_rx: tokio::sync::mpsc::Receiver<RecordingFrame>,
+    ) -> Result<Self, String> {
+        let AppsrcRecorderConfig {
+            base:
+                RecorderConfig {
+                    width,
+                    height,
+                    framerate,
+                    output_path,
+                    encoder_config,
+                    enable_audio,
+                    audio_device,
+                    audio_source_rate_hz,
+                    audio_processing,
+                    system_audio,
+                    encoder_info,
+                    // The caller only takes this path without rotation,
+                    // mirroring or overlays
+                    rotation: _,
+                    mirror_horizontal: _,
+                    orientation,
+                    audio_levels,
+                    audio_sync_offset,
+                },
+            pixel_format,
+            live_filter_code,
+            pip,
+            stabilization,
+            denoise,
+            timecode: _,
+            timestamp_overlay: _,
+            desqueeze: _,
+        } = config;
+
+        let Some(colorimetry) = transfer.gst_colorimetry() else {
+            return Err("HDR pipeline needs an HDR transfer".to_string());
+        };
+        if !pixel_format.is_10bit() {
+            return Err(format!(
+                "HDR pipeline does not support {:?}; falling back to legacy",
+                pixel_format
+            ));
+        }
+        if live_filter_code.load(std::sync::atomic::Ordering::Relaxed) != 0 {
+            return Err(
+                "HDR pipeline does not support filters; falling back to legacy".to_string(),
+            );
+        }
+        if pip.is_some() {
+            return Err(
+                "HDR pipeline does not support picture-in-picture; falling back to legacy"
+                    .to_string(),
+            );
+        }
+        if stabilization != crate::config::VideoStabilization::Off {
+            return Err(
+                "HDR pipeline does not support stabilization; falling back to legacy".to_string(),
+            );
+        }
+        if denoise.is_enabled() {
+            return Err(
+                "HDR pipeline does not support denoising; falling back to legacy".to_string(),
+            );
+        }
+
+        let pulse_volume_guard = build_pulse_volume_guard(enable_audio, audio_device);
+
+        let setup = prepare_recorder(
+            encoder_info,
+            &encoder_config,
+            &AudioInputs {
+                microphone: enable_audio,
+                device: audio_device,
+                source_rate_hz: audio_source_rate_hz,
+                processing: audio_processing,
+                sync_offset: &audio_sync_offset,
+                system: system_audio,
+            },
+            output_path,
+            framerate,
+        )?;
+
+        if !crate::media::encoders::detection::accepts_10bit_input(&setup.video.encoder_name) {
+            return Err(format!(
+                "{} has no 10-bit input; recording HDR tone-mapped to SDR",
+                setup.video.encoder_name
+            ));
+        }
+
+        info!(
+            width,
+            height,
+            framerate,
+            format = ?pixel_format,
+            ?transfer,
+            encoder = %setup.video.encoder_name,
+            output = %setup.output_path.display(),
+            "Creating HDR passthrough recording pipeline"
+        );
+
+        let pipeline_desc = format!(
+            "appsrc name=camera-appsrc \
+               caps=video/x-raw,format={format},width={w},height={h},framerate={fps}/1,colorimetry={colorimetry} \
+               is-live=true do-timestamp=false format=time \
+               min-latency={lat} max-latency={lat} \
+             ! queue max-size-buffers=30 max-size-time=3000000000 \
+             ! videoconvert \
+             ! {encoder} name=recording-encoder \
+             {parser} \
+             {tag}\
+             ! {muxer} name=recording-muxer \
+             ! filesink location={loc}",
+            format = pixel_format.to_gst_format_string(),
+            w = width,
+            h = height,
+            fps = framerate,
+            lat = setup.frame_duration_ns,
+            encoder = setup.video.encoder_name,
+            parser = setup.video.parser_str,
+            tag = super::muxer::orientation_tag_desc(orientation),
+            muxer = setup.video.muxer_name,
+            loc = setup.output_path.display(),
+        );
+
+        info!(desc = %pipeline_desc, "Launching HDR passthrough pipeline");
+
+        let (pipeline, appsrc) = build_recorder_pipeline(
+            &pipeline_desc,
+            &setup.video.encoder_name,
+            &encoder_config,
+            width,
+            height,
+            &setup.audio_branches,
+            &audio_levels,
+        )?;
+
+        if let Some(enc_element) = pipeline.by_name("recording-encoder") {
+            install_pts_trace_probe(&enc_element, "encoder-out");
+        }
+
+        let pusher_handle = Self::spawn_appsrc_hdr_pusher(
+            appsrc,
+            frame_rx,
+            pixel_format,
+            (width, height),
+            framerate,
+        );
+
+        publish_recording_diagnostics(RecordingDiagnostics {
+            mode: format!(
+                "HDR passthrough ({} {} → {})",
+                pixel_format.to_gst_format_string(),
+                colorimetry,
+                setup.video.encoder_name
+            ),
+            pipeline_string: pipeline_desc.clone(),
+            encoder: setup.video.encoder_name.clone(),
+            resolution: format!("{}x{}", width, height),
+            framerate,
+        });
+
+        let recorder = VideoRecorder {
+            pipeline,
+            file_path: setup.output_path,
+            _pulse_volume_guard: pulse_volume_guard,
+            pusher_handle: Some(pusher_handle),
+            frame_metadata: None,
+        };
+
+        // Start now so an encoder that rejects the 10-bit caps is caught
+        // here and the caller can fall back to the legacy path
+        recorder.start()?;
+
+        Ok(recorder)
+    }
+
+    /// Spawn the HDR passthrough pusher task.
+    ///
+    /// Packs 10-bit frames into tight rows via [`spawn_pusher`].
+    fn spawn_appsrc_hdr_pusher(
+        appsrc: gst_app::AppSrc,
+        frame_rx: tokio::sync::mpsc::Receiver<RecordingFrame>,
+        format: PixelFormat,
+        size: (u32, u32),
+        framerate: u32,
+    ) -> tokio::task::JoinHandle<()> {
+        spawn_pusher(
+            appsrc,
+            frame_rx,
+            framerate,
+            "HDR recorder",
+            move |rec_frame, _appsrc| match rec_frame {
+                // The caps are fixed at start, as with JPEG
+                RecordingFrame::Decoded(frame)
+                    if frame.format == format && (frame.width, frame.height) == size =>
+                {
+                    let data = pack_10bit_frame(&frame)?;
+                    Some(PusherFrame {
+                        buffer: gst::Buffer::from_mut_slice(data),
+                        sensor_ts: frame.sensor_timestamp_ns,
+                        sequence: None,
+                    })
+                }
+                _ => None,
+            },
+        )
+    }
+
     /// Create audio branch elements
     ///
     /// Uses `pulsesrc` (PipeWire's PulseAudio compatibility layer) for reliable
@@ -1207,6 +1917,8 @@ impl VideoRecorder {
     fn create_audio_branch(
         audio_device: Option<&str>,
         audio_source_rate_hz: u32,
+        audio_processing: AudioProcessing,
+        sync_offset: SharedSyncOffset,
         audio_encoder_config: crate::media::encoders::audio::SelectedAudioEncoder,
     ) -> Result<Option<AudioBranch>, String> {
         let mut source_builder = gst::ElementFactory::make("pulsesrc")
@@ -1267,8 +1979,8 @@ impl VideoRecorder {
         // returns the source's native rate when Opus accepts it (no resampling
         // anywhere); otherwise 48 kHz, in which case either PulseAudio resamples
         // internally or the `audioresample` element above picks up the slack.
-        let target_rate =
-            crate::media::encoders::audio::opus_target_rate(audio_source_rate_hz) as i32;
+        // Noise suppression pins 48 kHz, the rate both suppressors run at.
+        let target_rate = audio_processing.target_rate(audio_source_rate_hz) as i32;
         let capsfilter = gst::ElementFactory::make("capsfilter")
             .property(
                 "caps",
@@ -1285,6 +1997,25 @@ impl VideoRecorder {
             "Audio capsfilter rate negotiated"
         );
 
+        // Optional noise suppressor ahead of the dynamics chain, so the
+        // compressor and makeup gain don't lift the noise floor along with
+        // quiet speech. Built as a bin because each suppressor needs its
+        // own `audioconvert` pair to reach its preferred sample format.
+        let noise_suppression = match audio_processing.noise_suppressor() {
+            Some(desc) => {
+                info!(desc, "Enabling audio noise suppression");
+                let bin = gst::parse::bin_from_description(desc, true)
+                    .map_err(|e| format!("Failed to create noise suppressor: {}", e))?;
+                Some(bin.upcast::<gst::Element>())
+            }
+            None => {
+                if audio_processing.noise_suppression {
+                    warn!("Noise suppression requested but no suppressor plugin is installed");
+                }
+                None
+            }
+        };
+
         // Soft-knee downward compressor — squashes loud peaks so the following
         // makeup-gain stage can lift quiet content without clipping. Without
         // this, on-camera mics with low sensitivity (USB webcams) or PA
@@ -1315,9 +2046,10 @@ impl VideoRecorder {
         // `PulseSourceVolumeGuard` already boosting PA to 100%, +6 dB sits
         // safely below the brick-wall limiter that follows; on platforms
         // without `pactl` (so PA is wherever the user left it) +6 dB still
-        // provides a noticeable lift.
+        // provides a noticeable lift. The user's gain setting scales this
+        // in dB; the limiter below keeps the boosted range from clipping.
         let makeup_gain = gst::ElementFactory::make("volume")
-            .property("volume", dynamics::MAKEUP_GAIN)
+            .property("volume", audio_processing.makeup_volume())
             .build()
             .map_err(|e| format!("Failed to create makeup-gain element: {}", e))?;
 
@@ -1351,36 +2083,101 @@ impl VideoRecorder {
             convert,
             resample,
             capsfilter,
-            compressor,
-            makeup_gain,
-            limiter,
+            noise_suppression,
+            compressor: Some(compressor),
+            makeup_gain: Some(makeup_gain),
+            limiter: Some(limiter),
             level,
             encoder,
+            sync_offset: Some(sync_offset),
         }))
     }
 
+    /// Create the desktop-audio branch: the default output's monitor,
+    /// recorded in stereo as its own track.
+    ///
+    /// Desktop audio is already mixed and mastered, so it skips the
+    /// microphone's noise suppression and dynamics chain; the level meter
+    /// is named `audio-level-system` so the UI can show it separately.
+    fn create_system_audio_branch(
+        audio_encoder_config: crate::media::encoders::audio::SelectedAudioEncoder,
+    ) -> Result<AudioBranch, String> {
+        use crate::pipelines::audio_level::SYSTEM_AUDIO_DEVICE;
+
+        info!(device = SYSTEM_AUDIO_DEVICE, "Using desktop audio source");
+        let source = gst::ElementFactory::make("pulsesrc")
+            .property_from_str("slave-method", PULSESRC_SLAVE_METHOD)
+            .property("provide-clock", false)
+            .property("device", SYSTEM_AUDIO_DEVICE)
+            .build()
+            .map_err(|e| format!("Failed to create desktop audio source: {}", e))?;
+
+        let queue = gst::ElementFactory::make("queue")
+            .property("max-size-buffers", 200u32)
+            .property("max-size-time", 2_000_000_000u64)
+            .build()
+            .map_err(|e| format!("Failed to create desktop audio queue: {}", e))?;
+
+        let convert = gst::ElementFactory::make("audioconvert")
+            .build()
+            .map_err(|e| format!("Failed to create audioconvert: {}", e))?;
+
+        let resample = gst::ElementFactory::make("audioresample")
+            .build()
+            .map_err(|e| format!("Failed to create audioresample: {}", e))?;
+
+        // Sinks run at 48 kHz almost everywhere, so this is normally a
+        // pass-through; Opus accepts it either way.
+        let capsfilter = gst::ElementFactory::make("capsfilter")
+            .property(
+                "caps",
+                gst::Caps::builder("audio/x-raw")
+                    .field("channels", 2i32)
+                    .field("rate", 48_000i32)
+                    .build(),
+            )
+            .build()
+            .map_err(|e| format!("Failed to create desktop audio capsfilter: {}", e))?;
+
+        let level = gst::ElementFactory::make("level")
+            .name("audio-level-system")
+            .property("post-messages", true)
+            .property("interval", 100_000_000u64) // 100ms
+            .build()
+            .map_err(|e| format!("Failed to create desktop audio level meter: {}", e))?;
+
+        Ok(AudioBranch {
+            source,
+            queue,
+            convert,
+            resample,
+            capsfilter,
+            noise_suppression: None,
+            compressor: None,
+            makeup_gain: None,
+            limiter: None,
+            level,
+            encoder: audio_encoder_config.encoder,
+            sync_offset: None,
+        })
+    }
+
     /// Link audio chain:
-    /// source → queue → convert → resample → capsfilter(mono) → compressor → makeup_gain → limiter → level → encoder
+    /// source → queue → convert → resample → capsfilter → [noise suppression] → [compressor → makeup_gain → limiter] → level → encoder
+    ///
+    /// Bracketed stages are only present on the microphone branch.
     fn link_audio_chain(audio_branch: &AudioBranch) -> Result<(), String> {
-        gst::Element::link_many([
-            &audio_branch.source,
-            &audio_branch.queue,
-            &audio_branch.convert,
-            &audio_branch.resample,
-            &audio_branch.capsfilter,
-            &audio_branch.compressor,
-            &audio_branch.makeup_gain,
-            &audio_branch.limiter,
-            &audio_branch.level,
-            &audio_branch.encoder,
-        ])
-        .map_err(|_| "Failed to link audio chain")?;
+        gst::Element::link_many(audio_branch.elements())
+            .map_err(|_| "Failed to link audio chain")?;
 
         Ok(())
     }
 
     /// Start recording (idempotent — no-op if already playing)
-    pub fn start(&self) -> Result<(), String> {
+    ///
+    /// A failed start reports the error the pipeline posted, so a missing
+    /// encoder or an unwritable file is told apart from other failures.
+    pub fn start(&self) -> AppResult<()> {
         // Skip if already playing (e.g. JPEG zero-copy path starts eagerly)
         if self.pipeline.current_state() == gst::State::Playing {
             info!("Pipeline already playing, skipping start");
@@ -1396,16 +2193,27 @@ impl VideoRecorder {
         }
         info!(elements = ?element_names, "Pipeline elements");
 
-        let result = self
-            .pipeline
-            .set_state(gst::State::Playing)
-            .map_err(|e| format!("Failed to start recording: {}", e))?;
+        let result = self.pipeline.set_state(gst::State::Playing).map_err(|e| {
+            self.pipeline
+                .bus()
+                .and_then(|bus| bus.pop_filtered(&[gst::MessageType::Error]))
+                .and_then(|msg| match msg.view() {
+                    gst::MessageView::Error(err) => {
+                        Some(AppError::from_recording_pipeline(&err.error()))
+                    }
+                    _ => None,
+                })
+                .unwrap_or_else(|| RecordingError::StartFailed(e.to_string()).into())
+        })?;
         info!(state_change = ?result, "Pipeline set to Playing");
         Ok(())
     }
 
     /// Stop recording and finalize the file
-    pub fn stop(mut self) -> Result<PathBuf, String> {
+    ///
+    /// An error the pipeline posted while recording (e.g. the disk filling
+    /// up) is returned in place of the path.
+    pub fn stop(mut self) -> AppResult<PathBuf> {
         info!("Stopping video recording");
         clear_recording_diagnostics();
 
@@ -1438,6 +2246,7 @@ impl VideoRecorder {
         }
 
         let mut eos_timeout = false;
+        let mut pipeline_error = None;
 
         // Wait for EOS to propagate through the entire pipeline.
         // The bus posts an EOS message only after ALL sink elements have received
@@ -1460,6 +2269,7 @@ impl VideoRecorder {
                             source = ?err.src().map(|s| s.name()),
                             "GStreamer error while waiting for EOS"
                         );
+                        pipeline_error = Some(AppError::from_recording_pipeline(&err.error()));
                         eos_timeout = true;
                     }
                     _ => {}
@@ -1486,20 +2296,40 @@ impl VideoRecorder {
         info!("Setting pipeline to NULL state");
         self.pipeline
             .set_state(gst::State::Null)
-            .map_err(|e| format!("Failed to stop pipeline: {}", e))?;
+            .map_err(|e| RecordingError::StopFailed(format!("Failed to stop pipeline: {}", e)))?;
 
         let file_path = std::mem::take(&mut self.file_path);
         if eos_timeout {
             warn!(path = %file_path.display(), "Recording may be incomplete (EOS timeout)");
-            Err(format!(
-                "Recording saved but may be incomplete: {}",
-                file_path.display()
-            ))
+            Err(pipeline_error.unwrap_or_else(|| {
+                RecordingError::StopFailed(format!(
+                    "Recording saved but may be incomplete: {}",
+                    file_path.display()
+                ))
+                .into()
+            }))
         } else {
             info!(path = %file_path.display(), "Recording saved");
+            self.write_frame_metadata(&file_path);
             Ok(file_path)
         }
     }
+
+    /// Write the per-frame metadata sidecar next to the finished recording,
+    /// if any frame carried metadata. Failing to is logged, not fatal.
+    fn write_frame_metadata(&mut self, video: &std::path::Path) {
+        let Some(log) = self.frame_metadata.take() else {
+            return;
+        };
+        let log = log.lock().unwrap_or_else(|e| e.into_inner());
+        if log.is_empty() {
+            return;
+        }
+        match log.write(video) {
+            Ok(path) => info!(path = %path.display(), "Saved frame metadata"),
+            Err(e) => warn!(error = %e, "Failed to save frame metadata"),
+        }
+    }
 }
 
 impl Drop for VideoRecorder {
@@ -1519,7 +2349,8 @@ impl Drop for VideoRecorder {
     }
 }
 
-/// Audio branch elements
+/// Audio branch elements. The microphone branch uses every stage; the
+/// desktop-audio branch leaves the processing stages out.
 struct AudioBranch {
     source: gst::Element,
     queue: gst::Element,
@@ -1528,23 +2359,52 @@ struct AudioBranch {
     /// only does work when PA can't (or won't) serve the requested rate.
     resample: gst::Element,
     capsfilter: gst::Element,
+    /// Optional noise-suppressor bin (RNNoise or WebRTC), present only when
+    /// the user enabled it and a suppressor plugin is installed.
+    noise_suppression: Option<gst::Element>,
     /// Soft-knee downward compressor. Squashes loud peaks so the following
     /// makeup-gain stage can lift quiet content without clipping. Same chain
     /// is used by the settings audio probe so the meter reflects what the
-    /// recording captures.
-    compressor: gst::Element,
-    /// Makeup gain applied after the compressor. Linear scale; the user-facing
-    /// "audio gain" setting will eventually drive this property.
-    makeup_gain: gst::Element,
+    /// recording captures. `None` for the desktop-audio branch, which like
+    /// the two stages below leaves already-mastered audio untouched.
+    compressor: Option<gst::Element>,
+    /// Makeup gain applied after the compressor. Linear scale, driven by the
+    /// user-facing microphone gain setting.
+    makeup_gain: Option<gst::Element>,
     /// Brick-wall limiter after makeup gain — caps output around -0.4 dBFS
     /// so a strong source plus +6 dB makeup never clips the encoder.
-    limiter: gst::Element,
+    limiter: Option<gst::Element>,
     /// Level meter after compressor + makeup gain so the UI reads the actual
     /// recorded signal level. The pre-mix per-channel meter was removed to
     /// cut CPU pressure on weak ARM hardware — same reason `audioresample`
     /// is gone (source rate flows through unchanged).
     level: gst::Element,
     encoder: gst::Element,
+    /// User sync offset for this track, applied on the encoder's src pad.
+    /// Only the microphone has one, since the offset is set per input device.
+    sync_offset: Option<SharedSyncOffset>,
+}
+
+impl AudioBranch {
+    /// Elements in link order, skipping stages that are switched off.
+    fn elements(&self) -> Vec<&gst::Element> {
+        [
+            Some(&self.source),
+            Some(&self.queue),
+            Some(&self.convert),
+            Some(&self.resample),
+            Some(&self.capsfilter),
+            self.noise_suppression.as_ref(),
+            self.compressor.as_ref(),
+            self.makeup_gain.as_ref(),
+            self.limiter.as_ref(),
+            Some(&self.level),
+            Some(&self.encoder),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
 }
 
 /// Check which video encoders are available (backward compatibility)
@@ -0,0 +1,244 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Real-time stabilization of recorded video
+//!
+//! Each frame is shrunk to [`MOTION_WIDTH`] pixels across and aligned to the
+//! previous one with the burst-mode tile alignment, which gives the
+//! camera's motion since the last frame. [`CameraPath`] adds that motion up
+//! and follows it with a low-pass filter; the gap between the two is the
+//! shake to take out. The frame is cropped by the setting's margin and the
+//! crop window moved with the shake ([`stabilize_rgba_gpu`]), so the
+//! picture inside it holds still.
+//!
+//! Deliberate pans come through smoothed: once the gap reaches the margin,
+//! the filtered path is dragged along with the camera so the window never
+//! leaves the frame.
+
+use crate::config::VideoStabilization;
+use crate::pipelines::photo::burst_mode::{BurstModeGpuPipeline, MotionTracker};
+use crate::shaders::{StabilizeWindow, stabilize_rgba_gpu};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Width frames are shrunk to for motion estimation
+pub const MOTION_WIDTH: u32 = 320;
+
+/// Motion between two frames, as a fraction of the frame, beyond which
+/// tracking is taken as lost (a cut, a whip pan) and the path restarts
+const MAX_FRAME_MOTION: f32 = 0.15;
+
+/// Share of the remaining gap the filtered path closes each frame: the
+/// lower, the steadier the picture
+fn smoothing(strength: VideoStabilization) -> f32 {
+    match strength {
+        VideoStabilization::Off => 1.0,
+        VideoStabilization::Low => 0.2,
+        VideoStabilization::Medium => 0.08,
+        VideoStabilization::High => 0.03,
+    }
+}
+
+/// Camera motion and its smoothed version, in fractions of the frame
+#[derive(Debug, Clone)]
+pub struct CameraPath {
+    strength: VideoStabilization,
+    /// Accumulated motion of the picture
+    position: (f32, f32),
+    /// Low-pass filtered `position`
+    smoothed: (f32, f32),
+}
+
+impl CameraPath {
+    pub fn new(strength: VideoStabilization) -> Self {
+        Self {
+            strength,
+            position: (0.0, 0.0),
+            smoothed: (0.0, 0.0),
+        }
+    }
+
+    /// Feed the picture's motion since the previous frame (`None` when it
+    /// couldn't be measured) and get the crop window offset for this frame,
+    /// as a fraction of the frame
+    pub fn advance(&mut self, motion: Option<(f32, f32)>) -> (f32, f32) {
+        match motion {
+            Some((dx, dy)) if dx.abs().max(dy.abs()) <= MAX_FRAME_MOTION => {
+                self.position.0 += dx;
+                self.position.1 += dy;
+            }
+            Some(_) => {
+                // Lost track: recentre rather than chase a bogus offset
+                self.smoothed = self.position;
+            }
+            // Unmeasured: hold the window where it is
+            None => {}
+        }
+
+        let alpha = smoothing(self.strength);
+        self.smoothed.0 += (self.position.0 - self.smoothed.0) * alpha;
+        self.smoothed.1 += (self.position.1 - self.smoothed.1) * alpha;
+
+        let margin = self.strength.crop_margin();
+        let offset = (
+            (self.position.0 - self.smoothed.0).clamp(-margin, margin),
+            (self.position.1 - self.smoothed.1).clamp(-margin, margin),
+        );
+        // Drag the filtered path along when the window hits the edge
+        self.smoothed = (self.position.0 - offset.0, self.position.1 - offset.1);
+        offset
+    }
+}
+
+/// Per-recording stabilizer run by the filtered appsrc pusher
+pub struct VideoStabilizer {
+    /// Alignment pipeline; `None` if it couldn't be created, in which case
+    /// frames are still cropped so the output doesn't change framing
+    gpu: Option<Arc<BurstModeGpuPipeline>>,
+    tracker: Option<MotionTracker>,
+    path: CameraPath,
+}
+
+impl VideoStabilizer {
+    pub async fn new(strength: VideoStabilization) -> Self {
+        let gpu = match BurstModeGpuPipeline::shared().await {
+            Ok(gpu) => Some(gpu),
+            Err(e) => {
+                warn!(error = %e, "Motion estimation unavailable, recording a fixed crop");
+                None
+            }
+        };
+        info!(?strength, "Video stabilization enabled");
+        Self {
+            gpu,
+            tracker: None,
+            path: CameraPath::new(strength),
+        }
+    }
+
+    /// Stabilize one tightly packed RGBA frame
+    ///
+    /// A frame whose motion can't be measured keeps the previous window; a
+    /// frame the crop pass fails on is returned as it came.
+    pub async fn stabilize(&mut self, rgba: Vec<u8>, width: u32, height: u32) -> Vec<u8> {
+        let motion = self.measure_motion(&rgba, width, height).await;
+        let offset = self.path.advance(motion);
+        let window = StabilizeWindow {
+            scale: 1.0 - 2.0 * self.path.strength.crop_margin(),
+            offset: (offset.0 * width as f32, offset.1 * height as f32),
+        };
+        match stabilize_rgba_gpu(&rgba, width, height, window).await {
+            Ok(stabilized) => stabilized,
+            Err(e) => {
+                warn!(error = %e, "Failed to stabilize frame, using it unchanged");
+                rgba
+            }
+        }
+    }
+
+    /// Motion of the picture since the previous frame, as a fraction of
+    /// the frame
+    async fn measure_motion(&mut self, rgba: &[u8], width: u32, height: u32) -> Option<(f32, f32)> {
+        let gpu = self.gpu.as_ref()?;
+        let size = motion_size(width, height);
+        // A new tracker (first frame, or a standby camera with a different
+        // size took over) has nothing to compare against yet
+        if self.tracker.as_ref().is_none_or(|t| t.size() != size) {
+            self.tracker = Some(gpu.create_motion_tracker(size.0, size.1));
+        }
+        let tracker = self.tracker.as_mut()?;
+
+        let small = downscale_rgba(rgba, width, height, size.0, size.1);
+        match gpu.track_motion(tracker, &small).await {
+            Ok(motion) => motion.map(|(dx, dy)| (dx / size.0 as f32, dy / size.1 as f32)),
+            Err(e) => {
+                warn!(error = %e, "Motion estimation failed");
+                None
+            }
+        }
+    }
+}
+
+/// Size frames are shrunk to for motion estimation, keeping the aspect
+/// ratio. Frames already smaller are used as they are.
+fn motion_size(width: u32, height: u32) -> (u32, u32) {
+    if width <= MOTION_WIDTH {
+        return (width, height);
+    }
+    let height = (u64::from(height) * u64::from(MOTION_WIDTH) / u64::from(width)).max(1) as u32;
+    (MOTION_WIDTH, height)
+}
+
+/// Shrink tightly packed RGBA by averaging a 2x2 sample around each
+/// destination pixel's source position
+fn downscale_rgba(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    out_width: u32,
+    out_height: u32,
+) -> Vec<u8> {
+    if (width, height) == (out_width, out_height) {
+        return data.to_vec();
+    }
+    let (width, height) = (width as usize, height as usize);
+    let (out_width, out_height) = (out_width as usize, out_height as usize);
+    let mut out = Vec::with_capacity(out_width * out_height * 4);
+    for y in 0..out_height {
+        let sy = (y * height / out_height).min(height - 1);
+        let sy2 = (sy + 1).min(height - 1);
+        for x in 0..out_width {
+            let sx = (x * width / out_width).min(width - 1);
+            let sx2 = (sx + 1).min(width - 1);
+            for channel in 0..4 {
+                let sum: u32 = [(sx, sy), (sx2, sy), (sx, sy2), (sx2, sy2)]
+                    .iter()
+                    .map(|&(px, py)| u32::from(data[(py * width + px) * 4 + channel]))
+                    .sum();
+                out.push((sum / 4) as u8);
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shake_is_taken_out_within_the_margin() {
+        let mut path = CameraPath::new(VideoStabilization::High);
+        // Jitter back and forth: the window follows it almost one for one
+        let offsets: Vec<(f32, f32)> = [0.01, -0.02, 0.02, -0.01]
+            .iter()
+            .map(|&dx| path.advance(Some((dx, 0.0))))
+            .collect();
+        let position = [0.01, -0.01, 0.01, 0.0];
+        for (offset, position) in offsets.iter().zip(position) {
+            assert!((offset.0 - position).abs() < 0.005, "{offset:?}");
+        }
+    }
+
+    #[test]
+    fn pans_never_push_the_window_out_of_the_frame() {
+        let mut path = CameraPath::new(VideoStabilization::Low);
+        let margin = VideoStabilization::Low.crop_margin();
+        for _ in 0..200 {
+            let (dx, dy) = path.advance(Some((0.03, -0.03)));
+            assert!(dx.abs() <= margin && dy.abs() <= margin);
+        }
+        // Lost tracking recentres
+        assert_eq!(path.advance(Some((0.5, 0.0))), (0.0, 0.0));
+    }
+
+    #[test]
+    fn motion_frames_keep_the_aspect_ratio() {
+        assert_eq!(motion_size(1920, 1080), (320, 180));
+        assert_eq!(motion_size(640, 480), (320, 240));
+        assert_eq!(motion_size(160, 120), (160, 120));
+
+        let data = [10u8, 20, 30, 255].repeat(4 * 4);
+        let small = downscale_rgba(&data, 4, 4, 2, 2);
+        assert_eq!(small, [10u8, 20, 30, 255].repeat(4));
+    }
+}
@@ -0,0 +1,322 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Still extraction from recorded videos
+//!
+//! A batch job that walks an existing recording, typically a long source
+//! filmed for a timelapse, and saves stills from it: one every fixed
+//! interval, or one whenever the scene has changed since the last still.
+//! Frames come from the file-source decoder ([`VideoFrameReader`]) and go
+//! through the photo post-processor and encoder, so the stills land in the
+//! gallery like any other photo.
+//!
+//! Decoding runs on a blocking thread and hands the selected frames to the
+//! async side over a small channel, so encoding one still overlaps decoding
+//! the next.
+
+use crate::backends::camera::types::CameraFrame;
+use crate::backends::virtual_camera::VideoFrameReader;
+use crate::config::OutputSharpening;
+use crate::pipelines::photo::{EncodingFormat, PhotoEncoder, PostProcessingConfig, PostProcessor};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Columns and rows of the luma grid compared for scene changes
+const SIGNATURE_GRID: (usize, usize) = (16, 9);
+
+/// Mean luma difference (0-1) between the last still and a frame that
+/// counts as a new scene
+const SCENE_CHANGE_THRESHOLD: f32 = 0.08;
+
+/// Shortest gap between two scene-change stills, so a flash or a cut
+/// through several quick shots doesn't produce a burst of near-duplicates
+const MIN_SCENE_SPACING: f64 = 1.0;
+
+/// Which frames become stills
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StillSelection {
+    /// The first frame, then one every interval
+    Interval(Duration),
+    /// The first frame, then one whenever the scene differs from the last still
+    SceneChange,
+}
+
+/// Still extraction job settings
+#[derive(Debug, Clone)]
+pub struct StillExtractionConfig {
+    pub selection: StillSelection,
+    /// Directory the stills are saved to
+    pub output_dir: PathBuf,
+    pub encoding_format: EncodingFormat,
+}
+
+/// Progress and cancellation shared between a running batch job (still
+/// extraction, post-capture stabilization) and its caller
+#[derive(Debug, Clone, Default)]
+pub struct ExtractionControl {
+    /// Progress through the video in thousandths
+    progress_permille: Arc<AtomicU32>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ExtractionControl {
+    /// Progress through the video (0.0-1.0; stays 0 if the duration is unknown)
+    pub fn progress(&self) -> f32 {
+        self.progress_permille.load(Ordering::Relaxed) as f32 / 1000.0
+    }
+
+    /// Ask the job to stop after the still it is working on
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub(super) fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub(super) fn set_progress(&self, fraction: f64) {
+        let permille = (fraction.clamp(0.0, 1.0) * 1000.0) as u32;
+        self.progress_permille.store(permille, Ordering::Relaxed);
+    }
+}
+
+/// Extract stills from `source` into `config.output_dir`
+///
+/// Returns the saved paths in video order. A cancelled job returns the
+/// stills saved so far.
+pub async fn extract_stills(
+    source: PathBuf,
+    config: StillExtractionConfig,
+    control: ExtractionControl,
+) -> Result<Vec<PathBuf>, String> {
+    info!(
+        source = %source.display(),
+        selection = ?config.selection,
+        output_dir = %config.output_dir.display(),
+        "Extracting stills"
+    );
+
+    tokio::fs::create_dir_all(&config.output_dir)
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to create output directory '{}': {}",
+                config.output_dir.display(),
+                e
+            )
+        })?;
+
+    let (frame_tx, mut frame_rx) = tokio::sync::mpsc::channel::<(f64, CameraFrame)>(2);
+    let decode_source = source.clone();
+    let decode_control = control.clone();
+    let selection = config.selection;
+    let decoder = tokio::task::spawn_blocking(move || {
+        decode_selected_frames(&decode_source, selection, &decode_control, &frame_tx)
+    });
+
+    // Stills are videos' frames as they are: no extra sharpening on top of
+    // the encoder's own
+    let processor = PostProcessor::new(PostProcessingConfig {
+        output_sharpening: OutputSharpening {
+            amount: 0,
+            ..OutputSharpening::default()
+        },
+        ..PostProcessingConfig::default()
+    });
+    let mut encoder = PhotoEncoder::new();
+    encoder.set_format(config.encoding_format);
+
+    let stem = source
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("video")
+        .to_string();
+    let mut saved = Vec::new();
+    while let Some((timestamp, frame)) = frame_rx.recv().await {
+        let processed = processor.process(Arc::new(frame)).await?;
+        let encoded = encoder.encode(processed).await?;
+        let path = config.output_dir.join(still_file_name(
+            &stem,
+            timestamp,
+            encoded.format.extension(),
+        ));
+        tokio::fs::write(&path, &encoded.data)
+            .await
+            .map_err(|e| format!("Failed to save still to '{}': {}", path.display(), e))?;
+        info!(path = %path.display(), timestamp, "Saved still");
+        saved.push(path);
+    }
+
+    decoder
+        .await
+        .map_err(|e| format!("Still extraction task error: {}", e))??;
+
+    info!(count = saved.len(), "Still extraction finished");
+    Ok(saved)
+}
+
+/// Decode `source` and send the frames `selection` picks
+fn decode_selected_frames(
+    source: &Path,
+    selection: StillSelection,
+    control: &ExtractionControl,
+    frame_tx: &tokio::sync::mpsc::Sender<(f64, CameraFrame)>,
+) -> Result<(), String> {
+    let mut reader = VideoFrameReader::open(source).map_err(|e| e.to_string())?;
+    let duration = reader.duration().filter(|d| *d > 0.0);
+    let mut selector = StillSelector::new(selection);
+
+    while !control.is_cancelled() {
+        let Some((timestamp, frame)) = reader.next_frame().map_err(|e| e.to_string())? else {
+            break;
+        };
+        if let Some(duration) = duration {
+            control.set_progress(timestamp / duration);
+        }
+
+        if selector.accept(timestamp, || luma_signature(&frame)) {
+            // The receiver is gone only if the encoding side failed
+            if frame_tx.blocking_send((timestamp, frame)).is_err() {
+                warn!("Still encoder stopped, ending extraction");
+                break;
+            }
+        }
+    }
+    control.set_progress(1.0);
+    Ok(())
+}
+
+/// Decides frame by frame which ones become stills
+struct StillSelector {
+    selection: StillSelection,
+    /// Timestamp of the last still
+    last_still: Option<f64>,
+    /// Luma signature of the last still (scene change mode)
+    last_signature: Vec<f32>,
+}
+
+impl StillSelector {
+    fn new(selection: StillSelection) -> Self {
+        Self {
+            selection,
+            last_still: None,
+            last_signature: Vec::new(),
+        }
+    }
+
+    /// Whether the frame at `timestamp` becomes a still; `signature` is only
+    /// computed when the decision needs it
+    fn accept(&mut self, timestamp: f64, signature: impl FnOnce() -> Vec<f32>) -> bool {
+        let accepted = match (self.selection, self.last_still) {
+            (StillSelection::Interval(_), None) => true,
+            (StillSelection::Interval(interval), Some(last)) => {
+                timestamp - last >= interval.as_secs_f64()
+            }
+            (StillSelection::SceneChange, last) => {
+                if last.is_some_and(|last| timestamp - last < MIN_SCENE_SPACING) {
+                    return false;
+                }
+                let current = signature();
+                let changed = last.is_none()
+                    || signature_difference(&self.last_signature, &current)
+                        > SCENE_CHANGE_THRESHOLD;
+                if changed {
+                    self.last_signature = current;
+                }
+                changed
+            }
+        };
+        if accepted {
+            self.last_still = Some(timestamp);
+        }
+        accepted
+    }
+}
+
+/// Mean luma (0-1) of each cell of a [`SIGNATURE_GRID`] over an RGBA frame
+fn luma_signature(frame: &CameraFrame) -> Vec<f32> {
+    let (columns, rows) = SIGNATURE_GRID;
+    let width = frame.width as usize;
+    let height = frame.height as usize;
+    let stride = frame.stride as usize;
+    let data: &[u8] = &frame.data;
+
+    let mut sums = vec![0.0f32; columns * rows];
+    let mut counts = vec![0u32; columns * rows];
+    // Every 4th pixel on every 4th row is plenty for a 16x9 grid
+    for y in (0..height).step_by(4) {
+        let row = y * rows / height.max(1);
+        for x in (0..width).step_by(4) {
+            let i = y * stride + x * 4;
+            let Some(px) = data.get(i..i + 3) else {
+                continue;
+            };
+            let luma = (0.299 * px[0] as f32 + 0.587 * px[1] as f32 + 0.114 * px[2] as f32) / 255.0;
+            let cell = row * columns + x * columns / width.max(1);
+            sums[cell] += luma;
+            counts[cell] += 1;
+        }
+    }
+    sums.iter()
+        .zip(&counts)
+        .map(|(sum, &count)| if count > 0 { sum / count as f32 } else { 0.0 })
+        .collect()
+}
+
+/// Mean absolute difference of two luma signatures
+fn signature_difference(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return f32::MAX;
+    }
+    a.iter().zip(b).map(|(a, b)| (a - b).abs()).sum::<f32>() / a.len() as f32
+}
+
+/// `<video stem>_<HH>h<MM>m<SS>s<mmm>.<ext>`, so stills sort in video order
+fn still_file_name(stem: &str, timestamp: f64, extension: &str) -> String {
+    let millis = (timestamp.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{stem}_{:02}h{:02}m{:02}s{:03}.{extension}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interval_selection_keeps_the_spacing() {
+        let mut selector = StillSelector::new(StillSelection::Interval(Duration::from_secs(10)));
+        let picked: Vec<f64> = (0..60)
+            .map(|i| i as f64 * 0.5)
+            .filter(|&t| selector.accept(t, Vec::new))
+            .collect();
+        assert_eq!(picked, [0.0, 10.0, 20.0]);
+    }
+
+    #[test]
+    fn scene_selection_follows_the_picture() {
+        let mut selector = StillSelector::new(StillSelection::SceneChange);
+        let dark = vec![0.1; 4];
+        let bright = vec![0.9; 4];
+        assert!(selector.accept(0.0, || dark.clone()));
+        // Too soon after the last still, even though the scene changed
+        assert!(!selector.accept(0.5, || bright.clone()));
+        assert!(!selector.accept(2.0, || dark.clone()));
+        assert!(selector.accept(3.0, || bright.clone()));
+    }
+
+    #[test]
+    fn still_names_sort_in_video_order() {
+        assert_eq!(
+            still_file_name("VID", 3725.5, "jpg"),
+            "VID_01h02m05s500.jpg"
+        );
+        assert!(still_file_name("VID", 9.0, "jpg") < still_file_name("VID", 10.0, "jpg"));
+    }
+}
@@ -10,7 +10,7 @@
 
 use super::encoder_selection::{EncoderConfig, select_encoders, select_encoders_with_video};
 use super::muxer::{create_muxer, link_muxer_to_sink, link_video_to_muxer};
-use super::recorder::convert_frame_to_rgba;
+use super::recorder::{ENCODE_COLORIMETRY, RGBA_COLORIMETRY, convert_frame_to_rgba};
 use crate::backends::camera::types::{CameraFrame, SensorRotation};
 use crate::media::encoders::video::EncoderInfo;
 use gstreamer as gst;
@@ -113,6 +113,7 @@ pub async fn run_timelapse_encoder(
                 .field("width", width as i32)
                 .field("height", height as i32)
                 .field("framerate", gst::Fraction::new(TIMELAPSE_FPS as i32, 1))
+                .field("colorimetry", RGBA_COLORIMETRY)
                 .build(),
         )
         .format(gst::Format::Time)
@@ -122,6 +123,15 @@ pub async fn run_timelapse_encoder(
     let videoconvert = gst::ElementFactory::make("videoconvert")
         .build()
         .map_err(|e| format!("videoconvert: {e}"))?;
+    let encode_caps = gst::ElementFactory::make("capsfilter")
+        .property(
+            "caps",
+            gst::Caps::builder("video/x-raw")
+                .field("colorimetry", ENCODE_COLORIMETRY)
+                .build(),
+        )
+        .build()
+        .map_err(|e| format!("capsfilter: {e}"))?;
 
     // Optional videoflip for sensor rotation correction. If we also need to
     // mirror horizontally, chain a second videoflip after rotation.
@@ -169,6 +179,7 @@ pub async fn run_timelapse_encoder(
         .add_many([
             appsrc.upcast_ref(),
             &videoconvert,
+            &encode_caps,
             &encoder_elem,
             &muxer_cfg.muxer,
             &muxer_cfg.filesink,
@@ -190,17 +201,20 @@ pub async fn run_timelapse_encoder(
     appsrc
         .link(&videoconvert)
         .map_err(|_| "link appsrc→videoconvert")?;
+    videoconvert
+        .link(&encode_caps)
+        .map_err(|_| "link videoconvert→capsfilter")?;
 
-    // Chain: videoconvert → [videoflip → capsfilter →] [mirror →] encoder
+    // Chain: videoconvert → capsfilter → [videoflip → capsfilter →] [mirror →] encoder
     let after_rotation: &gst::Element = if let Some((ref flip, ref capsfilter)) = videoflip {
-        videoconvert
+        encode_caps
             .link(flip)
-            .map_err(|_| "link videoconvert→videoflip")?;
+            .map_err(|_| "link capsfilter→videoflip")?;
         flip.link(capsfilter)
             .map_err(|_| "link videoflip→capsfilter")?;
         capsfilter
     } else {
-        &videoconvert
+        &encode_caps
     };
     let pre_encoder: &gst::Element = if let Some(ref mirror) = mirror_flip {
         after_rotation
@@ -325,7 +339,7 @@ pub async fn run_timelapse_encoder(
 }
 
 /// Convert a frame to RGBA and apply the current live filter (if any).
-async fn convert_and_filter(
+pub(super) async fn convert_and_filter(
     frame: &CameraFrame,
     live_filter_code: &AtomicU32,
 ) -> Result<Vec<u8>, String> {
@@ -0,0 +1,318 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Recording pipeline warm-up
+//!
+//! Parsing the recording pipeline, loading the encoder plugin and probing
+//! and opening a hardware encoder took long enough that pressing Record
+//! visibly lagged, most of all the first time. While Video mode is idle the
+//! app [`request`]s a pipeline matching the current format and encoder
+//! settings; it is built on a background thread and parked in READY, where
+//! the encoder is open but the output file isn't. Starting a recording takes
+//! it, points the filesink at the new file and adds the audio branches, so
+//! only the switch to PLAYING is left. Changed settings rebuild it in the
+//! background.
+//!
+//! Only the RGBA appsrc pipeline is pre-built; the VA-API JPEG zero-copy
+//! pipeline is still built when recording starts.
+
+use super::encoder_selection::EncoderConfig;
+use super::recorder::{
+    appsrc_video_pipeline, build_video_pipeline, resolve_video_chain, select_encoder_set,
+};
+use crate::backends::camera::types::SensorRotation;
+use crate::media::encoders::video::EncoderInfo;
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use std::sync::Mutex;
+use std::time::Instant;
+use tracing::{debug, info, warn};
+
+/// Recording settings a pre-built pipeline is built for
+#[derive(Clone, Debug, PartialEq)]
+pub struct WarmupRequest {
+    /// Encoder chosen in settings (`None` = auto-select)
+    pub encoder_info: Option<EncoderInfo>,
+    pub encoder_config: EncoderConfig,
+    /// Frame size as delivered by the camera, before rotation
+    pub width: u32,
+    pub height: u32,
+    pub framerate: u32,
+    /// Rotation the recorder will apply
+    pub rotation: SensorRotation,
+    /// Rotation the recording will be tagged with
+    pub orientation: SensorRotation,
+}
+
+/// A pipeline parked in READY
+struct WarmPipeline {
+    /// Video description (without the sink) it was built from
+    desc: String,
+    encoder_config: EncoderConfig,
+    pipeline: gst::Pipeline,
+    appsrc: gst_app::AppSrc,
+}
+
+impl WarmPipeline {
+    fn matches(&self, desc: &str, encoder_config: &EncoderConfig) -> bool {
+        self.desc == desc && self.encoder_config == *encoder_config
+    }
+}
+
+/// Bookkeeping between the app, the build threads and the recorder: which
+/// settings are wanted, which build is current and what is parked. Holds no
+/// GStreamer state itself, only `W`.
+struct Handoff<R, W> {
+    /// Settings of the pipeline built or being built
+    requested: Option<R>,
+    /// Bumped whenever the wanted pipeline changes, so superseded builds
+    /// are thrown away
+    generation: u64,
+    warm: Option<W>,
+}
+
+impl<R: PartialEq, W> Handoff<R, W> {
+    const fn new() -> Self {
+        Self {
+            requested: None,
+            generation: 0,
+            warm: None,
+        }
+    }
+
+    /// Ask for `request`. Returns the generation to build it as, or `None`
+    /// when it is already built or being built.
+    fn request(&mut self, request: R) -> Option<u64> {
+        if self.requested.as_ref() == Some(&request) {
+            return None;
+        }
+        self.requested = Some(request);
+        self.generation += 1;
+        Some(self.generation)
+    }
+
+    /// Stop wanting a pipeline. Returns the parked one to discard.
+    fn release(&mut self) -> Option<W> {
+        self.requested.take()?;
+        self.generation += 1;
+        self.warm.take()
+    }
+
+    fn is_current(&self, generation: u64) -> bool {
+        self.generation == generation
+    }
+
+    /// Park what the build of `generation` made, returning the pipeline it
+    /// replaces. `Err` gives the build back when it was superseded.
+    fn park(&mut self, generation: u64, warm: W) -> Result<Option<W>, W> {
+        if self.is_current(generation) {
+            Ok(self.warm.replace(warm))
+        } else {
+            Err(warm)
+        }
+    }
+
+    /// Take the parked pipeline for a recording: `Ok` when `matches` it,
+    /// `Err` when it was built for something else. Either way it is used
+    /// up, and so is the request.
+    fn take(&mut self, matches: impl FnOnce(&W) -> bool) -> Option<Result<W, W>> {
+        self.requested = None;
+        self.generation += 1;
+        let warm = self.warm.take()?;
+        Some(if matches(&warm) { Ok(warm) } else { Err(warm) })
+    }
+}
+
+static HANDOFF: Mutex<Handoff<WarmupRequest, WarmPipeline>> = Mutex::new(Handoff::new());
+/// One build at a time, so two never open the hardware encoder at once
+static BUILD_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Have a pipeline for `request` ready for the next recording. Returns
+/// immediately; cheap when the settings haven't changed, so it can be called
+/// on every preview frame.
+pub fn request(request: WarmupRequest) {
+    let Some(generation) = lock(&HANDOFF).request(request.clone()) else {
+        return;
+    };
+
+    debug!(
+        width = request.width,
+        height = request.height,
+        framerate = request.framerate,
+        "Pre-building recording pipeline"
+    );
+    if let Err(e) = std::thread::Builder::new()
+        .name("recorder-warmup".into())
+        .spawn(move || build(request, generation))
+    {
+        warn!(error = %e, "Failed to spawn recorder warm-up thread");
+    }
+}
+
+/// Drop the parked pipeline, freeing the encoder (e.g. when leaving Video
+/// mode)
+pub fn release() {
+    let warm = lock(&HANDOFF).release();
+    discard(warm);
+}
+
+/// Take the parked pipeline if it was built from `desc` and
+/// `encoder_config`, still in READY. The pipeline is used up either way; the
+/// next [`request`] builds a new one.
+///
+/// Waits for a build in progress to finish first, so the recording never
+/// opens the encoder while a warm-up build still is. Builds that start
+/// later see they were superseded and stop.
+pub(super) fn take(
+    desc: &str,
+    encoder_config: &EncoderConfig,
+) -> Option<(gst::Pipeline, gst_app::AppSrc)> {
+    let _building = lock(&BUILD_LOCK);
+    let taken = lock(&HANDOFF).take(|warm| warm.matches(desc, encoder_config))?;
+    match taken {
+        Ok(warm) => Some((warm.pipeline, warm.appsrc)),
+        Err(stale) => {
+            debug!("Pre-built recording pipeline doesn't match the recording, discarding");
+            discard(Some(stale));
+            None
+        }
+    }
+}
+
+fn discard(warm: Option<WarmPipeline>) {
+    if let Some(warm) = warm {
+        let _ = warm.pipeline.set_state(gst::State::Null);
+    }
+}
+
+fn build(request: WarmupRequest, generation: u64) {
+    let _building = lock(&BUILD_LOCK);
+    if !lock(&HANDOFF).is_current(generation) {
+        return;
+    }
+
+    let started = Instant::now();
+    let warm = match build_pipeline(&request) {
+        Ok(warm) => warm,
+        Err(e) => {
+            warn!(error = %e, "Failed to pre-build recording pipeline");
+            return;
+        }
+    };
+
+    let parked = lock(&HANDOFF).park(generation, warm);
+    match parked {
+        Ok(replaced) => {
+            discard(replaced);
+            info!(
+                elapsed_ms = started.elapsed().as_millis() as u64,
+                "Recording pipeline pre-built"
+            );
+        }
+        Err(superseded) => discard(Some(superseded)),
+    }
+}
+
+fn build_pipeline(request: &WarmupRequest) -> Result<WarmPipeline, String> {
+    let encoders = select_encoder_set(
+        request.encoder_info.as_ref(),
+        &request.encoder_config,
+        false,
+    )?;
+    let chain = resolve_video_chain(&encoders.video, &request.encoder_config);
+    let (base_width, base_height) = if request.rotation.swaps_dimensions() {
+        (request.height, request.width)
+    } else {
+        (request.width, request.height)
+    };
+    let video = appsrc_video_pipeline(
+        &chain,
+        base_width,
+        base_height,
+        request.framerate,
+        request.orientation,
+        // Recordings with the stamp or a desqueeze build their own pipeline
+        false,
+        1.0,
+    );
+
+    // The location is set when the recording starts
+    let (pipeline, appsrc) = build_video_pipeline(
+        &format!("{} ! filesink name=recording-sink", video.desc),
+        &chain.encoder_name,
+        &request.encoder_config,
+        video.encode_width,
+        video.encode_height,
+    )?;
+
+    // READY loads the elements and opens the encoder's device; the filesink
+    // only opens its file on the way to PAUSED
+    if let Err(e) = pipeline.set_state(gst::State::Ready) {
+        let _ = pipeline.set_state(gst::State::Null);
+        return Err(format!("Failed to set pipeline to Ready: {}", e));
+    }
+
+    Ok(WarmPipeline {
+        desc: video.desc,
+        encoder_config: request.encoder_config.clone(),
+        pipeline,
+        appsrc,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestHandoff = Handoff<u32, &'static str>;
+
+    #[test]
+    fn repeated_request_is_not_rebuilt() {
+        let mut handoff = TestHandoff::new();
+        assert_eq!(handoff.request(720), Some(1));
+        assert_eq!(handoff.request(720), None);
+        assert_eq!(handoff.request(1080), Some(2));
+    }
+
+    #[test]
+    fn superseded_build_is_given_back() {
+        let mut handoff = TestHandoff::new();
+        let first = handoff.request(720).unwrap();
+        let second = handoff.request(1080).unwrap();
+        assert!(!handoff.is_current(first));
+
+        assert_eq!(handoff.park(first, "720p"), Err("720p"));
+        assert_eq!(handoff.park(second, "1080p"), Ok(None));
+        assert_eq!(handoff.take(|_| true), Some(Ok("1080p")));
+    }
+
+    #[test]
+    fn take_uses_up_a_mismatched_pipeline() {
+        let mut handoff = TestHandoff::new();
+        let generation = handoff.request(720).unwrap();
+        handoff.park(generation, "720p").unwrap();
+
+        assert_eq!(handoff.take(|warm| *warm == "1080p"), Some(Err("720p")));
+        assert_eq!(handoff.take(|_| true), None);
+        // The build the recording raced with is stale, and the same
+        // settings are built again afterwards
+        assert!(!handoff.is_current(generation));
+        assert!(handoff.request(720).is_some());
+    }
+
+    #[test]
+    fn release_drops_the_parked_pipeline() {
+        let mut handoff = TestHandoff::new();
+        assert_eq!(handoff.release(), None);
+
+        let generation = handoff.request(720).unwrap();
+        handoff.park(generation, "720p").unwrap();
+        assert_eq!(handoff.release(), Some("720p"));
+        assert!(!handoff.is_current(generation));
+        assert!(handoff.request(720).is_some());
+    }
+}
@@ -0,0 +1,201 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! History of scanned QR codes
+//!
+//! Every code the scanner decodes is kept, newest first, with when it was
+//! seen and what was done with it (link opened, network joined, text
+//! copied), so a code scanned earlier can be found again without the paper
+//! it was printed on. The history is a JSON file in the app's data directory
+//! ([`history_path`]) and never leaves the machine.
+//!
+//! Detection runs about once a second, so a code held in view is only
+//! recorded again once [`REPEAT_WINDOW_SECS`] have passed.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const APP_ID: &str = <crate::app::AppModel as cosmic::Application>::APP_ID;
+
+/// File name of the history inside the app's data directory
+const HISTORY_FILE_NAME: &str = "qr-history.json";
+
+/// Saves run in the background; one at a time, so they can't interleave
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Oldest entries are dropped beyond this many
+pub const MAX_ENTRIES: usize = 1000;
+
+/// A code seen again within this many seconds of its last entry doesn't get
+/// a new one
+pub const REPEAT_WINDOW_SECS: i64 = 10 * 60;
+
+/// What was done with a scanned code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum QrHistoryAction {
+    /// Link, phone number, address or location opened in another app
+    Opened,
+    /// WiFi network joined
+    JoinedWifi,
+    /// Text, contact or event copied to the clipboard
+    Copied,
+}
+
+/// A scanned code
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QrHistoryEntry {
+    /// Decoded content, as scanned
+    pub content: String,
+    /// When it was scanned, in seconds since the Unix epoch
+    pub scanned_at: i64,
+    /// What was done with it, first time each
+    #[serde(default)]
+    pub actions: Vec<QrHistoryAction>,
+}
+
+/// Scanned codes, newest first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QrHistory {
+    #[serde(default)]
+    pub entries: Vec<QrHistoryEntry>,
+}
+
+/// Default location of the history file
+pub fn history_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_ID).join(HISTORY_FILE_NAME))
+}
+
+impl QrHistory {
+    /// Load the history from `path`. A missing file is an empty history.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text)
+                .map_err(|e| format!("Invalid QR history {}: {e}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("Failed to read {}: {e}", path.display())),
+        }
+    }
+
+    /// Write the history to `path`. Blocking.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        let json = serde_json::to_string(self)
+            .map_err(|e| format!("Failed to serialize the QR history: {e}"))?;
+        // Written aside and renamed, so a crash never leaves half a file
+        let _lock = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let temp = path.with_extension("tmp");
+        std::fs::write(&temp, json)
+            .and_then(|()| std::fs::rename(&temp, path))
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))
+    }
+
+    /// Record `content` as scanned at `now`. Returns whether an entry was
+    /// added.
+    pub fn record_scan(&mut self, content: &str, now: i64) -> bool {
+        if self
+            .entries
+            .iter()
+            .any(|e| e.content == content && now - e.scanned_at < REPEAT_WINDOW_SECS)
+        {
+            return false;
+        }
+        self.entries.insert(
+            0,
+            QrHistoryEntry {
+                content: content.to_string(),
+                scanned_at: now,
+                actions: Vec::new(),
+            },
+        );
+        self.entries.truncate(MAX_ENTRIES);
+        true
+    }
+
+    /// Record `action` as done with `content`, on its newest entry (added
+    /// now if the code isn't in the history). Returns whether anything
+    /// changed.
+    pub fn record_action(&mut self, content: &str, action: QrHistoryAction, now: i64) -> bool {
+        let added =
+            !self.entries.iter().any(|e| e.content == content) && self.record_scan(content, now);
+        let Some(entry) = self.entries.iter_mut().find(|e| e.content == content) else {
+            return added;
+        };
+        if entry.actions.contains(&action) {
+            return added;
+        }
+        entry.actions.push(action);
+        true
+    }
+
+    /// Entries whose content contains `query`, ignoring case; all of them
+    /// for an empty query
+    pub fn search<'a>(&'a self, query: &str) -> impl Iterator<Item = &'a QrHistoryEntry> {
+        let query = query.trim().to_lowercase();
+        self.entries
+            .iter()
+            .filter(move |e| query.is_empty() || e.content.to_lowercase().contains(&query))
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_held_in_view_are_recorded_once() {
+        let mut history = QrHistory::default();
+        assert!(history.record_scan("https://example.com", 1000));
+        assert!(!history.record_scan("https://example.com", 1001));
+        assert!(history.record_scan("WIFI:S:Home;;", 1002));
+        assert!(history.record_scan("https://example.com", 1000 + REPEAT_WINDOW_SECS));
+        let contents: Vec<&str> = history.entries.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(
+            contents,
+            [
+                "https://example.com",
+                "WIFI:S:Home;;",
+                "https://example.com"
+            ]
+        );
+    }
+
+    #[test]
+    fn actions_land_on_the_newest_entry() {
+        let mut history = QrHistory::default();
+        history.record_scan("hello", 1);
+        history.record_scan("hello", 1 + REPEAT_WINDOW_SECS);
+        assert!(history.record_action("hello", QrHistoryAction::Copied, 2000));
+        assert!(!history.record_action("hello", QrHistoryAction::Copied, 2001));
+        assert_eq!(history.entries[0].actions, [QrHistoryAction::Copied]);
+        assert!(history.entries[1].actions.is_empty());
+
+        // Acting on a code from elsewhere records it too
+        assert!(history.record_action("other", QrHistoryAction::Opened, 3000));
+        assert_eq!(history.entries[0].content, "other");
+    }
+
+    #[test]
+    fn search_ignores_case_and_round_trips() {
+        let mut history = QrHistory::default();
+        history.record_scan("https://Example.com/menu", 1);
+        history.record_scan("WIFI:T:WPA;S:Cafe;P:x;;", 2);
+        history.record_action("WIFI:T:WPA;S:Cafe;P:x;;", QrHistoryAction::JoinedWifi, 3);
+        assert_eq!(history.search("example").count(), 1);
+        assert_eq!(history.search("  ").count(), 2);
+        assert_eq!(history.search("nothing").count(), 0);
+
+        let path = std::env::temp_dir().join(format!("camera-qr-history-{}", std::process::id()));
+        history.save(&path).unwrap();
+        assert_eq!(QrHistory::load(&path).unwrap(), history);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(QrHistory::load(&path).unwrap(), QrHistory::default());
+    }
+}
@@ -0,0 +1,229 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! End-to-end self-test
+//!
+//! `camera selftest` takes a photo and records a short video without a
+//! window, through the same photo pipeline and recorder the app uses, then
+//! decodes both files to check they were saved whole. Frames come from a
+//! synthetic test pattern, or with `--loopback` from a v4l2loopback device
+//! read through V4L2 like a webcam (see [`support`]).
+//!
+//! Each step prints PASS or FAIL; the command fails if any step did, so it
+//! can gate CI or confirm a fix for a saving bug on the affected machine.
+
+mod support;
+
+use camera::backends::camera::types::{CameraFrame, PixelFormat, RecordingFrame};
+use camera::pipelines::photo::PhotoPipeline;
+use camera::pipelines::video::{
+    AppsrcRecorderConfig, EncoderConfig, RecorderConfig, VideoRecorder,
+};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use support::{
+    LoopbackDevice, LoopbackReader, VideoSummary, test_pattern, verify_photo, verify_video,
+};
+
+/// Size and rate of the test frames
+const WIDTH: u32 = 1280;
+const HEIGHT: u32 = 720;
+const FRAMERATE: u32 = 30;
+
+/// Longest to wait for a loopback frame
+const FRAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time for EOS to reach the muxer once frames stop
+const EOS_SETTLE: Duration = Duration::from_millis(300);
+
+/// Options for `camera selftest`
+pub struct SelftestOptions {
+    /// Read frames from a v4l2loopback device instead of the test pattern
+    pub loopback: bool,
+    /// Length of the test recording
+    pub seconds: u64,
+    /// Where to save the files (default: a temporary directory)
+    pub output: Option<PathBuf>,
+    /// Keep the files after a successful run
+    pub keep: bool,
+}
+
+/// Where the test frames come from
+enum FrameSource {
+    Synthetic { index: u32 },
+    // The device must outlive its reader
+    Loopback(LoopbackReader, LoopbackDevice),
+}
+
+impl FrameSource {
+    fn open(loopback: bool) -> Result<Self, String> {
+        if !loopback {
+            return Ok(Self::Synthetic { index: 0 });
+        }
+        let device = LoopbackDevice::open(WIDTH, HEIGHT, FRAMERATE)?;
+        println!("Loopback device: {}", device.path().display());
+        Ok(Self::Loopback(device.reader()?, device))
+    }
+
+    fn next_frame(&mut self) -> Result<CameraFrame, String> {
+        match self {
+            Self::Synthetic { index } => {
+                *index += 1;
+                Ok(test_pattern(WIDTH, HEIGHT, *index))
+            }
+            Self::Loopback(reader, _) => reader.next_frame(FRAME_TIMEOUT),
+        }
+    }
+
+    fn pixel_format(&self) -> PixelFormat {
+        match self {
+            Self::Synthetic { .. } => PixelFormat::RGBA,
+            Self::Loopback(..) => PixelFormat::YUYV,
+        }
+    }
+}
+
+/// Run the self-test
+pub fn run(options: SelftestOptions) -> Result<(), Box<dyn std::error::Error>> {
+    gstreamer::init()?;
+    let rt = tokio::runtime::Runtime::new()?;
+
+    let output_dir = options.output.clone().unwrap_or_else(|| {
+        std::env::temp_dir().join(format!("camera-selftest-{}", std::process::id()))
+    });
+    std::fs::create_dir_all(&output_dir)?;
+    println!("Output: {}", output_dir.display());
+
+    let mut source = FrameSource::open(options.loopback)?;
+    let mut failures = 0;
+    let mut report = |step: &str, result: Result<String, String>| match result {
+        Ok(detail) => println!("PASS {step}: {detail}"),
+        Err(e) => {
+            failures += 1;
+            println!("FAIL {step}: {e}");
+        }
+    };
+
+    report(
+        "photo",
+        take_photo(&rt, &mut source, &output_dir).map(|path| format!("{}", path.display())),
+    );
+    report(
+        "video",
+        record_video(&rt, &mut source, &output_dir, options.seconds).map(|(path, summary)| {
+            match summary.duration {
+                Some(seconds) => format!(
+                    "{} ({} frames, {:.1} s)",
+                    path.display(),
+                    summary.frames,
+                    seconds
+                ),
+                None => format!("{} ({} frames)", path.display(), summary.frames),
+            }
+        }),
+    );
+
+    if failures > 0 {
+        return Err(format!("{failures} self-test step(s) failed").into());
+    }
+    if options.output.is_none() && !options.keep {
+        let _ = std::fs::remove_dir_all(&output_dir);
+    }
+    Ok(())
+}
+
+/// Save a photo through the photo pipeline and decode it back
+fn take_photo(
+    rt: &tokio::runtime::Runtime,
+    source: &mut FrameSource,
+    output_dir: &Path,
+) -> Result<PathBuf, String> {
+    let frame = Arc::new(source.next_frame()?);
+    let path =
+        rt.block_on(PhotoPipeline::new().capture_and_save(frame, output_dir.to_path_buf()))?;
+    verify_photo(&path, (WIDTH, HEIGHT))?;
+    Ok(path)
+}
+
+/// Record `seconds` of frames through the appsrc recorder and decode the
+/// file back, returning it and what decoding found
+fn record_video(
+    rt: &tokio::runtime::Runtime,
+    source: &mut FrameSource,
+    output_dir: &Path,
+    seconds: u64,
+) -> Result<(PathBuf, VideoSummary), String> {
+    let (frame_tx, frame_rx) = tokio::sync::mpsc::channel(15);
+    let output_path = output_dir.join("selftest.mp4");
+    let pixel_format = source.pixel_format();
+
+    let recorder = rt.block_on(async {
+        let rt_handle = tokio::runtime::Handle::current();
+        tokio::task::spawn_blocking(move || {
+            let _guard = rt_handle.enter();
+            VideoRecorder::new_from_appsrc(
+                AppsrcRecorderConfig {
+                    base: RecorderConfig {
+                        width: WIDTH,
+                        height: HEIGHT,
+                        framerate: FRAMERATE,
+                        output_path,
+                        encoder_config: EncoderConfig::default(),
+                        enable_audio: false,
+                        audio_device: None,
+                        audio_source_rate_hz: 0,
+                        audio_processing: Default::default(),
+                        system_audio: false,
+                        encoder_info: None,
+                        rotation: Default::default(),
+                        mirror_horizontal: false,
+                        orientation: Default::default(),
+                        audio_levels: Default::default(),
+                        audio_sync_offset: Default::default(),
+                    },
+                    pixel_format,
+                    live_filter_code: Arc::new(std::sync::atomic::AtomicU32::new(0)),
+                    pip: None,
+                    stabilization: Default::default(),
+                    denoise: Default::default(),
+                    timecode: false,
+                    timestamp_overlay: false,
+                    desqueeze: 1.0,
+                },
+                frame_rx,
+            )
+        })
+        .await
+        .unwrap_or_else(|e| Err(format!("Task join error: {}", e).into()))
+    })?;
+    recorder.start()?;
+
+    // Push frames at the framerate, as the capture thread would
+    let frame_interval = Duration::from_secs(1) / FRAMERATE;
+    let end = Instant::now() + Duration::from_secs(seconds.max(1));
+    let mut next = Instant::now();
+    let mut pushed = 0;
+    while Instant::now() < end {
+        let frame = Arc::new(source.next_frame()?);
+        if frame_tx
+            .blocking_send(RecordingFrame::Decoded(frame))
+            .is_err()
+        {
+            return Err("Recorder stopped taking frames".to_string());
+        }
+        pushed += 1;
+        next += frame_interval;
+        if let Some(wait) = next.checked_duration_since(Instant::now()) {
+            std::thread::sleep(wait);
+        }
+    }
+
+    // Ending the frame stream sends EOS, which finalizes the file
+    drop(frame_tx);
+    std::thread::sleep(EOS_SETTLE);
+    let path = recorder.stop()?;
+
+    // Encoders may hold back or drop a few frames at the edges
+    let summary = verify_video(&path, (WIDTH, HEIGHT), pushed * 3 / 4)?;
+    Ok((path, summary))
+}
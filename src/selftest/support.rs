@@ -0,0 +1,421 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Support for end-to-end tests of the capture → encode → save path
+//!
+//! `camera selftest` needs frames that don't depend on the camera in front
+//! of the machine, and a way to tell a good output file from a truncated or
+//! undecodable one. This module has both:
+//!
+//! - [`test_pattern`]: synthetic RGBA frames, each one different so encoded
+//!   video has motion
+//! - [`LoopbackDevice`]: a v4l2loopback node fed with GStreamer's test
+//!   source, read back as YUYV frames through V4L2 like a webcam. libcamera
+//!   doesn't list loopback devices, so it is read with `v4l2src`.
+//! - [`verify_photo`] and [`verify_video`]: decode a saved file and check it
+//!   is complete
+
+use camera::backends::camera::types::{CameraFrame, FrameData, PixelFormat};
+use camera::backends::camera::v4l2_utils::{get_v4l2_driver, scan_video_device_nodes};
+use camera::backends::virtual_camera::VideoFrameReader;
+use gstreamer::prelude::*;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Driver name v4l2loopback reports in `VIDIOC_QUERYCAP`
+const LOOPBACK_DRIVER: &str = "v4l2 loopback";
+
+/// Name given to a loopback device the self-test creates
+const LOOPBACK_LABEL: &str = "Camera self-test";
+
+/// How long the feeder and reader pipelines get to start
+const PIPELINE_START_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// SMPTE-style bars: white, yellow, cyan, green, magenta, red, blue, black
+const BARS: [[u8; 3]; 8] = [
+    [235, 235, 235],
+    [235, 235, 16],
+    [16, 235, 235],
+    [16, 235, 16],
+    [235, 16, 235],
+    [235, 16, 16],
+    [16, 16, 235],
+    [16, 16, 16],
+];
+
+/// Color bars with a white square that moves with `index`
+pub fn test_pattern(width: u32, height: u32, index: u32) -> CameraFrame {
+    let square = (height / 4).max(1);
+    let square_x = (index * 8) % width.saturating_sub(square).max(1);
+    let square_y = height / 2 - square / 2;
+
+    let mut data = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let in_square = (square_x..square_x + square).contains(&x)
+                && (square_y..square_y + square).contains(&y);
+            let [r, g, b] = if in_square {
+                [255, 255, 255]
+            } else {
+                BARS[(x * BARS.len() as u32 / width) as usize]
+            };
+            data.extend_from_slice(&[r, g, b, 255]);
+        }
+    }
+
+    CameraFrame {
+        width,
+        height,
+        data: FrameData::Copied(data.into()),
+        format: PixelFormat::RGBA,
+        stride: width * 4,
+        yuv_planes: None,
+        captured_at: Instant::now(),
+        sensor_timestamp_ns: None,
+        libcamera_metadata: None,
+    }
+}
+
+/// A v4l2loopback device playing GStreamer's test source
+///
+/// An existing loopback device is reused; otherwise one is created with
+/// `v4l2loopback-ctl`, which needs the module loaded and enough rights
+/// (typically root), and removed again on drop.
+pub struct LoopbackDevice {
+    path: PathBuf,
+    created: bool,
+    width: u32,
+    height: u32,
+    feeder: gstreamer::Pipeline,
+}
+
+impl LoopbackDevice {
+    /// Find or create a loopback device and start feeding it YUYV frames
+    pub fn open(width: u32, height: u32, framerate: u32) -> Result<Self, String> {
+        gstreamer::init().map_err(|e| format!("GStreamer init failed: {}", e))?;
+
+        let (path, created) = match find_loopback() {
+            Some(path) => (path, false),
+            None => (create_loopback()?, true),
+        };
+
+        let feeder = gstreamer::parse::launch(&format!(
+            "videotestsrc is-live=true pattern=smpte ! \
+             video/x-raw,format=YUY2,width={width},height={height},framerate={framerate}/1 ! \
+             v4l2sink device={}",
+            path.display()
+        ))
+        .map_err(|e| format!("Failed to create loopback feeder: {}", e))?
+        .downcast::<gstreamer::Pipeline>()
+        .map_err(|_| "Failed to downcast to Pipeline".to_string())?;
+
+        let device = Self {
+            path,
+            created,
+            width,
+            height,
+            feeder,
+        };
+        start_pipeline(&device.feeder)?;
+        Ok(device)
+    }
+
+    /// Device node, e.g. `/dev/video9`
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Start reading the device back like a camera
+    pub fn reader(&self) -> Result<LoopbackReader, String> {
+        let pipeline = gstreamer::parse::launch(&format!(
+            "v4l2src device={} ! video/x-raw,format=YUY2,width={},height={} ! \
+             appsink name=sink max-buffers=2 drop=true sync=false",
+            self.path.display(),
+            self.width,
+            self.height
+        ))
+        .map_err(|e| format!("Failed to create loopback reader: {}", e))?
+        .downcast::<gstreamer::Pipeline>()
+        .map_err(|_| "Failed to downcast to Pipeline".to_string())?;
+        let appsink = pipeline
+            .by_name("sink")
+            .and_then(|sink| sink.downcast::<gstreamer_app::AppSink>().ok())
+            .ok_or_else(|| "Failed to find appsink".to_string())?;
+        start_pipeline(&pipeline)?;
+
+        Ok(LoopbackReader {
+            pipeline,
+            appsink,
+            width: self.width,
+            height: self.height,
+        })
+    }
+}
+
+impl Drop for LoopbackDevice {
+    fn drop(&mut self) {
+        let _ = self.feeder.set_state(gstreamer::State::Null);
+        if self.created {
+            let _ = Command::new("v4l2loopback-ctl")
+                .arg("delete")
+                .arg(&self.path)
+                .status();
+        }
+    }
+}
+
+/// Frames read back from a [`LoopbackDevice`]
+pub struct LoopbackReader {
+    pipeline: gstreamer::Pipeline,
+    appsink: gstreamer_app::AppSink,
+    width: u32,
+    height: u32,
+}
+
+impl LoopbackReader {
+    /// Next frame, waiting up to `timeout`
+    pub fn next_frame(&self, timeout: Duration) -> Result<CameraFrame, String> {
+        let sample = self
+            .appsink
+            .try_pull_sample(gstreamer::ClockTime::from_nseconds(
+                timeout.as_nanos() as u64
+            ))
+            .ok_or_else(|| "Timeout waiting for a loopback frame".to_string())?;
+        let buffer = sample
+            .buffer()
+            .ok_or_else(|| "No buffer in sample".to_string())?;
+        let map = buffer
+            .map_readable()
+            .map_err(|_| "Failed to map buffer".to_string())?;
+
+        Ok(CameraFrame {
+            width: self.width,
+            height: self.height,
+            data: FrameData::Copied(Arc::from(map.as_slice())),
+            format: PixelFormat::YUYV,
+            stride: self.width * 2,
+            yuv_planes: None,
+            captured_at: Instant::now(),
+            sensor_timestamp_ns: None,
+            libcamera_metadata: None,
+        })
+    }
+}
+
+impl Drop for LoopbackReader {
+    fn drop(&mut self) {
+        let _ = self.pipeline.set_state(gstreamer::State::Null);
+    }
+}
+
+/// First existing v4l2loopback node
+fn find_loopback() -> Option<PathBuf> {
+    scan_video_device_nodes()
+        .into_iter()
+        .map(|name| Path::new("/dev").join(name))
+        .find(|path| {
+            path.to_str()
+                .and_then(get_v4l2_driver)
+                .is_some_and(|driver| driver == LOOPBACK_DRIVER)
+        })
+}
+
+/// Create a loopback device with `v4l2loopback-ctl`, which prints its node
+fn create_loopback() -> Result<PathBuf, String> {
+    let output = Command::new("v4l2loopback-ctl")
+        .args(["add", "-n", LOOPBACK_LABEL, "-x", "1"])
+        .output()
+        .map_err(|e| {
+            format!(
+                "No v4l2loopback device, and v4l2loopback-ctl is unavailable ({}). \
+                 Load one with `sudo modprobe v4l2loopback exclusive_caps=1`.",
+                e
+            )
+        })?;
+    if !output.status.success() {
+        return Err(format!(
+            "v4l2loopback-ctl failed: {} (try `sudo modprobe v4l2loopback exclusive_caps=1`)",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .find(|word| word.starts_with("/dev/video"))
+        .map(PathBuf::from)
+        .ok_or_else(|| "v4l2loopback-ctl did not report the new device".to_string())
+}
+
+/// Set a pipeline playing and wait until it is, or report its error
+fn start_pipeline(pipeline: &gstreamer::Pipeline) -> Result<(), String> {
+    pipeline
+        .set_state(gstreamer::State::Playing)
+        .map_err(|e| format!("Failed to start pipeline: {:?}", e))?;
+    let bus = pipeline
+        .bus()
+        .ok_or_else(|| "Pipeline has no bus".to_string())?;
+    let deadline = Instant::now() + PIPELINE_START_TIMEOUT;
+    while Instant::now() < deadline {
+        if let Some(msg) = bus.timed_pop(gstreamer::ClockTime::from_mseconds(100)) {
+            use gstreamer::MessageView;
+            match msg.view() {
+                MessageView::Error(err) => {
+                    let _ = pipeline.set_state(gstreamer::State::Null);
+                    return Err(format!("Pipeline error: {}", err.error()));
+                }
+                MessageView::StateChanged(state)
+                    if state.src() == Some(pipeline.upcast_ref())
+                        && state.current() == gstreamer::State::Playing =>
+                {
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+    }
+    let _ = pipeline.set_state(gstreamer::State::Null);
+    Err("Timeout starting pipeline".to_string())
+}
+
+/// Decode a saved photo and check its size and that it isn't blank
+pub fn verify_photo(path: &Path, expected: (u32, u32)) -> Result<(), String> {
+    let image = camera::storage::open_photo(path)?.to_rgba8();
+    let size = image.dimensions();
+    // Orientation is applied on decode, so either way round is fine
+    if size != expected && size != (expected.1, expected.0) {
+        return Err(format!(
+            "Photo is {}x{}, expected {}x{}",
+            size.0, size.1, expected.0, expected.1
+        ));
+    }
+    let first = image.pixels().next().copied();
+    if image.pixels().all(|pixel| Some(*pixel) == first) {
+        return Err("Photo is a single flat color".to_string());
+    }
+    Ok(())
+}
+
+/// What [`verify_video`] found
+#[derive(Debug, Clone, PartialEq)]
+pub struct VideoSummary {
+    pub frames: usize,
+    pub duration: Option<f64>,
+}
+
+/// Check a saved recording's container is complete, then decode every frame
+pub fn verify_video(
+    path: &Path,
+    expected: (u32, u32),
+    min_frames: usize,
+) -> Result<VideoSummary, String> {
+    let is_mp4 = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| matches!(e.to_lowercase().as_str(), "mp4" | "mov"));
+    if is_mp4 {
+        let data =
+            std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let boxes = mp4_top_level_boxes(&data)?;
+        for required in [b"ftyp", b"moov", b"mdat"] {
+            if !boxes.iter().any(|(kind, _)| kind == required) {
+                return Err(format!(
+                    "MP4 has no '{}' box; the recording was not finalized",
+                    String::from_utf8_lossy(required)
+                ));
+            }
+        }
+    }
+
+    let mut reader = VideoFrameReader::open(path).map_err(|e| e.to_string())?;
+    let duration = reader.duration();
+    let mut frames = 0;
+    while let Some((_, frame)) = reader.next_frame().map_err(|e| e.to_string())? {
+        if (frame.width, frame.height) != expected && (frame.height, frame.width) != expected {
+            return Err(format!(
+                "Video frame is {}x{}, expected {}x{}",
+                frame.width, frame.height, expected.0, expected.1
+            ));
+        }
+        frames += 1;
+    }
+    if frames < min_frames {
+        return Err(format!(
+            "Video has {} frames, expected at least {}",
+            frames, min_frames
+        ));
+    }
+    Ok(VideoSummary { frames, duration })
+}
+
+/// Type and size of each top-level box of an MP4 (ISO BMFF) file. Fails
+/// when a box runs past the end, as in a file cut off mid-write.
+pub fn mp4_top_level_boxes(data: &[u8]) -> Result<Vec<([u8; 4], u64)>, String> {
+    let mut boxes = Vec::new();
+    let mut offset = 0usize;
+    while offset < data.len() {
+        let header = data
+            .get(offset..offset + 8)
+            .ok_or_else(|| format!("Truncated box header at byte {}", offset))?;
+        let kind: [u8; 4] = header[4..8].try_into().unwrap_or_default();
+        let size = match u32::from_be_bytes(header[..4].try_into().unwrap_or_default()) {
+            // 64-bit size follows the type
+            1 => data
+                .get(offset + 8..offset + 16)
+                .and_then(|size| size.try_into().ok())
+                .map(u64::from_be_bytes)
+                .ok_or_else(|| format!("Truncated box header at byte {}", offset))?,
+            // Box runs to the end of the file
+            0 => (data.len() - offset) as u64,
+            size => u64::from(size),
+        };
+        if size < 8 || offset as u64 + size > data.len() as u64 {
+            return Err(format!(
+                "'{}' box at byte {} is {} bytes but the file ends first",
+                String::from_utf8_lossy(&kind),
+                offset,
+                size
+            ));
+        }
+        boxes.push((kind, size));
+        offset += size as usize;
+    }
+    Ok(boxes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mp4_box(kind: &[u8; 4], payload: usize) -> Vec<u8> {
+        let mut data = ((payload + 8) as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(kind);
+        data.resize(payload + 8, 0);
+        data
+    }
+
+    #[test]
+    fn mp4_boxes_are_listed_and_truncation_is_caught() {
+        let mut file = [
+            mp4_box(b"ftyp", 16),
+            mp4_box(b"mdat", 100),
+            mp4_box(b"moov", 40),
+        ]
+        .concat();
+        assert_eq!(
+            mp4_top_level_boxes(&file).unwrap(),
+            vec![(*b"ftyp", 24), (*b"mdat", 108), (*b"moov", 48)]
+        );
+
+        // A recording killed before the moov box was complete
+        file.truncate(file.len() - 10);
+        assert!(mp4_top_level_boxes(&file).is_err());
+    }
+
+    #[test]
+    fn test_pattern_moves_between_frames() {
+        let first = test_pattern(64, 32, 0);
+        let second = test_pattern(64, 32, 1);
+        assert_eq!(first.data.len(), 64 * 32 * 4);
+        assert_ne!(first.data.as_ref(), second.data.as_ref());
+    }
+}
@@ -180,6 +180,72 @@ fn reduce_sharpness(
     }
 }
 
+//=============================================================================
+// Focus stacking: per-pixel sharpness weighting
+//
+// Uses the main bindings with `partial_sums` as an RGBA accumulation buffer
+// (colour * weight in rgb, weight in a), one entry per pixel of the frame.
+// `accumulate_focus_weighted` runs once per aligned frame of a focus sweep,
+// then `normalize_focus_stack` divides the weights out in place. Each pixel
+// ends up taken mostly from the frames where its neighbourhood is sharpest.
+//=============================================================================
+
+// Neighbourhood the Laplacian energy is averaged over (5x5), so weights
+// follow detail rather than single noisy pixels
+const FOCUS_WINDOW_RADIUS: i32 = 2;
+// Higher powers pick the sharpest frame more decisively
+const FOCUS_WEIGHT_POWER: f32 = 2.0;
+// Keeps flat areas, sharp in no frame, evenly averaged
+const FOCUS_WEIGHT_FLOOR: f32 = 1e-10;
+
+fn local_focus_energy(x: i32, y: i32) -> f32 {
+    var energy = 0.0;
+    for (var dy = -FOCUS_WINDOW_RADIUS; dy <= FOCUS_WINDOW_RADIUS; dy++) {
+        for (var dx = -FOCUS_WINDOW_RADIUS; dx <= FOCUS_WINDOW_RADIUS; dx++) {
+            let lap = compute_laplacian(x + dx, y + dy);
+            energy += lap * lap;
+        }
+    }
+    let side = f32(2 * FOCUS_WINDOW_RADIUS + 1);
+    return energy / (side * side);
+}
+
+@compute @workgroup_size(16, 16)
+fn accumulate_focus_weighted(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let x = gid.x;
+    let y = gid.y;
+
+    if (x >= params.width || y >= params.height) {
+        return;
+    }
+
+    let energy = local_focus_energy(i32(x), i32(y));
+    let weight = pow(energy, FOCUS_WEIGHT_POWER) + FOCUS_WEIGHT_FLOOR;
+
+    let idx = get_pixel_idx(x, y);
+    partial_sums[idx] += input_frame[idx] * weight;
+    partial_sums[idx + 1u] += input_frame[idx + 1u] * weight;
+    partial_sums[idx + 2u] += input_frame[idx + 2u] * weight;
+    partial_sums[idx + 3u] += weight;
+}
+
+@compute @workgroup_size(16, 16)
+fn normalize_focus_stack(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let x = gid.x;
+    let y = gid.y;
+
+    if (x >= params.width || y >= params.height) {
+        return;
+    }
+
+    let idx = get_pixel_idx(x, y);
+    let weight = max(partial_sums[idx + 3u], 1e-30);
+    partial_sums[idx] /= weight;
+    partial_sums[idx + 1u] /= weight;
+    partial_sums[idx + 2u] /= weight;
+    partial_sums[idx + 3u] = 1.0;
+}
+
 //=============================================================================
 // Alternative: Simple per-pixel sharpness (no reduction, for debugging)
 //=============================================================================
@@ -0,0 +1,176 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//
+// Multi-frame super-resolution
+//
+// Drizzle-style reconstruction onto a grid twice as fine as the input. Each
+// frame of the burst is gathered onto the fine grid through its sub-pixel
+// alignment: every output pixel looks up where it lies in the frame and
+// takes the nearest input pixels, weighted by how close their centres fall.
+// Hand shake moves the frames by fractions of a pixel, so together they
+// fill in positions between the reference frame's pixels.
+//
+// `accumulate_frame` runs once per frame, the reference first, and adds
+// (colour * weight, weight) to the accumulation buffer; `normalize_accum`
+// then divides the weight out in place. The reference covers every output
+// pixel, so no pixel is left without samples.
+
+struct SuperResParams {
+    width: u32,             // Input frame width
+    height: u32,            // Input frame height
+    out_width: u32,         // Output width (width * scale)
+    out_height: u32,        // Output height (height * scale)
+    n_tiles_x: u32,         // Alignment tile grid
+    n_tiles_y: u32,
+    tile_step: u32,         // Distance between alignment tile centres, in input pixels
+    is_reference: u32,      // 1 for the reference frame (no displacement)
+    kernel_sigma: f32,      // Gaussian kernel width, in output pixels
+    robust_tolerance: f32,  // Colour difference to the reference still trusted
+    _padding0: u32,
+    _padding1: u32,
+}
+
+// Frame being accumulated (RGBA f32, input resolution)
+@group(0) @binding(0)
+var<storage, read> input_frame: array<f32>;
+
+// Reference frame (RGBA f32, input resolution)
+@group(0) @binding(1)
+var<storage, read> reference_frame: array<f32>;
+
+// Per-tile alignment of the frame against the reference (sub-pixel)
+@group(0) @binding(2)
+var<storage, read> alignment: array<vec2<f32>>;
+
+// Colour * weight in xyz, weight in w (output resolution)
+@group(0) @binding(3)
+var<storage, read_write> accum: array<vec4<f32>>;
+
+@group(0) @binding(4)
+var<uniform> params: SuperResParams;
+
+//=============================================================================
+// Utility functions
+//=============================================================================
+
+fn fetch(frame_is_reference: bool, x: i32, y: i32) -> vec3<f32> {
+    let cx = clamp(x, 0, i32(params.width) - 1);
+    let cy = clamp(y, 0, i32(params.height) - 1);
+    let idx = (u32(cy) * params.width + u32(cx)) * 4u;
+    if (frame_is_reference) {
+        return vec3<f32>(reference_frame[idx], reference_frame[idx + 1u], reference_frame[idx + 2u]);
+    }
+    return vec3<f32>(input_frame[idx], input_frame[idx + 1u], input_frame[idx + 2u]);
+}
+
+// Bilinear sample of the reference, what other frames are checked against
+fn sample_reference(pos: vec2<f32>) -> vec3<f32> {
+    let p0 = floor(pos);
+    let f = pos - p0;
+    let x0 = i32(p0.x);
+    let y0 = i32(p0.y);
+    let top = mix(fetch(true, x0, y0), fetch(true, x0 + 1, y0), f.x);
+    let bottom = mix(fetch(true, x0, y0 + 1), fetch(true, x0 + 1, y0 + 1), f.x);
+    return mix(top, bottom, f.y);
+}
+
+fn get_tile_alignment(tx: i32, ty: i32) -> vec2<f32> {
+    let cx = clamp(tx, 0, i32(params.n_tiles_x) - 1);
+    let cy = clamp(ty, 0, i32(params.n_tiles_y) - 1);
+    return alignment[u32(cy) * params.n_tiles_x + u32(cx)];
+}
+
+// Displacement at an input position, interpolated between tiles the same
+// way the warp shader does it
+fn get_interpolated_alignment(pos: vec2<f32>) -> vec2<f32> {
+    let tile_f = (pos + 0.5) / f32(params.tile_step) - 1.0;
+    let t0 = vec2<i32>(floor(tile_f));
+    let f = fract(tile_f);
+
+    let a00 = get_tile_alignment(t0.x, t0.y);
+    let a10 = get_tile_alignment(t0.x + 1, t0.y);
+    let a01 = get_tile_alignment(t0.x, t0.y + 1);
+    let a11 = get_tile_alignment(t0.x + 1, t0.y + 1);
+
+    let top = mix(a00, a10, f.x);
+    let bottom = mix(a01, a11, f.x);
+    return mix(top, bottom, f.y);
+}
+
+// Where an output pixel's centre lies on the input grid
+fn input_position(x: u32, y: u32) -> vec2<f32> {
+    let scale = vec2<f32>(
+        f32(params.out_width) / f32(params.width),
+        f32(params.out_height) / f32(params.height)
+    );
+    return (vec2<f32>(f32(x), f32(y)) + 0.5) / scale - 0.5;
+}
+
+//=============================================================================
+// Accumulation (once per frame)
+//=============================================================================
+
+@compute @workgroup_size(16, 16)
+fn accumulate_frame(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let x = gid.x;
+    let y = gid.y;
+    if (x >= params.out_width || y >= params.out_height) {
+        return;
+    }
+
+    let is_reference = params.is_reference == 1u;
+    let ref_pos = input_position(x, y);
+
+    // Inverse mapping, as in the warp: the frame shows the reference's
+    // position `p` at `p - displacement`
+    var src = ref_pos;
+    if (!is_reference) {
+        src = ref_pos - get_interpolated_alignment(ref_pos);
+    }
+
+    // Gaussian splat of the 2x2 nearest input pixels, measured in output
+    // pixels so the kernel stays as sharp as the finer grid
+    let scale = f32(params.out_width) / f32(params.width);
+    let base = floor(src);
+    let inv_two_sigma_sq = 1.0 / (2.0 * params.kernel_sigma * params.kernel_sigma);
+    var colour_sum = vec3<f32>(0.0);
+    var weight_sum = 0.0;
+    for (var dy = 0; dy < 2; dy++) {
+        for (var dx = 0; dx < 2; dx++) {
+            let p = base + vec2<f32>(f32(dx), f32(dy));
+            let dist = (p - src) * scale;
+            let w = exp(-dot(dist, dist) * inv_two_sigma_sq);
+            colour_sum += fetch(is_reference, i32(p.x), i32(p.y)) * w;
+            weight_sum += w;
+        }
+    }
+
+    // Robustness: trust the frame less where it disagrees with the
+    // reference (subject motion, alignment that failed)
+    var robustness = 1.0;
+    if (!is_reference) {
+        let frame_colour = colour_sum / max(weight_sum, 1e-6);
+        let diff = length(frame_colour - sample_reference(ref_pos));
+        let tolerance = max(params.robust_tolerance, 1e-4);
+        robustness = exp(-(diff * diff) / (tolerance * tolerance));
+    }
+
+    let idx = y * params.out_width + x;
+    accum[idx] += vec4<f32>(colour_sum * robustness, weight_sum * robustness);
+}
+
+//=============================================================================
+// Normalization (once, after all frames)
+//=============================================================================
+
+@compute @workgroup_size(16, 16)
+fn normalize_accum(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let x = gid.x;
+    let y = gid.y;
+    if (x >= params.out_width || y >= params.out_height) {
+        return;
+    }
+
+    let idx = y * params.out_width + x;
+    let sum = accum[idx];
+    accum[idx] = vec4<f32>(sum.xyz / max(sum.w, 1e-6), 1.0);
+}
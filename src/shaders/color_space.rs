@@ -0,0 +1,184 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! YUV color space of the camera signal
+//!
+//! YUV frames are decoded to RGB with the matrix (BT.601, BT.709 or BT.2020)
+//! and range (limited 16–235 or full 0–255) they were encoded with. A wrong
+//! guess shows as washed-out or oversaturated colors and shifted hues, and
+//! since the preview and the saved files share the conversion shader, they
+//! would be wrong the same way.
+//!
+//! The app sets the color space of the active camera (see
+//! [`set_color_space`]) from what the driver reports; MJPEG frames decoded
+//! by libjpeg-turbo are always JFIF (BT.601, full range).
+
+use super::hdr::frame_transfer;
+use crate::backends::camera::types::PixelFormat;
+use crate::backends::camera::v4l2_utils::V4l2Colorimetry;
+use std::sync::RwLock;
+
+// `v4l2_colorspace` values whose default YCbCr encoding is not BT.601
+const V4L2_COLORSPACE_REC709: u32 = 3;
+const V4L2_COLORSPACE_JPEG: u32 = 7;
+const V4L2_COLORSPACE_BT2020: u32 = 10;
+const V4L2_COLORSPACE_DCI_P3: u32 = 12;
+
+// `v4l2_ycbcr_encoding` values
+const V4L2_YCBCR_ENC_DEFAULT: u32 = 0;
+const V4L2_YCBCR_ENC_709: u32 = 2;
+const V4L2_YCBCR_ENC_XV709: u32 = 4;
+const V4L2_YCBCR_ENC_BT2020: u32 = 6;
+const V4L2_YCBCR_ENC_BT2020_CONST_LUM: u32 = 7;
+
+// `v4l2_quantization` values
+const V4L2_QUANTIZATION_FULL_RANGE: u32 = 1;
+const V4L2_QUANTIZATION_LIM_RANGE: u32 = 2;
+
+/// Matrix between YUV and RGB
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum YuvMatrix {
+    /// SD video, JPEG and most webcams
+    #[default]
+    Bt601,
+    /// HD video
+    Bt709,
+    /// UHD and HDR video
+    Bt2020,
+}
+
+/// YUV encoding of a camera signal
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ColorSpace {
+    pub matrix: YuvMatrix,
+    /// Samples use the whole 0–255 range rather than 16–235 (16–240 chroma)
+    pub full_range: bool,
+}
+
+impl ColorSpace {
+    /// JFIF: what libjpeg-turbo decodes MJPEG to
+    pub const JPEG: Self = Self {
+        matrix: YuvMatrix::Bt601,
+        full_range: true,
+    };
+
+    /// Color space a V4L2 driver reports, with the kernel's defaults
+    /// (`V4L2_MAP_YCBCR_ENC_DEFAULT`, `V4L2_MAP_QUANTIZATION_DEFAULT`)
+    /// filled in for the fields it leaves at 0
+    pub fn from_v4l2(colorimetry: &V4l2Colorimetry) -> Self {
+        let ycbcr_enc = if colorimetry.ycbcr_enc == V4L2_YCBCR_ENC_DEFAULT {
+            match colorimetry.colorspace {
+                V4L2_COLORSPACE_REC709 | V4L2_COLORSPACE_DCI_P3 => V4L2_YCBCR_ENC_709,
+                V4L2_COLORSPACE_BT2020 => V4L2_YCBCR_ENC_BT2020,
+                _ => V4L2_YCBCR_ENC_DEFAULT,
+            }
+        } else {
+            colorimetry.ycbcr_enc
+        };
+        let matrix = match ycbcr_enc {
+            V4L2_YCBCR_ENC_709 | V4L2_YCBCR_ENC_XV709 => YuvMatrix::Bt709,
+            V4L2_YCBCR_ENC_BT2020 | V4L2_YCBCR_ENC_BT2020_CONST_LUM => YuvMatrix::Bt2020,
+            // BT.601, xvYCC 601, sYCC, and SMPTE 240M, which is close to it
+            _ => YuvMatrix::Bt601,
+        };
+        let full_range = match colorimetry.quantization {
+            V4L2_QUANTIZATION_FULL_RANGE => true,
+            V4L2_QUANTIZATION_LIM_RANGE => false,
+            _ => colorimetry.colorspace == V4L2_COLORSPACE_JPEG,
+        };
+        Self { matrix, full_range }
+    }
+
+    /// Value of the conversion shaders' `matrix` parameter
+    /// (0=BT.601, 1=BT.709, 2=BT.2020, see `yuv_convert.wgsl`)
+    pub fn matrix_code(self) -> u32 {
+        match self.matrix {
+            YuvMatrix::Bt601 => 0,
+            YuvMatrix::Bt709 => 1,
+            YuvMatrix::Bt2020 => 2,
+        }
+    }
+}
+
+/// Color space of the active camera's YUV frames
+static COLOR_SPACE: RwLock<ColorSpace> = RwLock::new(ColorSpace {
+    matrix: YuvMatrix::Bt601,
+    full_range: false,
+});
+
+/// Set the color space of the active camera's YUV frames
+pub fn set_color_space(color_space: ColorSpace) {
+    if let Ok(mut current) = COLOR_SPACE.write() {
+        *current = color_space;
+    }
+}
+
+/// Color space of the active camera's YUV frames
+pub fn color_space() -> ColorSpace {
+    COLOR_SPACE
+        .read()
+        .map(|color_space| *color_space)
+        .unwrap_or_default()
+}
+
+/// Color space of a frame in `format`: HDR is always BT.2020, whatever a
+/// capture card that was told to treat its input as HDR reports
+pub fn frame_color_space(format: PixelFormat) -> ColorSpace {
+    let active = color_space();
+    if frame_transfer(format).is_hdr() {
+        ColorSpace {
+            matrix: YuvMatrix::Bt2020,
+            ..active
+        }
+    } else {
+        active
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v4l2(colorspace: u32, ycbcr_enc: u32, quantization: u32) -> V4l2Colorimetry {
+        V4l2Colorimetry {
+            colorspace,
+            ycbcr_enc,
+            quantization,
+            xfer_func: 0,
+        }
+    }
+
+    #[test]
+    fn driver_defaults_follow_the_colorspace() {
+        // UVC webcams report sRGB: BT.601, limited range
+        assert_eq!(ColorSpace::from_v4l2(&v4l2(8, 0, 0)), ColorSpace::default());
+        assert_eq!(
+            ColorSpace::from_v4l2(&v4l2(3, 0, 0)),
+            ColorSpace {
+                matrix: YuvMatrix::Bt709,
+                full_range: false,
+            }
+        );
+        assert_eq!(
+            ColorSpace::from_v4l2(&v4l2(10, 0, 0)).matrix,
+            YuvMatrix::Bt2020
+        );
+        assert_eq!(ColorSpace::from_v4l2(&v4l2(7, 0, 0)), ColorSpace::JPEG);
+    }
+
+    #[test]
+    fn explicit_encoding_and_range_win() {
+        assert_eq!(
+            ColorSpace::from_v4l2(&v4l2(8, 2, 1)),
+            ColorSpace {
+                matrix: YuvMatrix::Bt709,
+                full_range: true,
+            }
+        );
+        assert_eq!(
+            ColorSpace::from_v4l2(&v4l2(7, 1, 2)),
+            ColorSpace {
+                matrix: YuvMatrix::Bt601,
+                full_range: false,
+            }
+        );
+    }
+}
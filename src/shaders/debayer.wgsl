@@ -7,9 +7,12 @@
 // - GRBG (2): Row 0: G R G R..., Row 1: B G B G...
 // - GBRG (3): Row 0: G B G B..., Row 1: R G R G...
 //
-// Algorithm: Bilinear interpolation
-// - Each pixel directly measures one color (R, G, or B based on position)
-// - The other two colors are interpolated from neighboring pixels
+// Algorithms (params.demosaic):
+// - Bilinear (0, preview): the other two colors are averaged from the
+//   nearest neighbors that measure them
+// - Malvar-He-Cutler (1, stills): bilinear plus a correction from the
+//   measured channel's 5x5 Laplacian, which keeps edges sharp and avoids
+//   most color fringing
 //
 // White balance gains are read from a storage buffer (binding 3).
 // When ISP provides gains, the CPU writes them directly to the buffer.
@@ -21,7 +24,7 @@ struct DebayerParams {
     pattern: u32,           // 0=RGGB, 1=BGGR, 2=GRBG, 3=GBRG
     use_isp_colour: u32,    // 1 = apply gains+CCM, 0 = raw output
     black_level: f32,
-    _pad0: u32,
+    demosaic: u32,          // 0 = bilinear, 1 = Malvar-He-Cutler
     _pad1: u32,
     _pad2: u32,
     ccm_row0: vec4<f32>,
@@ -85,6 +88,67 @@ fn get_pixel_type(x: u32, y: u32, pattern: u32) -> u32 {
     }
 }
 
+// Bilinear interpolation based on pixel position in Bayer pattern
+fn demosaic_bilinear(ix: i32, iy: i32, pixel_type: u32) -> vec3<f32> {
+    let center = sample_bayer(ix, iy);
+    let adjacent = (sample_bayer(ix - 1, iy) + sample_bayer(ix + 1, iy) +
+                 sample_bayer(ix, iy - 1) + sample_bayer(ix, iy + 1)) * 0.25;
+    let diagonal = (sample_bayer(ix - 1, iy - 1) + sample_bayer(ix + 1, iy - 1) +
+                    sample_bayer(ix - 1, iy + 1) + sample_bayer(ix + 1, iy + 1)) * 0.25;
+    let horizontal = (sample_bayer(ix - 1, iy) + sample_bayer(ix + 1, iy)) * 0.5;
+    let vertical = (sample_bayer(ix, iy - 1) + sample_bayer(ix, iy + 1)) * 0.5;
+
+    if (pixel_type == 0u) {
+        // Red pixel - interpolate G and B
+        return vec3(center, adjacent, diagonal);
+    } else if (pixel_type == 1u) {
+        // Green pixel on R row - R from horizontal neighbors, B from vertical
+        return vec3(horizontal, center, vertical);
+    } else if (pixel_type == 2u) {
+        // Green pixel on B row - R from vertical neighbors, B from horizontal
+        return vec3(vertical, center, horizontal);
+    }
+    // Blue pixel - interpolate R and G
+    return vec3(diagonal, adjacent, center);
+}
+
+// Malvar-He-Cutler gradient-corrected interpolation (5x5 kernels, /8)
+fn demosaic_mhc(ix: i32, iy: i32, pixel_type: u32) -> vec3<f32> {
+    let c = sample_bayer(ix, iy);
+    let n1 = sample_bayer(ix, iy - 1);
+    let s1 = sample_bayer(ix, iy + 1);
+    let w1 = sample_bayer(ix - 1, iy);
+    let e1 = sample_bayer(ix + 1, iy);
+    let n2 = sample_bayer(ix, iy - 2);
+    let s2 = sample_bayer(ix, iy + 2);
+    let w2 = sample_bayer(ix - 2, iy);
+    let e2 = sample_bayer(ix + 2, iy);
+    let diagonals = sample_bayer(ix - 1, iy - 1) + sample_bayer(ix + 1, iy - 1) +
+                    sample_bayer(ix - 1, iy + 1) + sample_bayer(ix + 1, iy + 1);
+
+    // Green at a red or blue site
+    let g_at_rb = (4.0 * c + 2.0 * (n1 + s1 + w1 + e1) - (n2 + s2 + w2 + e2)) / 8.0;
+    // Red at blue (or blue at red): the other chroma channel on the diagonals
+    let rb_at_br = (6.0 * c + 2.0 * diagonals - 1.5 * (n2 + s2 + w2 + e2)) / 8.0;
+    // Chroma at a green site whose same-colored neighbors are left/right
+    let at_g_horizontal = (5.0 * c + 4.0 * (w1 + e1) - (w2 + e2) - diagonals +
+                           0.5 * (n2 + s2)) / 8.0;
+    // Chroma at a green site whose same-colored neighbors are above/below
+    let at_g_vertical = (5.0 * c + 4.0 * (n1 + s1) - (n2 + s2) - diagonals +
+                         0.5 * (w2 + e2)) / 8.0;
+
+    if (pixel_type == 0u) {
+        return vec3(c, g_at_rb, rb_at_br);
+    } else if (pixel_type == 1u) {
+        // Green on R row: red left/right, blue above/below
+        return vec3(at_g_horizontal, c, at_g_vertical);
+    } else if (pixel_type == 2u) {
+        // Green on B row: red above/below, blue left/right
+        return vec3(at_g_vertical, c, at_g_horizontal);
+    }
+    return vec3(rb_at_br, g_at_rb, c);
+}
+
 @compute @workgroup_size(16, 16)
 fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let x = global_id.x;
@@ -99,38 +163,15 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
 
     let pixel_type = get_pixel_type(x, y, params.pattern);
 
-    var r: f32;
-    var g: f32;
-    var b: f32;
-
-    let center = sample_bayer(ix, iy);
-
-    // Bilinear interpolation based on pixel position in Bayer pattern
-    if (pixel_type == 0u) {
-        // Red pixel - interpolate G and B
-        r = center;
-        g = (sample_bayer(ix - 1, iy) + sample_bayer(ix + 1, iy) +
-             sample_bayer(ix, iy - 1) + sample_bayer(ix, iy + 1)) * 0.25;
-        b = (sample_bayer(ix - 1, iy - 1) + sample_bayer(ix + 1, iy - 1) +
-             sample_bayer(ix - 1, iy + 1) + sample_bayer(ix + 1, iy + 1)) * 0.25;
-    } else if (pixel_type == 1u) {
-        // Green pixel on R row - interpolate R (horizontal neighbors) and B (vertical neighbors)
-        r = (sample_bayer(ix - 1, iy) + sample_bayer(ix + 1, iy)) * 0.5;
-        g = center;
-        b = (sample_bayer(ix, iy - 1) + sample_bayer(ix, iy + 1)) * 0.5;
-    } else if (pixel_type == 2u) {
-        // Green pixel on B row - interpolate R (vertical neighbors) and B (horizontal neighbors)
-        r = (sample_bayer(ix, iy - 1) + sample_bayer(ix, iy + 1)) * 0.5;
-        g = center;
-        b = (sample_bayer(ix - 1, iy) + sample_bayer(ix + 1, iy)) * 0.5;
+    var rgb_raw: vec3<f32>;
+    if (params.demosaic == 1u) {
+        rgb_raw = demosaic_mhc(ix, iy, pixel_type);
     } else {
-        // Blue pixel - interpolate R and G
-        r = (sample_bayer(ix - 1, iy - 1) + sample_bayer(ix + 1, iy - 1) +
-             sample_bayer(ix - 1, iy + 1) + sample_bayer(ix + 1, iy + 1)) * 0.25;
-        g = (sample_bayer(ix - 1, iy) + sample_bayer(ix + 1, iy) +
-             sample_bayer(ix, iy - 1) + sample_bayer(ix, iy + 1)) * 0.25;
-        b = center;
+        rgb_raw = demosaic_bilinear(ix, iy, pixel_type);
     }
+    var r = rgb_raw.r;
+    var g = rgb_raw.g;
+    var b = rgb_raw.b;
 
     // Apply white balance and colour correction (in linear space, before gamma)
     if (params.use_isp_colour == 1u) {
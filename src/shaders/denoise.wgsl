@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: GPL-3.0-only
+// GPU compute shader for low-light video denoising: an edge-preserving 3x3
+// spatial pass, then a running average with the previous output that backs
+// off wherever the picture changed by more than the noise, like the burst
+// merge's robustness weighting
+
+struct DenoiseParams {
+    width: u32,
+    height: u32,
+    // Noise standard deviation, 0-1 scale
+    noise_sd: f32,
+    // Blend of the spatial pass over the source (0-1)
+    spatial: f32,
+    // Largest weight the previous output gets (0-1)
+    temporal: f32,
+    // 0 on the first frame after a reset
+    has_history: u32,
+    _padding: vec2<u32>,
+}
+
+@group(0) @binding(0)
+var<storage, read> input_buffer: array<u32>;
+
+// Previous output at full precision, updated in place
+@group(0) @binding(1)
+var<storage, read_write> history_buffer: array<vec4<f32>>;
+
+@group(0) @binding(2)
+var<storage, read_write> output_buffer: array<u32>;
+
+@group(0) @binding(3)
+var<uniform> params: DenoiseParams;
+
+fn load(x: i32, y: i32) -> vec4<f32> {
+    let cx = clamp(x, 0, i32(params.width) - 1);
+    let cy = clamp(y, 0, i32(params.height) - 1);
+    return unpack4x8unorm(input_buffer[u32(cy) * params.width + u32(cx)]);
+}
+
+@compute @workgroup_size(16, 16)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let x = global_id.x;
+    let y = global_id.y;
+
+    if (x >= params.width || y >= params.height) {
+        return;
+    }
+
+    let index = y * params.width + x;
+    let center = load(i32(x), i32(y));
+    let sigma = max(params.noise_sd, 1.0 / 255.0);
+
+    // Spatial: neighbours within a few sigma of the centre are averaged in,
+    // edges are not
+    var sum = vec3<f32>(0.0);
+    var weight_sum = 0.0;
+    for (var dy = -1; dy <= 1; dy++) {
+        for (var dx = -1; dx <= 1; dx++) {
+            let sample = load(i32(x) + dx, i32(y) + dy).rgb;
+            let diff = sample - center.rgb;
+            let weight = exp(-dot(diff, diff) / (18.0 * sigma * sigma));
+            sum += sample * weight;
+            weight_sum += weight;
+        }
+    }
+    var result = mix(center.rgb, sum / weight_sum, params.spatial);
+
+    // Temporal: trust the previous output while the difference is within
+    // the noise, fade it out over the next two sigma
+    if (params.has_history != 0u) {
+        let previous = history_buffer[index].rgb;
+        let diff = length(result - previous) / sqrt(3.0);
+        let robustness = clamp(1.0 - (diff - sigma) / (2.0 * sigma), 0.0, 1.0);
+        result = mix(result, previous, params.temporal * robustness);
+    }
+
+    history_buffer[index] = vec4<f32>(result, center.a);
+    output_buffer[index] = pack4x8unorm(vec4<f32>(result, center.a));
+}
@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: GPL-3.0-only
+// Film emulation pack (filter modes 15-18)
+//
+// Every stock runs the same three stages with its own parameters:
+// - Halation: light scattered back through the film base blooms red-orange
+//   around highlights. It needs a blurred highlight sample, so the
+//   texture-sampling callers (`texture_filters.wgsl` for fragment shaders,
+//   `filter_compute.wgsl` for compute) measure it and pass it in as `glow`.
+// - Tone curve: per-channel S-curve with lifted blacks and a soft shoulder,
+//   then split toning of shadows and highlights.
+// - Grain: clumped noise, strongest in the midtones.
+//
+// Requires `filters.wgsl` (luminance, hash) ahead of it.
+
+// Distance of the halation samples from the pixel, in UV units
+const FILM_HALATION_RADIUS: f32 = 0.008;
+
+struct FilmStock {
+    // Slope of the tone curve around mid-grey
+    contrast: f32,
+    // Output level of black and white, for lifted blacks and rolled-off whites
+    black_level: f32,
+    white_level: f32,
+    // 0 = black and white, 1 = unchanged, above 1 = more saturated
+    saturation: f32,
+    // Colour balance multiplied into shadows and highlights
+    shadow_tint: vec3<f32>,
+    highlight_tint: vec3<f32>,
+    // Grain strength (luma swing) and clump size in pixels
+    grain_amount: f32,
+    grain_size: f32,
+    // Strength of the red-orange glow around highlights
+    halation: f32,
+}
+
+fn is_film_mode(filter_mode: u32) -> bool {
+    return filter_mode >= 15u && filter_mode <= 18u;
+}
+
+// Parameters of each stock, keyed by filter mode
+fn film_stock(filter_mode: u32) -> FilmStock {
+    switch filter_mode {
+        // Portrait 400: warm skin tones, soft contrast, fine grain
+        case 15u: {
+            return FilmStock(0.85, 0.03, 0.97, 0.9,
+                vec3(1.02, 1.0, 0.96), vec3(1.04, 1.0, 0.94), 0.035, 1.0, 0.0);
+        }
+        // Slide 50: punchy contrast and colour, cool shadows, almost no grain
+        case 16u: {
+            return FilmStock(1.3, 0.0, 1.0, 1.35,
+                vec3(0.95, 0.97, 1.06), vec3(1.03, 1.01, 0.96), 0.015, 1.0, 0.0);
+        }
+        // Pan 400: contrasty black and white with coarse grain
+        case 17u: {
+            return FilmStock(1.25, 0.02, 0.98, 0.0,
+                vec3(1.0), vec3(1.0), 0.08, 2.0, 0.0);
+        }
+        // Tungsten 800: cool balance, lifted blacks, halation around lights
+        default: {
+            return FilmStock(1.05, 0.05, 0.95, 1.05,
+                vec3(0.94, 0.98, 1.08), vec3(0.98, 1.0, 1.04), 0.045, 1.5, 0.6);
+        }
+    }
+}
+
+// S-shaped response: contrast around mid-grey with smooth toe and shoulder
+fn film_curve(x: f32, stock: FilmStock) -> f32 {
+    let linear = clamp((x - 0.5) * stock.contrast + 0.5, 0.0, 1.0);
+    let curved = mix(linear, smoothstep(0.0, 1.0, linear), 0.35);
+    return mix(stock.black_level, stock.white_level, curved);
+}
+
+// Grain noise in -1..1 (triangular distribution, like summed silver clumps).
+// Seeding with the colour makes the pattern change as sensor noise and
+// motion change the frame, so grain dances on video without a frame counter.
+fn film_grain(pixel_pos: vec2<f32>, color: vec3<f32>, grain_size: f32) -> f32 {
+    let cell = floor(pixel_pos / grain_size);
+    let seed = dot(color, vec3(12.9898, 78.233, 37.719));
+    return hash(cell + seed) + hash(cell * 1.7 + seed + 13.1) - 1.0;
+}
+
+// Apply a film stock to a colour. `pixel_pos` is in source pixels; `glow`
+// is the local highlight bloom (0-1) measured by the caller.
+fn apply_film(color: vec3<f32>, filter_mode: u32, pixel_pos: vec2<f32>, glow: f32) -> vec3<f32> {
+    let stock = film_stock(filter_mode);
+
+    var rgb = color + vec3(1.0, 0.35, 0.1) * glow * stock.halation;
+    rgb = mix(vec3(luminance(rgb)), rgb, stock.saturation);
+    rgb = vec3(film_curve(rgb.r, stock), film_curve(rgb.g, stock), film_curve(rgb.b, stock));
+
+    let tone = clamp(luminance(rgb), 0.0, 1.0);
+    rgb *= mix(stock.shadow_tint, stock.highlight_tint, tone);
+
+    let grain_weight = 0.4 + 2.4 * tone * (1.0 - tone);
+    rgb += vec3(film_grain(pixel_pos, color, stock.grain_size) * stock.grain_amount * grain_weight);
+
+    return clamp(rgb, vec3(0.0), vec3(1.0));
+}
+
+// Highlight level of a sampled colour, for the halation glow
+fn film_highlight(color: vec3<f32>) -> f32 {
+    return smoothstep(0.75, 1.0, luminance(color));
+}
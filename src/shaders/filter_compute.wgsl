@@ -1,13 +1,15 @@
 // SPDX-License-Identifier: GPL-3.0-only
 // GPU compute shader for applying filters to images
 // Used by photo capture and virtual camera for GPU-accelerated filtering
-// Filter functions are prepended by the Rust code from filters.wgsl
+// Filter functions are prepended by the Rust code from filters.wgsl, film.wgsl and lut.wgsl
 
 struct FilterParams {
     width: u32,
     height: u32,
     filter_mode: u32,
     _padding: u32,
+    // Software white balance gains (rgb; 1.0 = unchanged), applied before the filter
+    wb_gains: vec4<f32>,
 }
 
 @group(0) @binding(0)
@@ -22,6 +24,10 @@ var<uniform> params: FilterParams;
 @group(0) @binding(3)
 var tex_sampler: sampler;
 
+// Imported LUT for filter mode 19 (identity when none is active)
+@group(0) @binding(4)
+var lut_texture: texture_3d<f32>;
+
 // Sample luminance at offset for edge detection
 fn sample_luminance_at(uv: vec2<f32>) -> f32 {
     let color = textureSampleLevel(input_texture, tex_sampler, uv, 0.0);
@@ -45,6 +51,17 @@ fn sobel_edge(uv: vec2<f32>, texel_size: vec2<f32>) -> f32 {
     return sqrt(gx * gx + gy * gy);
 }
 
+// Highlight bloom around a UV for film halation: a ring of highlight samples
+fn film_glow(uv: vec2<f32>) -> f32 {
+    var glow = 0.0;
+    for (var i = 0; i < 8; i++) {
+        let angle = f32(i) * 0.785398;
+        let offset = vec2<f32>(cos(angle), sin(angle)) * FILM_HALATION_RADIUS;
+        glow += film_highlight(textureSampleLevel(input_texture, tex_sampler, uv + offset, 0.0).rgb);
+    }
+    return glow / 8.0;
+}
+
 @compute @workgroup_size(16, 16)
 fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let x = global_id.x;
@@ -59,7 +76,8 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
 
     // Sample input
     let pixel = textureSampleLevel(input_texture, tex_sampler, tex_coords, 0.0);
-    var color = pixel.rgb;
+    let wb = params.wb_gains.rgb;
+    var color = min(pixel.rgb * wb, vec3<f32>(1.0));
 
     // Apply filter
     if (params.filter_mode <= 12u) {
@@ -70,7 +88,7 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
         let offset_uv = 0.004;
         let color_r = textureSampleLevel(input_texture, tex_sampler, tex_coords + vec2<f32>(offset_uv, 0.0), 0.0);
         let color_b = textureSampleLevel(input_texture, tex_sampler, tex_coords - vec2<f32>(offset_uv, 0.0), 0.0);
-        color = vec3<f32>(color_r.r, color.g, color_b.b);
+        color = min(vec3<f32>(color_r.r * wb.r, color.g, color_b.b * wb.b), vec3<f32>(1.0));
     } else if (params.filter_mode == 14u) {
         // Pencil: Pencil sketch drawing effect
         // When used with multi-pass pre-blur, input is already smoothed for clean edges.
@@ -91,6 +109,16 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
         let final_val = clamp(pencil * paper, 0.0, 1.0);
         // Slight warm tint for natural paper look
         color = vec3<f32>(final_val, final_val * 0.98, final_val * 0.95);
+    } else if (is_film_mode(params.filter_mode)) {
+        // Film stocks (15-18), with halation from re-sampled highlights
+        var glow = 0.0;
+        if (film_stock(params.filter_mode).halation > 0.0) {
+            glow = film_glow(tex_coords);
+        }
+        color = apply_film(color, params.filter_mode, vec2<f32>(f32(x), f32(y)), glow);
+    } else if (params.filter_mode == 19u) {
+        // Imported LUT
+        color = apply_lut(color, lut_texture, tex_sampler);
     }
 
     // Pack RGBA into u32 (RGBA8 format)
@@ -8,8 +8,15 @@
 //! - NV12/NV21: Semi-planar 4:2:0
 //! - I420: Planar 4:2:0 (also handles I422/I444 from MJPEG)
 //! - YUYV/UYVY/YVYU/VYUY: Packed 4:2:2
-//! - Gray8/RGB24/ABGR/BGRA: Single-plane conversions
+//! - P010/Y210: 10-bit semi-planar 4:2:0 and packed 4:2:2, uploaded as
+//!   byte pairs so no 16-bit texture support is needed
+//! - Gray8/RGB24/ABGR/BGRA/RGBx/BGRx: Single-plane conversions
 //! - Bayer: Raw sensor data (separate debayer shader)
+//!
+//! Each conversion runs at a [`ProcessingQuality`]: previews take the fast
+//! path, stills get a better demosaic plus a finishing pass
+//! (`still_finish.wgsl`) for chroma denoise. Sharpening is a separate pass
+//! on the final photo (see `gpu_sharpen`), so it follows the user's setting.
 
 use crate::app::FilterType;
 use crate::backends::camera::types::{CameraFrame, PixelFormat};
@@ -27,12 +34,43 @@ const TILE_WORKGROUP_SIZE: u32 = 16;
 /// Required byte alignment for `copy_buffer_to_texture` / `copy_texture_to_buffer` row stride.
 const COPY_BYTES_PER_ROW_ALIGNMENT: u32 = 256;
 
+/// Chroma denoise window radius of the still finishing pass (5×5)
+const STILL_CHROMA_RADIUS: i32 = 2;
+
+/// Processing quality tier for a conversion
+///
+/// Preview frames are converted every frame and must stay cheap; a still is
+/// converted once and can afford slower, better paths.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProcessingQuality {
+    /// Bilinear demosaic, no denoising (preview, recording, virtual camera)
+    #[default]
+    Preview,
+    /// Gradient-corrected demosaic and chroma denoise (photos)
+    Still,
+}
+
+impl ProcessingQuality {
+    /// Demosaic algorithm for debayer.wgsl: 0 = bilinear, 1 = Malvar-He-Cutler
+    fn demosaic_code(self) -> u32 {
+        match self {
+            ProcessingQuality::Preview => 0,
+            ProcessingQuality::Still => 1,
+        }
+    }
+
+    /// Whether the finishing pass runs after conversion
+    fn has_finish_pass(self) -> bool {
+        self == ProcessingQuality::Still
+    }
+}
+
 /// Round `value` up to the next multiple of [`COPY_BYTES_PER_ROW_ALIGNMENT`].
 fn align_to_copy_row(value: u32) -> u32 {
     (value + COPY_BYTES_PER_ROW_ALIGNMENT - 1) & !(COPY_BYTES_PER_ROW_ALIGNMENT - 1)
 }
 
-/// YUV conversion parameters (32 bytes, matches yuv_convert.wgsl)
+/// YUV conversion parameters (48 bytes, matches yuv_convert.wgsl)
 #[repr(C)]
 #[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
 struct ConvertParams {
@@ -42,8 +80,13 @@ struct ConvertParams {
     y_stride: u32,
     uv_stride: u32,
     v_stride: u32,
-    _pad0: u32,
-    _pad1: u32,
+    /// HDR transfer to tone-map from (`HdrTransfer::shader_code`)
+    transfer: u32,
+    /// YUV matrix (`ColorSpace::matrix_code`)
+    matrix: u32,
+    /// 1 when YUV samples are full range
+    full_range: u32,
+    _pad: [u32; 3],
 }
 
 /// Debayer conversion parameters (80 bytes, std140-compatible)
@@ -56,7 +99,7 @@ struct DebayerParams {
     pattern: u32,        // 0=RGGB, 1=BGGR, 2=GRBG, 3=GBRG
     use_isp_colour: u32, // 1 = apply gains+CCM, 0 = raw output
     black_level: f32,
-    _pad0: u32,
+    demosaic: u32, // 0 = bilinear, 1 = Malvar-He-Cutler
     _pad1: u32,
     _pad2: u32,
     ccm_row0: [f32; 4], // xyz used, w=pad
@@ -64,6 +107,16 @@ struct DebayerParams {
     ccm_row2: [f32; 4], // xyz used, w=pad
 }
 
+/// Still finishing pass parameters (16 bytes, matches still_finish.wgsl)
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct FinishParams {
+    width: u32,
+    height: u32,
+    chroma_radius: i32,
+    _pad: u32,
+}
+
 /// White balance gains stored in a GPU storage buffer.
 /// Written by CPU (ISP gains) or by the GPU AWB finalize shader.
 #[repr(C)]
@@ -103,6 +156,8 @@ pub struct GpuFrameInput<'a> {
     pub colour_correction_matrix: Option<[[f32; 3]; 3]>,
     /// Sensor black level normalized to 0..1 (Bayer only)
     pub black_level: Option<f32>,
+    /// Which demosaic and finishing paths to use
+    pub quality: ProcessingQuality,
 }
 
 impl<'a> GpuFrameInput<'a> {
@@ -130,14 +185,15 @@ impl<'a> GpuFrameInput<'a> {
             colour_gains: None,
             colour_correction_matrix: None,
             black_level: None,
+            quality: ProcessingQuality::Preview,
         };
 
         match frame.format {
-            PixelFormat::NV12 | PixelFormat::NV21 => {
+            PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::P010 => {
                 let planes = frame
                     .yuv_planes
                     .as_ref()
-                    .ok_or("NV12/NV21 frame missing yuv_planes")?;
+                    .ok_or("NV12/NV21/P010 frame missing yuv_planes")?;
                 input.y_data = &buffer_data[planes.y_offset..planes.y_offset + planes.y_size];
                 input.uv_data =
                     Some(&buffer_data[planes.uv_offset..planes.uv_offset + planes.uv_size]);
@@ -167,7 +223,7 @@ impl<'a> GpuFrameInput<'a> {
                 input.colour_correction_matrix = meta.and_then(|m| m.colour_correction_matrix);
                 input.black_level = meta.and_then(|m| m.black_level);
             }
-            _ => {} // YUYV, UYVY, YVYU, VYUY, Gray8, RGB24, ABGR, BGRA: defaults are correct
+            _ => {} // Packed YUV, Y210, Gray8 and RGB variants: defaults are correct
         }
 
         Ok(input)
@@ -182,6 +238,8 @@ struct FilterParams {
     height: u32,
     filter_mode: u32,
     _padding: u32,
+    /// Software white balance gains (rgb, alpha unused)
+    wb_gains: [f32; 4],
 }
 
 /// CSI-2 unpack parameters (32 bytes, matches unpack_csi2.wgsl)
@@ -209,6 +267,7 @@ enum BindingSpec {
     ReadOnlyStorageBuffer,
     Uniform,
     Sampler,
+    Lut,
 }
 
 impl BindingSpec {
@@ -250,6 +309,7 @@ impl BindingSpec {
                 BindingSpec::Sampler => {
                     wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering)
                 }
+                BindingSpec::Lut => super::LutTexture::BINDING_TYPE,
             },
             count: None,
         }
@@ -291,10 +351,18 @@ pub struct GpuConvertPipeline {
     unpack_output_buffer_size: u64,
     /// Uniform buffer for unpack parameters
     unpack_uniform_buffer: wgpu::Buffer,
+    /// Still finishing pipeline (chroma denoise + unsharp mask)
+    finish_pipeline: Option<FormatPipeline>,
+    finish_uniform_buffer: wgpu::Buffer,
+    /// Finishing pass output, copied back into the output texture
+    finish_texture: Option<wgpu::Texture>,
+    finish_cached_width: u32,
+    finish_cached_height: u32,
     /// Integrated filter pipeline (runs on same device, avoids GPU round trip)
     filter_pipeline: Option<FormatPipeline>,
     filter_uniform_buffer: wgpu::Buffer,
     filter_sampler: wgpu::Sampler,
+    filter_lut: super::LutTexture,
     filter_output_buffer: Option<wgpu::Buffer>,
     filter_staging_buffer: Option<wgpu::Buffer>,
     filter_cached_width: u32,
@@ -363,6 +431,15 @@ impl GpuConvertPipeline {
             mapped_at_creation: false,
         });
 
+        // Finishing pass uniform buffer (its own, since it is written in the
+        // same submission as the conversion params)
+        let finish_uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("convert_finish_uniform"),
+            size: std::mem::size_of::<FinishParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
         // Filter pipeline resources (separate uniform buffer for FilterParams)
         let filter_uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("convert_filter_uniform"),
@@ -379,6 +456,7 @@ impl GpuConvertPipeline {
             min_filter: wgpu::FilterMode::Linear,
             ..Default::default()
         });
+        let filter_lut = super::LutTexture::new(&device);
 
         Ok(Self {
             device,
@@ -395,9 +473,15 @@ impl GpuConvertPipeline {
             unpack_output_buffer: None,
             unpack_output_buffer_size: 0,
             unpack_uniform_buffer,
+            finish_pipeline: None,
+            finish_uniform_buffer,
+            finish_texture: None,
+            finish_cached_width: 0,
+            finish_cached_height: 0,
             filter_pipeline: None,
             filter_uniform_buffer,
             filter_sampler,
+            filter_lut,
             filter_output_buffer: None,
             filter_staging_buffer: None,
             filter_cached_width: 0,
@@ -454,12 +538,20 @@ impl GpuConvertPipeline {
         (2, BindingSpec::Uniform),               // UnpackParams
     ];
 
+    // Bind group layout for the still finishing pass: converted frame, output, params
+    const BIND_LAYOUT_FINISH: [(u32, BindingSpec); 3] = [
+        (0, BindingSpec::Texture),        // converted RGBA
+        (1, BindingSpec::StorageTexture), // finished RGBA
+        (2, BindingSpec::Uniform),        // FinishParams
+    ];
+
     // Bind group layout for integrated filter (same as GpuFilterPipeline)
-    const BIND_LAYOUT_FILTER: [(u32, BindingSpec); 4] = [
+    const BIND_LAYOUT_FILTER: [(u32, BindingSpec); 5] = [
         (0, BindingSpec::FilterableTexture), // input texture (debayer output)
         (1, BindingSpec::StorageBuffer),     // output buffer (packed RGBA u32)
         (2, BindingSpec::Uniform),           // FilterParams
         (3, BindingSpec::Sampler),           // linear filtering sampler
+        (4, BindingSpec::Lut),               // imported LUT
     ];
 
     /// Ensure the unified YUV pipeline exists
@@ -519,6 +611,7 @@ impl GpuConvertPipeline {
         self.ensure_awb_finalize_pipeline();
         self.ensure_filter_pipeline();
         self.ensure_unpack_pipeline();
+        self.ensure_finish_pipeline();
         info!(
             elapsed_ms = format!("{:.1}", start.elapsed().as_millis()),
             "GPU convert pipelines warmed up"
@@ -568,6 +661,119 @@ impl GpuConvertPipeline {
         self.filter_cached_height = height;
     }
 
+    /// Ensure the still finishing pipeline exists
+    fn ensure_finish_pipeline(&mut self) {
+        if self.finish_pipeline.is_none() {
+            debug!("Creating still finishing pipeline");
+            self.finish_pipeline = Some(self.create_pipeline(
+                include_str!("still_finish.wgsl"),
+                "still_finish",
+                &Self::BIND_LAYOUT_FINISH,
+            ));
+        }
+    }
+
+    /// Ensure the finishing pipeline and texture exist for a still conversion
+    /// and upload its parameters
+    fn prepare_finish_pass(&mut self, width: u32, height: u32) {
+        self.ensure_finish_pipeline();
+        if self.finish_cached_width != width || self.finish_cached_height != height {
+            self.finish_texture = Some(self.device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("convert_finish_rgba"),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            }));
+            self.finish_cached_width = width;
+            self.finish_cached_height = height;
+        }
+
+        let params = FinishParams {
+            width,
+            height,
+            chroma_radius: STILL_CHROMA_RADIUS,
+            _pad: 0,
+        };
+        self.queue
+            .write_buffer(&self.finish_uniform_buffer, 0, bytemuck::bytes_of(&params));
+    }
+
+    /// Encode the finishing pass over the output texture, then copy the
+    /// result back so readback and the filter pass see the finished frame.
+    fn encode_finish_pass(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        width: u32,
+        height: u32,
+    ) -> Result<(), String> {
+        let finish_pipeline = self
+            .finish_pipeline
+            .as_ref()
+            .ok_or("Finishing pipeline not created")?;
+        let finish_texture = self
+            .finish_texture
+            .as_ref()
+            .ok_or("Finishing texture not allocated")?;
+        let output_texture = self
+            .output_texture
+            .as_ref()
+            .ok_or("Output texture not allocated")?;
+
+        let input_view = output_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let finish_view = finish_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("still_finish_bind_group"),
+            layout: &finish_pipeline.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&input_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&finish_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: self.finish_uniform_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("still_finish_pass"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&finish_pipeline.pipeline);
+            pass.set_bind_group(0, Some(&bind_group), &[]);
+            pass.dispatch_workgroups(
+                width.div_ceil(TILE_WORKGROUP_SIZE),
+                height.div_ceil(TILE_WORKGROUP_SIZE),
+                1,
+            );
+        }
+
+        encoder.copy_texture_to_texture(
+            finish_texture.as_image_copy(),
+            output_texture.as_image_copy(),
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+        Ok(())
+    }
+
     /// Ensure the CSI-2 unpack pipeline exists
     fn ensure_unpack_pipeline(&mut self) {
         if self.unpack_pipeline.is_none() {
@@ -767,7 +973,9 @@ impl GpuConvertPipeline {
 
         // Calculate texture dimensions based on format, with override for actual UV dims
         let (uv_width, uv_height) = uv_dims.unwrap_or(match format {
-            PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::I420 => (width / 2, height / 2),
+            PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::I420 | PixelFormat::P010 => {
+                (width / 2, height / 2)
+            }
             PixelFormat::YUYV | PixelFormat::UYVY | PixelFormat::YVYU | PixelFormat::VYUY => {
                 (width / 2, height)
             }
@@ -776,6 +984,9 @@ impl GpuConvertPipeline {
             | PixelFormat::RGB24
             | PixelFormat::ABGR
             | PixelFormat::BGRA
+            | PixelFormat::RGBx
+            | PixelFormat::BGRx
+            | PixelFormat::Y210
             | PixelFormat::BayerRGGB
             | PixelFormat::BayerBGGR
             | PixelFormat::BayerGRBG
@@ -787,9 +998,15 @@ impl GpuConvertPipeline {
             PixelFormat::YUYV | PixelFormat::UYVY | PixelFormat::YVYU | PixelFormat::VYUY => {
                 (wgpu::TextureFormat::Rgba8Unorm, width / 2)
             }
-            PixelFormat::RGBA | PixelFormat::RGB24 | PixelFormat::ABGR | PixelFormat::BGRA => {
-                (wgpu::TextureFormat::Rgba8Unorm, width)
-            }
+            PixelFormat::RGBA
+            | PixelFormat::RGB24
+            | PixelFormat::ABGR
+            | PixelFormat::BGRA
+            | PixelFormat::RGBx
+            | PixelFormat::BGRx => (wgpu::TextureFormat::Rgba8Unorm, width),
+            // One texel per pixel: 16-bit Y plus half of the 16-bit chroma pair
+            PixelFormat::Y210 => (wgpu::TextureFormat::Rgba8Unorm, width),
+            PixelFormat::P010 => (wgpu::TextureFormat::Rg8Unorm, width),
             PixelFormat::BayerRGGB
             | PixelFormat::BayerBGGR
             | PixelFormat::BayerGRBG
@@ -800,6 +1017,7 @@ impl GpuConvertPipeline {
         // UV plane texture format
         let uv_format = match format {
             PixelFormat::NV12 | PixelFormat::NV21 => wgpu::TextureFormat::Rg8Unorm,
+            PixelFormat::P010 => wgpu::TextureFormat::Rgba8Unorm,
             _ => wgpu::TextureFormat::R8Unorm,
         };
 
@@ -865,7 +1083,8 @@ impl GpuConvertPipeline {
             format: wgpu::TextureFormat::Rgba8Unorm,
             usage: wgpu::TextureUsages::STORAGE_BINDING
                 | wgpu::TextureUsages::TEXTURE_BINDING
-                | wgpu::TextureUsages::COPY_SRC,
+                | wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::COPY_DST,
             view_formats: &[],
         });
 
@@ -901,7 +1120,7 @@ impl GpuConvertPipeline {
             pattern: input.format.bayer_pattern_code().unwrap_or(0),
             use_isp_colour: use_isp,
             black_level: bl,
-            _pad0: 0,
+            demosaic: input.quality.demosaic_code(),
             _pad1: 0,
             _pad2: 0,
             ccm_row0: [ccm[0][0], ccm[0][1], ccm[0][2], 0.0],
@@ -1159,7 +1378,13 @@ impl GpuConvertPipeline {
 
         // Bayer path: use shared helper
         if input.format.is_bayer() {
-            let (encoder, needs_gpu_awb, gpu_unpack) = self.prepare_and_encode_bayer(input)?;
+            if input.quality.has_finish_pass() {
+                self.prepare_finish_pass(input.width, input.height);
+            }
+            let (mut encoder, needs_gpu_awb, gpu_unpack) = self.prepare_and_encode_bayer(input)?;
+            if input.quality.has_finish_pass() {
+                self.encode_finish_pass(&mut encoder, input.width, input.height)?;
+            }
             self.queue.submit(std::iter::once(encoder.finish()));
 
             let elapsed = start.elapsed();
@@ -1181,8 +1406,10 @@ impl GpuConvertPipeline {
                 .ok_or("Output texture not allocated".to_string());
         }
 
-        // YUV/packed-YUV path
-        let uv_dims = if input.uv_stride > 0 {
+        // YUV/packed-YUV path. Only I420 takes its chroma size from the data
+        // (MJPEG I422/I444 arrives tagged as I420); for semi-planar formats
+        // the stride is in bytes, not texels, and would overstate the width.
+        let uv_dims = if input.format == PixelFormat::I420 && input.uv_stride > 0 {
             input
                 .uv_data
                 .map(|uv_data| (input.uv_stride, uv_data.len() as u32 / input.uv_stride))
@@ -1192,6 +1419,9 @@ impl GpuConvertPipeline {
 
         self.ensure_resources(input.width, input.height, input.format, uv_dims);
         self.ensure_yuv_pipeline();
+        if input.quality.has_finish_pass() {
+            self.prepare_finish_pass(input.width, input.height);
+        }
 
         let tex_y = self.tex_y.as_ref().ok_or("Y texture not allocated")?;
         let tex_uv = self.tex_uv.as_ref().ok_or("UV texture not allocated")?;
@@ -1203,6 +1433,7 @@ impl GpuConvertPipeline {
 
         self.upload_textures(input, tex_y, tex_uv, tex_v)?;
 
+        let color_space = super::frame_color_space(input.format);
         let params = ConvertParams {
             width: input.width,
             height: input.height,
@@ -1210,8 +1441,10 @@ impl GpuConvertPipeline {
             y_stride: input.y_stride,
             uv_stride: input.uv_stride,
             v_stride: input.v_stride,
-            _pad0: 0,
-            _pad1: 0,
+            transfer: super::frame_transfer(input.format).shader_code(),
+            matrix: color_space.matrix_code(),
+            full_range: u32::from(color_space.full_range),
+            _pad: [0; 3],
         };
         self.queue
             .write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&params));
@@ -1272,6 +1505,10 @@ impl GpuConvertPipeline {
             compute_pass.dispatch_workgroups(workgroups_x, workgroups_y, 1);
         }
 
+        if input.quality.has_finish_pass() {
+            self.encode_finish_pass(&mut encoder, input.width, input.height)?;
+        }
+
         self.queue.submit(std::iter::once(encoder.finish()));
 
         let elapsed = start.elapsed();
@@ -1351,8 +1588,8 @@ impl GpuConvertPipeline {
                 );
             }
 
-            // NV12/NV21: Y plane + UV plane
-            PixelFormat::NV12 | PixelFormat::NV21 => {
+            // NV12/NV21 (and P010, with 16-bit samples): Y plane + UV plane
+            PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::P010 => {
                 self.write_plane(
                     tex_y,
                     input.y_data,
@@ -1399,8 +1636,24 @@ impl GpuConvertPipeline {
                 );
             }
 
+            // Y210: 8 bytes per pixel pair, uploaded as one Rgba8Unorm texel per pixel
+            PixelFormat::Y210 => {
+                self.write_plane(
+                    tex_y,
+                    input.y_data,
+                    input.y_stride,
+                    input.width,
+                    input.height,
+                );
+            }
+
             // RGBA-family: 4 bytes per pixel, uploaded as Rgba8Unorm
-            PixelFormat::RGBA | PixelFormat::ABGR | PixelFormat::BGRA | PixelFormat::RGB24 => {
+            PixelFormat::RGBA
+            | PixelFormat::ABGR
+            | PixelFormat::BGRA
+            | PixelFormat::RGBx
+            | PixelFormat::BGRx
+            | PixelFormat::RGB24 => {
                 self.write_plane(
                     tex_y,
                     input.y_data,
@@ -1549,9 +1802,15 @@ impl GpuConvertPipeline {
         // Ensure filter resources (&mut self calls before shared Bayer prep)
         self.ensure_filter_pipeline();
         self.ensure_filter_resources(input.width, input.height);
+        if input.quality.has_finish_pass() {
+            self.prepare_finish_pass(input.width, input.height);
+        }
 
         // Shared Bayer prep: unpack + AWB + debayer passes
         let (mut encoder, needs_gpu_awb, gpu_unpack) = self.prepare_and_encode_bayer(input)?;
+        if input.quality.has_finish_pass() {
+            self.encode_finish_pass(&mut encoder, input.width, input.height)?;
+        }
 
         // Write filter uniforms
         let filter_params = FilterParams {
@@ -1559,6 +1818,7 @@ impl GpuConvertPipeline {
             height: input.height,
             filter_mode: filter as u32,
             _padding: 0,
+            wb_gains: super::software_white_balance().to_uniform(),
         };
         self.queue.write_buffer(
             &self.filter_uniform_buffer,
@@ -1582,6 +1842,7 @@ impl GpuConvertPipeline {
             output_texture.create_view(&wgpu::TextureViewDescriptor::default());
 
         // Filter bind group — reads from debayer output texture
+        self.filter_lut.sync(&self.queue);
         let filter_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("convert_filter_filter_bg"),
             layout: &filter_pipeline.bind_group_layout,
@@ -1602,6 +1863,10 @@ impl GpuConvertPipeline {
                     binding: 3,
                     resource: wgpu::BindingResource::Sampler(&self.filter_sampler),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: wgpu::BindingResource::TextureView(self.filter_lut.view()),
+                },
             ],
         });
 
@@ -1720,3 +1985,40 @@ pub async fn get_gpu_convert_pipeline()
 
     Ok(guard)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate_shader(name: &str, source: &str) {
+        let module = naga::front::wgsl::parse_str(source)
+            .unwrap_or_else(|e| panic!("{name} failed to parse: {e:?}"));
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::all(),
+        )
+        .validate(&module)
+        .unwrap_or_else(|e| panic!("{name} failed validation: {e:?}"));
+    }
+
+    #[test]
+    fn only_stills_get_the_slow_paths() {
+        assert_eq!(ProcessingQuality::default(), ProcessingQuality::Preview);
+        assert_eq!(ProcessingQuality::Preview.demosaic_code(), 0);
+        assert!(!ProcessingQuality::Preview.has_finish_pass());
+        assert_eq!(ProcessingQuality::Still.demosaic_code(), 1);
+        assert!(ProcessingQuality::Still.has_finish_pass());
+    }
+
+    #[test]
+    fn params_match_their_shader_layouts() {
+        assert_eq!(std::mem::size_of::<DebayerParams>(), 80);
+        assert_eq!(std::mem::size_of::<FinishParams>(), 16);
+    }
+
+    #[test]
+    fn quality_shaders_validate() {
+        validate_shader("debayer.wgsl", include_str!("debayer.wgsl"));
+        validate_shader("still_finish.wgsl", include_str!("still_finish.wgsl"));
+    }
+}
@@ -0,0 +1,315 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! GPU denoise pass
+//!
+//! Temporal and spatial noise reduction for low-light video. Each frame gets
+//! an edge-preserving 3x3 blur and is then averaged with the previous output
+//! wherever the two agree to within the noise level; where something moved
+//! the history is dropped, so motion doesn't smear. It is the burst merge's
+//! idea without the alignment, cheap enough to run on every frame.
+//!
+//! Unlike the other passes there is no shared instance: the pipeline keeps
+//! the previous output, so each stream (recording, virtual camera) owns one.
+
+use crate::gpu::{self, wgpu};
+use std::sync::Arc;
+use tracing::{debug, info};
+
+/// How hard one frame is denoised
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DenoiseStrength {
+    /// Noise standard deviation, in 8-bit levels
+    pub noise_sd: f32,
+    /// Blend of the spatial pass over the source (0-1)
+    pub spatial: f32,
+    /// Largest weight the previous output gets (0-1)
+    pub temporal: f32,
+}
+
+/// Denoise parameters uniform
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct DenoiseParams {
+    width: u32,
+    height: u32,
+    noise_sd: f32,
+    spatial: f32,
+    temporal: f32,
+    has_history: u32,
+    _padding: [u32; 2],
+}
+
+/// GPU denoise pipeline
+pub struct GpuDenoisePipeline {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    pipeline: wgpu::ComputePipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    uniform_buffer: wgpu::Buffer,
+    // Cached resources for the current frame size
+    cached_size: (u32, u32),
+    input_buffer: Option<wgpu::Buffer>,
+    history_buffer: Option<wgpu::Buffer>,
+    output_buffer: Option<wgpu::Buffer>,
+    staging_buffer: Option<wgpu::Buffer>,
+    /// Whether `history_buffer` holds the previous frame's output
+    has_history: bool,
+}
+
+impl GpuDenoisePipeline {
+    /// Create a denoise pipeline on the shared GPU device
+    pub async fn new() -> Result<Self, String> {
+        info!("Initializing GPU denoise pipeline");
+
+        let gpu = gpu::get_shared_gpu().await?;
+        let device = gpu.device;
+        let queue = gpu.queue;
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("denoise_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("denoise.wgsl").into()),
+        });
+
+        let storage_entry = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("denoise_bind_group_layout"),
+            entries: &[
+                // Input frame
+                storage_entry(0, true),
+                // Previous output (f32 RGBA)
+                storage_entry(1, false),
+                // Output frame
+                storage_entry(2, false),
+                // Uniform buffer
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("denoise_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            immediate_size: 0,
+        });
+
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("denoise_pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("denoise_uniform_buffer"),
+            size: std::mem::size_of::<DenoiseParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Ok(Self {
+            device,
+            queue,
+            pipeline,
+            bind_group_layout,
+            uniform_buffer,
+            cached_size: (0, 0),
+            input_buffer: None,
+            history_buffer: None,
+            output_buffer: None,
+            staging_buffer: None,
+            has_history: false,
+        })
+    }
+
+    /// Forget the previous output, so the next frame starts afresh
+    pub fn reset(&mut self) {
+        self.has_history = false;
+    }
+
+    /// Ensure buffers are allocated for `width`x`height` frames
+    fn ensure_resources(&mut self, width: u32, height: u32) {
+        if self.cached_size == (width, height) {
+            return;
+        }
+        debug!(width, height, "Allocating denoise buffers");
+        let pixels = width as u64 * height as u64;
+        let buffer_size = pixels * 4;
+        self.input_buffer = Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("denoise_input_buffer"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        }));
+        self.history_buffer = Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("denoise_history_buffer"),
+            size: pixels * 4 * std::mem::size_of::<f32>() as u64,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        }));
+        self.output_buffer = Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("denoise_output_buffer"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        }));
+        self.staging_buffer = Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("denoise_staging_buffer"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        }));
+        self.cached_size = (width, height);
+        self.has_history = false;
+    }
+
+    /// Denoise one frame of tightly packed RGBA `data`
+    pub async fn denoise_rgba(
+        &mut self,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        strength: DenoiseStrength,
+    ) -> Result<Vec<u8>, String> {
+        let pixels = width as u64 * height as u64;
+        if data.len() as u64 != pixels * 4 {
+            return Err(format!(
+                "RGBA buffer is {} bytes, expected {} for {}x{}",
+                data.len(),
+                pixels * 4,
+                width,
+                height
+            ));
+        }
+        self.ensure_resources(width, height);
+
+        let input_buffer = self
+            .input_buffer
+            .as_ref()
+            .ok_or("Input buffer not allocated")?;
+        let history_buffer = self
+            .history_buffer
+            .as_ref()
+            .ok_or("History buffer not allocated")?;
+        let output_buffer = self
+            .output_buffer
+            .as_ref()
+            .ok_or("Output buffer not allocated")?;
+        let staging_buffer = self
+            .staging_buffer
+            .as_ref()
+            .ok_or("Staging buffer not allocated")?;
+
+        self.queue.write_buffer(input_buffer, 0, data);
+        let params = DenoiseParams {
+            width,
+            height,
+            noise_sd: strength.noise_sd / 255.0,
+            spatial: strength.spatial.clamp(0.0, 1.0),
+            temporal: strength.temporal.clamp(0.0, 1.0),
+            has_history: u32::from(self.has_history),
+            _padding: [0; 2],
+        };
+        self.queue
+            .write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&params));
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("denoise_bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: input_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: history_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: output_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: self.uniform_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("denoise_encoder"),
+            });
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("denoise_compute_pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.pipeline);
+            compute_pass.set_bind_group(0, Some(&bind_group), &[]);
+            compute_pass.dispatch_workgroups(width.div_ceil(16), height.div_ceil(16), 1);
+        }
+
+        let buffer_size = pixels * 4;
+        encoder.copy_buffer_to_buffer(output_buffer, 0, staging_buffer, 0, buffer_size);
+        self.queue.submit(std::iter::once(encoder.finish()));
+        self.has_history = true;
+
+        let buffer_slice = staging_buffer.slice(..);
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+
+        let _ = self.device.poll(wgpu::PollType::Wait {
+            submission_index: None,
+            timeout: None,
+        });
+
+        receiver
+            .await
+            .map_err(|_| "Failed to receive buffer mapping result")?
+            .map_err(|e| format!("Failed to map buffer: {:?}", e))?;
+
+        let mapped = buffer_slice.get_mapped_range();
+        let output = mapped.to_vec();
+        drop(mapped);
+        staging_buffer.unmap();
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn denoise_shader_validates() {
+        let module = naga::front::wgsl::parse_str(include_str!("denoise.wgsl"))
+            .expect("denoise.wgsl failed to parse");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::all(),
+        )
+        .validate(&module)
+        .expect("denoise.wgsl failed validation");
+    }
+}
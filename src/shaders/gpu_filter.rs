@@ -18,6 +18,8 @@ struct FilterParams {
     height: u32,
     filter_mode: u32,
     _padding: u32,
+    /// Software white balance gains (rgb, alpha unused)
+    wb_gains: [f32; 4],
 }
 
 /// Blur parameters uniform for the pre-blur compute shader
@@ -37,6 +39,8 @@ pub struct GpuFilterPipeline {
     bind_group_layout: wgpu::BindGroupLayout,
     sampler: wgpu::Sampler,
     uniform_buffer: wgpu::Buffer,
+    // 3D texture holding the active imported LUT
+    lut: super::LutTexture,
     // Pre-blur compute pipeline for multi-pass filters
     preblur_pipeline: wgpu::ComputePipeline,
     preblur_bind_group_layout: wgpu::BindGroupLayout,
@@ -126,6 +130,13 @@ impl GpuFilterPipeline {
                     ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                     count: None,
                 },
+                // Imported LUT
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: super::LutTexture::BINDING_TYPE,
+                    count: None,
+                },
             ],
         });
 
@@ -241,6 +252,8 @@ impl GpuFilterPipeline {
             mapped_at_creation: false,
         });
 
+        let lut = super::LutTexture::new(&device);
+
         Ok(Self {
             device,
             queue,
@@ -248,6 +261,7 @@ impl GpuFilterPipeline {
             bind_group_layout,
             sampler,
             uniform_buffer,
+            lut,
             preblur_pipeline,
             preblur_bind_group_layout,
             preblur_uniform_buffer,
@@ -333,8 +347,8 @@ impl GpuFilterPipeline {
         height: u32,
         filter: FilterType,
     ) -> Result<Vec<u8>, String> {
-        if filter == FilterType::Standard {
-            // No filter needed, return as-is
+        if !needs_filter_pass(filter) {
+            // No filter or white balance needed, return as-is
             return Ok(rgba_data.to_vec());
         }
 
@@ -380,6 +394,7 @@ impl GpuFilterPipeline {
             height,
             filter_mode: filter as u32,
             _padding: 0,
+            wb_gains: super::software_white_balance().to_uniform(),
         };
         self.queue
             .write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&params));
@@ -458,6 +473,7 @@ impl GpuFilterPipeline {
         };
 
         // Main filter pass
+        self.lut.sync(&self.queue);
         let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("filter_bind_group"),
             layout: &self.bind_group_layout,
@@ -478,6 +494,10 @@ impl GpuFilterPipeline {
                     binding: 3,
                     resource: wgpu::BindingResource::Sampler(&self.sampler),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: wgpu::BindingResource::TextureView(self.lut.view()),
+                },
             ],
         });
 
@@ -526,6 +546,12 @@ impl GpuFilterPipeline {
     }
 }
 
+/// Whether RGBA frames need the filter pass: a filter is selected or the
+/// software white balance is set
+pub fn needs_filter_pass(filter: FilterType) -> bool {
+    filter != FilterType::Standard || !super::software_white_balance().is_identity()
+}
+
 /// Cached GPU filter pipeline instance
 static GPU_FILTER_PIPELINE: std::sync::OnceLock<tokio::sync::Mutex<Option<GpuFilterPipeline>>> =
     std::sync::OnceLock::new();
@@ -570,3 +596,25 @@ pub async fn apply_filter_gpu_rgba(
         .apply_filter_rgba(rgba_data, width, height, filter)
         .await
 }
+
+#[cfg(test)]
+mod tests {
+    /// The compute module is the filter prelude (with the film pack) plus
+    /// filter_compute.wgsl, as assembled in `GpuFilterPipeline::new`
+    #[test]
+    fn filter_compute_shader_validates() {
+        let source = format!(
+            "{}\n{}",
+            super::super::FILTER_FUNCTIONS,
+            include_str!("filter_compute.wgsl")
+        );
+        let module =
+            naga::front::wgsl::parse_str(&source).expect("filter_compute.wgsl failed to parse");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::all(),
+        )
+        .validate(&module)
+        .expect("filter_compute.wgsl failed validation");
+    }
+}
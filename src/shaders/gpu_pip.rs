@@ -0,0 +1,416 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! GPU picture-in-picture compositor
+//!
+//! Draws the secondary camera into an RGBA frame, as a rounded inset or as
+//! one half of a side-by-side layout. Used by video recording, the virtual
+//! camera and photo capture, which all work on CPU-side RGBA
+//! buffers, so this follows the same upload / compute / read-back shape as
+//! the filter pipeline.
+
+use crate::backends::camera::pip::{DualComposite, UvRect};
+use crate::backends::camera::types::CameraFrame;
+use crate::gpu::{self, wgpu};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// Composite parameters uniform
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct PipParams {
+    width: u32,
+    height: u32,
+    _padding: [u32; 2],
+    inset: [f32; 4],
+    base_uv: [f32; 4],
+    inset_uv: [f32; 4],
+    radius: f32,
+    border: f32,
+    _padding2: [f32; 2],
+}
+
+/// GPU picture-in-picture compositor
+pub struct GpuPipPipeline {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    pipeline: wgpu::ComputePipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    uniform_buffer: wgpu::Buffer,
+    // Cached resources for the current output dimensions
+    cached_size: (u32, u32),
+    base_texture: Option<wgpu::Texture>,
+    output_buffer: Option<wgpu::Buffer>,
+    staging_buffer: Option<wgpu::Buffer>,
+    // Cached inset texture for the current secondary camera dimensions
+    cached_inset_size: (u32, u32),
+    inset_texture: Option<wgpu::Texture>,
+}
+
+impl GpuPipPipeline {
+    /// Create the compositor on the shared GPU device
+    pub async fn new() -> Result<Self, String> {
+        info!("Initializing GPU picture-in-picture pipeline");
+
+        let gpu = gpu::get_shared_gpu().await?;
+        let device = gpu.device;
+        let queue = gpu.queue;
+
+        let shader_source = format!(
+            "{}\n{}",
+            super::GEOMETRY_FUNCTIONS,
+            include_str!("pip_composite.wgsl")
+        );
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("pip_composite_shader"),
+            source: wgpu::ShaderSource::Wgsl(shader_source.into()),
+        });
+
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("pip_bind_group_layout"),
+            entries: &[
+                // Base frame
+                texture_entry(0),
+                // Secondary camera frame
+                texture_entry(1),
+                // Output storage buffer
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // Uniform buffer
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // Sampler (inset scaling)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("pip_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            immediate_size: 0,
+        });
+
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("pip_pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("pip_sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("pip_uniform_buffer"),
+            size: std::mem::size_of::<PipParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Ok(Self {
+            device,
+            queue,
+            pipeline,
+            bind_group_layout,
+            sampler,
+            uniform_buffer,
+            cached_size: (0, 0),
+            base_texture: None,
+            output_buffer: None,
+            staging_buffer: None,
+            cached_inset_size: (0, 0),
+            inset_texture: None,
+        })
+    }
+
+    fn create_rgba_texture(&self, label: &str, width: u32, height: u32) -> wgpu::Texture {
+        self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        })
+    }
+
+    /// Ensure resources are allocated for the given output and inset sizes
+    fn ensure_resources(&mut self, size: (u32, u32), inset_size: (u32, u32)) {
+        if self.cached_size != size {
+            debug!(
+                width = size.0,
+                height = size.1,
+                "Allocating PiP output resources"
+            );
+            let buffer_size = (size.0 * size.1 * 4) as u64;
+            self.base_texture = Some(self.create_rgba_texture("pip_base_texture", size.0, size.1));
+            self.output_buffer = Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("pip_output_buffer"),
+                size: buffer_size,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            }));
+            self.staging_buffer = Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("pip_staging_buffer"),
+                size: buffer_size,
+                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            }));
+            self.cached_size = size;
+        }
+
+        if self.cached_inset_size != inset_size {
+            self.inset_texture =
+                Some(self.create_rgba_texture("pip_inset_texture", inset_size.0, inset_size.1));
+            self.cached_inset_size = inset_size;
+        }
+    }
+
+    fn upload(&self, texture: &wgpu::Texture, data: &[u8], stride: u32, width: u32, height: u32) {
+        self.queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            data,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(stride),
+                rows_per_image: Some(height),
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+
+    /// Composite `inset` (an RGBA frame) into tightly packed RGBA `base`
+    /// data following `layout` and return the result.
+    pub async fn composite_rgba(
+        &mut self,
+        base: &[u8],
+        width: u32,
+        height: u32,
+        inset: &CameraFrame,
+        layout: DualComposite,
+    ) -> Result<Vec<u8>, String> {
+        self.ensure_resources((width, height), (inset.width, inset.height));
+
+        let base_texture = self
+            .base_texture
+            .as_ref()
+            .ok_or("Base texture not allocated")?;
+        let inset_texture = self
+            .inset_texture
+            .as_ref()
+            .ok_or("Inset texture not allocated")?;
+        let output_buffer = self
+            .output_buffer
+            .as_ref()
+            .ok_or("Output buffer not allocated")?;
+        let staging_buffer = self
+            .staging_buffer
+            .as_ref()
+            .ok_or("Staging buffer not allocated")?;
+
+        self.upload(base_texture, base, width * 4, width, height);
+        self.upload(
+            inset_texture,
+            &inset.data[..],
+            inset.stride,
+            inset.width,
+            inset.height,
+        );
+
+        let rect = layout.inset;
+        let params = PipParams {
+            width,
+            height,
+            _padding: [0; 2],
+            inset: [
+                rect.x as f32,
+                rect.y as f32,
+                rect.width as f32,
+                rect.height as f32,
+            ],
+            base_uv: uv_array(layout.base_crop),
+            inset_uv: uv_array(rect.crop),
+            radius: rect.radius,
+            border: rect.border,
+            _padding2: [0.0; 2],
+        };
+        self.queue
+            .write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&params));
+
+        let base_view = base_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let inset_view = inset_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("pip_bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&base_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&inset_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: output_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: self.uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("pip_encoder"),
+            });
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("pip_compute_pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.pipeline);
+            compute_pass.set_bind_group(0, Some(&bind_group), &[]);
+            compute_pass.dispatch_workgroups(width.div_ceil(16), height.div_ceil(16), 1);
+        }
+
+        let buffer_size = (width * height * 4) as u64;
+        encoder.copy_buffer_to_buffer(output_buffer, 0, staging_buffer, 0, buffer_size);
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        let buffer_slice = staging_buffer.slice(..);
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+
+        let _ = self.device.poll(wgpu::PollType::Wait {
+            submission_index: None,
+            timeout: None,
+        });
+
+        receiver
+            .await
+            .map_err(|_| "Failed to receive buffer mapping result")?
+            .map_err(|e| format!("Failed to map buffer: {:?}", e))?;
+
+        let data = buffer_slice.get_mapped_range();
+        let output = data.to_vec();
+        drop(data);
+        staging_buffer.unmap();
+
+        Ok(output)
+    }
+}
+
+/// Cached GPU picture-in-picture pipeline instance
+static GPU_PIP_PIPELINE: std::sync::OnceLock<tokio::sync::Mutex<Option<GpuPipPipeline>>> =
+    std::sync::OnceLock::new();
+
+/// Get or create the shared GPU picture-in-picture pipeline instance
+pub async fn get_gpu_pip_pipeline()
+-> Result<tokio::sync::MutexGuard<'static, Option<GpuPipPipeline>>, String> {
+    let lock = GPU_PIP_PIPELINE.get_or_init(|| tokio::sync::Mutex::new(None));
+    let mut guard = lock.lock().await;
+
+    if guard.is_none() {
+        match GpuPipPipeline::new().await {
+            Ok(pipeline) => {
+                *guard = Some(pipeline);
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to initialize GPU picture-in-picture pipeline: {}",
+                    e
+                );
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(guard)
+}
+
+fn uv_array(rect: UvRect) -> [f32; 4] {
+    [rect.x, rect.y, rect.width, rect.height]
+}
+
+/// Composite the secondary camera into RGBA data using the shared pipeline
+pub async fn composite_pip_gpu_rgba(
+    base: &[u8],
+    width: u32,
+    height: u32,
+    inset: &CameraFrame,
+    layout: DualComposite,
+) -> Result<Vec<u8>, String> {
+    let mut guard = get_gpu_pip_pipeline().await?;
+    let pipeline = guard
+        .as_mut()
+        .ok_or("GPU picture-in-picture pipeline not initialized")?;
+
+    pipeline
+        .composite_rgba(base, width, height, inset, layout)
+        .await
+}
@@ -0,0 +1,310 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! GPU rotation pass
+//!
+//! Rotates tightly packed RGBA frames by a [`SensorRotation`] correction and
+//! optionally mirrors them, so photos, recordings and the virtual camera all
+//! come out upright the same way the preview shader draws them. Pixels are
+//! moved one u32 at a time between storage buffers; no sampling is needed.
+
+use crate::backends::camera::types::SensorRotation;
+use crate::gpu::{self, wgpu};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// Rotation parameters uniform
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct RotateParams {
+    in_width: u32,
+    in_height: u32,
+    out_width: u32,
+    out_height: u32,
+    rotation: u32,
+    mirror: u32,
+    _padding: [u32; 2],
+}
+
+/// GPU rotation pipeline
+pub struct GpuRotatePipeline {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    pipeline: wgpu::ComputePipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    uniform_buffer: wgpu::Buffer,
+    // Cached resources for the current frame size (input and output hold
+    // the same number of pixels whatever the rotation)
+    cached_pixels: u64,
+    input_buffer: Option<wgpu::Buffer>,
+    output_buffer: Option<wgpu::Buffer>,
+    staging_buffer: Option<wgpu::Buffer>,
+}
+
+impl GpuRotatePipeline {
+    /// Create the rotation pipeline on the shared GPU device
+    pub async fn new() -> Result<Self, String> {
+        info!("Initializing GPU rotation pipeline");
+
+        let gpu = gpu::get_shared_gpu().await?;
+        let device = gpu.device;
+        let queue = gpu.queue;
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("rotate_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("rotate.wgsl").into()),
+        });
+
+        let storage_entry = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("rotate_bind_group_layout"),
+            entries: &[
+                // Input frame
+                storage_entry(0, true),
+                // Output frame
+                storage_entry(1, false),
+                // Uniform buffer
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("rotate_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            immediate_size: 0,
+        });
+
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("rotate_pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("rotate_uniform_buffer"),
+            size: std::mem::size_of::<RotateParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Ok(Self {
+            device,
+            queue,
+            pipeline,
+            bind_group_layout,
+            uniform_buffer,
+            cached_pixels: 0,
+            input_buffer: None,
+            output_buffer: None,
+            staging_buffer: None,
+        })
+    }
+
+    /// Ensure buffers are allocated for frames of `pixels` pixels
+    fn ensure_resources(&mut self, pixels: u64) {
+        if self.cached_pixels == pixels {
+            return;
+        }
+        debug!(pixels, "Allocating rotation buffers");
+        let buffer_size = pixels * 4;
+        self.input_buffer = Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("rotate_input_buffer"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        }));
+        self.output_buffer = Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("rotate_output_buffer"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        }));
+        self.staging_buffer = Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("rotate_staging_buffer"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        }));
+        self.cached_pixels = pixels;
+    }
+
+    /// Rotate tightly packed RGBA `data` to correct `rotation`, mirroring
+    /// horizontally afterwards if `mirror` is set. Returns the new data and
+    /// its dimensions.
+    pub async fn rotate_rgba(
+        &mut self,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        rotation: SensorRotation,
+        mirror: bool,
+    ) -> Result<(Vec<u8>, u32, u32), String> {
+        let pixels = width as u64 * height as u64;
+        if data.len() as u64 != pixels * 4 {
+            return Err(format!(
+                "RGBA buffer is {} bytes, expected {} for {}x{}",
+                data.len(),
+                pixels * 4,
+                width,
+                height
+            ));
+        }
+        self.ensure_resources(pixels);
+
+        let (out_width, out_height) = if rotation.swaps_dimensions() {
+            (height, width)
+        } else {
+            (width, height)
+        };
+
+        let input_buffer = self
+            .input_buffer
+            .as_ref()
+            .ok_or("Input buffer not allocated")?;
+        let output_buffer = self
+            .output_buffer
+            .as_ref()
+            .ok_or("Output buffer not allocated")?;
+        let staging_buffer = self
+            .staging_buffer
+            .as_ref()
+            .ok_or("Staging buffer not allocated")?;
+
+        self.queue.write_buffer(input_buffer, 0, data);
+        let params = RotateParams {
+            in_width: width,
+            in_height: height,
+            out_width,
+            out_height,
+            rotation: rotation.gpu_rotation_code(),
+            mirror: u32::from(mirror),
+            _padding: [0; 2],
+        };
+        self.queue
+            .write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&params));
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("rotate_bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: input_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: output_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: self.uniform_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("rotate_encoder"),
+            });
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("rotate_compute_pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.pipeline);
+            compute_pass.set_bind_group(0, Some(&bind_group), &[]);
+            compute_pass.dispatch_workgroups(out_width.div_ceil(16), out_height.div_ceil(16), 1);
+        }
+
+        let buffer_size = pixels * 4;
+        encoder.copy_buffer_to_buffer(output_buffer, 0, staging_buffer, 0, buffer_size);
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        let buffer_slice = staging_buffer.slice(..);
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+
+        let _ = self.device.poll(wgpu::PollType::Wait {
+            submission_index: None,
+            timeout: None,
+        });
+
+        receiver
+            .await
+            .map_err(|_| "Failed to receive buffer mapping result")?
+            .map_err(|e| format!("Failed to map buffer: {:?}", e))?;
+
+        let mapped = buffer_slice.get_mapped_range();
+        let output = mapped.to_vec();
+        drop(mapped);
+        staging_buffer.unmap();
+
+        Ok((output, out_width, out_height))
+    }
+}
+
+/// Cached GPU rotation pipeline instance
+static GPU_ROTATE_PIPELINE: std::sync::OnceLock<tokio::sync::Mutex<Option<GpuRotatePipeline>>> =
+    std::sync::OnceLock::new();
+
+/// Get or create the shared GPU rotation pipeline instance
+pub async fn get_gpu_rotate_pipeline()
+-> Result<tokio::sync::MutexGuard<'static, Option<GpuRotatePipeline>>, String> {
+    let lock = GPU_ROTATE_PIPELINE.get_or_init(|| tokio::sync::Mutex::new(None));
+    let mut guard = lock.lock().await;
+
+    if guard.is_none() {
+        match GpuRotatePipeline::new().await {
+            Ok(pipeline) => {
+                *guard = Some(pipeline);
+            }
+            Err(e) => {
+                warn!("Failed to initialize GPU rotation pipeline: {}", e);
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(guard)
+}
+
+/// Rotate (and optionally mirror) RGBA data using the shared pipeline
+pub async fn rotate_rgba_gpu(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    rotation: SensorRotation,
+    mirror: bool,
+) -> Result<(Vec<u8>, u32, u32), String> {
+    let mut guard = get_gpu_rotate_pipeline().await?;
+    let pipeline = guard
+        .as_mut()
+        .ok_or("GPU rotation pipeline not initialized")?;
+
+    pipeline
+        .rotate_rgba(data, width, height, rotation, mirror)
+        .await
+}
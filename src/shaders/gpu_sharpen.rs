@@ -0,0 +1,391 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! GPU output sharpening pass
+//!
+//! Applies an unsharp mask to the final RGBA photo: after tone mapping for
+//! burst results and after orientation for regular shots. Strength comes
+//! from the user's [`OutputSharpening`] setting; the radius defaults to one
+//! picked from the photo resolution, since fine detail spans more pixels
+//! on a denser sensor.
+
+use crate::config::OutputSharpening;
+use crate::gpu::{self, wgpu};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// Largest kernel half-size, which bounds the per-pixel cost
+const MAX_TAPS: i32 = 8;
+
+/// Sharpening parameters uniform (32 bytes, matches sharpen.wgsl)
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct SharpenParams {
+    width: u32,
+    height: u32,
+    amount: f32,
+    radius: f32,
+    threshold: f32,
+    taps: i32,
+    _padding: [u32; 2],
+}
+
+/// Unsharp mask for one photo
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnsharpMask {
+    /// Strength of the detail added back (1.0 = 100%)
+    pub amount: f32,
+    /// Gaussian sigma in pixels
+    pub radius: f32,
+    /// Luma detail below this (0-1) is not sharpened
+    pub threshold: f32,
+}
+
+impl UnsharpMask {
+    /// Resolve the sharpening setting for a photo of the given size.
+    /// Returns `None` when sharpening is off.
+    pub fn for_photo(setting: OutputSharpening, width: u32, height: u32) -> Option<Self> {
+        if setting.amount == 0 {
+            return None;
+        }
+        let radius = if setting.radius_tenths == 0 {
+            default_radius(width, height)
+        } else {
+            f32::from(setting.radius_tenths) / 10.0
+        };
+        Some(Self {
+            amount: f32::from(setting.amount) / 100.0,
+            radius,
+            threshold: f32::from(setting.threshold) / 255.0,
+        })
+    }
+
+    /// Kernel half-size covering the Gaussian out to 2.5 sigma
+    fn taps(&self) -> i32 {
+        ((self.radius * 2.5).ceil() as i32).clamp(1, MAX_TAPS)
+    }
+}
+
+/// Radius for a photo size: webcam-sized frames take a tight radius, large
+/// sensor stills a wider one
+fn default_radius(width: u32, height: u32) -> f32 {
+    let megapixels = u64::from(width) * u64::from(height) / 1_000_000;
+    match megapixels {
+        0..=2 => 0.7,
+        3..=8 => 1.0,
+        9..=20 => 1.4,
+        _ => 1.8,
+    }
+}
+
+/// GPU sharpening pipeline
+pub struct GpuSharpenPipeline {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    pipeline: wgpu::ComputePipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    uniform_buffer: wgpu::Buffer,
+    // Cached resources for the current frame size
+    cached_pixels: u64,
+    input_buffer: Option<wgpu::Buffer>,
+    output_buffer: Option<wgpu::Buffer>,
+    staging_buffer: Option<wgpu::Buffer>,
+}
+
+impl GpuSharpenPipeline {
+    /// Create the sharpening pipeline on the shared GPU device
+    pub async fn new() -> Result<Self, String> {
+        info!("Initializing GPU sharpening pipeline");
+
+        let gpu = gpu::get_shared_gpu().await?;
+        let device = gpu.device;
+        let queue = gpu.queue;
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("sharpen_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("sharpen.wgsl").into()),
+        });
+
+        let storage_entry = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("sharpen_bind_group_layout"),
+            entries: &[
+                // Input photo
+                storage_entry(0, true),
+                // Sharpened photo
+                storage_entry(1, false),
+                // Uniform buffer
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("sharpen_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            immediate_size: 0,
+        });
+
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("sharpen_pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("sharpen_uniform_buffer"),
+            size: std::mem::size_of::<SharpenParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Ok(Self {
+            device,
+            queue,
+            pipeline,
+            bind_group_layout,
+            uniform_buffer,
+            cached_pixels: 0,
+            input_buffer: None,
+            output_buffer: None,
+            staging_buffer: None,
+        })
+    }
+
+    /// Ensure buffers are allocated for photos of `pixels` pixels
+    fn ensure_resources(&mut self, pixels: u64) {
+        if self.cached_pixels == pixels {
+            return;
+        }
+        debug!(pixels, "Allocating sharpening buffers");
+        let buffer_size = pixels * 4;
+        self.input_buffer = Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("sharpen_input_buffer"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        }));
+        self.output_buffer = Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("sharpen_output_buffer"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        }));
+        self.staging_buffer = Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("sharpen_staging_buffer"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        }));
+        self.cached_pixels = pixels;
+    }
+
+    /// Sharpen tightly packed RGBA `data` with `mask`
+    pub async fn sharpen_rgba(
+        &mut self,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        mask: UnsharpMask,
+    ) -> Result<Vec<u8>, String> {
+        let pixels = width as u64 * height as u64;
+        if data.len() as u64 != pixels * 4 {
+            return Err(format!(
+                "RGBA buffer is {} bytes, expected {} for {}x{}",
+                data.len(),
+                pixels * 4,
+                width,
+                height
+            ));
+        }
+        self.ensure_resources(pixels);
+
+        let input_buffer = self
+            .input_buffer
+            .as_ref()
+            .ok_or("Input buffer not allocated")?;
+        let output_buffer = self
+            .output_buffer
+            .as_ref()
+            .ok_or("Output buffer not allocated")?;
+        let staging_buffer = self
+            .staging_buffer
+            .as_ref()
+            .ok_or("Staging buffer not allocated")?;
+
+        self.queue.write_buffer(input_buffer, 0, data);
+        let params = SharpenParams {
+            width,
+            height,
+            amount: mask.amount,
+            radius: mask.radius,
+            threshold: mask.threshold,
+            taps: mask.taps(),
+            _padding: [0; 2],
+        };
+        self.queue
+            .write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&params));
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("sharpen_bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: input_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: output_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: self.uniform_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("sharpen_encoder"),
+            });
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("sharpen_compute_pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.pipeline);
+            compute_pass.set_bind_group(0, Some(&bind_group), &[]);
+            compute_pass.dispatch_workgroups(width.div_ceil(16), height.div_ceil(16), 1);
+        }
+
+        let buffer_size = pixels * 4;
+        encoder.copy_buffer_to_buffer(output_buffer, 0, staging_buffer, 0, buffer_size);
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        let buffer_slice = staging_buffer.slice(..);
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+
+        let _ = self.device.poll(wgpu::PollType::Wait {
+            submission_index: None,
+            timeout: None,
+        });
+
+        receiver
+            .await
+            .map_err(|_| "Failed to receive buffer mapping result")?
+            .map_err(|e| format!("Failed to map buffer: {:?}", e))?;
+
+        let mapped = buffer_slice.get_mapped_range();
+        let output = mapped.to_vec();
+        drop(mapped);
+        staging_buffer.unmap();
+
+        Ok(output)
+    }
+}
+
+/// Cached GPU sharpening pipeline instance
+static GPU_SHARPEN_PIPELINE: std::sync::OnceLock<tokio::sync::Mutex<Option<GpuSharpenPipeline>>> =
+    std::sync::OnceLock::new();
+
+/// Get or create the shared GPU sharpening pipeline instance
+pub async fn get_gpu_sharpen_pipeline()
+-> Result<tokio::sync::MutexGuard<'static, Option<GpuSharpenPipeline>>, String> {
+    let lock = GPU_SHARPEN_PIPELINE.get_or_init(|| tokio::sync::Mutex::new(None));
+    let mut guard = lock.lock().await;
+
+    if guard.is_none() {
+        match GpuSharpenPipeline::new().await {
+            Ok(pipeline) => {
+                *guard = Some(pipeline);
+            }
+            Err(e) => {
+                warn!("Failed to initialize GPU sharpening pipeline: {}", e);
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(guard)
+}
+
+/// Sharpen RGBA data using the shared pipeline
+pub async fn sharpen_rgba_gpu(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    mask: UnsharpMask,
+) -> Result<Vec<u8>, String> {
+    let mut guard = get_gpu_sharpen_pipeline().await?;
+    let pipeline = guard
+        .as_mut()
+        .ok_or("GPU sharpening pipeline not initialized")?;
+
+    pipeline.sharpen_rgba(data, width, height, mask).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_amount_turns_sharpening_off() {
+        let setting = OutputSharpening {
+            amount: 0,
+            ..Default::default()
+        };
+        assert_eq!(UnsharpMask::for_photo(setting, 1920, 1080), None);
+    }
+
+    #[test]
+    fn radius_follows_resolution_unless_set() {
+        let auto = OutputSharpening::default();
+        let webcam = UnsharpMask::for_photo(auto, 1920, 1080).unwrap();
+        let sensor = UnsharpMask::for_photo(auto, 4608, 2592).unwrap();
+        assert!(webcam.radius < sensor.radius);
+
+        let fixed = OutputSharpening {
+            radius_tenths: 25,
+            ..auto
+        };
+        let mask = UnsharpMask::for_photo(fixed, 1920, 1080).unwrap();
+        assert_eq!(mask.radius, 2.5);
+        assert_eq!(mask.taps(), 7);
+    }
+
+    #[test]
+    fn sharpen_shader_validates() {
+        let module = naga::front::wgsl::parse_str(include_str!("sharpen.wgsl"))
+            .expect("sharpen.wgsl failed to parse");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::all(),
+        )
+        .validate(&module)
+        .expect("sharpen.wgsl failed validation");
+    }
+}
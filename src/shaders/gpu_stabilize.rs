@@ -0,0 +1,321 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! GPU stabilization pass
+//!
+//! Crops a shifted window out of tightly packed RGBA frames and scales it
+//! back up to the full frame size, which is how recorded video is
+//! stabilized: the window follows the camera's shake so the picture inside
+//! it stays steady. Sampling is bilinear, since the window moves by
+//! fractions of a pixel.
+
+use crate::gpu::{self, wgpu};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// Cropped window of a frame, as [`StabilizeWindow::scale`] of its size
+/// centred `offset` pixels from the frame centre
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StabilizeWindow {
+    /// Size of the window relative to the frame (0-1]
+    pub scale: f32,
+    /// Centre of the window relative to the frame centre, in pixels
+    pub offset: (f32, f32),
+}
+
+/// Stabilization parameters uniform
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct StabilizeParams {
+    width: u32,
+    height: u32,
+    crop_scale: f32,
+    _padding0: u32,
+    offset: [f32; 2],
+    _padding1: [u32; 2],
+}
+
+/// GPU stabilization pipeline
+pub struct GpuStabilizePipeline {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    pipeline: wgpu::ComputePipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    uniform_buffer: wgpu::Buffer,
+    // Cached resources for the current frame size
+    cached_pixels: u64,
+    input_buffer: Option<wgpu::Buffer>,
+    output_buffer: Option<wgpu::Buffer>,
+    staging_buffer: Option<wgpu::Buffer>,
+}
+
+impl GpuStabilizePipeline {
+    /// Create the stabilization pipeline on the shared GPU device
+    pub async fn new() -> Result<Self, String> {
+        info!("Initializing GPU stabilization pipeline");
+
+        let gpu = gpu::get_shared_gpu().await?;
+        let device = gpu.device;
+        let queue = gpu.queue;
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("stabilize_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("stabilize.wgsl").into()),
+        });
+
+        let storage_entry = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("stabilize_bind_group_layout"),
+            entries: &[
+                // Input frame
+                storage_entry(0, true),
+                // Output frame
+                storage_entry(1, false),
+                // Uniform buffer
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("stabilize_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            immediate_size: 0,
+        });
+
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("stabilize_pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("stabilize_uniform_buffer"),
+            size: std::mem::size_of::<StabilizeParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Ok(Self {
+            device,
+            queue,
+            pipeline,
+            bind_group_layout,
+            uniform_buffer,
+            cached_pixels: 0,
+            input_buffer: None,
+            output_buffer: None,
+            staging_buffer: None,
+        })
+    }
+
+    /// Ensure buffers are allocated for frames of `pixels` pixels
+    fn ensure_resources(&mut self, pixels: u64) {
+        if self.cached_pixels == pixels {
+            return;
+        }
+        debug!(pixels, "Allocating stabilization buffers");
+        let buffer_size = pixels * 4;
+        self.input_buffer = Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("stabilize_input_buffer"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        }));
+        self.output_buffer = Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("stabilize_output_buffer"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        }));
+        self.staging_buffer = Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("stabilize_staging_buffer"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        }));
+        self.cached_pixels = pixels;
+    }
+
+    /// Scale `window` of tightly packed RGBA `data` up to the full frame
+    pub async fn stabilize_rgba(
+        &mut self,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        window: StabilizeWindow,
+    ) -> Result<Vec<u8>, String> {
+        let pixels = width as u64 * height as u64;
+        if data.len() as u64 != pixels * 4 {
+            return Err(format!(
+                "RGBA buffer is {} bytes, expected {} for {}x{}",
+                data.len(),
+                pixels * 4,
+                width,
+                height
+            ));
+        }
+        self.ensure_resources(pixels);
+
+        let input_buffer = self
+            .input_buffer
+            .as_ref()
+            .ok_or("Input buffer not allocated")?;
+        let output_buffer = self
+            .output_buffer
+            .as_ref()
+            .ok_or("Output buffer not allocated")?;
+        let staging_buffer = self
+            .staging_buffer
+            .as_ref()
+            .ok_or("Staging buffer not allocated")?;
+
+        self.queue.write_buffer(input_buffer, 0, data);
+        let params = StabilizeParams {
+            width,
+            height,
+            crop_scale: window.scale,
+            _padding0: 0,
+            offset: [window.offset.0, window.offset.1],
+            _padding1: [0; 2],
+        };
+        self.queue
+            .write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&params));
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("stabilize_bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: input_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: output_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: self.uniform_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("stabilize_encoder"),
+            });
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("stabilize_compute_pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.pipeline);
+            compute_pass.set_bind_group(0, Some(&bind_group), &[]);
+            compute_pass.dispatch_workgroups(width.div_ceil(16), height.div_ceil(16), 1);
+        }
+
+        let buffer_size = pixels * 4;
+        encoder.copy_buffer_to_buffer(output_buffer, 0, staging_buffer, 0, buffer_size);
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        let buffer_slice = staging_buffer.slice(..);
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+
+        let _ = self.device.poll(wgpu::PollType::Wait {
+            submission_index: None,
+            timeout: None,
+        });
+
+        receiver
+            .await
+            .map_err(|_| "Failed to receive buffer mapping result")?
+            .map_err(|e| format!("Failed to map buffer: {:?}", e))?;
+
+        let mapped = buffer_slice.get_mapped_range();
+        let output = mapped.to_vec();
+        drop(mapped);
+        staging_buffer.unmap();
+
+        Ok(output)
+    }
+}
+
+/// Cached GPU stabilization pipeline instance
+static GPU_STABILIZE_PIPELINE: std::sync::OnceLock<
+    tokio::sync::Mutex<Option<GpuStabilizePipeline>>,
+> = std::sync::OnceLock::new();
+
+/// Get or create the shared GPU stabilization pipeline instance
+pub async fn get_gpu_stabilize_pipeline()
+-> Result<tokio::sync::MutexGuard<'static, Option<GpuStabilizePipeline>>, String> {
+    let lock = GPU_STABILIZE_PIPELINE.get_or_init(|| tokio::sync::Mutex::new(None));
+    let mut guard = lock.lock().await;
+
+    if guard.is_none() {
+        match GpuStabilizePipeline::new().await {
+            Ok(pipeline) => {
+                *guard = Some(pipeline);
+            }
+            Err(e) => {
+                warn!("Failed to initialize GPU stabilization pipeline: {}", e);
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(guard)
+}
+
+/// Crop and rescale RGBA data using the shared pipeline
+pub async fn stabilize_rgba_gpu(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    window: StabilizeWindow,
+) -> Result<Vec<u8>, String> {
+    let mut guard = get_gpu_stabilize_pipeline().await?;
+    let pipeline = guard
+        .as_mut()
+        .ok_or("GPU stabilization pipeline not initialized")?;
+
+    pipeline.stabilize_rgba(data, width, height, window).await
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn stabilize_shader_validates() {
+        let module = naga::front::wgsl::parse_str(include_str!("stabilize.wgsl"))
+            .expect("stabilize.wgsl failed to parse");
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::all(),
+        )
+        .validate(&module)
+        .expect("stabilize.wgsl failed validation");
+    }
+}
@@ -0,0 +1,133 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! HDR transfer of the camera signal
+//!
+//! 10-bit sources (P010/Y210 from HDMI capture cards and some ISPs) may carry
+//! BT.2100 HDR: BT.2020 primaries with the PQ or HLG transfer. The preview and
+//! photos are SDR, so the YUV conversion shader tone-maps such frames to
+//! BT.709; recordings keep the signal when the encoder takes 10-bit input.
+//!
+//! The app sets the transfer of the active camera (see [`set_hdr_transfer`])
+//! and every conversion of a 10-bit frame reads it.
+
+use crate::backends::camera::types::PixelFormat;
+use std::sync::RwLock;
+
+/// `V4L2_XFER_FUNC_SMPTE2084`; V4L2 has no code for HLG
+const V4L2_XFER_FUNC_SMPTE2084: u32 = 7;
+
+/// Transfer function of a camera signal
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HdrTransfer {
+    /// Standard dynamic range (BT.709 / sRGB)
+    #[default]
+    Sdr,
+    /// Perceptual quantizer (SMPTE ST 2084, HDR10): absolute, up to 10 000 nits
+    Pq,
+    /// Hybrid log-gamma (ARIB STD-B67): relative to the display's peak
+    Hlg,
+}
+
+impl HdrTransfer {
+    /// Whether the signal needs tone mapping for an SDR display
+    pub fn is_hdr(self) -> bool {
+        self != Self::Sdr
+    }
+
+    /// Value of the conversion shaders' `transfer` parameter
+    /// (0=SDR, 1=PQ, 2=HLG, see `yuv_convert.wgsl`)
+    pub fn shader_code(self) -> u32 {
+        match self {
+            Self::Sdr => 0,
+            Self::Pq => 1,
+            Self::Hlg => 2,
+        }
+    }
+
+    /// GStreamer colorimetry of the signal, for raw video caps
+    pub fn gst_colorimetry(self) -> Option<&'static str> {
+        match self {
+            Self::Sdr => None,
+            Self::Pq => Some("bt2100-pq"),
+            Self::Hlg => Some("bt2100-hlg"),
+        }
+    }
+
+    /// Transfer a V4L2 driver reports (`v4l2_xfer_func`)
+    pub fn from_v4l2_xfer_func(xfer_func: u32) -> Self {
+        if xfer_func == V4L2_XFER_FUNC_SMPTE2084 {
+            Self::Pq
+        } else {
+            Self::Sdr
+        }
+    }
+}
+
+/// Transfer of the active camera's 10-bit frames
+static HDR_TRANSFER: RwLock<HdrTransfer> = RwLock::new(HdrTransfer::Sdr);
+
+/// Set the transfer of the active camera's 10-bit frames
+pub fn set_hdr_transfer(transfer: HdrTransfer) {
+    if let Ok(mut current) = HDR_TRANSFER.write() {
+        *current = transfer;
+    }
+}
+
+/// Transfer of the active camera's 10-bit frames
+pub fn hdr_transfer() -> HdrTransfer {
+    HDR_TRANSFER
+        .read()
+        .map(|transfer| *transfer)
+        .unwrap_or_default()
+}
+
+/// Transfer of a frame in `format`
+pub fn frame_transfer(format: PixelFormat) -> HdrTransfer {
+    transfer_of(format, hdr_transfer())
+}
+
+/// Only 10-bit formats carry HDR: an 8-bit frame (a second camera, or a
+/// format switch not yet seen by the app) is SDR whatever the camera signals
+fn transfer_of(format: PixelFormat, active: HdrTransfer) -> HdrTransfer {
+    if format.is_10bit() {
+        active
+    } else {
+        HdrTransfer::Sdr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eight_bit_frames_are_never_tone_mapped() {
+        assert_eq!(
+            transfer_of(PixelFormat::P010, HdrTransfer::Pq),
+            HdrTransfer::Pq
+        );
+        assert_eq!(
+            transfer_of(PixelFormat::Y210, HdrTransfer::Hlg),
+            HdrTransfer::Hlg
+        );
+        assert_eq!(
+            transfer_of(PixelFormat::NV12, HdrTransfer::Pq),
+            HdrTransfer::Sdr
+        );
+        assert_eq!(
+            transfer_of(PixelFormat::RGBA, HdrTransfer::Hlg),
+            HdrTransfer::Sdr
+        );
+    }
+
+    #[test]
+    fn only_pq_is_read_from_v4l2() {
+        assert_eq!(HdrTransfer::from_v4l2_xfer_func(7), HdrTransfer::Pq);
+        // Default, Rec. 709 and sRGB
+        for xfer_func in [0, 1, 2] {
+            assert_eq!(
+                HdrTransfer::from_v4l2_xfer_func(xfer_func),
+                HdrTransfer::Sdr
+            );
+        }
+    }
+}
@@ -0,0 +1,305 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Imported 3D colour lookup tables (.cube)
+//!
+//! A `.cube` file is parsed here and resampled onto a fixed [`LUT_GRID`]³
+//! lattice. Every filter pass (preview, photos, recordings, virtual camera)
+//! allocates its LUT texture at that size once, so switching LUTs is a
+//! texture write and never invalidates the passes' cached bind groups.
+//!
+//! Like the software white balance, the active LUT is process-wide: the
+//! passes read it whenever the custom LUT filter is selected.
+
+use crate::gpu::wgpu;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use tracing::debug;
+
+/// Edge length of the LUT texture every pass samples
+///
+/// 33 is what most grading tools export; smaller and larger tables are
+/// resampled trilinearly.
+pub const LUT_GRID: u32 = 33;
+
+/// Largest `LUT_3D_SIZE` accepted (the .cube specification's limit)
+const MAX_CUBE_SIZE: usize = 256;
+
+/// A parsed 3D LUT
+#[derive(Debug, Clone, PartialEq)]
+pub struct CubeLut {
+    /// `TITLE` from the file, if any
+    pub title: Option<String>,
+    /// Lattice points per axis
+    pub size: usize,
+    /// Output colours in file order (red index changes fastest)
+    table: Vec<[f32; 3]>,
+}
+
+impl CubeLut {
+    /// Parse the text of a `.cube` file
+    ///
+    /// Only 3D tables over the default 0-1 input domain are accepted.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut title = None;
+        let mut size = None;
+        let mut table = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let rest = rest.trim();
+            match keyword {
+                "TITLE" => title = Some(rest.trim_matches('"').to_string()),
+                "LUT_3D_SIZE" => {
+                    let n = rest
+                        .parse::<usize>()
+                        .map_err(|_| format!("Line {line_number}: invalid LUT_3D_SIZE"))?;
+                    if !(2..=MAX_CUBE_SIZE).contains(&n) {
+                        return Err(format!("Line {line_number}: LUT_3D_SIZE {n} out of range"));
+                    }
+                    size = Some(n);
+                }
+                "LUT_1D_SIZE" => return Err("1D LUTs are not supported".to_string()),
+                "DOMAIN_MIN" | "DOMAIN_MAX" => {
+                    let expected = if keyword == "DOMAIN_MIN" { 0.0 } else { 1.0 };
+                    let values = parse_triplet(rest)
+                        .ok_or_else(|| format!("Line {line_number}: invalid {keyword}"))?;
+                    if values.iter().any(|v| (v - expected).abs() > f32::EPSILON) {
+                        return Err(format!(
+                            "Line {line_number}: input domains other than 0-1 are not supported"
+                        ));
+                    }
+                }
+                _ if keyword.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                    // Other keywords (e.g. LUT_IN_VIDEO_RANGE) don't change the table
+                    debug!(keyword, "Ignoring .cube keyword");
+                }
+                _ => {
+                    let rgb = parse_triplet(line)
+                        .ok_or_else(|| format!("Line {line_number}: expected three numbers"))?;
+                    table.push(rgb);
+                }
+            }
+        }
+
+        let size = size.ok_or("Missing LUT_3D_SIZE")?;
+        if table.len() != size * size * size {
+            return Err(format!(
+                "Expected {} table entries, found {}",
+                size * size * size,
+                table.len()
+            ));
+        }
+
+        Ok(Self { title, size, table })
+    }
+
+    /// Read and parse a `.cube` file
+    pub fn load(path: &std::path::Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        Self::parse(&text)
+    }
+
+    /// Look up a colour (channels 0-1) with trilinear interpolation
+    pub fn sample(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let max = (self.size - 1) as f32;
+        // Lower lattice index and position between it and the next, per axis
+        let [r, g, b] = rgb.map(|v| {
+            let x = v.clamp(0.0, 1.0) * max;
+            let low = (x.floor() as usize).min(self.size - 2);
+            (low, x - low as f32)
+        });
+
+        let at =
+            |ri: usize, gi: usize, bi: usize| self.table[ri + self.size * (gi + self.size * bi)];
+        let lerp = |from: [f32; 3], to: [f32; 3], t: f32| -> [f32; 3] {
+            std::array::from_fn(|c| from[c] + (to[c] - from[c]) * t)
+        };
+        let along_r = |gi: usize, bi: usize| lerp(at(r.0, gi, bi), at(r.0 + 1, gi, bi), r.1);
+        let along_g = |bi: usize| lerp(along_r(g.0, bi), along_r(g.0 + 1, bi), g.1);
+        lerp(along_g(b.0), along_g(b.0 + 1), b.1)
+    }
+
+    /// RGBA8 texels of this LUT resampled onto the [`LUT_GRID`] lattice
+    fn to_texture_data(&self) -> Vec<u8> {
+        grid_texture_data(|rgb| self.sample(rgb))
+    }
+}
+
+fn parse_triplet(text: &str) -> Option<[f32; 3]> {
+    let mut values = text.split_whitespace().map(|v| v.parse::<f32>().ok());
+    let rgb = [values.next()??, values.next()??, values.next()??];
+    values.next().is_none().then_some(rgb)
+}
+
+/// Fill the [`LUT_GRID`]³ lattice (red fastest, like the texture's x axis)
+fn grid_texture_data(lookup: impl Fn([f32; 3]) -> [f32; 3]) -> Vec<u8> {
+    let n = LUT_GRID as usize;
+    let step = 1.0 / (n - 1) as f32;
+    let mut data = Vec::with_capacity(n * n * n * 4);
+    for b in 0..n {
+        for g in 0..n {
+            for r in 0..n {
+                let out = lookup([r as f32 * step, g as f32 * step, b as f32 * step]);
+                for c in out {
+                    data.push((c.clamp(0.0, 1.0) * 255.0).round() as u8);
+                }
+                data.push(255);
+            }
+        }
+    }
+    data
+}
+
+/// The active LUT's texels, and a counter bumped on every change so each
+/// pass knows when to re-upload
+struct ActiveLut {
+    generation: u64,
+    texels: Option<Arc<Vec<u8>>>,
+}
+
+static ACTIVE_LUT: RwLock<ActiveLut> = RwLock::new(ActiveLut {
+    generation: 0,
+    texels: None,
+});
+
+/// Set the LUT applied by the custom LUT filter (`None` leaves colours unchanged)
+pub fn set_active_lut(lut: Option<&CubeLut>) {
+    let texels = lut.map(|lut| Arc::new(lut.to_texture_data()));
+    if let Ok(mut active) = ACTIVE_LUT.write() {
+        active.generation += 1;
+        active.texels = texels;
+    }
+}
+
+/// LUT texture owned by one filter pass, kept in step with the active LUT
+pub(crate) struct LutTexture {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    /// Generation of the active LUT last uploaded (`u64::MAX` = nothing yet)
+    uploaded: AtomicU64,
+}
+
+impl LutTexture {
+    /// Binding type of the LUT texture in the filter passes' layouts
+    pub(crate) const BINDING_TYPE: wgpu::BindingType = wgpu::BindingType::Texture {
+        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+        view_dimension: wgpu::TextureViewDimension::D3,
+        multisampled: false,
+    };
+
+    pub(crate) fn new(device: &wgpu::Device) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("filter_lut_texture"),
+            size: wgpu::Extent3d {
+                width: LUT_GRID,
+                height: LUT_GRID,
+                depth_or_array_layers: LUT_GRID,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D3,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        Self {
+            texture,
+            view,
+            uploaded: AtomicU64::new(u64::MAX),
+        }
+    }
+
+    /// Upload the active LUT if it changed since the last call. Without
+    /// one, an identity table is uploaded.
+    pub(crate) fn sync(&self, queue: &wgpu::Queue) {
+        let (generation, texels) = match ACTIVE_LUT.read() {
+            Ok(active) => (active.generation, active.texels.clone()),
+            Err(_) => return,
+        };
+        if self.uploaded.swap(generation, Ordering::AcqRel) == generation {
+            return;
+        }
+
+        let texels = texels.unwrap_or_else(|| Arc::new(grid_texture_data(|rgb| rgb)));
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            &texels,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(LUT_GRID * 4),
+                rows_per_image: Some(LUT_GRID),
+            },
+            wgpu::Extent3d {
+                width: LUT_GRID,
+                height: LUT_GRID,
+                depth_or_array_layers: LUT_GRID,
+            },
+        );
+    }
+
+    pub(crate) fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INVERT_2: &str = "\
+# Inverts every channel
+TITLE \"Invert\"
+LUT_3D_SIZE 2
+DOMAIN_MIN 0 0 0
+DOMAIN_MAX 1 1 1
+1 1 1
+0 1 1
+1 0 1
+0 0 1
+1 1 0
+0 1 0
+1 0 0
+0 0 0
+";
+
+    #[test]
+    fn parses_and_interpolates() {
+        let lut = CubeLut::parse(INVERT_2).unwrap();
+        assert_eq!(lut.title.as_deref(), Some("Invert"));
+        assert_eq!(lut.size, 2);
+        let out = lut.sample([0.25, 0.5, 1.0]);
+        assert!((out[0] - 0.75).abs() < 1e-6);
+        assert!((out[1] - 0.5).abs() < 1e-6);
+        assert!(out[2].abs() < 1e-6);
+    }
+
+    #[test]
+    fn resampled_grid_keeps_the_mapping() {
+        let texels = CubeLut::parse(INVERT_2).unwrap().to_texture_data();
+        let n = LUT_GRID as usize;
+        assert_eq!(texels.len(), n * n * n * 4);
+        // First texel is input black, last is input white
+        assert_eq!(&texels[..4], &[255, 255, 255, 255]);
+        assert_eq!(&texels[texels.len() - 4..], &[0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn rejects_unsupported_files() {
+        assert!(CubeLut::parse("LUT_1D_SIZE 4\n0 0 0\n").is_err());
+        assert!(CubeLut::parse("LUT_3D_SIZE 2\n0 0 0\n").is_err());
+        assert!(CubeLut::parse("LUT_3D_SIZE 2\nDOMAIN_MAX 2 2 2\n").is_err());
+        assert!(CubeLut::parse("0 0 0\n").is_err());
+    }
+}
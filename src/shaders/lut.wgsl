@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: GPL-3.0-only
+// Imported 3D LUT (filter mode 19)
+//
+// The table is resampled on the CPU onto a fixed lattice (see `lut.rs`) and
+// arrives here as a 3D texture parameter, so compute and fragment shaders share
+// this code over their own bindings. `textureSampleLevel` keeps it legal in
+// compute modules and in non-uniform control flow.
+
+// Map a colour through the LUT. Lattice points sit at texel centres, so input
+// 0-1 is squeezed into [0.5 / n, 1 - 0.5 / n] before the trilinear lookup.
+fn apply_lut(color: vec3<f32>, lut: texture_3d<f32>, samp: sampler) -> vec3<f32> {
+    let size = f32(textureDimensions(lut).x);
+    let coords = clamp(color, vec3<f32>(0.0), vec3<f32>(1.0)) * ((size - 1.0) / size) + 0.5 / size;
+    return textureSampleLevel(lut, samp, coords, 0.0).rgb;
+}
@@ -6,19 +6,58 @@
 //!
 //! ## Pipelines
 //!
-//! - **YUV Convert**: Converts YUV frames (NV12, I420, YUYV) to RGBA on GPU
-//! - **GPU Filter**: Applies visual filters (sepia, mono, etc.) to RGBA frames
+//! - **YUV Convert**: Converts YUV frames (NV12, I420, YUYV) to RGBA on GPU, at
+//!   preview or still [`ProcessingQuality`]
+//! - **GPU Filter**: Applies visual filters (sepia, mono, etc.) to RGBA frames,
+//!   including imported `.cube` LUTs (see [`CubeLut`])
 //! - **Histogram**: Analyzes brightness distribution for exposure metering
+//! - **PiP**: Composites a second camera into RGBA frames (corner inset or side by side)
+//! - **Rotate**: Corrects sensor and device rotation (and mirroring) of RGBA frames
+//! - **Sharpen**: Output unsharp mask for saved photos
+//! - **Stabilize**: Crops a moving window out of recorded frames to steady them
+//! - **Denoise**: Temporal and spatial noise reduction for low-light video
+//!
+//! The filter passes also apply the software white balance gains set from
+//! the eyedropper (see [`set_software_white_balance`]). The YUV conversion
+//! decodes YUV with the camera's matrix and range (see [`set_color_space`])
+//! and tone-maps 10-bit HDR frames to SDR (see [`set_hdr_transfer`]).
 //!
 //! All pipelines operate on RGBA textures for uniform downstream processing.
 
+mod color_space;
 mod gpu_convert;
+mod gpu_denoise;
 mod gpu_filter;
+mod gpu_pip;
+mod gpu_rotate;
+mod gpu_sharpen;
+mod gpu_stabilize;
+mod hdr;
 mod histogram_pipeline;
+mod lut;
+mod white_balance;
 
-pub use gpu_convert::{GpuConvertPipeline, GpuFrameInput, get_gpu_convert_pipeline};
-pub use gpu_filter::{GpuFilterPipeline, apply_filter_gpu_rgba, get_gpu_filter_pipeline};
+pub use color_space::{ColorSpace, YuvMatrix, color_space, frame_color_space, set_color_space};
+pub use gpu_convert::{
+    GpuConvertPipeline, GpuFrameInput, ProcessingQuality, get_gpu_convert_pipeline,
+};
+pub use gpu_denoise::{DenoiseStrength, GpuDenoisePipeline};
+pub use gpu_filter::{
+    GpuFilterPipeline, apply_filter_gpu_rgba, get_gpu_filter_pipeline, needs_filter_pass,
+};
+pub use gpu_pip::{GpuPipPipeline, composite_pip_gpu_rgba, get_gpu_pip_pipeline};
+pub use gpu_rotate::{GpuRotatePipeline, get_gpu_rotate_pipeline, rotate_rgba_gpu};
+pub use gpu_sharpen::{
+    GpuSharpenPipeline, UnsharpMask, get_gpu_sharpen_pipeline, sharpen_rgba_gpu,
+};
+pub use gpu_stabilize::{
+    GpuStabilizePipeline, StabilizeWindow, get_gpu_stabilize_pipeline, stabilize_rgba_gpu,
+};
+pub use hdr::{HdrTransfer, frame_transfer, hdr_transfer, set_hdr_transfer};
 pub use histogram_pipeline::{BrightnessMetrics, analyze_brightness_gpu};
+pub(crate) use lut::LutTexture;
+pub use lut::{CubeLut, set_active_lut};
+pub use white_balance::{WhiteBalanceGains, set_software_white_balance, software_white_balance};
 
 /// Precompile all GPU shader pipelines so the first capture doesn't pay compilation cost.
 ///
@@ -42,9 +81,17 @@ pub async fn warmup_gpu_pipelines() -> Result<(), String> {
 }
 
 /// Shared filter functions (WGSL)
-/// Contains: luminance(), hash(), apply_filter()
+/// Contains: luminance(), hash(), apply_filter(), the film emulation pack
+/// (apply_film() and its grain, tone curve and halation helpers) and
+/// apply_lut() for imported LUTs
 /// Used by: preview shaders, photo capture, virtual camera
-pub const FILTER_FUNCTIONS: &str = include_str!("filters.wgsl");
+pub const FILTER_FUNCTIONS: &str = concat!(
+    include_str!("filters.wgsl"),
+    "\n",
+    include_str!("film.wgsl"),
+    "\n",
+    include_str!("lut.wgsl")
+);
 
 /// Shared texture-sampling filter functions (WGSL)
 /// Contains: `apply_texture_filter()` — the filters that must re-sample the
@@ -57,5 +104,6 @@ pub const TEXTURE_FILTER_FUNCTIONS: &str = include_str!("texture_filters.wgsl");
 
 /// Shared UI-geometry functions (WGSL)
 /// Contains: rounded_box_sdf()
-/// Used by: the video shader, the frosted composite, the gallery thumbnail shader
+/// Used by: the video shader, the frosted composite, the gallery thumbnail
+/// shader, the picture-in-picture compositor
 pub const GEOMETRY_FUNCTIONS: &str = include_str!("geometry.wgsl");
@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: GPL-3.0-only
+// GPU compute shader compositing a second camera into a frame, either as a
+// picture-in-picture inset or as the right half of a side-by-side layout
+// Used by video recording, the virtual camera and photo capture
+// Geometry functions are prepended by the Rust code from geometry.wgsl
+
+struct PipParams {
+    width: u32,
+    height: u32,
+    _padding: vec2<u32>,
+    // Inset rectangle in output pixels: x, y, width, height
+    inset: vec4<f32>,
+    // Part of the base frame spread over the whole output: u, v, width, height
+    base_uv: vec4<f32>,
+    // Part of the secondary frame shown in the inset: u, v, width, height
+    inset_uv: vec4<f32>,
+    radius: f32,
+    // Width of the light outline around the inset, in pixels
+    border: f32,
+    _padding2: vec2<f32>,
+}
+
+@group(0) @binding(0)
+var base_texture: texture_2d<f32>;
+
+@group(0) @binding(1)
+var inset_texture: texture_2d<f32>;
+
+@group(0) @binding(2)
+var<storage, read_write> output_buffer: array<u32>;
+
+@group(0) @binding(3)
+var<uniform> params: PipParams;
+
+@group(0) @binding(4)
+var tex_sampler: sampler;
+
+@compute @workgroup_size(16, 16)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let x = global_id.x;
+    let y = global_id.y;
+
+    if (x >= params.width || y >= params.height) {
+        return;
+    }
+
+    let pos = vec2<f32>(f32(x) + 0.5, f32(y) + 0.5);
+    let size = vec2<f32>(f32(params.width), f32(params.height));
+    let base_uv = params.base_uv.xy + pos / size * params.base_uv.zw;
+    var color = textureSampleLevel(base_texture, tex_sampler, base_uv, 0.0);
+    let half_size = params.inset.zw * 0.5;
+    let dist = rounded_box_sdf(pos - (params.inset.xy + half_size), half_size, params.radius);
+
+    if (dist < 1.0) {
+        let local = clamp((pos - params.inset.xy) / params.inset.zw, vec2<f32>(0.0), vec2<f32>(1.0));
+        let uv = params.inset_uv.xy + local * params.inset_uv.zw;
+        var inset = textureSampleLevel(inset_texture, tex_sampler, uv, 0.0).rgb;
+
+        // Light outline so the inset stays readable over a similar background
+        if (params.border > 0.0) {
+            let outline = smoothstep(-params.border - 1.0, -params.border, dist);
+            inset = mix(inset, vec3<f32>(1.0), outline * 0.85);
+        }
+
+        let coverage = 1.0 - smoothstep(-1.0, 1.0, dist);
+        color = vec4<f32>(mix(color.rgb, inset, coverage), color.a);
+    }
+
+    // Pack RGBA into u32 (RGBA8 format)
+    let r = u32(clamp(color.r, 0.0, 1.0) * 255.0);
+    let g = u32(clamp(color.g, 0.0, 1.0) * 255.0);
+    let b = u32(clamp(color.b, 0.0, 1.0) * 255.0);
+    let a = u32(clamp(color.a, 0.0, 1.0) * 255.0);
+
+    output_buffer[y * params.width + x] = r | (g << 8u) | (b << 16u) | (a << 24u);
+}
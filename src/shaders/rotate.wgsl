@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: GPL-3.0-only
+// GPU compute shader rotating (and optionally mirroring) packed RGBA frames
+// Corrects sensor / device rotation for photos, recordings and the virtual camera
+
+struct RotateParams {
+    in_width: u32,
+    in_height: u32,
+    out_width: u32,
+    out_height: u32,
+    // Sensor rotation code: 0=None, 1=90CW, 2=180, 3=270CW. The frame is
+    // rotated the opposite way to correct it.
+    rotation: u32,
+    // 1 = flip horizontally after rotating
+    mirror: u32,
+    _padding: vec2<u32>,
+}
+
+@group(0) @binding(0)
+var<storage, read> input_buffer: array<u32>;
+
+@group(0) @binding(1)
+var<storage, read_write> output_buffer: array<u32>;
+
+@group(0) @binding(2)
+var<uniform> params: RotateParams;
+
+@compute @workgroup_size(16, 16)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let x = global_id.x;
+    let y = global_id.y;
+
+    if (x >= params.out_width || y >= params.out_height) {
+        return;
+    }
+
+    var ox = x;
+    if (params.mirror == 1u) {
+        ox = params.out_width - 1u - x;
+    }
+
+    var src = vec2<u32>(ox, y);
+    switch params.rotation {
+        // 90° clockwise sensor: rotate counter-clockwise
+        case 1u: {
+            src = vec2<u32>(params.in_width - 1u - y, ox);
+        }
+        case 2u: {
+            src = vec2<u32>(params.in_width - 1u - ox, params.in_height - 1u - y);
+        }
+        // 270° clockwise sensor: rotate clockwise
+        case 3u: {
+            src = vec2<u32>(y, params.in_height - 1u - ox);
+        }
+        default: {}
+    }
+
+    output_buffer[y * params.out_width + x] = input_buffer[src.y * params.in_width + src.x];
+}
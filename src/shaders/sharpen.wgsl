@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: GPL-3.0-only
+// GPU compute shader for output sharpening (unsharp mask) of packed RGBA photos
+//
+// Runs last on saved photos: after tone mapping for burst results, after
+// orientation for regular shots. Luma detail against a Gaussian blur is added
+// back to all three channels, so edges get crisper without color fringes.
+// Detail below the threshold is mostly sensor noise and is left alone.
+
+struct SharpenParams {
+    width: u32,
+    height: u32,
+    // Strength of the detail added back (1.0 = 100%)
+    amount: f32,
+    // Gaussian sigma in pixels
+    radius: f32,
+    // Detail below this (0-1 luma) is not sharpened
+    threshold: f32,
+    // Kernel half-size in pixels
+    taps: i32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
+@group(0) @binding(0)
+var<storage, read> input_buffer: array<u32>;
+
+@group(0) @binding(1)
+var<storage, read_write> output_buffer: array<u32>;
+
+@group(0) @binding(2)
+var<uniform> params: SharpenParams;
+
+// BT.601 luma of a pixel with clamp-to-edge addressing
+fn load_luma(x: i32, y: i32) -> f32 {
+    let cx = clamp(x, 0i, i32(params.width) - 1i);
+    let cy = clamp(y, 0i, i32(params.height) - 1i);
+    let rgb = unpack4x8unorm(input_buffer[u32(cy) * params.width + u32(cx)]).rgb;
+    return dot(rgb, vec3(0.299, 0.587, 0.114));
+}
+
+@compute @workgroup_size(16, 16)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    if (global_id.x >= params.width || global_id.y >= params.height) {
+        return;
+    }
+    let ix = i32(global_id.x);
+    let iy = i32(global_id.y);
+    let index = global_id.y * params.width + global_id.x;
+    let pixel = unpack4x8unorm(input_buffer[index]);
+
+    var blur = 0.0;
+    var weight_sum = 0.0;
+    let inv_two_sigma_sq = 1.0 / (2.0 * params.radius * params.radius);
+    for (var dy = -params.taps; dy <= params.taps; dy++) {
+        for (var dx = -params.taps; dx <= params.taps; dx++) {
+            let weight = exp(-f32(dx * dx + dy * dy) * inv_two_sigma_sq);
+            blur += load_luma(ix + dx, iy + dy) * weight;
+            weight_sum += weight;
+        }
+    }
+    blur /= weight_sum;
+
+    let luma = dot(pixel.rgb, vec3(0.299, 0.587, 0.114));
+    let detail = luma - blur;
+    let boost = select(0.0, detail * params.amount, abs(detail) > params.threshold);
+    let rgb = clamp(pixel.rgb + vec3(boost), vec3(0.0), vec3(1.0));
+
+    output_buffer[index] = pack4x8unorm(vec4(rgb, pixel.a));
+}
@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: GPL-3.0-only
+// GPU compute shader for video stabilization: crops a shifted window out of
+// a packed RGBA frame and scales it back up to the full frame size
+
+struct StabilizeParams {
+    width: u32,
+    height: u32,
+    // Size of the cropped window relative to the frame (0-1]
+    crop_scale: f32,
+    _padding0: u32,
+    // Centre of the window relative to the frame centre, in pixels
+    offset: vec2<f32>,
+    _padding1: vec2<u32>,
+}
+
+@group(0) @binding(0)
+var<storage, read> input_buffer: array<u32>;
+
+@group(0) @binding(1)
+var<storage, read_write> output_buffer: array<u32>;
+
+@group(0) @binding(2)
+var<uniform> params: StabilizeParams;
+
+fn load(x: i32, y: i32) -> vec4<f32> {
+    let cx = clamp(x, 0, i32(params.width) - 1);
+    let cy = clamp(y, 0, i32(params.height) - 1);
+    return unpack4x8unorm(input_buffer[u32(cy) * params.width + u32(cx)]);
+}
+
+@compute @workgroup_size(16, 16)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let x = global_id.x;
+    let y = global_id.y;
+
+    if (x >= params.width || y >= params.height) {
+        return;
+    }
+
+    let center = vec2<f32>(f32(params.width), f32(params.height)) * 0.5;
+    let dst = vec2<f32>(f32(x), f32(y)) + 0.5;
+    // Pixel-centre coordinates of the source sample
+    let src = center + (dst - center) * params.crop_scale + params.offset - 0.5;
+
+    // Bilinear sample
+    let base = floor(src);
+    let f = src - base;
+    let x0 = i32(base.x);
+    let y0 = i32(base.y);
+    let top = mix(load(x0, y0), load(x0 + 1, y0), f.x);
+    let bottom = mix(load(x0, y0 + 1), load(x0 + 1, y0 + 1), f.x);
+
+    output_buffer[y * params.width + x] = pack4x8unorm(mix(top, bottom, f.y));
+}
@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: GPL-3.0-only
+// GPU compute shader for the still-quality finishing pass
+//
+// Runs after format conversion for photos only (ProcessingQuality::Still);
+// preview, recording and virtual camera frames skip it.
+//
+// Chroma denoise: Cb/Cr are averaged over a square window, weighted by luma
+// similarity so color doesn't bleed across edges. Luma is left untouched;
+// output sharpening runs later on the final photo (sharpen.wgsl).
+
+struct FinishParams {
+    width: u32,
+    height: u32,
+    chroma_radius: i32,
+    _pad0: u32,
+}
+
+@group(0) @binding(0) var tex_input: texture_2d<f32>;
+@group(0) @binding(1) var output: texture_storage_2d<rgba8unorm, write>;
+@group(0) @binding(2) var<uniform> params: FinishParams;
+
+// Load a pixel with clamp-to-edge addressing
+fn load_rgb(x: i32, y: i32) -> vec3<f32> {
+    let cx = clamp(x, 0i, i32(params.width) - 1i);
+    let cy = clamp(y, 0i, i32(params.height) - 1i);
+    return textureLoad(tex_input, vec2(u32(cx), u32(cy)), 0).rgb;
+}
+
+// Full-range BT.601, with chroma centered on 0
+fn rgb_to_ycbcr(c: vec3<f32>) -> vec3<f32> {
+    let y = dot(c, vec3(0.299, 0.587, 0.114));
+    return vec3(y, (c.b - y) * 0.564, (c.r - y) * 0.713);
+}
+
+fn ycbcr_to_rgb(c: vec3<f32>) -> vec3<f32> {
+    let r = c.x + 1.403 * c.z;
+    let b = c.x + 1.773 * c.y;
+    let g = (c.x - 0.299 * r - 0.114 * b) / 0.587;
+    return vec3(r, g, b);
+}
+
+@compute @workgroup_size(16, 16)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    if (global_id.x >= params.width || global_id.y >= params.height) {
+        return;
+    }
+    let ix = i32(global_id.x);
+    let iy = i32(global_id.y);
+    let center = rgb_to_ycbcr(load_rgb(ix, iy));
+
+    // Edge-aware chroma average; the center always has weight 1
+    var chroma_sum = vec2(0.0);
+    var weight_sum = 0.0;
+    let radius = params.chroma_radius;
+    for (var dy = -radius; dy <= radius; dy++) {
+        for (var dx = -radius; dx <= radius; dx++) {
+            let neighbor = rgb_to_ycbcr(load_rgb(ix + dx, iy + dy));
+            let weight = max(1.0 - abs(neighbor.x - center.x) * 8.0, 0.0);
+            chroma_sum += neighbor.yz * weight;
+            weight_sum += weight;
+        }
+    }
+    let chroma = chroma_sum / weight_sum;
+
+    let rgb = clamp(ycbcr_to_rgb(vec3(center.x, chroma)), vec3(0.0), vec3(1.0));
+    textureStore(output, vec2(global_id.x, global_id.y), vec4(rgb, 1.0));
+}
@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: GPL-3.0-only
-// Filters that RE-SAMPLE the source texture — Chromatic Aberration (13) and
-// Pencil (14) — for the fragment shaders that draw the camera frame.
+// Filters that RE-SAMPLE the source texture — Chromatic Aberration (13),
+// Pencil (14) and the film stocks' halation (15-18) — for the fragment shaders
+// that draw the camera frame, plus the dispatch to the imported LUT (19), which
+// needs the caller's LUT texture.
 //
 // They cannot live in `filters.wgsl` next to the other thirteen: that prelude is
 // also concatenated into COMPUTE modules (`filter_compute.wgsl`), and
@@ -15,7 +17,8 @@
 // drifted between the two would put a visibly different sketch behind the
 // overlay chrome than in front of it.
 //
-// Requires `filters.wgsl` (luminance, hash, apply_filter) ahead of it.
+// Requires `filters.wgsl` (luminance, hash, apply_filter), `film.wgsl`
+// (apply_film) and `lut.wgsl` (apply_lut) ahead of it.
 
 // Sample luminance at a UV, for edge detection.
 fn sample_luminance_tex(uv: vec2<f32>, tex: texture_2d<f32>, samp: sampler) -> f32 {
@@ -44,17 +47,148 @@ fn sobel_edge_tex(
     return sqrt(gx * gx + gy * gy);
 }
 
-// Apply any filter (0-14) to a colour already sampled at `tex_coords`.
+// Highlight bloom around a UV for film halation: a ring of highlight samples.
+fn film_glow_tex(uv: vec2<f32>, tex: texture_2d<f32>, samp: sampler) -> f32 {
+    var glow = 0.0;
+    for (var i = 0; i < 8; i++) {
+        let angle = f32(i) * 0.785398;
+        let offset = vec2<f32>(cos(angle), sin(angle)) * FILM_HALATION_RADIUS;
+        glow += film_highlight(textureSample(tex, samp, uv + offset).rgb);
+    }
+    return glow / 8.0;
+}
+
+// Set in `filter_mode` to draw the false colour exposure map instead of the
+// filter. Mirrors `FALSE_COLOR_FLAG` in `video_primitive.rs`.
+const FALSE_COLOR_FLAG: u32 = 0x100u;
+
+// False colour exposure map, the scale professional monitors use: the luma
+// bands that matter for exposure are painted in flat colours and everything
+// else is shown in grey. Luma is the gamma-encoded signal, so the bands read
+// like IRE.
+fn false_color(color: vec3<f32>) -> vec3<f32> {
+    let luma = luminance(color);
+    if (luma < 0.025) {
+        return vec3<f32>(0.5, 0.0, 0.7);   // Crushed blacks: purple
+    }
+    if (luma < 0.10) {
+        return vec3<f32>(0.0, 0.35, 1.0);  // Just above black: blue
+    }
+    if (luma >= 0.38 && luma < 0.48) {
+        return vec3<f32>(0.2, 0.8, 0.2);   // Middle grey: green
+    }
+    if (luma >= 0.52 && luma < 0.58) {
+        return vec3<f32>(1.0, 0.6, 0.75);  // Skin tones: pink
+    }
+    if (luma >= 0.97) {
+        return vec3<f32>(1.0, 0.0, 0.0);   // Clipped: red
+    }
+    if (luma >= 0.80) {
+        return vec3<f32>(1.0, 0.9, 0.0);   // Nearly clipped: yellow
+    }
+    return vec3<f32>(luma);
+}
+
+// Bits of `filter_mode` that hold the filter code itself
+const FILTER_CODE_MASK: u32 = 0xffu;
+
+// `filter_mode` bits 9-11 hold the colour vision assist: 0 is off, bit 2
+// picks simulation over correction and the low two bits the deficiency
+// (1 protanopia, 2 deuteranopia, 3 tritanopia). Mirrors
+// `COLOR_VISION_SHIFT` in `video_primitive.rs`.
+const COLOR_VISION_SHIFT: u32 = 9u;
+
+// Colour seen by a dichromat with the given deficiency (1-3), from the
+// full-severity matrices of Machado, Oliveira and Fernandes (2009). They
+// work on linear light.
+fn simulate_color_vision(color: vec3<f32>, deficiency: u32) -> vec3<f32> {
+    let linear = pow(max(color, vec3<f32>(0.0)), vec3<f32>(2.2));
+    var r = vec3<f32>(1.0, 0.0, 0.0);
+    var g = vec3<f32>(0.0, 1.0, 0.0);
+    var b = vec3<f32>(0.0, 0.0, 1.0);
+    if (deficiency == 1u) {
+        r = vec3<f32>(0.152286, 1.052583, -0.204868);
+        g = vec3<f32>(0.114503, 0.786281, 0.099216);
+        b = vec3<f32>(-0.003882, -0.048116, 1.051998);
+    } else if (deficiency == 2u) {
+        r = vec3<f32>(0.367322, 0.860646, -0.227968);
+        g = vec3<f32>(0.280085, 0.672501, 0.047413);
+        b = vec3<f32>(-0.011820, 0.042940, 0.968881);
+    } else if (deficiency == 3u) {
+        r = vec3<f32>(1.255528, -0.076749, -0.178779);
+        g = vec3<f32>(-0.078411, 0.930809, 0.147602);
+        b = vec3<f32>(0.004733, 0.691367, 0.303900);
+    }
+    let seen = vec3<f32>(dot(r, linear), dot(g, linear), dot(b, linear));
+    return pow(clamp(seen, vec3<f32>(0.0), vec3<f32>(1.0)), vec3<f32>(1.0 / 2.2));
+}
+
+// Daltonize: take the difference the deficiency hides and move it onto the
+// channels that are still seen. Red-green losses go to green and blue,
+// blue-yellow losses to red and green.
+fn correct_color_vision(color: vec3<f32>, deficiency: u32) -> vec3<f32> {
+    let lost = color - simulate_color_vision(color, deficiency);
+    var shift: vec3<f32>;
+    if (deficiency == 3u) {
+        shift = vec3<f32>(lost.r + 0.7 * lost.b, lost.g + 0.7 * lost.b, 0.0);
+    } else {
+        shift = vec3<f32>(0.0, 0.7 * lost.r + lost.g, 0.7 * lost.r + lost.b);
+    }
+    return clamp(color + shift, vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+// Apply any filter (0-19) to a colour already sampled at `tex_coords` and
+// white balanced with `wb_gains`. The filters that re-sample apply the same
+// gains to what they fetch. With `FALSE_COLOR_FLAG` set, the false colour map
+// replaces the filter; a colour vision assist in bits 9-11 is applied last,
+// over either.
+fn apply_texture_filter(
+    color: vec3<f32>,
+    filter_mode: u32,
+    tex_coords: vec2<f32>,
+    tex: texture_2d<f32>,
+    samp: sampler,
+    wb_gains: vec3<f32>,
+    lut: texture_3d<f32>,
+) -> vec3<f32> {
+    var shaded: vec3<f32>;
+    if ((filter_mode & FALSE_COLOR_FLAG) != 0u) {
+        shaded = false_color(color);
+    } else {
+        shaded = filter_texture(
+            color,
+            filter_mode & FILTER_CODE_MASK,
+            tex_coords,
+            tex,
+            samp,
+            wb_gains,
+            lut,
+        );
+    }
+
+    let vision = (filter_mode >> COLOR_VISION_SHIFT) & 7u;
+    if (vision == 0u) {
+        return shaded;
+    }
+    if ((vision & 4u) != 0u) {
+        return simulate_color_vision(shaded, vision & 3u);
+    }
+    return correct_color_vision(shaded, vision & 3u);
+}
+
+// The filter itself, for a bare filter code.
 //
 // Total over the whole filter range: modes 0-12 delegate to `apply_filter`, so a
 // caller that draws the camera frame can route every mode through here and never
 // has to know which ones re-sample.
-fn apply_texture_filter(
+fn filter_texture(
     color: vec3<f32>,
     filter_mode: u32,
     tex_coords: vec2<f32>,
     tex: texture_2d<f32>,
     samp: sampler,
+    wb_gains: vec3<f32>,
+    lut: texture_3d<f32>,
 ) -> vec3<f32> {
     if (filter_mode <= 12u) {
         return apply_filter(color, filter_mode, tex_coords);
@@ -65,7 +199,7 @@ fn apply_texture_filter(
         let offset_uv = 0.004; // 0.4% of width
         let color_r = textureSample(tex, samp, tex_coords + vec2<f32>(offset_uv, 0.0));
         let color_b = textureSample(tex, samp, tex_coords - vec2<f32>(offset_uv, 0.0));
-        return vec3<f32>(color_r.r, color.g, color_b.b);
+        return min(vec3<f32>(color_r.r * wb_gains.r, color.g, color_b.b * wb_gains.b), vec3<f32>(1.0));
     }
 
     if (filter_mode == 14u) {
@@ -91,5 +225,19 @@ fn apply_texture_filter(
         return vec3<f32>(final_val, final_val * 0.98, final_val * 0.95);
     }
 
+    if (is_film_mode(filter_mode)) {
+        // Film stocks: halation re-samples the highlights around the pixel
+        var glow = 0.0;
+        if (film_stock(filter_mode).halation > 0.0) {
+            glow = film_glow_tex(tex_coords, tex, samp);
+        }
+        let tex_size = vec2<f32>(textureDimensions(tex));
+        return apply_film(color, filter_mode, tex_coords * tex_size, glow);
+    }
+
+    if (filter_mode == 19u) {
+        return apply_lut(color, lut, samp);
+    }
+
     return color;
 }
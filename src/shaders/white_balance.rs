@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Software white balance
+//!
+//! Cameras without a white balance temperature control can still be
+//! balanced from the gray-point eyedropper: it yields per-channel gains that
+//! every GPU filter pass (preview, photos, recordings, virtual camera)
+//! multiplies into the frame before its filter runs. The gains are held
+//! here, process-wide, so the capture paths pick them up without threading
+//! them through each pipeline's configuration.
+
+use std::sync::RwLock;
+
+/// Per-channel white balance gains
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WhiteBalanceGains {
+    pub red: f32,
+    pub green: f32,
+    pub blue: f32,
+}
+
+impl WhiteBalanceGains {
+    /// Gains that leave the frame unchanged
+    pub const IDENTITY: Self = Self {
+        red: 1.0,
+        green: 1.0,
+        blue: 1.0,
+    };
+
+    /// Whether applying these gains would change the frame
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    /// Layout of the `wb_gains` shader uniforms (alpha unused)
+    pub(crate) fn to_uniform(self) -> [f32; 4] {
+        [self.red, self.green, self.blue, 1.0]
+    }
+}
+
+impl Default for WhiteBalanceGains {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Gains currently applied by the GPU filter passes
+static SOFTWARE_WHITE_BALANCE: RwLock<WhiteBalanceGains> = RwLock::new(WhiteBalanceGains::IDENTITY);
+
+/// Set the gains applied by the GPU filter passes
+pub fn set_software_white_balance(gains: WhiteBalanceGains) {
+    if let Ok(mut current) = SOFTWARE_WHITE_BALANCE.write() {
+        *current = gains;
+    }
+}
+
+/// Gains currently applied by the GPU filter passes
+pub fn software_white_balance() -> WhiteBalanceGains {
+    SOFTWARE_WHITE_BALANCE
+        .read()
+        .map(|gains| *gains)
+        .unwrap_or_default()
+}
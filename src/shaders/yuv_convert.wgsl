@@ -5,8 +5,14 @@
 // - NV12: Semi-planar 4:2:0 (Y plane + interleaved UV plane)
 // - I420: Planar 4:2:0 (Y + U + V separate planes)
 // - YUYV: Packed 4:2:2 (Y0 U Y1 V interleaved)
+// - P010/Y210: NV12 and YUYV layouts with 16-bit samples (10 bits used)
 //
-// Uses BT.601 color matrix (standard for webcams and JPEG)
+// 16-bit samples are uploaded as pairs of 8-bit channels (low byte, high
+// byte) so no 16-bit texture formats are needed; `sample16` joins them.
+//
+// Uses the camera's color matrix (BT.601, BT.709 or BT.2020) and range
+// (limited or full). 10-bit HDR signals (BT.2100 PQ or HLG) are tone-mapped
+// to SDR BT.709 while the full precision is still at hand.
 
 struct ConvertParams {
     width: u32,
@@ -15,14 +21,20 @@ struct ConvertParams {
     y_stride: u32,    // Y plane stride in texels (for stride-aware sampling)
     uv_stride: u32,   // UV plane stride in texels
     v_stride: u32,    // V plane stride in texels (I420 only)
+    transfer: u32,    // 0=SDR, 1=PQ, 2=HLG (tone-mapped to SDR)
+    matrix: u32,      // 0=BT.601, 1=BT.709, 2=BT.2020
+    full_range: u32,  // 1 = samples use 0-255, 0 = limited (16-235)
     _pad0: u32,       // Padding for 16-byte alignment
     _pad1: u32,
+    _pad2: u32,
 }
 
-// Y plane texture (R8 for planar, RG8 for YUYV packed)
+// Y plane texture (R8 for planar, RGBA8 for YUYV packed, RG8 for P010,
+// RGBA8 with one texel per pixel for Y210)
 @group(0) @binding(0) var tex_y: texture_2d<f32>;
 
-// UV texture: RG8 for NV12 (interleaved UV), R8 for I420 (U plane only)
+// UV texture: RG8 for NV12 (interleaved UV), R8 for I420 (U plane only),
+// RGBA8 for P010 (interleaved 16-bit UV)
 @group(0) @binding(1) var tex_uv: texture_2d<f32>;
 
 // V texture: R8 for I420 only (V plane)
@@ -34,37 +46,40 @@ struct ConvertParams {
 // Conversion parameters
 @group(0) @binding(4) var<uniform> params: ConvertParams;
 
-// BT.601 YUV to RGB conversion (standard for webcams and JPEG)
-// Input: Y in [0,1], U/V in [0,1] (will be shifted to [-0.5, 0.5])
+// YUV to RGB conversion with the signal's matrix and range
+// Input: Y, U, V in [0,1]
 // Output: RGB in [0,1]
-fn yuv_to_rgb_bt601(y: f32, u: f32, v: f32) -> vec3<f32> {
-    // BT.601 uses limited range Y [16,235] and UV [16,240]
-    // Scale Y from [16/255, 235/255] to [0, 1]
-    let y_scaled = (y - 16.0 / 255.0) * (255.0 / 219.0);
+fn yuv_to_rgb(y: f32, u: f32, v: f32) -> vec3<f32> {
+    // Luma weights of red and blue: BT.601 (webcams and JPEG), BT.709 (HD)
+    // or BT.2020 (UHD and HDR)
+    var kr = 0.299;
+    var kb = 0.114;
+    if params.matrix == 1u {
+        kr = 0.2126;
+        kb = 0.0722;
+    } else if params.matrix == 2u {
+        kr = 0.2627;
+        kb = 0.0593;
+    }
 
-    // Shift U/V from [0, 1] to [-0.5, 0.5]
-    let u_shifted = u - 0.5;
-    let v_shifted = v - 0.5;
+    // Shift U/V to [-0.5, 0.5]; limited range also stretches Y from
+    // [16, 235] and U/V from [16, 240] to the full scale
+    var luma = y;
+    var cb = u - 128.0 / 255.0;
+    var cr = v - 128.0 / 255.0;
+    if params.full_range == 0u {
+        luma = (y - 16.0 / 255.0) * (255.0 / 219.0);
+        cb = cb * (255.0 / 224.0);
+        cr = cr * (255.0 / 224.0);
+    }
 
-    // BT.601 conversion matrix
-    let r = y_scaled + 1.402 * v_shifted;
-    let g = y_scaled - 0.344136 * u_shifted - 0.714136 * v_shifted;
-    let b = y_scaled + 1.772 * u_shifted;
+    let r = luma + 2.0 * (1.0 - kr) * cr;
+    let b = luma + 2.0 * (1.0 - kb) * cb;
+    let g = (luma - kr * r - kb * b) / (1.0 - kr - kb);
 
     return clamp(vec3(r, g, b), vec3(0.0), vec3(1.0));
 }
 
-// Alternative: BT.709 for HD content (uncomment if needed)
-// fn yuv_to_rgb_bt709(y: f32, u: f32, v: f32) -> vec3<f32> {
-//     let y_scaled = (y - 16.0 / 255.0) * (255.0 / 219.0);
-//     let u_shifted = u - 0.5;
-//     let v_shifted = v - 0.5;
-//     let r = y_scaled + 1.5748 * v_shifted;
-//     let g = y_scaled - 0.1873 * u_shifted - 0.4681 * v_shifted;
-//     let b = y_scaled + 1.8556 * u_shifted;
-//     return clamp(vec3(r, g, b), vec3(0.0), vec3(1.0));
-// }
-
 // Convert NV12 pixel at given position
 // NV12: Y plane (full res) + UV plane (half res, interleaved U0V0 U1V1...)
 fn convert_nv12(pos: vec2<u32>) -> vec3<f32> {
@@ -77,7 +92,7 @@ fn convert_nv12(pos: vec2<u32>) -> vec3<f32> {
     let uv_pos = vec2(pos.x * uv_dim.x / y_dim.x, pos.y * uv_dim.y / y_dim.y);
     let uv = textureLoad(tex_uv, uv_pos, 0);
 
-    return yuv_to_rgb_bt601(y, uv.r, uv.g);
+    return yuv_to_rgb(y, uv.r, uv.g);
 }
 
 // Convert planar YUV pixel at given position
@@ -98,7 +113,7 @@ fn convert_i420(pos: vec2<u32>) -> vec3<f32> {
     let u = textureLoad(tex_uv, uv_pos, 0).r;
     let v = textureLoad(tex_v, uv_pos, 0).r;
 
-    return yuv_to_rgb_bt601(y, u, v);
+    return yuv_to_rgb(y, u, v);
 }
 
 // Convert YUYV (YUY2) pixel at given position
@@ -119,7 +134,7 @@ fn convert_yuyv(pos: vec2<u32>) -> vec3<f32> {
     let u = packed.g;
     let v = packed.a;
 
-    return yuv_to_rgb_bt601(y, u, v);
+    return yuv_to_rgb(y, u, v);
 }
 
 // Convert UYVY pixel at given position
@@ -138,7 +153,7 @@ fn convert_uyvy(pos: vec2<u32>) -> vec3<f32> {
     let u = packed.r;
     let v = packed.b;
 
-    return yuv_to_rgb_bt601(y, u, v);
+    return yuv_to_rgb(y, u, v);
 }
 
 // Convert YVYU pixel at given position
@@ -157,7 +172,7 @@ fn convert_yvyu(pos: vec2<u32>) -> vec3<f32> {
     let u = packed.a;
     let v = packed.g;
 
-    return yuv_to_rgb_bt601(y, u, v);
+    return yuv_to_rgb(y, u, v);
 }
 
 // Convert VYUY pixel at given position
@@ -176,7 +191,7 @@ fn convert_vyuy(pos: vec2<u32>) -> vec3<f32> {
     let u = packed.b;
     let v = packed.r;
 
-    return yuv_to_rgb_bt601(y, u, v);
+    return yuv_to_rgb(y, u, v);
 }
 
 // Convert NV21 pixel at given position
@@ -192,7 +207,122 @@ fn convert_nv21(pos: vec2<u32>) -> vec3<f32> {
     let vu = textureLoad(tex_uv, uv_pos, 0);
 
     // VU layout: R=V, G=U (swapped from NV12's UV)
-    return yuv_to_rgb_bt601(y, vu.g, vu.r);
+    return yuv_to_rgb(y, vu.g, vu.r);
+}
+
+// Join a 16-bit little-endian sample from its low and high bytes, as loaded
+// from an 8-bit unorm texture, back into [0, 1]
+fn sample16(lo: f32, hi: f32) -> f32 {
+    return (hi * 255.0 * 256.0 + lo * 255.0) / 65535.0;
+}
+
+// Convert P010 pixel at given position
+// P010: NV12 layout with 16-bit samples. Y texture is RG8 (lo, hi), UV
+// texture is RGBA8 holding U (lo, hi) and V (lo, hi)
+fn convert_p010(pos: vec2<u32>) -> vec3<f32> {
+    let y_texel = textureLoad(tex_y, pos, 0);
+    let y = sample16(y_texel.r, y_texel.g);
+
+    let y_dim = textureDimensions(tex_y);
+    let uv_dim = textureDimensions(tex_uv);
+    let uv_pos = vec2(pos.x * uv_dim.x / y_dim.x, pos.y * uv_dim.y / y_dim.y);
+    let uv = textureLoad(tex_uv, uv_pos, 0);
+
+    return yuv_to_rgb(y, sample16(uv.r, uv.g), sample16(uv.b, uv.a));
+}
+
+// Convert Y210 pixel at given position
+// Y210: YUYV layout with 16-bit samples, 8 bytes per pixel pair:
+// [Y0 U Y1 V]. Uploaded as RGBA8 with one texel per pixel, so the even
+// texel holds Y0 and U and the odd one Y1 and V
+fn convert_y210(pos: vec2<u32>) -> vec3<f32> {
+    let pair_x = pos.x & ~1u;
+    let first = textureLoad(tex_y, vec2(pair_x, pos.y), 0);
+    let second = textureLoad(tex_y, vec2(pair_x + 1u, pos.y), 0);
+
+    let is_odd = (pos.x & 1u) == 1u;
+    let luma = select(first.rg, second.rg, is_odd);
+    let y = sample16(luma.x, luma.y);
+
+    return yuv_to_rgb(y, sample16(first.b, first.a), sample16(second.b, second.a));
+}
+
+// Luminance of SDR reference white (ITU-R BT.2408)
+const SDR_WHITE_NITS: f32 = 203.0;
+
+// Peak the tone curve rolls off to white: the usual HDR10 mastering peak,
+// and the HLG reference display
+const HDR_PEAK_NITS: f32 = 1000.0;
+
+// Fraction of SDR white below which the tone curve is the identity
+const TONE_KNEE: f32 = 0.75;
+
+// BT.2020 to BT.709 primaries, in linear light (columns)
+const BT2020_TO_BT709: mat3x3<f32> = mat3x3<f32>(
+    vec3<f32>(1.660491, -0.124550, -0.018151),
+    vec3<f32>(-0.587641, 1.132900, -0.100579),
+    vec3<f32>(-0.072850, -0.008349, 1.118730),
+);
+
+// PQ (SMPTE ST 2084) EOTF: signal [0, 1] to display nits
+fn pq_to_nits(signal: vec3<f32>) -> vec3<f32> {
+    let m1 = 0.1593017578125;
+    let m2 = 78.84375;
+    let c1 = 0.8359375;
+    let c2 = 18.8515625;
+    let c3 = 18.6875;
+    let p = pow(max(signal, vec3(0.0)), vec3(1.0 / m2));
+    return pow(max(p - c1, vec3(0.0)) / (c2 - c3 * p), vec3(1.0 / m1)) * 10000.0;
+}
+
+// HLG (ARIB STD-B67) inverse OETF, then the BT.2100 OOTF of a display
+// peaking at HDR_PEAK_NITS (system gamma 1.2)
+fn hlg_to_nits(signal: vec3<f32>) -> vec3<f32> {
+    let a = 0.17883277;
+    let b = 0.28466892;
+    let c = 0.55991073;
+    let low = signal * signal / 3.0;
+    let high = (exp((signal - c) / a) + b) / 12.0;
+    let scene = select(high, low, signal <= vec3(0.5));
+    let scene_luma = dot(scene, vec3(0.2627, 0.6780, 0.0593));
+    return HDR_PEAK_NITS * pow(max(scene_luma, 1e-6), 0.2) * scene;
+}
+
+// sRGB encoding of linear light [0, 1]
+fn srgb_encode(linear: vec3<f32>) -> vec3<f32> {
+    let high = 1.055 * pow(linear, vec3(1.0 / 2.4)) - 0.055;
+    return select(high, linear * 12.92, linear <= vec3(0.0031308));
+}
+
+// Tone curve, in units of SDR white: the identity up to TONE_KNEE, then an
+// extended Reinhard roll-off with matching slope that reaches 1.0 at `white`
+fn tone_curve(x: f32, white: f32) -> f32 {
+    if x <= TONE_KNEE {
+        return x;
+    }
+    let t = (x - TONE_KNEE) / (1.0 - TONE_KNEE);
+    let t_white = (white - TONE_KNEE) / (1.0 - TONE_KNEE);
+    let rolled = t * (1.0 + t / (t_white * t_white)) / (1.0 + t);
+    return TONE_KNEE + (1.0 - TONE_KNEE) * rolled;
+}
+
+// Tone-map an HDR signal (BT.2020 R'G'B') to SDR BT.709 for display
+// The curve runs on the brightest channel so hue is kept; HDR_PEAK_NITS
+// lands on white
+fn tone_map_hdr(signal: vec3<f32>, transfer: u32) -> vec3<f32> {
+    var nits: vec3<f32>;
+    if transfer == 1u {
+        nits = pq_to_nits(signal);
+    } else {
+        nits = hlg_to_nits(signal);
+    }
+    let rgb = max(BT2020_TO_BT709 * (nits / SDR_WHITE_NITS), vec3(0.0));
+
+    let peak = max(rgb.r, max(rgb.g, rgb.b));
+    let mapped = tone_curve(peak, HDR_PEAK_NITS / SDR_WHITE_NITS);
+    let sdr = select(rgb * (mapped / peak), vec3(0.0), peak <= 0.0);
+
+    return srgb_encode(clamp(sdr, vec3(0.0), vec3(1.0)));
 }
 
 // Convert Gray8 pixel at given position
@@ -228,6 +358,19 @@ fn convert_bgra(pos: vec2<u32>) -> vec4<f32> {
     return vec4(loaded.b, loaded.g, loaded.r, loaded.a);
 }
 
+// Convert RGBx pixel at given position
+// RGBx: RGBA byte order with an undefined fourth byte, made opaque
+fn convert_rgbx(pos: vec2<u32>) -> vec4<f32> {
+    return vec4(textureLoad(tex_y, pos, 0).rgb, 1.0);
+}
+
+// Convert BGRx pixel at given position
+// BGRx: BGRA byte order with an undefined fourth byte, made opaque
+fn convert_bgrx(pos: vec2<u32>) -> vec4<f32> {
+    let loaded = textureLoad(tex_y, pos, 0);
+    return vec4(loaded.b, loaded.g, loaded.r, 1.0);
+}
+
 // Passthrough for RGBA (or already converted) data
 fn passthrough_rgba(pos: vec2<u32>) -> vec4<f32> {
     return textureLoad(tex_y, pos, 0);
@@ -247,7 +390,8 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     var color: vec4<f32>;
 
     // Select conversion based on format
-    // Format codes: 0=RGBA, 1=NV12, 2=I420, 3=YUYV, 4=UYVY, 5=Gray8, 6=RGB24, 7=NV21, 8=YVYU, 9=VYUY, 10=ABGR, 11=BGRA
+    // Format codes: 0=RGBA, 1=NV12, 2=I420, 3=YUYV, 4=UYVY, 5=Gray8, 6=RGB24, 7=NV21, 8=YVYU, 9=VYUY, 10=ABGR, 11=BGRA,
+    // 16=P010, 17=Y210, 18=RGBx, 19=BGRx (12-15 are Bayer, which has its own shader)
     switch params.format {
         case 1u: {
             // NV12
@@ -293,12 +437,33 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
             // BGRA
             color = convert_bgra(pos);
         }
+        case 16u: {
+            // P010
+            color = vec4(convert_p010(pos), 1.0);
+        }
+        case 17u: {
+            // Y210
+            color = vec4(convert_y210(pos), 1.0);
+        }
+        case 18u: {
+            // RGBx
+            color = convert_rgbx(pos);
+        }
+        case 19u: {
+            // BGRx
+            color = convert_bgrx(pos);
+        }
         default: {
             // RGBA passthrough (format 0 or unknown)
             color = passthrough_rgba(pos);
         }
     }
 
+    // HDR signals leave the switch as BT.2020 R'G'B'; bring them to SDR
+    if params.transfer != 0u {
+        color = vec4(tone_map_hdr(color.rgb, params.transfer), color.a);
+    }
+
     // Write to output texture
     textureStore(output, pos, color);
 }
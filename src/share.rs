@@ -0,0 +1,226 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Sharing captures
+//!
+//! A photo or video can be handed to another application through the
+//! desktop portals: OpenURI with `ask` set shows the desktop's "Open with"
+//! chooser, and the Email portal starts a message in the user's mail client
+//! with the file attached. Anything else (a Nextcloud upload, a chat
+//! client's CLI, a script) is a custom target in a TOML file
+//! ([`targets_path`]):
+//!
+//! ```toml
+//! [[target]]
+//! name = "Nextcloud"
+//! command = ["nextcloud-upload", "--folder", "Camera", "{file}"]
+//! ```
+//!
+//! `{file}` in a command is replaced by the capture's path; a command
+//! without it gets the path as its last argument.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+const APP_ID: &str = <crate::app::AppModel as cosmic::Application>::APP_ID;
+
+/// File name of the share targets file inside the app's config directory
+const TARGETS_FILE_NAME: &str = "share.toml";
+
+/// Placeholder for the shared file in a custom target's command
+const FILE_PLACEHOLDER: &str = "{file}";
+
+/// Written when the user opens the targets file for the first time
+const EXAMPLE_TARGETS: &str = r#"# Custom share targets, listed next to "Open with" and "Email" when sharing
+# a capture.
+#
+# command: program and arguments; "{file}" is replaced by the capture's path,
+#          which is appended instead when no argument contains it.
+
+# [[target]]
+# name = "Nextcloud"
+# command = ["nextcloud-upload", "--folder", "Camera", "{file}"]
+"#;
+
+/// Contents of a share targets file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShareTargets {
+    #[serde(default, rename = "target")]
+    pub targets: Vec<CustomTarget>,
+}
+
+/// A user-defined share target
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomTarget {
+    pub name: String,
+    pub command: Vec<String>,
+}
+
+/// Where a capture is shared to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareTarget {
+    /// The desktop's application chooser
+    OpenWith,
+    /// A new message in the mail client
+    Email,
+    /// Index into [`ShareTargets::targets`]
+    Custom(usize),
+}
+
+/// Default location of the share targets file
+pub fn targets_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_ID).join(TARGETS_FILE_NAME))
+}
+
+impl ShareTargets {
+    /// Parse targets from TOML text
+    pub fn parse(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|e| format!("Invalid share targets file: {e}"))
+    }
+
+    /// Load targets from `path`. A missing file has no targets.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("Failed to read {}: {e}", path.display())),
+        }
+    }
+}
+
+/// Create the targets file with a commented example if it doesn't exist yet
+pub fn ensure_targets_file(path: &Path) -> Result<(), String> {
+    if path.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    std::fs::write(path, EXAMPLE_TARGETS)
+        .map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+/// Arguments for running `command` on `file`
+fn command_args(command: &[String], file: &Path) -> Vec<std::ffi::OsString> {
+    let file = file.as_os_str();
+    let mut args: Vec<std::ffi::OsString> = command
+        .iter()
+        .map(|arg| match arg.split_once(FILE_PLACEHOLDER) {
+            Some((before, after)) => {
+                let mut expanded = std::ffi::OsString::from(before);
+                expanded.push(file);
+                expanded.push(after);
+                expanded
+            }
+            None => arg.into(),
+        })
+        .collect();
+    if !command.iter().any(|arg| arg.contains(FILE_PLACEHOLDER)) {
+        args.push(file.to_owned());
+    }
+    args
+}
+
+/// Start a custom target's command on `file` without waiting for it
+pub fn run_custom(target: &CustomTarget, file: &Path) -> Result<(), String> {
+    let args = command_args(&target.command, file);
+    let (program, args) = args
+        .split_first()
+        .ok_or_else(|| format!("Share target '{}' has an empty command", target.name))?;
+    let mut child = std::process::Command::new(program)
+        .args(args)
+        .spawn()
+        .map_err(|e| format!("Failed to run '{}': {e}", program.to_string_lossy()))?;
+    info!(target = %target.name, file = %file.display(), "Started share target");
+
+    // Reap the child so it doesn't linger as a zombie
+    let name = target.name.clone();
+    std::thread::spawn(move || match child.wait() {
+        Ok(status) if !status.success() => warn!(?status, target = %name, "Share target failed"),
+        Err(e) => warn!(error = %e, "Failed to wait for share target"),
+        _ => {}
+    });
+    Ok(())
+}
+
+/// Show the desktop's "Open with" chooser for `file`
+pub async fn open_with(file: PathBuf) -> Result<(), String> {
+    use ashpd::desktop::open_uri::OpenFileRequest;
+
+    let fd = std::fs::File::open(&file)
+        .map_err(|e| format!("Failed to open {}: {e}", file.display()))?;
+    OpenFileRequest::default()
+        .ask(true)
+        .send_file(&fd)
+        .await
+        .map_err(|e| format!("Open with failed: {e}"))?;
+    info!(file = %file.display(), "Shared through the OpenURI portal");
+    Ok(())
+}
+
+/// Start an email with `file` attached
+pub async fn email(file: PathBuf) -> Result<(), String> {
+    use ashpd::desktop::email::EmailRequest;
+
+    let fd = std::fs::File::open(&file)
+        .map_err(|e| format!("Failed to open {}: {e}", file.display()))?;
+    EmailRequest::default()
+        .attach(&fd)
+        .send()
+        .await
+        .map_err(|e| format!("Email portal failed: {e}"))?;
+    info!(file = %file.display(), "Shared through the Email portal");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(command: &[&str], file: &str) -> Vec<String> {
+        let command: Vec<String> = command.iter().map(|s| s.to_string()).collect();
+        command_args(&command, Path::new(file))
+            .into_iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn targets_parse() {
+        assert!(
+            ShareTargets::parse(EXAMPLE_TARGETS)
+                .unwrap()
+                .targets
+                .is_empty()
+        );
+        let targets = ShareTargets::parse(
+            r#"
+            [[target]]
+            name = "Nextcloud"
+            command = ["nextcloud-upload", "{file}"]
+            "#,
+        )
+        .unwrap()
+        .targets;
+        assert_eq!(
+            targets,
+            [CustomTarget {
+                name: "Nextcloud".into(),
+                command: vec!["nextcloud-upload".into(), "{file}".into()],
+            }]
+        );
+    }
+
+    #[test]
+    fn file_replaces_the_placeholder_or_is_appended() {
+        assert_eq!(
+            args(&["up", "--to={file}", "-v"], "/p/IMG 1.jpg"),
+            ["up", "--to=/p/IMG 1.jpg", "-v"]
+        );
+        assert_eq!(
+            args(&["send", "-q"], "/p/a.mp4"),
+            ["send", "-q", "/p/a.mp4"]
+        );
+    }
+}
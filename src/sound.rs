@@ -0,0 +1,168 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Feedback sounds
+//!
+//! Short tones played through GStreamer's default audio sink, so they go
+//! wherever the desktop routes sound (PipeWire or PulseAudio) without the
+//! app shipping sound files. The capture sounds (shutter, countdown and
+//! recording chimes) are built from the same tones, with a burst of noise
+//! for the shutter.
+
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use tracing::{debug, warn};
+
+/// Pitch of the scan beep, in Hz: high and short like a till scanner
+const BEEP_FREQUENCY: u32 = 2400;
+
+/// Length of the scan beep, in milliseconds
+const BEEP_MILLIS: u32 = 90;
+
+/// Pitch of the A/V sync test beep, in Hz: well inside what laptop
+/// speakers and microphones both reproduce
+const SYNC_BEEP_FREQUENCY: u32 = 1000;
+
+/// Length of the A/V sync test beep, in milliseconds
+const SYNC_BEEP_MILLIS: u32 = 150;
+
+/// Sample rate the tones are generated at
+const SAMPLE_RATE: u32 = 48_000;
+
+/// Waveform of a tone, as `audiotestsrc` names it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Wave {
+    Sine,
+    WhiteNoise,
+}
+
+impl Wave {
+    fn name(self) -> &'static str {
+        match self {
+            Wave::Sine => "sine",
+            Wave::WhiteNoise => "white-noise",
+        }
+    }
+}
+
+/// One tone of a capture sound
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Tone {
+    wave: Wave,
+    /// Pitch in Hz (ignored for noise)
+    frequency: u32,
+    millis: u32,
+}
+
+const fn sine(frequency: u32, millis: u32) -> Tone {
+    Tone {
+        wave: Wave::Sine,
+        frequency,
+        millis,
+    }
+}
+
+/// Sounds played around captures when `Config::capture_sounds` is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureSound {
+    /// A photo is taken
+    Shutter,
+    /// A second of the self timer passes
+    CountdownTick,
+    /// The last second of the self timer starts
+    CountdownFinal,
+    /// A recording starts: rising notes
+    RecordStart,
+    /// A recording stops: the same notes falling
+    RecordStop,
+}
+
+impl CaptureSound {
+    /// Tones of the sound, played one after another
+    fn tones(self) -> &'static [Tone] {
+        match self {
+            CaptureSound::Shutter => &[Tone {
+                wave: Wave::WhiteNoise,
+                frequency: 0,
+                millis: 40,
+            }],
+            CaptureSound::CountdownTick => &[sine(1000, 60)],
+            CaptureSound::CountdownFinal => &[sine(1500, 150)],
+            CaptureSound::RecordStart => &[sine(880, 90), sine(1320, 140)],
+            CaptureSound::RecordStop => &[sine(1320, 90), sine(880, 140)],
+        }
+    }
+}
+
+/// Play a capture sound on a background thread. `volume` is 0.0-1.0.
+pub fn play(sound: CaptureSound, volume: f64) {
+    if volume <= 0.0 {
+        return;
+    }
+    std::thread::spawn(move || {
+        for tone in sound.tones() {
+            if let Err(e) = play_tone(tone.wave, tone.frequency, tone.millis, volume) {
+                warn!(error = %e, ?sound, "Failed to play capture sound");
+                return;
+            }
+        }
+    });
+}
+
+/// Play the scan beep on a background thread
+pub fn beep() {
+    std::thread::spawn(|| {
+        if let Err(e) = play_tone(Wave::Sine, BEEP_FREQUENCY, BEEP_MILLIS, 0.4) {
+            warn!(error = %e, "Failed to play beep");
+        }
+    });
+}
+
+/// Play the A/V sync test beep on a background thread, loud enough for the
+/// microphone to pick it up over the room
+pub fn sync_beep() {
+    std::thread::spawn(|| {
+        if let Err(e) = play_tone(Wave::Sine, SYNC_BEEP_FREQUENCY, SYNC_BEEP_MILLIS, 0.8) {
+            warn!(error = %e, "Failed to play sync test beep");
+        }
+    });
+}
+
+/// Play a tone and wait for it to finish. Blocking.
+fn play_tone(wave: Wave, frequency: u32, millis: u32, volume: f64) -> Result<(), String> {
+    gst::init().map_err(|e| format!("GStreamer init: {e}"))?;
+
+    // 10 ms buffers, as many as make up the tone
+    let samples_per_buffer = SAMPLE_RATE / 100;
+    let buffers = millis.div_ceil(10);
+    let pipeline = gst::parse::launch(&format!(
+        "audiotestsrc wave={} freq={frequency} volume={volume} \
+         samplesperbuffer={samples_per_buffer} num-buffers={buffers} \
+         ! audio/x-raw,rate={SAMPLE_RATE},channels=1 \
+         ! audioconvert ! audioresample ! autoaudiosink",
+        wave.name()
+    ))
+    .map_err(|e| format!("Failed to parse tone pipeline: {e}"))?
+    .downcast::<gst::Pipeline>()
+    .map_err(|_| "Tone pipeline is not a pipeline".to_string())?;
+
+    pipeline
+        .set_state(gst::State::Playing)
+        .map_err(|e| format!("Failed to start tone pipeline: {e}"))?;
+    let result = match pipeline.bus().and_then(|bus| {
+        bus.timed_pop_filtered(
+            gst::ClockTime::from_seconds(2),
+            &[gst::MessageType::Eos, gst::MessageType::Error],
+        )
+    }) {
+        Some(msg) => match msg.view() {
+            gst::MessageView::Error(err) => Err(err.error().to_string()),
+            _ => {
+                debug!(frequency, millis, "Played tone");
+                Ok(())
+            }
+        },
+        None => Err("Timed out playing tone".to_string()),
+    };
+    pipeline.set_state(gst::State::Null).ok();
+    result
+}
@@ -0,0 +1,231 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Storage utilities for managing photo and video files
+
+mod space;
+pub mod thumbnails;
+
+pub use space::{LOW_SPACE_WARNING_MB, SpaceLevel, available_space, space_level};
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Gallery thumbnail data: image handle, RGBA bytes, width, height, and file path
+pub type GalleryThumbnailData = (
+    cosmic::widget::image::Handle,
+    Arc<Vec<u8>>,
+    u32,
+    u32,
+    PathBuf,
+);
+
+/// Load latest thumbnail for gallery button
+///
+/// Finds the most recent photo or video in either directory and returns its
+/// thumbnail from the [`thumbnails`] cache, generating it on a miss, as both
+/// an image handle and RGBA data for custom rendering.
+pub async fn load_latest_thumbnail(
+    photos_dir: PathBuf,
+    videos_dir: PathBuf,
+) -> Option<GalleryThumbnailData> {
+    tokio::task::spawn_blocking(move || {
+        let (latest_path, _) = thumbnails::list_captures(&[photos_dir, videos_dir])
+            .into_iter()
+            .next()?;
+        debug!(path = ?latest_path, "Loading latest thumbnail");
+
+        let thumbnail = thumbnails::load(&latest_path)?;
+        let handle = cosmic::widget::image::Handle::from_bytes(thumbnail.png);
+        Some((
+            handle,
+            Arc::new(thumbnail.rgba),
+            thumbnail.width,
+            thumbnail.height,
+            latest_path,
+        ))
+    })
+    .await
+    .ok()?
+}
+
+/// Path for a new capture: `<dir>/<prefix>_<timestamp>.<extension>`
+///
+/// The extension has to match what will actually be written (e.g. the video
+/// container) so the gallery and file managers pick the right handler. A
+/// counter is appended if the name is already taken.
+pub fn capture_path(dir: &Path, prefix: &str, extension: &str) -> PathBuf {
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S_%3f");
+    unique_path(dir, &format!("{}_{}", prefix, timestamp), extension)
+}
+
+fn unique_path(dir: &Path, stem: &str, extension: &str) -> PathBuf {
+    let mut path = dir.join(format!("{}.{}", stem, extension));
+    let mut counter = 1;
+    while path.exists() {
+        path = dir.join(format!("{}_{}.{}", stem, counter, extension));
+        counter += 1;
+    }
+    path
+}
+
+/// Extension of the quick previews standing in for photos being processed
+const PENDING_EXTENSION: &str = "pending.jpg";
+
+/// Quick JPEG standing in for a photo that is still being processed
+///
+/// It is written next to the photos, so the gallery button, and the gallery
+/// opened from it, show the shot right after the shutter. Once processing
+/// ends it is removed with [`discard`](Self::discard); a finished photo is
+/// newer on disk and takes its place as the latest capture. Previews a crash
+/// left behind are removed by [`remove_pending_photos`] at the next start.
+#[derive(Debug, Clone)]
+pub struct PendingPhoto {
+    path: PathBuf,
+}
+
+impl PendingPhoto {
+    /// Save `jpeg` as the preview of a photo being processed for `dir`
+    pub async fn write(dir: &Path, jpeg: &[u8]) -> Result<Self, String> {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(|e| format!("Failed to create output directory: {}", e))?;
+        let path = capture_path(dir, "IMG", PENDING_EXTENSION);
+        tokio::fs::write(&path, jpeg)
+            .await
+            .map_err(|e| format!("Failed to save preview: {}", e))?;
+        Ok(Self { path })
+    }
+
+    /// Where the preview is
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Remove the preview, processing having finished or given up
+    pub async fn discard(self) {
+        match tokio::fs::remove_file(&self.path).await {
+            Ok(()) => debug!(path = %self.path.display(), "Removed pending preview"),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                warn!(path = %self.path.display(), error = %e, "Failed to remove pending preview")
+            }
+        }
+    }
+}
+
+/// Whether `path` is a [`PendingPhoto`]
+pub fn is_pending_photo(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(&format!(".{}", PENDING_EXTENSION)))
+}
+
+/// Remove previews of photos whose processing never finished (the app quit
+/// or crashed part way)
+pub async fn remove_pending_photos(dir: PathBuf) {
+    let _ = tokio::task::spawn_blocking(move || {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            return;
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if is_pending_photo(&path) {
+                match std::fs::remove_file(&path) {
+                    Ok(()) => debug!(path = %path.display(), "Removed stale pending preview"),
+                    Err(e) => warn!(path = %path.display(), error = %e, "Failed to remove stale pending preview"),
+                }
+            }
+        }
+    })
+    .await;
+}
+
+/// Format a byte count for display (e.g. "1.5 GB")
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Decode the photo at `path` and turn it by its EXIF orientation, the way
+/// viewers show it
+pub fn open_photo(path: &Path) -> Result<image::DynamicImage, String> {
+    use image::ImageDecoder;
+
+    let mut decoder = image::ImageReader::open(path)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(|e| format!("Failed to open '{}': {}", path.display(), e))?
+        .into_decoder()
+        .map_err(|e| format!("Failed to decode '{}': {}", path.display(), e))?;
+    let orientation = decoder
+        .orientation()
+        .unwrap_or(image::metadata::Orientation::NoTransforms);
+    let mut image = image::DynamicImage::from_decoder(decoder)
+        .map_err(|e| format!("Failed to decode '{}': {}", path.display(), e))?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+/// Encode RGBA data to PNG bytes
+fn encode_rgba_to_png(rgba_data: &[u8], width: u32, height: u32) -> Option<Vec<u8>> {
+    use image::{ImageBuffer, Rgba};
+
+    let img: ImageBuffer<Rgba<u8>, _> = ImageBuffer::from_raw(width, height, rgba_data.to_vec())?;
+
+    let mut png_bytes = Vec::new();
+    let mut cursor = std::io::Cursor::new(&mut png_bytes);
+
+    img.write_to(&mut cursor, image::ImageFormat::Png).ok()?;
+
+    Some(png_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_size_picks_unit() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1_500), "1.5 KB");
+        assert_eq!(format_size(61_200_000), "61.2 MB");
+        assert_eq!(format_size(2_000_000_000), "2.0 GB");
+    }
+
+    #[test]
+    fn unique_path_skips_existing_files() {
+        let dir = std::env::temp_dir().join(format!("camera-unique-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let first = unique_path(&dir, "VID_test", "mkv");
+        assert_eq!(first, dir.join("VID_test.mkv"));
+        std::fs::write(&first, b"").unwrap();
+        assert_eq!(
+            unique_path(&dir, "VID_test", "mkv"),
+            dir.join("VID_test_1.mkv")
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn pending_photos_are_found_and_removed() {
+        let dir = std::env::temp_dir().join(format!("camera-pending-{}", std::process::id()));
+        let pending = PendingPhoto::write(&dir, b"jpeg").await.unwrap();
+        assert!(is_pending_photo(pending.path()));
+        assert!(!is_pending_photo(&dir.join("IMG_1.jpg")));
+
+        let stale = PendingPhoto::write(&dir, b"jpeg").await.unwrap();
+        pending.discard().await;
+        remove_pending_photos(dir.clone()).await;
+        assert!(!stale.path().exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
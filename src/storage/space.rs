@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Free space on the output filesystem
+//!
+//! Photos and recordings check the filesystem they are saved to before they
+//! start, and recordings keep checking while they run. Below the user's
+//! warning threshold the app shows how much space is left; below
+//! [`STOP_RESERVE_BYTES`] new captures are refused and a running recording
+//! is stopped, which still leaves the muxer room to finalize the file.
+
+use std::path::Path;
+use tracing::warn;
+
+/// Warning thresholds offered in the settings, in megabytes
+pub const LOW_SPACE_WARNING_MB: [u32; 4] = [500, 1000, 2000, 5000];
+
+/// Space kept free for finishing what is already being written: a
+/// recording's index and trailer, or a burst being merged
+pub const STOP_RESERVE_BYTES: u64 = 200 * 1000 * 1000;
+
+/// How full the output filesystem is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpaceLevel {
+    Plenty,
+    /// Below the user's warning threshold
+    Low,
+    /// Below [`STOP_RESERVE_BYTES`]: nothing new is captured
+    Full,
+}
+
+/// Level for `free` bytes with the warning threshold at `warn_at` bytes
+pub fn space_level(free: u64, warn_at: u64) -> SpaceLevel {
+    if free < STOP_RESERVE_BYTES {
+        SpaceLevel::Full
+    } else if free < warn_at {
+        SpaceLevel::Low
+    } else {
+        SpaceLevel::Plenty
+    }
+}
+
+/// Free space available to the current user on the filesystem holding `path`
+///
+/// Walks up to the nearest existing ancestor so it also works for a save
+/// folder that has not been created yet.
+pub fn available_space(path: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let existing = path.ancestors().find(|p| p.exists())?;
+    let c_path = std::ffi::CString::new(existing.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        warn!(path = %existing.display(), "statvfs failed");
+        return None;
+    }
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn available_space_handles_missing_dirs() {
+        let missing = std::env::temp_dir().join("camera-nonexistent/nested/dir");
+        assert!(available_space(&missing).is_some());
+    }
+
+    #[test]
+    fn levels_follow_the_threshold_and_reserve() {
+        let warn_at = 1_000_000_000;
+        assert_eq!(space_level(5_000_000_000, warn_at), SpaceLevel::Plenty);
+        assert_eq!(space_level(warn_at, warn_at), SpaceLevel::Plenty);
+        assert_eq!(space_level(warn_at - 1, warn_at), SpaceLevel::Low);
+        assert_eq!(
+            space_level(STOP_RESERVE_BYTES - 1, warn_at),
+            SpaceLevel::Full
+        );
+        // The reserve applies even with a threshold set below it
+        assert_eq!(space_level(100_000_000, 0), SpaceLevel::Full);
+    }
+}
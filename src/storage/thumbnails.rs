@@ -0,0 +1,261 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Thumbnail cache
+//!
+//! Decoding a full-size photo, or starting GStreamer to pull the first frame
+//! out of a video, takes far longer than the gallery can wait for. Every
+//! capture gets a small PNG under `$XDG_CACHE_HOME/<app id>/thumbnails`
+//! instead, named after a hash of the capture's path and modification time:
+//! a file that is edited or replaced simply hashes to a new entry, and the
+//! old one is pruned by the next [`fill_cache`] run.
+//!
+//! [`load`] is the only way in: it returns the cached thumbnail, generating
+//! and storing it first on a miss. [`fill_cache`] runs in the background at
+//! startup so captures made elsewhere (or before the cache existed) are
+//! ready by the time anything asks for them.
+
+use super::{encode_rgba_to_png, is_pending_photo, open_photo};
+use crate::constants::file_formats;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tracing::{debug, info, warn};
+
+const APP_ID: &str = <crate::app::AppModel as cosmic::Application>::APP_ID;
+
+/// Longest side of a thumbnail, in pixels: sharp on the burst preview
+/// (160 logical pixels tall) at 2x scaling
+pub const THUMBNAIL_SIZE: u32 = 320;
+
+/// A decoded thumbnail and the PNG it was stored as
+#[derive(Debug, Clone)]
+pub struct Thumbnail {
+    pub png: Vec<u8>,
+    pub rgba: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Where thumbnails are kept
+pub fn cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join(APP_ID).join("thumbnails"))
+}
+
+/// Cache file name for `path` as last modified at `modified`
+///
+/// FNV-1a rather than `DefaultHasher`, whose output may change between
+/// Rust releases and would throw the whole cache away.
+fn cache_name(path: &Path, modified: SystemTime) -> String {
+    use std::os::unix::ffi::OsStrExt;
+
+    let nanos = modified
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for &byte in path
+        .as_os_str()
+        .as_bytes()
+        .iter()
+        .chain(&[0])
+        .chain(&nanos.to_le_bytes())
+    {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}.png", hash)
+}
+
+/// Thumbnail for the photo or video at `path`, from the cache or generated
+/// and cached now. Blocking; call from `spawn_blocking`.
+///
+/// Previews of photos still being processed are short-lived and never
+/// cached.
+pub fn load(path: &Path) -> Option<Thumbnail> {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    let cached = (!is_pending_photo(path))
+        .then(cache_dir)
+        .flatten()
+        .map(|dir| dir.join(cache_name(path, modified)));
+
+    if let Some(cached) = &cached
+        && let Ok(png) = std::fs::read(cached)
+    {
+        match image::load_from_memory_with_format(&png, image::ImageFormat::Png) {
+            Ok(img) => {
+                let rgba = img.to_rgba8();
+                return Some(Thumbnail {
+                    width: rgba.width(),
+                    height: rgba.height(),
+                    rgba: rgba.into_raw(),
+                    png,
+                });
+            }
+            Err(e) => warn!(path = %cached.display(), error = %e, "Discarding broken thumbnail"),
+        }
+    }
+
+    let thumbnail = generate(path)?;
+    if let Some(cached) = &cached {
+        store(cached, &thumbnail.png);
+    }
+    Some(thumbnail)
+}
+
+/// Decode `path` and scale it down to [`THUMBNAIL_SIZE`]
+fn generate(path: &Path) -> Option<Thumbnail> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+
+    let image = if file_formats::is_video_extension(&extension) {
+        let frame = match crate::backends::virtual_camera::load_preview_frame(path) {
+            Ok(frame) => frame,
+            Err(e) => {
+                warn!(path = %path.display(), error = ?e, "Failed to extract video thumbnail");
+                return None;
+            }
+        };
+        let rgba = image::RgbaImage::from_raw(frame.width, frame.height, frame.data.to_vec())?;
+        image::DynamicImage::ImageRgba8(rgba)
+    } else {
+        match open_photo(path) {
+            Ok(image) => image,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Failed to decode photo for thumbnail");
+                return None;
+            }
+        }
+    };
+
+    let rgba = image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgba8();
+    let (width, height) = rgba.dimensions();
+    let rgba = rgba.into_raw();
+    let png = encode_rgba_to_png(&rgba, width, height)?;
+    debug!(path = %path.display(), width, height, "Generated thumbnail");
+    Some(Thumbnail {
+        png,
+        rgba,
+        width,
+        height,
+    })
+}
+
+fn store(cached: &Path, png: &[u8]) {
+    let result = (|| {
+        if let Some(dir) = cached.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        // Written aside and renamed, so a crash never leaves half a PNG
+        let temp = cached.with_extension("tmp");
+        std::fs::write(&temp, png)?;
+        std::fs::rename(&temp, cached)
+    })();
+    if let Err(e) = result {
+        warn!(path = %cached.display(), error = %e, "Failed to store thumbnail");
+    }
+}
+
+/// Photos and videos in `dirs`, newest first
+pub fn list_captures(dirs: &[PathBuf]) -> Vec<(PathBuf, SystemTime)> {
+    let mut files: Vec<(PathBuf, SystemTime)> = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(ext) = path.extension() else {
+                continue;
+            };
+            let ext = ext.to_string_lossy().to_lowercase();
+            if (file_formats::is_image_extension(&ext) || file_formats::is_video_extension(&ext))
+                && let Ok(metadata) = entry.metadata()
+                && metadata.is_file()
+                && let Ok(modified) = metadata.modified()
+            {
+                files.push((path, modified));
+            }
+        }
+    }
+    files.sort_by_key(|file| std::cmp::Reverse(file.1));
+    files
+}
+
+/// Generate the thumbnails missing for the captures in `dirs`, newest
+/// first, and remove those of files that are gone or have changed
+pub async fn fill_cache(dirs: Vec<PathBuf>) {
+    let _ = tokio::task::spawn_blocking(move || {
+        let Some(cache) = cache_dir() else {
+            return;
+        };
+        let start = std::time::Instant::now();
+        let captures = list_captures(&dirs);
+
+        let mut wanted = HashSet::with_capacity(captures.len());
+        let mut generated = 0;
+        for (path, modified) in &captures {
+            if is_pending_photo(path) {
+                continue;
+            }
+            let name = cache_name(path, *modified);
+            if !cache.join(&name).exists() && load(path).is_some() {
+                generated += 1;
+            }
+            wanted.insert(name);
+        }
+
+        let mut pruned = 0;
+        if let Ok(entries) = std::fs::read_dir(&cache) {
+            for entry in entries.flatten() {
+                let name = entry.file_name();
+                if !wanted.contains(name.to_string_lossy().as_ref())
+                    && std::fs::remove_file(entry.path()).is_ok()
+                {
+                    pruned += 1;
+                }
+            }
+        }
+        info!(
+            captures = captures.len(),
+            generated,
+            pruned,
+            elapsed_ms = start.elapsed().as_millis(),
+            "Thumbnail cache up to date"
+        );
+    })
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn cache_names_follow_path_and_mtime() {
+        let t = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let a = cache_name(Path::new("/p/IMG_1.jpg"), t);
+        assert_eq!(a, cache_name(Path::new("/p/IMG_1.jpg"), t));
+        assert_ne!(a, cache_name(Path::new("/p/IMG_2.jpg"), t));
+        assert_ne!(
+            a,
+            cache_name(Path::new("/p/IMG_1.jpg"), t + Duration::from_millis(1))
+        );
+        assert!(a.ends_with(".png") && a.len() == 20);
+    }
+
+    #[test]
+    fn generated_thumbnails_fit_the_size() {
+        let dir = std::env::temp_dir().join(format!("camera-thumb-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("IMG_test.png");
+        image::RgbaImage::new(1280, 720).save(&path).unwrap();
+
+        let thumbnail = generate(&path).unwrap();
+        assert_eq!((thumbnail.width, thumbnail.height), (320, 180));
+        assert_eq!(thumbnail.rgba.len(), 320 * 180 * 4);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -403,7 +403,8 @@ fn sample_pixel(frame: &CameraFrame, x: u32, y: u32) -> Color {
     Color::Rgb(r, g, b)
 }
 
-fn sample_pixel_rgb(frame: &CameraFrame, x: u32, y: u32) -> (u8, u8, u8) {
+/// Sample one pixel as RGB, whatever the frame's pixel format
+pub(crate) fn sample_pixel_rgb(frame: &CameraFrame, x: u32, y: u32) -> (u8, u8, u8) {
     if frame.width == 0 || frame.height == 0 {
         return (0, 0, 0);
     }
@@ -412,7 +413,7 @@ fn sample_pixel_rgb(frame: &CameraFrame, x: u32, y: u32) -> (u8, u8, u8) {
     let data: &[u8] = &frame.data;
 
     match frame.format {
-        PixelFormat::RGBA => {
+        PixelFormat::RGBA | PixelFormat::RGBx => {
             let idx = (y * frame.stride + x * 4) as usize;
             if idx + 2 < data.len() {
                 (data[idx], data[idx + 1], data[idx + 2])
@@ -477,6 +478,41 @@ fn sample_pixel_rgb(frame: &CameraFrame, x: u32, y: u32) -> (u8, u8, u8) {
 
             yuv_to_rgb(luma, u, v)
         }
+        PixelFormat::P010 => {
+            // NV12 layout with little-endian 16-bit samples; keep the high bytes
+            let y_idx = (y * frame.stride + x * 2) as usize + 1;
+            if y_idx >= data.len() {
+                return (0, 0, 0);
+            }
+            let luma = data[y_idx];
+
+            let (uv_offset, uv_stride) = match &frame.yuv_planes {
+                Some(planes) => (planes.uv_offset, planes.uv_stride),
+                None => ((frame.stride * frame.height) as usize, frame.stride),
+            };
+            // Interleaved UV: each chroma sample is 4 bytes (U16, V16)
+            let uv_idx = uv_offset + (y as usize / 2) * uv_stride as usize + (x as usize / 2) * 4;
+            if uv_idx + 3 >= data.len() {
+                return (luma, luma, luma);
+            }
+
+            yuv_to_rgb(luma, data[uv_idx + 1], data[uv_idx + 3])
+        }
+        PixelFormat::Y210 => {
+            // Packed 4:2:2 with little-endian 16-bit samples: Y0 U Y1 V
+            // (8 bytes per 2 pixels); keep the high bytes
+            let pair_x = (x & !1) as usize;
+            let base = (y as usize) * (frame.stride as usize) + pair_x * 4;
+            if base + 7 >= data.len() {
+                return (0, 0, 0);
+            }
+            let luma = if x & 1 == 0 {
+                data[base + 1]
+            } else {
+                data[base + 5]
+            };
+            yuv_to_rgb(luma, data[base + 3], data[base + 7])
+        }
         PixelFormat::I420 => {
             let y_idx = (y * frame.stride + x) as usize;
             if y_idx >= data.len() {
@@ -574,7 +610,7 @@ fn sample_pixel_rgb(frame: &CameraFrame, x: u32, y: u32) -> (u8, u8, u8) {
                 (0, 0, 0)
             }
         }
-        PixelFormat::BGRA => {
+        PixelFormat::BGRA | PixelFormat::BGRx => {
             // B G R A byte order — 4 bytes per pixel
             let idx = (y * frame.stride + x * 4) as usize;
             if idx + 2 < data.len() {
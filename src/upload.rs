@@ -0,0 +1,296 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Automatic upload of captures
+//!
+//! With uploads on, every photo and video saved after the app started is
+//! sent to a remote once it is finished: a WebDAV folder (Nextcloud,
+//! ownCloud, a plain Apache/nginx share) with an HTTP PUT, or an
+//! S3-compatible bucket with a presigned PUT. Connection details live in
+//! [`UploadSettings`](crate::config::UploadSettings); the password or secret
+//! key is kept in the desktop's secret service.
+//!
+//! Network errors and server-side failures are retried a few times with
+//! growing delays ([`RETRY_DELAYS_SECS`]); a rejected login or missing
+//! folder is not, as retrying can't fix it.
+
+use crate::config::{UploadProvider, UploadSettings};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+const APP_ID: &str = <crate::app::AppModel as cosmic::Application>::APP_ID;
+
+/// Delay before each retry of a failed upload
+pub const RETRY_DELAYS_SECS: [u64; 3] = [5, 30, 120];
+
+/// Uploads sent at the same time; the rest of a burst waits its turn
+static UPLOAD_SLOTS: tokio::sync::Semaphore = tokio::sync::Semaphore::const_new(2);
+
+/// How long a presigned S3 request stays valid; long enough for a large
+/// video on a slow uplink
+const PRESIGN_VALIDITY: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Where a capture's upload stands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UploadStatus {
+    /// Being sent, or waiting for a retry
+    Uploading,
+    Uploaded,
+    /// Given up; the error of the last attempt
+    Failed(String),
+}
+
+/// A failed attempt, and whether trying again might help
+struct AttemptError {
+    message: String,
+    retry: bool,
+}
+
+impl AttemptError {
+    fn retry(message: String) -> Self {
+        Self {
+            message,
+            retry: true,
+        }
+    }
+
+    fn fatal(message: String) -> Self {
+        Self {
+            message,
+            retry: false,
+        }
+    }
+}
+
+fn client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .user_agent(concat!("cosmic-camera/", env!("CARGO_PKG_VERSION")))
+            .connect_timeout(Duration::from_secs(15))
+            .build()
+            .unwrap_or_default()
+    })
+}
+
+/// Secret service attributes of the secret for `settings`' account
+fn secret_attributes(settings: &UploadSettings) -> HashMap<&str, &str> {
+    HashMap::from([
+        ("application", APP_ID),
+        ("upload-url", settings.url.as_str()),
+        ("username", settings.username.as_str()),
+    ])
+}
+
+/// Password or secret key stored for `settings`' account, if any
+pub async fn load_secret(settings: &UploadSettings) -> Result<Option<String>, String> {
+    let keyring = oo7::Keyring::new()
+        .await
+        .map_err(|e| format!("Secret service unavailable: {e}"))?;
+    let items = keyring
+        .search_items(&secret_attributes(settings))
+        .await
+        .map_err(|e| format!("Failed to search the keyring: {e}"))?;
+    let Some(item) = items.first() else {
+        return Ok(None);
+    };
+    let secret = item
+        .secret()
+        .await
+        .map_err(|e| format!("Failed to read the upload secret: {e}"))?;
+    std::str::from_utf8(&secret)
+        .map(|secret| Some(secret.to_string()))
+        .map_err(|_| "The stored upload secret is not text".to_string())
+}
+
+/// Store the password or secret key for `settings`' account, replacing the
+/// one stored before
+pub async fn store_secret(settings: UploadSettings, secret: String) -> Result<(), String> {
+    let keyring = oo7::Keyring::new()
+        .await
+        .map_err(|e| format!("Secret service unavailable: {e}"))?;
+    keyring
+        .create_item(
+            &format!("Camera uploads ({})", settings.url),
+            &secret_attributes(&settings),
+            secret.as_str(),
+            true,
+        )
+        .await
+        .map_err(|e| format!("Failed to store the upload secret: {e}"))?;
+    info!(url = %settings.url, "Stored upload secret");
+    Ok(())
+}
+
+/// URL of `name` inside the WebDAV folder at `folder`
+fn webdav_file_url(folder: &str, name: &str) -> Result<reqwest::Url, String> {
+    let mut url =
+        reqwest::Url::parse(folder).map_err(|e| format!("Invalid WebDAV URL '{folder}': {e}"))?;
+    url.path_segments_mut()
+        .map_err(|_| format!("Invalid WebDAV URL '{folder}'"))?
+        .pop_if_empty()
+        .push(name);
+    Ok(url)
+}
+
+/// Whether a failed request might succeed later
+fn is_transient(status: reqwest::StatusCode) -> bool {
+    status.is_server_error()
+        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+        || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+fn check_response(response: Result<reqwest::Response, reqwest::Error>) -> Result<(), AttemptError> {
+    let response = response.map_err(|e| AttemptError::retry(format!("Upload failed: {e}")))?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else if is_transient(status) {
+        Err(AttemptError::retry(format!("Server answered {status}")))
+    } else {
+        Err(AttemptError::fatal(format!("Server answered {status}")))
+    }
+}
+
+async fn file_body(path: &Path) -> Result<(reqwest::Body, u64), AttemptError> {
+    let file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| AttemptError::fatal(format!("Failed to open {}: {e}", path.display())))?;
+    let len = file
+        .metadata()
+        .await
+        .map_err(|e| AttemptError::fatal(format!("Failed to read {}: {e}", path.display())))?
+        .len();
+    Ok((reqwest::Body::from(file), len))
+}
+
+async fn upload_webdav(
+    settings: &UploadSettings,
+    secret: &str,
+    path: &Path,
+    name: &str,
+) -> Result<(), AttemptError> {
+    let url = webdav_file_url(&settings.url, name).map_err(AttemptError::fatal)?;
+
+    // Create the folder; "405 Method Not Allowed" means it is already there
+    let mkcol = reqwest::Method::from_bytes(b"MKCOL").expect("valid method");
+    let folder = client()
+        .request(mkcol, &settings.url)
+        .basic_auth(&settings.username, Some(secret))
+        .send()
+        .await
+        .map_err(|e| AttemptError::retry(format!("Upload failed: {e}")))?;
+    debug!(status = %folder.status(), "WebDAV MKCOL");
+
+    let (body, len) = file_body(path).await?;
+    check_response(
+        client()
+            .put(url)
+            .basic_auth(&settings.username, Some(secret))
+            .header(reqwest::header::CONTENT_LENGTH, len)
+            .body(body)
+            .send()
+            .await,
+    )
+}
+
+async fn upload_s3(
+    settings: &UploadSettings,
+    secret: &str,
+    path: &Path,
+    name: &str,
+) -> Result<(), AttemptError> {
+    use rusty_s3::{Bucket, Credentials, S3Action, UrlStyle};
+
+    let endpoint = reqwest::Url::parse(&settings.url)
+        .map_err(|e| AttemptError::fatal(format!("Invalid S3 endpoint: {e}")))?;
+    let bucket = Bucket::new(
+        endpoint,
+        UrlStyle::Path,
+        settings.bucket.clone(),
+        settings.region.clone(),
+    )
+    .map_err(|e| AttemptError::fatal(format!("Invalid S3 bucket: {e}")))?;
+    let credentials = Credentials::new(settings.username.clone(), secret.to_string());
+    let url = bucket
+        .put_object(Some(&credentials), name)
+        .sign(PRESIGN_VALIDITY);
+
+    let (body, len) = file_body(path).await?;
+    check_response(
+        client()
+            .put(url)
+            .header(reqwest::header::CONTENT_LENGTH, len)
+            .body(body)
+            .send()
+            .await,
+    )
+}
+
+/// Upload the capture at `path`, retrying transient failures
+pub async fn upload(settings: UploadSettings, path: PathBuf) -> Result<(), String> {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| format!("No file name in {}", path.display()))?
+        .to_string();
+    let secret = load_secret(&settings)
+        .await?
+        .ok_or_else(|| "No upload password stored".to_string())?;
+    let _slot = UPLOAD_SLOTS
+        .acquire()
+        .await
+        .map_err(|e| format!("Upload queue closed: {e}"))?;
+
+    let mut delays = RETRY_DELAYS_SECS.iter();
+    loop {
+        let attempt = match settings.provider {
+            UploadProvider::WebDav => upload_webdav(&settings, &secret, &path, &name).await,
+            UploadProvider::S3 => upload_s3(&settings, &secret, &path, &name).await,
+        };
+        match attempt {
+            Ok(()) => {
+                info!(path = %path.display(), provider = ?settings.provider, "Uploaded capture");
+                return Ok(());
+            }
+            Err(e) if e.retry => {
+                let Some(&delay) = delays.next() else {
+                    return Err(e.message);
+                };
+                warn!(path = %path.display(), error = %e.message, delay, "Upload failed, retrying");
+                tokio::time::sleep(Duration::from_secs(delay)).await;
+            }
+            Err(e) => return Err(e.message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn webdav_urls_are_joined_and_encoded() {
+        let url = |folder: &str, name: &str| webdav_file_url(folder, name).unwrap().to_string();
+        assert_eq!(
+            url("https://cloud.example/dav/files/me/Camera/", "IMG_1.jpg"),
+            "https://cloud.example/dav/files/me/Camera/IMG_1.jpg"
+        );
+        assert_eq!(
+            url("https://cloud.example/dav/Camera", "VID #2.mp4"),
+            "https://cloud.example/dav/Camera/VID%20%232.mp4"
+        );
+        assert!(webdav_file_url("not a url", "IMG_1.jpg").is_err());
+    }
+
+    #[test]
+    fn only_server_side_failures_are_retried() {
+        use reqwest::StatusCode;
+        assert!(is_transient(StatusCode::BAD_GATEWAY));
+        assert!(is_transient(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_transient(StatusCode::UNAUTHORIZED));
+        assert!(!is_transient(StatusCode::NOT_FOUND));
+    }
+}
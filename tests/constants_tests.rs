@@ -6,8 +6,18 @@ use camera::constants::BitratePreset;
 
 #[test]
 fn test_bitrate_preset_values() {
-    // Test that all presets exist (Low, Medium, High)
-    assert_eq!(BitratePreset::ALL.len(), 3);
+    // Test that all presets exist (Low, Medium, High, EditFriendly)
+    assert_eq!(BitratePreset::ALL.len(), 4);
+}
+
+#[test]
+fn test_only_edit_friendly_is_intra_only() {
+    for preset in BitratePreset::ALL {
+        assert_eq!(
+            preset.is_intra_only(),
+            preset == BitratePreset::EditFriendly
+        );
+    }
 }
 
 #[test]